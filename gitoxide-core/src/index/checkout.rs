@@ -85,6 +85,7 @@ pub fn checkout_exclusive(
         bytes_written,
         delayed_paths_unknown,
         delayed_paths_unprocessed,
+        conflicts: _,
     } = match repo {
         Some(repo) => gix::worktree::state::checkout(
             &mut index,
@@ -62,6 +62,9 @@ pub struct Context<W> {
     /// If true, delta objects may refer to their base as reference, allowing it not to be included in the created back.
     /// Otherwise these have to be recompressed in order to make the pack self-contained.
     pub thin: bool,
+    /// If true, produce byte-identical packs for identical input by ordering objects independently of the order they were
+    /// counted in, and by using a fixed compression level instead of the one configured in the repository.
+    pub deterministic: bool,
     /// If set, don't use more than this amount of threads.
     /// Otherwise, usually use as many threads as there are logical cores.
     /// A value of 0 is interpreted as no-limit
@@ -94,6 +97,7 @@ pub fn create<W, P>(
         expansion,
         nondeterministic_thread_count,
         thin,
+        deterministic,
         thread_limit,
         statistics,
         pack_cache_size_in_bytes,
@@ -109,6 +113,11 @@ where
     type ObjectIdIter = dyn Iterator<Item = Result<ObjectId, Box<dyn std::error::Error + Send + Sync>>> + Send;
 
     let repo = gix::discover(repository_path)?.into_sync();
+    let compression_level = if deterministic {
+        pack::data::output::entry::iter_from_counts::Options::default().compression_level
+    } else {
+        repo.to_thread_local().pack_compression_level()?
+    };
     progress.init(Some(2), progress::steps());
     let tips = tips.into_iter();
     let make_cancellation_err = || anyhow!("Cancelled by user");
@@ -233,6 +242,8 @@ where
                 allow_thin_pack: thin,
                 chunk_size,
                 version: Default::default(),
+                compression_level,
+                deterministic,
             },
         ))
     };
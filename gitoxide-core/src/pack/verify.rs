@@ -50,6 +50,10 @@ impl From<Algorithm> for index::traverse::Algorithm {
 pub struct Context<'a, W1: io::Write, W2: io::Write> {
     /// If set, provide statistics to `out` in the given format
     pub output_statistics: Option<OutputFormat>,
+    /// If set, provide each object with its delta chain, along with delta chain histograms, to `out` in the given format.
+    ///
+    /// This only works when verifying a pack index whose pack is present.
+    pub output_delta_chains: Option<OutputFormat>,
     /// A stream to which to output operation results
     pub out: W1,
     /// A stream to which to errors
@@ -93,6 +97,7 @@ pub fn pack_or_pack_index<W1, W2>(
         mut err,
         mode,
         output_statistics,
+        output_delta_chains,
         thread_limit,
         algorithm,
         should_interrupt,
@@ -120,6 +125,9 @@ where
     };
     let res = match ext {
         "pack" => {
+            if output_delta_chains.is_some() {
+                return Err(anyhow!("Delta chains can only be listed for pack index files"));
+            }
             let pack = odb::pack::data::File::at(path, object_hash).with_context(|| "Could not open pack file")?;
             pack.verify_checksum(&mut progress.add_child("Sha1 of pack"), should_interrupt)
                 .map(|id| (id, None))?
@@ -141,23 +149,42 @@ where
                 })
                 .ok();
 
-            idx.verify_integrity(
-                pack.as_ref().map(|p| gix::odb::pack::index::verify::PackContext {
-                    data: p,
-                    options: gix::odb::pack::index::verify::integrity::Options {
-                        verify_mode: mode,
-                        traversal: algorithm.into(),
-                        make_pack_lookup_cache: cache,
-                        thread_limit
+            let res = idx
+                .verify_integrity(
+                    pack.as_ref().map(|p| gix::odb::pack::index::verify::PackContext {
+                        data: p,
+                        options: gix::odb::pack::index::verify::integrity::Options {
+                            verify_mode: mode,
+                            traversal: algorithm.into(),
+                            make_pack_lookup_cache: cache,
+                            thread_limit,
+                        },
+                    }),
+                    &mut progress,
+                    should_interrupt,
+                )
+                .map(|o| (o.actual_index_checksum, o.pack_traverse_statistics))
+                .with_context(|| "Verification failure")?;
+            if let Some(format) = output_delta_chains {
+                let pack =
+                    pack.ok_or_else(|| anyhow!("Delta chains can only be listed if the pack file is present"))?;
+                let chains = idx.delta_chains(&pack)?;
+                #[cfg_attr(not(feature = "serde"), allow(clippy::single_match))]
+                match format {
+                    OutputFormat::Human => print_delta_chains(&mut out, &chains)?,
+                    #[cfg(feature = "serde")]
+                    OutputFormat::Json => {
+                        serde_json::to_writer_pretty(&mut out, &chains)?;
+                        writeln!(out)?;
                     }
-                }),
-                &mut progress,
-                should_interrupt,
-            )
-            .map(|o| (o.actual_index_checksum, o.pack_traverse_statistics))
-            .with_context(|| "Verification failure")?
+                }
+            }
+            res
         }
         "" => {
+            if output_delta_chains.is_some() {
+                return Err(anyhow!("Delta chains can only be listed for pack index files"));
+            }
             match path.file_name() {
                 Some(file_name) if file_name == "multi-pack-index" => {
                     let multi_index = gix::odb::pack::multi_index::File::at(path)?;
@@ -252,8 +279,8 @@ fn print_statistics(out: &mut impl io::Write, stats: &index::traverse::Statistic
         "num tags", stats.num_tags,
         width = width
     )?;
-    let compression_ratio = stats.total_decompressed_entries_size as f64 / stats.total_compressed_entries_size as f64;
-    let delta_compression_ratio = stats.total_object_size as f64 / stats.total_compressed_entries_size as f64;
+    let compression_ratio = stats.compression_ratio();
+    let delta_compression_ratio = stats.delta_compression_ratio();
     #[rustfmt::skip]
     writeln!(
         out,
@@ -261,8 +288,53 @@ fn print_statistics(out: &mut impl io::Write, stats: &index::traverse::Statistic
         "compression ratio", compression_ratio,
         "delta compression ratio", delta_compression_ratio,
         "delta gain", delta_compression_ratio / compression_ratio,
-        "pack overhead", (1.0 - (stats.total_compressed_entries_size as f64 / stats.pack_size as f64)) * 100.0,
+        "pack overhead", stats.pack_overhead_percentage(),
         width = width
     )?;
     Ok(())
 }
+
+fn print_delta_chains(out: &mut impl io::Write, chains: &index::delta_chains::Outcome) -> io::Result<()> {
+    for entry in &chains.entries {
+        write!(
+            out,
+            "{} {:<6} {} {} {}",
+            entry.id,
+            entry.kind.to_string(),
+            entry.decompressed_size,
+            entry.size_in_pack,
+            entry.pack_offset
+        )?;
+        if let Some(base) = entry.base {
+            write!(out, " {} {}", entry.chain_length, base)?;
+        }
+        writeln!(out)?;
+    }
+
+    let num_deltas: u32 = chains
+        .objects_per_chain_length
+        .iter()
+        .filter_map(|(length, count)| (*length != 0).then_some(*count))
+        .sum();
+    writeln!(out, "\nnon delta: {} objects", chains.entries.len() as u32 - num_deltas)?;
+    for (chain_length, object_count) in chains
+        .objects_per_chain_length
+        .iter()
+        .filter(|(length, _)| **length != 0)
+    {
+        writeln!(out, "chain length = {chain_length}: {object_count} objects")?;
+    }
+    if let Some(average) = chains.average_chain_length() {
+        writeln!(
+            out,
+            "\naverage chain length: {average:.2}\nmax chain length: {}",
+            chains.max_chain_length()
+        )?;
+    }
+
+    writeln!(out, "\nbase objects per amount of deltas based on them")?;
+    for (num_deltas, base_count) in &chains.deltas_per_base {
+        writeln!(out, "\t{num_deltas:>4}: {base_count}")?;
+    }
+    Ok(())
+}
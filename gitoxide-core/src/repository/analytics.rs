@@ -0,0 +1,234 @@
+use gix::bstr::BString;
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// The amount of entries to show in each section of the report.
+    pub limit: usize,
+    /// The revisions whose history to attribute object sizes to paths with, or all references if empty.
+    pub revisions: Vec<BString>,
+}
+
+pub(crate) mod function {
+    use std::collections::HashMap;
+
+    use anyhow::{bail, Context};
+    use bytesize::ByteSize;
+    use gix::{
+        bstr::{BStr, BString, ByteSlice},
+        hashtable::HashSet,
+        objs::tree::EntryKind,
+        odb::HeaderExt,
+        prelude::ObjectIdExt,
+        Count, NestedProgress, ObjectId, Progress,
+    };
+
+    use super::Options;
+    use crate::OutputFormat;
+
+    /// The size of an object and the first path it was seen at, if it is reachable from the analysed history.
+    struct Object {
+        id: ObjectId,
+        size: u64,
+        path: Option<usize>,
+    }
+
+    /// The sum of the sizes of all distinct blobs that were ever stored at a path.
+    struct Path {
+        path: BString,
+        size: u64,
+        versions: usize,
+    }
+
+    pub fn analytics(
+        repo: gix::Repository,
+        mut progress: impl NestedProgress,
+        mut out: impl std::io::Write,
+        Options {
+            format,
+            limit,
+            revisions,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+
+        let mut blobs = Vec::new();
+        let mut trees = Vec::new();
+        let mut copies = HashMap::<ObjectId, usize>::new();
+        {
+            let mut progress = progress.add_child("scan objects");
+            progress.init(None, gix::progress::count("objects"));
+            for id in repo.objects.iter()? {
+                let id = id?;
+                progress.inc();
+                if gix::interrupt::is_triggered() {
+                    bail!("Cancelled by user");
+                }
+                let count = copies.entry(id).or_default();
+                *count += 1;
+                if *count > 1 {
+                    continue;
+                }
+                let header = repo.objects.header(id)?;
+                let object = Object {
+                    id,
+                    size: header.size(),
+                    path: None,
+                };
+                match header.kind() {
+                    gix::object::Kind::Blob => blobs.push(object),
+                    gix::object::Kind::Tree => trees.push(object),
+                    gix::object::Kind::Commit | gix::object::Kind::Tag => {}
+                }
+            }
+        }
+
+        let tips = if revisions.is_empty() {
+            repo.references()?
+                .all()?
+                .filter_map(Result::ok)
+                .filter_map(|mut r| r.peel_to_id_in_place().ok().map(gix::Id::detach))
+                .filter(|id| {
+                    repo.find_header(*id)
+                        .map_or(false, |header| header.kind() == gix::object::Kind::Commit)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            revisions
+                .iter()
+                .map(|rev| {
+                    Ok(repo
+                        .rev_parse_single(rev.as_bstr())?
+                        .object()?
+                        .peel_to_kind(gix::object::Kind::Commit)
+                        .with_context(|| format!("Revision '{rev}' doesn't point to a commit"))?
+                        .id)
+                })
+                .collect::<anyhow::Result<_>>()?
+        };
+
+        let mut paths = Vec::<Path>::new();
+        let mut blob_paths = HashMap::<ObjectId, usize>::new();
+        let mut tree_paths = HashMap::<ObjectId, usize>::new();
+        {
+            let mut progress = progress.add_child("traverse history");
+            progress.init(None, gix::progress::count("commits"));
+            let mut path_index = HashMap::<BString, usize>::new();
+            let mut seen_trees = HashSet::default();
+            let mut stack = Vec::<(ObjectId, BString)>::new();
+            for info in repo.rev_walk(tips).all()? {
+                let info = info?;
+                progress.inc();
+                if gix::interrupt::is_triggered() {
+                    bail!("Cancelled by user");
+                }
+                stack.push((info.object()?.tree_id()?.detach(), BString::default()));
+                while let Some((tree_id, tree_path)) = stack.pop() {
+                    if !seen_trees.insert(tree_id) {
+                        continue;
+                    }
+                    let idx = index_of(&mut paths, &mut path_index, tree_path.as_ref());
+                    tree_paths.entry(tree_id).or_insert(idx);
+                    let tree = tree_id.attach(&repo).object()?.into_tree();
+                    for entry in tree.decode()?.entries.iter() {
+                        let mut path = tree_path.clone();
+                        if !path.is_empty() {
+                            path.push(b'/');
+                        }
+                        path.extend_from_slice(entry.filename);
+                        match entry.mode.kind() {
+                            EntryKind::Tree => stack.push((entry.oid.to_owned(), path)),
+                            EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link => {
+                                let idx = index_of(&mut paths, &mut path_index, path.as_ref());
+                                if let std::collections::hash_map::Entry::Vacant(e) =
+                                    blob_paths.entry(entry.oid.to_owned())
+                                {
+                                    e.insert(idx);
+                                    let path = &mut paths[idx];
+                                    path.size += repo.find_header(entry.oid)?.size();
+                                    path.versions += 1;
+                                }
+                            }
+                            EntryKind::Commit => {}
+                        }
+                    }
+                }
+            }
+        }
+        for blob in &mut blobs {
+            blob.path = blob_paths.get(&blob.id).copied();
+        }
+        for tree in &mut trees {
+            tree.path = tree_paths.get(&tree.id).copied();
+        }
+
+        let mut write_objects = |title: &str, mut objects: Vec<Object>| -> std::io::Result<()> {
+            objects.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.id.cmp(&b.id)));
+            writeln!(out, "{title}:")?;
+            for object in objects.iter().take(limit) {
+                write!(out, "{:>10} {}", ByteSize(object.size).to_string(), object.id)?;
+                match object.path {
+                    Some(idx) if paths[idx].path.is_empty() => writeln!(out, " (root)")?,
+                    Some(idx) => writeln!(out, " {}", paths[idx].path)?,
+                    None => writeln!(out, " (unreachable)")?,
+                }
+            }
+            writeln!(out)
+        };
+        write_objects("largest blobs", blobs)?;
+        write_objects("largest trees", trees)?;
+
+        paths.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        writeln!(out, "paths by the size of all of their versions:")?;
+        for path in paths.iter().filter(|path| path.versions > 0).take(limit) {
+            writeln!(
+                out,
+                "{:>10} {:>5} {}",
+                ByteSize(path.size).to_string(),
+                format!("{}x", path.versions),
+                path.path
+            )?;
+        }
+        writeln!(out)?;
+
+        let mut duplicates = copies
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(id, count)| Ok((repo.find_header(id)?.size(), count, id)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        duplicates.sort_by(|a, b| (b.0 * b.1 as u64).cmp(&(a.0 * a.1 as u64)).then_with(|| a.2.cmp(&b.2)));
+        let wasted: u64 = duplicates
+            .iter()
+            .map(|(size, count, _)| size * (*count as u64 - 1))
+            .sum();
+        writeln!(
+            out,
+            "objects stored more than once: {} ({} uncompressed in redundant copies)",
+            duplicates.len(),
+            ByteSize(wasted)
+        )?;
+        for (size, count, id) in duplicates.iter().take(limit) {
+            writeln!(
+                out,
+                "{:>10} {:>5} {id}",
+                ByteSize(*size).to_string(),
+                format!("{count}x")
+            )?;
+        }
+        Ok(())
+    }
+
+    fn index_of(paths: &mut Vec<Path>, path_index: &mut HashMap<BString, usize>, path: &BStr) -> usize {
+        *path_index.entry(path.to_owned()).or_insert_with(|| {
+            paths.push(Path {
+                path: path.to_owned(),
+                size: 0,
+                versions: 0,
+            });
+            paths.len() - 1
+        })
+    }
+}
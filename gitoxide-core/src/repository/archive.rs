@@ -8,6 +8,9 @@ pub struct Options {
     pub files: Vec<(String, String)>,
     pub prefix: Option<String>,
     pub add_paths: Vec<PathBuf>,
+    /// If true, produce byte-identical archives for identical input by using a fixed modification time
+    /// if it can't be taken from a commit, instead of the current time.
+    pub deterministic: bool,
 }
 
 pub fn stream(
@@ -20,6 +23,7 @@ pub fn stream(
         prefix,
         add_paths,
         files,
+        deterministic,
     }: Options,
 ) -> anyhow::Result<()> {
     let format = format.map_or_else(|| format_from_ext(destination_path), Ok)?;
@@ -64,6 +68,10 @@ pub fn stream(
             format,
             tree_prefix: prefix.map(gix::bstr::BString::from),
             modification_time: modification_date.unwrap_or_else(|| {
+                if deterministic {
+                    // 1980-01-01, the earliest time that can be represented in `zip` archives.
+                    return 315_532_800;
+                }
                 std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .map(|d| d.as_secs())
@@ -0,0 +1,270 @@
+use std::ffi::OsStr;
+
+use anyhow::{bail, Context};
+use gix::{
+    bstr::{BStr, BString, ByteSlice},
+    refs::{transaction::PreviousValue, Category, FullName},
+    ObjectId,
+};
+
+use crate::OutputFormat;
+
+pub mod list {
+    use std::ffi::OsString;
+
+    pub struct Options {
+        /// List remote tracking branches instead of local ones.
+        pub remotes: bool,
+        /// List local and remote tracking branches.
+        pub all: bool,
+        /// Show the commit and subject of each branch along with how it relates to its upstream.
+        pub verbose: bool,
+        /// Only list branches whose history contains this commit.
+        pub contains: Option<OsString>,
+        /// Only list branches whose tips are reachable from this commit.
+        pub merged: Option<OsString>,
+        /// Only list branches whose tips are not reachable from this commit.
+        pub no_merged: Option<OsString>,
+    }
+}
+
+/// List branches like `git branch --list`, marking the one checked out in this worktree with `*`.
+pub fn list(
+    repo: gix::Repository,
+    format: OutputFormat,
+    mut out: impl std::io::Write,
+    list::Options {
+        remotes,
+        all,
+        verbose,
+        contains,
+        merged,
+        no_merged,
+    }: list::Options,
+) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+    let contains = contains
+        .as_deref()
+        .map(|spec| peel_to_commit(&repo, spec))
+        .transpose()?;
+    let merged = merged.as_deref().map(|spec| peel_to_commit(&repo, spec)).transpose()?;
+    let no_merged = no_merged
+        .as_deref()
+        .map(|spec| peel_to_commit(&repo, spec))
+        .transpose()?;
+
+    let head_name = repo.head_name()?;
+    let mut branches = Vec::new();
+    for reference in repo.references()?.all()? {
+        let mut reference = reference.map_err(|err| anyhow::anyhow!(err))?;
+        let category = match reference.name().category() {
+            Some(category @ (Category::LocalBranch | Category::RemoteBranch)) => category,
+            _ => continue,
+        };
+        let show = match category {
+            Category::LocalBranch => !remotes || all,
+            _ => remotes || all,
+        };
+        if !show {
+            continue;
+        }
+        let name = reference.name().to_owned();
+        let Ok(id) = reference.peel_to_id_in_place() else {
+            continue;
+        };
+        branches.push((name, id.detach()));
+    }
+    let keep = filter_reachable(&repo, branches.iter().map(|(_, id)| *id), contains, merged, no_merged)?;
+    let branches: Vec<_> = branches
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(b, keep)| keep.then_some(b))
+        .collect();
+
+    let width = branches
+        .iter()
+        .map(|(name, _)| display_name(name.as_ref(), all).len())
+        .max()
+        .unwrap_or_default();
+    for (name, id) in &branches {
+        let marker = if head_name.as_ref() == Some(name) { '*' } else { ' ' };
+        let short_name = display_name(name.as_ref(), all);
+        if !verbose {
+            writeln!(out, "{marker} {short_name}")?;
+            continue;
+        }
+        let commit = repo.find_object(*id)?.try_into_commit()?;
+        let mut tracking = String::new();
+        if let Some((Category::LocalBranch, short)) = name.category_and_short_name() {
+            if let Some(upstream) = repo.branch_remote_tracking_ref_name(short).transpose()? {
+                let upstream_id = repo
+                    .try_find_reference(upstream.as_ref())?
+                    .and_then(|r| r.into_fully_peeled_id().ok());
+                let upstream_name = upstream.shorten();
+                tracking = match upstream_id {
+                    None => format!("[{upstream_name}: gone] "),
+                    Some(upstream_id) => match repo.ahead_behind(*id, upstream_id)? {
+                        (0, 0) => format!("[{upstream_name}] "),
+                        (ahead, 0) => format!("[{upstream_name}: ahead {ahead}] "),
+                        (0, behind) => format!("[{upstream_name}: behind {behind}] "),
+                        (ahead, behind) => format!("[{upstream_name}: ahead {ahead}, behind {behind}] "),
+                    },
+                };
+            }
+        }
+        writeln!(
+            out,
+            "{marker} {short_name:width$} {} {tracking}{}",
+            id.to_hex_with_len(7),
+            commit.message()?.summary()
+        )?;
+    }
+    Ok(())
+}
+
+/// Create the branch `name` at `start_point`, or at `HEAD` if unset, overwriting an existing branch if `force` is set
+/// unless it is checked out.
+pub fn create(repo: gix::Repository, name: &str, start_point: Option<&OsStr>, force: bool) -> anyhow::Result<()> {
+    let full_name = branch_name(name)?;
+    let commit = match start_point {
+        Some(spec) => peel_to_commit(&repo, spec)?,
+        None => repo.head_commit()?.id,
+    };
+    if force {
+        if let Some(worktree) = checked_out_at(&repo, full_name.as_ref())? {
+            bail!(
+                "Cannot force update the branch '{name}' checked out at '{}'",
+                worktree.display()
+            );
+        }
+    }
+    repo.reference(
+        full_name,
+        commit,
+        if force {
+            PreviousValue::Any
+        } else {
+            PreviousValue::MustNotExist
+        },
+        format!(
+            "branch: Created from {}",
+            start_point.map_or("HEAD".into(), |spec| spec.to_string_lossy())
+        ),
+    )?;
+    Ok(())
+}
+
+/// Rename the branch `old`, or the currently checked out branch if unset, to `new` along with its reflog and configuration,
+/// and update all worktrees that have it checked out. If `force` is set, an existing branch named `new` is overwritten.
+pub fn rename(repo: gix::Repository, old: Option<&str>, new: &str, force: bool) -> anyhow::Result<()> {
+    let old: BString = match old {
+        Some(old) => old.into(),
+        None => repo
+            .head_name()?
+            .context("Cannot rename the current branch while HEAD is detached")?
+            .as_bstr()
+            .to_owned(),
+    };
+    repo.rename_branch(old.as_ref(), new.into(), force)?;
+    Ok(())
+}
+
+/// Delete the branches with the given `names` along with their configuration, and print each deleted branch to `out`.
+///
+/// Unless `force` is set, only branches that are merged into their upstream, or into `HEAD` if they don't have one,
+/// are deleted. Branches checked out, rebased or bisected in any worktree are never deleted.
+pub fn delete(
+    repo: gix::Repository,
+    names: &[String],
+    force: bool,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()> {
+    for name in names {
+        let id = match repo.delete_branch(name.as_str().into(), force) {
+            Ok(id) => id,
+            Err(gix::reference::delete_branch::Error::NotMerged { target, .. }) => bail!(
+                "The branch '{name}' is not fully merged into {}, use --force to delete it anyway",
+                target.shorten()
+            ),
+            Err(err) => return Err(err.into()),
+        };
+        writeln!(out, "Deleted branch {name} (was {}).", id.to_hex_with_len(7))?;
+    }
+    Ok(())
+}
+
+/// Return which of the commits in `tips` contain `contains` in their history, are reachable from `merged`
+/// and aren't reachable from `no_merged`, treating unset filters as matching everything.
+pub(super) fn filter_reachable(
+    repo: &gix::Repository,
+    tips: impl IntoIterator<Item = ObjectId>,
+    contains: Option<ObjectId>,
+    merged: Option<ObjectId>,
+    no_merged: Option<ObjectId>,
+) -> anyhow::Result<Vec<bool>> {
+    let tips: Vec<_> = tips.into_iter().collect();
+    let mut keep = vec![true; tips.len()];
+    if let Some(contains) = contains {
+        let matches = gix::revision::plumbing::contains(&tips, &contains, &mut repo.revision_graph())?;
+        for (keep, is_match) in keep.iter_mut().zip(matches) {
+            *keep &= is_match;
+        }
+    }
+    for (commit, expected) in [(merged, true), (no_merged, false)] {
+        if let Some(commit) = commit {
+            let reachable = repo.reachable_from(Some(commit), tips.iter().copied())?;
+            for (keep, reachable) in keep.iter_mut().zip(reachable) {
+                *keep &= reachable == expected;
+            }
+        }
+    }
+    Ok(keep)
+}
+
+pub(super) fn peel_to_commit(repo: &gix::Repository, spec: &OsStr) -> anyhow::Result<ObjectId> {
+    let spec = gix::path::os_str_into_bstr(spec)?;
+    Ok(repo
+        .rev_parse_single(spec)?
+        .object()?
+        .peel_to_kind(gix::object::Kind::Commit)
+        .with_context(|| format!("Need commitish, got '{spec}'"))?
+        .id)
+}
+
+fn branch_name(name: &str) -> anyhow::Result<FullName> {
+    Ok(format!("refs/heads/{name}").try_into()?)
+}
+
+fn display_name(name: &gix::refs::FullNameRef, all: bool) -> &BStr {
+    match name.category_and_short_name() {
+        Some((Category::RemoteBranch, _)) if all => name.as_bstr()["refs/".len()..].as_bstr(),
+        _ => name.shorten(),
+    }
+}
+
+/// Return the main worktree, if there is one, followed by all linked worktrees.
+fn all_worktrees(repo: &gix::Repository) -> anyhow::Result<Vec<gix::Repository>> {
+    let main = repo.main_repo()?;
+    let mut out = Vec::new();
+    for proxy in repo.worktrees()? {
+        out.push(proxy.into_repo_with_possibly_inaccessible_worktree()?);
+    }
+    if !main.is_bare() {
+        out.insert(0, main);
+    }
+    Ok(out)
+}
+
+/// Return the working tree of the worktree that has the branch `name` checked out, if there is one.
+fn checked_out_at(repo: &gix::Repository, name: &gix::refs::FullNameRef) -> anyhow::Result<Option<std::path::PathBuf>> {
+    for worktree in all_worktrees(repo)? {
+        if worktree.head_name()?.as_ref().map(AsRef::as_ref) == Some(name) {
+            return Ok(Some(
+                worktree.work_dir().unwrap_or_else(|| worktree.git_dir()).to_owned(),
+            ));
+        }
+    }
+    Ok(None)
+}
@@ -1,11 +1,23 @@
 use crate::OutputFormat;
 
+use gix::bstr::BString;
+
 pub struct Options {
     pub format: OutputFormat,
     pub bare: bool,
+    pub mirror: bool,
+    pub no_local: bool,
+    pub no_hardlinks: bool,
+    pub reference: Vec<std::path::PathBuf>,
+    pub dissociate: bool,
+    pub single_branch: bool,
+    pub branch: Option<BString>,
     pub handshake_info: bool,
     pub no_tags: bool,
     pub shallow: gix::remote::fetch::Shallow,
+    pub filter: Option<String>,
+    pub sparse: bool,
+    pub recurse_submodules: bool,
 }
 
 pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
@@ -30,8 +42,18 @@ pub(crate) mod function {
             format,
             handshake_info,
             bare,
+            mirror,
+            no_local,
+            no_hardlinks,
+            reference,
+            dissociate,
+            single_branch,
+            branch,
             no_tags,
             shallow,
+            filter,
+            sparse,
+            recurse_submodules,
         }: Options,
     ) -> anyhow::Result<()>
     where
@@ -42,7 +64,15 @@ pub(crate) mod function {
             bail!("JSON output isn't yet supported for fetching.");
         }
 
+        let bare = bare || mirror;
         let url: gix::Url = url.as_ref().try_into()?;
+        let local_objects = if no_local || url.scheme != gix::url::Scheme::File {
+            gix::clone::LocalObjects::Fetch
+        } else if no_hardlinks {
+            gix::clone::LocalObjects::Copy
+        } else {
+            gix::clone::LocalObjects::HardLink
+        };
         let directory = directory.map_or_else(
             || {
                 gix::path::from_bstr(Cow::Borrowed(url.path.as_ref()))
@@ -68,16 +98,45 @@ pub(crate) mod function {
                 opts
             },
         )?;
+        prepare = prepare.with_mode(if mirror {
+            gix::clone::Mode::Mirror
+        } else if bare {
+            gix::clone::Mode::Bare
+        } else {
+            gix::clone::Mode::Default
+        });
+        prepare = prepare
+            .with_local_objects(local_objects)
+            .with_dissociate(dissociate)
+            .with_single_branch(single_branch.then(|| {
+                branch
+                    .clone()
+                    .map_or(gix::clone::SingleBranch::RemoteHead, gix::clone::SingleBranch::Named)
+            }))
+            .with_ref_name(branch);
+        let filter = match filter {
+            Some(_) if local_objects != gix::clone::LocalObjects::Fetch => {
+                writeln!(err, "--filter is ignored in local clones; use --no-local instead.")?;
+                None
+            }
+            filter => filter,
+        };
+        for path in reference {
+            prepare = prepare.with_reference_repository(path);
+        }
         if no_tags {
             prepare = prepare.configure_remote(|r| Ok(r.with_fetch_tags(gix::remote::fetch::Tags::None)));
         }
         let (mut checkout, fetch_outcome) = prepare
             .with_shallow(shallow)
+            .with_filter(filter)
+            .with_sparse_checkout(sparse)
             .fetch_then_checkout(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
 
         let (repo, outcome) = if bare {
             (checkout.persist(), None)
         } else {
+            checkout = checkout.with_submodules(recurse_submodules);
             let (repo, outcome) = checkout.main_worktree(progress, &gix::interrupt::IS_INTERRUPTED)?;
             (repo, Some(outcome))
         };
@@ -13,9 +13,20 @@ pub(crate) mod function {
     use std::io;
 
     use anyhow::{Context as AnyhowContext, Result};
+    use gix::objs::FindExt;
 
     use crate::{repository::commitgraph::verify::Context, OutputFormat};
 
+    #[derive(Debug, thiserror::Error)]
+    enum Error {
+        #[error(transparent)]
+        FindCommit(#[from] gix::objs::find::existing_object::Error),
+        #[error(transparent)]
+        Parent(#[from] gix::commitgraph::file::commit::Error),
+        #[error("The {field} of commit {id} in the commit-graph doesn't match the object database")]
+        Mismatch { id: gix::ObjectId, field: &'static str },
+    }
+
     pub fn verify<W1, W2>(
         repo: gix::Repository,
         Context {
@@ -29,13 +40,32 @@ pub(crate) mod function {
         W2: io::Write,
     {
         let g = repo.commit_graph()?;
-
-        #[allow(clippy::unnecessary_wraps, unknown_lints)]
-        fn noop_processor(_commit: &gix::commitgraph::file::Commit<'_>) -> std::result::Result<(), std::fmt::Error> {
-            Ok(())
-        }
+        let mut buf = Vec::new();
         let stats = g
-            .verify_integrity(noop_processor)
+            .verify_integrity(|commit| {
+                // Like git, assure the commit-graph matches the commits in the object database.
+                let actual = repo.objects.find_commit(commit.id(), &mut buf)?;
+                let mismatch = |field: &'static str| Error::Mismatch {
+                    id: commit.id().to_owned(),
+                    field,
+                };
+                if actual.tree() != commit.root_tree_id() {
+                    return Err(mismatch("root tree"));
+                }
+                let parents = commit
+                    .iter_parents()
+                    .map(|pos| pos.map(|pos| g.id_at(pos).to_owned()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if !actual.parents().eq(parents) {
+                    return Err(mismatch("parents"));
+                }
+                if u64::try_from(actual.committer.time.seconds)
+                    .map_or(true, |time| time & 0x0003_ffff_ffff != commit.committer_timestamp())
+                {
+                    return Err(mismatch("committer timestamp"));
+                }
+                Ok(())
+            })
             .with_context(|| "Verification failure")?;
 
         #[cfg_attr(not(feature = "serde"), allow(clippy::single_match))]
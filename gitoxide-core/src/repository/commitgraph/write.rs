@@ -0,0 +1,82 @@
+pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=2;
+
+/// Options for [`write()`](function::write()).
+pub struct Options {
+    /// If `true`, start the traversal from all references instead of using all commits in the object database.
+    pub reachable: bool,
+    /// If `true`, write bloom filters for the paths changed by each commit.
+    pub changed_paths: bool,
+}
+
+pub(crate) mod function {
+    use std::{io::BufRead, sync::atomic::AtomicBool};
+
+    use anyhow::Context;
+    use gix::prelude::ObjectIdExt;
+
+    use super::Options;
+
+    /// Write a commit-graph for all commits reachable from the commits read from `stdin_commits`, from all references if
+    /// `reachable` is set, or for all commits in the object database otherwise.
+    pub fn write(
+        repo: gix::Repository,
+        stdin_commits: Option<impl BufRead>,
+        progress: impl gix::NestedProgress + 'static,
+        mut out: impl std::io::Write,
+        should_interrupt: &AtomicBool,
+        Options {
+            reachable,
+            changed_paths,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        let mut tips = Vec::new();
+        if let Some(stdin) = stdin_commits {
+            for line in stdin.lines() {
+                let line = line?;
+                let spec = line.trim();
+                if spec.is_empty() {
+                    continue;
+                }
+                let id = repo
+                    .rev_parse_single(spec)?
+                    .object()?
+                    .peel_to_kind(gix::object::Kind::Commit)
+                    .with_context(|| format!("'{spec}' does not point to a commit"))?
+                    .id;
+                tips.push(id);
+            }
+        } else if reachable {
+            for reference in repo.references()?.all()? {
+                let mut reference = reference.map_err(|err| anyhow::anyhow!(err))?;
+                let Ok(id) = reference.peel_to_id_in_place() else {
+                    continue;
+                };
+                if let Ok(commit) = id.object()?.peel_to_kind(gix::object::Kind::Commit) {
+                    tips.push(commit.id);
+                }
+            }
+        } else {
+            for id in repo.objects.iter()? {
+                let id = id?;
+                if repo.find_header(id)?.kind() == gix::object::Kind::Commit {
+                    tips.push(id);
+                }
+            }
+        }
+
+        let outcome = repo.write_commit_graph(
+            tips,
+            progress,
+            should_interrupt,
+            gix::maintenance::write_commit_graph::Options { changed_paths },
+        )?;
+        writeln!(
+            out,
+            "Wrote {} commits to '{}' with checksum {}",
+            outcome.num_commits,
+            outcome.path.display(),
+            outcome.checksum.attach(&repo).shorten_or_id()
+        )?;
+        Ok(())
+    }
+}
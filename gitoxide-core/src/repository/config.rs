@@ -1,8 +1,55 @@
-use anyhow::{bail, Result};
-use gix::bstr::{BStr, BString};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use gix::{
+    bstr::{BStr, BString, ByteSlice},
+    config::Source,
+};
 
 use crate::OutputFormat;
 
+/// The configuration file to read from or write to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Scope {
+    /// The configuration of the repository, `.git/config`.
+    Local,
+    /// The configuration of the user, `~/.gitconfig`, or `$XDG_CONFIG_HOME/git/config` if only that exists.
+    Global,
+    /// The configuration of the system, `$(prefix)/etc/gitconfig`.
+    System,
+    /// The configuration of the current worktree, `$GIT_DIR/config.worktree`, which needs `extensions.worktreeConfig`
+    /// unless there are no linked worktrees.
+    Worktree,
+}
+
+pub mod get {
+    /// The type to interpret values as before printing them.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum Type {
+        /// Print `true` or `false`.
+        Bool,
+        /// Print the value as decimal number, with unit suffixes like `k` applied.
+        Int,
+        /// Print the value as decimal number if it is one, or as boolean otherwise.
+        BoolOrInt,
+        /// Print the value as path, with `~/` expanded to the home directory.
+        Path,
+    }
+
+    pub struct Options {
+        /// Only read the configuration file of this scope instead of all configuration.
+        pub scope: Option<super::Scope>,
+        /// Interpret values as this type.
+        pub value_type: Option<Type>,
+        /// Print all values of multi-valued keys, instead of only the last one.
+        pub all: bool,
+        /// Print the file each value was read from.
+        pub show_origin: bool,
+        /// Print the scope of the file each value was read from.
+        pub show_scope: bool,
+    }
+}
+
 pub fn list(
     repo: gix::Repository,
     filters: Vec<BString>,
@@ -50,6 +97,272 @@ pub fn list(
     Ok(())
 }
 
+/// Print the value of `key`, like `core.bare` or `remote.origin.url`, similar to `git config --get`.
+pub fn get(
+    repo: gix::Repository,
+    key: &BStr,
+    get::Options {
+        scope,
+        value_type,
+        all,
+        show_origin,
+        show_scope,
+    }: get::Options,
+    mut out: impl std::io::Write,
+) -> Result<()> {
+    let parsed_key = parse_key(key)?;
+    let scoped;
+    let snapshot = repo.config_snapshot();
+    let config = match scope {
+        Some(scope) => {
+            scoped = open_scope(&repo, scope)?.0;
+            &scoped
+        }
+        None => snapshot.plumbing(),
+    };
+
+    let mut values = Vec::new();
+    for section in config.sections_by_name(parsed_key.section_name).into_iter().flatten() {
+        if section.header().subsection_name() != parsed_key.subsection_name {
+            continue;
+        }
+        for value in section.values(parsed_key.value_name) {
+            values.push((section.meta(), value));
+        }
+    }
+    if !all && values.len() > 1 {
+        values.drain(..values.len() - 1);
+    }
+    if values.is_empty() {
+        bail!("The key '{key}' isn't set");
+    }
+
+    for (meta, value) in values {
+        if show_scope {
+            write!(out, "{}\t", scope_name(meta.source))?;
+        }
+        if show_origin {
+            match &meta.path {
+                Some(path) => write!(out, "file:{}\t", path.display())?,
+                None => write!(out, "command line:\t")?,
+            }
+        }
+        let value = match value_type {
+            None => value.into_owned(),
+            Some(value_type) => coerce(value.as_ref(), value_type)
+                .with_context(|| format!("The value '{value}' of '{key}' can't be interpreted as {value_type:?}"))?,
+        };
+        out.write_all(&value)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Set `key` to `value` in the configuration file of `scope`, or the local one if unset, replacing the last existing value
+/// unless `add` is set, similar to `git config` and `git config --add`.
+pub fn set(repo: gix::Repository, key: &BStr, value: &BStr, scope: Option<Scope>, add: bool) -> Result<()> {
+    let key = parse_key(key)?;
+    let (mut config, lock) = open_scope_for_update(&repo, scope.unwrap_or(Scope::Local))?;
+    if add {
+        config
+            .section_mut_or_create_new(key.section_name, key.subsection_name)?
+            .push(key.value_name.to_owned().try_into()?, Some(value));
+    } else {
+        config.set_raw_value(key.section_name, key.subsection_name, key.value_name.to_owned(), value)?;
+    }
+    commit_scope(&config, lock)
+}
+
+/// Remove `key` from the configuration file of `scope`, or the local one if unset, similar to `git config --unset`.
+///
+/// Keys with multiple values are only removed if `all` is set, which removes all of their values.
+pub fn unset(repo: gix::Repository, key: &BStr, scope: Option<Scope>, all: bool) -> Result<()> {
+    let parsed_key = parse_key(key)?;
+    let (mut config, lock) = open_scope_for_update(&repo, scope.unwrap_or(Scope::Local))?;
+    let ids: Vec<_> = config
+        .sections_and_ids_by_name(parsed_key.section_name)
+        .into_iter()
+        .flatten()
+        .filter(|(section, _)| section.header().subsection_name() == parsed_key.subsection_name)
+        .map(|(section, id)| (id, section.values(parsed_key.value_name).len()))
+        .collect();
+    match ids.iter().map(|(_, count)| count).sum::<usize>() {
+        0 => bail!("The key '{key}' isn't set in '{}'", lock.resource_path().display()),
+        1 => {}
+        _ if all => {}
+        _ => bail!("The key '{key}' has multiple values, use --all to remove all of them"),
+    }
+    for (id, _) in ids {
+        let mut section = config.section_mut_by_id(id).expect("id was just obtained");
+        while section.remove(parsed_key.value_name).is_some() {}
+    }
+    commit_scope(&config, lock)
+}
+
+/// Rename all sections named `old` to `new` in the configuration file of `scope`, or the local one if unset,
+/// similar to `git config --rename-section`. Both names are `section` or `section.subsection`.
+pub fn rename_section(repo: gix::Repository, old: &BStr, new: &BStr, scope: Option<Scope>) -> Result<()> {
+    let (old_name, old_subsection) = split_section_name(old)?;
+    let (new_name, new_subsection) = split_section_name(new)?;
+    let (mut config, lock) = open_scope_for_update(&repo, scope.unwrap_or(Scope::Local))?;
+    if config.section(old_name, old_subsection).is_err() {
+        bail!(
+            "There is no section named '{old}' in '{}'",
+            lock.resource_path().display()
+        );
+    }
+    if (old_name, old_subsection) != (new_name, new_subsection) {
+        while config.section(old_name, old_subsection).is_ok() {
+            config.rename_section(
+                old_name,
+                old_subsection,
+                new_name.to_owned(),
+                new_subsection.map(|name| name.to_owned().into()),
+            )?;
+        }
+    }
+    commit_scope(&config, lock)
+}
+
+fn parse_key(key: &BStr) -> Result<gix::config::parse::Key<'_>> {
+    gix::config::parse::key(key)
+        .with_context(|| format!("The key '{key}' must look like 'section.name' or 'section.subsection.name'"))
+}
+
+fn split_section_name(name: &BStr) -> Result<(&str, Option<&BStr>)> {
+    let (section, subsection) = match name.find_byte(b'.') {
+        Some(pos) => (name[..pos].as_bytes(), Some(name[pos + 1..].as_bstr())),
+        None => (name.as_bytes(), None),
+    };
+    let section = section
+        .to_str()
+        .ok()
+        .filter(|section| !section.is_empty())
+        .with_context(|| format!("Invalid section name in '{name}'"))?;
+    Ok((section, subsection))
+}
+
+fn coerce(value: &BStr, value_type: get::Type) -> Result<BString> {
+    let boolean = |value: &BStr| -> Result<BString> {
+        Ok(if gix::config::Boolean::try_from(value)?.0 {
+            "true"
+        } else {
+            "false"
+        }
+        .into())
+    };
+    let integer = |value: &BStr| -> Result<BString> {
+        Ok(gix::config::Integer::try_from(value)?
+            .to_decimal()
+            .context("Integer overflow")?
+            .to_string()
+            .into())
+    };
+    Ok(match value_type {
+        get::Type::Bool => boolean(value)?,
+        get::Type::Int => integer(value)?,
+        get::Type::BoolOrInt => integer(value).or_else(|_| boolean(value))?,
+        get::Type::Path => {
+            let home = gix::path::env::home_dir();
+            let path = gix::config::Path::from(std::borrow::Cow::Borrowed(value)).interpolate(
+                gix::config::path::interpolate::Context {
+                    home_dir: home.as_deref(),
+                    ..Default::default()
+                },
+            )?;
+            gix::path::into_bstr(path).into_owned()
+        }
+    })
+}
+
+/// Open the configuration file of `scope` without resolving includes, or an empty one if it doesn't exist yet,
+/// and return it along with its path.
+pub(crate) fn open_scope(repo: &gix::Repository, scope: Scope) -> Result<(gix::config::File<'static>, PathBuf)> {
+    let (source, path) = scope_location(repo, scope)?;
+    Ok((read_scope(source, &path)?, path))
+}
+
+/// Like [`open_scope()`], but lock the configuration file before reading it so it can be changed without losing
+/// concurrent changes. The changes are written with [`commit_scope()`], or discarded if the lock is dropped.
+pub(crate) fn open_scope_for_update(
+    repo: &gix::Repository,
+    scope: Scope,
+) -> Result<(gix::config::File<'static>, gix::lock::File)> {
+    let (source, path) = scope_location(repo, scope)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let lock = gix::lock::File::acquire_to_update_resource(&path, repo.lock_timeout()?.0, None)
+        .with_context(|| format!("Could not lock configuration file at '{}'", path.display()))?;
+    Ok((read_scope(source, &path)?, lock))
+}
+
+/// Write `config` into the `lock` obtained by [`open_scope_for_update()`] and replace the configuration file with it.
+pub(crate) fn commit_scope(config: &gix::config::File<'static>, mut lock: gix::lock::File) -> Result<()> {
+    let path = lock.resource_path();
+    config
+        .write_to(&mut lock)
+        .and_then(|_| lock.commit().map_err(|err| err.error))
+        .with_context(|| format!("Could not write configuration file at '{}'", path.display()))?;
+    Ok(())
+}
+
+fn scope_location(repo: &gix::Repository, scope: Scope) -> Result<(Source, PathBuf)> {
+    let mut env = |name: &str| std::env::var_os(name);
+    Ok(match scope {
+        Scope::Local => (Source::Local, repo.common_dir().join("config")),
+        Scope::Worktree => {
+            if repo
+                .config_snapshot()
+                .boolean("extensions.worktreeConfig")
+                .unwrap_or(false)
+            {
+                (Source::Worktree, repo.git_dir().join("config.worktree"))
+            } else if repo.worktrees()?.is_empty() {
+                (Source::Local, repo.common_dir().join("config"))
+            } else {
+                bail!("--worktree needs extensions.worktreeConfig to be enabled if there are linked worktrees")
+            }
+        }
+        Scope::Global => {
+            let user = Source::User.storage_location(&mut env);
+            let xdg = Source::Git.storage_location(&mut env);
+            match (user, xdg) {
+                (Some(user), Some(xdg)) if !user.is_file() && xdg.is_file() => (Source::Git, xdg.into_owned()),
+                (Some(user), _) => (Source::User, user.into_owned()),
+                (None, Some(xdg)) => (Source::Git, xdg.into_owned()),
+                (None, None) => bail!("Could not determine the location of the global configuration file"),
+            }
+        }
+        Scope::System => (
+            Source::System,
+            Source::System
+                .storage_location(&mut env)
+                .context("The system configuration is disabled")?
+                .into_owned(),
+        ),
+    })
+}
+
+fn read_scope(source: Source, path: &std::path::Path) -> Result<gix::config::File<'static>> {
+    Ok(if path.is_file() {
+        gix::config::File::from_path_no_includes(path.to_owned(), source)?
+    } else {
+        gix::config::File::new(gix::config::file::Metadata::from(source).at(path))
+    })
+}
+
+fn scope_name(source: Source) -> &'static str {
+    match source {
+        Source::GitInstallation | Source::System => "system",
+        Source::Git | Source::User => "global",
+        Source::Local => "local",
+        Source::Worktree => "worktree",
+        Source::Env | Source::Cli | Source::EnvOverride => "command",
+        Source::Api => "unknown",
+    }
+}
+
 struct Filter {
     name: String,
     subsection: Option<BString>,
@@ -0,0 +1,31 @@
+use anyhow::bail;
+use gix::diff::stat::Format;
+
+use crate::OutputFormat;
+
+/// Write the statistics of the changes needed to turn the tree of `old_treeish` into the tree of `new_treeish` to `out`
+/// as configured by `stat_format`, with renames tracked as configured in the repository.
+pub fn tree(
+    repo: gix::Repository,
+    old_treeish: &str,
+    new_treeish: &str,
+    stat_format: Format,
+    format: OutputFormat,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+
+    let old_tree = repo
+        .rev_parse_single(format!("{old_treeish}^{{tree}}").as_str())?
+        .object()?
+        .into_tree();
+    let new_tree = repo
+        .rev_parse_single(format!("{new_treeish}^{{tree}}").as_str())?
+        .object()?
+        .into_tree();
+    let files = old_tree.changes()?.stats(&new_tree)?;
+    gix::diff::stat::write_to(&files, stat_format, &mut out)?;
+    Ok(())
+}
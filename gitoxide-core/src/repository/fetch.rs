@@ -2,16 +2,23 @@ use gix::bstr::BString;
 
 use crate::OutputFormat;
 
+#[derive(Clone)]
 pub struct Options {
     pub format: OutputFormat,
     pub dry_run: bool,
     pub remote: Option<String>,
+    /// If `true`, fetch from all configured remotes concurrently instead of only from `remote`.
+    pub all: bool,
+    /// The maximum amount of remotes to fetch from concurrently if `all` is set, or `None` to use all logical cores.
+    pub thread_limit: Option<usize>,
     /// If non-empty, override all ref-specs otherwise configured in the remote
     pub ref_specs: Vec<BString>,
     pub shallow: gix::remote::fetch::Shallow,
     pub handshake_info: bool,
     pub negotiation_info: bool,
     pub open_negotiation_graph: Option<std::path::PathBuf>,
+    /// If `true`, pack loose objects and index packs if there are too many of them after fetching, similar to `git gc --auto`.
+    pub auto_maintenance: bool,
 }
 
 pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
@@ -19,6 +26,7 @@ pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
 pub(crate) mod function {
     use anyhow::bail;
     use gix::{
+        maintenance::auto,
         prelude::ObjectIdExt,
         refspec::match_group::validate::Fix,
         remote::fetch::{refs::update::TypeChange, Status},
@@ -37,26 +45,191 @@ pub(crate) mod function {
         mut progress: P,
         mut out: impl std::io::Write,
         err: impl std::io::Write,
+        options: Options,
+    ) -> anyhow::Result<()>
+    where
+        P: gix::NestedProgress,
+        P::SubProgress: Send + 'static,
+        <P::SubProgress as gix::NestedProgress>::SubProgress: 'static,
+    {
+        if options.format != OutputFormat::Human {
+            bail!("JSON output isn't yet supported for fetching.");
+        }
+        let auto_maintenance = options.auto_maintenance && !options.dry_run;
+        if options.all {
+            fetch_all(&repo, &mut progress, &mut out, err, options)?;
+        } else {
+            let remote = crate::repository::remote::by_name_or_url(&repo, options.remote.as_deref())?;
+            fetch_remote(&repo, remote, &mut progress, &mut out, err, options)?;
+        }
+        if auto_maintenance {
+            run_auto_maintenance(&repo, progress, out)?;
+        }
+        Ok(())
+    }
+
+    /// Pack loose objects and index packs if there are too many of them, or let `git gc --auto` do it in the background
+    /// if `gc.autoDetach` is set to `true`.
+    fn run_auto_maintenance<P>(repo: &gix::Repository, progress: P, mut out: impl std::io::Write) -> anyhow::Result<()>
+    where
+        P: gix::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        // Unlike `git`, don't detach by default as it requires a `git` binary, which is otherwise never needed.
+        let detach = repo.config_snapshot().boolean("gc.autoDetach").unwrap_or(false);
+        match repo.auto_maintenance(
+            progress,
+            &gix::interrupt::IS_INTERRUPTED,
+            auto::Options {
+                thread_limit: None,
+                detach,
+            },
+        )? {
+            auto::Outcome::NotNeeded => {}
+            auto::Outcome::Performed { reasons, outcome } => {
+                writeln!(out, "auto-maintenance: {}", describe(reasons))?;
+                if let Some(pack) = outcome.pack {
+                    writeln!(out, "\tpacked {} loose objects", pack.index.num_objects)?;
+                }
+                if let Some(path) = outcome.multi_pack_index_path {
+                    writeln!(out, "\tmulti-pack index: \"{}\"", path.display())?;
+                }
+            }
+            auto::Outcome::AlreadyRunning { reasons, holder } => {
+                writeln!(
+                    out,
+                    "auto-maintenance: {}, but it is already running on machine '{}' with pid {}",
+                    describe(reasons),
+                    holder.hostname,
+                    holder.pid
+                )?;
+            }
+            auto::Outcome::Detached { reasons, pid } => {
+                writeln!(
+                    out,
+                    "auto-maintenance: {}, running in the background with pid {pid}",
+                    describe(reasons)
+                )?;
+            }
+        }
+        return Ok(());
+
+        fn describe(reasons: auto::Reasons) -> String {
+            let mut out = Vec::new();
+            if let Some(num) = reasons.loose_objects {
+                out.push(format!("about {num} loose objects"));
+            }
+            if let Some(num) = reasons.packs {
+                out.push(format!("{num} packs"));
+            }
+            out.join(" and ")
+        }
+    }
+
+    /// Fetch from all configured remotes with up to `thread_limit` remotes at a time, and print the outcome
+    /// of each remote in the order of their names once all of them are done.
+    fn fetch_all<P>(
+        repo: &gix::Repository,
+        mut progress: P,
+        mut out: impl std::io::Write,
+        mut err: impl std::io::Write,
+        options: Options,
+    ) -> anyhow::Result<()>
+    where
+        P: gix::NestedProgress,
+        P::SubProgress: Send + 'static,
+        <P::SubProgress as gix::NestedProgress>::SubProgress: 'static,
+    {
+        if options.remote.is_some() || !options.ref_specs.is_empty() {
+            bail!("Fetching from all remotes can't be combined with a remote or ref-specs");
+        }
+        if options.open_negotiation_graph.is_some() {
+            bail!("Fetching from all remotes can't be combined with opening the negotiation graph");
+        }
+        let names: Vec<String> = repo.remote_names().into_iter().map(ToOwned::to_owned).collect();
+        if names.is_empty() {
+            bail!("There is no remote to fetch from");
+        }
+
+        let threads = gix::parallel::num_threads(options.thread_limit).min(names.len());
+        let tasks = std::sync::Mutex::new(
+            names
+                .iter()
+                .map(|name| (name.as_str(), progress.add_child(name.clone())))
+                .enumerate()
+                .collect::<Vec<_>>()
+                .into_iter(),
+        );
+        // Each thread needs its own repository instance, opened just like the one we were given.
+        let (git_dir, open_options) = (repo.git_dir().to_owned(), repo.open_options().clone());
+        let mut outcomes = std::thread::scope(|scope| -> anyhow::Result<_> {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| -> anyhow::Result<_> {
+                        let repo = gix::open_opts(&git_dir, open_options.clone())?;
+                        let mut outcomes = Vec::new();
+                        loop {
+                            let Some((idx, (name, progress))) = tasks.lock().expect("not poisoned").next() else {
+                                break;
+                            };
+                            let (mut out, mut err) = (Vec::new(), Vec::new());
+                            let res = repo.find_remote(name).map_err(anyhow::Error::from).and_then(|remote| {
+                                fetch_remote(&repo, remote, progress, &mut out, &mut err, options.clone())
+                            });
+                            outcomes.push((idx, out, err, res));
+                        }
+                        Ok(outcomes)
+                    })
+                })
+                .collect();
+            let mut outcomes = Vec::new();
+            for handle in handles {
+                outcomes.extend(handle.join().expect("no panic")?);
+            }
+            Ok(outcomes)
+        })?;
+        outcomes.sort_by_key(|(idx, ..)| *idx);
+
+        let mut failed = 0;
+        for ((_, fetch_out, fetch_err, res), name) in outcomes.into_iter().zip(&names) {
+            writeln!(out, "Fetching {name}")?;
+            out.write_all(&fetch_out)?;
+            err.write_all(&fetch_err)?;
+            if let Err(fetch_err) = res {
+                writeln!(err, "Could not fetch {name}: {fetch_err:#}")?;
+                failed += 1;
+            }
+        }
+        if failed != 0 {
+            bail!("Could not fetch {failed} of {} remotes", names.len());
+        }
+        Ok(())
+    }
+
+    fn fetch_remote<P>(
+        repo: &gix::Repository,
+        mut remote: gix::Remote<'_>,
+        mut progress: P,
+        mut out: impl std::io::Write,
+        err: impl std::io::Write,
         Options {
-            format,
+            format: _,
             dry_run,
-            remote,
+            remote: _,
+            all: _,
+            thread_limit: _,
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
             shallow,
             ref_specs,
+            auto_maintenance: _,
         }: Options,
     ) -> anyhow::Result<()>
     where
         P: gix::NestedProgress,
         P::SubProgress: 'static,
     {
-        if format != OutputFormat::Human {
-            bail!("JSON output isn't yet supported for fetching.");
-        }
-
-        let mut remote = crate::repository::remote::by_name_or_url(&repo, remote.as_deref())?;
         if !ref_specs.is_empty() {
             remote.replace_refspecs(ref_specs.iter(), gix::remote::Direction::Fetch)?;
             remote = remote.with_fetch_tags(gix::remote::fetch::Tags::None);
@@ -82,7 +255,7 @@ pub(crate) mod function {
             } => {
                 let negotiate_default = Default::default();
                 print_updates(
-                    &repo,
+                    repo,
                     negotiate.as_ref().unwrap_or(&negotiate_default),
                     update_refs,
                     ref_specs,
@@ -96,7 +269,7 @@ pub(crate) mod function {
                 if let Some((negotiate, path)) =
                     open_negotiation_graph.and_then(|path| negotiate.as_ref().map(|n| (n, path)))
                 {
-                    render_graph(&repo, &negotiate.graph, &path, progress)?;
+                    render_graph(repo, &negotiate.graph, &path, progress)?;
                 }
                 Ok::<_, anyhow::Error>(())
             }
@@ -105,7 +278,7 @@ pub(crate) mod function {
                 write_pack_bundle,
                 negotiate,
             } => {
-                print_updates(&repo, &negotiate, update_refs, ref_specs, res.ref_map, &mut out, err)?;
+                print_updates(repo, &negotiate, update_refs, ref_specs, res.ref_map, &mut out, err)?;
                 if let Some(data_path) = write_pack_bundle.data_path {
                     writeln!(out, "pack  file: \"{}\"", data_path.display()).ok();
                 }
@@ -116,7 +289,7 @@ pub(crate) mod function {
                     print_negotiate_info(&mut out, Some(&negotiate))?;
                 }
                 if let Some(path) = open_negotiation_graph {
-                    render_graph(&repo, &negotiate.graph, &path, progress)?;
+                    render_graph(repo, &negotiate.graph, &path, progress)?;
                 }
                 Ok(())
             }
@@ -0,0 +1,83 @@
+use std::ffi::OsString;
+
+use gix::bstr::BString;
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// The format to use for each reference, with `%(atom)` placeholders.
+    pub ref_format: Option<BString>,
+    /// Sort keys like `refname` or `-committerdate`, with the last one being the primary key.
+    pub sort: Vec<BString>,
+    /// Only show references pointing at this object, directly or after peeling tags.
+    pub points_at: Option<OsString>,
+    /// Only show references whose tips are reachable from this commit.
+    pub merged: Option<OsString>,
+    /// Only show references matching one of these prefixes or glob patterns.
+    pub patterns: Vec<BString>,
+}
+
+pub(crate) mod function {
+    use anyhow::{bail, Context};
+    use gix::{bstr::ByteSlice, reference::list};
+
+    use super::Options;
+    use crate::OutputFormat;
+
+    pub fn for_each_ref(
+        mut repo: gix::Repository,
+        mut out: impl std::io::Write,
+        Options {
+            format,
+            ref_format,
+            sort,
+            points_at,
+            merged,
+            patterns,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+
+        let ref_format = ref_format
+            .map(|format| list::Format::parse(format.as_bstr()))
+            .transpose()?
+            .unwrap_or_default();
+        let points_at = points_at
+            .map(|spec| -> anyhow::Result<_> {
+                Ok(repo.rev_parse_single(gix::path::os_str_into_bstr(&spec)?)?.detach())
+            })
+            .transpose()?;
+        let merged = merged
+            .map(|spec| -> anyhow::Result<_> {
+                let spec = gix::path::os_str_into_bstr(&spec)?;
+                Ok(repo
+                    .rev_parse_single(spec)?
+                    .object()?
+                    .peel_to_kind(gix::object::Kind::Commit)
+                    .with_context(|| format!("Need commitish to check for merged references, got '{spec}'"))?
+                    .id)
+            })
+            .transpose()?;
+
+        let entries = repo.list_references(&list::Options {
+            patterns,
+            points_at,
+            merged,
+            sort: sort
+                .iter()
+                .map(|key| list::SortKey::parse(key.as_bstr()))
+                .collect::<Result<_, _>>()?,
+            upstream_tracking: ref_format.needs_upstream_tracking(),
+        })?;
+        for entry in &entries {
+            let mut line = entry.format(&ref_format, &repo)?;
+            line.push(b'\n');
+            out.write_all(&line)?;
+        }
+        Ok(())
+    }
+}
@@ -32,5 +32,10 @@ pub fn function(mut repo: gix::Repository, spec: Option<String>, mut out: impl s
         // correctly handle shallow repositories which are expected to have the commits
         // along the shallow boundary missing.
     }
+    let invalid_trees = check.invalid_trees().to_vec();
+    drop(check);
+    for (tree_id, err) in invalid_trees {
+        writeln!(out, "{tree_id}: invalid tree: {err}")?;
+    }
     Ok(())
 }
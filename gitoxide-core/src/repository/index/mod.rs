@@ -90,5 +90,56 @@ pub fn from_list(
     Ok(())
 }
 
+pub mod update_flags {
+    #[derive(Debug, Default, Copy, Clone)]
+    pub struct Options {
+        /// Set the `assume-unchanged` flag if `Some(true)`, or clear it if `Some(false)`.
+        pub assume_unchanged: Option<bool>,
+        /// Set the `skip-worktree` flag if `Some(true)`, or clear it if `Some(false)`.
+        pub skip_worktree: Option<bool>,
+    }
+}
+
+pub fn update_flags(
+    repo: gix::Repository,
+    pathspecs: Vec<gix::bstr::BString>,
+    mut err: impl std::io::Write,
+    update_flags::Options {
+        assume_unchanged,
+        skip_worktree,
+    }: update_flags::Options,
+) -> anyhow::Result<()> {
+    use gix::index::entry::Flags;
+    if assume_unchanged.is_none() && skip_worktree.is_none() {
+        bail!("Need at least one flag to set or clear");
+    }
+    let mut index = repo.index_or_empty()?;
+    let index = gix::threading::make_mut(&mut index);
+    let mut pathspec = repo.pathspec(
+        pathspecs,
+        true,
+        index,
+        gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+    )?;
+    let mut num_changed = 0;
+    for (flag, value) in [
+        (Flags::ASSUME_VALID, assume_unchanged),
+        (Flags::SKIP_WORKTREE, skip_worktree),
+    ] {
+        if let Some(value) = value {
+            num_changed += pathspec.set_index_entry_flags(index, flag, value);
+        }
+    }
+
+    if num_changed != 0 {
+        index.write(gix::index::write::Options {
+            extensions: Default::default(),
+            skip_hash: false, // TODO: make this based on configuration
+        })?;
+    }
+    writeln!(err, "Made {num_changed} change(s) to the flags of index entries")?;
+    Ok(())
+}
+
 pub mod entries;
 pub use entries::function::entries;
@@ -0,0 +1,90 @@
+use gix::bstr::BString;
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// Descend into subtrees instead of listing only the entries of the tree itself.
+    pub recursive: bool,
+    /// Show trees even when descending into them.
+    pub show_trees: bool,
+    /// Show the size of blobs and symlinks.
+    pub long: bool,
+    /// Show only the paths of entries.
+    pub name_only: bool,
+    /// Don't descend into trees this many levels below the tree itself.
+    pub max_depth: Option<usize>,
+    /// Only show entries matching these pathspecs.
+    pub pathspecs: Vec<BString>,
+}
+
+pub(crate) mod function {
+    use anyhow::bail;
+    use gix::bstr::ByteSlice;
+
+    use super::Options;
+    use crate::OutputFormat;
+
+    pub fn ls_tree(
+        repo: gix::Repository,
+        treeish: &str,
+        mut out: impl std::io::Write,
+        Options {
+            format,
+            recursive,
+            show_trees,
+            long,
+            name_only,
+            max_depth,
+            pathspecs,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+        let tree = repo
+            .rev_parse_single(format!("{treeish}^{{tree}}").as_str())?
+            .object()?
+            .into_tree();
+        let mut paths = tree
+            .traverse()
+            .paths()
+            .recursive(recursive)
+            .max_depth(max_depth)
+            .with_sizes(long);
+        if !pathspecs.is_empty() {
+            let tree_id = tree.id;
+            paths = paths.pathspec(gix::Pathspec::new(&repo, pathspecs.iter(), false, || {
+                repo.attributes_only_from_tree(&tree_id)
+                    .map(gix::AttributeStack::detach)
+                    .map_err(Into::into)
+            })?);
+        }
+
+        for entry in paths.all()? {
+            if recursive && !show_trees && entry.mode.is_tree() {
+                continue;
+            }
+            if name_only {
+                writeln!(out, "{}", entry.filepath)?;
+                continue;
+            }
+            let kind = if entry.mode.is_tree() {
+                "tree"
+            } else if entry.mode.is_commit() {
+                "commit"
+            } else {
+                "blob"
+            };
+            write!(out, "{:06o} {kind} {}", *entry.mode, entry.oid)?;
+            if long {
+                match entry.size {
+                    Some(size) => write!(out, " {size:>7}")?,
+                    None => write!(out, " {:>7}", "-")?,
+                }
+            }
+            writeln!(out, "\t{}", entry.filepath.as_bstr())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::bail;
+use gix::bstr::BStr;
+
+use crate::OutputFormat;
+
+/// Print all best common ancestors of the commits `first` and all of `others` to `out`, one per line.
+pub fn base(
+    repo: gix::Repository,
+    first: &str,
+    others: &[String],
+    format: OutputFormat,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+
+    let first = repo.rev_parse_single(first)?;
+    let others = others
+        .iter()
+        .map(|spec| Ok(repo.rev_parse_single(spec.as_str())?.detach()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let bases = repo.merge_bases(first, others)?;
+    if bases.is_empty() {
+        bail!("The commits don't have a common ancestor");
+    }
+    for id in bases {
+        writeln!(out, "{id}")?;
+    }
+    Ok(())
+}
+
+/// Merge the commits `theirs` into the commit `ours` using `strategy`, write the merged tree and print its id to `out`,
+/// followed by the entries of all conflicting paths and a message for each conflict, just like `git merge-tree --write-tree` does.
+pub fn commits(
+    repo: gix::Repository,
+    ours: &str,
+    theirs: &[String],
+    strategy: gix::merge::commits::Strategy,
+    format: OutputFormat,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+
+    let labels = gix::merge::plumbing::blob::Labels {
+        ancestor: None,
+        current: Some(BStr::new(ours)),
+        other: match theirs {
+            [theirs] => Some(BStr::new(theirs)),
+            _ => None,
+        },
+    };
+    let theirs = theirs
+        .iter()
+        .map(|spec| Ok(repo.rev_parse_single(spec.as_str())?.detach()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let outcome = repo.merge_commits(
+        repo.rev_parse_single(ours)?,
+        theirs,
+        labels,
+        gix::merge::commits::Options {
+            strategy,
+            ..Default::default()
+        },
+    )?;
+    writeln!(out, "{}", outcome.tree)?;
+    if !outcome.has_conflicts() {
+        return Ok(());
+    }
+    for conflict in &outcome.conflicts {
+        for (path, stage, entry) in conflict.index_entries() {
+            writeln!(out, "{:06o} {} {stage}\t{path}", *entry.mode, entry.id)?;
+        }
+    }
+    writeln!(out)?;
+    for conflict in &outcome.conflicts {
+        writeln!(out, "{}", conflict.message(&labels))?;
+    }
+    Ok(())
+}
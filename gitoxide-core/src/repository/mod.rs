@@ -3,11 +3,14 @@ use std::path::PathBuf;
 use anyhow::{Context as AnyhowContext, Result};
 use gix::bstr::BString;
 
-pub fn init(directory: Option<PathBuf>) -> Result<gix::discover::repository::Path> {
+pub fn init(directory: Option<PathBuf>, template_dir: Option<PathBuf>) -> Result<gix::discover::repository::Path> {
     gix::create::into(
         directory.unwrap_or_default(),
         gix::create::Kind::WithWorktree,
-        gix::create::Options::default(),
+        gix::create::Options {
+            template_dir,
+            ..Default::default()
+        },
     )
     .with_context(|| "Repository initialization failed")
 }
@@ -17,6 +20,8 @@ pub enum PathsOrPatterns {
     Patterns(Vec<BString>),
 }
 
+pub mod analytics;
+pub use analytics::function::analytics;
 #[cfg(feature = "archive")]
 pub mod archive;
 pub mod commit;
@@ -24,6 +29,7 @@ pub mod config;
 mod credential;
 pub use credential::function as credential;
 pub mod attributes;
+pub mod branch;
 #[cfg(feature = "blocking-client")]
 pub mod clone;
 pub mod exclude;
@@ -35,14 +41,26 @@ pub use clone::function::clone;
 pub use fetch::function::fetch;
 
 pub mod commitgraph;
+pub mod diff;
+pub mod for_each_ref;
+pub use for_each_ref::function::for_each_ref;
 mod fsck;
 pub use fsck::function as fsck;
 pub mod index;
+pub mod ls_tree;
+pub use ls_tree::function::ls_tree;
 pub mod mailmap;
+pub mod merge;
 pub mod odb;
 pub mod remote;
+pub mod rev_parse;
+pub use rev_parse::function::rev_parse;
 pub mod revision;
+pub mod shortlog;
+pub use shortlog::function::shortlog;
 pub mod status;
 pub mod submodule;
+pub mod tag;
 pub mod tree;
 pub mod verify;
+pub mod worktree;
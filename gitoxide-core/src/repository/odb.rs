@@ -89,6 +89,7 @@ pub fn statistics(
         commits_size: ByteSize,
         blobs: usize,
         blobs_size: ByteSize,
+        store: Option<gix::odb::store::statistics::Statistics>,
     }
 
     impl Statistics {
@@ -153,10 +154,11 @@ pub fn statistics(
         }
     }
 
+    let store = repo.objects.store();
     let cancelled = || anyhow::anyhow!("Cancelled by user");
     let object_ids = repo.objects.store_ref().iter()?.filter_map(Result::ok);
     let chunk_size = 1_000;
-    let stats = if gix::parallel::num_threads(thread_limit) > 1 {
+    let mut stats = if gix::parallel::num_threads(thread_limit) > 1 {
         gix::parallel::in_parallel(
             gix::interrupt::Iter::new(
                 gix::features::iter::Chunks {
@@ -192,6 +194,7 @@ pub fn statistics(
     };
 
     progress.show_throughput(start);
+    stats.store = Some(store.statistics()?);
 
     #[cfg(feature = "serde")]
     {
@@ -213,3 +216,62 @@ pub fn entries(repo: gix::Repository, format: OutputFormat, mut out: impl io::Wr
 
     Ok(())
 }
+
+pub mod pack_loose_objects {
+    pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
+
+    #[derive(Debug, Copy, Clone)]
+    pub struct Options {
+        pub thread_limit: Option<usize>,
+        /// The zlib compression level to use, or the configured one if unset.
+        pub compression_level: Option<u32>,
+        /// Keep the loose objects after they were packed.
+        pub keep_loose_objects: bool,
+        /// Write a multi-pack index even if there is none yet.
+        pub write_multi_pack_index: bool,
+    }
+}
+
+/// Write all loose objects into a new pack and delete them, and print a summary of what happened to `out`.
+pub fn pack_loose_objects(
+    repo: gix::Repository,
+    progress: impl gix::NestedProgress + 'static,
+    mut out: impl io::Write,
+    should_interrupt: &std::sync::atomic::AtomicBool,
+    pack_loose_objects::Options {
+        thread_limit,
+        compression_level,
+        keep_loose_objects,
+        write_multi_pack_index,
+    }: pack_loose_objects::Options,
+) -> anyhow::Result<()> {
+    let _lock = repo.lock_for_maintenance(Default::default())?;
+    let outcome = repo.pack_loose_objects(
+        progress,
+        should_interrupt,
+        gix::maintenance::pack_loose_objects::Options {
+            thread_limit,
+            compression_level,
+            delete_loose_objects: !keep_loose_objects,
+            write_multi_pack_index,
+        },
+    )?;
+    match outcome.pack {
+        Some(pack) => {
+            writeln!(
+                out,
+                "Packed {} loose objects into '{}'",
+                pack.index.num_objects,
+                pack.data_path.expect("written to disk").display()
+            )?;
+            if outcome.deleted_loose_objects != 0 {
+                writeln!(out, "Deleted {} loose objects", outcome.deleted_loose_objects)?;
+            }
+        }
+        None => writeln!(out, "There are no loose objects to pack")?,
+    }
+    if let Some(path) = outcome.multi_pack_index_path {
+        writeln!(out, "Updated multi-pack index at '{}'", path.display())?;
+    }
+    Ok(())
+}
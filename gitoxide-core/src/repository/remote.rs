@@ -336,3 +336,213 @@ pub(crate) fn by_name_or_url<'repo>(
 ) -> anyhow::Result<gix::Remote<'repo>> {
     repo.find_fetch_remote(name_or_url.map(Into::into)).map_err(Into::into)
 }
+
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+mod prune_impl {
+    use anyhow::Context;
+    use gix::{
+        bstr::BString,
+        refs::{
+            transaction::{Change, PreviousValue, RefEdit, RefLog},
+            TargetRef,
+        },
+    };
+
+    use super::by_name_or_url;
+
+    pub mod prune {
+        pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=2;
+    }
+
+    /// Delete all remote tracking references of the remote `name_or_url` whose counterpart on the remote doesn't exist anymore,
+    /// or only print them if `dry_run` is set, similar to `git remote prune`.
+    #[gix::protocol::maybe_async::maybe_async]
+    pub async fn prune_fn(
+        repo: gix::Repository,
+        name_or_url: Option<&str>,
+        dry_run: bool,
+        mut progress: impl gix::Progress,
+        mut out: impl std::io::Write,
+    ) -> anyhow::Result<()> {
+        let remote = by_name_or_url(&repo, name_or_url)?;
+        let name = remote
+            .name()
+            .context("Only named remotes have tracking references to prune")?
+            .as_bstr()
+            .to_owned();
+        let destinations: Vec<_> = remote
+            .refspecs(gix::remote::Direction::Fetch)
+            .iter()
+            .filter_map(|spec| spec.to_ref().destination().map(ToOwned::to_owned))
+            .collect();
+        let map = remote
+            .connect(gix::remote::Direction::Fetch)
+            .await?
+            .ref_map(&mut progress, Default::default())
+            .await?;
+        let live: std::collections::BTreeSet<BString> =
+            map.mappings.into_iter().filter_map(|mapping| mapping.local).collect();
+
+        let mut stale = Vec::new();
+        for reference in repo.references()?.all()? {
+            let reference = reference.map_err(|err| anyhow::anyhow!(err))?;
+            let TargetRef::Peeled(id) = reference.target() else {
+                continue;
+            };
+            let ref_name = reference.name().as_bstr();
+            let is_tracked = destinations
+                .iter()
+                .any(|destination| match destination.strip_suffix(b"*") {
+                    Some(prefix) => ref_name.starts_with(prefix),
+                    None => ref_name == destination,
+                });
+            if is_tracked && !live.contains(ref_name) {
+                stale.push((reference.name().to_owned(), id.to_owned()));
+            }
+        }
+
+        if stale.is_empty() {
+            return Ok(());
+        }
+        writeln!(out, "Pruning {name}")?;
+        for (ref_name, _) in &stale {
+            let action = if dry_run { "would prune" } else { "pruned" };
+            writeln!(out, " * [{action}] {}", ref_name.shorten())?;
+        }
+        if !dry_run {
+            repo.edit_references(stale.into_iter().map(|(ref_name, id)| RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(id.into()),
+                    log: RefLog::AndReference,
+                },
+                name: ref_name,
+                deref: false,
+            }))?;
+        }
+        Ok(())
+    }
+}
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use prune_impl::{prune, prune_fn as prune};
+
+/// Print the names of all remotes, along with their fetch and push URLs if `verbose` is set, similar to `git remote -v`.
+pub fn list(repo: gix::Repository, verbose: bool, mut out: impl std::io::Write) -> anyhow::Result<()> {
+    for name in repo.remote_names() {
+        if !verbose {
+            writeln!(out, "{name}")?;
+            continue;
+        }
+        let remote = repo.find_remote(name)?;
+        for (direction, label) in [
+            (gix::remote::Direction::Fetch, "fetch"),
+            (gix::remote::Direction::Push, "push"),
+        ] {
+            if let Some(url) = remote.url(direction) {
+                writeln!(out, "{name}\t{} ({label})", url.to_bstring())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the URLs, tracking references and the local branches that merge from the remote `name`, similar to `git remote show -n`.
+pub fn show(repo: gix::Repository, name: &str, mut out: impl std::io::Write) -> anyhow::Result<()> {
+    let remote = existing_remote(&repo, name)?;
+    writeln!(out, "* remote {name}")?;
+    for (direction, label) in [
+        (gix::remote::Direction::Fetch, "Fetch URL"),
+        (gix::remote::Direction::Push, "Push  URL"),
+    ] {
+        let url = remote.url(direction).map(gix::Url::to_bstring).unwrap_or_default();
+        writeln!(out, "  {label}: {url}")?;
+    }
+    for spec in remote.refspecs(gix::remote::Direction::Fetch) {
+        writeln!(out, "  Fetch refspec: {}", spec.to_ref().to_bstring())?;
+    }
+    for spec in remote.refspecs(gix::remote::Direction::Push) {
+        writeln!(out, "  Push refspec: {}", spec.to_ref().to_bstring())?;
+    }
+
+    let tracking: Vec<_> = repo
+        .references()?
+        .prefixed(format!("refs/remotes/{name}/"))?
+        .filter_map(Result::ok)
+        .map(|reference| reference.name().shorten().to_owned())
+        .collect();
+    if !tracking.is_empty() {
+        writeln!(out, "  Remote tracking branches:")?;
+        for tracking in tracking {
+            writeln!(out, "    {tracking}")?;
+        }
+    }
+
+    let pulls: Vec<_> = repo
+        .branch_names()
+        .into_iter()
+        .filter(|branch| {
+            repo.branch_remote_name(*branch)
+                .map_or(false, |remote| remote.as_bstr() == name)
+        })
+        .filter_map(|branch| {
+            let merge = repo.branch_remote_ref(branch)?.ok()?;
+            Some((branch, merge.shorten().to_owned()))
+        })
+        .collect();
+    if !pulls.is_empty() {
+        writeln!(out, "  Local branches configured for pulling:")?;
+        for (branch, merge) in pulls {
+            writeln!(out, "    {branch} merges with remote {merge}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Add a remote `name` fetching all branches from `url` into `refs/remotes/<name>/`, with `tags` controlling how tags are fetched.
+pub fn add(repo: gix::Repository, name: &str, url: &str, tags: gix::remote::fetch::Tags) -> anyhow::Result<()> {
+    if repo.remote_names().contains(name) {
+        anyhow::bail!("The remote '{name}' already exists");
+    }
+    let mut remote = repo
+        .remote_at(url)?
+        .with_refspecs(
+            Some(format!("+refs/heads/*:refs/remotes/{name}/*").as_str()),
+            gix::remote::Direction::Fetch,
+        )?
+        .with_fetch_tags(tags);
+    let (mut config, lock) = super::config::open_scope_for_update(&repo, super::config::Scope::Local)?;
+    remote.save_as_to(name, &mut config)?;
+    super::config::commit_scope(&config, lock)
+}
+
+/// Remove the remote `name` along with its remote tracking references, and unset the upstream of all branches that track it.
+pub fn remove(repo: gix::Repository, name: &str) -> anyhow::Result<()> {
+    Ok(repo.remove_remote(name)?)
+}
+
+/// Rename the remote `old` to `new`, moving its remote tracking references and adjusting its fetch refspecs
+/// as well as the upstream of all branches that track it.
+pub fn rename(repo: gix::Repository, old: &str, new: &str) -> anyhow::Result<()> {
+    Ok(repo.rename_remote(old, new)?)
+}
+
+/// Set the URL of the remote `name` to `url`, or its push URL if `push` is set, replacing the last one unless `add` is set.
+pub fn set_url(repo: gix::Repository, name: &str, url: &str, push: bool, add: bool) -> anyhow::Result<()> {
+    existing_remote(&repo, name)?;
+    gix::url::parse(url.into())?;
+    let key = if push { "pushurl" } else { "url" };
+    let (mut config, lock) = super::config::open_scope_for_update(&repo, super::config::Scope::Local)?;
+    let mut section = config.section_mut_or_create_new("remote", Some(name.into()))?;
+    if add {
+        section.push(key.try_into()?, Some(url.into()));
+    } else {
+        section.set(key.try_into()?, url.into());
+    }
+    super::config::commit_scope(&config, lock)
+}
+
+fn existing_remote<'repo>(repo: &'repo gix::Repository, name: &str) -> anyhow::Result<gix::Remote<'repo>> {
+    if !repo.remote_names().contains(name) {
+        anyhow::bail!("No such remote: '{name}'");
+    }
+    Ok(repo.find_remote(name)?)
+}
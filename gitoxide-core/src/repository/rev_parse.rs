@@ -0,0 +1,194 @@
+use std::path::PathBuf;
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// Fail unless exactly one spec is given which resolves to a single object.
+    pub verify: bool,
+    /// Shorten object ids to a unique prefix of the length configured by `core.abbrev` if `Some(None)`,
+    /// or of at least the given length.
+    pub short: Option<Option<usize>>,
+    /// Print the short name of the reference each spec refers to instead of its object id.
+    pub abbrev_ref: bool,
+    /// Print the absolute path to the top-level directory of the work tree.
+    pub show_toplevel: bool,
+    /// Print the path to the `.git` directory.
+    pub git_dir: bool,
+    /// Print `true` if `current_dir` is inside of the work tree, or `false` otherwise.
+    pub is_inside_work_tree: bool,
+    /// The directory the repository was discovered from.
+    pub current_dir: PathBuf,
+}
+
+pub(crate) mod function {
+    use std::{ffi::OsString, path::Path};
+
+    use anyhow::{bail, Context};
+    use gix::{
+        bstr::{BStr, BString, ByteSlice},
+        prelude::ObjectIdExt,
+        refs::{PartialNameRef, Target},
+        revision::plumbing::Spec,
+        ObjectId,
+    };
+
+    use super::Options;
+    use crate::OutputFormat;
+
+    pub fn rev_parse(
+        mut repo: gix::Repository,
+        specs: Vec<OsString>,
+        mut out: impl std::io::Write,
+        Options {
+            format,
+            verify,
+            short,
+            abbrev_ref,
+            show_toplevel,
+            git_dir,
+            is_inside_work_tree,
+            current_dir,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+        repo.object_cache_size_if_unset(1024 * 1024);
+
+        if show_toplevel {
+            let work_dir = repo
+                .work_dir()
+                .context("Cannot show the top-level directory of a bare repository")?;
+            writeln!(out, "{}", gix::path::realpath(work_dir)?.display())?;
+        }
+        if git_dir {
+            writeln!(out, "{}", repo.git_dir().display())?;
+        }
+        if is_inside_work_tree {
+            writeln!(out, "{}", is_inside(&repo, &current_dir)?)?;
+        }
+
+        let verify = verify || short.is_some();
+        if verify && specs.len() != 1 {
+            bail!("Needed a single revision, got {}", specs.len());
+        }
+        for spec in specs {
+            let text = gix::path::os_str_into_bstr(&spec)?;
+            let resolved = repo.rev_parse(text)?;
+            if verify && resolved.single().is_none() {
+                bail!("Needed a single revision, but '{text}' is a range");
+            }
+            if abbrev_ref {
+                for (prefix, name) in sides(text) {
+                    // Like `git`, we skip everything that isn't a reference.
+                    if let Some(name) = abbreviated_ref_name(&repo, name)? {
+                        writeln!(out, "{prefix}{name}")?;
+                    }
+                }
+                continue;
+            }
+            for (prefix, id) in expand(&repo, resolved.detach())? {
+                writeln!(out, "{prefix}{}", to_hex(&repo, id, short)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_inside(repo: &gix::Repository, current_dir: &Path) -> anyhow::Result<bool> {
+        let Some(work_dir) = repo.work_dir() else {
+            return Ok(false);
+        };
+        let current_dir = gix::path::realpath(current_dir)?;
+        Ok(current_dir.starts_with(gix::path::realpath(work_dir)?)
+            && !current_dir.starts_with(gix::path::realpath(repo.git_dir())?))
+    }
+
+    /// Turn `spec` into the list of objects to include or exclude, each with its `^` prefix if it's excluded,
+    /// in the order `git rev-parse` would print them.
+    fn expand(repo: &gix::Repository, spec: Spec) -> anyhow::Result<Vec<(&'static str, ObjectId)>> {
+        let parents = |id: ObjectId| -> anyhow::Result<Vec<ObjectId>> {
+            Ok(repo
+                .find_object(id)?
+                .try_into_commit()?
+                .parent_ids()
+                .map(gix::Id::detach)
+                .collect())
+        };
+        Ok(match spec {
+            Spec::Include(id) => vec![("", id)],
+            Spec::Exclude(id) => vec![("^", id)],
+            Spec::Range { from, to } => vec![("", to), ("^", from)],
+            Spec::Merge { theirs, ours } => {
+                let mut out = vec![("", ours), ("", theirs)];
+                out.extend(
+                    repo.merge_bases(theirs, Some(ours))?
+                        .into_iter()
+                        .map(|id| ("^", id.detach())),
+                );
+                out
+            }
+            Spec::IncludeOnlyParents(id) => parents(id)?.into_iter().map(|id| ("", id)).collect(),
+            Spec::ExcludeParents(id) => std::iter::once(("", id))
+                .chain(parents(id)?.into_iter().map(|id| ("^", id)))
+                .collect(),
+        })
+    }
+
+    fn to_hex(repo: &gix::Repository, id: ObjectId, short: Option<Option<usize>>) -> anyhow::Result<String> {
+        Ok(match short {
+            None => id.to_string(),
+            Some(None) => id.attach(repo).shorten()?.to_string(),
+            Some(Some(hex_len)) => {
+                let candidate = gix::odb::store::prefix::disambiguate::Candidate::new(
+                    id,
+                    hex_len.clamp(4, id.kind().len_in_hex()),
+                )?;
+                repo.objects
+                    .disambiguate_prefix(candidate)?
+                    .with_context(|| format!("Object {id} disappeared"))?
+                    .to_string()
+            }
+        })
+    }
+
+    /// Split the textual `spec` into the revisions on each side of a range along with the `^` prefix if they are
+    /// excluded, in the order `git rev-parse` would print them.
+    fn sides(spec: &BStr) -> Vec<(&'static str, &BStr)> {
+        fn or_head(side: &[u8]) -> &BStr {
+            if side.is_empty() {
+                "HEAD".into()
+            } else {
+                side.as_bstr()
+            }
+        }
+        if let Some((from, to)) = spec.split_once_str("...") {
+            vec![("", or_head(to)), ("", or_head(from))]
+        } else if let Some((from, to)) = spec.split_once_str("..") {
+            vec![("", or_head(to)), ("^", or_head(from))]
+        } else if let Some(excluded) = spec.strip_prefix(b"^") {
+            vec![("^", excluded.as_bstr())]
+        } else {
+            vec![("", spec)]
+        }
+    }
+
+    /// Return the shortened name of the reference `name` refers to, following symbolic references like `HEAD`,
+    /// or `None` if it doesn't name a reference.
+    fn abbreviated_ref_name(repo: &gix::Repository, name: &BStr) -> anyhow::Result<Option<BString>> {
+        let Ok(partial_name) = <&PartialNameRef>::try_from(name) else {
+            return Ok(None);
+        };
+        let Some(reference) = repo.try_find_reference(partial_name)? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            match &reference.inner.target {
+                Target::Symbolic(target) => target.as_ref(),
+                Target::Peeled(_) => reference.name(),
+            }
+            .shorten()
+            .to_owned(),
+        ))
+    }
+}
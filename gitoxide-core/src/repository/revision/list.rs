@@ -4,6 +4,10 @@ use crate::OutputFormat;
 
 pub struct Context {
     pub limit: Option<usize>,
+    /// Only list commits committed at or after this date.
+    pub since: Option<gix::date::Time>,
+    /// Only list commits committed at or before this date.
+    pub until: Option<gix::date::Time>,
     pub spec: OsString,
     pub format: OutputFormat,
     pub text: Format,
@@ -35,6 +39,8 @@ pub(crate) mod function {
             format,
             text,
             limit,
+            since,
+            until,
         }: super::Context,
     ) -> anyhow::Result<()> {
         if format != OutputFormat::Human {
@@ -53,6 +59,8 @@ pub(crate) mod function {
             .id()
             .ancestors()
             .sorting(Sorting::ByCommitTimeNewestFirst)
+            .since(since.map(|time| time.seconds))
+            .until(until.map(|time| time.seconds))
             .all()?;
 
         let mut vg = match text {
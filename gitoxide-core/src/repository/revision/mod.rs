@@ -6,5 +6,8 @@ pub use explain::explain;
 pub mod resolve;
 pub use resolve::function::resolve;
 
+pub mod name_rev;
+pub use name_rev::function::name_rev;
+
 mod previous_branches;
 pub use previous_branches::previous_branches;
@@ -0,0 +1,92 @@
+use gix::bstr::BString;
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub format: OutputFormat,
+    pub tags_only: bool,
+    pub refs: Vec<BString>,
+    pub exclude: Vec<BString>,
+    pub name_only: bool,
+}
+
+pub(crate) mod function {
+    use std::{ffi::OsString, io::BufRead};
+
+    use anyhow::{bail, Context};
+    use gix::bstr::ByteSlice;
+
+    use super::Options;
+    use crate::OutputFormat;
+
+    /// Commits this many seconds older than the oldest commit to name are ignored, to allow for clock skew.
+    const CUTOFF_DATE_SLOP: gix::date::SecondsSinceUnixEpoch = 86400;
+
+    pub fn name_rev(
+        mut repo: gix::Repository,
+        specs: Vec<OsString>,
+        stdin: Option<impl BufRead>,
+        mut out: impl std::io::Write,
+        Options {
+            format,
+            tags_only,
+            refs,
+            exclude,
+            name_only,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+
+        let commits = specs
+            .iter()
+            .map(|spec| -> anyhow::Result<_> {
+                let spec = gix::path::os_str_into_bstr(spec)?;
+                let commit = repo
+                    .rev_parse_single(spec)?
+                    .object()?
+                    .peel_to_kind(gix::object::Kind::Commit)
+                    .with_context(|| format!("Need commitish to name, got '{spec}'"))?
+                    .into_commit();
+                let time = commit.time()?.seconds;
+                Ok((spec, commit.id, time))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Like `git`, don't bother naming commits that are a lot older than the oldest one we are interested in,
+        // unless all commits could be named.
+        let cutoff = stdin
+            .is_none()
+            .then(|| commits.iter().map(|(_, _, time)| *time).min())
+            .flatten()
+            .map(|oldest| oldest.saturating_sub(CUTOFF_DATE_SLOP));
+
+        let names = repo.name_rev(gix::revision::name_rev::Options {
+            tags_only,
+            include: refs,
+            exclude,
+            cutoff,
+        })?;
+        if let Some(mut stdin) = stdin {
+            let mut line = Vec::new();
+            while stdin.read_until(b'\n', &mut line)? != 0 {
+                out.write_all(&names.annotate(line.as_bstr(), name_only))?;
+                line.clear();
+            }
+            return Ok(());
+        }
+
+        for (spec, id, _) in commits {
+            let name = names
+                .name(id)
+                .map_or_else(|| "undefined".to_owned(), ToString::to_string);
+            if name_only {
+                writeln!(out, "{name}")?;
+            } else {
+                writeln!(out, "{spec} {name}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use gix::bstr::BString;
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// Only print the amount of commits of each author instead of their summaries.
+    pub summary: bool,
+    /// Sort authors by their amount of commits instead of alphabetically.
+    pub numbered: bool,
+    /// Show the email address of each author along with their name.
+    pub email: bool,
+    /// The revisions and ranges to take commits from, or `HEAD` if empty.
+    pub revisions: Vec<BString>,
+}
+
+pub(crate) mod function {
+    use std::collections::BTreeMap;
+
+    use anyhow::bail;
+    use gix::{
+        bstr::{BString, ByteSlice},
+        hashtable::HashSet,
+        revision::plumbing::Spec,
+        traverse::commit::Sorting,
+        ObjectId,
+    };
+
+    use super::Options;
+    use crate::OutputFormat;
+
+    pub fn shortlog(
+        mut repo: gix::Repository,
+        mut out: impl std::io::Write,
+        Options {
+            format,
+            summary,
+            numbered,
+            email,
+            revisions,
+        }: Options,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+
+        let mut tips = Vec::new();
+        let mut hidden = Vec::new();
+        if revisions.is_empty() {
+            tips.push(repo.head_id()?.detach());
+        }
+        for revision in &revisions {
+            match repo.rev_parse(revision.as_bstr())?.detach() {
+                Spec::Include(id) => tips.push(id),
+                Spec::Exclude(id) => hidden.push(id),
+                Spec::Range { from, to } => {
+                    tips.push(to);
+                    hidden.push(from);
+                }
+                Spec::Merge { theirs, ours } => {
+                    tips.extend([theirs, ours]);
+                    hidden.extend(repo.merge_bases(theirs, Some(ours))?.into_iter().map(gix::Id::detach));
+                }
+                Spec::IncludeOnlyParents(id) => tips.extend(parents(&repo, id)?),
+                Spec::ExcludeParents(id) => {
+                    tips.push(id);
+                    hidden.extend(parents(&repo, id)?);
+                }
+            }
+        }
+        let hidden: HashSet<ObjectId> = if hidden.is_empty() {
+            HashSet::default()
+        } else {
+            repo.rev_walk(hidden)
+                .all()?
+                .map(|info| info.map(|info| info.id))
+                .collect::<Result<_, _>>()?
+        };
+
+        let mailmap = repo.open_mailmap();
+        let mut summaries_by_author = BTreeMap::<BString, Vec<BString>>::new();
+        for info in repo
+            .rev_walk(tips)
+            .sorting(Sorting::ByCommitTimeNewestFirst)
+            .selected(move |id| !hidden.contains(id))?
+        {
+            if gix::interrupt::is_triggered() {
+                bail!("Cancelled by user");
+            }
+            let commit = info?.object()?;
+            let author = mailmap.resolve(commit.author()?.trim());
+            let mut key = author.name;
+            if email {
+                key.extend_from_slice(b" <");
+                key.extend_from_slice(&author.email);
+                key.push(b'>');
+            }
+            summaries_by_author
+                .entry(key)
+                .or_default()
+                .push(commit.message()?.summary().into_owned());
+        }
+
+        let mut authors: Vec<_> = summaries_by_author.into_iter().collect();
+        if numbered {
+            authors.sort_by_key(|(_, summaries)| std::cmp::Reverse(summaries.len()));
+        }
+        for (author, summaries) in authors {
+            if summary {
+                writeln!(out, "{:>6}\t{author}", summaries.len())?;
+                continue;
+            }
+            writeln!(out, "{author} ({}):", summaries.len())?;
+            for line in summaries.iter().rev() {
+                writeln!(out, "      {line}")?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    fn parents(repo: &gix::Repository, id: ObjectId) -> anyhow::Result<Vec<ObjectId>> {
+        Ok(repo
+            .find_object(id)?
+            .try_into_commit()?
+            .parent_ids()
+            .map(gix::Id::detach)
+            .collect())
+    }
+}
@@ -43,8 +43,15 @@ pub fn show(
     if format != OutputFormat::Human {
         bail!("Only human format is supported right now");
     }
+    let workdir = repo
+        .work_dir()
+        .context("This operation cannot be run on a bare repository")?;
     let mut index = repo.index_or_empty()?;
     let index = gix::threading::make_mut(&mut index);
+    let sparse_outcome = repo
+        .sparse_checkout_patterns()?
+        .map(|patterns| patterns.update_skip_worktree(index, workdir))
+        .unwrap_or_default();
     let pathspec = repo.pathspec(
         pathspecs,
         true,
@@ -76,8 +83,7 @@ pub fn show(
     };
     let outcome = gix_status::index_as_worktree(
         index,
-        repo.work_dir()
-            .context("This operation cannot be run on a bare repository")?,
+        workdir,
         &mut printer,
         FastEq,
         Submodule,
@@ -92,7 +98,7 @@ pub fn show(
         options,
     )?;
 
-    if outcome.entries_to_update != 0 && allow_write {
+    if (!printer.changes.is_empty() || sparse_outcome.index_changed()) && allow_write {
         {
             let entries = index.entries_mut();
             for (entry_index, change) in printer.changes {
@@ -17,6 +17,45 @@ pub fn list(repo: Repository, mut out: impl std::io::Write, format: OutputFormat
     Ok(())
 }
 
+#[cfg(feature = "blocking-client")]
+pub fn update<P>(
+    repo: Repository,
+    paths: Vec<gix::bstr::BString>,
+    init: bool,
+    mut progress: P,
+    mut out: impl std::io::Write,
+    format: OutputFormat,
+) -> anyhow::Result<()>
+where
+    P: gix::NestedProgress,
+    P::SubProgress: 'static,
+{
+    if format != OutputFormat::Human {
+        bail!("Only human output is supported for now")
+    }
+
+    let Some(submodules) = repo.submodules()? else {
+        return Ok(());
+    };
+    for sm in submodules {
+        let path = sm.path()?.into_owned();
+        if !paths.is_empty() && !paths.contains(&path) {
+            continue;
+        }
+        let outcome = sm.update_with_checkout(init, &mut progress, &gix::interrupt::IS_INTERRUPTED)?;
+        if outcome.initialized {
+            writeln!(out, "Submodule '{name}' registered for path '{path}'", name = sm.name())?;
+        }
+        if outcome.cloned {
+            writeln!(out, "Cloned submodule '{name}' into '{path}'", name = sm.name())?;
+        }
+        if let Some(id) = outcome.checked_out {
+            writeln!(out, "Submodule path '{path}': checked out '{id}'")?;
+        }
+    }
+    Ok(())
+}
+
 fn print_sm(sm: Submodule<'_>, out: &mut impl std::io::Write) -> anyhow::Result<()> {
     let _span = gix::trace::coarse!("print_sm", path = ?sm.path());
     let state = sm.state()?;
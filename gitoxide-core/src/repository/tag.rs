@@ -0,0 +1,199 @@
+use anyhow::{bail, Context};
+use gix::{
+    bstr::{BString, ByteSlice},
+    refs::{transaction::PreviousValue, Category},
+};
+
+use super::branch::{filter_reachable, peel_to_commit};
+use crate::OutputFormat;
+
+pub mod list {
+    use std::ffi::OsString;
+
+    use gix::bstr::BString;
+
+    pub struct Options {
+        /// Show the subject of annotated tags, or of the tagged commit for lightweight tags.
+        pub show_subject: bool,
+        /// Only list tags whose history contains this commit.
+        pub contains: Option<OsString>,
+        /// Only list tags whose commits are reachable from this commit.
+        pub merged: Option<OsString>,
+        /// Only list tags matching one of these glob patterns.
+        pub patterns: Vec<BString>,
+    }
+}
+
+pub mod create {
+    use std::ffi::OsString;
+
+    pub struct Options {
+        /// Create an annotated tag object instead of a lightweight tag.
+        pub annotate: bool,
+        /// The message of the annotated tag. If unset for annotated tags, it's obtained from the editor.
+        pub message: Option<String>,
+        /// Replace an existing tag of the same name.
+        pub force: bool,
+        /// The object to tag, or `HEAD` if unset.
+        pub target: Option<OsString>,
+    }
+}
+
+/// List tags in version order, similar to `git tag --list`.
+pub fn list(
+    repo: gix::Repository,
+    format: OutputFormat,
+    mut out: impl std::io::Write,
+    list::Options {
+        show_subject,
+        contains,
+        merged,
+        patterns,
+    }: list::Options,
+) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+    let contains = contains
+        .as_deref()
+        .map(|spec| peel_to_commit(&repo, spec))
+        .transpose()?;
+    let merged = merged.as_deref().map(|spec| peel_to_commit(&repo, spec)).transpose()?;
+
+    let mut tags = Vec::new();
+    for reference in repo.references()?.tags()? {
+        let mut reference = reference.map_err(|err| anyhow::anyhow!(err))?;
+        let Some((Category::Tag, short_name)) = reference.name().category_and_short_name() else {
+            continue;
+        };
+        if !patterns.is_empty()
+            && !patterns
+                .iter()
+                .any(|pattern| gix::glob::wildmatch(pattern.as_bstr(), short_name, gix::glob::wildmatch::Mode::empty()))
+        {
+            continue;
+        }
+        let short_name = short_name.to_owned();
+        let direct_id = reference.target().try_id().map(ToOwned::to_owned);
+        let Ok(id) = reference.peel_to_id_in_place() else {
+            continue;
+        };
+        let object = repo.find_object(direct_id.unwrap_or(id.detach()))?;
+        let subject = match object.kind {
+            gix::object::Kind::Tag => Some(
+                gix::objs::commit::MessageRef::from_bytes(object.to_tag_ref().message)
+                    .summary()
+                    .into_owned(),
+            ),
+            _ => None,
+        };
+        let peeled = repo.find_object(id)?;
+        let subject = match (subject, peeled.kind) {
+            (Some(subject), _) => subject,
+            (None, gix::object::Kind::Commit) => peeled.into_commit().message()?.summary().into_owned(),
+            (None, _) => BString::default(),
+        };
+        tags.push((short_name, id.detach(), subject));
+    }
+
+    if contains.is_some() || merged.is_some() {
+        tags.retain(|(_, id, _)| {
+            repo.find_header(*id)
+                .map_or(false, |h| h.kind() == gix::object::Kind::Commit)
+        });
+    }
+    let keep = filter_reachable(&repo, tags.iter().map(|(_, id, _)| *id), contains, merged, None)?;
+    let mut tags: Vec<_> = tags
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(t, keep)| keep.then_some(t))
+        .collect();
+    let version_sort = repo.version_sort();
+    tags.sort_by(|(a, _, _), (b, _, _)| version_sort.compare(a.as_bstr(), b.as_bstr()));
+
+    let width = tags.iter().map(|(name, _, _)| name.len()).max().unwrap_or_default();
+    for (name, _, subject) in &tags {
+        if show_subject {
+            writeln!(out, "{name:width$} {subject}", name = name.to_str_lossy())?;
+        } else {
+            writeln!(out, "{name}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Create the tag `name` pointing to the target in `options`, either as lightweight tag or as annotated tag
+/// whose message is obtained from the editor if it wasn't provided, similar to `git tag`.
+pub fn create(repo: gix::Repository, name: &str, options: create::Options) -> anyhow::Result<()> {
+    let create::Options {
+        annotate,
+        message,
+        force,
+        target,
+    } = options;
+    let target = match target.as_deref() {
+        Some(spec) => repo.rev_parse_single(gix::path::os_str_into_bstr(spec)?)?,
+        None => repo.head_id()?,
+    };
+    let constraint = if force {
+        PreviousValue::Any
+    } else {
+        PreviousValue::MustNotExist
+    };
+    if !annotate && message.is_none() {
+        repo.tag_reference(name, target, constraint)?;
+        return Ok(());
+    }
+
+    let message = match message {
+        Some(message) => message,
+        None => message_from_editor(&repo, name)?,
+    };
+    let message = message.trim();
+    if message.is_empty() {
+        bail!("Aborting the creation of tag '{name}' due to an empty message");
+    }
+    let tagger = repo
+        .committer()
+        .transpose()?
+        .context("A tagger identity is needed, configure user.name and user.email")?;
+    let kind = target.object()?.kind;
+    repo.tag(name, target, kind, Some(tagger), format!("{message}\n"), constraint)?;
+    Ok(())
+}
+
+/// Delete the tags with the given `names`, and print each deleted tag to `out`.
+pub fn delete(repo: gix::Repository, names: &[String], mut out: impl std::io::Write) -> anyhow::Result<()> {
+    for name in names {
+        let reference = repo
+            .try_find_reference(format!("refs/tags/{name}").as_str())?
+            .with_context(|| format!("Tag '{name}' not found"))?;
+        let id = reference.target().try_id().map(ToOwned::to_owned);
+        reference.delete()?;
+        match id {
+            Some(id) => writeln!(out, "Deleted tag '{name}' (was {})", id.to_hex_with_len(7))?,
+            None => writeln!(out, "Deleted tag '{name}'")?,
+        }
+    }
+    Ok(())
+}
+
+/// Let the user edit the message of tag `name` in `TAG_EDITMSG` and return it with all comment lines removed.
+fn message_from_editor(repo: &gix::Repository, name: &str) -> anyhow::Result<String> {
+    let path = repo.git_dir().join("TAG_EDITMSG");
+    std::fs::write(
+        &path,
+        format!("\n#\n# Write a message for tag:\n#   {name}\n# Lines starting with '#' will be ignored.\n"),
+    )?;
+    let status = std::process::Command::from(repo.editor()?.arg(path.as_os_str())).status()?;
+    if !status.success() {
+        bail!("The editor exited with {status}, aborting");
+    }
+    let message = std::fs::read(&path)?;
+    Ok(message
+        .lines()
+        .filter(|line| !line.starts_with(b"#"))
+        .map(|line| line.to_str_lossy().trim_end().to_owned())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
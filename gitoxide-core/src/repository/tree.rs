@@ -173,6 +173,79 @@ pub fn entries(
     Ok(())
 }
 
+pub mod export {
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub filters: bool,
+        pub overwrite_existing: bool,
+        pub keep_going: bool,
+        pub thread_limit: Option<usize>,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn export(
+    repo: gix::Repository,
+    treeish: Option<&str>,
+    destination: &std::path::Path,
+    pathspecs: Vec<gix::bstr::BString>,
+    mut progress: impl gix::NestedProgress,
+    mut err: impl io::Write,
+    should_interrupt: &std::sync::atomic::AtomicBool,
+    export::Options {
+        filters,
+        overwrite_existing,
+        keep_going,
+        thread_limit,
+    }: export::Options,
+) -> anyhow::Result<()> {
+    use gix::Progress;
+
+    let tree = treeish_to_tree(treeish, &repo)?;
+    let mut files = progress.add_child("export");
+    let mut bytes = progress.add_child("writing");
+    files.init(None, gix::progress::count("files"));
+    bytes.init(None, gix::progress::bytes());
+
+    let start = std::time::Instant::now();
+    let outcome = repo.export_tree(
+        tree.id,
+        destination,
+        &pathspecs,
+        &files,
+        &bytes,
+        should_interrupt,
+        gix::worktree::export::Options {
+            filters,
+            overwrite_existing,
+            keep_going,
+            thread_limit,
+        },
+    )?;
+    files.show_throughput(start);
+    bytes.show_throughput(start);
+
+    progress.done(format!(
+        "Exported {} files of tree {} into '{}'",
+        outcome.files_updated,
+        tree.id,
+        destination.display()
+    ));
+    for collision in &outcome.collisions {
+        writeln!(err, "{}: collision ({:?})", collision.path, collision.error_kind)?;
+    }
+    for error in &outcome.errors {
+        writeln!(err, "{}: {}", error.path, error.error)?;
+    }
+    if !(outcome.collisions.is_empty() && outcome.errors.is_empty()) {
+        bail!(
+            "Failed to write {} files",
+            outcome.collisions.len() + outcome.errors.len()
+        );
+    }
+    Ok(())
+}
+
 fn treeish_to_tree<'repo>(treeish: Option<&str>, repo: &'repo gix::Repository) -> anyhow::Result<Tree<'repo>> {
     let spec = treeish.map_or_else(|| "@^{tree}".into(), |spec| format!("{spec}^{{tree}}"));
     Ok(repo.rev_parse_single(spec.as_str())?.object()?.into_tree())
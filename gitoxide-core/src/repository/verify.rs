@@ -1,4 +1,6 @@
-use std::sync::atomic::AtomicBool;
+use std::{path::PathBuf, sync::atomic::AtomicBool};
+
+use gix::{Count, NestedProgress, Progress};
 
 use crate::{pack, OutputFormat};
 
@@ -16,10 +18,48 @@ pub struct Context {
 
 pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
 
+/// The result of verifying one part of the repository.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Status<T> {
+    /// The part was verified successfully, yielding the contained statistics.
+    Ok(T),
+    /// The part doesn't exist in the repository, which isn't an error.
+    Absent,
+    /// The verification failed with the contained error message.
+    Failed(String),
+}
+
+impl<T> Status<T> {
+    fn from_result(res: anyhow::Result<T>) -> Self {
+        match res {
+            Ok(v) => Status::Ok(v),
+            Err(err) => Status::Failed(format!("{err:#}")),
+        }
+    }
+
+    fn failure(&self) -> Option<&str> {
+        match self {
+            Status::Failed(err) => Some(err),
+            Status::Ok(_) | Status::Absent => None,
+        }
+    }
+}
+
+/// Statistics about all verified reference logs.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReflogStatistics {
+    /// The amount of reference logs.
+    pub num_logs: usize,
+    /// The amount of entries in all reference logs.
+    pub num_entries: usize,
+}
+
 pub fn integrity(
     repo: gix::Repository,
     mut out: impl std::io::Write,
-    mut progress: impl gix::NestedProgress + 'static,
+    mut progress: impl NestedProgress + 'static,
     should_interrupt: &AtomicBool,
     Context {
         output_statistics,
@@ -28,37 +68,173 @@ pub fn integrity(
         algorithm,
     }: Context,
 ) -> anyhow::Result<()> {
-    #[cfg_attr(not(feature = "serde"), allow(unused))]
-    let outcome = repo.objects.store_ref().verify_integrity(
-        &mut progress,
-        should_interrupt,
-        gix::odb::pack::index::verify::integrity::Options {
-            verify_mode,
-            traversal: algorithm.into(),
-            thread_limit,
-            // TODO: a way to get the pack cache from a handle
-            make_pack_lookup_cache: || gix::odb::pack::cache::Never,
-        },
-    )?;
-    if let Some(index) = repo.worktree().map(|wt| wt.index()).transpose()? {
-        index.verify_integrity()?;
-        index.verify_entries()?;
-        index.verify_extensions(true, repo.objects)?;
-        progress.info(format!("Index at '{}' OK", index.path().display()));
-    }
+    let objects = Status::from_result(
+        repo.objects
+            .store_ref()
+            .verify_integrity(
+                &mut progress,
+                should_interrupt,
+                gix::odb::pack::index::verify::integrity::Options {
+                    verify_mode,
+                    traversal: algorithm.into(),
+                    thread_limit,
+                    // TODO: a way to get the pack cache from a handle
+                    make_pack_lookup_cache: || gix::odb::pack::cache::Never,
+                },
+            )
+            .map_err(Into::into),
+    );
+    let commit_graph = verify_commit_graph(&repo, &mut progress);
+    let reflogs = Status::from_result(verify_reflogs(&repo, &mut progress, should_interrupt));
+    let index = verify_index(repo, &mut progress);
+
     match output_statistics {
-        Some(OutputFormat::Human) => writeln!(out, "Human output is currently unsupported, use JSON instead")?,
+        Some(OutputFormat::Human) => {
+            fn write_status<T>(
+                out: &mut impl std::io::Write,
+                name: &str,
+                status: &Status<T>,
+                describe: impl FnOnce(&T) -> String,
+            ) -> std::io::Result<()> {
+                match status {
+                    Status::Ok(v) => writeln!(out, "{name}: OK ({})", describe(v)),
+                    Status::Absent => writeln!(out, "{name}: not present"),
+                    Status::Failed(err) => writeln!(out, "{name}: FAILED ({err})"),
+                }
+            }
+            write_status(&mut out, "objects", &objects, |outcome| {
+                format!(
+                    "{} indices, {} loose object databases",
+                    outcome.index_statistics.len(),
+                    outcome.loose_object_stores.len()
+                )
+            })?;
+            write_status(&mut out, "commit-graph", &commit_graph, |outcome| {
+                format!("{} commits", outcome.num_commits)
+            })?;
+            write_status(&mut out, "reflogs", &reflogs, |stats| {
+                format!("{} entries in {} logs", stats.num_entries, stats.num_logs)
+            })?;
+            write_status(&mut out, "index", &index, |path| format!("at '{}'", path.display()))?;
+        }
         #[cfg(feature = "serde")]
         Some(OutputFormat::Json) => {
+            let (index_statistics, loose_object_stores) = match &objects {
+                Status::Ok(outcome) => (Some(&outcome.index_statistics), Some(&outcome.loose_object_stores)),
+                Status::Absent | Status::Failed(_) => (None, None),
+            };
             serde_json::to_writer_pretty(
                 out,
                 &serde_json::json!({
-                    "index_statistics" : outcome.index_statistics,
-                    "loose_object-stores" : outcome.loose_object_stores
+                    "index_statistics" : index_statistics,
+                    "loose_object-stores" : loose_object_stores,
+                    "objects": match &objects {
+                        Status::Ok(_) => Status::Ok(()),
+                        Status::Absent => Status::Absent,
+                        Status::Failed(err) => Status::Failed(err.clone()),
+                    },
+                    "commit_graph": commit_graph,
+                    "reflogs": reflogs,
+                    "index": index,
                 }),
             )?;
         }
         None => {}
     }
+
+    let failures: Vec<_> = [
+        ("objects", objects.failure()),
+        ("commit-graph", commit_graph.failure()),
+        ("reflogs", reflogs.failure()),
+        ("index", index.failure()),
+    ]
+    .into_iter()
+    .filter_map(|(name, err)| err.map(|err| format!("{name}: {err}")))
+    .collect();
+    if !failures.is_empty() {
+        anyhow::bail!("Repository verification failed:\n{}", failures.join("\n"));
+    }
     Ok(())
 }
+
+fn verify_commit_graph(
+    repo: &gix::Repository,
+    progress: &mut impl NestedProgress,
+) -> Status<gix::commitgraph::verify::Outcome> {
+    let info_dir = repo.objects.store_ref().path().join("info");
+    if !info_dir.join("commit-graph").is_file() && !info_dir.join("commit-graphs").is_dir() {
+        return Status::Absent;
+    }
+    let status = Status::from_result(crate::repository::commitgraph::verify(
+        repo.clone(),
+        crate::repository::commitgraph::verify::Context {
+            err: std::io::sink(),
+            out: std::io::sink(),
+            output_statistics: None,
+        },
+    ));
+    if let Status::Ok(outcome) = &status {
+        progress.info(format!("Commit-graph with {} commits OK", outcome.num_commits));
+    }
+    status
+}
+
+fn verify_reflogs(
+    repo: &gix::Repository,
+    progress: &mut impl NestedProgress,
+    should_interrupt: &AtomicBool,
+) -> anyhow::Result<ReflogStatistics> {
+    use anyhow::Context;
+    let mut progress = progress.add_child("verify reflogs");
+    progress.init(None, gix::progress::count("entries"));
+
+    let mut stats = ReflogStatistics::default();
+    let head = repo.find_reference("HEAD").ok();
+    let references = repo.references()?;
+    for reference in head.into_iter().map(Ok).chain(references.all()?) {
+        if should_interrupt.load(std::sync::atomic::Ordering::Relaxed) {
+            anyhow::bail!("Interrupted");
+        }
+        let reference = reference.map_err(|err| anyhow::anyhow!(err))?;
+        let name = reference.name().as_bstr().to_owned();
+        let mut log = reference.log_iter();
+        let Some(entries) = log.all()? else {
+            continue;
+        };
+        stats.num_logs += 1;
+        for entry in entries {
+            let entry = entry.with_context(|| format!("Invalid entry in reflog of '{name}'"))?;
+            let new_oid = entry.new_oid();
+            if !new_oid.is_null() && !repo.has_object(new_oid) {
+                anyhow::bail!("Reflog of '{name}' refers to missing object {new_oid}");
+            }
+            stats.num_entries += 1;
+            progress.inc();
+        }
+    }
+    progress.info(format!(
+        "Reflogs with {} entries in {} logs OK",
+        stats.num_entries, stats.num_logs
+    ));
+    Ok(stats)
+}
+
+fn verify_index(repo: gix::Repository, progress: &mut impl NestedProgress) -> Status<PathBuf> {
+    let res = || -> anyhow::Result<Option<PathBuf>> {
+        let Some(index) = repo.worktree().map(|wt| wt.index()).transpose()? else {
+            return Ok(None);
+        };
+        index.verify_integrity()?;
+        index.verify_entries()?;
+        index.verify_extensions(true, repo.objects.clone())?;
+        Ok(Some(index.path().to_owned()))
+    };
+    match res() {
+        Ok(Some(path)) => {
+            progress.info(format!("Index at '{}' OK", path.display()));
+            Status::Ok(path)
+        }
+        Ok(None) => Status::Absent,
+        Err(err) => Status::Failed(format!("{err:#}")),
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use gix::{bstr::BString, worktree::add::Head};
+
+use crate::OutputFormat;
+
+/// Print the main worktree followed by all linked worktrees along with their `HEAD`, similar to `git worktree list`.
+pub fn list(repo: gix::Repository, format: OutputFormat, mut out: impl std::io::Write) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+
+    let main = repo.main_repo()?;
+    let main_path = main.work_dir().unwrap_or(main.git_dir()).to_owned();
+    if main.is_bare() {
+        writeln!(out, "{}  (bare)", main_path.display())?;
+    } else {
+        write_worktree(&mut out, &main_path, &main, None)?;
+    }
+    for proxy in repo.worktrees()? {
+        let base = proxy.base().unwrap_or_else(|_| proxy.git_dir().to_owned());
+        let is_locked = proxy.is_locked();
+        let is_prunable = !base.is_dir();
+        let wt = proxy.into_repo_with_possibly_inaccessible_worktree()?;
+        let annotation = match (is_locked, is_prunable) {
+            (true, _) => Some("locked"),
+            (false, true) => Some("prunable"),
+            (false, false) => None,
+        };
+        write_worktree(&mut out, &base, &wt, annotation)?;
+    }
+    Ok(())
+}
+
+/// Add a new worktree at `path`, similar to `git worktree add`.
+///
+/// Without `new_branch`, `detach` or `commitish`, a branch named after the last component of `path` is checked out,
+/// and created at `HEAD` if it doesn't exist yet. A `commitish` naming a local branch checks out that branch.
+#[allow(clippy::too_many_arguments)]
+pub fn add(
+    repo: gix::Repository,
+    path: &Path,
+    commitish: Option<&str>,
+    new_branch: Option<&str>,
+    detach: bool,
+    lock: Option<BString>,
+    checkout: bool,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()> {
+    let commit = |spec: Option<&str>| -> anyhow::Result<gix::ObjectId> {
+        Ok(match spec {
+            Some(spec) => {
+                repo.rev_parse_single(spec)?
+                    .object()?
+                    .peel_to_kind(gix::object::Kind::Commit)?
+                    .id
+            }
+            None => repo.head_commit()?.id,
+        })
+    };
+    let local_branch = |name: &str| -> anyhow::Result<Option<gix::refs::FullName>> {
+        let name: gix::refs::FullName = format!("refs/heads/{name}").try_into()?;
+        Ok(repo.try_find_reference(name.as_ref())?.map(|_| name))
+    };
+    let head = match (new_branch, commitish) {
+        (Some(name), spec) => Head::NewBranch {
+            name: format!("refs/heads/{name}").try_into()?,
+            commit: commit(spec)?,
+        },
+        (None, spec) if detach => Head::Detached(commit(spec)?),
+        (None, Some(spec)) => match local_branch(spec)? {
+            Some(name) => Head::Branch(name),
+            None => Head::Detached(commit(Some(spec))?),
+        },
+        (None, None) => {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| anyhow!("Cannot derive a branch name from '{}'", path.display()))?;
+            match local_branch(name)? {
+                Some(name) => Head::Branch(name),
+                None => Head::NewBranch {
+                    name: format!("refs/heads/{name}").try_into()?,
+                    commit: commit(None)?,
+                },
+            }
+        }
+    };
+
+    let description = match &head {
+        Head::Detached(id) => format!("detached HEAD {}", id.to_hex_with_len(7)),
+        Head::Branch(name) => format!("checking out '{}'", name.shorten()),
+        Head::NewBranch { name, .. } => format!("new branch '{}'", name.shorten()),
+    };
+    let proxy = repo.worktree_add(path, head, gix::worktree::add::Options { lock, checkout })?;
+    writeln!(
+        out,
+        "Prepared worktree '{}' at '{}' ({description})",
+        proxy.id(),
+        proxy.base()?.display()
+    )?;
+    Ok(())
+}
+
+/// Lock the worktree identified by `worktree`, its path or id, with an optional `reason`.
+pub fn lock(repo: gix::Repository, worktree: &Path, reason: Option<BString>) -> anyhow::Result<()> {
+    find(&repo, worktree)?.lock(reason.as_ref().map(AsRef::as_ref))?;
+    Ok(())
+}
+
+/// Unlock the worktree identified by `worktree`, its path or id.
+pub fn unlock(repo: gix::Repository, worktree: &Path) -> anyhow::Result<()> {
+    find(&repo, worktree)?.unlock()?;
+    Ok(())
+}
+
+/// Move the worktree identified by `worktree`, its path or id, to `destination`.
+pub fn move_to(repo: gix::Repository, worktree: &Path, destination: &Path) -> anyhow::Result<()> {
+    find(&repo, worktree)?.move_to(destination)?;
+    Ok(())
+}
+
+/// Remove the worktree identified by `worktree`, its path or id. Pass `force` once to remove it despite modifications,
+/// and twice to also remove it if it is locked.
+pub fn remove(repo: gix::Repository, worktree: &Path, force: u8) -> anyhow::Result<()> {
+    find(&repo, worktree)?.remove(gix::worktree::proxy::remove::Options {
+        allow_modifications: force > 0,
+        allow_locked: force > 1,
+    })?;
+    Ok(())
+}
+
+/// Remove the information about worktrees whose checkout doesn't exist anymore, and print them to `out`
+/// with their reason if `verbose` is set.
+pub fn prune(repo: gix::Repository, dry_run: bool, verbose: bool, mut out: impl std::io::Write) -> anyhow::Result<()> {
+    for pruned in repo.worktrees_prune(dry_run)? {
+        if verbose || dry_run {
+            writeln!(out, "Removing worktrees/{}: {}", pruned.id, pruned.reason.as_str())?;
+        }
+    }
+    Ok(())
+}
+
+/// Repair the links between all worktrees and their administrative files, as well as the worktrees at `paths`
+/// which may have been moved manually, and print all repaired files to `out`.
+pub fn repair(repo: gix::Repository, paths: &[PathBuf], mut out: impl std::io::Write) -> anyhow::Result<()> {
+    for repaired in repo.worktrees_repair(paths)? {
+        let what = match repaired.kind {
+            gix::worktree::repair::Kind::DotGitFile => ".git file",
+            gix::worktree::repair::Kind::GitDirFile => "gitdir file",
+        };
+        writeln!(out, "repair: {what} at '{}' fixed", repaired.path.display())?;
+    }
+    Ok(())
+}
+
+fn find<'repo>(repo: &'repo gix::Repository, worktree: &Path) -> anyhow::Result<gix::worktree::Proxy<'repo>> {
+    let path = gix::path::realpath(worktree).ok();
+    repo.worktrees()?
+        .into_iter()
+        .find(|proxy| {
+            gix::path::os_str_into_bstr(worktree.as_os_str()).map_or(false, |id| proxy.id() == id)
+                || path.is_some() && proxy.base().ok().and_then(|base| gix::path::realpath(base).ok()) == path
+        })
+        .with_context(|| format!("'{}' is not a linked worktree", worktree.display()))
+}
+
+fn write_worktree(
+    out: &mut dyn std::io::Write,
+    path: &Path,
+    repo: &gix::Repository,
+    annotation: Option<&str>,
+) -> anyhow::Result<()> {
+    let head = repo.head()?;
+    let id = head
+        .id()
+        .map_or_else(|| "0000000".into(), |id| id.to_hex_with_len(7).to_string());
+    let name = match head.referent_name() {
+        Some(name) => format!("[{}]", name.shorten()),
+        None => "(detached HEAD)".into(),
+    };
+    write!(out, "{}  {id} {name}", path.display())?;
+    if let Some(annotation) = annotation {
+        write!(out, " {annotation}")?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
@@ -85,6 +85,8 @@ pub struct Options {
     /// The modification time for all entries in the archive as seen since UNIX epoch.
     ///
     /// Defaults to the current time. The caller may set this to the commit time if available.
+    /// As all other parts of the archive only depend on its entries and the format, setting it to a fixed value
+    /// produces byte-identical archives for the same input.
     pub modification_time: gix_date::SecondsSinceUnixEpoch,
 }
 
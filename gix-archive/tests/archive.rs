@@ -222,6 +222,30 @@ mod from_tree {
         )
     }
 
+    #[test]
+    #[cfg(all(feature = "tar_gz", feature = "zip"))]
+    fn output_is_reproducible_with_fixed_modification_time() -> gix_testtools::Result {
+        for format in [
+            gix_archive::Format::Tar,
+            gix_archive::Format::TarGz {
+                compression_level: None,
+            },
+            gix_archive::Format::Zip {
+                compression_level: None,
+            },
+        ] {
+            let mut archives = Vec::new();
+            for _ in 0..2 {
+                basic_usage(format, |buf| {
+                    archives.push(buf);
+                    Ok(())
+                })?;
+            }
+            assert_eq!(archives[0], archives[1], "{format:?}: archives are byte-identical");
+        }
+        Ok(())
+    }
+
     fn basic_usage(
         format: gix_archive::Format,
         make_assertion: impl FnOnce(Vec<u8>) -> gix_testtools::Result,
@@ -150,6 +150,18 @@ impl Outcome {
         })
     }
 
+    /// Return the state of the selected attribute `name`, or `None` if `name` isn't part of the selection this instance
+    /// was [initialized with][Self::initialize_with_selection()].
+    ///
+    /// Selected attributes that didn't match are [`StateRef::Unspecified`].
+    pub fn selected_state(&self, name: &str) -> Option<StateRef<'_>> {
+        let (_name, id) = self.selected.iter().find(|(selected, _id)| selected.as_str() == name)?;
+        Some(
+            id.and_then(|id| self.matches_by_id[id.0].r#match.as_ref())
+                .map_or(StateRef::Unspecified, |m| m.to_outer(self).assignment.state),
+        )
+    }
+
     /// Obtain a match by the order of its attribute, if the order exists in our initialized attribute list and there was a match.
     pub fn match_by_id(&self, id: AttributeId) -> Option<crate::search::Match<'_>> {
         self.matches_by_id
@@ -257,6 +257,14 @@ fn given_attributes_are_made_available_in_given_order() -> crate::Result {
         let alt_actual: Vec<_> = alt.iter_selected().map(|m| m.assignment).collect();
         assert_eq!(alt_actual[0], actual[0]);
         assert_eq!(alt_actual[1], actual[2]);
+        for assignment in &actual {
+            assert_eq!(
+                out.selected_state(assignment.name.as_str()),
+                Some(assignment.state),
+                "{rela_path}: states can be obtained by name as well"
+            );
+        }
+        assert_eq!(alt.selected_state("recursive"), None, "it wasn't selected");
     }
     assert_eq!(
         out.iter().count(),
@@ -5,7 +5,7 @@ use std::{
 };
 
 use crate::{
-    file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH},
+    file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH, GENERATION_DATA_OVERFLOW_MASK},
     File,
 };
 
@@ -128,6 +128,23 @@ impl File {
         &self.data[start..][..entry_size]
     }
 
+    /// Returns the corrected commit date offset for the given commit from this file's Generation Data (GDA2) chunk,
+    /// resolving large offsets through the Generation Data Overflow (GDO2) chunk.
+    ///
+    /// Returns `None` if this file has no generation data, or if an overflow entry is out of bounds.
+    pub(crate) fn corrected_commit_date_offset(&self, pos: file::Position) -> Option<u64> {
+        let start = self.generation_data_offset? + usize::try_from(pos.0).expect("32 bit usize at least") * 4;
+        let offset = u32::from_be_bytes(self.data[start..][..4].try_into().unwrap());
+        if offset & GENERATION_DATA_OVERFLOW_MASK == 0 {
+            return Some(offset.into());
+        }
+        let overflow = &self.data[self.generation_data_overflow_range.clone()?];
+        let start = usize::try_from(offset & !GENERATION_DATA_OVERFLOW_MASK).expect("32 bit usize at least") * 8;
+        overflow
+            .get(start..start + 8)
+            .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
     /// Returns the byte slice for this file's entire Extra Edge List (EDGE) chunk.
     pub(crate) fn extra_edges_data(&self) -> Option<&[u8]> {
         Some(&self.data[self.extra_edges_list_range.clone()?])
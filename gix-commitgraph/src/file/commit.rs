@@ -51,9 +51,6 @@ impl<'a> Commit<'a> {
             root_tree_id: gix_hash::oid::from_bytes_unchecked(&bytes[..file.hash_len]),
             parent1: ParentEdge::from_raw(read_u32(&bytes[file.hash_len..][..4])),
             parent2: ParentEdge::from_raw(read_u32(&bytes[file.hash_len + 4..][..4])),
-            // TODO: Add support for corrected commit date offset overflow.
-            //      See https://github.com/git/git/commit/e8b63005c48696a26f976f5f9b0ccaf1983e439d and
-            //          https://github.com/git/git/commit/f90fca638e99a031dce8e3aca72427b2f9b4bb38 for more details and hints at a test.
             generation: read_u32(&bytes[file.hash_len + 8..][..4]) >> 2,
             commit_timestamp: u64::from_be_bytes(bytes[file.hash_len + 8..][..8].try_into().unwrap())
                 & 0x0003_ffff_ffff,
@@ -67,6 +64,18 @@ impl<'a> Commit<'a> {
         self.commit_timestamp
     }
 
+    /// Returns the corrected commit date of this commit, if the commit-graph stores generation data (version 2).
+    ///
+    /// It's the committer timestamp, raised as needed to be greater than the corrected commit date of all parents.
+    /// Thus, all ancestors of a commit have a committer timestamp that is smaller than its corrected commit date,
+    /// which allows to stop traversals early even in the presence of clock skew.
+    /// The value is the number of seconds since 1970-01-01 00:00:00 UTC.
+    pub fn corrected_commit_date(&self) -> Option<u64> {
+        self.file
+            .corrected_commit_date_offset(self.pos)
+            .map(|offset| self.commit_timestamp + offset)
+    }
+
     /// Returns the generation number of this commit.
     ///
     /// Commits without parents have generation number 1. Commits with parents have a generation
@@ -9,7 +9,8 @@ use memmap2::Mmap;
 use crate::{
     file::{
         ChunkId, BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
-        EXTENDED_EDGES_LIST_CHUNK_ID, FAN_LEN, HEADER_LEN, OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+        EXTENDED_EDGES_LIST_CHUNK_ID, FAN_LEN, GENERATION_DATA_CHUNK_ID, GENERATION_DATA_OVERFLOW_CHUNK_ID, HEADER_LEN,
+        OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
     },
     File,
 };
@@ -199,6 +200,38 @@ impl TryFrom<&Path> for File {
 
         let extra_edges_list_range = chunks.usize_offset_by_id(EXTENDED_EDGES_LIST_CHUNK_ID).ok();
 
+        let generation_data = chunks
+            .validated_usize_offset_by_id(GENERATION_DATA_CHUNK_ID, |chunk_range| {
+                let chunk_size = chunk_range.len();
+                if chunk_size % 4 != 0 {
+                    return Err(Error::InvalidChunkSize {
+                        id: GENERATION_DATA_CHUNK_ID,
+                        msg: format!("chunk size {chunk_size} is not a multiple of 4"),
+                    });
+                }
+                Ok((
+                    chunk_range.start,
+                    (chunk_size / 4)
+                        .try_into()
+                        .expect("number of commits in GDA2 chunk to fit in 32 bits"),
+                ))
+            })
+            .ok()
+            .transpose()?;
+        let generation_data_overflow_range = chunks
+            .validated_usize_offset_by_id(GENERATION_DATA_OVERFLOW_CHUNK_ID, |chunk_range| {
+                let chunk_size = chunk_range.len();
+                if chunk_size % 8 != 0 {
+                    return Err(Error::InvalidChunkSize {
+                        id: GENERATION_DATA_OVERFLOW_CHUNK_ID,
+                        msg: format!("chunk size {chunk_size} is not a multiple of 8"),
+                    });
+                }
+                Ok(chunk_range)
+            })
+            .ok()
+            .transpose()?;
+
         let trailer = &data[chunks.highest_offset() as usize..];
         if trailer.len() != object_hash.len_in_bytes() {
             return Err(Error::Trailer(format!(
@@ -232,6 +265,16 @@ impl TryFrom<&Path> for File {
                 chunk2_commits: commit_data_count,
             });
         }
+        if let Some((_, generation_data_count)) = generation_data {
+            if generation_data_count != fan[255] {
+                return Err(Error::CommitCountMismatch {
+                    chunk1_id: OID_FAN_CHUNK_ID,
+                    chunk1_commits: fan[255],
+                    chunk2_id: GENERATION_DATA_CHUNK_ID,
+                    chunk2_commits: generation_data_count,
+                });
+            }
+        }
         Ok(File {
             base_graph_count,
             base_graphs_list_offset,
@@ -239,6 +282,8 @@ impl TryFrom<&Path> for File {
             data,
             extra_edges_list_range,
             fan,
+            generation_data_offset: generation_data.map(|(offset, _count)| offset),
+            generation_data_overflow_range,
             oid_lookup_offset,
             path: path.to_owned(),
             hash_len: object_hash.len_in_bytes(),
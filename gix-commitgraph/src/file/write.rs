@@ -0,0 +1,392 @@
+//! Write commit-graph files.
+use std::{convert::TryInto, io::Write};
+
+use bstr::BString;
+
+use crate::{
+    file::{
+        COMMIT_DATA_CHUNK_ID, EXTENDED_EDGES_LIST_CHUNK_ID, EXTENDED_EDGES_MASK, FAN_LEN, GENERATION_DATA_CHUNK_ID,
+        GENERATION_DATA_OVERFLOW_CHUNK_ID, GENERATION_DATA_OVERFLOW_MASK, HEADER_LEN, LAST_EXTENDED_EDGE_MASK,
+        NO_PARENT, OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+    },
+    File, GENERATION_NUMBER_MAX, MAX_COMMITS,
+};
+
+const BLOOM_FILTER_INDEX_CHUNK_ID: gix_chunk::Id = *b"BIDX";
+const BLOOM_FILTER_DATA_CHUNK_ID: gix_chunk::Id = *b"BDAT";
+
+/// The error returned by [`File::write_from_commits()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Parent {parent} of commit {id} is not part of the commits to write")]
+    MissingParent {
+        id: gix_hash::ObjectId,
+        parent: gix_hash::ObjectId,
+    },
+    #[error("A commit-graph file can hold at most {} commits, got {actual}", MAX_COMMITS)]
+    TooManyCommits { actual: usize },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// A commit along with all information to store about it in a commit-graph file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: gix_hash::ObjectId,
+    /// The id of the commit's root tree.
+    pub tree: gix_hash::ObjectId,
+    /// The ids of all parents of the commit, in order. All of them must be part of the commits to write as well.
+    pub parents: Vec<gix_hash::ObjectId>,
+    /// The committer timestamp in seconds since the unix epoch.
+    pub committer_timestamp: u64,
+    /// The paths of all files that changed compared to the first parent, or compared to the empty tree if there is no parent,
+    /// like `foo/bar.txt`. Leading directories are added automatically.
+    ///
+    /// It's only used if [`Options::changed_paths`] is `true`.
+    pub changed_paths: Vec<BString>,
+}
+
+/// Options for use in [`File::write_from_commits()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// The kind of hash used for all object ids.
+    pub object_hash: gix_hash::Kind,
+    /// If `true`, write bloom filters for the [changed paths](Commit::changed_paths) of each commit, similar to
+    /// `git commit-graph write --changed-paths`.
+    pub changed_paths: bool,
+}
+
+/// The result of [`File::write_from_commits()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The checksum of the file, which is also its trailer.
+    pub checksum: gix_hash::ObjectId,
+    /// The amount of commits that were written after removing duplicates.
+    pub num_commits: u32,
+}
+
+/// Writing
+impl File {
+    /// Write a single commit-graph file without base graphs for `commits` to `out`, similar to what
+    /// `git commit-graph write --no-split` would produce.
+    ///
+    /// `commits` may be in any order and contain duplicates, but have to contain all of their parents.
+    /// Generation numbers and corrected commit dates are always written.
+    pub fn write_from_commits(
+        mut commits: Vec<Commit>,
+        out: &mut dyn std::io::Write,
+        Options {
+            object_hash,
+            changed_paths,
+        }: Options,
+    ) -> Result<Outcome, Error> {
+        commits.sort_by_key(|commit| commit.id);
+        commits.dedup_by(|a, b| a.id == b.id);
+        let num_commits: u32 = commits
+            .len()
+            .try_into()
+            .ok()
+            .filter(|num| *num <= MAX_COMMITS)
+            .ok_or(Error::TooManyCommits { actual: commits.len() })?;
+
+        let parents = commits
+            .iter()
+            .map(|commit| {
+                commit
+                    .parents
+                    .iter()
+                    .map(|parent| {
+                        commits
+                            .binary_search_by(|c| c.id.cmp(parent))
+                            .map(|pos| pos as u32)
+                            .map_err(|_| Error::MissingParent {
+                                id: commit.id,
+                                parent: *parent,
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let generations = generations(&commits, &parents);
+
+        let mut extra_edges = Vec::new();
+        let mut commit_data = Vec::with_capacity(commits.len() * (object_hash.len_in_bytes() + 16));
+        for ((commit, parents), (level, _)) in commits.iter().zip(&parents).zip(&generations) {
+            commit_data.extend_from_slice(commit.tree.as_slice());
+            let (parent1, parent2) = match parents.as_slice() {
+                [] => (NO_PARENT, NO_PARENT),
+                [p1] => (*p1, NO_PARENT),
+                [p1, p2] => (*p1, *p2),
+                [p1, rest @ ..] => {
+                    let edge_index = extra_edges.len() as u32;
+                    extra_edges.extend_from_slice(rest);
+                    *extra_edges.last_mut().expect("at least two parents") |= LAST_EXTENDED_EDGE_MASK;
+                    (*p1, EXTENDED_EDGES_MASK | edge_index)
+                }
+            };
+            commit_data.extend_from_slice(&parent1.to_be_bytes());
+            commit_data.extend_from_slice(&parent2.to_be_bytes());
+            let time_high_bits = ((commit.committer_timestamp >> 32) & 0x3) as u32;
+            commit_data.extend_from_slice(&((level << 2) | time_high_bits).to_be_bytes());
+            commit_data.extend_from_slice(&(commit.committer_timestamp as u32).to_be_bytes());
+        }
+
+        let mut generation_data_overflow = Vec::new();
+        let generation_data: Vec<u32> = commits
+            .iter()
+            .zip(&generations)
+            .map(|(commit, (_, corrected_date))| {
+                let offset = corrected_date - commit.committer_timestamp;
+                if offset > u64::from(!GENERATION_DATA_OVERFLOW_MASK) {
+                    generation_data_overflow.push(offset);
+                    GENERATION_DATA_OVERFLOW_MASK | (generation_data_overflow.len() as u32 - 1)
+                } else {
+                    offset as u32
+                }
+            })
+            .collect();
+
+        let bloom_filters: Option<Vec<Vec<u8>>> =
+            changed_paths.then(|| commits.iter().map(|c| bloom::filter(&c.changed_paths)).collect());
+
+        let mut cf = gix_chunk::file::Index::for_writing();
+        cf.plan_chunk(OID_FAN_CHUNK_ID, (FAN_LEN * 4) as u64);
+        cf.plan_chunk(OID_LOOKUP_CHUNK_ID, (commits.len() * object_hash.len_in_bytes()) as u64);
+        cf.plan_chunk(COMMIT_DATA_CHUNK_ID, commit_data.len() as u64);
+        cf.plan_chunk(GENERATION_DATA_CHUNK_ID, (generation_data.len() * 4) as u64);
+        if !generation_data_overflow.is_empty() {
+            cf.plan_chunk(
+                GENERATION_DATA_OVERFLOW_CHUNK_ID,
+                (generation_data_overflow.len() * 8) as u64,
+            );
+        }
+        if !extra_edges.is_empty() {
+            cf.plan_chunk(EXTENDED_EDGES_LIST_CHUNK_ID, (extra_edges.len() * 4) as u64);
+        }
+        if let Some(filters) = &bloom_filters {
+            cf.plan_chunk(BLOOM_FILTER_INDEX_CHUNK_ID, (filters.len() * 4) as u64);
+            cf.plan_chunk(
+                BLOOM_FILTER_DATA_CHUNK_ID,
+                (bloom::HEADER_LEN + filters.iter().map(Vec::len).sum::<usize>()) as u64,
+            );
+        }
+
+        let mut out = gix_features::hash::Write::new(out, object_hash);
+        out.write_all(SIGNATURE)?;
+        out.write_all(&[1 /* version */, object_hash as u8])?;
+        out.write_all(&[
+            cf.num_chunks().try_into().expect("BUG: wrote more than 256 chunks"),
+            0, /* base graphs */
+        ])?;
+
+        let mut chunk_write = cf.into_write(&mut out, HEADER_LEN)?;
+        while let Some(chunk_to_write) = chunk_write.next_chunk() {
+            match chunk_to_write {
+                OID_FAN_CHUNK_ID => {
+                    let mut fan = [0u32; FAN_LEN];
+                    for commit in &commits {
+                        fan[commit.id.first_byte() as usize] += 1;
+                    }
+                    let mut total = 0;
+                    for count in fan {
+                        total += count;
+                        chunk_write.write_all(&total.to_be_bytes())?;
+                    }
+                }
+                OID_LOOKUP_CHUNK_ID => {
+                    for commit in &commits {
+                        chunk_write.write_all(commit.id.as_slice())?;
+                    }
+                }
+                COMMIT_DATA_CHUNK_ID => chunk_write.write_all(&commit_data)?,
+                GENERATION_DATA_CHUNK_ID => write_u32s(&mut chunk_write, &generation_data)?,
+                GENERATION_DATA_OVERFLOW_CHUNK_ID => {
+                    for offset in &generation_data_overflow {
+                        chunk_write.write_all(&offset.to_be_bytes())?;
+                    }
+                }
+                EXTENDED_EDGES_LIST_CHUNK_ID => write_u32s(&mut chunk_write, &extra_edges)?,
+                BLOOM_FILTER_INDEX_CHUNK_ID => {
+                    let mut end = 0;
+                    for filter in bloom_filters.as_ref().expect("planned") {
+                        end += filter.len() as u32;
+                        chunk_write.write_all(&end.to_be_bytes())?;
+                    }
+                }
+                BLOOM_FILTER_DATA_CHUNK_ID => {
+                    write_u32s(
+                        &mut chunk_write,
+                        &[bloom::HASH_VERSION, bloom::NUM_HASHES, bloom::BITS_PER_ENTRY],
+                    )?;
+                    for filter in bloom_filters.as_ref().expect("planned") {
+                        chunk_write.write_all(filter)?;
+                    }
+                }
+                unknown => unreachable!("BUG: forgot to implement chunk {:?}", std::str::from_utf8(&unknown)),
+            }
+        }
+
+        let checksum: gix_hash::ObjectId = out.hash.digest().into();
+        out.inner.write_all(checksum.as_slice())?;
+        Ok(Outcome { checksum, num_commits })
+    }
+}
+
+fn write_u32s(out: &mut dyn Write, values: &[u32]) -> std::io::Result<()> {
+    for value in values {
+        out.write_all(&value.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// Compute the topological level and the corrected commit date of each of `commits`, whose parents are given
+/// as positions into `commits` in `parents`.
+fn generations(commits: &[Commit], parents: &[Vec<u32>]) -> Vec<(u32, u64)> {
+    #[derive(Clone, Copy)]
+    enum State {
+        Unvisited,
+        InProgress,
+        Done(u32, u64),
+    }
+    let mut states = vec![State::Unvisited; commits.len()];
+    let mut stack = Vec::new();
+    for start in 0..commits.len() {
+        stack.push(start);
+        while let Some(&pos) = stack.last() {
+            if let State::Done(..) = states[pos] {
+                stack.pop();
+                continue;
+            }
+            states[pos] = State::InProgress;
+            let unvisited_parent = parents[pos]
+                .iter()
+                .map(|parent| *parent as usize)
+                .find(|parent| matches!(states[*parent], State::Unvisited));
+            if let Some(parent) = unvisited_parent {
+                stack.push(parent);
+                continue;
+            }
+
+            let mut level = 0;
+            let mut corrected_date = commits[pos].committer_timestamp;
+            for parent in &parents[pos] {
+                // Cycles can only occur with invalid input, and parents that are in progress are ignored to handle them.
+                if let State::Done(parent_level, parent_date) = states[*parent as usize] {
+                    level = level.max(parent_level);
+                    corrected_date = corrected_date.max(parent_date + 1);
+                }
+            }
+            states[pos] = State::Done((level + 1).min(GENERATION_NUMBER_MAX), corrected_date);
+            stack.pop();
+        }
+    }
+    states
+        .into_iter()
+        .map(|state| match state {
+            State::Done(level, corrected_date) => (level, corrected_date),
+            State::Unvisited | State::InProgress => unreachable!("BUG: all commits are visited"),
+        })
+        .collect()
+}
+
+/// Bloom filters for changed paths, compatible to version 1 of git's implementation.
+mod bloom {
+    use std::collections::BTreeSet;
+
+    use bstr::{BStr, BString, ByteSlice};
+
+    pub const HEADER_LEN: usize = 12;
+    pub const HASH_VERSION: u32 = 1;
+    pub const NUM_HASHES: u32 = 7;
+    pub const BITS_PER_ENTRY: u32 = 10;
+    const MAX_CHANGED_PATHS: usize = 512;
+
+    /// Return the filter data for all `changed_paths` and their leading directories.
+    pub fn filter(changed_paths: &[BString]) -> Vec<u8> {
+        let mut paths = BTreeSet::<&BStr>::new();
+        for path in changed_paths {
+            let mut path = path.as_bstr();
+            paths.insert(path);
+            while let Some(pos) = path.rfind_byte(b'/') {
+                path = path[..pos].as_bstr();
+                if !paths.insert(path) {
+                    break;
+                }
+            }
+            if paths.len() > MAX_CHANGED_PATHS {
+                // A filter with all bits set matches everything, which means it has to be ignored.
+                return vec![0xff];
+            }
+        }
+
+        let len = ((paths.len() * BITS_PER_ENTRY as usize + 7) / 8).max(1);
+        let mut data = vec![0u8; len];
+        let num_bits = (len * 8) as u64;
+        for path in paths {
+            let hash0 = murmur3_seeded(0x293a_e76f, path);
+            let hash1 = murmur3_seeded(0x7e64_6e2c, path);
+            for i in 0..NUM_HASHES {
+                let bit = u64::from(hash0.wrapping_add(i.wrapping_mul(hash1))) % num_bits;
+                data[(bit / 8) as usize] |= 1 << (bit % 8);
+            }
+        }
+        data
+    }
+
+    /// Version 1 of git's murmur3 implementation, which sign-extends bytes with the high bit set.
+    pub fn murmur3_seeded(seed: u32, data: &[u8]) -> u32 {
+        const C1: u32 = 0xcc9e_2d51;
+        const C2: u32 = 0x1b87_3593;
+
+        fn scramble(k: u32) -> u32 {
+            k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2)
+        }
+        fn sign_extended(byte: u8) -> u32 {
+            byte as i8 as u32
+        }
+
+        let mut hash = seed;
+        let mut blocks = data.chunks_exact(4);
+        for block in &mut blocks {
+            let k = block
+                .iter()
+                .enumerate()
+                .fold(0, |k, (i, byte)| k | sign_extended(*byte) << (8 * i));
+            hash ^= scramble(k);
+            hash = hash.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+        }
+
+        let tail = blocks.remainder();
+        if !tail.is_empty() {
+            let k = tail
+                .iter()
+                .enumerate()
+                .fold(0, |k, (i, byte)| k ^ sign_extended(*byte) << (8 * i));
+            hash ^= scramble(k);
+        }
+
+        hash ^= data.len() as u32;
+        hash ^= hash >> 16;
+        hash = hash.wrapping_mul(0x85eb_ca6b);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(0xc2b2_ae35);
+        hash ^ (hash >> 16)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::murmur3_seeded;
+
+        #[test]
+        fn murmur3_matches_gits_test_vectors() {
+            assert_eq!(murmur3_seeded(0, b""), 0x0000_0000);
+            assert_eq!(murmur3_seeded(0, b"Hello world!"), 0x627b_0c2c);
+            assert_eq!(
+                murmur3_seeded(0, b"The quick brown fox jumps over the lazy dog"),
+                0x2e4f_f723
+            );
+        }
+    }
+}
@@ -85,7 +85,10 @@ impl Graph {
     }
 
     /// Create a new commit graph from a list of `files`.
-    pub fn new(files: Vec<File>) -> Result<Self, Error> {
+    ///
+    /// Note that corrected commit dates are only made available if all `files` provide them, as git
+    /// does the same to avoid mixing generation number versions within a chain.
+    pub fn new(mut files: Vec<File>) -> Result<Self, Error> {
         let num_commits: u64 = files.iter().map(|f| u64::from(f.num_commits())).sum();
         if num_commits > u64::from(MAX_COMMITS) {
             return Err(Error::TooManyCommits(num_commits));
@@ -104,6 +107,13 @@ impl Graph {
             }
         }
 
+        if files.iter().any(|f| f.generation_data_offset.is_none()) {
+            for file in &mut files {
+                file.generation_data_offset = None;
+                file.generation_data_overflow_range = None;
+            }
+        }
+
         Ok(Self { files })
     }
 }
@@ -69,6 +69,29 @@ fn generation_numbers_overflow_is_handled_in_chained_graph() {
     }
 }
 
+#[test]
+fn corrected_commit_dates_overflow_is_handled_in_chained_graph() {
+    let names = ["extra", "old-2", "future-2", "old-1", "future-1"];
+    let (cg, refs) = graph_and_expected("generation_number_overflow.sh", &names);
+    let future_date = 4147483646;
+    for (name, expected) in names.iter().zip((future_date..=future_date + 4).rev()) {
+        assert_eq!(
+            cg.commit_by_id(refs[*name].id)
+                .expect("present")
+                .corrected_commit_date(),
+            Some(expected),
+            "{name}: each commit is corrected to be one past its parent, even if the offset needs the overflow chunk"
+        );
+    }
+}
+
+#[test]
+fn corrected_commit_dates_are_unavailable_in_generation_v1() {
+    let (cg, refs) = graph_and_expected_named("single_commit_huge_dates.sh", "v1", &["HEAD"]);
+    let actual = cg.commit_by_id(refs["HEAD"].id).expect("present");
+    assert_eq!(actual.corrected_commit_date(), None);
+}
+
 #[test]
 fn octupus_merges() {
     let (cg, refs) = graph_and_expected(
@@ -0,0 +1,39 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config commitGraph.generationVersion 2
+
+git checkout -q --orphan root
+mkdir -p dir/subdir
+echo a > a
+echo b > dir/b
+echo c > dir/subdir/c
+git add .
+git commit -q -m root
+
+git checkout -q -b side root
+echo c2 > dir/subdir/c
+git commit -q -am side
+
+git checkout -q -b third root
+echo b2 > dir/b
+git commit -q -am third
+
+git checkout -q -b other root
+echo d > d
+git add d
+git commit -q -m other
+
+git checkout -q -b octopus other
+git merge -q -m octopus --no-ff side third >/dev/null
+
+git checkout -q -b many-files octopus
+mkdir many
+for i in $(seq 600); do
+  echo $i > many/$i
+done
+git add many
+git commit -q -m many-files
+
+git commit-graph write --no-progress --reachable --changed-paths
@@ -0,0 +1,129 @@
+use std::{path::Path, process::Command};
+
+use gix_commitgraph::{file::write, File, Graph};
+use gix_testtools::{bstr::BString, scripted_fixture_read_only, tempfile, Result};
+
+fn commits_in(
+    graph: &Graph,
+    changed_paths: impl Fn(&gix_hash::oid, Option<&gix_hash::oid>) -> Vec<BString>,
+) -> Vec<write::Commit> {
+    graph
+        .iter_commits()
+        .map(|commit| {
+            let parents: Vec<_> = commit
+                .iter_parents()
+                .map(|pos| graph.id_at(pos.expect("valid parent")).to_owned())
+                .collect();
+            write::Commit {
+                id: commit.id().to_owned(),
+                tree: commit.root_tree_id().to_owned(),
+                changed_paths: changed_paths(commit.id(), parents.first().map(AsRef::as_ref)),
+                parents,
+                committer_timestamp: commit.committer_timestamp(),
+            }
+        })
+        .collect()
+}
+
+fn changed_paths_with_git(
+    repo_dir: &Path,
+    commit: &gix_hash::oid,
+    first_parent: Option<&gix_hash::oid>,
+) -> Vec<BString> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_dir)
+        .args(["diff-tree", "-r", "-z", "--name-only", "--no-commit-id", "--root"]);
+    if let Some(parent) = first_parent {
+        cmd.arg(parent.to_string());
+    }
+    let output = cmd.arg(commit.to_string()).output().expect("git can be executed");
+    assert!(output.status.success(), "git diff-tree failed");
+    output
+        .stdout
+        .split(|b| *b == 0)
+        .filter(|path| !path.is_empty())
+        .map(Into::into)
+        .collect()
+}
+
+#[test]
+fn changed_paths_and_extra_edges_are_written_like_git_does() -> Result {
+    let repo_dir = scripted_fixture_read_only("changed_paths.sh")?;
+    let graph_path = repo_dir.join(".git").join("objects").join("info").join("commit-graph");
+    let graph = Graph::from_file(&graph_path)?;
+
+    let mut commits = commits_in(&graph, |id, parent| changed_paths_with_git(&repo_dir, id, parent));
+    commits.reverse();
+    commits.push(commits[0].clone());
+
+    let mut buf = Vec::new();
+    let outcome = File::write_from_commits(
+        commits,
+        &mut buf,
+        write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+            changed_paths: true,
+        },
+    )?;
+    assert_eq!(outcome.num_commits, 6, "duplicates are removed");
+    assert_eq!(
+        buf,
+        std::fs::read(&graph_path)?,
+        "the written file is the same as the one written by git"
+    );
+    Ok(())
+}
+
+#[test]
+fn generation_data_overflow_and_verification() -> Result {
+    let repo_dir = scripted_fixture_read_only("generation_number_overflow.sh")?;
+    let expected = Graph::from_info_dir(&repo_dir.join(".git").join("objects").join("info"))?;
+
+    let tmp = tempfile::tempdir()?;
+    let graph_path = tmp.path().join("commit-graph");
+    let mut file = std::fs::File::create(&graph_path)?;
+    let outcome = File::write_from_commits(
+        commits_in(&expected, |_, _| Vec::new()),
+        &mut file,
+        write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+            changed_paths: false,
+        },
+    )?;
+    drop(file);
+    assert_eq!(outcome.num_commits, expected.num_commits());
+
+    let actual = Graph::from_file(&graph_path)?;
+    actual.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    assert_eq!(File::at(&graph_path)?.checksum(), outcome.checksum);
+    for expected in expected.iter_commits() {
+        let commit = actual.commit_by_id(expected.id()).expect("all commits were written");
+        assert_eq!(commit.generation(), expected.generation());
+        assert_eq!(commit.corrected_commit_date(), expected.corrected_commit_date());
+        assert_eq!(commit.committer_timestamp(), expected.committer_timestamp());
+        assert_eq!(commit.root_tree_id(), expected.root_tree_id());
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_parents_are_an_error() {
+    let id = gix_hash::ObjectId::from_hex(b"0000000000000000000000000000000000000001").expect("valid");
+    let parent = gix_hash::ObjectId::from_hex(b"0000000000000000000000000000000000000002").expect("valid");
+    let err = File::write_from_commits(
+        vec![write::Commit {
+            id,
+            tree: gix_hash::ObjectId::empty_tree(gix_hash::Kind::Sha1),
+            parents: vec![parent],
+            committer_timestamp: 0,
+            changed_paths: Vec::new(),
+        }],
+        &mut Vec::new(),
+        write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+            changed_paths: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, write::Error::MissingParent { id: a, parent: b } if a == id && b == parent));
+}
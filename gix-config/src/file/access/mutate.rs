@@ -4,7 +4,7 @@ use bstr::BStr;
 use gix_features::threading::OwnShared;
 
 use crate::{
-    file::{self, rename_section, write::ends_with_newline, MetadataFilter, SectionBodyIdsLut, SectionId, SectionMut},
+    file::{self, rename_section, write::ends_with_newline, MetadataFilter, SectionId, SectionMut},
     lookup,
     parse::{section, Event, FrontMatterEvents},
     File,
@@ -255,29 +255,7 @@ impl<'event> File<'event> {
         self.section_order
             .remove(self.section_order.iter().position(|v| *v == id)?);
         let section = self.sections.remove(&id)?;
-        let lut = self
-            .section_lookup_tree
-            .get_mut(&section.header.name)
-            .expect("lookup cache still has name to be deleted");
-        // NOTE: this leaves empty lists in the data structure which our code now has to deal with.
-        for entry in lut {
-            match section.header.subsection_name.as_deref() {
-                Some(subsection_name) => {
-                    if let SectionBodyIdsLut::NonTerminal(map) = entry {
-                        if let Some(ids) = map.get_mut(subsection_name) {
-                            ids.remove(ids.iter().position(|v| *v == id).expect("present"));
-                            break;
-                        }
-                    }
-                }
-                None => {
-                    if let SectionBodyIdsLut::Terminal(ids) = entry {
-                        ids.remove(ids.iter().position(|v| *v == id).expect("present"));
-                        break;
-                    }
-                }
-            }
-        }
+        self.remove_section_id_from_lookup(id, &section.header);
         Some(section)
     }
 
@@ -336,8 +314,7 @@ impl<'event> File<'event> {
             .section_ids_by_name_and_subname(name.as_ref(), subsection_name.into())?
             .next_back()
             .expect("list of sections were empty, which violates invariant");
-        let section = self.sections.get_mut(&id).expect("known section-id");
-        section.header = section::Header::new(new_name, new_subsection_name)?;
+        self.rename_section_internal(id, section::Header::new(new_name, new_subsection_name)?);
         Ok(())
     }
 
@@ -359,8 +336,7 @@ impl<'event> File<'event> {
             .rev()
             .find(|id| filter(self.sections.get(id).expect("each id has a section").meta()))
             .ok_or(rename_section::Error::Lookup(lookup::existing::Error::KeyMissing))?;
-        let section = self.sections.get_mut(&id).expect("known section-id");
-        section.header = section::Header::new(new_name, new_subsection_name)?;
+        self.rename_section_internal(id, section::Header::new(new_name, new_subsection_name)?);
         Ok(())
     }
 
@@ -53,6 +53,81 @@ impl<'event> File<'event> {
         new_section_id
     }
 
+    /// Give the section with `id` the new `header`, and move it in our lookup structures accordingly while keeping
+    /// sections of the same name in the order they appear in the file.
+    pub(crate) fn rename_section_internal(&mut self, id: SectionId, header: section::Header<'event>) {
+        let section = self.sections.get_mut(&id).expect("known section-id");
+        let previous_header = std::mem::replace(&mut section.header, header.clone());
+        self.remove_section_id_from_lookup(id, &previous_header);
+
+        let lookup = self.section_lookup_tree.entry(header.name.clone()).or_default();
+        let ids = match header.subsection_name {
+            Some(subsection_name) => {
+                let pos = match lookup
+                    .iter()
+                    .position(|node| matches!(node, SectionBodyIdsLut::NonTerminal(_)))
+                {
+                    Some(pos) => pos,
+                    None => {
+                        lookup.push(SectionBodyIdsLut::NonTerminal(Default::default()));
+                        lookup.len() - 1
+                    }
+                };
+                match &mut lookup[pos] {
+                    SectionBodyIdsLut::NonTerminal(subsections) => subsections.entry(subsection_name).or_default(),
+                    SectionBodyIdsLut::Terminal(_) => unreachable!("we found or created a non-terminal"),
+                }
+            }
+            None => {
+                let pos = match lookup
+                    .iter()
+                    .position(|node| matches!(node, SectionBodyIdsLut::Terminal(_)))
+                {
+                    Some(pos) => pos,
+                    None => {
+                        lookup.push(SectionBodyIdsLut::Terminal(Vec::new()));
+                        lookup.len() - 1
+                    }
+                };
+                match &mut lookup[pos] {
+                    SectionBodyIdsLut::Terminal(ids) => ids,
+                    SectionBodyIdsLut::NonTerminal(_) => unreachable!("we found or created a terminal"),
+                }
+            }
+        };
+        ids.push(id);
+        let section_order = &self.section_order;
+        ids.sort_by_key(|id| section_order.iter().position(|v| v == id));
+    }
+
+    /// Remove `id` from our lookup structures, assuming it is stored under `header`.
+    ///
+    /// Note that this leaves empty lists in the data structure which our code has to deal with.
+    pub(crate) fn remove_section_id_from_lookup(&mut self, id: SectionId, header: &section::Header<'event>) {
+        let lut = self
+            .section_lookup_tree
+            .get_mut(&header.name)
+            .expect("lookup cache still has name to be deleted");
+        for entry in lut {
+            match header.subsection_name.as_deref() {
+                Some(subsection_name) => {
+                    if let SectionBodyIdsLut::NonTerminal(map) = entry {
+                        if let Some(ids) = map.get_mut(subsection_name) {
+                            ids.remove(ids.iter().position(|v| *v == id).expect("present"));
+                            break;
+                        }
+                    }
+                }
+                None => {
+                    if let SectionBodyIdsLut::Terminal(ids) = entry {
+                        ids.remove(ids.iter().position(|v| *v == id).expect("present"));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
     /// Inserts `section` after the section that comes `before` it, and maintains correct ordering in all of our lookup structures.
     pub(crate) fn insert_section_after(&mut self, mut section: file::Section<'event>, before: SectionId) -> SectionId {
         let lookup_section_order = {
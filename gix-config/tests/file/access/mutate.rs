@@ -66,6 +66,25 @@ mod rename_section {
             ))
         ));
     }
+
+    #[test]
+    fn renamed_sections_can_be_found_by_their_new_name_only() -> crate::Result {
+        let mut file = gix_config::File::try_from("[a \"one\"]\nx = 1\n[b]\ny = 2\n[a \"one\"]\nx = 3\n[c]\nz = 4")?;
+        file.rename_section("a", Some("one".into()), "c", None)?;
+        file.rename_section("a", Some("one".into()), "c", None)?;
+        assert!(
+            file.section("a", Some("one".into())).is_err(),
+            "all sections were renamed"
+        );
+        assert_eq!(
+            file.raw_values("c", None, "x")?,
+            vec![Cow::<bstr::BStr>::Borrowed("1".into()), Cow::Borrowed("3".into())],
+            "renamed sections are found in file order"
+        );
+        assert_eq!(file.raw_value("c", None, "z")?.as_ref(), "4");
+        assert_eq!(file.to_string(), "[c]\nx = 1\n[b]\ny = 2\n[c]\nx = 3\n[c]\nz = 4\n");
+        Ok(())
+    }
 }
 mod set_meta {
     use gix_config::file;
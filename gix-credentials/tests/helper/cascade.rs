@@ -130,7 +130,7 @@ mod invoke {
                 Action::get_for_url("ssh://git@host/repo"),
                 gix_prompt::Options {
                     mode: gix_prompt::Mode::Disable,
-                    askpass: None,
+                    ..Default::default()
                 },
             )
             .unwrap()
@@ -138,6 +138,30 @@ mod invoke {
         assert_eq!(actual.identity, identity("user", ""));
     }
 
+    #[test]
+    fn prompts_can_be_answered_by_a_provider() {
+        let actual = Cascade::default()
+            .invoke(
+                Action::get_for_url("https://example.com"),
+                gix_prompt::Options {
+                    provider: Some(std::sync::Arc::new(|prompt: &str, mode| {
+                        Ok(match mode {
+                            gix_prompt::Mode::Visible => {
+                                assert_eq!(prompt, "Username for https://example.com: ");
+                                "user"
+                            }
+                            _ => "pass",
+                        }
+                        .to_owned())
+                    })),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(actual.identity, identity("user", "pass"));
+    }
+
     fn action_get() -> Action {
         Action::get_for_url("does/not/matter")
     }
@@ -155,7 +179,7 @@ mod invoke {
             action,
             gix_prompt::Options {
                 mode: gix_prompt::Mode::Disable,
-                askpass: None,
+                ..Default::default()
             },
         )
     }
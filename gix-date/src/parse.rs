@@ -92,11 +92,18 @@ mod relative {
 
     use crate::parse::Error;
 
+    /// Parse `2 weeks ago` as well as `2.weeks.ago` or `2.weeks`, as git always looks into the past here.
     fn parse_inner(input: &str) -> Option<Duration> {
-        let mut split = input.split_whitespace();
+        let mut split = input
+            .split(|c: char| c.is_whitespace() || c == '.')
+            .filter(|token| !token.is_empty());
         let multiplier = i64::from_str(split.next()?).ok()?;
         let period = split.next()?;
-        if split.next()? != "ago" {
+        match split.next() {
+            None | Some("ago") => {}
+            Some(_) => return None,
+        }
+        if split.next().is_some() {
             return None;
         }
         duration(period, multiplier)
@@ -139,5 +146,12 @@ mod relative {
         fn two_weeks_ago() {
             assert_eq!(parse_inner("2 weeks ago"), Some(Duration::weeks(2)));
         }
+
+        #[test]
+        fn dot_separated_and_without_ago() {
+            assert_eq!(parse_inner("2.days.ago"), Some(Duration::days(2)));
+            assert_eq!(parse_inner("2.weeks"), Some(Duration::weeks(2)));
+            assert_eq!(parse_inner("2 weeks ago later"), None);
+        }
     }
 }
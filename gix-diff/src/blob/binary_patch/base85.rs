@@ -0,0 +1,72 @@
+//! The base85 encoding used by `git` in binary patches, which differs from other base85 variants in its alphabet.
+
+/// The alphabet of the encoding, in order of the values of its digits.
+const ALPHABET: &[u8; 85] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// The error returned by [`decode()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Invalid base85 character {byte:?} at position {position}")]
+    InvalidCharacter { byte: char, position: usize },
+    #[error("The base85 input must be a multiple of 5 characters, got {len}")]
+    InvalidLength { len: usize },
+    #[error("The base85 group at position {position} overflows 32 bits")]
+    Overflow { position: usize },
+}
+
+/// Return the amount of characters needed to encode `len` bytes.
+pub fn encoded_len(len: usize) -> usize {
+    (len + 3) / 4 * 5
+}
+
+/// Append the encoding of `data` to `out`, with each group of 4 bytes turning into 5 characters.
+///
+/// If the length of `data` isn't a multiple of 4, it's padded with null bytes, so the original length
+/// has to be known to decode it.
+pub fn encode(data: &[u8], out: &mut Vec<u8>) {
+    for chunk in data.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut acc = u32::from_be_bytes(group);
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = ALPHABET[(acc % 85) as usize];
+            acc /= 85;
+        }
+        out.extend_from_slice(&digits);
+    }
+}
+
+/// Append the `len` bytes encoded in `input` to `out`, where `input` may encode up to 3 more bytes of padding than `len`.
+pub fn decode(input: &[u8], len: usize, out: &mut Vec<u8>) -> Result<(), Error> {
+    if input.len() % 5 != 0 || encoded_len(len) != input.len() {
+        return Err(Error::InvalidLength { len: input.len() });
+    }
+    let mut remaining = len;
+    for (group_idx, group) in input.chunks(5).enumerate() {
+        let mut acc = 0u32;
+        for (idx, &byte) in group.iter().enumerate() {
+            let position = group_idx * 5 + idx;
+            let value = digit_value(byte).ok_or(Error::InvalidCharacter {
+                byte: byte as char,
+                position,
+            })?;
+            acc = acc
+                .checked_mul(85)
+                .and_then(|acc| acc.checked_add(value.into()))
+                .ok_or(Error::Overflow {
+                    position: group_idx * 5,
+                })?;
+        }
+        let bytes = acc.to_be_bytes();
+        let take = remaining.min(4);
+        out.extend_from_slice(&bytes[..take]);
+        remaining -= take;
+    }
+    Ok(())
+}
+
+fn digit_value(byte: u8) -> Option<u8> {
+    ALPHABET.iter().position(|b| *b == byte).map(|pos| pos as u8)
+}
@@ -0,0 +1,192 @@
+//! The delta format used in binary patches, which is the same as the one used for objects in packs.
+//!
+//! A delta starts with the size of the base and the size of the result, each encoded as variable-length integer,
+//! followed by instructions to either copy a range of the base or to insert literal bytes.
+use std::collections::HashMap;
+
+/// The amount of bytes of the base that are indexed at once to find matches.
+const BLOCK_SIZE: usize = 16;
+/// The maximum amount of bytes to copy with a single instruction, as older versions of `git` can't handle more.
+const MAX_COPY_SIZE: usize = 0x10000;
+/// The maximum amount of bytes to insert with a single instruction.
+const MAX_INSERT_SIZE: usize = 0x7f;
+
+/// The error returned by [`apply()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The delta header is truncated")]
+    TruncatedHeader,
+    #[error("The delta expects a base of {expected} bytes, but it has {actual} bytes")]
+    BaseSizeMismatch { expected: u64, actual: usize },
+    #[error("The delta instruction at offset {offset} is invalid or truncated")]
+    InvalidInstruction { offset: usize },
+    #[error("The delta instruction at offset {offset} copies bytes outside of the base")]
+    CopyOutOfBounds { offset: usize },
+    #[error("The delta should produce {expected} bytes, but produced {actual} bytes")]
+    ResultSizeMismatch { expected: u64, actual: usize },
+}
+
+/// Return a delta that produces `target` when applied to `base`.
+///
+/// Matches are found by indexing `base` in blocks of 16 bytes, so the delta may be larger than needed for
+/// small or very different inputs. Callers should compare its size to the size of `target` to decide which one to use.
+pub fn create(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_size(base.len() as u64, &mut out);
+    write_size(target.len() as u64, &mut out);
+
+    let mut blocks = HashMap::<&[u8], usize>::new();
+    for (idx, block) in base.chunks_exact(BLOCK_SIZE).enumerate().rev() {
+        blocks.insert(block, idx * BLOCK_SIZE);
+    }
+
+    let mut insert_start = 0;
+    let mut pos = 0;
+    while pos + BLOCK_SIZE <= target.len() {
+        let Some(&base_start) = blocks.get(&target[pos..pos + BLOCK_SIZE]) else {
+            pos += 1;
+            continue;
+        };
+        // Extend the match backwards into bytes that would otherwise be inserted, and forwards as far as possible.
+        let backwards = base[..base_start]
+            .iter()
+            .rev()
+            .zip(target[insert_start..pos].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let forwards = base[base_start..]
+            .iter()
+            .zip(&target[pos..])
+            .take_while(|(a, b)| a == b)
+            .count();
+        write_inserts(&target[insert_start..pos - backwards], &mut out);
+        write_copies(base_start - backwards, backwards + forwards, &mut out);
+        pos += forwards;
+        insert_start = pos;
+    }
+    write_inserts(&target[insert_start..], &mut out);
+    out
+}
+
+/// Apply `delta` to `base` and append the result to `out`.
+pub fn apply(base: &[u8], delta: &[u8], out: &mut Vec<u8>) -> Result<(), Error> {
+    let mut cursor = 0;
+    let base_size = read_size(delta, &mut cursor).ok_or(Error::TruncatedHeader)?;
+    let result_size = read_size(delta, &mut cursor).ok_or(Error::TruncatedHeader)?;
+    if base_size != base.len() as u64 {
+        return Err(Error::BaseSizeMismatch {
+            expected: base_size,
+            actual: base.len(),
+        });
+    }
+
+    let start_len = out.len();
+    while let Some(&cmd) = delta.get(cursor) {
+        let offset = cursor;
+        cursor += 1;
+        if cmd & 0x80 != 0 {
+            let mut read_le = |first_bit: u8, count: u8| -> Option<usize> {
+                let mut value = 0usize;
+                for idx in 0..count {
+                    if cmd & (1 << (first_bit + idx)) != 0 {
+                        value |= usize::from(*delta.get(cursor)?) << (idx * 8);
+                        cursor += 1;
+                    }
+                }
+                Some(value)
+            };
+            let copy_offset = read_le(0, 4).ok_or(Error::InvalidInstruction { offset })?;
+            let copy_size = match read_le(4, 3).ok_or(Error::InvalidInstruction { offset })? {
+                0 => MAX_COPY_SIZE,
+                size => size,
+            };
+            let range = copy_offset
+                .checked_add(copy_size)
+                .filter(|end| *end <= base.len())
+                .map(|end| copy_offset..end)
+                .ok_or(Error::CopyOutOfBounds { offset })?;
+            out.extend_from_slice(&base[range]);
+        } else if cmd != 0 {
+            let data = delta
+                .get(cursor..cursor + usize::from(cmd))
+                .ok_or(Error::InvalidInstruction { offset })?;
+            out.extend_from_slice(data);
+            cursor += data.len();
+        } else {
+            return Err(Error::InvalidInstruction { offset });
+        }
+    }
+
+    let actual = out.len() - start_len;
+    if actual as u64 != result_size {
+        return Err(Error::ResultSizeMismatch {
+            expected: result_size,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn write_size(mut size: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (size & 0x7f) as u8;
+        size >>= 7;
+        if size == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_size(data: &[u8], cursor: &mut usize) -> Option<u64> {
+    let mut size = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*cursor)?;
+        *cursor += 1;
+        size |= u64::from(byte & 0x7f).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(size);
+        }
+        shift += 7;
+    }
+}
+
+fn write_inserts(data: &[u8], out: &mut Vec<u8>) {
+    for chunk in data.chunks(MAX_INSERT_SIZE) {
+        out.push(chunk.len() as u8);
+        out.extend_from_slice(chunk);
+    }
+}
+
+fn write_copies(mut offset: usize, mut size: usize, out: &mut Vec<u8>) {
+    while size != 0 {
+        let copy_size = size.min(MAX_COPY_SIZE);
+        let cmd_pos = out.len();
+        let mut cmd = 0x80u8;
+        out.push(cmd);
+        for (idx, byte) in (offset as u32).to_le_bytes().into_iter().enumerate() {
+            if byte != 0 {
+                cmd |= 1 << idx;
+                out.push(byte);
+            }
+        }
+        // A size of 0 encodes the maximum copy size.
+        let encoded_size = if copy_size == MAX_COPY_SIZE {
+            0
+        } else {
+            copy_size as u32
+        };
+        for (idx, byte) in encoded_size.to_le_bytes().into_iter().take(3).enumerate() {
+            if byte != 0 {
+                cmd |= 1 << (4 + idx);
+                out.push(byte);
+            }
+        }
+        out[cmd_pos] = cmd;
+        offset += copy_size;
+        size -= copy_size;
+    }
+}
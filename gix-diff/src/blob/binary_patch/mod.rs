@@ -0,0 +1,258 @@
+//! Binary patches as produced by `git diff --binary` and `git format-patch`, which allow binary files to be
+//! changed by `git apply` and `git am`.
+//!
+//! A binary patch consists of a hunk to turn the old version into the new one, and typically another hunk to turn the new
+//! version back into the old one. Each hunk is either the entire content of the resulting file, or a [delta] against the
+//! other version, compressed with `zlib` and encoded with [base85].
+use std::io::Write;
+
+use bstr::ByteSlice;
+
+pub mod base85;
+pub mod delta;
+
+/// The line that introduces a binary patch.
+pub const HEADER: &str = "GIT binary patch";
+
+/// The maximum amount of bytes encoded in a single line.
+const MAX_BYTES_PER_LINE: usize = 52;
+
+/// The way the data of a [`Hunk`] has to be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HunkKind {
+    /// The data is the entire content of the resulting file.
+    Literal,
+    /// The data is a [delta] that has to be applied to the other version of the file.
+    Delta,
+}
+
+/// A single direction of a binary [`Patch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk {
+    /// The way `data` has to be interpreted.
+    pub kind: HunkKind,
+    /// The uncompressed data of the hunk.
+    pub data: Vec<u8>,
+}
+
+/// A binary patch with hunks for both directions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Patch {
+    /// The hunk that turns the old version into the new one.
+    pub forward: Hunk,
+    /// The hunk that turns the new version into the old one, which is always written by `git` but optional when applying.
+    pub reverse: Option<Hunk>,
+}
+
+///
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`Patch::from_bytes()`](super::Patch::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A binary patch must start with a line reading '{}'", super::HEADER)]
+        MissingHeader,
+        #[error("Expected a hunk header like 'literal <size>' or 'delta <size>', got '{line}'")]
+        InvalidHunkHeader { line: BString },
+        #[error("The hunk ended before the empty line that terminates it")]
+        UnterminatedHunk,
+        #[error("Invalid line in hunk data: '{line}'")]
+        InvalidLine { line: BString },
+        #[error(transparent)]
+        Base85(#[from] super::base85::Error),
+        #[error(transparent)]
+        Inflate(#[from] gix_features::zlib::inflate::Error),
+        #[error("The hunk data should decompress to {expected} bytes, but it didn't")]
+        SizeMismatch { expected: usize },
+    }
+}
+
+///
+pub mod apply {
+    /// The error returned by [`Hunk::apply()`](super::Hunk::apply()) and [`Patch::apply()`](super::Patch::apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Delta(#[from] super::delta::Error),
+        #[error("The patch has no reverse hunk, so it can't be applied in reverse")]
+        MissingReverseHunk,
+    }
+}
+
+/// Lifecycle
+impl Hunk {
+    /// Create a hunk that turns `base` into `target`, using whichever of a literal or a delta hunk is smaller once compressed,
+    /// just like `git` does.
+    pub fn new(base: &[u8], target: &[u8]) -> Self {
+        let literal = Hunk {
+            kind: HunkKind::Literal,
+            data: target.to_owned(),
+        };
+        if base.is_empty() || target.is_empty() {
+            return literal;
+        }
+        let delta = Hunk {
+            kind: HunkKind::Delta,
+            data: delta::create(base, target),
+        };
+        if deflate(&delta.data).len() < deflate(&literal.data).len() {
+            delta
+        } else {
+            literal
+        }
+    }
+}
+
+/// Lifecycle
+impl Patch {
+    /// Create a patch that turns `old` into `new`, along with a reverse hunk to turn `new` into `old`.
+    pub fn new(old: &[u8], new: &[u8]) -> Self {
+        Patch {
+            forward: Hunk::new(old, new),
+            reverse: Some(Hunk::new(new, old)),
+        }
+    }
+
+    /// Decode a binary patch from `data`, which starts with the [header](HEADER) line, in the format written
+    /// by [`write_to()`](Self::write_to()).
+    ///
+    /// Everything after the reverse hunk, or after the forward hunk if there is no reverse hunk, is ignored.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, decode::Error> {
+        let mut lines = data.lines();
+        if lines.next() != Some(HEADER.as_bytes()) {
+            return Err(decode::Error::MissingHeader);
+        }
+        let header = lines.next().unwrap_or_default();
+        let forward = Hunk::from_lines(header, &mut lines)?
+            .ok_or_else(|| decode::Error::InvalidHunkHeader { line: header.into() })?;
+        let reverse = match lines.next() {
+            Some(header) => Hunk::from_lines(header, &mut lines)?,
+            None => None,
+        };
+        Ok(Patch { forward, reverse })
+    }
+}
+
+impl Hunk {
+    /// Decode the hunk introduced by `header` from `lines`, or return `None` if `header` doesn't introduce a hunk.
+    fn from_lines<'a>(
+        header: &[u8],
+        lines: &mut impl Iterator<Item = &'a [u8]>,
+    ) -> Result<Option<Self>, decode::Error> {
+        let invalid_header = || decode::Error::InvalidHunkHeader { line: header.into() };
+        let (kind, size) = match header.split_once_str(b" ") {
+            Some((b"literal", size)) => (HunkKind::Literal, size),
+            Some((b"delta", size)) => (HunkKind::Delta, size),
+            _ => return Ok(None),
+        };
+        let size: usize = size
+            .to_str()
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .ok_or_else(invalid_header)?;
+
+        let mut compressed = Vec::new();
+        loop {
+            let line = lines.next().ok_or(decode::Error::UnterminatedHunk)?;
+            let Some((&len, encoded)) = line.split_first() else {
+                break;
+            };
+            let len = match len {
+                b'A'..=b'Z' => len - b'A' + 1,
+                b'a'..=b'z' => len - b'a' + 27,
+                _ => return Err(decode::Error::InvalidLine { line: line.into() }),
+            };
+            base85::decode(encoded, len.into(), &mut compressed)?;
+        }
+
+        // One more byte than needed to detect if there is more data than announced.
+        let mut data = vec![0; size + 1];
+        let (status, _consumed, produced) = gix_features::zlib::Inflate::default().once(&compressed, &mut data)?;
+        if status != gix_features::zlib::Status::StreamEnd || produced != size {
+            return Err(decode::Error::SizeMismatch { expected: size });
+        }
+        data.truncate(size);
+        Ok(Some(Hunk { kind, data }))
+    }
+}
+
+/// Application
+impl Hunk {
+    /// Apply this hunk to `base`, the other version of the file, and return the resulting content.
+    pub fn apply(&self, base: &[u8]) -> Result<Vec<u8>, delta::Error> {
+        match self.kind {
+            HunkKind::Literal => Ok(self.data.clone()),
+            HunkKind::Delta => {
+                let mut out = Vec::new();
+                delta::apply(base, &self.data, &mut out)?;
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// Application
+impl Patch {
+    /// Apply the forward hunk to `old` and return the new version of the file.
+    pub fn apply(&self, old: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        Ok(self.forward.apply(old)?)
+    }
+
+    /// Apply the reverse hunk to `new` and return the old version of the file, or fail if there is no reverse hunk.
+    pub fn apply_reverse(&self, new: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        Ok(self
+            .reverse
+            .as_ref()
+            .ok_or(apply::Error::MissingReverseHunk)?
+            .apply(new)?)
+    }
+}
+
+/// Encoding
+impl Patch {
+    /// Write this patch to `out` in the format used by `git diff --binary`, starting with the [header](HEADER) line.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(out, "{HEADER}")?;
+        self.forward.write_to(out)?;
+        if let Some(reverse) = &self.reverse {
+            reverse.write_to(out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Encoding
+impl Hunk {
+    /// Write this hunk to `out`, which is its header line followed by lines of compressed and encoded data,
+    /// and an empty line.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let kind = match self.kind {
+            HunkKind::Literal => "literal",
+            HunkKind::Delta => "delta",
+        };
+        writeln!(out, "{kind} {}", self.data.len())?;
+        let mut line = Vec::with_capacity(1 + base85::encoded_len(MAX_BYTES_PER_LINE) + 1);
+        for chunk in deflate(&self.data).chunks(MAX_BYTES_PER_LINE) {
+            line.clear();
+            line.push(match chunk.len() as u8 {
+                len @ 1..=26 => b'A' + len - 1,
+                len => b'a' + len - 27,
+            });
+            base85::encode(chunk, &mut line);
+            line.push(b'\n');
+            out.write_all(&line)?;
+        }
+        writeln!(out)
+    }
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+    out.write_all(data)
+        .and_then(|_| out.flush())
+        .expect("writing to memory never fails");
+    out.into_inner()
+}
@@ -5,12 +5,20 @@ use std::{collections::HashMap, path::PathBuf};
 use bstr::BString;
 pub use imara_diff::*;
 
+pub mod binary_patch;
+
 ///
 pub mod pipeline;
 
 ///
 pub mod platform;
 
+///
+pub mod whitespace;
+
+///
+pub mod words;
+
 /// Information about the diff performed to detect similarity.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct DiffLineStats {
@@ -62,6 +70,10 @@ pub struct Driver {
     /// If `Some(false)`, it won't be considered binary, and the its data will not be sampled for the null-byte either.
     /// Leaving it to `None` means binary detection is automatic, and is based on the presence of the `0` byte in the first 8kB of the buffer.
     pub is_binary: Option<bool>,
+    /// The regular expression to match words with when performing a [word diff](words), as set with `diff.<driver>.wordRegex`.
+    ///
+    /// If unset, words are separated by whitespace.
+    pub word_regex: Option<BString>,
 }
 
 /// A conversion pipeline to take an object or path from what's stored in `git` to what can be diffed, while
@@ -304,6 +304,10 @@ pub struct Options {
     /// Otherwise, the internal diff is called despite the configured external diff, which is
     /// typically what callers expect by default.
     pub skip_internal_diff_if_external_is_configured: bool,
+    /// The way whitespace should be ignored when comparing lines, for use with [`whitespace::Input`](crate::blob::whitespace::Input).
+    ///
+    /// Note that this isn't used by the platform itself, but is a setting for those who perform line diffs with it.
+    pub ignore_whitespace: crate::blob::whitespace::Ignore,
 }
 
 /// Lifecycle
@@ -490,7 +494,7 @@ impl Platform {
     ///
     /// The returned outcome allows to easily perform diff operations, based on the [`prepare_diff::Outcome::operation`] field,
     /// which hints at what should be done.
-    pub fn prepare_diff(&mut self) -> Result<prepare_diff::Outcome<'_>, prepare_diff::Error> {
+    pub fn prepare_diff(&self) -> Result<prepare_diff::Outcome<'_>, prepare_diff::Error> {
         let old_key = &self.old.as_ref().ok_or(prepare_diff::Error::SourceOrDestinationUnset)?;
         let old = self
             .diff_cache
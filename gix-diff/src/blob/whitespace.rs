@@ -0,0 +1,145 @@
+use std::{borrow::Cow, ops::Range};
+
+use bstr::ByteSlice;
+
+use crate::blob::{intern::InternedInput, Algorithm, Sink};
+
+/// Ways to ignore whitespace when comparing lines, similar to the respective flags of `git diff`.
+///
+/// Lines are only compared in their normalized form, while the original lines are still available for display.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ignore {
+    /// Ignore all whitespace when comparing lines, like `git diff -w` or `--ignore-all-space`.
+    pub all_space: bool,
+    /// Ignore changes in the amount of whitespace, like `git diff -b` or `--ignore-space-change`.
+    ///
+    /// Whitespace at the end of lines is ignored, and all other sequences of whitespace are considered equal.
+    pub space_change: bool,
+    /// Ignore a carriage-return at the end of lines, like `git diff --ignore-cr-at-eol`.
+    pub cr_at_eol: bool,
+    /// Ignore hunks whose lines are all blank, like `git diff --ignore-blank-lines`.
+    pub blank_lines: bool,
+}
+
+impl Ignore {
+    /// Return `true` if lines have to be normalized before they can be compared.
+    pub fn normalizes_lines(&self) -> bool {
+        self.all_space || self.space_change || self.cr_at_eol
+    }
+
+    /// Return `line`, which may end with a line terminator, in the form that is used for comparison.
+    ///
+    /// The line terminator itself is always preserved, so that a missing newline at the end of a file is still detected.
+    pub fn normalize<'a>(&self, line: &'a [u8]) -> Cow<'a, [u8]> {
+        if !self.normalizes_lines() {
+            return line.into();
+        }
+        let (content, newline) = match line.strip_suffix(b"\n") {
+            Some(content) => (content, &b"\n"[..]),
+            None => (line, &b""[..]),
+        };
+        let content = if self.all_space {
+            content
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect::<Vec<_>>()
+                .into()
+        } else if self.space_change {
+            let mut out = Vec::with_capacity(content.len());
+            for word in content.fields_with(|c| c.is_ascii_whitespace()) {
+                if !out.is_empty() || content.first().map_or(false, u8::is_ascii_whitespace) {
+                    out.push(b' ');
+                }
+                out.extend_from_slice(word);
+            }
+            out.into()
+        } else {
+            Cow::Borrowed(content.strip_suffix(b"\r").unwrap_or(content))
+        };
+        if newline.is_empty() {
+            return content;
+        }
+        let mut content = content.into_owned();
+        content.extend_from_slice(newline);
+        content.into()
+    }
+}
+
+/// Return `true` if `line` consists of whitespace only.
+pub fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+/// The lines of two resources, interned for comparison with whitespace ignored as configured,
+/// while the original lines remain available.
+pub struct Input<'a> {
+    /// The interned lines in their normalized form.
+    pub interned: InternedInput<Cow<'a, [u8]>>,
+    /// The original lines of the old or source resource, including their line terminator.
+    pub before: Vec<&'a [u8]>,
+    /// The original lines of the new or destination resource, including their line terminator.
+    pub after: Vec<&'a [u8]>,
+    /// The way whitespace is ignored.
+    pub ignore: Ignore,
+}
+
+impl<'a> Input<'a> {
+    /// Split `old` and `new` into lines, and intern them after normalizing them according to `ignore`.
+    pub fn new(old: &'a [u8], new: &'a [u8], ignore: Ignore) -> Self {
+        let before: Vec<_> = crate::blob::sources::byte_lines_with_terminator(old).collect();
+        let after: Vec<_> = crate::blob::sources::byte_lines_with_terminator(new).collect();
+        let mut interned = InternedInput::default();
+        interned.update_before(before.iter().map(|line| ignore.normalize(line)));
+        interned.update_after(after.iter().map(|line| ignore.normalize(line)));
+        Input {
+            interned,
+            before,
+            after,
+            ignore,
+        }
+    }
+
+    /// Perform a diff with `algorithm` and pass all hunks to `sink`, with line ranges that can be used to index
+    /// into the original lines in [`before`](Self::before) and [`after`](Self::after).
+    ///
+    /// If [blank lines are ignored](Ignore::blank_lines), hunks that only remove or add blank lines are skipped.
+    pub fn diff<S: Sink>(&self, algorithm: Algorithm, sink: S) -> S::Out {
+        crate::blob::diff(
+            algorithm,
+            &self.interned,
+            SkipBlankHunks {
+                input: self,
+                inner: sink,
+            },
+        )
+    }
+}
+
+struct SkipBlankHunks<'input, 'a, S> {
+    input: &'input Input<'a>,
+    inner: S,
+}
+
+impl<S: Sink> Sink for SkipBlankHunks<'_, '_, S> {
+    type Out = S::Out;
+
+    fn process_change(&mut self, before: Range<u32>, after: Range<u32>) {
+        let all_blank = |lines: &[&[u8]], range: &Range<u32>| {
+            lines[range.start as usize..range.end as usize]
+                .iter()
+                .all(|line| is_blank(line))
+        };
+        if self.input.ignore.blank_lines
+            && all_blank(&self.input.before, &before)
+            && all_blank(&self.input.after, &after)
+        {
+            return;
+        }
+        self.inner.process_change(before, after);
+    }
+
+    fn finish(self) -> Self::Out {
+        self.inner.finish()
+    }
+}
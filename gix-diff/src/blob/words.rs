@@ -0,0 +1,270 @@
+use std::ops::Range;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::blob::{intern::InternedInput, Algorithm};
+
+/// A way to split text into words, which are the units that a word diff operates on.
+///
+/// By default, words are runs of non-whitespace characters, just like in `git diff --word-diff`.
+#[derive(Default, Debug, Clone)]
+pub struct Words {
+    /// If set, every non-overlapping match is a word, and everything else is ignored.
+    regex: Option<regex::bytes::Regex>,
+}
+
+///
+pub mod init {
+    use bstr::BString;
+
+    /// The error returned by [`Words::new()`](super::Words::new()).
+    #[derive(Debug, thiserror::Error)]
+    #[error("The word regex '{regex}' could not be compiled")]
+    pub struct Error {
+        /// The regex that failed to compile.
+        pub regex: BString,
+        /// The reason for the failure.
+        pub source: regex::Error,
+    }
+}
+
+/// Lifecycle
+impl Words {
+    /// Create a new instance which matches words with `word_regex`, typically from `diff.<driver>.wordRegex`, or splits
+    /// words at whitespace if it is `None`.
+    ///
+    /// Just like in `git`, every non-overlapping match of `word_regex` is a word, and the text between matches is treated
+    /// like whitespace and ignored when finding differences. Matches are truncated at the first newline.
+    /// Character classes like `\w` only match ASCII characters.
+    pub fn new(word_regex: Option<&BStr>) -> Result<Self, init::Error> {
+        let regex = word_regex
+            .map(|regex| {
+                regex::bytes::RegexBuilder::new(&regex.to_str_lossy())
+                    .unicode(false)
+                    .build()
+                    .map_err(|source| init::Error {
+                        regex: regex.to_owned(),
+                        source,
+                    })
+            })
+            .transpose()?;
+        Ok(Words { regex })
+    }
+}
+
+/// Access
+impl Words {
+    /// Return the byte ranges of all words in `text`, in order.
+    pub fn split(&self, text: &[u8]) -> Vec<Range<usize>> {
+        match &self.regex {
+            Some(regex) => regex
+                .find_iter(text)
+                .map(|m| {
+                    let word = m.range();
+                    match text[word.clone()].find_byte(b'\n') {
+                        Some(newline) => word.start..word.start + newline,
+                        None => word,
+                    }
+                })
+                .filter(|word| !word.is_empty())
+                .collect(),
+            None => {
+                let mut words = Vec::new();
+                let mut start = None;
+                for (idx, byte) in text.iter().enumerate() {
+                    match (start, byte.is_ascii_whitespace()) {
+                        (None, false) => start = Some(idx),
+                        (Some(word_start), true) => {
+                            words.push(word_start..idx);
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                words.extend(start.map(|word_start| word_start..text.len()));
+                words
+            }
+        }
+    }
+}
+
+/// A consecutive piece of text as produced by [`diff()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chunk<'a> {
+    /// Text that is the same in both versions, taken from the new version so it includes its whitespace.
+    Unchanged(&'a BStr),
+    /// Words that only exist in the old version, along with the whitespace between them.
+    ///
+    /// If entire lines were removed, their last line-terminator is included as well.
+    Removed(&'a BStr),
+    /// Words that only exist in the new version, along with the whitespace between them.
+    Added(&'a BStr),
+}
+
+/// The way [chunks](Chunk) are written as text.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Mark removed words as `[-removed-]` and added words as `{+added+}`, like `git diff --word-diff=plain`.
+    #[default]
+    Plain,
+    /// Write each chunk on its own line, prefixed with ` `, `-` or `+`, and newlines of the text as line with
+    /// a single `~`, like `git diff --word-diff=porcelain`. It's meant to be easy to parse.
+    Porcelain,
+    /// Write removed words in red and added words in green using ANSI escape codes, like `git diff --color-words`.
+    Color,
+}
+
+/// Perform a diff of the words in `old` and `new`, as split by `words`, using `algorithm`, and return the
+/// [chunks](Chunk) that make up the new version, interleaved with what was removed from the old version.
+///
+/// Just like `git`, a line diff is performed first, and only the words within each hunk of changed lines are diffed.
+/// Unlike `git`, all unchanged text is returned, leaving it to the caller to decide how much context to show.
+pub fn diff<'a>(algorithm: Algorithm, old: &'a [u8], new: &'a [u8], words: &Words) -> Vec<Chunk<'a>> {
+    let lines = InternedInput::new(
+        crate::blob::sources::byte_lines_with_terminator(old),
+        crate::blob::sources::byte_lines_with_terminator(new),
+    );
+    let line_offsets = |tokens: &[crate::blob::intern::Token]| {
+        std::iter::once(0)
+            .chain(tokens.iter().scan(0, |offset, &token| {
+                *offset += lines.interner[token].len();
+                Some(*offset)
+            }))
+            .collect::<Vec<_>>()
+    };
+    let (old_offsets, new_offsets) = (line_offsets(&lines.before), line_offsets(&lines.after));
+
+    let mut out = Vec::new();
+    let mut new_pos = 0;
+    let mut input = InternedInput::default();
+    crate::blob::diff(algorithm, &lines, |before: Range<u32>, after: Range<u32>| {
+        let old_hunk = &old[old_offsets[before.start as usize]..old_offsets[before.end as usize]];
+        let new_start = new_offsets[after.start as usize];
+        if new_pos < new_start {
+            out.push(Chunk::Unchanged(new[new_pos..new_start].as_bstr()));
+        }
+        new_pos = new_offsets[after.end as usize];
+        diff_hunk(
+            algorithm,
+            old_hunk,
+            &new[new_start..new_pos],
+            words,
+            &mut input,
+            &mut out,
+        );
+    });
+    if new_pos < new.len() {
+        out.push(Chunk::Unchanged(new[new_pos..].as_bstr()));
+    }
+    out
+}
+
+/// Diff the words of the `old` and `new` lines of a single hunk, placing the chunks that make up `new` into `out`.
+fn diff_hunk<'a>(
+    algorithm: Algorithm,
+    old: &'a [u8],
+    new: &'a [u8],
+    words: &Words,
+    input: &mut InternedInput<&'a [u8]>,
+    out: &mut Vec<Chunk<'a>>,
+) {
+    let old_words = words.split(old);
+    if new.is_empty() {
+        // Lines were removed, so they keep their line-terminator to not be joined with the next line.
+        if let Some(first) = old_words.first() {
+            out.push(Chunk::Removed(old[first.start..].as_bstr()));
+        }
+        return;
+    }
+    let new_words = words.split(new);
+    input.update_before(old_words.iter().map(|word| &old[word.clone()]));
+    input.update_after(new_words.iter().map(|word| &new[word.clone()]));
+
+    let mut new_pos = 0;
+    crate::blob::diff(algorithm, input, |before: Range<u32>, after: Range<u32>| {
+        let (before, after) = (
+            before.start as usize..before.end as usize,
+            after.start as usize..after.end as usize,
+        );
+        // Without added words, the removed ones are placed right after the preceding word, like `git` does.
+        let (added_start, added_end) = if after.is_empty() {
+            let pos = after.start.checked_sub(1).map_or(0, |prev| new_words[prev].end);
+            (pos, pos)
+        } else {
+            (new_words[after.start].start, new_words[after.end - 1].end)
+        };
+        if new_pos < added_start {
+            out.push(Chunk::Unchanged(new[new_pos..added_start].as_bstr()));
+        }
+        if !before.is_empty() {
+            out.push(Chunk::Removed(
+                old[old_words[before.start].start..old_words[before.end - 1].end].as_bstr(),
+            ));
+        }
+        if !after.is_empty() {
+            out.push(Chunk::Added(new[added_start..added_end].as_bstr()));
+        }
+        new_pos = added_end;
+    });
+    if new_pos < new.len() {
+        out.push(Chunk::Unchanged(new[new_pos..].as_bstr()));
+    }
+}
+
+impl Chunk<'_> {
+    /// Write this chunk to `out` as configured by `mode`.
+    ///
+    /// Markers are applied to each line of the chunk individually, so that lines can be displayed on their own.
+    pub fn write_to(&self, mode: Mode, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let (text, porcelain_marker, (prefix, suffix)) = match *self {
+            Chunk::Unchanged(text) => (text, b" ", ("", "")),
+            Chunk::Removed(text) => (
+                text,
+                b"-",
+                match mode {
+                    Mode::Color => ("\x1b[31m", "\x1b[m"),
+                    Mode::Plain | Mode::Porcelain => ("[-", "-]"),
+                },
+            ),
+            Chunk::Added(text) => (
+                text,
+                b"+",
+                match mode {
+                    Mode::Color => ("\x1b[32m", "\x1b[m"),
+                    Mode::Plain | Mode::Porcelain => ("{+", "+}"),
+                },
+            ),
+        };
+        for (idx, line) in text.split_str(b"\n").enumerate() {
+            if mode == Mode::Porcelain {
+                if idx != 0 {
+                    out.write_all(b"~\n")?;
+                }
+                if !line.is_empty() {
+                    out.write_all(porcelain_marker)?;
+                    out.write_all(line)?;
+                    out.write_all(b"\n")?;
+                }
+            } else {
+                if idx != 0 {
+                    out.write_all(b"\n")?;
+                }
+                if !line.is_empty() {
+                    out.write_all(prefix.as_bytes())?;
+                    out.write_all(line)?;
+                    out.write_all(suffix.as_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write all `chunks` to a new buffer as configured by `mode`, which is useful for testing and simple displays.
+pub fn render(chunks: &[Chunk<'_>], mode: Mode) -> BString {
+    let mut out = Vec::new();
+    for chunk in chunks {
+        chunk.write_to(mode, &mut out).expect("writing to memory never fails");
+    }
+    out.into()
+}
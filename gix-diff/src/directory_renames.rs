@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// A directory that was renamed as a whole, as deduced from the files that were renamed out of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DirectoryRename {
+    /// The location of the directory before the rename, relative to the repository root.
+    pub source: BString,
+    /// The location of the directory after the rename, relative to the repository root, which is empty for the root itself.
+    pub destination: BString,
+    /// The amount of files that were renamed from `source` to `destination`.
+    pub num_files: usize,
+}
+
+impl DirectoryRename {
+    /// Return the location that `path` would have if it was moved along with the renamed directory,
+    /// or `None` if `path` isn't within the [source](Self::source) directory.
+    pub fn relocate(&self, path: &BStr) -> Option<BString> {
+        let rest = path.strip_prefix(self.source.as_bytes())?.strip_prefix(b"/")?;
+        let mut out = self.destination.clone();
+        if !out.is_empty() {
+            out.push(b'/');
+        }
+        out.push_str(rest);
+        Some(out)
+    }
+}
+
+/// Deduce which directories were renamed from the `renames` of files, passed as `(source, destination)` pairs of paths,
+/// similar to the directory rename detection `git merge` performs with the `ort` strategy. The result is sorted by source.
+///
+/// The directory of each file rename is paired with the directory of its destination after removing the trailing path
+/// components both have in common, so renaming `a/b/c/file` to `x/b/c/file` counts for `a` being renamed to `x`.
+/// A directory is considered renamed if `exists_in_destination(directory)` returns `false`, i.e. it's gone after the rename,
+/// and if most of the files renamed out of it went to the same destination. Directories whose files were renamed
+/// to multiple destinations in equal parts are ambiguous and thus not considered renamed.
+///
+/// The result can be used to [relocate](DirectoryRename::relocate()) files that were added to the source directory
+/// on another branch, as their destination is a hint for where they should be placed instead.
+pub fn detect<'a>(
+    renames: impl IntoIterator<Item = (&'a BStr, &'a BStr)>,
+    mut exists_in_destination: impl FnMut(&BStr) -> bool,
+) -> Vec<DirectoryRename> {
+    let mut counts = BTreeMap::<&BStr, BTreeMap<&BStr, usize>>::new();
+    for (source, destination) in renames {
+        if let Some((source_dir, destination_dir)) = renamed_directory_portion(source, destination) {
+            *counts
+                .entry(source_dir)
+                .or_default()
+                .entry(destination_dir)
+                .or_default() += 1;
+        }
+    }
+
+    let mut out = Vec::new();
+    for (source, destinations) in counts {
+        if exists_in_destination(source) {
+            continue;
+        }
+        let max = destinations.values().copied().max().unwrap_or_default();
+        let mut best = destinations.iter().filter(|(_, count)| **count == max);
+        if let (Some((destination, num_files)), None) = (best.next(), best.next()) {
+            out.push(DirectoryRename {
+                source: source.to_owned(),
+                destination: (*destination).to_owned(),
+                num_files: *num_files,
+            });
+        }
+    }
+    out
+}
+
+/// Return the portion of the directories of `source` and `destination` that differs, or `None` if the file didn't
+/// change its directory or was at the root before.
+fn renamed_directory_portion<'a>(source: &'a BStr, destination: &'a BStr) -> Option<(&'a BStr, &'a BStr)> {
+    let mut source_dir = parent(source)?;
+    let mut destination_dir = parent(destination).unwrap_or_default();
+    if source_dir == destination_dir {
+        return None;
+    }
+    while let (Some((source_parent, source_name)), Some(destination_name)) =
+        (split_last(source_dir), basename(destination_dir))
+    {
+        if source_name != destination_name {
+            break;
+        }
+        source_dir = source_parent;
+        destination_dir = parent(destination_dir).unwrap_or_default();
+    }
+    Some((source_dir, destination_dir))
+}
+
+/// Return the directory containing `path`, or `None` if it's at the root.
+fn parent(path: &BStr) -> Option<&BStr> {
+    path.rfind_byte(b'/').map(|pos| path[..pos].as_bstr())
+}
+
+/// Return the last component of the non-empty `path`.
+fn basename(path: &BStr) -> Option<&BStr> {
+    (!path.is_empty()).then(|| path.rfind_byte(b'/').map_or(path, |pos| path[pos + 1..].as_bstr()))
+}
+
+/// Split `path` into its parent and its last component, or return `None` if it only has a single component.
+fn split_last(path: &BStr) -> Option<(&BStr, &BStr)> {
+    path.rfind_byte(b'/')
+        .map(|pos| (path[..pos].as_bstr(), path[pos + 1..].as_bstr()))
+}
@@ -46,6 +46,12 @@ pub mod rewrites;
 ///
 pub mod tree;
 
+///
+pub mod stat;
+
+///
+pub mod directory_renames;
+
 ///
 #[cfg(feature = "blob")]
 pub mod blob;
@@ -0,0 +1,294 @@
+use std::borrow::Cow;
+
+use bstr::{BStr, BString, ByteSlice};
+
+/// The statistics of a single changed file, as shown by `git diff --stat` or `git diff --numstat`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct File {
+    /// The location of the file after the change, relative to the repository root.
+    pub location: BString,
+    /// The location of the file before the change if it was renamed or copied from another file.
+    pub source_location: Option<BString>,
+    /// The way the content of the file changed.
+    pub change: Change,
+}
+
+/// The way the content of a [`File`] changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// Lines were inserted or removed, with a modified line counting as both.
+    Lines {
+        /// The amount of inserted lines.
+        insertions: u32,
+        /// The amount of removed lines.
+        removals: u32,
+    },
+    /// At least one side of the change is binary, so lines can't be counted.
+    Binary {
+        /// The size of the file before the change in bytes, or `0` if it didn't exist.
+        old_size: u64,
+        /// The size of the file after the change in bytes, or `0` if it doesn't exist anymore.
+        new_size: u64,
+    },
+}
+
+/// The totals of a set of changed [files](File), as shown by `git diff --shortstat` or in the last line of `git diff --stat`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Summary {
+    /// The amount of changed files, including binary ones.
+    pub files_changed: usize,
+    /// The amount of inserted lines in all textual files.
+    pub insertions: u64,
+    /// The amount of removed lines in all textual files.
+    pub removals: u64,
+}
+
+/// The way statistics are written by [`write_to()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// A line per file with the amount of changed lines and a graph of `+` and `-`, followed by the [summary](Summary),
+    /// like `git diff --stat`.
+    Stat {
+        /// The maximum width of each line, which is `80` in `git` if the output isn't a terminal.
+        width: usize,
+    },
+    /// Only the [summary](Summary), like `git diff --shortstat`.
+    ShortStat,
+    /// A tab-separated line per file with inserted and removed lines, which are `-` for binary files, like `git diff --numstat`.
+    NumStat,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::Stat { width: 80 }
+    }
+}
+
+impl File {
+    /// Return the location to display for this file, which shows both locations with their common prefix and suffix
+    /// merged if it was renamed or copied, like `dir/{old => new}`, just like `git` does it.
+    pub fn display_location(&self) -> Cow<'_, BStr> {
+        match &self.source_location {
+            Some(source) if *source != self.location => {
+                merged_rename_location(source.as_ref(), self.location.as_ref()).into()
+            }
+            _ => self.location.as_bstr().into(),
+        }
+    }
+}
+
+impl Summary {
+    /// Compute the totals of all `files`.
+    pub fn from_files(files: &[File]) -> Self {
+        files.iter().fold(Summary::default(), |mut acc, file| {
+            acc.files_changed += 1;
+            if let Change::Lines { insertions, removals } = file.change {
+                acc.insertions += u64::from(insertions);
+                acc.removals += u64::from(removals);
+            }
+            acc
+        })
+    }
+
+    /// Write this instance to `out` as a single line like `git diff --shortstat` does.
+    ///
+    /// Insertions and removals are omitted if there are none, unless both are zero, in which case both are written.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let plural = |count: u64| if count == 1 { "" } else { "s" };
+        if self.files_changed == 0 {
+            return writeln!(out, " 0 files changed");
+        }
+        write!(
+            out,
+            " {} file{} changed",
+            self.files_changed,
+            plural(self.files_changed as u64)
+        )?;
+        if self.insertions != 0 || self.removals == 0 {
+            write!(out, ", {} insertion{}(+)", self.insertions, plural(self.insertions))?;
+        }
+        if self.removals != 0 || self.insertions == 0 {
+            write!(out, ", {} deletion{}(-)", self.removals, plural(self.removals))?;
+        }
+        writeln!(out)
+    }
+}
+
+/// Write the statistics of `files` to `out` as configured by `format`, producing the same output as `git` for the same files.
+///
+/// Nothing is written if there are no `files`.
+pub fn write_to(files: &[File], format: Format, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+    match format {
+        Format::Stat { width } => write_stat(files, width, out),
+        Format::ShortStat => Summary::from_files(files).write_to(out),
+        Format::NumStat => {
+            for file in files {
+                match file.change {
+                    Change::Lines { insertions, removals } => write!(out, "{insertions}\t{removals}\t")?,
+                    Change::Binary { .. } => out.write_all(b"-\t-\t")?,
+                }
+                out.write_all(&file.display_location())?;
+                out.write_all(b"\n")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_stat(files: &[File], width: usize, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    let decimal_width = |num: u64| num.to_string().len();
+    let names: Vec<_> = files.iter().map(File::display_location).collect();
+
+    let mut max_name_width = 0;
+    let mut max_change = 0;
+    let mut number_width = 0;
+    let mut bin_width = 0;
+    for (file, name) in files.iter().zip(&names) {
+        max_name_width = max_name_width.max(name.chars().count());
+        match file.change {
+            Change::Binary { old_size, new_size } => {
+                // "Bin <old> -> <new> bytes"
+                bin_width = bin_width.max(14 + decimal_width(old_size) + decimal_width(new_size));
+                number_width = 3;
+            }
+            Change::Lines { insertions, removals } => {
+                max_change = max_change.max(u64::from(insertions) + u64::from(removals));
+            }
+        }
+    }
+    number_width = number_width.max(decimal_width(max_change));
+
+    // Like `git`, guarantee a minimal width for the name and the graph.
+    let width = width.max(16 + 6 + number_width);
+    let mut graph_width = if max_change as usize + 4 > bin_width {
+        max_change as usize
+    } else {
+        bin_width - 4
+    };
+    let mut name_width = max_name_width;
+    if name_width + number_width + 6 + graph_width > width {
+        let max_graph_width = (width * 3 / 8).saturating_sub(number_width + 6);
+        if graph_width > max_graph_width {
+            graph_width = max_graph_width.max(6);
+        }
+        let available_name_width = width.saturating_sub(number_width + 6 + graph_width);
+        if name_width > available_name_width {
+            name_width = available_name_width;
+        } else {
+            graph_width = width - number_width - 6 - name_width;
+        }
+    }
+
+    let scale = |count: u64| {
+        if count == 0 {
+            0
+        } else {
+            1 + count * (graph_width as u64 - 1) / max_change
+        }
+    };
+    for (file, name) in files.iter().zip(&names) {
+        let name_len = name.chars().count();
+        let (prefix, name) = if name_len > name_width {
+            let available = name_width.saturating_sub(3);
+            let start = name
+                .char_indices()
+                .nth(name_len - available)
+                .map_or(name.len(), |(start, _, _)| start);
+            let name = &name[start..];
+            let name = name.find_byte(b'/').map_or(name, |slash| &name[slash..]);
+            ("...", name.as_bstr())
+        } else {
+            ("", name.as_ref())
+        };
+        let padding = name_width
+            .saturating_sub(prefix.len())
+            .saturating_sub(name.chars().count());
+        write!(out, " {prefix}{name}{:padding$} | ", "")?;
+
+        match file.change {
+            Change::Binary { old_size, new_size } => {
+                write!(out, "{:>number_width$}", "Bin")?;
+                if old_size == 0 && new_size == 0 {
+                    writeln!(out)?;
+                } else {
+                    writeln!(out, " {old_size} -> {new_size} bytes")?;
+                }
+            }
+            Change::Lines { insertions, removals } => {
+                let (mut added, mut removed) = (u64::from(insertions), u64::from(removals));
+                let total = added + removed;
+                if graph_width as u64 <= max_change {
+                    let mut scaled_total = scale(total);
+                    if scaled_total < 2 && added != 0 && removed != 0 {
+                        scaled_total = 2;
+                    }
+                    if added < removed {
+                        added = scale(added);
+                        removed = scaled_total - added;
+                    } else {
+                        removed = scale(removed);
+                        added = scaled_total - removed;
+                    }
+                }
+                write!(
+                    out,
+                    "{total:>number_width$}{}{}{}",
+                    if total != 0 { " " } else { "" },
+                    "+".repeat(added as usize),
+                    "-".repeat(removed as usize)
+                )?;
+                writeln!(out)?;
+            }
+        }
+    }
+    Summary::from_files(files).write_to(out)
+}
+
+/// Merge `old` and `new` into a single location that keeps the common prefix and suffix of both, like `dir/{old => new}`.
+///
+/// Just like in `git`, common prefixes and suffixes only consist of entire path components.
+fn merged_rename_location(old: &BStr, new: &BStr) -> BString {
+    let prefix_len = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .filter(|(_, (byte, _))| **byte == b'/')
+        .last()
+        .map_or(0, |(idx, _)| idx + 1);
+
+    // If there is a common prefix it ends in a slash, which may also start the suffix.
+    let min_len = prefix_len.saturating_sub(1);
+    let suffix_len = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .take_while(|(idx, _)| old.len() - idx > min_len && new.len() - idx > min_len)
+        .filter(|(_, (byte, _))| **byte == b'/')
+        .last()
+        .map_or(0, |(idx, _)| idx + 1);
+
+    if prefix_len + suffix_len == 0 {
+        let mut out = old.to_owned();
+        out.extend_from_slice(b" => ");
+        out.extend_from_slice(new);
+        return out;
+    }
+    let middle = |path: &BStr| {
+        let end = path.len().saturating_sub(suffix_len).max(prefix_len);
+        path[prefix_len..end].to_owned()
+    };
+    let mut out = BString::from(&old[..prefix_len]);
+    out.push(b'{');
+    out.extend_from_slice(&middle(old));
+    out.extend_from_slice(b" => ");
+    out.extend_from_slice(&middle(new));
+    out.push(b'}');
+    out.extend_from_slice(&old[old.len() - suffix_len..]);
+    out
+}
@@ -48,6 +48,20 @@ impl Change {
             | Change::Modification { entry_mode, .. } => *entry_mode,
         }
     }
+    /// Return `true` if this is a modification that changed the kind of the entry, like a file that turned into
+    /// a symlink or a submodule, which `git` reports as _typechange_.
+    ///
+    /// Note that files replaced by directories and vice versa are represented as deletion and addition instead.
+    pub fn is_typechange(&self) -> bool {
+        match self {
+            Change::Modification {
+                previous_entry_mode,
+                entry_mode,
+                ..
+            } => previous_entry_mode.is_typechange(*entry_mode),
+            Change::Addition { .. } | Change::Deletion { .. } => false,
+        }
+    }
     /// Return the current object id and tree entry mode of a change.
     pub fn oid_and_entry_mode(&self) -> (&gix_hash::oid, EntryMode) {
         match self {
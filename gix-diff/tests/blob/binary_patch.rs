@@ -0,0 +1,116 @@
+use gix_diff::blob::binary_patch::{base85, delta, HunkKind, Patch};
+use gix_object::bstr::ByteSlice;
+
+/// Data that doesn't compress well, so deltas pay off.
+fn noise(len: usize, mut seed: u32) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn decode_and_apply_patch_written_by_git() -> crate::Result {
+    let patch = Patch::from_bytes(b"GIT binary patch\nliteral 3\nKcmZQzWC8#H2LJ>B\n\nliteral 2\nJcmZQz1ONa700IC2\n\n")?;
+    assert_eq!(patch.forward.kind, HunkKind::Literal);
+    assert_eq!(patch.apply(b"\0\x01")?, b"\0\x01\x02");
+    assert_eq!(patch.apply_reverse(b"\0\x01\x02")?, b"\0\x01");
+    Ok(())
+}
+
+#[test]
+fn round_trip_with_delta_hunks() -> crate::Result {
+    let old = noise(4096, 42);
+    let mut new = old.clone();
+    new.splice(1000..1010, *b"inserted instead");
+    new.extend_from_slice(&noise(100, 7));
+
+    let patch = Patch::new(&old, &new);
+    assert_eq!(
+        patch.forward.kind,
+        HunkKind::Delta,
+        "a delta is much smaller than the literal"
+    );
+    assert_eq!(patch.reverse.as_ref().expect("always set").kind, HunkKind::Delta);
+    assert!(patch.forward.data.len() < 200, "the delta copies most of the data");
+
+    let mut buf = Vec::new();
+    patch.write_to(&mut buf)?;
+    assert!(buf.starts_with(b"GIT binary patch\ndelta "));
+    assert!(buf.ends_with(b"\n\n"), "every hunk is terminated by an empty line");
+    assert!(
+        buf.lines()
+            .skip(2)
+            .take(2)
+            .all(|line| line.len() == 66 || line.is_empty()),
+        "full lines encode 52 bytes"
+    );
+
+    let decoded = Patch::from_bytes(&buf)?;
+    assert_eq!(decoded, patch);
+    assert_eq!(decoded.apply(&old)?, new);
+    assert_eq!(decoded.apply_reverse(&new)?, old);
+    Ok(())
+}
+
+#[test]
+fn literal_hunks_for_added_and_deleted_files() -> crate::Result {
+    let patch = Patch::new(b"", b"\0new");
+    assert_eq!(patch.forward.kind, HunkKind::Literal);
+    let mut buf = Vec::new();
+    patch.write_to(&mut buf)?;
+    let decoded = Patch::from_bytes(&buf)?;
+    assert_eq!(decoded.apply(b"")?, b"\0new");
+    assert_eq!(decoded.apply_reverse(b"\0new")?, b"", "empty literals can be decoded");
+    Ok(())
+}
+
+#[test]
+fn invalid_patches() {
+    for input in [
+        &b"not a patch\n"[..],
+        b"GIT binary patch\nunknown 3\n\n",
+        b"GIT binary patch\nliteral 3\nKcmZQzWC8#H2LJ>B\n",
+        b"GIT binary patch\nliteral 4\nKcmZQzWC8#H2LJ>B\n\n",
+        b"GIT binary patch\nliteral 3\nAcmZQzWC8#H2LJ>B\n\n",
+        b"GIT binary patch\nliteral 3\nKcmZQzWC8#H2LJ>\"\n\n",
+    ] {
+        assert!(Patch::from_bytes(input).is_err(), "{:?}", input.as_bstr());
+    }
+}
+
+#[test]
+fn base85_round_trip() -> crate::Result {
+    for len in 0..10 {
+        let data = noise(len, len as u32);
+        let mut encoded = Vec::new();
+        base85::encode(&data, &mut encoded);
+        assert_eq!(encoded.len(), base85::encoded_len(len));
+        let mut decoded = Vec::new();
+        base85::decode(&encoded, len, &mut decoded)?;
+        assert_eq!(decoded, data);
+    }
+    assert!(
+        base85::decode(b"~~~~~", 4, &mut Vec::new()).is_err(),
+        "values beyond 32 bits are rejected"
+    );
+    Ok(())
+}
+
+#[test]
+fn delta_errors() {
+    let delta = delta::create(
+        b"base content that is long enough",
+        b"base content that is long enough!",
+    );
+    assert!(matches!(
+        delta::apply(b"other base", &delta, &mut Vec::new()),
+        Err(delta::Error::BaseSizeMismatch { .. })
+    ));
+    assert!(matches!(
+        delta::apply(b"", &[0, 1, 0x80 | 0x10, 1], &mut Vec::new()),
+        Err(delta::Error::CopyOutOfBounds { .. })
+    ));
+}
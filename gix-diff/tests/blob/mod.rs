@@ -1,2 +1,5 @@
+mod binary_patch;
 pub(crate) mod pipeline;
 mod platform;
+mod whitespace;
+mod words;
@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use gix_diff::blob::{
+    whitespace::{Ignore, Input},
+    Algorithm,
+};
+
+const OLD: &str = "a  b\nc\r\nd \n\nx\n";
+const NEW: &str = "a b\nc\nd\n\n\nx y\n";
+
+fn hunks(old: &str, new: &str, ignore: Ignore) -> Vec<(Range<u32>, Range<u32>)> {
+    let input = Input::new(old.as_bytes(), new.as_bytes(), ignore);
+    let mut out = Vec::new();
+    input.diff(Algorithm::Myers, |before: Range<u32>, after: Range<u32>| {
+        out.push((before, after))
+    });
+    out
+}
+
+#[test]
+fn nothing_is_ignored_by_default() {
+    assert_eq!(hunks(OLD, NEW, Ignore::default()), [(0..5, 0..6)]);
+}
+
+#[test]
+fn all_space() {
+    let ignore = Ignore {
+        all_space: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        hunks(OLD, NEW, ignore),
+        [(4..5, 4..6)],
+        "like `git diff -w`, where 'x' and 'x y' still differ"
+    );
+    assert_eq!(ignore.normalize(b" a \t b\r\n").as_ref(), b"ab\n");
+    assert_eq!(
+        hunks("ab\n", "a b\n", ignore),
+        [],
+        "whitespace within words is ignored as well"
+    );
+}
+
+#[test]
+fn space_change() {
+    let ignore = Ignore {
+        space_change: true,
+        ..Default::default()
+    };
+    assert_eq!(hunks(OLD, NEW, ignore), [(4..5, 4..6)], "like `git diff -b`");
+    assert_eq!(ignore.normalize(b" a \t b \r\n").as_ref(), b" a b\n");
+    assert_eq!(
+        hunks("ab\n", "a b\n", ignore),
+        [(0..1, 0..1)],
+        "whitespace can't be added where there was none"
+    );
+    assert_eq!(
+        hunks("a\n", "a", ignore),
+        [(0..1, 0..1)],
+        "a missing newline at the end of the file is still a change"
+    );
+}
+
+#[test]
+fn cr_at_eol() {
+    let ignore = Ignore {
+        cr_at_eol: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        hunks(OLD, NEW, ignore),
+        [(0..1, 0..1), (2..3, 2..3), (4..5, 4..6)],
+        "like `git diff --ignore-cr-at-eol`"
+    );
+    assert_eq!(
+        ignore.normalize(b"a\r\r\n").as_ref(),
+        b"a\r\n",
+        "only one CR is removed"
+    );
+}
+
+#[test]
+fn blank_lines() {
+    let ignore = Ignore {
+        blank_lines: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        hunks("a\nb\n", "a\n\n \nb\n", ignore),
+        [],
+        "only blank lines were added"
+    );
+    assert_eq!(hunks("a\n\nb\n", "a\nb\n", ignore), [], "only blank lines were removed");
+    assert_eq!(
+        hunks(OLD, NEW, ignore),
+        [(0..5, 0..6)],
+        "hunks with other changes are kept with their blank lines"
+    );
+}
@@ -0,0 +1,91 @@
+use gix_diff::blob::{
+    words::{diff, render, Chunk, Mode, Words},
+    Algorithm,
+};
+
+const OLD: &str = "a b c\nfoo bar\nsame\n";
+const NEW: &str = "a c\nfoo baz qux\nsame\nnew line\n";
+
+#[test]
+fn whitespace_separated_words_by_default() -> crate::Result {
+    let chunks = diff(Algorithm::Myers, OLD.as_bytes(), NEW.as_bytes(), &Words::new(None)?);
+    assert_eq!(
+        chunks,
+        [
+            Chunk::Unchanged("a".into()),
+            Chunk::Removed("b".into()),
+            Chunk::Unchanged(" c\nfoo ".into()),
+            Chunk::Removed("bar".into()),
+            Chunk::Added("baz qux".into()),
+            Chunk::Unchanged("\n".into()),
+            Chunk::Unchanged("same\n".into()),
+            Chunk::Added("new line".into()),
+            Chunk::Unchanged("\n".into()),
+        ],
+        "removed words are placed right after the preceding word, like git does"
+    );
+    Ok(())
+}
+
+#[test]
+fn modes_match_git() -> crate::Result {
+    let chunks = diff(Algorithm::Myers, OLD.as_bytes(), NEW.as_bytes(), &Words::default());
+    assert_eq!(
+        render(&chunks, Mode::Plain),
+        "a[-b-] c\nfoo [-bar-]{+baz qux+}\nsame\n{+new line+}\n",
+        "like `git diff --word-diff=plain`"
+    );
+    assert_eq!(
+        render(&chunks, Mode::Porcelain),
+        " a\n-b\n  c\n~\n foo \n-bar\n+baz qux\n~\n same\n~\n+new line\n~\n",
+        "like `git diff --word-diff=porcelain`"
+    );
+    assert_eq!(
+        render(&chunks, Mode::Color),
+        "a\x1b[31mb\x1b[m c\nfoo \x1b[31mbar\x1b[m\x1b[32mbaz qux\x1b[m\nsame\n\x1b[32mnew line\x1b[m\n",
+        "like `git diff --color-words`, but without resetting colors at the end of each line"
+    );
+    Ok(())
+}
+
+#[test]
+fn changes_spanning_lines_are_marked_per_line() -> crate::Result {
+    let chunks = diff(Algorithm::Myers, b"a\nb\nc\n", b"a\nc\n", &Words::default());
+    assert_eq!(
+        render(&chunks, Mode::Plain),
+        "a\n[-b-]\nc\n",
+        "removed lines stay lines"
+    );
+    assert_eq!(render(&chunks, Mode::Porcelain), " a\n~\n-b\n~\n c\n~\n");
+
+    let chunks = diff(Algorithm::Myers, b"a\n", b"a\nb\nc\n", &Words::default());
+    assert_eq!(render(&chunks, Mode::Plain), "a\n{+b+}\n{+c+}\n");
+    assert_eq!(render(&chunks, Mode::Porcelain), " a\n~\n+b\n~\n+c\n~\n");
+    Ok(())
+}
+
+#[test]
+fn word_regex() -> crate::Result {
+    let words = Words::new(Some("[a-z]+|[^[:space:]]".into()))?;
+    let chunks = diff(Algorithm::Myers, b"x=1;\n", b"x=2;\n", &words);
+    assert_eq!(render(&chunks, Mode::Plain), "x=[-1-]{+2+};\n");
+
+    let chunks = diff(Algorithm::Myers, b"x=1;\n", b"x=2;\n", &Words::default());
+    assert_eq!(
+        render(&chunks, Mode::Plain),
+        "[-x=1;-]{+x=2;+}\n",
+        "without regex, the whole line is a single word"
+    );
+
+    let words = Words::new(Some("[a-z]+".into()))?;
+    let chunks = diff(Algorithm::Myers, b"a = 1\n", b"a := 2\n", &words);
+    assert_eq!(
+        render(&chunks, Mode::Plain),
+        "a := 2\n",
+        "everything that isn't matched is ignored when finding differences"
+    );
+
+    let err = Words::new(Some("(".into())).unwrap_err();
+    assert_eq!(err.to_string(), "The word regex '(' could not be compiled");
+    Ok(())
+}
@@ -5,7 +5,9 @@ fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
 }
 
 mod blob;
+mod directory_renames;
 mod rewrites;
+mod stat;
 mod tree;
 
 mod util {
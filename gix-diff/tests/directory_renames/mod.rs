@@ -0,0 +1,71 @@
+use gix_diff::directory_renames::{detect, DirectoryRename};
+use gix_object::bstr::BStr;
+
+fn renames(pairs: &'static [(&'static str, &'static str)]) -> impl Iterator<Item = (&'static BStr, &'static BStr)> {
+    pairs
+        .iter()
+        .map(|(source, destination)| ((*source).into(), (*destination).into()))
+}
+
+fn rename(source: &str, destination: &str, num_files: usize) -> DirectoryRename {
+    DirectoryRename {
+        source: source.into(),
+        destination: destination.into(),
+        num_files,
+    }
+}
+
+#[test]
+fn common_trailing_components_are_ignored() {
+    let actual = detect(
+        renames(&[
+            ("a/b/c/file", "x/b/c/file"),
+            ("a/other", "x/other"),
+            ("old/nested/file", "nested/file"),
+            ("top/file", "new/top/file"),
+        ]),
+        |_| false,
+    );
+    assert_eq!(
+        actual,
+        vec![rename("a", "x", 2), rename("old", "", 1), rename("top", "new/top", 1)]
+    );
+}
+
+#[test]
+fn directories_that_still_exist_are_not_renamed() {
+    let actual = detect(renames(&[("a/b/file", "a/file"), ("c/file", "d/file")]), |dir| {
+        dir == "a/b"
+    });
+    assert_eq!(actual, vec![rename("c", "d", 1)]);
+}
+
+#[test]
+fn the_majority_of_files_decides_and_ties_are_ambiguous() {
+    let actual = detect(
+        renames(&[
+            ("a/1", "x/1"),
+            ("a/2", "x/2"),
+            ("a/3", "y/3"),
+            ("b/1", "x/b1"),
+            ("b/2", "y/b2"),
+            ("file", "root-files-are-ignored/file"),
+            ("same/1", "same/2"),
+        ]),
+        |_| false,
+    );
+    assert_eq!(actual, vec![rename("a", "x", 2)]);
+}
+
+#[test]
+fn relocate() {
+    let dir = rename("a/b", "x", 1);
+    assert_eq!(dir.relocate("a/b/new/file".into()).expect("within"), "x/new/file");
+    assert_eq!(dir.relocate("a/bb/file".into()), None, "prefixes must be directories");
+    assert_eq!(dir.relocate("a/b".into()), None, "the directory itself isn't relocated");
+    assert_eq!(
+        rename("a", "", 1).relocate("a/file".into()).expect("within"),
+        "file",
+        "directories can be moved into the root"
+    );
+}
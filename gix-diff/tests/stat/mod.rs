@@ -0,0 +1,113 @@
+use gix_diff::stat::{write_to, Change, File, Format, Summary};
+use gix_object::bstr::ByteSlice;
+
+fn files() -> Vec<File> {
+    vec![
+        File {
+            location: "a".into(),
+            source_location: None,
+            change: Change::Lines {
+                insertions: 2,
+                removals: 1,
+            },
+        },
+        File {
+            location: "bin".into(),
+            source_location: None,
+            change: Change::Binary {
+                old_size: 2,
+                new_size: 3,
+            },
+        },
+        File {
+            location: "dir/other-name.txt".into(),
+            source_location: Some("dir/sub/long-file-name.txt".into()),
+            change: Change::Lines {
+                insertions: 150,
+                removals: 0,
+            },
+        },
+    ]
+}
+
+fn render(files: &[File], format: Format) -> String {
+    let mut out = Vec::new();
+    write_to(files, format, &mut out).expect("writing to memory never fails");
+    out.to_str().expect("valid UTF-8").to_owned()
+}
+
+#[test]
+fn stat() {
+    assert_eq!(
+        render(&files(), Format::default()),
+        " a                                              |   3 +-
+ bin                                            | Bin 2 -> 3 bytes
+ dir/{sub/long-file-name.txt => other-name.txt} | 150 +++++++++++++++++++++++++
+ 3 files changed, 152 insertions(+), 1 deletion(-)
+",
+        "this is the output of `git diff --stat` for the same changes"
+    );
+    assert_eq!(
+        render(&files(), Format::Stat { width: 40 }),
+        " a                         |   3 +-
+ bin                       | Bin 2 -> 3 bytes
+ ...txt => other-name.txt} | 150 ++++++
+ 3 files changed, 152 insertions(+), 1 deletion(-)
+",
+        "names are shortened from the front to fit"
+    );
+}
+
+#[test]
+fn numstat() {
+    assert_eq!(
+        render(&files(), Format::NumStat),
+        "2\t1\ta\n-\t-\tbin\n150\t0\tdir/{sub/long-file-name.txt => other-name.txt}\n"
+    );
+}
+
+#[test]
+fn shortstat() {
+    assert_eq!(
+        render(&files(), Format::ShortStat),
+        " 3 files changed, 152 insertions(+), 1 deletion(-)\n"
+    );
+    assert_eq!(render(&[], Format::ShortStat), "", "nothing is written without changes");
+
+    let mut out = Vec::new();
+    Summary {
+        files_changed: 1,
+        insertions: 0,
+        removals: 2,
+    }
+    .write_to(&mut out)
+    .unwrap();
+    assert_eq!(
+        out.as_bstr(),
+        " 1 file changed, 2 deletions(-)\n",
+        "insertions are omitted if there are none"
+    );
+}
+
+#[test]
+fn display_location_of_renames() {
+    for (source, location, expected) in [
+        ("a", "b", "a => b"),
+        ("dir/a", "dir/b", "dir/{a => b}"),
+        ("a/file", "b/file", "{a => b}/file"),
+        ("dir/a/file", "dir/b/file", "dir/{a => b}/file"),
+        ("dir/file", "dir/sub/file", "dir/{ => sub}/file"),
+        ("dir/sub/file", "dir/file", "dir/{sub => }/file"),
+        ("same", "same", "same"),
+    ] {
+        let file = File {
+            location: location.into(),
+            source_location: Some(source.into()),
+            change: Change::Lines {
+                insertions: 0,
+                removals: 0,
+            },
+        };
+        assert_eq!(file.display_location().as_ref(), expected, "{source} -> {location}");
+    }
+}
@@ -1,5 +1,32 @@
 pub use flate2::{Decompress, Status};
 
+/// The zlib implementation used by `flate2` for compression and decompression, as selected with cargo features
+/// of this crate at compile time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Backend {
+    /// The `zlib-ng` C library, which is the fastest, selected by the `zlib-ng` or `zlib-ng-compat` features.
+    ZlibNg,
+    /// The system's or a vendored stock `zlib` C library, selected by the `zlib-stock` feature.
+    Zlib,
+    /// The pure Rust `miniz_oxide` implementation, the default if no other backend is selected.
+    MinizOxide,
+}
+
+impl Backend {
+    /// Return the backend selected by the features of this crate.
+    ///
+    /// Note that `flate2` features enabled by other crates of the dependency graph aren't visible here.
+    pub const fn current() -> Self {
+        if cfg!(any(feature = "zlib-ng", feature = "zlib-ng-compat")) {
+            Backend::ZlibNg
+        } else if cfg!(feature = "zlib-stock") {
+            Backend::Zlib
+        } else {
+            Backend::MinizOxide
+        }
+    }
+}
+
 /// non-streaming interfaces for decompression
 pub mod inflate {
     /// The error returned by various [Inflate methods][super::Inflate]
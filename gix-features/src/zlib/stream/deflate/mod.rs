@@ -2,11 +2,17 @@ use flate2::Compress;
 
 const BUF_SIZE: usize = 4096 * 8;
 
+/// The compression level that is fastest while still compressing, and the default for loose objects.
+pub const LEVEL_FAST: u32 = 1;
+/// The compression level `zlib` uses by default, which compresses better at the cost of speed.
+pub const LEVEL_DEFAULT: u32 = 6;
+
 /// A utility to zlib compress anything that is written via its [Write][std::io::Write] implementation.
 ///
 /// Be sure to call `flush()` when done to finalize the deflate stream.
 pub struct Write<W> {
     compressor: Compress,
+    level: u32,
     inner: W,
     buf: [u8; BUF_SIZE],
 }
@@ -17,7 +23,8 @@ where
 {
     fn clone(&self) -> Self {
         Write {
-            compressor: impls::new_compress(),
+            compressor: impls::new_compress(self.level),
+            level: self.level,
             inner: self.inner.clone(),
             buf: self.buf,
         }
@@ -31,18 +38,25 @@ mod impls {
 
     use crate::zlib::stream::deflate;
 
-    pub(crate) fn new_compress() -> Compress {
-        Compress::new(Compression::fast(), true)
+    pub(crate) fn new_compress(level: u32) -> Compress {
+        Compress::new(Compression::new(level.min(9)), true)
     }
 
     impl<W> deflate::Write<W>
     where
         W: io::Write,
     {
-        /// Create a new instance writing compressed bytes to `inner`.
+        /// Create a new instance writing compressed bytes to `inner`, using the fastest compression level.
         pub fn new(inner: W) -> deflate::Write<W> {
+            Self::new_with_level(inner, deflate::LEVEL_FAST)
+        }
+
+        /// Create a new instance writing compressed bytes to `inner`, compressed with `level` between 0 (no compression)
+        /// and 9 (best compression). Higher values are treated like 9.
+        pub fn new_with_level(inner: W, level: u32) -> deflate::Write<W> {
             deflate::Write {
-                compressor: new_compress(),
+                compressor: new_compress(level),
+                level,
                 inner,
                 buf: [0; deflate::BUF_SIZE],
             }
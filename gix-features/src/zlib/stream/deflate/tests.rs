@@ -98,4 +98,24 @@ mod deflate_stream {
 
         assert_deflate_buffer(w.inner, bytes)
     }
+
+    #[test]
+    fn compression_levels() -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = (0..10_000u64)
+            .map(|n| format!("{n} squared is {} and {n} cubed is {}\n", n * n, n * n * n))
+            .collect::<String>();
+        let bytes = bytes.as_bytes();
+        let mut sizes = Vec::new();
+        for level in [0, deflate::LEVEL_FAST, deflate::LEVEL_DEFAULT, 9, 42] {
+            let mut w = deflate::Write::new_with_level(Vec::new(), level).clone();
+            w.write_all(bytes)?;
+            w.flush()?;
+            sizes.push(w.inner.len());
+            assert_deflate_buffer(w.inner, bytes)?;
+        }
+        assert!(sizes[0] > bytes.len(), "level 0 only stores the data");
+        assert!(sizes[1] > sizes[2], "higher levels compress better");
+        assert_eq!(sizes[3], sizes[4], "levels are capped at 9");
+        Ok(())
+    }
 }
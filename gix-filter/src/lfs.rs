@@ -0,0 +1,202 @@
+use bstr::{BStr, BString, ByteSlice};
+
+/// The version line every `git-lfs` pointer file starts with.
+pub const VERSION: &str = "https://git-lfs.github.com/spec/v1";
+/// The version used by pre-release versions of `git-lfs`, which is still accepted when decoding.
+const LEGACY_VERSION: &str = "https://hawser.github.com/spec/v1";
+/// Pointer files larger than this are never considered pointers, just like `git-lfs` does it.
+pub const MAX_POINTER_SIZE: usize = 1024;
+
+/// The prefix of all object ids in pointer files, as `sha256` is the only supported hash.
+const OID_PREFIX: &str = "sha256:";
+const OID_HEX_LEN: usize = 64;
+
+/// A `git-lfs` pointer file, which is stored in `git` in place of the actual content of a large file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Pointer {
+    /// The hex-encoded `sha256` hash of the actual content, without the `sha256:` prefix.
+    pub oid: BString,
+    /// The size of the actual content in bytes.
+    pub size: u64,
+    /// Extensions that were applied to the content when cleaning it, in order of their priority.
+    pub extensions: Vec<Extension>,
+}
+
+/// An extension of a [`Pointer`], representing a program that transformed the content before it was stored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Extension {
+    /// The priority of the extension from `0` to `9`, which defines the order in which extensions are applied.
+    pub priority: u8,
+    /// The name of the extension.
+    pub name: BString,
+    /// The hex-encoded `sha256` hash of the content that was passed to the extension, without the `sha256:` prefix.
+    pub oid: BString,
+}
+
+///
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`Pointer::from_bytes()`](super::Pointer::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Pointer files can't be larger than {} bytes, got {size}", super::MAX_POINTER_SIZE)]
+        TooLarge { size: usize },
+        #[error("The first line must be the version of the pointer file")]
+        MissingVersion,
+        #[error("Unknown pointer file version '{version}'")]
+        UnknownVersion { version: BString },
+        #[error("Expected a line with a key and value separated by a space, and ending in a newline, got '{line}'")]
+        MalformedLine { line: BString },
+        #[error("Key '{key}' isn't in alphabetical order or was specified more than once")]
+        UnorderedKey { key: BString },
+        #[error("The pointer file lacks the required '{key}' key")]
+        MissingKey { key: &'static str },
+        #[error("Expected 'sha256:' followed by 64 lowercase hex characters, got '{oid}'")]
+        InvalidOid { oid: BString },
+        #[error("Could not parse '{size}' as size")]
+        InvalidSize { size: BString },
+        #[error("Extension key '{key}' isn't of the form 'ext-<priority>-<name>'")]
+        InvalidExtension { key: BString },
+    }
+}
+
+/// Return `true` if `data` is a valid `git-lfs` pointer file.
+pub fn is_pointer(data: &[u8]) -> bool {
+    Pointer::from_bytes(data).is_ok()
+}
+
+/// Decoding and encoding
+impl Pointer {
+    /// Decode `data` as pointer file in the format written by `git-lfs`, failing if it's not a pointer file.
+    ///
+    /// Keys other than `version`, `oid`, `size` and extensions are ignored, but all keys after the version must be
+    /// in alphabetical order.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, decode::Error> {
+        use decode::Error;
+        if data.len() > MAX_POINTER_SIZE {
+            return Err(Error::TooLarge { size: data.len() });
+        }
+
+        let mut lines = data.lines_with_terminator().map(|line| {
+            line.strip_suffix(b"\n")
+                .and_then(|line| line.split_once_str(b" "))
+                .map(|(key, value)| (key.as_bstr(), value.as_bstr()))
+                .ok_or_else(|| Error::MalformedLine { line: line.into() })
+        });
+        match lines.next().transpose()? {
+            Some((key, version)) if key == "version" => {
+                if version != VERSION && version != LEGACY_VERSION {
+                    return Err(Error::UnknownVersion {
+                        version: version.to_owned(),
+                    });
+                }
+            }
+            _ => return Err(Error::MissingVersion),
+        }
+
+        let mut previous_key: Option<&BStr> = None;
+        let mut oid = None;
+        let mut size = None;
+        let mut extensions = Vec::new();
+        for line in lines {
+            let (key, value) = line?;
+            if previous_key.map_or(false, |previous| previous >= key) {
+                return Err(Error::UnorderedKey { key: key.to_owned() });
+            }
+            previous_key = Some(key);
+
+            match key.as_bytes() {
+                b"oid" => oid = Some(parse_oid(value)?),
+                b"size" => {
+                    size = Some(
+                        value
+                            .to_str()
+                            .ok()
+                            .filter(|size| !size.starts_with('+'))
+                            .and_then(|size| size.parse().ok())
+                            .ok_or_else(|| Error::InvalidSize { size: value.to_owned() })?,
+                    )
+                }
+                _ => {
+                    if let Some(ext) = key.strip_prefix(b"ext-") {
+                        let invalid = || Error::InvalidExtension { key: key.to_owned() };
+                        let (priority, name) = ext.split_once_str(b"-").ok_or_else(invalid)?;
+                        let priority = match priority {
+                            [digit @ b'0'..=b'9'] => digit - b'0',
+                            _ => return Err(invalid()),
+                        };
+                        if name.is_empty() {
+                            return Err(invalid());
+                        }
+                        extensions.push(Extension {
+                            priority,
+                            name: name.into(),
+                            oid: parse_oid(value)?,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Pointer {
+            oid: oid.ok_or(Error::MissingKey { key: "oid" })?,
+            size: size.ok_or(Error::MissingKey { key: "size" })?,
+            extensions,
+        })
+    }
+
+    /// Write this instance to `out` in the format used by `git-lfs`, with the current [version](VERSION).
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        out.write_all(b"version ")?;
+        out.write_all(VERSION.as_bytes())?;
+        out.write_all(b"\n")?;
+        let mut extensions: Vec<_> = self.extensions.iter().collect();
+        extensions.sort_by_key(|ext| ext.priority);
+        for ext in extensions {
+            writeln!(out, "ext-{}-{} {OID_PREFIX}{}", ext.priority, ext.name, ext.oid)?;
+        }
+        writeln!(out, "oid {OID_PREFIX}{}", self.oid)?;
+        writeln!(out, "size {}", self.size)
+    }
+}
+
+fn parse_oid(value: &BStr) -> Result<BString, decode::Error> {
+    value
+        .strip_prefix(OID_PREFIX.as_bytes())
+        .filter(|hex| hex.len() == OID_HEX_LEN && hex.iter().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')))
+        .map(Into::into)
+        .ok_or_else(|| decode::Error::InvalidOid { oid: value.to_owned() })
+}
+
+///
+pub mod resolve {
+    /// The error returned by a [`Resolver`](super::Resolver).
+    pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+}
+
+/// A type to obtain the actual content of `git-lfs` pointers, and to turn content back into pointers.
+///
+/// If set in the [pipeline options](crate::pipeline::Options::lfs_resolver), it's used for all paths that have
+/// the `filter=lfs` attribute set, in place of the `lfs` filter driver, if one is configured at all.
+/// This allows applications to handle `git-lfs` content without having `git-lfs` installed, for example by
+/// downloading objects themselves or by reading them from a local store.
+pub trait Resolver: Send + Sync {
+    /// Write the content that `pointer` (read from the blob at `rela_path`) refers to into `out`, which is empty,
+    /// and return `true`, or return `false` to leave the pointer as is.
+    ///
+    /// If `false` is returned, an `lfs` filter driver is used if one is configured.
+    fn smudge(&self, pointer: &Pointer, rela_path: &BStr, out: &mut Vec<u8>) -> Result<bool, resolve::Error>;
+
+    /// Write the pointer for `content` (read from the worktree file at `rela_path`) into `out`, which is empty,
+    /// and return `true`, or return `false` to leave `content` as is.
+    ///
+    /// This is used when the worktree is compared to the index, for example, to be able to see that the content is
+    /// unchanged. The default implementation returns `false`, which leaves cleaning to an `lfs` filter driver if one
+    /// is configured.
+    fn clean(&self, content: &[u8], rela_path: &BStr, out: &mut Vec<u8>) -> Result<bool, resolve::Error> {
+        let _ = (content, rela_path, out);
+        Ok(false)
+    }
+}
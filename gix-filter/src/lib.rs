@@ -28,6 +28,9 @@ pub mod worktree;
 /// use filter programs to perform any kind of conversion.
 pub mod driver;
 
+/// parse and write `git-lfs` pointer files, and resolve them without the `git-lfs` filter program.
+pub mod lfs;
+
 ///
 pub mod pipeline;
 
@@ -2,7 +2,7 @@ use std::{io::Read, path::Path};
 
 use bstr::BStr;
 
-use crate::{driver, eol, ident, pipeline::util::Configuration, worktree, Pipeline};
+use crate::{driver, eol, ident, lfs, pipeline::util::Configuration, worktree, Pipeline};
 
 ///
 pub mod configuration {
@@ -38,6 +38,8 @@ pub mod to_git {
         Configuration(#[from] super::configuration::Error),
         #[error("Copy of driver process output to memory failed")]
         ReadProcessOutputToBuffer(#[from] std::io::Error),
+        #[error("The git-lfs resolver failed to turn content into a pointer")]
+        LfsResolver(#[source] crate::lfs::resolve::Error),
     }
 }
 
@@ -53,6 +55,8 @@ pub mod to_worktree {
         Driver(#[from] crate::driver::apply::Error),
         #[error(transparent)]
         Configuration(#[from] super::configuration::Error),
+        #[error("The git-lfs resolver failed to obtain the content of a pointer")]
+        LfsResolver(#[source] crate::lfs::resolve::Error),
     }
 }
 
@@ -79,6 +83,7 @@ impl Pipeline {
             _attr_digest: _,
             encoding,
             apply_ident_filter,
+            is_lfs,
         } = Configuration::at_path(
             bstr_path.as_ref(),
             &self.options.drivers,
@@ -88,6 +93,18 @@ impl Pipeline {
         )?;
 
         let mut in_buffer = false;
+        if let Some(resolver) = self.options.lfs_resolver.as_deref().filter(|_| is_lfs) {
+            self.bufs.clear();
+            src.read_to_end(&mut self.bufs.src)?;
+            if resolver
+                .clean(&self.bufs.src, bstr_path.as_ref(), &mut self.bufs.dest)
+                .map_err(to_git::Error::LfsResolver)?
+            {
+                self.bufs.swap();
+                return Ok(ToGitOutcome::Buffer(&self.bufs.src));
+            }
+            in_buffer = true;
+        }
         // this is just an approximation, but it's as good as it gets without reading the actual input.
         let would_convert_eol = eol::convert_to_git(
             b"\r\n",
@@ -101,20 +118,23 @@ impl Pipeline {
         )?;
 
         if let Some(driver) = driver {
-            if let Some(mut read) = self.processes.apply(
-                driver,
-                &mut src,
-                driver::Operation::Clean,
-                self.context.with_path(bstr_path.as_ref()),
-            )? {
+            let ctx = self.context.with_path(bstr_path.as_ref());
+            let maybe_read = if in_buffer {
+                self.processes
+                    .apply(driver, &mut self.bufs.src.as_slice(), driver::Operation::Clean, ctx)?
+            } else {
+                self.processes.apply(driver, &mut src, driver::Operation::Clean, ctx)?
+            };
+            if let Some(mut read) = maybe_read {
                 if !apply_ident_filter && encoding.is_none() && !would_convert_eol {
                     // Note that this is not typically a benefit in terms of saving memory as most filters
                     // aren't expected to make the output file larger. It's more about who is waiting for the filter's
                     // output to arrive, which won't be us now. For `git-lfs` it definitely won't matter though.
                     return Ok(ToGitOutcome::Process(read));
                 }
-                self.bufs.clear();
-                read.read_to_end(&mut self.bufs.src)?;
+                self.bufs.dest.clear();
+                read.read_to_end(&mut self.bufs.dest)?;
+                self.bufs.swap();
                 in_buffer = true;
             }
         }
@@ -181,6 +201,7 @@ impl Pipeline {
             _attr_digest: _,
             encoding,
             apply_ident_filter,
+            is_lfs,
         } = Configuration::at_path(
             rela_path,
             &self.options.drivers,
@@ -189,6 +210,19 @@ impl Pipeline {
             self.options.eol_config,
         )?;
 
+        if let Some(resolver) = self.options.lfs_resolver.as_deref().filter(|_| is_lfs) {
+            if let Ok(pointer) = lfs::Pointer::from_bytes(src) {
+                self.bufs.clear();
+                if resolver
+                    .smudge(&pointer, rela_path, &mut self.bufs.src)
+                    .map_err(to_worktree::Error::LfsResolver)?
+                {
+                    // The resolved content is what the `lfs` driver would have produced, so no other filter applies.
+                    return Ok(ToWorktreeOutcome::Buffer(&self.bufs.src));
+                }
+            }
+        }
+
         let mut bufs = self.bufs.use_foreign_src(src);
         let (src, dest) = bufs.src_and_dest();
         if apply_ident_filter && ident::apply(src, self.options.object_hash, dest) {
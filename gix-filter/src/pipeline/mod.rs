@@ -1,6 +1,6 @@
 use bstr::BString;
 
-use crate::{driver, eol, Driver, Pipeline};
+use crate::{driver, eol, lfs, Driver, Pipeline};
 
 /// Define how to perform CRLF round-trip checking when converting to git.
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
@@ -29,6 +29,9 @@ pub struct Options {
     pub encodings_with_roundtrip_check: Vec<&'static encoding_rs::Encoding>,
     /// The object hash to use when applying the `ident` filter.
     pub object_hash: gix_hash::Kind,
+    /// If set, it's used to smudge and clean `git-lfs` pointers of paths with the `filter=lfs` attribute, taking precedence
+    /// over the `lfs` driver in [`drivers`](Self::drivers).
+    pub lfs_resolver: Option<std::sync::Arc<dyn lfs::Resolver>>,
 }
 
 /// Context that typically doesn't change throughout the lifetime of a pipeline, for use with `process` filters.
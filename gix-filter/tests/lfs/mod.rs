@@ -0,0 +1,135 @@
+use bstr::ByteSlice;
+use gix_filter::lfs::{self, decode::Error, Extension, Pointer};
+
+const OID: &str = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+const EXT_OID: &str = "2f6dc6b5fc4b1b62d29e0d6b5a5c0f5cbf34c1fcda8c6e4e9a1c4e9c8aef1f8d";
+
+pub(crate) fn pointer_file() -> String {
+    format!("version https://git-lfs.github.com/spec/v1\noid sha256:{OID}\nsize 12345\n")
+}
+
+mod decode {
+    use super::*;
+
+    #[test]
+    fn pointer() {
+        let pointer = Pointer::from_bytes(pointer_file().as_bytes()).expect("valid");
+        assert_eq!(pointer.oid, OID);
+        assert_eq!(pointer.size, 12345);
+        assert!(pointer.extensions.is_empty());
+        assert!(lfs::is_pointer(pointer_file().as_bytes()));
+    }
+
+    #[test]
+    fn pointer_with_extensions_and_unknown_keys() {
+        let input = format!(
+            "version https://git-lfs.github.com/spec/v1\next-0-foo sha256:{EXT_OID}\next-1-bar sha256:{OID}\nnew value\noid sha256:{OID}\nsize 0\n"
+        );
+        let pointer = Pointer::from_bytes(input.as_bytes()).expect("valid");
+        assert_eq!(
+            pointer.extensions,
+            [
+                Extension {
+                    priority: 0,
+                    name: "foo".into(),
+                    oid: EXT_OID.into()
+                },
+                Extension {
+                    priority: 1,
+                    name: "bar".into(),
+                    oid: OID.into()
+                }
+            ]
+        );
+        assert_eq!(pointer.size, 0);
+    }
+
+    #[test]
+    fn legacy_version() {
+        let input = pointer_file().replace("git-lfs", "hawser");
+        assert!(
+            lfs::is_pointer(input.as_bytes()),
+            "pre-release pointers are still valid"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for (input, expected) in [
+            ("".to_string(), "MissingVersion"),
+            ("not a pointer file at all".into(), "MalformedLine"),
+            (pointer_file().trim_end().into(), "MalformedLine"),
+            (pointer_file().replace("spec/v1", "spec/v2"), "UnknownVersion"),
+            (pointer_file().replace("size 12345\n", ""), "MissingKey"),
+            (pointer_file().replace("sha256:", "sha1:"), "InvalidOid"),
+            (pointer_file().replace(OID, &OID.to_uppercase()), "InvalidOid"),
+            (pointer_file().replace("12345", "-1"), "InvalidSize"),
+            (
+                format!("version https://git-lfs.github.com/spec/v1\nsize 1\noid sha256:{OID}\n"),
+                "UnorderedKey",
+            ),
+            (
+                format!(
+                    "version https://git-lfs.github.com/spec/v1\next-10-foo sha256:{OID}\noid sha256:{OID}\nsize 1\n"
+                ),
+                "InvalidExtension",
+            ),
+            (format!("{}{}", pointer_file(), " ".repeat(1024)), "TooLarge"),
+        ] {
+            let err = Pointer::from_bytes(input.as_bytes()).unwrap_err();
+            assert!(
+                format!("{err:?}").starts_with(expected),
+                "{input:?}: expected {expected}, got {err:?}"
+            );
+        }
+        assert!(matches!(
+            Pointer::from_bytes(b"version https://git-lfs.github.com/spec/v1\n"),
+            Err(Error::MissingKey { key: "oid" })
+        ));
+    }
+}
+
+mod write_to {
+    use super::*;
+
+    #[test]
+    fn round_trips_with_extensions_in_order_of_priority() -> crate::Result {
+        let pointer = Pointer {
+            oid: OID.into(),
+            size: 42,
+            extensions: vec![
+                Extension {
+                    priority: 2,
+                    name: "second".into(),
+                    oid: OID.into(),
+                },
+                Extension {
+                    priority: 1,
+                    name: "first".into(),
+                    oid: EXT_OID.into(),
+                },
+            ],
+        };
+        let mut buf = Vec::new();
+        pointer.write_to(&mut buf)?;
+        assert_eq!(
+            buf.as_bstr(),
+            format!(
+                "version https://git-lfs.github.com/spec/v1\next-1-first sha256:{EXT_OID}\next-2-second sha256:{OID}\noid sha256:{OID}\nsize 42\n"
+            )
+        );
+
+        let mut decoded = Pointer::from_bytes(&buf)?;
+        decoded.extensions.reverse();
+        assert_eq!(decoded, pointer);
+
+        buf.clear();
+        Pointer::from_bytes(pointer_file().as_bytes())?.write_to(&mut buf)?;
+        assert_eq!(
+            buf.as_bstr(),
+            pointer_file(),
+            "the format matches what `git-lfs` writes"
+        );
+        Ok(())
+    }
+}
@@ -3,7 +3,10 @@ use std::{io::Read, path::Path};
 use bstr::ByteSlice;
 use gix_filter::{eol, pipeline::CrlfRoundTripCheck};
 
-use crate::{driver::apply::driver_with_process, pipeline::pipeline};
+use crate::{
+    driver::apply::driver_with_process,
+    pipeline::{pipeline, LfsStore},
+};
 
 #[test]
 fn no_driver_but_filter_with_autocrlf() -> gix_testtools::Result {
@@ -137,3 +140,34 @@ fn no_call(_buf: &mut Vec<u8>) -> Result<Option<()>, Box<dyn std::error::Error +
 fn no_object_in_index(_buf: &mut Vec<u8>) -> Result<Option<()>, Box<dyn std::error::Error + Send + Sync>> {
     Ok(None)
 }
+
+#[test]
+fn lfs_content_is_turned_into_pointers_by_the_resolver_or_the_driver() -> gix_testtools::Result {
+    let driver = gix_filter::Driver {
+        name: "lfs".into(),
+        ..driver_with_process()
+    };
+    let (mut cache, mut pipe) = LfsStore::pipeline(vec![driver])?;
+    let pointer = crate::lfs::pointer_file();
+    for (path, input, expected) in [
+        ("a.bin", LfsStore::CONTENT, pointer.as_str()),
+        ("a.bin", "➡other", "other"),
+        ("a.txt", LfsStore::CONTENT, LfsStore::CONTENT),
+    ] {
+        let mut out = pipe.convert_to_git(
+            input.as_bytes(),
+            Path::new(path),
+            &mut |path, attrs| {
+                cache
+                    .at_entry(path, Some(false), &gix_object::find::Never)
+                    .expect("cannot fail")
+                    .matching_attributes(attrs);
+            },
+            &mut no_object_in_index,
+        )?;
+        let mut buf = Vec::new();
+        out.read_to_end(&mut buf)?;
+        assert_eq!(buf.as_bstr(), expected, "{path}: {input}");
+    }
+    Ok(())
+}
@@ -3,7 +3,10 @@ use std::io::Read;
 use bstr::ByteSlice;
 use gix_filter::pipeline::CrlfRoundTripCheck;
 
-use crate::{driver::apply::driver_with_process, pipeline::pipeline};
+use crate::{
+    driver::apply::driver_with_process,
+    pipeline::{pipeline, LfsStore},
+};
 
 #[test]
 fn all_stages() -> gix_testtools::Result {
@@ -98,3 +101,28 @@ fn no_filter() -> gix_testtools::Result {
     assert_eq!(actual.as_ptr(), input.as_ptr(), "…which means it's exactly the same");
     Ok(())
 }
+
+#[test]
+fn lfs_pointers_are_resolved_if_the_filter_attribute_is_set() -> gix_testtools::Result {
+    let (mut cache, mut pipe) = LfsStore::pipeline(vec![])?;
+    let pointer = crate::lfs::pointer_file();
+    for (path, input, expected) in [
+        ("a.bin", pointer.as_str(), LfsStore::CONTENT),
+        ("a.txt", pointer.as_str(), pointer.as_str()),
+        ("unknown.bin", "not a pointer", "not a pointer"),
+    ] {
+        let out = pipe.convert_to_worktree(
+            input.as_bytes(),
+            path.into(),
+            &mut |path, attrs| {
+                cache
+                    .at_entry(path, Some(false), &gix_object::find::Never)
+                    .expect("cannot fail")
+                    .matching_attributes(attrs);
+            },
+            gix_filter::driver::apply::Delay::Forbid,
+        )?;
+        assert_eq!(out.as_bytes().expect("no driver").as_bstr(), expected, "{path}");
+    }
+    Ok(())
+}
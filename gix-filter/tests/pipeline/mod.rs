@@ -65,7 +65,52 @@ fn pipeline(
             encodings_with_roundtrip_check,
             crlf_roundtrip_check,
             object_hash: gix_hash::Kind::Sha1,
+            lfs_resolver: None,
         },
     );
     Ok((cache, pipe))
 }
+
+/// A store with a single object whose content is `content`, for use as `git-lfs` resolver.
+struct LfsStore;
+
+impl LfsStore {
+    const CONTENT: &'static str = "content";
+
+    fn pipeline(
+        drivers: Vec<gix_filter::Driver>,
+    ) -> gix_testtools::Result<(gix_worktree::Stack, gix_filter::Pipeline)> {
+        let (cache, mut pipe) = pipeline("lfs", || (drivers, Vec::new(), Default::default(), Default::default()))?;
+        pipe.options_mut().lfs_resolver = Some(std::sync::Arc::new(LfsStore));
+        Ok((cache, pipe))
+    }
+}
+
+impl gix_filter::lfs::Resolver for LfsStore {
+    fn smudge(
+        &self,
+        pointer: &gix_filter::lfs::Pointer,
+        _rela_path: &bstr::BStr,
+        out: &mut Vec<u8>,
+    ) -> Result<bool, gix_filter::lfs::resolve::Error> {
+        let expected = gix_filter::lfs::Pointer::from_bytes(crate::lfs::pointer_file().as_bytes())?;
+        if *pointer != expected {
+            return Ok(false);
+        }
+        out.extend_from_slice(Self::CONTENT.as_bytes());
+        Ok(true)
+    }
+
+    fn clean(
+        &self,
+        content: &[u8],
+        _rela_path: &bstr::BStr,
+        out: &mut Vec<u8>,
+    ) -> Result<bool, gix_filter::lfs::resolve::Error> {
+        if content != Self::CONTENT.as_bytes() {
+            return Ok(false);
+        }
+        out.extend_from_slice(crate::lfs::pointer_file().as_bytes());
+        Ok(true)
+    }
+}
@@ -21,6 +21,8 @@ where
     seen: HashSet,
     /// A buffer to keep a single object at a time.
     buf: Vec<u8>,
+    /// Trees that were found but failed validation, along with the reason.
+    invalid_trees: Vec<(ObjectId, gix_object::tree::validate::Error)>,
 }
 
 impl<T, F> Connectivity<T, F>
@@ -35,9 +37,16 @@ where
             missing_cb,
             seen: HashSet::default(),
             buf: Default::default(),
+            invalid_trees: Vec::new(),
         }
     }
 
+    /// Return all trees encountered so far that are present, but malformed as determined by
+    /// [`TreeRef::validate()`](gix_object::TreeRef::validate()), along with the first problem found in each of them.
+    pub fn invalid_trees(&self) -> &[(ObjectId, gix_object::tree::validate::Error)] {
+        &self.invalid_trees
+    }
+
     /// Run the connectivity check on the provided commit `oid`.
     ///
     /// ### Algorithm
@@ -45,6 +54,7 @@ where
     /// Walk the trees and blobs referenced by the commit and verify they exist in the ODB.
     /// Any objects previously encountered by this instance will be skipped silently.
     /// Any referenced blobs that are not present in the ODB will result in a call to the  `missing_cb`.
+    /// Trees that are present are validated, and those that are malformed can be obtained with [`invalid_trees()`](Self::invalid_trees()).
     /// Missing commits or trees will cause an error to be returned.
     ///     - TODO: consider how to handle a missing commit (invoke `missing_cb`, or possibly return a Result?)
     pub fn check_commit(&mut self, oid: &ObjectId) -> Result<(), gix_object::find::existing_object::Error> {
@@ -58,11 +68,9 @@ where
             commit.tree()
         };
 
-        let mut tree_ids = VecDeque::from_iter(Some(tree_id));
+        let mut tree_ids = VecDeque::from_iter(self.seen.insert(tree_id).then_some(tree_id));
         while let Some(tree_id) = tree_ids.pop_front() {
-            if self.seen.insert(tree_id) {
-                self.check_tree(&tree_id, &mut tree_ids);
-            }
+            self.check_tree(&tree_id, &mut tree_ids);
         }
 
         Ok(())
@@ -75,6 +83,9 @@ where
             (self.missing_cb)(oid, Kind::Tree);
             return;
         };
+        if let Err(err) = tree.validate() {
+            self.invalid_trees.push((*oid, err));
+        }
 
         for entry_ref in tree.entries.iter() {
             match entry_ref.mode.kind() {
@@ -76,3 +76,37 @@ fn missing_trees() {
     );
     assert_eq!(check_missing("treeless", all_commits()), expected);
 }
+
+#[test]
+fn invalid_trees() -> gix_testtools::Result {
+    let repo = gix_testtools::scripted_fixture_read_only("make_invalid_trees.sh")?;
+    let read_id =
+        |name: &str| -> std::io::Result<ObjectId> { Ok(hex_to_id(std::fs::read_to_string(repo.join(name))?.trim())) };
+    let mut db = gix_odb::at(repo.join(".git").join("objects"))?;
+    db.refresh_never();
+    let head = read_id(".git/refs/heads/main")?;
+
+    let mut check = Connectivity::new(db, |oid: &ObjectId, kind: Kind| {
+        unreachable!("no object is missing, got {oid} of kind {kind}")
+    });
+    check.check_commit(&head)?;
+
+    let mut invalid = check.invalid_trees().to_vec();
+    invalid.sort_by_key(|(id, _)| *id);
+    let mut expected = vec![
+        (
+            read_id("dot-git-tree")?,
+            gix_object::tree::validate::Error::ReservedName { name: ".git".into() },
+        ),
+        (
+            read_id("duplicate-tree")?,
+            gix_object::tree::validate::Error::Duplicate { name: "a".into() },
+        ),
+    ];
+    expected.sort_by_key(|(id, _)| *id);
+    assert_eq!(
+        invalid, expected,
+        "the root tree is valid, and each malformed tree is reported once"
+    );
+    Ok(())
+}
@@ -0,0 +1,16 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+blob=$(echo "content" | git hash-object -w --stdin)
+
+dot_git_tree=$(printf "100644 blob %s\t.git\n" "$blob" | git mktree)
+duplicate_tree=$(printf "100644 blob %s\ta\n100644 blob %s\ta\n" "$blob" "$blob" | git mktree)
+root_tree=$(printf "040000 tree %s\tdot-git\n040000 tree %s\tduplicates\n100644 blob %s\tvalid\n" "$dot_git_tree" "$duplicate_tree" "$blob" | git mktree)
+
+commit=$(git commit-tree -m "malformed trees" "$root_tree")
+git update-ref refs/heads/main "$commit"
+git symbolic-ref HEAD refs/heads/main
+
+echo "$dot_git_tree" > dot-git-tree
+echo "$duplicate_tree" > duplicate-tree
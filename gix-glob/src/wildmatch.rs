@@ -11,27 +11,29 @@ bitflags! {
     }
 }
 
+pub use compiled::Compiled;
+
 pub(crate) mod function {
     use bstr::{BStr, ByteSlice};
 
     use crate::wildmatch::Mode;
 
     #[derive(Eq, PartialEq)]
-    enum Result {
+    pub(super) enum Result {
         Match,
         NoMatch,
         AbortAll,
         AbortToStarStar,
     }
 
-    const STAR: u8 = b'*';
-    const BACKSLASH: u8 = b'\\';
-    const SLASH: u8 = b'/';
-    const BRACKET_OPEN: u8 = b'[';
-    const BRACKET_CLOSE: u8 = b']';
-    const COLON: u8 = b':';
+    pub(super) const STAR: u8 = b'*';
+    pub(super) const BACKSLASH: u8 = b'\\';
+    pub(super) const SLASH: u8 = b'/';
+    pub(super) const BRACKET_OPEN: u8 = b'[';
+    pub(super) const BRACKET_CLOSE: u8 = b']';
+    pub(super) const COLON: u8 = b':';
 
-    const NEGATE_CLASS: u8 = b'!';
+    pub(super) const NEGATE_CLASS: u8 = b'!';
 
     fn match_recursive(pattern: &BStr, text: &BStr, mode: Mode) -> Result {
         use self::Result::*;
@@ -42,7 +44,15 @@ pub(crate) mod function {
                 *c
             }
         };
-        let mut p = pattern.iter().map(possibly_lowercase).enumerate().peekable();
+        let pattern_from = |start: usize| {
+            pattern
+                .iter()
+                .map(possibly_lowercase)
+                .enumerate()
+                .skip(start)
+                .peekable()
+        };
+        let mut p = pattern_from(0);
         let mut t = text.iter().map(possibly_lowercase).enumerate();
 
         while let Some((mut p_idx, mut p_ch)) = p.next() {
@@ -88,8 +98,12 @@ pub(crate) mod function {
                                         c == SLASH || (c == BACKSLASH && p.peek().map(|t| t.1) == Some(SLASH))
                                     })
                                 {
-                                    if next.map_or(NoMatch, |(idx, _)| {
-                                        match_recursive(pattern[idx + 1..].as_bstr(), text[t_idx..].as_bstr(), mode)
+                                    if next.map_or(NoMatch, |(idx, c)| {
+                                        if c == SLASH {
+                                            match_recursive(pattern[idx + 1..].as_bstr(), text[t_idx..].as_bstr(), mode)
+                                        } else {
+                                            NoMatch
+                                        }
                                     }) == Match
                                     {
                                         return Match;
@@ -170,166 +184,11 @@ pub(crate) mod function {
                     };
                 }
                 BRACKET_OPEN => {
-                    match p.next() {
-                        Some(t) => {
-                            p_idx = t.0;
-                            p_ch = t.1;
-                        }
-                        None => return AbortAll,
+                    let Some((matched, closing_idx)) = match_bracket(pattern, p_idx, t_ch, mode) else {
+                        return AbortAll;
                     };
-
-                    if p_ch == b'^' {
-                        p_ch = NEGATE_CLASS;
-                    }
-                    let negated = p_ch == NEGATE_CLASS;
-                    let mut next = if negated { p.next() } else { Some((p_idx, p_ch)) };
-                    let mut prev_p_ch = 0;
-                    let mut matched = false;
-                    loop {
-                        match next {
-                            None => return AbortAll,
-                            Some((p_idx, mut p_ch)) => match p_ch {
-                                BACKSLASH => match p.next() {
-                                    Some((_, p_ch)) => {
-                                        if p_ch == t_ch {
-                                            matched = true
-                                        } else {
-                                            prev_p_ch = p_ch;
-                                        }
-                                    }
-                                    None => return AbortAll,
-                                },
-                                b'-' if prev_p_ch != 0
-                                    && p.peek().is_some()
-                                    && p.peek().map(|t| t.1) != Some(BRACKET_CLOSE) =>
-                                {
-                                    p_ch = p.next().expect("peeked").1;
-                                    if p_ch == BACKSLASH {
-                                        p_ch = match p.next() {
-                                            Some(t) => t.1,
-                                            None => return AbortAll,
-                                        };
-                                    }
-                                    if t_ch <= p_ch && t_ch >= prev_p_ch {
-                                        matched = true;
-                                    } else if mode.contains(Mode::IGNORE_CASE) && t_ch.is_ascii_lowercase() {
-                                        let t_ch_upper = t_ch.to_ascii_uppercase();
-                                        if (t_ch_upper <= p_ch.to_ascii_uppercase()
-                                            && t_ch_upper >= prev_p_ch.to_ascii_uppercase())
-                                            || (t_ch_upper <= prev_p_ch.to_ascii_uppercase()
-                                                && t_ch_upper >= p_ch.to_ascii_uppercase())
-                                        {
-                                            matched = true;
-                                        }
-                                    }
-                                    prev_p_ch = 0;
-                                }
-                                BRACKET_OPEN if matches!(p.peek(), Some((_, COLON))) => {
-                                    p.next();
-                                    while p.peek().map_or(false, |t| t.1 != BRACKET_CLOSE) {
-                                        p.next();
-                                    }
-                                    let closing_bracket_idx = match p.next() {
-                                        Some((idx, _)) => idx,
-                                        None => return AbortAll,
-                                    };
-                                    const BRACKET__COLON__BRACKET: usize = 3;
-                                    if closing_bracket_idx - p_idx < BRACKET__COLON__BRACKET
-                                        || pattern[closing_bracket_idx - 1] != COLON
-                                    {
-                                        if t_ch == BRACKET_OPEN {
-                                            matched = true
-                                        }
-                                        p = pattern[p_idx + 1..]
-                                            .iter()
-                                            .map(possibly_lowercase)
-                                            .enumerate()
-                                            .peekable();
-                                    } else {
-                                        let class = &pattern.as_bytes()[p_idx + 2..closing_bracket_idx - 1];
-                                        match class {
-                                            b"alnum" => {
-                                                if t_ch.is_ascii_alphanumeric() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"alpha" => {
-                                                if t_ch.is_ascii_alphabetic() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"blank" => {
-                                                if t_ch.is_ascii_whitespace() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"cntrl" => {
-                                                if t_ch.is_ascii_control() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"digit" => {
-                                                if t_ch.is_ascii_digit() {
-                                                    matched = true;
-                                                }
-                                            }
-
-                                            b"graph" => {
-                                                if t_ch.is_ascii_graphic() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"lower" => {
-                                                if t_ch.is_ascii_lowercase() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"print" => {
-                                                if (0x20u8..=0x7e).contains(&t_ch) {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"punct" => {
-                                                if t_ch.is_ascii_punctuation() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"space" => {
-                                                if t_ch == b' ' {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"upper" => {
-                                                if t_ch.is_ascii_uppercase()
-                                                    || mode.contains(Mode::IGNORE_CASE) && t_ch.is_ascii_lowercase()
-                                                {
-                                                    matched = true;
-                                                }
-                                            }
-                                            b"xdigit" => {
-                                                if t_ch.is_ascii_hexdigit() {
-                                                    matched = true;
-                                                }
-                                            }
-                                            _ => return AbortAll,
-                                        };
-                                        prev_p_ch = 0;
-                                    }
-                                }
-                                _ => {
-                                    prev_p_ch = p_ch;
-                                    if p_ch == t_ch {
-                                        matched = true;
-                                    }
-                                }
-                            },
-                        };
-                        next = p.next();
-                        if let Some((_, BRACKET_CLOSE)) = next {
-                            break;
-                        }
-                    }
-                    if matched == negated || mode.contains(Mode::NO_MATCH_SLASH_LITERAL) && t_ch == SLASH {
+                    p = pattern_from(closing_idx + 1);
+                    if !matched || mode.contains(Mode::NO_MATCH_SLASH_LITERAL) && t_ch == SLASH {
                         return NoMatch;
                     }
                     continue;
@@ -346,6 +205,106 @@ pub(crate) mod function {
         t.next().map_or(Match, |_| NoMatch)
     }
 
+    /// Match `t_ch` against the bracket expression whose opening `[` is at `open_idx` in `pattern`, and return whether
+    /// it matched along with the index of its closing `]`, or `None` if the bracket expression is malformed.
+    ///
+    /// `t_ch` is expected to be lower-cased already if `mode` ignores the case. Note that slashes are not handled specially.
+    pub(crate) fn match_bracket(pattern: &[u8], open_idx: usize, t_ch: u8, mode: Mode) -> Option<(bool, usize)> {
+        let ignore_case = mode.contains(Mode::IGNORE_CASE);
+        let at = |idx: usize| {
+            pattern
+                .get(idx)
+                .map(|c| if ignore_case { c.to_ascii_lowercase() } else { *c })
+        };
+        let mut idx = open_idx + 1;
+        let mut p_ch = at(idx)?;
+        if p_ch == b'^' {
+            p_ch = NEGATE_CLASS;
+        }
+        let negated = p_ch == NEGATE_CLASS;
+        if negated {
+            idx += 1;
+            p_ch = at(idx)?;
+        }
+        let mut prev_p_ch = 0;
+        let mut matched = false;
+        loop {
+            match p_ch {
+                BACKSLASH => {
+                    idx += 1;
+                    p_ch = at(idx)?;
+                    if p_ch == t_ch {
+                        matched = true;
+                    }
+                }
+                b'-' if prev_p_ch != 0 && at(idx + 1).map_or(false, |c| c != BRACKET_CLOSE) => {
+                    idx += 1;
+                    p_ch = at(idx)?;
+                    if p_ch == BACKSLASH {
+                        idx += 1;
+                        p_ch = at(idx)?;
+                    }
+                    if t_ch <= p_ch && t_ch >= prev_p_ch {
+                        matched = true;
+                    } else if ignore_case && t_ch.is_ascii_lowercase() {
+                        let t_ch_upper = t_ch.to_ascii_uppercase();
+                        if (t_ch_upper <= p_ch.to_ascii_uppercase() && t_ch_upper >= prev_p_ch.to_ascii_uppercase())
+                            || (t_ch_upper <= prev_p_ch.to_ascii_uppercase() && t_ch_upper >= p_ch.to_ascii_uppercase())
+                        {
+                            matched = true;
+                        }
+                    }
+                    p_ch = 0;
+                }
+                BRACKET_OPEN if at(idx + 1) == Some(COLON) => {
+                    let class_start = idx + 2;
+                    let closing_bracket_idx = class_start + pattern.get(class_start..)?.find_byte(BRACKET_CLOSE)?;
+                    const BRACKET__COLON__BRACKET: usize = 3;
+                    if closing_bracket_idx - idx < BRACKET__COLON__BRACKET || pattern[closing_bracket_idx - 1] != COLON
+                    {
+                        // Not a character class after all, so `[` is a normal character of the set.
+                        if t_ch == BRACKET_OPEN {
+                            matched = true;
+                        }
+                    } else {
+                        let is_member = match &pattern[class_start..closing_bracket_idx - 1] {
+                            b"alnum" => t_ch.is_ascii_alphanumeric(),
+                            b"alpha" => t_ch.is_ascii_alphabetic(),
+                            b"blank" => t_ch == b' ' || t_ch == b'\t',
+                            b"cntrl" => t_ch.is_ascii_control(),
+                            b"digit" => t_ch.is_ascii_digit(),
+                            b"graph" => t_ch.is_ascii_graphic(),
+                            b"lower" => t_ch.is_ascii_lowercase(),
+                            b"print" => (0x20u8..=0x7e).contains(&t_ch),
+                            b"punct" => t_ch.is_ascii_punctuation(),
+                            b"space" => matches!(t_ch, b' ' | b'\t' | b'\n' | b'\r'),
+                            b"upper" => t_ch.is_ascii_uppercase() || ignore_case && t_ch.is_ascii_lowercase(),
+                            b"xdigit" => t_ch.is_ascii_hexdigit(),
+                            _ => return None,
+                        };
+                        if is_member {
+                            matched = true;
+                        }
+                        idx = closing_bracket_idx;
+                        p_ch = 0;
+                    }
+                }
+                _ => {
+                    if p_ch == t_ch {
+                        matched = true;
+                    }
+                }
+            }
+            prev_p_ch = p_ch;
+            idx += 1;
+            p_ch = at(idx)?;
+            if p_ch == BRACKET_CLOSE {
+                break;
+            }
+        }
+        Some((matched != negated, idx))
+    }
+
     /// Employ pattern matching to see if `value` matches `pattern`.
     ///
     /// `mode` can be used to adjust the way the matching is performed.
@@ -353,3 +312,242 @@ pub(crate) mod function {
         match_recursive(pattern, value, mode) == Result::Match
     }
 }
+
+mod compiled {
+    use bstr::{BStr, ByteSlice};
+
+    use crate::wildmatch::{
+        function::{match_bracket, Result, BACKSLASH, BRACKET_OPEN, SLASH, STAR},
+        Mode,
+    };
+
+    /// A set of bytes, with one bit per possible value.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    struct ByteSet([u64; 4]);
+
+    impl ByteSet {
+        fn insert(&mut self, b: u8) {
+            self.0[usize::from(b / 64)] |= 1 << (b % 64);
+        }
+        fn contains(&self, b: u8) -> bool {
+            self.0[usize::from(b / 64)] & (1 << (b % 64)) != 0
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    enum Token {
+        /// A character to match literally, which was preceded by a backslash if `escaped` is `true`.
+        Literal { ch: u8, escaped: bool },
+        /// `?`
+        AnyChar,
+        /// One or more `*`, with `double` being `true` if there are at least two of them.
+        Star { double: bool, after_slash: bool },
+        /// A bracket expression, with all characters it matches in case-sensitive and case-insensitive mode respectively.
+        Bracket { sensitive: ByteSet, ignore_case: ByteSet },
+        /// A backslash at the end of the pattern.
+        DanglingBackslash,
+        /// A malformed bracket expression, which aborts all matching once reached.
+        Abort,
+    }
+
+    /// A pattern for use with [`wildmatch()`](crate::wildmatch()) that was parsed once to be matched against many values.
+    ///
+    /// This avoids parsing escapes and bracket expressions, including character classes like `[[:alpha:]]`, each time
+    /// a value is matched, while producing the same results as [`wildmatch()`](crate::wildmatch()).
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Compiled {
+        tokens: Vec<Token>,
+    }
+
+    impl Compiled {
+        /// Parse `pattern` for later use with [`matches()`](Self::matches()).
+        pub fn new(pattern: &BStr) -> Self {
+            let mut tokens = Vec::new();
+            let mut idx = 0;
+            while let Some(&ch) = pattern.get(idx) {
+                let token = match ch {
+                    BACKSLASH => match pattern.get(idx + 1) {
+                        Some(&ch) => {
+                            idx += 1;
+                            Token::Literal { ch, escaped: true }
+                        }
+                        None => Token::DanglingBackslash,
+                    },
+                    b'?' => Token::AnyChar,
+                    STAR => {
+                        let num_stars = pattern[idx..].iter().take_while(|c| **c == STAR).count();
+                        let after_slash = idx == 0 || pattern[idx - 1] == SLASH;
+                        idx += num_stars - 1;
+                        Token::Star {
+                            double: num_stars > 1,
+                            after_slash,
+                        }
+                    }
+                    BRACKET_OPEN => {
+                        let Some((_, closing_idx)) = match_bracket(pattern, idx, 0, Mode::empty()) else {
+                            tokens.push(Token::Abort);
+                            break;
+                        };
+                        let mut sensitive = ByteSet::default();
+                        let mut ignore_case = ByteSet::default();
+                        for t_ch in 0..=u8::MAX {
+                            if let Some((true, _)) = match_bracket(pattern, idx, t_ch, Mode::empty()) {
+                                sensitive.insert(t_ch);
+                            }
+                            if let Some((true, _)) = match_bracket(pattern, idx, t_ch, Mode::IGNORE_CASE) {
+                                ignore_case.insert(t_ch);
+                            }
+                        }
+                        idx = closing_idx;
+                        Token::Bracket { sensitive, ignore_case }
+                    }
+                    ch => Token::Literal { ch, escaped: false },
+                };
+                tokens.push(token);
+                idx += 1;
+            }
+            Compiled { tokens }
+        }
+
+        /// Return `true` if `value` matches this pattern, with `mode` adjusting the way the matching is performed.
+        pub fn matches(&self, value: &BStr, mode: Mode) -> bool {
+            match_tokens(&self.tokens, value, mode) == Result::Match
+        }
+    }
+
+    fn is_slash(token: Option<&Token>) -> bool {
+        matches!(token, Some(Token::Literal { ch: SLASH, .. }))
+    }
+
+    /// Like `match_recursive()`, but operating on pre-parsed `tokens`.
+    fn match_tokens(tokens: &[Token], text: &[u8], mode: Mode) -> Result {
+        use self::Result::*;
+        let ignore_case = mode.contains(Mode::IGNORE_CASE);
+        let no_match_slash = mode.contains(Mode::NO_MATCH_SLASH_LITERAL);
+        let lowercase = |c: u8| if ignore_case { c.to_ascii_lowercase() } else { c };
+
+        let mut p_idx = 0;
+        let mut t_next = 0;
+        while let Some(token) = tokens.get(p_idx) {
+            p_idx += 1;
+            let (mut t_idx, mut t_ch) = match text.get(t_next) {
+                Some(c) => (t_next, lowercase(*c)),
+                None if !matches!(token, Token::Star { .. }) => return AbortAll,
+                None => (text.len(), 0),
+            };
+            t_next = t_idx + 1;
+
+            match token {
+                Token::Literal { ch, .. } => {
+                    if lowercase(*ch) != t_ch {
+                        return NoMatch;
+                    }
+                }
+                Token::AnyChar => {
+                    if no_match_slash && t_ch == SLASH {
+                        return NoMatch;
+                    }
+                }
+                Token::Bracket {
+                    sensitive,
+                    ignore_case: ignore_case_set,
+                } => {
+                    let set = if ignore_case { ignore_case_set } else { sensitive };
+                    if !set.contains(t_ch) || no_match_slash && t_ch == SLASH {
+                        return NoMatch;
+                    }
+                }
+                Token::DanglingBackslash => return NoMatch,
+                Token::Abort => return AbortAll,
+                Token::Star { double, after_slash } => {
+                    let mut match_slash = !no_match_slash;
+                    if *double && no_match_slash {
+                        let next = tokens.get(p_idx);
+                        if *after_slash && (next.is_none() || is_slash(next)) {
+                            if let Some(Token::Literal {
+                                ch: SLASH,
+                                escaped: false,
+                            }) = next
+                            {
+                                if match_tokens(&tokens[p_idx + 1..], &text[t_idx..], mode) == Match {
+                                    return Match;
+                                }
+                            }
+                            match_slash = true;
+                        } else {
+                            match_slash = false;
+                        }
+                    } else if *double {
+                        match_slash = true;
+                    }
+
+                    let Some(next) = tokens.get(p_idx) else {
+                        return if !match_slash && text[t_idx..].contains(&SLASH) {
+                            NoMatch
+                        } else {
+                            Match
+                        };
+                    };
+                    if !match_slash
+                        && matches!(
+                            next,
+                            Token::Literal {
+                                ch: SLASH,
+                                escaped: false
+                            }
+                        )
+                    {
+                        match text[t_idx..].find_byte(SLASH) {
+                            Some(distance_to_slash) => {
+                                p_idx += 1;
+                                t_next = t_idx + distance_to_slash + 1;
+                                continue;
+                            }
+                            None => return NoMatch,
+                        }
+                    }
+
+                    return loop {
+                        if let Token::Literal { ch, escaped: false } = next {
+                            let p_ch = lowercase(*ch);
+                            while !((!match_slash && t_ch == SLASH) || t_ch == p_ch) {
+                                match text.get(t_next) {
+                                    Some(c) => {
+                                        t_idx = t_next;
+                                        t_ch = lowercase(*c);
+                                        t_next += 1;
+                                    }
+                                    None => break,
+                                }
+                            }
+                            if t_ch != p_ch {
+                                return NoMatch;
+                            }
+                        }
+                        let res = match_tokens(&tokens[p_idx..], &text[t_idx..], mode);
+                        if res != NoMatch {
+                            if !match_slash || res != AbortToStarStar {
+                                return res;
+                            }
+                        } else if !match_slash && t_ch == SLASH {
+                            return AbortToStarStar;
+                        }
+                        match text.get(t_next) {
+                            Some(c) => {
+                                t_idx = t_next;
+                                t_ch = lowercase(*c);
+                                t_next += 1;
+                            }
+                            None => break AbortAll,
+                        }
+                    };
+                }
+            }
+        }
+        if t_next < text.len() {
+            NoMatch
+        } else {
+            Match
+        }
+    }
+}
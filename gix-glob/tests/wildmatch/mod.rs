@@ -106,6 +106,13 @@ fn corpus() {
         (1,1,1,1, "b", "[a-c[:digit:]x-z]"),
         (1,1,1,1, "y", "[a-c[:digit:]x-z]"),
         (0,0,0,0, "q", "[a-c[:digit:]x-z]"),
+        (1,1,1,1, "\t", "[[:blank:]]"),
+        (0,0,0,0, "\n", "[[:blank:]]"),
+        (1,1,1,1, "\t", "[[:space:]]"),
+        (1,1,1,1, "\n", "[[:space:]]"),
+        (0,0,0,0, "\x0b", "[[:space:]]"),
+        (1,1,1,1, "a", "[^[:space:][:digit:]]"),
+        (0,0,0,0, "5", "[![:space:][:digit:]]"),
 
         // Additional tests, including some malformed wild(patterns
         (1,1,1,1, "]", r"[\\-^]"),
@@ -209,8 +216,21 @@ fn corpus() {
     ];
 
     let mut failures = Vec::new();
+    let mut compiled_failures = Vec::new();
     let mut at_least_one_panic = 0;
     for (path_match, path_imatch, glob_match, glob_imatch, text, pattern_text) in tests {
+        let compiled = wildmatch::Compiled::new(pattern_text.into());
+        for mode in [
+            wildmatch::Mode::empty(),
+            wildmatch::Mode::IGNORE_CASE,
+            wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+            wildmatch::Mode::all(),
+        ] {
+            let expected = gix_glob::wildmatch(pattern_text.into(), text.into(), mode);
+            if compiled.matches(text.into(), mode) != expected {
+                compiled_failures.push((pattern_text, text, mode, expected));
+            }
+        }
         let (pattern, actual) = multi_match(pattern_text, text);
         let expected = expect_multi(path_match, path_imatch, glob_match, glob_imatch);
 
@@ -223,8 +243,13 @@ fn corpus() {
         }
     }
 
-    dbg!(&failures);
+    dbg!(&failures, &compiled_failures);
     assert_eq!(failures.len(), 0);
+    assert_eq!(
+        compiled_failures.len(),
+        0,
+        "compiled patterns match exactly like their source"
+    );
     assert_eq!(at_least_one_panic, 0, "not a single panic in any invocation");
 
     // TODO: reproduce these
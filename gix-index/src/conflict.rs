@@ -0,0 +1,203 @@
+//! Access and resolve conflicts, which are paths with entries at stage 1, 2 or 3 instead of a single entry at stage 0.
+use bstr::{BStr, BString};
+use gix_hash::ObjectId;
+
+use crate::{
+    entry,
+    extension::resolve_undo::{self, ResolvePath},
+    Entry, State,
+};
+
+/// A conflicted path and the entries of all sides that are present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict<'a> {
+    /// The path of the conflict, relative to the root of the repository.
+    pub path: &'a BStr,
+    /// The entry of the common ancestor at stage 1, or `None` if the path didn't exist there.
+    pub ancestor: Option<&'a Entry>,
+    /// The entry of our side at stage 2, or `None` if we deleted the path.
+    pub ours: Option<&'a Entry>,
+    /// The entry of their side at stage 3, or `None` if they deleted the path.
+    pub theirs: Option<&'a Entry>,
+}
+
+/// The way to resolve a conflict with [`State::resolve_conflict()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the version of our side, similar to `git checkout --ours`.
+    Ours,
+    /// Keep the version of their side, similar to `git checkout --theirs`.
+    Theirs,
+    /// Keep the version of the common ancestor.
+    Ancestor,
+    /// Use the given object, typically the result of a merge of all sides, similar to `git add` after editing a file.
+    Merged {
+        /// The id of the object to use.
+        id: ObjectId,
+        /// The mode of the new entry.
+        mode: entry::Mode,
+    },
+    /// Remove the path from the index, similar to `git rm`.
+    Remove,
+}
+
+/// The error returned by [`State::resolve_conflict()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("There is no conflict at '{path}'")]
+    NoConflict { path: BString },
+    #[error("The conflict at '{path}' has no {side} version to resolve it with")]
+    MissingSide { path: BString, side: &'static str },
+}
+
+/// Conflicts
+impl State {
+    /// Return `true` if there is at least one conflicted path.
+    pub fn has_conflicts(&self) -> bool {
+        self.entries.iter().any(|entry| entry.stage() != 0)
+    }
+
+    /// Return an iterator over all conflicts, in the order of their paths.
+    pub fn conflicts(&self) -> impl Iterator<Item = Conflict<'_>> + '_ {
+        let mut entries = self.entries.iter().filter(|entry| entry.stage() != 0).peekable();
+        std::iter::from_fn(move || {
+            let first = entries.next()?;
+            let path = first.path(self);
+            let mut conflict = Conflict {
+                path,
+                ancestor: None,
+                ours: None,
+                theirs: None,
+            };
+            let mut entry = Some(first);
+            while let Some(current) = entry {
+                conflict.set(current);
+                entry = entries.next_if(|next| next.path(self) == path);
+            }
+            Some(conflict)
+        })
+    }
+
+    /// Return the conflict at `path`, or `None` if `path` isn't conflicted.
+    pub fn conflict_by_path(&self, path: &BStr) -> Option<Conflict<'_>> {
+        let entries = self.entry_range(path).map(|range| &self.entries[range])?;
+        if entries.iter().any(|entry| entry.stage() == 0) {
+            return None;
+        }
+        let mut conflict = Conflict {
+            path: entries.first()?.path(self),
+            ancestor: None,
+            ours: None,
+            theirs: None,
+        };
+        for entry in entries {
+            conflict.set(entry);
+        }
+        Some(conflict)
+    }
+
+    /// Mark `path` as conflicted by replacing all of its entries with entries for the `ancestor`, `ours` and `theirs`
+    /// sides at stage 1, 2 and 3 respectively, omitting the ones that are `None`.
+    ///
+    /// This is what a merge does if it can't resolve the changes to `path`. The [tree extension](Self::tree()) is invalidated
+    /// for `path` as it can't describe conflicts.
+    pub fn set_conflict(
+        &mut self,
+        path: &BStr,
+        ancestor: Option<(ObjectId, entry::Mode)>,
+        ours: Option<(ObjectId, entry::Mode)>,
+        theirs: Option<(ObjectId, entry::Mode)>,
+    ) {
+        self.invalidate_tree_path(path);
+        self.remove_entries(|_, entry_path, _| entry_path == path);
+        for (stage, side) in (1..).zip([ancestor, ours, theirs]) {
+            if let Some((id, mode)) = side {
+                self.dangerously_push_entry(Default::default(), id, entry::Flags::from_stage(stage), mode, path);
+            }
+        }
+        self.sort_entries();
+    }
+
+    /// Resolve the conflict at `path` with `resolution`, replacing the entries of all sides with a single entry at stage 0,
+    /// or with no entry at all for [`Resolution::Remove`].
+    ///
+    /// All sides of the conflict are recorded in the [resolve-undo extension](Self::resolve_undo()) just like `git` does,
+    /// so the conflict can be [restored](Self::restore_conflict()) later, and the [tree extension](Self::tree()) is invalidated
+    /// for `path`.
+    pub fn resolve_conflict(&mut self, path: &BStr, resolution: Resolution) -> Result<(), Error> {
+        let conflict = self
+            .conflict_by_path(path)
+            .ok_or_else(|| Error::NoConflict { path: path.into() })?;
+        let side = |entry: Option<&Entry>, side: &'static str| {
+            entry.map(|entry| (entry.id, entry.mode)).ok_or(Error::MissingSide {
+                path: path.into(),
+                side,
+            })
+        };
+        let resolved = match resolution {
+            Resolution::Ours => Some(side(conflict.ours, "our")?),
+            Resolution::Theirs => Some(side(conflict.theirs, "their")?),
+            Resolution::Ancestor => Some(side(conflict.ancestor, "ancestor")?),
+            Resolution::Merged { id, mode } => Some((id, mode)),
+            Resolution::Remove => None,
+        };
+        let stage = |entry: Option<&Entry>| {
+            entry.map(|entry| resolve_undo::Stage {
+                mode: entry.mode,
+                id: entry.id,
+            })
+        };
+        let undo = ResolvePath {
+            name: path.into(),
+            stages: [stage(conflict.ancestor), stage(conflict.ours), stage(conflict.theirs)],
+        };
+
+        let paths = self.resolve_undo.get_or_insert_with(Vec::new);
+        paths.retain(|resolved| resolved.name != path);
+        paths.push(undo);
+        self.invalidate_tree_path(path);
+        self.remove_entries(|_, entry_path, _| entry_path == path);
+        if let Some((id, mode)) = resolved {
+            self.dangerously_push_entry(Default::default(), id, entry::Flags::empty(), mode, path);
+            self.sort_entries();
+        }
+        Ok(())
+    }
+
+    /// Restore the conflict at `path` from the [resolve-undo extension](Self::resolve_undo()), replacing its current
+    /// entries, similar to `git update-index --unresolve`. Return `false` if there was no resolved conflict for `path`.
+    pub fn restore_conflict(&mut self, path: &BStr) -> bool {
+        let Some(paths) = self.resolve_undo.as_mut() else {
+            return false;
+        };
+        let Some(pos) = paths.iter().position(|resolved| resolved.name == path) else {
+            return false;
+        };
+        let [ancestor, ours, theirs] = paths
+            .remove(pos)
+            .stages
+            .map(|stage| stage.map(|stage| (stage.id, stage.mode)));
+        self.set_conflict(path, ancestor, ours, theirs);
+        true
+    }
+}
+
+impl State {
+    fn invalidate_tree_path(&mut self, path: &BStr) {
+        if let Some(tree) = self.tree.as_mut() {
+            tree.invalidate_path(path);
+        }
+    }
+}
+
+impl<'a> Conflict<'a> {
+    fn set(&mut self, entry: &'a Entry) {
+        match entry.stage() {
+            1 => self.ancestor = Some(entry),
+            2 => self.ours = Some(entry),
+            3 => self.theirs = Some(entry),
+            _ => {}
+        }
+    }
+}
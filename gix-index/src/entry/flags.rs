@@ -65,6 +65,11 @@ impl Flags {
         (*self & Flags::STAGE_MASK).bits() >> 12
     }
 
+    /// Return flags with only the given `stage` set, which is expected to be in the range of 0 to 3.
+    pub fn from_stage(stage: Stage) -> Self {
+        Flags::from_bits_retain(stage << 12) & Flags::STAGE_MASK
+    }
+
     /// Transform ourselves to a storage representation to keep all flags which are to be persisted,
     /// skipping all extended flags. Note that the caller has to check for the `EXTENDED` bit to be present
     /// and write extended flags as well if so.
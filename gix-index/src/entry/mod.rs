@@ -79,6 +79,21 @@ mod access {
         pub fn stage(&self) -> entry::Stage {
             self.flags.stage()
         }
+
+        /// Insert `flags` if `value` is `true` or remove them otherwise, and return `true` if this changed the entry.
+        ///
+        /// The [`EXTENDED`](entry::Flags::EXTENDED) flag is kept in sync so that extended flags like
+        /// [`SKIP_WORKTREE`](entry::Flags::SKIP_WORKTREE) are written along with the entry.
+        pub fn set_flags(&mut self, flags: entry::Flags, value: bool) -> bool {
+            let previous = self.flags;
+            self.flags.set(flags, value);
+            self.flags.set(
+                entry::Flags::EXTENDED,
+                self.flags
+                    .intersects(entry::Flags::INTENT_TO_ADD | entry::Flags::SKIP_WORKTREE),
+            );
+            self.flags != previous
+        }
     }
 }
 
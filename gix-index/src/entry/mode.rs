@@ -21,7 +21,7 @@ impl Mode {
     /// and returns the change needed to update this mode to match the file.
     ///
     /// * if `has_symlinks` is false symlink entries will simply check if there
-    ///   is a normal file on disk
+    ///   is a normal file on disk, but also accept actual symlinks, just like git
     /// * if `executable_bit` is false the executable bit will not be compared
     ///   `Change::ExecutableBit` will never be generated
     ///
@@ -48,9 +48,9 @@ impl Mode {
         executable_bit: bool,
     ) -> Option<Change> {
         match self {
-            Mode::FILE if !stat.is_file() => (),
+            Mode::FILE | Mode::FILE_EXECUTABLE if !stat.is_file() => (),
             Mode::SYMLINK if has_symlinks && !stat.is_symlink() => (),
-            Mode::SYMLINK if !has_symlinks && !stat.is_file() => (),
+            Mode::SYMLINK if !has_symlinks && !stat.is_file() && !stat.is_symlink() => (),
             Mode::COMMIT | Mode::DIR if !stat.is_dir() => (),
             Mode::FILE if executable_bit && stat.is_executable() => return Some(Change::ExecutableBit),
             Mode::FILE_EXECUTABLE if executable_bit && !stat.is_executable() => return Some(Change::ExecutableBit),
@@ -58,6 +58,8 @@ impl Mode {
         };
         let new_mode = if stat.is_dir() {
             Mode::COMMIT
+        } else if has_symlinks && stat.is_symlink() {
+            Mode::SYMLINK
         } else if executable_bit && stat.is_executable() {
             Mode::FILE_EXECUTABLE
         } else {
@@ -68,6 +70,7 @@ impl Mode {
 }
 
 /// A change of a [`Mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Change {
     /// The type of mode changed, like symlink => file.
     Type {
@@ -14,6 +14,9 @@ impl Stat {
     /// The index `timestamp` marks the point in time before which we definitely resolved the racy git problem
     /// for all index entries so any index entries that changed afterwards will need to be examined for
     /// changes by actually reading the file from disk at least once.
+    ///
+    /// If nanoseconds are used and `mtime` has none, it's assumed to be stored with a precision of seconds,
+    /// making it racy if it is within the same second as `timestamp`.
     pub fn is_racy(
         &self,
         timestamp: FileTime,
@@ -23,7 +26,9 @@ impl Stat {
     ) -> bool {
         match timestamp.unix_seconds().cmp(&(self.mtime.secs as i64)) {
             Ordering::Less => true,
-            Ordering::Equal if use_nsec && check_stat => timestamp.nanoseconds() <= self.mtime.nsecs,
+            Ordering::Equal if use_nsec && check_stat && self.mtime.nsecs != 0 => {
+                timestamp.nanoseconds() <= self.mtime.nsecs
+            }
             Ordering::Equal => true,
             Ordering::Greater => false,
         }
@@ -35,6 +40,9 @@ impl Stat {
     /// However there a lot of nobs in git that tweak whether certain stat information is used when checking
     /// equality, see [`Options`].
     /// This function respects those options while performing the stat comparison and may therefore ignore some fields.
+    ///
+    /// Note that nanoseconds are only compared if both timestamps have them, as timestamps without nanoseconds
+    /// are assumed to come from a filesystem or tool that only stores seconds.
     pub fn matches(
         &self,
         other: &Self,
@@ -48,7 +56,7 @@ impl Stat {
         if self.mtime.secs != other.mtime.secs {
             return false;
         }
-        if check_stat && use_nsec && self.mtime.nsecs != other.mtime.nsecs {
+        if check_stat && use_nsec && nsecs_differ(self.mtime, other.mtime) {
             return false;
         }
 
@@ -56,11 +64,11 @@ impl Stat {
             return false;
         }
 
-        if trust_ctime {
+        if trust_ctime && check_stat {
             if self.ctime.secs != other.ctime.secs {
                 return false;
             }
-            if check_stat && use_nsec && self.ctime.nsecs != other.ctime.nsecs {
+            if use_nsec && nsecs_differ(self.ctime, other.ctime) {
                 return false;
             }
         }
@@ -113,6 +121,11 @@ impl Stat {
     }
 }
 
+/// Return `true` if both `a` and `b` have nanoseconds, and these differ.
+fn nsecs_differ(a: Time, b: Time) -> bool {
+    a.nsecs != 0 && b.nsecs != 0 && a.nsecs != b.nsecs
+}
+
 impl TryFrom<SystemTime> for Time {
     type Error = SystemTimeError;
     fn try_from(s: SystemTime) -> Result<Self, SystemTimeError> {
@@ -167,12 +180,13 @@ impl PartialOrd<FileTime> for Time {
 pub struct Options {
     /// If true, a files creation time is taken into consideration when checking if a file changed.
     /// Can be set to false in case other tools alter the creation time in ways that interfere with our operation.
+    /// Note that it's only used if [`check_stat`](Self::check_stat) is `true` as well.
     ///
     /// Default `true`.
     pub trust_ctime: bool,
     /// If true, all stat fields will be used when checking for up-to-date'ness of the entry. Otherwise
-    /// nano-second parts of mtime and ctime,uid, gid, inode and device number _will not_ be used, leaving only
-    /// the whole-second part of ctime and mtime and the file size to be checked.
+    /// nano-second parts of mtime, ctime, uid, gid, inode and device number _will not_ be used, leaving only
+    /// the whole-second part of mtime and the file size to be checked, just like `core.checkStat=minimal` does in `git`.
     ///
     /// Default `true`.
     pub check_stat: bool,
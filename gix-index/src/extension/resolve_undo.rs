@@ -1,32 +1,40 @@
+use std::convert::TryFrom;
+
 use bstr::BString;
 use gix_hash::ObjectId;
 
 use crate::{
+    entry,
     extension::Signature,
     util::{split_at_byte_exclusive, split_at_pos},
 };
 
+/// All paths whose conflicts were resolved, in the order they were recorded.
 pub type Paths = Vec<ResolvePath>;
 
-#[allow(dead_code)]
-#[derive(Clone)]
+/// The stages of a conflict that was resolved, kept to be able to restore the conflict later.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolvePath {
     /// relative to the root of the repository, or what would be stored in the index
-    name: BString,
+    pub name: BString,
 
     /// 0 = ancestor/common, 1 = ours, 2 = theirs
-    stages: [Option<Stage>; 3],
+    pub stages: [Option<Stage>; 3],
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Copy)]
+/// A single side of a resolved conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Stage {
-    mode: u32,
-    id: ObjectId,
+    /// The mode of the entry at this stage.
+    pub mode: entry::Mode,
+    /// The id of the object at this stage.
+    pub id: ObjectId,
 }
 
+/// The signature of the resolve-undo extension.
 pub const SIGNATURE: Signature = *b"REUC";
 
+/// Decode the resolve-undo extension from `data`, or return `None` if it is malformed.
 pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
     let hash_len = object_hash.len_in_bytes();
     let mut out = Vec::new();
@@ -50,7 +58,7 @@ pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
             let (hash, rest) = split_at_pos(data, hash_len)?;
             data = rest;
             *stage = Some(Stage {
-                mode: *mode,
+                mode: entry::Mode::from_bits_retain(*mode),
                 id: ObjectId::from(hash),
             });
         }
@@ -62,3 +70,24 @@ pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
     }
     out.into()
 }
+
+/// Serialize `paths` as resolve-undo extension to `out`.
+pub fn write_to(paths: &[ResolvePath], mut out: impl std::io::Write) -> std::io::Result<()> {
+    let mut data = Vec::new();
+    for path in paths {
+        data.extend_from_slice(&path.name);
+        data.push(0);
+        for stage in &path.stages {
+            let mode = stage.map_or(0, |stage| stage.mode.bits());
+            data.extend_from_slice(format!("{mode:o}").as_bytes());
+            data.push(0);
+        }
+        for stage in path.stages.iter().flatten() {
+            data.extend_from_slice(stage.id.as_bytes());
+        }
+    }
+
+    out.write_all(&SIGNATURE)?;
+    out.write_all(&(u32::try_from(data.len()).expect("less than 4GB resolve-undo extension")).to_be_bytes())?;
+    out.write_all(&data)
+}
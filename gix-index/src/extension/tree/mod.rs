@@ -1,3 +1,5 @@
+use bstr::ByteSlice;
+
 use crate::extension::Signature;
 
 /// The signature for tree extensions
@@ -11,6 +13,22 @@ pub use decode::decode;
 
 mod write;
 
+impl crate::extension::Tree {
+    /// Mark this tree and all trees leading to the directory containing `path` as invalid, so they are recomputed
+    /// before they are used again, similar to what `git` does when changing entries at `path`.
+    ///
+    /// `path` is relative to the root of the repository, like `dir/file`.
+    pub fn invalidate_path(&mut self, path: &bstr::BStr) {
+        self.num_entries = None;
+        self.id = gix_hash::ObjectId::null(self.id.kind());
+        if let Some((dir, rest)) = path.split_once_str("/") {
+            if let Some(child) = self.children.iter_mut().find(|child| child.name.as_slice() == dir) {
+                child.invalidate_path(rest.as_bstr());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
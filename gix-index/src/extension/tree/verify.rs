@@ -66,7 +66,8 @@ impl Tree {
                 }
                 prev = Some(child);
             }
-            if let Some(buf) = object_buf.as_mut() {
+            // Invalidated trees have no id, and thus nothing to compare with.
+            if let Some(buf) = object_buf.as_mut().filter(|_| !parent_id.is_null()) {
                 let tree_entries = objects.find_tree_iter(&parent_id, buf)?;
                 let mut num_entries = 0;
                 for entry in tree_entries.filter_map(Result::ok).filter(|e| e.mode.is_tree()) {
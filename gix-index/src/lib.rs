@@ -22,6 +22,8 @@ pub mod entry;
 
 mod access;
 
+pub mod conflict;
+
 mod init;
 
 ///
@@ -33,6 +35,9 @@ pub mod verify;
 ///
 pub mod write;
 
+///
+pub mod merge;
+
 pub mod fs;
 
 /// All known versions of a git index file.
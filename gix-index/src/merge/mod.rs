@@ -0,0 +1,5 @@
+//! Merge trees into an index, similar to what `git read-tree -m` does.
+
+/// The two-tree merge of `git read-tree -m <current> <target>`.
+pub mod two_way;
+pub use two_way::function::two_way;
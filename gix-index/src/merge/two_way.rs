@@ -0,0 +1,325 @@
+use bstr::{BStr, BString};
+
+use crate::Entry;
+
+/// The error returned by [`two_way()`](crate::merge::two_way()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Sparse indices can't be merged yet")]
+    SparseIndex,
+    #[error("Could not obtain information about the worktree")]
+    Worktree(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+/// The kind of item found in the worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A directory.
+    Directory,
+    /// Anything that isn't a directory, like a file or a symbolic link.
+    File,
+}
+
+/// Provide information about the worktree as needed by [`two_way()`](crate::merge::two_way()).
+pub trait Worktree {
+    /// The error returned by all methods.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Return `true` if the worktree file at `rela_path` still matches `entry` of the index, which is also the case if it doesn't exist.
+    fn is_uptodate(&mut self, rela_path: &BStr, entry: &Entry) -> Result<bool, Self::Error>;
+
+    /// Return the kind of the untracked item at `rela_path`, or `None` if there is nothing or if what's there
+    /// may be overwritten, like ignored files or directories that contain nothing but ignored files or files that are
+    /// tracked in the index.
+    fn untracked_kind(&mut self, rela_path: &BStr) -> Result<Option<Kind>, Self::Error>;
+}
+
+/// Options for use in [`two_way()`](crate::merge::two_way()).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, the index wasn't populated yet, so entries that are only missing in the index are taken from the
+    /// target tree instead of being treated as staged deletions, just like `git read-tree` does for the initial checkout.
+    pub initial_checkout: bool,
+}
+
+/// A change to the worktree that is needed to make it match the merged index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Change {
+    /// The file at `path` has to be removed as it isn't tracked anymore.
+    Remove {
+        /// The path of the file to remove, relative to the worktree root.
+        path: BString,
+    },
+    /// The file at `path` has to be written with the content of the entry of the merged index at the same path.
+    Update {
+        /// The path of the file to write, relative to the worktree root.
+        path: BString,
+    },
+}
+
+/// The reason for a [`Conflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum ConflictKind {
+    /// The index or the worktree has changes that would be lost.
+    LocalChanges,
+    /// An untracked file or directory would be overwritten.
+    Untracked,
+    /// The index entry is unmerged and changes between the trees.
+    Unmerged,
+}
+
+/// A path that can't be merged without losing data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Conflict {
+    /// The path at which the conflict occurred, relative to the worktree root.
+    pub path: BString,
+    /// The reason for the conflict.
+    pub kind: ConflictKind,
+}
+
+/// The outcome of [`two_way()`](crate::merge::two_way()).
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The merged index, which has entries of the target tree unless these were locally modified.
+    ///
+    /// Paths in conflict keep the entries they had in the original index.
+    pub state: crate::State,
+    /// All changes to apply to the worktree to make it match `state`, with all removals before all updates.
+    pub changes: Vec<Change>,
+    /// All paths that can't be merged, sorted by path. If there is at least one, the merge must not be applied.
+    pub conflicts: Vec<Conflict>,
+}
+
+pub(crate) mod function {
+    use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+    use super::{Change, Conflict, ConflictKind, Error, Kind, Options, Outcome, Worktree};
+    use crate::{Entry, State};
+
+    enum Action {
+        /// Keep the entries of the index as they are.
+        Keep,
+        /// Use the entry of the target tree.
+        Take,
+        /// Use the entry of the target tree for a path that isn't tracked yet.
+        Add,
+        /// Remove the path from the index.
+        Remove,
+        /// Keep the entries of the index, but record a conflict.
+        Fail(ConflictKind),
+    }
+
+    /// Merge the `target` tree into `index`, knowing that `index` was based on the `current` tree, to switch from `current`
+    /// to `target` without losing local modifications, similar to `git read-tree -m -u <current> <target>`.
+    ///
+    /// `current` and `target` are typically created with [`State::from_tree()`], while `worktree` is used to learn about
+    /// local modifications and untracked files that would be overwritten.
+    ///
+    /// The rules are the ones of the *two tree merge* as documented for `git read-tree`: paths that are unchanged between
+    /// both trees keep whatever is in the index, and paths that changed are updated unless the index or the worktree
+    /// has modifications of its own, which are returned as [conflicts](Outcome::conflicts).
+    pub fn two_way(
+        index: &State,
+        current: &State,
+        target: &State,
+        worktree: &mut impl Worktree,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        if index.is_sparse() {
+            return Err(Error::SparseIndex);
+        }
+        let mut state = State {
+            object_hash: index.object_hash,
+            timestamp: index.timestamp,
+            version: index.version,
+            entries: Vec::with_capacity(target.entries.len()),
+            path_backing: Vec::with_capacity(target.path_backing.len()),
+            is_sparse: false,
+            tree: None,
+            link: None,
+            resolve_undo: index.resolve_undo.clone(),
+            untracked: None,
+            fs_monitor: None,
+        };
+        let mut removals = Vec::new();
+        let mut updates = Vec::new();
+        let mut added = Vec::new();
+        let mut conflicts = Vec::new();
+
+        let (mut i, mut h, mut m) = (0, 0, 0);
+        while let Some(path) = [
+            index.entries.get(i).map(|e| e.path(index)),
+            current.entries.get(h).map(|e| e.path(current)),
+            target.entries.get(m).map(|e| e.path(target)),
+        ]
+        .into_iter()
+        .flatten()
+        .min_by(|a, b| Entry::cmp_filepaths(a, b))
+        {
+            let ours_end = i + index.entries[i..].iter().take_while(|e| e.path(index) == path).count();
+            let ours = &index.entries[i..ours_end];
+            i = ours_end;
+            let theirs_current = current.entries.get(h).filter(|e| e.path(current) == path);
+            h += usize::from(theirs_current.is_some());
+            let theirs_target = target.entries.get(m).filter(|e| e.path(target) == path);
+            m += usize::from(theirs_target.is_some());
+
+            let action = match (ours, theirs_current, theirs_target) {
+                ([], _, None) => Action::Keep,
+                ([], None, Some(_)) => Action::Add,
+                ([], Some(_), Some(_)) if options.initial_checkout => Action::Add,
+                ([], Some(h), Some(m)) => {
+                    if same(h, m) {
+                        Action::Keep
+                    } else {
+                        Action::Fail(ConflictKind::LocalChanges)
+                    }
+                }
+                ([ours], h, m) if ours.stage() == 0 => match (h, m) {
+                    (None, None) => Action::Keep,
+                    (None, Some(m)) => {
+                        if same(ours, m) {
+                            Action::Keep
+                        } else {
+                            Action::Fail(ConflictKind::LocalChanges)
+                        }
+                    }
+                    (Some(h), None) => {
+                        if same(ours, h) && is_uptodate(worktree, path, ours)? {
+                            Action::Remove
+                        } else {
+                            Action::Fail(ConflictKind::LocalChanges)
+                        }
+                    }
+                    (Some(h), Some(m)) => {
+                        if same(h, m) || same(ours, m) {
+                            Action::Keep
+                        } else if same(ours, h) && is_uptodate(worktree, path, ours)? {
+                            Action::Take
+                        } else {
+                            Action::Fail(ConflictKind::LocalChanges)
+                        }
+                    }
+                },
+                (_unmerged, h, m) => match (h, m) {
+                    (None, None) => Action::Remove,
+                    (Some(h), Some(m)) if same(h, m) => Action::Take,
+                    _ => Action::Fail(ConflictKind::Unmerged),
+                },
+            };
+
+            match action {
+                Action::Keep => {
+                    for entry in ours {
+                        push_entry(&mut state, entry, path);
+                    }
+                }
+                Action::Take | Action::Add => {
+                    let theirs = theirs_target.expect("only taken if present");
+                    match ours {
+                        [ours] if ours.stage() == 0 && same(ours, theirs) => push_entry(&mut state, ours, path),
+                        _ => {
+                            push_entry(&mut state, theirs, path);
+                            updates.push(Change::Update { path: path.to_owned() });
+                        }
+                    }
+                    if matches!(action, Action::Add) {
+                        added.push(path.to_owned());
+                    }
+                }
+                Action::Remove => {
+                    if !ours.is_empty() {
+                        removals.push(Change::Remove { path: path.to_owned() });
+                    }
+                }
+                Action::Fail(kind) => {
+                    for entry in ours {
+                        push_entry(&mut state, entry, path);
+                    }
+                    conflicts.push(Conflict {
+                        path: path.to_owned(),
+                        kind,
+                    });
+                }
+            }
+        }
+
+        for path in added {
+            if let Some(conflict) = check_added_path(index, &state, path, worktree)? {
+                conflicts.push(conflict);
+            }
+        }
+        conflicts.sort();
+        conflicts.dedup();
+
+        removals.extend(updates);
+        Ok(Outcome {
+            state,
+            changes: removals,
+            conflicts,
+        })
+    }
+
+    /// Check if `path`, which wasn't tracked in `index`, can be written to the worktree without clashing with
+    /// tracked entries in the merged `state` or overwriting untracked files.
+    fn check_added_path(
+        index: &State,
+        state: &State,
+        path: BString,
+        worktree: &mut impl Worktree,
+    ) -> Result<Option<Conflict>, Error> {
+        for pos in path.find_iter(b"/") {
+            let leading_dir = path[..pos].as_bstr();
+            if state.entry_by_path(leading_dir).is_some() {
+                return Ok(Some(Conflict {
+                    path: leading_dir.to_owned(),
+                    kind: ConflictKind::LocalChanges,
+                }));
+            }
+            if index.entry_by_path(leading_dir).is_none() && untracked_kind(worktree, leading_dir)? == Some(Kind::File)
+            {
+                return Ok(Some(Conflict {
+                    path: leading_dir.to_owned(),
+                    kind: ConflictKind::Untracked,
+                }));
+            }
+        }
+
+        let mut dir = path.clone();
+        dir.push_byte(b'/');
+        if index.prefixed_entries(dir.as_bstr()).is_some() && state.prefixed_entries(dir.as_bstr()).is_some() {
+            return Ok(Some(Conflict {
+                path,
+                kind: ConflictKind::LocalChanges,
+            }));
+        }
+        Ok(untracked_kind(worktree, path.as_bstr())?.map(|_| Conflict {
+            path,
+            kind: ConflictKind::Untracked,
+        }))
+    }
+
+    fn same(a: &Entry, b: &Entry) -> bool {
+        a.id == b.id && a.mode == b.mode
+    }
+
+    fn push_entry(state: &mut State, entry: &Entry, path: &BStr) {
+        let start = state.path_backing.len();
+        state.path_backing.extend_from_slice(path);
+        state.entries.push(Entry {
+            path: start..state.path_backing.len(),
+            ..entry.clone()
+        });
+    }
+
+    fn is_uptodate(worktree: &mut impl Worktree, path: &BStr, entry: &Entry) -> Result<bool, Error> {
+        worktree
+            .is_uptodate(path, entry)
+            .map_err(|err| Error::Worktree(err.into()))
+    }
+
+    fn untracked_kind(worktree: &mut impl Worktree, path: &BStr) -> Result<Option<Kind>, Error> {
+        worktree.untracked_kind(path).map_err(|err| Error::Worktree(err.into()))
+    }
+}
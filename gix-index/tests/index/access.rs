@@ -176,3 +176,55 @@ fn check_prefix(index: &gix_index::State, prefix: &str, expected: &[&str]) {
         "{prefix:?}"
     );
 }
+
+#[test]
+fn conflicts_can_be_resolved_and_restored() {
+    use gix_index::conflict::Resolution;
+
+    let mut file = Fixture::Loose("conflicting-file").open();
+    assert!(file.has_conflicts());
+    let conflicts: Vec<_> = file.conflicts().collect();
+    assert_eq!(conflicts.len(), 1, "all stages of a path form a single conflict");
+    let conflict = conflicts[0];
+    assert_eq!(conflict.path, "file");
+    assert_eq!(file.conflict_by_path("file".into()), Some(conflict));
+    let (ours, theirs) = (
+        conflict.ours.expect("present").clone(),
+        conflict.theirs.expect("present").clone(),
+    );
+
+    assert!(matches!(
+        file.resolve_conflict("other".into(), Resolution::Ours),
+        Err(gix_index::conflict::Error::NoConflict { .. })
+    ));
+    file.resolve_conflict("file".into(), Resolution::Theirs)
+        .expect("conflict exists");
+    assert!(!file.has_conflicts());
+    assert_eq!(file.entries().len(), 1);
+    let entry = file.entry_by_path_and_stage("file".into(), 0).expect("resolved");
+    assert_eq!((entry.id, entry.mode), (theirs.id, theirs.mode));
+    assert_eq!(file.conflict_by_path("file".into()), None);
+
+    let undo = file.resolve_undo().expect("recorded");
+    assert_eq!(undo.len(), 1);
+    assert_eq!(undo[0].name, "file");
+    assert_eq!(undo[0].stages[1].map(|stage| stage.id), Some(ours.id));
+
+    assert!(file.restore_conflict("file".into()));
+    assert!(!file.restore_conflict("file".into()), "the record is consumed");
+    let restored = file.conflict_by_path("file".into()).expect("conflicted again");
+    assert_eq!(restored.ours.map(|entry| entry.id), Some(ours.id));
+    assert_eq!(restored.theirs.map(|entry| entry.id), Some(theirs.id));
+    assert!(restored.ancestor.is_some());
+
+    file.set_conflict("file".into(), None, Some((ours.id, ours.mode)), None);
+    let conflict = file.conflict_by_path("file".into()).expect("still conflicted");
+    assert!(conflict.ancestor.is_none() && conflict.theirs.is_none());
+    assert!(matches!(
+        file.resolve_conflict("file".into(), Resolution::Theirs),
+        Err(gix_index::conflict::Error::MissingSide { side: "their", .. })
+    ));
+    file.resolve_conflict("file".into(), Resolution::Remove)
+        .expect("conflict exists");
+    assert_eq!(file.entries().len(), 0);
+}
@@ -12,3 +12,106 @@ fn apply() {
         Mode::SYMLINK
     );
 }
+
+#[test]
+#[cfg(unix)]
+fn change_to_match_fs_respects_filesystem_capabilities() -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    use gix_index::fs::Metadata;
+
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let file = tmp.path().join("file");
+    std::fs::write(&file, b"target")?;
+    let executable = tmp.path().join("executable");
+    std::fs::write(&executable, b"")?;
+    std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755))?;
+    let symlink = tmp.path().join("symlink");
+    std::os::unix::fs::symlink("target", &symlink)?;
+    let (file, executable, symlink) = (
+        Metadata::from_path_no_follow(&file)?,
+        Metadata::from_path_no_follow(&executable)?,
+        Metadata::from_path_no_follow(&symlink)?,
+    );
+
+    assert_eq!(Mode::SYMLINK.change_to_match_fs(&symlink, true, true), None);
+    assert_eq!(
+        Mode::SYMLINK.change_to_match_fs(&file, true, true),
+        Some(Change::Type { new_mode: Mode::FILE })
+    );
+    assert_eq!(
+        Mode::SYMLINK.change_to_match_fs(&file, false, true),
+        None,
+        "without symlink support, symlinks are checked out as files"
+    );
+    assert_eq!(
+        Mode::SYMLINK.change_to_match_fs(&symlink, false, true),
+        None,
+        "actual symlinks are still fine, as git does it"
+    );
+
+    assert_eq!(
+        Mode::FILE.change_to_match_fs(&executable, true, true),
+        Some(Change::ExecutableBit)
+    );
+    assert_eq!(
+        Mode::FILE_EXECUTABLE.change_to_match_fs(&file, true, true),
+        Some(Change::ExecutableBit)
+    );
+    assert_eq!(
+        Mode::FILE.change_to_match_fs(&executable, true, false),
+        None,
+        "without executable bit support, the mode in the index is authoritative"
+    );
+    assert_eq!(Mode::FILE_EXECUTABLE.change_to_match_fs(&file, true, false), None);
+
+    assert_eq!(
+        Mode::FILE.change_to_match_fs(&symlink, true, true),
+        Some(Change::Type {
+            new_mode: Mode::SYMLINK
+        })
+    );
+    assert_eq!(
+        Mode::FILE_EXECUTABLE.change_to_match_fs(&symlink, true, true),
+        Some(Change::Type {
+            new_mode: Mode::SYMLINK
+        }),
+        "a file replaced by a symlink is a type change, not a change of the executable bit"
+    );
+    assert_eq!(
+        Mode::FILE_EXECUTABLE.change_to_match_fs(&symlink, false, false),
+        Some(Change::Type { new_mode: Mode::FILE }),
+        "without symlink support, the symlink would be added as file"
+    );
+    assert_eq!(
+        Mode::COMMIT.change_to_match_fs(&symlink, true, true),
+        Some(Change::Type {
+            new_mode: Mode::SYMLINK
+        })
+    );
+    assert_eq!(
+        Mode::COMMIT.change_to_match_fs(&executable, true, true),
+        Some(Change::Type {
+            new_mode: Mode::FILE_EXECUTABLE
+        })
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn change_to_match_fs_assumes_directories_to_be_submodules() -> std::io::Result<()> {
+    use gix_index::fs::Metadata;
+
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let dir = Metadata::from_path_no_follow(tmp.path())?;
+    assert_eq!(Mode::COMMIT.change_to_match_fs(&dir, true, true), None);
+    for mode in [Mode::FILE, Mode::FILE_EXECUTABLE, Mode::SYMLINK] {
+        assert_eq!(
+            mode.change_to_match_fs(&dir, true, true),
+            Some(Change::Type { new_mode: Mode::COMMIT }),
+            "{mode:?}"
+        );
+    }
+    Ok(())
+}
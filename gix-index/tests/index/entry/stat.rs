@@ -12,8 +12,8 @@ mod matches {
 
     #[test]
     fn use_nsec() {
-        let stat1 = Stat {
-            mtime: Time { secs: 0, nsecs: 0 },
+        let mut stat1 = Stat {
+            mtime: Time { secs: 0, nsecs: 5 },
             ctime: Time { secs: 0, nsecs: 0 },
             dev: 0,
             ino: 0,
@@ -56,6 +56,17 @@ mod matches {
             ),
             "nsec differences don't matter without check_stat"
         );
+        stat1.mtime.nsecs = 0;
+        assert!(
+            stat1.matches(
+                &stat2,
+                Options {
+                    use_nsec: true,
+                    ..Default::default()
+                },
+            ),
+            "timestamps without nsecs are assumed to have a precision of seconds, so only seconds are compared"
+        );
     }
 
     #[test]
@@ -108,6 +119,17 @@ mod matches {
             ),
             "ctime nsecs are different so stat doesn't match (trust_ctime=true,use_nsec=false)"
         );
+        stat2.ctime.secs = 3;
+        assert!(
+            stat1.matches(
+                &stat2,
+                Options {
+                    check_stat: false,
+                    ..Default::default()
+                },
+            ),
+            "ctime is ignored with check_stat=false, just like with `core.checkStat=minimal`"
+        );
     }
 
     #[test]
@@ -306,4 +328,19 @@ fn is_racy() {
         ),
         "entry with mtime before (seconds) timestamp is not racy (use_nsec=true)"
     );
+
+    let stat_without_nsecs = Stat {
+        mtime: Time { secs: 1, nsecs: 0 },
+        ..stat1
+    };
+    assert!(
+        stat_without_nsecs.is_racy(
+            FileTime::from_unix_time(1, 20),
+            Options {
+                use_nsec: true,
+                ..Default::default()
+            },
+        ),
+        "entry without nanoseconds in the same second as the timestamp may have been written after it (use_nsec=true)"
+    );
 }
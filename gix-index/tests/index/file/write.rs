@@ -168,6 +168,64 @@ fn state_comparisons_with_various_extension_configurations() {
     }
 }
 
+#[test]
+fn resolve_undo_extension_roundtrips() -> crate::Result {
+    let expected = Loose("REUC").open();
+    let mut out = Vec::<u8>::new();
+    expected.write_to(&mut out, options_with(write::Extensions::All))?;
+    let (actual, _) = State::from_bytes(&out, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    assert_eq!(actual.resolve_undo(), expected.resolve_undo());
+    assert!(actual.resolve_undo().map_or(false, |paths| !paths.is_empty()));
+
+    out.clear();
+    expected.write_to(&mut out, options_with(write::Extensions::None))?;
+    let (actual, _) = State::from_bytes(&out, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    assert_eq!(actual.resolve_undo(), None, "it's an optional extension");
+    Ok(())
+}
+
+#[test]
+fn conflicts_invalidate_the_tree_extension_along_their_path() -> crate::Result {
+    use gix_index::conflict::Resolution;
+
+    fn write_and_read(file: &gix_index::File) -> crate::Result<State> {
+        let mut out = Vec::<u8>::new();
+        file.write_to(&mut out, options_with(write::Extensions::All))?;
+        Ok(State::from_bytes(&out, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?.0)
+    }
+    fn validity(state: &State) -> (bool, bool) {
+        let tree = state.tree().expect("present");
+        assert_eq!(tree.children[0].name.as_slice(), b"d");
+        (tree.num_entries.is_some(), tree.children[0].num_entries.is_some())
+    }
+    let mut state = Generated("v2_more_files").open();
+    let id = state.entry_by_path("a".into()).expect("present").id;
+    let side = Some((id, entry::Mode::FILE));
+    assert_eq!(validity(&state), (true, true));
+
+    state.set_conflict("a".into(), None, side, side);
+    let actual = write_and_read(&state)?;
+    assert_eq!(validity(&actual), (false, true), "only the root tree contains `a`");
+    assert_eq!(actual.tree(), state.tree(), "invalid trees are written as well");
+
+    state.set_conflict("d/c".into(), side, side, None);
+    let actual = write_and_read(&state)?;
+    assert_eq!(
+        validity(&actual),
+        (false, false),
+        "all trees leading to the conflict are invalid"
+    );
+    assert!(actual.tree().expect("present").children[0].id.is_null());
+
+    let mut state = Generated("v2_more_files").open();
+    state.resolve_conflict("d/c".into(), Resolution::Ours).unwrap_err();
+    assert_eq!(validity(&state), (true, true), "nothing changes without conflict");
+    state.set_conflict("d/c".into(), side, side, None);
+    state.resolve_conflict("d/c".into(), Resolution::Ours)?;
+    assert_eq!(validity(&write_and_read(&state)?), (false, false));
+    Ok(())
+}
+
 #[test]
 fn extended_flags_automatically_upgrade_the_version_to_avoid_data_loss() -> crate::Result {
     let mut expected = Generated("v2").open();
@@ -181,6 +239,41 @@ fn extended_flags_automatically_upgrade_the_version_to_avoid_data_loss() -> crat
     Ok(())
 }
 
+#[test]
+fn set_flags_persists_assume_valid_and_skip_worktree() -> crate::Result {
+    let mut index = Generated("v2").open();
+    let entry = &mut index.entries_mut()[0];
+    assert!(entry.set_flags(entry::Flags::ASSUME_VALID | entry::Flags::SKIP_WORKTREE, true));
+    assert!(
+        entry.flags.contains(entry::Flags::EXTENDED),
+        "skip-worktree is an extended flag, and it will only be written if the entry is marked accordingly"
+    );
+    assert!(
+        !entry.set_flags(entry::Flags::SKIP_WORKTREE, true),
+        "setting flags that are already set doesn't change anything"
+    );
+
+    let mut buf = Vec::new();
+    let (actual_version, _digest) = index.write_to(&mut buf, Default::default())?;
+    assert_eq!(actual_version, Version::V3, "extended flags need V3");
+    let (mut state, _checksum) = State::from_bytes(&buf, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    let entry = &mut state.entries_mut()[0];
+    assert!(entry
+        .flags
+        .contains(entry::Flags::ASSUME_VALID | entry::Flags::SKIP_WORKTREE));
+
+    assert!(entry.set_flags(entry::Flags::SKIP_WORKTREE, false));
+    assert!(
+        !entry.flags.contains(entry::Flags::EXTENDED),
+        "without extended flags, the entry doesn't need the extended storage format anymore"
+    );
+    assert!(
+        entry.flags.contains(entry::Flags::ASSUME_VALID),
+        "other flags are untouched"
+    );
+    Ok(())
+}
+
 #[test]
 fn remove_flag_is_respected() -> crate::Result {
     let mut index = Generated("v4_more_files_IEOT").open();
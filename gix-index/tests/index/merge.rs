@@ -0,0 +1,268 @@
+mod two_way {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use bstr::{BStr, BString, ByteSlice};
+    use gix_index::{
+        entry,
+        merge::two_way::{Change, Conflict, ConflictKind, Kind, Options, Worktree},
+        Entry, State,
+    };
+
+    use crate::hex_to_id;
+
+    const A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const C: &str = "cccccccccccccccccccccccccccccccccccccccc";
+
+    #[derive(Default)]
+    struct MockWorktree {
+        dirty: BTreeSet<BString>,
+        untracked: BTreeMap<BString, Kind>,
+    }
+
+    impl Worktree for MockWorktree {
+        type Error = std::convert::Infallible;
+
+        fn is_uptodate(&mut self, rela_path: &BStr, _entry: &Entry) -> Result<bool, Self::Error> {
+            Ok(!self.dirty.contains(rela_path))
+        }
+
+        fn untracked_kind(&mut self, rela_path: &BStr) -> Result<Option<Kind>, Self::Error> {
+            Ok(self.untracked.get(rela_path).copied())
+        }
+    }
+
+    fn state(entries: &[(&str, &str)]) -> State {
+        staged_state(&entries.iter().map(|(path, id)| (*path, *id, 0)).collect::<Vec<_>>())
+    }
+
+    fn staged_state(entries: &[(&str, &str, u32)]) -> State {
+        let mut state = State::new(gix_hash::Kind::Sha1);
+        for (path, id, stage) in entries {
+            state.dangerously_push_entry(
+                entry::Stat::default(),
+                hex_to_id(id),
+                entry::Flags::from_bits(stage << 12).expect("valid stage"),
+                entry::Mode::FILE,
+                path.as_bytes().as_bstr(),
+            );
+        }
+        state.sort_entries();
+        state
+    }
+
+    fn entries(state: &State) -> Vec<(String, String)> {
+        state
+            .entries()
+            .iter()
+            .map(|e| (e.path(state).to_string(), e.id.to_string()))
+            .collect()
+    }
+
+    fn expected(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(path, id)| (path.to_string(), id.to_string()))
+            .collect()
+    }
+
+    fn merge(
+        index: &State,
+        current: &State,
+        target: &State,
+        worktree: &mut MockWorktree,
+    ) -> gix_index::merge::two_way::Outcome {
+        gix_index::merge::two_way(index, current, target, worktree, Options::default()).expect("no worktree errors")
+    }
+
+    fn update(path: &str) -> Change {
+        Change::Update { path: path.into() }
+    }
+
+    fn conflict(path: &str, kind: ConflictKind) -> Conflict {
+        Conflict {
+            path: path.into(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn changed_files_are_updated_and_deleted_files_are_removed() {
+        let current = state(&[("changed", A), ("deleted", A), ("same", A)]);
+        let target = state(&[("added", B), ("changed", B), ("same", A)]);
+        let out = merge(&current, &current, &target, &mut MockWorktree::default());
+        assert_eq!(out.conflicts, []);
+        assert_eq!(
+            entries(&out.state),
+            expected(&[("added", B), ("changed", B), ("same", A)])
+        );
+        assert_eq!(
+            out.changes,
+            [
+                Change::Remove { path: "deleted".into() },
+                update("added"),
+                update("changed")
+            ],
+            "removals come first"
+        );
+    }
+
+    #[test]
+    fn staged_changes_are_kept_if_the_trees_agree_or_the_index_already_has_the_target() {
+        let current = state(&[("staged", A), ("already-there", A)]);
+        let target = state(&[("staged", A), ("already-there", B)]);
+        let index = state(&[("already-there", B), ("new", C), ("staged", C)]);
+        let out = merge(&index, &current, &target, &mut MockWorktree::default());
+        assert_eq!(out.conflicts, []);
+        assert_eq!(entries(&out.state), entries(&index), "the index is kept as is");
+        assert_eq!(out.changes, []);
+    }
+
+    #[test]
+    fn local_changes_in_index_or_worktree_prevent_updates_and_removals() {
+        let current = state(&[("dirty", A), ("removed-dirty", A), ("staged", A)]);
+        let target = state(&[("dirty", B), ("staged", B)]);
+        let index = state(&[("dirty", A), ("removed-dirty", A), ("staged", C)]);
+        let mut worktree = MockWorktree {
+            dirty: ["dirty", "removed-dirty"].into_iter().map(Into::into).collect(),
+            ..Default::default()
+        };
+        let out = merge(&index, &current, &target, &mut worktree);
+        assert_eq!(
+            out.conflicts,
+            [
+                conflict("dirty", ConflictKind::LocalChanges),
+                conflict("removed-dirty", ConflictKind::LocalChanges),
+                conflict("staged", ConflictKind::LocalChanges),
+            ]
+        );
+        assert_eq!(
+            entries(&out.state),
+            entries(&index),
+            "conflicting paths keep the index entry"
+        );
+    }
+
+    #[test]
+    fn staged_deletions_conflict_unless_the_trees_agree_or_on_initial_checkout() {
+        let current = state(&[("changed", A), ("same", A)]);
+        let target = state(&[("changed", B), ("same", A)]);
+        let out = merge(
+            &State::new(gix_hash::Kind::Sha1),
+            &current,
+            &target,
+            &mut MockWorktree::default(),
+        );
+        assert_eq!(out.conflicts, [conflict("changed", ConflictKind::LocalChanges)]);
+        assert_eq!(entries(&out.state), [], "the deletion of 'same' stays staged");
+
+        let out = gix_index::merge::two_way(
+            &State::new(gix_hash::Kind::Sha1),
+            &current,
+            &target,
+            &mut MockWorktree::default(),
+            Options { initial_checkout: true },
+        )
+        .expect("no worktree errors");
+        assert_eq!(out.conflicts, []);
+        assert_eq!(entries(&out.state), expected(&[("changed", B), ("same", A)]));
+        assert_eq!(out.changes, [update("changed"), update("same")]);
+    }
+
+    #[test]
+    fn untracked_files_are_not_overwritten() {
+        let current = state(&[]);
+        let target = state(&[("dir/file", B), ("file", B)]);
+        let mut worktree = MockWorktree {
+            untracked: [("dir".into(), Kind::File), ("file".into(), Kind::Directory)].into(),
+            ..Default::default()
+        };
+        let out = merge(&current, &current, &target, &mut worktree);
+        assert_eq!(
+            out.conflicts,
+            [
+                conflict("dir", ConflictKind::Untracked),
+                conflict("file", ConflictKind::Untracked)
+            ]
+        );
+
+        let mut worktree = MockWorktree {
+            untracked: [("dir".into(), Kind::Directory)].into(),
+            ..Default::default()
+        };
+        let out = merge(&current, &current, &target, &mut worktree);
+        assert_eq!(out.conflicts, [], "untracked directories can receive new files");
+    }
+
+    #[test]
+    fn files_can_replace_directories_and_vice_versa() {
+        let current = state(&[("dir/file", A), ("file", A)]);
+        let target = state(&[("dir", B), ("file/new", B)]);
+        let out = merge(&current, &current, &target, &mut MockWorktree::default());
+        assert_eq!(out.conflicts, []);
+        assert_eq!(entries(&out.state), expected(&[("dir", B), ("file/new", B)]));
+        assert_eq!(
+            out.changes,
+            [
+                Change::Remove {
+                    path: "dir/file".into()
+                },
+                Change::Remove { path: "file".into() },
+                update("dir"),
+                update("file/new")
+            ]
+        );
+    }
+
+    #[test]
+    fn files_cannot_replace_directories_with_local_changes_and_vice_versa() {
+        let current = state(&[("dir/file", A), ("file", A)]);
+        let target = state(&[("dir", B), ("file/new", B)]);
+        let mut worktree = MockWorktree {
+            dirty: ["dir/file", "file"].into_iter().map(Into::into).collect(),
+            ..Default::default()
+        };
+        let out = merge(&current, &current, &target, &mut worktree);
+        assert_eq!(
+            out.conflicts,
+            [
+                conflict("dir", ConflictKind::LocalChanges),
+                conflict("dir/file", ConflictKind::LocalChanges),
+                conflict("file", ConflictKind::LocalChanges),
+            ]
+        );
+    }
+
+    #[test]
+    fn files_cannot_replace_directories_with_untracked_files() {
+        let current = state(&[("dir/file", A)]);
+        let target = state(&[("dir", B)]);
+        let mut worktree = MockWorktree {
+            untracked: [("dir".into(), Kind::Directory)].into(),
+            ..Default::default()
+        };
+        let out = merge(&current, &current, &target, &mut worktree);
+        assert_eq!(out.conflicts, [conflict("dir", ConflictKind::Untracked)]);
+    }
+
+    #[test]
+    fn unmerged_entries_are_resolved_only_if_the_trees_agree() {
+        let current = state(&[("changed", A), ("same", A)]);
+        let target = state(&[("changed", B), ("same", A)]);
+        let index = staged_state(&[
+            ("changed", A, 1),
+            ("changed", B, 2),
+            ("changed", C, 3),
+            ("same", A, 1),
+            ("same", B, 2),
+            ("same", C, 3),
+        ]);
+        let out = merge(&index, &current, &target, &mut MockWorktree::default());
+        assert_eq!(out.conflicts, [conflict("changed", ConflictKind::Unmerged)]);
+        assert_eq!(
+            entries(&out.state),
+            expected(&[("changed", A), ("changed", B), ("changed", C), ("same", A)])
+        );
+        assert_eq!(out.changes, [update("same")]);
+    }
+}
@@ -2,6 +2,35 @@ use bstr::BStr;
 
 use crate::Entry;
 
+/// Serialization
+impl<'a> Entry<'a> {
+    /// Write this entry as a single line in the format of `.mailmap` files to `out`, including the trailing newline.
+    ///
+    /// Parsing the written line yields an entry that is equal to this one.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        if let Some(name) = self.new_name {
+            out.write_all(name)?;
+            out.write_all(b" ")?;
+        }
+        if let Some(email) = self.new_email {
+            write_email(email, out)?;
+            out.write_all(b" ")?;
+        }
+        if let Some(name) = self.old_name {
+            out.write_all(name)?;
+            out.write_all(b" ")?;
+        }
+        write_email(self.old_email, out)?;
+        out.write_all(b"\n")
+    }
+}
+
+fn write_email(email: &BStr, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    out.write_all(b"<")?;
+    out.write_all(email)?;
+    out.write_all(b">")
+}
+
 /// Access
 impl<'a> Entry<'a> {
     /// The name to map to.
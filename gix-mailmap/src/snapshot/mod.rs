@@ -1,4 +1,4 @@
-use bstr::ByteSlice;
+use bstr::{BStr, ByteSlice};
 use gix_actor::SignatureRef;
 
 use crate::Snapshot;
@@ -51,6 +51,58 @@ impl Snapshot {
         self
     }
 
+    /// Remove the mapping for `old_email`, or the one for `old_name` and `old_email` if `old_name` is set,
+    /// and return `true` if it existed.
+    ///
+    /// Note that removing the mapping by email alone keeps all mappings by name and email, and that lookups of
+    /// names and emails are case-insensitive.
+    pub fn remove(&mut self, old_email: &BStr, old_name: Option<&BStr>) -> bool {
+        let old_email: EncodedStringRef<'_> = old_email.into();
+        let Ok(pos) = self
+            .entries_by_old_email
+            .binary_search_by(|e| e.old_email.cmp_ref(old_email))
+        else {
+            return false;
+        };
+        let entry = &mut self.entries_by_old_email[pos];
+        let removed = match old_name {
+            None => {
+                let had_mapping = entry.new_name.is_some() || entry.new_email.is_some();
+                entry.new_name = None;
+                entry.new_email = None;
+                had_mapping
+            }
+            Some(old_name) => {
+                let old_name: EncodedStringRef<'_> = old_name.into();
+                match entry
+                    .entries_by_old_name
+                    .binary_search_by(|e| e.old_name.cmp_ref(old_name))
+                {
+                    Ok(name_pos) => {
+                        entry.entries_by_old_name.remove(name_pos);
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+        };
+        if entry.new_name.is_none() && entry.new_email.is_none() && entry.entries_by_old_name.is_empty() {
+            self.entries_by_old_email.remove(pos);
+        }
+        removed
+    }
+
+    /// Write all [entries](Self::entries()) in the format of `.mailmap` files to `out`, one per line.
+    ///
+    /// As duplicate mappings were already merged, each mapping is written only once, and the order is stable
+    /// as it only depends on the mappings themselves. Comments and the order of the originally parsed input are lost.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        for entry in self.entries() {
+            entry.write_to(out)?;
+        }
+        Ok(())
+    }
+
     /// Transform our acceleration structure into a list of entries.
     ///
     /// Note that the order is different from how they were obtained initially, and are explicitly ordered by
@@ -81,6 +81,21 @@ fn valid_entries() {
     );
 }
 
+#[test]
+fn written_entries_parse_into_the_same_entry() {
+    for entry in [
+        Entry::change_name_by_email("proper name", "commit-email"),
+        Entry::change_email_by_email("proper email", "commit-email"),
+        Entry::change_name_and_email_by_email("proper name", "proper email", "commit-email"),
+        Entry::change_name_and_email_by_name_and_email("proper name", "proper email", "commit name", "commit-email"),
+    ] {
+        let mut buf = Vec::new();
+        entry.write_to(&mut buf).unwrap();
+        assert_eq!(buf.last(), Some(&b'\n'), "each entry is a line");
+        assert_eq!(line(std::str::from_utf8(&buf).unwrap()), entry);
+    }
+}
+
 #[test]
 fn error_if_there_is_just_a_name() {
     assert!(matches!(
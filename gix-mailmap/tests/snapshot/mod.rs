@@ -1,4 +1,5 @@
-use gix_mailmap::Snapshot;
+use bstr::ByteSlice;
+use gix_mailmap::{Entry, Snapshot};
 use gix_testtools::fixture_bytes;
 
 #[test]
@@ -113,6 +114,86 @@ fn overwrite_entries() {
     assert_eq!(snapshot.entries().len(), 4);
 }
 
+#[test]
+fn write_to_round_trips_in_stable_order() {
+    let snapshot = Snapshot::from_bytes(&fixture_bytes("typical.txt"));
+    let buf = to_bytes(&snapshot);
+    assert_eq!(
+        buf.as_bstr(),
+        "Jane Doe <jane@example.com> Jane <bugs@example.com>\n\
+         Joe R. Developer <joe@example.com> Joe <bugs@example.com>\n\
+         Jane Doe <jane@example.com> <jane@desktop.(none)>\n\
+         Jane Doe <jane@example.com> <jane@laptop.(none)>\n\
+         Joe R. Developer <joe@example.com>\n",
+        "entries are ordered by email and name, case-insensitively"
+    );
+
+    let input = fixture_bytes("typical.txt");
+    let mut reversed_entries = gix_mailmap::parse_ignore_errors(&input).collect::<Vec<_>>();
+    reversed_entries.reverse();
+    assert_eq!(
+        to_bytes(&Snapshot::new(reversed_entries)),
+        buf,
+        "the input order doesn't affect the output"
+    );
+    assert_eq!(
+        to_bytes(&Snapshot::from_bytes(&buf)),
+        buf,
+        "written snapshots parse into the same snapshot"
+    );
+}
+
+#[test]
+fn duplicates_are_written_once() {
+    let snapshot = Snapshot::from_bytes(&fixture_bytes("overwrite.txt"));
+    assert_eq!(
+        to_bytes(&snapshot).as_bstr(),
+        "A-overwritten <old-a-email>\n\
+         B-overwritten <new-b-email-overwritten> <old-b-email>\n\
+         C-overwritten <new-c-email-overwritten> old-C <old-c-email>\n\
+         <new-d-email-overwritten> <old-d-email>\n",
+        "only the last of each mapping is retained"
+    );
+}
+
+#[test]
+fn entries_can_be_added_updated_and_removed() {
+    let mut snapshot = Snapshot::default();
+    snapshot.merge([
+        Entry::change_name_by_email("Jane", "jane@laptop"),
+        Entry::change_name_and_email_by_name_and_email("Jane Doe", "jane@example.com", "jd", "jane@laptop"),
+    ]);
+    snapshot.merge(Some(Entry::change_name_by_email("Jane Doe", "Jane@Laptop")));
+    assert_eq!(
+        to_bytes(&snapshot).as_bstr(),
+        "Jane Doe <jane@laptop>\nJane Doe <jane@example.com> jd <jane@laptop>\n",
+        "emails are matched case-insensitively when updating"
+    );
+
+    assert!(snapshot.remove("JANE@laptop".into(), None));
+    assert!(!snapshot.remove("jane@laptop".into(), None), "it's already gone");
+    assert_eq!(
+        to_bytes(&snapshot).as_bstr(),
+        "Jane Doe <jane@example.com> jd <jane@laptop>\n",
+        "mappings by name and email remain"
+    );
+    assert_eq!(
+        snapshot.try_resolve(signature("jd", "jane@laptop").to_ref()),
+        Some(signature("Jane Doe", "jane@example.com"))
+    );
+
+    assert!(!snapshot.remove("jane@laptop".into(), Some("other".into())));
+    assert!(snapshot.remove("jane@laptop".into(), Some("JD".into())));
+    assert!(snapshot.entries().is_empty());
+    assert!(!snapshot.remove("jane@laptop".into(), Some("jd".into())));
+}
+
+fn to_bytes(snapshot: &Snapshot) -> Vec<u8> {
+    let mut buf = Vec::new();
+    snapshot.write_to(&mut buf).expect("writing to vec works");
+    buf
+}
+
 fn signature(name: &str, email: &str) -> gix_actor::Signature {
     gix_actor::Signature {
         name: name.into(),
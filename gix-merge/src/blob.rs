@@ -0,0 +1,285 @@
+use std::ops::Range;
+
+use bstr::{BStr, ByteSlice};
+use imara_diff::{intern::InternedInput, intern::Token, Algorithm};
+
+/// The names shown next to conflict markers, typically names of branches or commits.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Labels<'a> {
+    /// The name of the common ancestor, shown after `|||||||` with the [`Diff3`](ConflictStyle::Diff3) style.
+    pub ancestor: Option<&'a BStr>,
+    /// The name of the current version, also known as _ours_, shown after `<<<<<<<`.
+    pub current: Option<&'a BStr>,
+    /// The name of the other version, also known as _theirs_, shown after `>>>>>>>`.
+    pub other: Option<&'a BStr>,
+}
+
+/// The way conflicts are presented, similar to `merge.conflictStyle`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictStyle {
+    /// Show the conflicting lines of the current and the other version.
+    ///
+    /// Lines that are the same at the beginning and the end of both versions are moved out of the conflict.
+    #[default]
+    Merge,
+    /// Like [`Merge`](Self::Merge), but also show the lines of the common ancestor, and leave all lines in the conflict.
+    Diff3,
+}
+
+/// The way to resolve conflicts automatically, similar to `git merge-file --ours`, `--theirs` and `--union`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Favor {
+    /// Use the conflicting lines of the current version.
+    Ours,
+    /// Use the conflicting lines of the other version.
+    Theirs,
+    /// Use the conflicting lines of both versions, the current version first.
+    Union,
+}
+
+/// Options for [`merge()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The way conflicts are presented.
+    pub conflict_style: ConflictStyle,
+    /// If set, conflicts are resolved automatically this way and no conflict markers are written.
+    pub favor: Option<Favor>,
+    /// The amount of characters making up a conflict marker, `7` by default like in `git`.
+    pub marker_size: usize,
+    /// The algorithm used to diff both versions against their common ancestor.
+    pub diff_algorithm: Algorithm,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            conflict_style: Default::default(),
+            favor: None,
+            marker_size: 7,
+            diff_algorithm: Algorithm::Myers,
+        }
+    }
+}
+
+/// The result of a [`merge()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// All changes could be merged, or conflicts were resolved as configured by [`Options::favor`].
+    Complete,
+    /// There was at least one conflict, which is marked with conflict markers in the output.
+    Conflict,
+}
+
+/// Return `true` if `data` is considered binary and shouldn't be merged line by line, just like `git` does it.
+///
+/// This is the case if there is a null byte in the first 8000 bytes.
+pub fn is_binary(data: &[u8]) -> bool {
+    data[..data.len().min(8000)].contains(&0)
+}
+
+/// Merge the changes from `ancestor` to `current` and from `ancestor` to `other` line by line, and write the result to `out`,
+/// similar to `git merge-file`.
+///
+/// Changes of both sides that overlap or touch each other are conflicts, unless both sides changed the lines in the same way.
+/// Conflicts are written with conflict markers, annotated with `labels`, unless [`Options::favor`] is set.
+/// Binary data should not be merged this way, see [`is_binary()`].
+pub fn merge(
+    ancestor: &[u8],
+    current: &[u8],
+    other: &[u8],
+    labels: Labels<'_>,
+    options: Options,
+    out: &mut Vec<u8>,
+) -> Resolution {
+    let mut input = InternedInput::new(
+        imara_diff::sources::byte_lines_with_terminator(ancestor),
+        imara_diff::sources::byte_lines_with_terminator(current),
+    );
+    let current_hunks = hunks(options.diff_algorithm, &input);
+    let current_tokens = std::mem::take(&mut input.after);
+    input.update_after(other.lines_with_terminator());
+    let other_hunks = hunks(options.diff_algorithm, &input);
+
+    let mut merge = Merge {
+        input: &input,
+        labels,
+        options,
+        out,
+        resolution: Resolution::Complete,
+    };
+    let ancestor_tokens = &input.before;
+    let (mut current_side, mut other_side) = (
+        Side::new(&current_hunks, &current_tokens),
+        Side::new(&other_hunks, &input.after),
+    );
+    let mut ancestor_pos = 0;
+    loop {
+        let start = match (current_side.next_start(), other_side.next_start()) {
+            (None, None) => break,
+            (Some(start), None) | (None, Some(start)) => start,
+            (Some(a), Some(b)) => a.min(b),
+        };
+        // Find the range of the ancestor touched by overlapping or adjacent changes of both sides.
+        let mut end = start;
+        let (current_first, other_first) = (current_side.next, other_side.next);
+        loop {
+            let extended = current_side.consume_until(&mut end) | other_side.consume_until(&mut end);
+            if !extended {
+                break;
+            }
+        }
+
+        merge.write(&ancestor_tokens[ancestor_pos as usize..start as usize]);
+        let region = start..end;
+        let current_lines = current_side.lines(current_first, &region);
+        let other_lines = other_side.lines(other_first, &region);
+        if other_side.next == other_first || current_lines == other_lines {
+            merge.write(current_lines);
+        } else if current_side.next == current_first {
+            merge.write(other_lines);
+        } else {
+            merge.conflict(
+                current_lines,
+                &ancestor_tokens[region.start as usize..region.end as usize],
+                other_lines,
+            );
+        }
+        ancestor_pos = end;
+    }
+    merge.write(&ancestor_tokens[ancestor_pos as usize..]);
+    merge.resolution
+}
+
+fn hunks(algorithm: Algorithm, input: &InternedInput<&[u8]>) -> Vec<(Range<u32>, Range<u32>)> {
+    let mut out = Vec::new();
+    imara_diff::diff(algorithm, input, |before: Range<u32>, after: Range<u32>| {
+        out.push((before, after));
+    });
+    out
+}
+
+/// The changes of one side of the merge compared to the common ancestor.
+struct Side<'a> {
+    /// Ranges of lines in the ancestor and their replacement on this side, in order.
+    hunks: &'a [(Range<u32>, Range<u32>)],
+    /// The lines of this side.
+    tokens: &'a [Token],
+    /// The index of the next hunk to process.
+    next: usize,
+}
+
+impl<'a> Side<'a> {
+    fn new(hunks: &'a [(Range<u32>, Range<u32>)], tokens: &'a [Token]) -> Self {
+        Side { hunks, tokens, next: 0 }
+    }
+
+    fn next_start(&self) -> Option<u32> {
+        self.hunks.get(self.next).map(|(before, _)| before.start)
+    }
+
+    /// Consume all hunks that start before or at `end` and extend `end` to cover them, returning `true` if one was consumed.
+    fn consume_until(&mut self, end: &mut u32) -> bool {
+        let mut consumed = false;
+        while let Some((before, _)) = self.hunks.get(self.next).filter(|(before, _)| before.start <= *end) {
+            *end = (*end).max(before.end);
+            self.next += 1;
+            consumed = true;
+        }
+        consumed
+    }
+
+    /// Return the lines of this side that correspond to `region` of the ancestor, with `first` being the index of the
+    /// first hunk within the region.
+    fn lines(&self, first: usize, region: &Range<u32>) -> &'a [Token] {
+        let offset = |(before, after): &(Range<u32>, Range<u32>)| i64::from(after.end) - i64::from(before.end);
+        let (start, end) = if first == self.next {
+            let offset = first.checked_sub(1).map_or(0, |prev| offset(&self.hunks[prev]));
+            (i64::from(region.start) + offset, i64::from(region.end) + offset)
+        } else {
+            let (first_before, first_after) = &self.hunks[first];
+            let last = &self.hunks[self.next - 1];
+            (
+                i64::from(first_after.start) - i64::from(first_before.start - region.start),
+                i64::from(region.end) + offset(last),
+            )
+        };
+        &self.tokens[start as usize..end as usize]
+    }
+}
+
+struct Merge<'a, 'input> {
+    input: &'a InternedInput<&'input [u8]>,
+    labels: Labels<'a>,
+    options: Options,
+    out: &'a mut Vec<u8>,
+    resolution: Resolution,
+}
+
+impl Merge<'_, '_> {
+    fn write(&mut self, lines: &[Token]) {
+        for token in lines {
+            self.out.extend_from_slice(self.input.interner[*token]);
+        }
+    }
+
+    fn conflict(&mut self, current: &[Token], ancestor: &[Token], other: &[Token]) {
+        match self.options.favor {
+            Some(Favor::Ours) => self.write(current),
+            Some(Favor::Theirs) => self.write(other),
+            Some(Favor::Union) => {
+                self.write(current);
+                self.ensure_newline();
+                self.write(other);
+            }
+            None => {
+                self.resolution = Resolution::Conflict;
+                let (current, other, common_suffix) = match self.options.conflict_style {
+                    ConflictStyle::Diff3 => (current, other, &[][..]),
+                    ConflictStyle::Merge => {
+                        let prefix_len = current.iter().zip(other).take_while(|(a, b)| a == b).count();
+                        self.write(&current[..prefix_len]);
+                        let (current, other) = (&current[prefix_len..], &other[prefix_len..]);
+                        let suffix_len = current
+                            .iter()
+                            .rev()
+                            .zip(other.iter().rev())
+                            .take_while(|(a, b)| a == b)
+                            .count();
+                        (
+                            &current[..current.len() - suffix_len],
+                            &other[..other.len() - suffix_len],
+                            &current[current.len() - suffix_len..],
+                        )
+                    }
+                };
+                self.marker(b'<', self.labels.current);
+                self.write(current);
+                if self.options.conflict_style == ConflictStyle::Diff3 {
+                    self.marker(b'|', self.labels.ancestor);
+                    self.write(ancestor);
+                }
+                self.marker(b'=', None);
+                self.write(other);
+                self.marker(b'>', self.labels.other);
+                self.write(common_suffix);
+            }
+        }
+    }
+
+    fn marker(&mut self, marker: u8, label: Option<&BStr>) {
+        self.ensure_newline();
+        self.out
+            .extend(std::iter::repeat(marker).take(self.options.marker_size));
+        if let Some(label) = label {
+            self.out.push(b' ');
+            self.out.extend_from_slice(label);
+        }
+        self.out.push(b'\n');
+    }
+
+    fn ensure_newline(&mut self) {
+        if self.out.last().map_or(false, |b| *b != b'\n') {
+            self.out.push(b'\n');
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use bstr::BString;
+use gix_hash::ObjectId;
+
+use crate::tree;
+
+/// The error returned by [`merge()`][function::merge()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    MergeBase(#[from] gix_revision::merge_base::Error),
+    #[error(transparent)]
+    FindExistingObject(#[from] gix_object::find::existing_object::Error),
+    #[error(transparent)]
+    MergeTree(#[from] tree::Error),
+    #[error("There are no commits to merge")]
+    NoCommitsToMerge,
+    #[error("The {strategy} strategy can only merge a single commit, but {count} were given")]
+    TooManyCommitsToMerge { strategy: &'static str, count: usize },
+    #[error("Merging {id} caused conflicts, which the octopus strategy doesn't resolve")]
+    OctopusConflict { id: ObjectId },
+    #[error("Could not write a tree shifted into '{prefix}'")]
+    WriteShiftedTree {
+        prefix: BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+/// The way commits are merged, similar to `git merge --strategy`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Merge a single commit with a three-way merge of trees, with a virtual merge-base if there are multiple merge-bases,
+    /// like the `ort` strategy that `git merge` uses by default.
+    #[default]
+    Ort,
+    /// Merge any amount of commits one after another, each with its merge-bases with the commits merged before, and fail
+    /// on the first conflict, like the `octopus` strategy that `git merge` uses for multiple commits.
+    Octopus,
+    /// Keep the tree of _ours_ and ignore all changes of the commits to merge, like the `ours` strategy.
+    Ours,
+    /// Like [`Ort`](Self::Ort), but with the trees of _theirs_ and the merge-base moved into a subdirectory of _ours_ first,
+    /// which is useful to merge a project that is kept in a subdirectory, like the `subtree` strategy.
+    Subtree {
+        /// The subdirectory of _ours_ that corresponds to the root of _theirs_, similar to `-X subtree=<path>`,
+        /// or `None` to use the directory that is most similar to the tree of _theirs_, at most two levels deep.
+        prefix: Option<BString>,
+    },
+}
+
+impl Strategy {
+    /// Return the name of the strategy as used with `git merge --strategy`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Strategy::Ort => "ort",
+            Strategy::Octopus => "octopus",
+            Strategy::Ours => "ours",
+            Strategy::Subtree { .. } => "subtree",
+        }
+    }
+}
+
+/// Options for [`merge()`][function::merge()].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Options {
+    /// The way commits are merged.
+    pub strategy: Strategy,
+    /// The options to use when merging trees.
+    pub tree: tree::Options,
+}
+
+/// The result of [`merge()`][function::merge()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The id of the merged tree, which is written even if there were conflicts.
+    pub tree: ObjectId,
+    /// All conflicts that were encountered, in the order of their paths. If there are none, `tree` can be used for
+    /// a merge commit.
+    pub conflicts: Vec<tree::Conflict>,
+    /// The merge-bases of _ours_ and all commits to merge, newest first, or an empty list if they don't share any history.
+    pub merge_bases: Vec<ObjectId>,
+    /// The tree that was used as common ancestor, or `None` if there were no merge-bases and an empty tree was used,
+    /// or if the [strategy](Strategy) doesn't use a single common ancestor.
+    ///
+    /// If there were multiple merge-bases, this is the tree of their virtual merge, which may contain conflict markers.
+    pub ancestor_tree: Option<ObjectId>,
+}
+
+impl Outcome {
+    /// Return `true` if there were conflicts that have to be resolved before a merge commit can be created.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+pub(crate) mod function {
+    use std::collections::VecDeque;
+
+    use bstr::{BStr, BString, ByteSlice};
+    use gix_hash::{oid, ObjectId};
+    use gix_object::{FindExt, WriteTo};
+    use gix_revision::{merge_base, Graph};
+
+    use super::{Error, Options, Outcome, Strategy};
+    use crate::{blob, tree};
+
+    type WriteFn<'a> =
+        dyn FnMut(gix_object::Kind, &[u8]) -> Result<ObjectId, Box<dyn std::error::Error + Send + Sync + 'static>> + 'a;
+
+    /// Merge the commits `theirs` into the commit `ours` with the [strategy](Strategy) in `options` and write the resulting
+    /// tree, similar to `git merge-tree --write-tree ours theirs` for a single commit.
+    ///
+    /// The common ancestor is the tree of the merge-base of both commits. If there are multiple merge-bases, for instance
+    /// after criss-cross merges, they are merged into a _virtual merge-base_ first, recursively if their merges have multiple
+    /// merge-bases themselves, just like the `ort` and `recursive` strategies of `git merge` do it. Conflicts in the virtual
+    /// merge-base are kept with longer conflict markers, which typically resolves them in the final merge.
+    ///
+    /// `objects` is used to read commits, trees and blobs, `graph` is used to find merge-bases, and `write` is called to write
+    /// all new trees and blobs, returning their id. See [`tree::merge()`] for details on `labels` and the tree options.
+    pub fn merge<E>(
+        ours: &oid,
+        theirs: &[ObjectId],
+        labels: blob::Labels<'_>,
+        options: &Options,
+        objects: &impl gix_object::Find,
+        graph: &mut Graph<'_, merge_base::Flags>,
+        mut write: impl FnMut(gix_object::Kind, &[u8]) -> Result<ObjectId, E>,
+    ) -> Result<Outcome, Error>
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    {
+        let mut write = |kind, data: &[u8]| write(kind, data).map_err(Into::into);
+        if theirs.is_empty() {
+            return Err(Error::NoCommitsToMerge);
+        }
+        let merge_bases = gix_revision::merge_base(ours, theirs, graph)?.unwrap_or_default();
+        let mut buf = Vec::new();
+        let ours_tree = objects.find_commit(ours, &mut buf)?.tree();
+        let (theirs, prefix) = match (&options.strategy, theirs) {
+            (Strategy::Ours, _) => {
+                return Ok(Outcome {
+                    tree: ours_tree,
+                    conflicts: Vec::new(),
+                    merge_bases,
+                    ancestor_tree: None,
+                })
+            }
+            (Strategy::Octopus, _) => {
+                let tree = octopus(
+                    ours,
+                    ours_tree,
+                    theirs,
+                    labels,
+                    options.tree,
+                    objects,
+                    graph,
+                    &mut write,
+                )?;
+                return Ok(Outcome {
+                    tree,
+                    conflicts: Vec::new(),
+                    merge_bases,
+                    ancestor_tree: None,
+                });
+            }
+            (Strategy::Ort, [theirs]) => (theirs, None),
+            (Strategy::Subtree { prefix }, [theirs]) => (theirs, Some(prefix)),
+            (strategy, _) => {
+                return Err(Error::TooManyCommitsToMerge {
+                    strategy: strategy.as_str(),
+                    count: theirs.len(),
+                })
+            }
+        };
+
+        let mut ancestor_tree = virtual_merge_base(&merge_bases, options.tree, 0, objects, graph, &mut write)?;
+        let mut theirs_tree = objects.find_commit(theirs, &mut buf)?.tree();
+        if let Some(prefix) = prefix {
+            let prefix = match prefix {
+                Some(prefix) => Some(prefix.to_owned()),
+                None => find_subtree(ours_tree, theirs_tree, objects)?,
+            };
+            if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+                theirs_tree = shift_tree(
+                    Some(ours_tree),
+                    prefix.as_ref(),
+                    theirs_tree,
+                    objects,
+                    &mut write,
+                    &mut buf,
+                )?;
+                ancestor_tree = ancestor_tree
+                    .map(|tree| shift_tree(Some(ours_tree), prefix.as_ref(), tree, objects, &mut write, &mut buf))
+                    .transpose()?;
+            }
+        }
+        let outcome = tree::merge_at_depth(
+            ancestor_tree.as_deref(),
+            &ours_tree,
+            &theirs_tree,
+            labels,
+            options.tree,
+            0,
+            objects,
+            &mut write,
+        )?;
+        Ok(Outcome {
+            tree: outcome.tree,
+            conflicts: outcome.conflicts,
+            merge_bases,
+            ancestor_tree,
+        })
+    }
+
+    /// Merge all commits in `theirs` one after another into `ours` with its tree `ours_tree`, like `git merge-octopus`, and
+    /// return the merged tree.
+    ///
+    /// Commits that were already merged are skipped, and as long as no merge was needed, commits that contain all previously
+    /// merged commits are fast-forwarded to.
+    #[allow(clippy::too_many_arguments)]
+    fn octopus(
+        ours: &oid,
+        ours_tree: ObjectId,
+        theirs: &[ObjectId],
+        labels: blob::Labels<'_>,
+        options: tree::Options,
+        objects: &impl gix_object::Find,
+        graph: &mut Graph<'_, merge_base::Flags>,
+        write: &mut WriteFn<'_>,
+    ) -> Result<ObjectId, Error> {
+        let mut buf = Vec::new();
+        let mut merged = vec![ours.to_owned()];
+        let mut tree = ours_tree;
+        let mut may_fast_forward = true;
+        for id in theirs {
+            let merge_bases = gix_revision::merge_base(id, &merged, graph)?.unwrap_or_default();
+            if merge_bases.contains(id) {
+                continue;
+            }
+            let theirs_tree = objects.find_commit(id, &mut buf)?.tree();
+            if may_fast_forward && merge_bases == merged {
+                merged = vec![*id];
+                tree = theirs_tree;
+                continue;
+            }
+            may_fast_forward = false;
+            let ancestor_tree = virtual_merge_base(&merge_bases, options, 0, objects, graph, &mut *write)?;
+            let outcome = tree::merge_at_depth(
+                ancestor_tree.as_deref(),
+                &tree,
+                &theirs_tree,
+                labels,
+                options,
+                0,
+                objects,
+                &mut *write,
+            )?;
+            if outcome.has_conflicts() {
+                return Err(Error::OctopusConflict { id: *id });
+            }
+            tree = outcome.tree;
+            merged.push(*id);
+        }
+        Ok(tree)
+    }
+
+    /// Return the tree of the only commit in `merge_bases`, or the tree of the merge of all of them, oldest first,
+    /// to be used as common ancestor of a merge at `call_depth`.
+    fn virtual_merge_base(
+        merge_bases: &[ObjectId],
+        options: tree::Options,
+        call_depth: usize,
+        objects: &impl gix_object::Find,
+        graph: &mut Graph<'_, merge_base::Flags>,
+        write: &mut WriteFn<'_>,
+    ) -> Result<Option<ObjectId>, Error> {
+        let mut buf = Vec::new();
+        let mut merge_bases = merge_bases.iter().rev();
+        let Some(first) = merge_bases.next() else {
+            return Ok(None);
+        };
+        let mut tree = objects.find_commit(first, &mut buf)?.tree();
+        let mut merged_commits = vec![*first];
+        for next in merge_bases {
+            let inner_merge_bases = gix_revision::merge_base(next, &merged_commits, graph)?.unwrap_or_default();
+            let ancestor_tree =
+                virtual_merge_base(&inner_merge_bases, options, call_depth + 1, objects, graph, &mut *write)?;
+            let next_tree = objects.find_commit(next, &mut buf)?.tree();
+            tree = tree::merge_at_depth(
+                ancestor_tree.as_deref(),
+                &tree,
+                &next_tree,
+                blob::Labels {
+                    ancestor: Some("merged common ancestors".into()),
+                    current: Some("Temporary merge branch 1".into()),
+                    other: Some("Temporary merge branch 2".into()),
+                },
+                options,
+                call_depth + 1,
+                objects,
+                &mut *write,
+            )?
+            .tree;
+            merged_commits.push(*next);
+        }
+        Ok(Some(tree))
+    }
+
+    /// Return the path of the directory in `ours` whose entries are most similar to the ones of `theirs`, looking at most
+    /// two levels deep, or `None` if no directory is more similar than the root of `ours`.
+    fn find_subtree(
+        ours: ObjectId,
+        theirs: ObjectId,
+        objects: &impl gix_object::Find,
+    ) -> Result<Option<BString>, Error> {
+        let mut buf = Vec::new();
+        let theirs = gix_object::Tree::from(objects.find_tree(&theirs, &mut buf)?).entries;
+        let score = |entries: &[gix_object::tree::Entry]| -> isize {
+            let common: isize = theirs
+                .iter()
+                .map(
+                    |theirs| match entries.iter().find(|entry| entry.filename == theirs.filename) {
+                        Some(entry) if entry.oid == theirs.oid => 2,
+                        Some(_) => 1,
+                        None => -1,
+                    },
+                )
+                .sum();
+            let only_ours = entries
+                .iter()
+                .filter(|entry| theirs.iter().all(|theirs| theirs.filename != entry.filename))
+                .count();
+            common - only_ours as isize
+        };
+
+        let mut best: Option<(isize, BString)> = None;
+        let mut queue = VecDeque::from([(BString::default(), ours, 0)]);
+        while let Some((path, id, depth)) = queue.pop_front() {
+            let entries = gix_object::Tree::from(objects.find_tree(&id, &mut buf)?).entries;
+            let score = score(&entries);
+            if best.as_ref().map_or(true, |(best_score, _)| score > *best_score) {
+                best = Some((score, path.clone()));
+            }
+            if depth < 2 {
+                for entry in entries.iter().filter(|entry| entry.mode.is_tree()) {
+                    let mut path = path.clone();
+                    if !path.is_empty() {
+                        path.push(b'/');
+                    }
+                    path.extend_from_slice(&entry.filename);
+                    queue.push_back((path, entry.oid, depth + 1));
+                }
+            }
+        }
+        Ok(best.map(|(_, path)| path).filter(|path| !path.is_empty()))
+    }
+
+    /// Write a copy of the tree `base`, or of an empty tree if `None`, with `tree` placed at `prefix`, and return its id.
+    fn shift_tree(
+        base: Option<ObjectId>,
+        prefix: &BStr,
+        tree: ObjectId,
+        objects: &impl gix_object::Find,
+        write: &mut WriteFn<'_>,
+        buf: &mut Vec<u8>,
+    ) -> Result<ObjectId, Error> {
+        let (name, rest) = match prefix.find_byte(b'/') {
+            Some(pos) => (prefix[..pos].as_bstr(), Some(prefix[pos + 1..].as_bstr())),
+            None => (prefix, None),
+        };
+        let mut entries = match base {
+            Some(id) => gix_object::Tree::from(objects.find_tree(&id, buf)?).entries,
+            None => Vec::new(),
+        };
+        let existing = entries.iter().position(|entry| entry.filename == name);
+        let oid = match rest {
+            Some(rest) => {
+                let base = existing
+                    .map(|pos| &entries[pos])
+                    .filter(|entry| entry.mode.is_tree())
+                    .map(|entry| entry.oid);
+                shift_tree(base, rest, tree, objects, write, buf)?
+            }
+            None => tree,
+        };
+        let entry = gix_object::tree::Entry {
+            mode: gix_object::tree::EntryKind::Tree.into(),
+            filename: name.to_owned(),
+            oid,
+        };
+        match existing {
+            Some(pos) => entries[pos] = entry,
+            None => entries.push(entry),
+        }
+        entries.sort();
+        buf.clear();
+        gix_object::Tree { entries }
+            .write_to(&mut *buf)
+            .expect("writing to a vec never fails");
+        write(gix_object::Kind::Tree, buf).map_err(|source| Error::WriteShiftedTree {
+            prefix: prefix.to_owned(),
+            source,
+        })
+    }
+}
@@ -0,0 +1,13 @@
+//! Merge blobs, trees and commits with three-way merges, similar to what `git merge-file`, `git merge-tree` and `git merge` do.
+#![deny(missing_docs, rust_2018_idioms)]
+#![forbid(unsafe_code)]
+
+///
+pub mod blob;
+
+///
+pub mod commit;
+pub use commit::function::merge;
+
+///
+pub mod tree;
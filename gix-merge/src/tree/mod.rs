@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_hash::{oid, ObjectId};
+use gix_object::{tree::EntryMode, FindExt, WriteTo};
+
+use crate::blob;
+
+mod renames;
+
+/// The error returned by [`merge()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindExistingObject(#[from] gix_object::find::existing_object::Error),
+    #[error("Could not write the merged {kind} at '{path}'")]
+    WriteObject {
+        kind: gix_object::Kind,
+        path: BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+/// An entry of a tree that took part in a [`Conflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The mode of the entry.
+    pub mode: EntryMode,
+    /// The id of the object the entry points to.
+    pub id: ObjectId,
+}
+
+/// The kind of a [`Conflict`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConflictKind {
+    /// Both sides changed the content of a file in overlapping places, and the file in the result contains conflict markers.
+    Content,
+    /// Both sides added a file with different content, which was merged as if the common ancestor was empty,
+    /// and the file in the result contains conflict markers.
+    AddAdd,
+    /// One side deleted the file while the other side modified it, and the modified version was kept.
+    ModifyDelete,
+    /// One side added a directory where the other side has a file, so the file was moved to `renamed_to` instead.
+    FileDirectory {
+        /// The path at which the file can be found in the result.
+        renamed_to: BString,
+    },
+    /// Both sides changed a binary file, and the version of _ours_ was kept.
+    Binary,
+    /// Both sides changed a submodule to different commits, and the commit of _ours_ was kept.
+    Submodule,
+    /// Both sides changed an entry to a different type, like a file and a symbolic link, and the version of _ours_ was kept.
+    DistinctTypes,
+    /// Both sides renamed the file at the conflicting path, but to different locations, and both were kept.
+    RenameRename {
+        /// The path our side renamed the file to.
+        ours: BString,
+        /// The path their side renamed the file to.
+        theirs: BString,
+    },
+    /// One side renamed a file to the conflicting path while the other side deleted it, and the renamed file was kept.
+    RenameDelete {
+        /// The path of the file before it was renamed.
+        source: BString,
+    },
+    /// One side added a file to a directory that the other side renamed, so the file was moved to the conflicting path
+    /// within the renamed directory.
+    ///
+    /// This is only a conflict if [directory renames](Options::directory_renames) are [`DirectoryRenames::Conflict`].
+    DirectoryRename {
+        /// The path the file was added at.
+        original: BString,
+    },
+}
+
+/// A conflict that was encountered during a [`merge()`], along with the versions of the entry on each side.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The path of the entry in the result, relative to the root of the tree.
+    pub path: BString,
+    /// The kind of conflict.
+    pub kind: ConflictKind,
+    /// The entry in the common ancestor, or `None` if it didn't exist there.
+    pub ancestor: Option<Entry>,
+    /// The entry on our side, or `None` if it didn't exist there.
+    pub ours: Option<Entry>,
+    /// The entry on their side, or `None` if it didn't exist there.
+    pub theirs: Option<Entry>,
+}
+
+impl Conflict {
+    /// Return the entries that represent this conflict in the index along with their path and stage, which is `1` for the
+    /// common ancestor, `2` for _ours_ and `3` for _theirs_, just like `git` would write them.
+    ///
+    /// Directories are never part of the index, and the entries of renamed files are placed at their new location.
+    pub fn index_entries(&self) -> impl Iterator<Item = (&BStr, u32, Entry)> + '_ {
+        let paths: [&BStr; 3] = match &self.kind {
+            ConflictKind::FileDirectory { renamed_to } => [renamed_to.as_ref(); 3],
+            ConflictKind::RenameRename { ours, theirs } => [self.path.as_ref(), ours.as_ref(), theirs.as_ref()],
+            _ => [self.path.as_ref(); 3],
+        };
+        [self.ancestor, self.ours, self.theirs]
+            .into_iter()
+            .zip(paths)
+            .zip(1..)
+            .filter_map(|((entry, path), stage)| entry.filter(|e| e.mode.is_no_tree()).map(|e| (path, stage, e)))
+    }
+
+    /// Return a message describing this conflict similar to the one `git merge` prints, using the names in `labels`
+    /// to refer to _ours_ and _theirs_.
+    pub fn message(&self, labels: &blob::Labels<'_>) -> String {
+        let ours = labels.current.unwrap_or("ours".into());
+        let theirs = labels.other.unwrap_or("theirs".into());
+        let path = &self.path;
+        match &self.kind {
+            ConflictKind::Content => format!("CONFLICT (content): Merge conflict in {path}"),
+            ConflictKind::AddAdd => format!("CONFLICT (add/add): Merge conflict in {path}"),
+            ConflictKind::ModifyDelete => {
+                let (deleted_in, modified_in) = if self.ours.is_none() {
+                    (ours, theirs)
+                } else {
+                    (theirs, ours)
+                };
+                format!(
+                    "CONFLICT (modify/delete): {path} deleted in {deleted_in} and modified in {modified_in}.  Version {modified_in} of {path} left in tree."
+                )
+            }
+            ConflictKind::FileDirectory { renamed_to } => {
+                let file_in = if self.ours.map_or(false, |e| e.mode.is_no_tree()) {
+                    ours
+                } else {
+                    theirs
+                };
+                format!(
+                    "CONFLICT (file/directory): directory in the way of {path} from {file_in}; moving it to {renamed_to} instead."
+                )
+            }
+            ConflictKind::Binary => format!("CONFLICT (binary): Merge conflict in {path}"),
+            ConflictKind::Submodule => format!("CONFLICT (submodule): Merge conflict in {path}"),
+            ConflictKind::DistinctTypes => {
+                format!("CONFLICT (distinct types): {path} had different types on each side")
+            }
+            ConflictKind::RenameRename {
+                ours: ours_path,
+                theirs: theirs_path,
+            } => format!(
+                "CONFLICT (rename/rename): {path} renamed to {ours_path} in {ours} and to {theirs_path} in {theirs}."
+            ),
+            ConflictKind::RenameDelete { source } => {
+                let (renamed_in, deleted_in) = if self.ours.is_some() {
+                    (ours, theirs)
+                } else {
+                    (theirs, ours)
+                };
+                format!("CONFLICT (rename/delete): {source} renamed to {path} in {renamed_in}, but deleted in {deleted_in}.")
+            }
+            ConflictKind::DirectoryRename { original } => {
+                let (added_in, renamed_in) = if self.ours.is_some() {
+                    (ours, theirs)
+                } else {
+                    (theirs, ours)
+                };
+                format!(
+                    "CONFLICT (file location): {original} added in {added_in} inside a directory that was renamed in {renamed_in}, suggesting it should perhaps be moved to {path}."
+                )
+            }
+        }
+    }
+}
+
+/// The way renamed files are detected, similar to `merge.renames` and `merge.renameLimit`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rewrites {
+    /// The minimal fraction of content that a deleted and an added file must have in common to be considered a rename,
+    /// with `0.5` being the default like `git merge -X find-renames=50%`.
+    ///
+    /// Files with the same content are always considered renamed.
+    pub percentage: f32,
+    /// The maximum amount of deleted or added files for which similar content is searched, or `0` for no limit.
+    ///
+    /// This avoids quadratic runtime if a lot of files changed, while files with the same content are still matched.
+    pub limit: usize,
+}
+
+impl Default for Rewrites {
+    fn default() -> Self {
+        Rewrites {
+            percentage: 0.5,
+            limit: 1000,
+        }
+    }
+}
+
+/// The way files added to directories that were renamed on the other side are handled, similar to `merge.directoryRenames`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirectoryRenames {
+    /// Keep added files where they are.
+    Disabled,
+    /// Move added files into the renamed directory, but report a [conflict](ConflictKind::DirectoryRename) for each of them.
+    #[default]
+    Conflict,
+    /// Move added files into the renamed directory.
+    Apply,
+}
+
+/// Options for [`merge()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    /// The options to use when merging the content of files that were changed on both sides.
+    pub blob: blob::Options,
+    /// If set, renamed files are detected on each side, so that changes to a file are merged with changes to the file
+    /// at its new location.
+    pub rewrites: Option<Rewrites>,
+    /// The way files added to directories that were renamed on the other side are handled, if `rewrites` are set.
+    ///
+    /// A directory is considered renamed if it doesn't exist anymore on one side, and most of its files were renamed
+    /// to the same directory.
+    pub directory_renames: DirectoryRenames,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            blob: Default::default(),
+            rewrites: Some(Default::default()),
+            directory_renames: Default::default(),
+        }
+    }
+}
+
+/// The result of a [`merge()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The id of the merged tree, which is written even if there were conflicts.
+    ///
+    /// Files with conflicting content contain conflict markers, and for all other conflicts the version of _ours_ was kept.
+    pub tree: ObjectId,
+    /// All conflicts that were encountered, in the order of their paths.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Outcome {
+    /// Return `true` if there were conflicts which have to be resolved before the [tree](Self::tree) can be used
+    /// for a merge commit.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// Merge the changes from the `ancestor` tree to `ours` and from `ancestor` to `theirs` into a new tree, similar to
+/// `git merge-tree --write-tree`, without the need for a worktree or an index.
+///
+/// If `ancestor` is `None`, the trees are merged as if they had an empty common ancestor.
+/// Entries that were changed on only one side, or in the same way on both sides, are taken as they are, and files that were
+/// changed on both sides are merged [line by line](blob::merge()) with `labels` used for conflict markers.
+/// Files renamed on one side are [detected](Options::rewrites) so changes of the other side end up at the new location.
+/// All conflicts that can't be resolved are returned in the [outcome](Outcome) along with the id of the merged tree.
+///
+/// `objects` is used to read trees and blobs, and `write` is called to write all new trees and blobs, returning their id.
+pub fn merge<E>(
+    ancestor: Option<&oid>,
+    ours: &oid,
+    theirs: &oid,
+    labels: blob::Labels<'_>,
+    options: Options,
+    objects: &impl gix_object::Find,
+    write: impl FnMut(gix_object::Kind, &[u8]) -> Result<ObjectId, E>,
+) -> Result<Outcome, Error>
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    merge_at_depth(ancestor, ours, theirs, labels, options, 0, objects, write)
+}
+
+/// Like [`merge()`], but merge at `call_depth`, which is greater than `0` if the result is used as virtual merge-base
+/// in a recursive merge.
+///
+/// With each level, conflict markers get longer so they can be told apart from the ones of the final merge, and binary
+/// conflicts are resolved by using the version of the common ancestor.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn merge_at_depth<E>(
+    ancestor: Option<&oid>,
+    ours: &oid,
+    theirs: &oid,
+    labels: blob::Labels<'_>,
+    mut options: Options,
+    call_depth: usize,
+    objects: &impl gix_object::Find,
+    mut write: impl FnMut(gix_object::Kind, &[u8]) -> Result<ObjectId, E>,
+) -> Result<Outcome, Error>
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    if call_depth > 0 {
+        options.blob.marker_size += call_depth * 2;
+        options.blob.conflict_style = blob::ConflictStyle::Merge;
+        options.blob.favor = None;
+    }
+    let mut state = State {
+        objects,
+        write: |kind, data: &[u8]| write(kind, data).map_err(Into::into),
+        labels,
+        options,
+        call_depth,
+        object_hash: ours.kind(),
+        virtual_trees: HashMap::new(),
+        path: BString::default(),
+        conflicts: Vec::new(),
+        buf: Vec::new(),
+    };
+    let (mut ancestor, mut ours, mut theirs) = (ancestor.map(ToOwned::to_owned), ours.to_owned(), theirs.to_owned());
+    if let (Some(ancestor_tree), Some(rewrites)) = (ancestor, options.rewrites) {
+        let views = state.apply_renames(ancestor_tree, ours, theirs, rewrites)?;
+        (ancestor, ours, theirs) = (Some(views[0]), views[1], views[2]);
+    }
+    let tree = state.merge_trees(ancestor, Some(ours), Some(theirs))?;
+    let mut conflicts = state.conflicts;
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(Outcome { tree, conflicts })
+}
+
+struct State<'a, Find, Write> {
+    objects: &'a Find,
+    write: Write,
+    labels: blob::Labels<'a>,
+    options: Options,
+    call_depth: usize,
+    object_hash: gix_hash::Kind,
+    /// Trees that were created by moving renamed entries, which are only written if they end up in the result.
+    virtual_trees: HashMap<ObjectId, Vec<gix_object::tree::Entry>>,
+    /// The path of the tree that is currently merged.
+    path: BString,
+    conflicts: Vec<Conflict>,
+    buf: Vec<u8>,
+}
+
+impl<Find, Write> State<'_, Find, Write>
+where
+    Find: gix_object::Find,
+    Write: FnMut(gix_object::Kind, &[u8]) -> Result<ObjectId, Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    /// Merge the trees of all sides, with `None` meaning an empty tree, and return the id of the written result.
+    fn merge_trees(
+        &mut self,
+        ancestor: Option<ObjectId>,
+        ours: Option<ObjectId>,
+        theirs: Option<ObjectId>,
+    ) -> Result<ObjectId, Error> {
+        let [ancestor, ours, theirs] = [ancestor, ours, theirs].map(|id| -> Result<_, Error> {
+            Ok(match id {
+                Some(id) => self.tree_entries(&id)?,
+                None => Vec::new(),
+            })
+        });
+        let (mut ancestor, mut ours, mut theirs) = (ancestor?, ours?, theirs?);
+        // Tree entries are sorted with directories compared as if they had a trailing slash, so sort them by name
+        // to visit all entries of the same name at once.
+        for side in [&mut ancestor, &mut ours, &mut theirs] {
+            side.sort_by(|a, b| a.filename.cmp(&b.filename));
+        }
+        let mut sides = [
+            ancestor.iter().peekable(),
+            ours.iter().peekable(),
+            theirs.iter().peekable(),
+        ];
+
+        let mut entries = Vec::new();
+        while let Some(name) = sides
+            .iter_mut()
+            .filter_map(|side| side.peek().copied().map(|entry| entry.filename.as_bstr()))
+            .min()
+        {
+            let [a, o, t] = [0, 1, 2].map(|idx| {
+                sides[idx].next_if(|entry| entry.filename == name).map(|entry| Entry {
+                    mode: entry.mode,
+                    id: entry.oid,
+                })
+            });
+            let path_len = self.path.len();
+            if !self.path.is_empty() {
+                self.path.push(b'/');
+            }
+            self.path.push_str(name);
+            let merged = if o == t || a == t {
+                o
+            } else if a == o {
+                t
+            } else {
+                self.merge_entry(a, o, t, name, &mut entries)?
+            };
+            self.path.truncate(path_len);
+            if let Some(tree) = merged.filter(|entry| entry.mode.is_tree()) {
+                self.write_virtual_tree(tree.id)?;
+            }
+            entries.extend(merged.map(|entry| gix_object::tree::Entry {
+                mode: entry.mode,
+                filename: name.to_owned(),
+                oid: entry.id,
+            }));
+        }
+        entries.sort();
+        let tree = gix_object::Tree { entries };
+        self.buf.clear();
+        tree.write_to(&mut self.buf).expect("writing to a vec never fails");
+        self.write_object(gix_object::Kind::Tree)
+    }
+
+    /// Merge an entry that was changed differently on both sides and return the entry to put into the result, if any.
+    /// Additional entries, like files that had to be moved out of the way, are added to `entries`.
+    fn merge_entry(
+        &mut self,
+        ancestor: Option<Entry>,
+        ours: Option<Entry>,
+        theirs: Option<Entry>,
+        name: &BStr,
+        entries: &mut Vec<gix_object::tree::Entry>,
+    ) -> Result<Option<Entry>, Error> {
+        let tree_id = |entry: Option<Entry>| entry.filter(|e| e.mode.is_tree()).map(|e| e.id);
+        let is_tree = |entry: Option<Entry>| entry.map_or(false, |e| e.mode.is_tree());
+        let conflict = |path: &BString, kind| Conflict {
+            path: path.clone(),
+            kind,
+            ancestor,
+            ours,
+            theirs,
+        };
+
+        if is_tree(ours) || is_tree(theirs) {
+            // A directory exists on at least one side, so it's merged with sides that don't have a directory being empty.
+            // This also turns a deleted directory into conflicts for each of its modified files.
+            let file = match (ours, theirs) {
+                (Some(ours), _) if ours.mode.is_no_tree() => Some((ours, self.labels.current.unwrap_or("ours".into()))),
+                (_, Some(theirs)) if theirs.mode.is_no_tree() => {
+                    Some((theirs, self.labels.other.unwrap_or("theirs".into())))
+                }
+                _ => None,
+            };
+            if let Some((file, label)) = file {
+                // Like `git`, avoid creating directories with labels that look like paths, e.g. `origin/main`.
+                let mut renamed_name = name.to_owned();
+                renamed_name.push(b'~');
+                renamed_name.extend(label.iter().map(|b| if *b == b'/' { b'_' } else { *b }));
+                let mut renamed_to = self.path.clone();
+                renamed_to.truncate(self.path.len() - name.len());
+                renamed_to.push_str(&renamed_name);
+                self.conflicts
+                    .push(conflict(&self.path, ConflictKind::FileDirectory { renamed_to }));
+                entries.push(gix_object::tree::Entry {
+                    mode: file.mode,
+                    filename: renamed_name,
+                    oid: file.id,
+                });
+            }
+            let id = self.merge_trees(tree_id(ancestor), tree_id(ours), tree_id(theirs))?;
+            return Ok((id != ObjectId::empty_tree(id.kind())).then_some(Entry {
+                mode: gix_object::tree::EntryKind::Tree.into(),
+                id,
+            }));
+        }
+
+        let (ours, theirs) = match (ours, theirs) {
+            (Some(ours), Some(theirs)) => (ours, theirs),
+            (Some(modified), None) | (None, Some(modified)) => {
+                self.conflicts.push(conflict(&self.path, ConflictKind::ModifyDelete));
+                return Ok(Some(modified));
+            }
+            (None, None) => unreachable!("both sides are equal if they are both deleted"),
+        };
+        if ours.mode.is_commit() || theirs.mode.is_commit() {
+            let kind = if ours.mode == theirs.mode {
+                ConflictKind::Submodule
+            } else {
+                ConflictKind::DistinctTypes
+            };
+            self.conflicts.push(conflict(&self.path, kind));
+            return Ok(Some(ours));
+        }
+        if ours.mode.is_link() != theirs.mode.is_link() {
+            self.conflicts.push(conflict(&self.path, ConflictKind::DistinctTypes));
+            return Ok(Some(ours));
+        }
+        if ours.mode.is_link() {
+            self.conflicts.push(conflict(&self.path, ConflictKind::Content));
+            return Ok(Some(ours));
+        }
+
+        // Both sides are files, so their mode and content are merged separately.
+        let ancestor = ancestor.filter(|e| e.mode.is_blob());
+        let mode = match ancestor {
+            Some(ancestor) if ancestor.mode == ours.mode => theirs.mode,
+            _ => ours.mode,
+        };
+        if ours.id == theirs.id {
+            return Ok(Some(Entry { mode, id: ours.id }));
+        }
+        let [ancestor_data, ours_data, theirs_data] =
+            [ancestor.map(|e| e.id), Some(ours.id), Some(theirs.id)].map(|id| -> Result<_, Error> {
+                Ok(match id {
+                    Some(id) => self.objects.find_blob(&id, &mut self.buf)?.data.to_owned(),
+                    None => Vec::new(),
+                })
+            });
+        let (ancestor_data, ours_data, theirs_data) = (ancestor_data?, ours_data?, theirs_data?);
+        if [&ancestor_data, &ours_data, &theirs_data]
+            .into_iter()
+            .any(|data| blob::is_binary(data))
+        {
+            self.conflicts.push(conflict(&self.path, ConflictKind::Binary));
+            return Ok(Some(match ancestor {
+                Some(ancestor) if self.call_depth > 0 => ancestor,
+                _ => Entry { mode, id: ours.id },
+            }));
+        }
+
+        let mut out = std::mem::take(&mut self.buf);
+        out.clear();
+        let resolution = blob::merge(
+            &ancestor_data,
+            &ours_data,
+            &theirs_data,
+            self.labels,
+            self.options.blob,
+            &mut out,
+        );
+        if resolution == blob::Resolution::Conflict {
+            let kind = if ancestor.is_some() {
+                ConflictKind::Content
+            } else {
+                ConflictKind::AddAdd
+            };
+            self.conflicts.push(conflict(&self.path, kind));
+        }
+        self.buf = out;
+        let id = self.write_object(gix_object::Kind::Blob)?;
+        Ok(Some(Entry { mode, id }))
+    }
+
+    /// Return the entries of the tree with `id`, which may be a virtual tree.
+    fn tree_entries(&mut self, id: &oid) -> Result<Vec<gix_object::tree::Entry>, Error> {
+        if id == ObjectId::empty_tree(id.kind()) {
+            return Ok(Vec::new());
+        }
+        Ok(match self.virtual_trees.get(id) {
+            Some(entries) => entries.clone(),
+            None => gix_object::Tree::from(self.objects.find_tree(id, &mut self.buf)?).entries,
+        })
+    }
+
+    /// Store `entries` as virtual tree and return its id, without writing it.
+    fn virtual_tree(&mut self, mut entries: Vec<gix_object::tree::Entry>) -> ObjectId {
+        entries.sort();
+        let tree = gix_object::Tree { entries };
+        self.buf.clear();
+        tree.write_to(&mut self.buf).expect("writing to a vec never fails");
+        let id = gix_object::compute_hash(self.object_hash, gix_object::Kind::Tree, &self.buf);
+        self.virtual_trees.insert(id, tree.entries);
+        id
+    }
+
+    /// Write the tree with `id` along with all of its subtrees if it is a virtual tree.
+    fn write_virtual_tree(&mut self, id: ObjectId) -> Result<(), Error> {
+        let Some(entries) = self.virtual_trees.remove(&id) else {
+            return Ok(());
+        };
+        for subtree in entries.iter().filter(|entry| entry.mode.is_tree()) {
+            self.write_virtual_tree(subtree.oid)?;
+        }
+        let tree = gix_object::Tree { entries };
+        self.buf.clear();
+        tree.write_to(&mut self.buf).expect("writing to a vec never fails");
+        self.write_object(gix_object::Kind::Tree)?;
+        Ok(())
+    }
+
+    /// Write the object in our buffer as `kind`.
+    fn write_object(&mut self, kind: gix_object::Kind) -> Result<ObjectId, Error> {
+        (self.write)(kind, &self.buf).map_err(|source| Error::WriteObject {
+            kind,
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
@@ -0,0 +1,512 @@
+use std::collections::BTreeSet;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_diff::directory_renames::DirectoryRename;
+use gix_hash::ObjectId;
+use gix_object::FindExt;
+
+use super::{Conflict, ConflictKind, DirectoryRenames, Entry, Error, Rewrites, State};
+
+/// The files that were deleted and added on one side compared to the common ancestor, along with their path.
+///
+/// Submodules are never considered renamed and thus are not listed.
+#[derive(Default)]
+struct Changes {
+    deleted: Vec<(BString, Entry)>,
+    added: Vec<(BString, Entry)>,
+}
+
+impl Changes {
+    fn deleted(&self, path: &BStr) -> Option<Entry> {
+        self.deleted.iter().find(|(p, _)| p == path).map(|(_, e)| *e)
+    }
+
+    fn added(&self, path: &BStr) -> Option<Entry> {
+        self.added.iter().find(|(p, _)| p == path).map(|(_, e)| *e)
+    }
+}
+
+/// A file that was renamed from `source` to `target`.
+struct Rename {
+    source: BString,
+    target: BString,
+}
+
+/// An entry to move from one path to another within a tree.
+type Move = (BString, BString);
+
+impl<Find, Write> State<'_, Find, Write>
+where
+    Find: gix_object::Find,
+    Write: FnMut(gix_object::Kind, &[u8]) -> Result<ObjectId, Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    /// Detect renames on both sides and return views of the `ancestor`, `ours` and `theirs` trees in which files are moved
+    /// to the location they were renamed to on the other side, so that a merge of the views merges the content of renamed
+    /// files with the content at their original location.
+    ///
+    /// Conflicts of renames with other changes are recorded right away.
+    pub(super) fn apply_renames(
+        &mut self,
+        ancestor: ObjectId,
+        ours: ObjectId,
+        theirs: ObjectId,
+        rewrites: Rewrites,
+    ) -> Result<[ObjectId; 3], Error> {
+        let ours_changes = self.changes(ancestor, ours)?;
+        let theirs_changes = self.changes(ancestor, theirs)?;
+        let ours_renames = self.find_renames(&ours_changes, rewrites)?;
+        let theirs_renames = self.find_renames(&theirs_changes, rewrites)?;
+
+        let (mut ancestor_moves, mut ours_moves, mut theirs_moves) = (Vec::new(), Vec::new(), Vec::new());
+        for rename in &ours_renames {
+            match theirs_renames.iter().find(|r| r.source == rename.source) {
+                Some(theirs_rename) if theirs_rename.target == rename.target => {
+                    ancestor_moves.push((rename.source.clone(), rename.target.clone()));
+                }
+                Some(theirs_rename) => self.conflicts.push(Conflict {
+                    path: rename.source.clone(),
+                    kind: ConflictKind::RenameRename {
+                        ours: rename.target.clone(),
+                        theirs: theirs_rename.target.clone(),
+                    },
+                    ancestor: ours_changes.deleted(rename.source.as_ref()),
+                    ours: ours_changes.added(rename.target.as_ref()),
+                    theirs: theirs_changes.added(theirs_rename.target.as_ref()),
+                }),
+                None => self.resolve_one_sided_rename(
+                    rename,
+                    &ours_changes,
+                    &theirs_changes,
+                    &mut ancestor_moves,
+                    &mut theirs_moves,
+                    true,
+                ),
+            }
+        }
+        for rename in &theirs_renames {
+            if ours_renames.iter().all(|r| r.source != rename.source) {
+                self.resolve_one_sided_rename(
+                    rename,
+                    &theirs_changes,
+                    &ours_changes,
+                    &mut ancestor_moves,
+                    &mut ours_moves,
+                    false,
+                );
+            }
+        }
+
+        if self.options.directory_renames != DirectoryRenames::Disabled {
+            let ours_directory_renames = self.directory_renames(&ours_renames, ours)?;
+            let theirs_directory_renames = self.directory_renames(&theirs_renames, theirs)?;
+            self.move_into_renamed_directories(
+                &theirs_changes,
+                &theirs_renames,
+                &ours_directory_renames,
+                &theirs_directory_renames,
+                [ours, theirs],
+                &mut theirs_moves,
+                false,
+            )?;
+            self.move_into_renamed_directories(
+                &ours_changes,
+                &ours_renames,
+                &theirs_directory_renames,
+                &ours_directory_renames,
+                [ours, theirs],
+                &mut ours_moves,
+                true,
+            )?;
+        }
+
+        Ok([
+            self.apply_moves(ancestor, &ancestor_moves)?,
+            self.apply_moves(ours, &ours_moves)?,
+            self.apply_moves(theirs, &theirs_moves)?,
+        ])
+    }
+
+    /// Handle a `rename` of one side that wasn't renamed on the `other` side, by moving the file in the ancestor and
+    /// on the other side unless it was deleted or its new location is taken.
+    fn resolve_one_sided_rename(
+        &mut self,
+        rename: &Rename,
+        changes: &Changes,
+        other_changes: &Changes,
+        ancestor_moves: &mut Vec<Move>,
+        other_moves: &mut Vec<Move>,
+        renamed_by_ours: bool,
+    ) {
+        if other_changes.deleted(rename.source.as_ref()).is_some() {
+            let renamed = changes.added(rename.target.as_ref());
+            self.conflicts.push(Conflict {
+                path: rename.target.clone(),
+                kind: ConflictKind::RenameDelete {
+                    source: rename.source.clone(),
+                },
+                ancestor: changes.deleted(rename.source.as_ref()),
+                ours: renamed.filter(|_| renamed_by_ours),
+                theirs: renamed.filter(|_| !renamed_by_ours),
+            });
+        } else if other_changes.added(rename.target.as_ref()).is_none() {
+            ancestor_moves.push((rename.source.clone(), rename.target.clone()));
+            other_moves.push((rename.source.clone(), rename.target.clone()));
+        }
+    }
+
+    /// Move files that were added on one side into directories that were renamed on the other side, as found in
+    /// `directory_renames`, and record these moves in `moves`.
+    #[allow(clippy::too_many_arguments)]
+    fn move_into_renamed_directories(
+        &mut self,
+        changes: &Changes,
+        renames: &[Rename],
+        directory_renames: &[DirectoryRename],
+        own_directory_renames: &[DirectoryRename],
+        [ours, theirs]: [ObjectId; 2],
+        moves: &mut Vec<Move>,
+        added_by_ours: bool,
+    ) -> Result<(), Error> {
+        for (path, entry) in &changes.added {
+            if renames.iter().any(|r| r.target == *path) {
+                continue;
+            }
+            let Some(directory_rename) = directory_renames
+                .iter()
+                .filter(|d| gix_path::is_within(path.as_ref(), d.source.as_ref()))
+                .max_by_key(|d| d.source.len())
+            else {
+                continue;
+            };
+            if own_directory_renames
+                .iter()
+                .any(|d| d.source == directory_rename.source)
+            {
+                continue;
+            }
+            let new_path = directory_rename
+                .relocate(path.as_ref())
+                .expect("the path is within the renamed directory");
+            if self.entry_at(ours, new_path.as_ref())?.is_some() || self.entry_at(theirs, new_path.as_ref())?.is_some()
+            {
+                continue;
+            }
+            if self.options.directory_renames == DirectoryRenames::Conflict {
+                self.conflicts.push(Conflict {
+                    path: new_path.clone(),
+                    kind: ConflictKind::DirectoryRename { original: path.clone() },
+                    ancestor: None,
+                    ours: Some(*entry).filter(|_| added_by_ours),
+                    theirs: Some(*entry).filter(|_| !added_by_ours),
+                });
+            }
+            moves.push((path.clone(), new_path));
+        }
+        Ok(())
+    }
+
+    /// Find directories that were renamed according to `renames`.
+    ///
+    /// A directory is renamed if it doesn't exist in the `side` tree anymore, and most of the files renamed out of it
+    /// went to the same directory.
+    fn directory_renames(&mut self, renames: &[Rename], side: ObjectId) -> Result<Vec<DirectoryRename>, Error> {
+        let mut err = None;
+        let out = gix_diff::directory_renames::detect(
+            renames.iter().map(|r| (r.source.as_ref(), r.target.as_ref())),
+            |directory| match self.entry_at(side, directory) {
+                Ok(entry) => entry.is_some(),
+                Err(e) => {
+                    err.get_or_insert(e);
+                    true
+                }
+            },
+        );
+        err.map_or(Ok(out), Err)
+    }
+
+    /// Return the files deleted and added in `side` compared to `ancestor`.
+    fn changes(&mut self, ancestor: ObjectId, side: ObjectId) -> Result<Changes, Error> {
+        let mut out = Changes::default();
+        self.collect_changes(Some(ancestor), Some(side), &mut BString::default(), &mut out)?;
+        Ok(out)
+    }
+
+    fn collect_changes(
+        &mut self,
+        ancestor: Option<ObjectId>,
+        side: Option<ObjectId>,
+        prefix: &mut BString,
+        out: &mut Changes,
+    ) -> Result<(), Error> {
+        if ancestor == side {
+            return Ok(());
+        }
+        let ancestor = match ancestor {
+            Some(id) => self.tree_entries(&id)?,
+            None => Vec::new(),
+        };
+        let side = match side {
+            Some(id) => self.tree_entries(&id)?,
+            None => Vec::new(),
+        };
+        let names: BTreeSet<_> = ancestor.iter().chain(&side).map(|e| e.filename.clone()).collect();
+        let find = |entries: &[gix_object::tree::Entry], name: &BStr| {
+            entries.iter().find(|e| e.filename == name).map(|e| Entry {
+                mode: e.mode,
+                id: e.oid,
+            })
+        };
+        for name in names {
+            let (a, s) = (find(&ancestor, name.as_ref()), find(&side, name.as_ref()));
+            if a == s {
+                continue;
+            }
+            let prefix_len = prefix.len();
+            if !prefix.is_empty() {
+                prefix.push(b'/');
+            }
+            prefix.push_str(&name);
+            let tree = |e: Option<Entry>| e.filter(|e| e.mode.is_tree()).map(|e| e.id);
+            if tree(a).is_some() || tree(s).is_some() {
+                self.collect_changes(tree(a), tree(s), prefix, out)?;
+            }
+            let file = |e: Option<Entry>| e.filter(|e| e.mode.is_blob_or_symlink());
+            match (file(a), file(s)) {
+                (Some(_), Some(_)) => {}
+                (Some(a), None) => out.deleted.push((prefix.clone(), a)),
+                (None, Some(s)) => out.added.push((prefix.clone(), s)),
+                (None, None) => {}
+            }
+            prefix.truncate(prefix_len);
+        }
+        Ok(())
+    }
+
+    /// Pair deleted and added files of `changes` that have the same or similar content, as configured by `rewrites`.
+    fn find_renames(&mut self, changes: &Changes, rewrites: Rewrites) -> Result<Vec<Rename>, Error> {
+        let mut out = Vec::new();
+        let mut deleted_used = vec![false; changes.deleted.len()];
+        let mut added_used = vec![false; changes.added.len()];
+
+        // Files with the same content are renamed, preferring the ones with the same name.
+        for (added_idx, (added_path, added)) in changes.added.iter().enumerate() {
+            let candidates = || {
+                changes.deleted.iter().enumerate().filter(|(idx, (_, deleted))| {
+                    !deleted_used[*idx] && deleted.id == added.id && deleted.mode.is_link() == added.mode.is_link()
+                })
+            };
+            let best = candidates()
+                .find(|(_, (deleted_path, _))| basename(deleted_path.as_ref()) == basename(added_path.as_ref()))
+                .or_else(|| candidates().next())
+                .map(|(idx, _)| idx);
+            if let Some(deleted_idx) = best {
+                deleted_used[deleted_idx] = true;
+                added_used[added_idx] = true;
+                out.push(Rename {
+                    source: changes.deleted[deleted_idx].0.clone(),
+                    target: added_path.clone(),
+                });
+            }
+        }
+
+        let unused = |entries: &[(BString, Entry)], used: &[bool]| -> Vec<usize> {
+            (0..entries.len())
+                .filter(|idx| !used[*idx] && entries[*idx].1.mode.is_blob())
+                .collect()
+        };
+        let (deleted, added) = (
+            unused(&changes.deleted, &deleted_used),
+            unused(&changes.added, &added_used),
+        );
+        if deleted.is_empty()
+            || added.is_empty()
+            || (rewrites.limit != 0 && (deleted.len() > rewrites.limit || added.len() > rewrites.limit))
+        {
+            return Ok(out);
+        }
+        let mut load = |entries: &[(BString, Entry)], indices: &[usize]| -> Result<Vec<Vec<u8>>, Error> {
+            indices
+                .iter()
+                .map(|idx| {
+                    Ok(self
+                        .objects
+                        .find_blob(&entries[*idx].1.id, &mut self.buf)?
+                        .data
+                        .to_owned())
+                })
+                .collect()
+        };
+        let deleted_data = load(&changes.deleted, &deleted)?;
+        let added_data = load(&changes.added, &added)?;
+
+        let mut candidates = Vec::new();
+        for (deleted_pos, deleted_data) in deleted_data.iter().enumerate() {
+            for (added_pos, added_data) in added_data.iter().enumerate() {
+                let score = similarity(deleted_data, added_data, rewrites.percentage);
+                if score >= rewrites.percentage {
+                    candidates.push((score, deleted_pos, added_pos));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| (a.1, a.2).cmp(&(b.1, b.2))));
+        for (_, deleted_pos, added_pos) in candidates {
+            let (deleted_idx, added_idx) = (deleted[deleted_pos], added[added_pos]);
+            if deleted_used[deleted_idx] || added_used[added_idx] {
+                continue;
+            }
+            deleted_used[deleted_idx] = true;
+            added_used[added_idx] = true;
+            out.push(Rename {
+                source: changes.deleted[deleted_idx].0.clone(),
+                target: changes.added[added_idx].0.clone(),
+            });
+        }
+        Ok(out)
+    }
+
+    /// Apply all `moves` to the tree with `id` and return the id of the resulting virtual tree.
+    ///
+    /// Moves of entries that don't exist, or to locations that are already taken, are skipped.
+    fn apply_moves(&mut self, mut id: ObjectId, moves: &[Move]) -> Result<ObjectId, Error> {
+        for (from, to) in moves {
+            let (without_entry, entry) = self.remove_entry(id, from.as_ref())?;
+            let Some(entry) = entry else { continue };
+            if let Some(new_id) = self.insert_entry(Some(without_entry), to.as_ref(), entry)? {
+                id = new_id;
+            }
+        }
+        Ok(id)
+    }
+
+    /// Remove the non-tree entry at `path` from the tree with `id` and return the id of the new tree along with the
+    /// removed entry, or the unchanged `id` if there was no such entry.
+    fn remove_entry(&mut self, id: ObjectId, path: &BStr) -> Result<(ObjectId, Option<Entry>), Error> {
+        let (name, rest) = split_first_component(path);
+        let mut entries = self.tree_entries(&id)?;
+        let Some(pos) = entries.iter().position(|e| e.filename == name) else {
+            return Ok((id, None));
+        };
+        let removed = match rest {
+            None if entries[pos].mode.is_no_tree() => {
+                let entry = entries.remove(pos);
+                Entry {
+                    mode: entry.mode,
+                    id: entry.oid,
+                }
+            }
+            Some(rest) if entries[pos].mode.is_tree() => {
+                let (subtree, removed) = self.remove_entry(entries[pos].oid, rest)?;
+                let Some(removed) = removed else {
+                    return Ok((id, None));
+                };
+                if subtree == ObjectId::empty_tree(subtree.kind()) {
+                    entries.remove(pos);
+                } else {
+                    entries[pos].oid = subtree;
+                }
+                removed
+            }
+            _ => return Ok((id, None)),
+        };
+        Ok((self.virtual_tree(entries), Some(removed)))
+    }
+
+    /// Insert `entry` at `path` into the tree with `id`, or into an empty tree if `None`, and return the id of the new tree,
+    /// or `None` if there already is an entry at `path` or one of its parent directories is a file.
+    fn insert_entry(&mut self, id: Option<ObjectId>, path: &BStr, entry: Entry) -> Result<Option<ObjectId>, Error> {
+        let (name, rest) = split_first_component(path);
+        let mut entries = match id {
+            Some(id) => self.tree_entries(&id)?,
+            None => Vec::new(),
+        };
+        let existing = entries.iter().position(|e| e.filename == name);
+        match (rest, existing) {
+            (None, None) => entries.push(gix_object::tree::Entry {
+                mode: entry.mode,
+                filename: name.to_owned(),
+                oid: entry.id,
+            }),
+            (Some(rest), None) => {
+                let Some(subtree) = self.insert_entry(None, rest, entry)? else {
+                    return Ok(None);
+                };
+                entries.push(gix_object::tree::Entry {
+                    mode: gix_object::tree::EntryKind::Tree.into(),
+                    filename: name.to_owned(),
+                    oid: subtree,
+                });
+            }
+            (Some(rest), Some(pos)) if entries[pos].mode.is_tree() => {
+                let Some(subtree) = self.insert_entry(Some(entries[pos].oid), rest, entry)? else {
+                    return Ok(None);
+                };
+                entries[pos].oid = subtree;
+            }
+            _ => return Ok(None),
+        }
+        Ok(Some(self.virtual_tree(entries)))
+    }
+
+    /// Return the entry at `path` in the tree with `id`, if there is one.
+    fn entry_at(&mut self, mut id: ObjectId, path: &BStr) -> Result<Option<Entry>, Error> {
+        let mut path = Some(path);
+        while let Some(remaining) = path {
+            let (name, rest) = split_first_component(remaining);
+            let entries = self.tree_entries(&id)?;
+            let Some(entry) = entries.into_iter().find(|e| e.filename == name) else {
+                return Ok(None);
+            };
+            if rest.is_none() {
+                return Ok(Some(Entry {
+                    mode: entry.mode,
+                    id: entry.oid,
+                }));
+            }
+            if entry.mode.is_no_tree() {
+                return Ok(None);
+            }
+            id = entry.oid;
+            path = rest;
+        }
+        Ok(None)
+    }
+}
+
+/// Return the fraction of the bytes of `a` and `b` that are in lines both have in common, relative to the larger one,
+/// or `0.0` if it's clear that it can't reach the `minimum`.
+fn similarity(a: &[u8], b: &[u8], minimum: f32) -> f32 {
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    if (a.len().min(b.len()) as f32) < max_len as f32 * minimum {
+        return 0.0;
+    }
+    let input = imara_diff::intern::InternedInput::new(
+        imara_diff::sources::byte_lines_with_terminator(a),
+        imara_diff::sources::byte_lines_with_terminator(b),
+    );
+    let mut removed_bytes = 0;
+    imara_diff::diff(
+        imara_diff::Algorithm::Myers,
+        &input,
+        |before: std::ops::Range<u32>, _after: std::ops::Range<u32>| {
+            removed_bytes += input.before[before.start as usize..before.end as usize]
+                .iter()
+                .map(|token| input.interner[*token].len())
+                .sum::<usize>();
+        },
+    );
+    (a.len() - removed_bytes) as f32 / max_len as f32
+}
+
+fn split_first_component(path: &BStr) -> (&BStr, Option<&BStr>) {
+    match path.find_byte(b'/') {
+        Some(pos) => (path[..pos].as_bstr(), Some(path[pos + 1..].as_bstr())),
+        None => (path, None),
+    }
+}
+
+fn basename(path: &BStr) -> &BStr {
+    path.rfind_byte(b'/').map_or(path, |pos| path[pos + 1..].as_bstr())
+}
@@ -0,0 +1,119 @@
+use bstr::ByteSlice;
+use gix_merge::blob::{merge, ConflictStyle, Favor, Labels, Options, Resolution};
+
+const BASE: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+
+fn labels() -> Labels<'static> {
+    Labels {
+        ancestor: Some("base".into()),
+        current: Some("ours".into()),
+        other: Some("theirs".into()),
+    }
+}
+
+fn run(ancestor: &str, current: &str, other: &str, options: Options) -> (String, Resolution) {
+    let mut out = Vec::new();
+    let resolution = merge(
+        ancestor.as_bytes(),
+        current.as_bytes(),
+        other.as_bytes(),
+        labels(),
+        options,
+        &mut out,
+    );
+    (out.to_str().expect("valid UTF-8").to_owned(), resolution)
+}
+
+#[test]
+fn changes_in_different_places_merge_cleanly() {
+    let (out, resolution) = run(
+        BASE,
+        "one\n2\n3\n4\n5\n6\n7\n8\n9\n",
+        "1\n2\n3\n4\n5\n6\n7\n8\nnine\nten\n",
+        Options::default(),
+    );
+    assert_eq!(resolution, Resolution::Complete);
+    assert_eq!(out, "one\n2\n3\n4\n5\n6\n7\n8\nnine\nten\n");
+}
+
+#[test]
+fn identical_changes_are_no_conflict() {
+    let changed = "1\n2\n3\nfour\n5\n6\n7\n8\n";
+    let (out, resolution) = run(BASE, changed, changed, Options::default());
+    assert_eq!(resolution, Resolution::Complete);
+    assert_eq!(out, changed);
+
+    let (out, resolution) = run(BASE, BASE, changed, Options::default());
+    assert_eq!(resolution, Resolution::Complete);
+    assert_eq!(out, changed, "a side without changes yields the other side");
+}
+
+#[test]
+fn overlapping_changes_conflict_in_merge_and_diff3_style() {
+    let ours = "1\n2\n3\nfour\nfive\nsix\n7\n8\n9\n";
+    let theirs = "1\n2\n3\nfour\nFIVE\nsix\n7\n8\n9\n";
+    let (out, resolution) = run(BASE, ours, theirs, Options::default());
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(
+        out, "1\n2\n3\nfour\n<<<<<<< ours\nfive\n=======\nFIVE\n>>>>>>> theirs\nsix\n7\n8\n9\n",
+        "lines that are the same on both sides are moved out of the conflict"
+    );
+
+    let (out, resolution) = run(
+        BASE,
+        ours,
+        theirs,
+        Options {
+            conflict_style: ConflictStyle::Diff3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(
+        out,
+        "1\n2\n3\n<<<<<<< ours\nfour\nfive\nsix\n||||||| base\n4\n5\n6\n=======\nfour\nFIVE\nsix\n>>>>>>> theirs\n7\n8\n9\n"
+    );
+}
+
+#[test]
+fn conflict_markers_start_on_their_own_line() {
+    let (out, resolution) = run(
+        "a\nb",
+        "a\nx",
+        "a\ny",
+        Options {
+            marker_size: 3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(out, "a\n<<< ours\nx\n===\ny\n>>> theirs\n");
+}
+
+#[test]
+fn conflicts_can_be_resolved_by_favoring_a_side() {
+    let (ours, theirs) = ("a\nx", "a\ny");
+    for (favor, expected) in [
+        (Favor::Ours, "a\nx"),
+        (Favor::Theirs, "a\ny"),
+        (Favor::Union, "a\nx\ny"),
+    ] {
+        let (out, resolution) = run(
+            "a\nb",
+            ours,
+            theirs,
+            Options {
+                favor: Some(favor),
+                ..Default::default()
+            },
+        );
+        assert_eq!(resolution, Resolution::Complete);
+        assert_eq!(out, expected, "{favor:?}");
+    }
+}
+
+#[test]
+fn binary_detection() {
+    assert!(!gix_merge::blob::is_binary(BASE.as_bytes()));
+    assert!(gix_merge::blob::is_binary(b"a\0b"));
+}
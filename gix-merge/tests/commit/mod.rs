@@ -0,0 +1,62 @@
+use bstr::ByteSlice;
+use gix_hash::ObjectId;
+use gix_merge::tree::ConflictKind;
+use gix_object::FindExt;
+use gix_odb::Write;
+
+use crate::hex_to_id;
+
+fn commit_of(dir: &std::path::Path, name: &str) -> ObjectId {
+    let hex = std::fs::read_to_string(dir.join(".git/refs/tags").join(name)).expect("tag exists");
+    ObjectId::from_hex(hex.trim().as_bytes()).expect("valid hex")
+}
+
+#[test]
+fn multiple_merge_bases_are_merged_into_a_virtual_merge_base() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable("make_criss_cross_merges.sh").unwrap();
+    let odb = gix_odb::at(dir.path().join(".git/objects"))?;
+    let (ours, theirs) = (commit_of(dir.path(), "ours"), commit_of(dir.path(), "theirs"));
+    let outcome = gix_merge::merge(
+        &ours,
+        &[theirs],
+        gix_merge::blob::Labels {
+            ancestor: None,
+            current: Some("ours".into()),
+            other: Some("theirs".into()),
+        },
+        &Default::default(),
+        &odb,
+        &mut gix_revision::Graph::new(&odb, None),
+        |kind, data| odb.write_buf(kind, data),
+    )?;
+
+    assert_eq!(outcome.merge_bases.len(), 2, "both sides merged each other");
+    assert_eq!(
+        outcome.tree,
+        hex_to_id("053dd600c582e7007e230e82c4270e61e05fca0f"),
+        "the same tree as `git merge-tree --write-tree` is produced"
+    );
+    let conflicts: Vec<_> = outcome
+        .conflicts
+        .iter()
+        .map(|conflict| (conflict.path.to_str().expect("valid UTF-8"), conflict.kind.clone()))
+        .collect();
+    assert_eq!(conflicts, [("conflicting", ConflictKind::Content)]);
+
+    let ancestor_tree = outcome.ancestor_tree.expect("there are merge-bases");
+    let mut buf = Vec::new();
+    let conflicting = odb
+        .find_tree(&ancestor_tree, &mut buf)?
+        .entries
+        .iter()
+        .find(|entry| entry.filename == "conflicting")
+        .expect("present in virtual merge-base")
+        .oid
+        .to_owned();
+    assert_eq!(
+        odb.find_blob(&conflicting, &mut buf)?.data.as_bstr(),
+        "<<<<<<<<< Temporary merge branch 1\nb\n=========\na\n>>>>>>>>> Temporary merge branch 2\n",
+        "conflicts of the virtual merge-base are kept with longer markers"
+    );
+    Ok(())
+}
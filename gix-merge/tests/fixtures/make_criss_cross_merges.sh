@@ -0,0 +1,40 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+seq 1 9 > numbers
+echo base > conflicting
+git add . && git commit -q -m base
+
+git checkout -q -b a
+sed -i 's/^1$/one/' numbers
+echo a > conflicting
+git commit -q -am "a1"
+
+git checkout -q -b b main
+sed -i 's/^9$/nine/' numbers
+echo b > conflicting
+git commit -q -am "b1"
+
+git checkout -q a
+git merge -q b -m "merge b into a" >/dev/null || {
+  echo a > conflicting
+  git commit -q -am "merge b into a, keeping a"
+}
+git checkout -q b
+git merge -q a~1 -m "merge a into b" >/dev/null || {
+  echo b > conflicting
+  git commit -q -am "merge a into b, keeping b"
+}
+
+git checkout -q a
+sed -i 's/^3$/three/' numbers
+git commit -q -am "ours"
+git tag ours
+
+git checkout -q b
+sed -i 's/^7$/seven/' numbers
+git commit -q -am "theirs"
+git tag theirs
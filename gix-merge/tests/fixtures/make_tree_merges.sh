@@ -0,0 +1,70 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+mkdir dir
+seq 1 9 > numbers
+echo base > dir/modified-and-deleted
+echo base > unchanged
+echo base > file-becomes-dir
+git add . && git commit -q -m base
+git tag base
+
+git checkout -q -b clean-ours base
+sed -i 's/^1$/one/' numbers
+echo ours > added-by-ours
+git commit -q -am "change the first line" && git add . && git commit -q -m "add a file"
+git tag clean-ours
+
+git checkout -q -b clean-theirs base
+sed -i 's/^9$/nine/' numbers
+git rm -q dir/modified-and-deleted
+git commit -q -am "change the last line and delete a file"
+git tag clean-theirs
+
+git checkout -q -b ours base
+sed -i 's/^5$/five by ours/' numbers
+git rm -q dir/modified-and-deleted
+echo ours > added
+echo ours >> file-becomes-dir
+git add . && git commit -q -m "conflicting changes of ours"
+git tag ours
+
+git checkout -q -b theirs base
+sed -i 's/^5$/five by theirs/' numbers
+echo theirs >> dir/modified-and-deleted
+echo theirs > added
+git rm -q file-becomes-dir
+mkdir file-becomes-dir && echo theirs > file-becomes-dir/file
+git add . && git commit -q -m "conflicting changes of theirs"
+git tag theirs
+
+git checkout -q -b renames-base base
+mkdir old-dir
+seq 10 20 > old-dir/a
+seq 30 40 > old-dir/b
+seq 50 60 > to-be-renamed
+seq 70 80 > renamed-and-deleted
+seq 90 99 > renamed-differently
+git add . && git commit -q -m "add files to rename"
+git tag renames-base
+
+git checkout -q -b renames-ours refs/tags/renames-base
+git mv to-be-renamed renamed
+sed -i 's/^50$/fifty/' renamed
+git mv old-dir new-dir
+git mv renamed-and-deleted renamed-by-ours
+git mv renamed-differently renamed-differently-by-ours
+git commit -q -am "rename files and a directory"
+git tag renames-ours
+
+git checkout -q -b renames-theirs refs/tags/renames-base
+sed -i 's/^60$/sixty/' to-be-renamed
+sed -i 's/^10$/ten/' old-dir/a
+seq 1 5 > old-dir/c
+git rm -q renamed-and-deleted
+git mv renamed-differently renamed-differently-by-theirs
+git add . && git commit -q -m "change files that were renamed by ours"
+git tag renames-theirs
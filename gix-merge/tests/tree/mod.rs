@@ -0,0 +1,253 @@
+use bstr::ByteSlice;
+use gix_hash::ObjectId;
+use gix_merge::tree::{merge, ConflictKind, Options, Outcome};
+
+use crate::hex_to_id;
+use gix_object::FindExt;
+use gix_odb::Write;
+
+/// Resolve the tree of the commit that the tag `name` points to in the repository at `dir`.
+fn tree_of(odb: &gix_odb::Handle, dir: &std::path::Path, name: &str) -> ObjectId {
+    let hex = std::fs::read_to_string(dir.join(".git/refs/tags").join(name)).expect("tag exists");
+    let commit = ObjectId::from_hex(hex.trim().as_bytes()).expect("valid hex");
+    odb.find_commit(&commit, &mut Vec::new()).expect("commit exists").tree()
+}
+
+/// Merge the trees of the commits tagged `ours` and `theirs` with the one tagged `base` as common ancestor, keeping the
+/// repository alive as long as the returned directory.
+fn run(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+) -> crate::Result<(gix_testtools::tempfile::TempDir, gix_odb::Handle, Outcome)> {
+    let dir = gix_testtools::scripted_fixture_writable("make_tree_merges.sh").unwrap();
+    let odb = gix_odb::at(dir.path().join(".git/objects"))?;
+    let [base, ours, theirs] = [base, ours, theirs].map(|name| tree_of(&odb, dir.path(), name));
+    let outcome = merge(
+        Some(&base),
+        &ours,
+        &theirs,
+        gix_merge::blob::Labels {
+            ancestor: None,
+            current: Some("ours".into()),
+            other: Some("theirs".into()),
+        },
+        Options::default(),
+        &odb,
+        |kind, data| odb.write_buf(kind, data),
+    )?;
+    Ok((dir, odb, outcome))
+}
+
+/// Return the content of the blob at `path` in `tree`, or `None` if there is no such entry.
+fn content_at(odb: &gix_odb::Handle, tree: ObjectId, path: &str) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut id = tree;
+    for component in path.split('/') {
+        let tree = odb.find_tree(&id, &mut buf).expect("tree exists");
+        id = tree
+            .entries
+            .iter()
+            .find(|entry| entry.filename == component)?
+            .oid
+            .to_owned();
+    }
+    let blob = odb.find_blob(&id, &mut buf).expect("path points to a blob");
+    Some(blob.data.to_str().expect("valid UTF-8").to_owned())
+}
+
+#[test]
+fn changes_of_both_sides_are_combined() -> crate::Result {
+    let (_dir, odb, outcome) = run("base", "clean-ours", "clean-theirs")?;
+    assert!(!outcome.has_conflicts());
+    assert_eq!(
+        outcome.tree,
+        hex_to_id("16f01babae01ed2ac2d8547be08be944349ed41f"),
+        "the same tree as `git merge-tree --write-tree` is produced"
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "numbers").as_deref(),
+        Some("one\n2\n3\n4\n5\n6\n7\n8\nnine\n")
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "added-by-ours").as_deref(),
+        Some("ours\n")
+    );
+    assert_eq!(content_at(&odb, outcome.tree, "dir/modified-and-deleted"), None);
+    assert_eq!(content_at(&odb, outcome.tree, "unchanged").as_deref(), Some("base\n"));
+    Ok(())
+}
+
+#[test]
+fn conflicts_are_reported_and_written_into_the_tree() -> crate::Result {
+    let (_dir, odb, outcome) = run("base", "ours", "theirs")?;
+    assert_eq!(
+        outcome.tree,
+        hex_to_id("3cb4df36809039ae7f6fba8cb44eff00deddd6b7"),
+        "the same tree as `git merge-tree --write-tree` is produced"
+    );
+    let conflicts: Vec<_> = outcome
+        .conflicts
+        .iter()
+        .map(|conflict| (conflict.path.to_str().expect("valid UTF-8"), conflict.kind.clone()))
+        .collect();
+    assert_eq!(
+        conflicts,
+        [
+            ("added", ConflictKind::AddAdd),
+            ("dir/modified-and-deleted", ConflictKind::ModifyDelete),
+            (
+                "file-becomes-dir",
+                ConflictKind::FileDirectory {
+                    renamed_to: "file-becomes-dir~ours".into()
+                }
+            ),
+            ("numbers", ConflictKind::Content),
+        ]
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "added").as_deref(),
+        Some("<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\n")
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "numbers").as_deref(),
+        Some("1\n2\n3\n4\n<<<<<<< ours\nfive by ours\n=======\nfive by theirs\n>>>>>>> theirs\n6\n7\n8\n9\n")
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "dir/modified-and-deleted").as_deref(),
+        Some("base\ntheirs\n"),
+        "the modified version is kept"
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "file-becomes-dir/file").as_deref(),
+        Some("theirs\n")
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "file-becomes-dir~ours").as_deref(),
+        Some("base\nours\n"),
+        "the file is moved out of the way of the directory"
+    );
+
+    let message = outcome.conflicts[1].message(&gix_merge::blob::Labels {
+        current: Some("main".into()),
+        other: Some("feature".into()),
+        ..Default::default()
+    });
+    assert_eq!(
+        message,
+        "CONFLICT (modify/delete): dir/modified-and-deleted deleted in main and modified in feature.  Version feature of dir/modified-and-deleted left in tree."
+    );
+    Ok(())
+}
+
+#[test]
+fn renamed_files_and_directories_receive_changes_of_the_other_side() -> crate::Result {
+    let (_dir, odb, outcome) = run("renames-base", "renames-ours", "renames-theirs")?;
+    assert_eq!(
+        outcome.tree,
+        hex_to_id("6f71df6bfd7fa17f75e60f21b737fac9992bb9bf"),
+        "the same tree as `git merge-tree --write-tree` is produced"
+    );
+    let conflicts: Vec<_> = outcome
+        .conflicts
+        .iter()
+        .map(|conflict| (conflict.path.to_str().expect("valid UTF-8"), conflict.kind.clone()))
+        .collect();
+    assert_eq!(
+        conflicts,
+        [
+            (
+                "new-dir/c",
+                ConflictKind::DirectoryRename {
+                    original: "old-dir/c".into()
+                }
+            ),
+            (
+                "renamed-by-ours",
+                ConflictKind::RenameDelete {
+                    source: "renamed-and-deleted".into()
+                }
+            ),
+            (
+                "renamed-differently",
+                ConflictKind::RenameRename {
+                    ours: "renamed-differently-by-ours".into(),
+                    theirs: "renamed-differently-by-theirs".into()
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "renamed").as_deref(),
+        Some("fifty\n51\n52\n53\n54\n55\n56\n57\n58\n59\nsixty\n"),
+        "changes to the file at its old location are merged into the renamed file"
+    );
+    assert_eq!(content_at(&odb, outcome.tree, "to-be-renamed"), None);
+    assert_eq!(
+        content_at(&odb, outcome.tree, "new-dir/a")
+            .as_deref()
+            .and_then(|data| data.lines().next()),
+        Some("ten"),
+        "the file was moved along with its directory"
+    );
+    assert_eq!(content_at(&odb, outcome.tree, "old-dir/c"), None);
+    assert_eq!(
+        outcome.conflicts[2]
+            .index_entries()
+            .map(|(path, stage, _)| (path.to_str().expect("valid UTF-8"), stage))
+            .collect::<Vec<_>>(),
+        [
+            ("renamed-differently", 1),
+            ("renamed-differently-by-ours", 2),
+            ("renamed-differently-by-theirs", 3)
+        ],
+        "each side is staged at its own location, just like `git` does"
+    );
+
+    let labels = gix_merge::blob::Labels {
+        current: Some("main".into()),
+        other: Some("feature".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        outcome.conflicts[0].message(&labels),
+        "CONFLICT (file location): old-dir/c added in feature inside a directory that was renamed in main, suggesting it should perhaps be moved to new-dir/c."
+    );
+    assert_eq!(
+        outcome.conflicts[1].message(&labels),
+        "CONFLICT (rename/delete): renamed-and-deleted renamed to renamed-by-ours in main, but deleted in feature."
+    );
+    Ok(())
+}
+
+#[test]
+fn renames_can_be_ignored() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable("make_tree_merges.sh").unwrap();
+    let odb = gix_odb::at(dir.path().join(".git/objects"))?;
+    let [base, ours, theirs] =
+        ["renames-base", "renames-ours", "renames-theirs"].map(|name| tree_of(&odb, dir.path(), name));
+    let outcome = merge(
+        Some(&base),
+        &ours,
+        &theirs,
+        Default::default(),
+        Options {
+            rewrites: None,
+            ..Default::default()
+        },
+        &odb,
+        |kind, data| odb.write_buf(kind, data),
+    )?;
+    assert!(
+        outcome
+            .conflicts
+            .iter()
+            .any(|conflict| conflict.path == "to-be-renamed" && conflict.kind == ConflictKind::ModifyDelete),
+        "without rename tracking, the renamed file appears deleted"
+    );
+    assert_eq!(
+        content_at(&odb, outcome.tree, "old-dir/c").as_deref(),
+        Some("1\n2\n3\n4\n5\n")
+    );
+    Ok(())
+}
@@ -52,6 +52,8 @@ impl From<SignedData<'_>> for BString {
     }
 }
 
+///
+pub mod raw;
 ///
 pub mod ref_iter;
 
@@ -67,6 +69,19 @@ impl<'a> CommitRef<'a> {
             Err(err) => Err(crate::decode::Error::with_err(err, input)),
         }
     }
+
+    /// Deserialize a commit from the given `data` bytes, and in [strict mode](crate::decode::Mode::Strict)
+    /// additionally reject commits that `git fsck` would consider malformed.
+    pub fn from_bytes_with_mode(
+        data: &'a [u8],
+        mode: crate::decode::Mode,
+    ) -> Result<CommitRef<'a>, crate::decode::mode::Error> {
+        let commit = Self::from_bytes(data)?;
+        if mode == crate::decode::Mode::Strict {
+            crate::decode::mode::commit(data)?;
+        }
+        Ok(commit)
+    }
 }
 
 /// Access
@@ -0,0 +1,155 @@
+use std::io;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{raw, Kind, RawCommit};
+
+/// The names of headers that contain signatures, which are excluded from the data that is signed.
+pub const SIGNATURE_HEADERS: [&str; 2] = ["gpgsig", "gpgsig-sha256"];
+
+/// The error returned by [`RawCommit::from_bytes()`] and [`RawTag::from_bytes()`](crate::RawTag::from_bytes()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The headers must be separated from the message by an empty line")]
+    MissingMessageSeparator,
+    #[error("Header line {line:?} doesn't have a name followed by a space, or continues a non-existing header")]
+    MalformedHeader { line: BString },
+}
+
+/// Lifecycle
+impl RawCommit {
+    /// Split the commit in `data` into its headers and its message without interpreting any of them.
+    ///
+    /// Serializing the result reproduces `data` exactly, even if it contains header lines without value.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let (headers, message) = raw::parse(data)?;
+        Ok(RawCommit { headers, message })
+    }
+}
+
+/// Access
+impl RawCommit {
+    /// Return all headers in order as `(name, value)` pairs, with multi-line values separated by newlines.
+    ///
+    /// Headers without value, like a lone `encoding` line, have an empty value.
+    pub fn headers(&self) -> impl Iterator<Item = (&BStr, &BStr)> {
+        raw::iter(&self.headers)
+    }
+
+    /// Return the value of the first header with `name`, if present.
+    pub fn header(&self, name: &str) -> Option<&BStr> {
+        self.headers().find_map(|(n, value)| (n == name).then_some(value))
+    }
+
+    /// Return the message, which is everything after the empty line following the headers.
+    pub fn message(&self) -> &BStr {
+        self.message.as_bstr()
+    }
+
+    /// Return the signature of this commit, if it is signed.
+    pub fn signature(&self) -> Option<&BStr> {
+        SIGNATURE_HEADERS.iter().find_map(|name| self.header(name))
+    }
+
+    /// Return the data that was signed, or that has to be signed, which is this commit without its signature headers.
+    pub fn signed_data(&self) -> BString {
+        let mut buf = Vec::new();
+        raw::write(&self.headers, &self.message, &mut buf, |name| {
+            !SIGNATURE_HEADERS.iter().any(|sig| name == *sig)
+        })
+        .expect("writing to a vec never fails");
+        buf.into()
+    }
+}
+
+/// Editing
+impl RawCommit {
+    /// Set the value of the first header with `name` to `value`, or append it as last header if it doesn't exist yet.
+    ///
+    /// Newlines in `value` turn it into a multi-line header.
+    pub fn set_header(&mut self, name: &str, value: impl Into<BString>) -> &mut Self {
+        raw::set(&mut self.headers, name, value.into());
+        self
+    }
+
+    /// Remove all headers with `name` and return the amount of removed headers.
+    pub fn remove_header(&mut self, name: &str) -> usize {
+        raw::remove(&mut self.headers, name)
+    }
+
+    /// Set the tree this commit points to.
+    pub fn set_tree(&mut self, id: &gix_hash::oid) -> &mut Self {
+        self.set_header("tree", id.to_hex().to_string())
+    }
+
+    /// Replace all parents with `parents`, placing them right after the tree like `git` does.
+    pub fn set_parents(&mut self, parents: impl IntoIterator<Item = gix_hash::ObjectId>) -> &mut Self {
+        let first_parent_pos = self.headers.iter().position(|(name, _)| name == "parent");
+        self.remove_header("parent");
+        let insert_at = first_parent_pos
+            .or_else(|| {
+                self.headers
+                    .iter()
+                    .position(|(name, _)| name == "tree")
+                    .map(|pos| pos + 1)
+            })
+            .unwrap_or(0);
+        self.headers.splice(
+            insert_at..insert_at,
+            parents
+                .into_iter()
+                .map(|id| (BString::from("parent"), Some(BString::from(id.to_hex().to_string())))),
+        );
+        self
+    }
+
+    /// Set the author of this commit.
+    pub fn set_author(&mut self, author: gix_actor::SignatureRef<'_>) -> &mut Self {
+        self.set_header("author", raw::signature_to_bstring(author))
+    }
+
+    /// Set the committer of this commit.
+    pub fn set_committer(&mut self, committer: gix_actor::SignatureRef<'_>) -> &mut Self {
+        self.set_header("committer", raw::signature_to_bstring(committer))
+    }
+
+    /// Set the message of this commit.
+    pub fn set_message(&mut self, message: impl Into<BString>) -> &mut Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Set `signature` as value of the `gpgsig` header, as produced by a program like `gpg` when signing
+    /// the [signed data](Self::signed_data()).
+    ///
+    /// A single trailing newline is removed as it's implied by the header, and any existing signature is replaced.
+    pub fn set_signature(&mut self, signature: &BStr) -> &mut Self {
+        let signature = signature.strip_suffix(b"\n").unwrap_or(signature);
+        for name in &SIGNATURE_HEADERS[1..] {
+            self.remove_header(name);
+        }
+        self.set_header(SIGNATURE_HEADERS[0], signature)
+    }
+
+    /// Remove all signatures from this commit, returning `true` if there was at least one.
+    pub fn remove_signature(&mut self) -> bool {
+        let num_removed: usize = SIGNATURE_HEADERS.iter().map(|name| self.remove_header(name)).sum();
+        num_removed > 0
+    }
+}
+
+impl crate::WriteTo for RawCommit {
+    /// Serializes this instance to `out` in the git serialization format.
+    fn write_to(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        raw::write(&self.headers, &self.message, out, |_| true)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Commit
+    }
+
+    fn size(&self) -> u64 {
+        raw::size(&self.headers, &self.message)
+    }
+}
@@ -0,0 +1,141 @@
+use bstr::{BStr, BString, ByteSlice};
+
+/// The error returned by decoding functions that take a [`Mode`](super::Mode), like
+/// [`CommitRef::from_bytes_with_mode()`](crate::CommitRef::from_bytes_with_mode()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Decode(#[from] super::Error),
+    #[error(transparent)]
+    Tree(#[from] crate::tree::validate::Error),
+    #[error("The object headers contain a null byte")]
+    NullInHeader,
+    #[error("The object headers are not terminated by a newline")]
+    UnterminatedHeader,
+    #[error("The '{field}' header must only be present once")]
+    DuplicateHeader { field: &'static str },
+    #[error("The identity in the '{field}' header is malformed: {value:?}")]
+    BadIdentity { field: &'static str, value: BString },
+    #[error("The date in the '{field}' header must not be zero-padded: {value:?}")]
+    ZeroPaddedDate { field: &'static str, value: BString },
+    #[error("The date in the '{field}' header must be a non-negative number of seconds: {value:?}")]
+    BadDate { field: &'static str, value: BString },
+    #[error("The timezone in the '{field}' header must be a valid offset formatted as '+HHMM' or '-HHMM': {value:?}")]
+    BadTimezone { field: &'static str, value: BString },
+    #[error("The tag name is invalid")]
+    BadTagName(#[from] gix_validate::tag::name::Error),
+}
+
+/// The headers of commits and tags which must not be present more than once, along with the ones that contain identities.
+struct Headers {
+    unique: &'static [&'static str],
+    identities: &'static [&'static str],
+}
+
+const COMMIT: Headers = Headers {
+    unique: &["tree", "author", "committer", "encoding"],
+    identities: &["author", "committer"],
+};
+
+const TAG: Headers = Headers {
+    unique: &["object", "type", "tag", "tagger"],
+    identities: &["tagger"],
+};
+
+/// Check the raw `data` of a commit that was already decoded leniently.
+pub(crate) fn commit(data: &[u8]) -> Result<(), Error> {
+    headers(data, &COMMIT)
+}
+
+/// Check the raw `data` of a tag that was already decoded leniently into `tag`.
+pub(crate) fn tag(data: &[u8], tag: &crate::TagRef<'_>) -> Result<(), Error> {
+    headers(data, &TAG)?;
+    gix_validate::tag::name(tag.name)?;
+    Ok(())
+}
+
+fn headers(data: &[u8], expected: &Headers) -> Result<(), Error> {
+    let header = match data.find(b"\n\n") {
+        Some(pos) => &data[..=pos],
+        None => data,
+    };
+    if header.contains(&0) {
+        return Err(Error::NullInHeader);
+    }
+    if header.last() != Some(&b'\n') {
+        return Err(Error::UnterminatedHeader);
+    }
+
+    let mut seen = Vec::with_capacity(expected.unique.len());
+    for line in header[..header.len() - 1].split(|b| *b == b'\n') {
+        if line.starts_with(b" ") {
+            // continuation of a multi-line header
+            continue;
+        }
+        let Some((name, value)) = line.split_once_str(b" ") else {
+            continue;
+        };
+        let Some(field) = expected.unique.iter().copied().find(|field| field.as_bytes() == name) else {
+            continue;
+        };
+        if seen.contains(&field) {
+            return Err(Error::DuplicateHeader { field });
+        }
+        seen.push(field);
+        if expected.identities.contains(&field) {
+            identity(field, value.as_bstr())?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate `value` of `field` as `<name> <<email>> <seconds> <+|-><HHMM>`, similar to what `git fsck` checks.
+fn identity(field: &'static str, value: &BStr) -> Result<(), Error> {
+    let bad_identity = || Error::BadIdentity {
+        field,
+        value: value.to_owned(),
+    };
+    let email_start = value.find_byte(b'<').ok_or_else(bad_identity)?;
+    let name = &value[..email_start];
+    if name.last() != Some(&b' ') || name.contains(&b'>') {
+        return Err(bad_identity());
+    }
+    let email_and_time = &value[email_start + 1..];
+    let email_end = email_and_time.find_byte(b'>').ok_or_else(bad_identity)?;
+    if email_and_time[..email_end].contains(&b'<') {
+        return Err(bad_identity());
+    }
+    let time = email_and_time[email_end + 1..]
+        .strip_prefix(b" ")
+        .ok_or_else(bad_identity)?;
+
+    let (seconds, timezone) = time.split_once_str(b" ").ok_or_else(bad_identity)?;
+    if btoi::btou::<gix_date::SecondsSinceUnixEpoch>(seconds).is_err() {
+        return Err(Error::BadDate {
+            field,
+            value: seconds.into(),
+        });
+    }
+    if seconds.len() > 1 && seconds[0] == b'0' {
+        return Err(Error::ZeroPaddedDate {
+            field,
+            value: seconds.into(),
+        });
+    }
+
+    let is_valid_timezone = match timezone {
+        [b'+' | b'-', h1, h2, m1, m2] if [h1, h2, m1, m2].iter().all(|b| b.is_ascii_digit()) => {
+            let minutes = (m1 - b'0') * 10 + (m2 - b'0');
+            minutes < 60
+        }
+        _ => false,
+    };
+    if !is_valid_timezone {
+        return Err(Error::BadTimezone {
+            field,
+            value: timezone.into(),
+        });
+    }
+    Ok(())
+}
@@ -27,6 +27,7 @@ pub mod tag;
 pub mod tree;
 
 mod blob;
+mod raw;
 ///
 pub mod data;
 
@@ -133,6 +134,36 @@ pub struct Commit {
     pub extra_headers: Vec<(BString, BString)>,
 }
 
+/// A commit as sequence of header fields followed by a message, to edit individual fields while preserving all other
+/// bytes exactly, including the order of headers, unknown headers and signatures.
+///
+/// Unlike [`Commit`], serializing an unchanged instance reproduces its input byte-for-byte, which makes it suitable
+/// for tools that rewrite or re-sign commits.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawCommit {
+    /// All header fields in order, as `(name, value)` pairs, with the values of multi-line headers being unfolded
+    /// into values separated by newlines, and `None` for header lines without value.
+    headers: Vec<(BString, Option<BString>)>,
+    /// The message, which is everything after the empty line that terminates the headers.
+    message: BString,
+}
+
+/// A tag as sequence of header fields followed by a message and an optional signature, to edit individual fields while
+/// preserving all other bytes exactly, just like [`RawCommit`].
+///
+/// Unlike [`Tag`], serializing an unchanged instance reproduces its input byte-for-byte, which makes it suitable
+/// for tools that rewrite or re-sign tags.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawTag {
+    /// All header fields in order, as `(name, value)` pairs, with the values of multi-line headers being unfolded
+    /// into values separated by newlines, and `None` for header lines without value.
+    headers: Vec<(BString, Option<BString>)>,
+    /// The message, which is everything after the empty line that terminates the headers, including the signature.
+    message: BString,
+}
+
 /// Represents a git tag, commonly indicating a software release.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -352,6 +383,23 @@ pub mod decode {
     pub(crate) use _decode::empty_error;
     pub use _decode::{Error, ParseError};
 
+    ///
+    pub mod mode;
+
+    /// Determine how strictly objects are decoded.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Mode {
+        /// Accept everything that can be made sense of, like unusual timezones or dates, to be able to read and
+        /// round-trip all objects that exist in the wild, which is what's needed when analysing history.
+        #[default]
+        Lenient,
+        /// Additionally reject objects that `git fsck` would consider malformed, like identities with bad timezones or
+        /// zero-padded dates, duplicate headers or invalid tag names. Trees are [validated](crate::TreeRef::validate()) as well.
+        ///
+        /// This is useful when receiving objects from untrusted sources.
+        Strict,
+    }
+
     /// Returned by [`loose_header()`]
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
@@ -213,6 +213,27 @@ impl<'a> ObjectRef<'a> {
         })
     }
 
+    /// Deserialize an object of `kind` from `data`, and in [strict mode](crate::decode::Mode::Strict)
+    /// additionally reject objects that `git fsck` would consider malformed.
+    pub fn from_bytes_with_mode(
+        kind: Kind,
+        data: &'a [u8],
+        mode: crate::decode::Mode,
+    ) -> Result<ObjectRef<'a>, crate::decode::mode::Error> {
+        Ok(match kind {
+            Kind::Tree => {
+                let tree = TreeRef::from_bytes(data)?;
+                if mode == crate::decode::Mode::Strict {
+                    tree.validate()?;
+                }
+                ObjectRef::Tree(tree)
+            }
+            Kind::Blob => ObjectRef::Blob(BlobRef { data }),
+            Kind::Commit => ObjectRef::Commit(CommitRef::from_bytes_with_mode(data, mode)?),
+            Kind::Tag => ObjectRef::Tag(TagRef::from_bytes_with_mode(data, mode)?),
+        })
+    }
+
     /// Convert the immutable object into a mutable version, consuming the source in the process.
     ///
     /// Note that this is an expensive operation.
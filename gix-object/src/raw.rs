@@ -0,0 +1,109 @@
+//! The header model shared by [`RawCommit`](crate::RawCommit) and [`RawTag`](crate::RawTag), which keeps all header fields
+//! and the message as they are to be able to reproduce the original bytes exactly.
+use std::io;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{
+    commit::raw::Error,
+    encode::{self, NL, SPACE},
+};
+
+/// A header field as `(name, value)`, with the value being `None` if the header line consists of only its name.
+pub(crate) type Header = (BString, Option<BString>);
+
+/// Split `data` into its header fields and its message without interpreting any of them.
+pub(crate) fn parse(data: &[u8]) -> Result<(Vec<Header>, BString), Error> {
+    let separator = data.find(b"\n\n").ok_or(Error::MissingMessageSeparator)?;
+    let mut headers = Vec::<Header>::new();
+    for line in data[..separator].split(|b| *b == b'\n') {
+        if let Some(continuation) = line.strip_prefix(b" ") {
+            let value = headers
+                .last_mut()
+                .ok_or_else(|| Error::MalformedHeader { line: line.into() })?
+                .1
+                .get_or_insert_with(Default::default);
+            value.push(b'\n');
+            value.extend_from_slice(continuation);
+            continue;
+        }
+        let (name, value) = match line.split_once_str(b" ") {
+            Some((name, value)) => (name, Some(value.into())),
+            None => (line, None),
+        };
+        if name.is_empty() {
+            return Err(Error::MalformedHeader { line: line.into() });
+        }
+        headers.push((name.into(), value));
+    }
+    Ok((headers, data[separator + 2..].into()))
+}
+
+/// Return all `headers` as `(name, value)` pairs, with headers without value having an empty value.
+pub(crate) fn iter(headers: &[Header]) -> impl Iterator<Item = (&BStr, &BStr)> {
+    headers.iter().map(|(name, value)| {
+        (
+            name.as_bstr(),
+            value.as_ref().map_or_else(|| b"".as_bstr(), |value| value.as_bstr()),
+        )
+    })
+}
+
+/// Set the value of the first header with `name` to `value`, or append it as last header if it doesn't exist yet.
+pub(crate) fn set(headers: &mut Vec<Header>, name: &str, value: BString) {
+    match headers.iter_mut().find(|(n, _)| n == name) {
+        Some((_, existing)) => *existing = Some(value),
+        None => headers.push((name.into(), Some(value))),
+    }
+}
+
+/// Remove all headers with `name` and return the amount of removed headers.
+pub(crate) fn remove(headers: &mut Vec<Header>, name: &str) -> usize {
+    let num_headers = headers.len();
+    headers.retain(|(n, _)| n != name);
+    num_headers - headers.len()
+}
+
+/// Write all `headers` for which `keep(name)` returns `true` to `out`, followed by an empty line and `message`.
+pub(crate) fn write(
+    headers: &[Header],
+    message: &[u8],
+    out: &mut dyn io::Write,
+    mut keep: impl FnMut(&BStr) -> bool,
+) -> io::Result<()> {
+    for (name, value) in headers.iter().filter(|(name, _)| keep(name.as_bstr())) {
+        match value {
+            Some(value) => encode::header_field_multi_line(name, value, out)?,
+            None => {
+                out.write_all(name)?;
+                out.write_all(NL)?;
+            }
+        }
+    }
+    out.write_all(NL)?;
+    out.write_all(message)
+}
+
+/// Return the amount of bytes [`write()`] produces when keeping all headers.
+pub(crate) fn size(headers: &[Header], message: &[u8]) -> u64 {
+    (headers
+        .iter()
+        .map(|(name, value)| {
+            name.len()
+                + value.as_ref().map_or(0, |value| {
+                    // each continuation line is preceded by a space
+                    SPACE.len() + value.len() + value.find_iter(NL).count()
+                })
+                + 1 /* nl */
+        })
+        .sum::<usize>()
+        + 1 /* nl */
+        + message.len()) as u64
+}
+
+/// Serialize `signature` into a value suitable for a header.
+pub(crate) fn signature_to_bstring(signature: gix_actor::SignatureRef<'_>) -> BString {
+    let mut buf = Vec::new();
+    signature.write_to(&mut buf).expect("writing to a vec never fails");
+    buf.into()
+}
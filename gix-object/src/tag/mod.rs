@@ -7,6 +7,8 @@ mod decode;
 ///
 pub mod write;
 
+///
+pub mod raw;
 ///
 pub mod ref_iter;
 
@@ -19,6 +21,20 @@ impl<'a> TagRef<'a> {
             Err(err) => Err(crate::decode::Error::with_err(err, input)),
         }
     }
+
+    /// Deserialize a tag from `data`, and in [strict mode](crate::decode::Mode::Strict)
+    /// additionally reject tags that `git fsck` would consider malformed.
+    pub fn from_bytes_with_mode(
+        data: &'a [u8],
+        mode: crate::decode::Mode,
+    ) -> Result<TagRef<'a>, crate::decode::mode::Error> {
+        let tag = Self::from_bytes(data)?;
+        if mode == crate::decode::Mode::Strict {
+            crate::decode::mode::tag(data, &tag)?;
+        }
+        Ok(tag)
+    }
+
     /// The object this tag points to as `Id`.
     pub fn target(&self) -> gix_hash::ObjectId {
         gix_hash::ObjectId::from_hex(self.target).expect("prior validation")
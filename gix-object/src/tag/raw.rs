@@ -0,0 +1,146 @@
+use std::io;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{raw, Kind, RawTag};
+
+pub use crate::commit::raw::Error;
+
+/// The markers at the beginning of a line that start the signature at the end of a tag message, as recognized by `git`.
+pub const SIGNATURE_MARKERS: [&str; 4] = [
+    "-----BEGIN PGP SIGNATURE-----",
+    "-----BEGIN PGP MESSAGE-----",
+    "-----BEGIN SIGNED MESSAGE-----",
+    "-----BEGIN SSH SIGNATURE-----",
+];
+
+/// Lifecycle
+impl RawTag {
+    /// Split the tag in `data` into its headers and its message without interpreting any of them.
+    ///
+    /// Serializing the result reproduces `data` exactly, even if it contains header lines without value.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let (headers, message) = raw::parse(data)?;
+        Ok(RawTag { headers, message })
+    }
+}
+
+/// Access
+impl RawTag {
+    /// Return all headers in order as `(name, value)` pairs, with multi-line values separated by newlines.
+    ///
+    /// Headers without value have an empty value.
+    pub fn headers(&self) -> impl Iterator<Item = (&BStr, &BStr)> {
+        raw::iter(&self.headers)
+    }
+
+    /// Return the value of the first header with `name`, if present.
+    pub fn header(&self, name: &str) -> Option<&BStr> {
+        self.headers().find_map(|(n, value)| (n == name).then_some(value))
+    }
+
+    /// Return the message without the signature, which is everything after the empty line following the headers.
+    pub fn message(&self) -> &BStr {
+        self.message[..self.signature_start()].as_bstr()
+    }
+
+    /// Return the signature of this tag, if it is signed, which is the last part of its message starting with a line
+    /// like `-----BEGIN PGP SIGNATURE-----`.
+    pub fn signature(&self) -> Option<&BStr> {
+        let start = self.signature_start();
+        (start < self.message.len()).then(|| self.message[start..].as_bstr())
+    }
+
+    /// Return the data that was signed, or that has to be signed, which is this tag without its signature.
+    pub fn signed_data(&self) -> BString {
+        let mut buf = Vec::new();
+        raw::write(&self.headers, self.message(), &mut buf, |_| true).expect("writing to a vec never fails");
+        buf.into()
+    }
+}
+
+/// Editing
+impl RawTag {
+    /// Set the value of the first header with `name` to `value`, or append it as last header if it doesn't exist yet.
+    ///
+    /// Newlines in `value` turn it into a multi-line header.
+    pub fn set_header(&mut self, name: &str, value: impl Into<BString>) -> &mut Self {
+        raw::set(&mut self.headers, name, value.into());
+        self
+    }
+
+    /// Remove all headers with `name` and return the amount of removed headers.
+    pub fn remove_header(&mut self, name: &str) -> usize {
+        raw::remove(&mut self.headers, name)
+    }
+
+    /// Set the object this tag points to, along with its `kind`.
+    pub fn set_target(&mut self, id: &gix_hash::oid, kind: Kind) -> &mut Self {
+        self.set_header("object", id.to_hex().to_string())
+            .set_header("type", kind.as_bytes())
+    }
+
+    /// Set the name of this tag, like `v1.0`.
+    pub fn set_name(&mut self, name: impl Into<BString>) -> &mut Self {
+        self.set_header("tag", name)
+    }
+
+    /// Set the tagger of this tag.
+    pub fn set_tagger(&mut self, tagger: gix_actor::SignatureRef<'_>) -> &mut Self {
+        self.set_header("tagger", raw::signature_to_bstring(tagger))
+    }
+
+    /// Set the message of this tag, which also removes its signature as it doesn't match the new message anymore.
+    pub fn set_message(&mut self, message: impl Into<BString>) -> &mut Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Set `signature` as produced by a program like `gpg` when signing the [signed data](Self::signed_data()),
+    /// replacing any existing signature.
+    ///
+    /// It's appended to the message as is, just like `git` does.
+    pub fn set_signature(&mut self, signature: &BStr) -> &mut Self {
+        self.remove_signature();
+        self.message.extend_from_slice(signature);
+        self
+    }
+
+    /// Remove the signature from this tag, returning `true` if there was one.
+    pub fn remove_signature(&mut self) -> bool {
+        let start = self.signature_start();
+        let had_signature = start < self.message.len();
+        self.message.truncate(start);
+        had_signature
+    }
+}
+
+impl RawTag {
+    /// Return the position at which the signature starts in our message, or the length of the message if there is none.
+    fn signature_start(&self) -> usize {
+        let mut line_start = 0;
+        let mut start = self.message.len();
+        for line in self.message.lines_with_terminator() {
+            if SIGNATURE_MARKERS.iter().any(|marker| line.starts_with(marker.as_bytes())) {
+                start = line_start;
+            }
+            line_start += line.len();
+        }
+        start
+    }
+}
+
+impl crate::WriteTo for RawTag {
+    /// Serializes this instance to `out` in the git serialization format.
+    fn write_to(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        raw::write(&self.headers, &self.message, out, |_| true)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Tag
+    }
+
+    fn size(&self) -> u64 {
+        raw::size(&self.headers, &self.message)
+    }
+}
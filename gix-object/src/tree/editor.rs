@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_hash::ObjectId;
+
+use crate::{
+    tree::{Entry, EntryKind},
+    FindExt, Tree,
+};
+
+/// The error returned by [`Editor::upsert()`] and [`Editor::remove()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Path {path:?} must not be empty or contain empty components")]
+    EmptyPathComponent { path: BString },
+    #[error(transparent)]
+    FindExistingObject(#[from] crate::find::existing_object::Error),
+}
+
+/// A way to change the entries of a tree and its subtrees by path, which only reads and writes the trees that are edited.
+///
+/// Trees that become empty are removed from their parent tree when [writing](Self::write()).
+pub struct Editor<'a> {
+    find: &'a dyn crate::Find,
+    object_hash: gix_hash::Kind,
+    /// The trees that were edited or are the parent of an edited tree, by their path relative to the root tree,
+    /// with the root tree at the empty path.
+    trees: HashMap<BString, Tree>,
+    buf: Vec<u8>,
+}
+
+impl<'a> Editor<'a> {
+    /// Create a new instance to edit the `root` tree, using `find` to read the subtrees that are edited, which
+    /// all use `object_hash`.
+    pub fn new(root: Tree, find: &'a dyn crate::Find, object_hash: gix_hash::Kind) -> Self {
+        Editor {
+            find,
+            object_hash,
+            trees: std::iter::once((BString::default(), root)).collect(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Insert an entry of `kind` pointing to `id` at `rela_path`, like `dir/file`, or replace what's there.
+    ///
+    /// Leading trees are created as needed, replacing non-tree entries that are in the way.
+    /// Previous edits of a tree at `rela_path` are discarded.
+    pub fn upsert(&mut self, rela_path: &BStr, kind: EntryKind, id: ObjectId) -> Result<&mut Self, Error> {
+        let (dir, name) = split(rela_path)?;
+        self.load_tree(dir, true)?;
+        let tree = self.trees.get_mut(dir).expect("loaded");
+        match tree.entries.iter_mut().find(|entry| entry.filename == name) {
+            Some(entry) => {
+                entry.mode = kind.into();
+                entry.oid = id;
+            }
+            None => tree.entries.push(Entry {
+                mode: kind.into(),
+                filename: name.to_owned(),
+                oid: id,
+            }),
+        }
+        self.forget_tree(rela_path);
+        Ok(self)
+    }
+
+    /// Remove the entry at `rela_path`, like `dir/file`, if it exists.
+    pub fn remove(&mut self, rela_path: &BStr) -> Result<&mut Self, Error> {
+        let (dir, name) = split(rela_path)?;
+        if self.load_tree(dir, false)? {
+            let tree = self.trees.get_mut(dir).expect("loaded");
+            tree.entries.retain(|entry| entry.filename != name);
+            self.forget_tree(rela_path);
+        }
+        Ok(self)
+    }
+
+    /// Write all edited trees with `write`, children before their parents, and return the id of the root tree.
+    ///
+    /// `write` is called with trees whose entries are sorted, and returns the id of the tree after writing it.
+    pub fn write<E>(mut self, mut write: impl FnMut(&Tree) -> Result<ObjectId, E>) -> Result<ObjectId, E> {
+        let mut dirs: Vec<_> = self.trees.keys().filter(|dir| !dir.is_empty()).cloned().collect();
+        dirs.sort_by_key(|dir| std::cmp::Reverse(dir.find_iter(b"/").count()));
+        for dir in dirs {
+            let mut tree = self.trees.remove(&dir).expect("present");
+            let (parent_dir, name) = split(dir.as_ref()).expect("validated on insertion");
+            let id = if tree.entries.is_empty() {
+                None
+            } else {
+                tree.entries.sort();
+                Some(write(&tree)?)
+            };
+            let parent = self.trees.get_mut(parent_dir).expect("parents are always loaded");
+            match id {
+                Some(id) => {
+                    if let Some(entry) = parent.entries.iter_mut().find(|entry| entry.filename == name) {
+                        entry.oid = id;
+                    }
+                }
+                None => parent.entries.retain(|entry| entry.filename != name),
+            }
+        }
+        let mut root = self.trees.remove(&BString::default()).expect("root is always present");
+        root.entries.sort();
+        write(&root)
+    }
+
+    /// Make sure the tree at `dir` and all of its parents are loaded, and return `true` if it exists.
+    /// If `create` is `true`, missing trees are created and non-tree entries that are in the way are replaced.
+    fn load_tree(&mut self, dir: &BStr, create: bool) -> Result<bool, Error> {
+        if self.trees.contains_key(dir) {
+            return Ok(true);
+        }
+        let (parent_dir, name) = split(dir)?;
+        if !self.load_tree(parent_dir, create)? {
+            return Ok(false);
+        }
+        let parent = self.trees.get_mut(parent_dir).expect("just loaded");
+        let tree = match parent.entries.iter_mut().find(|entry| entry.filename == name) {
+            Some(entry) if entry.mode.is_tree() => self.find.find_tree(&entry.oid, &mut self.buf)?.into(),
+            Some(entry) if create => {
+                entry.mode = EntryKind::Tree.into();
+                Tree::empty()
+            }
+            None if create => {
+                parent.entries.push(Entry {
+                    mode: EntryKind::Tree.into(),
+                    filename: name.to_owned(),
+                    oid: ObjectId::empty_tree(self.object_hash),
+                });
+                Tree::empty()
+            }
+            _ => return Ok(false),
+        };
+        self.trees.insert(dir.to_owned(), tree);
+        Ok(true)
+    }
+
+    /// Discard the tree at `rela_path` and all of its subtrees as the entry at `rela_path` was replaced or removed.
+    fn forget_tree(&mut self, rela_path: &BStr) {
+        let mut prefix = rela_path.to_owned();
+        prefix.push_byte(b'/');
+        self.trees
+            .retain(|dir, _| dir.as_bstr() != rela_path && !dir.starts_with(prefix.as_slice()));
+    }
+}
+
+/// Split `rela_path` into its leading directory and its last component.
+fn split(rela_path: &BStr) -> Result<(&BStr, &BStr), Error> {
+    let (dir, name) = match rela_path.rfind_byte(b'/') {
+        Some(pos) => (Some(rela_path[..pos].as_bstr()), rela_path[pos + 1..].as_bstr()),
+        None => (None, rela_path),
+    };
+    if name.is_empty() || dir.map_or(false, |dir| dir.split_str("/").any(<[u8]>::is_empty)) {
+        return Err(Error::EmptyPathComponent {
+            path: rela_path.to_owned(),
+        });
+    }
+    Ok((dir.unwrap_or_default(), name))
+}
@@ -5,8 +5,13 @@ use crate::{
     tree,
 };
 
+///
+pub mod editor;
+pub use editor::Editor;
 mod ref_iter;
 ///
+pub mod validate;
+///
 pub mod write;
 
 /// The mode of items storable in a tree, similar to the file mode on a unix file system.
@@ -127,6 +132,15 @@ impl EntryMode {
         )
     }
 
+    /// Return true if `other` is a different kind of entry, like a symlink or a submodule instead of a blob,
+    /// which `git` calls a _typechange_. Changes of the executable bit of blobs aren't a change of type.
+    pub const fn is_typechange(&self, other: EntryMode) -> bool {
+        if self.is_blob() && other.is_blob() {
+            return false;
+        }
+        self.kind() as u16 != other.kind() as u16
+    }
+
     /// Represent the mode as descriptive string.
     pub const fn as_str(&self) -> &'static str {
         use EntryKind::*;
@@ -184,13 +198,7 @@ impl<'a> PartialOrd for EntryRef<'a> {
 
 impl<'a> Ord for EntryRef<'a> {
     fn cmp(&self, b: &Self) -> Ordering {
-        let a = self;
-        let common = a.filename.len().min(b.filename.len());
-        a.filename[..common].cmp(&b.filename[..common]).then_with(|| {
-            let a = a.filename.get(common).or_else(|| a.mode.is_tree().then_some(&b'/'));
-            let b = b.filename.get(common).or_else(|| b.mode.is_tree().then_some(&b'/'));
-            a.cmp(&b)
-        })
+        cmp_by_name_and_mode(self.filename, self.mode, b.filename, b.mode)
     }
 }
 
@@ -214,12 +222,16 @@ impl PartialOrd for Entry {
 
 impl Ord for Entry {
     fn cmp(&self, b: &Self) -> Ordering {
-        let a = self;
-        let common = a.filename.len().min(b.filename.len());
-        a.filename[..common].cmp(&b.filename[..common]).then_with(|| {
-            let a = a.filename.get(common).or_else(|| a.mode.is_tree().then_some(&b'/'));
-            let b = b.filename.get(common).or_else(|| b.mode.is_tree().then_some(&b'/'));
-            a.cmp(&b)
-        })
+        cmp_by_name_and_mode(self.filename.as_ref(), self.mode, b.filename.as_ref(), b.mode)
     }
 }
+
+/// Compare entries the way `git` sorts them in trees, which is by name with trees sorting as if their name had a trailing slash.
+fn cmp_by_name_and_mode(a_name: &BStr, a_mode: EntryMode, b_name: &BStr, b_mode: EntryMode) -> Ordering {
+    let common = a_name.len().min(b_name.len());
+    a_name[..common].cmp(&b_name[..common]).then_with(|| {
+        let a = a_name.get(common).or_else(|| a_mode.is_tree().then_some(&b'/'));
+        let b = b_name.get(common).or_else(|| b_mode.is_tree().then_some(&b'/'));
+        a.cmp(&b)
+    })
+}
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{
+    tree::{Entry, EntryKind, EntryMode, EntryRef},
+    Tree, TreeRef,
+};
+
+/// The error returned by [`Tree::validate()`], [`TreeRef::validate()`] and [`Tree::canonicalize()`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Entry {name:?} has the invalid mode {mode:o}")]
+    BadMode { name: BString, mode: u16 },
+    #[error("Entries must not have empty names")]
+    EmptyName,
+    #[error("Entry name {name:?} contains the forbidden byte {byte:?}")]
+    ForbiddenByte { name: BString, byte: char },
+    #[error("Entry name {name:?} is reserved and could be used to escape the tree or to alter the repository")]
+    ReservedName { name: BString },
+    #[error("Entry {name:?} must be sorted before {previous:?}")]
+    NotSorted { previous: BString, name: BString },
+    #[error("Entry {name:?} exists more than once")]
+    Duplicate { name: BString },
+}
+
+/// Validate a single entry `name` as it would be stored in a tree.
+///
+/// It must not be empty, must not contain slashes or null bytes, and must neither be `.` nor `..`.
+/// Names that any common filesystem might consider equal to `.git`, like `.GIT`, `.git.`, `git~1` or
+/// `.g\u{200c}it`, are refused as well as they could be used to write into the repository on checkout.
+pub fn filename(name: &BStr) -> Result<(), Error> {
+    if name.is_empty() {
+        return Err(Error::EmptyName);
+    }
+    if let Some(byte) = name.iter().find(|b| matches!(b, b'/' | b'\0')) {
+        return Err(Error::ForbiddenByte {
+            name: name.to_owned(),
+            byte: *byte as char,
+        });
+    }
+    if name == "." || name == ".." || is_dot_git(name) {
+        return Err(Error::ReservedName { name: name.to_owned() });
+    }
+    Ok(())
+}
+
+/// Validate a single entry `mode`, which may only be one of the modes `git` produces.
+///
+/// The legacy mode `100664` for group-writable files is accepted, just like `git fsck` does by default.
+/// Note that `name` is only used for error reporting.
+pub fn mode(name: &BStr, mode: EntryMode) -> Result<(), Error> {
+    const LEGACY_GROUP_WRITABLE_BLOB: u16 = 0o100664;
+    let is_valid = [
+        EntryKind::Tree,
+        EntryKind::Blob,
+        EntryKind::BlobExecutable,
+        EntryKind::Link,
+        EntryKind::Commit,
+    ]
+    .into_iter()
+    .any(|kind| *mode == kind as u16)
+        || *mode == LEGACY_GROUP_WRITABLE_BLOB;
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::BadMode {
+            name: name.to_owned(),
+            mode: *mode,
+        })
+    }
+}
+
+/// Validation
+impl<'a> TreeRef<'a> {
+    /// Check that all entries have valid [modes](mode()) and [names](filename()), and that they are sorted
+    /// in the order `git` expects without containing the same name more than once.
+    ///
+    /// The first problem found is returned.
+    pub fn validate(&self) -> Result<(), Error> {
+        entries(
+            self.entries
+                .iter()
+                .map(|EntryRef { mode, filename, .. }| (*mode, *filename)),
+        )
+    }
+}
+
+/// Validation
+impl Tree {
+    /// Check that all entries have valid [modes](mode()) and [names](filename()), and that they are sorted
+    /// in the order `git` expects without containing the same name more than once.
+    ///
+    /// The first problem found is returned.
+    pub fn validate(&self) -> Result<(), Error> {
+        entries(
+            self.entries
+                .iter()
+                .map(|Entry { mode, filename, .. }| (*mode, filename.as_bstr())),
+        )
+    }
+
+    /// Bring this tree into the canonical form that `git` expects by normalizing the modes of regular files to
+    /// either `100644` or `100755`, and by sorting its entries, before [validating](Self::validate()) it.
+    ///
+    /// Use this before writing trees whose entries were assembled by hand to assure they are well-formed.
+    pub fn canonicalize(&mut self) -> Result<(), Error> {
+        for entry in &mut self.entries {
+            const REGULAR_FILE: u16 = 0o100000;
+            if *entry.mode & 0o170000 == REGULAR_FILE {
+                entry.mode = entry.mode.kind().into();
+            }
+        }
+        self.entries.sort();
+        self.validate()
+    }
+}
+
+fn entries<'a>(entries: impl Iterator<Item = (EntryMode, &'a BStr)>) -> Result<(), Error> {
+    let mut seen = HashSet::<&BStr>::new();
+    let mut previous = None::<(EntryMode, &BStr)>;
+    for (entry_mode, name) in entries {
+        filename(name)?;
+        mode(name, entry_mode)?;
+        if !seen.insert(name) {
+            return Err(Error::Duplicate { name: name.to_owned() });
+        }
+        if let Some((previous_mode, previous_name)) = previous {
+            if super::cmp_by_name_and_mode(previous_name, previous_mode, name, entry_mode).is_gt() {
+                return Err(Error::NotSorted {
+                    previous: previous_name.to_owned(),
+                    name: name.to_owned(),
+                });
+            }
+        }
+        previous = Some((entry_mode, name));
+    }
+    Ok(())
+}
+
+/// Return `true` if `name` could be interpreted as `.git` directory by case-insensitive filesystems, NTFS or HFS+.
+fn is_dot_git(name: &BStr) -> bool {
+    /// Code points that HFS+ ignores when comparing names.
+    const HFS_IGNORED: &[char] = &[
+        '\u{200c}', '\u{200d}', '\u{200e}', '\u{200f}', '\u{202a}', '\u{202b}', '\u{202c}', '\u{202d}', '\u{202e}',
+        '\u{206a}', '\u{206b}', '\u{206c}', '\u{206d}', '\u{206e}', '\u{206f}', '\u{feff}',
+    ];
+    let is_git = |name: &[u8]| name.eq_ignore_ascii_case(b".git");
+    if is_git(name) || name.eq_ignore_ascii_case(b"git~1") {
+        return true;
+    }
+    let without_ntfs_trailer = name.trim_end_with(|c| c == '.' || c == ' ');
+    if is_git(without_ntfs_trailer) {
+        return true;
+    }
+    let without_hfs_ignorables: Vec<u8> = name
+        .chars()
+        .filter(|c| !HFS_IGNORED.contains(c))
+        .collect::<String>()
+        .into_bytes();
+    is_git(&without_hfs_ignorables)
+}
@@ -305,3 +305,82 @@ fn newline_right_after_signature_multiline_header() -> crate::Result {
     assert!(commit.message.starts_with(b"Rollup"));
     Ok(())
 }
+
+mod with_mode {
+    use gix_object::{
+        decode::{mode::Error, Mode},
+        CommitRef,
+    };
+
+    use crate::fixture_name;
+
+    #[test]
+    fn strict_mode_accepts_well_formed_commits() -> crate::Result {
+        for name in [
+            "merge.txt",
+            "mergetag.txt",
+            "message-with-footer.txt",
+            "signed-singleline.txt",
+            "signed-whitespace.txt",
+            "signed-with-encoding.txt",
+            "signed.txt",
+            "two-multiline-headers.txt",
+            "unsigned.txt",
+            "whitespace.txt",
+            "with-encoding.txt",
+        ] {
+            let data = fixture_name("commit", name);
+            assert_eq!(
+                CommitRef::from_bytes_with_mode(&data, Mode::Strict)?,
+                CommitRef::from_bytes(&data)?,
+                "{name}: strict mode only adds checks"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn strict_mode_rejects_commits_that_lenient_mode_accepts() {
+        let tree = "tree 1b2dfb4ac5e42080b682fc676e9738c94ce6d54d\n";
+        let author = "author Sebastian Thiel <sebastian.thiel@icloud.com> 1592437401 +0800\n";
+        let committer = "committer Sebastian Thiel <sebastian.thiel@icloud.com> 1592437401 +0800\n";
+        for (data, is_expected_error) in [
+            (
+                fixture_name("commit", "pre-epoch.txt"),
+                (|err| matches!(err, Error::BadDate { field: "author", .. })) as fn(&Error) -> bool,
+            ),
+            (
+                fixture_name("commit", "double-dash-date-offset.txt"),
+                |err| matches!(err, Error::BadTimezone { field: "author", value } if value == "--700"),
+            ),
+            (
+                format!("{tree}author a <b> 01 +0000\n{committer}\nmessage").into_bytes(),
+                |err| matches!(err, Error::ZeroPaddedDate { field: "author", .. }),
+            ),
+            (
+                format!("{tree}{author}committer a <b> 1 +0060\n\nmessage").into_bytes(),
+                |err| matches!(err, Error::BadTimezone { field: "committer", .. }),
+            ),
+            (
+                format!("{tree}{author}committer a <b> 1 +060\n\nmessage").into_bytes(),
+                |err| matches!(err, Error::BadTimezone { field: "committer", .. }),
+            ),
+            (
+                format!("{tree}author a> <b> 1 +0000\n{committer}\nmessage").into_bytes(),
+                |err| matches!(err, Error::BadIdentity { field: "author", .. }),
+            ),
+            (
+                format!("{tree}{author}{committer}{author}\nmessage").into_bytes(),
+                |err| matches!(err, Error::DuplicateHeader { field: "author" }),
+            ),
+            (
+                format!("{tree}{author}{committer}x-header with\0null\n\nmessage").into_bytes(),
+                |err| matches!(err, Error::NullInHeader),
+            ),
+        ] {
+            CommitRef::from_bytes_with_mode(&data, Mode::Lenient).expect("lenient mode accepts it");
+            let err = CommitRef::from_bytes_with_mode(&data, Mode::Strict).expect_err("strict mode rejects it");
+            assert!(is_expected_error(&err), "unexpected error: {err:?}");
+        }
+    }
+}
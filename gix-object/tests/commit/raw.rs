@@ -0,0 +1,175 @@
+use gix_object::{bstr::ByteSlice, CommitRefIter, RawCommit, WriteTo};
+
+use crate::{fixture_name, hex_to_id, signature};
+
+const FIXTURES: &[&str] = &[
+    "double-dash-date-offset.txt",
+    "merge.txt",
+    "mergetag.txt",
+    "message-with-footer.txt",
+    "pre-epoch.txt",
+    "signed-singleline.txt",
+    "signed-whitespace.txt",
+    "signed-with-encoding.txt",
+    "signed.txt",
+    "two-multiline-headers.txt",
+    "unsigned.txt",
+    "whitespace.txt",
+    "with-encoding.txt",
+];
+
+fn to_bytes(commit: &RawCommit) -> Vec<u8> {
+    let mut buf = Vec::new();
+    commit.write_to(&mut buf).expect("writing to a vec never fails");
+    assert_eq!(commit.size(), buf.len() as u64, "the size is computed correctly");
+    buf
+}
+
+#[test]
+fn round_trips_byte_for_byte() -> crate::Result {
+    for name in FIXTURES {
+        let data = fixture_name("commit", name);
+        let commit = RawCommit::from_bytes(&data)?;
+        assert_eq!(to_bytes(&commit).as_bstr(), data.as_bstr(), "{name}");
+    }
+    Ok(())
+}
+
+#[test]
+fn round_trips_unusual_header_order_and_unknown_headers() -> crate::Result {
+    let data = b"tree 1b2dfb4ac5e42080b682fc676e9738c94ce6d54d\nx-custom first\n continued\n \nauthor a <b> 1 +0000\nparent 09d8d3a12e161a7f6afb522dbe8900a9c09bce06\ncommitter a <b> 1 +0000\n\n\nmessage with leading newline";
+    let commit = RawCommit::from_bytes(data)?;
+    assert_eq!(commit.header("x-custom"), Some("first\ncontinued\n".into()));
+    assert_eq!(commit.message(), "\nmessage with leading newline");
+    assert_eq!(to_bytes(&commit).as_bstr(), data.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn round_trips_headers_without_value() -> crate::Result {
+    let data = b"tree 1b2dfb4ac5e42080b682fc676e9738c94ce6d54d\nencoding\nx-empty \n\nmessage";
+    let mut commit = RawCommit::from_bytes(data)?;
+    assert_eq!(commit.header("encoding"), Some("".into()));
+    assert_eq!(commit.header("x-empty"), Some("".into()));
+    assert_eq!(
+        to_bytes(&commit).as_bstr(),
+        data.as_bstr(),
+        "lone names and empty values are distinguished"
+    );
+
+    commit.set_header("encoding", "ISO-8859-1");
+    assert_eq!(commit.header("encoding"), Some("ISO-8859-1".into()));
+    assert!(to_bytes(&commit).contains_str("\nencoding ISO-8859-1\n"));
+    Ok(())
+}
+
+#[test]
+fn malformed_input() {
+    for input in [
+        &b"tree 1b2dfb4ac5e42080b682fc676e9738c94ce6d54d\n"[..],
+        b" continuation-without-header\n\n",
+    ] {
+        assert!(RawCommit::from_bytes(input).is_err(), "{:?}", input.as_bstr());
+    }
+}
+
+#[test]
+fn edits_preserve_signature_and_other_headers() -> crate::Result {
+    let data = fixture_name("commit", "signed-with-encoding.txt");
+    let mut commit = RawCommit::from_bytes(&data)?;
+    let signature_before = commit.signature().expect("signed").to_owned();
+    commit
+        .set_message("new message\n")
+        .set_committer(signature(1700000000))
+        .set_tree(&hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbe4904c"));
+
+    let actual = to_bytes(&commit);
+    let (actual_headers, actual_message) = actual.split_once_str("\n\n").expect("separator");
+    let (expected_headers, _) = data.split_once_str("\n\n").expect("separator");
+    assert_eq!(actual_message.as_bstr(), "new message\n");
+    assert_eq!(
+        actual_headers.lines().count(),
+        expected_headers.lines().count(),
+        "nothing was added or removed"
+    );
+    for (actual, expected) in actual_headers.lines().zip(expected_headers.lines()) {
+        if actual.starts_with(b"tree ") {
+            assert_eq!(actual.as_bstr(), "tree 4b825dc642cb6eb9a060e54bf8d69288fbe4904c");
+        } else if actual.starts_with(b"committer ") {
+            assert_eq!(
+                actual.as_bstr(),
+                "committer Sebastian Thiel <sebastian.thiel@icloud.com> 1700000000 +0800"
+            );
+        } else {
+            assert_eq!(actual.as_bstr(), expected.as_bstr(), "all other lines are unchanged");
+        }
+    }
+    assert_eq!(commit.signature(), Some(signature_before.as_bstr()));
+    Ok(())
+}
+
+#[test]
+fn signatures_can_be_removed_and_restored() -> crate::Result {
+    for name in [
+        "signed.txt",
+        "signed-singleline.txt",
+        "signed-whitespace.txt",
+        "signed-with-encoding.txt",
+        "message-with-footer.txt",
+    ] {
+        let data = fixture_name("commit", name);
+        let mut commit = RawCommit::from_bytes(&data)?;
+        let (expected_signature, expected_signed_data) =
+            CommitRefIter::signature(&data)?.expect("signature is present");
+        assert_eq!(commit.signature(), Some(expected_signature.as_ref()), "{name}");
+        assert_eq!(commit.signed_data(), expected_signed_data.to_bstring(), "{name}");
+
+        let signature = expected_signature.into_owned();
+        assert!(commit.remove_signature());
+        assert!(!commit.remove_signature(), "nothing left to remove");
+        assert_eq!(commit.signature(), None);
+        assert_eq!(
+            to_bytes(&commit).as_bstr(),
+            expected_signed_data.to_bstring(),
+            "{name}: without signature, the commit is what was signed"
+        );
+
+        let mut signature_from_signing_program = signature.clone();
+        signature_from_signing_program.push(b'\n');
+        commit.set_signature(signature_from_signing_program.as_ref());
+        assert_eq!(commit.signature(), Some(signature.as_ref()));
+        assert_eq!(
+            to_bytes(&commit).as_bstr(),
+            data.as_bstr(),
+            "{name}: signing appends the signature as last header, just like git"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn set_parents_keeps_them_after_the_tree() -> crate::Result {
+    let mut commit = RawCommit::from_bytes(&fixture_name("commit", "unsigned.txt"))?;
+    let parents = [
+        hex_to_id("09d8d3a12e161a7f6afb522dbe8900a9c09bce06"),
+        hex_to_id("6a6054db4ce3c1e4e6a37f8c4d7acb63a4d6ad71"),
+    ];
+    commit.set_parents(parents);
+    assert_eq!(
+        commit.headers().map(|(name, _)| name.to_string()).collect::<Vec<_>>(),
+        ["tree", "parent", "parent", "author", "committer"]
+    );
+
+    commit.set_parents(Some(parents[1]));
+    assert_eq!(
+        commit
+            .headers()
+            .filter(|(name, _)| *name == "parent")
+            .map(|(_, value)| value.to_string())
+            .collect::<Vec<_>>(),
+        ["6a6054db4ce3c1e4e6a37f8c4d7acb63a4d6ad71"]
+    );
+    assert_eq!(commit.remove_header("parent"), 1);
+    assert_eq!(commit.headers().count(), 3);
+    Ok(())
+}
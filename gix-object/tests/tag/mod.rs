@@ -136,6 +136,54 @@ fn invalid() {
     );
 }
 
+mod with_mode {
+    use gix_object::{
+        decode::{mode::Error, Mode},
+        TagRef,
+    };
+
+    use crate::fixture_name;
+
+    #[test]
+    fn strict_mode_accepts_well_formed_tags() -> crate::Result {
+        for name in [
+            "empty.txt",
+            "no-tagger.txt",
+            "signed.txt",
+            "whitespace.txt",
+            "with-newlines.txt",
+        ] {
+            let data = fixture_name("tag", name);
+            assert_eq!(
+                TagRef::from_bytes_with_mode(&data, Mode::Strict)?,
+                TagRef::from_bytes(&data)?,
+                "{name}: strict mode only adds checks"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn strict_mode_rejects_invalid_tag_names_and_taggers() {
+        let header = "object 01dd4e2a978a9f5bd773dae6da7aa4a5ac1cdbbc\ntype commit\n";
+        let data = format!("{header}tag in..valid\ntagger a <b> 1 +0000\n\nmessage\n");
+        TagRef::from_bytes_with_mode(data.as_bytes(), Mode::Lenient).expect("lenient mode accepts it");
+        assert!(matches!(
+            TagRef::from_bytes_with_mode(data.as_bytes(), Mode::Strict),
+            Err(Error::BadTagName(_))
+        ));
+
+        let data = format!("{header}tag valid\ntagger a <b> 1 ++0000\n");
+        TagRef::from_bytes_with_mode(data.as_bytes(), Mode::Lenient).expect("lenient mode accepts it");
+        assert!(matches!(
+            TagRef::from_bytes_with_mode(data.as_bytes(), Mode::Strict),
+            Err(Error::BadTimezone { field: "tagger", .. })
+        ));
+    }
+}
+
+mod raw;
+
 mod from_bytes {
     use gix_object::{bstr::ByteSlice, Kind, TagRef};
 
@@ -0,0 +1,117 @@
+use gix_object::{bstr::ByteSlice, Kind, RawTag, TagRef, WriteTo};
+
+use crate::{fixture_name, hex_to_id, signature};
+
+const FIXTURES: &[&str] = &[
+    "empty.txt",
+    "no-tagger.txt",
+    "signed.txt",
+    "whitespace.txt",
+    "with-newlines.txt",
+];
+
+fn to_bytes(tag: &RawTag) -> Vec<u8> {
+    let mut buf = Vec::new();
+    tag.write_to(&mut buf).expect("writing to a vec never fails");
+    assert_eq!(tag.size(), buf.len() as u64, "the size is computed correctly");
+    buf
+}
+
+#[test]
+fn round_trips_byte_for_byte() -> crate::Result {
+    for name in FIXTURES {
+        let data = fixture_name("tag", name);
+        let tag = RawTag::from_bytes(&data)?;
+        assert_eq!(to_bytes(&tag).as_bstr(), data.as_bstr(), "{name}");
+        assert_eq!(
+            tag.signature(),
+            TagRef::from_bytes(&data)?.pgp_signature,
+            "{name}: signatures are found like the decoder does"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn round_trips_unknown_headers_and_headers_without_value() -> crate::Result {
+    let data = b"object ffa700b4aca13b80cb6b98a078e7c96804f8e0ec\ntype commit\nx-lone\ntag 1.0.0\nx-custom first\n continued\n\nmessage\n";
+    let tag = RawTag::from_bytes(data)?;
+    assert_eq!(tag.header("x-lone"), Some("".into()));
+    assert_eq!(tag.header("x-custom"), Some("first\ncontinued".into()));
+    assert_eq!(tag.header("tagger"), None);
+    assert_eq!(tag.message(), "message\n");
+    assert_eq!(tag.signature(), None);
+    assert_eq!(to_bytes(&tag).as_bstr(), data.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn edits_preserve_the_signature_and_other_headers() -> crate::Result {
+    let data = fixture_name("tag", "signed.txt");
+    let mut tag = RawTag::from_bytes(&data)?;
+    let signature_before = tag.signature().expect("signed").to_owned();
+    tag.set_name("2.0.0")
+        .set_tagger(signature(1700000000))
+        .set_target(&hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbe4904c"), Kind::Tree);
+
+    let decoded_data = to_bytes(&tag);
+    let decoded = TagRef::from_bytes(&decoded_data)?;
+    assert_eq!(decoded.name, "2.0.0");
+    assert_eq!(decoded.target(), hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbe4904c"));
+    assert_eq!(decoded.target_kind, Kind::Tree);
+    assert_eq!(decoded.tagger.expect("present").time.seconds, 1700000000);
+    assert_eq!(decoded.message, "for the signature");
+    assert_eq!(tag.signature(), Some(signature_before.as_bstr()));
+    assert_eq!(
+        tag.headers().map(|(name, _)| name.to_string()).collect::<Vec<_>>(),
+        ["object", "type", "tag", "tagger"],
+        "the order of headers is kept"
+    );
+
+    tag.set_message("new message\n");
+    assert_eq!(tag.message(), "new message\n");
+    assert_eq!(tag.signature(), None, "the signature doesn't match the new message anymore");
+    Ok(())
+}
+
+#[test]
+fn signatures_can_be_removed_and_restored() -> crate::Result {
+    for name in ["signed.txt", "no-tagger.txt"] {
+        let data = fixture_name("tag", name);
+        let mut tag = RawTag::from_bytes(&data)?;
+        let signature = tag.signature().expect("signed").to_owned();
+        let signed_data = tag.signed_data();
+        assert!(
+            data.starts_with(&signed_data),
+            "{name}: the signature is at the very end, after the signed data"
+        );
+
+        assert!(tag.remove_signature());
+        assert!(!tag.remove_signature(), "nothing left to remove");
+        assert_eq!(tag.signature(), None);
+        assert_eq!(
+            to_bytes(&tag).as_bstr(),
+            signed_data,
+            "{name}: without signature, the tag is what was signed"
+        );
+
+        tag.set_signature(signature.as_ref());
+        assert_eq!(tag.signature(), Some(signature.as_ref()));
+        assert_eq!(
+            to_bytes(&tag).as_bstr(),
+            data.as_bstr(),
+            "{name}: signing appends the signature to the message, just like git"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn malformed_input() {
+    for input in [
+        &b"object ffa700b4aca13b80cb6b98a078e7c96804f8e0ec\n"[..],
+        b" continuation-without-header\n\n",
+    ] {
+        assert!(RawTag::from_bytes(input).is_err(), "{:?}", input.as_bstr());
+    }
+}
@@ -164,6 +164,7 @@ mod entries {
 
         tree.entries.sort();
         assert_eq!(tree.entries, expected);
+        tree.validate()?;
         let mut failures_when_searching_by_name = 0;
         for entry in expected {
             assert!(
@@ -196,6 +197,195 @@ mod entries {
     }
 }
 
+mod validate {
+    use gix_object::{
+        bstr::BString,
+        tree::{validate::Error, Entry, EntryKind, EntryMode},
+        Tree,
+    };
+
+    fn entry(name: &str, mode: EntryMode) -> Entry {
+        Entry {
+            mode,
+            filename: name.into(),
+            oid: gix_hash::Kind::Sha1.null(),
+        }
+    }
+
+    fn mode(octal: &str) -> EntryMode {
+        EntryMode::try_from(format!("{octal} ").as_bytes()).expect("valid octal mode")
+    }
+
+    fn blob(name: &str) -> Entry {
+        entry(name, EntryKind::Blob.into())
+    }
+
+    fn dir(name: &str) -> Entry {
+        entry(name, EntryKind::Tree.into())
+    }
+
+    #[test]
+    fn well_formed_trees_are_valid() -> crate::Result {
+        let tree = Tree {
+            entries: vec![
+                blob("a"),
+                blob("a.b"),
+                dir("a0"),
+                dir("b"),
+                entry("legacy", mode("100664")),
+            ],
+        };
+        tree.validate()?;
+        Tree::empty().validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn bad_modes() {
+        for mode in ["100744", "644", "40755", "0"].map(mode) {
+            let tree = Tree {
+                entries: vec![entry("a", mode)],
+            };
+            assert_eq!(
+                tree.validate(),
+                Err(Error::BadMode {
+                    name: "a".into(),
+                    mode: *mode
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn forbidden_names() {
+        for (name, expected) in [
+            ("", Error::EmptyName),
+            (
+                "a/b",
+                Error::ForbiddenByte {
+                    name: "a/b".into(),
+                    byte: '/',
+                },
+            ),
+            (
+                "a\0",
+                Error::ForbiddenByte {
+                    name: "a\0".into(),
+                    byte: '\0',
+                },
+            ),
+        ] {
+            let tree = Tree {
+                entries: vec![blob(name)],
+            };
+            assert_eq!(tree.validate(), Err(expected), "{name:?}");
+        }
+
+        for name in [
+            ".",
+            "..",
+            ".git",
+            ".GIT",
+            ".Git",
+            "git~1",
+            "GIT~1",
+            ".git.",
+            ".git ..",
+            ".g\u{200c}it",
+            "\u{feff}.GIT",
+        ] {
+            let tree = Tree {
+                entries: vec![dir(name)],
+            };
+            assert_eq!(
+                tree.validate(),
+                Err(Error::ReservedName { name: name.into() }),
+                "{name:?} is considered .git on some filesystems"
+            );
+        }
+
+        for name in [
+            ".gitignore",
+            ".git-blame-ignore-revs",
+            "git",
+            "..a",
+            "git~2",
+            ".g\u{2000}it",
+        ] {
+            let tree = Tree {
+                entries: vec![blob(name)],
+            };
+            assert_eq!(tree.validate(), Ok(()), "{name:?} is a regular name");
+        }
+    }
+
+    #[test]
+    fn unsorted_entries() {
+        let tree = Tree {
+            entries: vec![dir("a"), blob("a.b")],
+        };
+        assert_eq!(
+            tree.validate(),
+            Err(Error::NotSorted {
+                previous: "a".into(),
+                name: "a.b".into()
+            }),
+            "trees sort as if they had a trailing slash"
+        );
+    }
+
+    #[test]
+    fn duplicate_entries() {
+        let tree = Tree {
+            entries: vec![blob("a"), blob("a")],
+        };
+        assert_eq!(tree.validate(), Err(Error::Duplicate { name: "a".into() }));
+
+        let tree = Tree {
+            entries: vec![blob("a"), blob("a.b"), dir("a")],
+        };
+        assert_eq!(
+            tree.validate(),
+            Err(Error::Duplicate { name: "a".into() }),
+            "duplicates are detected even if they are not adjacent due to the sort order"
+        );
+    }
+
+    #[test]
+    fn canonicalize_sorts_entries_and_normalizes_file_modes() -> crate::Result {
+        let mut tree = Tree {
+            entries: vec![
+                blob("c"),
+                entry("b", mode("100664")),
+                entry("a", mode("100775")),
+                dir("a.b"),
+            ],
+        };
+        tree.canonicalize()?;
+        assert_eq!(
+            tree.entries,
+            vec![
+                entry("a", EntryKind::BlobExecutable.into()),
+                dir("a.b"),
+                blob("b"),
+                blob("c")
+            ]
+        );
+
+        let mut tree = Tree {
+            entries: vec![blob("b"), dir(".git")],
+        };
+        assert_eq!(
+            tree.canonicalize(),
+            Err(Error::ReservedName {
+                name: BString::from(".git")
+            }),
+            "canonicalization doesn't fix names"
+        );
+        Ok(())
+    }
+}
+
 mod entry_mode {
     use gix_object::tree::{EntryKind, EntryMode};
 
@@ -232,4 +422,182 @@ mod entry_mode {
             assert_eq!(mode.as_bytes(&mut buf), expected)
         }
     }
+
+    #[test]
+    fn is_typechange() {
+        let (blob, executable, link, commit, tree): (EntryMode, EntryMode, EntryMode, EntryMode, EntryMode) = (
+            EntryKind::Blob.into(),
+            EntryKind::BlobExecutable.into(),
+            EntryKind::Link.into(),
+            EntryKind::Commit.into(),
+            EntryKind::Tree.into(),
+        );
+        assert!(
+            !blob.is_typechange(executable),
+            "changes of the executable bit don't change the type"
+        );
+        assert!(!executable.is_typechange(blob));
+        for other in [link, commit, tree] {
+            assert!(blob.is_typechange(other));
+            assert!(executable.is_typechange(other));
+            assert!(other.is_typechange(blob));
+            assert!(!other.is_typechange(other));
+        }
+        assert!(link.is_typechange(commit));
+    }
+}
+
+mod editor {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use gix_hash::ObjectId;
+    use gix_object::{
+        bstr::BStr,
+        tree::{editor::Error, EntryKind},
+        FindExt, Tree, WriteTo,
+    };
+
+    #[derive(Default)]
+    struct Store(RefCell<HashMap<ObjectId, Vec<u8>>>);
+
+    impl gix_object::Find for Store {
+        fn try_find<'a>(
+            &self,
+            id: &gix_hash::oid,
+            buffer: &'a mut Vec<u8>,
+        ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
+            let Some(data) = self.0.borrow().get(id).cloned() else {
+                return Ok(None);
+            };
+            *buffer = data;
+            Ok(Some(gix_object::Data::new(gix_object::Kind::Tree, buffer)))
+        }
+    }
+
+    impl Store {
+        fn write(&self, tree: &Tree) -> Result<ObjectId, std::convert::Infallible> {
+            let mut buf = Vec::new();
+            tree.write_to(&mut buf).expect("in-memory writes don't fail");
+            let id = gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Tree, &buf);
+            self.0.borrow_mut().insert(id, buf);
+            Ok(id)
+        }
+
+        fn paths(&self, id: ObjectId) -> Vec<String> {
+            let mut out = Vec::new();
+            self.collect_paths(id, "", &mut out);
+            out
+        }
+
+        fn collect_paths(&self, id: ObjectId, prefix: &str, out: &mut Vec<String>) {
+            let tree: Tree = self.find_tree(&id, &mut Vec::new()).expect("present").into();
+            for entry in tree.entries {
+                let path = format!("{prefix}{}", entry.filename);
+                if entry.mode.is_tree() {
+                    self.collect_paths(entry.oid, &format!("{path}/"), out);
+                } else {
+                    out.push(path);
+                }
+            }
+        }
+    }
+
+    fn blob() -> ObjectId {
+        ObjectId::empty_blob(gix_hash::Kind::Sha1)
+    }
+
+    fn base(store: &Store) -> ObjectId {
+        let mut editor = gix_object::tree::Editor::new(Tree::empty(), store, gix_hash::Kind::Sha1);
+        for path in ["a", "dir/b", "dir/sub/c", "other/d"] {
+            editor.upsert(path.into(), EntryKind::Blob, blob()).expect("valid path");
+        }
+        editor.write(|tree| store.write(tree)).expect("infallible")
+    }
+
+    fn edit(store: &Store, id: ObjectId, edit: impl FnOnce(&mut gix_object::tree::Editor<'_>)) -> ObjectId {
+        let tree: Tree = store.find_tree(&id, &mut Vec::new()).expect("present").into();
+        let mut editor = gix_object::tree::Editor::new(tree, store, gix_hash::Kind::Sha1);
+        edit(&mut editor);
+        editor.write(|tree| store.write(tree)).expect("infallible")
+    }
+
+    fn subtree(store: &Store, id: ObjectId, name: &str) -> ObjectId {
+        let tree: Tree = store.find_tree(&id, &mut Vec::new()).expect("present").into();
+        tree.entries
+            .into_iter()
+            .find(|entry| entry.filename == name)
+            .expect("present")
+            .oid
+    }
+
+    #[test]
+    fn upsert_creates_leading_trees_and_keeps_untouched_ones() -> crate::Result {
+        let store = Store::default();
+        let root = base(&store);
+        assert_eq!(store.paths(root), ["a", "dir/b", "dir/sub/c", "other/d"]);
+
+        let edited = edit(&store, root, |editor| {
+            editor.upsert("dir/sub/e".into(), EntryKind::Blob, blob()).unwrap();
+            editor.upsert("new/deep/f".into(), EntryKind::Link, blob()).unwrap();
+        });
+        assert_eq!(
+            store.paths(edited),
+            ["a", "dir/b", "dir/sub/c", "dir/sub/e", "new/deep/f", "other/d"]
+        );
+        assert_eq!(
+            subtree(&store, edited, "other"),
+            subtree(&store, root, "other"),
+            "untouched trees are kept"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn upsert_replaces_blobs_in_the_way_and_trees() -> crate::Result {
+        let store = Store::default();
+        let root = base(&store);
+        let edited = edit(&store, root, |editor| {
+            editor.upsert("a/x".into(), EntryKind::Blob, blob()).unwrap();
+            editor.upsert("dir/sub/y".into(), EntryKind::Blob, blob()).unwrap();
+            editor.upsert("dir/sub".into(), EntryKind::Blob, blob()).unwrap();
+        });
+        assert_eq!(store.paths(edited), ["a/x", "dir/b", "dir/sub", "other/d"]);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_deletes_trees_that_become_empty() -> crate::Result {
+        let store = Store::default();
+        let root = base(&store);
+        let edited = edit(&store, root, |editor| {
+            editor.remove("dir/sub/c".into()).unwrap();
+            editor.remove("other".into()).unwrap();
+            editor.remove("missing/path".into()).unwrap();
+            editor.remove("a/not-a-tree".into()).unwrap();
+        });
+        assert_eq!(store.paths(edited), ["a", "dir/b"]);
+
+        let edited = edit(&store, edited, |editor| {
+            for path in ["a", "dir/b"] {
+                editor.remove(path.into()).unwrap();
+            }
+        });
+        assert_eq!(edited, ObjectId::empty_tree(gix_hash::Kind::Sha1));
+        Ok(())
+    }
+
+    #[test]
+    fn empty_path_components_are_rejected() {
+        let store = Store::default();
+        let mut editor = gix_object::tree::Editor::new(Tree::empty(), &store, gix_hash::Kind::Sha1);
+        for path in ["", "a/", "/a", "a//b"] {
+            assert!(
+                matches!(
+                    editor.upsert(BStr::new(path), EntryKind::Blob, blob()),
+                    Err(Error::EmptyPathComponent { .. })
+                ),
+                "{path:?}"
+            );
+        }
+    }
 }
@@ -0,0 +1,211 @@
+use std::{
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use gix_object::bstr::{BString, ByteSlice, ByteVec};
+use gix_path::realpath::MAX_SYMLINKS;
+
+use crate::alternate::parse;
+
+/// Returned by [`add()`] and [`remove()`].
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Realpath(#[from] gix_path::realpath::Error),
+    #[error(transparent)]
+    Parse(#[from] parse::Error),
+    #[error(transparent)]
+    Resolve(#[from] crate::alternate::Error),
+    #[error("Could not lock the alternates file for editing")]
+    Lock(#[from] gix_lock::acquire::Error),
+    #[error("Could not replace the alternates file with its edited version")]
+    Commit(#[from] gix_lock::commit::Error<gix_lock::File>),
+    #[error("The alternate object directory '{}' doesn't exist or isn't a directory", .0.display())]
+    NotADirectory(PathBuf),
+    #[error("Using '{}' as alternate of '{}' would form a cycle", alternate.display(), objects_directory.display())]
+    Cycle {
+        objects_directory: PathBuf,
+        alternate: PathBuf,
+    },
+    #[error("The path '{}' can't be written to an alternates file", .0.display())]
+    PathConversion(PathBuf),
+}
+
+/// Determines how the path of an alternate object directory is written to the alternates file.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PathKind {
+    /// Write the absolute path to the alternate object directory.
+    #[default]
+    Absolute,
+    /// Write the path to the alternate object directory relative to the objects directory whose alternates are edited,
+    /// which allows to move both directories together, or falls back to an absolute path if there is no relative path.
+    Relative,
+}
+
+/// Add the `alternate` object directory to the alternates file of `objects_directory`, or do nothing if it is already listed,
+/// with relative paths resolved using the `current_dir`. The path is written as determined by `path_kind`.
+/// Return `true` if the alternates file was changed.
+///
+/// It's an error if `alternate` isn't a directory, or if it is `objects_directory` itself or uses it as alternate
+/// directly or indirectly, as this would form a cycle.
+///
+/// The alternates file is locked while it is edited and replaced atomically, and all other lines in it are kept as they are.
+pub fn add(objects_directory: &Path, alternate: &Path, path_kind: PathKind, current_dir: &Path) -> Result<bool, Error> {
+    let alternate = gix_path::realpath_opts(alternate, current_dir, MAX_SYMLINKS)?;
+    if !alternate.is_dir() {
+        return Err(Error::NotADirectory(alternate));
+    }
+    let objects_directory_real = gix_path::realpath_opts(objects_directory, current_dir, MAX_SYMLINKS)?;
+    let cycle = || Error::Cycle {
+        objects_directory: objects_directory.to_owned(),
+        alternate: alternate.clone(),
+    };
+    if alternate == objects_directory_real {
+        return Err(cycle());
+    }
+    for transitive_alternate in crate::alternate::resolve(alternate.clone(), current_dir)? {
+        if gix_path::realpath_opts(&transitive_alternate, current_dir, MAX_SYMLINKS)? == objects_directory_real {
+            return Err(cycle());
+        }
+    }
+
+    let path = alternates_path(objects_directory);
+    let lock = lock(objects_directory, &path)?;
+    let mut content = read_or_empty(&path)?;
+    for line in content.lines() {
+        if let Some(existing) = parse::line_to_path(line.as_bstr())? {
+            let existing = gix_path::realpath_opts(&objects_directory.join(existing), current_dir, MAX_SYMLINKS)?;
+            if existing == alternate {
+                return Ok(false);
+            }
+        }
+    }
+
+    let alternate_to_write = match path_kind {
+        PathKind::Absolute => alternate.clone(),
+        PathKind::Relative => relative_path(&alternate, &objects_directory_real).unwrap_or_else(|| alternate.clone()),
+    };
+    if !content.is_empty() && !content.ends_with(b"\n") {
+        content.push(b'\n');
+    }
+    content.push_str(to_line(&alternate_to_write)?);
+    content.push(b'\n');
+    write_and_commit(lock, &content)?;
+    Ok(true)
+}
+
+/// Remove all lines referring to the `alternate` object directory from the alternates file of `objects_directory`,
+/// with relative paths resolved using the `current_dir`.
+/// Return `true` if the alternates file was changed.
+///
+/// The alternates file is locked while it is edited and replaced atomically with all other lines kept as they are,
+/// or deleted if it would be empty.
+pub fn remove(objects_directory: &Path, alternate: &Path, current_dir: &Path) -> Result<bool, Error> {
+    let alternate = gix_path::realpath_opts(alternate, current_dir, MAX_SYMLINKS)?;
+    let path = alternates_path(objects_directory);
+    let lock = lock(objects_directory, &path)?;
+    let content = read_or_empty(&path)?;
+
+    let mut new_content = Vec::with_capacity(content.len());
+    let mut removed_lines = false;
+    for line in content.lines_with_terminator() {
+        if let Some(existing) = parse::line_to_path(line.trim_end_with(|c| c == '\n').as_bstr())? {
+            let existing = gix_path::realpath_opts(&objects_directory.join(existing), current_dir, MAX_SYMLINKS)?;
+            if existing == alternate {
+                removed_lines = true;
+                continue;
+            }
+        }
+        new_content.extend_from_slice(line);
+    }
+
+    if !removed_lines {
+        return Ok(false);
+    }
+    if new_content.trim().is_empty() {
+        std::fs::remove_file(&path)?;
+    } else {
+        write_and_commit(lock, &new_content)?;
+    }
+    Ok(true)
+}
+
+fn alternates_path(objects_directory: &Path) -> PathBuf {
+    objects_directory.join("info").join("alternates")
+}
+
+fn read_or_empty(path: &Path) -> io::Result<Vec<u8>> {
+    match std::fs::read(path) {
+        Ok(content) => Ok(content),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Lock the alternates file at `path` for editing, creating its directory within `objects_directory` if needed.
+fn lock(objects_directory: &Path, path: &Path) -> Result<gix_lock::File, Error> {
+    Ok(gix_lock::File::acquire_to_update_resource(
+        path,
+        gix_lock::acquire::Fail::Immediately,
+        Some(objects_directory.to_owned()),
+    )?)
+}
+
+fn write_and_commit(mut lock: gix_lock::File, content: &[u8]) -> Result<(), Error> {
+    lock.write_all(content)?;
+    lock.commit()?;
+    Ok(())
+}
+
+/// Turn `path` into a line of an alternates file, quoting it if it would be misinterpreted otherwise.
+fn to_line(path: &Path) -> Result<BString, Error> {
+    let path_bytes = gix_path::os_str_into_bstr(path.as_os_str())
+        .map_err(|_| Error::PathConversion(path.to_owned()))?
+        .to_owned();
+    if !(path_bytes.starts_with(b"\"") || path_bytes.starts_with(b"#") || path_bytes.contains(&b'\n')) {
+        return Ok(path_bytes);
+    }
+    let mut out = BString::from("\"");
+    for byte in path_bytes.iter().copied() {
+        match byte {
+            b'"' => out.push_str(b"\\\""),
+            b'\\' => out.push_str(b"\\\\"),
+            b'\n' => out.push_str(b"\\n"),
+            b'\t' => out.push_str(b"\\t"),
+            _ => out.push(byte),
+        }
+    }
+    out.push(b'"');
+    Ok(out)
+}
+
+/// Return `path` relative to the `base` directory, assuming both are absolute and normalized,
+/// or `None` if there is no such path as they are located on different drives.
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    match (path_components.peek(), base_components.peek()) {
+        (Some(Component::Prefix(a)), Some(Component::Prefix(b))) if a != b => return None,
+        _ => {}
+    }
+    while let (Some(a), Some(b)) = (path_components.peek(), base_components.peek()) {
+        if a != b {
+            break;
+        }
+        path_components.next();
+        base_components.next();
+    }
+    let relative: PathBuf = base_components
+        .map(|_| Component::ParentDir)
+        .chain(path_components)
+        .collect();
+    Some(if relative.as_os_str().is_empty() {
+        ".".into()
+    } else {
+        relative
+    })
+}
@@ -1,6 +1,6 @@
 //! A file with directories of other git object databases to use when reading objects.
 //!
-//! This inherently makes alternates read-only.
+//! This inherently makes alternates read-only, and alternates can be managed with [`edit::add()`] and [`edit::remove()`].
 //!
 //! An alternate file in `<git-dir>/info/alternates` can look as follows:
 //!
@@ -23,6 +23,9 @@ use gix_path::realpath::MAX_SYMLINKS;
 ///
 pub mod parse;
 
+///
+pub mod edit;
+
 /// Returned by [`resolve()`]
 #[derive(thiserror::Error, Debug)]
 #[allow(missing_docs)]
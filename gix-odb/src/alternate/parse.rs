@@ -1,6 +1,6 @@
 use std::{borrow::Cow, path::PathBuf};
 
-use gix_object::bstr::ByteSlice;
+use gix_object::bstr::{BStr, ByteSlice};
 
 /// Returned as part of [`crate::alternate::Error::Parse`]
 #[derive(thiserror::Error, Debug)]
@@ -15,19 +15,23 @@ pub enum Error {
 pub(crate) fn content(input: &[u8]) -> Result<Vec<PathBuf>, Error> {
     let mut out = Vec::new();
     for line in input.split(|b| *b == b'\n') {
-        let line = line.as_bstr();
-        if line.is_empty() || line.starts_with(b"#") {
-            continue;
-        }
-        out.push(
-            gix_path::try_from_bstr(if line.starts_with(b"\"") {
-                gix_quote::ansi_c::undo(line)?.0
-            } else {
-                Cow::Borrowed(line)
-            })
-            .map_err(|_| Error::PathConversion(line.to_vec()))?
-            .into_owned(),
-        )
+        out.extend(line_to_path(line.as_bstr())?);
     }
     Ok(out)
 }
+
+/// Return the path in `line`, or `None` if it is empty or a comment.
+pub(crate) fn line_to_path(line: &BStr) -> Result<Option<PathBuf>, Error> {
+    if line.is_empty() || line.starts_with(b"#") {
+        return Ok(None);
+    }
+    Ok(Some(
+        gix_path::try_from_bstr(if line.starts_with(b"\"") {
+            gix_quote::ansi_c::undo(line)?.0
+        } else {
+            Cow::Borrowed(line)
+        })
+        .map_err(|_| Error::PathConversion(line.to_vec()))?
+        .into_owned(),
+    ))
+}
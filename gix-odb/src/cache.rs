@@ -17,6 +17,42 @@ pub type ObjectCache = dyn gix_pack::cache::Object + Send + 'static;
 /// A constructor for boxed object caches.
 pub type NewObjectCacheFn = dyn Fn() -> Box<ObjectCache> + Send + Sync + 'static;
 
+/// The efficiency of a cache, measured by the amount of lookups that could or could not be served by it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Efficiency {
+    /// The amount of lookups that were answered by the cache.
+    pub hits: usize,
+    /// The amount of lookups that the cache couldn't answer.
+    pub misses: usize,
+}
+
+impl Efficiency {
+    /// Return the ratio of hits to all lookups from 0.0 to 1.0, or `None` if there was no lookup yet.
+    pub fn hit_rate(&self) -> Option<f32> {
+        let lookups = self.hits + self.misses;
+        (lookups != 0).then(|| self.hits as f32 / lookups as f32)
+    }
+
+    fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+}
+
+/// Statistics about the caches of a [`Cache`], as returned by [`Cache::statistics()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// The efficiency of the object cache, which remains empty if there is none.
+    pub object_cache: Efficiency,
+    /// The efficiency of the pack cache, which remains empty if there is none.
+    pub pack_cache: Efficiency,
+}
+
 impl Cache<crate::store::Handle<Rc<crate::Store>>> {
     /// Convert this cache's handle into one that keeps its store in an arc. This creates an entirely new store,
     /// so should be done early to avoid unnecessary work (and mappings).
@@ -28,6 +64,7 @@ impl Cache<crate::store::Handle<Rc<crate::Store>>> {
             new_object_cache: self.new_object_cache,
             pack_cache: self.pack_cache,
             object_cache: self.object_cache,
+            statistics: self.statistics,
         })
     }
 }
@@ -79,6 +116,17 @@ impl<S> Cache<S> {
     pub fn has_pack_cache(&self) -> bool {
         self.pack_cache.is_some()
     }
+    /// Return statistics about the efficiency of our caches since this instance was created or since the last
+    /// call to [`reset_statistics()`](Self::reset_statistics()).
+    ///
+    /// Note that clones of this instance have their own caches and thus their own statistics.
+    pub fn statistics(&self) -> Statistics {
+        self.statistics.get()
+    }
+    /// Reset all statistics about the efficiency of our caches, and return the ones collected until now.
+    pub fn reset_statistics(&self) -> Statistics {
+        self.statistics.take()
+    }
     /// Remove the current pack cache as well as its constructor from this instance.
     pub fn unset_pack_cache(&mut self) {
         self.pack_cache = None;
@@ -102,6 +150,7 @@ where
             new_pack_cache: None,
             object_cache: None,
             new_object_cache: None,
+            statistics: Default::default(),
         }
     }
 }
@@ -114,6 +163,7 @@ impl<S: Clone> Clone for Cache<S> {
             new_object_cache: self.new_object_cache.clone(),
             pack_cache: self.new_pack_cache.as_ref().map(|create| RefCell::new(create())),
             object_cache: self.new_object_cache.as_ref().map(|create| RefCell::new(create())),
+            statistics: Default::default(),
         }
     }
 }
@@ -133,14 +183,39 @@ impl<S> DerefMut for Cache<S> {
 }
 
 mod impls {
-    use std::{cell::RefCell, io::Read, ops::DerefMut};
+    use std::{
+        cell::{Cell, RefCell},
+        io::Read,
+        ops::DerefMut,
+    };
 
     use gix_hash::{oid, ObjectId};
     use gix_object::{Data, Kind};
-    use gix_pack::cache::Object;
+    use gix_pack::cache::{DecodeEntry, Object};
 
+    use super::Statistics;
     use crate::{find::Header, pack::data::entry::Location, Cache};
 
+    /// A pack cache which records its efficiency in `statistics`.
+    struct RecordPackCacheEfficiency<'a> {
+        inner: &'a mut dyn DecodeEntry,
+        statistics: &'a Cell<Statistics>,
+    }
+
+    impl DecodeEntry for RecordPackCacheEfficiency<'_> {
+        fn put(&mut self, pack_id: u32, offset: u64, data: &[u8], kind: Kind, compressed_size: usize) {
+            self.inner.put(pack_id, offset, data, kind, compressed_size)
+        }
+
+        fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(Kind, usize)> {
+            let res = self.inner.get(pack_id, offset, out);
+            let mut statistics = self.statistics.get();
+            statistics.pack_cache.record(res.is_some());
+            self.statistics.set(statistics);
+            res
+        }
+    }
+
     impl<S> crate::Write for Cache<S>
     where
         S: crate::Write,
@@ -200,7 +275,14 @@ mod impls {
             buffer: &'a mut Vec<u8>,
         ) -> Result<Option<(Data<'a>, Option<Location>)>, gix_object::find::Error> {
             match self.pack_cache.as_ref().map(RefCell::borrow_mut) {
-                Some(mut pack_cache) => self.try_find_cached(id, buffer, pack_cache.deref_mut()),
+                Some(mut pack_cache) => self.try_find_cached(
+                    id,
+                    buffer,
+                    &mut RecordPackCacheEfficiency {
+                        inner: pack_cache.deref_mut(),
+                        statistics: &self.statistics,
+                    },
+                ),
                 None => self.try_find_cached(id, buffer, &mut gix_pack::cache::Never),
             }
         }
@@ -212,7 +294,11 @@ mod impls {
             pack_cache: &mut dyn gix_pack::cache::DecodeEntry,
         ) -> Result<Option<(Data<'a>, Option<gix_pack::data::entry::Location>)>, gix_object::find::Error> {
             if let Some(mut obj_cache) = self.object_cache.as_ref().map(RefCell::borrow_mut) {
-                if let Some(kind) = obj_cache.get(&id.as_ref().to_owned(), buffer) {
+                let kind = obj_cache.get(&id.as_ref().to_owned(), buffer);
+                let mut statistics = self.statistics.get();
+                statistics.object_cache.record(kind.is_some());
+                self.statistics.set(statistics);
+                if let Some(kind) = kind {
                     return Ok(Some((Data::new(kind, buffer), None)));
                 }
             }
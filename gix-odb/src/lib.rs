@@ -44,11 +44,15 @@ pub struct Cache<S> {
     new_object_cache: Option<Arc<cache::NewObjectCacheFn>>,
     pack_cache: Option<RefCell<Box<cache::PackCache>>>,
     object_cache: Option<RefCell<Box<cache::ObjectCache>>>,
+    statistics: std::cell::Cell<cache::Statistics>,
 }
 
 ///
 pub mod cache;
 
+///
+pub mod memory;
+
 ///
 /// It can optionally compress the content, similarly to what would happen when using a [`loose::Store`].
 ///
@@ -114,6 +118,9 @@ pub struct Store {
     /// Keeping it here helps to assure consistency even while a process changes its CWD.
     pub(crate) current_dir: PathBuf,
 
+    /// Additional object directories to use as alternates, made absolute using the `current_dir`.
+    pub(crate) alternates: Vec<PathBuf>,
+
     /// A set of replacements that given a source OID return a destination OID. The vector is sorted.
     pub(crate) replacements: Vec<(gix_hash::ObjectId, gix_hash::ObjectId)>,
 
@@ -137,6 +144,8 @@ pub struct Store {
     use_multi_pack_index: bool,
     /// The hash kind to use for some operations
     object_hash: gix_hash::Kind,
+    /// The zlib compression level to use when writing loose objects.
+    loose_compression_level: u32,
 }
 
 /// Create a new cached handle to the object store with support for additional options.
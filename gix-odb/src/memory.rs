@@ -0,0 +1,236 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::Read,
+    ops::{Deref, DerefMut},
+};
+
+use gix_hash::{oid, ObjectId};
+use gix_object::{Data, Kind};
+
+use crate::{find::Header, pack::data::entry::Location};
+
+/// The objects held in memory by a [`Proxy`], keyed by their id, along with their kind and data.
+pub type Storage = BTreeMap<ObjectId, (Kind, Vec<u8>)>;
+
+/// An object database which can keep written objects in memory instead of writing them to the object database it wraps.
+///
+/// Objects in memory are always looked up first, so written objects can be read back as if they were on disk.
+/// This makes it possible to simulate operations that write objects, like merges, without changing the object database.
+///
+/// By default, no memory is used and all operations are forwarded to the wrapped object database.
+pub struct Proxy<T> {
+    /// The object database we forward to.
+    ///
+    /// For calling methods on `inner`, prefer to make use of auto-dereferencing, i.e. `proxy.inner_method()` instead of `proxy.inner.inner_method()`.
+    inner: T,
+    memory: Option<RefCell<Storage>>,
+    object_hash: gix_hash::Kind,
+}
+
+impl<T> Proxy<T> {
+    /// Create a new instance which forwards all operations to `odb`, and uses `object_hash` to compute the ids
+    /// of objects written to memory.
+    pub fn new(odb: T, object_hash: gix_hash::Kind) -> Self {
+        Proxy {
+            inner: odb,
+            memory: None,
+            object_hash,
+        }
+    }
+
+    /// Keep all objects written from now on in memory.
+    pub fn with_memory(mut self) -> Self {
+        self.enable_memory();
+        self
+    }
+
+    /// Keep all objects written from now on in memory, or do nothing if memory is already enabled.
+    pub fn enable_memory(&mut self) {
+        if self.memory.is_none() {
+            self.memory = Some(Default::default());
+        }
+    }
+
+    /// Set the in-memory `storage` to use for written objects, or disable memory if `None`, and return the previous storage.
+    pub fn set_memory(&mut self, storage: Option<Storage>) -> Option<Storage> {
+        std::mem::replace(&mut self.memory, storage.map(RefCell::new)).map(RefCell::into_inner)
+    }
+
+    /// Disable memory and return all objects that were kept in it, or `None` if memory wasn't enabled.
+    pub fn take_memory(&mut self) -> Option<Storage> {
+        self.set_memory(None)
+    }
+
+    /// Return `true` if written objects are kept in memory.
+    pub fn has_memory(&self) -> bool {
+        self.memory.is_some()
+    }
+
+    /// Return the amount of objects currently kept in memory.
+    pub fn num_objects_in_memory(&self) -> usize {
+        self.memory.as_ref().map_or(0, |memory| memory.borrow().len())
+    }
+
+    /// Return the object database we wrap.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn find_in_memory<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Option<Data<'a>> {
+        let memory = self.memory.as_ref()?.borrow();
+        let (kind, data) = memory.get(id)?;
+        buffer.clear();
+        buffer.extend_from_slice(data);
+        Some(Data::new(*kind, buffer))
+    }
+
+    fn header_in_memory(&self, id: &oid) -> Option<(u64, Kind)> {
+        let memory = self.memory.as_ref()?.borrow();
+        memory.get(id).map(|(kind, data)| (data.len() as u64, *kind))
+    }
+
+    fn contains_in_memory(&self, id: &oid) -> bool {
+        self.memory
+            .as_ref()
+            .map_or(false, |memory| memory.borrow().contains_key(id))
+    }
+}
+
+impl Proxy<crate::Handle> {
+    /// Convert this instance into one that is thread-safe, keeping a copy of all objects in memory.
+    pub fn into_arc(self) -> std::io::Result<Proxy<crate::HandleArc>> {
+        Ok(Proxy {
+            inner: self.inner.into_arc()?,
+            memory: self.memory,
+            object_hash: self.object_hash,
+        })
+    }
+}
+
+impl<T: Clone> Clone for Proxy<T> {
+    fn clone(&self) -> Self {
+        Proxy {
+            inner: self.inner.clone(),
+            memory: self.memory.clone(),
+            object_hash: self.object_hash,
+        }
+    }
+}
+
+impl<T> Deref for Proxy<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Proxy<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T> crate::Write for Proxy<T>
+where
+    T: crate::Write,
+{
+    fn write_stream(&self, kind: Kind, size: u64, from: &mut dyn Read) -> Result<ObjectId, crate::write::Error> {
+        let Some(memory) = self.memory.as_ref() else {
+            return self.inner.write_stream(kind, size, from);
+        };
+        let mut data = Vec::with_capacity(size as usize);
+        from.take(size).read_to_end(&mut data).map_err(Box::new)?;
+        if data.len() as u64 != size {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("Expected {size} bytes of object data, but got only {}", data.len()),
+            )));
+        }
+        let id = gix_object::compute_hash(self.object_hash, kind, &data);
+        memory.borrow_mut().insert(id, (kind, data));
+        Ok(id)
+    }
+}
+
+impl<T> gix_object::Find for Proxy<T>
+where
+    T: gix_object::Find,
+{
+    fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, gix_object::find::Error> {
+        if self.contains_in_memory(id) {
+            return Ok(self.find_in_memory(id, buffer));
+        }
+        self.inner.try_find(id, buffer)
+    }
+}
+
+impl<T> gix_object::Exists for Proxy<T>
+where
+    T: gix_object::Exists,
+{
+    fn exists(&self, id: &oid) -> bool {
+        self.contains_in_memory(id) || self.inner.exists(id)
+    }
+}
+
+impl<T> crate::Header for Proxy<T>
+where
+    T: crate::Header,
+{
+    fn try_header(&self, id: &oid) -> Result<Option<Header>, gix_object::find::Error> {
+        if let Some(header) = self.header_in_memory(id) {
+            return Ok(Some(header.into()));
+        }
+        self.inner.try_header(id)
+    }
+}
+
+impl<T> gix_object::FindHeader for Proxy<T>
+where
+    T: gix_object::FindHeader,
+{
+    fn try_header(&self, id: &oid) -> Result<Option<gix_object::Header>, gix_object::find::Error> {
+        if let Some((size, kind)) = self.header_in_memory(id) {
+            return Ok(Some(gix_object::Header { kind, size }));
+        }
+        self.inner.try_header(id)
+    }
+}
+
+impl<T> gix_pack::Find for Proxy<T>
+where
+    T: gix_pack::Find,
+{
+    fn contains(&self, id: &oid) -> bool {
+        self.contains_in_memory(id) || self.inner.contains(id)
+    }
+
+    fn try_find_cached<'a>(
+        &self,
+        id: &oid,
+        buffer: &'a mut Vec<u8>,
+        pack_cache: &mut dyn gix_pack::cache::DecodeEntry,
+    ) -> Result<Option<(Data<'a>, Option<Location>)>, gix_object::find::Error> {
+        if self.contains_in_memory(id) {
+            return Ok(self.find_in_memory(id, buffer).map(|data| (data, None)));
+        }
+        self.inner.try_find_cached(id, buffer, pack_cache)
+    }
+
+    fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<Location> {
+        if self.contains_in_memory(id) {
+            return None;
+        }
+        self.inner.location_by_oid(id, buf)
+    }
+
+    fn pack_offsets_and_oid(&self, pack_id: u32) -> Option<Vec<(u64, ObjectId)>> {
+        self.inner.pack_offsets_and_oid(pack_id)
+    }
+
+    fn entry_by_location(&self, location: &Location) -> Option<gix_pack::find::Entry> {
+        self.inner.entry_by_location(location)
+    }
+}
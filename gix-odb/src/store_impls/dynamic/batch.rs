@@ -0,0 +1,103 @@
+use std::ops::Deref;
+
+use gix_hash::{oid, ObjectId};
+
+use super::find::Error;
+use crate::find::Header;
+
+/// Batched lookups
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return `true` for each of `ids` that exists in the object database, in the same order as `ids`.
+    ///
+    /// This is equivalent to calling [`contains()`](gix_pack::Find::contains()) for each id, but faster if many objects are
+    /// queried as all ids are looked up in each pack index at once, in sorted order so successive lookups can share their bounds.
+    /// This makes it useful for connectivity checks or negotiation.
+    ///
+    /// Note that like with `contains()`, errors that occur while loading new indices cause the remaining objects to be reported
+    /// as missing.
+    pub fn contains_many(&self, ids: &[ObjectId]) -> Vec<bool> {
+        let mut snapshot = self.snapshot.borrow_mut();
+        let mut out = vec![false; ids.len()];
+        let mut remaining = sorted_positions(ids);
+        loop {
+            for index in snapshot.indices.iter() {
+                if remaining.is_empty() {
+                    return out;
+                }
+                let sorted_ids: Vec<&oid> = remaining.iter().map(|pos| ids[*pos].as_ref()).collect();
+                let mut locations = index.lookup_many(&sorted_ids).into_iter();
+                remaining.retain(|pos| {
+                    let found = locations.next().flatten().is_some();
+                    out[*pos] = found;
+                    !found
+                });
+            }
+
+            for lodb in snapshot.loose_dbs.iter() {
+                remaining.retain(|pos| {
+                    let found = lodb.contains(&ids[*pos]);
+                    out[*pos] = found;
+                    !found
+                });
+            }
+
+            if remaining.is_empty() {
+                return out;
+            }
+            match self.store.load_one_index(self.refresh, snapshot.marker) {
+                Ok(Some(new_snapshot)) => {
+                    *snapshot = new_snapshot;
+                    self.clear_cache();
+                }
+                Ok(None) => return out, // nothing more to load, or our refresh mode doesn't allow disk refreshes
+                Err(_) => return out,   // like `contains()`, there is no way to communicate this error
+            }
+        }
+    }
+
+    /// Return the header of each of `ids`, or `None` if it doesn't exist, in the same order as `ids`.
+    ///
+    /// This is equivalent to calling [`try_header()`](crate::Header::try_header()) for each id, but faster if many objects are
+    /// queried as all ids are looked up in each pack index at once, and headers are decoded in the order in which they are
+    /// stored in their pack.
+    pub fn headers_many(&self, ids: &[ObjectId]) -> Result<Vec<Option<Header>>, Error> {
+        let mut snapshot = self.snapshot.borrow_mut();
+        let mut inflate = self.inflate.borrow_mut();
+
+        let mut remaining = sorted_positions(ids);
+        let mut packed = Vec::with_capacity(ids.len());
+        for index in snapshot.indices.iter() {
+            if remaining.is_empty() {
+                break;
+            }
+            let sorted_ids: Vec<&oid> = remaining.iter().map(|pos| ids[*pos].as_ref()).collect();
+            let mut locations = index.lookup_many(&sorted_ids).into_iter();
+            remaining.retain(|pos| match locations.next().flatten() {
+                Some(location) => {
+                    packed.push(((location.pack_id.to_intrinsic_pack_id(), location.pack_offset), *pos));
+                    false
+                }
+                None => true,
+            });
+        }
+        packed.sort_unstable_by_key(|(pack_location, _)| *pack_location);
+
+        // Objects that aren't in one of the currently loaded indices are looked up one by one, which also loads new indices
+        // and handles loose objects.
+        let mut out = vec![None; ids.len()];
+        for pos in packed.into_iter().map(|(_, pos)| pos).chain(remaining) {
+            out[pos] = self.try_header_inner(&ids[pos], &mut inflate, &mut snapshot, None)?;
+        }
+        Ok(out)
+    }
+}
+
+/// Return the positions of `ids` in the order of the ids they refer to.
+fn sorted_positions(ids: &[ObjectId]) -> Vec<usize> {
+    let mut positions: Vec<_> = (0..ids.len()).collect();
+    positions.sort_by_key(|pos| &ids[*pos]);
+    positions
+}
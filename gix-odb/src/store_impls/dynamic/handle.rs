@@ -120,6 +120,42 @@ pub(crate) mod index_lookup {
             }
         }
 
+        /// Return the location of each of the `sorted_ids` in its pack, or `None` if it isn't contained in this index.
+        pub(crate) fn lookup_many(&self, sorted_ids: &[&oid]) -> Vec<Option<handle::IndexForObjectInPack>> {
+            let id = self.id;
+            match &self.file {
+                handle::SingleOrMultiIndex::Single { index, .. } => index
+                    .lookup_many(sorted_ids)
+                    .into_iter()
+                    .map(|idx| {
+                        idx.map(|idx| handle::IndexForObjectInPack {
+                            pack_id: types::PackId {
+                                index: id,
+                                multipack_index: None,
+                            },
+                            pack_offset: index.pack_offset_at_index(idx),
+                        })
+                    })
+                    .collect(),
+                handle::SingleOrMultiIndex::Multi { index, .. } => index
+                    .lookup_many(sorted_ids)
+                    .into_iter()
+                    .map(|idx| {
+                        idx.map(|idx| {
+                            let (pack_index, pack_offset) = index.pack_id_and_pack_offset_at_index(idx);
+                            handle::IndexForObjectInPack {
+                                pack_id: types::PackId {
+                                    index: id,
+                                    multipack_index: Some(pack_index),
+                                },
+                                pack_offset,
+                            }
+                        })
+                    })
+                    .collect(),
+            }
+        }
+
         /// Return true if the given object id exists in this index
         pub(crate) fn oid_at_index(&self, entry_index: u32) -> &gix_hash::oid {
             match &self.file {
@@ -136,6 +172,28 @@ pub(crate) mod index_lookup {
             }
         }
 
+        /// Return `(is_contained, previous, next)` for `id`, where `previous` and `next` are the ids closest to `id`
+        /// in this index which aren't `id` itself.
+        pub(crate) fn neighbors(&self, id: &oid) -> (bool, Option<&oid>, Option<&oid>) {
+            let num_objects = self.num_objects();
+            let (mut lower_bound, mut upper_bound) = (0, num_objects);
+            while lower_bound < upper_bound {
+                let mid = (lower_bound + upper_bound) / 2;
+                if self.oid_at_index(mid) < id {
+                    lower_bound = mid + 1;
+                } else {
+                    upper_bound = mid;
+                }
+            }
+            let is_contained = lower_bound < num_objects && self.oid_at_index(lower_bound) == id;
+            let next = lower_bound + u32::from(is_contained);
+            (
+                is_contained,
+                lower_bound.checked_sub(1).map(|previous| self.oid_at_index(previous)),
+                (next < num_objects).then(|| self.oid_at_index(next)),
+            )
+        }
+
         /// Call `lookup_prefix(…)` on either index or multi-index, and transform matches into an object id.
         pub(crate) fn lookup_prefix(
             &self,
@@ -353,6 +411,8 @@ impl TryFrom<&super::Store> for super::Store {
                 object_hash: Default::default(),
                 use_multi_pack_index: false,
                 current_dir: s.current_dir.clone().into(),
+                loose_compression_level: s.loose_compression_level,
+                alternates: s.alternates.clone(),
             },
         )
     }
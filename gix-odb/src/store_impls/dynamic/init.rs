@@ -19,6 +19,13 @@ pub struct Options {
     /// The current directory of the process at the time of instantiation.
     /// If unset, it will be retrieved using `std::env::current_dir()`.
     pub current_dir: Option<std::path::PathBuf>,
+    /// The zlib compression level to use when writing loose objects, from 0 (none) to 9 (best).
+    pub loose_compression_level: u32,
+    /// Additional object directories to use as alternates, similar to `GIT_ALTERNATE_OBJECT_DIRECTORIES`.
+    ///
+    /// They are searched before the alternates listed in `info/alternates`, and their own alternates are followed as well.
+    /// Relative paths are relative to the `current_dir`.
+    pub alternates: Vec<PathBuf>,
 }
 
 impl Default for Options {
@@ -28,6 +35,8 @@ impl Default for Options {
             object_hash: Default::default(),
             use_multi_pack_index: true,
             current_dir: None,
+            loose_compression_level: gix_features::zlib::stream::deflate::LEVEL_FAST,
+            alternates: Vec::new(),
         }
     }
 }
@@ -77,6 +86,8 @@ impl Store {
             object_hash,
             use_multi_pack_index,
             current_dir,
+            loose_compression_level,
+            alternates,
         }: Options,
     ) -> std::io::Result<Self> {
         let _span = gix_features::trace::detail!("gix_odb::Store::at()");
@@ -90,9 +101,8 @@ impl Store {
         let slot_count = match slots {
             Slots::Given(n) => n as usize,
             Slots::AsNeededByDiskState { multiplier, minimum } => {
-                let mut db_paths = crate::alternate::resolve(objects_dir.clone(), &current_dir)
+                let db_paths = super::db_paths(&objects_dir, &alternates, &current_dir)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-                db_paths.insert(0, objects_dir.clone());
                 let num_slots = super::Store::collect_indices_and_mtime_sorted_by_size(db_paths, None, None)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
                     .len();
@@ -110,6 +120,7 @@ impl Store {
         replacements.sort_by(|a, b| a.0.cmp(&b.0));
 
         Ok(Store {
+            alternates: alternates.into_iter().map(|path| current_dir.join(path)).collect(),
             current_dir,
             write: Default::default(),
             replacements,
@@ -118,6 +129,7 @@ impl Store {
             index: ArcSwap::new(Arc::new(SlotMapIndex::default())),
             use_multi_pack_index,
             object_hash,
+            loose_compression_level,
             num_handles_stable: Default::default(),
             num_handles_unstable: Default::default(),
             num_disk_state_consolidation: Default::default(),
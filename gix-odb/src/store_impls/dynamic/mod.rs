@@ -1,5 +1,9 @@
 //! The standard object store which should fit all needs.
-use std::{cell::RefCell, ops::Deref};
+use std::{
+    cell::RefCell,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
 
 use gix_features::zlib;
 
@@ -58,6 +62,8 @@ pub mod prefix;
 
 mod header;
 
+mod batch;
+
 ///
 pub mod iter;
 
@@ -86,3 +92,31 @@ mod access;
 
 ///
 pub mod structure;
+
+///
+pub mod statistics;
+
+/// Return the paths of all object databases, starting with `objects_dir` itself, followed by the `extra_alternates` along with
+/// their alternates, and finally the alternates of `objects_dir`.
+fn db_paths(
+    objects_dir: &Path,
+    extra_alternates: &[PathBuf],
+    current_dir: &Path,
+) -> Result<Vec<PathBuf>, crate::alternate::Error> {
+    let mut out = vec![objects_dir.to_owned()];
+    for alternate in extra_alternates {
+        let alternate = current_dir.join(alternate);
+        let alternates = crate::alternate::resolve(alternate.clone(), current_dir)?;
+        for path in std::iter::once(alternate).chain(alternates) {
+            if !out.contains(&path) {
+                out.push(path);
+            }
+        }
+    }
+    for path in crate::alternate::resolve(objects_dir.to_owned(), current_dir)? {
+        if !out.contains(&path) {
+            out.push(path);
+        }
+    }
+    Ok(out)
+}
@@ -1,8 +1,14 @@
-use std::{collections::HashSet, ops::Deref};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    ops::Deref,
+};
 
 use gix_object::Exists;
 
-use crate::store::{load_index, Handle};
+use crate::{
+    loose,
+    store::{load_index, Handle},
+};
 
 ///
 pub mod lookup {
@@ -100,6 +106,8 @@ where
     /// Given a prefix `candidate` with an object id and an initial `hex_len`, check if it only matches a single
     /// object within the entire object database and increment its `hex_len` by one until it is unambiguous.
     /// Return `Ok(None)` if no object with that prefix exists.
+    ///
+    /// Use [`disambiguate_prefixes()`](Self::disambiguate_prefixes()) if more than a few ids are to be shortened.
     pub fn disambiguate_prefix(
         &self,
         mut candidate: disambiguate::Candidate,
@@ -123,6 +131,62 @@ where
         Ok(Some(candidate.to_prefix()))
     }
 
+    /// Like [`disambiguate_prefix()`](Self::disambiguate_prefix()), but compute the shortest unambiguous prefix for each of
+    /// `candidates` that is at least as long as its `hex_len`, and return them in order, or `None` if the respective object
+    /// doesn't exist.
+    ///
+    /// Instead of looking up increasingly longer prefixes, each id is compared to its closest neighbours in all indices
+    /// and loose object directories, with each loose object directory read only once.
+    /// This makes it suitable for shortening the ids of many objects, for instance when printing logs.
+    ///
+    /// ### Performance Note
+    ///
+    /// All indices will be loaded.
+    pub fn disambiguate_prefixes(
+        &self,
+        candidates: impl IntoIterator<Item = disambiguate::Candidate>,
+    ) -> Result<Vec<Option<gix_hash::Prefix>>, disambiguate::Error> {
+        *self.snapshot.borrow_mut() = self.store.load_all_indices().map_err(lookup::Error::from)?;
+        let snapshot = self.snapshot.borrow();
+        let mut loose_directories = HashMap::<(usize, u8), Vec<gix_hash::ObjectId>>::new();
+        candidates
+            .into_iter()
+            .map(|candidate| -> Result<_, disambiguate::Error> {
+                let id = candidate.id();
+                let mut hex_len = candidate.hex_len();
+                let mut exists = false;
+                let mut disambiguate_from = |other: &gix_hash::oid| {
+                    hex_len = hex_len.max(common_hex_len(id, other) + 1);
+                };
+
+                for index in &snapshot.indices {
+                    let (is_contained, previous, next) = index.neighbors(id);
+                    exists |= is_contained;
+                    previous.into_iter().chain(next).for_each(&mut disambiguate_from);
+                }
+                for (db_index, lodb) in snapshot.loose_dbs.iter().enumerate() {
+                    let ids = match loose_directories.entry((db_index, id.first_byte())) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => {
+                            entry.insert(ids_in_fan_out_directory(lodb, id.first_byte()).map_err(lookup::Error::from)?)
+                        }
+                    };
+                    for other in ids.iter() {
+                        if other.as_ref() == id {
+                            exists = true;
+                        } else {
+                            disambiguate_from(other);
+                        }
+                    }
+                }
+                Ok(exists.then(|| {
+                    gix_hash::Prefix::new(id, hex_len.min(id.kind().len_in_hex()))
+                        .expect("BUG: hex-len is always within bounds")
+                }))
+            })
+            .collect()
+    }
+
     /// Find the only object matching `prefix` and return it as `Ok(Some(Ok(<ObjectId>)))`, or return `Ok(Some(Err(()))`
     /// if multiple different objects with the same prefix were found.
     ///
@@ -194,3 +258,31 @@ where
         }
     }
 }
+
+/// Return the amount of hex characters that `a` and `b` have in common.
+fn common_hex_len(a: &gix_hash::oid, b: &gix_hash::oid) -> usize {
+    a.as_bytes()
+        .iter()
+        .zip(b.as_bytes())
+        .position(|(a, b)| a != b)
+        .map_or(a.kind().len_in_hex(), |pos| {
+            pos * 2 + usize::from((a.as_bytes()[pos] ^ b.as_bytes()[pos]) & 0xf0 == 0)
+        })
+}
+
+fn ids_in_fan_out_directory(
+    lodb: &loose::Store,
+    first_byte: u8,
+) -> Result<Vec<gix_hash::ObjectId>, loose::iter::Error> {
+    let mut out = Vec::new();
+    for id in lodb.iter_fan_out_directory(first_byte) {
+        match id {
+            Ok(id) => out.push(id),
+            Err(err) => match err.io_error() {
+                Some(err) if err.kind() == std::io::ErrorKind::NotFound => break,
+                None | Some(_) => return Err(err),
+            },
+        }
+    }
+    Ok(out)
+}
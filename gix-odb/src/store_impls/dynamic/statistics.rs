@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use crate::{
+    store::{load_index, types::IndexAndPacks, Metrics},
+    Store,
+};
+
+/// Statistics about a loose object database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LooseObjectDatabase {
+    /// The root of the object database.
+    pub objects_directory: PathBuf,
+    /// The total amount of object files.
+    pub num_objects: usize,
+    /// The amount of object files in each of the 256 fan-out directories, indexed by the first byte of the object id.
+    pub num_objects_by_fan_out: Vec<usize>,
+}
+
+/// The amount of bytes of files that are currently memory-mapped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MappedBytes {
+    /// The amount of bytes of mapped pack indices and multi-pack indices.
+    pub indices: u64,
+    /// The amount of bytes of mapped pack data files.
+    pub packs: u64,
+}
+
+/// A structured report about the state of an object database, see [`Store::statistics()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// The amount of packs that are reachable through indices or multi-pack indices.
+    pub num_packs: usize,
+    /// The amount of reachable pack indices, not counting multi-pack indices.
+    pub num_indices: usize,
+    /// The amount of reachable multi-pack indices.
+    ///
+    /// There can be more than one if alternates are used.
+    pub num_multi_pack_indices: usize,
+    /// All loose object databases, with the first one being the one of this store, followed by all alternates.
+    pub loose_object_databases: Vec<LooseObjectDatabase>,
+    /// The `objects` directories of all alternates in the order in which they are searched for objects.
+    pub alternates: Vec<PathBuf>,
+    /// The amount of bytes that are currently mapped into memory for reachable and unreachable files alike.
+    pub mapped_bytes: MappedBytes,
+    /// A snapshot of the store's resource usage.
+    pub metrics: Metrics,
+}
+
+impl Statistics {
+    /// Return `true` if at least one multi-pack index is used to find objects.
+    pub fn has_multi_pack_index(&self) -> bool {
+        self.num_multi_pack_indices != 0
+    }
+}
+
+impl Store {
+    /// Return a report about all files known to us, their memory usage as well as the alternates chain.
+    ///
+    /// Note that this call is expensive as it traverses all loose object databases, and that as with [`metrics()`](Self::metrics()),
+    /// the result is collected in a racy fashion and might not reflect the state of the store on disk.
+    pub fn statistics(&self) -> Result<Statistics, load_index::Error> {
+        let _span = gix_features::trace::detail!("gix_odb::Store::statistics()");
+        let index = self.index.load();
+        if !index.is_initialized() {
+            self.consolidate_with_disk_state(true, false /*load one new index*/)?;
+        }
+        let index = self.index.load();
+
+        let loose_object_databases: Vec<_> = index
+            .loose_dbs
+            .iter()
+            .map(|db| {
+                let mut num_objects_by_fan_out = vec![0; 256];
+                for id in db.iter().filter_map(Result::ok) {
+                    num_objects_by_fan_out[id.as_slice()[0] as usize] += 1;
+                }
+                LooseObjectDatabase {
+                    objects_directory: db.path.clone(),
+                    num_objects: num_objects_by_fan_out.iter().sum(),
+                    num_objects_by_fan_out,
+                }
+            })
+            .collect();
+
+        let mut num_packs = 0;
+        let mut num_indices = 0;
+        let mut num_multi_pack_indices = 0;
+        for slot in index.slot_indices.iter().map(|idx| &self.files[*idx]) {
+            match &**slot.files.load() {
+                Some(IndexAndPacks::Index(_)) => {
+                    num_indices += 1;
+                    num_packs += 1;
+                }
+                Some(IndexAndPacks::MultiIndex(multi)) => {
+                    num_multi_pack_indices += 1;
+                    num_packs += multi.data.len();
+                }
+                None => {}
+            }
+        }
+
+        let mut mapped_bytes = MappedBytes::default();
+        for slot in &self.files {
+            match &**slot.files.load() {
+                Some(IndexAndPacks::Index(bundle)) => {
+                    mapped_bytes.indices += bundle.index.loaded().map_or(0, |index| index.data_len() as u64);
+                    mapped_bytes.packs += bundle.data.loaded().map_or(0, |pack| pack.data_len() as u64);
+                }
+                Some(IndexAndPacks::MultiIndex(multi)) => {
+                    mapped_bytes.indices += multi.multi_index.loaded().map_or(0, |index| index.data_len() as u64);
+                    mapped_bytes.packs += multi
+                        .data
+                        .iter()
+                        .filter_map(|pack| pack.loaded())
+                        .map(|pack| pack.data_len() as u64)
+                        .sum::<u64>();
+                }
+                None => {}
+            }
+        }
+
+        Ok(Statistics {
+            num_packs,
+            num_indices,
+            num_multi_pack_indices,
+            alternates: loose_object_databases
+                .iter()
+                .skip(1 /* the first loose database is always our own */)
+                .map(|db| db.objects_directory.clone())
+                .collect(),
+            loose_object_databases,
+            mapped_bytes,
+            metrics: self.metrics(),
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use gix_features::progress::DynNestedProgress;
+
+use crate::{loose::Store, Write};
+
+///
+pub mod pack {
+    /// The error returned by [`explode_pack()`][super::Store::explode_pack()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not create a temporary directory to receive the pack in")]
+        TempDir(#[source] std::io::Error),
+        #[error(transparent)]
+        WritePack(#[from] gix_pack::bundle::write::Error),
+        #[error(transparent)]
+        OpenPack(#[from] gix_pack::bundle::init::Error),
+        #[error(transparent)]
+        Traverse(#[from] gix_pack::index::traverse::Error<WriteObjectError>),
+    }
+
+    /// The error returned if an object of the pack couldn't be written as loose object.
+    #[derive(Debug, thiserror::Error)]
+    #[error("Failed to write {kind} object {id}")]
+    pub struct WriteObjectError {
+        /// The error that occurred when writing.
+        pub source: crate::write::Error,
+        /// The kind of the object.
+        pub kind: gix_object::Kind,
+        /// The id of the object.
+        pub id: gix_hash::ObjectId,
+    }
+
+    /// Configuration for [`explode_pack()`][super::Store::explode_pack()].
+    #[derive(Debug, Clone, Copy)]
+    pub struct Options {
+        /// The amount of threads to use at most when resolving the pack. If `None`, all logical cores are used.
+        pub thread_limit: Option<usize>,
+        /// Determine how much processing to spend on protecting against corruption or recovering from errors.
+        pub iteration_mode: gix_pack::data::input::Mode,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                thread_limit: None,
+                iteration_mode: gix_pack::data::input::Mode::Verify,
+            }
+        }
+    }
+
+    /// The outcome of [`explode_pack()`][super::Store::explode_pack()].
+    #[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Outcome {
+        /// The outcome of resolving the received pack. Its paths are unset as the pack was removed after exploding it.
+        pub pack: gix_pack::bundle::write::Outcome,
+        /// The amount of objects that were written as loose objects.
+        pub num_written: usize,
+        /// The amount of objects that were skipped as they were excluded.
+        pub num_excluded: usize,
+    }
+}
+
+impl Store {
+    /// Read a pack from `pack`, as it is received over the wire, and write all of its objects as loose objects into this
+    /// store, similar to `git unpack-objects`.
+    ///
+    /// Objects for which `exclude(id)` returns `true`, typically because they are already present in the object database,
+    /// are skipped. It's called once per object before any object is written. Thin packs are supported if
+    /// `thin_pack_base_object_lookup` is set to find their base objects.
+    ///
+    /// The pack is resolved in a temporary directory within this store which is removed when done, even if the operation
+    /// fails or is interrupted. Loose objects written until then remain.
+    pub fn explode_pack(
+        &self,
+        pack: &mut dyn std::io::BufRead,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        thin_pack_base_object_lookup: Option<impl gix_object::Find>,
+        exclude: &mut dyn FnMut(&gix_hash::oid) -> bool,
+        options: pack::Options,
+    ) -> Result<pack::Outcome, pack::Error> {
+        let _span = gix_features::trace::coarse!("gix_odb::loose::Store::explode_pack()");
+        let incoming = tempfile::Builder::new()
+            .prefix("incoming-")
+            .tempdir_in(&self.path)
+            .map_err(pack::Error::TempDir)?;
+        let mut written = gix_pack::Bundle::write_to_directory(
+            pack,
+            Some(incoming.path()),
+            progress,
+            should_interrupt,
+            thin_pack_base_object_lookup,
+            gix_pack::bundle::write::Options {
+                thread_limit: options.thread_limit,
+                iteration_mode: options.iteration_mode,
+                index_version: Default::default(),
+                object_hash: self.object_hash,
+                resolve_memory_limit: None,
+            },
+        )?;
+        written.data_path = None;
+        written.keep_path = None;
+        let index_path = written
+            .index_path
+            .take()
+            .expect("an index is always written if a directory is given");
+        let bundle = gix_pack::Bundle::at(index_path, self.object_hash)?;
+
+        // The index yields objects sorted by id, which allows to look them up quickly when resolving objects in parallel.
+        let excluded: Vec<_> = bundle
+            .index
+            .iter()
+            .map(|entry| entry.oid)
+            .filter(|id| exclude(id))
+            .collect();
+        let num_written = AtomicUsize::default();
+        bundle.index.traverse(
+            &bundle.pack,
+            progress,
+            should_interrupt,
+            |kind, data, entry, _progress| {
+                if excluded.binary_search(&entry.oid).is_ok() {
+                    return Ok(());
+                }
+                self.write_buf(kind, data).map_err(|source| pack::WriteObjectError {
+                    source,
+                    kind,
+                    id: entry.oid,
+                })?;
+                num_written.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            },
+            gix_pack::index::traverse::Options {
+                traversal: Default::default(),
+                thread_limit: options.thread_limit,
+                check: gix_pack::index::traverse::SafetyCheck::SkipFileChecksumVerification,
+                make_pack_lookup_cache: || gix_pack::cache::Never,
+            },
+        )?;
+
+        Ok(pack::Outcome {
+            pack: written,
+            num_written: num_written.into_inner(),
+            num_excluded: excluded.len(),
+        })
+    }
+}
@@ -52,17 +52,7 @@ impl Store {
         prefix: gix_hash::Prefix,
         mut candidates: Option<&mut HashSet<gix_hash::ObjectId>>,
     ) -> Result<Option<crate::store::prefix::lookup::Outcome>, crate::loose::iter::Error> {
-        let single_directory_iter = crate::loose::Iter {
-            inner: gix_features::fs::walkdir_new(
-                &self.path.join(prefix.as_oid().to_hex_with_len(2).to_string()),
-                gix_features::fs::walkdir::Parallelism::Serial,
-            )
-            .min_depth(1)
-            .max_depth(1)
-            .follow_links(false)
-            .into_iter(),
-            hash_hex_len: prefix.as_oid().kind().len_in_hex(),
-        };
+        let single_directory_iter = self.iter_fan_out_directory(prefix.as_oid().first_byte());
         let mut candidate = None;
         for oid in single_directory_iter {
             let oid = match oid {
@@ -78,4 +78,18 @@ impl loose::Store {
             hash_hex_len: self.object_hash.len_in_hex(),
         }
     }
+    /// Return an iterator over all objects whose id starts with `first_byte`, as they are stored in the same fan-out directory.
+    pub(crate) fn iter_fan_out_directory(&self, first_byte: u8) -> loose::Iter {
+        loose::Iter {
+            inner: fs::walkdir_new(
+                &self.path.join(format!("{first_byte:02x}")),
+                fs::walkdir::Parallelism::Serial,
+            )
+            .min_depth(1)
+            .max_depth(1)
+            .follow_links(false)
+            .into_iter(),
+            hash_hex_len: self.object_hash.len_in_hex(),
+        }
+    }
 }
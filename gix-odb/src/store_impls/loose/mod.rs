@@ -12,6 +12,8 @@ pub struct Store {
     pub(crate) path: PathBuf,
     /// The kind of hash we should assume during iteration and when writing new objects.
     pub(crate) object_hash: gix_hash::Kind,
+    /// The zlib compression level to use when writing new objects, from 0 (none) to 9 (best).
+    pub(crate) compression_level: u32,
 }
 
 /// Initialization
@@ -26,9 +28,16 @@ impl Store {
         Store {
             path: objects_directory.into(),
             object_hash,
+            compression_level: gix_features::zlib::stream::deflate::LEVEL_FAST,
         }
     }
 
+    /// Use `level` as zlib compression level when writing new objects, from 0 (none) to 9 (best), with higher levels being capped.
+    pub fn with_compression_level(mut self, level: u32) -> Self {
+        self.compression_level = level;
+        self
+    }
+
     /// Return the path to our `objects` directory.
     pub fn path(&self) -> &Path {
         &self.path
@@ -38,6 +47,11 @@ impl Store {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+
+    /// Return the zlib compression level used when writing new objects.
+    pub fn compression_level(&self) -> u32 {
+        self.compression_level
+    }
 }
 
 fn hash_path(id: &gix_hash::oid, mut root: PathBuf) -> PathBuf {
@@ -49,6 +63,8 @@ fn hash_path(id: &gix_hash::oid, mut root: PathBuf) -> PathBuf {
     root
 }
 
+///
+pub mod explode;
 ///
 pub mod find;
 ///
@@ -108,11 +108,14 @@ impl Store {
 impl Store {
     fn dest(&self) -> Result<hash::Write<CompressedTempfile>, Error> {
         Ok(hash::Write::new(
-            deflate::Write::new(NamedTempFile::new_in(&self.path).map_err(|err| Error::Io {
-                source: err,
-                message: "create named temp file in",
-                path: self.path.to_owned(),
-            })?),
+            deflate::Write::new_with_level(
+                NamedTempFile::new_in(&self.path).map_err(|err| Error::Io {
+                    source: err,
+                    message: "create named temp file in",
+                    path: self.path.to_owned(),
+                })?,
+                self.compression_level,
+            ),
             self.object_hash,
         ))
     }
@@ -100,3 +100,134 @@ fn no_alternate_in_first_objects_dir() -> crate::Result {
     assert!(alternate::resolve(tmp.path().to_owned(), &std::env::current_dir()?)?.is_empty());
     Ok(())
 }
+
+mod edit {
+    use gix_odb::alternate::{self, edit::PathKind};
+
+    #[test]
+    fn add_writes_relative_or_absolute_paths_and_ignores_duplicates() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let cwd = std::env::current_dir()?;
+        let objects = tmp.path().join("repo").join("objects");
+        let (first, second) = (tmp.path().join("first"), tmp.path().join("second"));
+        for dir in [&objects, &first, &second] {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        assert!(alternate::edit::add(&objects, &first, PathKind::Relative, &cwd)?);
+        assert!(alternate::edit::add(&objects, &second, PathKind::Absolute, &cwd)?);
+        assert!(
+            !alternate::edit::add(
+                &objects,
+                &tmp.path().join("repo").join("..").join("first"),
+                PathKind::Absolute,
+                &cwd
+            )?,
+            "alternates are only added once, no matter how they are written"
+        );
+
+        let alternates_file = objects.join("info").join("alternates");
+        let content = std::fs::read_to_string(alternates_file)?;
+        let lines: Vec<_> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0], "../../first",
+            "relative paths are relative to the objects directory"
+        );
+        assert!(std::path::Path::new(lines[1]).is_absolute());
+
+        assert_eq!(
+            alternate::resolve(objects.clone(), &cwd)?
+                .into_iter()
+                .map(std::fs::canonicalize)
+                .collect::<Result<Vec<_>, _>>()?,
+            [second.canonicalize()?, first.canonicalize()?],
+            "what's written can be read back"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn add_refuses_cycles_and_missing_directories() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let cwd = std::env::current_dir()?;
+        let (a, b) = (tmp.path().join("a"), tmp.path().join("b"));
+        for dir in [&a, &b] {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        assert!(matches!(
+            alternate::edit::add(&a, &a, PathKind::Absolute, &cwd),
+            Err(alternate::edit::Error::Cycle { .. })
+        ));
+        assert!(alternate::edit::add(&a, &b, PathKind::Absolute, &cwd)?);
+        assert!(
+            matches!(
+                alternate::edit::add(&b, &a, PathKind::Relative, &cwd),
+                Err(alternate::edit::Error::Cycle { .. })
+            ),
+            "a already uses b, so b can't use a"
+        );
+        assert!(matches!(
+            alternate::edit::add(&a, &tmp.path().join("missing"), PathKind::Absolute, &cwd),
+            Err(alternate::edit::Error::NotADirectory(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn edits_fail_if_the_alternates_file_is_locked() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let cwd = std::env::current_dir()?;
+        let (objects, alternate) = (tmp.path().join("objects"), tmp.path().join("alternate"));
+        for dir in [&objects, &alternate] {
+            std::fs::create_dir_all(dir)?;
+        }
+        assert!(alternate::edit::add(&objects, &alternate, PathKind::Absolute, &cwd)?);
+
+        let lock_path = objects.join("info").join("alternates.lock");
+        std::fs::write(&lock_path, b"")?;
+        assert!(matches!(
+            alternate::edit::remove(&objects, &alternate, &cwd),
+            Err(alternate::edit::Error::Lock(_))
+        ));
+        assert!(
+            objects.join("info").join("alternates").is_file(),
+            "the alternates file is untouched"
+        );
+
+        std::fs::remove_file(lock_path)?;
+        assert!(alternate::edit::remove(&objects, &alternate, &cwd)?);
+        assert!(
+            !objects.join("info").join("alternates.lock").exists(),
+            "locks are released after editing"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn remove_keeps_other_lines_and_deletes_empty_files() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let cwd = std::env::current_dir()?;
+        let objects = tmp.path().join("objects");
+        let (first, second) = (tmp.path().join("first"), tmp.path().join("second"));
+        for dir in [&objects, &first, &second] {
+            std::fs::create_dir_all(dir)?;
+        }
+        let alternates_file = objects.join("info").join("alternates");
+        std::fs::create_dir_all(objects.join("info"))?;
+        std::fs::write(&alternates_file, "# comment\n../first\n../second\n")?;
+
+        assert!(alternate::edit::remove(&objects, &first, &cwd)?);
+        assert!(
+            !alternate::edit::remove(&objects, &first, &cwd)?,
+            "nothing changes if the alternate isn't present"
+        );
+        assert_eq!(std::fs::read_to_string(&alternates_file)?, "# comment\n../second\n");
+
+        std::fs::write(&alternates_file, "../second\n")?;
+        assert!(alternate::edit::remove(&objects, &second, &cwd)?);
+        assert!(!alternates_file.exists(), "empty files are removed");
+        Ok(())
+    }
+}
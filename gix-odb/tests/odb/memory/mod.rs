@@ -0,0 +1,35 @@
+use gix_object::{Exists, FindExt};
+use gix_odb::{HeaderExt, Write};
+
+use crate::{hex_to_id, odb::db};
+
+#[test]
+fn objects_are_kept_in_memory_and_found_before_the_object_database() -> crate::Result {
+    let mut odb = gix_odb::memory::Proxy::new(db(), gix_hash::Kind::Sha1).with_memory();
+    let existing = hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980");
+    assert!(odb.exists(&existing), "objects on disk are found through the proxy");
+
+    let id = odb.write_buf(gix_object::Kind::Blob, b"hello")?;
+    assert_eq!(id, hex_to_id("b6fc4c620b67d95f953a5c1c1230aaab5db5a1b0"));
+    assert_eq!(odb.num_objects_in_memory(), 1);
+    assert!(odb.exists(&id));
+    assert_eq!(odb.header(id)?.size(), 5);
+    let mut buf = Vec::new();
+    assert_eq!(odb.find_blob(&id, &mut buf)?.data, b"hello");
+    assert!(!db().exists(&id), "the object wasn't written to disk");
+
+    let memory = odb.take_memory().expect("memory was enabled");
+    assert_eq!(memory.len(), 1);
+    assert!(!odb.has_memory());
+    assert!(!odb.exists(&id), "without memory, the object isn't visible anymore");
+    Ok(())
+}
+
+#[test]
+fn without_memory_all_operations_are_forwarded() -> crate::Result {
+    let odb = gix_odb::memory::Proxy::new(gix_odb::sink(gix_hash::Kind::Sha1), gix_hash::Kind::Sha1);
+    let id = odb.write_buf(gix_object::Kind::Blob, b"hello")?;
+    assert_eq!(id, hex_to_id("b6fc4c620b67d95f953a5c1c1230aaab5db5a1b0"));
+    assert_eq!(odb.num_objects_in_memory(), 0);
+    Ok(())
+}
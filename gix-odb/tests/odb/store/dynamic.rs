@@ -241,6 +241,124 @@ fn alternate_dbs_query() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn statistics() -> crate::Result {
+    let (handle, _tmp) = db_with_all_object_sources()?;
+    let stats = handle.store_ref().statistics()?;
+    assert_eq!(stats.num_indices, 1, "one pack isn't part of the multi-pack index");
+    assert_eq!(stats.num_multi_pack_indices, 1);
+    assert!(stats.has_multi_pack_index());
+    assert_eq!(stats.num_packs, 3);
+    assert_eq!(stats.alternates.len(), 0, "there are no alternates");
+    assert_eq!(stats.loose_object_databases.len(), 1);
+    let loose = &stats.loose_object_databases[0];
+    assert_eq!(loose.objects_directory, handle.store_ref().path());
+    assert_eq!(loose.num_objects, 7);
+    assert_eq!(loose.num_objects_by_fan_out.len(), 256);
+    assert_eq!(loose.num_objects_by_fan_out[0x37], 1);
+    assert_eq!(loose.num_objects_by_fan_out[0x00], 0);
+    assert_eq!(stats.mapped_bytes.packs, 0, "nothing was looked up yet");
+    assert_eq!(stats.metrics, handle.store_ref().metrics());
+
+    let mut buf = Vec::new();
+    for id in handle.iter()? {
+        handle.find(&id?, &mut buf)?;
+    }
+    let stats = handle.store_ref().statistics()?;
+    assert!(stats.mapped_bytes.indices > 0, "indices are mapped after lookups");
+    assert!(stats.mapped_bytes.packs > 0, "packs are mapped after lookups");
+
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_alternates_odb.sh")?;
+    let handle = gix_odb::at(dir.join(".git/objects"))?;
+    let stats = handle.store_ref().statistics()?;
+    assert_eq!(stats.alternates, handle.store_ref().alternate_db_paths()?);
+    assert_eq!(
+        stats.loose_object_databases.len(),
+        2,
+        "our own loose objects and the alternate"
+    );
+    assert_eq!(stats.loose_object_databases[1].objects_directory, stats.alternates[0]);
+    Ok(())
+}
+
+#[test]
+fn cache_statistics() -> crate::Result {
+    #[derive(Default)]
+    struct ObjectCache(std::collections::HashMap<ObjectId, (gix_object::Kind, Vec<u8>)>);
+
+    impl gix_pack::cache::Object for ObjectCache {
+        fn put(&mut self, id: ObjectId, kind: gix_object::Kind, data: &[u8]) {
+            self.0.insert(id, (kind, data.to_owned()));
+        }
+
+        fn get(&mut self, id: &ObjectId, out: &mut Vec<u8>) -> Option<gix_object::Kind> {
+            self.0.get(id).map(|(kind, data)| {
+                out.clear();
+                out.extend_from_slice(data);
+                *kind
+            })
+        }
+    }
+
+    let handle = db().with_object_cache(|| Box::<ObjectCache>::default());
+    assert_eq!(handle.statistics(), gix_odb::cache::Statistics::default());
+    assert_eq!(
+        handle.statistics().object_cache.hit_rate(),
+        None,
+        "nothing was looked up"
+    );
+
+    let id = hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980");
+    let mut buf = Vec::new();
+    handle.find(&id, &mut buf)?;
+    handle.find(&id, &mut buf)?;
+    handle.find(&id, &mut buf)?;
+    let stats = handle.statistics();
+    assert_eq!(
+        stats.object_cache,
+        gix_odb::cache::Efficiency { hits: 2, misses: 1 },
+        "only the first lookup has to go to the object database"
+    );
+    assert_eq!(stats.pack_cache, Default::default(), "there is no pack cache");
+    assert_eq!(stats.object_cache.hit_rate(), Some(2.0 / 3.0));
+
+    assert_eq!(
+        handle.clone().statistics(),
+        Default::default(),
+        "clones have their own caches"
+    );
+    assert_eq!(handle.reset_statistics(), stats);
+    assert_eq!(handle.statistics(), Default::default());
+    Ok(())
+}
+
+#[test]
+fn contains_many_and_headers_many() -> crate::Result {
+    let (handle, _tmp) = db_with_all_object_sources()?;
+    let mut ids = handle.iter()?.collect::<Result<Vec<_>, _>>()?;
+    ids.reverse();
+    let missing = hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    ids.insert(ids.len() / 2, missing);
+    ids.push(ids[0]);
+
+    let new_handle = || -> crate::Result<gix_odb::Handle> { Ok(gix_odb::at(handle.store_ref().path())?) };
+    assert_eq!(
+        new_handle()?.contains_many(&ids),
+        ids.iter().map(|id| handle.exists(id)).collect::<Vec<_>>(),
+        "indices are loaded on demand, and the result is in input order"
+    );
+    assert_eq!(
+        new_handle()?.headers_many(&ids)?,
+        ids.iter()
+            .map(|id| handle.try_header(id))
+            .collect::<Result<Vec<_>, _>>()?,
+        "headers are returned in input order, and missing objects are None"
+    );
+    assert_eq!(handle.contains_many(&[]), Vec::<bool>::new());
+    assert_eq!(handle.headers_many(&[missing])?, vec![None]);
+    Ok(())
+}
+
 #[test]
 fn object_replacement() -> crate::Result {
     let dir = gix_testtools::scripted_fixture_read_only_standalone("make_replaced_history.sh")?;
@@ -606,6 +724,45 @@ mod disambiguate_prefix {
         );
     }
 
+    #[test]
+    fn many_at_once_yields_the_same_prefixes_as_one_at_a_time() -> crate::Result {
+        let (handle, _tmp) = db_with_all_object_sources()?;
+        let null = gix_hash::ObjectId::null(gix_hash::Kind::Sha1);
+        let ids: Vec<_> = handle.iter()?.map(Result::unwrap).chain(Some(null)).collect();
+
+        for hex_len in [4, 7, 40] {
+            let candidates: Vec<_> = ids
+                .iter()
+                .map(|id| Candidate::new(*id, hex_len))
+                .collect::<Result<_, _>>()?;
+            let expected = candidates
+                .iter()
+                .map(|candidate| handle.disambiguate_prefix(*candidate))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(handle.disambiguate_prefixes(candidates)?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn many_at_once_returns_disambiguated_prefixes_when_needed() -> crate::Result {
+        let (handle, _tmp) = db_with_all_object_sources()?;
+        assert_eq!(
+            handle
+                .disambiguate_prefixes(Some(Candidate::new(
+                    hex_to_id("a7065b5e971a6d8b55875d8cf634a3a37202ab23"),
+                    4
+                )?))?
+                .pop()
+                .flatten()
+                .map(|prefix| prefix.hex_len()),
+            Some(5),
+            "the hex_len was increased to disambiguate"
+        );
+        assert_all_indices_loaded(&handle, 1, 2);
+        Ok(())
+    }
+
     #[test]
     fn returns_none_if_id_does_not_exist() {
         let (handle, _tmp) = db_with_all_object_sources().unwrap();
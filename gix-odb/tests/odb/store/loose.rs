@@ -131,6 +131,78 @@ mod write {
     }
 }
 
+mod explode_pack {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_features::progress;
+    use gix_odb::loose;
+    use gix_testtools::fixture_path_standalone;
+
+    use crate::hex_to_id;
+
+    fn explode(
+        exclude: &mut dyn FnMut(&gix_hash::oid) -> bool,
+    ) -> crate::Result<(
+        gix_testtools::tempfile::TempDir,
+        loose::Store,
+        loose::explode::pack::Outcome,
+    )> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let mut pack = std::io::BufReader::new(std::fs::File::open(fixture_path_standalone(
+            "objects/pack/pack-a2bf8e71d8c18879e499335762dd95119d93d9f1.pack",
+        ))?);
+        let outcome = db.explode_pack(
+            &mut pack,
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            None::<gix_object::find::Never>,
+            exclude,
+            Default::default(),
+        )?;
+        Ok((dir, db, outcome))
+    }
+
+    #[test]
+    fn all_objects_are_written_as_loose_objects() -> crate::Result {
+        let (_dir, db, outcome) = explode(&mut |_| false)?;
+        assert_eq!(outcome.pack.index.num_objects, 42);
+        assert_eq!(
+            outcome.pack.data_path, None,
+            "the pack isn't kept, so there is nothing to point to"
+        );
+        assert_eq!(
+            (outcome.num_written, outcome.num_excluded),
+            (42, 0),
+            "all objects are written"
+        );
+        assert!(
+            std::fs::read_dir(db.path())?
+                .map(|entry| entry.map(|e| e.file_name()))
+                .collect::<Result<Vec<_>, _>>()?
+                .iter()
+                .all(|name| name.len() == 2),
+            "the temporary directory used to resolve the pack was removed"
+        );
+        let outcome = db.verify_integrity(&mut progress::Discard, &AtomicBool::new(false))?;
+        assert_eq!(outcome.num_objects, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn excluded_objects_are_skipped() -> crate::Result {
+        let excluded = hex_to_id("0d9726f3fd2359cda58217724f12af273bf440a6");
+        let (_dir, db, outcome) = explode(&mut |id| id == excluded)?;
+        assert_eq!(
+            (outcome.num_written, outcome.num_excluded),
+            (41, 1),
+            "the excluded object is skipped"
+        );
+        assert!(!db.contains(&excluded));
+        Ok(())
+    }
+}
+
 mod contains {
     use crate::store::loose::ldb;
 
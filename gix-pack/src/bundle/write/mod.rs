@@ -269,6 +269,7 @@ impl crate::Bundle {
             iteration_mode: _,
             index_version: index_kind,
             object_hash,
+            resolve_memory_limit,
         }: Options,
         data_file: SharedTempFile,
         mut pack_entries_iter: Box<dyn Iterator<Item = Result<data::input::Entry, data::input::Error>> + 'a>,
@@ -279,6 +280,12 @@ impl crate::Bundle {
             "create index file".into(),
             ProgressId::IndexingSteps(Default::default()).into(),
         );
+        let memory_limit = |spill_directory: Option<&Path>| {
+            resolve_memory_limit.map(|max_bytes| crate::cache::delta::traverse::MemoryLimit {
+                max_bytes,
+                spill_directory: spill_directory.map(ToOwned::to_owned),
+            })
+        };
         Ok(match directory {
             Some(directory) => {
                 let directory = directory.as_ref();
@@ -292,6 +299,7 @@ impl crate::Bundle {
                     },
                     &mut pack_entries_iter,
                     thread_limit,
+                    memory_limit(Some(directory)),
                     &mut indexing_progress,
                     &mut index_file,
                     should_interrupt,
@@ -333,6 +341,7 @@ impl crate::Bundle {
                     move || new_pack_file_resolver(data_file),
                     &mut pack_entries_iter,
                     thread_limit,
+                    memory_limit(None),
                     &mut indexing_progress,
                     &mut io::sink(),
                     should_interrupt,
@@ -14,6 +14,11 @@ pub struct Options {
     pub index_version: crate::index::Version,
     /// The kind of hash to use when writing the bundle.
     pub object_hash: gix_hash::Kind,
+    /// If `Some`, the amount of bytes that resolved base objects may occupy in memory while resolving the pack.
+    ///
+    /// Base objects beyond that are written to a temporary file next to the pack, or into the system's temporary
+    /// directory if there is no target directory, which allows to receive very large packs with little memory.
+    pub resolve_memory_limit: Option<usize>,
 }
 
 impl Default for Options {
@@ -24,6 +29,7 @@ impl Default for Options {
             iteration_mode: crate::data::input::Mode::Verify,
             index_version: Default::default(),
             object_hash: Default::default(),
+            resolve_memory_limit: None,
         }
     }
 }
@@ -1,4 +1,7 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use gix_features::{
     parallel::in_parallel_with_slice,
@@ -13,6 +16,7 @@ use crate::{
 };
 
 mod resolve;
+mod spill;
 pub(crate) mod util;
 
 /// Returned by [`Tree::traverse()`]
@@ -39,6 +43,8 @@ pub enum Error {
     },
     #[error("Failed to spawn thread when switching to work-stealing mode")]
     SpawnThread(#[from] std::io::Error),
+    #[error("Failed to write resolved objects exceeding the memory limit to disk, or to read them back")]
+    Spill(#[source] std::io::Error),
 }
 
 /// Additional context passed to the `inspect_object(…)` function of the [`Tree::traverse()`] method.
@@ -68,6 +74,21 @@ pub struct Options<'a, 's> {
     /// specifies what kind of hashes we expect to be stored in oid-delta entries, which is viable to decoding them
     /// with the correct size.
     pub object_hash: gix_hash::Kind,
+    /// If `Some`, limit the memory used to hold resolved base objects until all of their deltas are resolved.
+    pub memory_limit: Option<MemoryLimit>,
+}
+
+/// Limits the memory used to hold resolved base objects, which are needed until all of their deltas are resolved.
+///
+/// Without a limit, packs with many large base objects and wide delta trees can require a lot of memory to resolve.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryLimit {
+    /// The amount of bytes that resolved base objects may occupy in memory, across all threads.
+    ///
+    /// Base objects that would exceed the limit are written to a temporary file and read back when needed.
+    pub max_bytes: usize,
+    /// The directory in which to create the temporary file, or `None` to use the system's temporary directory.
+    pub spill_directory: Option<PathBuf>,
 }
 
 /// The outcome of [`Tree::traverse()`]
@@ -76,6 +97,8 @@ pub struct Outcome<T> {
     pub roots: Vec<Item<T>>,
     /// The items that children to a root object, i.e. delta objects.
     pub children: Vec<Item<T>>,
+    /// The amount of resolved base objects that were written to disk as they exceeded the [memory limit](Options::memory_limit).
+    pub num_spilled_objects: usize,
 }
 
 impl<T> Tree<T>
@@ -111,6 +134,7 @@ where
             size_progress,
             should_interrupt,
             object_hash,
+            memory_limit,
         }: Options<'_, '_>,
     ) -> Result<Outcome<T>, Error>
     where
@@ -134,6 +158,8 @@ where
         let start = std::time::Instant::now();
         let child_items = ItemSliceSync::new(&mut self.child_items);
         let child_items = &child_items;
+        let budget = spill::Budget::new(memory_limit);
+        let budget = &budget;
         in_parallel_with_slice(
             &mut self.root_items,
             thread_limit,
@@ -149,6 +175,7 @@ where
                         resolve: resolve.clone(),
                         modify_base: inspect_object.clone(),
                         child_items,
+                        budget,
                     }
                 }
             },
@@ -176,6 +203,7 @@ where
         Ok(Outcome {
             roots: self.root_items,
             children: self.child_items,
+            num_spilled_objects: budget.num_spilled(),
         })
     }
 }
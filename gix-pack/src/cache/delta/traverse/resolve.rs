@@ -1,13 +1,14 @@
-use std::{
-    collections::BTreeMap,
-    sync::atomic::{AtomicBool, AtomicIsize, Ordering},
-};
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
 
 use gix_features::{progress::Progress, threading, zlib};
 
 use crate::{
     cache::delta::{
-        traverse::{util::ItemSliceSync, Context, Error},
+        traverse::{
+            spill::{Bases, Budget},
+            util::ItemSliceSync,
+            Context, Error,
+        },
         Item,
     },
     data,
@@ -75,6 +76,7 @@ pub(crate) struct State<'items, F, MBFN, T: Send> {
     pub resolve: F,
     pub modify_base: MBFN,
     pub child_items: &'items ItemSliceSync<'items, Item<T>>,
+    pub budget: &'items Budget,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -89,6 +91,7 @@ pub(crate) fn deltas<T, F, MBFN, E, R>(
         resolve,
         modify_base,
         child_items,
+        budget,
     }: &mut State<'_, F, MBFN, T>,
     resolve_data: &R,
     hash_len: usize,
@@ -102,7 +105,7 @@ where
     MBFN: FnMut(&mut T, &dyn Progress, Context<'_>) -> Result<(), E> + Send + Clone,
     E: std::error::Error + Send + Sync + 'static,
 {
-    let mut decompressed_bytes_by_pack_offset = BTreeMap::new();
+    let mut bases = Bases::new(budget);
     let mut inflate = zlib::Inflate::default();
     let mut decompress_from_resolver = |slice: EntryRange, out: &mut Vec<u8>| -> Result<(data::Entry, u64), Error> {
         let bytes = resolve(slice.clone(), resolve_data).ok_or(Error::ResolveFailed {
@@ -131,8 +134,8 @@ where
             let (a, b) = decompress_from_resolver(base.entry_slice(), &mut buf)?;
             (a, b, buf)
         } else {
-            decompressed_bytes_by_pack_offset
-                .remove(&base.offset())
+            bases
+                .remove(base.offset())?
                 .expect("we store the resolved delta buffer when done")
         };
 
@@ -174,10 +177,12 @@ where
             //        at all
             child_entry.header = base_entry.header; // assign the actual object type, instead of 'delta'
             if child.has_children() {
-                decompressed_bytes_by_pack_offset.insert(
+                bases.insert(
                     child.offset(),
-                    (child_entry, entry_end, std::mem::take(fully_resolved_delta_bytes)),
-                );
+                    child_entry,
+                    entry_end,
+                    std::mem::take(fully_resolved_delta_bytes),
+                )?;
                 nodes.push((level + 1, child));
             } else {
                 modify_base(
@@ -211,7 +216,7 @@ where
                 *fully_resolved_delta_bytes = Vec::new();
                 return deltas_mt(
                     initial_threads,
-                    decompressed_bytes_by_pack_offset,
+                    bases,
                     objects,
                     size,
                     &progress,
@@ -236,7 +241,7 @@ where
 #[allow(clippy::too_many_arguments)]
 fn deltas_mt<T, F, MBFN, E, R>(
     mut threads_to_create: isize,
-    decompressed_bytes_by_pack_offset: BTreeMap<u64, (data::Entry, u64, Vec<u8>)>,
+    bases: Bases<'_>,
     objects: gix_features::progress::StepShared,
     size: gix_features::progress::StepShared,
     progress: &dyn Progress,
@@ -256,7 +261,7 @@ where
     E: std::error::Error + Send + Sync + 'static,
 {
     let nodes = gix_features::threading::Mutable::new(nodes);
-    let decompressed_bytes_by_pack_offset = gix_features::threading::Mutable::new(decompressed_bytes_by_pack_offset);
+    let bases = gix_features::threading::Mutable::new(bases);
     threads_to_create += 1; // ourselves
     let mut returned_ourselves = false;
 
@@ -269,7 +274,7 @@ where
                     .name(format!("gix-pack.traverse_deltas.{tid}"))
                     .spawn_scoped(s, {
                         let nodes = &nodes;
-                        let decompressed_bytes_by_pack_offset = &decompressed_bytes_by_pack_offset;
+                        let bases = &bases;
                         let resolve = resolve.clone();
                         let mut modify_base = modify_base.clone();
                         let objects = &objects;
@@ -304,8 +309,8 @@ where
                                     let (a, b) = decompress_from_resolver(base.entry_slice(), &mut buf)?;
                                     (a, b, buf)
                                 } else {
-                                    threading::lock(decompressed_bytes_by_pack_offset)
-                                        .remove(&base.offset())
+                                    threading::lock(bases)
+                                        .remove(base.offset())?
                                         .expect("we store the resolved delta buffer when done")
                                 };
 
@@ -353,10 +358,12 @@ where
                                     //        at all
                                     child_entry.header = base_entry.header; // assign the actual object type, instead of 'delta'
                                     if child.has_children() {
-                                        threading::lock(decompressed_bytes_by_pack_offset).insert(
+                                        threading::lock(bases).insert(
                                             child.offset(),
-                                            (child_entry, entry_end, std::mem::take(&mut fully_resolved_delta_bytes)),
-                                        );
+                                            child_entry,
+                                            entry_end,
+                                            std::mem::take(&mut fully_resolved_delta_bytes),
+                                        )?;
                                         threading::lock(nodes).push((level + 1, child));
                                     } else {
                                         modify_base(
@@ -0,0 +1,180 @@
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use gix_features::threading::{self, Mutable};
+use gix_tempfile::{handle::Writable, AutoRemove, ContainingDirectory};
+
+use crate::{
+    cache::delta::traverse::{Error, MemoryLimit},
+    data,
+};
+
+/// Keeps track of the memory used by resolved base objects of all threads, and of the file they are spilled to
+/// once the [limit](MemoryLimit) is reached.
+pub(crate) struct Budget {
+    limit: Option<MemoryLimit>,
+    bytes_in_memory: AtomicUsize,
+    num_spilled: AtomicUsize,
+    file: Mutable<Option<SpillFile>>,
+}
+
+impl Budget {
+    pub(crate) fn new(limit: Option<MemoryLimit>) -> Self {
+        Budget {
+            limit,
+            bytes_in_memory: AtomicUsize::new(0),
+            num_spilled: AtomicUsize::new(0),
+            file: Mutable::new(None),
+        }
+    }
+
+    /// Return `true` if `num_bytes` can be kept in memory, and reserve them if so.
+    fn try_reserve(&self, num_bytes: usize) -> bool {
+        match &self.limit {
+            None => true,
+            Some(limit) => self
+                .bytes_in_memory
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                    (used + num_bytes <= limit.max_bytes).then_some(used + num_bytes)
+                })
+                .is_ok(),
+        }
+    }
+
+    /// Return the amount of objects that were written to disk as they didn't fit into memory.
+    pub(crate) fn num_spilled(&self) -> usize {
+        self.num_spilled.load(Ordering::SeqCst)
+    }
+
+    fn release(&self, num_bytes: usize) {
+        if self.limit.is_some() {
+            self.bytes_in_memory.fetch_sub(num_bytes, Ordering::SeqCst);
+        }
+    }
+
+    fn spill(&self, bytes: &[u8]) -> Result<u64, Error> {
+        let mut file = threading::lock(&self.file);
+        if file.is_none() {
+            let directory = self
+                .limit
+                .as_ref()
+                .and_then(|limit| limit.spill_directory.clone())
+                .unwrap_or_else(std::env::temp_dir);
+            *file = Some(SpillFile::create_in(directory).map_err(Error::Spill)?);
+        }
+        let offset = file
+            .as_mut()
+            .expect("just created")
+            .write(bytes)
+            .map_err(Error::Spill)?;
+        self.num_spilled.fetch_add(1, Ordering::SeqCst);
+        Ok(offset)
+    }
+
+    fn read_back(&self, offset: u64, len: usize) -> Result<Vec<u8>, Error> {
+        threading::lock(&self.file)
+            .as_mut()
+            .expect("data was spilled before")
+            .read(offset, len)
+            .map_err(Error::Spill)
+    }
+}
+
+/// An append-only temporary file which is removed when dropped or when the process is interrupted,
+/// and truncated whenever it doesn't hold any data anymore.
+struct SpillFile {
+    file: gix_tempfile::Handle<Writable>,
+    end: u64,
+    num_entries: usize,
+}
+
+impl SpillFile {
+    fn create_in(directory: PathBuf) -> std::io::Result<Self> {
+        Ok(SpillFile {
+            file: gix_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile)?,
+            end: 0,
+            num_entries: 0,
+        })
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<u64> {
+        let offset = self.end;
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.write_all(bytes)?;
+        self.end += bytes.len() as u64;
+        self.num_entries += 1;
+        Ok(offset)
+    }
+
+    fn read(&mut self, offset: u64, len: usize) -> std::io::Result<Vec<u8>> {
+        let mut buf = vec![0; len];
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.read_exact(&mut buf)?;
+        self.num_entries -= 1;
+        if self.num_entries == 0 {
+            self.file.with_mut(|file| file.as_file().set_len(0))??;
+            self.end = 0;
+        }
+        Ok(buf)
+    }
+}
+
+enum Stored {
+    Memory(Vec<u8>),
+    Disk { offset: u64, len: usize },
+}
+
+/// Resolved base objects by their pack offset, waiting for their deltas to be resolved.
+pub(crate) struct Bases<'a> {
+    budget: &'a Budget,
+    by_pack_offset: BTreeMap<u64, (data::Entry, u64, Stored)>,
+}
+
+impl<'a> Bases<'a> {
+    pub(crate) fn new(budget: &'a Budget) -> Self {
+        Bases {
+            budget,
+            by_pack_offset: BTreeMap::new(),
+        }
+    }
+
+    /// Store the resolved `bytes` of `entry` at `pack_offset`, ending at `entry_end`, in memory if the budget allows,
+    /// or on disk otherwise.
+    pub(crate) fn insert(
+        &mut self,
+        pack_offset: u64,
+        entry: data::Entry,
+        entry_end: u64,
+        bytes: Vec<u8>,
+    ) -> Result<(), Error> {
+        let stored = if self.budget.try_reserve(bytes.len()) {
+            Stored::Memory(bytes)
+        } else {
+            Stored::Disk {
+                offset: self.budget.spill(&bytes)?,
+                len: bytes.len(),
+            }
+        };
+        self.by_pack_offset.insert(pack_offset, (entry, entry_end, stored));
+        Ok(())
+    }
+
+    /// Remove the base object at `pack_offset` and return it, or `None` if it wasn't stored.
+    pub(crate) fn remove(&mut self, pack_offset: u64) -> Result<Option<(data::Entry, u64, Vec<u8>)>, Error> {
+        let Some((entry, entry_end, stored)) = self.by_pack_offset.remove(&pack_offset) else {
+            return Ok(None);
+        };
+        let bytes = match stored {
+            Stored::Memory(bytes) => {
+                self.budget.release(bytes.len());
+                bytes
+            }
+            Stored::Disk { offset, len } => self.budget.read_back(offset, len)?,
+        };
+        Ok(Some((entry, entry_end, bytes)))
+    }
+}
@@ -125,14 +125,15 @@ impl output::Entry {
         })
     }
 
-    /// Create a new instance from the given `oid` and its corresponding git object data `obj`.
-    pub fn from_data(count: &output::Count, obj: &gix_object::Data<'_>) -> Result<Self, Error> {
+    /// Create a new instance from the given `oid` and its corresponding git object data `obj`, compressed with
+    /// zlib `compression_level` from 0 (none) to 9 (best).
+    pub fn from_data(count: &output::Count, obj: &gix_object::Data<'_>, compression_level: u32) -> Result<Self, Error> {
         Ok(output::Entry {
             id: count.id.to_owned(),
             kind: Kind::Base(obj.kind),
             decompressed_size: obj.data.len(),
             compressed_data: {
-                let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+                let mut out = gix_features::zlib::stream::deflate::Write::new_with_level(Vec::new(), compression_level);
                 if let Err(err) = std::io::copy(&mut &*obj.data, &mut out) {
                     match err.kind() {
                         std::io::ErrorKind::Other => return Err(Error::ZlibDeflate(err)),
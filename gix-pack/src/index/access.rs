@@ -122,6 +122,18 @@ impl index::File {
         lookup(id.as_ref(), &self.fan, &|idx| self.oid_at_index(idx))
     }
 
+    /// Like [`lookup()`](Self::lookup()), but looks up all `sorted_ids` at once and returns their `index` in the same order.
+    ///
+    /// As `sorted_ids` must be sorted in ascending order, each search can start where the previous one ended, which is
+    /// faster than individual lookups when many objects are queried. Objects may not be found if `sorted_ids` aren't sorted.
+    // NOTE: pretty much the same things as in `multi_index::File::lookup_many`, change things there
+    //       as well.
+    pub fn lookup_many<I: AsRef<gix_hash::oid>>(&self, sorted_ids: &[I]) -> Vec<Option<EntryIndex>> {
+        lookup_many(sorted_ids.iter().map(AsRef::as_ref), &self.fan, &|idx| {
+            self.oid_at_index(idx)
+        })
+    }
+
     /// Given a `prefix`, find an object that matches it uniquely within this index and return `Some(Ok(entry_index))`.
     /// If there is more than one object matching the object `Some(Err(())` is returned.
     ///
@@ -265,6 +277,43 @@ pub(crate) fn lookup_prefix<'a>(
     None
 }
 
+pub(crate) fn lookup_many<'a, 'b>(
+    sorted_ids: impl Iterator<Item = &'b gix_hash::oid>,
+    fan: &[u32; FAN_LEN],
+    oid_at_index: &dyn Fn(EntryIndex) -> &'a gix_hash::oid,
+) -> Vec<Option<EntryIndex>> {
+    // As ids are sorted, the position of the previous id is a lower bound for the position of the next one.
+    let mut previous_position = 0;
+    let mut previous_id = None;
+    sorted_ids
+        .map(|id| {
+            debug_assert!(previous_id.map_or(true, |prev| prev <= id), "ids must be sorted");
+            previous_id = Some(id);
+            let first_byte = id.first_byte() as usize;
+            let mut upper_bound = fan[first_byte];
+            let mut lower_bound = if first_byte != 0 { fan[first_byte - 1] } else { 0 };
+            lower_bound = lower_bound.max(previous_position).min(upper_bound);
+
+            while lower_bound < upper_bound {
+                let mid = (lower_bound + upper_bound) / 2;
+                let mid_sha = oid_at_index(mid);
+
+                use std::cmp::Ordering::*;
+                match id.cmp(mid_sha) {
+                    Less => upper_bound = mid,
+                    Equal => {
+                        previous_position = mid;
+                        return Some(mid);
+                    }
+                    Greater => lower_bound = mid + 1,
+                }
+            }
+            previous_position = lower_bound;
+            None
+        })
+        .collect()
+}
+
 pub(crate) fn lookup<'a>(
     id: &gix_hash::oid,
     fan: &[u32; FAN_LEN],
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use gix_hash::ObjectId;
+
+use crate::{data, data::entry::Header, index};
+
+/// Returned by [`index::File::delta_chains()`].
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The pack data file has {actual} objects, but the index file has {expected}")]
+    ObjectCount { expected: u32, actual: u32 },
+    #[error("The base object of the delta at offset {offset} is not contained in this pack")]
+    MissingBase { offset: data::Offset },
+    #[error("The delta at offset {offset} is part of a cycle of deltas")]
+    Cycle { offset: data::Offset },
+}
+
+/// Information about a single object in a pack, similar to what `git verify-pack -v` prints for each object.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// The id of the object.
+    pub id: ObjectId,
+    /// The kind of the object, which for deltas is the kind of the base object at the end of their delta chain.
+    pub kind: gix_object::Kind,
+    /// The size of the entry after decompression, which is the size of the delta instructions for deltas.
+    pub decompressed_size: u64,
+    /// The size of the entry in the pack including its header, i.e. the amount of bytes it occupies.
+    pub size_in_pack: u64,
+    /// The offset of the entry in the pack.
+    pub pack_offset: data::Offset,
+    /// The amount of deltas that need to be applied to obtain this object, or 0 if it's a base object.
+    pub chain_length: u32,
+    /// The id of the object that this delta is based on, or `None` if it's a base object.
+    pub base: Option<ObjectId>,
+}
+
+/// The outcome of [`index::File::delta_chains()`].
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// All objects of the pack, in the order they are stored in the pack.
+    pub entries: Vec<Entry>,
+    /// A mapping of the length of the chain to the amount of objects at that length.
+    ///
+    /// A length of 0 indicates base objects, and everything above that involves the given amount
+    /// of delta objects. Unlike [`traverse::Statistics::objects_per_chain_length`](index::traverse::Statistics::objects_per_chain_length),
+    /// this isn't affected by caches.
+    pub objects_per_chain_length: BTreeMap<u32, u32>,
+    /// A mapping of the amount of deltas that are ultimately based on the same base object to the amount of such base objects.
+    ///
+    /// Base objects that don't serve as base for any delta are counted with 0 deltas.
+    pub deltas_per_base: BTreeMap<u32, u32>,
+}
+
+impl Outcome {
+    /// Return the length of the longest delta chain.
+    pub fn max_chain_length(&self) -> u32 {
+        self.objects_per_chain_length
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Return the average length of all delta chains, counting only delta objects, or `None` if there are no deltas.
+    pub fn average_chain_length(&self) -> Option<f64> {
+        let (num_deltas, total_length) = self
+            .objects_per_chain_length
+            .iter()
+            .filter(|(length, _)| **length != 0)
+            .fold((0u64, 0u64), |(count, total), (length, num_objects)| {
+                (
+                    count + u64::from(*num_objects),
+                    total + u64::from(*length) * u64::from(*num_objects),
+                )
+            });
+        (num_deltas != 0).then(|| total_length as f64 / num_deltas as f64)
+    }
+}
+
+/// Analysis
+impl index::File {
+    /// Learn how the objects in `pack`, the pack data file belonging to this index, are stored, and obtain the
+    /// delta chain of each object, similar to `git verify-pack -v`.
+    ///
+    /// Only the entry headers are read, which is much faster than decoding each object during a
+    /// [traversal](index::File::traverse()), but doesn't validate the objects either.
+    pub fn delta_chains(&self, pack: &data::File) -> Result<Outcome, Error> {
+        if pack.num_objects() != self.num_objects() {
+            return Err(Error::ObjectCount {
+                expected: self.num_objects(),
+                actual: pack.num_objects(),
+            });
+        }
+        let reverse_index = self.reverse_index();
+        let entries: Vec<_> = reverse_index
+            .iter()
+            .map(|(offset, index)| (offset, self.oid_at_index(index).to_owned()))
+            .collect();
+        let index_of_offset = |offset: data::Offset| reverse_index.position_of(offset);
+
+        let mut headers = Vec::with_capacity(entries.len());
+        for (idx, (offset, _)) in entries.iter().enumerate() {
+            let entry = pack.entry(*offset);
+            let base = match entry.header {
+                Header::OfsDelta { base_distance } => Some(
+                    Header::verified_base_pack_offset(*offset, base_distance)
+                        .and_then(index_of_offset)
+                        .ok_or(Error::MissingBase { offset: *offset })?,
+                ),
+                Header::RefDelta { base_id } => Some(
+                    self.lookup(base_id)
+                        .and_then(|idx| index_of_offset(self.pack_offset_at_index(idx)))
+                        .ok_or(Error::MissingBase { offset: *offset })?,
+                ),
+                Header::Commit | Header::Tree | Header::Blob | Header::Tag => None,
+            };
+            let next_offset = entries
+                .get(idx + 1)
+                .map_or(pack.pack_end() as data::Offset, |(offset, _)| *offset);
+            headers.push((
+                entry.header.as_kind(),
+                entry.decompressed_size,
+                next_offset - offset,
+                base,
+            ));
+        }
+
+        // The chain length and the index of the base object at the end of the chain, for each entry.
+        let mut chains: Vec<Option<(u32, usize)>> = vec![None; entries.len()];
+        let mut stack = Vec::new();
+        for idx in 0..entries.len() {
+            let mut current = idx;
+            let (mut length, root) = loop {
+                if let Some(chain) = chains[current] {
+                    break chain;
+                }
+                match headers[current].3 {
+                    Some(base) => {
+                        if stack.len() > entries.len() {
+                            return Err(Error::Cycle { offset: entries[idx].0 });
+                        }
+                        stack.push(current);
+                        current = base;
+                    }
+                    None => {
+                        chains[current] = Some((0, current));
+                        break (0, current);
+                    }
+                }
+            };
+            while let Some(delta) = stack.pop() {
+                length += 1;
+                chains[delta] = Some((length, root));
+            }
+        }
+
+        let mut out = Outcome {
+            entries: Vec::with_capacity(entries.len()),
+            objects_per_chain_length: BTreeMap::new(),
+            deltas_per_base: BTreeMap::new(),
+        };
+        let mut deltas_per_root = vec![0u32; entries.len()];
+        for (idx, ((pack_offset, id), (kind, decompressed_size, size_in_pack, base))) in
+            entries.iter().zip(headers.iter()).enumerate()
+        {
+            let (chain_length, root) = chains[idx].expect("all chains were resolved");
+            *out.objects_per_chain_length.entry(chain_length).or_insert(0) += 1;
+            if chain_length != 0 {
+                deltas_per_root[root] += 1;
+            }
+            out.entries.push(Entry {
+                id: *id,
+                kind: headers[root].0.expect("chains end at base objects"),
+                decompressed_size: *decompressed_size,
+                size_in_pack: *size_in_pack,
+                pack_offset: *pack_offset,
+                chain_length,
+                base: base.map(|base| entries[base].1),
+            });
+            debug_assert_eq!(kind.is_none(), base.is_some(), "only deltas have a base");
+        }
+        for (idx, num_deltas) in deltas_per_root.into_iter().enumerate() {
+            if headers[idx].3.is_none() {
+                *out.deltas_per_base.entry(num_deltas).or_insert(0) += 1;
+            }
+        }
+        Ok(out)
+    }
+}
@@ -132,6 +132,10 @@ impl File {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+    /// The length of all mapped data, including the header and the trailing checksums.
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
 }
 
 const V2_SIGNATURE: &[u8] = b"\xfftOc";
@@ -142,6 +146,10 @@ pub(crate) mod access;
 pub use access::Entry;
 
 pub(crate) mod encode;
+mod reverse;
+pub use reverse::ReverseIndex;
+///
+pub mod delta_chains;
 ///
 pub mod traverse;
 mod util;
@@ -0,0 +1,79 @@
+use crate::{
+    data,
+    index::{self, EntryIndex},
+};
+
+/// A mapping of pack offsets back to the entries of the [index](index::File) they belong to, akin to git's reverse index.
+///
+/// Use it to learn which object is stored at a given pack offset, or to visit all entries in the order they are
+/// stored in the pack.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub struct ReverseIndex {
+    /// Pairs of pack offsets and entry indices, sorted by pack offset.
+    entries: Vec<(data::Offset, EntryIndex)>,
+}
+
+/// Access
+impl ReverseIndex {
+    /// Return the amount of entries, which is the same as the amount of objects in the index it was created from.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the index of the entry whose pack entry starts at `pack_offset`, for use with
+    /// [`oid_at_index()`](index::File::oid_at_index()), or `None` if no entry starts there.
+    pub fn entry_index_at_pack_offset(&self, pack_offset: data::Offset) -> Option<EntryIndex> {
+        self.position_of(pack_offset).map(|pos| self.entries[pos].1)
+    }
+
+    /// Return the offset of the pack entry that follows the one at `pack_offset`, or `None` if there is no entry at
+    /// `pack_offset` or if it is the last one in the pack.
+    ///
+    /// The size of a pack entry, including its header, is the distance to the following entry, or to the
+    /// [end of the pack](data::File::pack_end()) for the last one.
+    pub fn next_pack_offset(&self, pack_offset: data::Offset) -> Option<data::Offset> {
+        self.position_of(pack_offset)
+            .and_then(|pos| self.entries.get(pos + 1))
+            .map(|(offset, _)| *offset)
+    }
+
+    /// Return an iterator over pairs of pack offsets and entry indices, in the order they are stored in the pack.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (data::Offset, EntryIndex)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// Return the position of the entry at `pack_offset` in pack order.
+    pub(crate) fn position_of(&self, pack_offset: data::Offset) -> Option<usize> {
+        self.entries
+            .binary_search_by_key(&pack_offset, |(offset, _)| *offset)
+            .ok()
+    }
+}
+
+/// Reverse lookup
+impl index::File {
+    /// Create a [reverse index](ReverseIndex) to find entries by their pack offset.
+    ///
+    /// Note that this requires sorting all entries, so it's best to keep the result around.
+    pub fn reverse_index(&self) -> ReverseIndex {
+        let mut entries: Vec<_> = (0..self.num_objects())
+            .map(|index| (self.pack_offset_at_index(index), index))
+            .collect();
+        entries.sort_unstable();
+        ReverseIndex { entries }
+    }
+
+    /// Return the id of the object whose pack entry starts at `pack_offset`, or `None` if there is no such entry.
+    ///
+    /// This is a linear search, so use a [reverse index](Self::reverse_index()) to perform many lookups.
+    pub fn oid_at_pack_offset(&self, pack_offset: data::Offset) -> Option<&gix_hash::oid> {
+        (0..self.num_objects())
+            .find(|index| self.pack_offset_at_index(*index) == pack_offset)
+            .map(|index| self.oid_at_index(index))
+    }
+}
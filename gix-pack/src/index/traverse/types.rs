@@ -29,6 +29,25 @@ pub struct Statistics {
     pub num_blobs: u32,
 }
 
+impl Statistics {
+    /// Return the ratio of the size of all decompressed entries to the size of all compressed entries,
+    /// i.e. how much zlib compression saved.
+    pub fn compression_ratio(&self) -> f64 {
+        self.total_decompressed_entries_size as f64 / self.total_compressed_entries_size as f64
+    }
+
+    /// Return the ratio of the size of all fully decoded objects to the size of all compressed entries,
+    /// i.e. how much zlib compression and delta compression saved together.
+    pub fn delta_compression_ratio(&self) -> f64 {
+        self.total_object_size as f64 / self.total_compressed_entries_size as f64
+    }
+
+    /// Return the percentage of the pack that isn't occupied by compressed entries, like entry headers and the trailer.
+    pub fn pack_overhead_percentage(&self) -> f64 {
+        (1.0 - (self.total_compressed_entries_size as f64 / self.pack_size as f64)) * 100.0
+    }
+}
+
 impl Default for Statistics {
     fn default() -> Self {
         Statistics {
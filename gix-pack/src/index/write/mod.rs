@@ -25,6 +25,8 @@ pub struct Outcome {
     pub data_hash: gix_hash::ObjectId,
     /// The amount of objects that were verified, always the amount of objects in the pack.
     pub num_objects: u32,
+    /// The amount of resolved base objects that were temporarily written to disk as they exceeded the memory limit.
+    pub num_spilled_objects: usize,
 }
 
 /// The progress ids used in [`write_data_iter_from_stream()`][crate::index::File::write_data_iter_to_stream()].
@@ -68,6 +70,8 @@ impl crate::index::File {
     ///
     /// * `kind` is the version of pack index to produce, use [`crate::index::Version::default()`] if in doubt.
     /// * `tread_limit` is used for a parallel tree traversal for obtaining object hashes with optimal performance.
+    /// * `memory_limit`, if `Some`, limits the memory used to hold resolved base objects during the traversal,
+    ///    spilling them to disk if necessary.
     /// * `root_progress` is the top-level progress to stay informed about the progress of this potentially long-running
     ///    computation.
     /// * `object_hash` defines what kind of object hash we write into the index file.
@@ -81,12 +85,13 @@ impl crate::index::File {
     /// provides all bytes belonging to a pack entry writing them to the given mutable output `Vec`.
     /// It should return `None` if the entry cannot be resolved from the pack that produced the `entries` iterator, causing
     /// the write operation to fail.
-    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::too_many_arguments, clippy::drop_non_drop)]
     pub fn write_data_iter_to_stream<F, F2, R>(
         version: crate::index::Version,
         make_resolver: F,
         entries: &mut dyn Iterator<Item = Result<crate::data::input::Entry, crate::data::input::Error>>,
         thread_limit: Option<usize>,
+        memory_limit: Option<traverse::MemoryLimit>,
         root_progress: &mut dyn DynNestedProgress,
         out: &mut dyn io::Write,
         should_interrupt: &AtomicBool,
@@ -115,7 +120,14 @@ impl crate::index::File {
             root_progress.add_child_with_id("decompressing".into(), ProgressId::DecompressedBytes.into());
         decompressed_progress.init(None, progress::bytes());
         let mut pack_entries_end: u64 = 0;
+        let mut decompressed_bytes: u64 = 0;
 
+        let receive_span = gix_features::trace::coarse!(
+            "receive pack",
+            objects = gix_features::trace::field::Empty,
+            pack_bytes = gix_features::trace::field::Empty,
+            decompressed_bytes = gix_features::trace::field::Empty,
+        );
         for entry in entries {
             let crate::data::input::Entry {
                 header,
@@ -129,6 +141,7 @@ impl crate::index::File {
             } = entry?;
 
             decompressed_progress.inc_by(decompressed_size as usize);
+            decompressed_bytes += decompressed_size;
 
             let entry_len = header_size as u64 + compressed_size;
             pack_entries_end = pack_offset + entry_len;
@@ -172,6 +185,11 @@ impl crate::index::File {
         let num_objects: u32 = num_objects
             .try_into()
             .map_err(|_| Error::IteratorInvariantTooManyObjects(num_objects))?;
+        receive_span
+            .record("objects", num_objects)
+            .record("pack_bytes", pack_entries_end)
+            .record("decompressed_bytes", decompressed_bytes);
+        drop(receive_span);
 
         objects_progress.show_throughput(indexing_start);
         decompressed_progress.show_throughput(indexing_start);
@@ -181,8 +199,18 @@ impl crate::index::File {
         root_progress.inc();
 
         let (resolver, pack) = make_resolver()?;
-        let sorted_pack_offsets_by_oid = {
-            let traverse::Outcome { roots, children } = tree.traverse(
+        let resolve_span = gix_features::trace::coarse!(
+            "resolve",
+            objects = num_objects,
+            bases = gix_features::trace::field::Empty,
+            deltas = gix_features::trace::field::Empty,
+        );
+        let (sorted_pack_offsets_by_oid, num_spilled_objects) = {
+            let traverse::Outcome {
+                roots,
+                children,
+                num_spilled_objects,
+            } = tree.traverse(
                 resolver,
                 &pack,
                 pack_entries_end,
@@ -205,8 +233,13 @@ impl crate::index::File {
                     thread_limit,
                     should_interrupt,
                     object_hash,
+                    memory_limit,
                 },
             )?;
+            resolve_span
+                .record("bases", roots.len())
+                .record("deltas", children.len());
+            drop(resolve_span);
             root_progress.inc();
 
             let mut items = roots;
@@ -218,7 +251,7 @@ impl crate::index::File {
             }
 
             root_progress.inc();
-            items
+            (items, num_spilled_objects)
         };
 
         let pack_hash = match last_seen_trailer {
@@ -249,6 +282,7 @@ impl crate::index::File {
             index_hash,
             data_hash: pack_hash,
             num_objects,
+            num_spilled_objects,
         })
     }
 }
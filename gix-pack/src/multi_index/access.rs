@@ -47,6 +47,10 @@ impl File {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+    /// Returns the length of all mapped data, including the header and the trailing checksum.
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
     /// Returns the checksum over the entire content of the file (excluding the checksum itself).
     ///
     /// It can be used to validate it didn't change after creation.
@@ -101,6 +105,16 @@ impl File {
         crate::index::access::lookup(id.as_ref(), &self.fan, &|idx| self.oid_at_index(idx))
     }
 
+    /// Like [`lookup()`](Self::lookup()), but looks up all `sorted_ids` at once and returns their index in the same order.
+    ///
+    /// As `sorted_ids` must be sorted in ascending order, each search can start where the previous one ended, which is
+    /// faster than individual lookups when many objects are queried. Objects may not be found if `sorted_ids` aren't sorted.
+    pub fn lookup_many<I: AsRef<gix_hash::oid>>(&self, sorted_ids: &[I]) -> Vec<Option<EntryIndex>> {
+        crate::index::access::lookup_many(sorted_ids.iter().map(AsRef::as_ref), &self.fan, &|idx| {
+            self.oid_at_index(idx)
+        })
+    }
+
     /// Given the `index` ranging from 0 to [File::num_objects()], return the pack index and its absolute offset into the pack.
     ///
     /// The pack-index refers to an entry in the [`index_names`][File::index_names()] list, from which the pack can be derived.
@@ -106,6 +106,7 @@ mod write_to_directory {
                 index_hash: gix_hash::ObjectId::from_hex(b"544a7204a55f6e9cacccf8f6e191ea8f83575de3")?,
                 data_hash: gix_hash::ObjectId::from_hex(b"0f3ea84cd1bba10c2a03d736a460635082833e59")?,
                 num_objects: 42,
+                num_spilled_objects: 0,
             },
             pack_version: pack::data::Version::V2,
             index_path: None,
@@ -117,7 +118,7 @@ mod write_to_directory {
 
     #[test]
     fn without_providing_one() -> Result<(), Box<dyn std::error::Error>> {
-        let res = write_pack(None::<&Path>, SMALL_PACK)?;
+        let res = write_pack(None::<&Path>, SMALL_PACK, None)?;
         assert_eq!(res, expected_outcome()?);
         assert_eq!(
             res.index.index_hash,
@@ -130,7 +131,7 @@ mod write_to_directory {
     #[test]
     fn given_a_directory() -> Result<(), Box<dyn std::error::Error>> {
         let dir = TempDir::new()?;
-        let mut res = write_pack(Some(&dir), SMALL_PACK)?;
+        let mut res = write_pack(Some(&dir), SMALL_PACK, None)?;
         let (index_path, data_path, keep_path) = (res.index_path.take(), res.data_path.take(), res.keep_path.take());
         assert_eq!(res, expected_outcome()?);
         let mut sorted_entries = fs::read_dir(&dir)?.filter_map(Result::ok).collect::<Vec<_>>();
@@ -154,6 +155,34 @@ mod write_to_directory {
         Ok(())
     }
 
+    #[test]
+    fn with_memory_limit_resolved_objects_are_spilled_to_disk() -> Result<(), Box<dyn std::error::Error>> {
+        for limit in [0, 128] {
+            let dir = TempDir::new()?;
+            let mut res = write_pack(Some(&dir), SMALL_PACK, Some(limit))?;
+            assert!(
+                res.index.num_spilled_objects > 0,
+                "with a limit of {limit} bytes, some resolved objects don't fit into memory"
+            );
+            res.index.num_spilled_objects = 0;
+            res.index_path = None;
+            res.data_path = None;
+            res.keep_path = None;
+            assert_eq!(res, expected_outcome()?, "the memory limit doesn't affect the result");
+            assert_eq!(
+                fs::read_dir(&dir)?.count(),
+                3,
+                "the spill file was removed, leaving only the pack, its index and the keep file"
+            );
+
+            let mut res = write_pack(None::<&Path>, SMALL_PACK, Some(limit))?;
+            assert!(res.index.num_spilled_objects > 0);
+            res.index.num_spilled_objects = 0;
+            assert_eq!(res, expected_outcome()?, "spilling also works without target directory");
+        }
+        Ok(())
+    }
+
     fn file_name(entry: &fs::DirEntry) -> String {
         entry.path().file_name().unwrap().to_str().unwrap().to_owned()
     }
@@ -161,6 +190,7 @@ mod write_to_directory {
     fn write_pack(
         directory: Option<impl AsRef<Path>>,
         pack_file: &str,
+        resolve_memory_limit: Option<usize>,
     ) -> Result<pack::bundle::write::Outcome, Box<dyn std::error::Error>> {
         let pack_file = fs::File::open(fixture_path(pack_file))?;
         static SHOULD_INTERRUPT: AtomicBool = AtomicBool::new(false);
@@ -176,6 +206,7 @@ mod write_to_directory {
                 iteration_mode: pack::data::input::Mode::Verify,
                 index_version: pack::index::Version::V2,
                 object_hash: gix_hash::Kind::Sha1,
+                resolve_memory_limit,
             },
         )
         .map_err(Into::into)
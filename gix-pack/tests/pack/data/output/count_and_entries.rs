@@ -321,6 +321,49 @@ fn traversals() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn deterministic_entries_do_not_depend_on_count_order() -> crate::Result {
+    let db = db(DbKind::DeterministicGeneratedContent)?;
+    let head = hex_to_id("dfcb5e39ac6eb30179808bbab721e8a28ce1b52e");
+    let commits = commit::Ancestors::new(Some(head), commit::ancestors::State::default(), db.clone())
+        .map(|c| c.map(|c| c.id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (counts, _stats) = output::count::objects(
+        db.clone(),
+        Box::new(commits.into_iter().map(Ok)),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::Options {
+            input_object_expansion: count::objects::ObjectExpansion::TreeContents,
+            ..Default::default()
+        },
+    )?;
+
+    let entries = |counts: Vec<output::Count>| -> Result<Vec<output::Entry>, entry::iter_from_counts::Error> {
+        Ok(InOrderIter::from(output::entry::iter_from_counts(
+            counts,
+            db.clone(),
+            Box::new(progress::Discard),
+            output::entry::iter_from_counts::Options {
+                deterministic: true,
+                ..Default::default()
+            },
+        ))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect())
+    };
+    let mut reversed_counts = counts.clone();
+    reversed_counts.reverse();
+    assert_eq!(
+        entries(counts)?,
+        entries(reversed_counts)?,
+        "the order of counts doesn't affect the pack entries"
+    );
+    Ok(())
+}
+
 #[test]
 fn empty_pack_is_allowed() {
     write_and_verify(
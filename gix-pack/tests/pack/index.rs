@@ -58,7 +58,7 @@ mod version {
     mod v2 {
         use gix_pack::index;
 
-        use crate::{fixture_path, pack::INDEX_V2};
+        use crate::{fixture_path, hex_to_id, pack::INDEX_V2};
 
         #[test]
         fn lookup() -> Result<(), Box<dyn std::error::Error>> {
@@ -104,6 +104,23 @@ mod version {
                     }
                 }
             }
+
+            let mut ids: Vec<_> = file.iter().map(|entry| entry.oid).collect();
+            ids.extend(
+                [
+                    "0000000000000000000000000000000000000000",
+                    "0ead45fc727edcf5cadca25ef922284f32bb6fc0",
+                    "0ead45fc727edcf5cadca25ef922284f32bb6fc1",
+                    "ffffffffffffffffffffffffffffffffffffffff",
+                ]
+                .map(hex_to_id),
+            );
+            ids.sort();
+            assert_eq!(
+                file.lookup_many(&ids),
+                ids.iter().map(|id| file.lookup(id)).collect::<Vec<_>>(),
+                "batched lookups yield the same result as individual ones, even with duplicates and missing objects"
+            );
             Ok(())
         }
     }
@@ -153,6 +170,7 @@ mod version {
                     },
                     pack_iter,
                     None,
+                    None,
                     progress::Discard,
                     &mut actual,
                     &AtomicBool::new(false),
@@ -390,6 +408,41 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
                 );
             }
         }
+
+        let chains = idx.delta_chains(&pack)?;
+        assert_eq!(
+            chains.objects_per_chain_length, stats.objects_per_chain_length,
+            "without caches, the traversal sees the same chains as the header-only analysis"
+        );
+        assert_eq!(chains.entries.len(), idx.num_objects() as usize);
+        assert_eq!(
+            chains.entries.iter().map(|e| e.size_in_pack).sum::<u64>() + SHA1_SIZE as u64 + 12,
+            stats.pack_size,
+            "all entries together with the pack header and trailer make up the whole pack"
+        );
+        assert_eq!(
+            chains.deltas_per_base.values().sum::<u32>(),
+            chains.objects_per_chain_length[&0],
+            "each base object is counted exactly once"
+        );
+        for entry in &chains.entries {
+            assert_eq!(entry.base.is_some(), entry.chain_length != 0);
+            if let Some(base) = entry.base {
+                let base = &chains.entries[chains
+                    .entries
+                    .binary_search_by_key(&idx.pack_offset_at_index(idx.lookup(base).expect("present")), |e| {
+                        e.pack_offset
+                    })
+                    .expect("base is in pack")];
+                assert_eq!(base.chain_length + 1, entry.chain_length);
+                assert_eq!(base.kind, entry.kind, "deltas have the kind of their base");
+            }
+        }
+        assert_eq!(
+            chains.entries.iter().filter(|e| e.kind == object::Kind::Commit).count(),
+            stats.num_commits as usize
+        );
+
         let num_objects = stats
             .objects_per_chain_length
             .values()
@@ -446,6 +499,47 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn reverse_index() -> Result<(), Box<dyn std::error::Error>> {
+    for (index_path, pack_path) in [(INDEX_V1, PACK_FOR_INDEX_V1), (SMALL_PACK_INDEX, SMALL_PACK)] {
+        let idx = pack::index::File::at(fixture_path(index_path), gix_hash::Kind::Sha1)?;
+        let pack = pack::data::File::at(fixture_path(pack_path), gix_hash::Kind::Sha1)?;
+        let rev = idx.reverse_index();
+        assert_eq!(rev.len(), idx.num_objects() as usize);
+
+        let offsets: Vec<_> = rev.iter().map(|(offset, _)| offset).collect();
+        assert_eq!(offsets, idx.sorted_offsets(), "iteration is in pack order");
+
+        for entry in idx.iter() {
+            let index = rev
+                .entry_index_at_pack_offset(entry.pack_offset)
+                .expect("every entry can be found");
+            assert_eq!(idx.oid_at_index(index), entry.oid);
+            assert_eq!(idx.oid_at_pack_offset(entry.pack_offset), Some(entry.oid.as_ref()));
+            assert_eq!(
+                rev.entry_index_at_pack_offset(entry.pack_offset + 1),
+                None,
+                "only exact offsets match"
+            );
+        }
+
+        let (last_offset, _) = rev.iter().last().expect("non-empty");
+        assert_eq!(
+            rev.next_pack_offset(last_offset),
+            None,
+            "the last entry has no successor"
+        );
+        let (first_offset, _) = rev.iter().next().expect("non-empty");
+        assert_eq!(first_offset, 12, "the first entry follows the pack header");
+        let entry = pack.entry(first_offset);
+        assert!(
+            rev.next_pack_offset(first_offset).expect("more than one entry") >= entry.data_offset,
+            "the next entry starts after the data of the current one"
+        );
+    }
+    Ok(())
+}
+
 #[test]
 fn iter() -> Result<(), Box<dyn std::error::Error>> {
     for (path, kind, num_objects, index_checksum, pack_checksum) in &[
@@ -489,3 +583,72 @@ fn iter() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+#[test]
+fn write_data_iter_to_stream_traces_counts_and_byte_volumes() -> crate::Result {
+    use gix_pack::data::input;
+
+    fn slice_map(entry: gix_pack::data::EntryRange, map: &memmap2::Mmap) -> Option<&[u8]> {
+        map.get(entry.start as usize..entry.end as usize)
+    }
+
+    let pack_path = fixture_path(SMALL_PACK);
+    let data = pack::data::File::at(&pack_path, gix_hash::Kind::Sha1)?;
+    let index = pack::index::File::at(fixture_path(SMALL_PACK_INDEX), gix_hash::Kind::Sha1)?;
+    let mut decompressed_bytes = 0;
+    for entry in index.iter() {
+        decompressed_bytes += data.entry(entry.pack_offset).decompressed_size;
+    }
+
+    let (outcome, spans) = gix_testtools::capture_spans(|| -> crate::Result<_> {
+        let mut pack_iter = input::BytesToEntriesIter::new_from_header(
+            std::io::BufReader::new(std::fs::File::open(&pack_path)?),
+            input::Mode::Verify,
+            input::EntryDataMode::Crc32,
+            gix_hash::Kind::Sha1,
+        )?;
+        let pack_version = pack_iter.version();
+        Ok(pack::index::File::write_data_iter_to_stream(
+            pack::index::Version::default(),
+            || {
+                let file = std::fs::File::open(&pack_path)?;
+                let map = unsafe { memmap2::Mmap::map(&file)? };
+                Ok((slice_map, map))
+            },
+            &mut pack_iter,
+            None,
+            None,
+            &mut gix_features::progress::Discard,
+            &mut Vec::new(),
+            &AtomicBool::new(false),
+            gix_hash::Kind::Sha1,
+            pack_version,
+        )?)
+    });
+    let outcome = outcome?;
+    assert_eq!(outcome.num_objects, index.num_objects());
+
+    let span = |name: &str| {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap_or_else(|| panic!("span '{name}' was created"))
+    };
+    let receive = &span("receive pack").fields;
+    assert_eq!(receive["objects"], index.num_objects().to_string());
+    assert_eq!(
+        receive["pack_bytes"],
+        (data.data_len() - SHA1_SIZE).to_string(),
+        "all bytes between the pack header and its trailing checksum"
+    );
+    assert_eq!(receive["decompressed_bytes"], decompressed_bytes.to_string());
+
+    let resolve = &span("resolve").fields;
+    assert_eq!(resolve["objects"], index.num_objects().to_string());
+    assert_eq!(
+        resolve["bases"].parse::<u32>()? + resolve["deltas"].parse::<u32>()?,
+        index.num_objects(),
+        "every object is either a base or a delta"
+    );
+    Ok(())
+}
@@ -95,4 +95,19 @@ fn general() {
         count += 1;
     }
     assert_eq!(count, file.num_objects());
+
+    let mut ids: Vec<_> = file.iter().step_by(3).map(|entry| entry.oid).collect();
+    ids.extend(
+        [
+            "0000000000000000000000000000000000000000",
+            "ffffffffffffffffffffffffffffffffffffffff",
+        ]
+        .map(hex_to_id),
+    );
+    ids.sort();
+    assert_eq!(
+        file.lookup_many(&ids),
+        ids.iter().map(|id| file.lookup(id)).collect::<Vec<_>>(),
+        "batched lookups yield the same result as individual ones"
+    );
 }
@@ -54,7 +54,7 @@ mod convert;
 pub use convert::*;
 
 mod util;
-pub use util::is_absolute;
+pub use util::{is_absolute, is_within};
 
 ///
 pub mod realpath;
@@ -1,8 +1,15 @@
 use std::path::Path;
 
+use bstr::{BStr, ByteSlice};
+
 /// return true if `path` is absolute, which depends on the platform but is always true if it starts with a `slash`, hence looks like
 /// a linux path.
 pub fn is_absolute(path: impl AsRef<Path>) -> bool {
     let path = path.as_ref();
     path.is_absolute() || path.to_str().and_then(|s| s.chars().next()) == Some('/')
 }
+
+/// Return `true` if the slash-separated `path` is within `directory` or one of its subdirectories, but not if it is `directory` itself.
+pub fn is_within(path: &BStr, directory: &BStr) -> bool {
+    path.len() > directory.len() && path.starts_with_str(directory) && path[directory.len()] == b'/'
+}
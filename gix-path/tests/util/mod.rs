@@ -40,3 +40,22 @@ mod is_absolute {
         }
     }
 }
+
+mod is_within {
+    use gix_path::is_within;
+
+    #[test]
+    fn paths_in_the_directory_or_its_subdirectories_are_within() {
+        assert!(is_within("dir/file".into(), "dir".into()));
+        assert!(is_within("dir/sub/file".into(), "dir".into()));
+        assert!(is_within("dir/sub/file".into(), "dir/sub".into()));
+    }
+
+    #[test]
+    fn the_directory_itself_and_siblings_with_the_same_prefix_are_not_within() {
+        assert!(!is_within("dir".into(), "dir".into()));
+        assert!(!is_within("dir2/file".into(), "dir".into()));
+        assert!(!is_within("di".into(), "dir".into()));
+        assert!(!is_within("other/dir/file".into(), "dir".into()));
+    }
+}
@@ -117,6 +117,50 @@ impl Search {
             res
         }
     }
+
+    /// As opposed to [`Self::pattern_matching_relative_path()`], this method will return `true` for a possibly partial
+    /// `relative_path` if this pathspec *could* match by looking at the shortest shared prefix only.
+    ///
+    /// This is useful if `relative_path` is a directory leading up to the item that is going to be matched in full later,
+    /// as a result of `false` allows to skip the entire directory, like a tree whose entries can't possibly match.
+    /// Note that the result is conservative, so `true` doesn't mean that `relative_path` or anything below it matches,
+    /// and that attributes and excluded pathspecs aren't taken into account.
+    /// `is_dir` is `true` if `relative_path` is a directory.
+    pub fn can_match_relative_path(&self, relative_path: &BStr, is_dir: Option<bool>) -> bool {
+        if self.patterns.is_empty() || self.all_patterns_are_excluded {
+            return true;
+        }
+        let common_prefix_len = self.common_prefix_len.min(relative_path.len());
+        if relative_path[..common_prefix_len] != self.common_prefix()[..common_prefix_len] {
+            return false;
+        }
+
+        let is_dir = is_dir.unwrap_or(false);
+        self.patterns
+            .iter()
+            .filter(|mapping| !mapping.value.pattern.is_excluded())
+            .any(|mapping| {
+                let pattern = &mapping.value.pattern;
+                if pattern.is_nil() || pattern.path.is_empty() {
+                    return true;
+                }
+                let literal_len = match pattern.search_mode {
+                    SearchMode::Literal => pattern.path.len(),
+                    SearchMode::ShellGlob | SearchMode::PathAwareGlob => {
+                        mapping.pattern.first_wildcard_pos.unwrap_or(pattern.path.len())
+                    }
+                };
+                let literal = &pattern.path[..literal_len];
+                let common_len = literal.len().min(relative_path.len());
+                let (literal_prefix, rela_path_prefix) = (&literal[..common_len], &relative_path[..common_len]);
+                let prefix_matches = if pattern.signature.contains(MagicSignature::ICASE) {
+                    literal_prefix.eq_ignore_ascii_case(rela_path_prefix)
+                } else {
+                    literal_prefix == rela_path_prefix
+                };
+                prefix_matches && (!is_dir || literal.get(relative_path.len()).map_or(true, |b| *b == b'/'))
+            })
+    }
 }
 
 fn match_verbatim(
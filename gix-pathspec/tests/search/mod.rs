@@ -133,6 +133,40 @@ fn common_prefix() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn directories_that_cannot_match_can_be_pruned() -> crate::Result {
+    let search = gix_pathspec::Search::from_specs(
+        pathspecs(&["dir/sub/file", "other/*.rs", ":(icase)Case/file", ":!dir/sub/ignored"]),
+        None,
+        Path::new(""),
+    )?;
+    for (path, is_dir, expected) in [
+        ("dir", true, true),
+        ("dir/sub", true, true),
+        ("dir/other", true, false),
+        ("dir/sub/file", false, true),
+        ("dirt", true, false),
+        ("other", true, true),
+        ("other/deep/nested", true, true),
+        ("case", true, true),
+        ("unrelated", true, false),
+        ("unrelated", false, false),
+    ] {
+        assert_eq!(
+            search.can_match_relative_path(path.into(), Some(is_dir)),
+            expected,
+            "{path} {is_dir}"
+        );
+    }
+
+    let search = gix_pathspec::Search::from_specs(pathspecs(&[":!dir"]), None, Path::new(""))?;
+    assert!(
+        search.can_match_relative_path("dir".into(), Some(true)),
+        "exclusions don't prune as they may be refined by other pathspecs"
+    );
+    Ok(())
+}
+
 #[test]
 fn files() -> crate::Result {
     baseline::run("file", false, baseline::files)
@@ -178,6 +212,20 @@ mod baseline {
                 })
                 .cloned()
                 .collect();
+            for path in &actual {
+                let mut components: Vec<_> = path.split('/').collect();
+                assert!(
+                    search.can_match_relative_path(path.as_str().into(), Some(items_are_dirs)),
+                    "{path} matched and thus must be possible to match"
+                );
+                while components.pop().is_some() && !components.is_empty() {
+                    let dir = components.join("/");
+                    assert!(
+                        search.can_match_relative_path(dir.as_str().into(), Some(true)),
+                        "{dir} leads to matching {path} and can't be pruned"
+                    );
+                }
+            }
             let matches_expectation = actual == expected.matches;
             assert_eq!(
                 matches_expectation,
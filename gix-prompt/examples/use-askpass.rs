@@ -8,6 +8,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         &Options {
             askpass: Some(Cow::Owned(std::env::current_exe()?.parent().unwrap().join("askpass"))),
             mode: Mode::Disable,
+            ..Default::default()
         },
     )?;
     eprintln!("{pass:?}");
@@ -1,21 +1,28 @@
 //! Git style prompting with support for `GIT_ASKPASS` and `askpass` program configuration.
 //!
+//! Applications can answer prompts themselves by setting a [`Provider`] in the [`Options`].
+//!
 //! ### Compatibility
 //!
-//! This is a unix-only crate which will return with an error when trying to obtain any prompt on other platforms.
-//! On those platforms it is common to have helpers which perform this task so it shouldn't be a problem.
-#![deny(rust_2018_idioms, missing_docs)]
-#![forbid(unsafe_code)]
+//! Prompting in the terminal is supported on unix and windows, and an error is returned when trying to obtain any prompt
+//! on other platforms. On those platforms it is common to have helpers which perform this task so it shouldn't be a problem.
+// `unsafe_code` not forbidden because we need to interact with the windows console
+#![deny(rust_2018_idioms, missing_docs, unsafe_code)]
 
 mod types;
-pub use types::{Error, Mode, Options};
+pub use types::{Error, Mode, Options, Provider};
 
 ///
 pub mod unix;
 #[cfg(unix)]
 use unix::imp;
 
-#[cfg(not(unix))]
+///
+pub mod windows;
+#[cfg(windows)]
+use self::windows::imp;
+
+#[cfg(not(any(unix, windows)))]
 mod imp {
     use crate::{Error, Options};
 
@@ -25,13 +32,20 @@ mod imp {
 }
 
 /// Ask the user given a `prompt`, returning the result.
+///
+/// If a [`provider`][Options::provider] is set, it answers the prompt. Otherwise, an [`askpass`][Options::askpass] program
+/// is used to obtain the answer if set, falling back to the terminal if it can't be launched or fails.
 pub fn ask(prompt: &str, opts: &Options<'_>) -> Result<String, Error> {
+    if let Some(provider) = opts.provider.as_deref() {
+        return provider.ask(prompt, opts.mode);
+    }
     if let Some(askpass) = opts.askpass.as_deref() {
         match gix_command::prepare(askpass).arg(prompt).spawn() {
             Ok(cmd) => {
                 if let Some(mut stdout) = cmd
                     .wait_with_output()
                     .ok()
+                    .filter(|out| out.status.success())
                     .and_then(|out| String::from_utf8(out.stdout).ok())
                 {
                     if stdout.ends_with('\n') {
@@ -57,7 +71,7 @@ pub fn openly(prompt: impl AsRef<str>) -> Result<String, Error> {
         prompt.as_ref(),
         &Options {
             mode: Mode::Visible,
-            askpass: None,
+            ..Default::default()
         },
     )
 }
@@ -70,7 +84,7 @@ pub fn securely(prompt: impl AsRef<str>) -> Result<String, Error> {
         prompt.as_ref(),
         &Options {
             mode: Mode::Hidden,
-            askpass: None,
+            ..Default::default()
         },
     )
 }
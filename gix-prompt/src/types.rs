@@ -1,4 +1,4 @@
-use std::{borrow::Cow, convert::TryFrom, path::Path};
+use std::{borrow::Cow, convert::TryFrom, path::Path, sync::Arc, time::Duration};
 
 /// The error returned by [ask()][crate::ask()].
 #[derive(Debug, thiserror::Error)]
@@ -8,6 +8,10 @@ pub enum Error {
     Disabled,
     #[error("The current platform has no implementation for prompting in the terminal")]
     UnsupportedPlatform,
+    #[error("No terminal is available to prompt the user")]
+    NoTerminal(#[source] std::io::Error),
+    #[error("The user didn't respond to the prompt within {timeout:?}")]
+    Timeout { timeout: Duration },
     #[error(
         "Failed to open terminal at {:?} for writing prompt, or to write it",
         crate::unix::TTY_PATH
@@ -16,6 +20,28 @@ pub enum Error {
     #[cfg(unix)]
     #[error("Failed to obtain or set terminal configuration")]
     TerminalConfiguration(#[from] rustix::io::Errno),
+    #[cfg(windows)]
+    #[error("Failed to obtain or set console configuration")]
+    ConsoleConfiguration(#[from] ::windows::core::Error),
+}
+
+/// A way to answer prompts programmatically, for example through the user interface of an application, instead of
+/// using the terminal.
+///
+/// It's implemented for closures like `|prompt: &str, mode: Mode| -> Result<String, Error>`.
+pub trait Provider: Send + Sync {
+    /// Return the answer to `prompt`, with `mode` indicating if the answer is sensitive, like a password, if it is
+    /// [`Hidden`][Mode::Hidden].
+    fn ask(&self, prompt: &str, mode: Mode) -> Result<String, Error>;
+}
+
+impl<F> Provider for F
+where
+    F: Fn(&str, Mode) -> Result<String, Error> + Send + Sync,
+{
+    fn ask(&self, prompt: &str, mode: Mode) -> Result<String, Error> {
+        self(prompt, mode)
+    }
 }
 
 /// The way the user is prompted.
@@ -39,6 +65,13 @@ pub struct Options<'a> {
     pub askpass: Option<Cow<'a, Path>>,
     /// The way the user is prompted.
     pub mode: Mode,
+    /// If set, the amount of time to wait for the user to answer a prompt in the terminal before failing with
+    /// [`Error::Timeout`].
+    ///
+    /// This is only supported on unix.
+    pub timeout: Option<Duration>,
+    /// If set, the provider answers all prompts instead of the [`askpass`][Options::askpass] program or the terminal.
+    pub provider: Option<Arc<dyn Provider>>,
 }
 
 impl Options<'_> {
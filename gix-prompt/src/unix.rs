@@ -7,26 +7,28 @@ pub(crate) mod imp {
         fs::File,
         io,
         io::{BufRead, Read, Write},
+        time::Duration,
     };
 
     use parking_lot::{const_mutex, lock_api::MutexGuard, Mutex, RawMutex};
-    use rustix::termios::{self, Termios};
+    use rustix::{
+        event::{PollFd, PollFlags},
+        termios::{self, Termios},
+    };
 
     use crate::{unix::TTY_PATH, Error, Mode, Options};
 
     static TERM_STATE: Mutex<Option<Termios>> = const_mutex(None);
 
     /// Ask the user given a `prompt`, returning the result.
-    pub(crate) fn ask(prompt: &str, Options { mode, .. }: &Options<'_>) -> Result<String, Error> {
+    pub(crate) fn ask(prompt: &str, Options { mode, timeout, .. }: &Options<'_>) -> Result<String, Error> {
         match mode {
             Mode::Disable => Err(Error::Disabled),
             Mode::Hidden => {
                 let state = TERM_STATE.lock();
-                let mut in_out = save_term_state_and_disable_echo(
-                    state,
-                    std::fs::OpenOptions::new().write(true).read(true).open(TTY_PATH)?,
-                )?;
+                let mut in_out = save_term_state_and_disable_echo(state, open_tty()?)?;
                 in_out.write_all(prompt.as_bytes())?;
+                wait_for_input(&mut in_out.fd, *timeout)?;
 
                 let mut buf_read = std::io::BufReader::with_capacity(64, in_out);
                 let mut out = String::with_capacity(64);
@@ -40,8 +42,9 @@ pub(crate) mod imp {
                 Ok(out)
             }
             Mode::Visible => {
-                let mut in_out = std::fs::OpenOptions::new().write(true).read(true).open(TTY_PATH)?;
+                let mut in_out = open_tty()?;
                 in_out.write_all(prompt.as_bytes())?;
+                wait_for_input(&mut in_out, *timeout)?;
 
                 let mut buf_read = std::io::BufReader::with_capacity(64, in_out);
                 let mut out = String::with_capacity(64);
@@ -51,6 +54,29 @@ pub(crate) mod imp {
         }
     }
 
+    fn open_tty() -> Result<File, Error> {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .read(true)
+            .open(TTY_PATH)
+            .map_err(Error::NoTerminal)
+    }
+
+    /// Wait until the user finished typing a line into `tty`, or fail if that takes longer than `timeout`.
+    fn wait_for_input(tty: &mut File, timeout: Option<Duration>) -> Result<(), Error> {
+        let Some(timeout) = timeout else {
+            return Ok(());
+        };
+        let millis = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+        let num_ready = rustix::event::poll(&mut [PollFd::new(tty, PollFlags::IN)], millis).map_err(io::Error::from)?;
+        if num_ready == 0 {
+            // Move the cursor away from the unanswered prompt.
+            tty.write_all(b"\n")?;
+            return Err(Error::Timeout { timeout });
+        }
+        Ok(())
+    }
+
     type TermiosGuard<'a> = MutexGuard<'a, RawMutex, Option<Termios>>;
 
     struct RestoreTerminalStateOnDrop<'a> {
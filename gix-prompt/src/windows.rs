@@ -0,0 +1,85 @@
+/// The path to the console input buffer on windows
+pub const CONSOLE_INPUT_PATH: &str = "CONIN$";
+/// The path to the console screen buffer on windows
+pub const CONSOLE_OUTPUT_PATH: &str = "CONOUT$";
+
+#[cfg(windows)]
+pub(crate) mod imp {
+    use std::{
+        fs::File,
+        io::{BufRead, Write},
+        os::windows::io::AsRawHandle,
+    };
+
+    use ::windows::Win32::{
+        Foundation::HANDLE,
+        System::Console::{GetConsoleMode, SetConsoleMode, CONSOLE_MODE, ENABLE_ECHO_INPUT},
+    };
+
+    use crate::{
+        windows::{CONSOLE_INPUT_PATH, CONSOLE_OUTPUT_PATH},
+        Error, Mode, Options,
+    };
+
+    /// Ask the user given a `prompt`, returning the result.
+    pub(crate) fn ask(prompt: &str, Options { mode, .. }: &Options<'_>) -> Result<String, Error> {
+        if *mode == Mode::Disable {
+            return Err(Error::Disabled);
+        }
+        let input = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(CONSOLE_INPUT_PATH)
+            .map_err(Error::NoTerminal)?;
+        let mut output = std::fs::OpenOptions::new()
+            .write(true)
+            .open(CONSOLE_OUTPUT_PATH)
+            .map_err(Error::NoTerminal)?;
+        output.write_all(prompt.as_bytes())?;
+
+        let mut out = String::with_capacity(64);
+        if *mode == Mode::Hidden {
+            let input = RestoreConsoleModeOnDrop::disable_echo(&input)?;
+            std::io::BufReader::with_capacity(64, input.fd).read_line(&mut out)?;
+            // The newline typed by the user isn't echoed either.
+            output.write_all(b"\r\n")?;
+        } else {
+            std::io::BufReader::with_capacity(64, &input).read_line(&mut out)?;
+        }
+
+        if out.ends_with('\n') {
+            out.pop();
+        }
+        if out.ends_with('\r') {
+            out.pop();
+        }
+        Ok(out)
+    }
+
+    struct RestoreConsoleModeOnDrop<'a> {
+        fd: &'a File,
+        mode: CONSOLE_MODE,
+    }
+
+    impl<'a> RestoreConsoleModeOnDrop<'a> {
+        fn disable_echo(fd: &'a File) -> Result<Self, Error> {
+            let handle = HANDLE(fd.as_raw_handle() as isize);
+            let mut mode = CONSOLE_MODE::default();
+            #[allow(unsafe_code)]
+            unsafe {
+                GetConsoleMode(handle, &mut mode).ok()?;
+                SetConsoleMode(handle, CONSOLE_MODE(mode.0 & !ENABLE_ECHO_INPUT.0)).ok()?;
+            }
+            Ok(RestoreConsoleModeOnDrop { fd, mode })
+        }
+    }
+
+    impl Drop for RestoreConsoleModeOnDrop<'_> {
+        fn drop(&mut self) {
+            #[allow(unsafe_code)]
+            unsafe {
+                SetConsoleMode(HANDLE(self.fd.as_raw_handle() as isize), self.mode);
+            }
+        }
+    }
+}
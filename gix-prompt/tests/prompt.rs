@@ -63,6 +63,42 @@ mod ask {
         p.expect(expectrl::Eof).unwrap();
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn failing_askpass_falls_back_to_the_terminal() {
+        let err = gix_prompt::ask(
+            "Password: ",
+            &gix_prompt::Options {
+                askpass: Some(std::path::Path::new("false").into()),
+                mode: gix_prompt::Mode::Disable,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, gix_prompt::Error::Disabled),
+            "the terminal is tried next, but prompts are disabled"
+        );
+    }
+
+    #[test]
+    fn provider_answers_instead_of_askpass_and_terminal() {
+        let answer = gix_prompt::ask(
+            "Password: ",
+            &gix_prompt::Options {
+                askpass: Some(std::path::Path::new("false").into()),
+                mode: gix_prompt::Mode::Hidden,
+                provider: Some(std::sync::Arc::new(|prompt: &str, mode| {
+                    assert_eq!(mode, gix_prompt::Mode::Hidden);
+                    Ok(format!("answer to {prompt:?}"))
+                })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(answer, "answer to \"Password: \"");
+    }
+
     #[test]
     #[cfg(not(unix))]
     #[ignore]
@@ -3,7 +3,7 @@ use std::fmt;
 use bstr::{BStr, BString, ByteSlice, ByteVec};
 
 /// The arguments passed to a server command.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Arguments {
     /// The active features/capabilities of the fetch invocation
     #[cfg(any(feature = "async-client", feature = "blocking-client"))]
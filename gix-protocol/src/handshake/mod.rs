@@ -88,6 +88,7 @@ mod error {
         fn is_spurious(&self) -> bool {
             match self {
                 Error::Transport(err) => err.is_spurious(),
+                Error::ParseRefs(err) => err.is_spurious(),
                 _ => false,
             }
         }
@@ -29,6 +29,15 @@ pub mod parse {
         #[error("{message}")]
         InvariantViolation { message: &'static str },
     }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
 }
 
 impl Ref {
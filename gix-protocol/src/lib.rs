@@ -1,4 +1,4 @@
-//! An abstraction over [fetching][fetch()] a pack from the server.
+//! An abstraction over [fetching][fetch()] a pack from the server, and [pushing][push()] one to it.
 //!
 //! This implementation hides the transport layer, statefulness and the protocol version to the [fetch delegate][fetch::Delegate],
 //! the actual client implementation.
@@ -58,6 +58,16 @@ pub mod ls_refs;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
 pub use ls_refs::function::ls_refs;
 
+///
+#[cfg(feature = "blocking-client")]
+pub mod push;
+#[cfg(feature = "blocking-client")]
+pub use push::function::push;
+
+///
+#[cfg(feature = "blocking-client")]
+pub mod server;
+
 mod util;
 pub use util::agent;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
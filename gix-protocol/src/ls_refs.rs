@@ -18,6 +18,7 @@ mod error {
             match self {
                 Error::Io(err) => err.is_spurious(),
                 Error::Transport(err) => err.is_spurious(),
+                Error::Parse(err) => err.is_spurious(),
                 _ => false,
             }
         }
@@ -0,0 +1,246 @@
+use bstr::BString;
+use gix_hash::ObjectId;
+
+/// An instruction for the remote to change the ref at `ref_name` from `old` to `new`.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Command {
+    /// The name of the ref to change on the remote, like `refs/heads/main`.
+    pub ref_name: BString,
+    /// The id the remote ref is expected to point to, or the null id if it is expected to not exist.
+    pub old: ObjectId,
+    /// The id the remote ref should point to, or the null id if it should be deleted.
+    pub new: ObjectId,
+}
+
+impl Command {
+    /// Return `true` if this command deletes the remote ref.
+    pub fn is_delete(&self) -> bool {
+        self.new.is_null()
+    }
+}
+
+/// The capabilities agreed upon with the remote for a [`push()`][crate::push()], derived from what the server advertised.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Features {
+    /// If `true`, the remote sends its status report and progress messages multiplexed over a side-band.
+    pub side_band_64k: bool,
+    /// If `true`, commands may delete refs on the remote.
+    pub delete_refs: bool,
+    /// If `true`, the pack may contain deltas whose base is referred to by offset. Otherwise, all deltas must refer to
+    /// their base object by id.
+    pub ofs_delta: bool,
+}
+
+impl Features {
+    /// Determine the features to use based on the `capabilities` advertised by the server.
+    pub fn from_capabilities(capabilities: &gix_transport::client::Capabilities) -> Self {
+        Features {
+            side_band_64k: capabilities.contains("side-band-64k"),
+            delete_refs: capabilities.contains("delete-refs"),
+            ofs_delta: capabilities.contains("ofs-delta"),
+        }
+    }
+}
+
+/// The result of a single [`Command`] as reported by the remote.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefStatus {
+    /// The name of the ref the command was for.
+    pub ref_name: BString,
+    /// The reason for rejecting the command, or `None` if the ref was updated.
+    pub error: Option<BString>,
+}
+
+/// The result of the [`push()`][crate::push()] function.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// The reason for the remote to fail unpacking the pack we sent, or `None` if it was unpacked successfully.
+    pub unpack_error: Option<BString>,
+    /// The status of each command, in the order reported by the remote.
+    pub refs: Vec<RefStatus>,
+}
+
+impl Outcome {
+    /// Return `true` if the pack was unpacked and all refs were updated.
+    pub fn is_success(&self) -> bool {
+        self.unpack_error.is_none() && self.refs.iter().all(|status| status.error.is_none())
+    }
+}
+
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`push()`][crate::push()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error(transparent)]
+        PacketlineDecode(#[from] gix_transport::packetline::decode::Error),
+        #[error("The remote doesn't support the 'report-status' capability, which is required to learn about the outcome of the push")]
+        ReportStatusUnsupported,
+        #[error("The remote doesn't support deleting refs")]
+        DeleteRefsUnsupported,
+        #[error("Failed to write the pack to send")]
+        WritePack(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error("Could not parse line {line:?} of the status report sent by the remote")]
+        ParseStatusReport { line: BString },
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+pub(crate) mod function {
+    use std::io::{BufRead, Write};
+
+    use bstr::{BString, ByteSlice};
+    use gix_features::progress::NestedProgress;
+    use gix_transport::{
+        client::{Capabilities, MessageKind, Transport, WriteMode},
+        packetline::{PacketLineRef, StreamingPeekableIter},
+    };
+
+    use super::{Command, Error, Features, Outcome, RefStatus};
+    use crate::indicate_end_of_interaction;
+
+    /// Send `commands` to `transport` to change refs on the remote, along with a pack written by `write_pack(out)` that contains
+    /// all objects the remote needs to perform them.
+    ///
+    /// This requires a prior handshake for [`Service::ReceivePack`][gix_transport::Service::ReceivePack] which yielded the
+    /// server `capabilities` using protocol V0 or V1, as there is no V2 version of the push protocol.
+    /// `agent` is the name to present to the server as user agent, and `progress` receives the messages the remote sends while
+    /// processing the pack. `write_pack` receives the [`Features`] agreed upon with the remote, which determine the kind of pack
+    /// it may write, and isn't called if all `commands` delete refs.
+    /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    ///
+    /// Note that the remote might reject some or all of the `commands`, which is communicated through the returned [`Outcome`].
+    pub fn push<P>(
+        mut transport: impl Transport,
+        capabilities: &Capabilities,
+        commands: &[Command],
+        write_pack: impl FnOnce(&mut dyn Write, Features) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>,
+        agent: Option<&str>,
+        progress: &mut P,
+        trace: bool,
+    ) -> Result<Outcome, Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let _span = gix_features::trace::detail!("gix_protocol::push()", commands = commands.len());
+        if commands.is_empty() {
+            indicate_end_of_interaction(transport, trace)?;
+            return Ok(Outcome::default());
+        }
+        if !capabilities.contains("report-status") {
+            return Err(Error::ReportStatusUnsupported);
+        }
+        let agreed = Features::from_capabilities(capabilities);
+        if commands.iter().any(Command::is_delete) && !agreed.delete_refs {
+            return Err(Error::DeleteRefsUnsupported);
+        }
+
+        let mut features = vec!["report-status".to_owned()];
+        for (name, enabled) in [
+            ("side-band-64k", agreed.side_band_64k),
+            ("delete-refs", agreed.delete_refs),
+            ("ofs-delta", agreed.ofs_delta),
+        ] {
+            if enabled {
+                features.push(name.into());
+            }
+        }
+        if let Some(agent) = agent {
+            features.push(format!("agent={agent}"));
+        }
+
+        let mut writer = transport.request(WriteMode::Binary, MessageKind::Flush, trace)?;
+        for (idx, command) in commands.iter().enumerate() {
+            let mut line = BString::from(format!("{} {} {}", command.old, command.new, command.ref_name));
+            if idx == 0 {
+                line.push(0);
+                line.extend_from_slice(format!(" {}", features.join(" ")).as_bytes());
+            }
+            writer.write_all(&line)?;
+        }
+        writer.write_message(MessageKind::Flush)?;
+
+        let (mut write, mut read) = writer.into_parts();
+        if !commands.iter().all(Command::is_delete) {
+            write_pack(&mut write, agreed).map_err(Error::WritePack)?;
+        }
+        write.flush()?;
+        // Finish the request before reading the response, as this might be required by the transport.
+        drop(write);
+
+        let mut lines = Vec::new();
+        if agreed.side_band_64k {
+            read.set_progress_handler(Some(Box::new({
+                let mut remote_progress = progress.add_child("remote");
+                move |is_err: bool, data: &[u8]| {
+                    crate::RemoteProgress::translate_to_progress(is_err, data, &mut remote_progress);
+                    gix_transport::packetline::read::ProgressAction::Continue
+                }
+            }) as gix_transport::client::HandleProgress<'_>));
+            // The status report is packetline-encoded within the data band.
+            let mut report = StreamingPeekableIter::new(read, &[PacketLineRef::Flush], trace);
+            while let Some(line) = report.read_line() {
+                if let Some(line) = line??.as_bstr() {
+                    lines.push(line.trim_end_with(|c| c == '\n').into());
+                }
+            }
+            std::io::copy(&mut report.into_inner(), &mut std::io::sink())?;
+        } else {
+            for line in read.lines() {
+                lines.push(BString::from(line?));
+            }
+        }
+        parse_report(lines)
+    }
+
+    fn parse_report(lines: Vec<BString>) -> Result<Outcome, Error> {
+        let mut lines = lines.into_iter();
+        let unpack_error = match lines.next() {
+            Some(line) => match line.strip_prefix(b"unpack ") {
+                Some(b"ok") => None,
+                Some(reason) => Some(reason.into()),
+                None => return Err(Error::ParseStatusReport { line }),
+            },
+            None => return Err(Error::ParseStatusReport { line: "".into() }),
+        };
+        let refs = lines
+            .map(|line| {
+                if let Some(ref_name) = line.strip_prefix(b"ok ") {
+                    return Ok(RefStatus {
+                        ref_name: ref_name.into(),
+                        error: None,
+                    });
+                }
+                match line.strip_prefix(b"ng ").and_then(|rest| rest.split_once_str(" ")) {
+                    Some((ref_name, reason)) => Ok(RefStatus {
+                        ref_name: ref_name.into(),
+                        error: Some(reason.into()),
+                    }),
+                    None => Err(Error::ParseStatusReport { line }),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Outcome { unpack_error, refs })
+    }
+}
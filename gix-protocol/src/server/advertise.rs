@@ -0,0 +1,103 @@
+use std::io;
+
+use bstr::{BStr, BString};
+use gix_transport::packetline::encode;
+
+use super::{authorize, Action, Error, Hooks, LsRefsArguments};
+use crate::handshake::Ref;
+
+/// Write the ref advertisement of protocol V0 and V1 for `refs` along with `capabilities` to `out`, as expected by clients
+/// of `upload-pack` and `receive-pack`, and return the refs that were advertised.
+///
+/// The client must be authorized for [`Action::ListRefs`] by `hooks`, and only refs `hooks` want to [advertise](Hooks::advertise())
+/// are written. The targets of symbolic refs are added to the `capabilities` as `symref=<name>:<target>`, while unborn refs
+/// can't be advertised with this protocol and are skipped.
+/// If no ref is advertised, the `capabilities` are sent along with a null id of kind `object_hash`.
+pub fn write_v1_advertisement<C>(
+    refs: &[Ref],
+    capabilities: impl IntoIterator<Item = C>,
+    object_hash: gix_hash::Kind,
+    mut hooks: impl Hooks,
+    mut out: impl io::Write,
+) -> Result<Vec<&Ref>, Error>
+where
+    C: AsRef<BStr>,
+{
+    authorize(&mut hooks, Action::ListRefs)?;
+    let advertised: Vec<_> = refs
+        .iter()
+        .filter(|r| !matches!(r, Ref::Unborn { .. }) && hooks.advertise(r))
+        .collect();
+
+    let mut capabilities: Vec<BString> = capabilities.into_iter().map(|c| c.as_ref().to_owned()).collect();
+    capabilities.extend(advertised.iter().filter_map(|r| match r {
+        Ref::Symbolic {
+            full_ref_name, target, ..
+        } => Some(format!("symref={full_ref_name}:{target}").into()),
+        _ => None,
+    }));
+    let capabilities = capabilities.join(&b' ');
+
+    let mut line = Vec::new();
+    if advertised.is_empty() {
+        line.extend_from_slice(object_hash.null().to_hex().to_string().as_bytes());
+        line.extend_from_slice(b" capabilities^{}\0");
+        line.extend_from_slice(&capabilities);
+        line.push(b'\n');
+        encode::data_to_write(&line, &mut out)?;
+    }
+    for (idx, r) in advertised.iter().enumerate() {
+        let (full_ref_name, id, peeled) = r.unpack();
+        let id = id.expect("unborn refs were skipped");
+        line.clear();
+        line.extend_from_slice(format!("{id} {full_ref_name}").as_bytes());
+        if idx == 0 {
+            line.push(0);
+            line.extend_from_slice(&capabilities);
+        }
+        line.push(b'\n');
+        encode::data_to_write(&line, &mut out)?;
+        if let Some(peeled) = peeled {
+            encode::data_to_write(format!("{peeled} {full_ref_name}^{{}}\n").as_bytes(), &mut out)?;
+        }
+    }
+    encode::flush_to_write(&mut out)?;
+    Ok(advertised)
+}
+
+/// Write the response to the V2 `ls-refs` command with `arguments` for `refs` to `out`, and return the refs that were listed.
+///
+/// The client must be authorized for [`Action::ListRefs`] by `hooks`, and only refs `hooks` want to [advertise](Hooks::advertise())
+/// and which match the `ref-prefix` arguments are written.
+pub fn write_ls_refs_response<'a>(
+    refs: &'a [Ref],
+    arguments: &LsRefsArguments,
+    mut hooks: impl Hooks,
+    mut out: impl io::Write,
+) -> Result<Vec<&'a Ref>, Error> {
+    authorize(&mut hooks, Action::ListRefs)?;
+    let mut listed = Vec::new();
+    for r in refs {
+        let (full_ref_name, id, peeled) = r.unpack();
+        if !arguments.matches(full_ref_name) || (id.is_none() && !arguments.unborn) || !hooks.advertise(r) {
+            continue;
+        }
+        let mut line = match id {
+            Some(id) => format!("{id} {full_ref_name}"),
+            None => format!("unborn {full_ref_name}"),
+        };
+        if arguments.symrefs {
+            if let Ref::Symbolic { target, .. } | Ref::Unborn { target, .. } = r {
+                line.push_str(&format!(" symref-target:{target}"));
+            }
+        }
+        if let Some(peeled) = peeled.filter(|_| arguments.peel) {
+            line.push_str(&format!(" peeled:{peeled}"));
+        }
+        line.push('\n');
+        encode::data_to_write(line.as_bytes(), &mut out)?;
+        listed.push(r);
+    }
+    encode::flush_to_write(&mut out)?;
+    Ok(listed)
+}
@@ -0,0 +1,80 @@
+use bstr::{BStr, BString, ByteSlice};
+
+use super::{Action, Hooks};
+use crate::handshake::Ref;
+
+/// Refs to hide from clients, as configured with `uploadpack.hideRefs` and `transfer.hideRefs`.
+///
+/// A ref is hidden if it's equal to a pattern or is located in a directory named like it, unless a later pattern
+/// starting with `!` matches it. Patterns starting with `^` match the full ref name which includes the namespace of
+/// the repository, if there is one.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HiddenRefs {
+    patterns: Vec<BString>,
+}
+
+impl HiddenRefs {
+    /// Create an instance from the `values` of `transfer.hideRefs` and `uploadpack.hideRefs`, in the order they
+    /// appear in the configuration.
+    pub fn from_values<'a>(values: impl IntoIterator<Item = &'a BStr>) -> Self {
+        HiddenRefs {
+            patterns: values
+                .into_iter()
+                .map(|value| value.trim_end_with(|c| c == '/').into())
+                .collect(),
+        }
+    }
+
+    /// Return `true` if there are no patterns, so no ref is hidden.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Return `true` if the ref at `name` should be hidden, with `full_name` being its name including the namespace
+    /// of the repository, or the same as `name` if there is none.
+    pub fn is_hidden(&self, name: &BStr, full_name: &BStr) -> bool {
+        for pattern in self.patterns.iter().rev() {
+            let (is_negated, pattern) = match pattern.strip_prefix(b"!") {
+                Some(pattern) => (true, pattern),
+                None => (false, pattern.as_slice()),
+            };
+            let (subject, pattern) = match pattern.strip_prefix(b"^") {
+                Some(pattern) => (full_name, pattern),
+                None => (name, pattern),
+            };
+            let is_match = subject
+                .strip_prefix(pattern)
+                .map_or(false, |rest| rest.is_empty() || rest[0] == b'/');
+            if is_match {
+                return !is_negated;
+            }
+        }
+        false
+    }
+}
+
+/// Hide all matching refs from clients, and don't allow to fetch or update them by name.
+///
+/// Note that refs are expected to not be in a namespace.
+impl Hooks for HiddenRefs {
+    fn advertise(&mut self, r: &Ref) -> bool {
+        let name = r.unpack().0;
+        !self.is_hidden(name, name)
+    }
+
+    fn authorize(&mut self, action: Action<'_>) -> Result<(), BString> {
+        let name = match action {
+            Action::ListRefs | Action::Fetch { id: _, ref_name: None } => return Ok(()),
+            Action::Fetch {
+                ref_name: Some(name), ..
+            }
+            | Action::Update { ref_name: name, .. } => name,
+        };
+        if self.is_hidden(name, name) {
+            Err(format!("{name} is hidden").into())
+        } else {
+            Ok(())
+        }
+    }
+}
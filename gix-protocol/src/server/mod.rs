@@ -0,0 +1,141 @@
+//! Building blocks for servers to advertise refs to clients and to decide what clients may see and do.
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::handshake::Ref;
+
+/// Something a client wants to do, to be allowed or denied by [`Hooks::authorize()`].
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+pub enum Action<'a> {
+    /// List the refs of the repository, either with the initial advertisement of protocol V0 and V1, or with `ls-refs`.
+    ListRefs,
+    /// Fetch the object `id`, which was requested with a `want` line, or with a `want-ref` line for `ref_name`.
+    Fetch {
+        /// The object the client wants.
+        id: &'a gix_hash::oid,
+        /// The name of the ref the client asked for if it used `want-ref`.
+        ref_name: Option<&'a BStr>,
+    },
+    /// Change the ref at `ref_name` from `old` to `new` as part of a push.
+    Update {
+        /// The name of the ref to change, like `refs/heads/main`.
+        ref_name: &'a BStr,
+        /// The id the ref is expected to point to, or the null id if it is expected to not exist.
+        old: &'a gix_hash::oid,
+        /// The id the ref should point to, or the null id if it should be deleted.
+        new: &'a gix_hash::oid,
+    },
+}
+
+/// Hooks to let servers decide which refs a client gets to see and what it may do with them, which allows hosting services
+/// to implement per-user visibility and permissions.
+///
+/// All methods allow everything by default.
+pub trait Hooks {
+    /// Return `true` if `r` should be advertised to the client, or `false` to hide it.
+    fn advertise(&mut self, r: &Ref) -> bool {
+        let _ = r;
+        true
+    }
+
+    /// Return `Ok(())` if the client may perform `action`, or `Err(reason)` with a reason to tell the client otherwise.
+    fn authorize(&mut self, action: Action<'_>) -> Result<(), BString> {
+        let _ = action;
+        Ok(())
+    }
+}
+
+/// Advertise all refs and allow all actions.
+impl Hooks for () {}
+
+impl<T: Hooks + ?Sized> Hooks for &mut T {
+    fn advertise(&mut self, r: &Ref) -> bool {
+        (**self).advertise(r)
+    }
+
+    fn authorize(&mut self, action: Action<'_>) -> Result<(), BString> {
+        (**self).authorize(action)
+    }
+}
+
+/// Advertise refs and allow actions only if both hooks agree, asking the first one first.
+impl<A: Hooks, B: Hooks> Hooks for (A, B) {
+    fn advertise(&mut self, r: &Ref) -> bool {
+        self.0.advertise(r) && self.1.advertise(r)
+    }
+
+    fn authorize(&mut self, action: Action<'_>) -> Result<(), BString> {
+        self.0.authorize(action)?;
+        self.1.authorize(action)
+    }
+}
+
+/// The arguments of the V2 `ls-refs` command as sent by a client.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LsRefsArguments {
+    /// If `true`, the targets of symbolic refs are sent as well.
+    pub symrefs: bool,
+    /// If `true`, the objects annotated tags point to are sent as well.
+    pub peel: bool,
+    /// If `true`, symbolic refs pointing to refs that don't exist are sent as well.
+    pub unborn: bool,
+    /// Only send refs starting with one of these prefixes, or all refs if empty.
+    pub ref_prefixes: Vec<BString>,
+}
+
+impl LsRefsArguments {
+    /// Parse the argument `lines` the client sent with the `ls-refs` command, with or without trailing newline.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Self, Error> {
+        let mut out = LsRefsArguments::default();
+        for line in lines {
+            let line = line.trim_end_with(|c| c == '\n');
+            match line {
+                b"symrefs" => out.symrefs = true,
+                b"peel" => out.peel = true,
+                b"unborn" => out.unborn = true,
+                _ => match line.strip_prefix(b"ref-prefix ") {
+                    Some(prefix) => out.ref_prefixes.push(prefix.into()),
+                    None => return Err(Error::UnknownArgument { line: line.into() }),
+                },
+            }
+        }
+        Ok(out)
+    }
+
+    fn matches(&self, full_ref_name: &BStr) -> bool {
+        self.ref_prefixes.is_empty() || self.ref_prefixes.iter().any(|prefix| full_ref_name.starts_with(prefix))
+    }
+}
+
+mod error {
+    use bstr::BString;
+
+    /// The error returned by functions in the [`server`][crate::server] module.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("{reason}")]
+        Unauthorized { reason: BString },
+        #[error("Unexpected argument line {line:?}")]
+        UnknownArgument { line: BString },
+        #[error("upload-pack: not our ref {id}")]
+        WantNotAllowed { id: gix_hash::ObjectId },
+    }
+}
+pub use error::Error;
+
+/// Ask `hooks` if the client may perform `action`, and turn a denial into an [`Error::Unauthorized`].
+pub fn authorize(hooks: &mut impl Hooks, action: Action<'_>) -> Result<(), Error> {
+    hooks.authorize(action).map_err(|reason| Error::Unauthorized { reason })
+}
+
+mod advertise;
+pub use advertise::{write_ls_refs_response, write_v1_advertisement};
+
+mod hidden_refs;
+pub use hidden_refs::HiddenRefs;
+
+mod want;
+pub use want::{check_want, WantPolicy};
@@ -0,0 +1,65 @@
+use super::Error;
+use crate::handshake::Ref;
+
+/// The objects a client may ask for with `want` lines, as configured with `uploadpack.allowTipSHA1InWant`,
+/// `uploadpack.allowReachableSHA1InWant` and `uploadpack.allowAnySHA1InWant`.
+///
+/// Each policy allows everything the previous ones allow.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WantPolicy {
+    /// Only the objects advertised refs point to may be wanted, which is the default.
+    #[default]
+    Advertised,
+    /// The objects hidden refs point to may be wanted as well.
+    AllowTip,
+    /// All objects reachable from advertised refs may be wanted as well.
+    AllowReachable,
+    /// Any object may be wanted.
+    AllowAny,
+}
+
+impl WantPolicy {
+    /// Create the policy from the values of `uploadpack.allowTipSHA1InWant`, `uploadpack.allowReachableSHA1InWant`
+    /// and `uploadpack.allowAnySHA1InWant`, using the most permissive one that is set.
+    pub fn from_config(allow_tip: bool, allow_reachable: bool, allow_any: bool) -> Self {
+        if allow_any {
+            WantPolicy::AllowAny
+        } else if allow_reachable {
+            WantPolicy::AllowReachable
+        } else if allow_tip {
+            WantPolicy::AllowTip
+        } else {
+            WantPolicy::Advertised
+        }
+    }
+}
+
+/// Check if a client may want the object `id` according to `policy`, and fail with [`Error::WantNotAllowed`] otherwise.
+///
+/// `advertised` are the refs the client was shown, while `refs` are all refs of the repository including hidden ones.
+/// `is_reachable(id)` is only called with [`WantPolicy::AllowReachable`] if `id` isn't the tip of any ref, and is
+/// expected to return `true` if `id` can be reached from one of the `advertised` refs.
+pub fn check_want(
+    id: &gix_hash::oid,
+    policy: WantPolicy,
+    advertised: &[&Ref],
+    refs: &[Ref],
+    is_reachable: impl FnOnce(&gix_hash::oid) -> bool,
+) -> Result<(), Error> {
+    let is_tip = |r: &Ref| {
+        let (_, tip, peeled) = r.unpack();
+        tip == Some(id) || peeled == Some(id)
+    };
+    let is_allowed = match policy {
+        WantPolicy::Advertised => advertised.iter().any(|r| is_tip(r)),
+        WantPolicy::AllowTip => refs.iter().any(is_tip),
+        WantPolicy::AllowReachable => refs.iter().any(is_tip) || is_reachable(id),
+        WantPolicy::AllowAny => true,
+    };
+    if is_allowed {
+        Ok(())
+    } else {
+        Err(Error::WantNotAllowed { id: id.to_owned() })
+    }
+}
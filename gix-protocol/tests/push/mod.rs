@@ -0,0 +1,172 @@
+use bstr::ByteSlice;
+use gix_protocol::{handshake, push};
+use gix_transport::{
+    client::{git::ConnectMode, Capabilities},
+    Protocol, Service,
+};
+
+use crate::fetch::{oid, transport};
+
+#[allow(clippy::result_large_err)]
+fn helper_unused(_action: gix_credentials::helper::Action) -> gix_credentials::protocol::Result {
+    panic!("Call to credentials helper is unexpected")
+}
+
+#[test]
+fn commands_and_pack_are_sent_and_status_report_is_parsed() -> crate::Result {
+    let mut out = Vec::new();
+    let mut t = transport(&mut out, "v1/push.response", Protocol::V1, ConnectMode::Process);
+    let outcome = gix_protocol::handshake(
+        &mut t,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut gix_features::progress::Discard,
+    )?;
+    assert_eq!(outcome.server_protocol_version, Protocol::V1);
+    assert_eq!(
+        outcome.refs.expect("present in V1"),
+        [
+            handshake::Ref::Direct {
+                full_ref_name: "refs/heads/main".into(),
+                object: oid("7c09ba0c4c3680af369bda4fc8e3c58d3fccdc76"),
+            },
+            handshake::Ref::Direct {
+                full_ref_name: "refs/heads/other".into(),
+                object: oid("f1e8e2b6bb4d1b51c5b6f8e5d2c2bbd8c6c7d1a5"),
+            }
+        ]
+    );
+
+    let commands = [
+        push::Command {
+            ref_name: "refs/heads/main".into(),
+            old: oid("7c09ba0c4c3680af369bda4fc8e3c58d3fccdc76"),
+            new: oid("32690d87d3943c7c0dda81246d0cde344ca7e633"),
+        },
+        push::Command {
+            ref_name: "refs/heads/other".into(),
+            old: oid("f1e8e2b6bb4d1b51c5b6f8e5d2c2bbd8c6c7d1a5"),
+            new: oid("32690d87d3943c7c0dda81246d0cde344ca7e633"),
+        },
+    ];
+    let outcome = gix_protocol::push(
+        &mut t,
+        &outcome.capabilities,
+        &commands,
+        |out, features| {
+            assert_eq!(
+                features,
+                push::Features {
+                    side_band_64k: true,
+                    delete_refs: true,
+                    ofs_delta: true,
+                },
+                "all features are advertised by the server"
+            );
+            Ok(out.write_all(b"PACK and more")?)
+        },
+        Some("git/oxide-test"),
+        &mut gix_features::progress::Discard,
+        false,
+    )?;
+    assert_eq!(
+        outcome,
+        push::Outcome {
+            unpack_error: None,
+            refs: vec![
+                push::RefStatus {
+                    ref_name: "refs/heads/main".into(),
+                    error: None
+                },
+                push::RefStatus {
+                    ref_name: "refs/heads/other".into(),
+                    error: Some("non-fast-forward".into())
+                }
+            ]
+        }
+    );
+    assert!(!outcome.is_success(), "one ref was rejected");
+
+    drop(t);
+    assert_eq!(
+        out.as_bstr(),
+        b"00ad7c09ba0c4c3680af369bda4fc8e3c58d3fccdc76 32690d87d3943c7c0dda81246d0cde344ca7e633 refs/heads/main\0 report-status side-band-64k delete-refs ofs-delta agent=git/oxide-test\
+          0066f1e8e2b6bb4d1b51c5b6f8e5d2c2bbd8c6c7d1a5 32690d87d3943c7c0dda81246d0cde344ca7e633 refs/heads/other\
+          0000PACK and more"
+            .as_bstr()
+    );
+    Ok(())
+}
+
+#[test]
+fn deletions_require_server_support() -> crate::Result {
+    let mut t = transport(Vec::new(), "v1/push.response", Protocol::V1, ConnectMode::Process);
+    let (capabilities, _) = Capabilities::from_bytes(b"\0report-status side-band-64k")?;
+    let err = gix_protocol::push(
+        &mut t,
+        &capabilities,
+        &[push::Command {
+            ref_name: "refs/heads/main".into(),
+            old: oid("7c09ba0c4c3680af369bda4fc8e3c58d3fccdc76"),
+            new: gix_hash::Kind::Sha1.null(),
+        }],
+        |_out, _features| unreachable!("there is nothing to send"),
+        None,
+        &mut gix_features::progress::Discard,
+        false,
+    )
+    .unwrap_err();
+    assert!(matches!(err, push::Error::DeleteRefsUnsupported), "{err:?}");
+    Ok(())
+}
+
+#[test]
+fn packs_only_use_ofs_deltas_if_the_server_supports_them() -> crate::Result {
+    let mut out = Vec::new();
+    let mut t = transport(&mut out, "v1/push.response", Protocol::V1, ConnectMode::Process);
+    gix_protocol::handshake(
+        &mut t,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut gix_features::progress::Discard,
+    )?;
+    let (capabilities, _) = Capabilities::from_bytes(b"\0report-status side-band-64k")?;
+    let mut agreed = None;
+    gix_protocol::push(
+        &mut t,
+        &capabilities,
+        &[push::Command {
+            ref_name: "refs/heads/main".into(),
+            old: oid("7c09ba0c4c3680af369bda4fc8e3c58d3fccdc76"),
+            new: oid("32690d87d3943c7c0dda81246d0cde344ca7e633"),
+        }],
+        |out, features| {
+            agreed = Some(features);
+            Ok(out.write_all(b"PACK")?)
+        },
+        None,
+        &mut gix_features::progress::Discard,
+        false,
+    )?;
+    assert_eq!(
+        agreed.expect("a pack was written"),
+        push::Features {
+            side_band_64k: true,
+            delete_refs: false,
+            ofs_delta: false,
+        },
+        "the pack writer learns that it must not use offset deltas"
+    );
+
+    drop(t);
+    assert_eq!(
+        out.as_bstr(),
+        b"00827c09ba0c4c3680af369bda4fc8e3c58d3fccdc76 32690d87d3943c7c0dda81246d0cde344ca7e633 refs/heads/main\0 report-status side-band-64k\
+          0000PACK"
+            .as_bstr(),
+        "ofs-delta isn't requested either"
+    );
+    Ok(())
+}
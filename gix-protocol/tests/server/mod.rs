@@ -0,0 +1,395 @@
+use bstr::{BStr, BString};
+use gix_protocol::{
+    handshake::Ref,
+    server::{self, Action, LsRefsArguments},
+};
+
+use crate::fetch::oid;
+
+fn refs() -> Vec<Ref> {
+    vec![
+        Ref::Symbolic {
+            full_ref_name: "HEAD".into(),
+            target: "refs/heads/main".into(),
+            tag: None,
+            object: oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+        },
+        Ref::Direct {
+            full_ref_name: "refs/heads/main".into(),
+            object: oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+        },
+        Ref::Direct {
+            full_ref_name: "refs/heads/private".into(),
+            object: oid("7fe1b98b39423b71e14217aa299a03b7c937d6ff"),
+        },
+        Ref::Peeled {
+            full_ref_name: "refs/tags/v1.0".into(),
+            tag: oid("7fe1b98b39423b71e14217aa299a03b7c937d656"),
+            object: oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+        },
+        Ref::Unborn {
+            full_ref_name: "refs/heads/symbolic".into(),
+            target: "refs/heads/unborn".into(),
+        },
+    ]
+}
+
+/// Hide `refs/heads/private` and don't allow to list refs or to update any ref if `deny` is set.
+#[derive(Default)]
+struct Hooks {
+    deny: bool,
+    actions: Vec<String>,
+}
+
+impl server::Hooks for Hooks {
+    fn advertise(&mut self, r: &Ref) -> bool {
+        r.unpack().0 != "refs/heads/private"
+    }
+
+    fn authorize(&mut self, action: Action<'_>) -> Result<(), BString> {
+        self.actions.push(format!("{action:?}"));
+        if self.deny && !matches!(action, Action::Fetch { .. }) {
+            Err("permission denied".into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn packet_lines(lines: &[&str]) -> BString {
+    let mut out = Vec::new();
+    for line in lines {
+        gix_packetline::encode::data_to_write(line.as_bytes(), &mut out).expect("write to vec");
+    }
+    gix_packetline::encode::flush_to_write(&mut out).expect("write to vec");
+    out.into()
+}
+
+mod v1 {
+    use bstr::ByteSlice;
+    use gix_protocol::server;
+
+    use super::{packet_lines, refs, Hooks};
+
+    #[test]
+    fn advertisement_skips_hidden_and_unborn_refs_and_announces_symrefs() -> crate::Result {
+        let refs = refs();
+        let mut hooks = Hooks::default();
+        let mut out = Vec::new();
+        let advertised = server::write_v1_advertisement(
+            &refs,
+            ["multi_ack", "side-band-64k"],
+            gix_hash::Kind::Sha1,
+            &mut hooks,
+            &mut out,
+        )?;
+        assert_eq!(
+            advertised,
+            [&refs[0], &refs[1], &refs[3]],
+            "the private ref is hidden by the hooks, and unborn refs can't be advertised in V1"
+        );
+        assert_eq!(
+            out.as_bstr(),
+            packet_lines(&[
+                "808e50d724f604f69ab93c6da2919c014667bedb HEAD\0multi_ack side-band-64k symref=HEAD:refs/heads/main\n",
+                "808e50d724f604f69ab93c6da2919c014667bedb refs/heads/main\n",
+                "7fe1b98b39423b71e14217aa299a03b7c937d656 refs/tags/v1.0\n",
+                "808e50d724f604f69ab93c6da2919c014667bedb refs/tags/v1.0^{}\n",
+            ])
+        );
+        assert_eq!(hooks.actions, ["ListRefs"]);
+        Ok(())
+    }
+
+    #[test]
+    fn advertisement_without_refs_sends_capabilities_only() -> crate::Result {
+        let mut out = Vec::new();
+        let advertised = server::write_v1_advertisement(
+            &[],
+            ["report-status", "delete-refs"],
+            gix_hash::Kind::Sha1,
+            (),
+            &mut out,
+        )?;
+        assert!(advertised.is_empty());
+        assert_eq!(
+            out.as_bstr(),
+            packet_lines(&["0000000000000000000000000000000000000000 capabilities^{}\0report-status delete-refs\n"])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn advertisement_fails_if_listing_refs_is_not_authorized() {
+        let mut out = Vec::new();
+        let err = server::write_v1_advertisement(
+            &refs(),
+            ["multi_ack"],
+            gix_hash::Kind::Sha1,
+            Hooks {
+                deny: true,
+                ..Default::default()
+            },
+            &mut out,
+        )
+        .unwrap_err();
+        assert!(matches!(err, server::Error::Unauthorized { reason } if reason == "permission denied"));
+        assert!(out.is_empty(), "nothing is written if the client may not list refs");
+    }
+}
+
+mod v2 {
+    use bstr::ByteSlice;
+    use gix_protocol::{handshake, server, server::LsRefsArguments};
+
+    use super::{packet_lines, refs, Hooks};
+
+    #[test]
+    fn ls_refs_response_respects_arguments_and_hooks() -> crate::Result {
+        let refs = refs();
+        let mut out = Vec::new();
+        let arguments = LsRefsArguments::from_lines(
+            [
+                "symrefs\n",
+                "peel",
+                "unborn",
+                "ref-prefix HEAD",
+                "ref-prefix refs/heads/",
+            ]
+            .into_iter()
+            .map(|line| line.as_bytes().as_bstr()),
+        )?;
+        let listed = server::write_ls_refs_response(&refs, &arguments, Hooks::default(), &mut out)?;
+        assert_eq!(listed, [&refs[0], &refs[1], &refs[4]]);
+        assert_eq!(
+            out.as_bstr(),
+            packet_lines(&[
+                "808e50d724f604f69ab93c6da2919c014667bedb HEAD symref-target:refs/heads/main\n",
+                "808e50d724f604f69ab93c6da2919c014667bedb refs/heads/main\n",
+                "unborn refs/heads/symbolic symref-target:refs/heads/unborn\n",
+            ])
+        );
+
+        let mut lines =
+            gix_packetline::StreamingPeekableIter::new(out.as_slice(), &[gix_packetline::PacketLineRef::Flush], false);
+        let mut reader = lines.as_read_without_sidebands::<fn(bool, &[u8]) -> gix_packetline::read::ProgressAction>();
+        assert_eq!(
+            handshake::refs::from_v2_refs(&mut reader)?,
+            listed.into_iter().cloned().collect::<Vec<_>>(),
+            "clients see exactly the refs that were listed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ls_refs_response_without_arguments_lists_only_existing_refs() -> crate::Result {
+        let refs = refs();
+        let mut out = Vec::new();
+        let listed = server::write_ls_refs_response(&refs, &LsRefsArguments::default(), (), &mut out)?;
+        assert_eq!(listed, [&refs[0], &refs[1], &refs[2], &refs[3]]);
+        assert_eq!(
+            out.as_bstr(),
+            packet_lines(&[
+                "808e50d724f604f69ab93c6da2919c014667bedb HEAD\n",
+                "808e50d724f604f69ab93c6da2919c014667bedb refs/heads/main\n",
+                "7fe1b98b39423b71e14217aa299a03b7c937d6ff refs/heads/private\n",
+                "7fe1b98b39423b71e14217aa299a03b7c937d656 refs/tags/v1.0\n",
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ls_refs_arguments_must_be_known() {
+        let err = LsRefsArguments::from_lines(Some(b"ref-prefix".as_bstr())).unwrap_err();
+        assert!(matches!(err, server::Error::UnknownArgument { line } if line == "ref-prefix"));
+    }
+}
+
+#[test]
+fn authorize_turns_denials_into_errors() {
+    let mut hooks = Hooks {
+        deny: true,
+        ..Default::default()
+    };
+    let id = oid("808e50d724f604f69ab93c6da2919c014667bedb");
+    server::authorize(
+        &mut hooks,
+        Action::Fetch {
+            id: &id,
+            ref_name: Some(BStr::new("refs/heads/main")),
+        },
+    )
+    .expect("fetches are allowed");
+    let err = server::authorize(
+        &mut hooks,
+        Action::Update {
+            ref_name: "refs/heads/main".into(),
+            old: &id,
+            new: gix_hash::Kind::Sha1.null_ref(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "permission denied");
+    assert_eq!(hooks.actions.len(), 2, "the hooks see every action");
+    server::authorize(&mut (), Action::ListRefs).expect("everything is allowed by default");
+}
+
+#[test]
+fn default_ls_refs_arguments_are_empty() {
+    assert_eq!(
+        LsRefsArguments::from_lines(None::<&BStr>).expect("no lines are fine"),
+        LsRefsArguments::default()
+    );
+}
+
+mod hidden_refs {
+    use bstr::ByteSlice;
+    use gix_protocol::server::{self, Action, HiddenRefs, Hooks as _};
+
+    use super::{refs, Hooks};
+    use crate::fetch::oid;
+
+    fn hidden_refs(values: &[&str]) -> HiddenRefs {
+        HiddenRefs::from_values(values.iter().map(|value| value.as_bytes().as_bstr()))
+    }
+
+    #[test]
+    fn patterns_match_names_and_directories_and_can_be_negated() {
+        let hidden = hidden_refs(&[
+            "refs/pull/",
+            "!refs/pull/1",
+            "refs/heads/private",
+            "^refs/namespaces/other",
+        ]);
+        for (name, full_name, expected) in [
+            ("refs/pull/2/head", "refs/pull/2/head", true),
+            ("refs/pull/1/head", "refs/pull/1/head", false),
+            ("refs/pull/10/head", "refs/pull/10/head", true),
+            ("refs/pulls", "refs/pulls", false),
+            ("refs/heads/private", "refs/heads/private", true),
+            ("refs/heads/private/topic", "refs/heads/private/topic", true),
+            ("refs/heads/private-topic", "refs/heads/private-topic", false),
+            ("refs/heads/main", "refs/namespaces/other/refs/heads/main", true),
+            ("refs/heads/main", "refs/namespaces/ours/refs/heads/main", false),
+            ("refs/namespaces/other", "refs/namespaces/other", true),
+        ] {
+            assert_eq!(
+                hidden.is_hidden(name.into(), full_name.into()),
+                expected,
+                "{name} ({full_name})"
+            );
+        }
+        assert!(hidden_refs(&[]).is_empty());
+    }
+
+    #[test]
+    fn later_patterns_override_earlier_ones() {
+        let hidden = hidden_refs(&["!refs/tags/", "refs/tags/"]);
+        assert!(hidden.is_hidden("refs/tags/v1.0".into(), "refs/tags/v1.0".into()));
+        let hidden = hidden_refs(&["refs/tags/", "!refs/tags/"]);
+        assert!(!hidden.is_hidden("refs/tags/v1.0".into(), "refs/tags/v1.0".into()));
+    }
+
+    #[test]
+    fn as_hooks_they_hide_refs_and_prevent_access_by_name() -> crate::Result {
+        let mut hidden = hidden_refs(&["refs/heads/private"]);
+        let refs = refs();
+        let mut out = Vec::new();
+        let advertised =
+            server::write_v1_advertisement(&refs, ["multi_ack"], gix_hash::Kind::Sha1, &mut hidden, &mut out)?;
+        assert_eq!(advertised, [&refs[0], &refs[1], &refs[3]]);
+
+        let id = oid("7fe1b98b39423b71e14217aa299a03b7c937d6ff");
+        assert!(hidden
+            .authorize(Action::Fetch {
+                id: &id,
+                ref_name: Some("refs/heads/private".into()),
+            })
+            .is_err());
+        assert!(hidden
+            .authorize(Action::Update {
+                ref_name: "refs/heads/private".into(),
+                old: &id,
+                new: &id,
+            })
+            .is_err());
+        assert!(
+            hidden
+                .authorize(Action::Fetch {
+                    id: &id,
+                    ref_name: None,
+                })
+                .is_ok(),
+            "want lines are checked with the want policy instead"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn combined_with_other_hooks_both_must_agree() -> crate::Result {
+        let refs = refs();
+        let mut out = Vec::new();
+        let mut hooks = (hidden_refs(&["refs/tags"]), Hooks::default());
+        let advertised = server::write_ls_refs_response(&refs, &Default::default(), &mut hooks, &mut out)?;
+        assert_eq!(
+            advertised,
+            [&refs[0], &refs[1]],
+            "both, tags and the private ref, are hidden"
+        );
+        assert_eq!(hooks.1.actions, ["ListRefs"]);
+        Ok(())
+    }
+}
+
+mod want {
+    use gix_protocol::server::{self, WantPolicy};
+
+    use super::refs;
+    use crate::fetch::oid;
+
+    #[test]
+    fn policy_from_config_uses_the_most_permissive_setting() {
+        assert_eq!(WantPolicy::from_config(false, false, false), WantPolicy::Advertised);
+        assert_eq!(WantPolicy::from_config(true, false, false), WantPolicy::AllowTip);
+        assert_eq!(WantPolicy::from_config(true, true, false), WantPolicy::AllowReachable);
+        assert_eq!(WantPolicy::from_config(false, true, true), WantPolicy::AllowAny);
+        assert_eq!(WantPolicy::default(), WantPolicy::Advertised);
+    }
+
+    #[test]
+    fn each_policy_allows_more_objects() {
+        let refs = refs();
+        let advertised = [&refs[0], &refs[1], &refs[3]];
+        let advertised_tip = oid("808e50d724f604f69ab93c6da2919c014667bedb");
+        let tag = oid("7fe1b98b39423b71e14217aa299a03b7c937d656");
+        let hidden_tip = oid("7fe1b98b39423b71e14217aa299a03b7c937d6ff");
+        let reachable = oid("4d979abcde5cea47b079c38850828956c9382a56");
+        let other = oid("978f927e6397113757dfec6332e7d9c7e356ac25");
+
+        let is_allowed =
+            |id, policy| server::check_want(id, policy, &advertised, &refs, |id| id == reachable.as_ref()).is_ok();
+        for (policy, expected) in [
+            (WantPolicy::Advertised, [true, true, false, false, false]),
+            (WantPolicy::AllowTip, [true, true, true, false, false]),
+            (WantPolicy::AllowReachable, [true, true, true, true, false]),
+            (WantPolicy::AllowAny, [true, true, true, true, true]),
+        ] {
+            assert_eq!(
+                [&advertised_tip, &tag, &hidden_tip, &reachable, &other].map(|id| is_allowed(id, policy)),
+                expected,
+                "{policy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn disallowed_wants_are_reported_like_git_does() {
+        let id = oid("978f927e6397113757dfec6332e7d9c7e356ac25");
+        let err = server::check_want(&id, WantPolicy::AllowReachable, &[], &refs(), |_| false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "upload-pack: not our ref 978f927e6397113757dfec6332e7d9c7e356ac25"
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use std::{convert::TryInto, io::Read, path::PathBuf};
+use std::{
+    convert::TryInto,
+    io::{Read, Write},
+    path::PathBuf,
+};
 
 use crate::{
     store_impl::{file, file::log},
@@ -73,6 +77,41 @@ impl file::Store {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Insert `lines` before all entries of the reflog of the given fully qualified `name`, creating it if necessary,
+    /// which is useful to carry over the reflog of a reference that was renamed to `name`.
+    ///
+    /// The reflog is replaced atomically through a lock file acquired according to `lock_mode`, which is held while the existing
+    /// entries are read. Nothing happens if `lines` is empty.
+    pub fn reflog_prepend<'a, Name, E>(
+        &self,
+        name: Name,
+        lines: &[crate::log::Line],
+        lock_mode: gix_lock::acquire::Fail,
+    ) -> Result<(), Error>
+    where
+        Name: TryInto<&'a FullNameRef, Error = E>,
+        crate::name::Error: From<E>,
+    {
+        let name: &FullNameRef = name.try_into().map_err(|err| Error::RefnameValidation(err.into()))?;
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let path = self.reflog_path(name);
+        gix_tempfile::create_dir::all(path.parent().expect("always with parent directory"), Default::default())?;
+        let to_io_err = |err| std::io::Error::new(std::io::ErrorKind::Other, err);
+        let mut file = gix_lock::File::acquire_to_update_resource(&path, lock_mode, None).map_err(to_io_err)?;
+        for line in lines {
+            line.write_to(&mut file)?;
+        }
+        match std::fs::read(&path) {
+            Ok(existing) => file.write_all(&existing)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        file.commit().map_err(|err| err.error)?;
+        Ok(())
+    }
 }
 
 impl file::Store {
@@ -102,3 +102,5 @@ pub mod packed;
 
 mod raw_ext;
 pub use raw_ext::ReferenceExt;
+
+mod worktrees;
@@ -0,0 +1,103 @@
+use std::{io::Read, path::Path};
+
+use gix_object::bstr::{BString, ByteSlice, ByteVec};
+
+use crate::{
+    file::{iter::loose_then_packed::Error, loose, loose::iter::SortedLoosePaths},
+    store_impl::file,
+    FullName, Reference,
+};
+
+/// Pseudo-refs that are private to each worktree and hold a single object id or reference name.
+const PSEUDO_REFS: &[&str] = &[
+    "HEAD",
+    "ORIG_HEAD",
+    "MERGE_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+    "REBASE_HEAD",
+    "BISECT_HEAD",
+    "AUTO_MERGE",
+];
+
+/// Directories below `refs/` which contain references that are private to each worktree.
+const PRIVATE_REF_DIRS: &[&str] = &["refs/bisect", "refs/rewritten", "refs/worktree"];
+
+impl file::Store {
+    /// Return the references that are private to each worktree of the repository, those of the main worktree first,
+    /// followed by the ones of all linked worktrees in the order of their ids.
+    ///
+    /// Names are qualified so they refer to the same reference no matter which worktree they are [looked up](Self::find()) from,
+    /// like `main-worktree/HEAD`, `worktrees/<id>/HEAD` or `worktrees/<id>/refs/bisect/bad`. This includes `HEAD` and
+    /// other pseudo-refs like `ORIG_HEAD` or `REBASE_HEAD` if present, along with all references in `refs/bisect/`,
+    /// `refs/rewritten/` and `refs/worktree/`. As such references are never packed, only loose references are considered.
+    ///
+    /// This is useful to learn which branches are checked out in any worktree, as their `HEAD` is a symbolic reference to it.
+    pub fn worktree_refs(&self) -> Result<Vec<Reference>, Error> {
+        let common_dir = self.common_dir_resolved();
+        let mut out = Vec::new();
+        let mut buf = Vec::new();
+        self.collect_worktree_refs(common_dir, "main-worktree/".into(), &mut buf, &mut out)?;
+
+        let mut ids = match std::fs::read_dir(common_dir.join("worktrees")) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().join("HEAD").is_file())
+                .filter_map(|entry| gix_path::os_string_into_bstring(entry.file_name()).ok())
+                .collect::<Vec<_>>(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(Error::Traversal(err)),
+        };
+        ids.sort();
+        for id in ids {
+            let mut prefix = BString::from("worktrees/");
+            prefix.push_str(&id);
+            prefix.push(b'/');
+            let git_dir = common_dir.join("worktrees").join(gix_path::from_bstr(id.as_bstr()));
+            self.collect_worktree_refs(&git_dir, prefix, &mut buf, &mut out)?;
+        }
+        Ok(out)
+    }
+
+    fn collect_worktree_refs(
+        &self,
+        git_dir: &Path,
+        prefix: BString,
+        buf: &mut Vec<u8>,
+        out: &mut Vec<Reference>,
+    ) -> Result<(), Error> {
+        let mut read_ref = |path: &Path, name: &[u8]| -> Result<(), Error> {
+            buf.clear();
+            match std::fs::File::open(path).and_then(|mut f| f.read_to_end(buf)) {
+                Ok(_) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+                Err(err) => {
+                    return Err(Error::ReadFileContents {
+                        source: err,
+                        path: path.to_owned(),
+                    })
+                }
+            }
+            let mut full_name = prefix.clone();
+            full_name.extend_from_slice(name);
+            let reference =
+                loose::Reference::try_from_path(FullName(full_name), buf).map_err(|err| Error::ReferenceCreation {
+                    source: err,
+                    relative_path: path.strip_prefix(git_dir).unwrap_or(path).to_owned(),
+                })?;
+            out.push(reference.into());
+            Ok(())
+        };
+
+        for name in PSEUDO_REFS {
+            read_ref(&git_dir.join(name), name.as_bytes())?;
+        }
+        for dir in PRIVATE_REF_DIRS {
+            for res in SortedLoosePaths::at(&git_dir.join(dir), git_dir.to_owned(), None) {
+                let (path, name) = res.map_err(Error::Traversal)?;
+                read_ref(&path, name.as_bstr())?;
+            }
+        }
+        Ok(())
+    }
+}
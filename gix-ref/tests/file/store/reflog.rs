@@ -65,3 +65,56 @@ mod iter_rev {
         Ok(())
     }
 }
+
+mod prepend {
+    use gix_lock::acquire::Fail;
+
+    #[test]
+    fn lines_are_inserted_before_existing_ones_and_missing_logs_are_created() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable_standalone("make_repo_for_reflog.sh")?;
+        let store = crate::file::Store::at(
+            tmp.path().join(".git"),
+            gix_ref::store::WriteReflog::Disable,
+            gix_hash::Kind::Sha1,
+        );
+        let mut buf = Vec::new();
+        let head_lines: Vec<_> = store
+            .reflog_iter("HEAD", &mut buf)?
+            .expect("exists")
+            .map(|line| line.map(|line| line.to_owned()))
+            .collect::<Result<_, _>>()?;
+        let main_lines: Vec<_> = store
+            .reflog_iter("refs/heads/main", &mut buf)?
+            .expect("exists")
+            .map(|line| line.map(|line| line.to_owned()))
+            .collect::<Result<_, _>>()?;
+
+        store.reflog_prepend("refs/heads/main", &head_lines[..2], Fail::Immediately)?;
+        let lines: Vec<_> = store
+            .reflog_iter("refs/heads/main", &mut buf)?
+            .expect("exists")
+            .map(|line| line.map(|line| line.to_owned()))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(lines.len(), 2 + main_lines.len());
+        assert_eq!(lines[..2], head_lines[..2], "the new lines come first");
+        assert_eq!(lines[2..], main_lines[..], "existing lines are kept");
+
+        store.reflog_prepend("refs/heads/new/branch", &main_lines, Fail::Immediately)?;
+        let lines: Vec<_> = store
+            .reflog_iter("refs/heads/new/branch", &mut buf)?
+            .expect("created")
+            .map(|line| line.map(|line| line.to_owned()))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            lines, main_lines,
+            "missing logs are created along with their directories"
+        );
+
+        store.reflog_prepend("refs/heads/empty", &[], Fail::Immediately)?;
+        assert!(
+            !store.reflog_exists("refs/heads/empty")?,
+            "nothing is done without lines"
+        );
+        Ok(())
+    }
+}
@@ -186,6 +186,46 @@ mod read_only {
         }
         Ok(())
     }
+
+    #[test]
+    fn worktree_refs() -> crate::Result {
+        for packed in [false, true] {
+            for (store, _odb, _tmp) in [
+                main_store(packed, Mode::Read)?,
+                worktree_store(packed, "w1", Mode::Read)?,
+            ] {
+                let refs = store.worktree_refs()?;
+                let names: Vec<_> = refs.iter().map(|r| r.name.as_bstr().to_string()).collect();
+                assert_eq!(
+                    names,
+                    [
+                        "main-worktree/HEAD",
+                        "main-worktree/refs/bisect/bad",
+                        "worktrees/w-detached/HEAD",
+                        "worktrees/w-detached/ORIG_HEAD",
+                        "worktrees/w-detached/refs/bisect/bad",
+                        "worktrees/w1/HEAD",
+                        "worktrees/w1/ORIG_HEAD",
+                        "worktrees/w1/refs/bisect/bad",
+                    ],
+                    "the same references are seen from all worktrees, but shared ones are excluded"
+                );
+                for r in &refs {
+                    assert_eq!(
+                        &store.find(r.name.as_ref())?,
+                        r,
+                        "the qualified names can be looked up from any worktree"
+                    );
+                }
+                assert_eq!(
+                    refs[5].target.try_name().map(|name| name.as_bstr().to_string()),
+                    Some("refs/heads/w1".into()),
+                    "HEAD of linked worktrees shows which branch is checked out"
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 mod writable {
@@ -159,7 +159,9 @@ impl<'a> RefSpecRef<'a> {
                 };
                 if let Some(rest) = source.strip_prefix(b"refs/") {
                     if !rest.contains(&b'/') {
-                        out.push(source.into());
+                        // A glob like `refs/*` matches everything starting with what precedes it.
+                        let glob_pos = source.find_byte(b'*').unwrap_or(source.len());
+                        out.push(source[..glob_pos].into());
                     }
                     return;
                 } else if gix_hash::ObjectId::from_hex(source).is_ok() {
@@ -109,6 +109,11 @@ mod expand_prefixes {
         assert_eq!(out, ["refs/remote/"]);
     }
 
+    #[test]
+    fn globs_directly_below_refs_expand_to_what_precedes_them() {
+        assert_eq!(parse("+refs/*:refs/*"), ["refs/"]);
+    }
+
     #[test]
     fn strange_glob_patterns_expand_to_nothing() {
         assert_eq!(parse("refs/*/main:refs/*/main").len(), 0);
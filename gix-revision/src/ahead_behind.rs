@@ -0,0 +1,256 @@
+use gix_hash::ObjectId;
+
+/// The per-commit state used when computing ahead/behind counts, a bitset with one bit per tip.
+///
+/// It's opaque and only needed to name the type of [`Graph`](crate::Graph) to pass to [`ahead_behind()`][function::ahead_behind()]
+/// or [`contains()`](crate::contains()).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Flags {
+    pub(crate) tips: Vec<u64>,
+    /// If `true`, the parents of the commit were already updated with our `tips`.
+    pub(crate) processed: bool,
+    /// If `true`, the commit is currently in the queue and will pass on its `tips` to its parents once it's popped.
+    pub(crate) queued: bool,
+}
+
+impl Flags {
+    pub(crate) fn with_bit(num_tips: usize, bit: usize) -> Self {
+        let mut tips = vec![0; (num_tips + 63) / 64];
+        tips[bit / 64] |= 1 << (bit % 64);
+        Flags {
+            tips,
+            processed: false,
+            queued: false,
+        }
+    }
+
+    pub(crate) fn contains(&self, bit: usize) -> bool {
+        self.tips
+            .get(bit / 64)
+            .map_or(false, |word| word & (1 << (bit % 64)) != 0)
+    }
+
+    /// Add all tips of `other` to our tips and return `true` if we changed.
+    pub(crate) fn union(&mut self, other: &Flags) -> bool {
+        let mut changed = false;
+        for (word, other) in self.tips.iter_mut().zip(other.tips.iter()) {
+            changed |= (*word | *other) != *word;
+            *word |= *other;
+        }
+        changed
+    }
+
+    pub(crate) fn is_complete(&self, num_tips: usize) -> bool {
+        (0..num_tips).all(|bit| self.contains(bit))
+    }
+}
+
+/// The side of diverged histories a commit is reachable from, as returned by [`symmetric_difference()`][function::symmetric_difference()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The commit is only reachable from the left tip, like `<` in `git rev-list --left-right left...right`.
+    Left,
+    /// The commit is only reachable from the right tip, like `>` in `git rev-list --left-right left...right`.
+    Right,
+}
+
+/// The error returned by [`ahead_behind()`][function::ahead_behind()], [`ahead_behind_many()`][function::ahead_behind_many()]
+/// and [`symmetric_difference()`][function::symmetric_difference()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Commit {id} could not be found")]
+    FindCommit {
+        #[source]
+        err: gix_object::find::existing_iter::Error,
+        id: ObjectId,
+    },
+    #[error("The parents of commit {} could not be added to graph during traversal", id.to_hex())]
+    InsertParentsToGraph {
+        #[source]
+        err: crate::graph::insert_parents::Error,
+        id: ObjectId,
+    },
+}
+
+pub(crate) mod function {
+    use gix_hash::{oid, ObjectId};
+    use gix_hashtable::HashMap;
+
+    use super::{Error, Flags, Side};
+    use crate::{
+        priority::{priority_of, Priority},
+        Graph, PriorityQueue,
+    };
+
+    /// Count the commits reachable from `local` but not from `upstream` (_ahead_), and the ones reachable from `upstream`
+    /// but not from `local` (_behind_), returned as `(ahead, behind)`, similar to `git rev-list --left-right --count local...upstream`.
+    ///
+    /// `graph` is used to look up commits and will be cleared before use. If it's backed by a commit-graph, generation numbers
+    /// are used to order the traversal which makes it robust against clock skew, otherwise the commit time is used.
+    ///
+    /// The traversal doesn't continue past commits that are reachable from both tips,
+    /// so the amount of work is proportional to the divergence of both tips, and not to the size of the history.
+    pub fn ahead_behind(local: &oid, upstream: &oid, graph: &mut Graph<'_, Flags>) -> Result<(usize, usize), Error> {
+        Ok(ahead_behind_many(Some((local.to_owned(), upstream.to_owned())), graph)?
+            .pop()
+            .expect("one input yields one output"))
+    }
+
+    /// Like [`ahead_behind()`], but compute `(ahead, behind)` counts for each `(local, upstream)` pair in `pairs` with a single traversal
+    /// of `graph`, returning them in the order of `pairs`.
+    ///
+    /// This is much faster than performing one traversal per pair, which is useful when listing many branches along with their upstreams.
+    pub fn ahead_behind_many(
+        pairs: impl IntoIterator<Item = (ObjectId, ObjectId)>,
+        graph: &mut Graph<'_, Flags>,
+    ) -> Result<Vec<(usize, usize)>, Error> {
+        let mut bit_by_tip = HashMap::<ObjectId, usize>::default();
+        let mut tips = Vec::new();
+        let pairs: Vec<_> = pairs
+            .into_iter()
+            .map(|(local, upstream)| {
+                let mut bit_of = |id: ObjectId| {
+                    *bit_by_tip.entry(id).or_insert_with(|| {
+                        tips.push(id);
+                        tips.len() - 1
+                    })
+                };
+                (bit_of(local), bit_of(upstream))
+            })
+            .collect();
+        let _span = gix_trace::coarse!(
+            "gix_revision::ahead_behind_many()",
+            pairs = pairs.len(),
+            tips = tips.len()
+        );
+
+        let seen = paint(&tips, graph)?;
+        let mut counts = vec![(0, 0); pairs.len()];
+        for flags in seen.iter().map(|id| &graph[id]) {
+            for ((local, upstream), (ahead, behind)) in pairs.iter().zip(counts.iter_mut()) {
+                match (flags.contains(*local), flags.contains(*upstream)) {
+                    (true, false) => *ahead += 1,
+                    (false, true) => *behind += 1,
+                    _ => {}
+                }
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Return all commits reachable from either `left` or `right`, but not from both, along with the [`Side`] they are reachable from,
+    /// similar to `git rev-list --left-right left...right`.
+    ///
+    /// Commits are ordered by generation and commit time, newest first, and the traversal stops where the histories join
+    /// just like it does for [`ahead_behind()`]. `graph` is used to look up commits and will be cleared before use.
+    pub fn symmetric_difference(
+        left: &oid,
+        right: &oid,
+        graph: &mut Graph<'_, Flags>,
+    ) -> Result<Vec<(ObjectId, Side)>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::symmetric_difference()");
+        if left == right {
+            return Ok(Vec::new());
+        }
+        let seen = paint(&[left.to_owned(), right.to_owned()], graph)?;
+        let mut commits = Vec::new();
+        for id in seen {
+            let flags = &graph[&id];
+            let side = match (flags.contains(0), flags.contains(1)) {
+                (true, false) => Side::Left,
+                (false, true) => Side::Right,
+                _ => continue,
+            };
+            let priority = priority_of(&graph.lookup(&id).map_err(|err| Error::FindCommit { err, id })?);
+            commits.push((priority, id, side));
+        }
+        commits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        Ok(commits.into_iter().map(|(_, id, side)| (id, side)).collect())
+    }
+
+    /// Traverse from all `tips` and mark each commit with the bits of the tips it is reachable from,
+    /// returning all commits that were seen.
+    fn paint(tips: &[ObjectId], graph: &mut Graph<'_, Flags>) -> Result<Vec<ObjectId>, Error> {
+        let num_tips = tips.len();
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        let mut seen = Vec::new();
+        // The amount of queued commits that aren't reachable from all tips yet.
+        let mut num_incomplete = 0;
+        graph.clear();
+        for (bit, tip) in tips.iter().enumerate() {
+            let priority = priority_of(&graph.lookup(tip).map_err(|err| Error::FindCommit { err, id: *tip })?);
+            let mut flags = Flags::with_bit(num_tips, bit);
+            flags.queued = true;
+            if !flags.is_complete(num_tips) {
+                num_incomplete += 1;
+            }
+            graph.insert(*tip, flags);
+            queue.insert(priority, *tip);
+            seen.push(*tip);
+        }
+
+        // Commits reachable from all tips don't contribute to any count, so once only these are left we stop following
+        // their parents beyond updating the ones we already know. If these changed after we already processed them,
+        // which happens if the traversal order doesn't match the topology, we process them again to pass on their updated tips.
+        let (mut new_parents, mut changed_parents) = (Vec::new(), Vec::new());
+        while let Some(id) = queue.pop_value() {
+            let flags = {
+                let flags = graph.get_mut(&id).expect("queued commits are in graph");
+                flags.processed = true;
+                flags.queued = false;
+                flags.clone()
+            };
+            let is_complete = flags.is_complete(num_tips);
+            if !is_complete {
+                num_incomplete -= 1;
+            }
+            let follow_parents = !is_complete || num_incomplete != 0;
+            graph
+                .insert_parents(
+                    &id,
+                    &mut |parent_id, _parent_commit_time| {
+                        seen.push(parent_id);
+                        if follow_parents {
+                            new_parents.push(parent_id);
+                        }
+                        Flags {
+                            tips: flags.tips.clone(),
+                            processed: false,
+                            queued: false,
+                        }
+                    },
+                    &mut |parent_id, parent_flags| {
+                        let was_complete = parent_flags.is_complete(num_tips);
+                        if !parent_flags.union(&flags) {
+                            return;
+                        }
+                        if parent_flags.queued {
+                            if !was_complete && parent_flags.is_complete(num_tips) {
+                                num_incomplete -= 1;
+                            }
+                        } else if parent_flags.processed {
+                            changed_parents.push(parent_id);
+                        }
+                    },
+                    false,
+                )
+                .map_err(|err| Error::InsertParentsToGraph { err, id })?;
+            for parent_id in new_parents.drain(..).chain(changed_parents.drain(..)) {
+                let priority = priority_of(
+                    &graph
+                        .lookup(&parent_id)
+                        .map_err(|err| Error::FindCommit { err, id: parent_id })?,
+                );
+                let parent_flags = graph.get_mut(&parent_id).expect("parents were added to graph");
+                parent_flags.queued = true;
+                if !parent_flags.is_complete(num_tips) {
+                    num_incomplete += 1;
+                }
+                queue.insert(priority, parent_id);
+            }
+        }
+
+        Ok(seen)
+    }
+}
@@ -8,12 +8,30 @@
 #![cfg_attr(all(doc, feature = "document-features"), feature(doc_cfg, doc_auto_cfg))]
 #![deny(missing_docs, rust_2018_idioms, unsafe_code)]
 
+///
+pub mod ahead_behind;
+pub use ahead_behind::function::{ahead_behind, ahead_behind_many, symmetric_difference};
+
 ///
 #[cfg(feature = "describe")]
 pub mod describe;
 #[cfg(feature = "describe")]
 pub use describe::function::describe;
 
+///
+pub mod merge_base;
+pub use merge_base::function::merge_base;
+
+///
+pub mod name_rev;
+pub use name_rev::function::name_rev;
+
+///
+pub mod reachable;
+pub use reachable::function::{contains, is_ancestor, reachable_from};
+
+mod priority;
+
 ///
 pub mod spec;
 pub use gix_revwalk::{graph, Graph, PriorityQueue};
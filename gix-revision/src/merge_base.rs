@@ -0,0 +1,203 @@
+use gix_hash::ObjectId;
+
+/// The per-commit state used when computing merge-bases.
+///
+/// It's opaque and only needed to name the type of [`Graph`](crate::Graph) to pass to [`merge_base()`][function::merge_base()].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Flags(u8);
+
+impl Flags {
+    /// Reachable from the first commit.
+    const PARENT1: Flags = Flags(1 << 0);
+    /// Reachable from any of the other commits.
+    const PARENT2: Flags = Flags(1 << 1);
+    /// Reachable from a commit that is reachable from all commits, so it can't be a best common ancestor.
+    const STALE: Flags = Flags(1 << 2);
+    /// The commit was already added to the result.
+    const RESULT: Flags = Flags(1 << 3);
+    /// The commit is currently in the queue and will pass its flags on to its parents once it's popped.
+    const QUEUED: Flags = Flags(1 << 4);
+
+    fn contains(self, other: Flags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn union(self, other: Flags) -> Flags {
+        Flags(self.0 | other.0)
+    }
+
+    fn without(self, other: Flags) -> Flags {
+        Flags(self.0 & !other.0)
+    }
+}
+
+/// The error returned by [`merge_base()`][function::merge_base()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Commit {id} could not be found")]
+    FindCommit {
+        #[source]
+        err: gix_object::find::existing_iter::Error,
+        id: ObjectId,
+    },
+    #[error("The parents of commit {} could not be added to graph during traversal", id.to_hex())]
+    InsertParentsToGraph {
+        #[source]
+        err: crate::graph::insert_parents::Error,
+        id: ObjectId,
+    },
+}
+
+pub(crate) mod function {
+    use gix_hash::{oid, ObjectId};
+
+    use super::{Error, Flags};
+    use crate::{
+        priority::{priority_of, Priority},
+        Graph, PriorityQueue,
+    };
+
+    /// Find all best common ancestors of `first` and all of `others`, similar to `git merge-base first others…`,
+    /// or return `None` if there is no common ancestor.
+    ///
+    /// A best common ancestor is a common ancestor that isn't an ancestor of another common ancestor. There may be more than
+    /// one of them, for instance if branches were merged into each other in both directions, which is known as
+    /// _criss-cross merge_. Just like `git merge-base --all` does, all of them are returned, newest first.
+    /// If there are multiple `others`, the common ancestors are the ones of `first` and a hypothetical merge commit of all
+    /// `others`, which matches what `git` computes for octopus merges.
+    ///
+    /// `graph` is used to look up commits and will be cleared before use. If it's backed by a commit-graph, generation numbers
+    /// are used to order the traversal which makes it robust against clock skew, otherwise the commit time is used.
+    pub fn merge_base(
+        first: &oid,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, Flags>,
+    ) -> Result<Option<Vec<ObjectId>>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base()", others = others.len());
+        if others.is_empty() || others.iter().any(|other| other.as_ref() == first) {
+            return Ok(Some(vec![first.to_owned()]));
+        }
+
+        let bases = paint_down_to_common(first, others, graph)?;
+        let bases = remove_redundant(&bases, graph)?;
+        Ok((!bases.is_empty()).then_some(bases))
+    }
+
+    /// Return all commits that are reachable from `first` and from one of `others`, but not from another common ancestor,
+    /// leaving the flags of all traversed commits in `graph`.
+    ///
+    /// Note that the result may still contain commits that are ancestors of each other if the traversal order didn't match
+    /// the topology.
+    fn paint_down_to_common(
+        first: &oid,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, Flags>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        graph.clear();
+        // The amount of queued commits that aren't stale, as the traversal can stop once there is none left.
+        let mut num_non_stale = 0;
+        for (id, flags) in
+            std::iter::once((first.to_owned(), Flags::PARENT1)).chain(others.iter().map(|id| (*id, Flags::PARENT2)))
+        {
+            let priority = priority_of(&graph.lookup(&id).map_err(|err| Error::FindCommit { err, id })?);
+            let existing = graph.get(&id).copied();
+            graph.insert(id, existing.unwrap_or_default().union(flags).union(Flags::QUEUED));
+            if existing.is_none() {
+                queue.insert(priority, id);
+                num_non_stale += 1;
+            }
+        }
+
+        let mut out = Vec::new();
+        let (mut new_parents, mut changed_parents) = (Vec::new(), Vec::new());
+        while num_non_stale > 0 {
+            let id = queue.pop_value().expect("non-stale commits are queued");
+            let commit_flags = graph.get_mut(&id).expect("queued commits are in graph");
+            *commit_flags = commit_flags.without(Flags::QUEUED);
+            if !commit_flags.contains(Flags::STALE) {
+                num_non_stale -= 1;
+            }
+            let mut flags = Flags(commit_flags.0 & (Flags::PARENT1.0 | Flags::PARENT2.0 | Flags::STALE.0));
+            if flags == Flags::PARENT1.union(Flags::PARENT2) {
+                if !commit_flags.contains(Flags::RESULT) {
+                    *commit_flags = commit_flags.union(Flags::RESULT);
+                    out.push(id);
+                }
+                flags = flags.union(Flags::STALE);
+            }
+            graph
+                .insert_parents(
+                    &id,
+                    &mut |parent_id, _parent_commit_time| {
+                        new_parents.push(parent_id);
+                        flags
+                    },
+                    &mut |parent_id, parent_flags| {
+                        if parent_flags.contains(flags) {
+                            return;
+                        }
+                        if !parent_flags.contains(Flags::QUEUED) {
+                            changed_parents.push(parent_id);
+                        } else if !parent_flags.contains(Flags::STALE) && flags.contains(Flags::STALE) {
+                            num_non_stale -= 1;
+                        }
+                        // Queued parents pass on the new flags once they are popped.
+                        *parent_flags = parent_flags.union(flags);
+                    },
+                    false,
+                )
+                .map_err(|err| Error::InsertParentsToGraph { err, id })?;
+            for parent_id in new_parents.drain(..).chain(changed_parents.drain(..)) {
+                let priority = priority_of(
+                    &graph
+                        .lookup(&parent_id)
+                        .map_err(|err| Error::FindCommit { err, id: parent_id })?,
+                );
+                let parent_flags = graph.get_mut(&parent_id).expect("parents were inserted");
+                *parent_flags = parent_flags.union(Flags::QUEUED);
+                if !parent_flags.contains(Flags::STALE) {
+                    num_non_stale += 1;
+                }
+                queue.insert(priority, parent_id);
+            }
+        }
+        out.retain(|id| !graph[id].contains(Flags::STALE));
+        Ok(out)
+    }
+
+    /// Remove all `commits` that are ancestors of another commit in `commits`, keeping the order of the remaining ones.
+    fn remove_redundant(commits: &[ObjectId], graph: &mut Graph<'_, Flags>) -> Result<Vec<ObjectId>, Error> {
+        if commits.len() < 2 {
+            return Ok(commits.to_vec());
+        }
+        let mut redundant = vec![false; commits.len()];
+        for (idx, commit) in commits.iter().enumerate() {
+            if redundant[idx] {
+                continue;
+            }
+            let others: Vec<_> = commits
+                .iter()
+                .zip(&redundant)
+                .enumerate()
+                .filter(|(other_idx, (_, is_redundant))| *other_idx != idx && !**is_redundant)
+                .map(|(_, (id, _))| *id)
+                .collect();
+            paint_down_to_common(commit, &others, graph)?;
+            if graph[commit].contains(Flags::PARENT2) {
+                redundant[idx] = true;
+            }
+            for (other_idx, other) in commits.iter().enumerate() {
+                if other_idx != idx && graph.get(other).map_or(false, |flags| flags.contains(Flags::PARENT1)) {
+                    redundant[other_idx] = true;
+                }
+            }
+        }
+        Ok(commits
+            .iter()
+            .zip(redundant)
+            .filter_map(|(id, is_redundant)| (!is_redundant).then_some(*id))
+            .collect())
+    }
+}
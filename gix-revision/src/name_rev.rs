@@ -0,0 +1,224 @@
+use std::{borrow::Cow, sync::Arc};
+
+use bstr::{BStr, BString};
+use gix_date::SecondsSinceUnixEpoch;
+use gix_hash::ObjectId;
+
+/// Names of merge parents are penalized by this much, so paths through first parents are preferred.
+const MERGE_TRAVERSAL_WEIGHT: usize = 65535;
+
+/// A named commit to use as starting point when naming other commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tip<'name> {
+    /// The name to give to the commit, typically the shortened name of a reference, like `v1.2` or `main`.
+    ///
+    /// Like `git`, names of annotated tags should end in `^0`, like `tags/v1.2^0`, which is dropped once commits are named
+    /// relative to it, as in `tags/v1.2~1`.
+    pub name: Cow<'name, BStr>,
+    /// The commit the name refers to, with tags already peeled.
+    pub id: ObjectId,
+    /// If `true`, the name is considered a tag, and names based on tags are preferred over all other names.
+    pub from_tag: bool,
+    /// The time of the tag if it's an annotated tag, or the time of the commit otherwise.
+    ///
+    /// Names based on older tips are preferred.
+    pub time: SecondsSinceUnixEpoch,
+}
+
+/// The name of a commit relative to one of the [tips](Tip), as stored in the [`Graph`](crate::Graph).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Name {
+    /// The name of the tip, possibly extended with the path through merge commits, like `v1.2~3^2`.
+    pub tip_name: Arc<BString>,
+    /// The amount of first-parent hops from `tip_name` to reach the commit.
+    pub generation: usize,
+    /// The weighted distance to the original tip, used to prefer names reached without traversing into merged branches.
+    pub distance: usize,
+    /// If `true`, the name originated from a tag.
+    pub from_tag: bool,
+    /// The time of the tip this name originated from.
+    pub time: SecondsSinceUnixEpoch,
+}
+
+impl Name {
+    /// Return `true` if this name should be replaced with a name with the given properties.
+    ///
+    /// Names based on older tags are preferred even if they are farther away, and tags are preferred over other names.
+    /// Otherwise, the name with the shortest distance to its tip wins, or the one with the older tip.
+    fn is_worse_than(&self, time: SecondsSinceUnixEpoch, distance: usize, from_tag: bool) -> bool {
+        if self.from_tag && from_tag {
+            return self.time > time || (self.time == time && self.distance > distance);
+        }
+        if self.from_tag != from_tag {
+            return from_tag;
+        }
+        if self.distance != distance {
+            return self.distance > distance;
+        }
+        self.time > time
+    }
+}
+
+impl Name {
+    /// Return the name of the tip without the `^0` suffix of annotated tags, to be extended with the path to another commit.
+    fn tip_name_without_peel_suffix(&self) -> &BStr {
+        let name = self.tip_name.as_slice();
+        name.strip_suffix(b"^0").unwrap_or(name).into()
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.generation > 0 {
+            write!(f, "{}~{}", self.tip_name_without_peel_suffix(), self.generation)
+        } else {
+            self.tip_name.fmt(f)
+        }
+    }
+}
+
+/// The options for [`name_rev()`][function::name_rev()].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If set, commits older than this time will not be named and the traversal won't continue past them.
+    ///
+    /// This is an optimization to avoid traversing the entire history if only recent commits are to be named.
+    pub cutoff: Option<SecondsSinceUnixEpoch>,
+}
+
+/// The error returned by [`name_rev()`][function::name_rev()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Commit {id} could not be found")]
+    FindCommit {
+        #[source]
+        err: gix_object::find::existing_iter::Error,
+        id: ObjectId,
+    },
+    #[error("The parents of commit {} could not be decoded", id.to_hex())]
+    DecodeParents {
+        #[source]
+        err: crate::graph::commit::iter_parents::Error,
+        id: ObjectId,
+    },
+}
+
+pub(crate) mod function {
+    use std::sync::Arc;
+
+    use bstr::BString;
+
+    use super::{Error, Name, Options, Tip, MERGE_TRAVERSAL_WEIGHT};
+    use crate::Graph;
+
+    /// Name all commits reachable from `tips` relative to the best-suited tip, similar to `git name-rev`,
+    /// and store the resulting [`Name`] of each commit in `graph`, which is cleared before use.
+    ///
+    /// Commits are named by the amount of first-parent hops from a tip, like `main~2`, and merged commits are named by their
+    /// path through merge parents, like `v1.2~3^2~1`. If more than one name is possible, tags are preferred over other names,
+    /// older tags over newer ones, and otherwise names that are reached without traversing merges.
+    ///
+    /// Use [`Graph::get()`] to obtain the name of a commit after this call, which is `None` if it can't be reached from any tip.
+    pub fn name_rev<'name>(
+        tips: impl IntoIterator<Item = Tip<'name>>,
+        graph: &mut Graph<'_, Name>,
+        Options { cutoff }: Options,
+    ) -> Result<(), Error> {
+        let mut tips: Vec<_> = tips.into_iter().collect();
+        let _span = gix_trace::coarse!("gix_revision::name_rev()", tips = tips.len());
+        tips.sort_by(|a, b| b.from_tag.cmp(&a.from_tag).then_with(|| a.time.cmp(&b.time)));
+
+        graph.clear();
+        let mut stack = Vec::new();
+        let mut parents = Vec::new();
+        for tip in tips {
+            match committer_time(graph, &tip.id)? {
+                Some(time) if cutoff.map_or(true, |cutoff| time >= cutoff) => {}
+                _ => continue,
+            }
+            let name = Name {
+                tip_name: Arc::new(tip.name.into_owned()),
+                generation: 0,
+                distance: 0,
+                from_tag: tip.from_tag,
+                time: tip.time,
+            };
+            if !update_name(graph, tip.id, name) {
+                continue;
+            }
+
+            stack.push(tip.id);
+            while let Some(id) = stack.pop() {
+                let name = graph[&id].clone();
+                parents.clear();
+                for parent_id in graph
+                    .lookup(&id)
+                    .map_err(|err| Error::FindCommit { err, id })?
+                    .iter_parents()
+                {
+                    parents.push(parent_id.map_err(|err| Error::DecodeParents { err, id })?);
+                }
+
+                let mut to_push = Vec::with_capacity(parents.len());
+                for (parent_number, parent_id) in parents.iter().enumerate().map(|(idx, id)| (idx + 1, *id)) {
+                    match committer_time(graph, &parent_id)? {
+                        Some(time) if cutoff.map_or(true, |cutoff| time >= cutoff) => {}
+                        _ => continue,
+                    }
+                    let parent_name = if parent_number > 1 {
+                        let mut tip_name: BString = name.tip_name_without_peel_suffix().to_owned();
+                        if name.generation > 0 {
+                            tip_name.extend_from_slice(format!("~{}", name.generation).as_bytes());
+                        }
+                        tip_name.extend_from_slice(format!("^{parent_number}").as_bytes());
+                        Name {
+                            tip_name: Arc::new(tip_name),
+                            generation: 0,
+                            distance: name.distance + MERGE_TRAVERSAL_WEIGHT,
+                            ..name.clone()
+                        }
+                    } else {
+                        Name {
+                            generation: name.generation + 1,
+                            distance: name.distance + 1,
+                            ..name.clone()
+                        }
+                    };
+                    if update_name(graph, parent_id, parent_name) {
+                        to_push.push(parent_id);
+                    }
+                }
+                // Process the first parent first to give it the most natural name.
+                stack.extend(to_push.into_iter().rev());
+            }
+        }
+        Ok(())
+    }
+
+    /// Set `name` for `id` and return `true` if there was no name yet or if `name` is better than the existing one.
+    fn update_name(graph: &mut Graph<'_, Name>, id: gix_hash::ObjectId, name: Name) -> bool {
+        match graph.get_mut(&id) {
+            Some(existing) if !existing.is_worse_than(name.time, name.distance, name.from_tag) => false,
+            Some(existing) => {
+                *existing = name;
+                true
+            }
+            None => {
+                graph.insert(id, name);
+                true
+            }
+        }
+    }
+
+    /// Return the commit time of `id`, or `None` if it's not available as it's not a commit, or missing in a shallow repository.
+    fn committer_time(
+        graph: &mut Graph<'_, Name>,
+        id: &gix_hash::oid,
+    ) -> Result<Option<gix_date::SecondsSinceUnixEpoch>, Error> {
+        Ok(graph
+            .try_lookup(id)
+            .map_err(|err| Error::FindCommit { err, id: id.to_owned() })?
+            .map(|commit| commit.committer_timestamp().unwrap_or_default()))
+    }
+}
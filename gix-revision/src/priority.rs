@@ -0,0 +1,12 @@
+use crate::graph::{Generation, LazyCommit};
+
+/// The key by which commits are ordered in a [`PriorityQueue`](crate::PriorityQueue), highest generation and newest commits first.
+pub(crate) type Priority = (Generation, gix_date::SecondsSinceUnixEpoch);
+
+/// Return the key to queue `commit` with, using its generation number if it's backed by a commit-graph and its commit time otherwise.
+pub(crate) fn priority_of(commit: &LazyCommit<'_>) -> Priority {
+    (
+        commit.generation().unwrap_or(Generation::MAX),
+        commit.committer_timestamp().unwrap_or_default(),
+    )
+}
@@ -0,0 +1,212 @@
+use gix_hash::ObjectId;
+
+/// The error returned by [`is_ancestor()`][function::is_ancestor()], [`reachable_from()`][function::reachable_from()]
+/// and [`contains()`][function::contains()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Commit {id} could not be found")]
+    FindCommit {
+        #[source]
+        err: gix_object::find::existing_iter::Error,
+        id: ObjectId,
+    },
+    #[error("The parents of commit {} could not be added to graph during traversal", id.to_hex())]
+    InsertParentsToGraph {
+        #[source]
+        err: crate::graph::insert_parents::Error,
+        id: ObjectId,
+    },
+}
+
+pub(crate) mod function {
+    use gix_hash::{oid, ObjectId};
+    use gix_hashtable::HashMap;
+
+    use super::Error;
+    use crate::{
+        ahead_behind::Flags,
+        graph::Generation,
+        priority::{priority_of, Priority},
+        Graph, PriorityQueue,
+    };
+
+    /// Return `true` if `ancestor` is reachable from `descendant`, or if both are the same commit,
+    /// similar to `git merge-base --is-ancestor ancestor descendant`.
+    ///
+    /// This is typically used to answer whether a branch was merged into another one.
+    /// See [`reachable_from()`] for details on how `graph` is used.
+    pub fn is_ancestor(ancestor: &oid, descendant: &oid, graph: &mut Graph<'_, ()>) -> Result<bool, Error> {
+        Ok(reachable_from(Some(descendant.to_owned()), &[ancestor.to_owned()], graph)?[0])
+    }
+
+    /// For each of `targets`, return `true` if it's reachable from at least one of the `tips`, in the order of `targets`.
+    /// Each of the `tips` is considered to be reachable from itself.
+    ///
+    /// `graph` is used to look up commits and will be cleared before use. The traversal stops as soon as all `targets` were found,
+    /// and if it's backed by a commit-graph, generation numbers are used to avoid traversing commits that are too old to reach
+    /// any of the remaining targets. Without a commit-graph, all commits reachable from `tips` are traversed if one of the `targets`
+    /// isn't reachable.
+    ///
+    /// Note that reachability bitmaps aren't used, even if present.
+    pub fn reachable_from(
+        tips: impl IntoIterator<Item = ObjectId>,
+        targets: &[ObjectId],
+        graph: &mut Graph<'_, ()>,
+    ) -> Result<Vec<bool>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::reachable_from()", targets = targets.len());
+        let mut out = vec![false; targets.len()];
+        graph.clear();
+
+        // All remaining targets, along with their positions in `targets` and their generation number.
+        let mut remaining = HashMap::<ObjectId, (Vec<usize>, Option<Generation>)>::default();
+        for (pos, target) in targets.iter().enumerate() {
+            let generation = graph
+                .lookup(target)
+                .map_err(|err| Error::FindCommit { err, id: *target })?
+                .generation();
+            remaining.entry(*target).or_insert((Vec::new(), generation)).0.push(pos);
+        }
+        let mut cutoff = min_generation(&remaining);
+
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        for tip in tips {
+            if graph.contains(&tip) {
+                continue;
+            }
+            let priority = priority_of(&graph.lookup(&tip).map_err(|err| Error::FindCommit { err, id: tip })?);
+            graph.insert(tip, ());
+            queue.insert(priority, tip);
+        }
+
+        let mut new_parents = Vec::new();
+        while let Some(id) = queue.pop_value() {
+            if let Some((positions, _generation)) = remaining.remove(&id) {
+                for pos in positions {
+                    out[pos] = true;
+                }
+                if remaining.is_empty() {
+                    break;
+                }
+                cutoff = min_generation(&remaining);
+            }
+
+            graph
+                .insert_parents(
+                    &id,
+                    &mut |parent_id, _parent_commit_time| new_parents.push(parent_id),
+                    &mut |_parent_id, _| {},
+                    false,
+                )
+                .map_err(|err| Error::InsertParentsToGraph { err, id })?;
+            for parent_id in new_parents.drain(..) {
+                let parent = graph
+                    .lookup(&parent_id)
+                    .map_err(|err| Error::FindCommit { err, id: parent_id })?;
+                // Commits can only reach commits with a lower generation than their own.
+                if let (Some(cutoff), Some(generation)) = (cutoff, parent.generation()) {
+                    if generation < cutoff {
+                        continue;
+                    }
+                }
+                queue.insert(priority_of(&parent), parent_id);
+            }
+        }
+        Ok(out)
+    }
+
+    /// For each of `tips`, return `true` if `commit` is reachable from it, in the order of `tips`, similar to
+    /// `git branch --contains commit` or `git tag --contains commit`. Each of the `tips` contains itself.
+    ///
+    /// This is the inverse of [`reachable_from()`] and uses a single traversal of `graph` for all `tips`, which will be cleared before use.
+    /// If it's backed by a commit-graph, commits with a generation lower than the one of `commit` aren't traversed as they can't
+    /// lead to it. Without a commit-graph, all commits reachable from `tips` are traversed.
+    pub fn contains(tips: &[ObjectId], commit: &oid, graph: &mut Graph<'_, Flags>) -> Result<Vec<bool>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::contains()", tips = tips.len());
+        let num_tips = tips.len();
+        graph.clear();
+        let cutoff = graph
+            .lookup(commit)
+            .map_err(|err| Error::FindCommit {
+                err,
+                id: commit.to_owned(),
+            })?
+            .generation();
+
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        for (bit, tip) in tips.iter().enumerate() {
+            let flags = Flags::with_bit(num_tips, bit);
+            if let Some(existing) = graph.get_mut(tip) {
+                existing.union(&flags);
+                continue;
+            }
+            let priority = priority_of(&graph.lookup(tip).map_err(|err| Error::FindCommit { err, id: *tip })?);
+            graph.insert(*tip, Flags { queued: true, ..flags });
+            queue.insert(priority, *tip);
+        }
+
+        // As in `ahead_behind()`, commits are processed again if their tips changed after they were processed,
+        // which happens if the traversal order doesn't match the topology.
+        let (mut new_parents, mut changed_parents) = (Vec::new(), Vec::new());
+        while let Some(id) = queue.pop_value() {
+            let flags = {
+                let flags = graph.get_mut(&id).expect("queued commits are in graph");
+                flags.processed = true;
+                flags.queued = false;
+                flags.clone()
+            };
+            if id == commit {
+                if flags.is_complete(num_tips) {
+                    break;
+                }
+                continue;
+            }
+            graph
+                .insert_parents(
+                    &id,
+                    &mut |parent_id, _parent_commit_time| {
+                        new_parents.push(parent_id);
+                        Flags {
+                            tips: flags.tips.clone(),
+                            processed: false,
+                            queued: false,
+                        }
+                    },
+                    &mut |parent_id, parent_flags| {
+                        // Parents that are still queued will see their new tips when they are processed.
+                        if parent_flags.union(&flags) && parent_flags.processed && !parent_flags.queued {
+                            changed_parents.push(parent_id);
+                        }
+                    },
+                    false,
+                )
+                .map_err(|err| Error::InsertParentsToGraph { err, id })?;
+            for parent_id in new_parents.drain(..).chain(changed_parents.drain(..)) {
+                let parent = graph
+                    .lookup(&parent_id)
+                    .map_err(|err| Error::FindCommit { err, id: parent_id })?;
+                // Commits can only reach commits with a lower generation than their own.
+                if let (Some(cutoff), Some(generation)) = (cutoff, parent.generation()) {
+                    if generation < cutoff {
+                        continue;
+                    }
+                }
+                let priority = priority_of(&parent);
+                graph.get_mut(&parent_id).expect("parents were inserted").queued = true;
+                queue.insert(priority, parent_id);
+            }
+        }
+        Ok(match graph.get(commit) {
+            Some(flags) => (0..num_tips).map(|bit| flags.contains(bit)).collect(),
+            None => vec![false; num_tips],
+        })
+    }
+
+    /// Return the lowest generation number of all `remaining` targets, or `None` if one of them doesn't have a generation number.
+    fn min_generation(remaining: &HashMap<ObjectId, (Vec<usize>, Option<Generation>)>) -> Option<Generation> {
+        remaining
+            .values()
+            .map(|(_positions, generation)| *generation)
+            .try_fold(Generation::MAX, |min, generation| generation.map(|g| min.min(g)))
+    }
+}
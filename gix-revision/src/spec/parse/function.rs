@@ -428,11 +428,13 @@ where
                         return Err(Error::RefnameNeedsPositiveReflogEntries { nav: nav.into() });
                     }
                 } else if has_ref_or_implied_name {
-                    delegate
-                        .reflog(delegate::ReflogLookup::Entry(
-                            n.try_into().expect("non-negative isize fits usize"),
-                        ))
-                        .ok_or(Error::Delegate)?;
+                    // Like git, treat large numbers as seconds since the unix epoch.
+                    let lookup = if n >= 100_000_000 {
+                        delegate::ReflogLookup::Date(gix_date::Time::new(n as gix_date::SecondsSinceUnixEpoch, 0))
+                    } else {
+                        delegate::ReflogLookup::Entry(n.try_into().expect("non-negative isize fits usize"))
+                    };
+                    delegate.reflog(lookup).ok_or(Error::Delegate)?;
                 } else {
                     return Err(Error::ReflogLookupNeedsRefName { name: (*name).into() });
                 }
@@ -0,0 +1,102 @@
+use crate::hex_to_id;
+
+const MAIN: &str = "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b";
+const BRANCH1: &str = "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353";
+const AT_C1: &str = "134385f6d781b7e97062102c6a483440bfda2a03";
+const AT_C5: &str = "efd9a841189668f1bab5b8ebade9cd0a1b139a37";
+
+fn run_test(
+    mut assertions: impl FnMut(&mut gix_revision::Graph<'_, gix_revision::ahead_behind::Flags>) -> crate::Result,
+) -> crate::Result {
+    let store = gix_odb::at(
+        gix_testtools::scripted_fixture_read_only("make_repo_with_branches.sh")
+            .unwrap()
+            .join(".git/objects"),
+    )?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        assertions(&mut graph)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn diverged_branches() -> crate::Result {
+    run_test(|graph| {
+        assert_eq!(
+            gix_revision::ahead_behind(&hex_to_id(AT_C5), &hex_to_id(BRANCH1), graph)?,
+            (1, 2),
+            "c5 on one side, b1c1 and b1c2 on the other"
+        );
+        assert_eq!(
+            gix_revision::ahead_behind(&hex_to_id(BRANCH1), &hex_to_id(AT_C5), graph)?,
+            (2, 1),
+            "it's symmetric"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn ancestors_and_descendants() -> crate::Result {
+    run_test(|graph| {
+        assert_eq!(
+            gix_revision::ahead_behind(&hex_to_id(BRANCH1), &hex_to_id(MAIN), graph)?,
+            (0, 2),
+            "the merge commit and c5 are missing in branch1"
+        );
+        assert_eq!(
+            gix_revision::ahead_behind(&hex_to_id(MAIN), &hex_to_id(AT_C1), graph)?,
+            (7, 0),
+            "everything but the root commit is ahead"
+        );
+        assert_eq!(
+            gix_revision::ahead_behind(&hex_to_id(MAIN), &hex_to_id(MAIN), graph)?,
+            (0, 0),
+            "nothing differs between a commit and itself"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn many_at_once() -> crate::Result {
+    run_test(|graph| {
+        let counts = gix_revision::ahead_behind_many(
+            [(AT_C5, BRANCH1), (BRANCH1, MAIN), (MAIN, AT_C1), (AT_C1, AT_C1)]
+                .into_iter()
+                .map(|(local, upstream)| (hex_to_id(local), hex_to_id(upstream))),
+            graph,
+        )?;
+        assert_eq!(counts, [(1, 2), (0, 2), (7, 0), (0, 0)]);
+        Ok(())
+    })
+}
+
+#[test]
+fn symmetric_difference() -> crate::Result {
+    use gix_revision::ahead_behind::Side;
+    run_test(|graph| {
+        let commits = gix_revision::symmetric_difference(&hex_to_id(AT_C5), &hex_to_id(BRANCH1), graph)?;
+        assert_eq!(commits.len(), 3, "c5 on one side, b1c1 and b1c2 on the other");
+        assert_eq!(
+            commits
+                .iter()
+                .filter(|(_, side)| *side == Side::Left)
+                .collect::<Vec<_>>(),
+            [&(hex_to_id(AT_C5), Side::Left)]
+        );
+        assert_eq!(commits.iter().filter(|(_, side)| *side == Side::Right).count(), 2);
+
+        let commits = gix_revision::symmetric_difference(&hex_to_id(BRANCH1), &hex_to_id(MAIN), graph)?;
+        assert!(
+            commits.iter().all(|(_, side)| *side == Side::Right) && commits.len() == 2,
+            "only the merge commit and c5 are missing in branch1"
+        );
+        assert!(gix_revision::symmetric_difference(&hex_to_id(MAIN), &hex_to_id(MAIN), graph)?.is_empty());
+        Ok(())
+    })
+}
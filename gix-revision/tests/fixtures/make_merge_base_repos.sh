@@ -0,0 +1,22 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+git commit -q --allow-empty -m base
+git branch other
+git commit -q --allow-empty -m m1
+git tag m1
+
+git checkout -q other
+git commit -q --allow-empty -m o1
+git tag o1
+git merge -q --no-ff m1 -m o2-merge-m1
+
+git checkout -q main
+git merge -q --no-ff o1 -m m2-merge-o1
+
+git checkout -q --orphan unrelated
+git commit -q --allow-empty -m unrelated
+
+git commit-graph write --no-progress --reachable
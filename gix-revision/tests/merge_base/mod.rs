@@ -0,0 +1,92 @@
+use gix_hash::ObjectId;
+
+/// Resolve `name`, which is a branch or a tag, in the repository at `dir`.
+fn id_of(dir: &std::path::Path, name: &str) -> ObjectId {
+    let path = ["refs/heads", "refs/tags"]
+        .iter()
+        .map(|namespace| dir.join(".git").join(namespace).join(name))
+        .find(|path| path.is_file())
+        .expect("loose ref exists");
+    let hex = std::fs::read_to_string(path).expect("readable");
+    ObjectId::from_hex(hex.trim().as_bytes()).expect("valid hex")
+}
+
+fn run_test(
+    script: &str,
+    mut assertions: impl FnMut(
+        &mut gix_revision::Graph<'_, gix_revision::merge_base::Flags>,
+        &dyn Fn(&str) -> ObjectId,
+    ) -> crate::Result,
+) -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only(script).unwrap();
+    let store = gix_odb::at(dir.join(".git/objects"))?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        assertions(&mut graph, &|name| id_of(&dir, name))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn diverged_branches_and_ancestors() -> crate::Result {
+    run_test("make_repo_with_branches.sh", |graph, id| {
+        let fork_point = gix_revision::merge_base(&id("at-c5"), &[id("branch1")], graph)?.expect("common history");
+        assert_eq!(fork_point.len(), 1, "there is only one best common ancestor");
+        assert!(!fork_point.contains(&id("at-c5")) && !fork_point.contains(&id("branch1")));
+        assert_eq!(
+            gix_revision::merge_base(&id("at-b1c1"), &[id("at-c5")], graph)?,
+            Some(fork_point),
+            "all commits of the branch share the fork point, and the order of arguments doesn't matter"
+        );
+        assert_eq!(
+            gix_revision::merge_base(&id("main"), &[id("at-c1")], graph)?,
+            Some(vec![id("at-c1")]),
+            "an ancestor is its own merge-base"
+        );
+        assert_eq!(
+            gix_revision::merge_base(&id("at-b1c1"), &[id("main")], graph)?,
+            Some(vec![id("at-b1c1")]),
+            "it's symmetric"
+        );
+        assert_eq!(
+            gix_revision::merge_base(&id("main"), &[id("main")], graph)?,
+            Some(vec![id("main")])
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn criss_cross_merges_have_multiple_bases() -> crate::Result {
+    run_test("make_merge_base_repos.sh", |graph, id| {
+        let mut bases = gix_revision::merge_base(&id("main"), &[id("other")], graph)?.expect("common history");
+        bases.sort();
+        let mut expected = vec![id("m1"), id("o1")];
+        expected.sort();
+        assert_eq!(
+            bases, expected,
+            "each side merged the other's commit, so both are best common ancestors"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn octopus_bases_and_unrelated_histories() -> crate::Result {
+    run_test("make_merge_base_repos.sh", |graph, id| {
+        assert_eq!(
+            gix_revision::merge_base(&id("m1"), &[id("o1"), id("other")], graph)?,
+            Some(vec![id("m1")]),
+            "m1 is reachable from a merge of o1 and other"
+        );
+        assert_eq!(
+            gix_revision::merge_base(&id("main"), &[id("unrelated")], graph)?,
+            None,
+            "there is no common ancestor"
+        );
+        Ok(())
+    })
+}
@@ -0,0 +1,137 @@
+use gix_revision::name_rev::{Name, Options, Tip};
+
+use crate::hex_to_id;
+
+const MAIN: &str = "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b";
+const BRANCH1: &str = "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353";
+const AT_C1: &str = "134385f6d781b7e97062102c6a483440bfda2a03";
+const AT_C5: &str = "efd9a841189668f1bab5b8ebade9cd0a1b139a37";
+const AT_B1C1: &str = "9152eeee2328073cf23dcf8e90c949170b711659";
+const C4: &str = "9556057aee5abb06912922e9f26c46386a816822";
+
+/// The time of all commits in the fixture.
+const COMMIT_TIME: gix_date::SecondsSinceUnixEpoch = 946771200;
+
+fn tip(name: &'static str, id: &str, from_tag: bool) -> Tip<'static> {
+    Tip {
+        name: gix_object::bstr::BStr::new(name).into(),
+        id: hex_to_id(id),
+        from_tag,
+        time: COMMIT_TIME,
+    }
+}
+
+fn run_test(mut assertions: impl FnMut(&mut gix_revision::Graph<'_, Name>) -> crate::Result) -> crate::Result {
+    let store = gix_odb::at(
+        gix_testtools::scripted_fixture_read_only("make_repo_with_branches.sh")
+            .unwrap()
+            .join(".git/objects"),
+    )?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        assertions(&mut graph)?;
+    }
+    Ok(())
+}
+
+fn name_of(graph: &gix_revision::Graph<'_, Name>, id: &str) -> Option<String> {
+    graph.get(&hex_to_id(id)).map(ToString::to_string)
+}
+
+#[test]
+fn first_parents_are_preferred_over_merged_parents() -> crate::Result {
+    run_test(|graph| {
+        gix_revision::name_rev(Some(tip("main", MAIN, false)), graph, Options::default())?;
+        assert_eq!(name_of(graph, MAIN).as_deref(), Some("main"));
+        assert_eq!(name_of(graph, AT_C5).as_deref(), Some("main~1"));
+        assert_eq!(name_of(graph, C4).as_deref(), Some("main~2"), "not main^2~2");
+        assert_eq!(name_of(graph, BRANCH1).as_deref(), Some("main^2"));
+        assert_eq!(name_of(graph, AT_B1C1).as_deref(), Some("main^2~1"));
+        assert_eq!(name_of(graph, AT_C1).as_deref(), Some("main~5"));
+        Ok(())
+    })
+}
+
+#[test]
+fn tags_are_preferred_over_other_names() -> crate::Result {
+    run_test(|graph| {
+        gix_revision::name_rev(
+            [
+                tip("main", MAIN, false),
+                tip("at-c5", AT_C5, true),
+                tip("at-b1c1", AT_B1C1, true),
+            ],
+            graph,
+            Options::default(),
+        )?;
+        assert_eq!(name_of(graph, MAIN).as_deref(), Some("main"));
+        assert_eq!(
+            name_of(graph, BRANCH1).as_deref(),
+            Some("main^2"),
+            "no tag can reach it"
+        );
+        assert_eq!(name_of(graph, AT_C5).as_deref(), Some("at-c5"));
+        assert_eq!(name_of(graph, AT_B1C1).as_deref(), Some("at-b1c1"));
+        assert_eq!(
+            name_of(graph, AT_C1).as_deref(),
+            Some("at-c5~4"),
+            "the first tag wins if all else is equal"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn older_tags_are_preferred_even_if_they_are_farther_away() -> crate::Result {
+    run_test(|graph| {
+        let mut newer = tip("newer", AT_C5, true);
+        newer.time += 1;
+        gix_revision::name_rev([newer, tip("older", MAIN, true)], graph, Options::default())?;
+        assert_eq!(name_of(graph, AT_C5).as_deref(), Some("older~1"));
+        assert_eq!(name_of(graph, AT_C1).as_deref(), Some("older~5"));
+        Ok(())
+    })
+}
+
+#[test]
+fn commits_before_cutoff_are_not_named() -> crate::Result {
+    run_test(|graph| {
+        gix_revision::name_rev(
+            Some(tip("main", MAIN, false)),
+            graph,
+            Options {
+                cutoff: Some(COMMIT_TIME + 1),
+            },
+        )?;
+        assert_eq!(name_of(graph, MAIN), None, "the tip itself is too old");
+
+        gix_revision::name_rev(
+            Some(tip("main", MAIN, false)),
+            graph,
+            Options {
+                cutoff: Some(COMMIT_TIME),
+            },
+        )?;
+        assert_eq!(
+            name_of(graph, AT_C1).as_deref(),
+            Some("main~5"),
+            "the cutoff is inclusive"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn peel_suffix_of_annotated_tags_is_dropped_in_relative_names() -> crate::Result {
+    run_test(|graph| {
+        gix_revision::name_rev(Some(tip("tags/v1^0", MAIN, true)), graph, Options::default())?;
+        assert_eq!(name_of(graph, MAIN).as_deref(), Some("tags/v1^0"));
+        assert_eq!(name_of(graph, AT_C5).as_deref(), Some("tags/v1~1"));
+        assert_eq!(name_of(graph, BRANCH1).as_deref(), Some("tags/v1^2"));
+        assert_eq!(name_of(graph, AT_B1C1).as_deref(), Some("tags/v1^2~1"));
+        Ok(())
+    })
+}
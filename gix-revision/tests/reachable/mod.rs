@@ -0,0 +1,95 @@
+use crate::hex_to_id;
+
+const MAIN: &str = "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b";
+const BRANCH1: &str = "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353";
+const AT_C1: &str = "134385f6d781b7e97062102c6a483440bfda2a03";
+const AT_C5: &str = "efd9a841189668f1bab5b8ebade9cd0a1b139a37";
+
+fn run_test<T>(mut assertions: impl FnMut(&mut gix_revision::Graph<'_, T>) -> crate::Result) -> crate::Result {
+    let store = gix_odb::at(
+        gix_testtools::scripted_fixture_read_only("make_repo_with_branches.sh")
+            .unwrap()
+            .join(".git/objects"),
+    )?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        assertions(&mut graph)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn is_ancestor() -> crate::Result {
+    run_test(|graph| {
+        for (ancestor, descendant, expected, message) in [
+            (AT_C1, MAIN, true, "the root commit is an ancestor of everything"),
+            (MAIN, AT_C1, false, "descendants aren't ancestors"),
+            (BRANCH1, MAIN, true, "branch1 was merged into main"),
+            (AT_C5, BRANCH1, false, "diverged commits aren't ancestors of each other"),
+            (BRANCH1, AT_C5, false, "it's symmetric"),
+            (MAIN, MAIN, true, "a commit is its own ancestor"),
+        ] {
+            assert_eq!(
+                gix_revision::is_ancestor(&hex_to_id(ancestor), &hex_to_id(descendant), graph)?,
+                expected,
+                "{message}"
+            );
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn reachable_from_many_tips() -> crate::Result {
+    run_test(|graph| {
+        let reachable = gix_revision::reachable_from(
+            [AT_C5, BRANCH1].map(hex_to_id),
+            &[MAIN, AT_C1, BRANCH1, AT_C5, AT_C1].map(hex_to_id),
+            graph,
+        )?;
+        assert_eq!(
+            reachable,
+            [false, true, true, true, true],
+            "tips are reachable from themselves, and duplicate targets are supported"
+        );
+
+        assert_eq!(
+            gix_revision::reachable_from(Some(hex_to_id(AT_C1)), &[], graph)?,
+            Vec::<bool>::new(),
+            "no targets means nothing to do"
+        );
+        assert_eq!(
+            gix_revision::reachable_from(None, &[hex_to_id(AT_C1)], graph)?,
+            [false],
+            "nothing is reachable without tips"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn contains() -> crate::Result {
+    run_test(|graph| {
+        let tips = [MAIN, BRANCH1, AT_C5, AT_C1, MAIN].map(hex_to_id);
+        assert_eq!(
+            gix_revision::contains(&tips, &hex_to_id(BRANCH1), graph)?,
+            [true, true, false, false, true],
+            "branch1 was merged into main and contains itself"
+        );
+        assert_eq!(
+            gix_revision::contains(&tips, &hex_to_id(AT_C1), graph)?,
+            [true; 5],
+            "everything contains the root commit"
+        );
+        assert_eq!(
+            gix_revision::contains(&tips, &hex_to_id(MAIN), graph)?,
+            [true, false, false, false, true],
+            "only main contains itself"
+        );
+        assert!(gix_revision::contains(&[], &hex_to_id(MAIN), graph)?.is_empty());
+        Ok(())
+    })
+}
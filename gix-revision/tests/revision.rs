@@ -1,5 +1,9 @@
+mod ahead_behind;
 #[cfg(feature = "describe")]
 mod describe;
+mod merge_base;
+mod name_rev;
+mod reachable;
 mod spec;
 pub type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + 'static>>;
 
@@ -33,6 +33,20 @@ fn reflog_by_entry_for_current_branch() {
     }
 }
 
+#[test]
+fn reflog_by_unix_timestamp_for_current_branch() {
+    let rec = parse("@{100000000}");
+    assert_eq!(
+        rec.current_branch_reflog_entry[0],
+        Some("100000000 +0000".to_string()),
+        "like git, large numbers are seconds since the epoch"
+    );
+    assert_eq!(
+        parse("@{99999999}").current_branch_reflog_entry[0],
+        Some("99999999".into())
+    );
+}
+
 #[test]
 fn reflog_by_date_for_current_branch() {
     let rec = parse("@{1979-02-26 18:30:00}");
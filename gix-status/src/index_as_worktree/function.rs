@@ -1,5 +1,5 @@
 use std::{
-    io,
+    io::{self, Read},
     path::Path,
     slice::Chunks,
     sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
@@ -356,7 +356,8 @@ impl<'index> State<'_, 'index> {
                 // index entries are normally only for files/symlinks
                 // if a file turned into a directory it was removed
                 // the only exception here are submodules which are
-                // part of the index despite being directories
+                // part of the index despite being directories,
+                // so a file replaced by a repository is a type change, just like in git.
                 if entry.mode.is_submodule() {
                     let status = submodule
                         .status(entry, rela_path)
@@ -366,7 +367,9 @@ impl<'index> State<'_, 'index> {
                         })?;
                     return Ok(status.map(|status| Change::SubmoduleModification(status).into()));
                 } else {
-                    return Ok(Some(Change::Removed.into()));
+                    self.symlink_metadata_calls.fetch_add(1, Ordering::Relaxed);
+                    let is_repository = worktree_path.join(".git").symlink_metadata().is_ok();
+                    return Ok(Some(if is_repository { Change::Type } else { Change::Removed }.into()));
                 }
             }
             Ok(metadata) => metadata,
@@ -421,9 +424,9 @@ impl<'index> State<'_, 'index> {
             rela_path,
             entry,
             file_len: metadata.len(),
+            is_symlink: metadata.is_symlink(),
             filter: &mut self.filter,
             attr_stack: &mut self.attr_stack,
-            options: self.options,
             id: &entry.id,
             objects,
             worktree_reads: self.worktree_reads,
@@ -488,10 +491,11 @@ where
     path: &'a Path,
     rela_path: &'a BStr,
     file_len: u64,
+    /// `true` if the worktree file is an actual symlink.
+    is_symlink: bool,
     entry: &'a gix_index::Entry,
     filter: &'a mut gix_filter::Pipeline,
     attr_stack: &'a mut gix_worktree::Stack,
-    options: &'a Options,
     id: &'a gix_hash::oid,
     objects: Find,
     worktree_bytes: &'a AtomicU64,
@@ -515,15 +519,19 @@ where
     fn stream_worktree_file(self) -> Result<Stream<'a>, Error> {
         self.buf.clear();
         // symlinks are only stored as actual symlinks if the FS supports it otherwise they are just
-        // normal files with their content equal to the linked path (so can be read normally)
+        // normal files with their content equal to the linked path (so can be read normally, but without filters)
         //
         let is_symlink = self.entry.mode == gix_index::entry::Mode::SYMLINK;
         // TODO: what to do about precompose unicode and ignore_case for symlinks
-        let out = if is_symlink && self.options.fs.symlink {
-            // conversion to bstr can never fail because symlinks are only used
-            // on unix (by git) so no reason to use the try version here
-            let symlink_path = gix_path::into_bstr(std::fs::read_link(self.path)?);
-            self.buf.extend_from_slice(&symlink_path);
+        let out = if is_symlink {
+            if self.is_symlink {
+                // conversion to bstr can never fail because symlinks are only used
+                // on unix (by git) so no reason to use the try version here
+                let symlink_path = gix_path::into_bstr(std::fs::read_link(self.path)?);
+                self.buf.extend_from_slice(&symlink_path);
+            } else {
+                std::fs::File::open(self.path)?.read_to_end(self.buf)?;
+            }
             self.worktree_bytes.fetch_add(self.buf.len() as u64, Ordering::Relaxed);
             Stream {
                 inner: ToGitOutcome::Buffer(self.buf),
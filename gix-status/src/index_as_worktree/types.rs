@@ -85,7 +85,10 @@ impl Outcome {
 pub enum Change<T = (), U = ()> {
     /// This corresponding file does not exist in the worktree anymore.
     Removed,
-    /// The type of file changed compared to the worktree, i.e. a symlink s now a file.
+    /// The type of file changed compared to the worktree, i.e. a symlink is now a file, or a file is now a
+    /// repository like the one of a submodule.
+    ///
+    /// Note that files that were replaced by plain directories are considered [removed](Self::Removed).
     Type,
     /// This worktree file was modified in some form, like a permission change or content change or both,
     /// as compared to this entry.
@@ -13,6 +13,10 @@ use bstr::BStr;
 pub mod index_as_worktree;
 pub use index_as_worktree::function::index_as_worktree;
 
+/// Refresh the stat information of index entries to match the worktree.
+pub mod refresh;
+pub use refresh::function::refresh;
+
 /// A trait to facilitate working working with pathspecs.
 pub trait Pathspec {
     /// Return the portion of the prefix among all of the pathspecs involved in this search, or an empty string if
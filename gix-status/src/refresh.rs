@@ -0,0 +1,168 @@
+/// The error returned by [`refresh()`](crate::refresh()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Status(#[from] crate::index_as_worktree::Error),
+    #[error("Could not write the refreshed index")]
+    WriteIndex(#[from] gix_index::file::write::Error),
+}
+
+/// Options for use in [`refresh()`](crate::refresh()).
+#[derive(Clone, Default)]
+pub struct Options {
+    /// Options to configure how the status of each entry is computed.
+    pub status: crate::index_as_worktree::Options,
+    /// If `Some(options)`, the index is written back to disk with `options` if at least one entry was refreshed.
+    pub write: Option<gix_index::write::Options>,
+}
+
+/// The outcome of [`refresh()`](crate::refresh()).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Outcome {
+    /// Information about the status computation that was performed to learn which entries need refreshing.
+    pub status: crate::index_as_worktree::Outcome,
+    /// The amount of entries that received new stat information from the worktree as their content didn't change.
+    pub entries_updated: usize,
+    /// The amount of racily clean entries that turned out to be modified, and whose size was set to 0 so they keep
+    /// showing up as modified without having to look at their content.
+    pub entries_smudged: usize,
+    /// If `true`, the index was written back to disk.
+    pub index_written: bool,
+}
+
+/// A [submodule status](crate::index_as_worktree::traits::SubmoduleStatus) implementation which never reports
+/// any status, for use when submodules are of no interest, like when refreshing as they have no stat information
+/// worth refreshing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IgnoreSubmodules;
+
+impl crate::index_as_worktree::traits::SubmoduleStatus for IgnoreSubmodules {
+    type Output = ();
+    type Error = std::convert::Infallible;
+
+    fn status(
+        &mut self,
+        _entry: &gix_index::Entry,
+        _rela_path: &bstr::BStr,
+    ) -> Result<Option<Self::Output>, Self::Error> {
+        Ok(None)
+    }
+}
+
+pub(crate) mod function {
+    use std::{path::Path, sync::atomic::AtomicBool};
+
+    use bstr::BStr;
+    use filetime::FileTime;
+
+    use super::{Error, IgnoreSubmodules, Options, Outcome};
+    use crate::{
+        index_as_worktree::{traits, Change, EntryStatus, VisitEntry},
+        Pathspec,
+    };
+
+    /// Refresh the stat information of all entries in `index` that match `pathspec` with what's currently in `worktree`,
+    /// similar to `git update-index --refresh`.
+    ///
+    /// Entries whose stat information doesn't match anymore, or which are *racily clean* as their `mtime` isn't before the
+    /// index timestamp, are re-hashed to learn if they actually changed, reading objects from `objects` and converting
+    /// worktree files with `filter`. Unchanged entries receive the current stat information so they don't have to be
+    /// re-hashed next time, while modified entries that were racily clean get their size set to 0, so that they
+    /// are seen as modified even if their stat information doesn't change in a way that reveals it.
+    ///
+    /// The timestamp of `index` is set to the time just before the refresh started, and if [`Options::write`] is set,
+    /// `index` is written back to disk if it was changed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn refresh<Find>(
+        index: &mut gix_index::File,
+        worktree: &Path,
+        objects: Find,
+        progress: &mut dyn gix_features::progress::Progress,
+        pathspec: impl Pathspec + Send + Clone,
+        filter: gix_filter::Pipeline,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error>
+    where
+        Find: gix_object::Find + Send + Clone,
+    {
+        let start = FileTime::now();
+        let mut collect = CollectUpdates::default();
+        let status = crate::index_as_worktree(
+            index,
+            worktree,
+            &mut collect,
+            traits::FastEq,
+            IgnoreSubmodules,
+            objects,
+            progress,
+            pathspec,
+            filter,
+            should_interrupt,
+            options.status,
+        )?;
+
+        let mut out = Outcome {
+            status,
+            ..Default::default()
+        };
+        if collect.updates.is_empty() {
+            return Ok(out);
+        }
+        let entries = index.entries_mut();
+        for (entry_index, update) in collect.updates {
+            let entry = &mut entries[entry_index];
+            match update {
+                Update::Stat(stat) => {
+                    entry.stat = stat;
+                    out.entries_updated += 1;
+                }
+                Update::Smudge => {
+                    entry.stat.size = 0;
+                    out.entries_smudged += 1;
+                }
+            }
+        }
+        index.set_timestamp(start);
+        if let Some(write_options) = options.write {
+            index.write(write_options)?;
+            out.index_written = true;
+        }
+        Ok(out)
+    }
+
+    enum Update {
+        Stat(gix_index::entry::Stat),
+        Smudge,
+    }
+
+    #[derive(Default)]
+    struct CollectUpdates {
+        updates: Vec<(usize, Update)>,
+    }
+
+    impl<'index> VisitEntry<'index> for CollectUpdates {
+        type ContentChange = ();
+        type SubmoduleStatus = ();
+
+        fn visit_entry(
+            &mut self,
+            _entries: &'index [gix_index::Entry],
+            _entry: &'index gix_index::Entry,
+            entry_index: usize,
+            _rela_path: &'index BStr,
+            status: EntryStatus<Self::ContentChange, Self::SubmoduleStatus>,
+        ) {
+            let update = match status {
+                EntryStatus::NeedsUpdate(stat) => Update::Stat(stat),
+                EntryStatus::Change(Change::Modification {
+                    set_entry_stat_size_zero: true,
+                    ..
+                }) => Update::Smudge,
+                _ => return,
+            };
+            self.updates.push((entry_index, update));
+        }
+    }
+}
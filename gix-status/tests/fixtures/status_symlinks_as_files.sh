@@ -0,0 +1,15 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+echo -n "content" > file
+ln -s file symlink
+
+git add -A
+git commit -m "Commit"
+
+git config core.symlinks false
+rm symlink
+git checkout -- symlink
+git reset # ensure index timestamp is large enough to not mark everything racy
@@ -0,0 +1,17 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+touch file plain repo
+echo -n "content" > executable
+chmod +x executable
+ln -s file link
+
+git add -A
+git commit -q -m "Commit"
+
+rm executable && ln -s file executable
+rm link && echo -n "file" > link
+rm plain && mkdir plain && touch plain/untracked
+rm repo && git init -q repo
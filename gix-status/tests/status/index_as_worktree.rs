@@ -21,7 +21,7 @@ use crate::fixture_path;
 // changes when extracting the data so we need to disable all advanced stat
 // changes and only look at mtime seconds and file size to properly
 // test all code paths (and to trigger racy git).
-const TEST_OPTIONS: index::entry::stat::Options = index::entry::stat::Options {
+pub(crate) const TEST_OPTIONS: index::entry::stat::Options = index::entry::stat::Options {
     trust_ctime: false,
     check_stat: false,
     use_nsec: false,
@@ -41,23 +41,39 @@ fn fixture_with_index(
     prepare_index: impl FnMut(&mut gix_index::State),
     expected_status: &[Expectation<'_>],
 ) -> Outcome {
-    fixture_filtered_detailed(name, "", &[], expected_status, prepare_index, false)
+    fixture_filtered_detailed(name, "", &[], expected_status, prepare_index, |_| {}, false)
 }
 
 fn submodule_fixture(name: &str, expected_status: &[Expectation<'_>]) -> Outcome {
-    fixture_filtered_detailed("status_submodule", name, &[], expected_status, |_| {}, false)
+    fixture_filtered_detailed("status_submodule", name, &[], expected_status, |_| {}, |_| {}, false)
 }
 
 fn conflict_fixture(name: &str, expected_status: &[Expectation<'_>]) -> Outcome {
-    fixture_filtered_detailed("conflicts", name, &[], expected_status, |_| {}, false)
+    fixture_filtered_detailed("conflicts", name, &[], expected_status, |_| {}, |_| {}, false)
 }
 
 fn submodule_fixture_status(name: &str, expected_status: &[Expectation<'_>], submodule_dirty: bool) -> Outcome {
-    fixture_filtered_detailed("status_submodule", name, &[], expected_status, |_| {}, submodule_dirty)
+    fixture_filtered_detailed(
+        "status_submodule",
+        name,
+        &[],
+        expected_status,
+        |_| {},
+        |_| {},
+        submodule_dirty,
+    )
 }
 
 fn fixture_filtered(name: &str, pathspecs: &[&str], expected_status: &[Expectation<'_>]) -> Outcome {
-    fixture_filtered_detailed(name, "", pathspecs, expected_status, |_| {}, false)
+    fixture_filtered_detailed(name, "", pathspecs, expected_status, |_| {}, |_| {}, false)
+}
+
+fn fixture_with_fs(
+    name: &str,
+    adjust_fs: impl FnOnce(&mut gix_fs::Capabilities),
+    expected_status: &[Expectation<'_>],
+) -> Outcome {
+    fixture_filtered_detailed(name, "", &[], expected_status, |_| {}, adjust_fs, false)
 }
 
 fn fixture_filtered_detailed(
@@ -66,6 +82,7 @@ fn fixture_filtered_detailed(
     pathspecs: &[&str],
     expected_status: &[Expectation<'_>],
     mut prepare_index: impl FnMut(&mut gix_index::State),
+    adjust_fs: impl FnOnce(&mut gix_fs::Capabilities),
     submodule_dirty: bool,
 ) -> Outcome {
     // This can easily happen in some fixtures, which can cause flakyness. It's time-dependent after all.
@@ -91,6 +108,8 @@ fn fixture_filtered_detailed(
         gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default()).unwrap();
     prepare_index(&mut index);
     let mut recorder = Recorder::default();
+    let mut fs = gix_fs::Capabilities::probe(&git_dir);
+    adjust_fs(&mut fs);
     let search = gix_pathspec::Search::from_specs(to_pathspecs(pathspecs), None, std::path::Path::new(""))
         .expect("valid specs can be normalized");
     let outcome = index_as_worktree(
@@ -105,7 +124,7 @@ fn fixture_filtered_detailed(
         Default::default(),
         &AtomicBool::default(),
         Options {
-            fs: gix_fs::Capabilities::probe(&git_dir),
+            fs,
             stat: TEST_OPTIONS,
             ..Options::default()
         },
@@ -252,6 +271,28 @@ fn subomdule_typechange() {
     )
 }
 
+#[test]
+fn typechange() {
+    assert_eq!(
+        fixture(
+            "status_typechange",
+            &[
+                (BStr::new(b"executable"), 0, Change::Type.into()),
+                (BStr::new(b"link"), 2, Change::Type.into()),
+                (BStr::new(b"plain"), 3, status_removed()),
+                (BStr::new(b"repo"), 4, Change::Type.into()),
+            ],
+        ),
+        Outcome {
+            entries_to_process: 5,
+            entries_processed: 5,
+            symlink_metadata_calls: 7,
+            ..Default::default()
+        },
+        "files replaced by directories are only a type change if the directory is a repository"
+    );
+}
+
 #[test]
 fn subomdule_empty_dir_no_change() {
     assert_eq!(
@@ -398,6 +439,30 @@ fn unchanged() {
     fixture("status_unchanged", &[]);
 }
 
+#[test]
+#[cfg(unix)]
+fn unchanged_without_symlink_and_executable_bit_support() {
+    fixture_with_fs(
+        "status_unchanged",
+        |fs| {
+            fs.symlink = false;
+            fs.executable_bit = false;
+        },
+        &[],
+    );
+}
+
+#[test]
+fn symlinks_checked_out_as_files() {
+    fixture_with_fs("status_symlinks_as_files", |fs| fs.symlink = false, &[]);
+    #[cfg(unix)]
+    fixture_with_fs(
+        "status_symlinks_as_files",
+        |fs| fs.symlink = true,
+        &[(BStr::new(b"symlink"), 1, Change::Type.into())],
+    );
+}
+
 #[test]
 fn refresh() {
     let expected_outcome = Outcome {
@@ -697,7 +762,7 @@ fn racy_git() {
 }
 
 #[derive(Clone)]
-struct Pathspec(gix_pathspec::Search);
+pub(crate) struct Pathspec(gix_pathspec::Search);
 
 impl Default for Pathspec {
     fn default() -> Self {
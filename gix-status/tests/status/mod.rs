@@ -1,4 +1,5 @@
 mod index_as_worktree;
+mod refresh;
 
 pub fn fixture_path(name: &str) -> std::path::PathBuf {
     let dir = gix_testtools::scripted_fixture_read_only_standalone(std::path::Path::new(name).with_extension("sh"))
@@ -0,0 +1,91 @@
+use std::sync::atomic::AtomicBool;
+
+use filetime::{set_file_mtime, FileTime};
+use gix_status::refresh::{Options, Outcome};
+
+use crate::status::index_as_worktree::{Pathspec, TEST_OPTIONS};
+
+/// Make the only entry of the `racy_git` fixture racily clean by giving it the same `mtime` as the index timestamp,
+/// while `content` is written to the worktree file.
+fn racy_fixture(content: &str) -> (gix_testtools::tempfile::TempDir, gix_index::File) {
+    let timestamp = 940040400;
+    let dir = gix_testtools::scripted_fixture_writable_standalone("racy_git.sh").expect("script works");
+    let worktree = dir.path();
+    let mut index = gix_index::File::at(
+        worktree.join(".git").join("index"),
+        gix_hash::Kind::Sha1,
+        false,
+        Default::default(),
+    )
+    .unwrap();
+
+    std::fs::write(worktree.join("content"), content).unwrap();
+    index.entries_mut()[0].stat.mtime.secs = timestamp;
+    set_file_mtime(worktree.join("content"), FileTime::from_unix_time(timestamp as i64, 0)).unwrap();
+    index.set_timestamp(FileTime::from_unix_time(timestamp as i64, 0));
+    (dir, index)
+}
+
+fn refresh(dir: &gix_testtools::tempfile::TempDir, index: &mut gix_index::File, write: bool) -> Outcome {
+    let worktree = dir.path();
+    let mut out = gix_status::refresh(
+        index,
+        worktree,
+        gix_object::find::Never,
+        &mut gix_features::progress::Discard,
+        Pathspec::default(),
+        Default::default(),
+        &AtomicBool::default(),
+        Options {
+            status: gix_status::index_as_worktree::Options {
+                fs: gix_fs::Capabilities::probe(&worktree.join(".git")),
+                stat: TEST_OPTIONS,
+                ..Default::default()
+            },
+            write: write.then(Default::default),
+        },
+    )
+    .unwrap();
+    out.status.worktree_bytes = 0;
+    out
+}
+
+#[test]
+fn racily_clean_entries_that_changed_are_smudged() {
+    let (dir, mut index) = racy_fixture("bar");
+    let before = FileTime::now();
+    let out = refresh(&dir, &mut index, true);
+    assert_eq!(out.status.racy_clean, 1);
+    assert_eq!(out.entries_smudged, 1);
+    assert_eq!(out.entries_updated, 0);
+    assert!(out.index_written);
+    assert!(
+        index.timestamp() >= before,
+        "the timestamp is set to when the refresh started"
+    );
+
+    let index = gix_index::File::at(index.path(), gix_hash::Kind::Sha1, false, Default::default()).unwrap();
+    assert_eq!(
+        index.entries()[0].stat.size,
+        0,
+        "the smudged size was written so the entry shows up as modified from now on"
+    );
+}
+
+#[test]
+fn racily_clean_entries_that_did_not_change_receive_new_stats() {
+    let (dir, mut index) = racy_fixture("foo");
+    let out = refresh(&dir, &mut index, false);
+    assert_eq!(out.status.racy_clean, 1);
+    assert_eq!(out.entries_smudged, 0);
+    assert_eq!(out.entries_updated, 1);
+    assert!(!out.index_written);
+    assert_eq!(index.entries()[0].stat.size, 3);
+
+    let out = refresh(&dir, &mut index, false);
+    assert_eq!(
+        out.status.racy_clean, 0,
+        "the entry isn't racy anymore as the index timestamp is newer than the file"
+    );
+    assert_eq!(out.entries_updated + out.entries_smudged, 0, "nothing left to refresh");
+}
@@ -4,6 +4,7 @@ use std::{
     error::Error,
     ffi::{OsStr, OsString},
     io::Write,
+    path::PathBuf,
     process::{self, Stdio},
 };
 
@@ -45,18 +46,23 @@ pub struct SpawnProcessOnDemand {
     /// The environment variables to set in the invoked command.
     envs: Vec<(&'static str, String)>,
     ssh_disallow_shell: bool,
+    ssh_control_path: Option<PathBuf>,
+    ssh_askpass: Option<PathBuf>,
     connection: Option<git::Connection<Box<dyn std::io::Read + Send>, process::ChildStdin>>,
     child: Option<process::Child>,
     trace: bool,
 }
 
 impl SpawnProcessOnDemand {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new_ssh(
         url: gix_url::Url,
         program: impl Into<OsString>,
         path: BString,
         ssh_kind: ssh::ProgramKind,
         ssh_disallow_shell: bool,
+        ssh_control_path: Option<PathBuf>,
+        ssh_askpass: Option<PathBuf>,
         version: Protocol,
         trace: bool,
     ) -> SpawnProcessOnDemand {
@@ -66,6 +72,8 @@ impl SpawnProcessOnDemand {
             ssh_cmd: Some((program.into(), ssh_kind)),
             envs: Default::default(),
             ssh_disallow_shell,
+            ssh_control_path,
+            ssh_askpass,
             child: None,
             connection: None,
             desired_version: version,
@@ -82,6 +90,8 @@ impl SpawnProcessOnDemand {
                 .then(|| vec![("GIT_PROTOCOL", format!("version={}", version as usize))])
                 .unwrap_or_default(),
             ssh_disallow_shell: false,
+            ssh_control_path: None,
+            ssh_askpass: None,
             child: None,
             connection: None,
             desired_version: version,
@@ -202,9 +212,16 @@ impl client::Transport for SpawnProcessOnDemand {
     ) -> Result<SetServiceResponse<'_>, client::Error> {
         let (mut cmd, ssh_kind, cmd_name) = match &self.ssh_cmd {
             Some((command, kind)) => (
-                kind.prepare_invocation(command, &self.url, self.desired_version, self.ssh_disallow_shell)
-                    .map_err(client::Error::SshInvocation)?
-                    .stderr(Stdio::piped()),
+                kind.prepare_invocation(
+                    command,
+                    &self.url,
+                    self.desired_version,
+                    self.ssh_disallow_shell,
+                    self.ssh_control_path.as_deref(),
+                    self.ssh_askpass.as_deref(),
+                )
+                .map_err(client::Error::SshInvocation)?
+                .stderr(Stdio::piped()),
                 Some(*kind),
                 Cow::Owned(command.to_owned()),
             ),
@@ -92,6 +92,8 @@ impl curl::easy::Handler for Handler {
                         .send(Err(io::Error::new(
                             if status == 401 {
                                 io::ErrorKind::PermissionDenied
+                            } else if status == 404 {
+                                io::ErrorKind::NotFound
                             } else if (500..600).contains(&status) {
                                 io::ErrorKind::ConnectionAborted
                             } else {
@@ -148,6 +150,8 @@ pub fn new() -> (
                     follow_redirects,
                     low_speed_limit_bytes_per_second,
                     low_speed_time_seconds,
+                    max_receive_bytes_per_second,
+                    max_send_bytes_per_second,
                     connect_timeout,
                     proxy,
                     no_proxy,
@@ -267,6 +271,9 @@ pub fn new() -> (
                 handle.low_speed_limit(low_speed_limit_bytes_per_second)?;
                 handle.low_speed_time(Duration::from_secs(low_speed_time_seconds))?;
             }
+            // A value of 0 means unlimited, which also resets previous limits as the handle is reused.
+            handle.max_recv_speed(max_receive_bytes_per_second)?;
+            handle.max_send_speed(max_send_bytes_per_second)?;
             let (receive_data, receive_headers, send_body, mut receive_body) = {
                 let handler = handle.get_mut();
                 let (send, receive_data) = pipe::unidirectional(1);
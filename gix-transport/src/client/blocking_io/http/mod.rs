@@ -7,7 +7,7 @@ use std::{
 };
 
 use base64::Engine;
-use bstr::BStr;
+use bstr::{BStr, ByteSlice};
 use gix_packetline::PacketLineRef;
 pub use traits::{Error, GetResponse, Http, PostBodyDataKind, PostResponse};
 
@@ -141,6 +141,15 @@ pub struct Options {
     ///
     /// Refers to `http.lowSpeedTime`.
     pub low_speed_time_seconds: u64,
+    /// If non-0, the amount of bytes per second that won't be exceeded when receiving data, which slows down the transfer
+    /// to leave bandwidth for others.
+    ///
+    /// Refers to `gitoxide.http.maxReceiveSpeed`.
+    pub max_receive_bytes_per_second: u64,
+    /// If non-0, the amount of bytes per second that won't be exceeded when sending data.
+    ///
+    /// Refers to `gitoxide.http.maxSendSpeed`.
+    pub max_send_bytes_per_second: u64,
     /// A curl-style proxy declaration of the form `[protocol://][user[:password]@]proxyhost[:port]`.
     ///
     /// Note that an empty string means the proxy is disabled entirely.
@@ -196,6 +205,8 @@ impl Default for Options {
             follow_redirects: Default::default(),
             low_speed_limit_bytes_per_second: 0,
             low_speed_time_seconds: 0,
+            max_receive_bytes_per_second: 0,
+            max_send_bytes_per_second: 0,
             proxy: None,
             no_proxy: None,
             proxy_auth_method: Default::default(),
@@ -228,6 +239,8 @@ pub struct Transport<H: Http> {
     http: H,
     service: Option<Service>,
     line_provider: Option<gix_packetline::StreamingPeekableIter<H::ResponseBody>>,
+    /// The refs advertisement we synthesized from `info/refs` if the remote only supports the 'dumb' protocol.
+    dumb_refs: Option<gix_packetline::StreamingPeekableIter<std::io::Cursor<Vec<u8>>>>,
     identity: Option<gix_sec::identity::Account>,
     trace: bool,
 }
@@ -252,6 +265,7 @@ impl<H: Http> Transport<H> {
             service: None,
             http,
             line_provider: None,
+            dumb_refs: None,
             identity,
             trace,
         }
@@ -278,22 +292,91 @@ impl Transport<Impl> {
 
 impl<H: Http> Transport<H> {
     fn check_content_type(service: Service, kind: &str, headers: <H as Http>::Headers) -> Result<(), client::Error> {
-        let wanted_content_type = format!("application/x-{}-{}", service.as_str(), kind);
-        if !headers.lines().collect::<Result<Vec<_>, _>>()?.iter().any(|l| {
-            let mut tokens = l.split(':');
-            tokens.next().zip(tokens.next()).map_or(false, |(name, value)| {
-                name.eq_ignore_ascii_case("content-type") && value.trim() == wanted_content_type
-            })
-        }) {
-            return Err(client::Error::Http(Error::Detail {
-                description: format!(
-                    "Didn't find '{wanted_content_type}' header to indicate 'smart' protocol, and 'dumb' protocol is not supported."
-                ),
-            }));
+        let headers = headers.lines().collect::<Result<Vec<_>, _>>()?;
+        if !has_content_type(service, kind, &headers) {
+            return Err(not_smart(service, kind));
         }
         Ok(())
     }
 
+    /// Turn the `info/refs` file in `body` of a remote that only supports the 'dumb' protocol into a protocol V1 advertisement
+    /// so it can be handled like the one of a 'smart' remote.
+    fn dumb_handshake(&mut self, mut body: H::ResponseBody) -> Result<client::SetServiceResponse<'_>, client::Error> {
+        let mut info_refs = Vec::new();
+        body.read_to_end(&mut info_refs)?;
+        let mut refs = Vec::new();
+        for line in ByteSlice::lines(info_refs.as_slice()) {
+            let (id, name) = line
+                .split_once_str(b"\t")
+                .and_then(|(id, name)| gix_hash::ObjectId::from_hex(id).ok().map(|id| (id, name.as_bstr())))
+                .ok_or_else(|| {
+                    client::Error::Http(Error::Detail {
+                        description: format!(
+                            "Could not parse line {:?} in 'info/refs' of 'dumb' remote",
+                            line.as_bstr()
+                        ),
+                    })
+                })?;
+            refs.push((id, name));
+        }
+
+        let mut head = Vec::new();
+        if let Some(mut file) = client::Transport::get_file(self, "HEAD")? {
+            file.read_to_end(&mut head)?;
+        }
+        let head = head.trim_end();
+        let head = match head.strip_prefix(b"ref: ") {
+            Some(target) => refs
+                .iter()
+                .find(|(_, name)| *name == target)
+                .map(|(id, _)| (*id, Some(target.as_bstr()))),
+            None => gix_hash::ObjectId::from_hex(head).ok().map(|id| (id, None)),
+        };
+
+        let mut advertisement = Vec::new();
+        let mut capabilities = Some(match head.and_then(|(_, target)| target) {
+            Some(target) => format!("symref=HEAD:{target}"),
+            None => String::new(),
+        });
+        let mut write_ref = |id: &gix_hash::oid, name: &BStr| -> std::io::Result<()> {
+            let mut line = format!("{id} {name}");
+            if let Some(capabilities) = capabilities.take() {
+                line.push('\0');
+                line.push_str(&capabilities);
+            }
+            line.push('\n');
+            gix_packetline::encode::data_to_write(line.as_bytes(), &mut advertisement).map(|_| ())
+        };
+        if let Some((id, _)) = head {
+            write_ref(&id, "HEAD".into())?;
+        }
+        for (id, name) in &refs {
+            write_ref(id, name)?;
+        }
+        if refs.is_empty() && head.is_none() {
+            write_ref(&gix_hash::Kind::Sha1.null(), "capabilities^{}".into())?;
+        }
+        gix_packetline::encode::flush_to_write(&mut advertisement)?;
+
+        let line_reader = self.dumb_refs.insert(gix_packetline::StreamingPeekableIter::new(
+            std::io::Cursor::new(advertisement),
+            &[PacketLineRef::Flush],
+            self.trace,
+        ));
+        let capabilities::recv::Outcome {
+            capabilities,
+            refs,
+            protocol: actual_protocol,
+        } = Capabilities::from_lines_with_version_detection(line_reader)?;
+        self.actual_version = actual_protocol;
+        self.service = Some(Service::UploadPack);
+        Ok(client::SetServiceResponse {
+            actual_protocol,
+            capabilities,
+            refs,
+        })
+    }
+
     #[allow(clippy::unnecessary_wraps, unknown_lints)]
     fn add_basic_auth_if_present(&self, headers: &mut Vec<Cow<'_, str>>) -> Result<(), client::Error> {
         if let Some(gix_sec::identity::Account { username, password }) = &self.identity {
@@ -312,6 +395,25 @@ impl<H: Http> Transport<H> {
     }
 }
 
+fn has_content_type(service: Service, kind: &str, headers: &[String]) -> bool {
+    let wanted_content_type = format!("application/x-{}-{}", service.as_str(), kind);
+    headers.iter().any(|l| {
+        let mut tokens = l.split(':');
+        tokens.next().zip(tokens.next()).map_or(false, |(name, value)| {
+            name.eq_ignore_ascii_case("content-type") && value.trim() == wanted_content_type
+        })
+    })
+}
+
+fn not_smart(service: Service, kind: &str) -> client::Error {
+    client::Error::Http(Error::Detail {
+        description: format!(
+            "Didn't find 'application/x-{}-{kind}' header to indicate 'smart' protocol, and 'dumb' protocol is not supported for this service.",
+            service.as_str()
+        ),
+    })
+}
+
 fn append_url(base: &str, suffix: &str) -> String {
     let mut buf = base.to_owned();
     if base.as_bytes().last() != Some(&b'/') {
@@ -334,6 +436,11 @@ impl<H: Http> client::TransportWithoutIO for Transport<H> {
         trace: bool,
     ) -> Result<RequestWriter<'_>, client::Error> {
         let service = self.service.expect("handshake() must have been called first");
+        if self.dumb_refs.is_some() {
+            return Err(client::Error::Http(Error::Detail {
+                description: "Remotes that only support the 'dumb' protocol can't receive requests".into(),
+            }));
+        }
         let url = append_url(&self.url, service.as_str());
         let static_headers = &[
             Cow::Borrowed(self.user_agent_header),
@@ -381,6 +488,10 @@ impl<H: Http> client::TransportWithoutIO for Transport<H> {
         Cow::Borrowed(self.url.as_str().into())
     }
 
+    fn is_dumb(&self) -> bool {
+        self.dumb_refs.is_some()
+    }
+
     fn connection_persists_across_multiple_requests(&self) -> bool {
         false
     }
@@ -422,14 +533,27 @@ impl<H: Http> client::Transport for Transport<H> {
             dynamic_headers.push(format!("Git-Protocol: {parameters}").into());
         }
         self.add_basic_auth_if_present(&mut dynamic_headers)?;
+        // The response of a previous interaction, possibly failed or not fully read, must not keep the
+        // http implementation busy while the new request is made.
+        self.line_provider = None;
+        self.dumb_refs = None;
         let GetResponse { headers, body } =
             self.http
                 .get(url.as_ref(), &self.url, static_headers.iter().chain(&dynamic_headers))?;
-        <Transport<H>>::check_content_type(service, "advertisement", headers)?;
+        let headers = headers.lines().collect::<Result<Vec<_>, _>>()?;
+        if !has_content_type(service, "advertisement", &headers) {
+            // Without the content type, the server merely serves files, which is enough to fetch.
+            if service == Service::UploadPack {
+                return self.dumb_handshake(body);
+            }
+            return Err(not_smart(service, "advertisement"));
+        }
 
-        let line_reader = self.line_provider.get_or_insert_with(|| {
-            gix_packetline::StreamingPeekableIter::new(body, &[PacketLineRef::Flush], self.trace)
-        });
+        let line_reader = self.line_provider.insert(gix_packetline::StreamingPeekableIter::new(
+            body,
+            &[PacketLineRef::Flush],
+            self.trace,
+        ));
 
         // the service announcement is only sent sometimes depending on the exact server/protocol version/used protocol (http?)
         // eat the announcement when its there to avoid errors later (and check that the correct service was announced).
@@ -466,6 +590,25 @@ impl<H: Http> client::Transport for Transport<H> {
             refs,
         })
     }
+
+    fn get_file(&mut self, path: &str) -> Result<Option<Box<dyn BufRead + '_>>, client::Error> {
+        let url = append_url(&self.url, path);
+        let static_headers = [Cow::Borrowed(self.user_agent_header)];
+        let mut dynamic_headers = Vec::<Cow<'_, str>>::new();
+        self.add_basic_auth_if_present(&mut dynamic_headers)?;
+        self.line_provider = None;
+        let GetResponse { headers, body } =
+            self.http
+                .get(url.as_ref(), &self.url, static_headers.iter().chain(&dynamic_headers))?;
+        for line in headers.lines() {
+            match line {
+                Ok(_) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Some(Box::new(body)))
+    }
 }
 
 struct HeadersThenBody<H: Http, B: Unpin> {
@@ -477,8 +620,11 @@ struct HeadersThenBody<H: Http, B: Unpin> {
 impl<H: Http, B: Unpin> HeadersThenBody<H, B> {
     fn handle_headers(&mut self) -> std::io::Result<()> {
         if let Some(headers) = self.headers.take() {
-            <Transport<H>>::check_content_type(self.service, "result", headers)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
+            <Transport<H>>::check_content_type(self.service, "result", headers).map_err(|err| match err {
+                // Keep the kind of IO errors so they can still be recognized as spurious.
+                client::Error::Io(err) => err,
+                err => std::io::Error::new(std::io::ErrorKind::Other, err),
+            })?
         }
         Ok(())
     }
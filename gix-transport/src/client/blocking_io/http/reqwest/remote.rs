@@ -112,9 +112,19 @@ impl Default for Remote {
                     Some(PostBodyDataKind::BoundedAndFitsIntoMemory) => {
                         let mut buf = Vec::<u8>::with_capacity(512);
                         post_body_rx.read_to_end(&mut buf)?;
-                        req_builder.body(buf)
+                        if config.max_send_bytes_per_second == 0 {
+                            req_builder.body(buf)
+                        } else {
+                            let len = buf.len() as u64;
+                            req_builder.body(reqwest::blocking::Body::sized(
+                                Throttled::new(std::io::Cursor::new(buf), config.max_send_bytes_per_second, None),
+                                len,
+                            ))
+                        }
                     }
-                    Some(PostBodyDataKind::Unbounded) => req_builder.body(reqwest::blocking::Body::new(post_body_rx)),
+                    Some(PostBodyDataKind::Unbounded) => req_builder.body(reqwest::blocking::Body::new(
+                        Throttled::new(post_body_rx, config.max_send_bytes_per_second, None),
+                    )),
                     None => req_builder,
                 };
                 let mut req = req_builder.build()?;
@@ -146,6 +156,8 @@ impl Default for Remote {
                             Some(status) => {
                                 let kind = if status == reqwest::StatusCode::UNAUTHORIZED {
                                     std::io::ErrorKind::PermissionDenied
+                                } else if status == reqwest::StatusCode::NOT_FOUND {
+                                    std::io::ErrorKind::NotFound
                                 } else if status.is_server_error() {
                                     std::io::ErrorKind::ConnectionAborted
                                 } else {
@@ -187,6 +199,12 @@ impl Default for Remote {
 
                 // reading the response body is streaming and may fail for many reasons. If so, we send the error over the response
                 // body channel and that's all we can do.
+                let low_speed = (config.low_speed_limit_bytes_per_second > 0 && config.low_speed_time_seconds > 0)
+                    .then_some((
+                        u64::from(config.low_speed_limit_bytes_per_second),
+                        std::time::Duration::from_secs(config.low_speed_time_seconds),
+                    ));
+                let mut res = Throttled::new(res, config.max_receive_bytes_per_second, low_speed);
                 if let Err(err) = std::io::copy(&mut res, &mut response_body_tx) {
                     response_body_tx.channel.send(Err(err)).ok();
                 }
@@ -308,6 +326,78 @@ impl http::Http for Remote {
     }
 }
 
+/// A reader which is slowed down to not exceed `max_bytes_per_second` if it's non-zero, and which fails if its average speed
+/// stays below the `low_speed` limit in bytes per second for the given duration, similar to what `curl` does natively.
+///
+/// Note that a transfer that stalls entirely is only noticed once more data arrives or the `reqwest` timeout triggers.
+struct Throttled<R> {
+    inner: R,
+    max_bytes_per_second: u64,
+    low_speed: Option<(u64, std::time::Duration)>,
+    start: std::time::Instant,
+    bytes: u64,
+    window_start: std::time::Instant,
+    window_bytes: u64,
+}
+
+impl<R> Throttled<R> {
+    fn new(inner: R, max_bytes_per_second: u64, low_speed: Option<(u64, std::time::Duration)>) -> Self {
+        let now = std::time::Instant::now();
+        Throttled {
+            inner,
+            max_bytes_per_second,
+            low_speed,
+            start: now,
+            bytes: 0,
+            window_start: now,
+            window_bytes: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for Throttled<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let buf = match usize::try_from(self.max_bytes_per_second) {
+            Ok(0) | Err(_) => buf,
+            Ok(max) => {
+                let len = buf.len().min(max);
+                &mut buf[..len]
+            }
+        };
+        let num_read = self.inner.read(buf)?;
+        if num_read == 0 {
+            return Ok(0);
+        }
+
+        self.bytes += num_read as u64;
+        if self.max_bytes_per_second != 0 {
+            let due = std::time::Duration::from_secs_f64(self.bytes as f64 / self.max_bytes_per_second as f64);
+            if let Some(wait) = due.checked_sub(self.start.elapsed()) {
+                std::thread::sleep(wait);
+            }
+        }
+
+        if let Some((limit, time)) = self.low_speed {
+            self.window_bytes += num_read as u64;
+            let elapsed = self.window_start.elapsed();
+            if elapsed >= time {
+                if (self.window_bytes as f64 / elapsed.as_secs_f64()) < limit as f64 {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        format!(
+                            "Transfer was slower than {limit} bytes per second for more than {} seconds",
+                            time.as_secs()
+                        ),
+                    ));
+                }
+                self.window_start = std::time::Instant::now();
+                self.window_bytes = 0;
+            }
+        }
+        Ok(num_read)
+    }
+}
+
 pub(crate) struct Request {
     pub url: String,
     pub base_url: String,
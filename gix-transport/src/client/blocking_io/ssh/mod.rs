@@ -73,6 +73,16 @@ pub mod connect {
         /// when invoking the program.
         /// If unset, the `program` basename determines the variant, or an invocation of the `command` itself.
         pub kind: Option<ProgramKind>,
+        /// If set, a directory in which the `ssh` program may place control sockets to share a single master connection
+        /// per host among all connections, with the master connection lingering for a minute after its last use.
+        ///
+        /// This is only supported by [`ProgramKind::Ssh`] on unix and ignored otherwise.
+        pub control_path: Option<std::path::PathBuf>,
+        /// If set, the program to ask the user for passwords and passphrases of keys, which is passed to the `ssh` program
+        /// via `SSH_ASKPASS` and used in favor of the terminal, even if there is one.
+        ///
+        /// This is only supported by [`ProgramKind::Ssh`] and ignored otherwise.
+        pub askpass: Option<std::path::PathBuf>,
     }
 
     impl Options {
@@ -133,6 +143,8 @@ pub fn connect(
         path,
         kind,
         options.disallow_shell,
+        options.control_path.clone(),
+        options.askpass.clone(),
         desired_version,
         trace,
     ))
@@ -1,4 +1,8 @@
-use std::{ffi::OsStr, io::ErrorKind};
+use std::{
+    ffi::{OsStr, OsString},
+    io::ErrorKind,
+    path::Path,
+};
 
 use bstr::{BString, ByteSlice, ByteVec};
 
@@ -26,6 +30,8 @@ impl ProgramKind {
         url: &gix_url::Url,
         desired_version: Protocol,
         disallow_shell: bool,
+        control_path: Option<&Path>,
+        askpass: Option<&Path>,
     ) -> Result<gix_command::Prepare, ssh::invocation::Error> {
         let mut prepare = gix_command::prepare(ssh_cmd).with_shell();
         if disallow_shell {
@@ -41,6 +47,18 @@ impl ProgramKind {
                 if let Some(port) = url.port {
                     prepare = prepare.arg(format!("-p{port}"));
                 }
+                if let Some(dir) = control_path.filter(|_| cfg!(unix)) {
+                    let mut path = OsString::from("ControlPath=");
+                    path.push(dir.join("%C"));
+                    prepare = prepare
+                        .args(["-o", "ControlMaster=auto", "-o"])
+                        .arg(path)
+                        .args(["-o", "ControlPersist=60"]);
+                }
+                if let Some(askpass) = askpass {
+                    // Without `SSH_ASKPASS_REQUIRE`, the program is only used if there is no terminal.
+                    prepare = prepare.env("SSH_ASKPASS", askpass).env("SSH_ASKPASS_REQUIRE", "force");
+                }
             }
             ProgramKind::Plink | ProgramKind::Putty | ProgramKind::TortoisePlink => {
                 if *self == ProgramKind::TortoisePlink {
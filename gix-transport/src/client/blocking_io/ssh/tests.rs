@@ -130,6 +130,82 @@ mod program_kind {
             }
         }
 
+        #[test]
+        #[cfg(unix)]
+        fn ssh_with_control_path() -> Result {
+            let url = gix_url::parse("ssh://user@host/p".into()).expect("valid url");
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("ssh"),
+                &url,
+                Protocol::V1,
+                false,
+                Some(std::path::Path::new("/tmp/sockets")),
+                None,
+            )?;
+            assert_eq!(
+                args(prepare),
+                joined(&[
+                    "ssh",
+                    "-o",
+                    "ControlMaster=auto",
+                    "-o",
+                    "ControlPath=/tmp/sockets/%C",
+                    "-o",
+                    "ControlPersist=60",
+                    "user@host"
+                ])
+            );
+
+            let prepare = ProgramKind::Plink.prepare_invocation(
+                OsStr::new("plink"),
+                &url,
+                Protocol::V1,
+                false,
+                Some(std::path::Path::new("/tmp/sockets")),
+                None,
+            )?;
+            assert_eq!(args(prepare), "plink user@host", "other programs don't support it");
+            Ok(())
+        }
+
+        #[test]
+        fn ssh_with_askpass() -> Result {
+            let url = gix_url::parse("ssh://user@host/p".into()).expect("valid url");
+            let askpass = std::path::Path::new("/usr/bin/askpass");
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("ssh"),
+                &url,
+                Protocol::V1,
+                false,
+                None,
+                Some(askpass),
+            )?;
+            assert_eq!(
+                prepare.env,
+                [
+                    ("SSH_ASKPASS".into(), askpass.into()),
+                    ("SSH_ASKPASS_REQUIRE".into(), "force".into()),
+                    ("LANG".into(), "C".into()),
+                    ("LC_ALL".into(), "C".into())
+                ],
+                "the askpass program is used even if there is a terminal"
+            );
+
+            let prepare = ProgramKind::Plink.prepare_invocation(
+                OsStr::new("plink"),
+                &url,
+                Protocol::V1,
+                false,
+                None,
+                Some(askpass),
+            )?;
+            assert!(
+                !prepare.env.iter().any(|(name, _)| name == "SSH_ASKPASS"),
+                "other programs don't support it"
+            );
+            Ok(())
+        }
+
         #[test]
         fn tortoise_plink_has_batch_command() {
             assert_eq!(
@@ -192,13 +268,25 @@ mod program_kind {
             let url = gix_url::parse("ssh://host/path".into()).expect("valid url");
 
             let disallow_shell = false;
-            let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("echo hi"),
+                &url,
+                Protocol::V1,
+                disallow_shell,
+                None,
+                None,
+            )?;
             assert!(prepare.use_shell, "shells are used when needed");
 
             let disallow_shell = true;
-            let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("echo hi"),
+                &url,
+                Protocol::V1,
+                disallow_shell,
+                None,
+                None,
+            )?;
             assert!(
                 !prepare.use_shell,
                 "but we can enforce it not to be used as well for historical reasons"
@@ -216,13 +304,16 @@ mod program_kind {
         ) -> std::result::Result<gix_command::Prepare, ssh::invocation::Error> {
             let ssh_cmd = kind.exe().unwrap_or_else(|| OsStr::new("simple"));
             let url = gix_url::parse(url.into()).expect("valid url");
-            kind.prepare_invocation(ssh_cmd, &url, version, false)
+            kind.prepare_invocation(ssh_cmd, &url, version, false, None, None)
         }
         fn call(kind: ProgramKind, url: &str, version: Protocol) -> gix_command::Prepare {
             try_call(kind, url, version).expect("no error")
         }
         fn call_args(kind: ProgramKind, url: &str, version: Protocol) -> String {
-            let cmd = std::process::Command::from(call(kind, url, version));
+            args(call(kind, url, version))
+        }
+        fn args(prepare: gix_command::Prepare) -> String {
+            let cmd = std::process::Command::from(prepare);
             format!(
                 "{} {}",
                 cmd.get_program().to_string_lossy(),
@@ -1,4 +1,7 @@
-use std::{io::Write, ops::DerefMut};
+use std::{
+    io::{BufRead, Write},
+    ops::DerefMut,
+};
 
 use bstr::BString;
 
@@ -39,6 +42,15 @@ pub trait Transport: TransportWithoutIO {
         service: Service,
         extra_parameters: &'a [(&'a str, Option<&'a str>)],
     ) -> Result<SetServiceResponse<'_>, Error>;
+
+    /// Retrieve the file at `path`, relative to the repository root of the remote, or `None` if it doesn't exist.
+    ///
+    /// This is only supported by transports that can talk to [dumb][TransportWithoutIO::is_dumb()] remotes,
+    /// and the returned reader must be exhausted before making another call.
+    fn get_file(&mut self, path: &str) -> Result<Option<Box<dyn BufRead + '_>>, Error> {
+        let _ = path;
+        Err(Error::DumbProtocolUnsupported)
+    }
 }
 
 // Would be nice if the box implementation could auto-forward to all implemented traits.
@@ -50,6 +62,10 @@ impl<T: Transport + ?Sized> Transport for Box<T> {
     ) -> Result<SetServiceResponse<'_>, Error> {
         self.deref_mut().handshake(service, extra_parameters)
     }
+
+    fn get_file(&mut self, path: &str) -> Result<Option<Box<dyn BufRead + '_>>, Error> {
+        self.deref_mut().get_file(path)
+    }
 }
 
 impl<T: Transport + ?Sized> Transport for &mut T {
@@ -60,6 +76,10 @@ impl<T: Transport + ?Sized> Transport for &mut T {
     ) -> Result<SetServiceResponse<'_>, Error> {
         self.deref_mut().handshake(service, extra_parameters)
     }
+
+    fn get_file(&mut self, path: &str) -> Result<Option<Box<dyn BufRead + '_>>, Error> {
+        self.deref_mut().get_file(path)
+    }
 }
 
 /// An extension trait to add more methods to everything implementing [`Transport`].
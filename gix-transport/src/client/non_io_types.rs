@@ -140,6 +140,8 @@ mod error {
         Http(#[from] HttpError),
         #[error(transparent)]
         SshInvocation(SshInvocationError),
+        #[error("The transport doesn't support retrieving files from remotes that only support the 'dumb' protocol")]
+        DumbProtocolUnsupported,
         #[error("The repository path '{path}' could be mistaken for a command-line argument")]
         AmbiguousPath { path: BString },
     }
@@ -53,6 +53,15 @@ pub trait TransportWithoutIO {
         &[]
     }
 
+    /// Returns true if the remote only serves plain files as it doesn't support the 'smart' protocol, as determined
+    /// during the handshake.
+    ///
+    /// If so, [requests][TransportWithoutIO::request()] aren't possible and objects have to be obtained by walking
+    /// the remote object database file by file instead.
+    fn is_dumb(&self) -> bool {
+        false
+    }
+
     /// Returns true if the transport provides persistent connections across multiple requests, or false otherwise.
     /// Not being persistent implies that certain information has to be resent on each 'turn'
     /// of the fetch negotiation or that the end of interaction (i.e. no further request will be made) has to be indicated
@@ -89,6 +98,10 @@ impl<T: TransportWithoutIO + ?Sized> TransportWithoutIO for Box<T> {
         self.deref().supported_protocol_versions()
     }
 
+    fn is_dumb(&self) -> bool {
+        self.deref().is_dumb()
+    }
+
     fn connection_persists_across_multiple_requests(&self) -> bool {
         self.deref().connection_persists_across_multiple_requests()
     }
@@ -121,6 +134,10 @@ impl<T: TransportWithoutIO + ?Sized> TransportWithoutIO for &mut T {
         self.deref().supported_protocol_versions()
     }
 
+    fn is_dumb(&self) -> bool {
+        self.deref().is_dumb()
+    }
+
     fn connection_persists_across_multiple_requests(&self) -> bool {
         self.deref().connection_persists_across_multiple_requests()
     }
@@ -9,6 +9,10 @@ pub struct Ancestors<Find, Predicate, StateMut> {
     state: StateMut,
     parents: Parents,
     sorting: Sorting,
+    since: Option<gix_date::SecondsSinceUnixEpoch>,
+    until: Option<gix_date::SecondsSinceUnixEpoch>,
+    slop: usize,
+    grafts: Grafts,
 }
 
 /// Specify how to handle commit parents during traversal.
@@ -74,6 +78,12 @@ pub enum Sorting {
 /// Note that this list is truncated if [`Parents::First`] was used.
 pub type ParentIds = SmallVec<[gix_hash::ObjectId; 1]>;
 
+/// A mapping of commits to the parents that should be used instead of the ones they actually have, similar to
+/// what git does with the `info/grafts` file.
+///
+/// Commits at the boundary of a shallow clone can be represented by grafting them onto no parents at all.
+pub type Grafts = gix_hashtable::HashMap<gix_hash::ObjectId, ParentIds>;
+
 /// Information about a commit that we obtained naturally as part of the iteration.
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct Info {
@@ -81,9 +91,12 @@ pub struct Info {
     pub id: gix_hash::ObjectId,
     /// All parent ids we have encountered. Note that these will be at most one if [`Parents::First`] is enabled.
     pub parent_ids: ParentIds,
-    /// The time at which the commit was created. It's only `Some(_)` if sorting is not [`Sorting::BreadthFirst`], as the walk
-    /// needs to require the commit-date.
+    /// The time at which the commit was created. It's only `Some(_)` if sorting is not [`Sorting::BreadthFirst`]
+    /// or if a date limit is set, as the walk needs to require the commit-date.
     pub commit_time: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// If `true`, the [`parent_ids`](Self::parent_ids) are the ones of a graft instead of the ones stored in the commit,
+    /// which is the case for commits at the boundary of a shallow clone.
+    pub is_grafted: bool,
 }
 
 ///
@@ -99,7 +112,13 @@ pub mod ancestors {
     use gix_object::{CommitRefIter, FindExt};
     use smallvec::SmallVec;
 
-    use crate::commit::{collect_parents, Ancestors, Either, Info, ParentIds, Parents, Sorting};
+    use crate::commit::{
+        collect_parents, committer_time, parent_ids, Ancestors, Either, Grafts, Info, ParentIds, Parents, Sorting,
+    };
+
+    /// The amount of commits older than the `since` limit we traverse through before assuming that all remaining
+    /// commits are older as well, to deal with clock skew like git does.
+    const SLOP: usize = 5;
 
     /// The error is part of the item returned by the [Ancestors] iterator.
     #[derive(Debug, thiserror::Error)]
@@ -187,6 +206,33 @@ pub mod ancestors {
             self
         }
 
+        /// Hide all commits whose commit time is older than `since`, similar to `git log --since`, and stop traversing
+        /// their ancestors.
+        ///
+        /// If the [commit-graph](Self::commit_graph()) provides corrected commit dates, the traversal stops exactly
+        /// where no commit newer than `since` can be found anymore, even in the presence of clock skew.
+        /// Otherwise, when sorting by commit time, a few more commits are traversed in case a commit with a skewed
+        /// clock hides newer ancestors, similar to what git does.
+        /// Without sorting by commit time, the ancestors of older commits are never traversed.
+        pub fn since(mut self, since: Option<SecondsSinceUnixEpoch>) -> Self {
+            self.since = since;
+            self
+        }
+
+        /// Hide all commits whose commit time is newer than `until`, similar to `git log --until`, while still traversing
+        /// their ancestors.
+        pub fn until(mut self, until: Option<SecondsSinceUnixEpoch>) -> Self {
+            self.until = until;
+            self
+        }
+
+        /// Use the parents of `grafts` instead of the actual parents of the respective commits, which allows to
+        /// stop at the boundary of shallow clones instead of failing to find the missing parents.
+        pub fn grafts(mut self, grafts: Grafts) -> Self {
+            self.grafts = grafts;
+            self
+        }
+
         /// Set the commitgraph as `cache` to greatly accelerate any traversal.
         ///
         /// The cache will be used if possible, but we will fall-back without error to using the object
@@ -271,6 +317,10 @@ pub mod ancestors {
                 state,
                 parents: Default::default(),
                 sorting: Default::default(),
+                since: None,
+                until: None,
+                slop: SLOP,
+                grafts: Default::default(),
             }
         }
     }
@@ -299,20 +349,71 @@ pub mod ancestors {
         type Item = Result<Info, Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            if matches!(self.parents, Parents::First) {
-                self.next_by_topology()
-            } else {
-                match self.sorting {
-                    Sorting::BreadthFirst => self.next_by_topology(),
-                    Sorting::ByCommitTimeNewestFirst => self.next_by_commit_date(None),
-                    Sorting::ByCommitTimeNewestFirstCutoffOlderThan { seconds } => {
-                        self.next_by_commit_date(seconds.into())
+            loop {
+                let item = if matches!(self.parents, Parents::First) {
+                    self.next_by_topology()
+                } else {
+                    match self.sorting {
+                        Sorting::BreadthFirst => self.next_by_topology(),
+                        Sorting::ByCommitTimeNewestFirst => self.next_by_commit_date(None),
+                        Sorting::ByCommitTimeNewestFirstCutoffOlderThan { seconds } => {
+                            self.next_by_commit_date(seconds.into())
+                        }
                     }
+                };
+                match item {
+                    Some(Ok(info)) if self.is_hidden(&info) => continue,
+                    item => return item,
                 }
             }
         }
     }
 
+    impl<Find, Predicate, StateMut> Ancestors<Find, Predicate, StateMut> {
+        fn has_date_limits(&self) -> bool {
+            self.since.is_some() || self.until.is_some()
+        }
+
+        fn is_hidden(&self, info: &Info) -> bool {
+            info.commit_time.map_or(false, |time| {
+                self.since.map_or(false, |since| time < since) || self.until.map_or(false, |until| time > until)
+            })
+        }
+    }
+
+    /// Return `true` if the parents of a commit created at `commit_time` should be traversed, given the `since` limit
+    /// and the commit's `corrected_commit_date` if known.
+    ///
+    /// If `by_date` is `true`, `newest_queued` is the commit time of the newest commit still in the queue. While it's not older
+    /// than the commit at hand, the latter may only be older than `since` due to clock skew and we keep traversing.
+    /// Otherwise, `slop` is the amount of older commits we are still willing to traverse through, just like `git` does.
+    fn traverse_parents_since(
+        since: Option<SecondsSinceUnixEpoch>,
+        commit_time: SecondsSinceUnixEpoch,
+        corrected_commit_date: Option<u64>,
+        slop: &mut usize,
+        by_date: bool,
+        newest_queued: Option<SecondsSinceUnixEpoch>,
+    ) -> bool {
+        let Some(since) = since else { return true };
+        if commit_time >= since {
+            *slop = SLOP;
+            return true;
+        }
+        match corrected_commit_date {
+            Some(date) => SecondsSinceUnixEpoch::try_from(date).map_or(true, |date| date >= since),
+            None if by_date && newest_queued.map_or(false, |newest| commit_time <= newest) => {
+                *slop = SLOP;
+                true
+            }
+            None if by_date && *slop > 0 => {
+                *slop -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
     impl Sorting {
         /// If not topo sort, provide the cutoff date if present.
         fn cutoff_time(&self) -> Option<SecondsSinceUnixEpoch> {
@@ -337,7 +438,9 @@ pub mod ancestors {
             let state = self.state.borrow_mut();
 
             let (commit_time, oid) = state.queue.pop()?;
+            let newest_queued = state.queue.peek().map(|(time, _)| *time);
             let mut parents: ParentIds = Default::default();
+            let graft = self.grafts.get(&oid);
             match super::find(self.cache.as_ref(), &self.objects, &oid, &mut state.buf) {
                 Ok(Either::CachedCommit(commit)) => {
                     if !collect_parents(&mut state.parent_ids, self.cache.as_ref(), commit.iter_parents()) {
@@ -345,6 +448,23 @@ pub mod ancestors {
                         self.cache = None;
                         return self.next_by_commit_date(cutoff_older_than);
                     }
+                    if let Some(graft) = graft {
+                        state.parent_ids.clear();
+                        for id in graft {
+                            let time = committer_time(self.cache.as_ref(), &self.objects, id, &mut state.parents_buf);
+                            state.parent_ids.push((*id, time));
+                        }
+                    }
+                    if !traverse_parents_since(
+                        self.since,
+                        commit_time,
+                        commit.corrected_commit_date(),
+                        &mut self.slop,
+                        true,
+                        newest_queued,
+                    ) {
+                        state.parent_ids.clear();
+                    }
                     for (id, parent_commit_time) in state.parent_ids.drain(..) {
                         parents.push(id);
                         let was_inserted = state.seen.insert(id);
@@ -359,28 +479,36 @@ pub mod ancestors {
                     }
                 }
                 Ok(Either::CommitRefIter(commit_iter)) => {
-                    for token in commit_iter {
-                        match token {
-                            Ok(gix_object::commit::ref_iter::Token::Tree { .. }) => continue,
-                            Ok(gix_object::commit::ref_iter::Token::Parent { id }) => {
-                                parents.push(id);
-                                let was_inserted = state.seen.insert(id);
-                                if !(was_inserted && (self.predicate)(&id)) {
-                                    continue;
-                                }
-
-                                let parent = self.objects.find_commit_iter(id.as_ref(), &mut state.parents_buf).ok();
-                                let parent_commit_time = parent
-                                    .and_then(|parent| parent.committer().ok().map(|committer| committer.time.seconds))
-                                    .unwrap_or_default();
-
-                                match cutoff_older_than {
-                                    Some(cutoff_older_than) if parent_commit_time < cutoff_older_than => continue,
-                                    Some(_) | None => state.queue.insert(parent_commit_time, id),
-                                }
-                            }
-                            Ok(_unused_token) => break,
+                    if !traverse_parents_since(self.since, commit_time, None, &mut self.slop, true, newest_queued) {
+                        return Some(Ok(Info {
+                            id: oid,
+                            parent_ids: parents,
+                            commit_time: Some(commit_time),
+                            is_grafted: graft.is_some(),
+                        }));
+                    }
+                    let parent_ids = match graft {
+                        Some(graft) => graft.clone(),
+                        None => match parent_ids(commit_iter) {
+                            Ok(ids) => ids,
                             Err(err) => return Some(Err(err.into())),
+                        },
+                    };
+                    for id in parent_ids {
+                        parents.push(id);
+                        let was_inserted = state.seen.insert(id);
+                        if !(was_inserted && (self.predicate)(&id)) {
+                            continue;
+                        }
+
+                        let parent = self.objects.find_commit_iter(id.as_ref(), &mut state.parents_buf).ok();
+                        let parent_commit_time = parent
+                            .and_then(|parent| parent.committer().ok().map(|committer| committer.time.seconds))
+                            .unwrap_or_default();
+
+                        match cutoff_older_than {
+                            Some(cutoff_older_than) if parent_commit_time < cutoff_older_than => continue,
+                            Some(_) | None => state.queue.insert(parent_commit_time, id),
                         }
                     }
                 }
@@ -390,6 +518,7 @@ pub mod ancestors {
                 id: oid,
                 parent_ids: parents,
                 commit_time: Some(commit_time),
+                is_grafted: graft.is_some(),
             }))
         }
     }
@@ -402,9 +531,12 @@ pub mod ancestors {
         StateMut: BorrowMut<State>,
     {
         fn next_by_topology(&mut self) -> Option<Result<Info, Error>> {
+            let has_date_limits = self.has_date_limits();
             let state = self.state.borrow_mut();
             let oid = state.next.pop_front()?;
             let mut parents: ParentIds = Default::default();
+            let mut commit_time = None;
+            let graft = self.grafts.get(&oid);
             match super::find(self.cache.as_ref(), &self.objects, &oid, &mut state.buf) {
                 Ok(Either::CachedCommit(commit)) => {
                     if !collect_parents(&mut state.parent_ids, self.cache.as_ref(), commit.iter_parents()) {
@@ -412,6 +544,24 @@ pub mod ancestors {
                         self.cache = None;
                         return self.next_by_topology();
                     }
+                    if let Some(graft) = graft {
+                        state.parent_ids.clear();
+                        state.parent_ids.extend(graft.iter().map(|id| (*id, 0)));
+                    }
+                    if has_date_limits {
+                        let time = commit.committer_timestamp() as SecondsSinceUnixEpoch;
+                        commit_time = Some(time);
+                        if !traverse_parents_since(
+                            self.since,
+                            time,
+                            commit.corrected_commit_date(),
+                            &mut self.slop,
+                            false,
+                            None,
+                        ) {
+                            state.parent_ids.clear();
+                        }
+                    }
 
                     for (id, _commit_time) in state.parent_ids.drain(..) {
                         parents.push(id);
@@ -425,21 +575,36 @@ pub mod ancestors {
                     }
                 }
                 Ok(Either::CommitRefIter(commit_iter)) => {
-                    for token in commit_iter {
-                        match token {
-                            Ok(gix_object::commit::ref_iter::Token::Tree { .. }) => continue,
-                            Ok(gix_object::commit::ref_iter::Token::Parent { id }) => {
-                                parents.push(id);
-                                let was_inserted = state.seen.insert(id);
-                                if was_inserted && (self.predicate)(&id) {
-                                    state.next.push_back(id);
-                                }
-                                if matches!(self.parents, Parents::First) {
-                                    break;
-                                }
-                            }
-                            Ok(_a_token_past_the_parents) => break,
+                    if has_date_limits {
+                        let time = match commit_iter.committer() {
+                            Ok(committer) => committer.time.seconds,
                             Err(err) => return Some(Err(err.into())),
+                        };
+                        commit_time = Some(time);
+                        if !traverse_parents_since(self.since, time, None, &mut self.slop, false, None) {
+                            return Some(Ok(Info {
+                                id: oid,
+                                parent_ids: parents,
+                                commit_time,
+                                is_grafted: graft.is_some(),
+                            }));
+                        }
+                    }
+                    let parent_ids = match graft {
+                        Some(graft) => graft.clone(),
+                        None => match parent_ids(commit_iter) {
+                            Ok(ids) => ids,
+                            Err(err) => return Some(Err(err.into())),
+                        },
+                    };
+                    for id in parent_ids {
+                        parents.push(id);
+                        let was_inserted = state.seen.insert(id);
+                        if was_inserted && (self.predicate)(&id) {
+                            state.next.push_back(id);
+                        }
+                        if matches!(self.parents, Parents::First) {
+                            break;
                         }
                     }
                 }
@@ -448,7 +613,8 @@ pub mod ancestors {
             Some(Ok(Info {
                 id: oid,
                 parent_ids: parents,
-                commit_time: None,
+                commit_time,
+                is_grafted: graft.is_some(),
             }))
         }
     }
@@ -495,3 +661,36 @@ where
         None => objects.find_commit_iter(id, buf).map(Either::CommitRefIter),
     }
 }
+
+/// Return the commit time of the commit with `id`, or `0` if it can't be found.
+fn committer_time<Find>(
+    cache: Option<&gix_commitgraph::Graph>,
+    objects: Find,
+    id: &gix_hash::oid,
+    buf: &mut Vec<u8>,
+) -> gix_date::SecondsSinceUnixEpoch
+where
+    Find: gix_object::Find,
+{
+    match find(cache, objects, id, buf) {
+        Ok(Either::CachedCommit(commit)) => commit.committer_timestamp() as gix_date::SecondsSinceUnixEpoch,
+        Ok(Either::CommitRefIter(commit_iter)) => commit_iter
+            .committer()
+            .map(|committer| committer.time.seconds)
+            .unwrap_or_default(),
+        Err(_) => 0,
+    }
+}
+
+/// Collect the ids of all parents of the commit behind `commit_iter`.
+fn parent_ids(commit_iter: gix_object::CommitRefIter<'_>) -> Result<ParentIds, gix_object::decode::Error> {
+    let mut ids = ParentIds::new();
+    for token in commit_iter {
+        match token? {
+            gix_object::commit::ref_iter::Token::Tree { .. } => continue,
+            gix_object::commit::ref_iter::Token::Parent { id } => ids.push(id),
+            _a_token_past_the_parents => break,
+        }
+    }
+    Ok(ids)
+}
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+
+use gix_object::{
+    bstr::{BStr, ByteSlice, ByteVec},
+    tree,
+};
+
+use crate::tree::{visit::Action, Filter, Visit};
+
+/// Lifecycle
+impl<V, F> Filter<V, F>
+where
+    V: Visit,
+    F: FnMut(&BStr, bool) -> bool,
+{
+    /// Pass only the entries for which `filter(path, is_tree)` returns `true` on to `delegate`.
+    pub fn new(delegate: V, filter: F) -> Self {
+        Filter {
+            delegate,
+            filter,
+            path_deque: VecDeque::new(),
+            path: Default::default(),
+        }
+    }
+}
+
+/// Access
+impl<V, F> Filter<V, F> {
+    /// Return the delegate receiving the accepted entries.
+    pub fn delegate(&self) -> &V {
+        &self.delegate
+    }
+
+    /// Return the delegate receiving the accepted entries, consuming `self`.
+    pub fn into_delegate(self) -> V {
+        self.delegate
+    }
+}
+
+impl<V, F> Filter<V, F> {
+    fn pop_element(&mut self) {
+        if let Some(pos) = self.path.rfind_byte(b'/') {
+            self.path.resize(pos, 0);
+        } else {
+            self.path.clear();
+        }
+    }
+
+    fn push_element(&mut self, name: &BStr) {
+        if !self.path.is_empty() {
+            self.path.push(b'/');
+        }
+        self.path.push_str(name);
+    }
+}
+
+impl<V, F> Visit for Filter<V, F>
+where
+    V: Visit,
+    F: FnMut(&BStr, bool) -> bool,
+{
+    fn pop_front_tracked_path_and_set_current(&mut self) {
+        self.path = self
+            .path_deque
+            .pop_front()
+            .expect("every call is matched with push_tracked_path_component");
+        self.delegate.pop_front_tracked_path_and_set_current();
+    }
+
+    fn push_back_tracked_path_component(&mut self, component: &BStr) {
+        self.push_element(component);
+        self.path_deque.push_back(self.path.clone());
+        self.delegate.push_back_tracked_path_component(component);
+    }
+
+    fn push_path_component(&mut self, component: &BStr) {
+        self.push_element(component);
+        self.delegate.push_path_component(component);
+    }
+
+    fn pop_path_component(&mut self) {
+        self.pop_element();
+        self.delegate.pop_path_component();
+    }
+
+    fn visit_tree(&mut self, entry: &tree::EntryRef<'_>) -> Action {
+        if (self.filter)(self.path.as_ref(), true) {
+            self.delegate.visit_tree(entry)
+        } else {
+            Action::Skip
+        }
+    }
+
+    fn visit_nontree(&mut self, entry: &tree::EntryRef<'_>) -> Action {
+        if (self.filter)(self.path.as_ref(), false) {
+            self.delegate.visit_nontree(entry)
+        } else {
+            Action::Continue
+        }
+    }
+}
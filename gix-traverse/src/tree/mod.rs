@@ -40,6 +40,22 @@ pub struct Recorder {
     pub records: Vec<recorder::Entry>,
 }
 
+/// A [Visit] implementation which passes only the entries accepted by a filter on to its delegate, while not
+/// traversing trees at all that the filter deems unable to contain accepted entries.
+///
+/// The filter is called as `filter(path, is_tree) -> bool` with the full path to the entry, and is expected to return
+/// `true` for trees that *may* contain accepted entries, and for all other entries only if they are accepted.
+/// This is typically implemented with a pathspec search, to only traverse the portions of a large tree that are relevant.
+///
+/// Note that the delegate's own path tracking remains unaffected.
+#[derive(Clone, Debug)]
+pub struct Filter<V, F> {
+    delegate: V,
+    filter: F,
+    path_deque: VecDeque<BString>,
+    path: BString,
+}
+
 ///
 pub mod visit {
     /// What to do after an entry was [recorded][super::Visit::visit_tree()].
@@ -64,6 +80,8 @@ pub mod visit {
 ///
 pub mod recorder;
 
+mod filter;
+
 ///
 pub mod breadthfirst;
 pub use breadthfirst::impl_::traverse as breadthfirst;
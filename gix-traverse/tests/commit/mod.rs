@@ -427,4 +427,183 @@ mod ancestor {
             .check()
         }
     }
+
+    /// Linear histories with commits whose clock was skewed to 1990, between commits made in 2000 and later.
+    mod clock_skew {
+        use gix_hash::ObjectId;
+        use gix_traverse::commit::{ancestors, Ancestors, Sorting};
+
+        use crate::hex_to_id;
+
+        const C1: &str = "134385f6d781b7e97062102c6a483440bfda2a03";
+        const C2: &str = "6ff3cd597172534f0825a355ec2cab6d073c0a94";
+        const S1: &str = "ffd4711ed4cb43e98526f7f5de9f89af6618a293";
+        const S2: &str = "513f0dd25898294a84299b7923e46003e3ee7050";
+        const S3: &str = "0a980374f2f1532e929cf54542b9b2f44f9fe94b";
+        const WITHIN_SLOP_C3: &str = "dba93f37435e67b4a7cc2528758c680dac03c074";
+        const BEYOND_SLOP_C3: &str = "c8a33a5e04132922eb0c78a2f195d575088adf4e";
+        const SINCE: i64 = 1104537600; // =2005-01-01 00:00:00 +0000
+
+        fn traverse(
+            repo_name: &str,
+            tip: &str,
+            sorting: Sorting,
+            since: Option<i64>,
+            until: Option<i64>,
+            use_commitgraph: bool,
+        ) -> crate::Result<Vec<ObjectId>> {
+            let dir = gix_testtools::scripted_fixture_read_only_standalone(
+                "make_traversal_repo_for_commits_with_clock_skew.sh",
+            )?;
+            let objects_dir = dir.join(repo_name).join(".git").join("objects");
+            let store = gix_odb::at(&objects_dir)?;
+            let graph = use_commitgraph.then(|| gix_commitgraph::at(objects_dir.join("info")).expect("graph present"));
+            Ok(
+                Ancestors::new(Some(hex_to_id(tip)), ancestors::State::default(), &store)
+                    .sorting(sorting)?
+                    .commit_graph(graph)
+                    .since(since)
+                    .until(until)
+                    .map(|res| res.map(|info| info.id))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+
+        fn ids(hex: &[&str]) -> Vec<ObjectId> {
+            hex.iter().copied().map(hex_to_id).collect()
+        }
+
+        #[test]
+        fn since_traverses_through_skewed_commits_within_slop() -> crate::Result {
+            for use_commitgraph in [false, true] {
+                assert_eq!(
+                    traverse(
+                        "within-slop",
+                        WITHIN_SLOP_C3,
+                        Sorting::ByCommitTimeNewestFirst,
+                        Some(SINCE),
+                        None,
+                        use_commitgraph
+                    )?,
+                    ids(&[WITHIN_SLOP_C3, C2]),
+                    "skewed commits are hidden, but c2 is found behind them, and c1 is too old"
+                );
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn since_needs_corrected_commit_dates_beyond_slop() -> crate::Result {
+            for (use_commitgraph, expected) in [(false, ids(&[BEYOND_SLOP_C3])), (true, ids(&[BEYOND_SLOP_C3, C2]))] {
+                assert_eq!(
+                    traverse(
+                        "beyond-slop",
+                        BEYOND_SLOP_C3,
+                        Sorting::ByCommitTimeNewestFirst,
+                        Some(SINCE),
+                        None,
+                        use_commitgraph
+                    )?,
+                    expected,
+                    "without corrected commit dates, too many skewed commits make the traversal stop early, just like in git"
+                );
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn since_in_breadth_first_mode_only_traverses_through_skewed_commits_with_corrected_commit_dates(
+        ) -> crate::Result {
+            for (use_commitgraph, expected) in [(false, ids(&[WITHIN_SLOP_C3])), (true, ids(&[WITHIN_SLOP_C3, C2]))] {
+                assert_eq!(
+                    traverse(
+                        "within-slop",
+                        WITHIN_SLOP_C3,
+                        Sorting::BreadthFirst,
+                        Some(SINCE),
+                        None,
+                        use_commitgraph
+                    )?,
+                    expected
+                );
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn until_hides_newer_commits_but_traverses_their_ancestors() -> crate::Result {
+            for sorting in [Sorting::BreadthFirst, Sorting::ByCommitTimeNewestFirst] {
+                for use_commitgraph in [false, true] {
+                    assert_eq!(
+                        traverse(
+                            "within-slop",
+                            WITHIN_SLOP_C3,
+                            sorting,
+                            None,
+                            Some(1275350400), // =2010-06-01 00:00:00 +0000
+                            use_commitgraph
+                        )?,
+                        ids(&[S3, S2, S1, C2, C1])
+                    );
+                }
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn since_and_until_combined() -> crate::Result {
+            for use_commitgraph in [false, true] {
+                assert_eq!(
+                    traverse(
+                        "within-slop",
+                        WITHIN_SLOP_C3,
+                        Sorting::ByCommitTimeNewestFirst,
+                        Some(SINCE),
+                        Some(1275350400), // =2010-06-01 00:00:00 +0000
+                        use_commitgraph
+                    )?,
+                    ids(&[C2])
+                );
+            }
+            Ok(())
+        }
+    }
+
+    mod grafts {
+        use gix_traverse::commit::{ancestors, Ancestors, Grafts, Sorting};
+
+        use crate::hex_to_id;
+
+        #[test]
+        fn replace_parents_and_stop_at_commits_without_parents() -> crate::Result {
+            let dir =
+                gix_testtools::scripted_fixture_read_only_standalone("make_traversal_repo_for_commits_with_dates.sh")?;
+            let objects_dir = dir.join(".git").join("objects");
+            let store = gix_odb::at(&objects_dir)?;
+            let merge = hex_to_id("288e509293165cb5630d08f4185bdf2445bf6170");
+            let b1c1 = hex_to_id("bcb05040a6925f2ff5e10d3ae1f9264f2e8c43ac");
+            let grafts: Grafts = [(merge, Some(b1c1).into_iter().collect()), (b1c1, Default::default())]
+                .into_iter()
+                .collect();
+
+            for use_commitgraph in [false, true] {
+                for sorting in [Sorting::BreadthFirst, Sorting::ByCommitTimeNewestFirst] {
+                    let graph =
+                        use_commitgraph.then(|| gix_commitgraph::at(objects_dir.join("info")).expect("graph present"));
+                    let commits = Ancestors::new(Some(merge), ancestors::State::default(), &store)
+                        .grafts(grafts.clone())
+                        .sorting(sorting)?
+                        .commit_graph(graph)
+                        .map(|res| res.map(|info| (info.id, info.parent_ids.to_vec(), info.is_grafted)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    assert_eq!(
+                        commits,
+                        [(merge, vec![b1c1], true), (b1c1, vec![], true)],
+                        "the parents of the graft are used, and traversal stops at the commit grafted onto nothing"
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,28 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function commit_at() {
+  local message=${1:?first argument is the commit message}
+  local date=${2:?second argument is the commit date}
+  GIT_COMMITTER_DATE="$date +0000" git commit -q --allow-empty -m "$message"
+}
+
+# A linear history with `skewed` commits whose clock was set to 1990, between commits made in 2000 and later.
+function setup_repo() {
+  local skewed=${1:?need amount of skewed commits}
+  git init -q
+  git config commitGraph.generationVersion 2
+
+  commit_at c1 "2000-01-02 00:00:00"
+  commit_at c2 "2010-01-02 00:00:00"
+  for i in $(seq 1 "$skewed"); do
+    commit_at "s$i" "1990-01-0$i 00:00:00"
+  done
+  commit_at c3 "2011-01-02 00:00:00"
+
+  git commit-graph write --no-progress --reachable
+  git repack -adq
+}
+
+(mkdir within-slop && cd within-slop && setup_repo 3)
+(mkdir beyond-slop && cd beyond-slop && setup_repo 6)
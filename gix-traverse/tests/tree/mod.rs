@@ -1,4 +1,4 @@
-use gix_object::bstr::BString;
+use gix_object::bstr::{BStr, BString};
 use gix_odb::pack::FindExt;
 use gix_traverse::{tree, tree::recorder::Location};
 
@@ -147,3 +147,46 @@ fn breadth_first_no_location() -> crate::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn breadth_first_with_pathspec_filter_skips_unmatched_trees() -> crate::Result<()> {
+    let db = db()?;
+    let mut buf = Vec::new();
+    let mut buf2 = Vec::new();
+    let mut commit = db
+        .find_commit_iter(&hex_to_id("85df34aa34848b8138b2b3dcff5fb5c2b734e0ce"), &mut buf)?
+        .0;
+    let mut search = gix_pathspec::Search::from_specs(
+        [gix_pathspec::parse(b"f/d", Default::default())?],
+        None,
+        std::path::Path::new(""),
+    )?;
+    let mut filter = tree::Filter::new(tree::Recorder::default(), |path: &BStr, is_tree| {
+        if is_tree {
+            search.can_match_relative_path(path, Some(true))
+        } else {
+            search
+                .pattern_matching_relative_path(path, Some(false), &mut |_, _, _, _| false)
+                .map_or(false, |m| !m.is_excluded())
+        }
+    });
+    gix_traverse::tree::breadthfirst(
+        db.find_tree_iter(&commit.tree_id().expect("a tree is available in a commit"), &mut buf2)?
+            .0,
+        tree::breadthfirst::State::default(),
+        &db,
+        &mut filter,
+    )?;
+
+    assert_eq!(
+        filter
+            .into_delegate()
+            .records
+            .into_iter()
+            .map(|e| e.filepath)
+            .collect::<Vec<_>>(),
+        ["f", "f/d", "f/d/x"].into_iter().map(BString::from).collect::<Vec<_>>(),
+        "the trees `d` and `e` are never entered, and neither are non-matching blobs recorded"
+    );
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::{hash_map, HashMap, HashSet},
+    path::Path,
+};
+
+use bstr::{BStr, ByteSlice};
+use gix_index::entry::{stat, Flags, Mode, Stat};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::checkout::{windows_names, Conflict, ConflictKind, Error};
+
+/// Find all conflicts that checking out `index` into `dir` would run into, without writing anything.
+#[allow(clippy::result_large_err)]
+pub fn conflicts(
+    index: &gix_index::State,
+    paths: &gix_index::PathStorageRef,
+    dir: &Path,
+    stat_options: stat::Options,
+) -> Result<Vec<Conflict>, Error> {
+    let mut out = Vec::new();
+    let mut seen = Seen::default();
+    let mut checked_dirs = HashSet::new();
+    for entry in index.entries() {
+        if entry.flags.contains(Flags::SKIP_WORKTREE) {
+            continue;
+        }
+        let rela_path = entry.path_in(paths);
+        for pos in rela_path.find_iter(b"/") {
+            out.extend(seen.check(rela_path[..pos].as_bstr(), true));
+        }
+        out.extend(seen.check(rela_path, false));
+        if windows_names::is_invalid(rela_path) {
+            out.push(Conflict {
+                path: rela_path.to_owned(),
+                kind: ConflictKind::InvalidOnWindows,
+            });
+        }
+
+        if let Some(path) = untracked(dir, rela_path, entry, &mut checked_dirs, stat_options)? {
+            out.push(Conflict {
+                path: path.to_owned(),
+                kind: ConflictKind::Untracked,
+            });
+        }
+    }
+    out.sort();
+    out.dedup();
+    Ok(out)
+}
+
+/// Return `rela_path` or one of its leading directories if something exists there in `dir` that checking out `entry` would overwrite.
+#[allow(clippy::result_large_err)]
+fn untracked<'a>(
+    dir: &Path,
+    rela_path: &'a BStr,
+    entry: &gix_index::Entry,
+    checked_dirs: &mut HashSet<&'a BStr>,
+    stat_options: stat::Options,
+) -> Result<Option<&'a BStr>, Error> {
+    for pos in rela_path.find_iter(b"/") {
+        let leading_dir = rela_path[..pos].as_bstr();
+        if checked_dirs.contains(leading_dir) {
+            continue;
+        }
+        match metadata(&dir.join(gix_path::from_bstr(leading_dir)))? {
+            None => return Ok(None),
+            Some(meta) if !meta.is_dir() => return Ok(Some(leading_dir)),
+            Some(_) => {
+                checked_dirs.insert(leading_dir);
+            }
+        }
+    }
+    Ok(match metadata(&dir.join(gix_path::from_bstr(rela_path)))? {
+        None => None,
+        Some(meta) if entry.mode == Mode::COMMIT => (!meta.is_dir()).then_some(rela_path),
+        Some(meta) if meta.is_dir() => Some(rela_path),
+        Some(meta) => (!Stat::from_fs(&meta)?.matches(&entry.stat, stat_options)).then_some(rela_path),
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn metadata(path: &Path) -> Result<Option<gix_index::fs::Metadata>, Error> {
+    match gix_index::fs::Metadata::from_path_no_follow(path) {
+        Ok(meta) => Ok(Some(meta)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The paths seen so far, keyed by their forms after unicode normalization and case-folding respectively.
+#[derive(Default)]
+struct Seen<'a> {
+    by_unicode: HashMap<String, (&'a BStr, bool)>,
+    by_case: HashMap<String, (&'a BStr, bool)>,
+}
+
+impl<'a> Seen<'a> {
+    /// Record `path` and return a conflict if it is a different path than one seen before, yet refers to the same
+    /// item on some filesystems. Directories only conflict with files, as their content is merged otherwise.
+    fn check(&mut self, path: &'a BStr, is_dir: bool) -> Option<Conflict> {
+        let normalized: String = path.to_str_lossy().nfc().collect();
+        let folded = normalized.to_lowercase();
+        if let Some(kind) = record(&mut self.by_unicode, normalized, path, is_dir, |other| {
+            ConflictKind::Unicode { other }
+        }) {
+            return kind.map(|kind| Conflict {
+                path: path.to_owned(),
+                kind,
+            });
+        }
+        record(&mut self.by_case, folded, path, is_dir, |other| ConflictKind::Case {
+            other,
+        })
+        .flatten()
+        .map(|kind| Conflict {
+            path: path.to_owned(),
+            kind,
+        })
+    }
+}
+
+/// Record `path` under `key` in `map`, returning `None` if it wasn't seen before under the same key, and `Some(None)` if it
+/// doesn't conflict with what's there.
+fn record<'a>(
+    map: &mut HashMap<String, (&'a BStr, bool)>,
+    key: String,
+    path: &'a BStr,
+    is_dir: bool,
+    make_kind: impl FnOnce(bstr::BString) -> ConflictKind,
+) -> Option<Option<ConflictKind>> {
+    match map.entry(key) {
+        hash_map::Entry::Vacant(entry) => {
+            entry.insert((path, is_dir));
+            None
+        }
+        hash_map::Entry::Occupied(entry) => {
+            let (other, other_is_dir) = *entry.get();
+            Some((other != path && !(is_dir && other_is_dir)).then(|| make_kind(other.to_owned())))
+        }
+    }
+}
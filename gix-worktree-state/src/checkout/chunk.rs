@@ -6,7 +6,7 @@ use std::{
 use bstr::{BStr, BString};
 use gix_worktree::Stack;
 
-use crate::{checkout, checkout::entry};
+use crate::{checkout, checkout::entry, checkout::uring};
 
 mod reduce {
     use crate::checkout;
@@ -26,6 +26,7 @@ mod reduce {
             let super::Outcome {
                 bytes_written,
                 files,
+                files_written_with_io_uring,
                 delayed_symlinks,
                 errors,
                 collisions,
@@ -34,6 +35,7 @@ mod reduce {
             } = item;
             self.aggregate.bytes_written += bytes_written;
             self.aggregate.files += files;
+            self.aggregate.files_written_with_io_uring += files_written_with_io_uring;
             self.aggregate.delayed_symlinks.extend(delayed_symlinks);
             self.aggregate.errors.extend(errors);
             self.aggregate.collisions.extend(collisions);
@@ -63,6 +65,8 @@ pub struct Outcome<'a> {
     pub bytes_written: u64,
     // the amount of files we processed
     pub files: usize,
+    // the amount of files that were written in batches using `io_uring`
+    pub files_written_with_io_uring: usize,
     /// Relative paths that the process listed as 'delayed' even though we never passed them.
     pub delayed_paths_unknown: Vec<BString>,
     /// All paths that were left unprocessed, because they were never listed by the process even though we passed them.
@@ -85,6 +89,9 @@ pub struct Options {
     pub overwrite_existing: bool,
     pub keep_going: bool,
     pub filter_process_delay: gix_filter::driver::apply::Delay,
+    pub skip_filters: bool,
+    pub windows_names: checkout::WindowsNames,
+    pub io_uring: bool,
 }
 
 impl From<&checkout::Options> for Options {
@@ -95,6 +102,9 @@ impl From<&checkout::Options> for Options {
             overwrite_existing: opts.overwrite_existing,
             keep_going: opts.keep_going,
             filter_process_delay: opts.filter_process_delay,
+            skip_filters: opts.skip_filters,
+            windows_names: opts.windows_names,
+            io_uring: opts.io_uring,
         }
     }
 }
@@ -114,7 +124,12 @@ where
     let mut errors = Vec::new();
     let mut bytes_written = 0;
     let mut files_in_chunk = 0;
-
+    let mut files_written_with_io_uring = 0;
+    let mut batch = if ctx.options.io_uring {
+        uring::Batch::new()
+    } else {
+        None
+    };
     for (entry, entry_path) in entries_with_paths {
         // TODO: write test for that
         if entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE) {
@@ -134,18 +149,48 @@ where
             continue;
         }
 
-        match checkout_entry_handle_result(entry, entry_path, &mut errors, &mut collisions, files, bytes, ctx)? {
+        let defer_writes = batch.is_some();
+        match checkout_entry_handle_result(
+            entry,
+            entry_path,
+            defer_writes,
+            &mut errors,
+            &mut collisions,
+            files,
+            bytes,
+            ctx,
+        )? {
             entry::Outcome::Written { bytes } => {
                 bytes_written += bytes as u64;
                 files_in_chunk += 1
             }
             entry::Outcome::Delayed(delayed) => delayed_filter_results.push(delayed),
+            entry::Outcome::Deferred(file) => {
+                let batch = batch
+                    .as_mut()
+                    .expect("files are only deferred if there is a batch to write them");
+                if batch.push(file, &ctx.options) {
+                    let (num_files, num_bytes, num_written) =
+                        write_batch(batch, &mut errors, &mut collisions, files, bytes, &ctx.options)?;
+                    files_in_chunk += num_files;
+                    bytes_written += num_bytes;
+                    files_written_with_io_uring += num_written;
+                }
+            }
         }
     }
+    if let Some(batch) = batch.as_mut() {
+        let (num_files, num_bytes, num_written) =
+            write_batch(batch, &mut errors, &mut collisions, files, bytes, &ctx.options)?;
+        files_in_chunk += num_files;
+        bytes_written += num_bytes;
+        files_written_with_io_uring += num_written;
+    }
 
     Ok(Outcome {
         bytes_written,
         files: files_in_chunk,
+        files_written_with_io_uring,
         errors,
         collisions,
         delayed_symlinks,
@@ -154,6 +199,31 @@ where
     })
 }
 
+/// Write all files in `batch` and return the amount of processed files, the amount of bytes written, and the amount
+/// of files that were written successfully.
+fn write_batch(
+    batch: &mut uring::Batch<'_>,
+    errors: &mut Vec<checkout::ErrorRecord>,
+    collisions: &mut Vec<checkout::Collision>,
+    files: &AtomicUsize,
+    bytes: &AtomicUsize,
+    options: &Options,
+) -> Result<(usize, u64, usize), checkout::Error> {
+    let (mut num_files, mut num_bytes, mut num_written) = (0, 0, 0);
+    for (entry_path, res) in batch.write(options)? {
+        let res = res.map(|bytes| entry::Outcome::Written { bytes });
+        let is_ok = res.is_ok();
+        if let entry::Outcome::Written { bytes } =
+            handle_result(res, entry_path, errors, collisions, files, bytes, options.keep_going)?
+        {
+            num_bytes += bytes as u64;
+        }
+        num_files += 1;
+        num_written += usize::from(is_ok);
+    }
+    Ok((num_files, num_bytes, num_written))
+}
+
 pub fn process_delayed_filter_results<Find>(
     mut delayed_filter_results: Vec<DelayedFilteredStream<'_>>,
     files: &AtomicUsize,
@@ -279,9 +349,11 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn checkout_entry_handle_result<'entry, Find>(
     entry: &'entry mut gix_index::Entry,
     entry_path: &'entry BStr,
+    defer_writes: bool,
     errors: &mut Vec<checkout::ErrorRecord>,
     collisions: &mut Vec<checkout::Collision>,
     files: &AtomicUsize,
@@ -305,9 +377,23 @@ where
             path_cache,
             filters,
             buf,
+            defer_writes,
         },
         *options,
     );
+    handle_result(res, entry_path, errors, collisions, files, bytes, options.keep_going)
+}
+
+/// Count the outcome of checking out the entry at `entry_path`, and turn errors into collisions or error records if possible.
+fn handle_result<'entry>(
+    res: Result<entry::Outcome<'entry>, checkout::Error>,
+    entry_path: &BStr,
+    errors: &mut Vec<checkout::ErrorRecord>,
+    collisions: &mut Vec<checkout::Collision>,
+    files: &AtomicUsize,
+    bytes: &AtomicUsize,
+    keep_going: bool,
+) -> Result<entry::Outcome<'entry>, checkout::Error> {
     match res {
         Ok(out) => {
             if let Some(num) = out.as_bytes() {
@@ -319,8 +405,9 @@ where
         Err(checkout::Error::Io(err)) if is_collision(&err, entry_path, collisions, files) => {
             Ok(entry::Outcome::Written { bytes: 0 })
         }
-        Err(err) => handle_error(err, entry_path, files, errors, options.keep_going)
-            .map(|()| entry::Outcome::Written { bytes: 0 }),
+        Err(err) => {
+            handle_error(err, entry_path, files, errors, keep_going).map(|()| entry::Outcome::Written { bytes: 0 })
+        }
     }
 }
 
@@ -11,11 +11,15 @@ use gix_object::FindExt;
 use gix_worktree::Stack;
 use io_close::Close;
 
+use crate::checkout::{windows_names, WindowsNames};
+
 pub struct Context<'a, Find> {
     pub objects: &'a mut Find,
     pub path_cache: &'a mut Stack,
     pub filters: &'a mut gix_filter::Pipeline,
     pub buf: &'a mut Vec<u8>,
+    /// If `true`, small files aren't written right away but returned as [`Outcome::Deferred`] to be written in batches.
+    pub defer_writes: bool,
 }
 
 /// A delayed result of a long-running filter process, which is made available as stream.
@@ -32,6 +36,21 @@ pub struct DelayedFilteredStream<'a> {
     pub entry_path: &'a BStr,
 }
 
+/// A file whose content is known, but which is yet to be written along with other files.
+#[cfg_attr(not(all(feature = "io-uring", target_os = "linux")), allow(dead_code))]
+pub struct DeferredFile<'a> {
+    /// The entry to adjust with the file we will write.
+    pub entry: &'a mut gix_index::Entry,
+    /// The relative path at which the entry resides.
+    pub entry_path: &'a BStr,
+    /// The validated path on disk at which the file should be placed.
+    pub validated_file_path: PathBuf,
+    /// The content of the file.
+    pub data: Vec<u8>,
+    /// If the file is going to be an executable.
+    pub needs_executable_bit: bool,
+}
+
 pub enum Outcome<'a> {
     /// The file was written.
     Written {
@@ -40,6 +59,8 @@ pub enum Outcome<'a> {
     },
     /// The will be ready later.
     Delayed(DelayedFilteredStream<'a>),
+    /// The file is yet to be written as part of a batch.
+    Deferred(DeferredFile<'a>),
 }
 
 impl Outcome<'_> {
@@ -47,7 +68,7 @@ impl Outcome<'_> {
     pub fn as_bytes(&self) -> Option<usize> {
         match self {
             Outcome::Written { bytes } => Some(*bytes),
-            Outcome::Delayed { .. } => None,
+            Outcome::Delayed { .. } | Outcome::Deferred(_) => None,
         }
     }
 }
@@ -61,6 +82,7 @@ pub fn checkout<'entry, Find>(
         filters,
         path_cache,
         buf,
+        defer_writes,
     }: Context<'_, Find>,
     crate::checkout::chunk::Options {
         fs: gix_fs::Capabilities {
@@ -71,13 +93,28 @@ pub fn checkout<'entry, Find>(
         destination_is_initially_empty,
         overwrite_existing,
         filter_process_delay,
+        skip_filters,
+        windows_names,
         ..
     }: crate::checkout::chunk::Options,
 ) -> Result<Outcome<'entry>, crate::checkout::Error>
 where
     Find: gix_object::Find,
 {
-    let dest_relative = gix_path::try_from_bstr(entry_path).map_err(|_| crate::checkout::Error::IllformedUtf8 {
+    let escaped_path;
+    let dest_path = match windows_names {
+        WindowsNames::Reject if windows_names::is_invalid(entry_path) => {
+            return Err(crate::checkout::Error::InvalidOnWindows {
+                path: entry_path.to_owned(),
+            })
+        }
+        WindowsNames::Escape => {
+            escaped_path = windows_names::escape(entry_path);
+            escaped_path.as_ref().map_or(entry_path, AsRef::as_ref)
+        }
+        WindowsNames::Allow | WindowsNames::Reject => entry_path,
+    };
+    let dest_relative = gix_path::try_from_bstr(dest_path).map_err(|_| crate::checkout::Error::IllformedUtf8 {
         path: entry_path.to_owned(),
     })?;
     let is_dir = Some(entry.mode == gix_index::entry::Mode::COMMIT || entry.mode == gix_index::entry::Mode::DIR);
@@ -93,15 +130,30 @@ where
                     path: dest.to_path_buf(),
                 })?;
 
-            let filtered = filters.convert_to_worktree(
-                obj.data,
-                entry_path,
-                &mut |_, attrs| {
-                    path_cache.matching_attributes(attrs);
-                },
-                filter_process_delay,
-            )?;
+            let filtered = if skip_filters {
+                ToWorktreeOutcome::Unchanged(obj.data)
+            } else {
+                filters.convert_to_worktree(
+                    obj.data,
+                    entry_path,
+                    &mut |_, attrs| {
+                        path_cache.matching_attributes(attrs);
+                    },
+                    filter_process_delay,
+                )?
+            };
             let (num_bytes, file, set_executable_after_creation) = match filtered {
+                ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf)
+                    if defer_writes && buf.len() <= crate::checkout::uring::MAX_FILE_SIZE =>
+                {
+                    return Ok(Outcome::Deferred(DeferredFile {
+                        needs_executable_bit: executable_bit && entry.mode == gix_index::entry::Mode::FILE_EXECUTABLE,
+                        validated_file_path: dest.to_owned(),
+                        data: buf.to_vec(),
+                        entry,
+                        entry_path,
+                    }))
+                }
                 ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => {
                     let (mut file, flag) = open_file(
                         dest,
@@ -146,10 +198,9 @@ where
                     err,
                     path: dest.to_path_buf(),
                 })?;
-            let symlink_destination = gix_path::try_from_byte_slice(obj.data)
-                .map_err(|_| crate::checkout::Error::IllformedUtf8 { path: obj.data.into() })?;
-
             if symlink {
+                let symlink_destination = gix_path::try_from_byte_slice(obj.data)
+                    .map_err(|_| crate::checkout::Error::IllformedUtf8 { path: obj.data.into() })?;
                 try_op_or_unlink(dest, overwrite_existing, |p| {
                     gix_fs::symlink::create(symlink_destination, p)
                 })?;
@@ -172,10 +223,9 @@ where
             0
         }
         gix_index::entry::Mode::COMMIT => {
-            gix_features::trace::warn!(
-                "Skipped submodule at '{entry_path}' ({id}) as it cannot yet be handled",
-                id = entry.id
-            );
+            // The empty directory was created by the path cache, just like `git` does to mark the place of the submodule,
+            // which is left to the caller to clone or populate.
+            entry.stat = Stat::from_fs(&gix_index::fs::Metadata::from_path_no_follow(dest)?)?;
             0
         }
         _ => unreachable!(),
@@ -238,6 +288,7 @@ fn open_options(path: &Path, destination_is_initially_empty: bool, overwrite_exi
     options
         .create_new(destination_is_initially_empty && !overwrite_existing)
         .create(!destination_is_initially_empty || overwrite_existing)
+        .truncate(!destination_is_initially_empty || overwrite_existing)
         .write(true);
     options
 }
@@ -278,10 +329,7 @@ pub(crate) fn finalize_entry(
     // For possibly existing, overwritten files, we must change the file mode explicitly.
     #[cfg(unix)]
     if let Some(path) = set_executable_after_creation {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perm = std::fs::symlink_metadata(path)?.permissions();
-        perm.set_mode(0o777);
-        std::fs::set_permissions(path, perm)?;
+        set_executable(path)?;
     }
     // NOTE: we don't call `file.sync_all()` here knowing that some filesystems don't handle this well.
     //       revisit this once there is a bug to fix.
@@ -289,3 +337,12 @@ pub(crate) fn finalize_entry(
     file.close()?;
     Ok(())
 }
+
+/// Make the file at `path` executable, which is needed if it existed before as it keeps its previous mode then.
+#[cfg(unix)]
+pub(crate) fn set_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perm = std::fs::symlink_metadata(path)?.permissions();
+    perm.set_mode(0o777);
+    std::fs::set_permissions(path, perm)
+}
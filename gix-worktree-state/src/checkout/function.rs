@@ -3,13 +3,14 @@ use std::sync::atomic::AtomicBool;
 use gix_features::{interrupt, parallel::in_parallel_with_finalize};
 use gix_worktree::{stack, Stack};
 
-use crate::checkout::chunk;
+use crate::checkout::{analysis, chunk};
 
 /// Checkout the entire `index` into `dir`, and resolve objects found in index entries with `objects` to write their content to their
 /// respective path in `dir`.
 /// Use `files` to count each fully checked out file, and count the amount written `bytes`. If `should_interrupt` is `true`, the
 /// operation will abort.
-/// `options` provide a lot of context on how to perform the operation.
+/// `options` provide a lot of context on how to perform the operation, and can turn it into a [dry run](crate::checkout::Options::dry_run)
+/// which only reports conflicts without writing anything.
 ///
 /// ### Handling the return value
 ///
@@ -28,9 +29,19 @@ pub fn checkout<Find>(
 where
     Find: gix_object::Find + Send + Clone,
 {
+    let span = gix_features::trace::coarse!(
+        "gix_worktree_state::checkout()",
+        entries = index.entries().len(),
+        files_updated = gix_features::trace::field::Empty,
+        bytes_written = gix_features::trace::field::Empty,
+    );
     let paths = index.take_path_backing();
     let res = checkout_inner(index, &paths, dir, objects, files, bytes, should_interrupt, options);
     index.return_path_backing(paths);
+    if let Ok(outcome) = &res {
+        span.record("files_updated", outcome.files_updated)
+            .record("bytes_written", outcome.bytes_written);
+    }
     res
 }
 
@@ -48,9 +59,20 @@ fn checkout_inner<Find>(
 where
     Find: gix_object::Find + Send + Clone,
 {
+    let mut dir = dir.into();
+    if options.dry_run {
+        return Ok(crate::checkout::Outcome {
+            conflicts: analysis::conflicts(index, paths, &dir, options.stat_options)?,
+            ..Default::default()
+        });
+    }
+
+    if options.long_paths {
+        dir = extended_length_path(dir)?;
+    }
+
     let num_files = files.counter();
     let num_bytes = bytes.counter();
-    let dir = dir.into();
     let (chunk_size, thread_limit, num_threads) = gix_features::parallel::optimize_chunk_size_and_thread_limit(
         100,
         index.entries().len().into(),
@@ -77,6 +99,7 @@ where
         mut errors,
         mut bytes_written,
         files: files_updated,
+        files_written_with_io_uring,
         delayed_symlinks,
         delayed_paths_unknown,
         delayed_paths_unprocessed,
@@ -128,6 +151,7 @@ where
         bytes_written += chunk::checkout_entry_handle_result(
             entry,
             entry_path,
+            false,
             &mut errors,
             &mut collisions,
             &num_files,
@@ -141,10 +165,24 @@ where
 
     Ok(crate::checkout::Outcome {
         files_updated,
+        files_written_with_io_uring,
         collisions,
         errors,
         bytes_written,
         delayed_paths_unknown,
         delayed_paths_unprocessed,
+        conflicts: Vec::new(),
     })
 }
+
+/// Turn `dir` into an extended-length path on Windows, which allows paths within it to exceed `MAX_PATH`.
+/// On other platforms, `dir` is returned unchanged.
+fn extended_length_path(dir: std::path::PathBuf) -> std::io::Result<std::path::PathBuf> {
+    if cfg!(windows) && !dir.as_os_str().to_string_lossy().starts_with(r"\\?\") {
+        // Canonicalization yields an absolute and normalized path with the `\\?\` prefix, which makes it usable as root
+        // for all paths we will create.
+        std::fs::create_dir_all(&dir)?;
+        return dir.canonicalize();
+    }
+    Ok(dir)
+}
@@ -10,6 +10,55 @@ pub struct Collision {
     pub error_kind: std::io::ErrorKind,
 }
 
+/// The reason for a [`Conflict`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ConflictKind {
+    /// The path only differs in case from `other`, so both would refer to the same file on a case-insensitive filesystem.
+    Case {
+        /// The path that was seen first, which takes precedence.
+        other: BString,
+    },
+    /// The path is equal to `other` after unicode normalization, so both would refer to the same file on filesystems
+    /// which normalize unicode, like HFS+ on MacOS.
+    Unicode {
+        /// The path that was seen first, which takes precedence.
+        other: BString,
+    },
+    /// Something that isn't tracked by the index, or doesn't match the state recorded in the index, exists at the path
+    /// and would be overwritten.
+    Untracked,
+    /// The path contains a component that can't be created on Windows, like a reserved device name such as `CON` or `NUL`,
+    /// with or without extension, or a name ending in a dot or space.
+    InvalidOnWindows,
+}
+
+/// What to do with paths that can't be created on Windows, as their components are reserved device names like `CON`
+/// or `NUL`, with or without extension, or end in a dot or space.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowsNames {
+    /// Check out such paths as they are, which works on all other platforms.
+    #[default]
+    Allow,
+    /// Refuse to check out such paths, failing with [`Error::InvalidOnWindows`] unless [`keep_going`](Options::keep_going)
+    /// is set, just like Git for Windows does.
+    Reject,
+    /// Check out such paths with the offending characters replaced by characters of the unicode private use area,
+    /// similar to what Cygwin does. Trailing dots and spaces are escaped, along with the last character of reserved names,
+    /// so `nul.txt` is written as `nu\u{f06c}.txt`.
+    ///
+    /// Note that the files can't be found under the path recorded in the index anymore, so they will appear deleted.
+    Escape,
+}
+
+/// Information about a path that would fail to checkout or overwrite something, as determined by a [dry run](Options::dry_run).
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Conflict {
+    /// The path that conflicts, either an entry of the index or one of its leading directories.
+    pub path: BString,
+    /// The reason for the conflict.
+    pub kind: ConflictKind,
+}
+
 /// A path that encountered an IO error.
 #[derive(Debug)]
 pub struct ErrorRecord {
@@ -26,6 +75,9 @@ pub struct Outcome {
     pub files_updated: usize,
     /// The amount of bytes written to disk,
     pub bytes_written: u64,
+    /// The amount of files among the [updated files](Self::files_updated) which were written in batches
+    /// using `io_uring`, if [enabled](Options::io_uring).
+    pub files_written_with_io_uring: usize,
     /// The encountered collisions, which can happen on a case-insensitive filesystem.
     pub collisions: Vec<Collision>,
     /// Other errors that happened during checkout.
@@ -34,6 +86,8 @@ pub struct Outcome {
     pub delayed_paths_unknown: Vec<BString>,
     /// All paths that were left unprocessed, because they were never listed by the process even though we passed them.
     pub delayed_paths_unprocessed: Vec<BString>,
+    /// All conflicts that a checkout would run into, sorted by path, which are only collected in a [dry run](Options::dry_run).
+    pub conflicts: Vec<Conflict>,
 }
 
 /// Options to further configure the checkout operation.
@@ -66,6 +120,25 @@ pub struct Options {
     pub filters: gix_filter::Pipeline,
     /// Control how long-running processes may use the 'delay' capability.
     pub filter_process_delay: gix_filter::driver::apply::Delay,
+    /// If true, default false, blobs are written exactly as they are stored in the object database, without
+    /// applying any of the [`filters`](Self::filters) and thus without any end-of-line conversion.
+    pub skip_filters: bool,
+    /// If true, default false, nothing will be written. Instead, all [conflicts](Outcome::conflicts) are collected that
+    /// a checkout would run into: paths that would collide on case-insensitive or unicode-normalizing filesystems or
+    /// can't be created on Windows, independently of the capabilities of the current filesystem, and paths at which
+    /// existing files or directories would be overwritten.
+    pub dry_run: bool,
+    /// Control how paths that can't be created on Windows are handled.
+    pub windows_names: WindowsNames,
+    /// If true, default false, turn the destination directory into an extended-length path on Windows, so that paths within it may
+    /// exceed the `MAX_PATH` limit of 260 characters, similar to `core.longpaths` of Git for Windows. It has no effect on other platforms.
+    pub long_paths: bool,
+    /// If true, default false, small files are opened, written and closed in batches using `io_uring`, which needs far
+    /// fewer system calls than writing them one by one and helps with checkouts of many small files.
+    ///
+    /// This only has an effect on Linux with the `io-uring` feature enabled. If the kernel doesn't support `io_uring` or
+    /// doesn't permit its use, or if a file can't be written that way, files are written one by one as usual.
+    pub io_uring: bool,
 }
 
 /// The error returned by the [checkout()][crate::checkout()] function.
@@ -94,8 +167,13 @@ pub enum Error {
     FilterPathUnknown { rela_path: BString },
     #[error("The following paths were delayed and apparently forgotten to be processed by the filter driver: ")]
     FilterPathsUnprocessed { rela_paths: Vec<BString> },
+    #[error("The path '{path}' can't be checked out on Windows as it contains a reserved name or a component ending in a dot or space")]
+    InvalidOnWindows { path: BString },
 }
 
+mod analysis;
 mod chunk;
 mod entry;
 pub(crate) mod function;
+mod uring;
+mod windows_names;
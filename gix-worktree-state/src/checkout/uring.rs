@@ -0,0 +1,261 @@
+pub(crate) use imp::Batch;
+
+/// The largest file to write as part of a batch, as larger files don't benefit from it and would use too much memory.
+pub(crate) const MAX_FILE_SIZE: usize = 256 * 1024;
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+#[allow(unsafe_code)]
+mod imp {
+    use std::{
+        ffi::CString,
+        fs::File,
+        io::{self, Write},
+        os::unix::{
+            ffi::OsStrExt,
+            io::{FromRawFd, RawFd},
+        },
+    };
+
+    use bstr::BStr;
+    use gix_index::entry::Stat;
+    use io_uring::{opcode, squeue, types, IoUring};
+
+    use crate::checkout::{chunk, entry, entry::DeferredFile};
+
+    /// The maximum amount of files written with a single batch.
+    const MAX_FILES: usize = 128;
+    /// The maximum amount of bytes a batch may hold before it should be written.
+    const MAX_BYTES: usize = 8 * 1024 * 1024;
+
+    /// A file in a batch, along with everything needed to open it.
+    struct Pending<'entry> {
+        file: DeferredFile<'entry>,
+        /// The path to open, or `None` if it can't be represented as C string.
+        path: Option<CString>,
+        flags: i32,
+        mode: u32,
+        set_executable_after_creation: bool,
+    }
+
+    /// Files to be opened, written and closed with `io_uring`, each step for all files at once.
+    pub(crate) struct Batch<'entry> {
+        ring: IoUring,
+        files: Vec<Pending<'entry>>,
+        num_bytes: usize,
+    }
+
+    impl<'entry> Batch<'entry> {
+        /// Return a new batch, or `None` if `io_uring` isn't supported by the kernel or its use isn't permitted.
+        pub(crate) fn new() -> Option<Self> {
+            Some(Batch {
+                ring: IoUring::new(MAX_FILES as u32).ok()?,
+                files: Vec::with_capacity(MAX_FILES),
+                num_bytes: 0,
+            })
+        }
+
+        /// Add `file` to be written according to `options`, and return `true` if the batch is full and should be written.
+        pub(crate) fn push(&mut self, file: DeferredFile<'entry>, options: &chunk::Options) -> bool {
+            // Mirror `entry::open_file()`, which only creates new files with the mode they are supposed to have.
+            let create = if options.destination_is_initially_empty && !options.overwrite_existing {
+                libc::O_CREAT | libc::O_EXCL
+            } else {
+                libc::O_CREAT | libc::O_TRUNC
+            };
+            let (mode, set_executable_after_creation) =
+                if file.needs_executable_bit && options.destination_is_initially_empty {
+                    (0o777, false)
+                } else {
+                    (0o666, file.needs_executable_bit)
+                };
+            self.num_bytes += file.data.len();
+            self.files.push(Pending {
+                path: CString::new(file.validated_file_path.as_os_str().as_bytes()).ok(),
+                flags: libc::O_WRONLY | libc::O_CLOEXEC | libc::O_NOFOLLOW | create,
+                mode,
+                set_executable_after_creation,
+                file,
+            });
+            self.files.len() >= MAX_FILES || self.num_bytes >= MAX_BYTES
+        }
+
+        /// Write all files in this batch according to `options` and return the amount of bytes written for each of them,
+        /// along with their path.
+        ///
+        /// Files that can't be opened this way are written one by one so that they produce the usual errors.
+        #[allow(clippy::type_complexity)]
+        pub(crate) fn write(
+            &mut self,
+            options: &chunk::Options,
+        ) -> io::Result<Vec<(&'entry BStr, Result<usize, crate::checkout::Error>)>> {
+            let mut files = std::mem::take(&mut self.files);
+            self.num_bytes = 0;
+
+            let mut fds: Vec<Option<RawFd>> = vec![None; files.len()];
+            submit_all(
+                &mut self.ring,
+                files.iter().enumerate().filter_map(|(idx, pending)| {
+                    let path = pending.path.as_ref()?;
+                    Some(
+                        opcode::OpenAt::new(types::Fd(libc::AT_FDCWD), path.as_ptr())
+                            .flags(pending.flags)
+                            .mode(pending.mode)
+                            .build()
+                            .user_data(idx as u64),
+                    )
+                }),
+                |idx, res| fds[idx] = (res >= 0).then_some(res),
+            )?;
+
+            let mut num_written: Vec<i32> = vec![0; files.len()];
+            submit_all(
+                &mut self.ring,
+                files.iter().zip(&fds).enumerate().filter_map(|(idx, (pending, fd))| {
+                    Some(
+                        opcode::Write::new(
+                            types::Fd((*fd)?),
+                            pending.file.data.as_ptr(),
+                            pending.file.data.len() as u32,
+                        )
+                        .offset(0)
+                        .build()
+                        .user_data(idx as u64),
+                    )
+                }),
+                |idx, res| num_written[idx] = res,
+            )?;
+
+            let mut close_results: Vec<i32> = vec![0; files.len()];
+            submit_all(
+                &mut self.ring,
+                files.iter().zip(&fds).enumerate().filter_map(|(idx, (pending, fd))| {
+                    let is_complete = usize::try_from(num_written[idx]).ok() == Some(pending.file.data.len());
+                    fd.filter(|_| is_complete)
+                        .map(|fd| opcode::Close::new(types::Fd(fd)).build().user_data(idx as u64))
+                }),
+                |idx, res| close_results[idx] = res,
+            )?;
+
+            Ok(files
+                .iter_mut()
+                .zip(fds)
+                .zip(num_written.into_iter().zip(close_results))
+                .map(|((pending, fd), (num_written, close_result))| {
+                    let res = match fd {
+                        Some(fd) => finalize(pending, fd, num_written, close_result),
+                        None => write_regular(&mut pending.file, options),
+                    };
+                    (pending.file.entry_path, res)
+                })
+                .collect())
+        }
+    }
+
+    /// Submit all `entries` and call `on_completion(user_data, result)` for each of them once they are complete.
+    fn submit_all(
+        ring: &mut IoUring,
+        entries: impl Iterator<Item = squeue::Entry>,
+        mut on_completion: impl FnMut(usize, i32),
+    ) -> io::Result<()> {
+        let mut num_pending = 0;
+        for entry in entries {
+            // SAFETY: the paths and buffers `entry` refers to are owned by the batch and outlive the operation,
+            //         as we wait for its completion below.
+            while unsafe { ring.submission().push(&entry) }.is_err() {
+                ring.submit()?;
+            }
+            num_pending += 1;
+        }
+        while num_pending > 0 {
+            match ring.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+            for completion in ring.completion() {
+                on_completion(completion.user_data() as usize, completion.result());
+                num_pending -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Complete writing the file of `pending` which was opened as `fd`, after `num_written` bytes were written and it
+    /// was closed with `close_result`, and store its stats.
+    fn finalize(
+        pending: &mut Pending<'_>,
+        fd: RawFd,
+        num_written: i32,
+        close_result: i32,
+    ) -> Result<usize, crate::checkout::Error> {
+        let DeferredFile {
+            entry,
+            validated_file_path: path,
+            data,
+            ..
+        } = &mut pending.file;
+        let executable_path = pending.set_executable_after_creation.then_some(path.as_path());
+        if usize::try_from(num_written).ok() != Some(data.len()) {
+            // SAFETY: the file was opened by us and wasn't closed as the write didn't complete.
+            let mut file = unsafe { File::from_raw_fd(fd) };
+            let num_written = usize::try_from(num_written).map_err(|_| io::Error::from_raw_os_error(-num_written))?;
+            file.write_all(&data[num_written..])?;
+            entry::finalize_entry(entry, file, executable_path)?;
+            return Ok(data.len());
+        }
+        if close_result < 0 {
+            return Err(io::Error::from_raw_os_error(-close_result).into());
+        }
+        if let Some(path) = executable_path {
+            entry::set_executable(path)?;
+        }
+        entry.stat = Stat::from_fs(&gix_index::fs::Metadata::from_path_no_follow(path)?)?;
+        Ok(data.len())
+    }
+
+    /// Write `file` the usual way, according to `options`.
+    fn write_regular(file: &mut DeferredFile<'_>, options: &chunk::Options) -> Result<usize, crate::checkout::Error> {
+        let (mut out, set_executable_after_creation) = entry::open_file(
+            &file.validated_file_path,
+            options.destination_is_initially_empty,
+            options.overwrite_existing,
+            file.needs_executable_bit,
+            file.entry.mode,
+        )?;
+        out.write_all(&file.data)?;
+        entry::finalize_entry(
+            file.entry,
+            out,
+            set_executable_after_creation.then_some(file.validated_file_path.as_path()),
+        )?;
+        Ok(file.data.len())
+    }
+}
+
+#[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+mod imp {
+    use bstr::BStr;
+
+    use crate::checkout::{chunk, entry::DeferredFile};
+
+    /// A batch which can't be instantiated as `io_uring` isn't available.
+    pub(crate) struct Batch<'entry>(std::convert::Infallible, std::marker::PhantomData<DeferredFile<'entry>>);
+
+    impl<'entry> Batch<'entry> {
+        pub(crate) fn new() -> Option<Self> {
+            None
+        }
+
+        pub(crate) fn push(&mut self, _file: DeferredFile<'entry>, _options: &chunk::Options) -> bool {
+            match self.0 {}
+        }
+
+        #[allow(clippy::type_complexity)]
+        pub(crate) fn write(
+            &mut self,
+            _options: &chunk::Options,
+        ) -> std::io::Result<Vec<(&'entry BStr, Result<usize, crate::checkout::Error>)>> {
+            match self.0 {}
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use bstr::{BStr, BString, ByteSlice};
+
+/// Device names that Windows reserves in every directory, with or without an extension.
+const RESERVED: &[&[u8]] = &[
+    b"CON", b"PRN", b"AUX", b"NUL", b"CONIN$", b"CONOUT$", b"COM1", b"COM2", b"COM3", b"COM4", b"COM5", b"COM6",
+    b"COM7", b"COM8", b"COM9", b"LPT1", b"LPT2", b"LPT3", b"LPT4", b"LPT5", b"LPT6", b"LPT7", b"LPT8", b"LPT9",
+];
+
+/// Return `true` if any component of the slash-separated `rela_path` can't be created on Windows.
+pub fn is_invalid(rela_path: &BStr) -> bool {
+    rela_path
+        .split_str("/")
+        .any(|component| reserved_stem_len(component).is_some() || has_trailing_dot_or_space(component))
+}
+
+/// Escape all components of `rela_path` that can't be created on Windows using characters of the unicode private use area,
+/// or return `None` if there is nothing to escape.
+///
+/// Only the offending ASCII bytes are replaced, all other bytes are kept as is, even if they aren't valid UTF-8.
+pub fn escape(rela_path: &BStr) -> Option<BString> {
+    if !is_invalid(rela_path) {
+        return None;
+    }
+    let mut out = BString::default();
+    for (idx, component) in rela_path.split_str("/").enumerate() {
+        if idx != 0 {
+            out.push(b'/');
+        }
+        let trailing_start = component.len()
+            - component
+                .iter()
+                .rev()
+                .take_while(|b| **b == b'.' || **b == b' ')
+                .count();
+        let reserved_last = reserved_stem_len(component).map(|stem_len| stem_len - 1);
+        for (pos, byte) in component.iter().enumerate() {
+            if pos >= trailing_start || Some(pos) == reserved_last {
+                push_private_use(&mut out, *byte);
+            } else {
+                out.push(*byte);
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Append the ASCII `byte` mapped into the unicode private use area to `out`, like Cygwin does for characters
+/// that are invalid in Windows filenames.
+fn push_private_use(out: &mut BString, byte: u8) {
+    let c = char::from_u32(0xf000 | u32::from(byte)).expect("ASCII characters map to valid code points");
+    out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+}
+
+fn has_trailing_dot_or_space(component: &[u8]) -> bool {
+    component.last().map_or(false, |b| *b == b'.' || *b == b' ')
+}
+
+/// Return the length of the reserved device name at the start of `component`, if it is one.
+///
+/// Windows ignores everything past the first dot or colon, as well as trailing spaces before it.
+fn reserved_stem_len(component: &[u8]) -> Option<usize> {
+    let stem = &component[..component.find_byteset(b".:").unwrap_or(component.len())];
+    let stem = stem.trim_end_with(|c| c == ' ');
+    RESERVED
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(stem))
+        .then_some(stem.len())
+}
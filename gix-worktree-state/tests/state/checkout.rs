@@ -10,7 +10,7 @@ use std::{
 use gix_features::progress;
 use gix_object::{bstr::ByteSlice, Data};
 use gix_testtools::tempfile::TempDir;
-use gix_worktree_state::checkout::Collision;
+use gix_worktree_state::checkout::{Collision, Conflict, ConflictKind, WindowsNames};
 use once_cell::sync::Lazy;
 
 use crate::fixture_path;
@@ -46,11 +46,17 @@ fn driver_exe() -> String {
 fn submodules_are_instantiated_as_directories() -> crate::Result {
     let mut opts = opts_from_probe();
     opts.overwrite_existing = false;
-    let (_source_tree, destination, _index, _outcome) = checkout_index_in_tmp_dir(opts.clone(), "make_mixed")?;
+    let (_source_tree, destination, index, _outcome) = checkout_index_in_tmp_dir(opts.clone(), "make_mixed")?;
 
     for path in ["m1", "modules/m1"] {
         let sm = destination.path().join(path);
         assert!(sm.is_dir());
+        let entry = index.entry_by_path(path.into()).expect("submodule is tracked");
+        assert_eq!(
+            entry.stat,
+            gix_index::entry::Stat::from_fs(&gix_index::fs::Metadata::from_path_no_follow(&sm)?)?,
+            "the stat of the submodule directory is recorded just like for files"
+        );
         assure_is_empty(sm)?;
     }
 
@@ -167,6 +173,24 @@ fn delayed_driver_process() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn filters_are_not_applied_if_skipped() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.skip_filters = true;
+    setup_filter_pipeline(opts.filters.options_mut());
+    let (_source, destination, _index, outcome) =
+        checkout_index_in_tmp_dir_opts(opts, "make_mixed_without_submodules_and_symlinks", |_| true, |_| Ok(()))?;
+    assert_eq!(outcome.errors.len(), 0);
+    assert_eq!(outcome.files_updated, 5);
+
+    assert_eq!(
+        std::fs::read(destination.path().join("dir").join("content"))?.as_bstr(),
+        "other content\n",
+        "blobs are written as stored"
+    );
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(
     windows,
@@ -189,7 +213,7 @@ fn overwriting_files_and_lone_directories_works() -> crate::Result {
             |d| {
                 let empty = d.join("empty");
                 symlink::symlink_dir(d.join(".."), &empty)?; // empty is symlink to the directory above
-                std::fs::write(d.join("executable"), b"foo")?; // executable is regular file and has different content
+                std::fs::write(d.join("executable"), b"longer than content")?; // executable is regular file and has different content
                 let dir = d.join("dir");
                 std::fs::create_dir(&dir)?;
                 std::fs::create_dir(dir.join("content"))?; // 'content' is a directory now
@@ -257,6 +281,37 @@ fn symlinks_become_files_if_disabled() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn checkout_is_traced_with_the_amount_of_files_and_bytes_written() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.fs.symlink = false;
+    let (res, spans) =
+        gix_testtools::capture_spans(|| checkout_index_in_tmp_dir(opts.clone(), "make_mixed_without_submodules"));
+    let (_source_tree, destination, index, outcome) = res?;
+
+    let mut bytes_on_disk = 0;
+    for entry in walkdir::WalkDir::new(destination.path()) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            bytes_on_disk += entry.metadata()?.len();
+        }
+    }
+    assert_eq!(outcome.bytes_written, bytes_on_disk);
+
+    let span = spans
+        .iter()
+        .find(|span| span.name == "gix_worktree_state::checkout()")
+        .expect("checkout span was created");
+    assert_eq!(span.fields["entries"], index.entries().len().to_string());
+    assert_eq!(span.fields["files_updated"], outcome.files_updated.to_string());
+    assert_eq!(
+        span.fields["bytes_written"],
+        bytes_on_disk.to_string(),
+        "the span sees all bytes written to disk"
+    );
+    Ok(())
+}
+
 #[test]
 fn allow_or_disallow_symlinks() -> crate::Result {
     let mut opts = opts_from_probe();
@@ -271,6 +326,42 @@ fn allow_or_disallow_symlinks() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn io_uring_writes_the_same_files_and_stats() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.io_uring = true;
+    for overwrite_existing in [false, true] {
+        opts.destination_is_initially_empty = !overwrite_existing;
+        opts.overwrite_existing = overwrite_existing;
+        let (source_tree, destination, index, outcome) =
+            checkout_index_in_tmp_dir(opts.clone(), "make_mixed_without_submodules")?;
+
+        assert_equality(&source_tree, &destination, opts.fs.symlink)?;
+        assert!(outcome.collisions.is_empty());
+        assert!(outcome.errors.is_empty());
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        assert!(
+            outcome.files_written_with_io_uring > 0,
+            "io_uring is available here and used for small files"
+        );
+        #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+        assert_eq!(
+            outcome.files_written_with_io_uring, 0,
+            "without io_uring support, files are written the usual way"
+        );
+
+        for entry in index.entries() {
+            if entry.mode != gix_index::entry::Mode::FILE && entry.mode != gix_index::entry::Mode::FILE_EXECUTABLE {
+                continue;
+            }
+            let path = destination.path().join(entry.path(&index).to_path()?);
+            let stat = gix_index::entry::Stat::from_fs(&gix_index::fs::Metadata::from_path_no_follow(&path)?)?;
+            assert_eq!(entry.stat, stat, "the stat of {path:?} is up to date in the index");
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn keep_going_collects_results() {
     let mut opts = opts_from_probe();
@@ -431,6 +522,304 @@ fn collisions_are_detected_on_a_case_insensitive_filesystem_even_with_delayed_fi
     }
 }
 
+#[test]
+fn dry_run_reports_case_collisions_without_writing() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.dry_run = true;
+    let (_source_tree, destination, _index, outcome) = checkout_index_in_tmp_dir(opts, "make_ignorecase_collisions")?;
+
+    assert_eq!(std::fs::read_dir(destination.path())?.count(), 0, "nothing is written");
+    assert_eq!(outcome.files_updated, 0);
+    assert!(outcome.collisions.is_empty());
+    let case = |path: &str, other: &str| Conflict {
+        path: path.into(),
+        kind: ConflictKind::Case { other: other.into() },
+    };
+    assert_eq!(
+        outcome.conflicts,
+        [
+            case("FILE_x", "FILE_X"),
+            case("d", "D"),
+            case("file_X", "FILE_X"),
+            case("file_x", "FILE_X"),
+            case("x", "X"),
+        ],
+        "the analysis is independent of the filesystem, and directories collide with files as well"
+    );
+    Ok(())
+}
+
+#[test]
+fn dry_run_reports_paths_colliding_after_unicode_normalization() -> crate::Result {
+    let mut index = gix_index::State::new(gix_hash::Kind::Sha1);
+    for path in ["caf\u{e9}", "cafe\u{301}/file", "other"] {
+        index.dangerously_push_entry(
+            Default::default(),
+            gix_hash::Kind::Sha1.null(),
+            gix_index::entry::Flags::empty(),
+            gix_index::entry::Mode::FILE,
+            path.into(),
+        );
+    }
+    index.sort_entries();
+    let destination = gix_testtools::tempfile::tempdir()?;
+    let outcome = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        gix_object::find::Never,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        gix_worktree_state::checkout::Options {
+            dry_run: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome.conflicts,
+        [Conflict {
+            path: "caf\u{e9}".into(),
+            kind: ConflictKind::Unicode {
+                other: "cafe\u{301}".into()
+            }
+        }],
+        "the precomposed file is seen after the decomposed directory, as entries are sorted by bytes"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore = "on windows, the symlink to a directory doesn't seem to work")]
+fn dry_run_reports_files_and_directories_that_would_be_overwritten() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.dry_run = true;
+    opts.destination_is_initially_empty = false;
+    let (_source_tree, destination, _index, outcome) = checkout_index_in_tmp_dir_opts(
+        opts,
+        "make_mixed",
+        |_| true,
+        |d| {
+            std::fs::write(d.join("executable"), b"foo")?;
+            std::fs::create_dir(d.join("empty"))?;
+            symlink::symlink_dir(d.join("empty"), d.join("dir"))?;
+            std::fs::write(d.join("m1"), b"")?;
+            std::fs::create_dir(d.join("modules"))?;
+            std::fs::create_dir(d.join("modules").join("m1"))?;
+            Ok(())
+        },
+    )?;
+
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths(["dir", "executable", "m1"]),
+        "nothing is changed"
+    );
+    let untracked = |path: &str| Conflict {
+        path: path.into(),
+        kind: ConflictKind::Untracked,
+    };
+    assert_eq!(
+        outcome.conflicts,
+        [
+            untracked("dir"),
+            untracked("empty"),
+            untracked("executable"),
+            untracked("m1")
+        ],
+        "symlinked leading directories can't be written through, and existing submodule directories are fine"
+    );
+    Ok(())
+}
+
+#[test]
+fn dry_run_reports_paths_that_are_invalid_on_windows() -> crate::Result {
+    let (_odb_dir, odb, mut index) = index_with_files(["CON", "dir./file", "valid", "x/nul.txt"])?;
+    let destination = gix_testtools::tempfile::tempdir()?;
+    let outcome = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        gix_worktree_state::checkout::Options {
+            dry_run: true,
+            ..Default::default()
+        },
+    )?;
+    let invalid = |path: &str| Conflict {
+        path: path.into(),
+        kind: ConflictKind::InvalidOnWindows,
+    };
+    assert_eq!(
+        outcome.conflicts,
+        [invalid("CON"), invalid("dir./file"), invalid("x/nul.txt")],
+        "the analysis is independent of the current platform"
+    );
+    Ok(())
+}
+
+#[test]
+fn paths_invalid_on_windows_can_be_rejected() -> crate::Result {
+    let (_odb_dir, odb, mut index) = index_with_files(["aux", "file ", "valid"])?;
+    let destination = gix_testtools::tempfile::tempdir()?;
+    let opts = gix_worktree_state::checkout::Options {
+        windows_names: WindowsNames::Reject,
+        destination_is_initially_empty: true,
+        ..Default::default()
+    };
+    let err = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        odb.clone(),
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        opts.clone(),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(&err, gix_worktree_state::checkout::Error::InvalidOnWindows { path } if path == "aux"),
+        "{err:?}"
+    );
+
+    let outcome = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        gix_worktree_state::checkout::Options {
+            keep_going: true,
+            ..opts
+        },
+    )?;
+    assert_eq!(
+        outcome.errors.iter().map(|e| e.path.to_string()).collect::<Vec<_>>(),
+        ["aux", "file "]
+    );
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths(["valid"]),
+        "only valid paths are checked out"
+    );
+    Ok(())
+}
+
+#[test]
+fn paths_invalid_on_windows_can_be_escaped() -> crate::Result {
+    let (_odb_dir, odb, mut index) = index_with_files(["dir./NUL.txt", "lpt1", "valid"])?;
+    let destination = gix_testtools::tempfile::tempdir()?;
+    let outcome = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        gix_worktree_state::checkout::Options {
+            windows_names: WindowsNames::Escape,
+            destination_is_initially_empty: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.files_updated, 3);
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths(["dir\u{f02e}/NU\u{f04c}.txt", "lpt\u{f031}", "valid"]),
+        "trailing dots and the last character of reserved names are mapped into the private use area"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn escaping_paths_invalid_on_windows_keeps_non_utf8_bytes() -> crate::Result {
+    use std::os::unix::ffi::OsStrExt;
+
+    let (_odb_dir, odb, mut index) = index_with_files([&b"caf\xe9 /aux\xff.txt/nul"[..]])?;
+    let destination = gix_testtools::tempfile::tempdir()?;
+    let outcome = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        gix_worktree_state::checkout::Options {
+            windows_names: WindowsNames::Escape,
+            destination_is_initially_empty: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.files_updated, 1);
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination))
+            .into_iter()
+            .map(|path| path.as_os_str().as_bytes())
+            .collect::<Vec<_>>(),
+        [&b"caf\xe9\xef\x80\xa0/aux\xff.txt/nu\xef\x81\xac"[..]],
+        "only the offending ASCII bytes are escaped, invalid UTF-8 is kept as is"
+    );
+    Ok(())
+}
+
+#[test]
+fn long_paths_can_be_checked_out() -> crate::Result {
+    let long_path = ["a".repeat(100), "b".repeat(100), "c".repeat(100)].join("/");
+    let (_odb_dir, odb, mut index) = index_with_files([long_path.as_str()])?;
+    let destination = gix_testtools::tempfile::tempdir()?;
+    let outcome = gix_worktree_state::checkout(
+        &mut index,
+        destination.path(),
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        gix_worktree_state::checkout::Options {
+            long_paths: true,
+            destination_is_initially_empty: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.files_updated, 1);
+    assert!(outcome.errors.is_empty());
+    // On Windows, this exceeds `MAX_PATH` and can only be accessed with an extended-length path, in which `/` isn't a separator.
+    let file = long_path
+        .split('/')
+        .fold(destination.path().canonicalize()?, |path, component| {
+            path.join(component)
+        });
+    assert_eq!(fs::read(file)?, b"content");
+    Ok(())
+}
+
+/// Return an index with a file entry for each of `paths`, along with the object database containing their content.
+fn index_with_files(
+    paths: impl IntoIterator<Item = impl AsRef<[u8]>>,
+) -> crate::Result<(TempDir, gix_odb::HandleArc, gix_index::State)> {
+    use gix_odb::Write;
+
+    let odb_dir = gix_testtools::tempfile::tempdir()?;
+    let odb = gix_odb::at(odb_dir.path())?.into_arc()?;
+    let id = odb
+        .write_buf(gix_object::Kind::Blob, b"content")
+        .map_err(|err| err as Box<dyn std::error::Error>)?;
+    let mut index = gix_index::State::new(gix_hash::Kind::Sha1);
+    for path in paths {
+        index.dangerously_push_entry(
+            Default::default(),
+            id,
+            gix_index::entry::Flags::empty(),
+            gix_index::entry::Mode::FILE,
+            path.as_ref().into(),
+        );
+    }
+    index.sort_entries();
+    Ok((odb_dir, odb, index))
+}
+
 fn multi_threaded() -> bool {
     gix_features::parallel::num_threads(None) > 1
 }
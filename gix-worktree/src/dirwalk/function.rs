@@ -0,0 +1,277 @@
+use std::{
+    fs::FileType,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_features::parallel::{in_parallel_if, optimize_chunk_size_and_thread_limit, Reduce};
+
+use crate::{
+    dirwalk::{Delegate, EmissionMode, Entry, Error, Kind, Options, Outcome, Status},
+    Stack,
+};
+
+/// Walk the worktree at `worktree_root` to find all items that aren't tracked in `index` and pass them to `delegate`
+/// in order of their paths.
+///
+/// `excludes` is a stack rooted at `worktree_root` which is configured to provide exclude information, and which is cloned
+/// for each thread. `objects` is used to read exclude files from the object database if needed.
+/// If `should_interrupt` is set, the operation stops with an error.
+///
+/// Directories named `.git` are never entered or emitted, and directories containing one are emitted as [nested
+/// repositories](Kind::Repository) unless [configured otherwise](Options::recurse_repositories).
+/// Directories that contain no tracked files can be emitted as a whole depending on the [emission mode](EmissionMode),
+/// just like ignored directories which are never entered unless they contain tracked files.
+///
+/// Top-level directories are walked in parallel, if enabled by the `parallel` feature toggle.
+pub fn dirwalk<Find>(
+    worktree_root: &Path,
+    index: &gix_index::State,
+    excludes: &Stack,
+    objects: Find,
+    delegate: &mut dyn Delegate,
+    should_interrupt: &AtomicBool,
+    options: Options,
+) -> Result<Outcome, Error>
+where
+    Find: gix_object::Find + Send + Clone,
+{
+    let mut out = Outcome::default();
+    let root_entries = read_dir(worktree_root, options, &mut out)?;
+    let (_, thread_limit, num_threads) =
+        optimize_chunk_size_and_thread_limit(1, Some(root_entries.len()), options.thread_limit, None);
+    let ctx = Context {
+        worktree_root,
+        index,
+        options,
+        should_interrupt,
+    };
+    let (entries, stats) = in_parallel_if(
+        || num_threads > 1,
+        root_entries.into_iter().enumerate(),
+        thread_limit,
+        {
+            let excludes = excludes.clone();
+            move |_| (excludes, objects)
+        },
+        |(idx, (mut rela_path, file_type)), (excludes, objects)| {
+            let mut entries = Vec::new();
+            let mut stats = Outcome::default();
+            let res = visit(
+                &ctx,
+                excludes,
+                &*objects,
+                &mut rela_path,
+                file_type,
+                &mut entries,
+                &mut stats,
+            );
+            (idx, res.map(|()| (entries, stats)))
+        },
+        Collect::default(),
+    )?;
+
+    out.read_dir_calls += stats.read_dir_calls;
+    out.seen_entries += stats.seen_entries;
+    for entry in entries {
+        out.returned_entries += 1;
+        delegate.emit(entry);
+    }
+    Ok(out)
+}
+
+struct Context<'a> {
+    worktree_root: &'a Path,
+    index: &'a gix_index::State,
+    options: Options,
+    should_interrupt: &'a AtomicBool,
+}
+
+/// Handle the item at `rela_path` and all of its children if it's a directory, placing untracked and ignored items into `out`.
+fn visit(
+    ctx: &Context<'_>,
+    excludes: &mut Stack,
+    objects: &dyn gix_object::Find,
+    rela_path: &mut BString,
+    file_type: FileType,
+    out: &mut Vec<Entry>,
+    stats: &mut Outcome,
+) -> Result<(), Error> {
+    stats.seen_entries += 1;
+    let kind = if file_type.is_symlink() {
+        Kind::Symlink
+    } else if file_type.is_dir() {
+        let is_repository = !ctx.options.recurse_repositories
+            && ctx
+                .worktree_root
+                .join(gix_path::from_bstr(rela_path.as_bstr()))
+                .join(".git")
+                .symlink_metadata()
+                .is_ok();
+        if is_repository {
+            Kind::Repository
+        } else {
+            Kind::Directory
+        }
+    } else {
+        Kind::File
+    };
+    let is_dir = matches!(kind, Kind::Directory | Kind::Repository);
+
+    if let Some(entry) = ctx.index.entry_by_path(rela_path.as_bstr()) {
+        // A directory in place of a tracked file makes the file appear deleted, and the directory untracked.
+        if !is_dir || entry.mode.is_submodule() {
+            return Ok(());
+        }
+    }
+    let has_tracked_files = is_dir && {
+        rela_path.push_byte(b'/');
+        let res = ctx.index.prefixed_entries(rela_path.as_bstr()).is_some();
+        rela_path.pop();
+        res
+    };
+
+    let platform = excludes
+        .at_entry(rela_path.as_bstr(), Some(is_dir), objects)
+        .map_err(|err| Error::ExcludeStack {
+            rela_path: rela_path.clone(),
+            source: err,
+        })?;
+    let is_ignored = platform.is_excluded();
+    if is_ignored && !has_tracked_files {
+        if ctx.options.emit_ignored.is_some() {
+            out.push(Entry {
+                rela_path: rela_path.clone(),
+                status: Status::Ignored,
+                kind,
+                exclude_match: ctx
+                    .options
+                    .record_exclude_match
+                    .then(|| platform.matching_exclude_pattern().map(Into::into))
+                    .flatten(),
+            });
+        }
+        return Ok(());
+    }
+
+    if !is_dir || (kind == Kind::Repository && !has_tracked_files) {
+        out.push(Entry {
+            rela_path: rela_path.clone(),
+            status: Status::Untracked,
+            kind,
+            exclude_match: None,
+        });
+        return Ok(());
+    }
+
+    if ctx.should_interrupt.load(Ordering::Relaxed) {
+        return Err(Error::Interrupted);
+    }
+    let start = out.len();
+    let children = read_dir(
+        &ctx.worktree_root.join(gix_path::from_bstr(rela_path.as_bstr())),
+        ctx.options,
+        stats,
+    )?;
+    for (name, file_type) in children {
+        let prev_len = rela_path.len();
+        rela_path.push_byte(b'/');
+        rela_path.push_str(&name);
+        let res = visit(ctx, excludes, objects, rela_path, file_type, out, stats);
+        rela_path.truncate(prev_len);
+        res?;
+    }
+    if !has_tracked_files {
+        collapse(out, start, rela_path.as_bstr(), kind, ctx.options);
+    }
+    Ok(())
+}
+
+/// Replace the entries of the untracked directory at `rela_path`, starting at `start` in `out`, with the directory itself
+/// if the emission mode permits it.
+fn collapse(out: &mut Vec<Entry>, start: usize, rela_path: &BStr, kind: Kind, options: Options) {
+    let entries = &out[start..];
+    if entries.is_empty() {
+        return;
+    }
+    let status = if entries.iter().any(|e| e.status == Status::Untracked) {
+        if options.emit_untracked != EmissionMode::CollapseDirectory {
+            return;
+        }
+        Status::Untracked
+    } else {
+        if options.emit_ignored != Some(EmissionMode::CollapseDirectory) {
+            return;
+        }
+        Status::Ignored
+    };
+    let remaining: Vec<_> = out.drain(start..).filter(|e| e.status != status).collect();
+    out.push(Entry {
+        rela_path: rela_path.to_owned(),
+        status,
+        kind,
+        exclude_match: None,
+    });
+    out.extend(remaining);
+}
+
+/// Read all entries in the directory at `path`, except for `.git`, sorted by the order of their paths in the index.
+fn read_dir(path: &Path, options: Options, stats: &mut Outcome) -> Result<Vec<(BString, FileType)>, Error> {
+    stats.read_dir_calls += 1;
+    let io_err = |source| Error::ReadDir {
+        path: path.to_owned(),
+        source,
+    };
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(path).map_err(io_err)? {
+        let entry = entry.map_err(io_err)?;
+        let name = gix_path::into_bstr(std::path::PathBuf::from(entry.file_name())).into_owned();
+        if name == ".git" || (options.ignore_case && name.eq_ignore_ascii_case(b".git")) {
+            continue;
+        }
+        entries.push((name, entry.file_type().map_err(io_err)?));
+    }
+    // Directories sort as if they had a trailing slash, just like their content does in the index.
+    entries.sort_by(|(a, a_type), (b, b_type)| {
+        let common_len = a.len().min(b.len());
+        a[..common_len].cmp(&b[..common_len]).then_with(|| {
+            let a_next = a.get(common_len).copied().or(a_type.is_dir().then_some(b'/'));
+            let b_next = b.get(common_len).copied().or(b_type.is_dir().then_some(b'/'));
+            a_next.cmp(&b_next)
+        })
+    });
+    Ok(entries)
+}
+
+type WalkResult = (usize, Result<(Vec<Entry>, Outcome), Error>);
+
+/// Collect the results of walking top-level items, to restore their original order.
+#[derive(Default)]
+struct Collect {
+    entries: Vec<(usize, Vec<Entry>)>,
+    stats: Outcome,
+}
+
+impl Reduce for Collect {
+    type Input = WalkResult;
+    type FeedProduce = ();
+    type Output = (Vec<Entry>, Outcome);
+    type Error = Error;
+
+    fn feed(&mut self, (idx, res): Self::Input) -> Result<Self::FeedProduce, Self::Error> {
+        let (entries, stats) = res?;
+        self.stats.read_dir_calls += stats.read_dir_calls;
+        self.stats.seen_entries += stats.seen_entries;
+        self.entries.push((idx, entries));
+        Ok(())
+    }
+
+    fn finalize(mut self) -> Result<Self::Output, Self::Error> {
+        self.entries.sort_by_key(|(idx, _)| *idx);
+        Ok((
+            self.entries.into_iter().flat_map(|(_, entries)| entries).collect(),
+            self.stats,
+        ))
+    }
+}
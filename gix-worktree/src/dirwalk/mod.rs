@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use bstr::BString;
+
+/// The kind of an item found on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Kind {
+    /// A file, which might be executable.
+    File,
+    /// A symbolic link, which is never followed.
+    Symlink,
+    /// A directory.
+    Directory,
+    /// A directory with a `.git` file or directory in it, which is a nested repository.
+    Repository,
+}
+
+/// The status of an item found on disk, in relation to the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Status {
+    /// The item isn't tracked by the index, and isn't ignored either.
+    Untracked,
+    /// The item isn't tracked by the index and matches an exclude pattern.
+    Ignored,
+}
+
+/// An untracked or ignored item on disk, as emitted by [`dirwalk()`](crate::dirwalk()).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Entry {
+    /// The path to the item, relative to the worktree root and with `/` as separator.
+    pub rela_path: BString,
+    /// The status of the item.
+    pub status: Status,
+    /// The kind of the item.
+    ///
+    /// Directories are only emitted if they are collapsed, or if they are ignored as a whole.
+    pub kind: Kind,
+    /// The exclude pattern that caused the item to be [ignored](Status::Ignored), if
+    /// [recorded](Options::record_exclude_match).
+    ///
+    /// It's always `None` for directories that were collapsed as all their content is ignored.
+    pub exclude_match: Option<ExcludeMatch>,
+}
+
+/// An owned version of the exclude pattern that matched an [ignored](Status::Ignored) [`Entry`].
+///
+/// As exclude files are layered, the source indicates which of them decided the item to be ignored, with command-line
+/// overrides taking precedence over `.gitignore` files of the innermost directory, followed by `.gitignore` files of
+/// their parent directories, `$GIT_DIR/info/exclude` and finally the file configured in `core.excludesFile`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct ExcludeMatch {
+    /// The glob pattern itself, like `/target/*`.
+    pub pattern: gix_glob::Pattern,
+    /// The path to the file from which the pattern was loaded, or `None` if it was specified by other means.
+    pub source: Option<PathBuf>,
+    /// The line at which the pattern was found in its `source` file, or the occurrence in which it was provided.
+    pub sequence_number: usize,
+}
+
+impl From<gix_ignore::search::Match<'_>> for ExcludeMatch {
+    fn from(m: gix_ignore::search::Match<'_>) -> Self {
+        ExcludeMatch {
+            pattern: m.pattern.clone(),
+            source: m.source.map(ToOwned::to_owned),
+            sequence_number: m.sequence_number,
+        }
+    }
+}
+
+/// Control how items of directories are emitted.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmissionMode {
+    /// Emit each item individually, similar to `git status --untracked-files=all`.
+    #[default]
+    Matching,
+    /// Emit a directory instead of its content if it contains no tracked files and at least one item with the same status,
+    /// similar to `git status --untracked-files=normal`.
+    CollapseDirectory,
+}
+
+/// Options for use in [`dirwalk()`](crate::dirwalk()).
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Options {
+    /// Control how untracked items are emitted.
+    pub emit_untracked: EmissionMode,
+    /// If `Some(mode)`, ignored items are emitted as well, and `mode` controls how.
+    ///
+    /// Note that directories that are ignored by themselves are never entered, and emitted as a whole, unless they
+    /// contain tracked files.
+    pub emit_ignored: Option<EmissionMode>,
+    /// If `true`, the exclude pattern that caused an item to be ignored is recorded in [`Entry::exclude_match`],
+    /// which is useful to learn which of the layered exclude files is responsible.
+    pub record_exclude_match: bool,
+    /// If `true`, nested repositories are entered like any other directory, instead of being emitted as a whole.
+    pub recurse_repositories: bool,
+    /// If `true`, the filesystem is case-insensitive, which affects the detection of `.git` directories.
+    pub ignore_case: bool,
+    /// If set, don't use more than this amount of threads.
+    /// Otherwise, usually use as many threads as there are logical cores.
+    /// A value of 0 is interpreted as no-limit
+    pub thread_limit: Option<usize>,
+}
+
+/// Statistics about a [`dirwalk()`](crate::dirwalk()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of directories that were read.
+    pub read_dir_calls: usize,
+    /// The amount of items that were seen on disk, including tracked ones.
+    pub seen_entries: usize,
+    /// The amount of entries that were passed to the delegate.
+    pub returned_entries: usize,
+}
+
+/// The error returned by [`dirwalk()`](crate::dirwalk()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not read directory or its entries at '{}'", path.display())]
+    ReadDir { path: PathBuf, source: std::io::Error },
+    #[error("Could not obtain exclude information for '{rela_path}'")]
+    ExcludeStack { rela_path: BString, source: std::io::Error },
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// A type receiving untracked and ignored entries as found by [`dirwalk()`](crate::dirwalk()).
+pub trait Delegate {
+    /// Called for each untracked or ignored `entry`, in order of their paths.
+    fn emit(&mut self, entry: Entry);
+}
+
+/// A delegate that records all entries it receives.
+#[derive(Debug, Default, Clone)]
+pub struct Recorder {
+    /// All entries in the order they were emitted.
+    pub entries: Vec<Entry>,
+}
+
+impl Delegate for Recorder {
+    fn emit(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+}
+
+pub(crate) mod function;
@@ -51,3 +51,7 @@ pub(crate) type PathIdMapping = (BString, gix_hash::ObjectId);
 
 ///
 pub mod stack;
+
+/// Find untracked and ignored items in a worktree.
+pub mod dirwalk;
+pub use dirwalk::function::dirwalk;
@@ -55,6 +55,19 @@ pub mod attributes;
 ///
 pub mod ignore;
 
+///
+pub mod id_mappings_from_tree {
+    /// The error returned by [`State::id_mappings_from_tree()`][crate::stack::State::id_mappings_from_tree()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindTree(#[from] gix_object::find::existing_iter::Error),
+        #[error(transparent)]
+        DecodeTree(#[from] gix_object::decode::Error),
+    }
+}
+
 /// Initialization
 impl State {
     /// Configure a state to be suitable for checking out files, which only needs access to attribute files read from the index.
@@ -158,6 +171,70 @@ impl State {
             .collect()
     }
 
+    /// Like [`id_mappings_from_index()`][Self::id_mappings_from_index()], but obtains the blob hashes of *ignore* or
+    /// *attribute* files by traversing the tree with `tree_id` and all of its subtrees, which are looked up in `objects`.
+    ///
+    /// This avoids creating an index from a tree just to read attributes and excludes from it, which is useful when operating
+    /// on trees in bare repositories.
+    ///
+    /// Note that this method…
+    /// - ignores entries which aren't blobs.
+    /// - uses ignore files irrespective of their [source](ignore::Source) as trees have no notion of skipped worktree entries.
+    ///
+    /// * `case` determines if the search for files should be case-sensitive or not.
+    pub fn id_mappings_from_tree(
+        &self,
+        tree_id: &gix_hash::oid,
+        objects: &dyn gix_object::Find,
+        case: Case,
+    ) -> Result<Vec<PathIdMapping>, id_mappings_from_tree::Error> {
+        use gix_object::FindExt;
+
+        let mut names: Vec<&bstr::BStr> = Vec::with_capacity(2);
+        match self {
+            State::IgnoreStack(ignore) => names.push(ignore.exclude_file_name_for_directories.as_bytes().as_bstr()),
+            #[cfg(feature = "attributes")]
+            State::AttributesAndIgnoreStack { ignore, .. } => {
+                names.push(ignore.exclude_file_name_for_directories.as_bytes().as_bstr());
+                names.push(".gitattributes".into());
+            }
+            #[cfg(feature = "attributes")]
+            State::CreateDirectoryAndAttributesStack { .. } | State::AttributesStack(_) => {
+                names.push(".gitattributes".into());
+            }
+        }
+
+        let mut out = Vec::new();
+        let mut buf = Vec::new();
+        let mut trees = vec![(BString::default(), tree_id.to_owned())];
+        while let Some((dir, tree_id)) = trees.pop() {
+            for entry in objects.find_tree_iter(&tree_id, &mut buf)? {
+                let entry = entry?;
+                let is_tree = entry.mode.is_tree();
+                let is_wanted_file = entry.mode.kind() == gix_object::tree::EntryKind::Blob
+                    && names.iter().any(|name| match case {
+                        Case::Sensitive => entry.filename == *name,
+                        Case::Fold => entry.filename.eq_ignore_ascii_case(name),
+                    });
+                if !is_tree && !is_wanted_file {
+                    continue;
+                }
+                let mut path = dir.clone();
+                if !path.is_empty() {
+                    path.push(b'/');
+                }
+                path.extend_from_slice(entry.filename);
+                if is_tree {
+                    trees.push((path, entry.oid.to_owned()));
+                } else {
+                    out.push((path, entry.oid.to_owned()));
+                }
+            }
+        }
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(out)
+    }
+
     pub(crate) fn ignore_or_panic(&self) -> &Ignore {
         match self {
             State::IgnoreStack(v) => v,
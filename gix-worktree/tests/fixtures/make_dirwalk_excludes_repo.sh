@@ -0,0 +1,27 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+cat <<EOF >.gitignore
+*.o
+EOF
+mkdir sub
+cat <<EOF >sub/.gitignore
+special.log
+!keep.o
+EOF
+git add .
+git commit -q -m "init"
+
+cat <<EOF >.git/info/exclude
+*.log
+*.tmp
+EOF
+cat <<EOF >.git/user-excludes
+*.tmp
+*.bak
+*.o
+EOF
+
+touch a.log a.tmp a.bak a.o sub/special.log sub/keep.o
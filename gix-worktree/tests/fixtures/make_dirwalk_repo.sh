@@ -0,0 +1,24 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+cat <<EOF >.gitignore
+*.o
+/ignored-dir/
+EOF
+mkdir -p tracked/sub
+touch tracked/file tracked/sub/file
+git add .
+git commit -q -m "init"
+
+touch untracked untracked-dir.txt tracked/untracked tracked/sub/untracked.o file.o
+mkdir -p untracked-dir/sub
+touch untracked-dir/a untracked-dir/ignored.o untracked-dir/sub/b
+mkdir -p only-ignored/sub
+touch only-ignored/a.o only-ignored/sub/b.o
+mkdir ignored-dir
+touch ignored-dir/file
+mkdir empty-dir
+git init -q nested-repo
+touch nested-repo/file
@@ -0,0 +1,195 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_worktree::{
+    dirwalk::{EmissionMode, Entry, Kind, Options, Recorder, Status},
+    stack::state::ignore::Source,
+    Stack,
+};
+
+fn walk(options: Options) -> crate::Result<Vec<Entry>> {
+    let worktree = gix_testtools::scripted_fixture_read_only_standalone("make_dirwalk_repo.sh")?;
+    walk_worktree(&worktree, None, options)
+}
+
+fn walk_worktree(worktree: &Path, excludes_file: Option<PathBuf>, options: Options) -> crate::Result<Vec<Entry>> {
+    let git_dir = worktree.join(".git");
+    let index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    let mut buf = Vec::new();
+    let state = gix_worktree::stack::State::IgnoreStack(gix_worktree::stack::state::Ignore::new(
+        Default::default(),
+        gix_ignore::Search::from_git_dir(&git_dir, excludes_file, &mut buf)?,
+        None,
+        Source::WorktreeThenIdMappingIfNotSkipped,
+    ));
+    let excludes = Stack::new(
+        worktree,
+        state,
+        gix_glob::pattern::Case::Sensitive,
+        buf,
+        Default::default(),
+    );
+
+    let mut recorder = Recorder::default();
+    let out = gix_worktree::dirwalk(
+        worktree,
+        &index,
+        &excludes,
+        gix_object::find::Never,
+        &mut recorder,
+        &AtomicBool::default(),
+        options,
+    )?;
+    assert_eq!(out.returned_entries, recorder.entries.len());
+    Ok(recorder.entries)
+}
+
+fn untracked(path: &str, kind: Kind) -> Entry {
+    Entry {
+        rela_path: path.into(),
+        status: Status::Untracked,
+        kind,
+        exclude_match: None,
+    }
+}
+
+fn ignored(path: &str, kind: Kind) -> Entry {
+    Entry {
+        rela_path: path.into(),
+        status: Status::Ignored,
+        kind,
+        exclude_match: None,
+    }
+}
+
+#[test]
+fn untracked_files_are_emitted_individually_by_default() -> crate::Result {
+    assert_eq!(
+        walk(Options::default())?,
+        [
+            untracked("nested-repo", Kind::Repository),
+            untracked("tracked/untracked", Kind::File),
+            untracked("untracked", Kind::File),
+            untracked("untracked-dir.txt", Kind::File),
+            untracked("untracked-dir/a", Kind::File),
+            untracked("untracked-dir/sub/b", Kind::File),
+        ],
+        "similar to `git status --untracked-files=all`, and in index order"
+    );
+    Ok(())
+}
+
+#[test]
+fn untracked_directories_can_be_collapsed_while_ignored_files_are_kept() -> crate::Result {
+    assert_eq!(
+        walk(Options {
+            emit_untracked: EmissionMode::CollapseDirectory,
+            emit_ignored: Some(EmissionMode::Matching),
+            ..Default::default()
+        })?,
+        [
+            ignored("file.o", Kind::File),
+            ignored("ignored-dir", Kind::Directory),
+            untracked("nested-repo", Kind::Repository),
+            ignored("only-ignored/a.o", Kind::File),
+            ignored("only-ignored/sub/b.o", Kind::File),
+            ignored("tracked/sub/untracked.o", Kind::File),
+            untracked("tracked/untracked", Kind::File),
+            untracked("untracked", Kind::File),
+            untracked("untracked-dir.txt", Kind::File),
+            untracked("untracked-dir", Kind::Directory),
+            ignored("untracked-dir/ignored.o", Kind::File),
+        ],
+        "ignored directories are never entered"
+    );
+    Ok(())
+}
+
+#[test]
+fn directories_with_only_ignored_files_can_be_collapsed() -> crate::Result {
+    assert_eq!(
+        walk(Options {
+            emit_untracked: EmissionMode::CollapseDirectory,
+            emit_ignored: Some(EmissionMode::CollapseDirectory),
+            ..Default::default()
+        })?,
+        [
+            ignored("file.o", Kind::File),
+            ignored("ignored-dir", Kind::Directory),
+            untracked("nested-repo", Kind::Repository),
+            ignored("only-ignored", Kind::Directory),
+            ignored("tracked/sub/untracked.o", Kind::File),
+            untracked("tracked/untracked", Kind::File),
+            untracked("untracked", Kind::File),
+            untracked("untracked-dir.txt", Kind::File),
+            untracked("untracked-dir", Kind::Directory),
+            ignored("untracked-dir/ignored.o", Kind::File),
+        ],
+        "similar to `git status --untracked-files=normal --ignored`"
+    );
+    Ok(())
+}
+
+#[test]
+fn nested_repositories_can_be_entered() -> crate::Result {
+    let entries = walk(Options {
+        recurse_repositories: true,
+        ..Default::default()
+    })?;
+    assert_eq!(
+        entries[0],
+        untracked("nested-repo/file", Kind::File),
+        "the `.git` directory itself is never emitted"
+    );
+    assert_eq!(entries.len(), 6);
+    Ok(())
+}
+
+#[test]
+fn exclude_sources_are_layered_and_the_matching_one_can_be_recorded() -> crate::Result {
+    let worktree = gix_testtools::scripted_fixture_read_only_standalone("make_dirwalk_excludes_repo.sh")?;
+    let git_dir = worktree.join(".git");
+    let user_excludes = git_dir.join("user-excludes");
+    let entries = walk_worktree(
+        &worktree,
+        Some(user_excludes.clone()),
+        Options {
+            emit_ignored: Some(EmissionMode::Matching),
+            record_exclude_match: true,
+            ..Default::default()
+        },
+    )?;
+    let actual: Vec<_> = entries
+        .iter()
+        .map(|e| {
+            let m = e.exclude_match.as_ref();
+            (
+                e.rela_path.to_string(),
+                e.status,
+                m.and_then(|m| m.source.clone()),
+                m.map(|m| m.sequence_number),
+            )
+        })
+        .collect();
+    let info_exclude = git_dir.join("info").join("exclude");
+    assert_eq!(
+        actual,
+        [
+            ("a.bak".into(), Status::Ignored, Some(user_excludes.clone()), Some(2)),
+            ("a.log".into(), Status::Ignored, Some(info_exclude.clone()), Some(1)),
+            ("a.o".into(), Status::Ignored, Some(worktree.join(".gitignore")), Some(1)),
+            ("a.tmp".into(), Status::Ignored, Some(info_exclude), Some(2)),
+            ("sub/keep.o".into(), Status::Untracked, None, None),
+            (
+                "sub/special.log".into(),
+                Status::Ignored,
+                Some(worktree.join("sub").join(".gitignore")),
+                Some(1)
+            ),
+        ],
+        "`.gitignore` files of the innermost directory win over those of their parents, `info/exclude` and `core.excludesFile`, in that order"
+    );
+    Ok(())
+}
@@ -152,3 +152,38 @@ fn check_against_baseline() -> crate::Result {
     }
     Ok(())
 }
+
+#[test]
+fn id_mappings_from_tree() -> crate::Result {
+    use gix_object::FindExt;
+
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_ignore_and_attributes_setup.sh")?;
+    let git_dir = dir.join("repo").join(".git");
+    let odb = gix_odb::at(git_dir.join("objects"))?;
+    let head_ref = std::fs::read_to_string(git_dir.join("HEAD"))?;
+    let head_ref = head_ref.trim().strip_prefix("ref: ").expect("symbolic HEAD");
+    let head_id = gix_hash::ObjectId::from_hex(std::fs::read_to_string(git_dir.join(head_ref))?.trim().as_bytes())?;
+    let tree_id = odb.find_commit(&head_id, &mut Vec::new())?.tree();
+
+    let state = gix_worktree::stack::State::IgnoreStack(gix_worktree::stack::state::Ignore::new(
+        Default::default(),
+        Default::default(),
+        None,
+        Source::WorktreeThenIdMappingIfNotSkipped,
+    ));
+    let index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    let in_tree = state.id_mappings_from_tree(&tree_id, &odb, gix_glob::pattern::Case::Sensitive)?;
+    assert_eq!(
+        in_tree.iter().map(|(path, _)| path.as_bstr()).collect::<Vec<_>>(),
+        [".gitignore", "dir-with-ignore/.gitignore"],
+        "the committed ignore files are found in subtrees as well, sorted by path"
+    );
+    for (path, id) in &in_tree {
+        assert_eq!(
+            index.entry_by_path(path.as_bstr()).map(|entry| entry.id),
+            Some(*id),
+            "the tree has the same ignore files as the index"
+        );
+    }
+    Ok(())
+}
@@ -62,4 +62,27 @@ impl<'repo> AttributeStack<'repo> {
     ) -> std::io::Result<gix_worktree::stack::Platform<'_>> {
         self.inner.at_entry(relative, is_dir, &self.repo.objects)
     }
+
+    /// Determine the states of the attributes selected in `out` for the repository-`relative` path, typically obtained from an
+    /// index entry, and return `out` to access them by name with [`selected_state()`][gix_attributes::search::Outcome::selected_state()].
+    ///
+    /// `out` is created once with [`selected_attribute_matches()`][gix_worktree::Stack::selected_attribute_matches()]
+    /// for the attributes of interest, like `eol`, `diff`, `merge` or `filter`, and reused for each path.
+    /// As attribute files are cached per directory and the search stops once all selected attributes are known,
+    /// this is efficient even when querying many paths.
+    ///
+    /// ### Panics
+    ///
+    /// - on illformed UTF8 in `relative`
+    #[cfg(feature = "attributes")]
+    pub fn selected_attributes_at<'r, 'o>(
+        &mut self,
+        relative: impl Into<&'r BStr>,
+        is_dir: Option<bool>,
+        out: &'o mut gix_attributes::search::Outcome,
+    ) -> std::io::Result<&'o gix_attributes::search::Outcome> {
+        out.reset();
+        self.at_entry(relative, is_dir)?.matching_attributes(out);
+        Ok(out)
+    }
 }
@@ -35,6 +35,81 @@ impl PrepareFetch {
         self
     }
 
+    /// Set how remote references are mapped to local ones, like `git clone --bare` or `git clone --mirror` would.
+    ///
+    /// Note that [`Mode::Bare`](crate::clone::Mode::Bare) and [`Mode::Mirror`](crate::clone::Mode::Mirror) can only be used
+    /// with bare repositories, which also can't be checked out.
+    pub fn with_mode(mut self, mode: crate::clone::Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set how objects are obtained if the remote is a repository on the local filesystem, which allows to hardlink or
+    /// copy its objects instead of receiving them through the pack protocol.
+    ///
+    /// References are still obtained from the remote as usual.
+    pub fn with_local_objects(mut self, local_objects: crate::clone::LocalObjects) -> Self {
+        self.local_objects = local_objects;
+        self
+    }
+
+    /// Borrow objects from the repository at `path` by adding its object database as alternate to the new repository,
+    /// so objects present there don't have to be received from the remote, like `git clone --reference` does.
+    ///
+    /// This can be called multiple times to borrow objects from more than one repository.
+    pub fn with_reference_repository(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.reference_repositories.push(path.into());
+        self
+    }
+
+    /// If `dissociate` is `true`, copy all objects borrowed from alternates into the new repository once the clone is done,
+    /// and stop using alternates, like `git clone --dissociate` does.
+    ///
+    /// This is useful to speed up a clone with [reference repositories](Self::with_reference_repository()) while still
+    /// obtaining an independent repository.
+    pub fn with_dissociate(mut self, dissociate: bool) -> Self {
+        self.dissociate = dissociate;
+        self
+    }
+
+    /// If `Some`, fetch only a single branch instead of all branches of the remote, and configure the remote to only fetch this
+    /// branch in future as well, which can save a lot of bandwidth.
+    ///
+    /// Tags are then only fetched if they point to commits of the fetched branch, unless configured otherwise
+    /// with [`configure_remote()`](Self::configure_remote()).
+    pub fn with_single_branch(mut self, branch: Option<crate::clone::SingleBranch>) -> Self {
+        self.single_branch = branch;
+        self
+    }
+
+    /// If `Some`, point `HEAD` to the branch with the given name, like `main` or `refs/heads/main`, instead of the branch
+    /// the remote `HEAD` points to, like `git clone --branch <name>` does. All branches are still fetched.
+    ///
+    /// This is ignored if a [single branch](Self::with_single_branch()) is fetched.
+    pub fn with_ref_name(mut self, name: Option<impl Into<BString>>) -> Self {
+        self.ref_name = name.map(Into::into);
+        self
+    }
+
+    /// If `Some`, ask the remote to omit objects according to the given filter specification, like `blob:none`, and
+    /// configure the remote as promisor remote of the resulting partial clone, like `git clone --filter=<spec>` does.
+    ///
+    /// Blobs needed for the checkout of the main worktree are fetched from the remote right before it starts. Other omitted
+    /// objects can be obtained with [`Repository::fetch_missing_objects()`](crate::Repository::fetch_missing_objects()).
+    pub fn with_filter(mut self, spec: Option<impl Into<String>>) -> Self {
+        self.filter = spec.map(Into::into);
+        self
+    }
+
+    /// If `sparse` is `true`, set up a sparse checkout in cone mode which only includes the files in the root directory,
+    /// like `git clone --sparse` does.
+    ///
+    /// More directories can be added to `$GIT_DIR/info/sparse-checkout` later. This has no effect on bare repositories.
+    pub fn with_sparse_checkout(mut self, sparse: bool) -> Self {
+        self.sparse_checkout = sparse;
+        self
+    }
+
     /// Apply the given configuration `values` right before readying the actual fetch from the remote.
     /// The configuration is marked with [source API](gix_config::Source::Api), and will not be written back, it's
     /// retained only in memory.
@@ -2,7 +2,11 @@ use crate::{clone::PrepareCheckout, Repository};
 
 ///
 pub mod main_worktree {
-    use std::{path::PathBuf, sync::atomic::AtomicBool};
+    use std::{
+        path::PathBuf,
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
+    };
 
     use crate::{clone::PrepareCheckout, Progress, Repository};
 
@@ -31,6 +35,56 @@ pub mod main_worktree {
         FindHead(#[from] crate::reference::find::existing::Error),
         #[error("The HEAD reference could not be located")]
         PeelHeadToId(#[from] crate::head::peel::Error),
+        #[error(transparent)]
+        SparseCheckout(#[from] crate::sparse::Error),
+        #[error("The checkout was interrupted")]
+        Interrupted,
+        #[cfg(feature = "blocking-network-client")]
+        #[error(transparent)]
+        FetchMissingObjects(#[from] crate::promisor::fetch_missing::Error),
+        #[error(transparent)]
+        PromisorRemoteNames(#[from] crate::config::boolean::Error),
+        #[cfg(feature = "blocking-network-client")]
+        #[error("Could not obtain the submodules of the repository at \"{}\"", git_dir.display())]
+        Submodules {
+            git_dir: PathBuf,
+            source: crate::submodule::modules::Error,
+        },
+        #[cfg(feature = "blocking-network-client")]
+        #[error(transparent)]
+        OpenSubmodule(#[from] crate::submodule::open::Error),
+        #[cfg(feature = "blocking-network-client")]
+        #[error(transparent)]
+        UpdateSubmodule(#[from] crate::submodule::update_with_checkout::Error),
+        #[error("The callback to run before the checkout failed")]
+        BeforeCheckout(#[source] Box<dyn std::error::Error + Send + Sync>),
+        #[error("The callback to run after the checkout failed")]
+        AfterCheckout(#[source] Box<dyn std::error::Error + Send + Sync>),
+    }
+
+    /// What to do once the callback set with [`PrepareCheckout::before_checkout()`] returns.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum Action {
+        /// Proceed with the checkout of the main worktree.
+        Checkout,
+        /// Don't check out anything and hand out the repository as is, similar to `git clone --no-checkout`.
+        Skip,
+    }
+
+    /// Timings and counts of the phases of [`PrepareCheckout::main_worktree()`], as obtained by
+    /// [`PrepareCheckout::performance()`].
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Performance {
+        /// The time it took to create the index from the tree at `HEAD`.
+        pub index_from_tree: Duration,
+        /// The time it took to write all files into the worktree.
+        pub checkout: Duration,
+        /// The amount of files that were written.
+        pub files_updated: usize,
+        /// The amount of bytes that were written.
+        pub bytes_written: u64,
+        /// The time it took to write the index.
+        pub write_index: Duration,
     }
 
     /// The progress ids used in [`PrepareCheckout::main_worktree()`].
@@ -60,6 +114,9 @@ pub mod main_worktree {
         ///
         /// Note that this is a no-op if the remote was empty, leaving this repository empty as well. This can be validated by checking
         /// if the `head()` of the returned repository is not unborn.
+        ///
+        /// If the checkout fails or is interrupted, the files written so far are remembered, so calling this method again
+        /// only writes the files that are still missing, along with overwriting those that may have been written partially.
         pub fn main_worktree<P>(
             &mut self,
             mut progress: P,
@@ -78,6 +135,18 @@ pub mod main_worktree {
             should_interrupt: &AtomicBool,
         ) -> Result<(Repository, gix_worktree_state::checkout::Outcome), Error> {
             let _span = gix_trace::coarse!("gix::clone::PrepareCheckout::main_worktree()");
+            if let Some(f) = self.before_checkout.as_mut().filter(|_| self.resume.is_none()) {
+                let repo = self
+                    .repo
+                    .as_mut()
+                    .expect("still present as we never succeeded the worktree checkout yet");
+                if f(repo).map_err(Error::BeforeCheckout)? == Action::Skip {
+                    return Ok((
+                        self.repo.take().expect("still present"),
+                        gix_worktree_state::checkout::Outcome::default(),
+                    ));
+                }
+            }
             let repo = self
                 .repo
                 .as_ref()
@@ -94,16 +163,65 @@ pub mod main_worktree {
                     ))
                 }
             };
-            let index = gix_index::State::from_tree(&root_tree, &repo.objects).map_err(|err| Error::IndexFromTree {
-                id: root_tree,
-                source: err,
-            })?;
-            let mut index = gix_index::File::from_state(index, repo.index_path());
+            let index_start = Instant::now();
+            let (mut index, is_resumed) = match self.resume.take() {
+                Some(index) => (index, true),
+                None => {
+                    let index =
+                        gix_index::State::from_tree(&root_tree, &repo.objects).map_err(|err| Error::IndexFromTree {
+                            id: root_tree,
+                            source: err,
+                        })?;
+                    let mut index = gix_index::File::from_state(index, repo.index_path());
+                    if let Some(patterns) = repo.sparse_checkout_patterns()? {
+                        patterns.update_skip_worktree(&mut index, workdir);
+                    }
+                    (index, false)
+                }
+            };
+            // Entries written by a previous attempt have their stat information set, and are skipped this time.
+            let written_previously: Vec<_> = index
+                .entries_mut()
+                .iter_mut()
+                .enumerate()
+                .filter(|(_, entry)| {
+                    entry.stat != Default::default() && !entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE)
+                })
+                .map(|(idx, entry)| {
+                    entry.set_flags(gix_index::entry::Flags::SKIP_WORKTREE, true);
+                    idx
+                })
+                .collect();
+            let index_from_tree = index_start.elapsed();
+
+            // Obtain all blobs that were omitted in a partial clone at once, instead of failing to check them out.
+            #[cfg(feature = "blocking-network-client")]
+            if !repo.promisor_remote_names()?.is_empty() {
+                let missing: Vec<_> = index
+                    .entries()
+                    .iter()
+                    .filter(|entry| {
+                        !entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE)
+                            && entry.mode != gix_index::entry::Mode::COMMIT
+                            && !repo.has_object(entry.id)
+                    })
+                    .map(|entry| entry.id)
+                    .collect();
+                if !missing.is_empty() {
+                    repo.fetch_missing_objects(
+                        missing,
+                        progress.add_child("fetch missing objects".into()),
+                        should_interrupt,
+                    )?;
+                }
+            }
 
             let mut opts = repo
                 .config
                 .checkout_options(repo, gix_worktree::stack::state::attributes::Source::IdMapping)?;
-            opts.destination_is_initially_empty = true;
+            opts.destination_is_initially_empty = !is_resumed;
+            // Files that failed to be written may exist partially.
+            opts.overwrite_existing |= is_resumed;
 
             let mut files = progress.add_child_with_id("checkout".to_string(), ProgressId::CheckoutFiles.into());
             let mut bytes = progress.add_child_with_id("writing".to_string(), ProgressId::BytesWritten.into());
@@ -111,8 +229,8 @@ pub mod main_worktree {
             files.init(Some(index.entries().len()), crate::progress::count("files"));
             bytes.init(None, crate::progress::bytes());
 
-            let start = std::time::Instant::now();
-            let outcome = gix_worktree_state::checkout(
+            let start = Instant::now();
+            let res = gix_worktree_state::checkout(
                 &mut index,
                 workdir,
                 repo.objects.clone().into_arc()?,
@@ -120,14 +238,64 @@ pub mod main_worktree {
                 &bytes,
                 should_interrupt,
                 opts,
-            )?;
+            );
+            for idx in written_previously.iter().copied() {
+                index.entries_mut()[idx].set_flags(gix_index::entry::Flags::SKIP_WORKTREE, false);
+            }
+            let mut outcome = match res {
+                Ok(outcome) if !should_interrupt.load(Ordering::Relaxed) => outcome,
+                res => {
+                    self.resume = Some(index);
+                    res?;
+                    return Err(Error::Interrupted);
+                }
+            };
+            outcome.files_updated -= written_previously.len();
+            let checkout = start.elapsed();
             files.show_throughput(start);
             bytes.show_throughput(start);
 
+            let write_index_start = Instant::now();
             index.write(Default::default())?;
+            self.performance = Some(Performance {
+                index_from_tree,
+                checkout,
+                files_updated: outcome.files_updated,
+                bytes_written: outcome.bytes_written,
+                write_index: write_index_start.elapsed(),
+            });
+            #[cfg(feature = "blocking-network-client")]
+            if self.recurse_submodules {
+                let repo = self.repo.as_ref().expect("still present");
+                update_submodules(repo, &mut progress.add_child("submodules".into()), should_interrupt)?;
+            }
+            if let Some(f) = self.after_checkout.as_mut() {
+                let repo = self.repo.as_mut().expect("still present");
+                f(repo, &outcome).map_err(Error::AfterCheckout)?;
+            }
             Ok((self.repo.take().expect("still present"), outcome))
         }
     }
+
+    /// Clone and check out all submodules of `repo` that aren't checked out yet, along with their submodules.
+    #[cfg(feature = "blocking-network-client")]
+    fn update_submodules(
+        repo: &Repository,
+        progress: &mut dyn gix_features::progress::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(), Error> {
+        let submodules = repo.submodules().map_err(|source| Error::Submodules {
+            git_dir: repo.git_dir().to_owned(),
+            source,
+        })?;
+        for sm in submodules.into_iter().flatten() {
+            sm.update_with_checkout(true, progress.add_child(sm.name().to_string()), should_interrupt)?;
+            if let Some(sm_repo) = sm.open()? {
+                update_submodules(&sm_repo, progress, should_interrupt)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Access
@@ -142,6 +310,69 @@ impl PrepareCheckout {
             .as_ref()
             .expect("present as checkout operation isn't complete")
     }
+
+    /// Return `true` if a previous [main worktree checkout](Self::main_worktree()) failed or was interrupted, and calling
+    /// it again resumes where it left off.
+    pub fn is_resumable(&self) -> bool {
+        self.resume.is_some()
+    }
+
+    /// Return timings and counts of the [main worktree checkout](Self::main_worktree()), or `None` if it wasn't
+    /// performed yet or didn't have anything to check out.
+    pub fn performance(&self) -> Option<main_worktree::Performance> {
+        self.performance
+    }
+}
+
+/// Builder
+impl PrepareCheckout {
+    /// If `cleanup` is `false`, keep the repository and everything written into its worktree even if this instance is dropped
+    /// before the [checkout](Self::main_worktree()) succeeded, instead of deleting it.
+    ///
+    /// This is useful to keep a clone whose checkout was interrupted, to finish it later.
+    pub fn with_cleanup_on_drop(mut self, cleanup: bool) -> Self {
+        self.cleanup_on_drop = cleanup;
+        self
+    }
+
+    /// Set a callback to call right before the [main worktree checkout](Self::main_worktree()), which may adjust the
+    /// configuration or references of the repository, for instance to change what `HEAD` points to.
+    ///
+    /// It decides whether to proceed with the checkout or to [skip it](main_worktree::Action::Skip).
+    /// It isn't called again if an interrupted checkout is resumed.
+    pub fn before_checkout(
+        mut self,
+        f: impl FnMut(&mut Repository) -> Result<main_worktree::Action, Box<dyn std::error::Error + Send + Sync>> + 'static,
+    ) -> Self {
+        self.before_checkout = Some(Box::new(f));
+        self
+    }
+
+    /// If `recurse` is `true`, clone and check out all submodules recursively once the
+    /// [main worktree checkout](Self::main_worktree()) succeeded, similar to `git clone --recurse-submodules`.
+    ///
+    /// Only the `checkout` update strategy is supported, see [`Submodule::update_with_checkout()`](crate::Submodule::update_with_checkout()).
+    /// If updating a submodule fails, the main worktree is checked out already, and the repository can be
+    /// [persisted](Self::persist()).
+    #[cfg(feature = "blocking-network-client")]
+    pub fn with_submodules(mut self, recurse: bool) -> Self {
+        self.recurse_submodules = recurse;
+        self
+    }
+
+    /// Set a callback to call once the [main worktree checkout](Self::main_worktree()) succeeded and the index was written,
+    /// which receives the outcome of the checkout. If it fails, the checkout fails as well.
+    pub fn after_checkout(
+        mut self,
+        f: impl FnMut(
+                &mut Repository,
+                &gix_worktree_state::checkout::Outcome,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+            + 'static,
+    ) -> Self {
+        self.after_checkout = Some(Box::new(f));
+        self
+    }
 }
 
 /// Consumption
@@ -154,6 +385,9 @@ impl PrepareCheckout {
 
 impl Drop for PrepareCheckout {
     fn drop(&mut self) {
+        if !self.cleanup_on_drop {
+            return;
+        }
         if let Some(repo) = self.repo.take() {
             std::fs::remove_dir_all(repo.work_dir().unwrap_or_else(|| repo.path())).ok();
         }
@@ -0,0 +1,114 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use super::Error;
+use crate::{clone::LocalObjects, Repository};
+
+/// Hardlink or copy all objects of the repository at `url` into `repo` according to `mode`, and return the
+/// object directories the remote borrows objects from, which `repo` will need as alternates as well.
+pub fn link_or_copy_objects(repo: &Repository, url: &gix_url::Url, mode: LocalObjects) -> Result<Vec<PathBuf>, Error> {
+    let path = gix_path::from_bstr(url.path.as_ref()).into_owned();
+    let remote = crate::open_opts(&path, crate::open::Options::isolated()).map_err(|err| Error::OpenObjectSource {
+        path,
+        source: err.into(),
+    })?;
+    let remote_objects = remote.objects.store_ref().path().to_owned();
+    link_or_copy_dir(
+        &remote_objects,
+        repo.objects.store_ref().path(),
+        matches!(mode, LocalObjects::HardLink),
+    )?;
+    Ok(gix_odb::alternate::resolve(
+        remote_objects,
+        repo.options.current_dir_or_empty(),
+    )?)
+}
+
+/// Return the object directories of all repositories at `paths`, with relative paths being relative to the current
+/// directory of `repo`.
+pub fn objects_dirs_of(repo: &Repository, paths: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    paths
+        .iter()
+        .map(|path| repo.options.current_dir_or_empty().join(path))
+        .map(|path| match crate::open_opts(&path, crate::open::Options::isolated()) {
+            Ok(repo) => Ok(repo.objects.store_ref().path().to_owned()),
+            Err(err) => Err(Error::OpenObjectSource {
+                path,
+                source: err.into(),
+            }),
+        })
+        .collect()
+}
+
+/// Write all `object_dirs` into the alternates file of `repo` as absolute and canonicalized paths, replacing its
+/// previous content.
+///
+/// Relative `object_dirs` are relative to the current directory of `repo`.
+pub fn write_alternates(repo: &Repository, object_dirs: &[PathBuf]) -> Result<(), Error> {
+    let info_dir = repo.objects.store_ref().path().join("info");
+    let path = info_dir.join("alternates");
+    let io_err = |source| Error::LocalObjects {
+        path: path.clone(),
+        source,
+    };
+    std::fs::create_dir_all(&info_dir).map_err(io_err)?;
+    let mut file = std::fs::File::create(&path).map_err(io_err)?;
+    for dir in object_dirs {
+        let dir = repo.options.current_dir_or_empty().join(dir);
+        let dir = gix_path::realpath(&dir).map_err(|source| Error::CanonicalizeAlternate { path: dir, source })?;
+        file.write_all(&gix_path::into_bstr(dir.as_path()))
+            .and_then(|_| file.write_all(b"\n"))
+            .map_err(io_err)?;
+    }
+    Ok(())
+}
+
+/// Copy all objects `repo` borrows from its alternates into its own object database and remove its alternates file.
+pub fn dissociate(repo: &Repository) -> Result<(), Error> {
+    let objects_dir = repo.objects.store_ref().path();
+    for alternate in gix_odb::alternate::resolve(objects_dir.to_owned(), repo.options.current_dir_or_empty())? {
+        link_or_copy_dir(&alternate, objects_dir, false)?;
+    }
+    let alternates = objects_dir.join("info").join("alternates");
+    match std::fs::remove_file(&alternates) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(Error::LocalObjects {
+            path: alternates,
+            source: err,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Recursively hardlink or copy all files in `src` to `dst`, skipping hidden files, files that already exist, and alternates,
+/// which need to be handled separately.
+/// Temporary files and lock files are skipped as well as they may be incomplete or vanish while copying.
+fn link_or_copy_dir(src: &Path, dst: &Path, hardlink: bool) -> Result<(), Error> {
+    let io_err = |path: &Path| {
+        let path = path.to_owned();
+        move |source| Error::LocalObjects { path, source }
+    };
+    std::fs::create_dir_all(dst).map_err(io_err(dst))?;
+    for entry in std::fs::read_dir(src).map_err(io_err(src))? {
+        let entry = entry.map_err(io_err(src))?;
+        let name = entry.file_name();
+        let lossy_name = name.to_string_lossy();
+        if lossy_name.starts_with('.') || lossy_name.starts_with("tmp_") || lossy_name.ends_with(".lock") {
+            continue;
+        }
+        let (from, to) = (entry.path(), dst.join(&name));
+        if entry.file_type().map_err(io_err(&from))?.is_dir() {
+            link_or_copy_dir(&from, &to, hardlink)?;
+            continue;
+        }
+        if (name == "alternates" && src.file_name().map_or(false, |dir| dir == "info")) || to.exists() {
+            continue;
+        }
+        if hardlink && std::fs::hard_link(&from, &to).is_ok() {
+            continue;
+        }
+        std::fs::copy(&from, &to).map_err(io_err(&from))?;
+    }
+    Ok(())
+}
@@ -16,6 +16,8 @@ pub enum Error {
     RemoteConfiguration(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("Custom configuration of connection to use when cloning failed")]
     RemoteConnection(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("The callback to run after fetching failed")]
+    AfterFetch(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error(transparent)]
     RemoteName(#[from] crate::config::remote::symbolic_name::Error),
     #[error(transparent)]
@@ -35,6 +37,41 @@ pub enum Error {
     },
     #[error("Failed to update HEAD with values from remote")]
     HeadUpdate(#[from] crate::reference::edit::Error),
+    #[error("Cloning with mode {mode:?} requires a bare repository")]
+    ModeRequiresBareRepository { mode: crate::clone::Mode },
+    #[error("Could not open repository at \"{}\" to obtain objects from", path.display())]
+    OpenObjectSource {
+        path: std::path::PathBuf,
+        source: Box<crate::open::Error>,
+    },
+    #[error("Failed to link, copy or write objects at \"{}\"", path.display())]
+    LocalObjects {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not obtain the canonical path of the alternate object directory at \"{}\"", path.display())]
+    CanonicalizeAlternate {
+        path: std::path::PathBuf,
+        source: gix_path::realpath::Error,
+    },
+    #[error(transparent)]
+    ResolveAlternates(#[from] gix_odb::alternate::Error),
+    #[error("The remote HEAD is detached or missing, so the single branch to fetch can't be determined")]
+    RemoteHeadNotABranch,
+    #[error("The branch name {name:?} is invalid")]
+    InvalidBranchName {
+        name: crate::bstr::BString,
+        source: gix_validate::reference::name::Error,
+    },
+    #[error("Remote branch {name:?} not found")]
+    RemoteBranchNotFound { name: crate::bstr::BString },
+    #[error(transparent)]
+    SetConfig(#[from] crate::config::set_value::Error),
+    #[error("Failed to write the sparse-checkout patterns to \"{}\"", path.display())]
+    WriteSparseCheckout {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
 }
 
 /// Modification
@@ -72,12 +109,20 @@ impl PrepareFetch {
         progress: &mut dyn crate::DynNestedProgress,
         should_interrupt: &std::sync::atomic::AtomicBool,
     ) -> Result<(crate::Repository, crate::remote::fetch::Outcome), Error> {
-        use crate::{bstr::ByteVec, remote, remote::fetch::RefLogMessage};
+        use crate::{
+            bstr::{ByteSlice, ByteVec},
+            clone::{LocalObjects, Mode, SingleBranch},
+            remote,
+            remote::fetch::RefLogMessage,
+        };
 
         let repo = self
             .repo
             .as_mut()
             .expect("user error: multiple calls are allowed only until it succeeds");
+        if self.mode != Mode::Default && !repo.is_bare() {
+            return Err(Error::ModeRequiresBareRepository { mode: self.mode });
+        }
 
         if !self.config_overrides.is_empty() {
             let mut snapshot = repo.config_snapshot_mut();
@@ -85,6 +130,16 @@ impl PrepareFetch {
             snapshot.commit()?;
         }
 
+        // Borrow or obtain objects before connecting so they don't have to be received through the pack protocol.
+        let mut alternates = Vec::new();
+        if self.local_objects != LocalObjects::Fetch && self.url.scheme == gix_url::Scheme::File {
+            alternates = local::link_or_copy_objects(repo, &self.url, self.local_objects)?;
+        }
+        alternates.extend(local::objects_dirs_of(repo, &self.reference_repositories)?);
+        if !alternates.is_empty() {
+            local::write_alternates(repo, &alternates)?;
+        }
+
         let remote_name = match self.remote_name.as_ref() {
             Some(name) => name.to_owned(),
             None => repo
@@ -96,36 +151,88 @@ impl PrepareFetch {
                 .unwrap_or_else(|| "origin".into()),
         };
 
+        let strip_heads = |name: &crate::bstr::BString| {
+            name.strip_prefix(b"refs/heads/")
+                .map_or(name.as_bstr(), ByteSlice::as_bstr)
+                .to_owned()
+        };
+        let single_branch = match self.single_branch.as_ref() {
+            Some(SingleBranch::Named(name)) => Some(strip_heads(name)),
+            None | Some(SingleBranch::RemoteHead) => None,
+        };
+        // The branch that the remote HEAD points to is learned from the refs it advertises when connecting.
+        let remote_head_only = self.single_branch == Some(SingleBranch::RemoteHead);
+        // A branch chosen by name is what HEAD should point to, so we don't need to know the remote HEAD.
+        let head_branch = match self.single_branch {
+            Some(SingleBranch::Named(_)) => single_branch.clone(),
+            Some(SingleBranch::RemoteHead) => None,
+            None => self.ref_name.as_ref().map(strip_heads),
+        }
+        .map(|mut name| {
+            name.insert_str(0, "refs/heads/");
+            name
+        });
+        if let Some(name) = &head_branch {
+            gix_validate::reference::name(name.as_ref()).map_err(|err| Error::InvalidBranchName {
+                name: name.clone(),
+                source: err,
+            })?;
+        }
+
+        let branch_destination = match self.mode {
+            Mode::Default => format!("refs/remotes/{remote_name}/"),
+            Mode::Bare | Mode::Mirror => "refs/heads/".into(),
+        };
+        let fetch_refspec = match (&single_branch, self.mode) {
+            (Some(branch), _) => format!("+refs/heads/{branch}:{branch_destination}{branch}"),
+            (None, Mode::Mirror) => "+refs/*:refs/*".into(),
+            (None, Mode::Default | Mode::Bare) => format!("+refs/heads/*:{branch_destination}*"),
+        };
+        // Like `git`, bare clones don't persist their refspec, and the one of the remote HEAD is only known after connecting.
+        let persist_refspec = self.mode != Mode::Bare && !remote_head_only;
         let mut remote = repo
             .remote_at(self.url.clone())?
             .with_refspecs(
-                Some(format!("+refs/heads/*:refs/remotes/{remote_name}/*").as_str()),
+                persist_refspec.then_some(fetch_refspec.as_str()),
                 remote::Direction::Fetch,
             )
             .expect("valid static spec");
         let mut clone_fetch_tags = None;
         if let Some(f) = self.configure_remote.as_mut() {
             remote = f(remote).map_err(Error::RemoteConfiguration)?;
-        } else {
+        } else if self.single_branch.is_none() {
             clone_fetch_tags = remote::fetch::Tags::All.into();
         }
 
-        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone())?;
+        let mut config = util::remote_to_local_config(&mut remote, remote_name.clone(), self.mode == Mode::Mirror)?;
 
         // Now we are free to apply remote configuration we don't want to be written to disk.
         if let Some(fetch_tags) = clone_fetch_tags {
             remote = remote.with_fetch_tags(fetch_tags);
         }
+        if !persist_refspec {
+            remote = remote
+                .with_refspecs(Some(fetch_refspec.as_str()), remote::Direction::Fetch)
+                .expect("valid static spec");
+        }
 
         // Add HEAD after the remote was written to config, we need it to know what to checkout later, and assure
         // the ref that HEAD points to is present no matter what.
-        let head_refspec = gix_refspec::parse(
-            format!("HEAD:refs/remotes/{remote_name}/HEAD").as_str().into(),
-            gix_refspec::parse::Operation::Fetch,
-        )
-        .expect("valid")
-        .to_owned();
-        let pending_pack: remote::fetch::Prepare<'_, '_, _> = {
+        // Bare clones don't have remote tracking branches, so HEAD is only needed to learn what it points to.
+        let head_refspec = head_branch.is_none().then(|| {
+            gix_refspec::parse(
+                match self.mode {
+                    Mode::Default => format!("HEAD:refs/remotes/{remote_name}/HEAD"),
+                    Mode::Bare | Mode::Mirror => "HEAD".into(),
+                }
+                .as_str()
+                .into(),
+                gix_refspec::parse::Operation::Fetch,
+            )
+            .expect("valid")
+            .to_owned()
+        });
+        let mut pending_pack: remote::fetch::Prepare<'_, '_, _> = {
             let mut connection = remote.connect(remote::Direction::Fetch).await?;
             if let Some(f) = self.configure_connection.as_mut() {
                 f(&mut connection).map_err(Error::RemoteConnection)?;
@@ -133,7 +240,7 @@ impl PrepareFetch {
             connection
                 .prepare_fetch(&mut *progress, {
                     let mut opts = self.fetch_options.clone();
-                    if !opts.extra_refspecs.contains(&head_refspec) {
+                    if let Some(head_refspec) = head_refspec.filter(|spec| !opts.extra_refspecs.contains(spec)) {
                         opts.extra_refspecs.push(head_refspec)
                     }
                     opts
@@ -143,27 +250,82 @@ impl PrepareFetch {
         if pending_pack.ref_map().object_hash != repo.object_hash() {
             unimplemented!("configure repository to expect a different object hash as advertised by the server")
         }
+        if let Some(name) = head_branch.as_ref() {
+            if !pending_pack.ref_map().remote_refs.iter().any(|r| r.unpack().0 == name) {
+                return Err(Error::RemoteBranchNotFound { name: name.clone() });
+            }
+        }
+        if remote_head_only {
+            let remote_head_branch = pending_pack
+                .ref_map()
+                .remote_refs
+                .iter()
+                .find_map(|r| match r {
+                    gix_protocol::handshake::Ref::Symbolic {
+                        full_ref_name, target, ..
+                    }
+                    | gix_protocol::handshake::Ref::Unborn { full_ref_name, target }
+                        if full_ref_name == "HEAD" && target.starts_with(b"refs/heads/") =>
+                    {
+                        Some(target.clone())
+                    }
+                    _ => None,
+                })
+                .ok_or(Error::RemoteHeadNotABranch)?;
+            // Only update the branch HEAD points to, along with the implied refs like HEAD itself and tags.
+            pending_pack.ref_map_mut().mappings.retain(|mapping| {
+                matches!(mapping.spec_index, remote::fetch::SpecIndex::Implicit(_))
+                    || mapping.remote.as_name() == Some(remote_head_branch.as_bstr())
+            });
+            if self.mode != Mode::Bare {
+                let branch = remote_head_branch["refs/heads/".len()..].as_bstr();
+                util::add_fetch_refspec(
+                    &mut config,
+                    remote_name.as_ref(),
+                    &format!("+refs/heads/{branch}:{branch_destination}{branch}"),
+                );
+            }
+        }
+        util::write_remote_config(&config)?;
+
         let reflog_message = {
             let mut b = self.url.to_bstring();
             b.insert_str(0, "clone: from ");
             b
         };
-        let outcome = pending_pack
+        let mut pending_pack = pending_pack
             .with_write_packed_refs_only(true)
             .with_reflog_message(RefLogMessage::Override {
                 message: reflog_message.clone(),
             })
             .with_shallow(self.shallow.clone())
-            .receive_inner(progress, should_interrupt)
-            .await?;
+            .with_keep_pack(true);
+        if let Some(spec) = &self.filter {
+            pending_pack = pending_pack.with_filter(spec.as_str());
+        }
+        let outcome = pending_pack.receive_inner(progress, should_interrupt).await?;
 
         util::append_config_to_repo_config(repo, config);
+        if let Some(spec) = &self.filter {
+            util::configure_partial_clone(repo, remote_name.as_ref(), spec)?;
+        }
+        if self.sparse_checkout && !repo.is_bare() {
+            util::configure_sparse_checkout(repo)?;
+        }
         util::update_head(
             repo,
             &outcome.ref_map.remote_refs,
             reflog_message.as_ref(),
             remote_name.as_ref(),
+            head_branch.as_ref().map(|name| name.as_bstr()),
+            self.mode == Mode::Default,
         )?;
+        if self.dissociate {
+            local::dissociate(repo)?;
+        }
+        if let Some(f) = self.after_fetch.as_mut() {
+            f(repo, &outcome).map_err(Error::AfterFetch)?;
+        }
 
         Ok((self.repo.take().expect("still present"), outcome))
     }
@@ -180,8 +342,21 @@ impl PrepareFetch {
         P::SubProgress: 'static,
     {
         let (repo, fetch_outcome) = self.fetch_only(progress, should_interrupt)?;
-        Ok((crate::clone::PrepareCheckout { repo: repo.into() }, fetch_outcome))
+        Ok((
+            crate::clone::PrepareCheckout {
+                repo: repo.into(),
+                performance: None,
+                resume: None,
+                cleanup_on_drop: true,
+                before_checkout: None,
+                after_checkout: None,
+                #[cfg(feature = "blocking-network-client")]
+                recurse_submodules: false,
+            },
+            fetch_outcome,
+        ))
     }
 }
 
+mod local;
 mod util;
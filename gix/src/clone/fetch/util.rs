@@ -16,18 +16,51 @@ enum WriteMode {
     Append,
 }
 
+/// Save `remote` as `remote_name` into a new configuration file for the local repository configuration,
+/// which also names `remote`.
 #[allow(clippy::result_large_err)]
-pub fn write_remote_to_local_config_file(
+pub fn remote_to_local_config(
     remote: &mut crate::Remote<'_>,
     remote_name: BString,
+    mirror: bool,
 ) -> Result<gix_config::File<'static>, Error> {
     let mut config = gix_config::File::new(local_config_meta(remote.repo));
-    remote.save_as_to(remote_name, &mut config)?;
-
-    write_to_local_config(&config, WriteMode::Append)?;
+    remote.save_as_to(remote_name.clone(), &mut config)?;
+    if mirror {
+        config
+            .set_raw_value(
+                "remote",
+                Some(remote_name.as_ref()),
+                crate::config::tree::Remote::MIRROR.name,
+                "true",
+            )
+            .expect("valid at compile time, and the remote name was validated");
+    }
     Ok(config)
 }
 
+/// Add `spec` as fetch refspec to the remote named `remote_name` in `config`, which must have been created
+/// with [`remote_to_local_config()`].
+pub fn add_fetch_refspec(config: &mut gix_config::File<'static>, remote_name: &BStr, spec: &str) {
+    config
+        .section_mut("remote", Some(remote_name))
+        .expect("remote section was written")
+        .push(
+            crate::config::tree::Remote::FETCH
+                .name
+                .try_into()
+                .expect("valid at compile time"),
+            Some(spec.into()),
+        );
+}
+
+/// Append `config`, as obtained by [`remote_to_local_config()`], to the configuration file of the local repository.
+#[allow(clippy::result_large_err)]
+pub fn write_remote_config(config: &gix_config::File<'static>) -> Result<(), Error> {
+    write_to_local_config(config, WriteMode::Append)?;
+    Ok(())
+}
+
 fn local_config_meta(repo: &Repository) -> gix_config::file::Metadata {
     let meta = repo.config.resolved.meta().clone();
     assert_eq!(
@@ -53,6 +86,40 @@ fn write_to_local_config(config: &gix_config::File<'static>, mode: WriteMode) ->
     config.write_to_filter(&mut local_config, &mut |s| s.meta().source == gix_config::Source::Local)
 }
 
+/// Configure the remote named `remote_name` as promisor remote which omitted objects according to the filter `spec`,
+/// and use it to obtain missing objects, like `git clone --filter` does.
+#[allow(clippy::result_large_err)]
+pub fn configure_partial_clone(repo: &mut Repository, remote_name: &BStr, spec: &str) -> Result<(), Error> {
+    use crate::config::tree::{Core, Extensions, Remote};
+    let mut config = repo.config_snapshot_mut();
+    config.set_value(&Core::REPOSITORY_FORMAT_VERSION, "1")?;
+    config.set_value(&Extensions::PARTIAL_CLONE, remote_name)?;
+    config.set_subsection_value(&Remote::PROMISOR, remote_name, "true")?;
+    config.set_subsection_value(&Remote::PARTIAL_CLONE_FILTER, remote_name, spec)?;
+    write_to_local_config(&config, WriteMode::Overwrite)?;
+    config.commit()?;
+    Ok(())
+}
+
+/// Enable a sparse checkout in cone mode which only includes the files in the root directory, like `git clone --sparse` does.
+#[allow(clippy::result_large_err)]
+pub fn configure_sparse_checkout(repo: &mut Repository) -> Result<(), Error> {
+    use crate::config::tree::Core;
+    let path = repo.git_dir().join("info").join("sparse-checkout");
+    std::fs::create_dir_all(path.parent().expect("file in directory"))
+        .and_then(|_| std::fs::write(&path, "/*\n!/*/\n"))
+        .map_err(|err| Error::WriteSparseCheckout {
+            path: path.clone(),
+            source: err,
+        })?;
+    let mut config = repo.config_snapshot_mut();
+    config.set_value(&Core::SPARSE_CHECKOUT, "true")?;
+    config.set_value(&Core::SPARSE_CHECKOUT_CONE, "true")?;
+    write_to_local_config(&config, WriteMode::Overwrite)?;
+    config.commit()?;
+    Ok(())
+}
+
 pub fn append_config_to_repo_config(repo: &mut Repository, config: gix_config::File<'static>) {
     let repo_config = gix_features::threading::OwnShared::make_mut(&mut repo.config.resolved);
     repo_config.append(config);
@@ -60,33 +127,44 @@ pub fn append_config_to_repo_config(repo: &mut Repository, config: gix_config::F
 
 /// HEAD cannot be written by means of refspec by design, so we have to do it manually here. Also create the pointed-to ref
 /// if we have to, as it might not have been naturally included in the ref-specs.
+/// If `head_branch` is set, HEAD will point to this branch instead of the branch that the remote HEAD points to.
+/// If `setup_branch_tracking` is `true`, the branch HEAD points to will also be configured to track its remote counterpart.
 pub fn update_head(
     repo: &mut Repository,
     remote_refs: &[gix_protocol::handshake::Ref],
     reflog_message: &BStr,
     remote_name: &BStr,
+    head_branch: Option<&BStr>,
+    setup_branch_tracking: bool,
 ) -> Result<(), Error> {
     use gix_ref::{
         transaction::{PreviousValue, RefEdit},
         Target,
     };
-    let (head_peeled_id, head_ref) = match remote_refs.iter().find_map(|r| {
-        Some(match r {
-            gix_protocol::handshake::Ref::Symbolic {
-                full_ref_name,
-                target,
-                tag: _,
-                object,
-            } if full_ref_name == "HEAD" => (Some(object.as_ref()), Some(target)),
-            gix_protocol::handshake::Ref::Direct { full_ref_name, object } if full_ref_name == "HEAD" => {
-                (Some(object.as_ref()), None)
-            }
-            gix_protocol::handshake::Ref::Unborn { full_ref_name, target } if full_ref_name == "HEAD" => {
-                (None, Some(target))
-            }
-            _ => return None,
-        })
-    }) {
+    let head = match head_branch {
+        Some(branch) => remote_refs.iter().find_map(|r| {
+            let (name, id, _peeled) = r.unpack();
+            (name == branch).then_some((id, Some(branch)))
+        }),
+        None => remote_refs.iter().find_map(|r| {
+            Some(match r {
+                gix_protocol::handshake::Ref::Symbolic {
+                    full_ref_name,
+                    target,
+                    tag: _,
+                    object,
+                } if full_ref_name == "HEAD" => (Some(object.as_ref()), Some(target.as_bstr())),
+                gix_protocol::handshake::Ref::Direct { full_ref_name, object } if full_ref_name == "HEAD" => {
+                    (Some(object.as_ref()), None)
+                }
+                gix_protocol::handshake::Ref::Unborn { full_ref_name, target } if full_ref_name == "HEAD" => {
+                    (None, Some(target.as_bstr()))
+                }
+                _ => return None,
+            })
+        }),
+    };
+    let (head_peeled_id, head_ref) = match head {
         Some(t) => t,
         None => return Ok(()),
     };
@@ -157,7 +235,9 @@ pub fn update_head(
                 })?;
             }
 
-            setup_branch_config(repo, referent.as_ref(), head_peeled_id, remote_name)?;
+            if setup_branch_tracking {
+                setup_branch_config(repo, referent.as_ref(), head_peeled_id, remote_name)?;
+            }
         }
         None => {
             repo.edit_reference(RefEdit {
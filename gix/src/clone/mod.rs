@@ -11,6 +11,23 @@ type ConfigureConnectionFn = Box<
         &mut remote::Connection<'_, '_, Box<dyn gix_protocol::transport::client::Transport + Send>>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>,
 >;
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+type AfterFetchFn = Box<
+    dyn FnMut(&mut crate::Repository, &remote::fetch::Outcome) -> Result<(), Box<dyn std::error::Error + Send + Sync>>,
+>;
+#[cfg(feature = "worktree-mutation")]
+type BeforeCheckoutFn = Box<
+    dyn FnMut(
+        &mut crate::Repository,
+    ) -> Result<checkout::main_worktree::Action, Box<dyn std::error::Error + Send + Sync>>,
+>;
+#[cfg(feature = "worktree-mutation")]
+type AfterCheckoutFn = Box<
+    dyn FnMut(
+        &mut crate::Repository,
+        &gix_worktree_state::checkout::Outcome,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>,
+>;
 
 /// A utility to collect configuration on how to fetch from a remote and initiate a fetch operation. It will delete the newly
 /// created repository on when dropped without successfully finishing a fetch.
@@ -27,6 +44,9 @@ pub struct PrepareFetch {
     /// A function to configure a connection before using it.
     #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
     configure_connection: Option<ConfigureConnectionFn>,
+    /// A function to call once the fetch is complete and `HEAD` was updated.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    after_fetch: Option<AfterFetchFn>,
     /// Options for preparing a fetch operation.
     #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
     fetch_options: remote::ref_map::Options,
@@ -36,6 +56,78 @@ pub struct PrepareFetch {
     /// How to handle shallow clones
     #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
     shallow: remote::fetch::Shallow,
+    /// How to map remote references to local ones.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    mode: Mode,
+    /// How to obtain objects if the remote is on the local filesystem.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    local_objects: LocalObjects,
+    /// Repositories whose objects to borrow via alternates.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    reference_repositories: Vec<std::path::PathBuf>,
+    /// If `true`, copy borrowed objects and stop using alternates once the clone is done.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    dissociate: bool,
+    /// If set, the only branch to fetch.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    single_branch: Option<SingleBranch>,
+    /// If set, the branch to point `HEAD` to instead of the one the remote `HEAD` points to.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    ref_name: Option<BString>,
+    /// If set, the filter specification to receive a partial clone.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    filter: Option<String>,
+    /// If `true`, set up a sparse checkout that only includes files in the root directory.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    sparse_checkout: bool,
+}
+
+/// Determines how the references of the remote are mapped to local references when cloning.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Map remote branches to remote tracking branches, like `refs/remotes/origin/main`, and configure the branch
+    /// that `HEAD` points to to track its counterpart on the remote, just like `git clone` does.
+    #[default]
+    Default,
+    /// Map remote branches to local branches of the same name, like `git clone --bare` does.
+    ///
+    /// The refspec used for this isn't persisted, and no branch is configured to track a remote branch.
+    /// This mode requires the repository to be [bare](crate::create::Kind::Bare).
+    Bare,
+    /// Map all references of the remote to local references of the same name with `+refs/*:refs/*`
+    /// and set `remote.<name>.mirror`, like `git clone --mirror` does, which is useful to maintain server-side mirrors.
+    ///
+    /// Subsequent fetches will keep the local references in sync with the remote.
+    /// This mode requires the repository to be [bare](crate::create::Kind::Bare).
+    Mirror,
+}
+
+/// Determines the only branch to fetch when cloning, like `git clone --single-branch` does.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SingleBranch {
+    /// Fetch only the branch that `HEAD` of the remote points to, as advertised by the remote when connecting.
+    RemoteHead,
+    /// Fetch only the branch with the given name, like `main` or `refs/heads/main`, and point `HEAD` to it,
+    /// like `git clone --single-branch --branch <name>` does.
+    Named(BString),
+}
+
+/// Determines how objects are obtained when cloning from a repository on the local filesystem,
+/// i.e. from a url with the `file` scheme.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LocalObjects {
+    /// Receive objects through the pack protocol, just like with any other remote, which is what `git clone --no-local` does.
+    #[default]
+    Fetch,
+    /// Hardlink all loose objects and packs of the remote into the new repository, and copy them if that's not possible,
+    /// for example if the repositories are on different devices. This is what `git clone` does for local paths by default.
+    ///
+    /// The objects of alternates of the remote are borrowed by adding them as alternates to the new repository.
+    HardLink,
+    /// Like [`HardLink`](LocalObjects::HardLink), but always copy loose objects and packs, like `git clone --no-hardlinks`.
+    ///
+    /// Note that copies are made with reflinks on filesystems and platforms that support them, making them similarly cheap.
+    Copy,
 }
 
 /// The error returned by [`PrepareFetch::new()`].
@@ -133,18 +225,41 @@ impl PrepareFetch {
             configure_remote: None,
             #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
             configure_connection: None,
+            #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+            after_fetch: None,
             shallow: remote::fetch::Shallow::NoChange,
+            mode: Mode::Default,
+            local_objects: LocalObjects::Fetch,
+            reference_repositories: Vec::new(),
+            dissociate: false,
+            single_branch: None,
+            ref_name: None,
+            filter: None,
+            sparse_checkout: false,
         })
     }
 }
 
 /// A utility to collect configuration on how to perform a checkout into a working tree, and when dropped without checking out successfully
-/// the fetched repository will be dropped.
+/// the fetched repository will be dropped, unless [configured otherwise](PrepareCheckout::with_cleanup_on_drop()).
 #[must_use]
 #[cfg(feature = "worktree-mutation")]
 pub struct PrepareCheckout {
     /// A freshly initialized repository which is owned by us, or `None` if it was handed to the user
     pub(self) repo: Option<crate::Repository>,
+    /// Timings and counts of the main worktree checkout, once it was performed.
+    pub(self) performance: Option<checkout::main_worktree::Performance>,
+    /// The index of a checkout that was interrupted or failed, with the stat information of all files that were written.
+    pub(self) resume: Option<gix_index::File>,
+    /// If `true`, the repository is deleted when dropped before the checkout succeeded.
+    pub(self) cleanup_on_drop: bool,
+    /// A function to call right before the checkout, which may also skip it.
+    pub(self) before_checkout: Option<BeforeCheckoutFn>,
+    /// A function to call once the checkout is complete and the index was written.
+    pub(self) after_checkout: Option<AfterCheckoutFn>,
+    /// If `true`, submodules are cloned and checked out recursively after the main worktree checkout.
+    #[cfg(feature = "blocking-network-client")]
+    pub(self) recurse_submodules: bool,
 }
 
 // This module encapsulates functionality that works with both feature toggles. Can be combined with `fetch`
@@ -170,6 +285,23 @@ mod access_feat {
             self
         }
 
+        /// Set a callback to call once all objects were received, references were updated and `HEAD` was set up,
+        /// but before the repository is handed out.
+        ///
+        /// It receives the outcome of the fetch operation and may adjust the configuration or references of the repository.
+        /// If it fails, the clone fails as well.
+        pub fn after_fetch(
+            mut self,
+            f: impl FnMut(
+                    &mut crate::Repository,
+                    &crate::remote::fetch::Outcome,
+                ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+                + 'static,
+        ) -> Self {
+            self.after_fetch = Some(Box::new(f));
+            self
+        }
+
         /// Set additional options to adjust parts of the fetch operation that are not affected by the git configuration.
         pub fn with_fetch_options(mut self, opts: crate::remote::ref_map::Options) -> Self {
             self.fetch_options = opts;
@@ -74,8 +74,16 @@ pub mod describe {
     }
 
     impl SelectRef {
-        fn names(&self, repo: &Repository) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
+        fn names(
+            &self,
+            repo: &Repository,
+            version_sort: Option<&crate::reference::VersionSort>,
+        ) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
             let platform = repo.references()?;
+            let cmp_names = |a: &BStr, b: &BStr| match version_sort {
+                Some(sort) => sort.compare(a, b),
+                None => a.cmp(b),
+            };
 
             Ok(match self {
                 SelectRef::AllTags | SelectRef::AllRefs => {
@@ -104,14 +112,14 @@ pub mod describe {
                             .into()
                     })
                     .collect();
-                    // By priority, then by time ascending, then lexicographically.
+                    // By priority, then by time ascending, then lexicographically or by version.
                     // More recent entries overwrite older ones due to collection into hashmap.
                     refs.sort_by(
                         |(_a_peeled_id, a_prio, a_time, a_name), (_b_peeled_id, b_prio, b_time, b_name)| {
                             a_prio
                                 .cmp(b_prio)
                                 .then_with(|| a_time.cmp(b_time))
-                                .then_with(|| b_name.cmp(a_name))
+                                .then_with(|| cmp_names(b_name.as_ref(), a_name.as_ref()))
                         },
                     );
                     refs.into_iter().map(|(a, _, _, b)| (a, b)).collect()
@@ -129,10 +137,12 @@ pub mod describe {
                             Some((commit_id, tag_time, Cow::<BStr>::from(r.name().shorten().to_owned())))
                         })
                         .collect();
-                    // Sort by time ascending, then lexicographically.
+                    // Sort by time ascending, then lexicographically or by version.
                     // More recent entries overwrite older ones due to collection into hashmap.
                     peeled_commits_and_tag_date.sort_by(|(_a_id, a_time, a_name), (_b_id, b_time, b_name)| {
-                        a_time.cmp(b_time).then_with(|| b_name.cmp(a_name))
+                        a_time
+                            .cmp(b_time)
+                            .then_with(|| cmp_names(b_name.as_ref(), a_name.as_ref()))
                     });
                     peeled_commits_and_tag_date
                         .into_iter()
@@ -151,6 +161,7 @@ pub mod describe {
         pub(crate) first_parent: bool,
         pub(crate) id_as_fallback: bool,
         pub(crate) max_candidates: usize,
+        pub(crate) sort_by_version: bool,
     }
 
     impl<'repo> Platform<'repo> {
@@ -172,6 +183,15 @@ pub mod describe {
             self
         }
 
+        /// If true, break ties between names of the same priority and age by the version they contain, using the
+        /// [repository version sort](crate::Repository::version_sort()), instead of lexicographically.
+        ///
+        /// This makes `v1.9` preferable over `v1.10` if both point to the same commit.
+        pub fn sort_names_by_version(mut self, by_version: bool) -> Self {
+            self.sort_by_version = by_version;
+            self
+        }
+
         /// If true, even if no candidate is available a format will always be produced.
         pub fn id_as_fallback(mut self, use_fallback: bool) -> Self {
             self.id_as_fallback = use_fallback;
@@ -205,7 +225,10 @@ pub mod describe {
                 &self.id,
                 &mut graph,
                 gix_revision::describe::Options {
-                    name_by_oid: self.select.names(self.repo)?,
+                    name_by_oid: self.select.names(
+                        self.repo,
+                        self.sort_by_version.then(|| self.repo.version_sort()).as_ref(),
+                    )?,
                     fallback_to_oid: self.id_as_fallback,
                     first_parent: self.first_parent,
                     max_candidates: self.max_candidates,
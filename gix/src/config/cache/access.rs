@@ -80,6 +80,9 @@ impl Cache {
             if let Some(textconv) = section.value(config::tree::Diff::DRIVER_TEXTCONV.name) {
                 driver.binary_to_text_command = textconv.into_owned().into();
             }
+            if let Some(word_regex) = section.value(config::tree::Diff::DRIVER_WORD_REGEX.name) {
+                driver.word_regex = word_regex.into_owned().into();
+            }
             if let Some(algorithm) = section.value("algorithm") {
                 driver.algorithm = config::tree::Diff::DRIVER_ALGORITHM
                     .try_into_algorithm(algorithm)
@@ -156,6 +159,34 @@ impl Cache {
             .unwrap_or_default()
     }
 
+    /// Return how requests over stateless transports are retried after spurious failures, which defaults to
+    /// 3 retries, waiting 1 second before the first one.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    pub(crate) fn network_retry(&self) -> Result<crate::remote::Retry, config::network_retry::Error> {
+        use config::tree::gitoxide::Http;
+
+        let mut retry = crate::remote::Retry::default();
+        if let Some(max) = self
+            .resolved
+            .integer_by_key(Http::MAX_RETRIES.logical_name().as_str())
+            .map(|value| Http::MAX_RETRIES.try_into_usize(value))
+            .transpose()
+            .with_leniency(self.lenient_config)?
+        {
+            retry.max = max;
+        }
+        if let Some(delay) = self
+            .resolved
+            .integer_by_key(Http::RETRY_DELAY.logical_name().as_str())
+            .map(|value| Http::RETRY_DELAY.try_into_duration(value))
+            .transpose()
+            .with_leniency(self.lenient_config)?
+        {
+            retry.delay = delay;
+        }
+        Ok(retry)
+    }
+
     pub(crate) fn personas(&self) -> &identity::Personas {
         self.personas
             .get_or_init(|| identity::Personas::from_config_and_env(&self.resolved))
@@ -236,6 +267,25 @@ impl Cache {
         res.transpose().with_leniency(self.lenient_config)
     }
 
+    /// Return the zlib compression level configured at `key`, falling back to `core.compression` and finally to `default`.
+    pub(crate) fn compression_level(
+        &self,
+        key: &'static config::tree::keys::Compression,
+        default: u32,
+    ) -> Result<u32, config::key::GenericError> {
+        let level = |key: &'static config::tree::keys::Compression| {
+            self.apply_leniency(
+                self.resolved
+                    .integer_by_key(key.logical_name().as_str())
+                    .map(|res| key.try_into_compression_level(res)),
+            )
+        };
+        Ok(match level(key)? {
+            Some(level) => level,
+            None => level(&Core::COMPRESSION)?.unwrap_or(default),
+        })
+    }
+
     pub(crate) fn fs_capabilities(&self) -> Result<gix_fs::Capabilities, boolean::Error> {
         Ok(gix_fs::Capabilities {
             precompose_unicode: boolean(self, "core.precomposeUnicode", &Core::PRECOMPOSE_UNICODE, false)?,
@@ -299,8 +349,16 @@ impl Cache {
         } else {
             gix_filter::driver::apply::Delay::Forbid
         };
+        let windows_names = if cfg!(windows) && boolean(self, "core.protectNTFS", &Core::PROTECT_NTFS, true)? {
+            gix_worktree_state::checkout::WindowsNames::Reject
+        } else {
+            gix_worktree_state::checkout::WindowsNames::Allow
+        };
+        let long_paths = boolean(self, "core.longpaths", &Core::LONG_PATHS, false)?;
         Ok(gix_worktree_state::checkout::Options {
             filter_process_delay,
+            windows_names,
+            long_paths,
             filters,
             attributes: self
                 .assemble_attribute_globals(git_dir, attributes_source, self.attributes)?
@@ -310,6 +368,8 @@ impl Cache {
             destination_is_initially_empty: false,
             overwrite_existing: false,
             keep_going: false,
+            dry_run: false,
+            skip_filters: false,
             stat_options: self.stat_options().map_err(|err| match err {
                 config::stat_options::Error::ConfigCheckStat(err) => {
                     config::checkout_options::Error::ConfigCheckStat(err)
@@ -437,6 +437,10 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Core::SHALLOW_FILE;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Core::INDEX_FILE;
+                    (env(key), key.name)
+                },
                 {
                     let key = &gitoxide::Core::REFS_NAMESPACE;
                     (env(key), key.name)
@@ -505,6 +509,14 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Objects::CACHE_LIMIT;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Objects::DIRECTORY;
+                    (env(key), key.name)
+                },
+                {
+                    let key = &gitoxide::Objects::ALTERNATE_DIRECTORIES;
+                    (env(key), key.name)
+                },
             ],
         ),
         (
@@ -539,6 +551,15 @@ fn apply_environment_overrides(
                 },
             ],
         ),
+        (
+            "sequence",
+            None,
+            git_prefix,
+            &[{
+                let key = &config::tree::Sequence::EDITOR;
+                (env(key), key.name)
+            }],
+        ),
         (
             "ssh",
             None,
@@ -548,6 +569,15 @@ fn apply_environment_overrides(
                 (env(key), key.name)
             }],
         ),
+        (
+            "init",
+            None,
+            git_prefix,
+            &[{
+                let key = &config::tree::Init::TEMPLATE_DIR;
+                (env(key), key.name)
+            }],
+        ),
         #[cfg(feature = "blob-diff")]
         (
             "diff",
@@ -595,6 +625,14 @@ fn apply_environment_overrides(
                 let key = &Core::USE_REPLACE_REFS;
                 (env(key), key.name, objects)
             },
+            {
+                let key = &Core::EDITOR;
+                (env(key), key.name, git_prefix)
+            },
+            {
+                let key = &Core::PAGER;
+                (env(key), key.name, git_prefix)
+            },
         ] {
             if let Some(value) = var_as_bstring(var, permission) {
                 section.push_with_comment(
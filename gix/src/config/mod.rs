@@ -77,6 +77,8 @@ pub enum Error {
     #[error(transparent)]
     ConfigTypedString(#[from] key::GenericErrorWithValue),
     #[error(transparent)]
+    CompressionLevel(#[from] key::GenericError),
+    #[error(transparent)]
     RefsNamespace(#[from] refs_namespace::Error),
     #[error("Cannot handle objects formatted as {:?}", .name)]
     UnsupportedObjectFormat { name: BString },
@@ -154,6 +156,20 @@ pub mod diff {
     }
 }
 
+///
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub mod network_retry {
+    /// The error produced when obtaining how often and when requests are retried after spurious network failures.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        MaxRetries(#[from] super::unsigned_integer::Error),
+        #[error(transparent)]
+        RetryDelay(#[from] super::duration::Error),
+    }
+}
+
 ///
 pub mod stat_options {
     /// The error produced when collecting stat information, and returned by [Repository::stat_options()](crate::Repository::stat_options()).
@@ -206,6 +222,19 @@ pub mod command_context {
     }
 }
 
+///
+#[cfg(feature = "attributes")]
+pub mod editor {
+    /// The error produced when obtaining the editor to use for editing messages,
+    /// obtained via [Repository::editor()](crate::Repository::editor()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Terminal is dumb, but neither core.editor, VISUAL nor EDITOR are set")]
+        DumbTerminal,
+    }
+}
+
 ///
 pub mod exclude_stack {
     use std::path::PathBuf;
@@ -258,8 +287,13 @@ pub mod ssh_connect_options {
     /// The error produced when obtaining ssh connection configuration.
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
-    #[error(transparent)]
-    pub struct Error(#[from] super::key::GenericErrorWithValue);
+    pub enum Error {
+        #[error(transparent)]
+        Variant(#[from] super::key::GenericErrorWithValue),
+        #[cfg(feature = "credentials")]
+        #[error("Could not obtain the askpass program to use for ssh")]
+        Askpass(#[from] super::credential_helpers::Error),
+    }
 }
 
 ///
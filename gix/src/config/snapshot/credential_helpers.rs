@@ -139,23 +139,7 @@ impl Snapshot<'_> {
             }
         }
 
-        let allow_git_env = self.repo.options.permissions.env.git_prefix.is_allowed();
-        let allow_ssh_env = self.repo.options.permissions.env.ssh_prefix.is_allowed();
-        let prompt_options = gix_prompt::Options {
-            askpass: self
-                .trusted_path(Core::ASKPASS.logical_name().as_str())
-                .transpose()
-                .ignore_empty()?
-                .map(|c| Cow::Owned(c.into_owned())),
-            mode: self
-                .try_boolean(Credentials::TERMINAL_PROMPT.logical_name().as_str())
-                .map(|val| Credentials::TERMINAL_PROMPT.enrich_error(val))
-                .transpose()
-                .with_leniency(self.repo.config.lenient_config)?
-                .and_then(|val| (!val).then_some(gix_prompt::Mode::Disable))
-                .unwrap_or_default(),
-        }
-        .apply_environment(allow_git_env, allow_ssh_env, false /* terminal prompt */);
+        let prompt_options = self.prompt_options()?;
         Ok((
             gix_credentials::helper::Cascade {
                 programs,
@@ -173,6 +157,31 @@ impl Snapshot<'_> {
             prompt_options,
         ))
     }
+
+    /// Return the options to use when prompting the user for credentials or passphrases.
+    ///
+    /// The `askpass` program is obtained from `GIT_ASKPASS`, `core.askPass` or `SSH_ASKPASS`, in that order, as permitted by
+    /// the environment permissions of the repository. Terminal prompts are disabled if `GIT_TERMINAL_PROMPT` is false.
+    pub fn prompt_options(&self) -> Result<gix_prompt::Options<'static>, Error> {
+        let allow_git_env = self.repo.options.permissions.env.git_prefix.is_allowed();
+        let allow_ssh_env = self.repo.options.permissions.env.ssh_prefix.is_allowed();
+        Ok(gix_prompt::Options {
+            askpass: self
+                .trusted_path(Core::ASKPASS.logical_name().as_str())
+                .transpose()
+                .ignore_empty()?
+                .map(|c| Cow::Owned(c.into_owned())),
+            mode: self
+                .try_boolean(Credentials::TERMINAL_PROMPT.logical_name().as_str())
+                .map(|val| Credentials::TERMINAL_PROMPT.enrich_error(val))
+                .transpose()
+                .with_leniency(self.repo.config.lenient_config)?
+                .and_then(|val| (!val).then_some(gix_prompt::Mode::Disable))
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+        .apply_environment(allow_git_env, allow_ssh_env, false /* terminal prompt */))
+    }
 }
 
 fn host_matches(pattern: Option<&str>, host: Option<&str>) -> bool {
@@ -152,6 +152,9 @@ pub type Time = Any<validate::Time>;
 /// The `core.(filesRefLockTimeout|packedRefsTimeout)` keys, or any other lock timeout for that matter.
 pub type LockTimeout = Any<validate::LockTimeout>;
 
+/// The `core.compression` key, or any other zlib compression level for that matter.
+pub type Compression = Any<validate::Compression>;
+
 /// Keys specifying durations in milliseconds.
 pub type DurationInMilliseconds = Any<validate::DurationInMilliseconds>;
 
@@ -246,6 +249,33 @@ mod lock_timeout {
     }
 }
 
+mod compression {
+    use crate::{
+        config,
+        config::tree::{keys::Compression, Section},
+    };
+
+    impl Compression {
+        /// Create a new instance.
+        pub const fn new_compression(name: &'static str, section: &'static dyn Section) -> Self {
+            Self::new_with_validate(name, section, super::validate::Compression)
+        }
+
+        /// Return the zlib compression level from 0 (none) to 9 (best), with -1 meaning zlib's default level.
+        pub fn try_into_compression_level(
+            &'static self,
+            value: Result<i64, gix_config::value::Error>,
+        ) -> Result<u32, config::key::GenericError> {
+            let value = value.map_err(|err| config::key::GenericError::from(self).with_source(err))?;
+            Ok(match value {
+                -1 => gix_features::zlib::stream::deflate::LEVEL_DEFAULT,
+                0..=9 => value as u32,
+                _ => return Err(config::key::GenericError::from(self)),
+            })
+        }
+    }
+}
+
 mod refspecs {
     use crate::config::tree::{
         keys::{validate, FetchRefSpec, PushRefSpec},
@@ -599,6 +629,18 @@ pub mod validate {
         }
     }
 
+    /// Zlib compression levels from -1 to 9.
+    pub struct Compression;
+    impl Validate for Compression {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            let value = gix_config::Integer::try_from(value)?
+                .to_decimal()
+                .ok_or_else(|| format!("integer {value} cannot be represented as integer"));
+            super::super::Core::COMPRESSION.try_into_compression_level(Ok(value?))?;
+            Ok(())
+        }
+    }
+
     /// Durations in milliseconds.
     pub struct DurationInMilliseconds;
     impl Validate for DurationInMilliseconds {
@@ -37,6 +37,8 @@ pub(crate) mod root {
         pub const EXTENSIONS: sections::Extensions = sections::Extensions;
         /// The `fetch` section.
         pub const FETCH: sections::Fetch = sections::Fetch;
+        /// The `gc` section.
+        pub const GC: sections::Gc = sections::Gc;
         /// The `gitoxide` section.
         pub const GITOXIDE: sections::Gitoxide = sections::Gitoxide;
         /// The `http` section.
@@ -47,20 +49,30 @@ pub(crate) mod root {
         pub const INIT: sections::Init = sections::Init;
         /// The `mailmap` section.
         pub const MAILMAP: sections::Mailmap = sections::Mailmap;
+        /// The `merge` section.
+        pub const MERGE: sections::Merge = sections::Merge;
         /// The `pack` section.
         pub const PACK: sections::Pack = sections::Pack;
+        /// The `promisor` section.
+        pub const PROMISOR: sections::Promisor = sections::Promisor;
         /// The `protocol` section.
         pub const PROTOCOL: sections::Protocol = sections::Protocol;
         /// The `remote` section.
         pub const REMOTE: sections::Remote = sections::Remote;
         /// The `safe` section.
         pub const SAFE: sections::Safe = sections::Safe;
+        /// The `sequence` section.
+        pub const SEQUENCE: sections::Sequence = sections::Sequence;
         /// The `ssh` section.
         pub const SSH: sections::Ssh = sections::Ssh;
+        /// The `transfer` section.
+        pub const TRANSFER: sections::Transfer = sections::Transfer;
         /// The `user` section.
         pub const USER: sections::User = sections::User;
         /// The `url` section.
         pub const URL: sections::Url = sections::Url;
+        /// The `versionsort` section.
+        pub const VERSION_SORT: sections::VersionSort = sections::VersionSort;
 
         /// List all available sections.
         pub fn sections(&self) -> &[&dyn Section] {
@@ -76,18 +88,24 @@ pub(crate) mod root {
                 &Self::DIFF,
                 &Self::EXTENSIONS,
                 &Self::FETCH,
+                &Self::GC,
                 &Self::GITOXIDE,
                 &Self::HTTP,
                 &Self::INDEX,
                 &Self::INIT,
                 &Self::MAILMAP,
+                &Self::MERGE,
                 &Self::PACK,
+                &Self::PROMISOR,
                 &Self::PROTOCOL,
                 &Self::REMOTE,
                 &Self::SAFE,
+                &Self::SEQUENCE,
                 &Self::SSH,
+                &Self::TRANSFER,
                 &Self::USER,
                 &Self::URL,
+                &Self::VERSION_SORT,
             ]
         }
     }
@@ -96,8 +114,8 @@ pub(crate) mod root {
 mod sections;
 pub use sections::{
     branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init, Mailmap,
-    Pack, Protocol, Remote, Safe, Ssh, Url, User,
+    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gc, Gitoxide, Http, Index, Init, Mailmap,
+    Merge, Pack, Promisor, Protocol, Remote, Safe, Sequence, Ssh, Transfer, Url, User, VersionSort,
 };
 #[cfg(feature = "blob-diff")]
 pub use sections::{diff, Diff};
@@ -14,6 +14,9 @@ impl Core {
     /// The `core.checkStat` key.
     pub const CHECK_STAT: CheckStat =
         CheckStat::new_with_validate("checkStat", &config::Tree::CORE, validate::CheckStat);
+    /// The `core.compression` key.
+    pub const COMPRESSION: keys::Compression = keys::Compression::new_compression("compression", &config::Tree::CORE)
+        .with_note("it's the fallback for `core.looseCompression` and `pack.compression`");
     /// The `core.deltaBaseCacheLimit` key.
     pub const DELTA_BASE_CACHE_LIMIT: keys::UnsignedInteger =
         keys::UnsignedInteger::new_unsigned_integer("deltaBaseCacheLimit", &config::Tree::CORE)
@@ -32,19 +35,34 @@ impl Core {
     /// The `core.packedRefsTimeout` key.
     pub const PACKED_REFS_TIMEOUT: keys::LockTimeout =
         keys::LockTimeout::new_lock_timeout("packedRefsTimeout", &config::Tree::CORE);
+    /// The `core.looseCompression` key.
+    pub const LOOSE_COMPRESSION: keys::Compression =
+        keys::Compression::new_compression("looseCompression", &config::Tree::CORE);
     /// The `core.multiPackIndex` key.
     pub const MULTIPACK_INDEX: keys::Boolean = keys::Boolean::new_boolean("multiPackIndex", &config::Tree::CORE);
     /// The `core.logAllRefUpdates` key.
     pub const LOG_ALL_REF_UPDATES: LogAllRefUpdates =
         LogAllRefUpdates::new_with_validate("logAllRefUpdates", &config::Tree::CORE, validate::LogAllRefUpdates);
+    /// The `core.longpaths` key.
+    pub const LONG_PATHS: keys::Boolean = keys::Boolean::new_boolean("longpaths", &config::Tree::CORE)
+        .with_note("only has an effect on Windows, where it is used when checking out files");
     /// The `core.precomposeUnicode` key.
     ///
     /// Needs application to use [`env::args_os`][crate::env::args_os()] to conform all input paths before they are used.
     pub const PRECOMPOSE_UNICODE: keys::Boolean = keys::Boolean::new_boolean("precomposeUnicode", &config::Tree::CORE)
         .with_note("application needs to conform all program input by using gix::env::args_os()");
+    /// The `core.protectNTFS` key.
+    pub const PROTECT_NTFS: keys::Boolean = keys::Boolean::new_boolean("protectNTFS", &config::Tree::CORE)
+        .with_note("only refuses to check out reserved names like `NUL` on Windows");
     /// The `core.repositoryFormatVersion` key.
     pub const REPOSITORY_FORMAT_VERSION: keys::UnsignedInteger =
         keys::UnsignedInteger::new_unsigned_integer("repositoryFormatVersion", &config::Tree::CORE);
+    /// The `core.sparseCheckout` key.
+    pub const SPARSE_CHECKOUT: keys::Boolean = keys::Boolean::new_boolean("sparseCheckout", &config::Tree::CORE);
+    /// The `core.sparseCheckoutCone` key.
+    pub const SPARSE_CHECKOUT_CONE: keys::Boolean =
+        keys::Boolean::new_boolean("sparseCheckoutCone", &config::Tree::CORE)
+            .with_note("patterns that aren't in cone mode are interpreted in non-cone mode, just like `git` does");
     /// The `core.symlinks` key.
     pub const SYMLINKS: keys::Boolean = keys::Boolean::new_boolean("symlinks", &config::Tree::CORE);
     /// The `core.trustCTime` key.
@@ -71,6 +89,14 @@ impl Core {
         .with_environment_override("GIT_NO_REPLACE_OBJECTS");
     /// The `core.commitGraph` key.
     pub const COMMIT_GRAPH: keys::Boolean = keys::Boolean::new_boolean("commitGraph", &config::Tree::CORE);
+    /// The `core.editor` key.
+    pub const EDITOR: keys::Program = keys::Program::new_program("editor", &config::Tree::CORE)
+        .with_environment_override("GIT_EDITOR")
+        .with_note("fallbacks are 'VISUAL' (unless 'TERM' is 'dumb') and 'EDITOR', and finally 'vi'");
+    /// The `core.pager` key.
+    pub const PAGER: keys::Program = keys::Program::new_program("pager", &config::Tree::CORE)
+        .with_environment_override("GIT_PAGER")
+        .with_note("fallbacks are 'PAGER' and finally 'less'");
     /// The `core.safecrlf` key.
     #[cfg(feature = "attributes")]
     pub const SAFE_CRLF: SafeCrlf = SafeCrlf::new_with_validate("safecrlf", &config::Tree::CORE, validate::SafeCrlf);
@@ -100,16 +126,22 @@ impl Section for Core {
             &Self::BARE,
             &Self::BIG_FILE_THRESHOLD,
             &Self::CHECK_STAT,
+            &Self::COMPRESSION,
             &Self::DELTA_BASE_CACHE_LIMIT,
             &Self::DISAMBIGUATE,
             &Self::FILE_MODE,
             &Self::IGNORE_CASE,
             &Self::FILES_REF_LOCK_TIMEOUT,
             &Self::PACKED_REFS_TIMEOUT,
+            &Self::LOOSE_COMPRESSION,
             &Self::MULTIPACK_INDEX,
             &Self::LOG_ALL_REF_UPDATES,
+            &Self::LONG_PATHS,
             &Self::PRECOMPOSE_UNICODE,
+            &Self::PROTECT_NTFS,
             &Self::REPOSITORY_FORMAT_VERSION,
+            &Self::SPARSE_CHECKOUT,
+            &Self::SPARSE_CHECKOUT_CONE,
             &Self::SYMLINKS,
             &Self::TRUST_C_TIME,
             &Self::WORKTREE,
@@ -119,6 +151,8 @@ impl Section for Core {
             &Self::SSH_COMMAND,
             &Self::USE_REPLACE_REFS,
             &Self::COMMIT_GRAPH,
+            &Self::EDITOR,
+            &Self::PAGER,
             #[cfg(feature = "attributes")]
             &Self::SAFE_CRLF,
             #[cfg(feature = "attributes")]
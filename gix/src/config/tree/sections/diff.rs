@@ -28,6 +28,9 @@ impl Diff {
     pub const DRIVER_ALGORITHM: Algorithm =
         Algorithm::new_with_validate("algorithm", &config::Tree::DIFF, validate::Algorithm)
             .with_subsection_requirement(Some(SubSectionRequirement::Parameter("driver")));
+    /// The `diff.<driver>.wordRegex` key.
+    pub const DRIVER_WORD_REGEX: keys::String = keys::String::new_string("wordRegex", &config::Tree::DIFF)
+        .with_subsection_requirement(Some(SubSectionRequirement::Parameter("driver")));
     /// The `diff.<driver>.binary` key.
     pub const DRIVER_BINARY: Binary = Binary::new_with_validate("binary", &config::Tree::DIFF, validate::Binary)
         .with_subsection_requirement(Some(SubSectionRequirement::Parameter("driver")));
@@ -51,6 +54,7 @@ impl Section for Diff {
             &Self::DRIVER_TEXTCONV,
             &Self::DRIVER_ALGORITHM,
             &Self::DRIVER_BINARY,
+            &Self::DRIVER_WORD_REGEX,
             &Self::EXTERNAL,
         ]
     }
@@ -14,6 +14,10 @@ impl Fetch {
     #[cfg(feature = "attributes")]
     pub const RECURSE_SUBMODULES: RecurseSubmodules =
         RecurseSubmodules::new_with_validate("recurseSubmodules", &config::Tree::FETCH, validate::RecurseSubmodules);
+    /// The `fetch.unpackLimit` key.
+    pub const UNPACK_LIMIT: UnpackLimit =
+        UnpackLimit::new_with_validate("unpackLimit", &config::Tree::FETCH, validate::UnpackLimit)
+            .with_note("falls back to `transfer.unpackLimit`");
 }
 
 impl Section for Fetch {
@@ -26,6 +30,7 @@ impl Section for Fetch {
             &Self::NEGOTIATION_ALGORITHM,
             #[cfg(feature = "attributes")]
             &Self::RECURSE_SUBMODULES,
+            &Self::UNPACK_LIMIT,
         ]
     }
 }
@@ -37,6 +42,9 @@ pub type NegotiationAlgorithm = keys::Any<validate::NegotiationAlgorithm>;
 #[cfg(feature = "attributes")]
 pub type RecurseSubmodules = keys::Any<validate::RecurseSubmodules>;
 
+/// The `fetch.unpackLimit` and `transfer.unpackLimit` keys.
+pub type UnpackLimit = keys::Any<validate::UnpackLimit>;
+
 mod algorithm {
     #[cfg(feature = "credentials")]
     impl crate::config::tree::sections::fetch::NegotiationAlgorithm {
@@ -61,6 +69,27 @@ mod algorithm {
         }
     }
 
+    impl crate::config::tree::sections::fetch::UnpackLimit {
+        /// Obtain the amount of objects below which received packs should be exploded into loose objects,
+        /// or `None` if the value is negative, which is equivalent to the key not being set.
+        pub fn try_into_unpack_limit(
+            &'static self,
+            value: Result<i64, gix_config::value::Error>,
+        ) -> Result<Option<u32>, crate::config::unsigned_integer::Error> {
+            match value {
+                Ok(value) if value < 0 => Ok(None),
+                value => value
+                    .map_err(|err| crate::config::unsigned_integer::Error::from(self).with_source(err))
+                    .and_then(|value| {
+                        value
+                            .try_into()
+                            .map(Some)
+                            .map_err(|_| crate::config::unsigned_integer::Error::from(self))
+                    }),
+            }
+        }
+    }
+
     #[cfg(feature = "attributes")]
     impl crate::config::tree::sections::fetch::RecurseSubmodules {
         /// Obtain the way submodules should be updated.
@@ -74,7 +103,7 @@ mod algorithm {
     }
 }
 
-mod validate {
+pub(super) mod validate {
     use crate::{bstr::BStr, config::tree::keys};
 
     pub struct NegotiationAlgorithm;
@@ -87,6 +116,17 @@ mod validate {
         }
     }
 
+    pub struct UnpackLimit;
+    impl keys::Validate for UnpackLimit {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            let value = gix_config::Integer::try_from(value)?
+                .to_decimal()
+                .ok_or_else(|| format!("integer {value} cannot be represented as `i64`"))?;
+            crate::config::tree::Fetch::UNPACK_LIMIT.try_into_unpack_limit(Ok(value))?;
+            Ok(())
+        }
+    }
+
     pub struct RecurseSubmodules;
     impl keys::Validate for RecurseSubmodules {
         #[cfg_attr(not(feature = "attributes"), allow(unused_variables))]
@@ -0,0 +1,24 @@
+use crate::config::{
+    tree::{keys, Gc, Key, Section},
+    Tree,
+};
+
+impl Gc {
+    /// The `gc.auto` key.
+    pub const AUTO: keys::UnsignedInteger = keys::UnsignedInteger::new_unsigned_integer("auto", &Tree::GC);
+    /// The `gc.autoPackLimit` key.
+    pub const AUTO_PACK_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("autoPackLimit", &Tree::GC);
+    /// The `gc.autoDetach` key.
+    pub const AUTO_DETACH: keys::Boolean = keys::Boolean::new_boolean("autoDetach", &Tree::GC);
+}
+
+impl Section for Gc {
+    fn name(&self) -> &str {
+        "gc"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::AUTO, &Self::AUTO_PACK_LIMIT, &Self::AUTO_DETACH]
+    }
+}
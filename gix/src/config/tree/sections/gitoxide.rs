@@ -110,6 +110,13 @@ mod subsections {
                 "relative file paths will always be made relative to the git-common-dir, whereas `git` keeps them as is.",
             );
 
+        /// The `gitoxide.core.indexFile` key.
+        pub const INDEX_FILE: keys::Path = keys::Path::new_path("indexFile", &Gitoxide::CORE)
+            .with_environment_override("GIT_INDEX_FILE")
+            .with_note(
+                "Relative paths are relative to the current working directory, and `$GIT_DIR/index` is used if unset",
+            );
+
         /// The `gitoxide.core.filterProcessDelay` key (default `true`).
         ///
         /// It controls whether or not long running filter driver processes can use the 'delay' capability.
@@ -142,6 +149,7 @@ mod subsections {
                 &Self::USE_NSEC,
                 &Self::USE_STDEV,
                 &Self::SHALLOW_FILE,
+                &Self::INDEX_FILE,
                 &Self::FILTER_PROCESS_DELAY,
                 &Self::EXTERNAL_COMMAND_STDERR,
                 &Self::REFS_NAMESPACE,
@@ -180,6 +188,25 @@ mod subsections {
             keys::DurationInMilliseconds::new_duration("connectTimeout", &Gitoxide::HTTP).with_note(
                 "entirely new, and in milliseconds, to describe how long to wait until a connection attempt is aborted",
             );
+        /// The `gitoxide.http.maxRetries` key.
+        ///
+        /// The amount of times a request over a stateless transport like HTTP is retried if it failed due to a spurious error.
+        pub const MAX_RETRIES: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("maxRetries", &Gitoxide::HTTP)
+                .with_note("entirely new, and defaults to 3. Set it to 0 to disable retries. Broken pack downloads are restarted, not resumed");
+        /// The `gitoxide.http.retryDelay` key.
+        pub const RETRY_DELAY: keys::DurationInMilliseconds =
+            keys::DurationInMilliseconds::new_duration("retryDelay", &Gitoxide::HTTP).with_note(
+                "entirely new, and in milliseconds, to describe how long to wait before the first retry, doubling with each subsequent one. Defaults to 1000",
+            );
+        /// The `gitoxide.http.maxReceiveSpeed` key.
+        pub const MAX_RECEIVE_SPEED: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("maxReceiveSpeed", &Gitoxide::HTTP)
+                .with_note("entirely new, the maximum amount of bytes per second to receive, or 0 to not limit it");
+        /// The `gitoxide.http.maxSendSpeed` key.
+        pub const MAX_SEND_SPEED: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("maxSendSpeed", &Gitoxide::HTTP)
+                .with_note("entirely new, the maximum amount of bytes per second to send, or 0 to not limit it");
         /// The `gitoxide.http.sslVersionMin` key.
         pub const SSL_VERSION_MIN: http::SslVersion =
             http::SslVersion::new_ssl_version("sslVersionMin", &Gitoxide::HTTP).with_note(
@@ -216,6 +243,10 @@ mod subsections {
                 &Self::VERBOSE,
                 &Self::NO_PROXY,
                 &Self::CONNECT_TIMEOUT,
+                &Self::MAX_RETRIES,
+                &Self::RETRY_DELAY,
+                &Self::MAX_RECEIVE_SPEED,
+                &Self::MAX_SEND_SPEED,
                 &Self::SSL_VERSION_MIN,
                 &Self::SSL_VERSION_MAX,
                 &Self::SSL_NO_VERIFY,
@@ -421,11 +452,24 @@ mod subsections {
             keys::UnsignedInteger::new_unsigned_integer("cacheLimit", &Gitoxide::OBJECTS)
                 .with_note("If unset or 0, there is no object cache")
                 .with_environment_override("GIX_OBJECT_CACHE_MEMORY");
+        /// The `gitoxide.objects.packResolveMemoryLimit` key.
+        pub const PACK_RESOLVE_MEMORY_LIMIT: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("packResolveMemoryLimit", &Gitoxide::OBJECTS).with_note(
+                "If unset or 0, received packs are resolved in memory. Otherwise resolved base objects beyond this amount of bytes are written to a temporary file",
+            );
         /// The `gitoxide.objects.noReplace` key.
         pub const NO_REPLACE: keys::Boolean = keys::Boolean::new_boolean("noReplace", &Gitoxide::OBJECTS);
         /// The `gitoxide.objects.replaceRefBase` key.
         pub const REPLACE_REF_BASE: keys::Any =
             keys::Any::new("replaceRefBase", &Gitoxide::OBJECTS).with_environment_override("GIT_REPLACE_REF_BASE");
+        /// The `gitoxide.objects.directory` key.
+        pub const DIRECTORY: keys::Path = keys::Path::new_path("directory", &Gitoxide::OBJECTS)
+            .with_environment_override("GIT_OBJECT_DIRECTORY")
+            .with_note("Relative paths are relative to the current working directory, and `$GIT_COMMON_DIR/objects` is used if unset");
+        /// The `gitoxide.objects.alternateDirectories` key.
+        pub const ALTERNATE_DIRECTORIES: keys::Any = keys::Any::new("alternateDirectories", &Gitoxide::OBJECTS)
+            .with_environment_override("GIT_ALTERNATE_OBJECT_DIRECTORIES")
+            .with_note("A list of object directories separated by `:`, or `;` on Windows, which are used in addition to `info/alternates`");
     }
 
     impl Section for Objects {
@@ -434,7 +478,13 @@ mod subsections {
         }
 
         fn keys(&self) -> &[&dyn Key] {
-            &[&Self::CACHE_LIMIT, &Self::REPLACE_REF_BASE]
+            &[
+                &Self::CACHE_LIMIT,
+                &Self::PACK_RESOLVE_MEMORY_LIMIT,
+                &Self::REPLACE_REF_BASE,
+                &Self::DIRECTORY,
+                &Self::ALTERNATE_DIRECTORIES,
+            ]
         }
 
         fn parent(&self) -> Option<&dyn Section> {
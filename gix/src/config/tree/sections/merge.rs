@@ -0,0 +1,23 @@
+use crate::config::{
+    tree::{keys, Key, Merge, Section},
+    Tree,
+};
+
+impl Merge {
+    /// The `merge.log` key.
+    pub const LOG: keys::Any = keys::Any::new("log", &Tree::MERGE)
+        .with_note("a boolean, or the maximum amount of commit summaries to list in merge messages");
+    /// The `merge.suppressDest` key.
+    pub const SUPPRESS_DEST: keys::Any = keys::Any::new("suppressDest", &Tree::MERGE)
+        .with_note("multi-valued, with each value being a glob pattern, and an empty value clearing all previous ones");
+}
+
+impl Section for Merge {
+    fn name(&self) -> &str {
+        "merge"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::LOG, &Self::SUPPRESS_DEST]
+    }
+}
@@ -52,6 +52,11 @@ pub mod extensions;
 pub struct Fetch;
 pub mod fetch;
 
+/// The `gc` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Gc;
+mod gc;
+
 /// The `gitoxide` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Gitoxide;
@@ -76,11 +81,21 @@ mod init;
 pub struct Mailmap;
 mod mailmap;
 
+/// The `merge` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Merge;
+mod merge;
+
 /// The `pack` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Pack;
 pub mod pack;
 
+/// The `promisor` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Promisor;
+mod promisor;
+
 /// The `protocol` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Protocol;
@@ -96,11 +111,21 @@ pub mod remote;
 pub struct Safe;
 mod safe;
 
+/// The `sequence` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Sequence;
+mod sequence;
+
 /// The `ssh` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Ssh;
 pub mod ssh;
 
+/// The `transfer` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Transfer;
+mod transfer;
+
 /// The `user` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct User;
@@ -110,3 +135,8 @@ mod user;
 #[derive(Copy, Clone, Default)]
 pub struct Url;
 mod url;
+
+/// The `versionsort` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct VersionSort;
+mod versionsort;
@@ -4,6 +4,10 @@ use crate::{
 };
 
 impl Pack {
+    /// The `pack.compression` key.
+    pub const COMPRESSION: keys::Compression = keys::Compression::new_compression("compression", &config::Tree::PACK)
+        .with_deviation("Leaving this and `core.compression` unspecified uses level 3 instead of 6, which compresses nearly as well in a fraction of the time");
+
     /// The `pack.threads` key.
     pub const THREADS: keys::UnsignedInteger =
         keys::UnsignedInteger::new_unsigned_integer("threads", &config::Tree::PACK)
@@ -42,7 +46,7 @@ impl Section for Pack {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::THREADS, &Self::INDEX_VERSION]
+        &[&Self::COMPRESSION, &Self::THREADS, &Self::INDEX_VERSION]
     }
 }
 
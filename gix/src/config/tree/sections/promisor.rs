@@ -0,0 +1,19 @@
+use crate::config::{
+    tree::{keys, Key, Promisor, Section},
+    Tree,
+};
+
+impl Promisor {
+    /// The `promisor.quiet` key.
+    pub const QUIET: keys::Boolean = keys::Boolean::new_boolean("quiet", &Tree::PROMISOR);
+}
+
+impl Section for Promisor {
+    fn name(&self) -> &str {
+        "promisor"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::QUIET]
+    }
+}
@@ -23,6 +23,9 @@ impl Remote {
     /// The `remote.<name>.push` key
     pub const PUSH: keys::PushRefSpec =
         keys::PushRefSpec::new_push_refspec("push", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.mirror` key
+    pub const MIRROR: keys::Boolean =
+        keys::Boolean::new_boolean("mirror", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
     /// The `remote.<name>.proxy` key
     pub const PROXY: keys::String =
         keys::String::new_string("proxy", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
@@ -31,6 +34,17 @@ impl Remote {
         http::ProxyAuthMethod::new_proxy_auth_method("proxyAuthMethod", &config::Tree::REMOTE)
             .with_subsection_requirement(NAME_PARAMETER)
             .with_deviation("implemented like git, but never actually tried");
+    /// The `remote.<name>.promisor` key.
+    pub const PROMISOR: keys::Boolean =
+        keys::Boolean::new_boolean("promisor", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.partialCloneFilter` key.
+    pub const PARTIAL_CLONE_FILTER: keys::String =
+        keys::String::new_string("partialCloneFilter", &config::Tree::REMOTE)
+            .with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.disabledCapabilities` key.
+    pub const DISABLED_CAPABILITIES: keys::Any = keys::Any::new("disabledCapabilities", &config::Tree::REMOTE)
+        .with_subsection_requirement(NAME_PARAMETER)
+        .with_note("gitoxide-specific: a capability, like `sideband-all`, that is never requested when fetching from this remote. Can be specified multiple times.");
 }
 
 impl Section for Remote {
@@ -46,8 +60,12 @@ impl Section for Remote {
             &Self::PUSH_URL,
             &Self::FETCH,
             &Self::PUSH,
+            &Self::MIRROR,
             &Self::PROXY,
             &Self::PROXY_AUTH_METHOD,
+            &Self::PROMISOR,
+            &Self::PARTIAL_CLONE_FILTER,
+            &Self::DISABLED_CAPABILITIES,
         ]
     }
 }
@@ -0,0 +1,21 @@
+use crate::config::{
+    tree::{keys, Key, Section, Sequence},
+    Tree,
+};
+
+impl Sequence {
+    /// The `sequence.editor` key.
+    pub const EDITOR: keys::Program = keys::Program::new_program("editor", &Tree::SEQUENCE)
+        .with_environment_override("GIT_SEQUENCE_EDITOR")
+        .with_note("falls back to the editor configured in `core.editor` and its fallbacks");
+}
+
+impl Section for Sequence {
+    fn name(&self) -> &str {
+        "sequence"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::EDITOR]
+    }
+}
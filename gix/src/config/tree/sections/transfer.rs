@@ -0,0 +1,20 @@
+use crate::config::{
+    tree::{fetch, keys, Key, Section, Transfer},
+    Tree,
+};
+
+impl Transfer {
+    /// The `transfer.unpackLimit` key.
+    pub const UNPACK_LIMIT: fetch::UnpackLimit =
+        keys::Any::new_with_validate("unpackLimit", &Tree::TRANSFER, super::fetch::validate::UnpackLimit);
+}
+
+impl Section for Transfer {
+    fn name(&self) -> &str {
+        "transfer"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::UNPACK_LIMIT]
+    }
+}
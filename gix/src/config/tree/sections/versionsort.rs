@@ -0,0 +1,23 @@
+use crate::config::{
+    tree::{keys, Key, Section, VersionSort},
+    Tree,
+};
+
+impl VersionSort {
+    /// The `versionsort.suffix` key.
+    pub const SUFFIX: keys::Any = keys::Any::new("suffix", &Tree::VERSION_SORT)
+        .with_note("multi-valued, with each value being a suffix that sorts before the version it is attached to");
+    /// The `versionsort.prereleaseSuffix` key.
+    pub const PRERELEASE_SUFFIX: keys::Any = keys::Any::new("prereleaseSuffix", &Tree::VERSION_SORT)
+        .with_note("deprecated, and ignored if `versionsort.suffix` is set");
+}
+
+impl Section for VersionSort {
+    fn name(&self) -> &str {
+        "versionsort"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::SUFFIX, &Self::PRERELEASE_SUFFIX]
+    }
+}
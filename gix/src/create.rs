@@ -25,6 +25,14 @@ pub enum Error {
     DirectoryNotEmpty { path: PathBuf },
     #[error("Could not create directory at '{}'", .path.display())]
     CreateDirectory { source: std::io::Error, path: PathBuf },
+    #[error("Could not read the template directory at '{}'", .path.display())]
+    ReadTemplateDirectory { source: std::io::Error, path: PathBuf },
+    #[error("Could not copy template file from '{}' to '{}'", .source_path.display(), .destination_path.display())]
+    CopyTemplateFile {
+        source: std::io::Error,
+        source_path: PathBuf,
+        destination_path: PathBuf,
+    },
 }
 
 /// The kind of repository to create.
@@ -109,7 +117,7 @@ fn create_dir(p: &Path) -> Result<(), Error> {
 }
 
 /// Options for use in [`into()`];
-#[derive(Copy, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct Options {
     /// If true, and the kind of repository to create has a worktree, then the destination directory must be empty.
     ///
@@ -118,6 +126,10 @@ pub struct Options {
     /// If set, use these filesystem capabilities to populate the respective git-config fields.
     /// If `None`, the directory will be probed.
     pub fs_capabilities: Option<gix_fs::Capabilities>,
+    /// If set, the content of this directory will be copied into the new `.git` directory, similar to `git init --template`.
+    ///
+    /// See [`apply_template()`] for details.
+    pub template_dir: Option<PathBuf>,
 }
 
 /// Create a new `.git` repository of `kind` within the possibly non-existing `directory`
@@ -132,6 +144,7 @@ pub fn into(
     Options {
         fs_capabilities,
         destination_must_be_empty,
+        template_dir,
     }: Options,
 ) -> Result<gix_discover::repository::Path, Error> {
     let mut dot_git = directory.into();
@@ -227,6 +240,10 @@ pub fn into(
         })?;
     }
 
+    if let Some(template_dir) = template_dir {
+        apply_template(&template_dir, &dot_git)?;
+    }
+
     Ok(gix_discover::repository::Path::from_dot_git_dir(
         dot_git,
         if bare {
@@ -239,6 +256,66 @@ pub fn into(
     .expect("by now the `dot_git` dir is valid as we have accessed it"))
 }
 
+/// Copy all files and directories in `template_dir` into `git_dir` recursively, overwriting existing files,
+/// and return `true` if the repository configuration was changed.
+///
+/// This is typically used to inject hooks or a custom `info/exclude` file into newly created repositories.
+/// A `HEAD` file in `template_dir` is ignored, and a `config` file is placed in front of the existing configuration
+/// so values of the repository configuration take precedence.
+/// Nothing happens if `template_dir` is empty or doesn't exist, just like in `git`.
+///
+/// # Deviation
+///
+/// `git` uses the template directory instead of its built-in templates, whereas we copy it on top of them.
+pub fn apply_template(template_dir: &Path, git_dir: &Path) -> Result<bool, Error> {
+    if template_dir.as_os_str().is_empty() || !template_dir.is_dir() {
+        return Ok(false);
+    }
+    let mut config_changed = false;
+    let mut stack = vec![PathBuf::new()];
+    while let Some(rela_dir) = stack.pop() {
+        let dir = template_dir.join(&rela_dir);
+        let entries = fs::read_dir(&dir).map_err(|err| Error::ReadTemplateDirectory {
+            source: err,
+            path: dir.clone(),
+        })?;
+        for entry in entries {
+            let entry = entry.map_err(|err| Error::ReadTemplateDirectory {
+                source: err,
+                path: dir.clone(),
+            })?;
+            let rela_path = rela_dir.join(entry.file_name());
+            let source_path = entry.path();
+            let destination_path = git_dir.join(&rela_path);
+            if source_path.is_dir() {
+                create_dir(&destination_path)?;
+                stack.push(rela_path);
+                continue;
+            }
+            if rela_path == Path::new("HEAD") {
+                continue;
+            }
+            let copy_error = |err| Error::CopyTemplateFile {
+                source: err,
+                source_path: source_path.clone(),
+                destination_path: destination_path.clone(),
+            };
+            if rela_path == Path::new("config") {
+                let mut config = fs::read(&source_path).map_err(copy_error)?;
+                if !config.ends_with(b"\n") {
+                    config.push(b'\n');
+                }
+                config.extend(fs::read(&destination_path).map_err(copy_error)?);
+                fs::write(&destination_path, config).map_err(copy_error)?;
+                config_changed = true;
+            } else {
+                fs::copy(&source_path, &destination_path).map_err(copy_error)?;
+            }
+        }
+    }
+    Ok(config_changed)
+}
+
 fn key(name: &'static str) -> section::Key<'static> {
     section::Key::try_from(name).expect("valid key name")
 }
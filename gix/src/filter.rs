@@ -107,6 +107,7 @@ impl<'repo> Pipeline<'repo> {
             encodings_with_roundtrip_check: encodings,
             crlf_roundtrip_check: safe_crlf,
             object_hash: repo.object_hash(),
+            lfs_resolver: None,
         })
     }
 
@@ -193,6 +194,12 @@ impl<'repo> Pipeline<'repo> {
     pub fn driver_context_mut(&mut self) -> &mut gix_filter::pipeline::Context {
         self.inner.driver_context_mut()
     }
+
+    /// Return the options of the underlying pipeline for adjustment, for example to set a
+    /// [`git-lfs` resolver](gix_filter::pipeline::Options::lfs_resolver).
+    pub fn options_mut(&mut self) -> &mut gix_filter::pipeline::Options {
+        self.inner.options_mut()
+    }
 }
 
 /// Obtain a list of all configured driver, but ignore those in sections that we don't trust enough.
@@ -0,0 +1,195 @@
+//! Operations on the commit history as a whole.
+
+///
+pub mod rewrite {
+    use gix_hash::ObjectId;
+    use gix_ref::transaction::RefEdit;
+
+    use crate::bstr::{BStr, BString};
+
+    /// Decide how each commit is rewritten by [`Repository::rewrite_history()`](crate::Repository::rewrite_history()).
+    ///
+    /// All methods have default implementations which keep everything as is, so implementors only override what they need.
+    pub trait Delegate {
+        /// Return `false` if the tree `entry` at `path`, like `dir/file.txt`, should be removed from all commits.
+        ///
+        /// Directories that are kept are visited recursively, and directories that end up empty are removed as well.
+        fn keep_entry(&mut self, path: &BStr, entry: &gix_object::tree::EntryRef<'_>) -> bool {
+            let _ = (path, entry);
+            true
+        }
+
+        /// Return a new message for `commit`, or `None` to keep its message.
+        fn rewrite_message(&mut self, commit: &gix_object::Commit) -> Option<BString> {
+            let _ = commit;
+            None
+        }
+
+        /// Return a replacement for `signature`, or `None` to keep it.
+        ///
+        /// It's called for the author and committer of each commit, as well as for the tagger of annotated tags.
+        fn rewrite_signature(&mut self, signature: &gix_actor::Signature) -> Option<gix_actor::Signature> {
+            let _ = signature;
+            None
+        }
+    }
+
+    /// Options for [`Repository::rewrite_history()`](crate::Repository::rewrite_history()).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Options {
+        /// If set, remove all blobs that are larger than the given amount of bytes from all trees.
+        pub max_blob_size: Option<u64>,
+        /// If `true`, commits that become empty as their tree doesn't differ from the one of their only parent
+        /// anymore are removed, and their children are attached to their parent instead.
+        ///
+        /// Commits that were empty to begin with are always kept.
+        pub prune_empty_commits: bool,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                max_blob_size: None,
+                prune_empty_commits: true,
+            }
+        }
+    }
+
+    /// The outcome of [`Repository::rewrite_history()`](crate::Repository::rewrite_history()).
+    #[derive(Debug, Clone)]
+    pub struct Outcome {
+        /// A mapping of all commits reachable from the rewritten references to their rewritten version, or to `None`
+        /// if the commit and all of its ancestors were removed.
+        ///
+        /// Commits that didn't change map to themselves, and removed commits map to their closest rewritten ancestor.
+        pub commits: gix_hashtable::HashMap<ObjectId, Option<ObjectId>>,
+        /// The edits that were applied to references whose target changed, with references being deleted if all
+        /// commits they were pointing to were removed.
+        pub ref_edits: Vec<RefEdit>,
+    }
+
+    /// The error returned by [`Repository::rewrite_history()`](crate::Repository::rewrite_history()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        FindHeader(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        FindObject(#[from] gix_object::find::existing_object::Error),
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error("Could not update the rewritten references")]
+        EditReferences(#[from] crate::reference::edit::Error),
+        #[error("The operation was interrupted")]
+        Interrupted,
+    }
+
+    /// The progress ids used in [`Repository::rewrite_history()`](crate::Repository::rewrite_history()).
+    ///
+    /// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+    #[derive(Debug, Copy, Clone)]
+    pub enum ProgressId {
+        /// The amount of commits that were rewritten.
+        RewriteCommits,
+    }
+
+    impl From<ProgressId> for gix_features::progress::Id {
+        fn from(v: ProgressId) -> Self {
+            match v {
+                ProgressId::RewriteCommits => *b"HRWC",
+            }
+        }
+    }
+}
+
+///
+#[cfg(all(feature = "mailmap", feature = "blob-diff"))]
+pub mod authors {
+    use crate::bstr::BString;
+
+    /// Options for [`Repository::author_stats()`](crate::Repository::author_stats()).
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Options {
+        /// If `true`, diff each commit with its parent to learn how many files and lines each author changed.
+        ///
+        /// This is considerably more expensive than just counting commits. Merge commits are skipped as their changes
+        /// were already accounted for in the commits that were merged.
+        pub line_stats: bool,
+        /// The amount of threads to use for computing line statistics, or `None` to use all logical cores.
+        pub thread_limit: Option<usize>,
+    }
+
+    /// The contributions of a single author, after their identity was mapped with the [mailmap](crate::Repository::open_mailmap()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Author {
+        /// The name the author used in their most recent commit.
+        pub name: BString,
+        /// The email address of the author, which is what identifies them, ignoring its case.
+        pub email: BString,
+        /// The amount of commits the author made, including merge commits.
+        pub num_commits: usize,
+        /// The time of the oldest commit of the author, as recorded in the commit.
+        pub first_commit: gix_date::Time,
+        /// The time of the most recent commit of the author, as recorded in the commit.
+        pub last_commit: gix_date::Time,
+        /// The amount of distinct days the author made commits on, in the author's own timezone.
+        pub active_days: usize,
+        /// The sum of all changes the author made, which is only set if [line statistics](Options::line_stats) were requested.
+        pub lines: Option<gix_diff::stat::Summary>,
+    }
+
+    /// The outcome of [`Repository::author_stats()`](crate::Repository::author_stats()).
+    #[derive(Debug, Clone)]
+    pub struct Outcome {
+        /// All authors, with the ones that made the most commits first, and ordered by email if the amount of commits is equal.
+        pub authors: Vec<Author>,
+        /// The amount of commits that were traversed.
+        pub num_commits: usize,
+        /// The amount of merge commits that weren't diffed if [line statistics](Options::line_stats) were requested.
+        pub skipped_merge_commits: usize,
+    }
+
+    /// The error returned by [`Repository::author_stats()`](crate::Repository::author_stats()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Walk(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        Traverse(#[from] gix_traverse::commit::ancestors::Error),
+        #[error(transparent)]
+        FindCommit(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        PeelToTree(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        DiffInit(#[from] crate::diff::new_rewrites::Error),
+        #[error(transparent)]
+        DiffStats(#[from] crate::object::tree::diff::stats::Error),
+        #[error("The operation was interrupted")]
+        Interrupted,
+    }
+
+    /// The progress ids used in [`Repository::author_stats()`](crate::Repository::author_stats()).
+    ///
+    /// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+    #[derive(Debug, Copy, Clone)]
+    pub enum ProgressId {
+        /// The amount of commits that were traversed.
+        TraverseCommits,
+        /// The amount of commits that were diffed with their parent.
+        DiffCommits,
+    }
+
+    impl From<ProgressId> for gix_features::progress::Id {
+        fn from(v: ProgressId) -> Self {
+            match v {
+                ProgressId::TraverseCommits => *b"HATC",
+                ProgressId::DiffCommits => *b"HADC",
+            }
+        }
+    }
+}
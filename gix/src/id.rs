@@ -40,18 +40,14 @@ impl<'repo> Id<'repo> {
     }
 
     /// Turn this object id into a shortened id with a length in hex as configured by `core.abbrev`.
+    ///
+    /// Use [`Repository::shorten_ids()`](crate::Repository::shorten_ids()) to shorten many ids at once.
     pub fn shorten(&self) -> Result<gix_hash::Prefix, shorten::Error> {
-        let hex_len = self.repo.config.hex_len.map_or_else(
-            || self.repo.objects.packed_object_count().map(calculate_auto_hex_len),
-            Ok,
-        )?;
-
-        let prefix = gix_odb::store::prefix::disambiguate::Candidate::new(self.inner, hex_len)
-            .expect("BUG: internal hex-len must always be valid");
-        self.repo
-            .objects
-            .disambiguate_prefix(prefix)?
-            .ok_or(shorten::Error::NotFound { oid: self.inner })
+        Ok(self
+            .repo
+            .shorten_ids(Some(self.inner))?
+            .pop()
+            .expect("one prefix per id"))
     }
 
     /// Turn this object id into a shortened id with a length in hex as configured by `core.abbrev`, or default
@@ -61,7 +57,7 @@ impl<'repo> Id<'repo> {
     }
 }
 
-fn calculate_auto_hex_len(num_packed_objects: u64) -> usize {
+pub(crate) fn calculate_auto_hex_len(num_packed_objects: u64) -> usize {
     let mut len = 64 - num_packed_objects.leading_zeros();
     len = (len + 1) / 2;
     len.max(7) as usize
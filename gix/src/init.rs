@@ -1,14 +1,9 @@
 #![allow(clippy::result_large_err)]
-use std::{borrow::Cow, convert::TryInto, path::Path};
+use std::{borrow::Cow, path::Path};
 
 use gix_macros::momo;
-use gix_ref::{
-    store::WriteReflog,
-    transaction::{PreviousValue, RefEdit},
-    FullName, Target,
-};
 
-use crate::{bstr::BString, config::tree::Init, ThreadSafeRepository};
+use crate::{config::tree::Init, ThreadSafeRepository};
 
 /// The name of the branch to use if non is configured via git configuration.
 ///
@@ -27,13 +22,10 @@ pub enum Error {
     Init(#[from] crate::create::Error),
     #[error(transparent)]
     Open(#[from] crate::open::Error),
-    #[error("Invalid default branch name: {name:?}")]
-    InvalidBranchName {
-        name: BString,
-        source: gix_validate::reference::name::Error,
-    },
-    #[error("Could not edit HEAD reference with new default name")]
-    EditHeadForDefaultBranch(#[from] crate::reference::edit::Error),
+    #[error("Could not interpolate the path to the template directory")]
+    TemplateDirInterpolation(#[from] gix_config::path::interpolate::Error),
+    #[error("Could not point HEAD to the configured default branch")]
+    DefaultBranch(#[from] crate::reference::rename_unborn_head::Error),
 }
 
 impl ThreadSafeRepository {
@@ -58,6 +50,9 @@ impl ThreadSafeRepository {
     ///
     /// Instead of naming the default branch `master`, we name it `main` unless configured explicitly using the `init.defaultBranch`
     /// configuration key.
+    ///
+    /// If no [template directory](crate::create::Options::template_dir) is set, `init.templateDir` or the `GIT_TEMPLATE_DIR`
+    /// environment variable are used to find one, as far as `open_options` allow.
     #[momo]
     pub fn init_opts(
         directory: impl AsRef<Path>,
@@ -65,11 +60,25 @@ impl ThreadSafeRepository {
         create_options: crate::create::Options,
         mut open_options: crate::open::Options,
     ) -> Result<Self, Error> {
+        let has_template_dir = create_options.template_dir.is_some();
         let path = crate::create::into(directory.as_ref(), kind, create_options)?;
         let (git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
         open_options.git_dir_trust = Some(gix_sec::Trust::Full);
         open_options.current_dir = std::env::current_dir()?.into();
-        let repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+        let mut repo =
+            ThreadSafeRepository::open_from_paths(git_dir.clone(), worktree_dir.clone(), open_options.clone())?;
+
+        if !has_template_dir {
+            let template_dir = repo
+                .config
+                .trusted_file_path("init", None, Init::TEMPLATE_DIR.name)
+                .transpose()?;
+            if let Some(template_dir) = template_dir {
+                if crate::create::apply_template(&template_dir, &git_dir)? {
+                    repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+                }
+            }
+        }
 
         let branch_name = repo
             .config
@@ -77,26 +86,7 @@ impl ThreadSafeRepository {
             .string("init", None, Init::DEFAULT_BRANCH.name)
             .unwrap_or_else(|| Cow::Borrowed(DEFAULT_BRANCH_NAME.into()));
         if branch_name.as_ref() != DEFAULT_BRANCH_NAME {
-            let sym_ref: FullName =
-                format!("refs/heads/{branch_name}")
-                    .try_into()
-                    .map_err(|err| Error::InvalidBranchName {
-                        name: branch_name.into_owned(),
-                        source: err,
-                    })?;
-            let mut repo = repo.to_thread_local();
-            let prev_write_reflog = repo.refs.write_reflog;
-            repo.refs.write_reflog = WriteReflog::Disable;
-            repo.edit_reference(RefEdit {
-                change: gix_ref::transaction::Change::Update {
-                    log: Default::default(),
-                    expected: PreviousValue::Any,
-                    new: Target::Symbolic(sym_ref),
-                },
-                name: "HEAD".try_into().expect("valid"),
-                deref: false,
-            })?;
-            repo.refs.write_reflog = prev_write_reflog;
+            repo.to_thread_local().rename_unborn_head(branch_name.as_ref())?;
         }
 
         Ok(repo)
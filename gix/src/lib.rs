@@ -95,6 +95,8 @@
 pub use gix_actor as actor;
 #[cfg(feature = "attributes")]
 pub use gix_attributes as attrs;
+#[cfg(feature = "attributes")]
+pub use gix_command as command;
 pub use gix_commitgraph as commitgraph;
 #[cfg(feature = "credentials")]
 pub use gix_credentials as credentials;
@@ -154,8 +156,9 @@ pub mod path;
 
 /// The standard type for a store to handle git references.
 pub type RefStore = gix_ref::file::Store;
-/// A handle for finding objects in an object database, abstracting away caches for thread-local use.
-pub type OdbHandle = gix_odb::Handle;
+/// A handle for finding objects in an object database, abstracting away caches for thread-local use,
+/// which can optionally keep written objects in memory.
+pub type OdbHandle = gix_odb::memory::Proxy<gix_odb::Handle>;
 /// A way to access git configuration
 pub(crate) type Config = OwnShared<gix_config::File<'static>>;
 
@@ -299,6 +302,20 @@ pub mod revision;
 #[cfg(feature = "attributes")]
 pub mod filter;
 
+///
+#[cfg(feature = "maintenance")]
+pub mod maintenance;
+
+///
+pub mod history;
+
+#[cfg(feature = "blocking-network-client")]
+pub mod promisor;
+
+///
+#[cfg(feature = "merge")]
+pub mod merge;
+
 ///
 pub mod remote;
 
@@ -311,6 +328,10 @@ pub mod state;
 ///
 pub mod shallow;
 
+///
+#[cfg(feature = "excludes")]
+pub mod sparse;
+
 ///
 pub mod discover;
 
@@ -0,0 +1,411 @@
+//! Maintenance tasks for the object database, similar to what `git maintenance` or `git repack` would do.
+
+/// A lock on the repository to coordinate maintenance between processes, which is compatible with the `gc.pid` file
+/// used by `git gc`.
+///
+/// Obtained by [`Repository::lock_for_maintenance()`](crate::Repository::lock_for_maintenance()), the lock is released
+/// when dropped unless another process took it over in the meantime.
+#[derive(Debug)]
+pub struct Lock {
+    pub(crate) path: std::path::PathBuf,
+    pub(crate) holder: lock::Holder,
+    /// Identifies the `gc.pid` file we wrote, to tell it apart from one written by a thread of this process that stole the lock.
+    pub(crate) id: Option<lock::FileId>,
+}
+
+impl Lock {
+    /// The path to the file marking the repository as locked.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        // Like `git gc`, hold `gc.pid.lock` while looking at `gc.pid` so nobody can steal it while we remove it.
+        // If that's not possible in time, remove it anyway if it's still ours.
+        let _lock_file = gix_lock::File::acquire_to_update_resource(
+            &self.path,
+            gix_lock::acquire::Fail::AfterDurationWithBackoff(lock::LOCK_FILE_TIMEOUT),
+            None,
+        )
+        .ok();
+        // It may have been stolen by another process or thread in the meantime, and then it's not ours to remove.
+        if lock::Holder::from_path(&self.path).ok().flatten().as_ref() == Some(&self.holder)
+            && lock::FileId::from_path(&self.path).ok().flatten() == self.id
+        {
+            std::fs::remove_file(&self.path).ok();
+        }
+    }
+}
+
+///
+pub mod lock {
+    use std::time::Duration;
+
+    use crate::bstr::{BString, ByteSlice};
+
+    /// How long to wait for `gc.pid.lock`, which is only ever held for a moment while `gc.pid` is checked and updated.
+    pub(crate) const LOCK_FILE_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// What to do if the maintenance lock is held by another process that seems to be alive.
+    ///
+    /// Note that locks which are older than [`Options::stale_after`] or whose process isn't running anymore on this machine
+    /// are always taken over.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Policy {
+        /// Fail with [`Error::Held`] right away.
+        #[default]
+        Fail,
+        /// Wait for at most the given duration for the lock to be released, and fail with [`Error::Held`] if it wasn't.
+        Wait(Duration),
+        /// Take over the lock even though its holder seems to be alive, similar to `git gc --force`.
+        Steal,
+    }
+
+    /// Options for [`Repository::lock_for_maintenance()`](crate::Repository::lock_for_maintenance()).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Options {
+        /// What to do if the lock is held by another process.
+        pub policy: Policy,
+        /// The age after which a lock is considered stale even if its holder seems to be alive,
+        /// as it may have been created on another machine whose processes we can't see.
+        pub stale_after: Duration,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                policy: Policy::default(),
+                stale_after: Duration::from_secs(12 * 60 * 60),
+            }
+        }
+    }
+
+    /// The process holding the maintenance lock.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Holder {
+        /// The id of the process.
+        pub pid: u32,
+        /// The name of the machine the process is running on.
+        pub hostname: BString,
+    }
+
+    impl Holder {
+        /// Parse `<pid> <hostname>` from `data` as written by `git gc`, or return `None` if it's malformed.
+        pub fn from_bytes(data: &[u8]) -> Option<Self> {
+            let (pid, hostname) = data.trim_end().split_once_str(" ")?;
+            Some(Holder {
+                pid: pid.to_str().ok()?.parse().ok()?,
+                hostname: hostname.into(),
+            })
+        }
+
+        pub(crate) fn from_path(path: &std::path::Path) -> std::io::Result<Option<Self>> {
+            match std::fs::read(path) {
+                Ok(data) => Ok(Self::from_bytes(&data)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err),
+            }
+        }
+    }
+
+    /// Identifies a `gc.pid` file independently of its content, which is the same for all threads of a process.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct FileId {
+        #[cfg(unix)]
+        dev_ino: (u64, u64),
+        #[cfg(not(unix))]
+        modified: std::time::SystemTime,
+    }
+
+    impl FileId {
+        pub(crate) fn from_path(path: &std::path::Path) -> std::io::Result<Option<Self>> {
+            let meta = match std::fs::metadata(path) {
+                Ok(meta) => meta,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                Ok(Some(FileId {
+                    dev_ino: (meta.dev(), meta.ino()),
+                }))
+            }
+            #[cfg(not(unix))]
+            {
+                Ok(Some(FileId {
+                    modified: meta.modified()?,
+                }))
+            }
+        }
+    }
+
+    /// The error returned by [`Repository::lock_for_maintenance()`](crate::Repository::lock_for_maintenance()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Maintenance is already running on machine '{}' with pid {}", holder.hostname, holder.pid)]
+        Held { holder: Holder },
+        #[error(transparent)]
+        Acquire(#[from] gix_lock::acquire::Error),
+        #[error(transparent)]
+        Commit(#[from] gix_lock::commit::Error<gix_lock::File>),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+pub mod pack_loose_objects {
+    use std::path::PathBuf;
+
+    /// Options for [`Repository::pack_loose_objects()`](crate::Repository::pack_loose_objects()).
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        /// The amount of threads to use at most when compressing objects and indexing the new pack.
+        /// If `None`, all logical cores are used.
+        pub thread_limit: Option<usize>,
+        /// The zlib compression level from 0 (none) to 9 (best) to use for objects in the new pack.
+        /// If `None`, the [configured level](crate::Repository::pack_compression_level()) is used.
+        pub compression_level: Option<u32>,
+        /// If `true`, loose objects are deleted once the new pack containing them was written successfully,
+        /// similar to what `git prune-packed` does.
+        pub delete_loose_objects: bool,
+        /// If `true`, write a multi-pack index for all packs if there is none yet.
+        /// Note that an existing multi-pack index is always rewritten to include the new pack.
+        pub write_multi_pack_index: bool,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                thread_limit: None,
+                compression_level: None,
+                delete_loose_objects: true,
+                write_multi_pack_index: false,
+            }
+        }
+    }
+
+    /// The outcome of [`Repository::pack_loose_objects()`](crate::Repository::pack_loose_objects()).
+    #[derive(Debug, Clone)]
+    pub struct Outcome {
+        /// Information about the newly written pack and its index, or `None` if there were no loose objects.
+        pub pack: Option<gix_pack::bundle::write::Outcome>,
+        /// The amount of loose objects that were deleted after they were written to the new pack.
+        pub deleted_loose_objects: usize,
+        /// The path to the multi-pack index if it was written.
+        pub multi_pack_index_path: Option<PathBuf>,
+    }
+
+    /// The error returned by [`Repository::pack_loose_objects()`](crate::Repository::pack_loose_objects()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not traverse loose objects")]
+        IterLooseObjects(#[from] gix_odb::loose::iter::Error),
+        #[error(transparent)]
+        CompressionLevel(#[from] crate::config::key::GenericError),
+        #[error("Could not write the pack with all loose objects")]
+        WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+        #[error("Could not verify and index the new pack")]
+        WriteBundle(#[from] gix_pack::bundle::write::Error),
+        #[error("Could not write the multi-pack index")]
+        WriteMultiPackIndex(#[from] gix_pack::multi_index::write::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        LockMultiPackIndex(#[from] gix_lock::acquire::Error),
+        #[error(transparent)]
+        CommitMultiPackIndex(#[from] gix_lock::commit::Error<gix_lock::File>),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("The operation was interrupted")]
+        Interrupted,
+    }
+
+    /// The progress ids used in [`Repository::pack_loose_objects()`](crate::Repository::pack_loose_objects()).
+    ///
+    /// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+    #[derive(Debug, Copy, Clone)]
+    pub enum ProgressId {
+        /// The amount of loose objects found.
+        CollectLooseObjects,
+        /// The amount of objects that were compressed for the new pack.
+        CompressObjects,
+        /// The amount of bytes written to the new pack.
+        WritePack,
+        /// The amount of loose objects deleted after they were packed.
+        DeleteLooseObjects,
+    }
+
+    impl From<ProgressId> for gix_features::progress::Id {
+        fn from(v: ProgressId) -> Self {
+            match v {
+                ProgressId::CollectLooseObjects => *b"MPCL",
+                ProgressId::CompressObjects => *b"MPCO",
+                ProgressId::WritePack => *b"MPWP",
+                ProgressId::DeleteLooseObjects => *b"MPDL",
+            }
+        }
+    }
+}
+
+///
+pub mod write_commit_graph {
+    use std::path::PathBuf;
+
+    /// Options for [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Options {
+        /// If `true`, compute the paths that changed in each commit compared to its first parent and store them as bloom filters,
+        /// which speeds up path-limited traversals, similar to `git commit-graph write --changed-paths`.
+        pub changed_paths: bool,
+    }
+
+    /// The outcome of [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+    #[derive(Debug, Clone)]
+    pub struct Outcome {
+        /// The path to the commit-graph file that was written.
+        pub path: PathBuf,
+        /// The amount of commits in the commit-graph file.
+        pub num_commits: u32,
+        /// The checksum of the commit-graph file.
+        pub checksum: gix_hash::ObjectId,
+    }
+
+    /// The error returned by [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindCommit(#[from] gix_object::find::existing_object::Error),
+        #[error(transparent)]
+        FindTree(#[from] gix_object::find::existing_iter::Error),
+        #[error("Could not compute the paths changed by commit {id}")]
+        DiffTree {
+            source: gix_diff::tree::changes::Error,
+            id: gix_hash::ObjectId,
+        },
+        #[error("Could not write the commit-graph")]
+        WriteCommitGraph(#[from] gix_commitgraph::file::write::Error),
+        #[error(transparent)]
+        LockCommitGraph(#[from] gix_lock::acquire::Error),
+        #[error(transparent)]
+        CommitCommitGraph(#[from] gix_lock::commit::Error<gix_lock::File>),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("The operation was interrupted")]
+        Interrupted,
+    }
+
+    /// The progress ids used in [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+    ///
+    /// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+    #[derive(Debug, Copy, Clone)]
+    pub enum ProgressId {
+        /// The amount of commits that were traversed.
+        TraverseCommits,
+        /// The amount of commits whose changed paths were computed.
+        ComputeChangedPaths,
+    }
+
+    impl From<ProgressId> for gix_features::progress::Id {
+        fn from(v: ProgressId) -> Self {
+            match v {
+                ProgressId::TraverseCommits => *b"MCTC",
+                ProgressId::ComputeChangedPaths => *b"MCCP",
+            }
+        }
+    }
+}
+
+///
+pub mod auto {
+    /// Options for [`Repository::auto_maintenance()`](crate::Repository::auto_maintenance()).
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Options {
+        /// The amount of threads to use at most when packing loose objects, or all logical cores if `None`.
+        pub thread_limit: Option<usize>,
+        /// If `true`, instead of performing the maintenance in this process, spawn `git gc --auto` in the background
+        /// and return without waiting for it, similar to what `gc.autoDetach` does for `git`.
+        ///
+        /// This is opt-in as it requires a `git` binary in the `PATH`. The spawned process is waited for on a
+        /// background thread so long-lived callers don't accumulate zombie processes.
+        pub detach: bool,
+    }
+
+    /// The reasons for performing maintenance as determined by
+    /// [`Repository::auto_maintenance_reasons()`](crate::Repository::auto_maintenance_reasons()).
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct Reasons {
+        /// The estimated amount of loose objects if it exceeds `gc.auto`.
+        pub loose_objects: Option<usize>,
+        /// The amount of packs if it reaches `gc.autoPackLimit`.
+        pub packs: Option<usize>,
+    }
+
+    impl Reasons {
+        /// Return `true` if there is no reason to perform maintenance.
+        pub fn is_empty(&self) -> bool {
+            self.loose_objects.is_none() && self.packs.is_none()
+        }
+    }
+
+    /// The outcome of [`Repository::auto_maintenance()`](crate::Repository::auto_maintenance()).
+    #[derive(Debug, Clone)]
+    pub enum Outcome {
+        /// The thresholds were not exceeded, so nothing was done.
+        NotNeeded,
+        /// Maintenance was performed in this process.
+        Performed {
+            /// Why maintenance was performed.
+            reasons: Reasons,
+            /// The outcome of packing the loose objects and writing the multi-pack index.
+            outcome: super::pack_loose_objects::Outcome,
+        },
+        /// Maintenance is already running in another process, so nothing was done.
+        AlreadyRunning {
+            /// Why maintenance would have been performed.
+            reasons: Reasons,
+            /// The process holding the maintenance lock.
+            holder: super::lock::Holder,
+        },
+        /// A `git gc --auto` process was spawned to perform the maintenance in the background.
+        Detached {
+            /// Why maintenance was started.
+            reasons: Reasons,
+            /// The id of the spawned process.
+            pid: u32,
+        },
+    }
+
+    ///
+    pub mod reasons {
+        /// The error returned by [`Repository::auto_maintenance_reasons()`](crate::Repository::auto_maintenance_reasons()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            ConfigValue(#[from] crate::config::unsigned_integer::Error),
+            #[error("Could not count loose objects or packs")]
+            Io(#[from] std::io::Error),
+        }
+    }
+
+    /// The error returned by [`Repository::auto_maintenance()`](crate::Repository::auto_maintenance()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Reasons(#[from] reasons::Error),
+        #[error(transparent)]
+        Lock(#[from] super::lock::Error),
+        #[error(transparent)]
+        PackLooseObjects(#[from] super::pack_loose_objects::Error),
+        #[error("Could not spawn 'git gc --auto' to perform maintenance in the background")]
+        Spawn(#[source] std::io::Error),
+    }
+}
@@ -0,0 +1,174 @@
+//! Merge commits, trees and blobs without touching the worktree, similar to `git merge-tree` and `git merge-file`.
+pub use gix_merge as plumbing;
+
+///
+pub mod commits {
+    /// The error returned by [`Repository::merge_commits()`](crate::Repository::merge_commits()).
+    pub type Error = gix_merge::commit::Error;
+
+    /// Options for [`Repository::merge_commits()`](crate::Repository::merge_commits()).
+    pub type Options = gix_merge::commit::Options;
+
+    pub use gix_merge::commit::Strategy;
+
+    /// The result of [`Repository::merge_commits()`](crate::Repository::merge_commits()).
+    pub type Outcome = gix_merge::commit::Outcome;
+}
+
+///
+pub mod message {
+    use gix_hash::ObjectId;
+    use gix_ref::FullName;
+
+    use crate::bstr::BString;
+
+    /// The amount of commit summaries shown for each merged head if `merge.log` is `true`.
+    pub const DEFAULT_LOG_LENGTH: usize = 20;
+
+    /// A commit to merge, along with the way it was named.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Head {
+        /// The commit to merge.
+        pub id: ObjectId,
+        /// The reference the commit was obtained from, like `refs/heads/feature` or `refs/tags/v1.0`,
+        /// or `None` if it was named directly, in which case its id is used in the message.
+        pub reference: Option<FullName>,
+    }
+
+    /// Options for [`Repository::merge_message()`](crate::Repository::merge_message()), typically obtained with
+    /// [`Repository::merge_message_options()`](crate::Repository::merge_message_options()).
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct Options {
+        /// If set, list the summaries of at most this many commits that each head brings in below the title, like `merge.log`.
+        pub log: Option<usize>,
+        /// Glob patterns matching the names of branches which don't get an ` into <branch>` suffix in the title,
+        /// like `merge.suppressDest`, or `None` to use `main` and `master` like `git` does if it's not configured.
+        pub suppress_destinations: Option<Vec<BString>>,
+    }
+
+    /// The error returned by [`Repository::merge_message()`](crate::Repository::merge_message()) and
+    /// [`Repository::merge_message_options()`](crate::Repository::merge_message_options()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The value of merge.log must be a boolean or an integer: {value:?}")]
+        InvalidLog { value: BString },
+        #[error(transparent)]
+        Head(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        HeadCommit(#[from] crate::reference::head_commit::Error),
+        #[error(transparent)]
+        MergeBase(#[from] crate::revision::merge_base::Error),
+        #[error(transparent)]
+        Walk(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        Traverse(#[from] gix_traverse::commit::ancestors::Error),
+        #[error(transparent)]
+        FindCommit(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+    }
+}
+
+///
+#[cfg(feature = "worktree-mutation")]
+pub mod worktree {
+    use gix_hash::ObjectId;
+
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::merge_into_worktree()`](crate::Repository::merge_into_worktree()),
+    /// [`Repository::merge_continue()`](crate::Repository::merge_continue()) and
+    /// [`Repository::merge_abort()`](crate::Repository::merge_abort()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot merge in a bare repository")]
+        BareRepository,
+        #[error("A merge is already in progress, it has to be continued or aborted first")]
+        MergeInProgress,
+        #[error("There is no merge in progress")]
+        NoMergeInProgress,
+        #[error("There are unresolved conflicts in {} path(s), like '{}'", paths.len(), paths[0])]
+        UnresolvedConflicts { paths: Vec<BString> },
+        #[error("Local changes of '{path}' would be overwritten by the merge")]
+        LocalChanges { path: BString },
+        #[error("MERGE_HEAD contains an invalid object id: '{line}'")]
+        DecodeMergeHead { line: BString },
+        #[error(transparent)]
+        HeadCommit(#[from] crate::reference::head_commit::Error),
+        #[error(transparent)]
+        MergeBase(#[from] crate::revision::merge_base::Error),
+        #[error(transparent)]
+        MergeCommits(#[from] super::commits::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        FindExistingObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        IndexFromTree(#[from] gix_traverse::tree::breadthfirst::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        EditTree(#[from] gix_object::tree::editor::Error),
+        #[error(transparent)]
+        UpdateWorktree(#[from] crate::worktree::switch::Error),
+        #[error(transparent)]
+        Commit(#[from] crate::commit::Error),
+        #[error(transparent)]
+        EditReference(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+
+    /// Options for [`Repository::merge_into_worktree()`](crate::Repository::merge_into_worktree()).
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Options {
+        /// The options for merging the commits, including the strategy.
+        pub merge: super::commits::Options,
+        /// If `true`, `HEAD` is moved to the commit to merge if it contains `HEAD`, instead of creating a merge commit.
+        /// If `false`, a merge commit is always created, like `git merge --no-ff`.
+        pub allow_fast_forward: bool,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                merge: Default::default(),
+                allow_fast_forward: true,
+            }
+        }
+    }
+
+    /// The result of [`Repository::merge_into_worktree()`](crate::Repository::merge_into_worktree()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Outcome {
+        /// All commits to merge were already contained in `HEAD`, so nothing was done.
+        UpToDate,
+        /// `HEAD` was moved to `commit`, which contains the previous `HEAD`, and the worktree was updated accordingly.
+        FastForward {
+            /// The commit `HEAD` now points to.
+            commit: ObjectId,
+        },
+        /// The merge commit `commit` was created after merging without conflicts, and the worktree was updated accordingly.
+        Merged {
+            /// The new merge commit that `HEAD` now points to.
+            commit: ObjectId,
+        },
+        /// The merge stopped due to `conflicts`, which are represented in the index and the worktree like `git` does it.
+        ///
+        /// Once they are resolved, use [`Repository::merge_continue()`](crate::Repository::merge_continue()) to create
+        /// the merge commit, or [`Repository::merge_abort()`](crate::Repository::merge_abort()) to restore the state
+        /// before the merge.
+        Conflicts {
+            /// All conflicts that were encountered.
+            conflicts: Vec<gix_merge::tree::Conflict>,
+        },
+    }
+}
@@ -153,9 +153,29 @@ pub mod diff {
         }
     }
 
+    ///
+    pub mod words {
+        /// The error returned by [Platform::words()](super::Platform::words()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error<E>
+        where
+            E: std::error::Error + Send + Sync + 'static,
+        {
+            #[error(transparent)]
+            ProcessChunk(E),
+            #[error(transparent)]
+            PrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
+            #[error(transparent)]
+            WordRegex(#[from] gix_diff::blob::words::init::Error),
+        }
+    }
+
     impl<'a> Platform<'a> {
         /// Perform a diff on lines between the old and the new version of a blob, passing each hunk of lines to `process_hunk`.
         /// The diffing algorithm is determined by the `diff.algorithm` configuration, or individual diff drivers.
+        /// Lines are compared while ignoring whitespace as configured in
+        /// [`Options::ignore_whitespace`](gix_diff::blob::platform::Options::ignore_whitespace), but are passed as they are.
         /// Note that `process_hunk` is not called if one of the involved resources are binary, but that can be determined
         /// by introspecting the outcome.
         // TODO: more tests (only tested insertion right now)
@@ -172,11 +192,11 @@ pub mod diff {
             let prep = self.resource_cache.prepare_diff()?;
             match prep.operation {
                 Operation::InternalDiff { algorithm } => {
-                    let input = prep.interned_input();
+                    let input = whitespace_input(&prep, self.resource_cache.options.ignore_whitespace);
                     let mut err = None;
                     let mut lines = Vec::new();
 
-                    gix_diff::blob::diff(algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+                    input.diff(algorithm, |before: Range<u32>, after: Range<u32>| {
                         if err.is_some() {
                             return;
                         }
@@ -184,13 +204,13 @@ pub mod diff {
                         lines.extend(
                             input.before[before.start as usize..before.end as usize]
                                 .iter()
-                                .map(|&line| input.interner[line].as_bstr()),
+                                .map(|line| line.as_bstr()),
                         );
                         let end_of_before = lines.len();
                         lines.extend(
                             input.after[after.start as usize..after.end as usize]
                                 .iter()
-                                .map(|&line| input.interner[line].as_bstr()),
+                                .map(|line| line.as_bstr()),
                         );
                         let hunk_before = &lines[..end_of_before];
                         let hunk_after = &lines[end_of_before..];
@@ -219,7 +239,49 @@ pub mod diff {
             Ok(prep)
         }
 
-        /// Count the amount of removed and inserted lines efficiently.
+        /// Perform a diff on words between the old and the new version of a blob, passing each chunk of text to `process_chunk`
+        /// in order, which can [write it](gix_diff::blob::words::Chunk::write_to()) in the desired format.
+        ///
+        /// Words are matched with the `diff.<driver>.wordRegex` of the old or new resource's diff driver, in that order,
+        /// or are separated by whitespace if none is configured.
+        /// The diffing algorithm is determined by the `diff.algorithm` configuration, or individual diff drivers.
+        /// Note that `process_chunk` is not called if one of the involved resources are binary, but that can be determined
+        /// by introspecting the outcome.
+        pub fn words<FnC, E>(
+            &mut self,
+            mut process_chunk: FnC,
+        ) -> Result<gix_diff::blob::platform::prepare_diff::Outcome<'_>, words::Error<E>>
+        where
+            FnC: FnMut(gix_diff::blob::words::Chunk<'_>) -> Result<(), E>,
+            E: std::error::Error + Send + Sync + 'static,
+        {
+            self.resource_cache.options.skip_internal_diff_if_external_is_configured = false;
+
+            let prep = self.resource_cache.prepare_diff()?;
+            match prep.operation {
+                Operation::InternalDiff { algorithm } => {
+                    let drivers = self.resource_cache.filter.drivers();
+                    let word_regex = [prep.old.driver_index, prep.new.driver_index]
+                        .into_iter()
+                        .flatten()
+                        .find_map(|idx| drivers[idx].word_regex.as_ref());
+                    let words = gix_diff::blob::words::Words::new(word_regex.map(AsRef::as_ref))?;
+                    let old = prep.old.data.as_slice().unwrap_or_default();
+                    let new = prep.new.data.as_slice().unwrap_or_default();
+                    for chunk in gix_diff::blob::words::diff(algorithm, old, new, &words) {
+                        process_chunk(chunk).map_err(words::Error::ProcessChunk)?;
+                    }
+                }
+                Operation::ExternalCommand { .. } => {
+                    unreachable!("we disabled that")
+                }
+                Operation::SourceOrDestinationIsBinary => {}
+            };
+            Ok(prep)
+        }
+
+        /// Count the amount of removed and inserted lines efficiently, while ignoring whitespace as configured in
+        /// [`Options::ignore_whitespace`](gix_diff::blob::platform::Options::ignore_whitespace).
         /// Note that nothing will happen if one of the inputs is binary, and `None` will be returned.
         pub fn line_counts(
             &mut self,
@@ -229,8 +291,8 @@ pub mod diff {
             let prep = self.resource_cache.prepare_diff()?;
             match prep.operation {
                 Operation::InternalDiff { algorithm } => {
-                    let tokens = prep.interned_input();
-                    let counter = gix_diff::blob::diff(algorithm, &tokens, gix_diff::blob::sink::Counter::default());
+                    let input = whitespace_input(&prep, self.resource_cache.options.ignore_whitespace);
+                    let counter = input.diff(algorithm, gix_diff::blob::sink::Counter::default());
                     Ok(Some(counter))
                 }
                 Operation::ExternalCommand { .. } => {
@@ -240,6 +302,17 @@ pub mod diff {
             }
         }
     }
+
+    fn whitespace_input<'a>(
+        prep: &gix_diff::blob::platform::prepare_diff::Outcome<'a>,
+        ignore: gix_diff::blob::whitespace::Ignore,
+    ) -> gix_diff::blob::whitespace::Input<'a> {
+        gix_diff::blob::whitespace::Input::new(
+            prep.old.data.as_slice().unwrap_or_default(),
+            prep.new.data.as_slice().unwrap_or_default(),
+            ignore,
+        )
+    }
 }
 
 /// Remove Lifetime
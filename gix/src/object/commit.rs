@@ -154,6 +154,7 @@ impl<'repo> Commit<'repo> {
             first_parent: false,
             id_as_fallback: false,
             max_candidates: 10,
+            sort_by_version: false,
         }
     }
 
@@ -95,4 +95,52 @@ impl<'a, 'old, 'new> Event<'a, 'old, 'new> {
             | Event::Rewrite { entry_mode, .. } => *entry_mode,
         }
     }
+
+    /// Return `true` if this is a modification or rewrite that changed the kind of the entry, like a file that turned
+    /// into a symlink or a submodule, which `git` reports as _typechange_.
+    ///
+    /// Note that files replaced by directories and vice versa are represented as deletion and addition instead.
+    pub fn is_typechange(&self) -> bool {
+        match *self {
+            Event::Modification {
+                previous_entry_mode,
+                entry_mode,
+                ..
+            }
+            | Event::Rewrite {
+                source_entry_mode: previous_entry_mode,
+                entry_mode,
+                ..
+            } => previous_entry_mode.is_typechange(entry_mode),
+            Event::Addition { .. } | Event::Deletion { .. } => false,
+        }
+    }
+
+    /// Return the commits a submodule pointed to before and after this change, or `None` if no submodule is involved.
+    ///
+    /// One side is `None` if the submodule was added or removed, or if it replaced an entry of another kind or vice versa.
+    /// As these commits typically aren't available in this repository, such changes can't be [diffed](super::Change::diff())
+    /// line by line and are best displayed as `Subproject commit <id>` lines, just like `git diff` does.
+    pub fn submodule_ids(&self) -> Option<(Option<Id<'old>>, Option<Id<'new>>)> {
+        let (previous, current) = match *self {
+            Event::Addition { entry_mode, id } => (None, Some((entry_mode, id))),
+            Event::Deletion { entry_mode, id } => (Some((entry_mode, id)), None),
+            Event::Modification {
+                previous_entry_mode,
+                previous_id,
+                entry_mode,
+                id,
+            }
+            | Event::Rewrite {
+                source_entry_mode: previous_entry_mode,
+                source_id: previous_id,
+                entry_mode,
+                id,
+                ..
+            } => (Some((previous_entry_mode, previous_id)), Some((entry_mode, id))),
+        };
+        let previous = previous.filter(|(mode, _)| mode.is_commit()).map(|(_, id)| id);
+        let current = current.filter(|(mode, _)| mode.is_commit()).map(|(_, id)| id);
+        (previous.is_some() || current.is_some()).then_some((previous, current))
+    }
 }
@@ -0,0 +1,54 @@
+use std::{collections::HashSet, convert::Infallible};
+
+use gix_diff::directory_renames::DirectoryRename;
+
+use super::{change::Event, Action, Platform};
+use crate::Tree;
+
+/// The error returned by [`Platform::directory_renames()`].
+pub type Error = super::for_each::Error;
+
+/// Directory renames
+impl<'a, 'old> Platform<'a, 'old> {
+    /// Find the directories that were renamed as a whole to turn the source of the diff into `other`, as deduced from the
+    /// files that were renamed out of them, like `git merge` does it to learn where files that were added to a renamed
+    /// directory on another branch should be placed.
+    ///
+    /// A directory is considered renamed if it doesn't exist in `other` anymore, and most of its renamed files went to the same
+    /// [destination](gix_diff::directory_renames::detect()). Renamed files are tracked as [configured](Self::track_rewrites()),
+    /// so nothing is found if rename tracking is disabled. Copies are ignored, and full paths are always
+    /// [tracked](Self::track_path()) as they are needed for the result.
+    pub fn directory_renames(&mut self, other: &Tree<'_>) -> Result<Vec<DirectoryRename>, Error> {
+        let mut renames = Vec::new();
+        let mut deleted_directories = HashSet::new();
+        self.track_path()
+            .for_each_to_obtain_tree(other, |change| -> Result<_, Infallible> {
+                match change.event {
+                    Event::Rewrite {
+                        source_location,
+                        copy: false,
+                        ..
+                    } => renames.push((source_location.to_owned(), change.location.to_owned())),
+                    Event::Deletion { entry_mode, .. } if entry_mode.is_tree() => {
+                        deleted_directories.insert(change.location.to_owned());
+                    }
+                    Event::Modification {
+                        previous_entry_mode,
+                        entry_mode,
+                        ..
+                    } if previous_entry_mode.is_tree() && !entry_mode.is_tree() => {
+                        deleted_directories.insert(change.location.to_owned());
+                    }
+                    _ => {}
+                }
+                Ok(Action::Continue)
+            })?;
+
+        Ok(gix_diff::directory_renames::detect(
+            renames
+                .iter()
+                .map(|(source, destination)| (source.as_ref(), destination.as_ref())),
+            |directory| !deleted_directories.contains(directory),
+        ))
+    }
+}
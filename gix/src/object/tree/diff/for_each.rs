@@ -79,8 +79,13 @@ impl<'a, 'old> Platform<'a, 'old> {
         E: std::error::Error + Sync + Send + 'static,
     {
         let repo = self.lhs.repo;
+        let mut objects = repo.objects.clone();
+        if let Some(toggle) = self.use_object_replacements {
+            objects.ignore_replacements = !toggle;
+        }
         let mut delegate = Delegate {
             src_tree: self.lhs,
+            objects: &objects,
             other_repo: other.repo,
             recorder: gix_diff::tree::Recorder::default().track_location(self.tracking),
             visit: for_each,
@@ -91,7 +96,7 @@ impl<'a, 'old> Platform<'a, 'old> {
         match gix_diff::tree::Changes::from(TreeRefIter::from_bytes(&self.lhs.data)).needed_to_obtain(
             TreeRefIter::from_bytes(&other.data),
             &mut self.state,
-            &repo.objects,
+            &objects,
             &mut delegate,
         ) {
             Ok(()) => {
@@ -115,6 +120,7 @@ impl<'a, 'old> Platform<'a, 'old> {
 
 struct Delegate<'a, 'old, 'new, VisitFn, E> {
     src_tree: &'a Tree<'old>,
+    objects: &'a crate::OdbHandle,
     other_repo: &'new Repository,
     recorder: gix_diff::tree::Recorder,
     visit: VisitFn,
@@ -226,7 +232,7 @@ where
                 ),
             },
             diff_cache,
-            &self.src_tree.repo.objects,
+            self.objects,
             |push| {
                 self.src_tree
                     .traverse()
@@ -45,6 +45,7 @@ impl<'repo> Tree<'repo> {
             lhs: self,
             tracking: None,
             rewrites: self.repo.config.diff_renames()?.unwrap_or_default().into(),
+            use_object_replacements: None,
         })
     }
 }
@@ -56,6 +57,7 @@ pub struct Platform<'a, 'repo> {
     lhs: &'a Tree<'repo>,
     tracking: Option<Location>,
     rewrites: Option<Rewrites>,
+    use_object_replacements: Option<bool>,
 }
 
 /// Configuration
@@ -83,7 +85,23 @@ impl<'a, 'repo> Platform<'a, 'repo> {
         self.rewrites = renames;
         self
     }
+
+    /// If `false`, look up trees and blobs as they are stored instead of using their replacements in `refs/replace/`,
+    /// or use replacements with `true`. Set it to `None` to use the
+    /// [default of the repository](crate::open::Options::object_replacements()) (the default).
+    ///
+    /// Note that this doesn't affect the trees to compare, which have been looked up already.
+    pub fn use_object_replacements(&mut self, toggle: impl Into<Option<bool>>) -> &mut Self {
+        self.use_object_replacements = toggle.into();
+        self
+    }
 }
 
 ///
 pub mod for_each;
+
+///
+pub mod stats;
+
+///
+pub mod directory_renames;
@@ -0,0 +1,104 @@
+use std::convert::Infallible;
+
+use gix_diff::{blob::platform::resource::Data, stat};
+
+use super::{change::Event, Action, Platform};
+use crate::Tree;
+
+/// The error returned by [`Platform::stats()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ResourceCache(#[from] crate::repository::diff::resource_cache::Error),
+    #[error(transparent)]
+    ForEachChange(#[from] super::for_each::Error),
+    #[error(transparent)]
+    SetResource(#[from] crate::object::blob::diff::init::Error),
+    #[error(transparent)]
+    PrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
+}
+
+/// Statistics
+impl<'a, 'old> Platform<'a, 'old> {
+    /// Compute the amount of inserted and removed lines of each file that changed to turn the source of the diff into `other`,
+    /// or determine the sizes of binary files, similar to `git diff --stat`.
+    ///
+    /// The result can be [written](gix_diff::stat::write_to()) in the formats known from `git`, or can be
+    /// [summarized](gix_diff::stat::Summary::from_files()), for example to show the impact of a commit.
+    /// Renamed and copied files are tracked as [configured](Self::track_rewrites()), and full paths are always
+    /// [tracked](Self::track_path()) as they are needed for the result.
+    /// Changes to submodules count as one removed line for the previous commit and one inserted line for the current one,
+    /// just like `git` counts the `Subproject commit <id>` lines.
+    pub fn stats(&mut self, other: &Tree<'_>) -> Result<Vec<stat::File>, Error> {
+        let mut resource_cache = self
+            .lhs
+            .repo
+            .diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        let mut files = Vec::new();
+        let mut err = None;
+        let res = self
+            .track_path()
+            .for_each_to_obtain_tree(other, |change| -> Result<_, Infallible> {
+                if change.event.entry_mode().is_tree() {
+                    return Ok(Action::Continue);
+                }
+                let source_location = match change.event {
+                    Event::Rewrite { source_location, .. } => Some(source_location.to_owned()),
+                    _ => None,
+                };
+                let stat_change = match change.event.submodule_ids() {
+                    Some((previous, current)) => Ok(stat::Change::Lines {
+                        insertions: current.is_some().into(),
+                        removals: previous.is_some().into(),
+                    }),
+                    None => file_change(&change, &mut resource_cache),
+                };
+                resource_cache.clear_resource_cache();
+                match stat_change {
+                    Ok(stat_change) => {
+                        files.push(stat::File {
+                            location: change.location.to_owned(),
+                            source_location,
+                            change: stat_change,
+                        });
+                        Ok(Action::Continue)
+                    }
+                    Err(e) => {
+                        err = Some(e);
+                        Ok(Action::Cancel)
+                    }
+                }
+            });
+        if let Some(err) = err {
+            return Err(err);
+        }
+        res?;
+        Ok(files)
+    }
+}
+
+fn file_change(
+    change: &super::Change<'_, '_, '_>,
+    resource_cache: &mut gix_diff::blob::Platform,
+) -> Result<stat::Change, Error> {
+    let mut diff = change.diff(resource_cache)?;
+    Ok(match diff.line_counts()? {
+        Some(counts) => stat::Change::Lines {
+            insertions: counts.insertions,
+            removals: counts.removals,
+        },
+        None => {
+            let prep = diff.resource_cache.prepare_diff()?;
+            let size = |data: Data<'_>| match data {
+                Data::Missing => 0,
+                Data::Buffer(buf) => buf.len() as u64,
+                Data::Binary { size } => size,
+            };
+            stat::Change::Binary {
+                old_size: size(prep.old.data),
+                new_size: size(prep.new.data),
+            }
+        }
+    })
+}
@@ -38,6 +38,18 @@ impl<'a, 'repo> BreadthFirstPresets<'a, 'repo> {
 }
 
 impl<'a, 'repo> Platform<'a, 'repo> {
+    /// Prepare to list the entries of this tree along with their paths, recursively by default, similar to `git ls-tree -r -t`.
+    pub fn paths(&self) -> Paths<'a, 'repo> {
+        Paths {
+            root: self.root,
+            recursive: true,
+            max_depth: None,
+            with_sizes: false,
+            #[cfg(feature = "attributes")]
+            pathspec: None,
+        }
+    }
+
     /// Start a breadth-first, recursive traversal using `delegate`, for which a [`Recorder`][gix_traverse::tree::Recorder] can be used to get started.
     ///
     /// # Note
@@ -53,3 +65,215 @@ impl<'a, 'repo> Platform<'a, 'repo> {
         gix_traverse::tree::breadthfirst(root, state, &self.root.repo.objects, delegate)
     }
 }
+
+/// A builder to list the entries of a tree along with their paths, as obtained by [`Platform::paths()`].
+pub struct Paths<'a, 'repo> {
+    root: &'a Tree<'repo>,
+    recursive: bool,
+    max_depth: Option<usize>,
+    with_sizes: bool,
+    #[cfg(feature = "attributes")]
+    pathspec: Option<crate::Pathspec<'repo>>,
+}
+
+///
+pub mod paths {
+    use gix_hash::ObjectId;
+    use gix_object::{bstr::BString, tree::EntryMode};
+
+    /// The error returned by [`Paths::all()`](super::Paths::all()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Traverse(#[from] gix_traverse::tree::breadthfirst::Error),
+        #[error("Could not obtain the size of the object at '{path}'")]
+        FindHeader {
+            path: BString,
+            source: crate::object::find::existing::Error,
+        },
+    }
+
+    /// A tree entry along with its path, as returned by [`Paths::all()`](super::Paths::all()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Entry {
+        /// The kind of entry, similar to entries in a unix directory tree.
+        pub mode: EntryMode,
+        /// The id of the object the entry points to.
+        pub oid: ObjectId,
+        /// The full path to the entry, relative to the tree the listing started at, like `d/a`.
+        pub filepath: BString,
+        /// The size of the object in bytes, if [requested](super::Paths::with_sizes()) and if it is a blob or symlink.
+        pub size: Option<u64>,
+    }
+}
+
+/// Builder
+impl<'a, 'repo> Paths<'a, 'repo> {
+    /// If `false`, only list the entries of the tree itself without descending into its subtrees.
+    pub fn recursive(mut self, toggle: bool) -> Self {
+        self.recursive = toggle;
+        self
+    }
+
+    /// If `Some(depth)`, don't descend into trees that are `depth` levels below the tree itself, so `0` lists only
+    /// its own entries. This only has an effect if the listing is [recursive](Self::recursive()).
+    pub fn max_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// If `true`, obtain the size of blobs and symlinks, which requires looking up each object's header.
+    pub fn with_sizes(mut self, toggle: bool) -> Self {
+        self.with_sizes = toggle;
+        self
+    }
+
+    /// Only list entries included in `pathspec`, and don't descend into trees which can't contain any of them.
+    ///
+    /// Note that trees leading up to included entries aren't listed unless they are included themselves.
+    #[cfg(feature = "attributes")]
+    pub fn pathspec(mut self, pathspec: crate::Pathspec<'repo>) -> Self {
+        self.pathspec = Some(pathspec);
+        self
+    }
+}
+
+impl<'a, 'repo> Paths<'a, 'repo> {
+    /// Traverse the tree breadth-first and return all entries that passed the configured filters, in the order they
+    /// were encountered.
+    pub fn all(self) -> Result<Vec<paths::Entry>, paths::Error> {
+        let repo = self.root.repo;
+        let mut delegate = collect::Delegate {
+            path: Default::default(),
+            path_deque: Default::default(),
+            records: Vec::new(),
+            max_depth: if self.recursive { self.max_depth } else { Some(0) },
+            #[cfg(feature = "attributes")]
+            pathspec: self.pathspec,
+            #[cfg(not(feature = "attributes"))]
+            _phantom: Default::default(),
+        };
+        Platform {
+            root: self.root,
+            breadthfirst: BreadthFirstPresets { root: self.root },
+        }
+        .breadthfirst(&mut delegate)?;
+
+        let mut records = delegate.records;
+        if self.with_sizes {
+            for entry in records.iter_mut().filter(|e| e.mode.is_blob_or_symlink()) {
+                entry.size = Some(
+                    repo.find_header(entry.oid)
+                        .map_err(|source| paths::Error::FindHeader {
+                            path: entry.filepath.clone(),
+                            source,
+                        })?
+                        .size(),
+                );
+            }
+        }
+        Ok(records)
+    }
+}
+
+mod collect {
+    use std::collections::VecDeque;
+
+    use gix_object::{
+        bstr::{BStr, BString, ByteSlice, ByteVec},
+        tree::EntryRef,
+    };
+    use gix_traverse::tree::visit::Action;
+
+    use super::paths::Entry;
+
+    pub(super) struct Delegate<'repo> {
+        pub path: BString,
+        pub path_deque: VecDeque<BString>,
+        pub records: Vec<Entry>,
+        pub max_depth: Option<usize>,
+        #[cfg(feature = "attributes")]
+        pub pathspec: Option<crate::Pathspec<'repo>>,
+        #[cfg(not(feature = "attributes"))]
+        pub _phantom: std::marker::PhantomData<&'repo ()>,
+    }
+
+    impl<'repo> Delegate<'repo> {
+        fn pop_element(&mut self) {
+            if let Some(pos) = self.path.rfind_byte(b'/') {
+                self.path.resize(pos, 0);
+            } else {
+                self.path.clear();
+            }
+        }
+
+        fn push_element(&mut self, name: &BStr) {
+            if !self.path.is_empty() {
+                self.path.push(b'/');
+            }
+            self.path.push_str(name);
+        }
+
+        /// Return `(is_included, may_contain_included)` for the current path.
+        fn matches(&mut self, is_dir: bool) -> (bool, bool) {
+            #[cfg(feature = "attributes")]
+            if let Some(pathspec) = self.pathspec.as_mut() {
+                let path = self.path.as_bstr();
+                return (
+                    pathspec.is_included(path, Some(is_dir)),
+                    is_dir && pathspec.search().can_match_relative_path(path, Some(true)),
+                );
+            }
+            (true, is_dir)
+        }
+
+        fn record(&mut self, entry: &EntryRef<'_>) {
+            self.records.push(Entry {
+                mode: entry.mode,
+                oid: entry.oid.to_owned(),
+                filepath: self.path.clone(),
+                size: None,
+            });
+        }
+    }
+
+    impl<'repo> gix_traverse::tree::Visit for Delegate<'repo> {
+        fn pop_front_tracked_path_and_set_current(&mut self) {
+            self.path = self.path_deque.pop_front().expect("every parent is set only once");
+        }
+
+        fn push_back_tracked_path_component(&mut self, component: &BStr) {
+            self.push_element(component);
+            self.path_deque.push_back(self.path.clone());
+        }
+
+        fn push_path_component(&mut self, component: &BStr) {
+            self.push_element(component);
+        }
+
+        fn pop_path_component(&mut self) {
+            self.pop_element();
+        }
+
+        fn visit_tree(&mut self, entry: &EntryRef<'_>) -> Action {
+            let (is_included, may_contain_included) = self.matches(true);
+            if is_included {
+                self.record(entry);
+            }
+            let depth = self.path.iter().filter(|b| **b == b'/').count();
+            if may_contain_included && self.max_depth.map_or(true, |max_depth| depth < max_depth) {
+                Action::Continue
+            } else {
+                Action::Skip
+            }
+        }
+
+        fn visit_nontree(&mut self, entry: &EntryRef<'_>) -> Action {
+            if self.matches(false).0 {
+                self.record(entry);
+            }
+            Action::Continue
+        }
+    }
+}
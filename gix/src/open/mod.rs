@@ -20,6 +20,11 @@ pub struct Permissions {
 /// The environment variables `GIT_REPLACE_REF_BASE` and `GIT_NO_REPLACE_OBJECTS` are mapped to `gitoxide.objects.replaceRefBase`
 /// and `gitoxide.objects.noReplace` respectively and then interpreted exactly as their environment variable counterparts.
 ///
+/// ### Object directories and the index file
+///
+/// The environment variables `GIT_OBJECT_DIRECTORY`, `GIT_ALTERNATE_OBJECT_DIRECTORIES` and `GIT_INDEX_FILE` are mapped to
+/// `gitoxide.objects.directory`, `gitoxide.objects.alternateDirectories` and `gitoxide.core.indexFile` respectively.
+///
 /// Use [Permissions] to control which environment variables can be read, and config-overrides to control these values programmatically.
 #[derive(Clone)]
 pub struct Options {
@@ -35,6 +40,9 @@ pub struct Options {
     pub(crate) api_config_overrides: Vec<BString>,
     pub(crate) cli_config_overrides: Vec<BString>,
     pub(crate) open_path_as_is: bool,
+    pub(crate) object_replacements: bool,
+    pub(crate) mailmap: bool,
+    pub(crate) object_memory: bool,
     /// Internal to pass an already obtained CWD on to where it may also be used. This avoids the CWD being queried more than once per repo.
     pub(crate) current_dir: Option<PathBuf>,
 }
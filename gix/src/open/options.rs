@@ -14,6 +14,9 @@ impl Default for Options {
             lenient_config: true,
             bail_if_untrusted: false,
             open_path_as_is: false,
+            object_replacements: true,
+            mailmap: true,
+            object_memory: false,
             api_config_overrides: Vec::new(),
             cli_config_overrides: Vec::new(),
             current_dir: None,
@@ -79,6 +82,38 @@ impl Options {
         self
     }
 
+    /// If `false`, default `true`, objects will not be replaced by the objects that `refs/replace/` designates as their
+    /// replacement, so the raw history can be seen even if replacements are configured.
+    ///
+    /// It can be overridden for individual operations like [rev-walks](crate::revision::walk::Platform::use_object_replacements())
+    /// or [tree diffs](crate::object::tree::diff::Platform::use_object_replacements()).
+    pub fn object_replacements(mut self, toggle: bool) -> Self {
+        self.object_replacements = toggle;
+        self
+    }
+
+    /// If `false`, default `true`, [`Repository::open_mailmap()`](crate::Repository::open_mailmap()) will return an empty mailmap
+    /// so names and emails are seen as they are recorded in the history.
+    ///
+    /// It can be overridden for individual operations by loading the mailmap with
+    /// [`Repository::open_mailmap_into()`](crate::Repository::open_mailmap_into()), which always loads it.
+    pub fn mailmap(mut self, toggle: bool) -> Self {
+        self.mailmap = toggle;
+        self
+    }
+
+    /// If `true`, default `false`, all objects written by the opened repository are kept in memory instead of being written
+    /// to the object database, so the object database on disk remains unchanged.
+    ///
+    /// Combined with [config overrides](Self::config_overrides()), this allows to simulate operations like merges without
+    /// side-effects. However, references and the index are still written to disk, so operations like
+    /// [`commit()`](crate::Repository::commit()) leave references that point to objects which are lost with the process.
+    /// See [`Repository::with_object_memory()`](crate::Repository::with_object_memory()) for details.
+    pub fn object_memory(mut self, toggle: bool) -> Self {
+        self.object_memory = toggle;
+        self
+    }
+
     /// Set the trust level of the `.git` directory we are about to open.
     ///
     /// This can be set manually to force trust even though otherwise it might
@@ -167,6 +202,9 @@ impl gix_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 open_path_as_is: false,
+                object_replacements: true,
+                mailmap: true,
+                object_memory: false,
                 api_config_overrides: Vec::new(),
                 cli_config_overrides: Vec::new(),
                 current_dir: None,
@@ -179,6 +217,9 @@ impl gix_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 open_path_as_is: false,
+                object_replacements: true,
+                mailmap: true,
+                object_memory: false,
                 lossy_config: None,
                 api_config_overrides: Vec::new(),
                 cli_config_overrides: Vec::new(),
@@ -162,6 +162,9 @@ impl ThreadSafeRepository {
             lenient_config,
             bail_if_untrusted,
             open_path_as_is: _,
+            object_replacements: _,
+            mailmap: _,
+            object_memory: _,
             permissions:
                 Permissions {
                     ref env,
@@ -293,15 +296,45 @@ impl ThreadSafeRepository {
             })
             .unwrap_or_default();
 
+        let loose_compression_level = config
+            .compression_level(
+                &Core::LOOSE_COMPRESSION,
+                gix_features::zlib::stream::deflate::LEVEL_FAST,
+            )
+            .map_err(config::Error::from)?;
+        let objects_dir = config
+            .resolved
+            .string_filter_by_key(
+                gitoxide::Objects::DIRECTORY.logical_name().as_str(),
+                &mut filter_config_section,
+            )
+            .map_or_else(
+                || common_dir_ref.join("objects"),
+                |dir| current_dir.join(gix_path::from_bstr(dir)),
+            );
+        let alternates = config
+            .resolved
+            .string_filter_by_key(
+                gitoxide::Objects::ALTERNATE_DIRECTORIES.logical_name().as_str(),
+                &mut filter_config_section,
+            )
+            .map(|dirs| {
+                std::env::split_paths(gix_path::from_bstr(dirs).as_os_str())
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
         Ok(ThreadSafeRepository {
             objects: OwnShared::new(gix_odb::Store::at_opts(
-                common_dir_ref.join("objects"),
+                objects_dir,
                 &mut replacements.into_iter(),
                 gix_odb::store::init::Options {
                     slots: object_store_slots,
                     object_hash: config.object_hash,
                     use_multi_pack_index: config.use_multi_pack_index,
                     current_dir: current_dir.to_owned().into(),
+                    loose_compression_level,
+                    alternates,
                 },
             )?),
             common_dir,
@@ -325,11 +358,11 @@ fn replacement_objects_refs_prefix(
     lenient: bool,
     mut filter_config_section: fn(&gix_config::file::Metadata) -> bool,
 ) -> Result<Option<PathBuf>, Error> {
-    let is_disabled = config::shared::is_replace_refs_enabled(config, lenient, filter_config_section)
+    let is_enabled = config::shared::is_replace_refs_enabled(config, lenient, filter_config_section)
         .map_err(config::Error::ConfigBoolean)?
         .unwrap_or(true);
 
-    if is_disabled {
+    if !is_enabled {
         return Ok(None);
     }
 
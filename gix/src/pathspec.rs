@@ -149,6 +149,32 @@ impl<'repo> Pathspec<'repo> {
             })
         })
     }
+
+    /// Insert `flags` into all entries of `index` that match this pathspec if `value` is `true`, or remove them otherwise,
+    /// and return the amount of entries that were changed.
+    ///
+    /// This is most useful with [`ASSUME_VALID`](gix_index::entry::Flags::ASSUME_VALID) to implement
+    /// `git update-index --[no-]assume-unchanged`, and with [`SKIP_WORKTREE`](gix_index::entry::Flags::SKIP_WORKTREE)
+    /// for `git update-index --[no-]skip-worktree`, as entries with these flags are not compared with the worktree
+    /// when computing the status. Note that `index` has to be written for the changes to persist.
+    pub fn set_index_entry_flags(
+        &mut self,
+        index: &mut gix_index::State,
+        flags: gix_index::entry::Flags,
+        value: bool,
+    ) -> usize {
+        let Some(range) = index.prefixed_entries_range(self.search.common_prefix()) else {
+            return 0;
+        };
+        let (entries, path_backing) = index.entries_mut_and_pathbacking();
+        let mut num_changed = 0;
+        for entry in &mut entries[range] {
+            if self.is_included(entry.path_in(path_backing), Some(false)) && entry.set_flags(flags, value) {
+                num_changed += 1;
+            }
+        }
+        num_changed
+    }
 }
 
 /// Access
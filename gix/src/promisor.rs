@@ -0,0 +1,29 @@
+//! Obtain objects that were omitted when creating a partial clone from promisor remotes.
+
+///
+pub mod fetch_missing {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::fetch_missing_objects()`](crate::Repository::fetch_missing_objects()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        PromisorRemoteNames(#[from] crate::config::boolean::Error),
+        #[error("{} object(s) could not be obtained from any of the {} promisor remote(s)", missing.len(), remote_errors.len())]
+        Incomplete {
+            /// The objects that are still missing.
+            missing: Vec<gix_hash::ObjectId>,
+            /// The names of the promisor remotes that failed to provide objects, along with the error they produced.
+            remote_errors: Vec<(BString, Box<dyn std::error::Error + Send + Sync + 'static>)>,
+        },
+    }
+
+    /// The outcome of [`Repository::fetch_missing_objects()`](crate::Repository::fetch_missing_objects()).
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The names of the promisor remotes that objects were fetched from, in the order they were asked,
+        /// along with the amount of missing objects each of them provided.
+        pub remotes: Vec<(BString, usize)>,
+    }
+}
@@ -0,0 +1,107 @@
+//! Map objects to the references pointing at them, for decorating commits similar to `git log --decorate`.
+use gix_hash::ObjectId;
+use gix_ref::{Category, FullName};
+
+use crate::bstr::ByteSlice;
+
+/// The error returned by [`Repository::decorations()`](crate::Repository::decorations()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterateReferences(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+}
+
+/// The kind of a [`Decoration`], which also determines the order in which decorations are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    /// `HEAD`, which is either detached or points to a local branch.
+    Head,
+    /// A local branch in `refs/heads/`.
+    LocalBranch,
+    /// A tag in `refs/tags/`.
+    Tag,
+    /// A remote tracking branch in `refs/remotes/`.
+    RemoteBranch,
+    /// Any other reference, like `refs/stash` or `refs/notes/commits`.
+    Other,
+}
+
+/// A reference pointing at an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoration {
+    /// The full name of the reference, like `refs/heads/main`.
+    pub name: FullName,
+    /// The kind of reference.
+    pub kind: Kind,
+}
+
+/// A map of object ids to the references pointing at them, built once by
+/// [`Repository::decorations()`](crate::Repository::decorations()) to provide cheap lookups while rendering many commits.
+///
+/// Annotated tags decorate the tag object as well as the object they point to after peeling.
+#[derive(Debug, Clone, Default)]
+pub struct Decorations {
+    pub(crate) by_id: gix_hashtable::HashMap<ObjectId, Vec<Decoration>>,
+    /// The branch `HEAD` points to, if it isn't detached.
+    pub(crate) head_branch: Option<FullName>,
+}
+
+impl Decorations {
+    /// Return all decorations of the object with `id`, with `HEAD` first, followed by local branches, tags,
+    /// remote branches and other references, each ordered by name.
+    pub fn get(&self, id: impl AsRef<gix_hash::oid>) -> &[Decoration] {
+        self.by_id.get(id.as_ref()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the full name of the branch `HEAD` points to, or `None` if `HEAD` is detached or unborn.
+    pub fn head_branch(&self) -> Option<&FullName> {
+        self.head_branch.as_ref()
+    }
+
+    /// Return `true` if no object is decorated.
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    /// Format the decorations of the object with `id` like `git log --decorate` does,
+    /// for example `HEAD -> main, tag: v1.0, origin/main`, or return `None` if it isn't decorated.
+    pub fn format(&self, id: impl AsRef<gix_hash::oid>) -> Option<String> {
+        let decorations = self.get(id);
+        if decorations.is_empty() {
+            return None;
+        }
+        let mut out = String::new();
+        for decoration in decorations {
+            let short_name = decoration.name.as_ref().shorten().to_str_lossy();
+            let part = match (decoration.kind, &self.head_branch) {
+                (Kind::Head, Some(branch)) => format!("HEAD -> {}", branch.as_ref().shorten().to_str_lossy()),
+                // It's already shown along with `HEAD`, which always decorates the same object.
+                (Kind::LocalBranch, Some(branch)) if *branch == decoration.name => continue,
+                (Kind::Tag, _) => format!("tag: {short_name}"),
+                _ => short_name.into_owned(),
+            };
+            if !out.is_empty() {
+                out.push_str(", ");
+            }
+            out.push_str(&part);
+        }
+        Some(out)
+    }
+}
+
+pub(crate) fn kind_of(name: &gix_ref::FullNameRef) -> Kind {
+    if name.as_bstr() == "HEAD" {
+        return Kind::Head;
+    }
+    match name.category() {
+        Some(Category::LocalBranch) => Kind::LocalBranch,
+        Some(Category::Tag) => Kind::Tag,
+        Some(Category::RemoteBranch) => Kind::RemoteBranch,
+        _ => Kind::Other,
+    }
+}
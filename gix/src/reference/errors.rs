@@ -73,6 +73,125 @@ pub mod head_tree_id {
     }
 }
 
+///
+pub mod rename_unborn_head {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::rename_unborn_head()`](crate::Repository::rename_unborn_head()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Head(#[from] crate::reference::find::existing::Error),
+        #[error("HEAD can only be renamed if it is unborn, i.e. if its branch has no commit yet")]
+        NotUnborn,
+        #[error("Invalid branch name: {name:?}")]
+        InvalidBranchName {
+            name: BString,
+            source: gix_validate::reference::name::Error,
+        },
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+    }
+}
+
+///
+#[cfg(feature = "revision")]
+pub mod delete_branch {
+    use gix_ref::FullName;
+
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::delete_branch()`](crate::Repository::delete_branch()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Invalid branch name: {name:?}")]
+        InvalidBranchName {
+            name: BString,
+            source: gix_validate::reference::name::Error,
+        },
+        #[error("The branch {name:?} does not exist")]
+        NotFound { name: FullName },
+        #[error(transparent)]
+        Find(#[from] crate::reference::find::Error),
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        WorktreeRefs(#[from] crate::worktree::checked_out::Error),
+        #[error("Cannot delete branch {name:?} as it is used by the worktree with {head:?}")]
+        InUse { name: FullName, head: FullName },
+        #[error(transparent)]
+        Peel(#[from] crate::reference::peel::Error),
+        #[error(transparent)]
+        PeelHead(#[from] crate::head::peel::Error),
+        #[error(transparent)]
+        Upstream(#[from] crate::repository::branch_remote_tracking_ref_name::Error),
+        #[error(transparent)]
+        Traverse(#[from] crate::revision::reachable::Error),
+        #[error("The branch {name:?} is not fully merged into {target:?}")]
+        NotMerged { name: FullName, target: FullName },
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        OpenConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error(transparent)]
+        LockConfig(#[from] gix_lock::acquire::Error),
+        #[error("Could not write the configuration file")]
+        WriteConfig(#[from] std::io::Error),
+        #[error(transparent)]
+        CommitConfig(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
+
+///
+pub mod rename_branch {
+    use gix_ref::FullName;
+
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::rename_branch()`](crate::Repository::rename_branch()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Invalid branch name: {name:?}")]
+        InvalidBranchName {
+            name: BString,
+            source: gix_validate::reference::name::Error,
+        },
+        #[error("The branch {name:?} does not exist")]
+        NotFound { name: FullName },
+        #[error("The branch {name:?} already exists")]
+        AlreadyExists { name: FullName },
+        #[error(transparent)]
+        Find(#[from] crate::reference::find::Error),
+        #[error(transparent)]
+        WorktreeRefs(#[from] crate::worktree::checked_out::Error),
+        #[error("Cannot overwrite branch {name:?} as it is used by the worktree with {head:?}")]
+        InUse { name: FullName, head: FullName },
+        #[error(transparent)]
+        Peel(#[from] crate::reference::peel::Error),
+        #[error("Could not read the reflog of the branch")]
+        ReadReflog(#[from] gix_ref::file::log::Error),
+        #[error("Could not parse the reflog of the branch")]
+        DecodeReflog(#[from] gix_ref::file::log::iter::decode::Error),
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        OpenConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error(transparent)]
+        LockConfig(#[from] gix_lock::acquire::Error),
+        #[error("Could not write the configuration file")]
+        WriteConfig(#[from] std::io::Error),
+        #[error(transparent)]
+        CommitConfig(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
+
 ///
 pub mod find {
     ///
@@ -85,6 +85,88 @@ impl<'r> Iter<'r> {
         self.peel = true;
         self
     }
+
+    /// Collect all references and sort them by the versions contained in their full names using `sort`,
+    /// similar to `git for-each-ref --sort=version:refname`.
+    ///
+    /// Use [`Repository::version_sort()`][crate::Repository::version_sort()] to obtain a comparator that respects `versionsort.suffix`.
+    pub fn sorted_by_version(
+        self,
+        sort: &crate::reference::VersionSort,
+    ) -> Result<Vec<crate::Reference<'r>>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let mut refs = self.collect::<Result<Vec<_>, _>>()?;
+        refs.sort_by(|a, b| sort.compare(a.name().as_bstr(), b.name().as_bstr()));
+        Ok(refs)
+    }
+}
+
+/// Filtering by reachability
+#[cfg(feature = "revision")]
+impl<'r> Iter<'r> {
+    /// Collect all references whose commit contains `commit`, i.e. it's reachable from it, similar to `git branch --contains <commit>`.
+    ///
+    /// References that don't point to a commit, even after peeling tags, are skipped. All references are checked with a single
+    /// traversal that doesn't visit commits older than `commit` if a [commit-graph](crate::Repository::revision_graph()) is available.
+    pub fn contains(self, commit: impl Into<gix_hash::ObjectId>) -> Result<Vec<crate::Reference<'r>>, filter::Error> {
+        let repo = self.repo;
+        let (refs, tips) = self.with_commits()?;
+        let keep = gix_revision::contains(&tips, &commit.into(), &mut repo.revision_graph())?;
+        Ok(select(refs, keep, true))
+    }
+
+    /// Collect all references whose commit is reachable from `commit`, similar to `git branch --merged <commit>`.
+    ///
+    /// References that don't point to a commit, even after peeling tags, are skipped. All references are checked with a single
+    /// traversal that stops once all of them were found.
+    pub fn merged(self, commit: impl Into<gix_hash::ObjectId>) -> Result<Vec<crate::Reference<'r>>, filter::Error> {
+        self.filter_merged(commit.into(), true)
+    }
+
+    /// Collect all references whose commit is not reachable from `commit`, similar to `git branch --no-merged <commit>`.
+    ///
+    /// This is the inverse of [`merged()`](Self::merged()), and references that don't point to a commit are skipped as well.
+    pub fn no_merged(self, commit: impl Into<gix_hash::ObjectId>) -> Result<Vec<crate::Reference<'r>>, filter::Error> {
+        self.filter_merged(commit.into(), false)
+    }
+
+    fn filter_merged(
+        self,
+        commit: gix_hash::ObjectId,
+        merged: bool,
+    ) -> Result<Vec<crate::Reference<'r>>, filter::Error> {
+        let repo = self.repo;
+        let (refs, tips) = self.with_commits()?;
+        let reachable = gix_revision::reachable_from(Some(commit), &tips, &mut repo.revision_graph())?;
+        Ok(select(refs, reachable, merged))
+    }
+
+    /// Collect all references that point to a commit, along with the ids of these commits.
+    fn with_commits(self) -> Result<(Vec<crate::Reference<'r>>, Vec<gix_hash::ObjectId>), filter::Error> {
+        let repo = self.repo;
+        let mut refs = Vec::new();
+        let mut commits = Vec::new();
+        for reference in self {
+            let reference = reference.map_err(filter::Error::Iterate)?;
+            let id = match reference.clone().peel_to_id_in_place() {
+                Ok(id) => id.detach(),
+                Err(_) => continue,
+            };
+            if repo.find_header(id)?.kind() != gix_object::Kind::Commit {
+                continue;
+            }
+            refs.push(reference);
+            commits.push(id);
+        }
+        Ok((refs, commits))
+    }
+}
+
+#[cfg(feature = "revision")]
+fn select<'r>(refs: Vec<crate::Reference<'r>>, matches: Vec<bool>, keep: bool) -> Vec<crate::Reference<'r>> {
+    refs.into_iter()
+        .zip(matches)
+        .filter_map(|(r, is_match)| (is_match == keep).then_some(r))
+        .collect()
 }
 
 impl<'r> Iterator for Iter<'r> {
@@ -119,5 +201,28 @@ pub mod init {
     }
 }
 
+///
+#[cfg(feature = "revision")]
+pub mod filter {
+    /// The error returned by [`Iter::contains()`][super::Iter::contains()], [`Iter::merged()`][super::Iter::merged()]
+    /// and [`Iter::no_merged()`][super::Iter::no_merged()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A reference could not be obtained during iteration")]
+        Iterate(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        Traverse(#[from] gix_revision::reachable::Error),
+    }
+}
+
+///
+pub mod worktrees {
+    /// The error returned by [`Repository::worktree_references()`](crate::Repository::worktree_references()).
+    pub type Error = gix_ref::file::iter::loose_then_packed::Error;
+}
+
 /// The error returned by [references()][crate::Repository::references()].
 pub type Error = gix_ref::packed::buffer::open::Error;
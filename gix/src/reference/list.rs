@@ -0,0 +1,448 @@
+//! List references with filters, sort keys and format placeholders, similar to `git for-each-ref`.
+use std::{borrow::Cow, cmp::Ordering};
+
+use gix_hash::ObjectId;
+use gix_ref::{FullName, FullNameRef};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    ext::ObjectIdExt,
+};
+
+/// The error returned by [`Repository::list_references()`](crate::Repository::list_references())
+/// and [`Entry::format()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterateReferences(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    FindUpstream(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    Decode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Merged(#[from] gix_revision::reachable::Error),
+    #[error(transparent)]
+    AheadBehind(#[from] crate::revision::ahead_behind::Error),
+    #[error(transparent)]
+    ShortenId(#[from] crate::id::shorten::Error),
+}
+
+///
+pub mod parse {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Format::parse()`](super::Format::parse()) and [`SortKey::parse()`](super::SortKey::parse()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Unknown field name: '{name}'")]
+        UnknownField { name: BString },
+        #[error("Unterminated placeholder in '{format}'")]
+        UnterminatedPlaceholder { format: BString },
+    }
+}
+
+/// A field of a reference that can be printed with a `%(field)` placeholder or used as sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// `refname` or `refname:short`, the name of the reference.
+    RefName {
+        /// If `true`, the shortest unambiguous name is used, like `main` instead of `refs/heads/main`.
+        short: bool,
+    },
+    /// `objectname` or `objectname:short`, the id of the object the reference points to.
+    ObjectName {
+        /// If `true`, the shortest unambiguous hex prefix of the id is used.
+        short: bool,
+    },
+    /// `objecttype`, the kind of object the reference points to.
+    ObjectType,
+    /// `upstream` or `upstream:short`, the name of the local reference tracking the upstream of a local branch.
+    Upstream {
+        /// If `true`, the shortest unambiguous name is used, like `origin/main`.
+        short: bool,
+    },
+    /// `upstream:track` or `upstream:trackshort`, how far a local branch is ahead or behind its upstream.
+    UpstreamTrack {
+        /// If `true`, use `<`, `>`, `<>` or `=` instead of `[ahead N, behind M]`.
+        short: bool,
+    },
+    /// `subject` or `contents:subject`, the first paragraph of the tag or commit message.
+    Subject,
+    /// `HEAD`, which is `*` if `HEAD` points to the reference and a space otherwise.
+    Head,
+    /// `committerdate`, `creatordate` or `taggerdate`, the time of the tagger or the committer, whichever comes first when peeling.
+    CommitterDate,
+}
+
+impl Field {
+    fn parse(name: &BStr) -> Result<Self, parse::Error> {
+        Ok(match name.as_bytes() {
+            b"refname" => Field::RefName { short: false },
+            b"refname:short" => Field::RefName { short: true },
+            b"objectname" => Field::ObjectName { short: false },
+            b"objectname:short" => Field::ObjectName { short: true },
+            b"objecttype" => Field::ObjectType,
+            b"upstream" => Field::Upstream { short: false },
+            b"upstream:short" => Field::Upstream { short: true },
+            b"upstream:track" => Field::UpstreamTrack { short: false },
+            b"upstream:trackshort" => Field::UpstreamTrack { short: true },
+            b"subject" | b"contents:subject" => Field::Subject,
+            b"HEAD" => Field::Head,
+            b"committerdate" | b"creatordate" | b"taggerdate" => Field::CommitterDate,
+            _ => return Err(parse::Error::UnknownField { name: name.to_owned() }),
+        })
+    }
+}
+
+/// A part of a [`Format`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Atom {
+    /// Text to print as is.
+    Literal(BString),
+    /// A `%(field)` placeholder.
+    Field(Field),
+}
+
+/// A parsed format like `%(objectname) %(objecttype)\t%(refname)` to print each [`Entry`] with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    atoms: Vec<Atom>,
+}
+
+impl Default for Format {
+    /// The format `git for-each-ref` uses by default.
+    fn default() -> Self {
+        Format::parse("%(objectname) %(objecttype)\t%(refname)".into()).expect("valid default format")
+    }
+}
+
+impl Format {
+    /// Parse `format` into literals and `%(field)` placeholders, with `%%` being a literal `%` and `%xx` a hex-encoded byte.
+    pub fn parse(format: &BStr) -> Result<Self, parse::Error> {
+        let mut atoms = Vec::new();
+        let mut literal = BString::default();
+        let mut rest = format.as_bytes();
+        while let Some(pos) = rest.find_byte(b'%') {
+            literal.extend_from_slice(&rest[..pos]);
+            rest = &rest[pos + 1..];
+            match rest.first() {
+                Some(b'%') => {
+                    literal.push(b'%');
+                    rest = &rest[1..];
+                }
+                Some(b'(') => {
+                    let end = rest
+                        .find_byte(b')')
+                        .ok_or_else(|| parse::Error::UnterminatedPlaceholder {
+                            format: format.to_owned(),
+                        })?;
+                    if !literal.is_empty() {
+                        atoms.push(Atom::Literal(std::mem::take(&mut literal)));
+                    }
+                    atoms.push(Atom::Field(Field::parse(rest[1..end].as_bstr())?));
+                    rest = &rest[end + 1..];
+                }
+                _ => match rest
+                    .get(..2)
+                    .and_then(|hex| u8::from_str_radix(hex.to_str().ok()?, 16).ok())
+                {
+                    Some(byte) => {
+                        literal.push(byte);
+                        rest = &rest[2..];
+                    }
+                    None => literal.push(b'%'),
+                },
+            }
+        }
+        literal.extend_from_slice(rest);
+        if !literal.is_empty() {
+            atoms.push(Atom::Literal(literal));
+        }
+        Ok(Format { atoms })
+    }
+
+    /// Return the parts of this format in order.
+    pub fn atoms(&self) -> &[Atom] {
+        &self.atoms
+    }
+
+    /// Return `true` if printing this format requires [`Options::upstream_tracking`] to be enabled.
+    pub fn needs_upstream_tracking(&self) -> bool {
+        self.atoms
+            .iter()
+            .any(|atom| matches!(atom, Atom::Field(Field::UpstreamTrack { .. })))
+    }
+}
+
+/// A key to sort entries by, like `-committerdate` or `version:refname`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    /// The field to compare.
+    pub field: Field,
+    /// If `true`, compare the values as versions using [`Repository::version_sort()`](crate::Repository::version_sort()).
+    pub by_version: bool,
+    /// If `true`, sort in descending order.
+    pub reverse: bool,
+}
+
+impl SortKey {
+    /// Parse `key` like `refname`, with a `-` prefix to reverse the order and a `version:` or `v:` prefix to compare versions.
+    pub fn parse(key: &BStr) -> Result<Self, parse::Error> {
+        let (reverse, key) = match key.strip_prefix(b"-") {
+            Some(key) => (true, key.as_bstr()),
+            None => (false, key),
+        };
+        let (by_version, key) = match key.strip_prefix(b"version:").or_else(|| key.strip_prefix(b"v:")) {
+            Some(key) => (true, key.as_bstr()),
+            None => (false, key),
+        };
+        Ok(SortKey {
+            field: Field::parse(key)?,
+            by_version,
+            reverse,
+        })
+    }
+
+    fn compare(&self, a: &Entry, b: &Entry, version_sort: &crate::reference::VersionSort) -> Ordering {
+        let ordering = match self.field {
+            Field::CommitterDate => a.time.map(|t| t.seconds).cmp(&b.time.map(|t| t.seconds)),
+            Field::UpstreamTrack { .. } => a.ahead_behind.cmp(&b.ahead_behind),
+            Field::Head => a.is_head.cmp(&b.is_head),
+            field => {
+                let value = |e: &Entry| -> BString {
+                    match field {
+                        Field::RefName { short } => shorten(e.name.as_ref(), short).into_owned(),
+                        Field::ObjectName { .. } => e.id.to_string().into(),
+                        Field::ObjectType => e.kind.as_bytes().into(),
+                        Field::Upstream { short } => e
+                            .upstream
+                            .as_ref()
+                            .map(|name| shorten(name.as_ref(), short).into_owned())
+                            .unwrap_or_default(),
+                        _ => e.subject.clone(),
+                    }
+                };
+                let (a, b) = (value(a), value(b));
+                if self.by_version {
+                    version_sort.compare(a.as_bstr(), b.as_bstr())
+                } else {
+                    a.cmp(&b)
+                }
+            }
+        };
+        if self.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Options for use in [`Repository::list_references()`](crate::Repository::list_references()).
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Only list references matching one of these prefixes like `refs/heads`, or glob patterns like `refs/tags/v1.*`.
+    ///
+    /// If empty, all references are listed.
+    pub patterns: Vec<BString>,
+    /// Only list references pointing at this object, directly or after peeling tags.
+    pub points_at: Option<ObjectId>,
+    /// Only list references whose commits are reachable from this commit, similar to `git for-each-ref --merged`.
+    pub merged: Option<ObjectId>,
+    /// The keys to sort by, with the last one being the primary key. If empty, entries are sorted by `refname`.
+    pub sort: Vec<SortKey>,
+    /// If `true`, compute how far local branches are ahead or behind their upstream as needed by [`Field::UpstreamTrack`].
+    ///
+    /// This is enabled automatically if one of the sort keys needs it.
+    pub upstream_tracking: bool,
+}
+
+/// A reference along with everything [`Field`]s are computed from, as returned by
+/// [`Repository::list_references()`](crate::Repository::list_references()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The full name of the reference.
+    pub name: FullName,
+    /// The object the reference points to, after following symbolic references.
+    pub id: ObjectId,
+    /// The kind of object `id` refers to.
+    pub kind: gix_object::Kind,
+    /// The object after peeling all tags.
+    pub peeled_id: ObjectId,
+    /// The kind of object `peeled_id` refers to.
+    pub peeled_kind: gix_object::Kind,
+    /// The time of the tagger or the committer, whichever applies first.
+    pub time: Option<gix_date::Time>,
+    /// The summary of the tag or commit message, whichever applies first.
+    pub subject: BString,
+    /// `true` if `HEAD` points to this reference.
+    pub is_head: bool,
+    /// The name of the local reference tracking the upstream of a local branch.
+    pub upstream: Option<FullName>,
+    /// The commit `upstream` points to, or `None` if it doesn't exist, i.e. is _gone_.
+    pub upstream_id: Option<ObjectId>,
+    /// How far the branch is `(ahead, behind)` its upstream, if [`Options::upstream_tracking`] was enabled.
+    pub ahead_behind: Option<(usize, usize)>,
+}
+
+impl Entry {
+    /// Print all atoms of `format` with the values of this entry, using `repo` to shorten object ids.
+    pub fn format(&self, format: &Format, repo: &crate::Repository) -> Result<BString, Error> {
+        let mut out = BString::default();
+        for atom in &format.atoms {
+            match atom {
+                Atom::Literal(text) => out.extend_from_slice(text),
+                Atom::Field(field) => out.extend_from_slice(&self.value(*field, repo)?),
+            }
+        }
+        Ok(out)
+    }
+
+    fn value(&self, field: Field, repo: &crate::Repository) -> Result<Cow<'_, BStr>, Error> {
+        Ok(match field {
+            Field::RefName { short } => shorten(self.name.as_ref(), short),
+            Field::ObjectName { short: false } => BString::from(self.id.to_string()).into(),
+            Field::ObjectName { short: true } => BString::from(self.id.attach(repo).shorten()?.to_string()).into(),
+            Field::ObjectType => self.kind.as_bytes().as_bstr().into(),
+            Field::Upstream { short } => self
+                .upstream
+                .as_ref()
+                .map_or_else(|| BStr::new("").into(), |name| shorten(name.as_ref(), short)),
+            Field::UpstreamTrack { short } => {
+                let value = match (&self.upstream, self.upstream_id, self.ahead_behind) {
+                    (None, _, _) | (Some(_), Some(_), None) => String::new(),
+                    (Some(_), None, _) if short => String::new(),
+                    (Some(_), None, _) => "[gone]".into(),
+                    (Some(_), Some(_), Some((ahead, behind))) => track(ahead, behind, short),
+                };
+                BString::from(value).into()
+            }
+            Field::Subject => self.subject.as_bstr().into(),
+            Field::Head => BStr::new(if self.is_head { "*" } else { " " }).into(),
+            Field::CommitterDate => self.time.map(|t| t.to_bstring()).unwrap_or_default().into(),
+        })
+    }
+
+    pub(crate) fn from_reference(
+        repo: &crate::Repository,
+        mut reference: crate::Reference<'_>,
+        head_name: Option<&FullName>,
+    ) -> Result<Option<Self>, Error> {
+        // Like `git`, don't follow symbolic references forever as they may form a loop.
+        const MAX_SYMBOLIC_DEPTH: usize = 5;
+        let name = reference.name().to_owned();
+        let mut depth = 0;
+        let id = loop {
+            match reference.try_id() {
+                Some(id) => break id.detach(),
+                None if depth == MAX_SYMBOLIC_DEPTH => return Ok(None),
+                None => match reference.follow() {
+                    Some(Ok(next)) => {
+                        reference = next;
+                        depth += 1;
+                    }
+                    Some(Err(_)) | None => return Ok(None),
+                },
+            }
+        };
+        let object = repo.find_object(id)?;
+        let kind = object.kind;
+        let (mut time, mut subject) = (None, BString::default());
+        if let Ok(tag) = object.clone().try_into_tag() {
+            let tag_ref = tag.decode()?;
+            time = tag_ref.tagger.map(|s| s.time);
+            subject = gix_object::commit::MessageRef::from_bytes(tag_ref.message)
+                .summary()
+                .into_owned();
+        }
+        let peeled = object.peel_tags_to_end()?;
+        if let Ok(commit) = peeled.clone().try_into_commit() {
+            time = time.or(Some(commit.committer()?.time));
+            if subject.is_empty() {
+                subject = commit.message()?.summary().into_owned();
+            }
+        }
+
+        let upstream = match name.category_and_short_name() {
+            Some((gix_ref::Category::LocalBranch, short_name)) => repo
+                .branch_remote_tracking_ref_name(short_name)
+                .transpose()
+                .ok()
+                .flatten(),
+            _ => None,
+        };
+        let upstream_id = match &upstream {
+            Some(upstream) => repo
+                .try_find_reference(upstream.as_ref())?
+                .and_then(|r| r.into_fully_peeled_id().ok())
+                .map(crate::Id::detach),
+            None => None,
+        };
+        Ok(Some(Entry {
+            is_head: head_name == Some(&name),
+            name,
+            id,
+            kind,
+            peeled_id: peeled.id,
+            peeled_kind: peeled.kind,
+            time,
+            subject,
+            upstream,
+            upstream_id,
+            ahead_behind: None,
+        }))
+    }
+}
+
+/// Return `true` if `name` matches `pattern` as a glob, or if `pattern` is a prefix of `name` ending at a path component.
+pub(crate) fn matches(name: &BStr, pattern: &BStr) -> bool {
+    if pattern.iter().any(|b| matches!(b, b'*' | b'?' | b'[')) {
+        return gix_glob::wildmatch(pattern, name, gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL);
+    }
+    name.strip_prefix(pattern.as_bytes()).map_or(false, |rest| {
+        rest.is_empty() || pattern.ends_with(b"/") || rest.starts_with(b"/")
+    })
+}
+
+pub(crate) fn sort(entries: &mut [Entry], keys: &[SortKey], version_sort: &crate::reference::VersionSort) {
+    let default_key = [SortKey {
+        field: Field::RefName { short: false },
+        by_version: false,
+        reverse: false,
+    }];
+    let keys = if keys.is_empty() { &default_key[..] } else { keys };
+    entries.sort_by(|a, b| {
+        keys.iter().rev().fold(Ordering::Equal, |ordering, key| {
+            ordering.then_with(|| key.compare(a, b, version_sort))
+        })
+    });
+}
+
+fn shorten(name: &FullNameRef, short: bool) -> Cow<'_, BStr> {
+    if short {
+        name.shorten().into()
+    } else {
+        name.as_bstr().into()
+    }
+}
+
+fn track(ahead: usize, behind: usize, short: bool) -> String {
+    match (ahead, behind, short) {
+        (0, 0, true) => "=".into(),
+        (0, 0, false) => String::new(),
+        (_, 0, true) => ">".into(),
+        (0, _, true) => "<".into(),
+        (_, _, true) => "<>".into(),
+        (ahead, 0, false) => format!("[ahead {ahead}]"),
+        (0, behind, false) => format!("[behind {behind}]"),
+        (ahead, behind, false) => format!("[ahead {ahead}, behind {behind}]"),
+    }
+}
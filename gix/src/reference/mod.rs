@@ -9,12 +9,23 @@ pub mod iter;
 pub mod remote;
 
 mod errors;
-pub use errors::{edit, find, head_commit, head_id, head_tree_id, peel};
+#[cfg(feature = "revision")]
+pub use errors::delete_branch;
+pub use errors::{edit, find, head_commit, head_id, head_tree_id, peel, rename_branch, rename_unborn_head};
 
 use crate::ext::ObjectIdExt;
 
 pub mod log;
 
+pub mod decorations;
+pub use decorations::Decorations;
+
+pub mod version_sort;
+pub use version_sort::VersionSort;
+
+#[cfg(feature = "revision")]
+pub mod list;
+
 pub use gix_ref::{Category, Kind};
 
 /// Access
@@ -0,0 +1,181 @@
+//! Sort reference names by the versions they contain, similar to `git tag --sort=version:refname`.
+use std::cmp::Ordering;
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// A comparator for names containing version numbers, like `v1.9` and `v1.10`, which is what `git` calls `versionsort`.
+///
+/// Sequences of digits are compared by their numerical value, so `v1.9` sorts before `v1.10`, while everything else is compared bytewise.
+/// Additionally, names with a configured suffix like `-rc` sort before the name without it, so `v1.0-rc1` comes before `v1.0`.
+///
+/// Use [`Repository::version_sort()`](crate::Repository::version_sort()) to obtain an instance configured with `versionsort.suffix`.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct VersionSort {
+    suffixes: Vec<BString>,
+}
+
+impl VersionSort {
+    /// Create a new instance which sorts names ending in one of `suffixes` before the name without them,
+    /// with suffixes sorting in the order in which they are given.
+    pub fn new(suffixes: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+        VersionSort {
+            suffixes: suffixes.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Return the suffixes used to order pre-release versions.
+    pub fn suffixes(&self) -> &[BString] {
+        &self.suffixes
+    }
+
+    /// Compare `a` with `b` by the versions they contain.
+    pub fn compare(&self, a: &BStr, b: &BStr) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        let (mut c1, mut c2) = (byte_at(a, 0), byte_at(b, 0));
+        let mut pos = 1;
+        let mut state = S_N + class(c1);
+        while c1 == c2 {
+            if c1 == 0 {
+                return Ordering::Equal;
+            }
+            state = NEXT_STATE[state];
+            c1 = byte_at(a, pos);
+            c2 = byte_at(b, pos);
+            pos += 1;
+            state += class(c1);
+        }
+        let diff = c1.cmp(&c2);
+
+        if let Some(ordering) = self.compare_suffixes(a, b, pos - 1) {
+            return ordering;
+        }
+        match RESULT_TYPE[state * 3 + class(c2)] {
+            Outcome::Cmp => diff,
+            Outcome::Len => {
+                // Both continue with digits, the longer number is the greater one, or the first differing digit decides.
+                let mut pos = pos;
+                while byte_at(a, pos).is_ascii_digit() {
+                    if !byte_at(b, pos).is_ascii_digit() {
+                        return Ordering::Greater;
+                    }
+                    pos += 1;
+                }
+                if byte_at(b, pos).is_ascii_digit() {
+                    Ordering::Less
+                } else {
+                    diff
+                }
+            }
+            Outcome::Less => Ordering::Less,
+            Outcome::Greater => Ordering::Greater,
+        }
+    }
+
+    /// Return an ordering if `a` and `b`, which are equal up to `offset`, contain different pre-release suffixes at or around
+    /// `offset`, ordering them by the position of the suffix in our list, and names with suffix before names without one.
+    fn compare_suffixes(&self, a: &BStr, b: &BStr, offset: usize) -> Option<Ordering> {
+        if self.suffixes.is_empty() {
+            return None;
+        }
+        let (mut m1, mut m2) = (SuffixMatch::new(offset), SuffixMatch::new(offset));
+        for (index, suffix) in self.suffixes.iter().enumerate() {
+            let start = offset.saturating_sub(suffix.len());
+            m1.find_better_match(a, suffix.as_bstr(), start, index);
+            m2.find_better_match(b, suffix.as_bstr(), start, index);
+        }
+        match (m1.suffix_index, m2.suffix_index) {
+            (None, None) => None,
+            (a, b) if a == b => None,
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            (Some(_), None) => Some(Ordering::Less),
+            (None, Some(_)) => Some(Ordering::Greater),
+        }
+    }
+}
+
+/// The location of the best suffix found in a name.
+struct SuffixMatch {
+    suffix_index: Option<usize>,
+    start: usize,
+    len: Option<usize>,
+}
+
+impl SuffixMatch {
+    fn new(offset: usize) -> Self {
+        SuffixMatch {
+            suffix_index: None,
+            start: offset,
+            len: None,
+        }
+    }
+
+    /// A better match either starts earlier, or starts at the same position but is longer.
+    fn find_better_match(&mut self, name: &BStr, suffix: &BStr, start: usize, suffix_index: usize) {
+        let end = match self.len {
+            Some(len) if len >= suffix.len() => match self.start.checked_sub(1) {
+                Some(end) => end,
+                None => return,
+            },
+            _ => self.start,
+        };
+        if let Some(pos) = (start..=end).find(|pos| name.get(*pos..).map_or(false, |rest| rest.starts_with(suffix))) {
+            self.suffix_index = Some(suffix_index);
+            self.start = pos;
+            self.len = Some(suffix.len());
+        }
+    }
+}
+
+/// The states of the comparison based on the common prefix seen so far: not within a number, within an integral number,
+/// within a fractional number, i.e. one with leading zeroes, and within leading zeroes.
+const S_N: usize = 0;
+const S_I: usize = 3;
+const S_F: usize = 6;
+const S_Z: usize = 9;
+
+/// What to do once the first differing character was found.
+#[derive(Clone, Copy)]
+enum Outcome {
+    /// Compare the differing characters.
+    Cmp,
+    /// Compare the length of the numbers starting at the differing characters.
+    Len,
+    Less,
+    Greater,
+}
+
+/// Transition to the next state, indexed by the current state plus the [class] of the current character.
+#[rustfmt::skip]
+const NEXT_STATE: [usize; 12] = [
+    /* state    x    d    0  */
+    /* S_N */  S_N, S_I, S_Z,
+    /* S_I */  S_N, S_I, S_I,
+    /* S_F */  S_N, S_F, S_F,
+    /* S_Z */  S_N, S_F, S_Z,
+];
+
+/// The outcome, indexed by the state including the [class] of the first differing character in the first name,
+/// times three, plus the class of the differing character in the second name.
+#[rustfmt::skip]
+const RESULT_TYPE: [Outcome; 36] = {
+    use Outcome::{Cmp, Greater as Gt, Len, Less as Lt};
+    [
+        /* state   x/x  x/d  x/0  d/x  d/d  d/0  0/x  0/d  0/0 */
+        /* S_N */  Cmp, Cmp, Cmp, Cmp, Len, Cmp, Cmp, Cmp, Cmp,
+        /* S_I */  Cmp, Lt,  Lt,  Gt,  Len, Len, Gt,  Len, Len,
+        /* S_F */  Cmp, Cmp, Cmp, Cmp, Cmp, Cmp, Cmp, Cmp, Cmp,
+        /* S_Z */  Cmp, Gt,  Gt,  Lt,  Cmp, Cmp, Lt,  Cmp, Cmp,
+    ]
+};
+
+/// Return `0` for non-digits, `1` for digits other than zero, and `2` for `0`.
+fn class(byte: u8) -> usize {
+    usize::from(byte == b'0') + usize::from(byte.is_ascii_digit())
+}
+
+/// Like indexing, but returns `0` past the end just like a C-string would.
+fn byte_at(name: &BStr, pos: usize) -> u8 {
+    name.get(pos).copied().unwrap_or(0)
+}
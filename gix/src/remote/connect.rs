@@ -73,6 +73,9 @@ impl<'repo> Remote<'repo> {
     /// Note that the `protocol.version` configuration key affects the transport protocol used to connect,
     /// with `2` being the default.
     ///
+    /// If the [connection cache](crate::Repository::connection_cache()) is enabled, an idle connection to the same url
+    /// is reused, and the new connection is kept alive for reuse once it's dropped.
+    ///
     /// The transport used for connection can be configured via `transport_mut().configure()` assuming the actually
     /// used transport is well known. If that's not the case, the transport can be created by hand and passed to
     /// [to_connection_with_transport()][Self::to_connection_with_transport()].
@@ -84,7 +87,11 @@ impl<'repo> Remote<'repo> {
     ) -> Result<Connection<'_, 'repo, Box<dyn Transport + Send>>, Error> {
         let (url, version) = self.sanitized_url_and_version(direction)?;
         #[cfg(feature = "blocking-network-client")]
-        let scheme_is_ssh = url.scheme == gix_url::Scheme::Ssh;
+        if let Some(transport) = self.repo.connection_cache.checkout(&url, version) {
+            return Ok(self.to_connection_with_transport(transport));
+        }
+        #[cfg(feature = "blocking-network-client")]
+        let (scheme_is_ssh, cache_url) = (url.scheme == gix_url::Scheme::Ssh, url.clone());
         let transport = gix_protocol::transport::connect(
             url,
             gix_protocol::transport::client::connect::Options {
@@ -98,6 +105,8 @@ impl<'repo> Remote<'repo> {
             },
         )
         .await?;
+        #[cfg(feature = "blocking-network-client")]
+        let transport = self.repo.connection_cache.keep_alive(&cache_url, version, transport);
         Ok(self.to_connection_with_transport(transport))
     }
 
@@ -0,0 +1,231 @@
+use std::{
+    any::Any,
+    borrow::Cow,
+    path::PathBuf,
+    sync::{Arc, Mutex, Weak},
+};
+
+use gix_protocol::transport::{
+    client::{self, MessageKind, RequestWriter, SetServiceResponse, Transport, TransportWithoutIO, WriteMode},
+    Protocol, Service,
+};
+
+use crate::bstr::{BStr, BString};
+
+/// A cache to keep connections to remotes alive after use so that successive operations like listing references
+/// and fetching can reuse them, which avoids the latency of establishing a new connection each time.
+///
+/// Transports over `http(s)` are kept along with their pool of keep-alive connections, whereas `ssh` connections to the
+/// same host share a master connection if the `ssh` program supports it.
+/// Other transports don't benefit from the cache.
+///
+/// The cache is disabled by default and shared among all clones of the [`Repository`](crate::Repository) it belongs to.
+#[derive(Clone, Default)]
+pub struct ConnectionCache {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    enabled: bool,
+    idle: Vec<(Key, Box<dyn Transport + Send>)>,
+    ssh_control_dir: Option<PathBuf>,
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        if let Some(dir) = self.ssh_control_dir.take() {
+            std::fs::remove_dir_all(dir).ok();
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Key {
+    url: BString,
+    version: Protocol,
+}
+
+/// Lifecycle
+impl ConnectionCache {
+    /// Enable the cache if `toggle` is `true`, or disable it and drop all idle connections otherwise.
+    pub fn set_enabled(&self, toggle: bool) -> &Self {
+        let mut state = self.state();
+        state.enabled = toggle;
+        if !toggle {
+            state.idle.clear();
+        }
+        self
+    }
+
+    /// Drop all idle connections, while keeping the cache enabled if it was.
+    pub fn clear(&self) {
+        self.state().idle.clear();
+    }
+}
+
+/// Access
+impl ConnectionCache {
+    /// Return `true` if connections are kept alive for reuse.
+    pub fn is_enabled(&self) -> bool {
+        self.state().enabled
+    }
+
+    /// Return the amount of idle connections that are ready to be reused.
+    pub fn len(&self) -> usize {
+        self.state().idle.len()
+    }
+
+    /// Return `true` if there is no idle connection.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Utilities
+impl ConnectionCache {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("no panic while holding the lock")
+    }
+
+    /// Return an idle transport connected to `url` using `version`, if there is one.
+    pub(crate) fn checkout(&self, url: &gix_url::Url, version: Protocol) -> Option<Box<dyn Transport + Send>> {
+        let key = Key {
+            url: url.to_bstring(),
+            version,
+        };
+        let mut state = self.state();
+        let pos = state.idle.iter().position(|(idle, _)| *idle == key)?;
+        let (key, transport) = state.idle.swap_remove(pos);
+        Some(self.track(key, transport))
+    }
+
+    /// Wrap `transport` connected to `url` using `version` so that it's returned to the cache once dropped,
+    /// if it doesn't need a persistent connection and the cache is enabled.
+    pub(crate) fn keep_alive(
+        &self,
+        url: &gix_url::Url,
+        version: Protocol,
+        transport: Box<dyn Transport + Send>,
+    ) -> Box<dyn Transport + Send> {
+        if !self.is_enabled() || transport.connection_persists_across_multiple_requests() {
+            return transport;
+        }
+        self.track(
+            Key {
+                url: url.to_bstring(),
+                version,
+            },
+            transport,
+        )
+    }
+
+    fn track(&self, key: Key, transport: Box<dyn Transport + Send>) -> Box<dyn Transport + Send> {
+        Box::new(Pooled {
+            transport: Some(transport),
+            key,
+            cache: Arc::downgrade(&self.state),
+        })
+    }
+
+    /// Return the directory for `ssh` control sockets if the cache is enabled, creating it on first use.
+    pub(crate) fn ssh_control_path(&self) -> Option<PathBuf> {
+        let mut state = self.state();
+        if !state.enabled {
+            return None;
+        }
+        if state.ssh_control_dir.is_none() {
+            state.ssh_control_dir = create_private_dir().ok();
+        }
+        state.ssh_control_dir.clone()
+    }
+}
+
+/// Create a directory that is only accessible by the current user, with a short path as socket paths are limited in length.
+fn create_private_dir() -> std::io::Result<PathBuf> {
+    static COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let count = COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("gix-ssh-{}-{count}", std::process::id()));
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+    Ok(dir)
+}
+
+/// A transport which returns itself to the cache when dropped.
+struct Pooled {
+    transport: Option<Box<dyn Transport + Send>>,
+    key: Key,
+    cache: Weak<Mutex<State>>,
+}
+
+impl Pooled {
+    fn inner(&mut self) -> &mut Box<dyn Transport + Send> {
+        self.transport.as_mut().expect("present until dropped")
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        let (Some(transport), Some(state)) = (self.transport.take(), self.cache.upgrade()) else {
+            return;
+        };
+        let mut state = state.lock().expect("no panic while holding the lock");
+        if state.enabled && !state.idle.iter().any(|(key, _)| *key == self.key) {
+            state.idle.push((self.key.clone(), transport));
+        }
+    }
+}
+
+impl TransportWithoutIO for Pooled {
+    fn set_identity(&mut self, identity: gix_sec::identity::Account) -> Result<(), client::Error> {
+        self.inner().set_identity(identity)
+    }
+
+    fn request(
+        &mut self,
+        write_mode: WriteMode,
+        on_into_read: MessageKind,
+        trace: bool,
+    ) -> Result<RequestWriter<'_>, client::Error> {
+        self.inner().request(write_mode, on_into_read, trace)
+    }
+
+    fn to_url(&self) -> Cow<'_, BStr> {
+        self.transport.as_ref().expect("present until dropped").to_url()
+    }
+
+    fn supported_protocol_versions(&self) -> &[Protocol] {
+        self.transport
+            .as_ref()
+            .expect("present until dropped")
+            .supported_protocol_versions()
+    }
+
+    fn is_dumb(&self) -> bool {
+        self.transport.as_ref().expect("present until dropped").is_dumb()
+    }
+
+    fn connection_persists_across_multiple_requests(&self) -> bool {
+        false
+    }
+
+    fn configure(&mut self, config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.inner().configure(config)
+    }
+}
+
+impl Transport for Pooled {
+    fn handshake<'a>(
+        &mut self,
+        service: Service,
+        extra_parameters: &'a [(&'a str, Option<&'a str>)],
+    ) -> Result<SetServiceResponse<'_>, client::Error> {
+        self.inner().handshake(service, extra_parameters)
+    }
+
+    fn get_file(&mut self, path: &str) -> Result<Option<Box<dyn std::io::BufRead + '_>>, client::Error> {
+        self.inner().get_file(path)
+    }
+}
@@ -1,6 +1,10 @@
 use super::Error;
 use crate::{
-    config::{cache::util::ApplyLeniency, tree::Pack},
+    bstr::BString,
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{gitoxide, Fetch, Key, Pack, Remote, Transfer},
+    },
     Repository,
 };
 
@@ -24,3 +28,54 @@ pub fn pack_index_version(repo: &Repository) -> Result<gix_pack::index::Version,
         .with_leniency(repo.options.lenient_config)?
         .unwrap_or(gix_pack::index::Version::V2))
 }
+
+/// Return the amount of bytes that resolved base objects may occupy in memory while resolving a received pack,
+/// or `None` if there is no limit.
+pub fn pack_resolve_memory_limit(repo: &Repository) -> Result<Option<usize>, Error> {
+    let key = &gitoxide::Objects::PACK_RESOLVE_MEMORY_LIMIT;
+    Ok(repo
+        .config
+        .resolved
+        .integer_filter_by_key(key.logical_name().as_str(), &mut repo.filter_config_section())
+        .map(|value| key.try_into_usize(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::PackResolveMemoryLimit)?
+        .filter(|limit| *limit != 0))
+}
+
+/// Return the amount of objects a received pack must reach to be kept as pack, or `None` if packs are always kept.
+/// Packs with fewer objects are exploded into loose objects instead.
+pub fn unpack_limit(repo: &Repository) -> Result<Option<u32>, Error> {
+    for key in [&Fetch::UNPACK_LIMIT, &Transfer::UNPACK_LIMIT] {
+        let limit = repo
+            .config
+            .resolved
+            .integer_filter_by_key(key.logical_name().as_str(), &mut repo.filter_config_section())
+            .map(|value| key.try_into_unpack_limit(value))
+            .transpose()
+            .with_leniency(repo.options.lenient_config)
+            .map_err(Error::UnpackLimit)?
+            .flatten();
+        if let Some(limit) = limit {
+            return Ok((limit != 0).then_some(limit));
+        }
+    }
+    Ok(Some(100))
+}
+
+/// Return the capabilities configured in `remote.<name>.disabledCapabilities` for `remote`, which must not be requested from it.
+pub fn disabled_capabilities(remote: &crate::Remote<'_>) -> Vec<BString> {
+    remote
+        .name()
+        .and_then(|name| {
+            remote.repo.config.resolved.strings_filter(
+                Remote::DISABLED_CAPABILITIES.section.name(),
+                Some(name.as_bstr()),
+                Remote::DISABLED_CAPABILITIES.name,
+                &mut remote.repo.filter_config_section(),
+            )
+        })
+        .map(|values| values.into_iter().map(std::borrow::Cow::into_owned).collect())
+        .unwrap_or_default()
+}
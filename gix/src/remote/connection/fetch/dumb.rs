@@ -0,0 +1,549 @@
+use std::{
+    collections::VecDeque,
+    io::{Read, Seek, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use gix_hash::ObjectId;
+use gix_object::{Exists, FindExt};
+use gix_protocol::transport::client::Transport;
+
+use crate::{
+    remote::{
+        connection::fetch::{config, receive_pack::MeasureRead},
+        fetch,
+        fetch::{negotiate, outcome, Prepare},
+        Connection,
+    },
+    Repository,
+};
+
+/// The error returned when fetching from a remote that only supports the 'dumb' protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Transport(#[from] gix_protocol::transport::client::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Could not decompress object {id}")]
+    Inflate {
+        id: ObjectId,
+        source: gix_features::zlib::inflate::Error,
+    },
+    #[error("Could not decode the header of loose object {id}")]
+    LooseHeader {
+        id: ObjectId,
+        source: gix_object::decode::LooseHeaderDecodeError,
+    },
+    #[error("Loose object {id} sent by the remote doesn't have the size of {declared} bytes declared in its header")]
+    SizeMismatch { id: ObjectId, declared: u64 },
+    #[error("Object {expected} sent by the remote actually hashed to {actual}")]
+    HashMismatch { expected: ObjectId, actual: ObjectId },
+    #[error("Could not decode object {id} to find the objects it refers to")]
+    Decode {
+        id: ObjectId,
+        source: gix_object::decode::Error,
+    },
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing::Error),
+    #[error("Object {id} could neither be found as loose object nor in any pack of the remote")]
+    ObjectNotFound { id: ObjectId },
+    #[error("Could not open a pack index downloaded from the remote")]
+    OpenIndex(#[from] gix_pack::index::init::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::bundle::write::Error),
+    #[error("Could not prepare objects received as loose objects for writing them into a pack")]
+    CreatePackEntry(#[from] gix_pack::data::output::entry::Error),
+    #[error("Interrupted by user")]
+    Interrupted,
+}
+
+impl gix_protocol::transport::IsSpuriousError for Error {
+    fn is_spurious(&self) -> bool {
+        match self {
+            Error::Transport(err) => err.is_spurious(),
+            Error::Io(err) => err.is_spurious(),
+            _ => false,
+        }
+    }
+}
+
+impl<'remote, 'repo, T> Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// Fetch all objects we don't have by walking the object graph of the remote, which only serves its files, starting at the
+    /// tips we want. Objects are obtained as loose objects if possible, or else the pack containing them is downloaded as a whole.
+    ///
+    /// Objects we received as loose objects are written into a pack of their own, and this pack or, without loose objects,
+    /// the first downloaded pack is reported in the outcome.
+    ///
+    /// Note that shallow repositories can't be fetched into, and that alternates of the remote aren't followed.
+    pub(super) fn receive_dumb(
+        mut self,
+        mut con: Connection<'remote, 'repo, T>,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<fetch::Outcome, fetch::Error> {
+        let _span = gix_trace::coarse!("fetch::Prepare::receive_dumb()");
+        let repo = con.remote.repo;
+        if self.shallow != fetch::Shallow::NoChange || repo.is_shallow() {
+            return Err(fetch::Error::MissingServerFeature {
+                feature: "shallow",
+                description: "remotes that only support the 'dumb' protocol can't send partial histories",
+            });
+        }
+        if self.filter.is_some() && self.require_filter {
+            return Err(fetch::Error::MissingServerFeature {
+                feature: "filter",
+                description: "remotes that only support the 'dumb' protocol can't omit objects",
+            });
+        }
+        if self.ref_map.object_hash != repo.object_hash() {
+            return Err(fetch::Error::IncompatibleObjectHash {
+                local: repo.object_hash(),
+                remote: self.ref_map.object_hash,
+            });
+        }
+
+        let mut performance = outcome::Performance::default();
+        let negotiate_start = Instant::now();
+        let mut negotiator = gix_negotiate::Algorithm::Noop.into_negotiator();
+        let mut graph = repo.revision_graph();
+        let is_ignored = negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map);
+        let action = negotiate::mark_complete_and_common_ref(
+            repo,
+            &mut *negotiator,
+            &mut graph,
+            &self.ref_map,
+            &self.shallow,
+            &is_ignored,
+        )?;
+        performance.negotiate = negotiate_start.elapsed();
+        let (write_pack_bundle, negotiate) = match action {
+            negotiate::Action::NoChange | negotiate::Action::SkipToRefUpdate => (None, None),
+            negotiate::Action::MustNegotiate {
+                remote_ref_target_known,
+            } => {
+                let wants: Vec<_> = self
+                    .ref_map
+                    .mappings
+                    .iter()
+                    .zip(remote_ref_target_known)
+                    .filter(|(mapping, known)| !*known && !is_ignored(mapping))
+                    .filter_map(|(mapping, _)| mapping.remote.as_id().map(ToOwned::to_owned))
+                    .collect();
+                let write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
+                    progress.set_name("walk remote objects".into());
+                    let receive_start = Instant::now();
+                    let mut walk = Walk::new(&mut con.transport, repo)?;
+                    walk.objects(wants, progress, should_interrupt)?;
+                    if matches!(con.remote.fetch_tags, fetch::Tags::Included) {
+                        let tags: Vec<_> = self
+                            .ref_map
+                            .mappings
+                            .iter()
+                            .filter(|mapping| is_ignored(mapping))
+                            .filter_map(|mapping| match &mapping.remote {
+                                fetch::Source::Ref(r) => r.unpack().1.zip(r.unpack().2),
+                                fetch::Source::ObjectId(_) => None,
+                            })
+                            .filter(|(tag, peeled)| walk.is_present(peeled) && !walk.is_present(tag))
+                            .map(|(tag, _peeled)| tag.to_owned())
+                            .collect();
+                        walk.objects(tags, progress, should_interrupt)?;
+                    }
+                    performance.pack_bytes_received = walk.bytes_received;
+                    performance.pack_receive = receive_start.elapsed();
+                    let resolve_start = Instant::now();
+                    let bundle = walk.into_bundle(progress, should_interrupt)?;
+                    performance.pack_resolve = resolve_start.elapsed();
+                    bundle
+                } else {
+                    None
+                };
+                let negotiate = outcome::Negotiate {
+                    graph: graph.detach(),
+                    rounds: Vec::new(),
+                };
+                (write_pack_bundle, Some(negotiate))
+            }
+        };
+        drop(is_ignored);
+        self.update_refs_and_finish(con.remote, write_pack_bundle, negotiate, performance)
+    }
+}
+
+/// A pack of the remote, whose index we downloaded to learn which objects it contains.
+struct RemotePack {
+    /// The file name of the pack, like `pack-<hash>.pack`.
+    name: String,
+    index: gix_pack::index::File,
+    /// The temporary file holding the index, which is deleted once we are done with it.
+    _index_file: gix_tempfile::Handle<gix_tempfile::handle::Writable>,
+}
+
+/// A pack that objects received as loose objects are streamed into, with a header that is completed once all objects are known.
+struct LoosePack {
+    file: std::io::BufWriter<gix_tempfile::Handle<gix_tempfile::handle::Writable>>,
+    num_objects: u32,
+}
+
+/// Download all objects reachable from a set of tips that we don't have yet.
+struct Walk<'a, T> {
+    transport: &'a mut T,
+    repo: &'a Repository,
+    pack_dir: PathBuf,
+    options: gix_pack::bundle::write::Options,
+    /// All objects we encountered so far, to process each only once.
+    seen: gix_hashtable::HashSet,
+    /// The pack that objects received as loose objects are written to, or `None` if there was no such object yet.
+    loose: Option<LoosePack>,
+    /// The packs of the remote, or `None` if we didn't need them yet.
+    remote_packs: Option<Vec<RemotePack>>,
+    /// The packs we downloaded and wrote into our object database so far, along with their indices.
+    downloaded: Vec<(gix_pack::bundle::write::Outcome, gix_pack::index::File)>,
+    /// The amount of bytes of loose objects and packs received so far.
+    bytes_received: u64,
+}
+
+impl<'a, T> Walk<'a, T>
+where
+    T: Transport,
+{
+    fn new(transport: &'a mut T, repo: &'a Repository) -> Result<Self, fetch::Error> {
+        Ok(Walk {
+            transport,
+            repo,
+            pack_dir: repo.objects.store_ref().path().join("pack"),
+            options: gix_pack::bundle::write::Options {
+                thread_limit: config::index_threads(repo)?,
+                index_version: config::pack_index_version(repo)?,
+                iteration_mode: gix_pack::data::input::Mode::Verify,
+                object_hash: repo.object_hash(),
+                resolve_memory_limit: config::pack_resolve_memory_limit(repo)?,
+            },
+            seen: Default::default(),
+            loose: None,
+            remote_packs: None,
+            downloaded: Vec::new(),
+            bytes_received: 0,
+        })
+    }
+
+    /// Return `true` if `id` was received or is present in our object database.
+    fn is_present(&self, id: &gix_hash::oid) -> bool {
+        self.seen.contains(id) || self.repo.objects.exists(id)
+    }
+
+    /// Obtain all objects reachable from `tips`, assuming that objects we already have are complete, i.e. that
+    /// all objects reachable from them are present as well.
+    fn objects(
+        &mut self,
+        tips: Vec<ObjectId>,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(), Error> {
+        let mut queue: VecDeque<_> = tips.into_iter().filter(|id| self.seen.insert(*id)).collect();
+        let mut buf = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+            let loose_data;
+            let (kind, data) = if self.downloaded.iter().any(|(_, index)| index.lookup(id).is_some()) {
+                let obj = self.repo.objects.find(&id, &mut buf)?;
+                (obj.kind, obj.data)
+            } else if self.repo.objects.exists(&id) {
+                continue;
+            } else if let Some((kind, data)) = self.loose_object(id)? {
+                self.write_loose_object(id, kind, &data)?;
+                loose_data = data;
+                (kind, loose_data.as_slice())
+            } else {
+                self.download_pack_with(id, progress, should_interrupt)?;
+                let obj = self.repo.objects.find(&id, &mut buf)?;
+                (obj.kind, obj.data)
+            };
+            progress.inc();
+
+            let decode_err = |source| Error::Decode { id, source };
+            let mut enqueue = |id: ObjectId| {
+                if self.seen.insert(id) {
+                    queue.push_back(id);
+                }
+            };
+            match kind {
+                gix_object::Kind::Commit => {
+                    let commit = gix_object::CommitRef::from_bytes(data).map_err(decode_err)?;
+                    enqueue(commit.tree());
+                    commit.parents().for_each(&mut enqueue);
+                }
+                gix_object::Kind::Tree => {
+                    for entry in gix_object::TreeRefIter::from_bytes(data) {
+                        let entry = entry.map_err(decode_err)?;
+                        // Submodule commits aren't part of this repository.
+                        if !entry.mode.is_commit() {
+                            enqueue(entry.oid.to_owned());
+                        }
+                    }
+                }
+                gix_object::Kind::Tag => {
+                    enqueue(gix_object::TagRef::from_bytes(data).map_err(decode_err)?.target());
+                }
+                gix_object::Kind::Blob => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Try to obtain `id` as loose object of the remote, and return it decompressed and verified, or `None` if it doesn't exist.
+    fn loose_object(&mut self, id: ObjectId) -> Result<Option<(gix_object::Kind, Vec<u8>)>, Error> {
+        let hex = id.to_hex().to_string();
+        let mut compressed = Vec::new();
+        match self
+            .transport
+            .get_file(&format!("objects/{}/{}", &hex[..2], &hex[2..]))?
+        {
+            Some(mut file) => file.read_to_end(&mut compressed)?,
+            None => return Ok(None),
+        };
+        self.bytes_received += compressed.len() as u64;
+        decode_loose_object(id, &compressed).map(Some)
+    }
+
+    /// Append the object `id` of `kind` with `data` to the pack of objects received as loose objects, creating it if needed.
+    fn write_loose_object(&mut self, id: ObjectId, kind: gix_object::Kind, data: &[u8]) -> Result<(), Error> {
+        let pack = match self.loose.as_mut() {
+            Some(pack) => pack,
+            None => {
+                std::fs::create_dir_all(&self.pack_dir)?;
+                let mut file = std::io::BufWriter::new(gix_tempfile::new(
+                    &self.pack_dir,
+                    gix_tempfile::ContainingDirectory::Exists,
+                    gix_tempfile::AutoRemove::Tempfile,
+                )?);
+                // The amount of objects is only known once the walk is done, when the header is written again.
+                file.write_all(&gix_pack::data::header::encode(gix_pack::data::Version::V2, 0))?;
+                self.loose.insert(LoosePack { file, num_objects: 0 })
+            }
+        };
+        let entry = gix_pack::data::output::Entry::from_data(
+            &gix_pack::data::output::Count::from_data(id, None),
+            &gix_object::Data::new(kind, data),
+            gix_features::zlib::stream::deflate::LEVEL_FAST,
+        )?;
+        entry
+            .to_entry_header(gix_pack::data::Version::V2, |_| {
+                unreachable!("objects received as loose objects are never deltified")
+            })
+            .write_to(entry.decompressed_size as u64, &mut pack.file)?;
+        pack.file.write_all(&entry.compressed_data)?;
+        pack.num_objects += 1;
+        Ok(())
+    }
+
+    /// Download the pack of the remote that contains `id` into our object database.
+    fn download_pack_with(
+        &mut self,
+        id: ObjectId,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(), Error> {
+        if self.remote_packs.is_none() {
+            self.remote_packs = Some(self.remote_packs()?);
+        }
+        let remote_packs = self.remote_packs.as_mut().expect("just set");
+        let pos = remote_packs
+            .iter()
+            .position(|pack| pack.index.lookup(id).is_some())
+            .ok_or(Error::ObjectNotFound { id })?;
+        let pack = remote_packs.remove(pos);
+
+        progress.info(format!("Downloading {}", pack.name));
+        let file = self
+            .transport
+            .get_file(&format!("objects/pack/{}", pack.name))?
+            .ok_or(Error::ObjectNotFound { id })?;
+        let mut file = MeasureRead::new(file);
+        let outcome = gix_pack::Bundle::write_to_directory(
+            &mut file,
+            Some(&self.pack_dir),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            self.options.clone(),
+        )?;
+        self.bytes_received += file.bytes_received();
+        self.downloaded.push((outcome, pack.index));
+        Ok(())
+    }
+
+    /// Download the indices of all packs listed in `objects/info/packs` of the remote.
+    fn remote_packs(&mut self) -> Result<Vec<RemotePack>, Error> {
+        let mut list = Vec::new();
+        match self.transport.get_file("objects/info/packs")? {
+            Some(mut file) => file.read_to_end(&mut list)?,
+            None => return Ok(Vec::new()),
+        };
+        std::fs::create_dir_all(&self.pack_dir)?;
+
+        let mut packs = Vec::new();
+        for name in crate::bstr::ByteSlice::lines(list.as_slice())
+            .filter_map(|line| line.strip_prefix(b"P "))
+            .filter_map(|name| std::str::from_utf8(name).ok())
+            .filter(|name| name.starts_with("pack-") && name.ends_with(".pack"))
+        {
+            let index_name = format!("{}.idx", name.trim_end_matches(".pack"));
+            let Some(mut remote_index) = self.transport.get_file(&format!("objects/pack/{index_name}"))? else {
+                continue;
+            };
+            let mut index_file = gix_tempfile::new(
+                &self.pack_dir,
+                gix_tempfile::ContainingDirectory::Exists,
+                gix_tempfile::AutoRemove::Tempfile,
+            )?;
+            std::io::copy(&mut remote_index, &mut index_file)?;
+            let path = index_file
+                .with_mut(|file| file.path().to_owned())
+                .expect("the tempfile wasn't removed yet");
+            packs.push(RemotePack {
+                name: name.to_owned(),
+                index: gix_pack::index::File::at(path, self.repo.object_hash())?,
+                _index_file: index_file,
+            });
+        }
+        Ok(packs)
+    }
+
+    /// Write all objects that we received as loose objects into a pack, and return it or the first downloaded pack as
+    /// the pack to report. The `.keep` files of all other downloaded packs are removed.
+    fn into_bundle(
+        mut self,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Option<gix_pack::bundle::write::Outcome>, Error> {
+        let mut bundles: Vec<_> = self.downloaded.drain(..).map(|(outcome, _)| outcome).collect();
+        if let Some(LoosePack { file, num_objects }) = self.loose.take() {
+            let mut file = file.into_inner().map_err(std::io::IntoInnerError::into_error)?;
+            file.rewind()?;
+            file.write_all(&gix_pack::data::header::encode(
+                gix_pack::data::Version::V2,
+                num_objects,
+            ))?;
+            file.rewind()?;
+            let mut hash = gix_features::hash::Write::new(std::io::sink(), self.repo.object_hash());
+            std::io::copy(&mut file, &mut hash)?;
+            file.write_all(&hash.hash.digest())?;
+            file.rewind()?;
+
+            let outcome = gix_pack::Bundle::write_to_directory(
+                &mut std::io::BufReader::new(file),
+                Some(&self.pack_dir),
+                progress,
+                should_interrupt,
+                None::<gix_object::find::Never>,
+                self.options.clone(),
+            )?;
+            bundles.insert(0, outcome);
+        }
+
+        let mut bundles = bundles.into_iter();
+        let first = bundles.next();
+        for mut bundle in bundles {
+            if let Some(path) = bundle.keep_path.take() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(first)
+    }
+}
+
+/// Decompress the loose object `id` from its `compressed` data and verify it.
+///
+/// The size declared in the header isn't trusted, so data is decompressed in chunks and only up to the declared size.
+fn decode_loose_object(id: ObjectId, compressed: &[u8]) -> Result<(gix_object::Kind, Vec<u8>), Error> {
+    let inflate_err = |source| Error::Inflate { id, source };
+    let mut inflate = gix_features::zlib::Inflate::default();
+    let mut header = [0u8; 64];
+    let (_status, _consumed, written) = inflate.once(compressed, &mut header).map_err(inflate_err)?;
+    let (kind, size, header_size) =
+        gix_object::decode::loose_header(&header[..written]).map_err(|source| Error::LooseHeader { id, source })?;
+    let size_mismatch = || Error::SizeMismatch { id, declared: size };
+    let expected_len = usize::try_from(size)
+        .ok()
+        .and_then(|size| size.checked_add(header_size))
+        .ok_or_else(size_mismatch)?;
+
+    inflate.reset();
+    let mut data = Vec::new();
+    let mut buf = [0u8; 8192];
+    let mut input = compressed;
+    loop {
+        let (status, consumed, written) = inflate.once(input, &mut buf).map_err(inflate_err)?;
+        input = &input[consumed..];
+        if data.len() + written > expected_len {
+            return Err(size_mismatch());
+        }
+        data.extend_from_slice(&buf[..written]);
+        match status {
+            gix_features::zlib::Status::StreamEnd => break,
+            status if consumed == 0 && written == 0 => {
+                return Err(inflate_err(gix_features::zlib::inflate::Error::Status(status)))
+            }
+            _ => {}
+        }
+    }
+    if data.len() != expected_len {
+        return Err(size_mismatch());
+    }
+    data.drain(..header_size);
+
+    let actual = gix_object::compute_hash(id.kind(), kind, &data);
+    if actual != id {
+        return Err(Error::HashMismatch { expected: id, actual });
+    }
+    Ok((kind, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_loose_object, Error};
+
+    fn loose_object(header_and_data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut deflate = gix_features::zlib::stream::deflate::Write::new(&mut out);
+        std::io::Write::write_all(&mut deflate, header_and_data).expect("in-memory");
+        std::io::Write::flush(&mut deflate).expect("in-memory");
+        drop(deflate);
+        out
+    }
+
+    #[test]
+    fn valid_objects_are_decoded() {
+        let id = gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, b"hello");
+        let (kind, data) = decode_loose_object(id, &loose_object(b"blob 5\0hello")).expect("valid");
+        assert_eq!(kind, gix_object::Kind::Blob);
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn declared_sizes_are_not_trusted() {
+        let id = gix_hash::Kind::Sha1.null();
+        for (header_and_data, declared) in [
+            (&b"blob 18446744073709551615\0hello"[..], u64::MAX),
+            (b"blob 4294967296\0hello", 1 << 32),
+            (b"blob 2\0hello", 2),
+            (b"blob 6\0hello", 6),
+        ] {
+            let err = decode_loose_object(id, &loose_object(header_and_data)).unwrap_err();
+            assert!(
+                matches!(err, Error::SizeMismatch { declared: actual, .. } if actual == declared),
+                "{err:?}"
+            );
+        }
+    }
+}
@@ -8,6 +8,10 @@ pub enum Error {
     PackThreads(#[from] config::unsigned_integer::Error),
     #[error("The value to configure the pack index version should be 1 or 2")]
     PackIndexVersion(#[from] config::key::GenericError),
+    #[error("The value to configure the unpack limit should be a positive amount of objects, 0 to never unpack, or negative to be unset")]
+    UnpackLimit(#[source] config::unsigned_integer::Error),
+    #[error("The value to configure the memory limit for resolving packs should be a positive amount of bytes, or 0 for no limit")]
+    PackResolveMemoryLimit(#[source] config::unsigned_integer::Error),
     #[error("Could not decode server reply")]
     FetchResponse(#[from] gix_protocol::fetch::response::Error),
     #[error("Cannot fetch from a remote that uses {remote} while local repository uses {local} for object hashes")]
@@ -22,6 +26,8 @@ pub enum Error {
     #[error(transparent)]
     WritePack(#[from] gix_pack::bundle::write::Error),
     #[error(transparent)]
+    ExplodePack(#[from] gix_odb::loose::explode::pack::Error),
+    #[error(transparent)]
     UpdateRefs(#[from] super::refs::update::Error),
     #[error("Failed to remove .keep file at \"{}\"", path.display())]
     RemovePackKeepFile {
@@ -45,6 +51,11 @@ pub enum Error {
     RejectShallowRemote,
     #[error(transparent)]
     NegotiationAlgorithmConfig(#[from] config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    NetworkRetry(#[from] config::network_retry::Error),
+    #[cfg(feature = "blocking-network-client")]
+    #[error("Could not fetch from a remote that only supports the 'dumb' protocol")]
+    Dumb(#[from] super::dumb::Error),
 }
 
 impl gix_protocol::transport::IsSpuriousError for Error {
@@ -52,6 +63,8 @@ impl gix_protocol::transport::IsSpuriousError for Error {
         match self {
             Error::FetchResponse(err) => err.is_spurious(),
             Error::Client(err) => err.is_spurious(),
+            #[cfg(feature = "blocking-network-client")]
+            Error::Dumb(err) => err.is_spurious(),
             _ => false,
         }
     }
@@ -74,10 +74,36 @@ pub struct Outcome {
     pub ref_map: RefMap,
     /// The status of the operation to indicate what happened.
     pub status: Status,
+    /// Timings and byte counts of the phases of the operation.
+    pub performance: outcome::Performance,
 }
 
 /// Additional types related to the outcome of a fetch operation.
 pub mod outcome {
+    use std::time::Duration;
+
+    /// Timings and byte counts for each phase of a fetch, to learn where time was spent without having to
+    /// interpret progress messages.
+    ///
+    /// Phases that didn't happen, like receiving a pack if there was no change, are left at zero.
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Performance {
+        /// The time it took to figure out which objects to request, including all rounds of negotiation with the remote,
+        /// until it started sending the pack.
+        pub negotiate: Duration,
+        /// The amount of bytes of pack data received from the remote.
+        pub pack_bytes_received: u64,
+        /// The time it took to receive the pack, from the start of the transfer to the last byte.
+        ///
+        /// Note that objects are decompressed and hashed while the pack is received.
+        /// If the pack had to be requested again, only the last attempt is accounted for.
+        pub pack_receive: Duration,
+        /// The time it took to resolve all deltas and write the pack index after the pack was received.
+        pub pack_resolve: Duration,
+        /// The time it took to update local references.
+        pub update_refs: Duration,
+    }
+
     /// Information about the negotiation phase of a fetch.
     ///
     /// Note that negotiation can happen even if no pack is ultimately produced.
@@ -186,6 +212,20 @@ where
             return Err(prepare::Error::MissingRefSpecs);
         }
         let ref_map = self.ref_map_inner(progress, options).await?;
+        // Like `git`, keep fetching from promisor remotes with the filter used when cloning.
+        let filter = self.remote.name().and_then(|name| {
+            use crate::config::tree::{Key, Remote};
+            let repo = self.remote.repo;
+            repo.config
+                .resolved
+                .string_filter(
+                    "remote",
+                    Some(name.as_bstr()),
+                    Remote::PARTIAL_CLONE_FILTER.name(),
+                    &mut repo.filter_config_section(),
+                )
+                .map(|spec| spec.to_string())
+        });
         Ok(Prepare {
             con: Some(self),
             ref_map,
@@ -193,6 +233,9 @@ where
             reflog_message: None,
             write_packed_refs: WritePackedRefs::Never,
             shallow: Default::default(),
+            keep_pack: false,
+            filter,
+            require_filter: false,
         })
     }
 }
@@ -205,9 +248,41 @@ where
     pub fn ref_map(&self) -> &RefMap {
         &self.ref_map
     }
+
+    /// Return the `ref_map` to adjust the mappings that are fetched and used to update local references.
+    #[cfg(any(feature = "async-network-client-async-std", feature = "blocking-network-client"))]
+    pub(crate) fn ref_map_mut(&mut self) -> &mut RefMap {
+        &mut self.ref_map
+    }
+
+    /// Return the features, i.e. the capabilities, that will be requested from the remote when receiving the pack,
+    /// which is useful for debugging.
+    ///
+    /// They are negotiated from the capabilities the remote advertised during the handshake and the ones we support,
+    /// without those disabled in `remote.<name>.disabledCapabilities`.
+    pub fn negotiated_features(&self) -> Vec<gix_protocol::command::Feature> {
+        let con = self.con.as_ref().expect("present until the pack is received");
+        fetch_features(con.remote, &self.ref_map.handshake)
+    }
+}
+
+/// Return the features to request from `remote` when fetching, based on the capabilities advertised in `handshake`.
+fn fetch_features(
+    remote: &crate::Remote<'_>,
+    handshake: &gix_protocol::handshake::Outcome,
+) -> Vec<gix_protocol::command::Feature> {
+    let mut features =
+        gix_protocol::Command::Fetch.default_features(handshake.server_protocol_version, &handshake.capabilities);
+    let disabled = config::disabled_capabilities(remote);
+    features.retain(|(name, _)| !disabled.iter().any(|disabled| disabled == name));
+    features.push(remote.repo.config.user_agent_tuple());
+    features
 }
 
 mod config;
+///
+#[cfg(feature = "blocking-network-client")]
+pub mod dumb;
 mod receive_pack;
 ///
 #[path = "update_refs/mod.rs"]
@@ -224,6 +299,10 @@ where
     reflog_message: Option<RefLogMessage>,
     write_packed_refs: WritePackedRefs,
     shallow: remote::fetch::Shallow,
+    keep_pack: bool,
+    filter: Option<String>,
+    /// If `false`, the `filter` is dropped if the server doesn't support it, as it wasn't explicitly requested.
+    require_filter: bool,
 }
 
 /// Builder
@@ -265,6 +344,35 @@ where
         self.shallow = shallow;
         self
     }
+
+    /// If enabled, always keep the received pack, even if it has fewer objects than configured in `fetch.unpackLimit` or
+    /// `transfer.unpackLimit`, in which case it would otherwise be exploded into loose objects.
+    ///
+    /// This is what `git` does when cloning.
+    pub fn with_keep_pack(mut self, enabled: bool) -> Self {
+        self.keep_pack = enabled;
+        self
+    }
+
+    /// Ask the remote to omit objects according to the filter `spec`, like `blob:none` or `blob:limit=1m`, to receive a
+    /// partial pack just like `git fetch --filter=<spec>` does.
+    ///
+    /// Note that the remote has to support filtering. Objects that were omitted can later be obtained from promisor remotes
+    /// with [`Repository::fetch_missing_objects()`](crate::Repository::fetch_missing_objects()).
+    ///
+    /// If not set, the filter configured in `remote.<name>.partialCloneFilter` is used if the remote supports it.
+    pub fn with_filter(mut self, spec: impl Into<String>) -> Self {
+        self.filter = Some(spec.into());
+        self.require_filter = true;
+        self
+    }
+
+    /// Like [`with_filter()`](Self::with_filter()), but don't fail if the remote can't filter objects.
+    pub(crate) fn with_optional_filter(mut self, spec: impl Into<String>) -> Self {
+        self.filter = Some(spec.into());
+        self.require_filter = false;
+        self
+    }
 }
 
 impl<'remote, 'repo, T> Drop for Prepare<'remote, 'repo, T>
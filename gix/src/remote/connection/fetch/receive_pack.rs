@@ -1,12 +1,14 @@
 use std::{
     ops::DerefMut,
     sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
 };
 
+use gix_object::Exists;
 use gix_odb::store::RefreshMode;
 use gix_protocol::{
     fetch::Arguments,
-    transport::{client::Transport, packetline::read::ProgressAction},
+    transport::{client::Transport, packetline::read::ProgressAction, IsSpuriousError},
 };
 
 use crate::{
@@ -71,6 +73,13 @@ where
     /// ### Configuration
     ///
     /// - `gitoxide.userAgent` is read to obtain the application user agent for git servers and for HTTP servers as well.
+    /// - `remote.<name>.disabledCapabilities` lists capabilities that are never requested from the remote, even if it supports them.
+    /// - `gitoxide.http.maxRetries` and `gitoxide.http.retryDelay` control how often and when requests over stateless transports
+    ///    like HTTP are repeated after spurious failures. If receiving the pack fails, the entire pack is requested again as the
+    ///    protocol can't resume partial downloads.
+    /// - `fetch.unpackLimit` and `transfer.unpackLimit` determine the amount of objects a pack needs to have to be kept as pack.
+    ///    Smaller packs are exploded into loose objects, and the returned `write_pack_bundle` has no paths in that case.
+    ///    Use [`with_keep_pack()`][Self::with_keep_pack()] to always keep the pack.
     ///
     #[gix_protocol::maybe_async::maybe_async]
     pub async fn receive<P>(self, mut progress: P, should_interrupt: &AtomicBool) -> Result<Outcome, Error>
@@ -90,17 +99,16 @@ where
     ) -> Result<Outcome, Error> {
         let _span = gix_trace::coarse!("fetch::Prepare::receive()");
         let mut con = self.con.take().expect("receive() can only be called once");
+        #[cfg(feature = "blocking-network-client")]
+        if con.transport.is_dumb() {
+            return self.receive_dumb(con, progress, should_interrupt);
+        }
 
         let handshake = &self.ref_map.handshake;
         let protocol_version = handshake.server_protocol_version;
 
-        let fetch = gix_protocol::Command::Fetch;
         let repo = con.remote.repo;
-        let fetch_features = {
-            let mut f = fetch.default_features(protocol_version, &handshake.capabilities);
-            f.push(repo.config.user_agent_tuple());
-            f
-        };
+        let fetch_features = super::fetch_features(con.remote, handshake);
 
         gix_protocol::fetch::Response::check_required_features(protocol_version, &fetch_features)?;
         let sideband_all = fetch_features.iter().any(|(n, _)| *n == "sideband-all");
@@ -117,6 +125,18 @@ where
             arguments.use_include_tag();
         }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
+        if let Some(spec) = &self.filter {
+            if arguments.can_use_filter() {
+                arguments.filter(spec);
+            } else if self.require_filter {
+                return Err(Error::MissingServerFeature {
+                    feature: "filter",
+                    description: "partial clones need the server to omit objects, otherwise all objects would be sent",
+                });
+            } else {
+                gix_trace::warn!("filtering not recognized by server, ignoring");
+            }
+        }
 
         if self.ref_map.object_hash != repo.object_hash() {
             return Err(Error::IncompatibleObjectHash {
@@ -125,9 +145,12 @@ where
             });
         }
 
-        let negotiate_span = gix_trace::detail!(
+        let mut performance = outcome::Performance::default();
+        let negotiate_start = Instant::now();
+        let negotiate_span = gix_trace::coarse!(
             "negotiate",
-            protocol_version = self.ref_map.handshake.server_protocol_version as usize
+            protocol_version = self.ref_map.handshake.server_protocol_version as usize,
+            rounds = gix_trace::field::Empty,
         );
         let mut negotiator = repo
             .config
@@ -156,11 +179,12 @@ where
             negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
         )?;
         let mut previous_response = None::<gix_protocol::fetch::Response>;
-        let (mut write_pack_bundle, negotiate) = match &action {
+        let (write_pack_bundle, negotiate) = match &action {
             negotiate::Action::NoChange | negotiate::Action::SkipToRefUpdate => {
                 gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
                     .await
                     .ok();
+                performance.negotiate = negotiate_start.elapsed();
                 (None, None)
             }
             negotiate::Action::MustNegotiate {
@@ -181,128 +205,233 @@ where
                 let mut seen_ack = false;
                 let mut in_vain = 0;
                 let mut common = is_stateless.then(Vec::new);
-                let mut reader = 'negotiation: loop {
-                    let _round = gix_trace::detail!("negotiate round", round = rounds.len() + 1);
-                    progress.step();
-                    progress.set_name(format!("negotiate (round {})", rounds.len() + 1));
-
-                    let is_done = match negotiate::one_round(
-                        negotiator.deref_mut(),
-                        &mut graph,
-                        haves_to_send,
-                        &mut arguments,
-                        previous_response.as_ref(),
-                        common.as_mut(),
-                    ) {
-                        Ok((haves_sent, ack_seen)) => {
-                            if ack_seen {
-                                in_vain = 0;
+                // Only stateless transports can repeat requests, as the server doesn't keep any state between them.
+                let can_retry = |err: &dyn IsSpuriousError| {
+                    is_stateless && err.is_spurious() && !should_interrupt.load(Ordering::Relaxed)
+                };
+                // The request that led to the pack, along with its `is_done` flag, to repeat it if receiving the pack fails.
+                let mut last_request: (Arguments, bool);
+                let mut replay_request = None;
+                let retry = repo.config.network_retry()?;
+                let mut pack_backoff = retry.backoff();
+                let write_pack_bundle = 'pack: loop {
+                    let mut reader = 'negotiation: loop {
+                        let (request, is_done) = match replay_request.take() {
+                            Some(request) => request,
+                            None => {
+                                let _round = gix_trace::detail!("negotiate round", round = rounds.len() + 1);
+                                progress.step();
+                                progress.set_name(format!("negotiate (round {})", rounds.len() + 1));
+
+                                let is_done = match negotiate::one_round(
+                                    negotiator.deref_mut(),
+                                    &mut graph,
+                                    haves_to_send,
+                                    &mut arguments,
+                                    previous_response.as_ref(),
+                                    common.as_mut(),
+                                ) {
+                                    Ok((haves_sent, ack_seen)) => {
+                                        if ack_seen {
+                                            in_vain = 0;
+                                        }
+                                        seen_ack |= ack_seen;
+                                        in_vain += haves_sent;
+                                        rounds.push(outcome::negotiate::Round {
+                                            haves_sent,
+                                            in_vain,
+                                            haves_to_send,
+                                            previous_response_had_at_least_one_in_common: ack_seen,
+                                        });
+                                        let is_done = haves_sent != haves_to_send || (seen_ack && in_vain >= 256);
+                                        haves_to_send = gix_negotiate::window_size(is_stateless, Some(haves_to_send));
+                                        is_done
+                                    }
+                                    Err(err) => {
+                                        gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
+                                            .await
+                                            .ok();
+                                        return Err(err.into());
+                                    }
+                                };
+                                (arguments.clone(), is_done)
                             }
-                            seen_ack |= ack_seen;
-                            in_vain += haves_sent;
-                            rounds.push(outcome::negotiate::Round {
-                                haves_sent,
-                                in_vain,
-                                haves_to_send,
-                                previous_response_had_at_least_one_in_common: ack_seen,
-                            });
-                            let is_done = haves_sent != haves_to_send || (seen_ack && in_vain >= 256);
-                            haves_to_send = gix_negotiate::window_size(is_stateless, Some(haves_to_send));
-                            is_done
-                        }
-                        Err(err) => {
-                            gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
-                                .await
-                                .ok();
-                            return Err(err.into());
+                        };
+
+                        let mut backoff = retry.backoff();
+                        let (mut reader, response) = loop {
+                            let mut attempt = request.clone();
+                            let res = match attempt.send(&mut con.transport, is_done).await {
+                                Ok(mut reader) => {
+                                    if sideband_all {
+                                        setup_remote_progress(progress, &mut reader, should_interrupt);
+                                    }
+                                    gix_protocol::fetch::Response::from_line_reader(
+                                        protocol_version,
+                                        &mut reader,
+                                        is_done,
+                                        !is_done,
+                                    )
+                                    .await
+                                    .map(|response| (reader, response))
+                                    .map_err(Error::from)
+                                }
+                                Err(err) => Err(err.into()),
+                            };
+                            match res {
+                                Ok(res) => {
+                                    arguments = attempt;
+                                    break res;
+                                }
+                                Err(err) => match backoff.next().filter(|_| can_retry(&err)) {
+                                    Some(delay) => {
+                                        progress.info(format!("Retrying request in {delay:?} after error: {err}"));
+                                        remote::Retry::wait(delay);
+                                    }
+                                    None => return Err(err),
+                                },
+                            }
+                        };
+                        let has_pack = response.has_pack();
+                        previous_response = Some(response);
+                        if has_pack {
+                            // Requests for the pack that are repeated after an error don't negotiate again.
+                            if performance.negotiate.is_zero() {
+                                performance.negotiate = negotiate_start.elapsed();
+                            }
+                            last_request = (request, is_done);
+                            progress.step();
+                            progress.set_name("receiving pack".into());
+                            if !sideband_all {
+                                setup_remote_progress(progress, &mut reader, should_interrupt);
+                            }
+                            break 'negotiation reader;
                         }
                     };
-                    let mut reader = arguments.send(&mut con.transport, is_done).await?;
-                    if sideband_all {
-                        setup_remote_progress(progress, &mut reader, should_interrupt);
-                    }
-                    let response = gix_protocol::fetch::Response::from_line_reader(
-                        protocol_version,
-                        &mut reader,
-                        is_done,
-                        !is_done,
-                    )
-                    .await?;
-                    let has_pack = response.has_pack();
-                    previous_response = Some(response);
-                    if has_pack {
-                        progress.step();
-                        progress.set_name("receiving pack".into());
-                        if !sideband_all {
-                            setup_remote_progress(progress, &mut reader, should_interrupt);
-                        }
-                        break 'negotiation reader;
-                    }
-                };
-                let graph = graph.detach();
-                drop(graph_repo);
-                drop(negotiate_span);
 
-                let previous_response = previous_response.expect("knowledge of a pack means a response was received");
-                if !previous_response.shallow_updates().is_empty() && shallow_lock.is_none() {
-                    let reject_shallow_remote = repo
-                        .config
-                        .resolved
-                        .boolean_filter_by_key("clone.rejectShallow", &mut repo.filter_config_section())
-                        .map(|val| Clone::REJECT_SHALLOW.enrich_error(val))
-                        .transpose()?
-                        .unwrap_or(false);
-                    if reject_shallow_remote {
-                        return Err(Error::RejectShallowRemote);
+                    let response = previous_response
+                        .as_ref()
+                        .expect("knowledge of a pack means a response was received");
+                    if !response.shallow_updates().is_empty() && shallow_lock.is_none() {
+                        let reject_shallow_remote = repo
+                            .config
+                            .resolved
+                            .boolean_filter_by_key("clone.rejectShallow", &mut repo.filter_config_section())
+                            .map(|val| Clone::REJECT_SHALLOW.enrich_error(val))
+                            .transpose()?
+                            .unwrap_or(false);
+                        if reject_shallow_remote {
+                            return Err(Error::RejectShallowRemote);
+                        }
+                        shallow_lock = acquire_shallow_lock(repo).map(Some)?;
                     }
-                    shallow_lock = acquire_shallow_lock(repo).map(Some)?;
-                }
 
-                let options = gix_pack::bundle::write::Options {
-                    thread_limit: config::index_threads(repo)?,
-                    index_version: config::pack_index_version(repo)?,
-                    iteration_mode: gix_pack::data::input::Mode::Verify,
-                    object_hash: con.remote.repo.object_hash(),
-                };
+                    let options = gix_pack::bundle::write::Options {
+                        thread_limit: config::index_threads(repo)?,
+                        index_version: config::pack_index_version(repo)?,
+                        iteration_mode: gix_pack::data::input::Mode::Verify,
+                        object_hash: con.remote.repo.object_hash(),
+                        resolve_memory_limit: config::pack_resolve_memory_limit(repo)?,
+                    };
 
-                let write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
-                    #[cfg(not(feature = "async-network-client"))]
-                    let mut rd = reader;
-                    #[cfg(feature = "async-network-client")]
-                    let mut rd = gix_protocol::futures_lite::io::BlockOn::new(reader);
-                    let res = gix_pack::Bundle::write_to_directory(
-                        &mut rd,
-                        Some(&repo.objects.store_ref().path().join("pack")),
-                        progress,
-                        should_interrupt,
-                        Some(Box::new({
+                    let unpack_limit = if self.keep_pack {
+                        None
+                    } else {
+                        config::unpack_limit(repo)?
+                    };
+                    let write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
+                        #[cfg(not(feature = "async-network-client"))]
+                        let mut rd = reader;
+                        #[cfg(feature = "async-network-client")]
+                        let mut rd = gix_protocol::futures_lite::io::BlockOn::new(reader);
+                        let pack_start = Instant::now();
+                        let mut pack = MeasureRead::new(&mut rd);
+                        let explode = unpack_limit
+                            .zip(pack_num_objects(&mut pack))
+                            .map_or(false, |(limit, num_objects)| num_objects < limit);
+                        let thin_pack_base_object_lookup = Some(Box::new({
                             let repo = repo.clone();
                             repo.objects
-                        })),
-                        options,
-                    )?;
-                    // Assure the final flush packet is consumed.
-                    #[cfg(feature = "async-network-client")]
-                    let has_read_to_end = { rd.get_ref().stopped_at().is_some() };
-                    #[cfg(not(feature = "async-network-client"))]
-                    let has_read_to_end = { rd.stopped_at().is_some() };
-                    if !has_read_to_end {
-                        std::io::copy(&mut rd, &mut std::io::sink()).unwrap();
-                    }
-                    #[cfg(feature = "async-network-client")]
-                    {
-                        reader = rd.into_inner();
-                    }
+                        }));
+                        let res = if explode {
+                            let loose = gix_odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash());
+                            loose
+                                .explode_pack(
+                                    &mut pack,
+                                    progress,
+                                    should_interrupt,
+                                    thin_pack_base_object_lookup,
+                                    &mut |id| repo.objects.exists(id),
+                                    gix_odb::loose::explode::pack::Options {
+                                        thread_limit: options.thread_limit,
+                                        iteration_mode: options.iteration_mode,
+                                    },
+                                )
+                                .map(|outcome| outcome.pack)
+                                .map_err(|err| match err {
+                                    gix_odb::loose::explode::pack::Error::WritePack(err) => Error::WritePack(err),
+                                    err => Error::ExplodePack(err),
+                                })
+                        } else {
+                            gix_pack::Bundle::write_to_directory(
+                                &mut pack,
+                                Some(&repo.objects.store_ref().path().join("pack")),
+                                progress,
+                                should_interrupt,
+                                thin_pack_base_object_lookup,
+                                options,
+                            )
+                            .map_err(Error::WritePack)
+                        };
+                        pack.finish(pack_start, &mut performance);
+                        let res = match res {
+                            Ok(res) => res,
+                            Err(err) => {
+                                match pack_backoff.next().filter(
+                                    |_| matches!(&err, Error::WritePack(err) if is_spurious_pack_error(err, can_retry)),
+                                ) {
+                                    Some(delay) => {
+                                        // The protocol doesn't allow to continue where the pack stream broke off,
+                                        // so the entire pack is requested once more without repeating the negotiation.
+                                        drop(rd);
+                                        progress
+                                            .info(format!("Requesting the pack again in {delay:?} after error: {err}"));
+                                        remote::Retry::wait(delay);
+                                        replay_request = Some(last_request.clone());
+                                        continue 'pack;
+                                    }
+                                    None => return Err(err),
+                                }
+                            }
+                        };
+                        // Assure the final flush packet is consumed.
+                        #[cfg(feature = "async-network-client")]
+                        let has_read_to_end = { rd.get_ref().stopped_at().is_some() };
+                        #[cfg(not(feature = "async-network-client"))]
+                        let has_read_to_end = { rd.stopped_at().is_some() };
+                        if !has_read_to_end {
+                            std::io::copy(&mut rd, &mut std::io::sink()).unwrap();
+                        }
+                        #[cfg(feature = "async-network-client")]
+                        {
+                            reader = rd.into_inner();
+                        }
 
-                    #[cfg(not(feature = "async-network-client"))]
-                    {
-                        reader = rd;
-                    }
-                    Some(res)
-                } else {
-                    None
+                        #[cfg(not(feature = "async-network-client"))]
+                        {
+                            reader = rd;
+                        }
+                        Some(res)
+                    } else {
+                        None
+                    };
+                    drop(reader);
+                    break 'pack write_pack_bundle;
                 };
-                drop(reader);
+                let graph = graph.detach();
+                drop(graph_repo);
+                negotiate_span.record("rounds", rounds.len());
+                drop(negotiate_span);
 
                 if matches!(protocol_version, gix_protocol::transport::Protocol::V2) {
                     gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
@@ -310,6 +439,7 @@ where
                         .ok();
                 }
 
+                let previous_response = previous_response.expect("knowledge of a pack means a response was received");
                 if let Some(shallow_lock) = shallow_lock {
                     if !previous_response.shallow_updates().is_empty() {
                         crate::shallow::write(shallow_lock, shallow_commits, previous_response.shallow_updates())?;
@@ -319,18 +449,33 @@ where
             }
         };
 
+        self.update_refs_and_finish(con.remote, write_pack_bundle, negotiate, performance)
+    }
+
+    /// Update refs according to our ref-map after `write_pack_bundle` was received, if any, and produce the outcome.
+    #[allow(clippy::result_large_err)]
+    pub(super) fn update_refs_and_finish(
+        &mut self,
+        remote: &crate::Remote<'repo>,
+        mut write_pack_bundle: Option<gix_pack::bundle::write::Outcome>,
+        negotiate: Option<outcome::Negotiate>,
+        mut performance: outcome::Performance,
+    ) -> Result<Outcome, Error> {
+        let repo = remote.repo;
+        let update_refs_start = Instant::now();
         let update_refs = refs::update(
             repo,
             self.reflog_message
                 .take()
                 .unwrap_or_else(|| RefLogMessage::Prefixed { action: "fetch".into() }),
             &self.ref_map.mappings,
-            con.remote.refspecs(remote::Direction::Fetch),
+            remote.refspecs(remote::Direction::Fetch),
             &self.ref_map.extra_refspecs,
-            con.remote.fetch_tags,
+            remote.fetch_tags,
             self.dry_run,
             self.write_packed_refs,
         )?;
+        performance.update_refs = update_refs_start.elapsed();
 
         if let Some(bundle) = write_pack_bundle.as_mut() {
             if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
@@ -354,11 +499,95 @@ where
                     update_refs,
                 },
             },
+            performance,
         };
         Ok(out)
     }
 }
 
+/// Return `true` if writing the pack failed due to a spurious IO error while reading it from the remote,
+/// and if `can_retry` permits it.
+fn is_spurious_pack_error(
+    err: &gix_pack::bundle::write::Error,
+    can_retry: impl Fn(&dyn IsSpuriousError) -> bool,
+) -> bool {
+    use gix_pack::{bundle::write::Error, data::input, index};
+    match err {
+        Error::Io(err)
+        | Error::PackIter(input::Error::Io(err))
+        | Error::IndexWrite(
+            index::write::Error::Io(err) | index::write::Error::PackEntryDecode(input::Error::Io(err)),
+        ) => can_retry(err),
+        _ => false,
+    }
+}
+
+/// A reader to learn how many bytes of a pack were received, and when the last of them arrived.
+pub(super) struct MeasureRead<R> {
+    inner: R,
+    bytes: u64,
+    last_read: Option<Instant>,
+}
+
+impl<R> MeasureRead<R> {
+    pub(super) fn new(inner: R) -> Self {
+        MeasureRead {
+            inner,
+            bytes: 0,
+            last_read: None,
+        }
+    }
+
+    /// Return the amount of bytes received so far.
+    #[cfg(feature = "blocking-network-client")]
+    pub(super) fn bytes_received(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Record the received bytes into `performance`, along with the time it took to receive them since `start`,
+    /// and the time it took to process them after the last byte was received.
+    pub(super) fn finish(self, start: Instant, performance: &mut outcome::Performance) {
+        let last_read = self.last_read.unwrap_or(start);
+        performance.pack_bytes_received = self.bytes;
+        performance.pack_receive = last_read.saturating_duration_since(start);
+        performance.pack_resolve = last_read.elapsed();
+    }
+
+    fn record(&mut self, num_bytes: usize) {
+        if num_bytes != 0 {
+            self.bytes += num_bytes as u64;
+            self.last_read = Some(Instant::now());
+        }
+    }
+}
+
+impl<R: std::io::Read> std::io::Read for MeasureRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let num_bytes = self.inner.read(buf)?;
+        self.record(num_bytes);
+        Ok(num_bytes)
+    }
+}
+
+impl<R: std::io::BufRead> std::io::BufRead for MeasureRead<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.record(amt);
+    }
+}
+
+/// Peek at the header of the pack in `pack` to learn how many objects it contains, or `None` if it couldn't be read.
+fn pack_num_objects(pack: &mut dyn std::io::BufRead) -> Option<u32> {
+    let header: &[u8; 12] = pack.fill_buf().ok()?.get(..12)?.try_into().ok()?;
+    gix_pack::data::header::decode(header)
+        .ok()
+        .map(|(_version, num_objects)| num_objects)
+}
+
 fn acquire_shallow_lock(repo: &Repository) -> Result<gix_lock::File, Error> {
     gix_lock::File::acquire_to_update_resource(repo.shallow_file(), gix_lock::acquire::Fail::Immediately, None)
         .map_err(Into::into)
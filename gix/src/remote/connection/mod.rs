@@ -20,8 +20,53 @@ pub struct Connection<'a, 'repo, T> {
     pub(crate) trace: bool,
 }
 
+/// Controls how often requests over stateless transports like HTTP are retried if they failed due to spurious errors.
+///
+/// Stateful transports can't recover from failed requests as they lose the state of the conversation with the server.
+/// Async clients never retry as there is no way to wait that doesn't block their executor.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Retry {
+    /// The maximum amount of retries per request.
+    pub max: usize,
+    /// The delay before the first retry, doubling with each subsequent retry of the same request.
+    pub delay: std::time::Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Retry {
+            max: 3,
+            delay: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+impl Retry {
+    /// Return the delays to wait for before each retry of a request, to be passed to [`wait()`](Self::wait()).
+    pub fn backoff(&self) -> impl Iterator<Item = std::time::Duration> {
+        let delay = self.delay;
+        let max = if cfg!(feature = "async-network-client") {
+            0
+        } else {
+            self.max
+        };
+        (0..max).map(move |attempt| delay.saturating_mul(1 << attempt.min(16)))
+    }
+
+    /// Wait for `delay` before retrying a request.
+    pub fn wait(delay: std::time::Duration) {
+        #[cfg(not(feature = "async-network-client"))]
+        std::thread::sleep(delay);
+        #[cfg(feature = "async-network-client")]
+        unreachable!("async clients never retry, but would have to wait {delay:?}")
+    }
+}
+
 mod access;
 
+#[cfg(feature = "blocking-network-client")]
+pub(crate) mod cache;
+
 ///
 pub mod ref_map;
 
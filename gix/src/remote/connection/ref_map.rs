@@ -1,12 +1,12 @@
 use std::collections::HashSet;
 
 use gix_features::progress::Progress;
-use gix_protocol::transport::client::Transport;
+use gix_protocol::transport::{client::Transport, IsSpuriousError};
 
 use crate::{
     bstr,
     bstr::{BString, ByteVec},
-    remote::{connection::HandshakeWithRefs, fetch, fetch::SpecIndex, Connection, Direction},
+    remote::{connection::HandshakeWithRefs, fetch, fetch::SpecIndex, Connection, Direction, Retry},
 };
 
 /// The error returned by [`Connection::ref_map()`].
@@ -32,6 +32,8 @@ pub enum Error {
     ConfigureCredentials(#[from] crate::config::credential_helpers::Error),
     #[error(transparent)]
     MappingValidation(#[from] gix_refspec::match_group::validate::Error),
+    #[error(transparent)]
+    NetworkRetry(#[from] crate::config::network_retry::Error),
 }
 
 impl gix_protocol::transport::IsSpuriousError for Error {
@@ -215,37 +217,72 @@ where
         if let Some(config) = self.transport_options.as_ref() {
             self.transport.configure(&**config)?;
         }
-        let mut outcome =
-            gix_protocol::fetch::handshake(&mut self.transport, authenticate, extra_parameters, &mut progress).await?;
+        // Retrying is only possible if there is no connection state to be lost.
+        let can_retry = !self.transport.connection_persists_across_multiple_requests();
+        let retry = self.remote.repo.config.network_retry()?;
+        let mut backoff = retry.backoff();
+        let mut outcome = loop {
+            match gix_protocol::fetch::handshake(
+                &mut self.transport,
+                &mut *authenticate,
+                extra_parameters.clone(),
+                &mut progress,
+            )
+            .await
+            {
+                Ok(outcome) => break outcome,
+                Err(err) => match backoff.next().filter(|_| can_retry && err.is_spurious()) {
+                    Some(delay) => {
+                        progress.info(format!("Retrying handshake in {delay:?} after error: {err}"));
+                        Retry::wait(delay);
+                    }
+                    None => return Err(err.into()),
+                },
+            }
+        };
         let refs = match outcome.refs.take() {
             Some(refs) => refs,
             None => {
                 let agent_feature = self.remote.repo.config.user_agent_tuple();
-                gix_protocol::ls_refs(
-                    &mut self.transport,
-                    &outcome.capabilities,
-                    move |_capabilities, arguments, features| {
-                        features.push(agent_feature);
-                        if filter_by_prefix {
-                            let mut seen = HashSet::new();
-                            for spec in refspecs {
-                                let spec = spec.to_ref();
-                                if seen.insert(spec.instruction()) {
-                                    let mut prefixes = Vec::with_capacity(1);
-                                    spec.expand_prefixes(&mut prefixes);
-                                    for mut prefix in prefixes {
-                                        prefix.insert_str(0, "ref-prefix ");
-                                        arguments.push(prefix);
+                let mut backoff = retry.backoff();
+                loop {
+                    let agent_feature = agent_feature.clone();
+                    let res = gix_protocol::ls_refs(
+                        &mut self.transport,
+                        &outcome.capabilities,
+                        move |_capabilities, arguments, features| {
+                            features.push(agent_feature);
+                            if filter_by_prefix {
+                                let mut seen = HashSet::new();
+                                for spec in refspecs {
+                                    let spec = spec.to_ref();
+                                    if seen.insert(spec.instruction()) {
+                                        let mut prefixes = Vec::with_capacity(1);
+                                        spec.expand_prefixes(&mut prefixes);
+                                        for mut prefix in prefixes {
+                                            prefix.insert_str(0, "ref-prefix ");
+                                            arguments.push(prefix);
+                                        }
                                     }
                                 }
                             }
-                        }
-                        Ok(gix_protocol::ls_refs::Action::Continue)
-                    },
-                    &mut progress,
-                    self.trace,
-                )
-                .await?
+                            Ok(gix_protocol::ls_refs::Action::Continue)
+                        },
+                        &mut progress,
+                        self.trace,
+                    )
+                    .await;
+                    match res {
+                        Ok(refs) => break refs,
+                        Err(err) => match backoff.next().filter(|_| can_retry && err.is_spurious()) {
+                            Some(delay) => {
+                                progress.info(format!("Retrying to list references in {delay:?} after error: {err}"));
+                                Retry::wait(delay);
+                            }
+                            None => return Err(err.into()),
+                        },
+                    }
+                }
             }
         };
         Ok(HandshakeWithRefs { outcome, refs })
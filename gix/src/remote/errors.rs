@@ -62,3 +62,73 @@ pub mod find {
         }
     }
 }
+
+///
+pub mod remove {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::remove_remote()`](crate::Repository::remove_remote()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The remote {name:?} does not exist")]
+        NotFound { name: BString },
+        #[error(transparent)]
+        Iter(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        IterInit(#[from] crate::reference::iter::init::Error),
+        #[error("Could not iterate the remote tracking references")]
+        Iterate(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        OpenConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error(transparent)]
+        LockConfig(#[from] gix_lock::acquire::Error),
+        #[error("Could not write the configuration file")]
+        WriteConfig(#[from] std::io::Error),
+        #[error(transparent)]
+        CommitConfig(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
+
+///
+pub mod rename {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::rename_remote()`](crate::Repository::rename_remote()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The remote {name:?} does not exist")]
+        NotFound { name: BString },
+        #[error("The remote {name:?} already exists")]
+        AlreadyExists { name: BString },
+        #[error(transparent)]
+        InvalidName(#[from] crate::remote::name::Error),
+        #[error(transparent)]
+        Iter(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        IterInit(#[from] crate::reference::iter::init::Error),
+        #[error("Could not iterate the remote tracking references")]
+        Iterate(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        RefName(#[from] gix_validate::reference::name::Error),
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        RenameSection(#[from] gix_config::file::rename_section::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        OpenConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error(transparent)]
+        LockConfig(#[from] gix_lock::acquire::Error),
+        #[error("Could not write the configuration file")]
+        WriteConfig(#[from] std::io::Error),
+        #[error(transparent)]
+        CommitConfig(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
@@ -11,6 +11,8 @@ pub mod negotiate {
     };
 }
 
+#[cfg(feature = "blocking-network-client")]
+pub use super::connection::fetch::dumb;
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 pub use super::connection::fetch::{
     outcome, prepare, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage, Status,
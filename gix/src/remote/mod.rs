@@ -37,7 +37,7 @@ pub mod name;
 mod build;
 
 mod errors;
-pub use errors::find;
+pub use errors::{find, remove, rename};
 
 ///
 pub mod init;
@@ -51,6 +51,10 @@ pub mod connect;
 
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
 mod connection;
+#[cfg(feature = "blocking-network-client")]
+pub use connection::cache::ConnectionCache;
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+pub(crate) use connection::Retry;
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
 pub use connection::{ref_map, AuthenticateFn, Connection};
 
@@ -1,7 +1,7 @@
 //! exclude information
 use crate::{config, AttributeStack, Repository};
 
-/// The error returned by [`Repository::attributes()`].
+/// The error returned by [`Repository::attributes()`] and the methods to configure stacks from trees.
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -9,6 +9,8 @@ pub enum Error {
     ConfigureAttributes(#[from] config::attribute_stack::Error),
     #[error(transparent)]
     ConfigureExcludes(#[from] config::exclude_stack::Error),
+    #[error("Could not find attribute or ignore files in tree")]
+    IdMappingsFromTree(#[from] gix_worktree::stack::state::id_mappings_from_tree::Error),
 }
 
 impl Repository {
@@ -136,4 +138,88 @@ impl Repository {
             self,
         ))
     }
+
+    /// Like [`attributes()`][Self::attributes()], but reads in-tree `.gitattributes` and `.gitignore` files from the tree
+    /// with `tree_id` and its subtrees instead of from an index or the worktree.
+    ///
+    /// This is useful in bare repositories or when operating on trees that aren't checked out, as no index has to be
+    /// created from the tree first.
+    #[cfg(feature = "attributes")]
+    pub fn attributes_from_tree(
+        &self,
+        tree_id: &gix_hash::oid,
+        exclude_overrides: Option<gix_ignore::Search>,
+    ) -> Result<AttributeStack<'_>, Error> {
+        let (attributes, mut buf) = self.config.assemble_attribute_globals(
+            self.git_dir(),
+            gix_worktree::stack::state::attributes::Source::IdMapping,
+            self.options.permissions.attributes,
+        )?;
+        let ignore = self.config.assemble_exclude_globals(
+            self.git_dir(),
+            exclude_overrides,
+            gix_worktree::stack::state::ignore::Source::IdMapping,
+            &mut buf,
+        )?;
+        let state = gix_worktree::stack::State::AttributesAndIgnoreStack { attributes, ignore };
+        self.stack_from_tree(tree_id, state, buf)
+    }
+
+    /// Like [`attributes_only()`][Self::attributes_only()], but reads in-tree `.gitattributes` files from the tree
+    /// with `tree_id` and its subtrees instead of from an index or the worktree.
+    #[cfg(feature = "attributes")]
+    pub fn attributes_only_from_tree(&self, tree_id: &gix_hash::oid) -> Result<AttributeStack<'_>, Error> {
+        let (attributes, buf) = self.config.assemble_attribute_globals(
+            self.git_dir(),
+            gix_worktree::stack::state::attributes::Source::IdMapping,
+            self.options.permissions.attributes,
+        )?;
+        let state = gix_worktree::stack::State::AttributesStack(attributes);
+        self.stack_from_tree(tree_id, state, buf)
+    }
+
+    /// Like [`excludes()`][Self::excludes()], but reads in-tree `.gitignore` files from the tree with `tree_id`
+    /// and its subtrees instead of from an index or the worktree.
+    #[cfg(feature = "excludes")]
+    pub fn excludes_from_tree(
+        &self,
+        tree_id: &gix_hash::oid,
+        overrides: Option<gix_ignore::Search>,
+    ) -> Result<AttributeStack<'_>, Error> {
+        let mut buf = Vec::with_capacity(512);
+        let ignore = self.config.assemble_exclude_globals(
+            self.git_dir(),
+            overrides,
+            gix_worktree::stack::state::ignore::Source::IdMapping,
+            &mut buf,
+        )?;
+        let state = gix_worktree::stack::State::IgnoreStack(ignore);
+        self.stack_from_tree(tree_id, state, buf)
+    }
+
+    #[cfg(feature = "excludes")]
+    fn stack_from_tree(
+        &self,
+        tree_id: &gix_hash::oid,
+        state: gix_worktree::stack::State,
+        buf: Vec<u8>,
+    ) -> Result<AttributeStack<'_>, Error> {
+        let case = if self.config.ignore_case {
+            gix_glob::pattern::Case::Fold
+        } else {
+            gix_glob::pattern::Case::Sensitive
+        };
+        let id_mappings = state.id_mappings_from_tree(tree_id, &self.objects, case)?;
+        Ok(AttributeStack::new(
+            gix_worktree::Stack::new(
+                // this is alright as we don't cause mutation of that directory, it's virtual.
+                self.work_dir().unwrap_or(self.git_dir()),
+                state,
+                case,
+                buf,
+                id_mappings,
+            ),
+            self,
+        ))
+    }
 }
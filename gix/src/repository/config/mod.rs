@@ -37,6 +37,24 @@ impl crate::Repository {
         self.config.stat_options()
     }
 
+    /// Return the zlib compression level to use for objects that are newly compressed into packs, from 0 (none) to 9 (best).
+    ///
+    /// It's read from `pack.compression`, falling back to `core.compression` and finally to level 3.
+    pub fn pack_compression_level(&self) -> Result<u32, config::key::GenericError> {
+        self.config.compression_level(&config::tree::Pack::COMPRESSION, 3)
+    }
+
+    /// Return how to acquire locks as `(files, packed_refs)`, with `files` being the mode for locking individual files
+    /// like loose references, and `packed_refs` the mode for locking the `packed-refs` file.
+    ///
+    /// They are read from `core.filesRefLockTimeout` and `core.packedRefsTimeout` respectively, and default to
+    /// retrying for 100ms and 1s.
+    pub fn lock_timeout(
+        &self,
+    ) -> Result<(gix_lock::acquire::Fail, gix_lock::acquire::Fail), config::lock_timeout::Error> {
+        self.config.lock_timeout()
+    }
+
     /// The options used to open the repository.
     pub fn open_options(&self) -> &crate::open::Options {
         &self.options
@@ -75,6 +93,12 @@ impl crate::Repository {
                 .and_then(|variant| Ssh::VARIANT.try_into_variant(variant).transpose())
                 .transpose()
                 .with_leniency(self.options.lenient_config)?,
+            control_path: self.connection_cache.ssh_control_path(),
+            askpass: self
+                .config_snapshot()
+                .prompt_options()?
+                .askpass
+                .map(std::borrow::Cow::into_owned),
         };
         Ok(opts)
     }
@@ -134,6 +158,9 @@ impl crate::Repository {
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 mod transport;
 
+#[cfg(feature = "attributes")]
+mod programs;
+
 mod remote {
     use std::{borrow::Cow, collections::BTreeSet};
 
@@ -183,10 +210,10 @@ mod remote {
 mod branch {
     use std::{borrow::Cow, collections::BTreeSet, convert::TryInto};
 
-    use gix_ref::FullNameRef;
+    use gix_ref::{FullName, FullNameRef};
     use gix_validate::reference::name::Error as ValidateNameError;
 
-    use crate::bstr::BStr;
+    use crate::{bstr::BStr, remote};
 
     impl crate::Repository {
         /// Return a set of unique short branch names for which custom configuration exists in the configuration,
@@ -227,6 +254,50 @@ mod branch {
                 .string("branch", Some(short_branch_name.into()), "remote")
                 .and_then(|name| name.try_into().ok())
         }
+
+        /// Returns the name of the local reference that tracks the upstream of the given `short_branch_name`,
+        /// like `refs/remotes/origin/main` for `main`, which is what `git` compares a branch to when showing
+        /// how far it is ahead or behind.
+        ///
+        /// The upstream is configured with `branch.<name>.merge` and mapped to a local reference by the fetch refspecs
+        /// of the remote in `branch.<name>.remote`. If the remote is `.`, the upstream is a local branch which is returned as is.
+        /// Returns `None` if the branch has no upstream, or if the refspecs of its remote don't map the upstream to a local reference.
+        pub fn branch_remote_tracking_ref_name<'a>(
+            &self,
+            short_branch_name: impl Into<&'a BStr>,
+        ) -> Option<Result<FullName, crate::repository::branch_remote_tracking_ref_name::Error>> {
+            let short_branch_name = short_branch_name.into();
+            let remote_name = self.branch_remote_name(short_branch_name)?;
+            let merge = match self.branch_remote_ref(short_branch_name)? {
+                Ok(merge) => merge,
+                Err(err) => return Some(Err(err.into())),
+            };
+            if remote_name.as_bstr() == "." {
+                return Some(Ok(merge.into_owned()));
+            }
+            let remote = match self.find_remote(remote_name.as_bstr()) {
+                Ok(remote) => remote,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            let null_id = gix_hash::ObjectId::null(self.object_hash());
+            let item = gix_refspec::match_group::Item {
+                full_ref_name: merge.as_bstr(),
+                target: &null_id,
+                object: None,
+            };
+            let tracking_name = gix_refspec::MatchGroup::from_fetch_specs(
+                remote
+                    .refspecs(remote::Direction::Fetch)
+                    .iter()
+                    .map(gix_refspec::RefSpec::to_ref),
+            )
+            .match_remotes(std::iter::once(item))
+            .mappings
+            .into_iter()
+            .find_map(|mapping| mapping.rhs)?;
+            Some(FullName::try_from(tracking_name.into_owned()).map_err(Into::into))
+        }
     }
 }
 
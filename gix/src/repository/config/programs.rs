@@ -0,0 +1,118 @@
+use std::{ffi::OsString, process::Stdio};
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    config,
+    config::tree::{Core, Key, Sequence},
+};
+
+/// The editor to use if nothing else is configured.
+const DEFAULT_EDITOR: &str = "vi";
+/// The pager to use if nothing else is configured.
+const DEFAULT_PAGER: &str = "less";
+
+/// Programs for user interaction, like editors and pagers.
+impl crate::Repository {
+    /// Return a command ready to launch the editor for editing messages, like commit messages, with the path to
+    /// the file to edit passed as additional argument by the caller.
+    ///
+    /// The editor is looked up in `GIT_EDITOR`, `core.editor`, `VISUAL` (unless `TERM` is `dumb`) and `EDITOR`, in
+    /// that order, and defaults to `vi`.
+    /// Environment variables without `GIT_` prefix are only used if the [`git_prefix`](crate::open::permissions::Environment::git_prefix)
+    /// permission allows it.
+    ///
+    /// The returned command inherits all standard streams so the editor can interact with the terminal,
+    /// and it is run through a shell if needed, just like `git` does.
+    pub fn editor(&self) -> Result<gix_command::Prepare, config::editor::Error> {
+        let editor = self.configured_program(&Core::EDITOR);
+        self.editor_command(editor)
+    }
+
+    /// Return a command ready to launch the editor for editing sequencer instructions, like those of an interactive rebase.
+    ///
+    /// The editor is looked up in `GIT_SEQUENCE_EDITOR` and `sequence.editor`, and falls back to the [`editor()`](Self::editor()).
+    pub fn sequence_editor(&self) -> Result<gix_command::Prepare, config::editor::Error> {
+        let editor = self
+            .configured_program(&Sequence::EDITOR)
+            .or_else(|| self.configured_program(&Core::EDITOR));
+        self.editor_command(editor)
+    }
+
+    /// Return a command ready to launch the pager to which output of commands can be written via its `stdin`,
+    /// or `None` if paging is disabled as the configured pager is empty or `cat`.
+    ///
+    /// The pager is looked up in `GIT_PAGER`, `core.pager` and `PAGER`, in that order, and defaults to `less`.
+    /// Environment variables without `GIT_` prefix are only used if the [`git_prefix`](crate::open::permissions::Environment::git_prefix)
+    /// permission allows it.
+    ///
+    /// Just like `git`, `LESS` is set to `FRX` and `LV` is set to `-c` unless these are already present in the environment.
+    /// Note that it's up to the caller to decide if a pager is appropriate, which typically is only the case if
+    /// `stdout` is a terminal.
+    pub fn pager(&self) -> Option<gix_command::Prepare> {
+        let pager = self
+            .configured_program(&Core::PAGER)
+            .or_else(|| self.env_var("PAGER"))
+            .unwrap_or_else(|| DEFAULT_PAGER.into());
+        let is_disabled = gix_path::os_str_into_bstr(&pager).map_or(false, |pager| {
+            let pager = pager.trim();
+            pager.is_empty() || pager == b"cat"
+        });
+        if is_disabled {
+            return None;
+        }
+        let mut cmd = gix_command::prepare(pager)
+            .with_shell()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit());
+        for (var, default) in [("LESS", "FRX"), ("LV", "-c")] {
+            if std::env::var_os(var).is_none() {
+                cmd = cmd.env(var, default);
+            }
+        }
+        Some(cmd)
+    }
+}
+
+/// Utilities
+impl crate::Repository {
+    fn configured_program(&self, key: &'static dyn Key) -> Option<OsString> {
+        self.config
+            .resolved
+            .string_filter(
+                key.section().name(),
+                None,
+                key.name(),
+                &mut self.filter_config_section(),
+            )
+            .map(|value| gix_path::from_bstr(BStr::new(value.as_ref())).into_owned().into())
+    }
+
+    fn env_var(&self, name: &str) -> Option<OsString> {
+        self.options
+            .permissions
+            .env
+            .git_prefix
+            .check_opt(name)
+            .and_then(std::env::var_os)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn editor_command(&self, configured: Option<OsString>) -> Result<gix_command::Prepare, config::editor::Error> {
+        let is_dumb_terminal = self.env_var("TERM").map_or(true, |term| term == "dumb");
+        let editor = match configured
+            .filter(|editor| !editor.is_empty())
+            .or_else(|| (!is_dumb_terminal).then(|| self.env_var("VISUAL")).flatten())
+            .or_else(|| self.env_var("EDITOR"))
+        {
+            Some(editor) => editor,
+            None if is_dumb_terminal => return Err(config::editor::Error::DumbTerminal),
+            None => DEFAULT_EDITOR.into(),
+        };
+        Ok(gix_command::prepare(editor)
+            .with_shell()
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit()))
+    }
+}
@@ -189,6 +189,28 @@ impl crate::Repository {
                         .with_leniency(lenient)
                         .map_err(config::transport::http::Error::from)?
                         .unwrap_or_default();
+                    opts.max_receive_bytes_per_second = {
+                        let key = "gitoxide.http.maxReceiveSpeed";
+                        debug_assert_eq!(key, gitoxide::Http::MAX_RECEIVE_SPEED.logical_name());
+                        config
+                            .integer_filter_by_key(key, &mut trusted_only)
+                            .map(|value| gitoxide::Http::MAX_RECEIVE_SPEED.try_into_u64(value))
+                            .transpose()
+                            .with_leniency(lenient)
+                            .map_err(config::transport::http::Error::from)?
+                            .unwrap_or_default()
+                    };
+                    opts.max_send_bytes_per_second = {
+                        let key = "gitoxide.http.maxSendSpeed";
+                        debug_assert_eq!(key, gitoxide::Http::MAX_SEND_SPEED.logical_name());
+                        config
+                            .integer_filter_by_key(key, &mut trusted_only)
+                            .map(|value| gitoxide::Http::MAX_SEND_SPEED.try_into_u64(value))
+                            .transpose()
+                            .with_leniency(lenient)
+                            .map_err(config::transport::http::Error::from)?
+                            .unwrap_or_default()
+                    };
                     opts.proxy = proxy(
                         remote_name
                             .and_then(|name| {
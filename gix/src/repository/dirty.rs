@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use crate::{bstr::BStr, repository::is_dirty::Error};
+
+impl crate::Repository {
+    /// Return `true` if tracked files in the worktree were modified, deleted or are in conflict compared to the index,
+    /// or if `untracked_files` is `true` and there are files that are neither tracked nor ignored, similar to what
+    /// `git status` would show. Changes between the index and `HEAD` aren't considered.
+    ///
+    /// Bare repositories are never dirty, and submodules are ignored.
+    pub fn is_dirty(&self, untracked_files: bool) -> Result<bool, Error> {
+        let Some(work_dir) = self.work_dir() else {
+            return Ok(false);
+        };
+        let index = self.index_or_empty()?;
+        if has_modifications(self, work_dir, &index)? {
+            return Ok(true);
+        }
+        if !untracked_files {
+            return Ok(false);
+        }
+        let excludes = self.excludes(
+            &index,
+            None,
+            gix_worktree::stack::state::ignore::Source::WorktreeThenIdMappingIfNotSkipped,
+        )?;
+        has_untracked_files(self, work_dir, &index, &excludes)
+    }
+}
+
+/// Return `true` if tracked files in `work_dir` differ from their entries in `index`.
+fn has_modifications(repo: &crate::Repository, work_dir: &Path, index: &gix_index::State) -> Result<bool, Error> {
+    let source = gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping;
+    let pathspec = repo.pathspec(None::<&BStr>, true, index, source)?.detach()?;
+    let attributes = match repo.attributes_only(index, source)?.detach().state_mut() {
+        gix_worktree::stack::State::AttributesStack(attributes) => std::mem::take(attributes),
+        _ => unreachable!("state must be attributes stack only"),
+    };
+    let mut is_dirty = IsDirty(false);
+    gix_status::index_as_worktree(
+        index,
+        work_dir,
+        &mut is_dirty,
+        gix_status::index_as_worktree::traits::FastEq,
+        gix_status::refresh::IgnoreSubmodules,
+        repo.objects.clone().into_arc()?,
+        &mut gix_features::progress::Discard,
+        pathspec,
+        repo.filter_pipeline(None)?.0.into_parts().0,
+        &std::sync::atomic::AtomicBool::default(),
+        gix_status::index_as_worktree::Options {
+            fs: repo.filesystem_options()?,
+            thread_limit: None,
+            stat: repo.stat_options()?,
+            attributes,
+        },
+    )?;
+    Ok(is_dirty.0)
+}
+
+/// Return `true` if `work_dir` contains files that are neither tracked in `index` nor excluded by `excludes`, at any depth.
+///
+/// Directories that are repositories of their own are untracked unless `index` tracks them as submodule.
+fn has_untracked_files(
+    repo: &crate::Repository,
+    work_dir: &Path,
+    index: &gix_index::State,
+    excludes: &gix_worktree::Stack,
+) -> Result<bool, Error> {
+    let mut untracked = gix_worktree::dirwalk::Recorder::default();
+    gix_worktree::dirwalk(
+        work_dir,
+        index,
+        excludes,
+        repo.objects.clone().into_arc()?,
+        &mut untracked,
+        &std::sync::atomic::AtomicBool::default(),
+        gix_worktree::dirwalk::Options {
+            emit_untracked: gix_worktree::dirwalk::EmissionMode::CollapseDirectory,
+            ignore_case: repo.filesystem_options()?.ignore_case,
+            ..Default::default()
+        },
+    )?;
+    Ok(!untracked.entries.is_empty())
+}
+
+struct IsDirty(bool);
+
+impl<'index> gix_status::index_as_worktree::VisitEntry<'index> for IsDirty {
+    type ContentChange = ();
+    type SubmoduleStatus = ();
+
+    fn visit_entry(
+        &mut self,
+        _entries: &'index [gix_index::Entry],
+        _entry: &'index gix_index::Entry,
+        _entry_index: usize,
+        _rela_path: &'index BStr,
+        status: gix_status::index_as_worktree::EntryStatus<Self::ContentChange, Self::SubmoduleStatus>,
+    ) {
+        if !matches!(status, gix_status::index_as_worktree::EntryStatus::NeedsUpdate(_)) {
+            self.0 = true;
+        }
+    }
+}
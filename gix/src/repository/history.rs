@@ -0,0 +1,553 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gix_features::progress::{Count, DynNestedProgress, NestedProgress, Progress};
+use gix_hash::ObjectId;
+use gix_object::FindExt;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+use smallvec::SmallVec;
+
+use crate::{
+    bstr::{BString, ByteSlice},
+    history::rewrite::{Delegate, Error, Options, Outcome, ProgressId},
+};
+
+/// History
+impl crate::Repository {
+    /// Rewrite all commits reachable from the given `references`, like `refs/heads/main`, similar to what `git filter-repo` does,
+    /// and update the references to point to the rewritten commits.
+    ///
+    /// `delegate` decides which tree entries to keep and how messages and signatures are changed, while `options` further
+    /// configure the operation. Commits are only written if they actually change, and signatures of rewritten commits are removed
+    /// as they would be invalid otherwise. Annotated tags that point to rewritten commits are rewritten as well, and symbolic
+    /// references are skipped as they follow the references they point to.
+    ///
+    /// References are only changed if they still point to the object they pointed to when the operation started, and are deleted
+    /// if no commit remains. The original history is never deleted and remains accessible through the reference log.
+    /// Note that shallow repositories can't be rewritten as the parents of commits at the shallow boundary are missing.
+    pub fn rewrite_history<P>(
+        &self,
+        references: impl IntoIterator<Item = FullName>,
+        delegate: &mut dyn Delegate,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        self.rewrite_history_inner(
+            references.into_iter().collect(),
+            delegate,
+            &mut progress,
+            should_interrupt,
+            options,
+        )
+    }
+
+    fn rewrite_history_inner(
+        &self,
+        mut names: Vec<FullName>,
+        delegate: &mut dyn Delegate,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("gix::Repository::rewrite_history()");
+        names.sort();
+        names.dedup();
+
+        let mut buf = Vec::new();
+        let mut tips = Vec::new();
+        for name in names {
+            let id = match self.find_reference(name.as_ref())?.inner.target {
+                Target::Peeled(id) => id,
+                Target::Symbolic(_) => continue,
+            };
+            let tag = match self.find_header(id)?.kind() {
+                gix_object::Kind::Commit => None,
+                gix_object::Kind::Tag => {
+                    let tag: gix_object::Tag = self.objects.find_tag(&id, &mut buf)?.into();
+                    if tag.target_kind != gix_object::Kind::Commit {
+                        continue;
+                    }
+                    Some(tag)
+                }
+                gix_object::Kind::Tree | gix_object::Kind::Blob => continue,
+            };
+            tips.push((name, id, tag));
+        }
+
+        let mut rewriter = Rewriter {
+            repo: self,
+            delegate,
+            options,
+            trees: Default::default(),
+            commits: Default::default(),
+        };
+        {
+            let mut progress =
+                progress.add_child_with_id("rewriting commits".into(), ProgressId::RewriteCommits.into());
+            progress.init(None, crate::progress::count("commits"));
+            // Parents are rewritten before their children, so commits stay on the stack until all of their parents are done.
+            let mut stack: Vec<_> = tips
+                .iter()
+                .map(|(_, id, tag)| tag.as_ref().map_or(*id, |tag| tag.target))
+                .collect();
+            while let Some(&id) = stack.last() {
+                if rewriter.commits.contains_key(&id) {
+                    stack.pop();
+                    continue;
+                }
+                let commit: gix_object::Commit = self.objects.find_commit(&id, &mut buf)?.into();
+                let num_pending = stack.len();
+                stack.extend(
+                    commit
+                        .parents
+                        .iter()
+                        .filter(|parent| !rewriter.commits.contains_key(*parent)),
+                );
+                if stack.len() != num_pending {
+                    continue;
+                }
+                rewriter.rewrite_commit(id, commit)?;
+                stack.pop();
+                progress.inc();
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+            }
+        }
+
+        let mut edits = Vec::new();
+        for (name, id, tag) in tips {
+            let new_id = match tag {
+                None => rewriter.commits[&id].id,
+                Some(mut tag) => {
+                    let tagger = tag
+                        .tagger
+                        .as_ref()
+                        .and_then(|tagger| rewriter.rewrite_signature(tagger));
+                    match rewriter.commits[&tag.target].id {
+                        None => None,
+                        Some(target) if target == tag.target && tagger.is_none() => Some(id),
+                        Some(target) => {
+                            tag.target = target;
+                            if tagger.is_some() {
+                                tag.tagger = tagger;
+                            }
+                            tag.pgp_signature = None;
+                            Some(self.write_object(&tag)?.detach())
+                        }
+                    }
+                }
+            };
+            if new_id == Some(id) {
+                continue;
+            }
+            let expected = PreviousValue::MustExistAndMatch(Target::Peeled(id));
+            edits.push(RefEdit {
+                change: match new_id {
+                    Some(new_id) => Change::Update {
+                        log: LogChange {
+                            mode: RefLog::AndReference,
+                            force_create_reflog: false,
+                            message: "rewrite history".into(),
+                        },
+                        expected,
+                        new: Target::Peeled(new_id),
+                    },
+                    None => Change::Delete {
+                        expected,
+                        log: RefLog::AndReference,
+                    },
+                },
+                name,
+                deref: false,
+            });
+        }
+        let ref_edits = self.edit_references(edits)?;
+
+        Ok(Outcome {
+            commits: rewriter
+                .commits
+                .into_iter()
+                .map(|(original, rewritten)| (original, rewritten.id))
+                .collect(),
+            ref_edits,
+        })
+    }
+}
+
+/// The result of rewriting a single commit.
+struct Rewritten {
+    /// The id of the rewritten commit, or of the closest rewritten ancestor if the commit was removed.
+    id: Option<ObjectId>,
+    /// The tree of the commit at `id`, or the empty tree.
+    tree: ObjectId,
+    /// The tree of the original commit.
+    original_tree: ObjectId,
+}
+
+struct Rewriter<'repo, 'a> {
+    repo: &'repo crate::Repository,
+    delegate: &'a mut dyn Delegate,
+    options: Options,
+    /// Rewritten trees by their original id and their location, as the delegate decides based on the path of an entry.
+    trees: std::collections::HashMap<(ObjectId, BString), Option<ObjectId>>,
+    commits: gix_hashtable::HashMap<ObjectId, Rewritten>,
+}
+
+impl Rewriter<'_, '_> {
+    /// Rewrite `commit` with `id`, whose parents must have been rewritten already.
+    fn rewrite_commit(&mut self, id: ObjectId, mut commit: gix_object::Commit) -> Result<(), Error> {
+        let empty_tree = ObjectId::empty_tree(self.repo.object_hash());
+        let original_tree = commit.tree;
+        let tree = match self.rewrite_tree(commit.tree, &mut BString::default())? {
+            Some(tree) => tree,
+            None => self.repo.write_object(gix_object::Tree::empty())?.detach(),
+        };
+
+        let mut parents = SmallVec::<[ObjectId; 1]>::new();
+        for parent in &commit.parents {
+            if let Some(parent) = self.commits[parent].id {
+                if !parents.contains(&parent) {
+                    parents.push(parent);
+                }
+            }
+        }
+        if self.options.prune_empty_commits && parents.len() <= 1 {
+            let first_parent = commit.parents.first().map(|parent| &self.commits[parent]);
+            let was_empty = commit.parents.len() <= 1
+                && first_parent.map_or(empty_tree, |parent| parent.original_tree) == original_tree;
+            let parent_tree = commit
+                .parents
+                .iter()
+                .map(|parent| &self.commits[parent])
+                .find(|parent| parent.id.is_some())
+                .map_or(empty_tree, |parent| parent.tree);
+            if !was_empty && tree == parent_tree {
+                self.commits.insert(
+                    id,
+                    Rewritten {
+                        id: parents.first().copied(),
+                        tree,
+                        original_tree,
+                    },
+                );
+                return Ok(());
+            }
+        }
+
+        let message = self.delegate.rewrite_message(&commit);
+        let author = self.rewrite_signature(&commit.author);
+        let committer = self.rewrite_signature(&commit.committer);
+        let is_unchanged = tree == commit.tree
+            && parents == commit.parents
+            && message.is_none()
+            && author.is_none()
+            && committer.is_none();
+        let new_id = if is_unchanged {
+            id
+        } else {
+            commit.tree = tree;
+            commit.parents = parents;
+            if let Some(message) = message {
+                commit.message = message;
+            }
+            if let Some(author) = author {
+                commit.author = author;
+            }
+            if let Some(committer) = committer {
+                commit.committer = committer;
+            }
+            commit
+                .extra_headers
+                .retain(|(name, _)| name != "gpgsig" && name != "gpgsig-sha256");
+            self.repo.write_object(&commit)?.detach()
+        };
+        self.commits.insert(
+            id,
+            Rewritten {
+                id: Some(new_id),
+                tree,
+                original_tree,
+            },
+        );
+        Ok(())
+    }
+
+    /// Rewrite the tree with `id` at `path`, or return `None` if no entry remains.
+    fn rewrite_tree(&mut self, id: ObjectId, path: &mut BString) -> Result<Option<ObjectId>, Error> {
+        if id == ObjectId::empty_tree(self.repo.object_hash()) {
+            return Ok(None);
+        }
+        let key = (id, path.clone());
+        if let Some(rewritten) = self.trees.get(&key) {
+            return Ok(*rewritten);
+        }
+
+        let mut buf = Vec::new();
+        let tree: gix_object::Tree = self.repo.objects.find_tree(&id, &mut buf)?.into();
+        let num_entries = tree.entries.len();
+        let mut is_unchanged = true;
+        let mut entries = Vec::with_capacity(num_entries);
+        for mut entry in tree.entries {
+            let prev_len = path.len();
+            if !path.is_empty() {
+                path.push(b'/');
+            }
+            path.extend_from_slice(&entry.filename);
+            let keep = self.delegate.keep_entry(
+                path.as_bstr(),
+                &gix_object::tree::EntryRef {
+                    mode: entry.mode,
+                    filename: entry.filename.as_bstr(),
+                    oid: &entry.oid,
+                },
+            );
+            let rewritten = if !keep {
+                None
+            } else if entry.mode.is_tree() {
+                self.rewrite_tree(entry.oid, path)?
+            } else if entry.mode.is_blob()
+                && self.options.max_blob_size.map_or(Ok(false), |max| {
+                    self.repo.find_header(entry.oid).map(|header| header.size() > max)
+                })?
+            {
+                None
+            } else {
+                Some(entry.oid)
+            };
+            path.truncate(prev_len);
+
+            if let Some(rewritten) = rewritten {
+                is_unchanged &= rewritten == entry.oid;
+                entry.oid = rewritten;
+                entries.push(entry);
+            }
+        }
+
+        let rewritten = if entries.is_empty() {
+            None
+        } else if is_unchanged && entries.len() == num_entries {
+            Some(id)
+        } else {
+            Some(self.repo.write_object(gix_object::Tree { entries })?.detach())
+        };
+        self.trees.insert(key, rewritten);
+        Ok(rewritten)
+    }
+
+    /// Return the signature `delegate` provides instead of `signature`, if it's different.
+    fn rewrite_signature(&mut self, signature: &gix_actor::Signature) -> Option<gix_actor::Signature> {
+        self.delegate
+            .rewrite_signature(signature)
+            .filter(|rewritten| rewritten != signature)
+    }
+}
+
+#[cfg(all(feature = "mailmap", feature = "blob-diff"))]
+mod authors {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use gix_diff::stat::Summary;
+    use gix_features::progress::{Count, DynNestedProgress, NestedProgress, Progress};
+    use gix_hash::ObjectId;
+
+    use crate::{
+        bstr::BString,
+        history::authors::{Author, Error, Options, Outcome, ProgressId},
+    };
+
+    /// History statistics
+    impl crate::Repository {
+        /// Summarize the contributions of each author in all commits reachable from `tips` but not from `hidden`,
+        /// similar to the commits `git log ^<hidden> <tips>` would show, after mapping their identities with the
+        /// [mailmap](Self::open_mailmap()).
+        ///
+        /// Authors are identified by their email address ignoring its case, and are returned with their commit counts,
+        /// the times of their first and last commit and the amount of days they were active on.
+        /// If [line statistics](Options::line_stats) are requested, each commit is diffed with its parent to also learn
+        /// how many files and lines each author changed, spreading the work over the configured amount of threads.
+        ///
+        /// `progress` is informed about traversed and diffed commits, and `should_interrupt` is checked regularly to abort
+        /// the operation.
+        pub fn author_stats<P>(
+            &self,
+            tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+            hidden: impl IntoIterator<Item = impl Into<ObjectId>>,
+            mut progress: P,
+            should_interrupt: &AtomicBool,
+            options: Options,
+        ) -> Result<Outcome, Error>
+        where
+            P: NestedProgress,
+            P::SubProgress: 'static,
+        {
+            self.author_stats_inner(
+                tips.into_iter().map(Into::into).collect(),
+                hidden.into_iter().map(Into::into).collect(),
+                &mut progress,
+                should_interrupt,
+                options,
+            )
+        }
+
+        fn author_stats_inner(
+            &self,
+            tips: Vec<ObjectId>,
+            hidden: Vec<ObjectId>,
+            progress: &mut dyn DynNestedProgress,
+            should_interrupt: &AtomicBool,
+            options: Options,
+        ) -> Result<Outcome, Error> {
+            let _span = gix_trace::coarse!("gix::Repository::author_stats()");
+            let mailmap = self.open_mailmap();
+            let hidden = if hidden.is_empty() {
+                gix_hashtable::HashSet::default()
+            } else {
+                self.rev_walk(hidden)
+                    .all()?
+                    .map(|info| info.map(|info| info.id))
+                    .collect::<Result<_, _>>()?
+            };
+
+            let mut traverse_progress =
+                progress.add_child_with_id("traverse commits".into(), ProgressId::TraverseCommits.into());
+            traverse_progress.init(None, gix_features::progress::count("commits"));
+
+            let mut authors = Vec::<(Author, HashSet<i64>)>::new();
+            let mut author_by_email = HashMap::<BString, usize>::new();
+            let mut commits_to_diff = Vec::<(usize, Option<ObjectId>, ObjectId)>::new();
+            let mut num_commits = 0;
+            let mut skipped_merge_commits = 0;
+            for info in self.rev_walk(tips).selected(move |id| !hidden.contains(id))? {
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+                let info = info?;
+                let commit = info.object()?;
+                let signature = mailmap.resolve_cow(commit.author()?.trim());
+                let author_idx = *author_by_email
+                    .entry(signature.email.to_ascii_lowercase().into())
+                    .or_insert_with(|| {
+                        authors.push((
+                            Author {
+                                name: signature.name.as_ref().into(),
+                                email: signature.email.as_ref().into(),
+                                num_commits: 0,
+                                first_commit: signature.time,
+                                last_commit: signature.time,
+                                active_days: 0,
+                                lines: options.line_stats.then(Summary::default),
+                            },
+                            HashSet::new(),
+                        ));
+                        authors.len() - 1
+                    });
+                let (author, days) = &mut authors[author_idx];
+                author.num_commits += 1;
+                if signature.time.seconds < author.first_commit.seconds {
+                    author.first_commit = signature.time;
+                }
+                if signature.time.seconds > author.last_commit.seconds {
+                    author.last_commit = signature.time;
+                    author.name = signature.name.as_ref().into();
+                    author.email = signature.email.as_ref().into();
+                }
+                const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+                days.insert((signature.time.seconds + i64::from(signature.time.offset)).div_euclid(SECONDS_PER_DAY));
+
+                if options.line_stats {
+                    let mut parents = info.parent_ids.iter().copied();
+                    match (parents.next(), parents.next()) {
+                        (first_parent, None) => commits_to_diff.push((author_idx, first_parent, info.id)),
+                        (Some(_), Some(_)) => skipped_merge_commits += 1,
+                        (None, Some(_)) => unreachable!("the first parent exists if there is a second one"),
+                    }
+                }
+                num_commits += 1;
+                traverse_progress.inc();
+            }
+
+            if options.line_stats && !commits_to_diff.is_empty() {
+                let mut diff_progress =
+                    progress.add_child_with_id("diff commits".into(), ProgressId::DiffCommits.into());
+                diff_progress.init(Some(commits_to_diff.len()), gix_features::progress::count("commits"));
+                let counter = diff_progress.counter();
+
+                let threads = gix_features::parallel::num_threads(options.thread_limit);
+                let chunk_size = (commits_to_diff.len() + threads - 1) / threads;
+                let summaries = gix_features::parallel::threads(|scope| -> Result<Vec<Summary>, Error> {
+                    let workers: Vec<_> = commits_to_diff
+                        .chunks(chunk_size)
+                        .enumerate()
+                        .map(|(idx, chunk)| {
+                            let mut repo = self.clone();
+                            repo.object_cache_size_if_unset((64 * 1024 * 1024) / threads);
+                            let counter = counter.clone();
+                            gix_features::parallel::build_thread()
+                                .name(format!("gix.author_stats.diff.{idx}"))
+                                .spawn_scoped(scope, move || {
+                                    chunk
+                                        .iter()
+                                        .map(|(_, parent, id)| {
+                                            if should_interrupt.load(Ordering::Relaxed) {
+                                                return Err(Error::Interrupted);
+                                            }
+                                            let tree = repo.find_object(*id)?.peel_to_tree()?;
+                                            let parent_tree = match parent {
+                                                Some(parent) => repo.find_object(*parent)?.peel_to_tree()?,
+                                                None => repo.empty_tree(),
+                                            };
+                                            let files = parent_tree.changes()?.stats(&tree)?;
+                                            counter.fetch_add(1, Ordering::Relaxed);
+                                            Ok(Summary::from_files(&files))
+                                        })
+                                        .collect::<Result<Vec<_>, _>>()
+                                })
+                                .expect("valid name")
+                        })
+                        .collect();
+                    let mut summaries = Vec::with_capacity(commits_to_diff.len());
+                    for worker in workers {
+                        summaries.extend(worker.join().expect("no panic")?);
+                    }
+                    Ok(summaries)
+                })?;
+
+                for ((author_idx, _, _), summary) in commits_to_diff.iter().zip(summaries) {
+                    let lines = authors[*author_idx]
+                        .0
+                        .lines
+                        .as_mut()
+                        .expect("set if stats are computed");
+                    lines.files_changed += summary.files_changed;
+                    lines.insertions += summary.insertions;
+                    lines.removals += summary.removals;
+                }
+            }
+
+            let mut authors: Vec<_> = authors
+                .into_iter()
+                .map(|(mut author, days)| {
+                    author.active_days = days.len();
+                    author
+                })
+                .collect();
+            authors.sort_by(|a, b| b.num_commits.cmp(&a.num_commits).then_with(|| a.email.cmp(&b.email)));
+            Ok(Outcome {
+                authors,
+                num_commits,
+                skipped_merge_commits,
+            })
+        }
+    }
+}
@@ -1,6 +1,6 @@
 impl Clone for crate::Repository {
     fn clone(&self) -> Self {
-        crate::Repository::from_refs_and_objects(
+        let mut repo = crate::Repository::from_refs_and_objects(
             self.refs.clone(),
             self.objects.clone(),
             self.work_tree.clone(),
@@ -12,7 +12,13 @@ impl Clone for crate::Repository {
             self.shallow_commits.clone(),
             #[cfg(feature = "attributes")]
             self.modules.clone(),
-        )
+        );
+        repo.objects.ignore_replacements = self.objects.ignore_replacements;
+        #[cfg(feature = "blocking-network-client")]
+        {
+            repo.connection_cache = self.connection_cache.clone();
+        }
+        repo
     }
 }
 
@@ -36,9 +42,9 @@ impl PartialEq<crate::Repository> for crate::Repository {
 
 impl From<&crate::ThreadSafeRepository> for crate::Repository {
     fn from(repo: &crate::ThreadSafeRepository) -> Self {
-        crate::Repository::from_refs_and_objects(
+        let mut repo = crate::Repository::from_refs_and_objects(
             repo.refs.clone(),
-            repo.objects.to_handle().into(),
+            gix_odb::memory::Proxy::new(repo.objects.to_handle().into(), repo.config.object_hash),
             repo.work_tree.clone(),
             repo.common_dir.clone(),
             repo.config.clone(),
@@ -48,15 +54,19 @@ impl From<&crate::ThreadSafeRepository> for crate::Repository {
             repo.shallow_commits.clone(),
             #[cfg(feature = "attributes")]
             repo.modules.clone(),
-        )
+        );
+        if repo.options.object_memory {
+            repo.objects.enable_memory();
+        }
+        repo
     }
 }
 
 impl From<crate::ThreadSafeRepository> for crate::Repository {
     fn from(repo: crate::ThreadSafeRepository) -> Self {
-        crate::Repository::from_refs_and_objects(
+        let mut repo = crate::Repository::from_refs_and_objects(
             repo.refs,
-            repo.objects.to_handle().into(),
+            gix_odb::memory::Proxy::new(repo.objects.to_handle().into(), repo.config.object_hash),
             repo.work_tree,
             repo.common_dir,
             repo.config,
@@ -66,7 +76,11 @@ impl From<crate::ThreadSafeRepository> for crate::Repository {
             repo.shallow_commits,
             #[cfg(feature = "attributes")]
             repo.modules.clone(),
-        )
+        );
+        if repo.options.object_memory {
+            repo.objects.enable_memory();
+        }
+        repo
     }
 }
 
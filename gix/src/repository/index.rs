@@ -112,9 +112,49 @@ impl crate::Repository {
     ) -> Result<gix_index::File, gix_traverse::tree::breadthfirst::Error> {
         Ok(gix_index::File::from_state(
             gix_index::State::from_tree(tree, &self.objects)?,
-            self.git_dir().join("index"),
+            self.index_path(),
         ))
     }
+
+    /// Return the sparse-checkout patterns of this worktree, or `None` if `core.sparseCheckout` isn't enabled or if there
+    /// is no `$GIT_DIR/info/sparse-checkout` file, in which case all files are supposed to be present.
+    ///
+    /// The patterns are interpreted in cone mode if `core.sparseCheckoutCone` is enabled. They can be used to
+    /// [update the `SKIP_WORKTREE` flags](crate::sparse::Patterns::update_skip_worktree()) of index entries before
+    /// computing the status, and to [avoid adding files](crate::sparse::Patterns::is_included()) outside of the sparse-checkout.
+    #[cfg(feature = "excludes")]
+    pub fn sparse_checkout_patterns(&self) -> Result<Option<crate::sparse::Patterns>, crate::sparse::Error> {
+        use crate::config::tree::{Core, Key};
+        let boolean = |key: &'static crate::config::tree::keys::Boolean| -> Result<bool, crate::sparse::Error> {
+            Ok(self
+                .config
+                .resolved
+                .boolean("core", None, key.name())
+                .map(|res| key.enrich_error(res))
+                .transpose()
+                .with_lenient_default(self.config.lenient_config)?
+                .unwrap_or_default())
+        };
+        if !boolean(&Core::SPARSE_CHECKOUT)? {
+            return Ok(None);
+        }
+        let path = self.git_dir().join("info").join("sparse-checkout");
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(crate::sparse::Error::Io { source: err, path }),
+        };
+        let case = if self.config.ignore_case {
+            gix_glob::pattern::Case::Fold
+        } else {
+            gix_glob::pattern::Case::Sensitive
+        };
+        Ok(Some(crate::sparse::Patterns::from_bytes(
+            &bytes,
+            boolean(&Core::SPARSE_CHECKOUT_CONE)?,
+            case,
+        )))
+    }
 }
 
 impl std::ops::Deref for IndexPersistedOrInMemory {
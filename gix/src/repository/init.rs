@@ -14,6 +14,7 @@ impl crate::Repository {
         #[cfg(feature = "attributes")] modules: crate::submodule::ModulesFileStorage,
     ) -> Self {
         setup_objects(&mut objects, &config);
+        objects.ignore_replacements = !linked_worktree_options.object_replacements;
         crate::Repository {
             bufs: RefCell::new(Vec::with_capacity(4)),
             work_tree,
@@ -27,6 +28,8 @@ impl crate::Repository {
             shallow_commits,
             #[cfg(feature = "attributes")]
             modules,
+            #[cfg(feature = "blocking-network-client")]
+            connection_cache: Default::default(),
         }
     }
 
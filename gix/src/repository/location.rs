@@ -21,8 +21,17 @@ impl crate::Repository {
     }
 
     /// Return the path to the worktree index file, which may or may not exist.
+    ///
+    /// It can be overridden with `gitoxide.core.indexFile` or the `GIT_INDEX_FILE` environment variable.
     pub fn index_path(&self) -> PathBuf {
-        self.git_dir().join("index")
+        use crate::config::tree::{gitoxide, Key};
+        match self.config.resolved.string_filter_by_key(
+            gitoxide::Core::INDEX_FILE.logical_name().as_str(),
+            &mut self.filter_config_section(),
+        ) {
+            Some(path) => self.options.current_dir_or_empty().join(gix_path::from_bstr(path)),
+            None => self.git_dir().join("index"),
+        }
     }
 
     /// The path to the `.gitmodules` file in the worktree, if a worktree is available.
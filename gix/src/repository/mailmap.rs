@@ -8,8 +8,13 @@ impl crate::Repository {
     ///
     /// This represents typical usage within git, which also works with what's there without considering a populated mailmap
     /// a reason to abort an operation, considering it optional.
+    ///
+    /// The mailmap is always empty if it was [disabled when opening the repository](crate::open::Options::mailmap()).
     pub fn open_mailmap(&self) -> gix_mailmap::Snapshot {
         let mut out = gix_mailmap::Snapshot::default();
+        if !self.options.mailmap {
+            return out;
+        }
         self.open_mailmap_into(&mut out).ok();
         out
     }
@@ -0,0 +1,630 @@
+use std::{
+    collections::BTreeSet,
+    io::{Seek, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use gix_features::{
+    parallel::InOrderIter,
+    progress::{Count, DynNestedProgress, NestedProgress, Progress},
+};
+use gix_object::FindExt;
+use gix_pack::data::output;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    config::{cache::util::ApplyLeniency, tree::Key},
+    maintenance::{
+        auto, lock,
+        pack_loose_objects::{Error, Options, Outcome, ProgressId},
+        write_commit_graph,
+    },
+};
+
+/// Maintenance
+impl crate::Repository {
+    /// Write all loose objects of this repository into a single new pack and delete them afterwards, which is the
+    /// most basic maintenance task to keep object access fast and to reduce the amount of files in the object database.
+    ///
+    /// Objects are compressed using multiple threads and the new pack is verified while its index is created, before
+    /// any loose object is deleted. An existing multi-pack index is rewritten to include the new pack, and
+    /// `options` further configure the operation.
+    ///
+    /// Note that objects of alternate object databases are not considered, and that loose objects which are written
+    /// while this operation is in progress are left untouched.
+    pub fn pack_loose_objects<P>(
+        &self,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        self.pack_loose_objects_inner(&mut progress, should_interrupt, options)
+    }
+
+    fn pack_loose_objects_inner(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        Options {
+            thread_limit,
+            compression_level,
+            delete_loose_objects,
+            write_multi_pack_index,
+        }: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("gix::Repository::pack_loose_objects()");
+        let object_hash = self.object_hash();
+        let objects_dir = self.objects.store_ref().path();
+        let pack_dir = objects_dir.join("pack");
+        let loose = gix_odb::loose::Store::at(objects_dir, object_hash);
+
+        let ids = {
+            let mut collect_progress = progress.add_child_with_id(
+                "collecting loose objects".into(),
+                ProgressId::CollectLooseObjects.into(),
+            );
+            collect_progress.init(None, crate::progress::count("objects"));
+            let mut ids = Vec::new();
+            for id in loose.iter() {
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+                ids.push(id?);
+                collect_progress.inc();
+            }
+            ids.sort();
+            ids
+        };
+        if ids.is_empty() {
+            return Ok(Outcome {
+                pack: None,
+                deleted_loose_objects: 0,
+                multi_pack_index_path: None,
+            });
+        }
+
+        let compression_level = match compression_level {
+            Some(level) => level,
+            None => self.pack_compression_level()?,
+        };
+        let num_objects = ids.len();
+        let entries = output::entry::iter_from_counts(
+            ids.iter().map(|id| output::Count::from_data(*id, None)).collect(),
+            self.objects.clone().into_arc()?,
+            Box::new(progress.add_child_with_id("compressing".into(), ProgressId::CompressObjects.into())),
+            output::entry::iter_from_counts::Options {
+                thread_limit,
+                compression_level,
+                ..Default::default()
+            },
+        );
+
+        // The pack is written to a temporary file first so it can be verified and indexed like any received pack.
+        std::fs::create_dir_all(&pack_dir)?;
+        let mut pack_file = std::io::BufWriter::new(gix_tempfile::new(
+            &pack_dir,
+            gix_tempfile::ContainingDirectory::Exists,
+            gix_tempfile::AutoRemove::Tempfile,
+        )?);
+        {
+            let mut write_progress = progress.add_child_with_id("writing pack".into(), ProgressId::WritePack.into());
+            write_progress.init(None, crate::progress::bytes());
+            let start = std::time::Instant::now();
+            let mut pack_bytes = output::bytes::FromEntriesIter::new(
+                InOrderIter::from(entries),
+                &mut pack_file,
+                num_objects as u32,
+                gix_pack::data::Version::default(),
+                object_hash,
+            );
+            for written in pack_bytes.by_ref() {
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+                write_progress.inc_by(written? as usize);
+            }
+            write_progress.show_throughput(start);
+        }
+        let mut pack_file = pack_file.into_inner().map_err(std::io::IntoInnerError::into_error)?;
+        pack_file.rewind()?;
+
+        let bundle = gix_pack::Bundle::write_to_directory(
+            &mut std::io::BufReader::new(pack_file),
+            Some(&pack_dir),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            gix_pack::bundle::write::Options {
+                thread_limit,
+                iteration_mode: gix_pack::data::input::Mode::Verify,
+                index_version: Default::default(),
+                object_hash,
+                resolve_memory_limit: None,
+            },
+        )?;
+
+        let mut deleted_loose_objects = 0;
+        if delete_loose_objects {
+            let mut delete_progress =
+                progress.add_child_with_id("deleting loose objects".into(), ProgressId::DeleteLooseObjects.into());
+            delete_progress.init(Some(num_objects), crate::progress::count("objects"));
+            let mut fan_out_dirs = BTreeSet::new();
+            for id in &ids {
+                let path = loose.object_path(id);
+                match std::fs::remove_file(&path) {
+                    Ok(()) => deleted_loose_objects += 1,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err.into()),
+                }
+                if let Some(dir) = path.parent() {
+                    fan_out_dirs.insert(dir.to_owned());
+                }
+                delete_progress.inc();
+            }
+            for dir in fan_out_dirs {
+                // Fails if the directory still contains objects, which is expected.
+                std::fs::remove_dir(dir).ok();
+            }
+        }
+
+        let multi_pack_index_path = if write_multi_pack_index || pack_dir.join("multi-pack-index").is_file() {
+            Some(write_multi_pack_index_for_all_packs(
+                &pack_dir,
+                object_hash,
+                self.config.lock_timeout()?.0,
+                progress,
+                should_interrupt,
+            )?)
+        } else {
+            None
+        };
+
+        if let Some(keep_path) = &bundle.keep_path {
+            std::fs::remove_file(keep_path)?;
+        }
+        Ok(Outcome {
+            pack: Some(bundle),
+            deleted_loose_objects,
+            multi_pack_index_path,
+        })
+    }
+}
+
+/// Write a multi-pack index for all packs in `pack_dir`, locking it with `lock_mode`, and return its path.
+fn write_multi_pack_index_for_all_packs(
+    pack_dir: &Path,
+    object_hash: gix_hash::Kind,
+    lock_mode: gix_lock::acquire::Fail,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<PathBuf, Error> {
+    let multi_pack_index_path = pack_dir.join("multi-pack-index");
+    let mut index_paths = Vec::new();
+    for entry in std::fs::read_dir(pack_dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(false, |ext| ext == "idx") {
+            index_paths.push(path);
+        }
+    }
+    let mut out = std::io::BufWriter::new(gix_lock::File::acquire_to_update_resource(
+        &multi_pack_index_path,
+        lock_mode,
+        None,
+    )?);
+    gix_pack::multi_index::File::write_from_index_paths(
+        index_paths,
+        &mut out,
+        progress,
+        should_interrupt,
+        gix_pack::multi_index::write::Options { object_hash },
+    )?;
+    out.flush()?;
+    out.into_inner()
+        .map_err(std::io::IntoInnerError::into_error)?
+        .commit()?;
+    Ok(multi_pack_index_path)
+}
+
+/// Automatic maintenance
+impl crate::Repository {
+    /// Determine if the object database needs maintenance using the same heuristics as `git gc --auto`, which is
+    /// useful to call after write-heavy operations like fetches or commits.
+    ///
+    /// The amount of loose objects is estimated by counting the objects in a single fan-out directory, and
+    /// is a reason for maintenance if it exceeds `gc.auto` (defaulting to 6700).
+    /// The amount of packs which aren't marked with a `.keep` file is a reason for maintenance if it exceeds
+    /// `gc.autoPackLimit` (defaulting to 50). Setting either of these to 0 disables the respective check, and setting
+    /// `gc.auto` to 0 disables all checks.
+    ///
+    /// Note that packs which are covered by the multi-pack index aren't counted as it already provides fast access
+    /// to all of their objects.
+    pub fn auto_maintenance_reasons(&self) -> Result<auto::Reasons, auto::reasons::Error> {
+        use crate::config::tree::Gc;
+
+        let config = &self.config.resolved;
+        let lenient = self.config.lenient_config;
+        let auto_threshold = config
+            .integer_by_key(Gc::AUTO.logical_name().as_str())
+            .map(|res| Gc::AUTO.try_into_usize(res))
+            .transpose()
+            .with_leniency(lenient)?
+            .unwrap_or(6700);
+        let pack_limit = config
+            .integer_by_key(Gc::AUTO_PACK_LIMIT.logical_name().as_str())
+            .map(|res| Gc::AUTO_PACK_LIMIT.try_into_usize(res))
+            .transpose()
+            .with_leniency(lenient)?
+            .unwrap_or(50);
+
+        let mut reasons = auto::Reasons::default();
+        if auto_threshold == 0 {
+            return Ok(reasons);
+        }
+
+        let objects_dir = self.objects.store_ref().path();
+        // Objects are evenly distributed among the 256 fan-out directories, so one of them is enough for an estimate.
+        let hex_len = self.object_hash().len_in_hex() - 2;
+        let loose_in_fan_out_dir = match std::fs::read_dir(objects_dir.join("17")) {
+            Ok(entries) => {
+                let mut count = 0;
+                for entry in entries {
+                    let name = entry?.file_name();
+                    let name = name.to_string_lossy();
+                    if name.len() == hex_len && name.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        count += 1;
+                    }
+                }
+                count
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err.into()),
+        };
+        if loose_in_fan_out_dir > (auto_threshold + 255) / 256 {
+            reasons.loose_objects = Some(loose_in_fan_out_dir * 256);
+        }
+
+        if pack_limit != 0 {
+            let pack_dir = objects_dir.join("pack");
+            let indexed_by_multi_pack_index: BTreeSet<PathBuf> =
+                gix_pack::multi_index::File::at(pack_dir.join("multi-pack-index"))
+                    .map(|index| index.index_names().to_owned())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+            let mut packs = 0;
+            match std::fs::read_dir(&pack_dir) {
+                Ok(entries) => {
+                    for entry in entries {
+                        let path = entry?.path();
+                        if path.extension().map_or(true, |ext| ext != "pack")
+                            || path.with_extension("keep").is_file()
+                            || path
+                                .with_extension("idx")
+                                .file_name()
+                                .map_or(false, |name| indexed_by_multi_pack_index.contains(Path::new(name)))
+                        {
+                            continue;
+                        }
+                        packs += 1;
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            if packs > pack_limit {
+                reasons.packs = Some(packs);
+            }
+        }
+        Ok(reasons)
+    }
+
+    /// Perform maintenance if [`auto_maintenance_reasons()`](Self::auto_maintenance_reasons()) indicates that
+    /// it's needed, to keep the performance of long-lived processes which keep writing objects from degrading over time.
+    ///
+    /// If `options.detach` is `true`, `git gc --auto` is spawned to do the work in the background, and this method
+    /// returns without waiting for it while a thread reaps the process once it's done. Otherwise all loose objects are packed using
+    /// [`pack_loose_objects()`](Self::pack_loose_objects()), and if there are too many packs, a multi-pack index is
+    /// written for all of them. This happens while holding the [maintenance lock](Self::lock_for_maintenance()),
+    /// and nothing is done if another process holds it already.
+    pub fn auto_maintenance<P>(
+        &self,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: auto::Options,
+    ) -> Result<auto::Outcome, auto::Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        self.auto_maintenance_inner(&mut progress, should_interrupt, options)
+    }
+
+    fn auto_maintenance_inner(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        auto::Options { thread_limit, detach }: auto::Options,
+    ) -> Result<auto::Outcome, auto::Error> {
+        let _span = gix_trace::coarse!("gix::Repository::auto_maintenance()");
+        let reasons = self.auto_maintenance_reasons()?;
+        if reasons.is_empty() {
+            return Ok(auto::Outcome::NotNeeded);
+        }
+
+        if detach {
+            let mut child = std::process::Command::new(if cfg!(windows) { "git.exe" } else { "git" })
+                .args(["gc", "--auto", "--quiet"])
+                .current_dir(self.git_dir())
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .map_err(auto::Error::Spawn)?;
+            let pid = child.id();
+            // Wait for the process so it doesn't turn into a zombie in long-running processes. If this process exits
+            // first, the child is reparented and reaped by the system.
+            std::thread::Builder::new()
+                .name("gix-auto-maintenance-reaper".into())
+                .spawn(move || child.wait())
+                .map_err(auto::Error::Spawn)?;
+            return Ok(auto::Outcome::Detached { reasons, pid });
+        }
+
+        let _lock = match self.lock_for_maintenance(lock::Options::default()) {
+            Ok(lock) => lock,
+            Err(lock::Error::Held { holder }) => return Ok(auto::Outcome::AlreadyRunning { reasons, holder }),
+            Err(err) => return Err(err.into()),
+        };
+        let outcome = if reasons.loose_objects.is_some() {
+            self.pack_loose_objects_inner(
+                progress,
+                should_interrupt,
+                Options {
+                    thread_limit,
+                    write_multi_pack_index: reasons.packs.is_some(),
+                    ..Default::default()
+                },
+            )?
+        } else {
+            Outcome {
+                pack: None,
+                deleted_loose_objects: 0,
+                multi_pack_index_path: Some(write_multi_pack_index_for_all_packs(
+                    &self.objects.store_ref().path().join("pack"),
+                    self.object_hash(),
+                    self.config
+                        .lock_timeout()
+                        .map_err(crate::maintenance::pack_loose_objects::Error::from)?
+                        .0,
+                    progress,
+                    should_interrupt,
+                )?),
+            }
+        };
+        Ok(auto::Outcome::Performed { reasons, outcome })
+    }
+}
+
+/// Locking
+impl crate::Repository {
+    /// Lock the repository to indicate that maintenance is running, using the same `gc.pid` file as `git gc` so that
+    /// neither `gix` nor `git` processes start conflicting maintenance while the returned lock is alive.
+    ///
+    /// Like `git gc`, `gc.pid.lock` is only held for a moment to check the holder recorded in `gc.pid` and to replace it
+    /// with this process, so `git gc --auto` running concurrently sees the holder and backs off.
+    /// A lock is stale and taken over if it's older than `options.stale_after`, or if it was created on this machine
+    /// by a process that isn't running anymore. Otherwise, `options.policy` determines whether to fail, wait
+    /// for the lock to be released or to steal it. Note that this also applies if the lock is held by this process,
+    /// so maintenance is coordinated between threads as well.
+    pub fn lock_for_maintenance(&self, options: lock::Options) -> Result<crate::maintenance::Lock, lock::Error> {
+        let path = self.common_dir().join("gc.pid");
+        let ours = lock::Holder {
+            pid: std::process::id(),
+            hostname: hostname(),
+        };
+        let deadline = match options.policy {
+            lock::Policy::Wait(duration) => Some(Instant::now() + duration),
+            lock::Policy::Fail | lock::Policy::Steal => None,
+        };
+        let remaining = || deadline.map_or(Duration::ZERO, |d| d.saturating_duration_since(Instant::now()));
+        let steal = options.policy == lock::Policy::Steal;
+        loop {
+            let mut file = match gix_lock::File::acquire_to_update_resource(
+                &path,
+                gix_lock::acquire::Fail::AfterDurationWithBackoff(lock::LOCK_FILE_TIMEOUT),
+                None,
+            ) {
+                Ok(file) => file,
+                // Never remove the lock file of another process, it's only held briefly unless it crashed.
+                Err(err) if remaining().is_zero() => return Err(err.into()),
+                Err(_) => continue,
+            };
+            // Like `git gc`, check the holder recorded by a previous process while holding the lock file,
+            // as it may have been interrupted before it could remove `gc.pid`.
+            match lock::Holder::from_path(&path)? {
+                Some(holder) if !steal && !is_stale(&path, &holder, ours.hostname.as_ref(), options.stale_after)? => {
+                    drop(file);
+                    let remaining = remaining();
+                    if remaining.is_zero() {
+                        return Err(lock::Error::Held { holder });
+                    }
+                    std::thread::sleep(remaining.min(Duration::from_millis(100)));
+                }
+                _ => {
+                    file.write_all(format!("{} {}", ours.pid, ours.hostname).as_bytes())?;
+                    let (path, _) = file.commit()?;
+                    let id = lock::FileId::from_path(&path)?;
+                    return Ok(crate::maintenance::Lock { path, holder: ours, id });
+                }
+            }
+        }
+    }
+}
+
+/// Return `true` if the lock at `path`, held by `holder`, is older than `stale_after` or was created by a process
+/// on this machine, named `hostname`, which isn't running anymore.
+fn is_stale(path: &Path, holder: &lock::Holder, hostname: &BStr, stale_after: Duration) -> std::io::Result<bool> {
+    let modified = match std::fs::metadata(path).and_then(|m| m.modified()) {
+        Ok(modified) => modified,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+        Err(err) => return Err(err),
+    };
+    let is_old = modified.elapsed().map_or(false, |age| age >= stale_after);
+    Ok(is_old || (holder.hostname == hostname && !process_is_alive(holder.pid)))
+}
+
+/// Return the name of this machine, or `unknown` if it can't be determined.
+fn hostname() -> BString {
+    ["/proc/sys/kernel/hostname", "/etc/hostname"]
+        .iter()
+        .find_map(|path| {
+            let name = std::fs::read(path).ok()?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| name.into())
+        })
+        .or_else(|| {
+            ["HOSTNAME", "COMPUTERNAME"]
+                .iter()
+                .find_map(std::env::var_os)
+                .and_then(|name| gix_path::os_string_into_bstring(name).ok())
+        })
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Return `true` if the process with `pid` on this machine may still be running.
+fn process_is_alive(pid: u32) -> bool {
+    let proc = Path::new("/proc");
+    // Without a way to tell, only the age of the lock can make it stale.
+    !proc.join("self").is_dir() || proc.join(pid.to_string()).is_dir()
+}
+
+/// Commit-graph
+impl crate::Repository {
+    /// Write a new commit-graph file for all commits reachable from `tips` into `objects/info/commit-graph`, replacing an
+    /// existing one, similar to `git commit-graph write --no-split`.
+    ///
+    /// All `tips` must be commits, and `options` control whether or not bloom filters for changed paths are written as well.
+    pub fn write_commit_graph<P>(
+        &self,
+        tips: impl IntoIterator<Item = gix_hash::ObjectId>,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: write_commit_graph::Options,
+    ) -> Result<write_commit_graph::Outcome, write_commit_graph::Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        self.write_commit_graph_inner(&mut tips.into_iter(), &mut progress, should_interrupt, options)
+    }
+
+    fn write_commit_graph_inner(
+        &self,
+        tips: &mut dyn Iterator<Item = gix_hash::ObjectId>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        write_commit_graph::Options { changed_paths }: write_commit_graph::Options,
+    ) -> Result<write_commit_graph::Outcome, write_commit_graph::Error> {
+        use write_commit_graph::{Error, ProgressId};
+        let _span = gix_trace::coarse!("gix::Repository::write_commit_graph()");
+
+        let mut commits = Vec::new();
+        {
+            let mut progress =
+                progress.add_child_with_id("traversing commits".into(), ProgressId::TraverseCommits.into());
+            progress.init(None, crate::progress::count("commits"));
+            let mut seen = gix_hashtable::HashSet::default();
+            let mut queue: Vec<_> = tips.collect();
+            let mut buf = Vec::new();
+            while let Some(id) = queue.pop() {
+                if !seen.insert(id) {
+                    continue;
+                }
+                let commit = self.objects.find_commit(&id, &mut buf)?;
+                let parents: Vec<_> = commit.parents().collect();
+                queue.extend(parents.iter().filter(|id| !seen.contains(*id)));
+                commits.push(gix_commitgraph::file::write::Commit {
+                    id,
+                    tree: commit.tree(),
+                    parents,
+                    committer_timestamp: commit.committer.time.seconds.try_into().unwrap_or_default(),
+                    changed_paths: Vec::new(),
+                });
+                progress.inc();
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+            }
+        }
+
+        if changed_paths {
+            let mut progress =
+                progress.add_child_with_id("computing changed paths".into(), ProgressId::ComputeChangedPaths.into());
+            progress.init(Some(commits.len()), crate::progress::count("commits"));
+            let tree_by_commit: gix_hashtable::HashMap<_, _> =
+                commits.iter().map(|commit| (commit.id, commit.tree)).collect();
+            let (mut buf, mut parent_buf) = (Vec::new(), Vec::new());
+            let mut state = gix_diff::tree::State::default();
+            for commit in &mut commits {
+                let tree = self.objects.find_tree_iter(&commit.tree, &mut buf)?;
+                let parent_tree = commit
+                    .parents
+                    .first()
+                    .map(|parent| self.objects.find_tree_iter(&tree_by_commit[parent], &mut parent_buf))
+                    .transpose()?;
+                let mut recorder = gix_diff::tree::Recorder::default();
+                gix_diff::tree::Changes::from(parent_tree)
+                    .needed_to_obtain(tree, &mut state, &self.objects, &mut recorder)
+                    .map_err(|source| Error::DiffTree { source, id: commit.id })?;
+                // Like git, only the paths of changed files are recorded, directories are implied.
+                commit.changed_paths = recorder
+                    .records
+                    .into_iter()
+                    .filter_map(|change| {
+                        use gix_diff::tree::recorder::Change::*;
+                        match change {
+                            Addition { entry_mode, path, .. }
+                            | Deletion { entry_mode, path, .. }
+                            | Modification { entry_mode, path, .. } => entry_mode.is_no_tree().then_some(path),
+                        }
+                    })
+                    .collect();
+                progress.inc();
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+            }
+        }
+
+        let path = self.objects.store_ref().path().join("info").join("commit-graph");
+        std::fs::create_dir_all(path.parent().expect("parent is objects directory"))?;
+        let mut out = std::io::BufWriter::new(gix_lock::File::acquire_to_update_resource(
+            &path,
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?);
+        let outcome = gix_commitgraph::File::write_from_commits(
+            commits,
+            &mut out,
+            gix_commitgraph::file::write::Options {
+                object_hash: self.object_hash(),
+                changed_paths,
+            },
+        )?;
+        out.flush()?;
+        out.into_inner()
+            .map_err(std::io::IntoInnerError::into_error)?
+            .commit()?;
+        Ok(write_commit_graph::Outcome {
+            path,
+            num_commits: outcome.num_commits,
+            checksum: outcome.checksum,
+        })
+    }
+}
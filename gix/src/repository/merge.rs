@@ -0,0 +1,533 @@
+use gix_hash::ObjectId;
+use gix_ref::Category;
+
+use crate::{
+    bstr::{BString, ByteVec},
+    merge, Id,
+};
+
+/// Merging
+impl crate::Repository {
+    /// Merge the commits `theirs` into the commit `ours` and write the resulting tree to the object database, similar to
+    /// `git merge-tree --write-tree ours theirs`, without the need for a worktree or an index.
+    ///
+    /// This is useful to preview merges or to merge branches on the server side, as the tree of the outcome can be used
+    /// for a merge commit if there are no conflicts. Otherwise, the tree contains files with conflict markers, and the
+    /// conflicts are described in detail along with the involved entries of all sides.
+    /// `labels` are used to annotate conflict markers, and typically contain the names of the branches to merge.
+    ///
+    /// The [merge-base](Self::merge_bases()) is used as common ancestor, or an empty tree if there is none. If there are
+    /// multiple merge-bases, they are merged into a virtual merge-base first, just like `git merge` does it.
+    ///
+    /// Only the [octopus](merge::commits::Strategy::Octopus) and [ours](merge::commits::Strategy::Ours) strategies
+    /// can merge more than one commit.
+    pub fn merge_commits(
+        &self,
+        ours: impl Into<ObjectId>,
+        theirs: impl IntoIterator<Item = impl Into<ObjectId>>,
+        labels: gix_merge::blob::Labels<'_>,
+        options: merge::commits::Options,
+    ) -> Result<merge::commits::Outcome, merge::commits::Error> {
+        let theirs: Vec<_> = theirs.into_iter().map(Into::into).collect();
+        gix_merge::merge(
+            &ours.into(),
+            &theirs,
+            labels,
+            &options,
+            &self.objects,
+            &mut self.revision_graph(),
+            |kind, data| self.write_object_inner(data, kind).map(Id::detach),
+        )
+    }
+}
+
+/// Merge messages
+impl crate::Repository {
+    /// Produce the message of a commit that merges `heads` into `HEAD`, similar to what `git fmt-merge-msg` produces
+    /// for `git merge`.
+    ///
+    /// The title names all heads grouped by kind, like `Merge branches 'a' and 'b', tag 'v1.0'`, and ends with ` into <branch>`
+    /// unless the current branch is [suppressed](merge::message::Options::suppress_destinations). If a [log](merge::message::Options::log)
+    /// is requested, the summaries of the commits each head brings in are listed below the title, with merge commits
+    /// being counted but not listed.
+    pub fn merge_message(
+        &self,
+        heads: &[merge::message::Head],
+        options: merge::message::Options,
+    ) -> Result<BString, merge::message::Error> {
+        let mut branches = Vec::new();
+        let mut remote_branches = Vec::new();
+        let mut tags = Vec::new();
+        let mut commits = Vec::new();
+        let mut log_names = Vec::with_capacity(heads.len());
+        for head in heads {
+            let (list, name) = match head
+                .reference
+                .as_ref()
+                .map(|name| (name.category_and_short_name(), name))
+            {
+                Some((Some((Category::LocalBranch, short)), _)) => (&mut branches, short.to_owned()),
+                Some((Some((Category::RemoteBranch, short)), _)) => (&mut remote_branches, short.to_owned()),
+                Some((Some((Category::Tag, short)), _)) => (&mut tags, short.to_owned()),
+                Some((_, name)) => (&mut commits, name.shorten().to_owned()),
+                None => (&mut commits, head.id.to_string().into()),
+            };
+            list.push(format!("'{name}'"));
+            log_names.push(name);
+        }
+
+        let mut title = String::from("Merge ");
+        let mut separator = "";
+        for (singular, plural, names) in [
+            ("branch", "branches", &branches),
+            ("remote-tracking branch", "remote-tracking branches", &remote_branches),
+            ("tag", "tags", &tags),
+            ("commit", "commits", &commits),
+        ] {
+            let Some((last, rest)) = names.split_last() else {
+                continue;
+            };
+            title.push_str(separator);
+            separator = ", ";
+            if rest.is_empty() {
+                title.push_str(&format!("{singular} {last}"));
+            } else {
+                title.push_str(&format!("{plural} {} and {last}", rest.join(", ")));
+            }
+        }
+
+        let head = self.head()?;
+        let destination = head
+            .referent_name()
+            .map_or_else(|| "HEAD".into(), |name| name.shorten().to_owned());
+        let is_suppressed = match &options.suppress_destinations {
+            Some(patterns) => patterns.iter().any(|pattern| {
+                gix_glob::wildmatch(
+                    pattern.as_ref(),
+                    destination.as_ref(),
+                    gix_glob::wildmatch::Mode::empty(),
+                )
+            }),
+            None => destination == "main" || destination == "master",
+        };
+        if !is_suppressed {
+            title.push_str(&format!(" into {destination}"));
+        }
+
+        let mut message = BString::from(title);
+        message.push(b'\n');
+        let Some(limit) = options.log else {
+            return Ok(message);
+        };
+        let head_id = head.id().map(crate::Id::detach);
+        for (head, name) in heads.iter().zip(log_names) {
+            let hidden: gix_hashtable::HashSet<ObjectId> = match head_id {
+                Some(head_id) => {
+                    let merge_bases = self.merge_bases(head.id, Some(head_id))?;
+                    if merge_bases.is_empty() {
+                        Default::default()
+                    } else {
+                        self.rev_walk(merge_bases)
+                            .all()?
+                            .map(|info| info.map(|info| info.id))
+                            .collect::<Result<_, _>>()?
+                    }
+                }
+                None => Default::default(),
+            };
+            let mut num_commits = 0;
+            let mut summaries = Vec::new();
+            for info in self
+                .rev_walk(Some(head.id))
+                .sorting(gix_traverse::commit::Sorting::ByCommitTimeNewestFirst)
+                .selected(move |id| !hidden.contains(id))?
+            {
+                let info = info?;
+                num_commits += 1;
+                if summaries.len() > limit || info.parent_ids.len() > 1 {
+                    continue;
+                }
+                summaries.push(info.object()?.message()?.summary().into_owned());
+            }
+            if num_commits == 0 {
+                continue;
+            }
+            if num_commits > limit {
+                message.push_str(format!("\n* {name}: ({num_commits} commits)\n"));
+            } else {
+                message.push_str(format!("\n* {name}:\n"));
+            }
+            for (idx, summary) in summaries.iter().enumerate() {
+                if idx >= limit {
+                    message.push_str("  ...\n");
+                    break;
+                }
+                message.push_str("  ");
+                message.push_str(summary);
+                message.push(b'\n');
+            }
+        }
+        Ok(message)
+    }
+
+    /// Obtain the options for [`merge_message()`](Self::merge_message()) from the `merge.log` and `merge.suppressDest`
+    /// configuration.
+    pub fn merge_message_options(&self) -> Result<merge::message::Options, merge::message::Error> {
+        use crate::config::tree::{Merge, Section};
+
+        let mut filter = self.filter_config_section();
+        let config = &self.config.resolved;
+        let log = match config.string_filter(Merge.name(), None, Merge::LOG.name, &mut filter) {
+            None => None,
+            Some(value) => match gix_config::Integer::try_from(value.as_ref()) {
+                Ok(integer) => usize::try_from(integer.value).ok().filter(|limit| *limit > 0),
+                Err(_) => gix_config::Boolean::try_from(value.as_ref())
+                    .map_err(|_| merge::message::Error::InvalidLog {
+                        value: value.into_owned(),
+                    })?
+                    .0
+                    .then_some(merge::message::DEFAULT_LOG_LENGTH),
+            },
+        };
+        let suppress_destinations = config
+            .strings_filter(Merge.name(), None, Merge::SUPPRESS_DEST.name, &mut filter)
+            .map(|values| {
+                values.into_iter().fold(Vec::new(), |mut patterns, value| {
+                    if value.is_empty() {
+                        patterns.clear();
+                    } else {
+                        patterns.push(value.into_owned());
+                    }
+                    patterns
+                })
+            });
+        Ok(merge::message::Options {
+            log,
+            suppress_destinations,
+        })
+    }
+}
+
+#[cfg(feature = "worktree-mutation")]
+mod worktree {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        path::Path,
+    };
+
+    use gix_hash::ObjectId;
+    use gix_index::merge::two_way::Change as WorktreeChange;
+    use gix_ref::{
+        transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+        Target,
+    };
+
+    use crate::{
+        bstr::{BString, ByteSlice, ByteVec},
+        merge::worktree::{Error, Options, Outcome},
+        state,
+    };
+
+    /// The files that describe a merge in progress, relative to the `.git` directory.
+    const MERGE_STATE_FILES: [&str; 3] = ["MERGE_HEAD", "MERGE_MSG", "MERGE_MODE"];
+
+    /// Merging in the worktree
+    impl crate::Repository {
+        /// Merge the commits `theirs` into `HEAD` and update the index and the worktree accordingly, similar to `git merge`.
+        ///
+        /// If `HEAD` already contains all commits, nothing is done, and if [allowed](Options::allow_fast_forward),
+        /// `HEAD` is fast-forwarded to a single commit that contains it. Otherwise, the commits are [merged](Self::merge_commits())
+        /// and a merge commit with `message` is created if there are no conflicts.
+        /// Use [`merge_message()`](crate::Repository::merge_message()) to produce the message `git merge` would use.
+        ///
+        /// If there are conflicts, files with conflict markers are written to the worktree, the versions of all sides are written
+        /// to the index as stages `1`, `2` and `3`, and `MERGE_HEAD`, `MERGE_MSG` and `MERGE_MODE` are written just like `git`
+        /// does, so the merge can be concluded with [`merge_continue()`](Self::merge_continue()) or `git merge --continue`, or
+        /// aborted with [`merge_abort()`](Self::merge_abort()) or `git merge --abort`.
+        ///
+        /// The merge fails without changing anything if local changes of files would be overwritten. Unless `HEAD` is
+        /// fast-forwarded, the index must also match `HEAD` so that aborting the merge can't lose staged changes, just like
+        /// `git merge` requires it.
+        pub fn merge_into_worktree(
+            &self,
+            theirs: impl IntoIterator<Item = impl Into<ObjectId>>,
+            labels: gix_merge::blob::Labels<'_>,
+            message: &str,
+            options: Options,
+        ) -> Result<Outcome, Error> {
+            self.work_dir().ok_or(Error::BareRepository)?;
+            if self.state() == Some(state::InProgress::Merge) {
+                return Err(Error::MergeInProgress);
+            }
+            let head = self.head_commit()?;
+            let mut unmerged = Vec::new();
+            for id in theirs.into_iter().map(Into::into) {
+                if self.merge_bases(id, Some(head.id))? != [id] && !unmerged.contains(&id) {
+                    unmerged.push(id);
+                }
+            }
+            if unmerged.is_empty() {
+                return Ok(Outcome::UpToDate);
+            }
+            let head_tree = head.tree_id()?.detach();
+            let fast_forward = match (unmerged.as_slice(), options.allow_fast_forward) {
+                ([commit], true) if self.merge_bases(head.id, Some(*commit))? == [head.id] => Some(*commit),
+                _ => None,
+            };
+            if fast_forward.is_none() {
+                if let Some(path) = self.first_staged_change(head_tree)? {
+                    return Err(Error::LocalChanges { path });
+                }
+            }
+            self.edit_reference(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: Default::default(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(head.id),
+                },
+                name: "ORIG_HEAD".try_into().expect("valid"),
+                deref: false,
+            })?;
+
+            if let Some(commit) = fast_forward {
+                let tree = self.find_object(commit)?.try_into_commit()?.tree_id()?.detach();
+                self.checkout_merged_tree(head_tree, tree)?.write(Default::default())?;
+                self.edit_reference(RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            mode: RefLog::AndReference,
+                            force_create_reflog: false,
+                            message: format!("merge {commit}: Fast-forward").into(),
+                        },
+                        expected: PreviousValue::MustExistAndMatch(Target::Peeled(head.id)),
+                        new: Target::Peeled(commit),
+                    },
+                    name: "HEAD".try_into().expect("valid"),
+                    deref: true,
+                })?;
+                return Ok(Outcome::FastForward { commit });
+            }
+
+            let outcome = self.merge_commits(head.id, unmerged.iter().copied(), labels, options.merge)?;
+            let mut index = self.checkout_merged_tree(head_tree, outcome.tree)?;
+            type Side = Option<(ObjectId, gix_index::entry::Mode)>;
+            let mut conflicted_paths = BTreeMap::<BString, [Side; 3]>::new();
+            for (path, stage, entry) in outcome
+                .conflicts
+                .iter()
+                .flat_map(gix_merge::tree::Conflict::index_entries)
+            {
+                let mode = gix_index::entry::Mode::from_bits_truncate(u32::from(*entry.mode));
+                conflicted_paths.entry(path.to_owned()).or_default()[stage as usize - 1] = Some((entry.id, mode));
+            }
+            for (path, [ancestor, ours, theirs]) in &conflicted_paths {
+                index.set_conflict(path.as_ref(), *ancestor, *ours, *theirs);
+            }
+            index.write(Default::default())?;
+
+            if !outcome.has_conflicts() {
+                let commit = self.commit("HEAD", message, outcome.tree, Some(head.id).into_iter().chain(unmerged))?;
+                return Ok(Outcome::Merged {
+                    commit: commit.detach(),
+                });
+            }
+            let git_dir = self.git_dir();
+            let mut merge_head = String::new();
+            for id in &unmerged {
+                merge_head.push_str(&format!("{id}\n"));
+            }
+            std::fs::write(git_dir.join("MERGE_HEAD"), merge_head)?;
+            let mut merge_msg = BString::from(message.trim_end());
+            merge_msg.push_str("\n\n# Conflicts:\n");
+            for path in conflicted_paths.keys() {
+                merge_msg.push_str(b"#\t");
+                merge_msg.push_str(path);
+                merge_msg.push(b'\n');
+            }
+            std::fs::write(git_dir.join("MERGE_MSG"), merge_msg)?;
+            std::fs::write(
+                git_dir.join("MERGE_MODE"),
+                if options.allow_fast_forward { "" } else { "no-ff" },
+            )?;
+            Ok(Outcome::Conflicts {
+                conflicts: outcome.conflicts,
+            })
+        }
+
+        /// Conclude a merge that stopped due to conflicts by creating the merge commit from the index, similar to
+        /// `git merge --continue`, and return its id.
+        ///
+        /// The message is read from `MERGE_MSG` without comment lines, and the commits to merge are read from `MERGE_HEAD`,
+        /// so merges started by `git merge` can be concluded as well. All conflicts must have been resolved in the index.
+        pub fn merge_continue(&self) -> Result<crate::Id<'_>, Error> {
+            if self.state() != Some(state::InProgress::Merge) {
+                return Err(Error::NoMergeInProgress);
+            }
+            let index = self.open_index()?;
+            let unresolved: BTreeSet<_> = index
+                .entries()
+                .iter()
+                .filter(|entry| entry.stage() != 0)
+                .map(|entry| entry.path(&index).to_owned())
+                .collect();
+            if !unresolved.is_empty() {
+                return Err(Error::UnresolvedConflicts {
+                    paths: unresolved.into_iter().collect(),
+                });
+            }
+            let head = self.head_commit()?;
+            let tree = self.write_tree_from_index(head.tree_id()?.detach(), &index)?;
+
+            let git_dir = self.git_dir();
+            let mut parents = vec![head.id];
+            for line in std::fs::read(git_dir.join("MERGE_HEAD"))?.lines() {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                parents.push(ObjectId::from_hex(line).map_err(|_| Error::DecodeMergeHead {
+                    line: line.as_bstr().to_owned(),
+                })?);
+            }
+            let message = match std::fs::read(git_dir.join("MERGE_MSG")) {
+                Ok(message) => message,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => return Err(err.into()),
+            };
+            let mut message = message
+                .lines_with_terminator()
+                .filter(|line| !line.starts_with(b"#"))
+                .fold(BString::default(), |mut out, line| {
+                    out.push_str(line);
+                    out
+                });
+            let len = message.trim_end().len();
+            message.truncate(len);
+            message.push(b'\n');
+
+            let id = self.commit("HEAD", message.to_str_lossy(), tree, parents)?;
+            remove_merge_state(git_dir)?;
+            Ok(id)
+        }
+
+        /// Abort a merge that stopped due to conflicts and restore the index and all files that were changed by the merge
+        /// to the state of `HEAD`, similar to `git merge --abort`.
+        ///
+        /// Local changes to files that weren't changed by the merge are kept.
+        pub fn merge_abort(&self) -> Result<(), Error> {
+            let workdir = self.work_dir().ok_or(Error::BareRepository)?;
+            if self.state() != Some(state::InProgress::Merge) {
+                return Err(Error::NoMergeInProgress);
+            }
+            let current = self.open_index()?;
+            let mut index = self.index_from_tree(&self.head_commit()?.tree_id()?)?;
+            let mut changes = Vec::new();
+            let mut previous_path = None;
+            for entry in current.entries() {
+                let path = entry.path(&current);
+                if previous_path != Some(path) && index.entry_by_path(path).is_none() {
+                    changes.push(WorktreeChange::Remove { path: path.to_owned() });
+                }
+                previous_path = Some(path);
+            }
+            let mut unchanged = Vec::new();
+            for (idx, entry) in index.entries().iter().enumerate() {
+                let path = entry.path(&index);
+                match current.entry_by_path_and_stage(path, 0) {
+                    Some(current) if current.id == entry.id && current.mode == entry.mode => {
+                        unchanged.push((idx, current.stat));
+                    }
+                    _ => changes.push(WorktreeChange::Update { path: path.to_owned() }),
+                }
+            }
+            for (idx, stat) in unchanged {
+                index.entries_mut()[idx].stat = stat;
+            }
+            self.apply_changes_to_worktree(workdir, &changes, &mut index)?;
+            index.write(Default::default())?;
+            remove_merge_state(self.git_dir())?;
+            Ok(())
+        }
+
+        /// Return the path of the first entry in the index that differs from its entry in the tree `head_tree` of `HEAD`,
+        /// or that exists on only one side.
+        fn first_staged_change(&self, head_tree: ObjectId) -> Result<Option<BString>, Error> {
+            let index = if self.index_path().is_file() {
+                self.open_index()?.into()
+            } else {
+                gix_index::State::new(self.object_hash())
+            };
+            let head_index = self.index_from_tree(&head_tree)?;
+            for entry in index.entries() {
+                let path = entry.path(&index);
+                match head_index.entry_by_path_and_stage(path, 0) {
+                    Some(head) if entry.stage() == 0 && head.id == entry.id && head.mode == entry.mode => {}
+                    _ => return Ok(Some(path.to_owned())),
+                }
+            }
+            Ok(head_index
+                .entries()
+                .iter()
+                .map(|entry| entry.path(&head_index))
+                .find(|path| index.entry_by_path(path).is_none())
+                .map(ToOwned::to_owned))
+        }
+
+        /// Update the index and the worktree from the tree `current` of `HEAD` to the `merged` tree with the two-tree merge,
+        /// and return the index without writing it.
+        ///
+        /// This fails before touching the worktree if files with local changes or untracked files would be overwritten.
+        fn checkout_merged_tree(&self, current: ObjectId, merged: ObjectId) -> Result<gix_index::File, Error> {
+            let current = self.index_from_tree(&current)?;
+            let merged = self.index_from_tree(&merged)?;
+            self.checkout_tree_from(&current, &merged).map_err(|err| match err {
+                crate::worktree::switch::Error::Conflicts { mut conflicts } => Error::LocalChanges {
+                    path: conflicts.remove(0).path,
+                },
+                err => err.into(),
+            })
+        }
+
+        /// Write the trees for all entries in `index` by editing the tree `head_tree` of `HEAD`, and return the id of the
+        /// root tree.
+        fn write_tree_from_index(&self, head_tree: ObjectId, index: &gix_index::State) -> Result<ObjectId, Error> {
+            let head_index = self.index_from_tree(&head_tree)?;
+            let head_tree = self.find_object(head_tree)?.try_into_tree()?;
+            let mut editor =
+                gix_object::tree::Editor::new(head_tree.decode()?.into(), &self.objects, self.object_hash());
+            for entry in head_index.entries() {
+                let path = entry.path(&head_index);
+                if index.entry_by_path(path).is_none() {
+                    editor.remove(path)?;
+                }
+            }
+            for entry in index.entries() {
+                let Some(mode) = entry.mode.to_tree_entry_mode() else {
+                    continue;
+                };
+                let path = entry.path(index);
+                if head_index
+                    .entry_by_path(path)
+                    .map_or(true, |head| head.id != entry.id || head.mode != entry.mode)
+                {
+                    editor.upsert(path, mode.kind(), entry.id)?;
+                }
+            }
+            Ok(editor.write(|tree| self.write_object(tree).map(crate::Id::detach))?)
+        }
+    }
+
+    fn remove_merge_state(git_dir: &Path) -> std::io::Result<()> {
+        for name in MERGE_STATE_FILES {
+            match std::fs::remove_file(git_dir.join(name)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -42,10 +42,13 @@ mod config;
 ///
 #[cfg(feature = "blob-diff")]
 pub mod diff;
+#[cfg(all(feature = "status", feature = "attributes"))]
+mod dirty;
 ///
 #[cfg(feature = "attributes")]
 pub mod filter;
 mod graph;
+mod history;
 pub(crate) mod identity;
 mod impls;
 #[cfg(feature = "index")]
@@ -55,9 +58,14 @@ mod kind;
 mod location;
 #[cfg(feature = "mailmap")]
 mod mailmap;
+#[cfg(feature = "maintenance")]
+mod maintenance;
+#[cfg(feature = "merge")]
+mod merge;
 mod object;
 #[cfg(feature = "attributes")]
 mod pathspec;
+mod promisor;
 mod reference;
 mod remote;
 #[cfg(feature = "revision")]
@@ -66,6 +74,8 @@ mod shallow;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
+#[cfg(feature = "worktree-mutation")]
+mod switch;
 mod thread_safe;
 mod worktree;
 
@@ -94,6 +104,19 @@ pub mod pathspec_defaults_ignore_case {
     }
 }
 
+///
+pub mod branch_remote_tracking_ref_name {
+    /// The error returned by [Repository::branch_remote_tracking_ref_name()](crate::Repository::branch_remote_tracking_ref_name()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The configured upstream or its local tracking name is not a valid reference name")]
+        ValidateName(#[from] gix_validate::reference::name::Error),
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+    }
+}
+
 ///
 #[cfg(feature = "index")]
 pub mod index_or_load_from_head {
@@ -112,6 +135,36 @@ pub mod index_or_load_from_head {
     }
 }
 
+///
+#[cfg(all(feature = "status", feature = "attributes"))]
+pub mod is_dirty {
+    /// The error returned by [`Repository::is_dirty()`][crate::Repository::is_dirty()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        Pathspec(#[from] crate::pathspec::init::Error),
+        #[error(transparent)]
+        AttributeStack(#[from] crate::config::attribute_stack::Error),
+        #[error(transparent)]
+        ExcludeStack(#[from] crate::config::exclude_stack::Error),
+        #[error(transparent)]
+        FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+        #[error(transparent)]
+        FilesystemOptions(#[from] crate::config::boolean::Error),
+        #[error(transparent)]
+        StatOptions(#[from] crate::config::stat_options::Error),
+        #[error("Could not compare the index with the worktree")]
+        IndexAsWorktree(#[from] gix_status::index_as_worktree::Error),
+        #[error("Could not find untracked files")]
+        Dirwalk(#[from] gix_worktree::dirwalk::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
 ///
 #[cfg(feature = "worktree-stream")]
 pub mod worktree_stream {
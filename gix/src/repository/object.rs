@@ -139,7 +139,11 @@ impl crate::Repository {
         self.write_object_inner(&buf, object.kind())
     }
 
-    fn write_object_inner(&self, buf: &[u8], kind: gix_object::Kind) -> Result<Id<'_>, object::write::Error> {
+    pub(crate) fn write_object_inner(
+        &self,
+        buf: &[u8],
+        kind: gix_object::Kind,
+    ) -> Result<Id<'_>, object::write::Error> {
         let oid = gix_object::compute_hash(self.object_hash(), kind, buf);
         if self.objects.exists(&oid) {
             return Ok(oid.attach(self));
@@ -352,3 +356,54 @@ impl crate::Repository {
         }
     }
 }
+
+/// Keeping objects in memory
+impl crate::Repository {
+    /// Keep all objects written from now on in memory instead of writing them to the object database, while making them
+    /// available to all operations on this instance as if they were written.
+    ///
+    /// This allows to simulate operations like merges without changing the object database, particularly when combined
+    /// with in-memory configuration changes through [`config_snapshot_mut()`](Self::config_snapshot_mut()).
+    /// Only objects are kept in memory though, and references and the index are still written to disk. Thus, operations that
+    /// write them, like [`commit()`](Self::commit()), should be avoided as what they write would point to objects that don't
+    /// exist once this instance is dropped.
+    /// Note that objects in memory are lost when converting this instance into a [`ThreadSafeRepository`](crate::ThreadSafeRepository),
+    /// but are retained when cloning it.
+    pub fn with_object_memory(mut self) -> Self {
+        self.objects.enable_memory();
+        self
+    }
+
+    /// Stop keeping objects in memory and return all objects that were written since, or `None` if objects weren't kept in memory.
+    ///
+    /// Objects written from now on will be written to the object database.
+    pub fn take_object_memory(&mut self) -> Option<gix_odb::memory::Storage> {
+        self.objects.take_memory()
+    }
+}
+
+/// Shortening object ids
+impl crate::Repository {
+    /// Return the shortest unambiguous prefix of each of `ids` in order, which is at least as long as configured
+    /// by `core.abbrev` or derived from the amount of packed objects if unset.
+    ///
+    /// This is like calling [`Id::shorten()`] for each id, but faster as loose object directories are read only once.
+    pub fn shorten_ids(
+        &self,
+        ids: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<gix_hash::Prefix>, crate::id::shorten::Error> {
+        let hex_len = match self.config.hex_len {
+            Some(hex_len) => hex_len,
+            None => crate::id::calculate_auto_hex_len(self.objects.packed_object_count()?),
+        };
+        let ids: Vec<ObjectId> = ids.into_iter().map(Into::into).collect();
+        let prefixes = self.objects.disambiguate_prefixes(ids.iter().map(|id| {
+            gix_odb::store::prefix::disambiguate::Candidate::new(*id, hex_len)
+                .expect("BUG: internal hex-len must always be valid")
+        }))?;
+        ids.into_iter()
+            .zip(prefixes)
+            .map(|(oid, prefix)| prefix.ok_or(crate::id::shorten::Error::NotFound { oid }))
+            .collect()
+    }
+}
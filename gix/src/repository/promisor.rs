@@ -0,0 +1,167 @@
+use crate::{bstr::BString, config::cache::util::ApplyLeniencyDefault};
+
+/// Partial clones
+impl crate::Repository {
+    /// Return the names of all promisor remotes in the order in which they are asked for objects that are missing
+    /// in this partial clone, or an empty list if this isn't a partial clone.
+    ///
+    /// Like in `git`, all remotes with `remote.<name>.promisor` enabled or with a `remote.<name>.partialCloneFilter`
+    /// are promisor remotes, in the order in which they are configured. The remote named by `extensions.partialClone`,
+    /// typically the one the repository was cloned from, is always asked last.
+    ///
+    /// Note that only remotes we deem [trustworthy](crate::open::Options::filter_config_section()) are considered.
+    pub fn promisor_remote_names(&self) -> Result<Vec<BString>, crate::config::boolean::Error> {
+        use crate::config::tree::{Extensions, Key, Remote};
+        let mut filter = self.filter_config_section();
+        let mut remote_names = Vec::<BString>::new();
+        for section in self
+            .config
+            .resolved
+            .sections_by_name_and_filter("remote", &mut filter)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(name) = section.header().subsection_name() {
+                if !remote_names.iter().any(|existing| existing == name) {
+                    remote_names.push(name.to_owned());
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        for name in remote_names {
+            let is_promisor = self
+                .config
+                .resolved
+                .boolean_filter("remote", Some(name.as_ref()), Remote::PROMISOR.name(), &mut filter)
+                .map(|res| Remote::PROMISOR.enrich_error(res))
+                .transpose()
+                .with_lenient_default(self.config.lenient_config)?
+                .unwrap_or_default();
+            let has_filter = self
+                .config
+                .resolved
+                .string_filter(
+                    "remote",
+                    Some(name.as_ref()),
+                    Remote::PARTIAL_CLONE_FILTER.name(),
+                    &mut filter,
+                )
+                .is_some();
+            if is_promisor || has_filter {
+                names.push(name);
+            }
+        }
+        if let Some(name) =
+            self.config
+                .resolved
+                .string_filter("extensions", None, Extensions::PARTIAL_CLONE.name(), &mut filter)
+        {
+            names.retain(|existing| existing != name.as_ref());
+            names.push(name.into_owned());
+        }
+        Ok(names)
+    }
+}
+
+#[cfg(feature = "blocking-network-client")]
+mod fetch_missing {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_features::progress::{DynNestedProgress, NestedProgress};
+
+    use crate::{
+        bstr::{BStr, BString},
+        config::cache::util::ApplyLeniencyDefault,
+        promisor::fetch_missing::{Error, Outcome},
+        remote,
+    };
+
+    /// Partial clones
+    impl crate::Repository {
+        /// Obtain all objects with the given `ids` which aren't present in this partial clone from its
+        /// [promisor remotes](Self::promisor_remote_names()), asking one after another until no object is missing anymore.
+        ///
+        /// Just like `git`, only the objects in question are requested, without any blobs they may refer to. Remotes that
+        /// fail to provide objects are skipped, and an error is only returned if objects remain missing in the end.
+        /// If `promisor.quiet` is enabled, no `progress` is reported for the fetch operations.
+        pub fn fetch_missing_objects<P>(
+            &self,
+            ids: impl IntoIterator<Item = gix_hash::ObjectId>,
+            mut progress: P,
+            should_interrupt: &AtomicBool,
+        ) -> Result<Outcome, Error>
+        where
+            P: NestedProgress,
+            P::SubProgress: 'static,
+        {
+            self.fetch_missing_objects_inner(ids.into_iter().collect(), &mut progress, should_interrupt)
+        }
+
+        fn fetch_missing_objects_inner(
+            &self,
+            mut missing: Vec<gix_hash::ObjectId>,
+            progress: &mut dyn DynNestedProgress,
+            should_interrupt: &AtomicBool,
+        ) -> Result<Outcome, Error> {
+            let _span = gix_trace::coarse!("gix::Repository::fetch_missing_objects()");
+            missing.retain(|id| !self.has_object(id));
+            missing.sort();
+            missing.dedup();
+            let mut out = Outcome::default();
+            if missing.is_empty() {
+                return Ok(out);
+            }
+
+            let quiet = self
+                .config
+                .resolved
+                .boolean_by_key("promisor.quiet")
+                .map(|res| crate::config::tree::Promisor::QUIET.enrich_error(res))
+                .transpose()
+                .with_lenient_default(self.config.lenient_config)?
+                .unwrap_or_default();
+            let mut remote_errors = Vec::new();
+            for name in self.promisor_remote_names()? {
+                if missing.is_empty() {
+                    break;
+                }
+                let mut discard = gix_features::progress::Discard;
+                let progress: &mut dyn DynNestedProgress = if quiet { &mut discard } else { &mut *progress };
+                match self.fetch_objects_from(name.as_ref(), &missing, progress, should_interrupt) {
+                    Ok(()) => {
+                        let num_missing = missing.len();
+                        missing.retain(|id| !self.has_object(id));
+                        out.remotes.push((name, num_missing - missing.len()));
+                    }
+                    Err(err) => remote_errors.push((name, err)),
+                }
+            }
+            if missing.is_empty() {
+                Ok(out)
+            } else {
+                Err(Error::Incomplete { missing, remote_errors })
+            }
+        }
+
+        fn fetch_objects_from(
+            &self,
+            name: &BStr,
+            ids: &[gix_hash::ObjectId],
+            progress: &mut dyn DynNestedProgress,
+            should_interrupt: &AtomicBool,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            let mut remote = self.find_remote(name)?.with_fetch_tags(remote::fetch::Tags::None);
+            remote.replace_refspecs(
+                ids.iter().map(|id| BString::from(id.to_string())),
+                remote::Direction::Fetch,
+            )?;
+            remote
+                .connect(remote::Direction::Fetch)?
+                .prepare_fetch(&mut *progress, Default::default())?
+                .with_optional_filter("blob:none")
+                .receive_inner(progress, should_interrupt)?;
+            Ok(())
+        }
+    }
+}
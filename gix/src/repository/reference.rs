@@ -7,7 +7,11 @@ use gix_ref::{
     FullName, PartialNameRef, Target,
 };
 
-use crate::{bstr::BString, ext::ReferenceExt, reference, Reference};
+use crate::{
+    bstr::{BStr, BString},
+    ext::ReferenceExt,
+    reference, Reference,
+};
 
 /// Obtain and alter references comfortably
 impl crate::Repository {
@@ -194,6 +198,282 @@ impl crate::Repository {
         Ok(self.head()?.referent_name().map(std::borrow::ToOwned::to_owned))
     }
 
+    /// Let the unborn `HEAD` point to the branch `name`, like `main` or `refs/heads/main`, and return the full name of the branch.
+    ///
+    /// This is useful to change the name of the initial branch of freshly initialized repositories, similar to
+    /// `git symbolic-ref HEAD refs/heads/<name>`. It fails if `HEAD` is detached or if its branch already exists.
+    pub fn rename_unborn_head(&self, name: &BStr) -> Result<FullName, reference::rename_unborn_head::Error> {
+        use reference::rename_unborn_head::Error;
+        let current = match self.head()?.kind {
+            crate::head::Kind::Unborn(current) => current,
+            crate::head::Kind::Symbolic(_) | crate::head::Kind::Detached { .. } => return Err(Error::NotUnborn),
+        };
+        let new: FullName = if name.starts_with(b"refs/heads/") {
+            name.to_owned()
+        } else {
+            format!("refs/heads/{name}").into()
+        }
+        .try_into()
+        .map_err(|err| Error::InvalidBranchName {
+            name: name.to_owned(),
+            source: err,
+        })?;
+        if new == current {
+            return Ok(new);
+        }
+
+        // There is nothing to log as there is no commit yet.
+        let mut refs = self.refs.clone();
+        refs.write_reflog = gix_ref::store::WriteReflog::Disable;
+        let (file_lock_fail, packed_refs_lock_fail) =
+            self.config.lock_timeout().map_err(reference::edit::Error::from)?;
+        refs.transaction()
+            .prepare(
+                Some(RefEdit {
+                    change: Change::Update {
+                        log: Default::default(),
+                        expected: PreviousValue::MustExistAndMatch(Target::Symbolic(current)),
+                        new: Target::Symbolic(new.clone()),
+                    },
+                    name: "HEAD".try_into().expect("valid"),
+                    deref: false,
+                }),
+                file_lock_fail,
+                packed_refs_lock_fail,
+            )
+            .map_err(reference::edit::Error::from)?
+            .commit(None)
+            .map_err(reference::edit::Error::from)?;
+        Ok(new)
+    }
+
+    /// Delete the local branch `name`, like `main` or `refs/heads/main`, along with its `branch.<name>` sections in the
+    /// local configuration file, and return the id it pointed to, similar to `git branch --delete`.
+    ///
+    /// Branches that are checked out, rebased or bisected in [any worktree](Self::checked_out_branches()) are never deleted.
+    /// Unless `force` is set, the branch must also be merged into its upstream, or into `HEAD` if it has no upstream,
+    /// or if the upstream doesn't exist locally.
+    ///
+    /// Note that the configuration of this instance isn't updated, so it will still see the removed `branch.<name>` sections.
+    #[cfg(feature = "revision")]
+    pub fn delete_branch(&self, name: &BStr, force: bool) -> Result<ObjectId, reference::delete_branch::Error> {
+        use reference::delete_branch::Error;
+        let full_name = branch_name(name).map_err(|err| Error::InvalidBranchName {
+            name: name.to_owned(),
+            source: err,
+        })?;
+        let mut branch = self
+            .try_find_reference(full_name.as_ref())?
+            .ok_or_else(|| Error::NotFound {
+                name: full_name.clone(),
+            })?;
+        if let Some(in_use) = self
+            .checked_out_branches()?
+            .into_iter()
+            .find(|branch| branch.name == full_name)
+        {
+            return Err(Error::InUse {
+                name: full_name,
+                head: in_use.head,
+            });
+        }
+        let id = branch.peel_to_id_in_place()?.detach();
+
+        if !force {
+            let upstream = match self.branch_remote_tracking_ref_name(full_name.shorten()).transpose()? {
+                Some(upstream_name) => match self.try_find_reference(upstream_name.as_ref())? {
+                    Some(mut upstream) => Some((upstream.peel_to_id_in_place()?.detach(), upstream_name)),
+                    None => None,
+                },
+                None => None,
+            };
+            let (target, target_name) = match upstream {
+                Some((id, name)) => (Some(id), name),
+                None => (
+                    self.head()?.try_into_peeled_id()?.map(crate::Id::detach),
+                    "HEAD".try_into().expect("valid"),
+                ),
+            };
+            let is_merged = match target {
+                Some(target) => self.is_ancestor(id, target)?,
+                None => false,
+            };
+            if !is_merged {
+                return Err(Error::NotMerged {
+                    name: full_name,
+                    target: target_name,
+                });
+            }
+        }
+
+        branch.delete()?;
+        self.remove_branch_config(full_name.shorten())?;
+        Ok(id)
+    }
+
+    /// Rename the local branch `old` to `new`, both like `main` or `refs/heads/main`, along with its reflog and its
+    /// `branch.<name>` sections in the local configuration file, similar to `git branch --move`.
+    ///
+    /// All worktrees that have `old` checked out are changed to have `new` checked out instead, in the same transaction that
+    /// renames the branch. Unless `force` is set, `new` must not exist yet, and it must never be checked out in
+    /// [any worktree](Self::checked_out_branches()) if it is overwritten. `old` can't be renamed while it's rebased or bisected
+    /// in any worktree.
+    ///
+    /// Note that the configuration of this instance isn't updated, so it will still see the old `branch.<name>` sections.
+    pub fn rename_branch(&self, old: &BStr, new: &BStr, force: bool) -> Result<(), reference::rename_branch::Error> {
+        use reference::rename_branch::Error;
+        let [old, new] = [old, new].map(|name| {
+            branch_name(name).map_err(|err| Error::InvalidBranchName {
+                name: name.to_owned(),
+                source: err,
+            })
+        });
+        let (old, new) = (old?, new?);
+        let mut branch = self
+            .try_find_reference(old.as_ref())?
+            .ok_or_else(|| Error::NotFound { name: old.clone() })?;
+        let id = branch.peel_to_id_in_place()?.detach();
+        if old == new {
+            return Ok(());
+        }
+        let in_use = self.checked_out_branches()?;
+        // Like `git`, branches that are rebased or bisected can't be renamed as the operation would recreate the old name.
+        if let Some(branch) = in_use
+            .iter()
+            .find(|branch| branch.name == old && branch.state != crate::worktree::checked_out::State::Head)
+        {
+            return Err(Error::InUse {
+                name: old,
+                head: branch.head.clone(),
+            });
+        }
+        if self.try_find_reference(new.as_ref())?.is_some() {
+            if !force {
+                return Err(Error::AlreadyExists { name: new });
+            }
+            if let Some(branch) = in_use.iter().find(|branch| branch.name == new) {
+                return Err(Error::InUse {
+                    name: new,
+                    head: branch.head.clone(),
+                });
+            }
+        }
+
+        let mut buf = Vec::new();
+        let reflog = match self.refs.reflog_iter(old.as_ref(), &mut buf)? {
+            Some(lines) => lines
+                .map(|line| line.map(|line| line.to_owned()))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+        let message: BString = format!("Branch: renamed {} to {}", old.as_bstr(), new.as_bstr()).into();
+        let log = LogChange {
+            mode: RefLog::AndReference,
+            force_create_reflog: false,
+            message,
+        };
+        let mut edits = vec![
+            RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                    log: RefLog::AndReference,
+                },
+                name: old.clone(),
+                deref: false,
+            },
+            RefEdit {
+                change: Change::Update {
+                    log: log.clone(),
+                    expected: if force {
+                        PreviousValue::Any
+                    } else {
+                        PreviousValue::MustNotExist
+                    },
+                    new: Target::Peeled(id),
+                },
+                name: new.clone(),
+                deref: false,
+            },
+        ];
+        edits.extend(
+            in_use
+                .into_iter()
+                .filter(|branch| branch.name == old && branch.state == crate::worktree::checked_out::State::Head)
+                .map(|branch| RefEdit {
+                    change: Change::Update {
+                        log: log.clone(),
+                        expected: PreviousValue::MustExistAndMatch(Target::Symbolic(old.clone())),
+                        new: Target::Symbolic(new.clone()),
+                    },
+                    name: branch.head,
+                    deref: false,
+                }),
+        );
+        self.edit_references(edits)?;
+        let (file_lock_fail, _) = self.config.lock_timeout()?;
+        self.refs.reflog_prepend(new.as_ref(), &reflog, file_lock_fail)?;
+
+        self.edit_local_config(|config| {
+            let mut renamed = false;
+            while config.section("branch", Some(old.shorten())).is_ok() {
+                if config
+                    .rename_section(
+                        "branch",
+                        Some(old.shorten()),
+                        "branch",
+                        Some(new.shorten().to_owned().into()),
+                    )
+                    .is_err()
+                {
+                    break;
+                }
+                renamed = true;
+            }
+            Ok(renamed)
+        })
+    }
+
+    /// Lock the local configuration file, read it and let `edit` change it, writing it back only if `edit` returns `true`.
+    /// The file isn't touched at all if it doesn't exist.
+    pub(crate) fn edit_local_config<E>(
+        &self,
+        edit: impl FnOnce(&mut gix_config::File<'static>) -> Result<bool, E>,
+    ) -> Result<(), E>
+    where
+        E: From<crate::config::lock_timeout::Error>
+            + From<gix_config::file::init::from_paths::Error>
+            + From<gix_lock::acquire::Error>
+            + From<std::io::Error>
+            + From<gix_lock::commit::Error<gix_lock::File>>,
+    {
+        let path = self.common_dir().join("config");
+        if !path.is_file() {
+            return Ok(());
+        }
+        // Lock first so changes made by others while we read the file can't be lost.
+        let (file_lock_fail, _) = self.config.lock_timeout()?;
+        let mut file = gix_lock::File::acquire_to_update_resource(&path, file_lock_fail, None)?;
+        let mut config = gix_config::File::from_path_no_includes(path, gix_config::Source::Local)?;
+        if !edit(&mut config)? {
+            return Ok(());
+        }
+        config.write_to(&mut file)?;
+        file.commit()?;
+        Ok(())
+    }
+
+    /// Remove all `branch.<short_name>` sections from the local configuration file, without touching the file if there are none.
+    #[cfg(feature = "revision")]
+    fn remove_branch_config(&self, short_name: &BStr) -> Result<(), reference::delete_branch::Error> {
+        self.edit_local_config(|config| {
+            let mut removed = false;
+            while config.remove_section("branch", Some(short_name)).is_some() {
+                removed = true;
+            }
+            Ok(removed)
+        })
+    }
+
     /// Return the reference that `HEAD` points to, or `None` if the head is detached or unborn.
     pub fn head_ref(&self) -> Result<Option<Reference<'_>>, reference::find::existing::Error> {
         Ok(self.head()?.try_into_referent())
@@ -244,6 +524,139 @@ impl crate::Repository {
         })
     }
 
+    /// Return the references that are private to each worktree of the repository, like `HEAD` or the ones in `refs/bisect/`,
+    /// for the main worktree and all linked worktrees.
+    ///
+    /// Names are qualified like `main-worktree/HEAD` or `worktrees/<id>/refs/bisect/bad` so they can be looked up from any worktree,
+    /// and the ones of the main worktree come first. These are not returned when [iterating](Self::references()) references, which
+    /// only shows the private references of the current worktree, unqualified.
+    /// Use [`checked_out_branches()`](Self::checked_out_branches()) to learn which branches are in use by any worktree.
+    pub fn worktree_references(&self) -> Result<Vec<Reference<'_>>, reference::iter::worktrees::Error> {
+        Ok(self
+            .refs
+            .worktree_refs()?
+            .into_iter()
+            .map(|r| Reference::from_ref(r, self))
+            .collect())
+    }
+
+    /// Build a map of all objects pointed to by references to the references pointing at them, including `HEAD`,
+    /// for decorating commits similar to `git log --decorate`.
+    ///
+    /// Annotated tags decorate both the tag object and the object they point to, and symbolic references like `origin/HEAD`
+    /// decorate the object they ultimately point to. References that can't be read or peeled are ignored.
+    pub fn decorations(&self) -> Result<reference::Decorations, reference::decorations::Error> {
+        use reference::decorations::{kind_of, Decoration};
+        let mut out = reference::Decorations::default();
+        let mut add = |id: ObjectId, name: FullName| {
+            let kind = kind_of(name.as_ref());
+            out.by_id.entry(id).or_default().push(Decoration { name, kind });
+        };
+
+        for mut reference in self.references()?.all()?.filter_map(Result::ok) {
+            let name = reference.name().to_owned();
+            let target_id = reference.try_id().map(crate::Id::detach);
+            let Ok(peeled_id) = reference.peel_to_id_in_place().map(crate::Id::detach) else {
+                continue;
+            };
+            if let Some(target_id) = target_id.filter(|id| *id != peeled_id) {
+                add(target_id, name.clone());
+            }
+            add(peeled_id, name);
+        }
+
+        let head = self.head()?;
+        out.head_branch = head.referent_name().map(ToOwned::to_owned);
+        if let Some(head_id) = head.id() {
+            add(
+                head_id.detach(),
+                "HEAD".try_into().expect("HEAD is a valid reference name"),
+            );
+        }
+        for decorations in out.by_id.values_mut() {
+            decorations.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name)));
+        }
+        Ok(out)
+    }
+
+    /// List all references matching `options`, sorted by its sort keys, similar to `git for-each-ref`.
+    ///
+    /// Symbolic references are listed with the object they ultimately point to, and references that can't be read or followed are skipped.
+    /// Use [`Entry::format()`](reference::list::Entry::format()) to print each entry with a [`Format`](reference::list::Format).
+    #[cfg(feature = "revision")]
+    pub fn list_references(
+        &self,
+        options: &reference::list::Options,
+    ) -> Result<Vec<reference::list::Entry>, reference::list::Error> {
+        use reference::list::{matches, Entry, Field};
+
+        let head_name = self.head_name()?;
+        let mut entries = Vec::new();
+        for reference in self.references()?.all()?.filter_map(Result::ok) {
+            if !options.patterns.is_empty()
+                && !options
+                    .patterns
+                    .iter()
+                    .any(|pattern| matches(reference.name().as_bstr(), pattern.as_ref()))
+            {
+                continue;
+            }
+            let Some(entry) = Entry::from_reference(self, reference, head_name.as_ref())? else {
+                continue;
+            };
+            if options
+                .points_at
+                .map_or(false, |id| entry.id != id && entry.peeled_id != id)
+            {
+                continue;
+            }
+            entries.push(entry);
+        }
+
+        if let Some(merged) = options.merged {
+            entries.retain(|e| e.peeled_kind == gix_object::Kind::Commit);
+            let tips: Vec<_> = entries.iter().map(|e| e.peeled_id).collect();
+            let reachable = gix_revision::reachable_from(Some(merged), &tips, &mut self.revision_graph())?;
+            let mut reachable = reachable.into_iter();
+            entries.retain(|_| reachable.next().expect("one result per entry"));
+        }
+        if options.upstream_tracking
+            || options
+                .sort
+                .iter()
+                .any(|key| matches!(key.field, Field::UpstreamTrack { .. }))
+        {
+            let with_upstream: Vec<_> = entries
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, e)| e.upstream_id.map(|upstream_id| (idx, e.peeled_id, upstream_id)))
+                .collect();
+            let counts =
+                self.ahead_behind_many(with_upstream.iter().map(|(_, local, upstream)| (*local, *upstream)))?;
+            for ((idx, _, _), counts) in with_upstream.into_iter().zip(counts) {
+                entries[idx].ahead_behind = Some(counts);
+            }
+        }
+
+        reference::list::sort(&mut entries, &options.sort, &self.version_sort());
+        Ok(entries)
+    }
+
+    /// Return a comparator to sort reference names by the versions they contain, configured with the pre-release suffixes
+    /// in `versionsort.suffix`, or the deprecated `versionsort.prereleaseSuffix` if the former isn't set.
+    ///
+    /// Use it with [`Iter::sorted_by_version()`][crate::reference::iter::Iter::sorted_by_version()] to obtain sorted references.
+    pub fn version_sort(&self) -> reference::VersionSort {
+        use crate::config::tree::VersionSort;
+        let mut filter = self.filter_config_section();
+        let config = &self.config.resolved;
+        let suffixes = config
+            .strings_filter("versionsort", None, VersionSort::SUFFIX.name, &mut filter)
+            .or_else(|| config.strings_filter("versionsort", None, VersionSort::PRERELEASE_SUFFIX.name, &mut filter))
+            .unwrap_or_default();
+        reference::VersionSort::new(suffixes.into_iter().map(std::borrow::Cow::into_owned))
+    }
+
     /// Try to find the reference named `name`, like `main`, `heads/branch`, `HEAD` or `origin/other`, and return it.
     ///
     /// Otherwise return `None` if the reference wasn't found.
@@ -263,3 +676,13 @@ impl crate::Repository {
         }
     }
 }
+
+/// Turn a branch `name` like `main` or `refs/heads/main` into its full name.
+fn branch_name(name: &BStr) -> Result<FullName, gix_validate::reference::name::Error> {
+    if name.starts_with(b"refs/heads/") {
+        name.to_owned()
+    } else {
+        format!("refs/heads/{name}").into()
+    }
+    .try_into()
+}
@@ -1,9 +1,22 @@
 #![allow(clippy::result_large_err)]
 use std::convert::TryInto;
 
-use crate::{bstr::BStr, config, remote, remote::find, Remote};
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    config, remote,
+    remote::find,
+    Remote,
+};
 
 impl crate::Repository {
+    /// Return the cache which allows to keep connections to remotes alive for reuse by successive operations.
+    ///
+    /// It's disabled by default, and can be enabled with [`set_enabled(true)`](remote::ConnectionCache::set_enabled()).
+    #[cfg(feature = "blocking-network-client")]
+    pub fn connection_cache(&self) -> &remote::ConnectionCache {
+        &self.connection_cache
+    }
+
     /// Create a new remote available at the given `url`.
     ///
     /// It's configured to fetch included tags by default, similar to git.
@@ -52,6 +65,147 @@ impl crate::Repository {
             .map(|name| self.find_remote(name.as_ref()))
     }
 
+    /// Remove the remote `name` from the local configuration file along with all of its remote tracking references,
+    /// and unset the upstream of all branches tracking it, similar to `git remote remove`.
+    ///
+    /// Note that the configuration of this instance isn't updated, so it will still see the removed remote.
+    pub fn remove_remote(&self, name: &str) -> Result<(), remote::remove::Error> {
+        use gix_ref::transaction::{Change, PreviousValue, RefEdit, RefLog};
+        use remote::remove::Error;
+        if !self.remote_names().contains(name) {
+            return Err(Error::NotFound { name: name.into() });
+        }
+        self.edit_local_config(|config| {
+            let mut changed = false;
+            while config.remove_section("remote", Some(name.into())).is_some() {
+                changed = true;
+            }
+            for id in branch_sections_with_remote(config, name) {
+                let mut section = config.section_mut_by_id(id).expect("id was just obtained");
+                while section.remove("remote").is_some() {}
+                while section.remove("merge").is_some() {}
+                changed = true;
+            }
+            Ok::<_, Error>(changed)
+        })?;
+
+        let mut edits = Vec::new();
+        for reference in self.references()?.prefixed(format!("refs/remotes/{name}/"))? {
+            let reference = reference.map_err(Error::Iterate)?;
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(reference.target().into_owned()),
+                    log: RefLog::AndReference,
+                },
+                name: reference.name().to_owned(),
+                deref: false,
+            });
+        }
+        self.edit_references(edits)?;
+        Ok(())
+    }
+
+    /// Rename the remote `old` to `new` in the local configuration file, similar to `git remote rename`.
+    ///
+    /// Fetch ref-specs writing into `refs/remotes/<old>/` are changed to write into `refs/remotes/<new>/`, and branches
+    /// tracking `old` are changed to track `new`. All remote tracking references of `old` are moved to `new` in a single
+    /// transaction, and symbolic references among them, like `refs/remotes/<old>/HEAD`, are changed to point to the
+    /// moved references.
+    ///
+    /// Note that the configuration of this instance isn't updated, so it will still see the remote under its old name.
+    pub fn rename_remote(&self, old: &str, new: &str) -> Result<(), remote::rename::Error> {
+        use gix_ref::{
+            transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+            FullName, Target,
+        };
+        use remote::rename::Error;
+
+        let remote_names = self.remote_names();
+        if !remote_names.contains(old) {
+            return Err(Error::NotFound { name: old.into() });
+        }
+        remote::name::validated(new)?;
+        if remote_names.contains(new) {
+            return Err(Error::AlreadyExists { name: new.into() });
+        }
+
+        let (old_prefix, new_prefix) = (format!("refs/remotes/{old}/"), format!("refs/remotes/{new}/"));
+        self.edit_local_config(|config| {
+            let mut changed = false;
+            while config.section("remote", Some(old.into())).is_ok() {
+                config.rename_section("remote", Some(old.into()), "remote", Some(BString::from(new).into()))?;
+                changed = true;
+            }
+            let remote_ids: Vec<_> = config
+                .sections_and_ids_by_name("remote")
+                .into_iter()
+                .flatten()
+                .filter(|(section, _)| section.header().subsection_name() == Some(new.into()))
+                .map(|(_, id)| id)
+                .collect();
+            for id in remote_ids {
+                let mut section = config.section_mut_by_id(id).expect("id was just obtained");
+                let specs: Vec<_> = section
+                    .values("fetch")
+                    .into_iter()
+                    .map(|spec| spec.replace(format!(":{old_prefix}"), format!(":{new_prefix}")))
+                    .collect();
+                if specs.is_empty() {
+                    continue;
+                }
+                while section.remove("fetch").is_some() {}
+                for spec in specs {
+                    section.push("fetch".try_into().expect("valid"), Some(spec.as_bstr()));
+                }
+            }
+            for id in branch_sections_with_remote(config, old) {
+                let mut section = config.section_mut_by_id(id).expect("id was just obtained");
+                section.set("remote".try_into().expect("valid"), new.into());
+                changed = true;
+            }
+            Ok::<_, Error>(changed)
+        })?;
+
+        let rename = |name: &BStr| -> Result<FullName, Error> {
+            Ok(format!("{new_prefix}{}", &name[old_prefix.len()..]).try_into()?)
+        };
+        let log = LogChange {
+            mode: RefLog::AndReference,
+            force_create_reflog: false,
+            message: format!("remote: renamed {old_prefix} to {new_prefix}").into(),
+        };
+        let mut edits = Vec::new();
+        for reference in self.references()?.prefixed(old_prefix.as_str())? {
+            let reference = reference.map_err(Error::Iterate)?;
+            let target = reference.target().into_owned();
+            let new_target = match &target {
+                Target::Symbolic(target) if target.as_bstr().starts_with(old_prefix.as_bytes()) => {
+                    Target::Symbolic(rename(target.as_bstr())?)
+                }
+                target => target.clone(),
+            };
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: log.clone(),
+                    expected: PreviousValue::MustNotExist,
+                    new: new_target,
+                },
+                name: rename(reference.name().as_bstr())?,
+                deref: false,
+            });
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(target),
+                    log: RefLog::AndReference,
+                },
+                name: reference.name().to_owned(),
+                deref: false,
+            });
+        }
+        self.edit_references(edits)?;
+        Ok(())
+    }
+
     /// Find the configured remote with the given `name_or_url` or return `None` if it doesn't exist,
     /// for the purpose of fetching or pushing data.
     ///
@@ -227,3 +381,14 @@ impl crate::Repository {
         }
     }
 }
+
+/// Return the ids of all `branch` sections whose `remote` is `name`.
+fn branch_sections_with_remote(config: &gix_config::File<'static>, name: &str) -> Vec<gix_config::file::SectionId> {
+    config
+        .sections_and_ids_by_name("branch")
+        .into_iter()
+        .flatten()
+        .filter(|(section, _)| section.value("remote").map_or(false, |remote| remote.as_ref() == name))
+        .map(|(_, id)| id)
+        .collect()
+}
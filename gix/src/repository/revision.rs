@@ -1,6 +1,11 @@
 use gix_macros::momo;
 
-use crate::{bstr::BStr, revision, Id};
+#[cfg(feature = "blob-diff")]
+use std::convert::Infallible;
+
+#[cfg(feature = "blob-diff")]
+use crate::bstr::ByteSlice;
+use crate::{bstr::BStr, ext::ObjectIdExt, revision, Id};
 
 /// Methods for resolving revisions by spec or working with the commit graph.
 impl crate::Repository {
@@ -45,4 +50,327 @@ impl crate::Repository {
     ) -> revision::walk::Platform<'_> {
         revision::walk::Platform::new(tips, self)
     }
+
+    /// Count the commits reachable from `local` but not from `upstream`, and the ones reachable from `upstream` but not from `local`,
+    /// returned as `(ahead, behind)` similar to `git rev-list --left-right --count local...upstream`.
+    ///
+    /// This is typically used to show how a branch diverged from its upstream.
+    /// The traversal uses the [commit-graph](Self::revision_graph()) if available and only visits commits until the histories join.
+    #[doc(alias = "graph_ahead_behind", alias = "git2")]
+    pub fn ahead_behind(
+        &self,
+        local: impl Into<gix_hash::ObjectId>,
+        upstream: impl Into<gix_hash::ObjectId>,
+    ) -> Result<(usize, usize), revision::ahead_behind::Error> {
+        gix_revision::ahead_behind(&local.into(), &upstream.into(), &mut self.revision_graph())
+    }
+
+    /// Like [`ahead_behind()`](Self::ahead_behind()), but compute `(ahead, behind)` for each `(local, upstream)` pair in `pairs` with a
+    /// single traversal, returning the counts in the order of `pairs`.
+    ///
+    /// Use this when many branches are compared to their upstreams at once, as done when listing branches.
+    pub fn ahead_behind_many(
+        &self,
+        pairs: impl IntoIterator<Item = (impl Into<gix_hash::ObjectId>, impl Into<gix_hash::ObjectId>)>,
+    ) -> Result<Vec<(usize, usize)>, revision::ahead_behind::Error> {
+        gix_revision::ahead_behind_many(
+            pairs
+                .into_iter()
+                .map(|(local, upstream)| (local.into(), upstream.into())),
+            &mut self.revision_graph(),
+        )
+    }
+
+    /// Return all commits reachable from either `left` or `right`, but not from both, along with the side they are reachable from,
+    /// similar to `git rev-list --left-right left...right`.
+    ///
+    /// Commits are ordered newest first, and the traversal uses the [commit-graph](Self::revision_graph()) if available and only visits
+    /// commits until the histories join. Use [`mark_equivalent_commits()`](Self::mark_equivalent_commits()) to find commits that
+    /// were cherry-picked from one side to the other.
+    pub fn symmetric_difference(
+        &self,
+        left: impl Into<gix_hash::ObjectId>,
+        right: impl Into<gix_hash::ObjectId>,
+    ) -> Result<Vec<revision::range::Commit>, revision::range::Error> {
+        Ok(
+            gix_revision::symmetric_difference(&left.into(), &right.into(), &mut self.revision_graph())?
+                .into_iter()
+                .map(|(id, side)| revision::range::Commit {
+                    id,
+                    side,
+                    equivalent: None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Find all best common ancestors of `first` and all of `others`, newest first, similar to `git merge-base --all first others…`,
+    /// or return an empty list if they don't share any history.
+    ///
+    /// Most of the time there is only a single merge-base, but criss-cross merges can lead to multiple ones.
+    /// The traversal uses the [commit-graph](Self::revision_graph()) if available.
+    #[doc(alias = "merge_base", alias = "git2")]
+    pub fn merge_bases(
+        &self,
+        first: impl Into<gix_hash::ObjectId>,
+        others: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+    ) -> Result<Vec<Id<'_>>, revision::merge_base::Error> {
+        let others: Vec<_> = others.into_iter().map(Into::into).collect();
+        Ok(
+            gix_revision::merge_base(&first.into(), &others, &mut self.revision_graph())?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|id| id.attach(self))
+                .collect(),
+        )
+    }
+
+    /// Return `true` if `ancestor` is reachable from `descendant`, or if both are the same commit,
+    /// similar to `git merge-base --is-ancestor ancestor descendant`.
+    ///
+    /// This answers questions like _is this branch merged?_, and uses generation numbers of the [commit-graph](Self::revision_graph())
+    /// if available to stop the traversal early.
+    #[doc(alias = "graph_descendant_of", alias = "git2")]
+    pub fn is_ancestor(
+        &self,
+        ancestor: impl Into<gix_hash::ObjectId>,
+        descendant: impl Into<gix_hash::ObjectId>,
+    ) -> Result<bool, revision::reachable::Error> {
+        gix_revision::is_ancestor(&ancestor.into(), &descendant.into(), &mut self.revision_graph())
+    }
+
+    /// For each of `targets`, return `true` if it's reachable from at least one of `commits`, in the order of `targets`.
+    ///
+    /// This is like calling [`is_ancestor()`](Self::is_ancestor()) for each target, but with a single traversal that stops as soon
+    /// as all `targets` were found.
+    pub fn reachable_from(
+        &self,
+        commits: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+        targets: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+    ) -> Result<Vec<bool>, revision::reachable::Error> {
+        let targets: Vec<_> = targets.into_iter().map(Into::into).collect();
+        gix_revision::reachable_from(
+            commits.into_iter().map(Into::into),
+            &targets,
+            &mut self.revision_graph(),
+        )
+    }
+
+    /// Name all commits reachable from references relative to the best-suited reference, similar to `git name-rev --all`,
+    /// using `options` to select references.
+    ///
+    /// Commits are named like `tags/v1.2~3^2`, preferring tags over other references and avoiding traversal into merged branches.
+    /// Use [`Names::annotate()`](revision::name_rev::Names::annotate()) to process text containing object ids in bulk,
+    /// like `git name-rev --annotate-stdin`.
+    pub fn name_rev(
+        &self,
+        options: revision::name_rev::Options,
+    ) -> Result<revision::name_rev::Names, revision::name_rev::Error> {
+        let platform = self.references()?;
+        let refs = if options.tags_only {
+            platform.tags()?
+        } else {
+            platform.all()?
+        };
+        let mut tips = Vec::new();
+        for r in refs {
+            let mut r = r.map_err(revision::name_rev::Error::ObtainReference)?;
+            let name = r.name().as_bstr();
+            if !(options.include.is_empty() || revision::name_rev::matches_any(name, &options.include))
+                || revision::name_rev::matches_any(name, &options.exclude)
+            {
+                continue;
+            }
+            // References that don't point to commits can't be used to name commits, just like in `git`.
+            let target_id = r.target().try_id().map(ToOwned::to_owned);
+            let Ok(peeled_id) = r.peel_to_id_in_place().map(crate::Id::detach) else {
+                continue;
+            };
+            let Some(commit) = self
+                .find_object(peeled_id)
+                .ok()
+                .and_then(|obj| obj.try_into_commit().ok())
+            else {
+                continue;
+            };
+            let is_annotated_tag = target_id.map_or(false, |target_id| target_id != peeled_id);
+            // Tags without tagger are ordered by the time of the commit they point to.
+            let tagger_time = is_annotated_tag
+                .then(|| {
+                    let tag = self.find_object(target_id?).ok()?.try_into_tag().ok()?;
+                    let time = tag.tagger().ok()??.time;
+                    Some(time.seconds)
+                })
+                .flatten();
+            let time = match tagger_time {
+                Some(time) => time,
+                None => commit.time().map_err(revision::name_rev::Error::DecodeCommit)?.seconds,
+            };
+            let full_name = r.name().as_bstr();
+            let mut name = revision::name_rev::display_name(full_name).into_owned();
+            if is_annotated_tag {
+                name.extend_from_slice(b"^0");
+            }
+            tips.push(gix_revision::name_rev::Tip {
+                name: name.into(),
+                id: peeled_id,
+                from_tag: full_name.starts_with(b"refs/tags/"),
+                time,
+            });
+        }
+
+        let mut graph = self.revision_graph();
+        gix_revision::name_rev(
+            tips,
+            &mut graph,
+            gix_revision::name_rev::Options { cutoff: options.cutoff },
+        )?;
+        Ok(revision::name_rev::Names {
+            names: graph.detach(),
+            hash_kind: self.object_hash(),
+        })
+    }
+}
+
+/// Patch ids
+#[cfg(feature = "blob-diff")]
+impl crate::Repository {
+    /// Compute an id of the changes the commit with `id` introduces compared to its parent, or return `None` if it's a merge commit.
+    ///
+    /// Commits which introduce the same changes have the same patch id, even if their metadata or the lines around the changes differ,
+    /// which is how `git` finds commits that were cherry-picked. Similar to `git patch-id`, whitespace is ignored entirely,
+    /// but the id itself is not compatible with the one computed by `git`.
+    pub fn patch_id(
+        &self,
+        id: impl Into<gix_hash::ObjectId>,
+    ) -> Result<Option<gix_hash::ObjectId>, revision::range::patch_id::Error> {
+        let commit = self.find_object(id.into())?.try_into_commit()?;
+        let mut parent_ids = commit.parent_ids();
+        let parent_tree = match (parent_ids.next(), parent_ids.next()) {
+            (Some(parent_id), None) => parent_id.object()?.peel_to_tree()?,
+            (None, _) => self.empty_tree(),
+            (Some(_), Some(_)) => return Ok(None),
+        };
+        let tree = commit.tree()?;
+
+        let mut resource_cache = self.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        let mut hasher = gix_features::hash::hasher(self.object_hash());
+        let mut err = None;
+        parent_tree
+            .changes()?
+            .track_path()
+            .track_rewrites(None)
+            .for_each_to_obtain_tree(&tree, |change| -> Result<_, Infallible> {
+                use crate::object::tree::diff::{change::Event, Action};
+                if change.event.entry_mode().is_tree() {
+                    return Ok(Action::Continue);
+                }
+                hasher.update(change.location);
+                hasher.update(b"\0");
+                let ids = match change.event {
+                    Event::Addition { id, .. } => (None, Some(id.detach())),
+                    Event::Deletion { id, .. } => (Some(id.detach()), None),
+                    Event::Modification { previous_id, id, .. } => (Some(previous_id.detach()), Some(id.detach())),
+                    Event::Rewrite { source_id, id, .. } => (Some(source_id.detach()), Some(id.detach())),
+                };
+                let res = if change.event.entry_mode().is_commit() {
+                    Ok(false)
+                } else {
+                    change
+                        .diff(&mut resource_cache)
+                        .map_err(Into::into)
+                        .and_then(|mut diff| {
+                            let outcome = diff
+                                .lines(|hunk| {
+                                    use crate::object::blob::diff::lines::Change;
+                                    let (removed, added): (&[&BStr], &[&BStr]) = match hunk {
+                                        Change::Addition { lines } => (&[], lines),
+                                        Change::Deletion { lines } => (lines, &[]),
+                                        Change::Modification {
+                                            lines_before,
+                                            lines_after,
+                                        } => (lines_before, lines_after),
+                                    };
+                                    for (prefix, lines) in [(b"-", removed), (b"+", added)] {
+                                        for line in lines {
+                                            hasher.update(prefix);
+                                            for word in line.fields_with(|c| c.is_ascii_whitespace()) {
+                                                hasher.update(word.as_bytes());
+                                            }
+                                            hasher.update(b"\n");
+                                        }
+                                    }
+                                    Ok(())
+                                })
+                                .map_err(revision::range::patch_id::Error::from)?;
+                            Ok(matches!(
+                                outcome.operation,
+                                gix_diff::blob::platform::prepare_diff::Operation::SourceOrDestinationIsBinary
+                            ))
+                        })
+                };
+                resource_cache.clear_resource_cache();
+                match res {
+                    Ok(hash_ids) => {
+                        if hash_ids {
+                            for id in [ids.0, ids.1].iter().flatten() {
+                                hasher.update(id.as_bytes());
+                            }
+                        }
+                        Ok(Action::Continue)
+                    }
+                    Err(e) => {
+                        err = Some(e);
+                        Ok(Action::Cancel)
+                    }
+                }
+            })?;
+        if let Some(err) = err {
+            return Err(err);
+        }
+        Ok(Some(gix_hash::ObjectId::from(hasher.digest())))
+    }
+
+    /// Find commits in `commits` which introduce the same changes as a commit on the other side, and set their
+    /// [`equivalent`](revision::range::Commit::equivalent) to that commit, similar to `git rev-list --cherry-mark left...right`.
+    ///
+    /// `commits` are typically obtained with [`symmetric_difference()`](Self::symmetric_difference()), and commits are considered
+    /// equivalent if they have the same [patch id](Self::patch_id()). Merge commits are never equivalent to another commit.
+    pub fn mark_equivalent_commits(
+        &self,
+        commits: &mut [revision::range::Commit],
+    ) -> Result<(), revision::range::patch_id::Error> {
+        use revision::range::Side;
+        let num_left = commits.iter().filter(|c| c.side == Side::Left).count();
+        let smaller_side = if num_left <= commits.len() - num_left {
+            Side::Left
+        } else {
+            Side::Right
+        };
+
+        let mut by_patch_id = gix_hashtable::HashMap::<gix_hash::ObjectId, Vec<usize>>::default();
+        for (idx, commit) in commits.iter().enumerate().filter(|(_, c)| c.side == smaller_side) {
+            if let Some(patch_id) = self.patch_id(commit.id)? {
+                by_patch_id.entry(patch_id).or_default().push(idx);
+            }
+        }
+        if by_patch_id.is_empty() {
+            return Ok(());
+        }
+        for idx in 0..commits.len() {
+            if commits[idx].side == smaller_side {
+                continue;
+            }
+            let Some(patch_id) = self.patch_id(commits[idx].id)? else {
+                continue;
+            };
+            if let Some(matches) = by_patch_id.get(&patch_id) {
+                commits[idx].equivalent = Some(commits[matches[0]].id);
+                for other_idx in matches {
+                    commits[*other_idx].equivalent = Some(commits[idx].id);
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -47,6 +47,43 @@ impl Repository {
         )
     }
 
+    /// Return the grafts to use when traversing commits, which replace the parents of the commits listed in the `info/grafts`
+    /// file, and which remove the parents of all [shallow commits](Self::shallow_commits()) so traversals stop at the
+    /// shallow boundary instead of failing to find the missing parents.
+    ///
+    /// The result is empty if there is no grafts file and the repository isn't shallow.
+    pub fn commit_grafts(&self) -> Result<gix_traverse::commit::Grafts, crate::shallow::grafts::Error> {
+        use crate::shallow::grafts::Error;
+        let mut grafts = gix_traverse::commit::Grafts::default();
+        match std::fs::read(self.common_dir().join("info").join("grafts")) {
+            Ok(buf) => {
+                for line in buf.lines().map(ByteSlice::trim) {
+                    if line.is_empty() || line.starts_with(b"#") {
+                        continue;
+                    }
+                    let mut ids = line
+                        .fields_with(|c| c.is_ascii_whitespace())
+                        .map(gix_hash::ObjectId::from_hex);
+                    let (commit, parents) = ids
+                        .next()
+                        .expect("non-empty lines have at least one field")
+                        .and_then(|commit| Ok((commit, ids.collect::<Result<_, _>>()?)))
+                        .map_err(|source| Error::DecodeLine {
+                            line: line.into(),
+                            source,
+                        })?;
+                    grafts.insert(commit, parents);
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        for commit in self.shallow_commits()?.iter().flat_map(|commits| commits.iter()) {
+            grafts.insert(*commit, Default::default());
+        }
+        Ok(grafts)
+    }
+
     /// Return the path to the `shallow` file which contains hashes, one per line, that describe commits that don't have their
     /// parents within this repository.
     ///
@@ -0,0 +1,357 @@
+use std::{io::Read, path::Path};
+
+use gix_hash::ObjectId;
+use gix_index::merge::two_way::{Change, Kind};
+use gix_ref::{
+    transaction::{Change as RefChange, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    worktree::{add::Head, switch::Error},
+};
+
+/// Switching the worktree between trees
+impl crate::Repository {
+    /// Check out `head` by updating the index and the worktree from the tree of `HEAD` to the tree of the commit it refers to,
+    /// and point `HEAD` to it, similar to `git switch` or `git checkout <branch>`.
+    ///
+    /// Local changes are kept, and the switch fails without changing anything if they would be overwritten.
+    /// Branches that are in use by another worktree can't be checked out, and [new branches](Head::NewBranch) must not exist yet.
+    pub fn switch(&self, head: Head) -> Result<(), Error> {
+        let previous = self.head()?;
+        let previous_name = match previous.referent_name() {
+            Some(name) => name.shorten().to_owned(),
+            None => previous.id().map(|id| id.to_string()).unwrap_or_default().into(),
+        };
+        let (commit, target, name) = match &head {
+            Head::Detached(commit) => (*commit, Target::Peeled(*commit), commit.to_string().into()),
+            Head::Branch(name) => {
+                if previous.referent_name() != Some(name.as_ref()) {
+                    self.ensure_branch_not_in_use(name)?;
+                }
+                let commit = self.find_reference(name.as_ref())?.peel_to_id_in_place()?.detach();
+                (commit, Target::Symbolic(name.clone()), name.shorten().to_owned())
+            }
+            Head::NewBranch { name, commit } => (*commit, Target::Symbolic(name.clone()), name.shorten().to_owned()),
+        };
+        let tree = self.find_object(commit)?.try_into_commit()?.tree_id()?.detach();
+
+        // Create the new branch first so an existing branch is detected before the worktree changes.
+        if let Head::NewBranch { name, commit } = &head {
+            self.reference(
+                name.clone(),
+                *commit,
+                PreviousValue::MustNotExist,
+                format!("branch: Created from {commit}"),
+            )?;
+        }
+        if let Err(err) = self.checkout_tree(tree) {
+            if let Head::NewBranch { name, commit } = &head {
+                self.edit_reference(RefEdit {
+                    change: RefChange::Delete {
+                        expected: PreviousValue::MustExistAndMatch(Target::Peeled(*commit)),
+                        log: RefLog::AndReference,
+                    },
+                    name: name.clone(),
+                    deref: false,
+                })?;
+            }
+            return Err(err);
+        }
+        self.edit_reference(RefEdit {
+            change: RefChange::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("checkout: moving from {previous_name} to {name}").into(),
+                },
+                expected: PreviousValue::Any,
+                new: target,
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(())
+    }
+
+    /// Update the index and the worktree from the tree of `HEAD` to `tree`, similar to `git read-tree -m -u HEAD <tree>`,
+    /// without changing `HEAD`.
+    ///
+    /// Local changes are kept, and nothing is changed if they would be overwritten.
+    pub fn checkout_tree(&self, tree: impl Into<ObjectId>) -> Result<(), Error> {
+        let current = match self.head()?.try_peel_to_id_in_place()? {
+            Some(commit) => self
+                .index_from_tree(&commit.object()?.try_into_commit()?.tree_id()?)?
+                .into(),
+            None => gix_index::State::new(self.object_hash()),
+        };
+        let target = self.index_from_tree(&tree.into())?;
+        self.checkout_tree_from(&current, &target)?.write(Default::default())?;
+        Ok(())
+    }
+
+    /// Point the current branch, or `HEAD` if it's detached, to `commit` and update the index and the worktree to match,
+    /// similar to `git reset --keep`.
+    ///
+    /// Local changes are kept, and nothing is changed if they are in files that differ between `HEAD` and `commit`.
+    /// `ORIG_HEAD` is set to the previous commit of `HEAD`.
+    pub fn reset_keep(&self, commit: impl Into<ObjectId>) -> Result<(), Error> {
+        let commit = commit.into();
+        let head = self.head_commit()?;
+        let current = self.index_from_tree(&head.tree_id()?)?;
+        let target = self.index_from_tree(&self.find_object(commit)?.try_into_commit()?.tree_id()?)?;
+        self.checkout_tree_from(&current, &target)?.write(Default::default())?;
+
+        for (name, deref, message) in [
+            ("ORIG_HEAD", false, String::new()),
+            ("HEAD", true, format!("reset: moving to {commit}")),
+        ] {
+            let new = if name == "HEAD" { commit } else { head.id };
+            self.edit_reference(RefEdit {
+                change: RefChange::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: message.into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(new),
+                },
+                name: name.try_into().expect("valid"),
+                deref,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Merge `target` into the index with the two-tree merge, knowing that the index is based on `current`, and apply
+    /// the result to the worktree. The returned index isn't written yet.
+    ///
+    /// Fails without changing anything if local changes would be overwritten.
+    pub(crate) fn checkout_tree_from(
+        &self,
+        current: &gix_index::State,
+        target: &gix_index::State,
+    ) -> Result<gix_index::File, Error> {
+        let workdir = self.work_dir().ok_or(Error::BareRepository)?;
+        // Read the index from disk as the shared one may be stale if it was just written.
+        let index_exists = self.index_path().is_file();
+        let index = if index_exists {
+            self.open_index()?.into()
+        } else {
+            gix_index::State::new(self.object_hash())
+        };
+        let (pipeline, _) = self.filter_pipeline(None)?;
+        let mut worktree = WorktreeState {
+            workdir,
+            index: &index,
+            pipeline,
+            excludes: self.excludes(
+                &index,
+                None,
+                gix_worktree::stack::state::ignore::Source::WorktreeThenIdMappingIfNotSkipped,
+            )?,
+            stat: self.stat_options()?,
+            fs: self.filesystem_options()?,
+            buf: Vec::new(),
+        };
+        let outcome = gix_index::merge::two_way(
+            &index,
+            current,
+            target,
+            &mut worktree,
+            gix_index::merge::two_way::Options {
+                initial_checkout: !index_exists,
+            },
+        )?;
+        if !outcome.conflicts.is_empty() {
+            return Err(Error::Conflicts {
+                conflicts: outcome.conflicts,
+            });
+        }
+        let mut state = outcome.state;
+        self.apply_changes_to_worktree(workdir, &outcome.changes, &mut state)?;
+        Ok(gix_index::File::from_state(state, self.index_path()))
+    }
+
+    /// Apply `changes` to the files in `workdir` so that they match the entries in `state`, and update the stat information
+    /// of all written entries in `state`.
+    ///
+    /// Local changes are overwritten, and directories that are in the way of files are removed along with their content.
+    pub(crate) fn apply_changes_to_worktree(
+        &self,
+        workdir: &Path,
+        changes: &[Change],
+        state: &mut gix_index::State,
+    ) -> Result<(), Error> {
+        let mut updated = Vec::new();
+        for change in changes {
+            match change {
+                Change::Remove { path } => {
+                    let path = workdir.join(gix_path::from_bstr(path.as_bstr()));
+                    match std::fs::remove_file(&path) {
+                        Ok(()) => {}
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                        Err(err) => return Err(err.into()),
+                    }
+                    for dir in path.ancestors().skip(1).take_while(|dir| *dir != workdir) {
+                        if std::fs::remove_dir(dir).is_err() {
+                            break;
+                        }
+                    }
+                }
+                Change::Update { path: rela_path } => {
+                    // Existing files are written into without truncation during checkout, so they must be removed first.
+                    let path = workdir.join(gix_path::from_bstr(rela_path.as_bstr()));
+                    match std::fs::symlink_metadata(&path) {
+                        Ok(metadata) if metadata.is_dir() => std::fs::remove_dir_all(&path)?,
+                        Ok(_) => std::fs::remove_file(&path)?,
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(err) => return Err(err.into()),
+                    }
+                    updated.push(rela_path.as_bstr());
+                }
+            }
+        }
+
+        if updated.is_empty() {
+            return Ok(());
+        }
+        let mut checkout = state.clone();
+        checkout.remove_entries(|_, path, _| updated.binary_search_by(|p| p.cmp(&path)).is_err());
+        let mut options = self
+            .config
+            .checkout_options(self, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+        options.overwrite_existing = true;
+        gix_worktree_state::checkout(
+            &mut checkout,
+            workdir,
+            self.objects.clone().into_arc()?,
+            &gix_features::progress::Discard,
+            &gix_features::progress::Discard,
+            &Default::default(),
+            options,
+        )?;
+        for entry in checkout.entries() {
+            if let Some(idx) = state.entry_index_by_path_and_stage(entry.path(&checkout), 0) {
+                state.entries_mut()[idx].stat = entry.stat;
+            }
+        }
+        Ok(())
+    }
+
+    fn ensure_branch_not_in_use(&self, name: &gix_ref::FullName) -> Result<(), Error> {
+        match self
+            .checked_out_branches()?
+            .into_iter()
+            .find(|branch| &branch.name == name)
+        {
+            Some(branch) => Err(Error::BranchInUse {
+                name: name.clone(),
+                head: branch.head,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Provide information about the worktree to the two-tree merge.
+struct WorktreeState<'a, 'repo> {
+    workdir: &'a Path,
+    index: &'a gix_index::State,
+    pipeline: crate::filter::Pipeline<'repo>,
+    excludes: crate::AttributeStack<'repo>,
+    stat: gix_index::entry::stat::Options,
+    fs: gix_fs::Capabilities,
+    buf: Vec<u8>,
+}
+
+impl gix_index::merge::two_way::Worktree for WorktreeState<'_, '_> {
+    type Error = Error;
+
+    fn is_uptodate(&mut self, rela_path: &BStr, entry: &gix_index::Entry) -> Result<bool, Self::Error> {
+        let path = self.workdir.join(gix_path::from_bstr(rela_path));
+        let metadata = match gix_index::fs::Metadata::from_path_no_follow(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if is_missing(&err, self.workdir, &path) => return Ok(true),
+            Err(err) => return Err(err.into()),
+        };
+        if entry.mode.is_submodule() {
+            return Ok(true);
+        }
+        if metadata.is_dir()
+            || entry
+                .mode
+                .change_to_match_fs(&metadata, self.fs.symlink, self.fs.executable_bit)
+                .is_some()
+        {
+            return Ok(false);
+        }
+        let stat = gix_index::entry::Stat::from_fs(&metadata)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        if stat.matches(&entry.stat, self.stat) && !stat.is_racy(self.index.timestamp(), self.stat) {
+            return Ok(true);
+        }
+
+        self.buf.clear();
+        if metadata.is_symlink() {
+            self.buf
+                .extend_from_slice(&gix_path::into_bstr(std::fs::read_link(&path)?));
+        } else {
+            let file = std::fs::File::open(&path)?;
+            self.pipeline
+                .convert_to_git(file, gix_path::from_bstr(rela_path).as_ref(), self.index)
+                .map_err(|source| Error::ConvertToGit {
+                    rela_path: rela_path.to_owned(),
+                    source,
+                })?
+                .read_to_end(&mut self.buf)?;
+        }
+        Ok(gix_object::compute_hash(entry.id.kind(), gix_object::Kind::Blob, &self.buf) == entry.id)
+    }
+
+    fn untracked_kind(&mut self, rela_path: &BStr) -> Result<Option<Kind>, Self::Error> {
+        if self.index.entry_by_path(rela_path).is_some() {
+            return Ok(None);
+        }
+        let path = self.workdir.join(gix_path::from_bstr(rela_path));
+        let metadata = match std::fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if is_missing(&err, self.workdir, &path) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let is_dir = metadata.is_dir();
+        if self.excludes.at_entry(rela_path, Some(is_dir))?.is_excluded() {
+            return Ok(None);
+        }
+        if !is_dir {
+            return Ok(Some(Kind::File));
+        }
+        for entry in std::fs::read_dir(&path)? {
+            let name = entry?.file_name();
+            let mut rela_path = rela_path.to_owned();
+            rela_path.push(b'/');
+            rela_path.extend_from_slice(
+                gix_path::os_str_into_bstr(&name)
+                    .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "illformed UTF-8 in path"))?,
+            );
+            if self.untracked_kind(rela_path.as_bstr())?.is_some() {
+                return Ok(Some(Kind::Directory));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Return `true` if `err` indicates that `path` within `workdir` doesn't exist, also if one of its leading directories is
+/// a file.
+fn is_missing(err: &std::io::Error, workdir: &Path, path: &Path) -> bool {
+    err.kind() == std::io::ErrorKind::NotFound
+        || path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != workdir)
+            .any(|dir| std::fs::symlink_metadata(dir).map_or(false, |metadata| !metadata.is_dir()))
+}
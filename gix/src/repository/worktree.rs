@@ -1,4 +1,9 @@
-use crate::{worktree, Worktree};
+use gix_ref::{Category, FullName, Target};
+
+use crate::{
+    bstr::{BString, ByteSlice, ByteVec},
+    worktree, Worktree,
+};
 
 /// Interact with individual worktrees and their information.
 impl crate::Repository {
@@ -25,6 +30,157 @@ impl crate::Repository {
         res.sort_by(|a, b| a.git_dir.cmp(&b.git_dir));
         Ok(res)
     }
+
+    /// Return all branches that are in use by the main worktree or any linked worktree, either because they are checked out,
+    /// or because they are being rebased or bisected, similar to the checks `git` performs before deleting or checking out a branch.
+    ///
+    /// A branch may be listed multiple times if it's in use in multiple ways, and the main worktree comes first.
+    pub fn checked_out_branches(&self) -> Result<Vec<worktree::checked_out::Branch>, worktree::checked_out::Error> {
+        use worktree::checked_out::{Branch, State};
+        let mut out = Vec::new();
+        for head in self.refs.worktree_refs()? {
+            let git_dir = match head.name.category_and_short_name() {
+                Some((Category::MainPseudoRef, name)) if name == "HEAD" => self.common_dir().to_owned(),
+                Some((Category::LinkedPseudoRef { name: id }, name)) if name == "HEAD" => {
+                    self.common_dir().join("worktrees").join(gix_path::from_bstr(id))
+                }
+                _ => continue,
+            };
+            let mut push = |name: FullName, state: State| {
+                if name.category() == Some(Category::LocalBranch) {
+                    out.push(Branch {
+                        name,
+                        head: head.name.clone(),
+                        state,
+                    });
+                }
+            };
+            if let Target::Symbolic(name) = &head.target {
+                push(name.clone(), State::Head);
+            }
+            let read_name = |path: &std::path::Path| -> Option<BString> {
+                let content = std::fs::read(git_dir.join(path)).ok()?;
+                Some(content.trim_end().into())
+            };
+            if let Some(name) = ["rebase-merge/head-name", "rebase-apply/head-name"]
+                .iter()
+                .find_map(|path| read_name(path.as_ref()))
+                .and_then(|name| FullName::try_from(name).ok())
+            {
+                push(name, State::Rebasing);
+            }
+            if let Some(name) = read_name("BISECT_START".as_ref()).and_then(|mut name| {
+                name.insert_str(0, "refs/heads/");
+                FullName::try_from(name).ok()
+            }) {
+                push(name, State::Bisecting);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Remove the private git directories of all linked worktrees whose checkout doesn't exist anymore, similar to
+    /// `git worktree prune`, and return information about them. Locked worktrees are never pruned.
+    ///
+    /// If `dry_run` is `true`, nothing is removed.
+    pub fn worktrees_prune(&self, dry_run: bool) -> std::io::Result<Vec<worktree::prune::Pruned>> {
+        let mut res = Vec::new();
+        let worktrees_dir = self.common_dir().join("worktrees");
+        let iter = match std::fs::read_dir(&worktrees_dir) {
+            Ok(iter) => iter,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(res),
+            Err(err) => return Err(err),
+        };
+        let mut git_dirs = iter
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        git_dirs.sort();
+        for git_dir in git_dirs.into_iter().filter(|git_dir| git_dir.is_dir()) {
+            let proxy = worktree::Proxy::new(self, git_dir);
+            if proxy.is_locked() {
+                continue;
+            }
+            let reason = match proxy.base() {
+                Ok(base) if base.is_dir() => continue,
+                Ok(_) => worktree::prune::Reason::MissingWorktree,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => worktree::prune::Reason::MissingGitDirFile,
+                Err(err) => return Err(err),
+            };
+            if !dry_run {
+                std::fs::remove_dir_all(proxy.git_dir())?;
+            }
+            res.push(worktree::prune::Pruned {
+                id: proxy.id().to_owned(),
+                reason,
+            });
+        }
+        if !dry_run && !res.is_empty() {
+            // Like `git`, remove the directory if it became empty, and ignore failure if it isn't.
+            std::fs::remove_dir(&worktrees_dir).ok();
+        }
+        Ok(res)
+    }
+
+    /// Repair the links between linked worktrees and their private git directories, similar to `git worktree repair`,
+    /// and return all files that were rewritten.
+    ///
+    /// The `.git` files of all existing worktree checkouts are fixed to point to their private git directory.
+    /// Worktree checkouts that were moved manually can be passed as `paths` to make their private git directory
+    /// point to their new location.
+    pub fn worktrees_repair(
+        &self,
+        paths: impl IntoIterator<Item = impl AsRef<std::path::Path>>,
+    ) -> std::io::Result<Vec<worktree::repair::Repaired>> {
+        let mut res = Vec::new();
+        for proxy in self.worktrees()? {
+            let Ok(base) = proxy.base() else { continue };
+            if !base.is_dir() {
+                continue;
+            }
+            let dot_git = base.join(gix_discover::DOT_GIT_DIR);
+            let git_dir = gix_path::realpath(proxy.git_dir()).map_err(into_io_error)?;
+            let is_valid = gix_discover::path::from_gitdir_file(&dot_git)
+                .ok()
+                .and_then(|path| gix_path::realpath(path).ok())
+                .map_or(false, |path| path == git_dir);
+            if !is_valid {
+                worktree::proxy::write_dot_git_file(&base, &git_dir)?;
+                res.push(worktree::repair::Repaired {
+                    path: dot_git,
+                    kind: worktree::repair::Kind::DotGitFile,
+                });
+            }
+        }
+
+        let worktrees_dir = gix_path::realpath(self.common_dir().join("worktrees")).ok();
+        for path in paths {
+            let base = gix_path::realpath(path.as_ref()).map_err(into_io_error)?;
+            let Some(git_dir) = gix_discover::path::from_gitdir_file(&base.join(gix_discover::DOT_GIT_DIR))
+                .ok()
+                .and_then(|path| gix_path::realpath(path).ok())
+            else {
+                continue;
+            };
+            if worktrees_dir.is_none() || git_dir.parent() != worktrees_dir.as_deref() || !git_dir.is_dir() {
+                continue;
+            }
+            let proxy = worktree::Proxy::new(self, git_dir);
+            let is_valid = proxy
+                .base()
+                .ok()
+                .and_then(|base| gix_path::realpath(base).ok())
+                .map_or(false, |recorded| recorded == base);
+            if !is_valid {
+                worktree::proxy::write_git_dir_file(proxy.git_dir(), &base)?;
+                res.push(worktree::repair::Repaired {
+                    path: proxy.git_dir().join("gitdir"),
+                    kind: worktree::repair::Kind::GitDirFile,
+                });
+            }
+        }
+        Ok(res)
+    }
+
     /// Return the repository owning the main worktree, typically from a linked worktree.
     ///
     /// Note that it might be the one that is currently open if this repository doesn't point to a linked worktree.
@@ -139,3 +295,251 @@ impl crate::Repository {
         Ok(())
     }
 }
+
+fn into_io_error(err: gix_path::realpath::Error) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, err)
+}
+
+#[cfg(feature = "worktree-mutation")]
+mod add {
+    use std::path::{Path, PathBuf};
+
+    use gix_ref::{
+        transaction::{Change, PreviousValue, RefEdit, RefLog},
+        Target,
+    };
+
+    use crate::{
+        bstr::{BString, ByteSlice},
+        worktree::{
+            self,
+            add::{Error, Head, Options},
+        },
+    };
+
+    /// Managing linked worktrees
+    impl crate::Repository {
+        /// Create a new linked worktree with its checkout at `path` and `HEAD` set according to `head`, similar to `git worktree add`.
+        ///
+        /// The id of the new worktree is derived from the name of `path`, with a number appended to make it unique.
+        /// `path` must not exist or be an empty directory. Branches can only be checked out in one worktree at a time.
+        /// If the worktree can't be created, for instance because the checkout fails, its private git directory and
+        /// the checkout are removed again.
+        pub fn worktree_add(
+            &self,
+            path: impl AsRef<Path>,
+            head: Head,
+            options: Options,
+        ) -> Result<worktree::Proxy<'_>, Error> {
+            let path = path.as_ref();
+            if path.exists() && (!path.is_dir() || std::fs::read_dir(path)?.next().is_some()) {
+                return Err(Error::DestinationExists { path: path.to_owned() });
+            }
+            let name = path
+                .file_name()
+                .and_then(|name| gix_path::os_str_into_bstr(name).ok())
+                .filter(|name| !name.is_empty() && !name.starts_with(b"."))
+                .ok_or_else(|| Error::InvalidName { path: path.to_owned() })?;
+
+            let (head_contents, commit) = match &head {
+                Head::Detached(commit) => (format!("{commit}\n"), *commit),
+                Head::Branch(name) => {
+                    if let Some(branch) = self
+                        .checked_out_branches()?
+                        .into_iter()
+                        .find(|branch| &branch.name == name)
+                    {
+                        return Err(Error::BranchInUse {
+                            name: name.clone(),
+                            head: branch.head,
+                        });
+                    }
+                    let commit = self.find_reference(name.as_ref())?.peel_to_id_in_place()?.detach();
+                    (format!("ref: {}\n", name.as_bstr()), commit)
+                }
+                Head::NewBranch { name, commit } => (format!("ref: {}\n", name.as_bstr()), *commit),
+            };
+            let tree = self.find_object(commit)?.try_into_commit()?.tree_id()?.detach();
+            if let Head::NewBranch { name, commit } = &head {
+                self.reference(
+                    name.clone(),
+                    *commit,
+                    PreviousValue::MustNotExist,
+                    format!("branch: Created from {commit}"),
+                )?;
+            }
+
+            let worktrees_dir = self.common_dir().join("worktrees");
+            let mut id = BString::from(name);
+            let mut counter = 0;
+            while worktrees_dir.join(gix_path::from_bstr(id.as_bstr())).exists() {
+                counter += 1;
+                id = format!("{name}{counter}").into();
+            }
+            let git_dir = worktrees_dir.join(gix_path::from_bstr(id.as_bstr()));
+            let path_existed = path.exists();
+            std::fs::create_dir_all(&git_dir)?;
+            match self.populate_worktree(path, &git_dir, head_contents, tree, options) {
+                Ok(git_dir) => Ok(worktree::Proxy::new(self, git_dir)),
+                Err(err) => {
+                    // Like `git`, don't leave a half-initialized worktree or the branch created for it behind.
+                    std::fs::remove_dir_all(&git_dir).ok();
+                    std::fs::remove_dir_all(path).ok();
+                    if path_existed {
+                        std::fs::create_dir(path).ok();
+                    }
+                    if let Head::NewBranch { name, commit } = &head {
+                        self.edit_reference(RefEdit {
+                            change: Change::Delete {
+                                expected: PreviousValue::MustExistAndMatch(Target::Peeled(*commit)),
+                                log: RefLog::AndReference,
+                            },
+                            name: name.clone(),
+                            deref: false,
+                        })?;
+                    }
+                    Err(err)
+                }
+            }
+        }
+
+        /// Write the private `git_dir` of the worktree at `path` and check out `tree` if configured in `options`,
+        /// and return the canonicalized `git_dir`.
+        fn populate_worktree(
+            &self,
+            path: &Path,
+            git_dir: &Path,
+            head_contents: String,
+            tree: gix_hash::ObjectId,
+            options: Options,
+        ) -> Result<PathBuf, Error> {
+            std::fs::write(git_dir.join("locked"), "initializing")?;
+            std::fs::create_dir_all(path)?;
+            let (base, git_dir) = (realpath(path)?, realpath(git_dir)?);
+            worktree::proxy::write_git_dir_file(&git_dir, &base)?;
+            std::fs::write(git_dir.join("commondir"), "../..\n")?;
+            std::fs::write(git_dir.join("HEAD"), head_contents)?;
+            worktree::proxy::write_dot_git_file(&base, &git_dir)?;
+
+            if options.checkout {
+                let repo = crate::open_opts(&base, self.options.clone())?;
+                let mut index = repo.index_from_tree(&tree)?;
+                let mut opts = repo
+                    .config
+                    .checkout_options(&repo, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+                opts.destination_is_initially_empty = true;
+                gix_worktree_state::checkout(
+                    &mut index,
+                    &base,
+                    repo.objects.clone().into_arc()?,
+                    &gix_features::progress::Discard,
+                    &gix_features::progress::Discard,
+                    &Default::default(),
+                    opts,
+                )?;
+                index.write(Default::default())?;
+            }
+
+            match options.lock {
+                Some(reason) => std::fs::write(git_dir.join("locked"), reason)?,
+                None => std::fs::remove_file(git_dir.join("locked"))?,
+            }
+            Ok(git_dir)
+        }
+    }
+
+    fn realpath(path: &Path) -> std::io::Result<PathBuf> {
+        gix_path::realpath(path).map_err(super::into_io_error)
+    }
+}
+
+#[cfg(feature = "worktree-mutation")]
+mod export {
+    use std::{path::Path, sync::atomic::AtomicBool};
+
+    use gix_odb::HeaderExt;
+    use gix_worktree::stack::state::attributes::Source;
+
+    use crate::{
+        bstr::BStr,
+        worktree::export::{Error, Options},
+    };
+
+    /// Exporting trees
+    impl crate::Repository {
+        /// Write all files of the tree at `id` whose paths match `patterns` into the `destination` directory, which is
+        /// created if needed, or all files if `patterns` is empty.
+        ///
+        /// This is like a checkout that doesn't touch the index or `HEAD`, so `destination` can be any directory, which
+        /// makes it useful for tools that just need the files of a tree, like build systems.
+        /// Files are written in parallel as configured by `checkout.workers` or [`Options::thread_limit`], and filters
+        /// are applied according to the `.gitattributes` files in the tree unless [turned off](Options::filters).
+        /// Use `files` and `bytes` to count the files and bytes that were written, and `should_interrupt` to abort the operation.
+        #[allow(clippy::too_many_arguments)]
+        pub fn export_tree(
+            &self,
+            id: impl Into<gix_hash::ObjectId>,
+            destination: impl AsRef<Path>,
+            patterns: impl IntoIterator<Item = impl AsRef<BStr>>,
+            files: &dyn gix_features::progress::Count,
+            bytes: &dyn gix_features::progress::Count,
+            should_interrupt: &AtomicBool,
+            options: Options,
+        ) -> Result<gix_worktree_state::checkout::Outcome, Error> {
+            let id = id.into();
+            let header = self.objects.header(id)?;
+            if !header.kind().is_tree() {
+                return Err(Error::NotATree {
+                    id,
+                    actual: header.kind(),
+                });
+            }
+            let destination = destination.as_ref();
+            if destination.exists() && !destination.is_dir() {
+                return Err(Error::DestinationNotADirectory {
+                    path: destination.to_owned(),
+                });
+            }
+            std::fs::create_dir_all(destination)?;
+            let destination_is_initially_empty = std::fs::read_dir(destination)?.next().is_none();
+
+            let mut index = self.index_from_tree(&id)?;
+            let mut patterns = patterns.into_iter().peekable();
+            let mut num_excluded = 0;
+            if patterns.peek().is_some() {
+                let mut pathspec = self.pathspec(patterns, false, &index, Source::IdMapping)?;
+                // Excluded entries are skipped instead of removed so `.gitattributes` files outside of the pathspec still apply.
+                for (entry, path) in index.entries_mut_with_paths() {
+                    if !pathspec.is_included(path, Some(false)) {
+                        entry.flags.insert(gix_index::entry::Flags::SKIP_WORKTREE);
+                        num_excluded += 1;
+                    }
+                }
+            }
+
+            let mut opts = self.config.checkout_options(self, Source::IdMapping)?;
+            let ctx = opts.filters.driver_context_mut();
+            ctx.ref_name = None;
+            ctx.treeish = Some(id);
+            opts.skip_filters = !options.filters;
+            // Only files that are created exclusively detect existing files as collisions.
+            opts.destination_is_initially_empty = destination_is_initially_empty || !options.overwrite_existing;
+            opts.overwrite_existing = options.overwrite_existing;
+            opts.keep_going = options.keep_going;
+            if options.thread_limit.is_some() {
+                opts.thread_limit = options.thread_limit;
+            }
+            let mut outcome = gix_worktree_state::checkout(
+                &mut index,
+                destination,
+                self.objects.clone().into_arc()?,
+                files,
+                bytes,
+                should_interrupt,
+                opts,
+            )?;
+            outcome.files_updated -= num_excluded;
+            Ok(outcome)
+        }
+    }
+}
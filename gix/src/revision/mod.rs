@@ -5,6 +5,34 @@
 #[cfg(feature = "revision")]
 pub use gix_revision as plumbing;
 
+///
+#[cfg(feature = "revision")]
+pub mod ahead_behind {
+    /// The error returned by [`Repository::ahead_behind()`](crate::Repository::ahead_behind()).
+    pub type Error = gix_revision::ahead_behind::Error;
+}
+
+///
+#[cfg(feature = "revision")]
+pub mod merge_base {
+    /// The error returned by [`Repository::merge_bases()`](crate::Repository::merge_bases()).
+    pub type Error = gix_revision::merge_base::Error;
+}
+
+#[cfg(feature = "revision")]
+pub mod name_rev;
+
+#[cfg(feature = "revision")]
+pub mod range;
+
+///
+#[cfg(feature = "revision")]
+pub mod reachable {
+    /// The error returned by [`Repository::is_ancestor()`](crate::Repository::is_ancestor()) and
+    /// [`Repository::reachable_from()`](crate::Repository::reachable_from()).
+    pub type Error = gix_revision::reachable::Error;
+}
+
 ///
 pub mod walk;
 pub use walk::iter::Walk;
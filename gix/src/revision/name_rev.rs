@@ -0,0 +1,122 @@
+//! Name commits relative to references, similar to `git name-rev`.
+use std::borrow::Cow;
+
+use gix_hash::ObjectId;
+
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+pub use gix_revision::name_rev::Name;
+
+/// The error returned by [`Repository::name_rev()`](crate::Repository::name_rev()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterateReferences(#[from] crate::reference::iter::init::Error),
+    #[error("Could not obtain a reference during iteration")]
+    ObtainReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    DecodeCommit(crate::object::commit::Error),
+    #[error(transparent)]
+    NameRev(#[from] gix_revision::name_rev::Error),
+}
+
+/// Options for [`Repository::name_rev()`](crate::Repository::name_rev()).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, only use tags to name commits, like `git name-rev --tags`.
+    pub tags_only: bool,
+    /// If not empty, only use references matching one of these glob patterns, like `git name-rev --refs`.
+    ///
+    /// Patterns are matched against the full reference name, like `refs/heads/main`, and against each of its
+    /// trailing path components, so `main` or `v1.*` match as well.
+    pub include: Vec<BString>,
+    /// Do not use references matching one of these glob patterns, like `git name-rev --exclude`.
+    ///
+    /// Patterns are matched just like [`include`](Self::include), and exclusions take precedence.
+    pub exclude: Vec<BString>,
+    /// If set, do not name commits whose committer time is older than this.
+    pub cutoff: Option<gix_date::SecondsSinceUnixEpoch>,
+}
+
+/// The result of [`Repository::name_rev()`](crate::Repository::name_rev()), naming commits relative to references.
+#[derive(Debug, Clone)]
+pub struct Names {
+    pub(crate) names: gix_revwalk::graph::IdMap<Name>,
+    pub(crate) hash_kind: gix_hash::Kind,
+}
+
+impl Names {
+    /// Return the name of the commit with `id`, or `None` if it can't be reached from any of the selected references.
+    pub fn name(&self, id: impl AsRef<gix_hash::oid>) -> Option<&Name> {
+        self.names.get(id.as_ref())
+    }
+
+    /// Return the amount of named commits.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Return `true` if no commit could be named.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Return a copy of `text` with each full hexadecimal object id that names a commit followed by its name in braces,
+    /// like `git name-rev --annotate-stdin`, turning `<hex>` into `<hex> (main~2)`.
+    ///
+    /// If `name_only` is `true`, the object id is replaced by its name instead, similar to `--name-only`.
+    pub fn annotate(&self, text: &BStr, name_only: bool) -> BString {
+        let hex_len = self.hash_kind.len_in_hex();
+        let mut out = BString::from(Vec::with_capacity(text.len()));
+        let mut pos = 0;
+        while pos < text.len() {
+            let hex_run = text[pos..].iter().take_while(|b| b.is_ascii_hexdigit()).count();
+            if hex_run == 0 {
+                out.push(text[pos]);
+                pos += 1;
+                continue;
+            }
+            let candidate = &text[pos..][..hex_run];
+            match (hex_run == hex_len)
+                .then(|| ObjectId::from_hex(candidate).ok())
+                .flatten()
+                .and_then(|id| self.name(id))
+            {
+                Some(name) if name_only => out.push_str(name.to_string()),
+                Some(name) => {
+                    out.push_str(candidate);
+                    out.push_str(format!(" ({name})"));
+                }
+                None => out.push_str(candidate),
+            }
+            pos += hex_run;
+        }
+        out
+    }
+}
+
+/// Return `true` if the full reference `name` or any of its trailing path components match one of `patterns`.
+pub(crate) fn matches_any(name: &BStr, patterns: &[BString]) -> bool {
+    let mut suffixes = std::iter::once(name).chain(
+        name.iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'/')
+            .map(|(pos, _)| name[pos + 1..].as_bstr()),
+    );
+    suffixes.any(|suffix| {
+        patterns
+            .iter()
+            .any(|pattern| gix_glob::wildmatch(pattern.as_bstr(), suffix, gix_glob::wildmatch::Mode::empty()))
+    })
+}
+
+/// Return the name to show for the reference with `full_name`, similar to what `git name-rev` does.
+pub(crate) fn display_name(full_name: &BStr) -> Cow<'_, BStr> {
+    full_name
+        .strip_prefix(b"refs/heads/")
+        .or_else(|| full_name.strip_prefix(b"refs/"))
+        .map_or_else(|| full_name.into(), |name| name.as_bstr().into())
+}
@@ -0,0 +1,50 @@
+//! Set operations on revision ranges, like `git rev-list --left-right --cherry-mark left...right`.
+use gix_hash::ObjectId;
+
+pub use gix_revision::ahead_behind::Side;
+
+/// The error returned by [`Repository::symmetric_difference()`](crate::Repository::symmetric_difference()).
+pub type Error = gix_revision::ahead_behind::Error;
+
+/// A commit that is reachable from only one side of a symmetric difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: ObjectId,
+    /// The side the commit is reachable from.
+    pub side: Side,
+    /// The id of a commit on the other side which introduces the same changes, if any and if it was
+    /// [marked](crate::Repository::mark_equivalent_commits()).
+    ///
+    /// Commits with an equivalent are shown with `=` by `git rev-list --cherry-mark`, and are omitted with `--cherry-pick`.
+    pub equivalent: Option<ObjectId>,
+}
+
+///
+#[cfg(feature = "blob-diff")]
+pub mod patch_id {
+    /// The error returned by [`Repository::patch_id()`](crate::Repository::patch_id()) and
+    /// [`Repository::mark_equivalent_commits()`](crate::Repository::mark_equivalent_commits()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindCommit(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        NotACommit(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] crate::object::commit::Error),
+        #[error(transparent)]
+        PeelToTree(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        DiffInit(#[from] crate::diff::new_rewrites::Error),
+        #[error(transparent)]
+        ResourceCache(#[from] crate::repository::diff::resource_cache::Error),
+        #[error(transparent)]
+        ForEachChange(#[from] crate::object::tree::diff::for_each::Error),
+        #[error(transparent)]
+        SetResource(#[from] crate::object::blob::diff::init::Error),
+        #[error(transparent)]
+        DiffLines(#[from] crate::object::blob::diff::lines::Error<std::convert::Infallible>),
+    }
+}
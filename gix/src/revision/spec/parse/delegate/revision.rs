@@ -104,59 +104,71 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
 
     fn reflog(&mut self, query: ReflogLookup) -> Option<()> {
         self.unset_disambiguate_call();
+        let r = match &mut self.refs[self.idx] {
+            Some(r) => r.clone().attach(self.repo),
+            val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
+                Ok(Some(r)) => {
+                    *val = Some(r.clone().detach());
+                    r
+                }
+                Ok(None) => {
+                    self.err.push(Error::UnbornHeadsHaveNoRefLog);
+                    return None;
+                }
+                Err(err) => {
+                    self.err.push(err.into());
+                    return None;
+                }
+            },
+        };
+        let mut platform = r.log_iter();
         match query {
-            ReflogLookup::Date(_date) => {
-                self.err.push(Error::Planned {
-                    dependency: "remote handling and ref-specs are fleshed out more",
-                });
-                None
-            }
-            ReflogLookup::Entry(no) => {
-                let r = match &mut self.refs[self.idx] {
-                    Some(r) => r.clone().attach(self.repo),
-                    val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
-                        Ok(Some(r)) => {
-                            *val = Some(r.clone().detach());
-                            r
-                        }
-                        Ok(None) => {
-                            self.err.push(Error::UnbornHeadsHaveNoRefLog);
-                            return None;
-                        }
-                        Err(err) => {
-                            self.err.push(err.into());
-                            return None;
-                        }
-                    },
-                };
-                let mut platform = r.log_iter();
-                match platform.rev().ok().flatten() {
-                    Some(mut it) => match it.nth(no).and_then(Result::ok) {
-                        Some(line) => {
-                            self.objs[self.idx]
-                                .get_or_insert_with(HashSet::default)
-                                .insert(line.new_oid);
-                            Some(())
-                        }
-                        None => {
-                            let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
-                            self.err.push(Error::RefLogEntryOutOfRange {
-                                reference: r.detach(),
-                                desired: no,
-                                available,
-                            });
-                            None
-                        }
-                    },
+            ReflogLookup::Date(date) => {
+                match platform
+                    .rev()
+                    .ok()
+                    .flatten()
+                    .and_then(|it| id_at_time(&r, it.filter_map(Result::ok), date.seconds))
+                {
+                    Some(id) => {
+                        self.objs[self.idx].get_or_insert_with(HashSet::default).insert(id);
+                        Some(())
+                    }
                     None => {
                         self.err.push(Error::MissingRefLog {
                             reference: r.name().as_bstr().into(),
-                            action: "lookup entry",
+                            action: "lookup entry by date",
                         });
                         None
                     }
                 }
             }
+            ReflogLookup::Entry(no) => match platform.rev().ok().flatten() {
+                Some(mut it) => match it.nth(no).and_then(Result::ok) {
+                    Some(line) => {
+                        self.objs[self.idx]
+                            .get_or_insert_with(HashSet::default)
+                            .insert(line.new_oid);
+                        Some(())
+                    }
+                    None => {
+                        let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
+                        self.err.push(Error::RefLogEntryOutOfRange {
+                            reference: r.detach(),
+                            desired: no,
+                            available,
+                        });
+                        None
+                    }
+                },
+                None => {
+                    self.err.push(Error::MissingRefLog {
+                        reference: r.name().as_bstr().into(),
+                        action: "lookup entry",
+                    });
+                    None
+                }
+            },
         }
     }
 
@@ -223,3 +235,62 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         None
     }
 }
+
+/// Find the value `r` had at time `at` by searching its reflog, with `log` providing the most recent entries first,
+/// and warn like git does if the log is inconsistent or doesn't go back far enough.
+///
+/// Returns `None` only if the log is empty.
+fn id_at_time(
+    r: &crate::Reference<'_>,
+    log: impl Iterator<Item = gix_ref::log::Line>,
+    at: gix_date::SecondsSinceUnixEpoch,
+) -> Option<ObjectId> {
+    let current_id = || {
+        r.try_id()
+            .or_else(|| r.follow().and_then(Result::ok).and_then(|r| r.try_id()))
+            .map(crate::Id::detach)
+    };
+    let mut newer: Option<gix_ref::log::Line> = None;
+    for line in log {
+        let time = line.signature.time;
+        if time.seconds <= at {
+            let newer_previous_id = newer.map(|newer| newer.previous_oid).filter(|id| !id.is_null());
+            if newer_previous_id.map_or(false, |id| id != line.new_oid) {
+                gix_trace::warn!(
+                    "log for ref {} has gap after {}",
+                    r.name().as_bstr(),
+                    time.format(gix_date::time::format::GIT_RFC2822)
+                );
+            }
+            return Some(if newer_previous_id.is_some() || time.seconds == at {
+                line.new_oid
+            } else {
+                match current_id() {
+                    Some(id) => {
+                        if id != line.new_oid {
+                            gix_trace::warn!(
+                                "log for ref {} unexpectedly ended on {}",
+                                r.name().as_bstr(),
+                                time.format(gix_date::time::format::GIT_RFC2822)
+                            );
+                        }
+                        id
+                    }
+                    None => line.new_oid,
+                }
+            });
+        }
+        newer = Some(line);
+    }
+    let oldest = newer?;
+    gix_trace::warn!(
+        "log for '{}' only goes back to {}",
+        r.name().shorten(),
+        oldest.signature.time.format(gix_date::time::format::GIT_RFC2822)
+    );
+    Some(if oldest.previous_oid.is_null() {
+        oldest.new_oid
+    } else {
+        oldest.previous_oid
+    })
+}
@@ -22,10 +22,12 @@ pub enum CandidateInfo {
     Tag {
         /// The name of the tag.
         name: BString,
+        /// The date of the tagger, if present.
+        date: Option<gix_date::Time>,
     },
     /// The candidate is a commit.
     Commit {
-        /// The date of the commit.
+        /// The date of the commit author.
         date: gix_date::Time,
         /// The subject line.
         title: BString,
@@ -36,16 +38,32 @@ impl std::fmt::Display for CandidateInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CandidateInfo::FindError { source } => write!(f, "lookup error: {source}"),
-            CandidateInfo::Tag { name } => write!(f, "tag {name:?}"),
+            CandidateInfo::Tag { name, date: Some(date) } => {
+                write!(f, "tag {} - {name}", date.format(gix_date::time::format::SHORT))
+            }
+            CandidateInfo::Tag { name, date: None } => write!(f, "tag {name}"),
             CandidateInfo::Object { kind } => std::fmt::Display::fmt(kind, f),
             CandidateInfo::Commit { date, title } => {
-                write!(f, "commit {} {title:?}", date.format(gix_date::time::format::SHORT))
+                write!(f, "commit {} - {title}", date.format(gix_date::time::format::SHORT))
             }
         }
     }
 }
 
 impl Error {
+    /// If this error or any of the errors it consists of is due to an ambiguous short id, return the ambiguous prefix
+    /// along with the shortened ids of all objects it matched and information about them, in the order `git` would show them.
+    pub fn ambiguous_candidates(&self) -> Option<(gix_hash::Prefix, &[(gix_hash::Prefix, CandidateInfo)])> {
+        match self {
+            Error::AmbiguousPrefix { prefix, info } => Some((*prefix, info)),
+            Error::Multi { current, next } => current
+                .downcast_ref::<Error>()
+                .and_then(Error::ambiguous_candidates)
+                .or_else(|| next.as_ref()?.downcast_ref::<Error>()?.ambiguous_candidates()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn ambiguous(candidates: HashSet<ObjectId>, prefix: gix_hash::Prefix, repo: &Repository) -> Self {
         #[derive(PartialOrd, Ord, Eq, PartialEq, Copy, Clone)]
         enum Order {
@@ -85,13 +103,16 @@ impl Error {
                             gix_object::Kind::Tree | gix_object::Kind::Blob => CandidateInfo::Object { kind: obj.kind },
                             gix_object::Kind::Tag => {
                                 let tag = obj.to_tag_ref();
-                                CandidateInfo::Tag { name: tag.name.into() }
+                                CandidateInfo::Tag {
+                                    name: tag.name.into(),
+                                    date: tag.tagger.map(|tagger| tagger.time),
+                                }
                             }
                             gix_object::Kind::Commit => {
                                 use bstr::ByteSlice;
                                 let commit = obj.to_commit_ref();
                                 CandidateInfo::Commit {
-                                    date: commit.committer().time,
+                                    date: commit.author().time,
                                     title: commit.message().title.trim().into(),
                                 }
                             }
@@ -1,5 +1,4 @@
 use gix_hash::ObjectId;
-use gix_object::FindExt;
 
 use crate::{ext::ObjectIdExt, revision, Repository};
 
@@ -10,7 +9,7 @@ pub enum Error {
     #[error(transparent)]
     AncestorIter(#[from] gix_traverse::commit::ancestors::Error),
     #[error(transparent)]
-    ShallowCommits(#[from] crate::shallow::open::Error),
+    Grafts(#[from] crate::shallow::grafts::Error),
     #[error(transparent)]
     ConfigBoolean(#[from] crate::config::boolean::Error),
 }
@@ -22,9 +21,12 @@ pub struct Info<'repo> {
     pub id: gix_hash::ObjectId,
     /// All parent ids we have encountered. Note that these will be at most one if [`Parents::First`][gix_traverse::commit::Parents::First] is enabled.
     pub parent_ids: gix_traverse::commit::ParentIds,
-    /// The time at which the commit was created. It's only `Some(_)` if sorting is not [`Sorting::BreadthFirst`][gix_traverse::commit::Sorting::BreadthFirst],
-    /// as the walk needs to require the commit-date.
+    /// The time at which the commit was created. It's only `Some(_)` if sorting is not [`Sorting::BreadthFirst`][gix_traverse::commit::Sorting::BreadthFirst]
+    /// or if a [date limit][Platform::since()] is set, as the walk needs to require the commit-date.
     pub commit_time: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// If `true`, the [`parent_ids`](Self::parent_ids) are the ones of a graft instead of the ones stored in the commit,
+    /// which is the case for commits at the [shallow boundary](Repository::shallow_commits()).
+    pub is_grafted: bool,
 
     repo: &'repo Repository,
 }
@@ -53,7 +55,7 @@ impl<'repo> Info<'repo> {
     ///
     /// ### Panics
     ///
-    /// If the iteration wasn't ordered by date.
+    /// If the iteration wasn't ordered or limited by date.
     pub fn commit_time(&self) -> gix_date::SecondsSinceUnixEpoch {
         self.commit_time.expect("traversal involving date caused it to be set")
     }
@@ -67,6 +69,7 @@ impl<'repo> Info<'repo> {
             id: info.id,
             parent_ids: info.parent_ids,
             commit_time: info.commit_time,
+            is_grafted: info.is_grafted,
             repo,
         }
     }
@@ -78,6 +81,7 @@ impl<'repo> Info<'repo> {
             id: self.id,
             parent_ids: self.parent_ids,
             commit_time: self.commit_time,
+            is_grafted: self.is_grafted,
         }
     }
 }
@@ -95,6 +99,9 @@ pub struct Platform<'repo> {
     pub(crate) parents: gix_traverse::commit::Parents,
     pub(crate) use_commit_graph: Option<bool>,
     pub(crate) commit_graph: Option<gix_commitgraph::Graph>,
+    pub(crate) since: Option<gix_date::SecondsSinceUnixEpoch>,
+    pub(crate) until: Option<gix_date::SecondsSinceUnixEpoch>,
+    pub(crate) use_object_replacements: Option<bool>,
 }
 
 impl<'repo> Platform<'repo> {
@@ -106,6 +113,9 @@ impl<'repo> Platform<'repo> {
             parents: Default::default(),
             use_commit_graph: None,
             commit_graph: None,
+            since: None,
+            until: None,
+            use_object_replacements: None,
         }
     }
 }
@@ -124,6 +134,24 @@ impl<'repo> Platform<'repo> {
         self
     }
 
+    /// Only return commits that were committed at or after `seconds` since the unix epoch, similar to `git log --since`,
+    /// or unset the limit with `None`.
+    ///
+    /// The traversal stops once no newer commits can be found anymore, which is exact if the commit-graph
+    /// provides corrected commit dates. Otherwise, it tolerates a few commits with skewed clocks
+    /// when [sorting by commit time][gix_traverse::commit::Sorting::ByCommitTimeNewestFirst], like git does.
+    pub fn since(mut self, seconds: impl Into<Option<gix_date::SecondsSinceUnixEpoch>>) -> Self {
+        self.since = seconds.into();
+        self
+    }
+
+    /// Only return commits that were committed at or before `seconds` since the unix epoch, similar to `git log --until`,
+    /// or unset the limit with `None`.
+    pub fn until(mut self, seconds: impl Into<Option<gix_date::SecondsSinceUnixEpoch>>) -> Self {
+        self.until = seconds.into();
+        self
+    }
+
     /// Allow using the commitgraph, if present, if `toggle` is `true`, or disallow it with `false`. Set it to `None` to leave
     /// control over this to the configuration of `core.commitGraph` (the default).
     ///
@@ -133,6 +161,14 @@ impl<'repo> Platform<'repo> {
         self
     }
 
+    /// If `false`, use commits as they are stored instead of their replacements in `refs/replace/`, or use replacements
+    /// with `true`. Set it to `None` to use the [default of the repository](crate::open::Options::object_replacements())
+    /// (the default).
+    pub fn use_object_replacements(mut self, toggle: impl Into<Option<bool>>) -> Self {
+        self.use_object_replacements = toggle.into();
+        self
+    }
+
     /// Set or unset the commit-graph to use for the iteration. This is useful if the caller wants to check if a commit-graph exists
     /// and refer different implementations depending on the outcome.
     ///
@@ -151,10 +187,10 @@ impl<'repo> Platform<'repo> {
     /// if the traversal should exclude it and its ancestry entirely.
     ///
     /// If `filter` is None, no pruning of the graph will be performed which is the default.
-    pub fn selected(
-        self,
-        mut filter: impl FnMut(&gix_hash::oid) -> bool + 'repo,
-    ) -> Result<revision::Walk<'repo>, Error> {
+    ///
+    /// The [commit grafts](Repository::commit_grafts()) of the repository are respected, so the traversal stops at the
+    /// shallow boundary, with the commits there being marked as [grafted](Info::is_grafted).
+    pub fn selected(self, filter: impl FnMut(&gix_hash::oid) -> bool + 'repo) -> Result<revision::Walk<'repo>, Error> {
         let Platform {
             repo,
             tips,
@@ -162,44 +198,24 @@ impl<'repo> Platform<'repo> {
             parents,
             use_commit_graph,
             commit_graph,
+            since,
+            until,
+            use_object_replacements,
         } = self;
+        let mut objects = repo.objects.clone();
+        if let Some(toggle) = use_object_replacements {
+            objects.ignore_replacements = !toggle;
+        }
         Ok(revision::Walk {
             repo,
             inner: Box::new(
                 gix_traverse::commit::Ancestors::filtered(
                     tips,
                     gix_traverse::commit::ancestors::State::default(),
-                    &repo.objects,
-                    {
-                        // Note that specific shallow handling for commit-graphs isn't needed as these contain
-                        // all information there is, and exclude shallow parents to be structurally consistent.
-                        let shallow_commits = repo.shallow_commits()?;
-                        let mut grafted_parents_to_skip = Vec::new();
-                        let mut buf = Vec::new();
-                        move |id| {
-                            if !filter(id) {
-                                return false;
-                            }
-                            match shallow_commits.as_ref() {
-                                Some(commits) => {
-                                    let id = id.to_owned();
-                                    if let Ok(idx) = grafted_parents_to_skip.binary_search(&id) {
-                                        grafted_parents_to_skip.remove(idx);
-                                        return false;
-                                    };
-                                    if commits.binary_search(&id).is_ok() {
-                                        if let Ok(commit) = repo.objects.find_commit_iter(&id, &mut buf) {
-                                            grafted_parents_to_skip.extend(commit.parent_ids());
-                                            grafted_parents_to_skip.sort();
-                                        }
-                                    };
-                                    true
-                                }
-                                None => true,
-                            }
-                        }
-                    },
+                    objects,
+                    filter,
                 )
+                .grafts(repo.commit_grafts()?)
                 .sorting(sorting)?
                 .parents(parents)
                 .commit_graph(
@@ -207,7 +223,9 @@ impl<'repo> Platform<'repo> {
                         .map_or_else(|| self.repo.config.may_use_commit_graph(), Ok)?
                         .then(|| self.repo.commit_graph().ok())
                         .flatten()),
-                ),
+                )
+                .since(since)
+                .until(until),
             ),
         })
     }
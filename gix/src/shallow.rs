@@ -78,6 +78,26 @@ pub mod write {
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 pub use write::function::write;
 
+///
+pub mod grafts {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::commit_grafts()`][crate::Repository::commit_grafts()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ShallowCommits(#[from] super::open::Error),
+        #[error("Could not read the grafts file")]
+        Io(#[from] std::io::Error),
+        #[error("Could not decode line {line:?} of the grafts file")]
+        DecodeLine {
+            line: BString,
+            source: gix_hash::decode::Error,
+        },
+    }
+}
+
 ///
 pub mod open {
     /// The error returned by [`Repository::shallow_commits()`][crate::Repository::shallow_commits()].
@@ -0,0 +1,195 @@
+//! Sparse-checkout patterns to learn which files are supposed to be present in the worktree, like
+//! the ones written by `git sparse-checkout set`.
+use std::{collections::BTreeSet, path::Path};
+
+use gix_index::entry::Flags;
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// The error returned by [`Repository::sparse_checkout_patterns()`](crate::Repository::sparse_checkout_patterns()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[error("Could not read the sparse-checkout patterns at '{}'", path.display())]
+    Io {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+}
+
+/// The patterns of `$GIT_DIR/info/sparse-checkout`, which define the files that are supposed to be present in the worktree.
+///
+/// All other files are marked with the [`SKIP_WORKTREE`](Flags::SKIP_WORKTREE) flag in the index, so they are neither
+/// checked out nor reported as deleted, and shouldn't be added to the index either.
+#[derive(Clone)]
+pub struct Patterns {
+    kind: Kind,
+}
+
+#[derive(Clone)]
+enum Kind {
+    /// Patterns restricted to directories, as written by `git sparse-checkout set --cone`.
+    Cone {
+        /// Directories whose files are all included, recursively.
+        recursive: BTreeSet<BString>,
+        /// Directories of which only the files directly within them are included, with the root being an empty path.
+        parents: BTreeSet<BString>,
+    },
+    /// Patterns just like the ones in `.gitignore` files, with matching files being included instead of ignored.
+    NonCone {
+        search: gix_ignore::Search,
+        case: gix_glob::pattern::Case,
+    },
+}
+
+/// The result of [`Patterns::update_skip_worktree()`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Outcome {
+    /// The amount of entries outside of the sparse-checkout patterns which received the `SKIP_WORKTREE` flag.
+    pub skipped: usize,
+    /// The amount of entries that are present in the worktree even though they had the `SKIP_WORKTREE` flag, which was removed.
+    pub unskipped: usize,
+}
+
+impl Outcome {
+    /// Return `true` if the index was changed and should be written.
+    pub fn index_changed(&self) -> bool {
+        self.skipped != 0 || self.unskipped != 0
+    }
+}
+
+/// Initialization
+impl Patterns {
+    /// Parse the sparse-checkout patterns in `bytes`, using _cone mode_ if `cone` is `true`, similar to
+    /// `core.sparseCheckoutCone`, or `.gitignore` style patterns otherwise.
+    ///
+    /// Like `git`, non-cone mode is used if the patterns can't be interpreted in cone mode, and `case` is used for
+    /// matching in non-cone mode.
+    pub fn from_bytes(bytes: &[u8], cone: bool, case: gix_glob::pattern::Case) -> Self {
+        let kind = cone.then(|| parse_cone(bytes)).flatten().unwrap_or_else(|| {
+            let mut search = gix_ignore::Search::default();
+            search.add_patterns_buffer(bytes, "sparse-checkout", None);
+            Kind::NonCone { search, case }
+        });
+        Patterns { kind }
+    }
+}
+
+/// Access
+impl Patterns {
+    /// Return `true` if these patterns are in _cone mode_, which is used if it was configured and if the patterns allowed it.
+    pub fn is_cone_mode(&self) -> bool {
+        matches!(self.kind, Kind::Cone { .. })
+    }
+
+    /// Return `true` if the file or directory at `relative_path` is supposed to be present in the worktree.
+    /// `is_dir` is `true` if `relative_path` is a directory, which is included if it contains included files.
+    ///
+    /// This is useful to avoid adding files to the index that are outside of the sparse-checkout patterns.
+    pub fn is_included(&self, relative_path: &BStr, is_dir: Option<bool>) -> bool {
+        match &self.kind {
+            Kind::Cone { recursive, parents } => {
+                if recursive
+                    .iter()
+                    .any(|dir| dir == relative_path || gix_path::is_within(relative_path, dir.as_ref()))
+                {
+                    return true;
+                }
+                if is_dir == Some(true) {
+                    // Directories that lead to included directories are needed to reach them.
+                    return relative_path.is_empty()
+                        || recursive
+                            .iter()
+                            .chain(parents)
+                            .any(|dir| dir == relative_path || gix_path::is_within(dir.as_ref(), relative_path));
+                }
+                let parent = relative_path
+                    .rfind_byte(b'/')
+                    .map_or("".into(), |pos| relative_path[..pos].as_bstr());
+                parents.contains(parent)
+            }
+            Kind::NonCone { search, case } => {
+                let mut path = relative_path;
+                let mut is_dir = is_dir;
+                loop {
+                    if let Some(m) = search.pattern_matching_relative_path(path, is_dir, *case) {
+                        return !m.pattern.is_negative();
+                    }
+                    match path.rfind_byte(b'/') {
+                        Some(pos) => {
+                            path = path[..pos].as_bstr();
+                            is_dir = Some(true);
+                        }
+                        None => return false,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Index manipulation
+impl Patterns {
+    /// Set the `SKIP_WORKTREE` flag on all entries of `index` that are outside of these patterns if they are missing in `worktree`,
+    /// and remove it from all entries that are present in `worktree`, similar to what `git` does before computing the status
+    /// when `sparse.expectFilesOutsideOfPatterns` is `false`.
+    ///
+    /// That way, files outside of the sparse-checkout aren't reported as deleted, while files that are present nonetheless
+    /// are treated like any other file. Entries with conflicts or of directories in a sparse index are left untouched.
+    pub fn update_skip_worktree(&self, index: &mut gix_index::State, worktree: &Path) -> Outcome {
+        let mut out = Outcome::default();
+        let (entries, path_backing) = index.entries_mut_and_pathbacking();
+        for entry in entries.iter_mut() {
+            if entry.stage() != 0 || entry.mode == gix_index::entry::Mode::DIR {
+                continue;
+            }
+            let path = entry.path_in(path_backing);
+            let is_present =
+                || gix_path::try_from_bstr(path).map_or(false, |path| worktree.join(path).symlink_metadata().is_ok());
+            if entry.flags.contains(Flags::SKIP_WORKTREE) {
+                if is_present() {
+                    entry.set_flags(Flags::SKIP_WORKTREE, false);
+                    out.unskipped += 1;
+                }
+            } else if !self.is_included(path, Some(false)) && !is_present() {
+                entry.set_flags(Flags::SKIP_WORKTREE, true);
+                out.skipped += 1;
+            }
+        }
+        out
+    }
+}
+
+/// Parse cone-mode patterns as written by `git sparse-checkout`, or return `None` if any of them isn't a cone-mode pattern.
+///
+/// Recursively included directories are listed as `/dir/`, while directories of which only files are included are
+/// additionally followed by `!/dir/*/`, with `/*` and `!/*/` doing the same for the root.
+fn parse_cone(bytes: &[u8]) -> Option<Kind> {
+    let mut recursive = BTreeSet::new();
+    let mut parents = BTreeSet::new();
+    for line in bytes.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+        if line == b"/*" {
+            continue;
+        }
+        if let Some(negated) = line.strip_prefix(b"!") {
+            let dir = negated.strip_prefix(b"/")?.strip_suffix(b"*/")?;
+            let dir = dir.strip_suffix(b"/").unwrap_or(dir);
+            parents.insert(BString::from(dir));
+            continue;
+        }
+        let dir = line.strip_prefix(b"/")?.strip_suffix(b"/")?;
+        if dir.is_empty() || dir.contains(&b'*') {
+            return None;
+        }
+        recursive.insert(BString::from(dir));
+    }
+    recursive.retain(|dir| !parents.contains(dir));
+    parents.insert(BString::default());
+    Some(Kind::Cone { recursive, parents })
+}
@@ -104,3 +104,128 @@ pub mod head_id {
         PathConfiguration(#[from] gix_submodule::config::path::Error),
     }
 }
+
+///
+#[cfg(feature = "status")]
+pub mod status {
+    /// The error returned by [Submodule::status()](crate::Submodule::status()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        PathConfiguration(#[from] gix_submodule::config::path::Error),
+        #[error(transparent)]
+        IndexId(#[from] super::index_id::Error),
+        #[error(transparent)]
+        OpenRepository(#[from] super::open::Error),
+        #[error("Could not read the HEAD reference of the submodule")]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error("Could not check the worktree of the submodule for changes")]
+        IsDirty(#[from] crate::repository::is_dirty::Error),
+    }
+}
+
+///
+pub mod init {
+    use crate::bstr::BString;
+
+    /// The error returned by [Submodule::init()](crate::Submodule::init()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Url(#[from] gix_submodule::config::url::Error),
+        #[error("The url of submodule '{name}' is relative, but the url of the superproject's default remote couldn't be obtained")]
+        FindRemote {
+            name: BString,
+            source: crate::remote::find::existing::Error,
+        },
+        #[error("The resolved url '{url}' of submodule '{name}' couldn't be parsed")]
+        ParseUrl {
+            name: BString,
+            url: BString,
+            source: gix_url::parse::Error,
+        },
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error("Could not open the configuration file of the superproject")]
+        OpenConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error("Could not lock the configuration file of the superproject")]
+        LockConfig(#[from] gix_lock::acquire::Error),
+        #[error(transparent)]
+        SetConfigValue(#[from] gix_config::file::set_raw_value::Error),
+        #[error("Could not write the configuration file of the superproject")]
+        WriteConfig(#[from] std::io::Error),
+        #[error("Could not commit the configuration file of the superproject")]
+        CommitConfig(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
+
+///
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+pub mod update_with_checkout {
+    use crate::bstr::BString;
+
+    /// The error returned by [Submodule::update_with_checkout()](crate::Submodule::update_with_checkout()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Submodule '{name}' isn't initialized")]
+        NotInitialized { name: BString },
+        #[error("Submodule '{name}' is configured to be updated with {strategy:?}, but only the checkout strategy is supported")]
+        UnsupportedStrategy {
+            name: BString,
+            strategy: gix_submodule::config::Update,
+        },
+        #[error("Submodules can't be updated in a superproject without worktree")]
+        BareSuperproject,
+        #[error("The commit {id} recorded for submodule '{name}' couldn't be fetched")]
+        CommitNotFound { name: BString, id: gix_hash::ObjectId },
+        #[error(transparent)]
+        Init(#[from] super::init::Error),
+        #[error(transparent)]
+        IsActive(#[from] super::is_active::Error),
+        #[error(transparent)]
+        Url(#[from] gix_submodule::config::url::Error),
+        #[error(transparent)]
+        Update(#[from] gix_submodule::config::update::Error),
+        #[error(transparent)]
+        PathConfiguration(#[from] gix_submodule::config::path::Error),
+        #[error(transparent)]
+        IndexId(#[from] super::index_id::Error),
+        #[error(transparent)]
+        OpenSubmodule(#[from] super::open::Error),
+        #[error(transparent)]
+        OpenRepository(#[from] crate::open::Error),
+        #[error(transparent)]
+        PrepareClone(#[from] crate::clone::Error),
+        #[error(transparent)]
+        Clone(#[from] crate::clone::fetch::Error),
+        #[error("Could not find the default remote of the submodule")]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
+        #[error(transparent)]
+        Fetch(#[from] crate::remote::fetch::Error),
+        #[error("Could not read the HEAD reference of the submodule")]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        Checkout(#[from] crate::worktree::switch::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error("Could not open the configuration file of the submodule")]
+        OpenConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error("Could not lock the configuration file of the submodule")]
+        LockConfig(#[from] gix_lock::acquire::Error),
+        #[error(transparent)]
+        SetConfigValue(#[from] gix_config::file::set_raw_value::Error),
+        #[error(transparent)]
+        Realpath(#[from] gix_path::realpath::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("Could not commit the configuration file of the submodule")]
+        CommitConfig(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
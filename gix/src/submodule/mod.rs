@@ -9,7 +9,11 @@ use std::{
 
 pub use gix_submodule::*;
 
-use crate::{bstr::BStr, repository::IndexPersistedOrInMemory, Repository, Submodule};
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    repository::IndexPersistedOrInMemory,
+    Repository, Submodule,
+};
 
 pub(crate) type ModulesFileStorage = gix_features::threading::OwnShared<gix_fs::SharedFileSnapshotMut<File>>;
 /// A lazily loaded and auto-updated worktree index.
@@ -247,6 +251,38 @@ impl<'repo> Submodule<'repo> {
         })
     }
 
+    /// Query the submodule and its repository to learn how it relates to the superproject, similar to `git submodule status`.
+    ///
+    /// `ignore` controls how much work is done, and is typically obtained from [`Self::ignore()`]:
+    ///
+    /// * [`All`](config::Ignore::All) - the submodule repository isn't opened at all.
+    /// * [`Dirty`](config::Ignore::Dirty) - the `HEAD` commit of the submodule is obtained, but its worktree isn't checked.
+    /// * [`Untracked`](config::Ignore::Untracked) - the worktree of the submodule is checked for modifications of tracked files as well.
+    /// * [`None`](config::Ignore::None) - the worktree of the submodule is also checked for untracked files.
+    ///
+    /// Note that modifications of nested submodules aren't detected yet.
+    #[cfg(feature = "status")]
+    pub fn status(&self, ignore: config::Ignore) -> Result<Status, status::Error> {
+        let mut status = Status {
+            state: self.state()?,
+            index_id: self.index_id()?,
+            checked_out_head_id: None,
+            is_dirty: None,
+        };
+        if ignore == config::Ignore::All {
+            return Ok(status);
+        }
+        let Some(sm_repo) = self.open()? else {
+            return Ok(status);
+        };
+        status.checked_out_head_id = sm_repo.head()?.id().map(crate::Id::detach);
+        if ignore == config::Ignore::Dirty || !status.state.worktree_checkout {
+            return Ok(status);
+        }
+        status.is_dirty = Some(sm_repo.is_dirty(ignore == config::Ignore::None)?);
+        Ok(status)
+    }
+
     /// Open the submodule as repository, or `None` if the submodule wasn't initialized yet.
     ///
     /// More states can be derived here:
@@ -265,6 +301,194 @@ impl<'repo> Submodule<'repo> {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Initialize the submodule by writing its url into the local configuration of the superproject, similar to
+    /// `git submodule init`, and return the written url, or `None` if the submodule was already initialized.
+    ///
+    /// Relative urls like `../other` are resolved against the url of the default remote of the superproject, or against
+    /// its working directory if there is no such remote. Unless `submodule.active` is configured, the submodule is also
+    /// marked active.
+    ///
+    /// Note that the configuration of the superproject in memory isn't changed.
+    pub fn init(&self) -> Result<Option<gix_url::Url>, init::Error> {
+        let repo = self.state.repo;
+        if repo
+            .config
+            .resolved
+            .string("submodule", Some(self.name()), "url")
+            .is_some()
+        {
+            return Ok(None);
+        }
+        let mut url = self.url()?;
+        let relative_url = url.to_bstring();
+        if relative_url.starts_with(b"./") || relative_url.starts_with(b"../") {
+            let remote_url = repo
+                .find_default_remote(crate::remote::Direction::Fetch)
+                .transpose()
+                .map_err(|source| init::Error::FindRemote {
+                    name: self.name().to_owned(),
+                    source,
+                })?
+                .and_then(|remote| {
+                    remote
+                        .url(crate::remote::Direction::Fetch)
+                        .map(gix_url::Url::to_bstring)
+                });
+            let base = remote_url
+                .unwrap_or_else(|| gix_path::into_bstr(repo.work_dir().unwrap_or_else(|| repo.git_dir())).into_owned());
+            let resolved = resolve_relative_url(base.as_ref(), relative_url.as_ref());
+            url = gix_url::Url::from_bytes(resolved.as_ref()).map_err(|source| init::Error::ParseUrl {
+                name: self.name().to_owned(),
+                url: resolved,
+                source,
+            })?;
+        }
+
+        let set_active = repo.config.resolved.string_by_key("submodule.active").is_none();
+        repo.edit_local_config(|config| {
+            config.set_raw_value("submodule", Some(self.name()), "url", url.to_bstring().as_bstr())?;
+            if set_active {
+                config.set_raw_value("submodule", Some(self.name()), "active", "true")?;
+            }
+            Ok::<_, init::Error>(true)
+        })?;
+        Ok(Some(url))
+    }
+
+    /// Bring the submodule up to date with the commit recorded in the index of the superproject, similar to
+    /// `git submodule update [--init]` with the `checkout` strategy.
+    ///
+    /// If `init` is `true`, the submodule is [initialized](Self::init()) first if needed, otherwise uninitialized submodules
+    /// are an error. If the submodule repository doesn't exist yet, it's cloned into [`git_dir()`](Self::git_dir())
+    /// and its worktree is connected to [`work_dir()`](Self::work_dir()). If the recorded commit isn't present in an existing
+    /// repository, it's fetched from the default remote of the submodule. Finally, the recorded commit is checked out
+    /// on a detached `HEAD`, which fails without changing anything if local changes would be overwritten.
+    ///
+    /// Submodules that aren't in the index, that aren't active, or that are configured with `update = none`
+    /// are skipped. The `rebase`, `merge` and custom command strategies aren't supported yet and cause an error,
+    /// just like limiting the depth of the clone isn't possible yet.
+    #[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+    pub fn update_with_checkout<P>(
+        &self,
+        init: bool,
+        mut progress: P,
+        should_interrupt: &std::sync::atomic::AtomicBool,
+    ) -> Result<UpdateOutcome, update_with_checkout::Error>
+    where
+        P: crate::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let repo = self.state.repo;
+        let mut outcome = UpdateOutcome::default();
+        let Some(id) = self.index_id()? else {
+            return Ok(outcome);
+        };
+        let url = match self.init_url(init)? {
+            Some(url) => {
+                outcome.initialized = true;
+                url
+            }
+            None if self.is_active()? => self.url()?,
+            None => return Ok(outcome),
+        };
+        match self.update()? {
+            None | Some(config::Update::Checkout) => {}
+            Some(config::Update::None) => return Ok(outcome),
+            Some(strategy) => {
+                return Err(update_with_checkout::Error::UnsupportedStrategy {
+                    name: self.name().to_owned(),
+                    strategy,
+                })
+            }
+        }
+
+        let work_dir = match repo.work_dir() {
+            Some(superproject_work_dir) => superproject_work_dir.join(gix_path::from_bstr(self.path()?)),
+            None => return Err(update_with_checkout::Error::BareSuperproject),
+        };
+        let mut sm_repo = match self.open()? {
+            Some(sm_repo) => sm_repo,
+            None => {
+                let git_dir = self.git_dir();
+                std::fs::create_dir_all(&git_dir)?;
+                let mut prepare = crate::clone::PrepareFetch::new(
+                    url,
+                    git_dir,
+                    crate::create::Kind::Bare,
+                    Default::default(),
+                    repo.options.clone(),
+                )?;
+                outcome.cloned = true;
+                prepare.fetch_only(&mut progress, should_interrupt)?.0
+            }
+        };
+        let has_worktree = work_dir.join(gix_discover::DOT_GIT_DIR).exists();
+        if !has_worktree {
+            std::fs::create_dir_all(&work_dir)?;
+            let work_dir = gix_path::realpath(&work_dir)?;
+            let git_dir = gix_path::realpath(sm_repo.git_dir())?;
+            sm_repo.edit_local_config(|config| {
+                config.set_raw_value("core", None, "bare", "false")?;
+                config.set_raw_value("core", None, "worktree", gix_path::into_bstr(&work_dir).as_ref())?;
+                Ok::<_, update_with_checkout::Error>(true)
+            })?;
+            crate::worktree::proxy::write_dot_git_file(&work_dir, &git_dir)?;
+            // The worktree is empty, so make sure the checkout doesn't consider its files deleted.
+            match std::fs::remove_file(git_dir.join("index")) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+            sm_repo = crate::open_opts(&work_dir, repo.options.clone())?;
+        }
+
+        if has_worktree && sm_repo.head()?.id().map(crate::Id::detach) == Some(id) {
+            return Ok(outcome);
+        }
+        if !sm_repo.has_object(id) {
+            if let Some(remote) = sm_repo
+                .find_default_remote(crate::remote::Direction::Fetch)
+                .transpose()?
+            {
+                remote
+                    .connect(crate::remote::Direction::Fetch)?
+                    .prepare_fetch(&mut progress, Default::default())?
+                    .receive(&mut progress, should_interrupt)?;
+                outcome.fetched = true;
+            }
+            if !sm_repo.has_object(id) {
+                return Err(update_with_checkout::Error::CommitNotFound {
+                    name: self.name().to_owned(),
+                    id,
+                });
+            }
+        }
+        sm_repo.switch(crate::worktree::add::Head::Detached(id))?;
+        outcome.checked_out = Some(id);
+        Ok(outcome)
+    }
+
+    /// Initialize the submodule if `init` is `true` and return the url it was initialized with, or `None` if it was
+    /// already initialized.
+    #[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+    fn init_url(&self, init: bool) -> Result<Option<gix_url::Url>, update_with_checkout::Error> {
+        let is_initialized = self
+            .state
+            .repo
+            .config
+            .resolved
+            .string("submodule", Some(self.name()), "url")
+            .is_some();
+        if is_initialized {
+            return Ok(None);
+        }
+        if !init {
+            return Err(update_with_checkout::Error::NotInitialized {
+                name: self.name().to_owned(),
+            });
+        }
+        Ok(self.init()?)
+    }
 }
 
 /// A summary of the state of all parts forming a submodule, which allows to answer various questions about it.
@@ -282,3 +506,82 @@ pub struct State {
     /// Note that the presence of a single section is enough, independently of the actual values.
     pub superproject_configuration: bool,
 }
+
+/// The status of a submodule in relation to its superproject, as returned by [`Submodule::status()`].
+#[cfg(feature = "status")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Status {
+    /// The state of all parts forming the submodule.
+    pub state: State,
+    /// The commit recorded for the submodule in the index of the superproject, or `None` if there is none.
+    pub index_id: Option<gix_hash::ObjectId>,
+    /// The commit that `HEAD` of the submodule repository points to, or `None` if the repository isn't initialized,
+    /// `HEAD` is unborn, or if it wasn't checked due to [`config::Ignore::All`].
+    pub checked_out_head_id: Option<gix_hash::ObjectId>,
+    /// `Some(true)` if tracked files in the submodule worktree are modified compared to its index, or if it has untracked
+    /// files unless these are ignored with [`config::Ignore::Untracked`].
+    /// It's `None` if there is no worktree checkout or if it wasn't checked due to [`config::Ignore`].
+    pub is_dirty: Option<bool>,
+}
+
+#[cfg(feature = "status")]
+impl Status {
+    /// Return `true` if the submodule repository exists and its worktree is checked out.
+    pub fn is_initialized(&self) -> bool {
+        self.state.repository_exists && self.state.worktree_checkout
+    }
+
+    /// Return `Some(true)` if the commit checked out in the submodule differs from the one recorded in the index of
+    /// the superproject, or `None` if one of them isn't known.
+    pub fn is_head_changed(&self) -> Option<bool> {
+        Some(self.checked_out_head_id? != self.index_id?)
+    }
+
+    /// Return the prefix `git submodule status` would use for this submodule, i.e. `-` if it's not initialized,
+    /// `+` if the checked out commit differs from the recorded one, and ` ` otherwise.
+    pub fn prefix(&self) -> char {
+        if !self.is_initialized() {
+            '-'
+        } else if self.is_head_changed().unwrap_or(false) {
+            '+'
+        } else {
+            ' '
+        }
+    }
+}
+
+/// What was done by [`Submodule::update_with_checkout()`].
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct UpdateOutcome {
+    /// `true` if the submodule was initialized as part of the update.
+    pub initialized: bool,
+    /// `true` if the submodule repository didn't exist and was cloned.
+    pub cloned: bool,
+    /// `true` if the recorded commit wasn't present in the existing submodule repository and was fetched.
+    pub fetched: bool,
+    /// The commit that was checked out, or `None` if the submodule was skipped or its worktree was already at the recorded commit.
+    pub checked_out: Option<gix_hash::ObjectId>,
+}
+
+/// Resolve `url` relative to `base` like `git` does for submodule urls starting with `./` or `../`, removing one
+/// path component from `base` for each `../`.
+fn resolve_relative_url(base: &BStr, mut url: &BStr) -> BString {
+    let mut base = base.trim_end_with(|c| c == '/').as_bstr().to_owned();
+    loop {
+        if let Some(rest) = url.strip_prefix(b"./") {
+            url = rest.as_bstr();
+        } else if let Some(rest) = url.strip_prefix(b"../") {
+            url = rest.as_bstr();
+            match base.rfind_byteset(b"/:") {
+                Some(pos) => base.truncate(pos),
+                None => base = ".".into(),
+            }
+        } else {
+            break;
+        }
+    }
+    base.push(b'/');
+    base.extend_from_slice(url);
+    base
+}
@@ -163,6 +163,9 @@ pub struct Repository {
     #[cfg(feature = "attributes")]
     pub(crate) modules: crate::submodule::ModulesFileStorage,
     pub(crate) shallow_commits: crate::shallow::CommitsStorage,
+    /// Connections to remotes that are kept alive for reuse, if enabled.
+    #[cfg(feature = "blocking-network-client")]
+    pub(crate) connection_cache: crate::remote::ConnectionCache,
 }
 
 /// An instance with access to everything a git repository entails, best imagined as container implementing `Sync + Send` for _most_
@@ -246,8 +249,8 @@ pub struct PathspecDetached {
     pub stack: Option<gix_worktree::Stack>,
     /// The prepared search to use for checking matches.
     pub search: gix_pathspec::Search,
-    /// A thread-safe version of an ODB.
-    pub odb: gix_odb::HandleArc,
+    /// A thread-safe version of an ODB, along with the objects the repository kept in memory.
+    pub odb: gix_odb::memory::Proxy<gix_odb::HandleArc>,
 }
 
 /// A stand-in for the submodule of a particular name.
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use gix_hash::ObjectId;
+use gix_ref::FullName;
+
+use crate::bstr::BString;
+
+/// The error returned by [`Repository::worktree_add()`](crate::Repository::worktree_add()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The worktree destination at '{}' exists and isn't an empty directory", path.display())]
+    DestinationExists { path: PathBuf },
+    #[error("Branch '{}' is in use by the worktree with '{}'", name.as_bstr(), head.as_bstr())]
+    BranchInUse { name: FullName, head: FullName },
+    #[error(transparent)]
+    CheckedOutBranches(#[from] crate::worktree::checked_out::Error),
+    #[error("Could not create a worktree id from the name of '{}'", path.display())]
+    InvalidName { path: PathBuf },
+    #[error(transparent)]
+    FindBranch(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelBranch(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    CreateBranch(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    OpenWorktree(#[from] crate::open::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// What to check out in a new worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Head {
+    /// Detach `HEAD` at the given commit, similar to `git worktree add --detach`.
+    Detached(ObjectId),
+    /// Check out an existing branch, which must not be checked out in another worktree.
+    Branch(FullName),
+    /// Create a new branch with `name` at `commit` and check it out, similar to `git worktree add -b`.
+    NewBranch {
+        /// The full name of the branch to create, which must not exist yet.
+        name: FullName,
+        /// The commit the branch should point to.
+        commit: ObjectId,
+    },
+}
+
+/// Options for use in [`Repository::worktree_add()`](crate::Repository::worktree_add()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// If `Some(reason)`, lock the new worktree with the given reason, which may be empty, similar to `--lock --reason`.
+    pub lock: Option<BString>,
+    /// If `true`, the default, write all files of the `HEAD` commit to the new worktree and its index.
+    /// Otherwise, the worktree stays empty, similar to `--no-checkout`.
+    pub checkout: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            lock: None,
+            checkout: true,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+/// The error returned by [`Repository::export_tree()`](crate::Repository::export_tree()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Needed {id} to be a tree to export it, got {actual}")]
+    NotATree {
+        id: gix_hash::ObjectId,
+        actual: gix_object::Kind,
+    },
+    #[error("The export destination at '{}' exists and isn't a directory", path.display())]
+    DestinationNotADirectory { path: PathBuf },
+    #[error(transparent)]
+    FindTree(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    Pathspec(#[from] crate::pathspec::init::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Options for use in [`Repository::export_tree()`](crate::Repository::export_tree()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// If `true`, the default, apply filters and end-of-line conversions as configured by git configuration and the
+    /// `.gitattributes` files in the exported tree, just like a checkout would.
+    /// Otherwise, blobs are written exactly as they are stored in the object database.
+    pub filters: bool,
+    /// If `true`, default `false`, overwrite files that already exist in the destination.
+    /// Otherwise, existing files are reported as collisions and are left untouched.
+    pub overwrite_existing: bool,
+    /// If `true`, default `false`, don't abort on the first error and instead collect them in the outcome.
+    pub keep_going: bool,
+    /// If `Some`, the amount of threads to use for writing files, overriding `checkout.workers`.
+    pub thread_limit: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            filters: true,
+            overwrite_existing: false,
+            keep_going: false,
+            thread_limit: None,
+        }
+    }
+}
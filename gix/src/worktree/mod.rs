@@ -85,6 +85,104 @@ pub(crate) fn id(git_dir: &std::path::Path, has_common_dir: bool) -> Option<&BSt
 ///
 pub mod proxy;
 
+///
+#[cfg(feature = "worktree-mutation")]
+pub mod add;
+
+///
+#[cfg(feature = "worktree-mutation")]
+pub mod export;
+
+///
+#[cfg(feature = "worktree-mutation")]
+pub mod switch;
+
+///
+pub mod prune {
+    use crate::bstr::BString;
+
+    /// Why a linked worktree was pruned by [`Repository::worktrees_prune()`](crate::Repository::worktrees_prune()).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Reason {
+        /// The `gitdir` file that points to the worktree checkout doesn't exist.
+        MissingGitDirFile,
+        /// The `gitdir` file points to a checkout that doesn't exist anymore.
+        MissingWorktree,
+    }
+
+    impl Reason {
+        /// Return a description like the one `git worktree prune --verbose` prints.
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                Reason::MissingGitDirFile => "gitdir file does not exist",
+                Reason::MissingWorktree => "gitdir file points to non-existent location",
+            }
+        }
+    }
+
+    /// A linked worktree that was pruned, or would have been pruned.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Pruned {
+        /// The id of the worktree, the name of its private git directory.
+        pub id: BString,
+        /// The reason for the worktree to be pruned.
+        pub reason: Reason,
+    }
+}
+
+///
+pub mod checked_out {
+    /// The error returned by [`Repository::checked_out_branches()`](crate::Repository::checked_out_branches()).
+    pub type Error = crate::reference::iter::worktrees::Error;
+
+    /// How a worktree uses a branch.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum State {
+        /// The branch is checked out, and `HEAD` points to it.
+        Head,
+        /// The branch is being rebased, while `HEAD` is detached.
+        Rebasing,
+        /// The branch was checked out when bisecting started, and will be checked out again once it's done.
+        Bisecting,
+    }
+
+    /// A branch that is in use by a worktree, and that should not be deleted or modified behind its back.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Branch {
+        /// The full name of the branch, like `refs/heads/main`.
+        pub name: gix_ref::FullName,
+        /// The qualified name of the `HEAD` reference of the worktree that uses the branch, like `main-worktree/HEAD`
+        /// or `worktrees/<id>/HEAD`.
+        pub head: gix_ref::FullName,
+        /// How the worktree uses the branch.
+        pub state: State,
+    }
+}
+
+///
+pub mod repair {
+    use std::path::PathBuf;
+
+    /// The kind of repair performed by [`Repository::worktrees_repair()`](crate::Repository::worktrees_repair()).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Kind {
+        /// The `.git` file in the worktree checkout didn't point to the private git directory of the worktree.
+        DotGitFile,
+        /// The `gitdir` file in the private git directory of the worktree didn't point to the worktree checkout,
+        /// typically because it was moved manually.
+        GitDirFile,
+    }
+
+    /// A file that was repaired.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Repaired {
+        /// The path to the file that was rewritten.
+        pub path: PathBuf,
+        /// What was repaired.
+        pub kind: Kind,
+    }
+}
+
 ///
 #[cfg(feature = "index")]
 pub mod open_index {
@@ -24,6 +24,78 @@ pub mod into_repo {
     }
 }
 
+///
+pub mod lock {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Proxy::lock()`][super::Proxy::lock()] and [`Proxy::unlock()`][super::Proxy::unlock()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The worktree is already locked{}", if reason.is_empty() { String::new() } else { format!(", reason: {reason}") })]
+        AlreadyLocked { reason: BString },
+        #[error("The worktree is not locked")]
+        NotLocked,
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+pub mod move_to {
+    use std::path::PathBuf;
+
+    use crate::bstr::BString;
+
+    /// The error returned by [`Proxy::move_to()`][super::Proxy::move_to()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot move a locked worktree{}", if reason.is_empty() { String::new() } else { format!(", lock reason: {reason}") })]
+        Locked { reason: BString },
+        #[error("The destination at '{}' already exists", path.display())]
+        DestinationExists { path: PathBuf },
+        #[error("Worktree at '{}' is inaccessible", .base.display())]
+        MissingWorktree { base: PathBuf },
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+#[cfg(all(feature = "status", feature = "attributes"))]
+pub mod remove {
+    use std::path::PathBuf;
+
+    use crate::bstr::BString;
+
+    /// The error returned by [`Proxy::remove()`][super::Proxy::remove()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot remove a locked worktree{}", if reason.is_empty() { String::new() } else { format!(", lock reason: {reason}") })]
+        Locked { reason: BString },
+        #[error("The worktree at '{}' contains modified or untracked files", base.display())]
+        Modified { base: PathBuf },
+        #[error(transparent)]
+        Open(#[from] crate::open::Error),
+        #[error(transparent)]
+        IsDirty(#[from] crate::repository::is_dirty::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+
+    /// Options for use in [`Proxy::remove()`][super::Proxy::remove()].
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Options {
+        /// If `true`, remove the worktree even if tracked files were modified or if there are untracked files,
+        /// similar to `git worktree remove --force`.
+        pub allow_modifications: bool,
+        /// If `true`, remove the worktree even if it is locked, similar to passing `--force` twice.
+        pub allow_locked: bool,
+    }
+}
+
 impl<'repo> Proxy<'repo> {
     pub(crate) fn new(parent: &'repo Repository, git_dir: impl Into<PathBuf>) -> Self {
         Proxy {
@@ -74,6 +146,77 @@ impl<'repo> Proxy<'repo> {
             .map(|contents| contents.trim().into())
     }
 
+    /// Lock this worktree with an optional `reason` to prevent it from being pruned, moved or removed, similar to `git worktree lock`.
+    pub fn lock(&self, reason: Option<&BStr>) -> Result<(), lock::Error> {
+        if let Some(reason) = self.lock_reason() {
+            return Err(lock::Error::AlreadyLocked { reason });
+        }
+        std::fs::write(
+            self.git_dir.join("locked"),
+            reason.map(|r| r.as_bytes()).unwrap_or_default(),
+        )?;
+        Ok(())
+    }
+
+    /// Unlock this worktree, similar to `git worktree unlock`.
+    pub fn unlock(&self) -> Result<(), lock::Error> {
+        match std::fs::remove_file(self.git_dir.join("locked")) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(lock::Error::NotLocked),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Move the checkout of this worktree to `destination`, or into it if it is an existing directory,
+    /// and return the new location, similar to `git worktree move`.
+    ///
+    /// Locked worktrees can't be moved.
+    pub fn move_to(&self, destination: impl AsRef<Path>) -> Result<PathBuf, move_to::Error> {
+        if let Some(reason) = self.lock_reason() {
+            return Err(move_to::Error::Locked { reason });
+        }
+        let base = self.base()?;
+        if !base.is_dir() {
+            return Err(move_to::Error::MissingWorktree { base });
+        }
+        let mut destination = destination.as_ref().to_owned();
+        if destination.is_dir() {
+            destination.push(base.file_name().expect("worktrees have a name"));
+        }
+        if destination.exists() {
+            return Err(move_to::Error::DestinationExists { path: destination });
+        }
+        std::fs::rename(&base, &destination)?;
+        let destination =
+            gix_path::realpath(&destination).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        write_git_dir_file(&self.git_dir, &destination)?;
+        Ok(destination)
+    }
+
+    /// Delete the checkout and the private git directory of this worktree, similar to `git worktree remove`.
+    ///
+    /// The removal fails if the worktree is locked, if tracked files are modified or if there are untracked files
+    /// that aren't ignored, unless allowed by `options`.
+    #[cfg(all(feature = "status", feature = "attributes"))]
+    pub fn remove(self, options: remove::Options) -> Result<(), remove::Error> {
+        if let Some(reason) = self.lock_reason().filter(|_| !options.allow_locked) {
+            return Err(remove::Error::Locked { reason });
+        }
+        if let Ok(base) = self.base() {
+            if base.is_dir() {
+                if !options.allow_modifications {
+                    let repo = self.clone().into_repo_with_possibly_inaccessible_worktree()?;
+                    if repo.is_dirty(true)? {
+                        return Err(remove::Error::Modified { base });
+                    }
+                }
+                std::fs::remove_dir_all(&base)?;
+            }
+        }
+        std::fs::remove_dir_all(&self.git_dir)?;
+        Ok(())
+    }
+
     /// Transform this proxy into a [`Repository`] while ignoring issues reading `base()` and ignoring that it might not exist.
     ///
     /// Most importantly, the `Repository` might be initialized with a non-existing work tree directory as the checkout
@@ -99,3 +242,18 @@ impl<'repo> Proxy<'repo> {
         Ok(repo.into())
     }
 }
+
+/// Write the `gitdir` file into the private `git_dir` of a worktree to point to the `.git` file of the checkout at `base`.
+pub(crate) fn write_git_dir_file(git_dir: &Path, base: &Path) -> std::io::Result<()> {
+    let mut contents = gix_path::into_bstr(base.join(gix_discover::DOT_GIT_DIR)).into_owned();
+    contents.push(b'\n');
+    std::fs::write(git_dir.join("gitdir"), contents)
+}
+
+/// Write the `.git` file into the checkout at `base` to point to the private `git_dir` of its worktree.
+pub(crate) fn write_dot_git_file(base: &Path, git_dir: &Path) -> std::io::Result<()> {
+    let mut contents = crate::bstr::BString::from("gitdir: ");
+    contents.extend_from_slice(&gix_path::into_bstr(git_dir));
+    contents.push(b'\n');
+    std::fs::write(base.join(gix_discover::DOT_GIT_DIR), contents)
+}
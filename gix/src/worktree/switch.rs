@@ -0,0 +1,66 @@
+use gix_ref::FullName;
+
+use crate::bstr::BString;
+
+/// The error returned by [`Repository::switch()`](crate::Repository::switch()),
+/// [`Repository::checkout_tree()`](crate::Repository::checkout_tree()) and
+/// [`Repository::reset_keep()`](crate::Repository::reset_keep()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot update the worktree of a bare repository")]
+    BareRepository,
+    #[error("Local changes of {} would be overwritten", conflicts.iter().map(|c| c.path.to_string()).collect::<Vec<_>>().join(", "))]
+    Conflicts {
+        conflicts: Vec<gix_index::merge::two_way::Conflict>,
+    },
+    #[error("Branch '{}' is in use by the worktree with '{}'", name.as_bstr(), head.as_bstr())]
+    BranchInUse { name: FullName, head: FullName },
+    #[error(transparent)]
+    CheckedOutBranches(#[from] crate::worktree::checked_out::Error),
+    #[error("Could not hash '{rela_path}' in the worktree")]
+    ConvertToGit {
+        rela_path: BString,
+        source: crate::filter::pipeline::convert_to_git::Error,
+    },
+    #[error(transparent)]
+    FindBranch(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelBranch(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    HeadCommit(#[from] crate::reference::head_commit::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    CommitTree(#[from] crate::object::commit::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+    #[error(transparent)]
+    ExcludeStack(#[from] crate::config::exclude_stack::Error),
+    #[error(transparent)]
+    StatOptions(#[from] crate::config::stat_options::Error),
+    #[error(transparent)]
+    FilesystemOptions(#[from] crate::config::boolean::Error),
+    #[error("Could not merge the trees into the index")]
+    Merge(#[from] gix_index::merge::two_way::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -488,9 +488,19 @@ mod blocking_io {
             Default::default(),
             restricted(),
         )?;
-        let (mut checkout, _out) =
+        let (mut checkout, out) =
             prepare.fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
-        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert!(
+            out.performance.pack_bytes_received > 0,
+            "the amount of received bytes is tracked"
+        );
+        assert!(checkout.performance().is_none(), "nothing was checked out yet");
+        let (repo, outcome) =
+            checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let performance = checkout.performance().expect("checkout was performed");
+        assert_eq!(performance.files_updated, outcome.files_updated);
+        assert_eq!(performance.bytes_written, outcome.bytes_written);
 
         let index = repo.index()?;
         assert_eq!(index.entries().len(), 1, "All entries are known as per HEAD tree");
@@ -503,6 +513,159 @@ mod blocking_io {
         Ok(())
     }
 
+    #[test]
+    fn fetch_and_checkout_interrupted_then_resumed() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_dir = gix_testtools::scripted_fixture_read_only("make_partial_clone_remote.sh")?;
+        let (mut checkout, _out) = gix::clone::PrepareFetch::new(
+            remote_dir,
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert!(!checkout.is_resumable(), "nothing was attempted yet");
+
+        let err = checkout
+            .main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::new(true))
+            .unwrap_err();
+        assert!(
+            matches!(err, gix::clone::checkout::main_worktree::Error::Interrupted),
+            "{err:?}"
+        );
+        assert!(checkout.is_resumable(), "the progress was recorded");
+        assert!(checkout.performance().is_none(), "the checkout didn't complete");
+
+        let (repo, outcome) =
+            checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert_eq!(outcome.files_updated, 3, "all missing files were written");
+        let index = repo.index()?;
+        let work_dir = repo.work_dir().expect("non-bare");
+        for entry in index.entries() {
+            let entry_path = work_dir.join(gix_path::from_bstr(entry.path(&index)));
+            assert!(entry_path.is_file(), "{entry_path:?} not found on disk");
+            assert_ne!(entry.stat, Default::default(), "stat information is recorded");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_interrupted_checkout_is_kept_without_cleanup_on_drop() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (checkout, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let mut checkout = checkout.with_cleanup_on_drop(false);
+        assert!(checkout
+            .main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::new(true))
+            .is_err());
+        drop(checkout);
+
+        let repo = gix::open_opts(tmp.path(), restricted())?;
+        assert!(repo.head_id().is_ok(), "the fetched repository is still present");
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_checkout_with_callbacks() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (checkout, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .after_fetch(|repo, outcome| {
+            assert!(
+                matches!(outcome.status, gix::remote::fetch::Status::Change { .. }),
+                "the fetch is complete"
+            );
+            let mut config = repo.config_snapshot_mut();
+            config.set_raw_value("user", None, "name", "from-callback")?;
+            config.commit()?;
+            Ok(())
+        })
+        .fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert_eq!(
+            checkout
+                .repo()
+                .config_snapshot()
+                .string("user.name")
+                .expect("set")
+                .as_ref(),
+            "from-callback",
+            "changes by the callback are visible"
+        );
+
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::default());
+        let mut checkout = checkout
+            .before_checkout({
+                let calls = calls.clone();
+                move |repo| {
+                    assert!(
+                        repo.index_or_empty()?.entries().is_empty(),
+                        "nothing was checked out yet"
+                    );
+                    calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    Ok(gix::clone::checkout::main_worktree::Action::Checkout)
+                }
+            })
+            .after_checkout({
+                let calls = calls.clone();
+                move |repo, outcome| {
+                    assert_eq!(outcome.files_updated, 1);
+                    assert_eq!(repo.index()?.entries().len(), 1, "the index was written");
+                    calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    Ok(())
+                }
+            });
+        checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2, "both callbacks ran");
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_skip_checkout_in_callback() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (checkout, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let mut checkout = checkout
+            .before_checkout(|_repo| Ok(gix::clone::checkout::main_worktree::Action::Skip))
+            .after_checkout(|_repo, _outcome| unreachable!("there is no checkout"));
+        let (repo, outcome) =
+            checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert_eq!(outcome.files_updated, 0);
+        assert!(!repo.index_path().exists(), "no index was written");
+        assert!(checkout.performance().is_none());
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .after_fetch(|_repo, _outcome| Err("refused".into()))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+        .unwrap_err();
+        assert!(matches!(err, gix::clone::fetch::Error::AfterFetch(_)), "{err:?}");
+        Ok(())
+    }
+
     #[test]
     fn fetch_and_checkout_empty_remote_repo() -> crate::Result {
         for version in [
@@ -554,6 +717,25 @@ mod blocking_io {
         Ok(())
     }
 
+    #[test]
+    fn fetch_empty_remote_repo_without_unborn_support_uses_configured_default_branch() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            gix_testtools::scripted_fixture_read_only("make_empty_repo.sh")?,
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted().config_overrides(["protocol.version=0", "init.defaultBranch=trunk"]),
+        )?
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert_eq!(
+            repo.head_name()?.expect("unborn").as_bstr(),
+            "refs/heads/trunk",
+            "without information from the server, the configured default is used"
+        );
+        Ok(())
+    }
+
     #[test]
     fn fetch_only_without_configuration() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -577,6 +759,633 @@ mod blocking_io {
         }
         Ok(())
     }
+
+    #[test]
+    fn fetch_only_in_bare_mode() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_mode(gix::clone::Mode::Bare)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let remote = repo.find_remote("origin")?;
+        assert_eq!(
+            remote.refspecs(Direction::Fetch).len(),
+            0,
+            "like git, bare clones don't persist their refspec"
+        );
+        assert_eq!(repo.config_snapshot().boolean("remote.origin.mirror"), None);
+        let names = ref_names(&repo)?;
+        assert!(
+            names.iter().all(|name| !name.starts_with("refs/remotes/")),
+            "remote branches are mapped to local branches: {names:?}"
+        );
+        assert!(names.contains(&"refs/heads/a".to_string()));
+        assert!(names.contains(&"refs/tags/b-tag".to_string()));
+
+        let head = repo.head()?;
+        assert_eq!(
+            head.referent_name().expect("symbolic").as_bstr(),
+            "refs/heads/main",
+            "HEAD points to the local branch that the remote HEAD points to"
+        );
+        assert!(
+            repo.branch_remote_name("main").is_none(),
+            "bare clones don't configure branches to track the remote"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_in_mirror_mode() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_mode(gix::clone::Mode::Mirror)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let remote = repo.find_remote("origin")?;
+        assert_eq!(
+            remote
+                .refspecs(Direction::Fetch)
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring())
+                .collect::<Vec<_>>(),
+            ["+refs/*:refs/*"]
+        );
+        assert_eq!(repo.config_snapshot().boolean("remote.origin.mirror"), Some(true));
+        assert_eq!(
+            ref_names(&repo)?,
+            remote::repo("base")
+                .references()?
+                .all()?
+                .map(|r| r.map(|r| r.name().as_bstr().to_string()))
+                .collect::<Result<Vec<_>, _>>()?,
+            "all references are mirrored as is"
+        );
+        assert_eq!(
+            repo.head()?.referent_name().expect("symbolic").as_bstr(),
+            "refs/heads/main"
+        );
+        assert!(repo.branch_remote_name("main").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn non_default_modes_require_a_bare_repository() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .with_mode(gix::clone::Mode::Mirror)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            gix::clone::fetch::Error::ModeRequiresBareRepository {
+                mode: gix::clone::Mode::Mirror
+            }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_local_objects() -> crate::Result {
+        for local_objects in [gix::clone::LocalObjects::HardLink, gix::clone::LocalObjects::Copy] {
+            let tmp = gix_testtools::tempfile::TempDir::new()?;
+            let (repo, out) = gix::clone::PrepareFetch::new(
+                remote::repo("base").path(),
+                tmp.path(),
+                gix::create::Kind::Bare,
+                Default::default(),
+                restricted(),
+            )?
+            .with_local_objects(local_objects)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+            assert!(
+                matches!(out.status, gix::remote::fetch::Status::NoPackReceived { .. }),
+                "{local_objects:?}: all objects are present locally, so no pack is needed"
+            );
+            assert!(
+                repo.objects.store_ref().alternate_db_paths()?.is_empty(),
+                "the remote has no alternates, so we don't have them either"
+            );
+            let head = repo.head_commit()?;
+            assert_eq!(
+                head.id,
+                remote::repo("base").head_id()?,
+                "{local_objects:?}: references are set up as usual"
+            );
+            assert!(
+                repo.references()?.remote_branches()?.count() > 1,
+                "{local_objects:?}: remote branches are present"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_local_objects_skips_temporary_and_lock_files() -> crate::Result {
+        let remote_dir = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let remote_repo = gix::open_opts(remote_dir.path().join("base"), restricted())?;
+        let remote_objects = remote_repo.objects.store_ref().path();
+        let skipped = ["tmp_obj_123", "pack/tmp_pack_123", "info/packs.lock"];
+        for path in skipped {
+            std::fs::write(remote_objects.join(path), b"incomplete")?;
+        }
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote_repo.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_local_objects(gix::clone::LocalObjects::Copy)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        for path in skipped {
+            assert!(
+                !repo.objects.store_ref().path().join(path).exists(),
+                "{path}: temporary and lock files aren't copied"
+            );
+        }
+        assert_eq!(repo.head_commit()?.id, remote_repo.head_id()?);
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_reference_repository() -> crate::Result {
+        for dissociate in [false, true] {
+            let tmp = gix_testtools::tempfile::TempDir::new()?;
+            let reference = remote::repo("base");
+            let (repo, out) = gix::clone::PrepareFetch::new(
+                reference.path(),
+                tmp.path(),
+                gix::create::Kind::Bare,
+                Default::default(),
+                restricted(),
+            )?
+            .with_reference_repository(reference.path())
+            .with_dissociate(dissociate)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+            assert!(
+                matches!(out.status, gix::remote::fetch::Status::NoPackReceived { .. }),
+                "all objects are borrowed from the reference repository"
+            );
+            let alternates = repo.objects.store_ref().path().join("info").join("alternates");
+            assert_eq!(alternates.is_file(), !dissociate, "dissociation removes alternates");
+            if !dissociate {
+                let expected = gix::path::realpath(reference.objects.store_ref().path())?;
+                assert_eq!(
+                    std::fs::read(&alternates)?,
+                    format!("{}\n", expected.display()).into_bytes(),
+                    "alternates are always absolute and canonicalized"
+                );
+            }
+
+            let repo = gix::open_opts(repo.path(), restricted())?;
+            assert_eq!(
+                repo.objects.store_ref().alternate_db_paths()?.len(),
+                usize::from(!dissociate)
+            );
+            assert_eq!(
+                repo.head_commit()?.id,
+                reference.head_id()?,
+                "objects are available either way"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_single_branch_of_remote_head() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_single_branch(Some(gix::clone::SingleBranch::RemoteHead))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let remote = repo.find_remote("origin")?;
+        assert_eq!(
+            remote
+                .refspecs(Direction::Fetch)
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring())
+                .collect::<Vec<_>>(),
+            ["+refs/heads/main:refs/remotes/origin/main"],
+            "future fetches are restricted to the branch as well"
+        );
+        let names = ref_names(&repo)?;
+        assert_eq!(
+            names
+                .iter()
+                .filter(|name| name.starts_with("refs/remotes/") || name.starts_with("refs/heads/"))
+                .collect::<Vec<_>>(),
+            [
+                "refs/heads/main",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/main"
+            ]
+        );
+        assert_eq!(
+            repo.branch_remote_ref("main").expect("present")?.as_bstr(),
+            "refs/heads/main",
+            "the branch is tracked as usual"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_single_named_branch() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let base = remote::repo("base");
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            base.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_single_branch(Some(gix::clone::SingleBranch::Named("refs/heads/a".into())))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let head = repo.head()?;
+        assert_eq!(
+            head.referent_name().expect("symbolic").as_bstr(),
+            "refs/heads/a",
+            "HEAD points to the chosen branch, not to what the remote HEAD points to"
+        );
+        assert_eq!(head.id().expect("born"), base.find_reference("a")?.id());
+        assert!(
+            repo.try_find_reference("refs/remotes/origin/main")?.is_none(),
+            "other branches aren't fetched"
+        );
+        assert!(repo.try_find_reference("refs/remotes/origin/a")?.is_some());
+        assert!(
+            repo.try_find_reference("refs/remotes/origin/HEAD")?.is_none(),
+            "the remote HEAD isn't needed and points to a branch that wasn't fetched"
+        );
+        assert_eq!(repo.branch_remote_ref("a").expect("present")?.as_bstr(), "refs/heads/a");
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::clone::PrepareFetch::new(
+            base.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_single_branch(Some(gix::clone::SingleBranch::Named("does-not-exist".into())))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+        .unwrap_err();
+        assert!(
+            matches!(err, gix::clone::fetch::Error::RemoteBranchNotFound { ref name } if name == "refs/heads/does-not-exist"),
+            "{err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_ref_name() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let base = remote::repo("base");
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            base.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_ref_name(Some("a"))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let head = repo.head()?;
+        assert_eq!(
+            head.referent_name().expect("symbolic").as_bstr(),
+            "refs/heads/a",
+            "HEAD points to the chosen branch"
+        );
+        assert!(
+            repo.try_find_reference("refs/remotes/origin/main")?.is_some(),
+            "all branches are still fetched"
+        );
+        assert!(repo.try_find_reference("refs/remotes/origin/a")?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_filter() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_dir = gix_testtools::scripted_fixture_read_only("make_partial_clone_remote.sh")?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote_dir,
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_filter(Some("blob:none"))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        for repo in [repo.clone(), gix::open_opts(repo.git_dir(), restricted())?] {
+            let config = repo.config_snapshot();
+            assert_eq!(config.boolean("remote.origin.promisor"), Some(true));
+            assert_eq!(
+                config.string("remote.origin.partialCloneFilter").expect("set").as_ref(),
+                "blob:none"
+            );
+            assert_eq!(
+                config.string("extensions.partialClone").expect("set").as_ref(),
+                "origin"
+            );
+            assert_eq!(config.integer("core.repositoryFormatVersion"), Some(1));
+        }
+
+        let tree = repo.head_commit()?.tree()?;
+        let entry = tree.find_entry("root-file").expect("present");
+        assert!(!repo.has_object(entry.oid()), "blobs were omitted by the remote");
+        assert!(repo.has_object(tree.id), "trees are still received");
+
+        let out = repo.fetch_missing_objects(
+            Some(entry.oid().to_owned()),
+            gix::progress::Discard,
+            &std::sync::atomic::AtomicBool::default(),
+        )?;
+        assert_eq!(
+            out.remotes,
+            [("origin".into(), 1)],
+            "the promisor remote provided the blob"
+        );
+        assert!(repo.has_object(entry.oid()), "the blob is now present");
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_filter(Some("blob:none"))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                gix::clone::fetch::Error::Fetch(gix::remote::fetch::Error::MissingServerFeature {
+                    feature: "filter",
+                    ..
+                })
+            ),
+            "servers that don't allow filters can't be used for partial clones: {err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_missing_objects_from_multiple_promisor_remotes() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_dir = gix_testtools::scripted_fixture_read_only("make_partial_clone_remote.sh")?;
+        let (mut repo, _out) = gix::clone::PrepareFetch::new(
+            remote_dir,
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_filter(Some("blob:none"))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let other = remote::repo("base");
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value(
+            "remote",
+            Some("other".into()),
+            "url",
+            other.path().to_str().expect("valid UTF-8"),
+        )?;
+        config.set_raw_value("remote", Some("other".into()), "promisor", "true")?;
+        drop(config);
+        assert_eq!(repo.promisor_remote_names()?, ["other", "origin"]);
+
+        let missing: Vec<_> = repo
+            .head_commit()?
+            .tree()?
+            .traverse()
+            .breadthfirst
+            .files()?
+            .into_iter()
+            .filter(|entry| entry.mode.is_blob())
+            .map(|entry| entry.oid)
+            .collect();
+        assert_eq!(missing.len(), 3);
+        assert!(missing.iter().all(|id| !repo.has_object(id)));
+
+        let out = repo.fetch_missing_objects(
+            missing.iter().copied(),
+            gix::progress::Discard,
+            &std::sync::atomic::AtomicBool::default(),
+        )?;
+        assert_eq!(
+            out.remotes,
+            [("origin".into(), 3)],
+            "the first remote doesn't have the objects, so the next one is asked"
+        );
+        assert!(missing.iter().all(|id| repo.has_object(id)));
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_after_partial_clone_inherits_filter() -> crate::Result {
+        let remote_dir = gix_testtools::scripted_fixture_writable("make_partial_clone_remote.sh")?;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote_dir.path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .with_filter(Some("blob:none"))
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        std::fs::write(remote_dir.path().join("new-file"), "new")?;
+        assert!(gix_testtools::run_git(remote_dir.path(), &["add", "new-file"])?.success());
+        assert!(gix_testtools::run_git(
+            remote_dir.path(),
+            &[
+                "-c",
+                "user.name=author",
+                "-c",
+                "user.email=author@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "c2"
+            ]
+        )?
+        .success());
+
+        repo.find_remote("origin")?
+            .connect(Direction::Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let repo = gix::open_opts(repo.git_dir(), restricted())?;
+        let commit = repo.find_reference("refs/remotes/origin/main")?.peel_to_id_in_place()?;
+        assert_eq!(
+            commit,
+            gix::open_opts(remote_dir.path(), restricted())?.head_id()?,
+            "the new commit was fetched"
+        );
+        let tree = commit.object()?.peel_to_tree()?;
+        let entry = tree.find_entry("new-file").expect("present");
+        assert!(
+            !repo.has_object(entry.oid()),
+            "the filter used when cloning is used for subsequent fetches as well"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_checkout_with_filter() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_dir = gix_testtools::scripted_fixture_read_only("make_partial_clone_remote.sh")?;
+        let (mut checkout, _out) = gix::clone::PrepareFetch::new(
+            remote_dir,
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .with_filter(Some("blob:none"))
+        .fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let (repo, _outcome) =
+            checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let index = repo.index()?;
+        assert_eq!(index.entries().len(), 3);
+        let work_dir = repo.work_dir().expect("non-bare");
+        for entry in index.entries() {
+            let entry_path = work_dir.join(gix_path::from_bstr(entry.path(&index)));
+            assert!(entry_path.is_file(), "omitted blobs are fetched before the checkout");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_checkout_sparse() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_dir = gix_testtools::scripted_fixture_read_only("make_partial_clone_remote.sh")?;
+        let (mut checkout, _out) = gix::clone::PrepareFetch::new(
+            remote_dir,
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .with_sparse_checkout(true)
+        .fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let (repo, _outcome) =
+            checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        assert_eq!(repo.config_snapshot().boolean("core.sparseCheckout"), Some(true));
+        assert_eq!(repo.config_snapshot().boolean("core.sparseCheckoutCone"), Some(true));
+
+        let work_dir = repo.work_dir().expect("non-bare");
+        assert!(work_dir.join("root-file").is_file());
+        assert!(
+            !work_dir.join("dir").exists(),
+            "directories aren't included in the cone"
+        );
+
+        let index = repo.index()?;
+        assert_eq!(index.entries().len(), 3, "the index still knows all files");
+        for entry in index.entries() {
+            assert_eq!(
+                entry.flags.contains(gix::index::entry::Flags::SKIP_WORKTREE),
+                entry.path(&index) != "root-file",
+                "everything outside of the sparse-checkout is marked accordingly"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "worktree-mutation")]
+    fn fetch_and_checkout_with_submodules() -> crate::Result {
+        let remote_dir = gix_testtools::scripted_fixture_writable_with_args(
+            "make_submodules_to_update.sh",
+            None::<String>,
+            gix_testtools::Creation::ExecuteScript,
+        )?;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (checkout, _out) = gix::clone::PrepareFetch::new(
+            remote_dir.path().join("super-nested"),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        let (repo, _outcome) = checkout
+            .with_submodules(true)
+            .main_worktree(gix::progress::Discard, &AtomicBool::default())?;
+
+        let work_dir = repo.work_dir().expect("non-bare");
+        assert!(
+            work_dir.join("n/inner/this").is_file(),
+            "submodules are checked out recursively"
+        );
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+        let sm_repo = sm.open()?.expect("cloned");
+        assert_eq!(sm_repo.head_id()?, sm.index_id()?.expect("present"));
+        let inner = sm_repo
+            .submodules()?
+            .into_iter()
+            .flatten()
+            .next()
+            .expect("one nested submodule");
+        assert_eq!(
+            inner.open()?.expect("cloned").head_id()?,
+            inner.index_id()?.expect("present")
+        );
+        assert!(
+            !sm_repo.is_dirty(true)?,
+            "the nested submodule is checked out as recorded"
+        );
+        Ok(())
+    }
+
+    fn ref_names(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for reference in repo.references()?.all()? {
+            names.push(reference?.name().as_bstr().to_string());
+        }
+        Ok(names)
+    }
 }
 
 #[test]
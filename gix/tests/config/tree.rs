@@ -209,6 +209,30 @@ mod fetch {
         );
         Ok(())
     }
+
+    #[test]
+    fn unpack_limit() -> crate::Result {
+        use gix::config::tree::{Fetch, Key, Transfer};
+
+        assert_eq!(Fetch::UNPACK_LIMIT.try_into_unpack_limit(Ok(100))?, Some(100));
+        assert_eq!(Fetch::UNPACK_LIMIT.try_into_unpack_limit(Ok(0))?, Some(0));
+        assert_eq!(
+            Fetch::UNPACK_LIMIT.try_into_unpack_limit(Ok(-1))?,
+            None,
+            "negative values are the same as not setting the key"
+        );
+        assert_eq!(
+            Transfer::UNPACK_LIMIT
+                .try_into_unpack_limit(Ok(i64::from(u32::MAX) + 1))
+                .unwrap_err()
+                .to_string(),
+            "The value of key \"transfer.unpackLimit\" could not be parsed as unsigned integer"
+        );
+        assert!(Fetch::UNPACK_LIMIT.validate("1k".into()).is_ok());
+        assert!(Transfer::UNPACK_LIMIT.validate("-1".into()).is_ok());
+        assert!(Fetch::UNPACK_LIMIT.validate("foo".into()).is_err());
+        Ok(())
+    }
 }
 
 #[cfg(feature = "blob-diff")]
@@ -668,6 +692,30 @@ mod pack {
         assert!(Pack::INDEX_VERSION.validate("-1".into()).is_err());
         Ok(())
     }
+
+    #[test]
+    fn compression() -> crate::Result {
+        assert_eq!(
+            Pack::COMPRESSION.try_into_compression_level(Ok(-1))?,
+            6,
+            "zlib's default"
+        );
+        assert!(Pack::COMPRESSION.validate("-1".into()).is_ok());
+        assert_eq!(Pack::COMPRESSION.try_into_compression_level(Ok(0))?, 0);
+        assert_eq!(Pack::COMPRESSION.try_into_compression_level(Ok(9))?, 9);
+        assert!(Pack::COMPRESSION.validate("9".into()).is_ok());
+        assert_eq!(
+            Pack::COMPRESSION
+                .try_into_compression_level(Ok(10))
+                .unwrap_err()
+                .to_string(),
+            "The value of key \"pack.compression\" was invalid"
+        );
+        assert!(Pack::COMPRESSION.validate("10".into()).is_err());
+        assert!(Pack::COMPRESSION.validate("-2".into()).is_err());
+        assert!(Pack::COMPRESSION.validate("foo".into()).is_err());
+        Ok(())
+    }
 }
 
 mod protocol {
@@ -758,6 +806,25 @@ mod gitoxide {
             );
             Ok(())
         }
+
+        #[test]
+        fn max_retries() -> crate::Result {
+            assert_eq!(
+                gitoxide::Http::MAX_RETRIES.validated_assignment_fmt(&5)?,
+                "gitoxide.http.maxRetries=5"
+            );
+            assert!(gitoxide::Http::MAX_RETRIES.validate("-1".into()).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn retry_delay() -> crate::Result {
+            assert_eq!(
+                gitoxide::Http::RETRY_DELAY.validated_assignment_fmt(&Duration::from_millis(250).as_millis())?,
+                "gitoxide.http.retryDelay=250"
+            );
+            Ok(())
+        }
     }
     mod allow {
         use gix::config::tree::{gitoxide, Key};
@@ -0,0 +1,33 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function commit_as() {
+  local name=${1:?name} email=${2:?email} date=${3:?date} file=${4:?file}
+  echo "$name $date" >> "$file"
+  git add "$file"
+  GIT_AUTHOR_NAME="$name" GIT_AUTHOR_EMAIL="$email" GIT_AUTHOR_DATE="$date" \
+    git commit -q -m "$file by $name"
+}
+
+git init -q
+git checkout -q -b main
+
+cat <<EOF > .mailmap
+Jane Doe <jane@example.com> <jane@laptop.(none)>
+EOF
+git add .mailmap
+GIT_AUTHOR_NAME="Joe" GIT_AUTHOR_EMAIL="joe@example.com" GIT_AUTHOR_DATE="2000-01-01 10:00:00 +0000" \
+  git commit -q -m "add mailmap"
+
+commit_as Jane jane@laptop.\(none\) "2000-01-01 12:00:00 +0000" a
+commit_as "Jane Doe" JANE@example.com "2000-01-01 23:30:00 -0200" a
+git branch base
+
+git checkout -q -b feature
+commit_as Joe joe@example.com "2000-01-03 09:00:00 +0000" b
+commit_as Joe joe@example.com "2000-01-04 09:00:00 +0000" b
+
+git checkout -q main
+commit_as "Jane Doe" jane@example.com "2000-01-05 09:00:00 +0000" c
+GIT_AUTHOR_NAME="Joe" GIT_AUTHOR_EMAIL="joe@example.com" GIT_AUTHOR_DATE="2000-01-06 09:00:00 +0000" \
+  git merge -q --no-ff -m "merge feature" feature
@@ -0,0 +1,22 @@
+#!/bin/bash
+set -eu -o pipefail
+
+mkdir repo
+(cd repo
+  git init -q
+  git checkout -q -b main
+  git commit -q --allow-empty -m c1
+  git commit -q --allow-empty -m c2
+
+  git branch merged HEAD~1
+  git config branch.merged.description "merged into HEAD"
+
+  git checkout -q -b unmerged HEAD~1
+  git commit -q --allow-empty -m c3
+  git branch upstream
+  git branch --track tracking upstream
+  git checkout -q main
+
+  git branch in-worktree
+  git worktree add -q ../wt in-worktree
+)
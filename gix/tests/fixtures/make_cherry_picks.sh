@@ -0,0 +1,28 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+seq 10 > numbers
+git add numbers
+git commit -q -m base
+
+git checkout -q -b left
+sed -i 's/^2$/two/' numbers
+git commit -q -am "spell out two"
+echo left > left
+git add left
+git commit -q -m "add left"
+echo "  indented" >> numbers
+git commit -q -am "indent"
+
+git checkout -q -b right main
+sed -i 's/^9$/nine/' numbers
+git commit -q -am "spell out nine"
+git cherry-pick left~2 >/dev/null
+echo right > right
+git add right
+git commit -q -m "add right"
+echo "indented	" >> numbers
+git commit -q -am "indent differently"
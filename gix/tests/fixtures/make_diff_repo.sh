@@ -19,6 +19,7 @@ cat <<EOF >>.git/config
   textconv = textconv
   algorithm = histogram
   binary = auto
+  wordRegex = [a-z]+|[^[:space:]]
 EOF
 
 git checkout -b main
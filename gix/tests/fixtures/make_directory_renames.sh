@@ -0,0 +1,17 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+mkdir -p src/sub keep
+seq 10 > src/a
+seq 20 > src/b
+seq 30 > src/sub/c
+seq 40 > keep/k1
+seq 50 > keep/k2
+git add . && git commit -q -m "c1"
+
+git mv src renamed
+echo changed >> renamed/b
+mkdir elsewhere && git mv keep/k1 elsewhere/k1
+git commit -q -m "c2 - rename directory"
@@ -0,0 +1,33 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q work
+(cd work
+  git checkout -q -b main
+  mkdir dir
+  echo a > a && echo b > dir/b
+  git add . && git commit -q -m c1
+  git tag -a -m "first release" v1.0
+  echo c > dir/c
+  git add . && git commit -q -m c2
+)
+
+git init -q --bare server.git
+git -C server.git symbolic-ref HEAD refs/heads/main
+git -C work push -q ../server.git main v1.0
+git -C server.git repack -qad
+
+(cd work
+  echo d > dir/d
+  git add . && git commit -q -m c3
+  git tag -a -m "second release" v2.0
+  git checkout -q -b other
+  echo e > e
+  git add . && git commit -q -m c4
+)
+# few objects are pushed as loose objects
+git -C work push -q ../server.git main other v2.0
+git -C server.git update-server-info
+
+git init -q local
+git -C local remote add origin ../server.git
@@ -0,0 +1,11 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+mkdir -p dir/sub
+echo a > a
+echo b > dir/b
+echo text > dir/sub/text.txt
+echo "*.txt text eol=crlf" > .gitattributes
+git add . && git commit -q -m c1
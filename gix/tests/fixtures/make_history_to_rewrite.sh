@@ -0,0 +1,21 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+git checkout -q -b main
+echo a > a
+mkdir dir
+echo secret > dir/secret
+git add . && git commit -q -m c1
+
+head -c 2000 /dev/zero > big
+git add big && git commit -q -m "add big file"
+
+echo b >> a
+git commit -q -am c3
+git tag -a -m "annotated" v1
+git branch other
+
+echo more >> dir/secret
+git commit -q -am "change secret only"
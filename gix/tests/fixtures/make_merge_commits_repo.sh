@@ -0,0 +1,60 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+seq 1 9 > numbers
+echo unchanged > file
+git add . && git commit -q -m base
+git tag base
+
+git checkout -q -b clean
+echo new > new
+git add new && git commit -q -m "add a file"
+
+git checkout -q -b conflicting main
+sed -i 's/^5$/five by conflicting/' numbers
+git commit -q -am "change the middle line"
+
+git checkout -q main
+sed -i 's/^1$/one/; s/^5$/five by main/' numbers
+git commit -q -am "change the first and the middle line"
+
+git checkout -q --orphan unrelated
+git rm -q -r --cached .
+git clean -q -f
+echo unrelated > unrelated
+git add unrelated && git commit -q -m "unrelated history"
+git checkout -q main
+
+git checkout -q -b clean-too base
+echo another > another
+git add another && git commit -q -m "add another file"
+
+git checkout -q --orphan subproject
+git rm -q -r --cached .
+git clean -q -f
+seq 1 5 > lib
+git add lib && git commit -q -m "subproject"
+
+git checkout -q -b with-subproject main
+git merge -q -s ours --no-commit --allow-unrelated-histories subproject &>/dev/null
+git read-tree --prefix=sub/ -u subproject
+git commit -q -m "add subproject in sub/"
+
+git checkout -q subproject
+sed -i 's/^1$/one/' lib
+git commit -q -am "change subproject"
+git checkout -q main
+
+git checkout -q -b file-to-dir main
+git rm -q file
+mkdir file && echo inner > file/inner
+git add file && git commit -q -m "turn file into a directory"
+
+git checkout -q -b dir-to-file
+git rm -q -r file
+echo "file again" > file
+git add file && git commit -q -m "turn the directory back into a file"
+git checkout -q main
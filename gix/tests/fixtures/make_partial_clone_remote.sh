@@ -0,0 +1,12 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config uploadpack.allowFilter true
+git config uploadpack.allowAnySHA1InWant true
+
+mkdir -p dir/sub
+echo root > root-file
+echo a > dir/a
+echo b > dir/sub/b
+git add . && git commit -q -m c1
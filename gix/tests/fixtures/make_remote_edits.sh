@@ -0,0 +1,20 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q base
+(cd base
+  git checkout -q -b main
+  git commit -q --allow-empty -m c1
+  git branch other
+)
+
+git clone -q base repo
+(cd repo
+  git config --add remote.origin.fetch '+refs/tags/*:refs/tags/*'
+  git branch -q --track feature origin/other
+  git pack-refs --all
+
+  git remote add upstream ../base
+  git fetch -q upstream
+  git branch -q --track up upstream/other
+)
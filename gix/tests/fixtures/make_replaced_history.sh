@@ -0,0 +1,19 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+git checkout -q -b main
+echo a > a
+git add a && git commit -q -m c1
+echo b > a
+git commit -q -am c2
+echo c > a
+git commit -q -am c3
+
+# replace c2 with a copy that has no parent, which cuts off c1
+git replace --graft HEAD~1
+
+cat <<EOF2 > .mailmap
+Replaced Name <replaced@example.com> <author@example.com>
+EOF2
@@ -0,0 +1,29 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function make_repo() {
+  git init -q "$1"
+  (cd "$1"
+    mkdir -p included/sub excluded/sub
+    touch root-file included/a included/sub/b excluded/c excluded/sub/d
+    git add . && git commit -q -m "c1"
+  )
+}
+
+make_repo cone
+(cd cone
+  git sparse-checkout set --cone included
+  # a file outside of the patterns that is present anyway
+  mkdir excluded && touch excluded/c
+)
+
+make_repo no-cone
+(cd no-cone
+  git sparse-checkout set --no-cone '/*' '!excluded/' 'excluded/sub/'
+)
+
+make_repo disabled
+(cd disabled
+  git sparse-checkout set --cone included
+  git sparse-checkout disable
+)
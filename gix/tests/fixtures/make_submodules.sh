@@ -71,3 +71,21 @@ git clone with-submodules not-a-submodule
   mv modules.bak .gitmodules
   git add m1 && git commit -m "no submodule in index and commit, but in configuration"
 )
+
+git clone with-submodules submodule-head-changed
+(cd submodule-head-changed
+  git submodule update --init
+  cd m1 && git checkout -q HEAD~1
+)
+
+git clone with-submodules submodule-dirty
+(cd submodule-dirty
+  git submodule update --init
+  echo change >> m1/this
+)
+
+git clone with-submodules submodule-untracked
+(cd submodule-untracked
+  git submodule update --init
+  echo untracked > m1/untracked
+)
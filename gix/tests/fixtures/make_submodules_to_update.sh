@@ -0,0 +1,45 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q module1
+(cd module1
+  touch this
+  git add this
+  git commit -q -m c1
+  echo hello >> this
+  git commit -q -am c2
+)
+
+git init -q super
+(cd super
+  git submodule add -q ../module1 m1
+  git commit -q -m "add module 1"
+  git -C m1 checkout -q HEAD~1
+  git commit -q -am "module 1 at c1"
+)
+
+git clone -q super super-clone
+
+git clone -q super super-clone-index-ahead
+(cd super-clone-index-ahead
+  git submodule update -q --init
+)
+(cd module1
+  echo more >> this
+  git commit -q -am c3
+)
+(cd super-clone-index-ahead
+  git update-index --cacheinfo 160000,$(git -C ../module1 rev-parse HEAD),m1
+)
+
+git init -q nested
+(cd nested
+  git submodule add -q ../module1 inner
+  git commit -q -m "add inner module"
+)
+
+git init -q super-nested
+(cd super-nested
+  git submodule add -q ../nested n
+  git commit -q -m "add nested module"
+)
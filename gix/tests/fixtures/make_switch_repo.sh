@@ -0,0 +1,22 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+mkdir dir
+echo a > a
+echo c > dir/c
+echo keep > keep
+git add .
+git commit -q -m c1
+
+git checkout -q -b other
+echo changed > a
+git rm -q dir/c
+mkdir new
+echo d > new/d
+git add a new
+git commit -q -m c2
+
+git checkout -q main
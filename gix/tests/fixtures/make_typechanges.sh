@@ -0,0 +1,16 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+echo content > file
+ln -s file link
+echo -n content > exe
+mkdir dir && echo content > dir/file
+git add . && git commit -q -m "c1"
+
+rm file && ln -s link file
+rm link && echo -n file > link
+chmod +x exe
+rm -r dir && echo content > dir
+git add -A && git commit -q -m "c2 - change types"
@@ -14,6 +14,11 @@ mod with_overrides {
     #[serial]
     fn order_from_api_and_cli_and_environment() -> gix_testtools::Result {
         let default_date = "1979-02-26 18:30:00";
+        let object_dir = gix_testtools::scripted_fixture_read_only("make_config_repos.sh")?
+            .join("http-config")
+            .join(".git")
+            .join("objects");
+        let object_dir = object_dir.to_str().expect("valid UTF-8").to_owned();
         let _env = Env::new()
             .set("GIT_HTTP_USER_AGENT", "agent-from-env")
             .set("GIT_HTTP_LOW_SPEED_LIMIT", "1")
@@ -54,7 +59,14 @@ mod with_overrides {
             .set("GIT_TERMINAL_PROMPT", "42")
             .set("GIT_SHALLOW_FILE", "shallow-file-env")
             .set("GIT_NAMESPACE", "namespace-env")
-            .set("GIT_EXTERNAL_DIFF", "external-diff-env");
+            .set("GIT_EXTERNAL_DIFF", "external-diff-env")
+            .set("GIT_EDITOR", "editor-env")
+            .set("GIT_SEQUENCE_EDITOR", "sequence-editor-env")
+            .set("GIT_TEMPLATE_DIR", "template-dir-env")
+            .set("GIT_OBJECT_DIRECTORY", object_dir.as_str())
+            .set("GIT_INDEX_FILE", "index-file-env")
+            .set("GIT_ALTERNATE_OBJECT_DIRECTORIES", "alternates-env")
+            .set("GIT_PAGER", "pager-env");
         let mut opts = gix::open::Options::isolated()
             .cli_overrides([
                 "http.userAgent=agent-from-cli",
@@ -92,6 +104,11 @@ mod with_overrides {
             gix::config::Source::Local,
             "config always refers to the local one for safety"
         );
+        assert_eq!(
+            repo.objects.store_ref().path(),
+            std::env::current_dir()?.join(&object_dir),
+            "the object directory is taken from the environment, relative to the current directory"
+        );
         let config = repo.config_snapshot();
         assert_eq!(
             config
@@ -250,6 +267,13 @@ mod with_overrides {
             ("gitoxide.commit.committerDate", default_date),
             ("gitoxide.user.emailFallback", "user email"),
             ("core.deltaBaseCacheLimit", "0"),
+            ("core.editor", "editor-env"),
+            ("core.pager", "pager-env"),
+            ("sequence.editor", "sequence-editor-env"),
+            ("init.templateDir", "template-dir-env"),
+            ("gitoxide.core.indexFile", "index-file-env"),
+            ("gitoxide.objects.directory", object_dir.as_str()),
+            ("gitoxide.objects.alternateDirectories", "alternates-env"),
             ("gitoxide.objects.cacheLimit", "5m"),
             ("gitoxide.pathspec.icase", "pathspecs-icase"),
             ("gitoxide.pathspec.glob", "pathspecs-glob"),
@@ -165,4 +165,42 @@ mod ancestors {
         }
         Ok(())
     }
+
+    #[test]
+    fn since_and_until() -> crate::Result {
+        let repo = crate::repo("make_repo_with_fork_and_dates.sh")?.to_thread_local();
+        let head = repo.head()?.into_peeled_id()?;
+        let (since, until) = (978307200 /* 2001-01-01 */, 991353600 /* 2001-06-01 */);
+
+        for use_commit_graph in [false, true] {
+            for sorting in [commit::Sorting::BreadthFirst, commit::Sorting::ByCommitTimeNewestFirst] {
+                let ids = |since: Option<i64>, until: Option<i64>| -> crate::Result<Vec<_>> {
+                    Ok(head
+                        .ancestors()
+                        .sorting(sorting)
+                        .use_commit_graph(use_commit_graph)
+                        .since(since)
+                        .until(until)
+                        .all()?
+                        .map(|c| c.map(|c| c.id))
+                        .collect::<Result<Vec<_>, _>>()?)
+                };
+                assert_eq!(
+                    ids(Some(since), None)?,
+                    [
+                        hex_to_id("288e509293165cb5630d08f4185bdf2445bf6170"), /* m1b1 */
+                        hex_to_id("bcb05040a6925f2ff5e10d3ae1f9264f2e8c43ac"), /* b1c1 */
+                    ],
+                    "commits from 2000 are too old"
+                );
+                assert_eq!(
+                    ids(Some(since), Some(until))?,
+                    [hex_to_id("bcb05040a6925f2ff5e10d3ae1f9264f2e8c43ac")],
+                    "the merge is too new"
+                );
+                assert_eq!(ids(None, Some(until))?.len(), 3, "all but the merge are old enough");
+            }
+        }
+        Ok(())
+    }
 }
@@ -126,3 +126,85 @@ mod non_bare {
         Ok(())
     }
 }
+
+mod template {
+    use gix_testtools::tempfile;
+
+    fn template_dir() -> crate::Result<tempfile::TempDir> {
+        let tmp = tempfile::tempdir()?;
+        std::fs::create_dir(tmp.path().join("hooks"))?;
+        std::fs::write(tmp.path().join("hooks").join("pre-commit"), b"#!/bin/sh\nexit 0\n")?;
+        std::fs::create_dir(tmp.path().join("info"))?;
+        std::fs::write(tmp.path().join("info").join("exclude"), b"/target\n")?;
+        std::fs::write(tmp.path().join("HEAD"), b"ref: refs/heads/ignored\n")?;
+        std::fs::write(
+            tmp.path().join("config"),
+            b"[template]\n\tfrom = tpl\n[core]\n\tbare = true\n",
+        )?;
+        Ok(tmp)
+    }
+
+    #[test]
+    fn content_is_copied_into_the_git_dir_with_repository_configuration_taking_precedence() -> crate::Result {
+        let template = template_dir()?;
+        let tmp = tempfile::tempdir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                template_dir: Some(template.path().into()),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )?
+        .into();
+
+        let git_dir = repo.git_dir();
+        assert_eq!(
+            std::fs::read(git_dir.join("hooks").join("pre-commit"))?,
+            b"#!/bin/sh\nexit 0\n"
+        );
+        assert!(
+            git_dir.join("hooks").join("pre-push.sample").is_file(),
+            "built-in templates are still present"
+        );
+        assert_eq!(
+            std::fs::read(git_dir.join("info").join("exclude"))?,
+            b"/target\n",
+            "template files override built-in ones"
+        );
+        assert_eq!(
+            repo.head_name()?.expect("unborn").as_bstr(),
+            "refs/heads/main",
+            "HEAD isn't copied"
+        );
+        let config = repo.config_snapshot();
+        assert_eq!(config.string("template.from").expect("present").as_ref(), "tpl");
+        assert_eq!(config.boolean("core.bare"), Some(false), "the repository config wins");
+        Ok(())
+    }
+
+    #[test]
+    fn configuration_is_used_if_no_template_dir_is_set() -> crate::Result {
+        let template = template_dir()?;
+        let tmp = tempfile::tempdir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::Bare,
+            gix::create::Options::default(),
+            gix::open::Options::isolated()
+                .config_overrides([format!("init.templateDir={}", template.path().display())]),
+        )?
+        .into();
+
+        assert!(repo.git_dir().join("hooks").join("pre-commit").is_file());
+        assert_eq!(
+            repo.config_snapshot()
+                .string("template.from")
+                .expect("config was reloaded")
+                .as_ref(),
+            "tpl"
+        );
+        Ok(())
+    }
+}
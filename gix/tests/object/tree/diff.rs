@@ -47,6 +47,14 @@ fn changes_against_tree_modified() -> crate::Result {
                 Ok::<_, Infallible>(())
             })
             .expect("infallible");
+
+            let mut words = Vec::new();
+            change
+                .diff(&mut cache)
+                .expect("objects available")
+                .words(|chunk| chunk.write_to(gix_diff::blob::words::Mode::Plain, &mut words))
+                .expect("no diff error");
+            assert_eq!(words.as_bstr(), "a\n{+a1+}\n");
             Ok(Default::default())
         })?;
     Ok(())
@@ -91,6 +99,122 @@ fn changes_against_tree_with_filename_tracking() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn submodule_changes_are_reported_with_their_commits() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_submodules.sh", "with-submodules", gix::open::Options::isolated())?;
+    let from = tree_named(&repo, "@~1");
+    let to = tree_named(&repo, "@");
+    let mut seen = Vec::new();
+    from.changes()?
+        .track_path()
+        .for_each_to_obtain_tree(&to, |change| -> Result<_, Infallible> {
+            let ids = change.event.submodule_ids();
+            if change.location == "m1" {
+                let (previous, current) = ids.expect("m1 is a submodule");
+                assert_eq!(previous, None, "the submodule was added");
+                let Event::Addition { id, .. } = change.event else {
+                    unreachable!("only additions are expected")
+                };
+                assert_eq!(current, Some(id), "the recorded commit is provided");
+            } else {
+                assert_eq!(ids, None, "{} isn't a submodule", change.location);
+            }
+            seen.push(change.location.to_owned());
+            Ok(Default::default())
+        })?;
+    assert_eq!(seen, ["m1", ".gitmodules"]);
+    Ok(())
+}
+
+#[test]
+fn typechanges() -> crate::Result {
+    let repo = named_repo("make_typechanges.sh")?;
+    let from = tree_named(&repo, "@~1");
+    let to = tree_named(&repo, "@");
+    let mut typechanges = Vec::new();
+    let mut others = Vec::new();
+    from.changes()?
+        .track_path()
+        .for_each_to_obtain_tree(&to, |change| -> Result<_, Infallible> {
+            if change.event.is_typechange() {
+                typechanges.push(change.location.to_owned());
+            } else {
+                others.push(change.location.to_owned());
+            }
+            Ok(Default::default())
+        })?;
+    assert_eq!(typechanges, ["file", "link"]);
+    assert_eq!(
+        others,
+        ["dir", "exe", "dir"],
+        "a directory replaced by a file is a deletion, here followed by the rename of its only file to the new file, \
+        and changes of executable bits don't change the type even if the content stays the same"
+    );
+    Ok(())
+}
+
+#[test]
+fn stats_with_rename_tracking() -> crate::Result {
+    let repo = named_repo("make_diff_repo.sh")?;
+    let from = tree_named(&repo, "@^{/c3-modification}~1");
+    let to = tree_named(&repo, ":/c3-modification");
+    let files = from.changes()?.stats(&to)?;
+    assert_eq!(
+        files,
+        [gix::diff::stat::File {
+            location: "a".into(),
+            source_location: None,
+            change: gix::diff::stat::Change::Lines {
+                insertions: 1,
+                removals: 0
+            }
+        }]
+    );
+
+    let from = tree_named(&repo, "@^{/r1-identity}~1");
+    let to = tree_named(&repo, ":/r1-identity");
+    let files = from.changes()?.stats(&to)?;
+    let mut out = Vec::new();
+    for format in [
+        gix::diff::stat::Format::default(),
+        gix::diff::stat::Format::NumStat,
+        gix::diff::stat::Format::ShortStat,
+    ] {
+        gix::diff::stat::write_to(&files, format, &mut out)?;
+    }
+    assert_eq!(
+        out.as_bstr(),
+        " a => dir/a-moved | 0\n 1 file changed, 0 insertions(+), 0 deletions(-)\n0\t0\ta => dir/a-moved\n 1 file changed, 0 insertions(+), 0 deletions(-)\n",
+        "the output matches `git diff --stat`, `--numstat` and `--shortstat` respectively"
+    );
+    Ok(())
+}
+
+#[test]
+fn directory_renames() -> crate::Result {
+    let repo = named_repo("make_directory_renames.sh")?;
+    let from = tree_named(&repo, "@~1");
+    let to = tree_named(&repo, "@");
+    let renames = from.changes()?.directory_renames(&to)?;
+    assert_eq!(
+        renames,
+        [gix::diff::directory_renames::DirectoryRename {
+            source: "src".into(),
+            destination: "renamed".into(),
+            num_files: 3,
+        }],
+        "`keep` still exists, and renames into subdirectories count for their top-most renamed directory"
+    );
+    assert_eq!(
+        renames[0].relocate("src/sub/new".into()).expect("within"),
+        "renamed/sub/new"
+    );
+
+    let renames = from.changes()?.track_rewrites(None).directory_renames(&to)?;
+    assert!(renames.is_empty(), "without rename tracking, nothing can be found");
+    Ok(())
+}
+
 fn tree_named(repo: &gix::Repository, rev_spec: impl AsRef<str>) -> gix::Tree {
     repo.rev_parse_single(rev_spec.as_ref())
         .unwrap()
@@ -25,3 +25,53 @@ fn lookup_entry_by_path() -> crate::Result {
     );
     Ok(())
 }
+
+mod traverse {
+    use gix::bstr::BString;
+
+    use crate::util::named_subrepo_opts;
+
+    fn paths(entries: &[gix::object::tree::traverse::paths::Entry]) -> Vec<BString> {
+        entries.iter().map(|e| e.filepath.clone()).collect()
+    }
+
+    #[test]
+    fn paths_recursive_by_default_with_depth_limit() -> crate::Result {
+        let repo = named_subrepo_opts("make_worktree_repo.sh", "repo", gix::open::Options::isolated())?;
+        let tree = repo.head_commit()?.tree()?;
+
+        let entries = tree.traverse().paths().all()?;
+        assert_eq!(paths(&entries), ["a", "b", "dir", "dir/c"]);
+        assert!(entries.iter().all(|e| e.size.is_none()), "sizes must be requested");
+
+        for entries in [
+            tree.traverse().paths().recursive(false).all()?,
+            tree.traverse().paths().max_depth(Some(0)).all()?,
+        ] {
+            assert_eq!(paths(&entries), ["a", "b", "dir"], "subtrees aren't entered");
+        }
+
+        let entries = tree.traverse().paths().with_sizes(true).all()?;
+        assert_eq!(
+            entries.iter().map(|e| e.size).collect::<Vec<_>>(),
+            [Some(6), Some(0), None, Some(0)],
+            "trees have no size"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "attributes")]
+    fn paths_with_pathspec() -> crate::Result {
+        let repo = named_subrepo_opts("make_worktree_repo.sh", "repo", gix::open::Options::isolated())?;
+        let tree = repo.head_commit()?.tree()?;
+        let pathspec = gix::Pathspec::new(&repo, ["dir/c"], false, || unreachable!("no attributes needed"))?;
+        let entries = tree.traverse().paths().pathspec(pathspec).all()?;
+        assert_eq!(
+            paths(&entries),
+            ["dir/c"],
+            "trees leading to matches are entered, but not listed"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use gix::reference::decorations::Kind;
+
+use crate::util::named_repo;
+
+#[test]
+fn head_branch_and_remote_tracking_branch() -> crate::Result {
+    let repo = named_repo("make_remote_repo.sh")?;
+    let decorations = repo.decorations()?;
+    let head_id = repo.head_id()?;
+
+    assert_eq!(
+        decorations.head_branch().map(|name| name.as_bstr().to_string()),
+        Some("refs/heads/main".into())
+    );
+    assert_eq!(
+        decorations
+            .get(head_id)
+            .iter()
+            .map(|d| (d.name.as_bstr().to_string(), d.kind))
+            .collect::<Vec<_>>(),
+        [
+            ("HEAD".into(), Kind::Head),
+            ("refs/heads/main".into(), Kind::LocalBranch),
+            ("refs/remotes/remote_repo/main".into(), Kind::RemoteBranch),
+        ]
+    );
+    assert_eq!(
+        decorations.format(head_id).as_deref(),
+        Some("HEAD -> main, remote_repo/main"),
+        "the branch HEAD points to is only shown once"
+    );
+    Ok(())
+}
+
+#[test]
+fn annotated_tags_decorate_the_tag_object_and_the_peeled_commit() -> crate::Result {
+    let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+    let decorations = repo.decorations()?;
+    let head_id = repo.head_id()?;
+
+    assert_eq!(
+        decorations.format(head_id).as_deref(),
+        Some("HEAD -> main, tag: v2, tag: v2.5, tag: v3, tag: v4, tag: v5")
+    );
+    let tag_id = repo.find_reference("v2")?.id();
+    assert_ne!(tag_id, head_id, "v2 is an annotated tag");
+    assert_eq!(decorations.format(tag_id).as_deref(), Some("tag: v2"));
+
+    let first_commit = repo.rev_parse_single("HEAD~2")?;
+    assert_eq!(decorations.format(first_commit).as_deref(), Some("tag: l0, tag: l1"));
+
+    let undecorated = repo.object_hash().null();
+    assert!(decorations.get(undecorated).is_empty());
+    assert_eq!(decorations.format(undecorated), None);
+    Ok(())
+}
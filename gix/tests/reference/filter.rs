@@ -0,0 +1,44 @@
+use crate::util::named_repo;
+
+fn names(refs: Vec<gix::Reference<'_>>) -> Vec<String> {
+    refs.iter().map(|r| r.name().shorten().to_string()).collect()
+}
+
+fn commit(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+#[test]
+fn contains() -> crate::Result {
+    let repo = named_repo("make_cherry_picks.sh")?;
+    let platform = repo.references()?;
+    let branches = || platform.local_branches();
+    assert_eq!(
+        names(branches()?.contains(commit(&repo, "main")?)?),
+        ["left", "main", "right"],
+        "all branches were started from main"
+    );
+    assert_eq!(names(branches()?.contains(commit(&repo, "left~1")?)?), ["left"]);
+    assert_eq!(
+        names(branches()?.contains(commit(&repo, "right~2")?)?),
+        ["right"],
+        "cherry-picks are different commits"
+    );
+    Ok(())
+}
+
+#[test]
+fn merged_and_no_merged() -> crate::Result {
+    let repo = named_repo("make_cherry_picks.sh")?;
+    let platform = repo.references()?;
+    let branches = || platform.local_branches();
+    assert_eq!(names(branches()?.merged(commit(&repo, "left")?)?), ["left", "main"]);
+    assert_eq!(names(branches()?.no_merged(commit(&repo, "left")?)?), ["right"]);
+    assert_eq!(
+        names(branches()?.merged(commit(&repo, "main")?)?),
+        ["main"],
+        "a branch is merged into itself"
+    );
+    assert_eq!(names(branches()?.no_merged(commit(&repo, "main")?)?), ["left", "right"]);
+    Ok(())
+}
@@ -103,4 +103,8 @@ fn set_target_id() {
         .starts_with("Reference \"refs/heads/main\" was supposed to exist"));
 }
 
+mod decorations;
+#[cfg(feature = "revision")]
+mod filter;
 mod remote;
+mod version_sort;
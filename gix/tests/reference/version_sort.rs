@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+
+use gix::reference::VersionSort;
+
+fn sorted<'a>(sort: &VersionSort, names: &[&'a str]) -> Vec<&'a str> {
+    let mut names = names.to_vec();
+    names.sort_by(|a, b| sort.compare((*a).into(), (*b).into()));
+    names
+}
+
+#[test]
+fn numbers_are_compared_by_value() {
+    let sort = VersionSort::default();
+    assert_eq!(sort.compare("v1.9".into(), "v1.10".into()), Ordering::Less);
+    assert_eq!(sort.compare("v1.10".into(), "v1.9".into()), Ordering::Greater);
+    assert_eq!(sort.compare("v1.0".into(), "v1.0.1".into()), Ordering::Less);
+    assert_eq!(sort.compare("v2".into(), "v2".into()), Ordering::Equal);
+    assert_eq!(
+        sorted(&sort, &["v10.0", "v1.10", "v2.0", "v1.2", "v1.9"]),
+        ["v1.2", "v1.9", "v1.10", "v2.0", "v10.0"]
+    );
+}
+
+#[test]
+fn leading_zeroes_are_fractional_parts() {
+    assert_eq!(
+        sorted(
+            &VersionSort::default(),
+            &["10", "9", "1", "0", "09", "010", "01", "00", "000"]
+        ),
+        ["000", "00", "01", "010", "09", "0", "1", "9", "10"],
+        "the same order as `strverscmp()`"
+    );
+}
+
+#[test]
+fn suffixes_sort_before_the_release() {
+    let names = ["v1.0", "v1.0-rc2", "v1.0-beta1", "v1.0-rc1", "v1.1-rc1", "v0.9"];
+    assert_eq!(
+        sorted(&VersionSort::default(), &names),
+        ["v0.9", "v1.0", "v1.0-beta1", "v1.0-rc1", "v1.0-rc2", "v1.1-rc1"],
+        "without suffixes, longer names sort later"
+    );
+    assert_eq!(
+        sorted(&VersionSort::new(["-rc"]), &names),
+        ["v0.9", "v1.0-rc1", "v1.0-rc2", "v1.0", "v1.0-beta1", "v1.1-rc1"],
+    );
+    assert_eq!(
+        sorted(&VersionSort::new(["-beta", "-rc"]), &names),
+        ["v0.9", "v1.0-beta1", "v1.0-rc1", "v1.0-rc2", "v1.0", "v1.1-rc1"],
+        "suffixes are ordered by their configuration order"
+    );
+    assert_eq!(
+        sorted(&VersionSort::new(["-rc", "-beta"]), &names),
+        ["v0.9", "v1.0-rc1", "v1.0-rc2", "v1.0-beta1", "v1.0", "v1.1-rc1"],
+    );
+}
+
+#[test]
+fn repository_configuration_and_sorted_references() -> crate::Result {
+    let (mut repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+    let head = repo.head_id()?.detach();
+    for name in ["v1.10", "v1.9", "v1.10-rc1"] {
+        repo.tag_reference(name, head, gix::refs::transaction::PreviousValue::MustNotExist)?;
+    }
+    let tag_names = |repo: &gix::Repository| -> crate::Result<Vec<String>> {
+        Ok(repo
+            .references()?
+            .tags()?
+            .sorted_by_version(&repo.version_sort())?
+            .iter()
+            .map(|r| r.name().shorten().to_string())
+            .collect())
+    };
+    assert_eq!(tag_names(&repo)?, ["v1.9", "v1.10", "v1.10-rc1"]);
+
+    let mut config = repo.config_snapshot_mut();
+    config.set_raw_value("versionsort", None, "prereleaseSuffix", "-rc")?;
+    config.commit()?;
+    assert_eq!(
+        repo.version_sort().suffixes(),
+        ["-rc"],
+        "the deprecated key is used as fallback"
+    );
+    assert_eq!(tag_names(&repo)?, ["v1.9", "v1.10-rc1", "v1.10"]);
+
+    let mut config = repo.config_snapshot_mut();
+    config.set_raw_value("versionsort", None, "suffix", "-beta")?;
+    config.commit()?;
+    assert_eq!(repo.version_sort().suffixes(), ["-beta"], "the new key has precedence");
+
+    #[cfg(feature = "revision")]
+    {
+        let mut describe = repo
+            .head_commit()?
+            .describe()
+            .names(gix::commit::describe::SelectRef::AllTags);
+        assert_eq!(describe.format()?.to_string(), "v1.10", "lexicographically smallest");
+        let mut describe = describe.sort_names_by_version(true);
+        assert_eq!(describe.format()?.to_string(), "v1.9", "the smallest version");
+    }
+    Ok(())
+}
@@ -42,4 +42,51 @@ mod blocking_io {
             Ok(())
         }
     }
+
+    mod connection_cache {
+        use gix::remote::Direction::Fetch;
+
+        use crate::remote;
+
+        #[test]
+        fn disabled_by_default_and_shared_among_clones() {
+            let repo = remote::repo("clone");
+            let cache = repo.connection_cache();
+            assert!(!cache.is_enabled());
+            assert!(
+                repo.ssh_connect_options().expect("valid").control_path.is_none(),
+                "ssh connections aren't shared either"
+            );
+
+            let clone = repo.clone();
+            assert!(cache.set_enabled(true).is_enabled());
+            assert!(clone.connection_cache().is_enabled(), "the state is shared");
+
+            let control_path = clone
+                .ssh_connect_options()
+                .expect("valid")
+                .control_path
+                .expect("set if enabled");
+            assert!(control_path.is_dir(), "it's created on first use");
+            drop((repo, clone));
+            assert!(!control_path.exists(), "…and removed once the cache is dropped");
+        }
+
+        #[test]
+        fn connections_that_persist_are_not_kept() -> crate::Result {
+            let repo = remote::repo("clone");
+            repo.connection_cache().set_enabled(true);
+            let remote = repo.find_remote("origin")?;
+            for _ in 0..2 {
+                remote
+                    .connect(Fetch)?
+                    .ref_map(gix::progress::Discard, Default::default())?;
+                assert!(
+                    repo.connection_cache().is_empty(),
+                    "file transports spawn a process and can't be reused"
+                );
+            }
+            Ok(())
+        }
+    }
 }
@@ -109,6 +109,487 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[cfg(feature = "blocking-network-client")]
+    mod dumb {
+        use std::{
+            borrow::Cow,
+            io::{BufRead, Read},
+            path::PathBuf,
+            sync::atomic::AtomicBool,
+        };
+
+        use gix::{
+            bstr::{BStr, ByteSlice},
+            remote::fetch::Status,
+        };
+        use gix_protocol::transport::{
+            client,
+            client::{Capabilities, MessageKind, RequestWriter, SetServiceResponse, WriteMode},
+            packetline,
+            packetline::{PacketLineRef, StreamingPeekableIter},
+            Protocol, Service,
+        };
+
+        /// A transport that serves the files of a repository, like a static HTTP server would.
+        struct Files {
+            root: PathBuf,
+            refs: Option<StreamingPeekableIter<std::io::Cursor<Vec<u8>>>>,
+        }
+
+        impl client::TransportWithoutIO for Files {
+            fn request(
+                &mut self,
+                _write_mode: WriteMode,
+                _on_into_read: MessageKind,
+                _trace: bool,
+            ) -> Result<RequestWriter<'_>, client::Error> {
+                unreachable!("there is no request in the dumb protocol")
+            }
+
+            fn to_url(&self) -> Cow<'_, BStr> {
+                gix::path::into_bstr(self.root.as_path())
+            }
+
+            fn is_dumb(&self) -> bool {
+                true
+            }
+
+            fn connection_persists_across_multiple_requests(&self) -> bool {
+                false
+            }
+
+            fn configure(
+                &mut self,
+                _config: &dyn std::any::Any,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+                Ok(())
+            }
+        }
+
+        impl client::Transport for Files {
+            fn handshake<'a>(
+                &mut self,
+                service: Service,
+                _extra_parameters: &'a [(&'a str, Option<&'a str>)],
+            ) -> Result<SetServiceResponse<'_>, client::Error> {
+                assert_eq!(service, Service::UploadPack);
+                let info_refs = std::fs::read(self.root.join("info").join("refs"))?;
+                let refs: Vec<_> = ByteSlice::lines(info_refs.as_slice())
+                    .map(|line| line.split_once_str("\t").expect("valid info/refs"))
+                    .collect();
+                let (main, _) = refs
+                    .iter()
+                    .find(|(_, name)| *name == b"refs/heads/main")
+                    .expect("HEAD points to main");
+                let mut advertisement = Vec::new();
+                let head = format!("{} HEAD\0symref=HEAD:refs/heads/main\n", main.as_bstr());
+                packetline::encode::data_to_write(head.as_bytes(), &mut advertisement)?;
+                for (id, name) in &refs {
+                    let line = format!("{} {}\n", id.as_bstr(), name.as_bstr());
+                    packetline::encode::data_to_write(line.as_bytes(), &mut advertisement)?;
+                }
+                packetline::encode::flush_to_write(&mut advertisement)?;
+
+                let refs = self.refs.insert(StreamingPeekableIter::new(
+                    std::io::Cursor::new(advertisement),
+                    &[PacketLineRef::Flush],
+                    false,
+                ));
+                let client::capabilities::recv::Outcome {
+                    capabilities,
+                    refs,
+                    protocol,
+                } = Capabilities::from_lines_with_version_detection(refs)?;
+                assert_eq!(protocol, Protocol::V1);
+                Ok(SetServiceResponse {
+                    actual_protocol: protocol,
+                    capabilities,
+                    refs,
+                })
+            }
+
+            fn get_file(&mut self, path: &str) -> Result<Option<Box<dyn BufRead + '_>>, client::Error> {
+                match std::fs::File::open(self.root.join(path)) {
+                    Ok(file) => Ok(Some(Box::new(std::io::BufReader::new(file)))),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+
+        #[test]
+        fn fetch_walks_loose_objects_and_downloads_packs() -> crate::Result {
+            let tmp = gix_testtools::scripted_fixture_writable("make_dumb_remote.sh")?;
+            let server = gix::open_opts(tmp.path().join("server.git"), crate::restricted())?;
+            let repo = gix::open_opts(tmp.path().join("local"), crate::restricted())?;
+
+            let remote = repo.find_remote("origin")?;
+            let transport = Files {
+                root: server.git_dir().to_owned(),
+                refs: None,
+            };
+            let outcome = remote
+                .to_connection_with_transport(transport)
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+
+            assert!(
+                outcome.performance.pack_bytes_received > 0,
+                "loose objects and packs are accounted for"
+            );
+            match outcome.status {
+                Status::Change {
+                    write_pack_bundle,
+                    update_refs,
+                    negotiate,
+                } => {
+                    assert!(negotiate.rounds.is_empty(), "there is no negotiation");
+                    assert_eq!(
+                        write_pack_bundle.index.num_objects, 8,
+                        "the loose objects of the remote were written into their own pack"
+                    );
+                    assert_eq!(write_pack_bundle.keep_path, None, "refs were updated");
+                    assert_eq!(update_refs.edits.len(), 4, "two branches and two tags");
+                }
+                _ => unreachable!("the local repository is empty"),
+            }
+
+            for (local, remote) in [
+                ("refs/remotes/origin/main", "refs/heads/main"),
+                ("refs/remotes/origin/other", "refs/heads/other"),
+                ("refs/tags/v1.0", "refs/tags/v1.0"),
+                ("refs/tags/v2.0", "refs/tags/v2.0"),
+            ] {
+                let mut reference = repo.find_reference(local)?;
+                assert_eq!(
+                    reference.id(),
+                    server.find_reference(remote)?.id(),
+                    "{local} points to the same object as {remote} on the remote"
+                );
+                reference.peel_to_id_in_place()?;
+            }
+            for id in server.objects.iter()? {
+                let id = id?;
+                assert!(repo.has_object(id), "{id} was fetched");
+            }
+            assert!(
+                !std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+                    .any(|entry| entry.map_or(true, |e| e.path().extension().map_or(false, |ext| ext == "keep"))),
+                "no pack is kept"
+            );
+
+            let outcome = repo
+                .find_remote("origin")?
+                .to_connection_with_transport(Files {
+                    root: server.git_dir().to_owned(),
+                    refs: None,
+                })
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            assert!(
+                matches!(outcome.status, Status::NoPackReceived { .. }),
+                "nothing changed on the remote"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn fetch_into_shallow_repository_is_not_possible() -> crate::Result {
+            let tmp = gix_testtools::scripted_fixture_writable("make_dumb_remote.sh")?;
+            let repo = gix::open_opts(tmp.path().join("local"), crate::restricted())?;
+            let err = repo
+                .find_remote("origin")?
+                .to_connection_with_transport(Files {
+                    root: tmp.path().join("server.git"),
+                    refs: None,
+                })
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(1.try_into()?))
+                .receive(gix::progress::Discard, &AtomicBool::default())
+                .unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    gix::remote::fetch::Error::MissingServerFeature { feature: "shallow", .. }
+                ),
+                "{err:?}"
+            );
+            let mut buf = Vec::new();
+            std::fs::File::open(tmp.path().join("server.git").join("HEAD"))?.read_to_end(&mut buf)?;
+            assert_eq!(buf.as_bstr(), "ref: refs/heads/main\n", "the remote is unchanged");
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "blocking-network-client")]
+    mod retry {
+        use std::{
+            borrow::Cow,
+            cell::RefCell,
+            io,
+            io::{BufRead, Read},
+            path::PathBuf,
+            rc::Rc,
+            sync::atomic::AtomicBool,
+        };
+
+        use gix::{bstr::BStr, remote::Direction::Fetch};
+        use gix_protocol::transport::{
+            client,
+            client::{
+                file::SpawnProcessOnDemand, ExtendedBufRead, HandleProgress, MessageKind, ReadlineBufRead,
+                RequestWriter, SetServiceResponse, Transport, WriteMode,
+            },
+            packetline,
+            packetline::PacketLineRef,
+            IsSpuriousError, Protocol, Service,
+        };
+
+        type Failures = Rc<RefCell<Vec<&'static str>>>;
+
+        /// Return `true` if `what` should fail as it didn't fail yet, and remember that it failed.
+        fn fail_once(failures: &Failures, what: &'static str) -> bool {
+            let mut failures = failures.borrow_mut();
+            let fail = !failures.contains(&what);
+            if fail {
+                failures.push(what);
+            }
+            fail
+        }
+
+        /// A stateless transport that connects to a local repository for each request, like an HTTP server would,
+        /// and fails the first handshake as well as the first download of a pack with a spurious error.
+        struct FailOnce {
+            path: PathBuf,
+            connection: Option<SpawnProcessOnDemand>,
+            failures: Failures,
+        }
+
+        impl FailOnce {
+            fn connect(&mut self) -> &mut SpawnProcessOnDemand {
+                self.connection.insert(
+                    client::file::connect(
+                        gix::path::into_bstr(self.path.as_path()).into_owned(),
+                        Protocol::V2,
+                        false,
+                    )
+                    .expect("infallible"),
+                )
+            }
+        }
+
+        fn spurious_error() -> io::Error {
+            io::Error::new(io::ErrorKind::ConnectionReset, "injected failure")
+        }
+
+        impl client::TransportWithoutIO for FailOnce {
+            fn request(
+                &mut self,
+                write_mode: WriteMode,
+                on_into_read: MessageKind,
+                trace: bool,
+            ) -> Result<RequestWriter<'_>, client::Error> {
+                let failures = self.failures.clone();
+                let connection = self.connect();
+                connection.handshake(Service::UploadPack, &[])?;
+                let (writer, reader) = connection.request(write_mode, on_into_read, trace)?.into_parts();
+                let reader = Box::new(FailAfter {
+                    inner: reader,
+                    budget: 100,
+                    failures,
+                });
+                Ok(RequestWriter::new_from_bufread(
+                    writer,
+                    reader,
+                    write_mode,
+                    on_into_read,
+                    trace,
+                ))
+            }
+
+            fn to_url(&self) -> Cow<'_, BStr> {
+                gix::path::into_bstr(self.path.as_path())
+            }
+
+            fn connection_persists_across_multiple_requests(&self) -> bool {
+                false
+            }
+
+            fn configure(
+                &mut self,
+                _config: &dyn std::any::Any,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+                Ok(())
+            }
+        }
+
+        impl client::Transport for FailOnce {
+            fn handshake<'a>(
+                &mut self,
+                service: Service,
+                extra_parameters: &'a [(&'a str, Option<&'a str>)],
+            ) -> Result<SetServiceResponse<'_>, client::Error> {
+                if fail_once(&self.failures, "handshake") {
+                    return Err(spurious_error().into());
+                }
+                self.connect().handshake(service, extra_parameters)
+            }
+        }
+
+        /// Fail reading the pack after `budget` bytes were read, unless it failed before. Reading lines isn't affected.
+        struct FailAfter<'a> {
+            inner: Box<dyn ExtendedBufRead<'a> + Unpin + 'a>,
+            budget: usize,
+            failures: Failures,
+        }
+
+        impl Read for FailAfter<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let available = self.fill_buf()?;
+                let amount = available.len().min(buf.len());
+                buf[..amount].copy_from_slice(&available[..amount]);
+                self.consume(amount);
+                Ok(amount)
+            }
+        }
+
+        impl BufRead for FailAfter<'_> {
+            fn fill_buf(&mut self) -> io::Result<&[u8]> {
+                if self.budget == 0 && fail_once(&self.failures, "pack") {
+                    return Err(spurious_error());
+                }
+                let buf = self.inner.fill_buf()?;
+                let len = if self.failures.borrow().contains(&"pack") {
+                    buf.len()
+                } else {
+                    buf.len().min(self.budget)
+                };
+                Ok(&buf[..len])
+            }
+
+            fn consume(&mut self, amount: usize) {
+                self.budget = self.budget.saturating_sub(amount);
+                self.inner.consume(amount);
+            }
+        }
+
+        impl ReadlineBufRead for FailAfter<'_> {
+            fn readline(&mut self) -> Option<io::Result<Result<PacketLineRef<'_>, packetline::decode::Error>>> {
+                self.inner.readline()
+            }
+
+            fn readline_str(&mut self, line: &mut String) -> io::Result<usize> {
+                self.inner.readline_str(line)
+            }
+        }
+
+        impl<'a> ExtendedBufRead<'a> for FailAfter<'a> {
+            fn set_progress_handler(&mut self, handle_progress: Option<HandleProgress<'a>>) {
+                self.inner.set_progress_handler(handle_progress);
+            }
+
+            fn peek_data_line(&mut self) -> Option<io::Result<Result<&[u8], client::Error>>> {
+                self.inner.peek_data_line()
+            }
+
+            fn reset(&mut self, version: Protocol) {
+                self.inner.reset(version);
+            }
+
+            fn stopped_at(&self) -> Option<MessageKind> {
+                self.inner.stopped_at()
+            }
+        }
+
+        fn empty_repo(strict: bool) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+            let tmp = gix_testtools::tempfile::tempdir()?;
+            gix::init(tmp.path())?;
+            let repo = gix::open_opts(tmp.path(), crate::restricted().strict_config(strict))?;
+            Ok((repo, tmp))
+        }
+
+        fn fetch(repo: &gix::Repository, failures: &Failures) -> crate::Result<gix::remote::fetch::Outcome> {
+            let remote = repo
+                .remote_at(super::base_repo_path())?
+                .with_refspecs(Some("+refs/heads/*:refs/remotes/origin/*"), Fetch)?;
+            let transport = FailOnce {
+                path: super::base_repo_path().into(),
+                connection: None,
+                failures: failures.clone(),
+            };
+            let outcome = remote
+                .to_connection_with_transport(transport)
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_keep_pack(true)
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            Ok(outcome)
+        }
+
+        #[test]
+        fn spurious_failures_of_stateless_transports_are_retried() -> crate::Result {
+            let (mut repo, _tmp) = empty_repo(false)?;
+            repo.config_snapshot_mut()
+                .set_value(&gix::config::tree::gitoxide::Http::RETRY_DELAY, "0")?;
+            let failures = Rc::new(RefCell::new(Vec::new()));
+            let outcome = fetch(&repo, &failures)?;
+            assert_eq!(
+                *failures.borrow(),
+                ["handshake", "pack"],
+                "the handshake is repeated, and so is the request for the pack after its download broke"
+            );
+            let gix::remote::fetch::Status::Change { write_pack_bundle, .. } = outcome.status else {
+                panic!("the repository was empty")
+            };
+            assert!(write_pack_bundle.index.num_objects > 0);
+            let main = repo.find_reference("refs/remotes/origin/main")?.peel_to_id_in_place()?;
+            assert!(repo.find_object(main).is_ok(), "all objects were received");
+            Ok(())
+        }
+
+        #[test]
+        fn without_retries_spurious_failures_are_returned() -> crate::Result {
+            let (mut repo, _tmp) = empty_repo(false)?;
+            repo.config_snapshot_mut()
+                .set_value(&gix::config::tree::gitoxide::Http::MAX_RETRIES, "0")?;
+            let failures = Rc::new(RefCell::new(Vec::new()));
+            let err = fetch(&repo, &failures).unwrap_err();
+            assert_eq!(*failures.borrow(), ["handshake"]);
+            assert!(
+                err.downcast_ref::<gix::remote::fetch::prepare::Error>()
+                    .map_or(false, |err| err.is_spurious()),
+                "{err:?}"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn invalid_retry_configuration_is_an_error_unless_lenient() -> crate::Result {
+            for strict in [true, false] {
+                let (mut repo, _tmp) = empty_repo(strict)?;
+                repo.config_snapshot_mut()
+                    .set_raw_value("gitoxide", Some("http".into()), "retryDelay", "invalid")?;
+                let failures = Rc::new(RefCell::new(Vec::new()));
+                let res = fetch(&repo, &failures);
+                if strict {
+                    let err = res.unwrap_err();
+                    assert!(
+                        matches!(
+                            err.downcast_ref::<gix::remote::fetch::prepare::Error>(),
+                            Some(gix::remote::fetch::prepare::Error::RefMap(
+                                gix::remote::ref_map::Error::NetworkRetry(_)
+                            ))
+                        ),
+                        "{err:?}"
+                    );
+                    assert!(failures.borrow().is_empty(), "nothing was attempted");
+                } else {
+                    res?;
+                }
+            }
+            Ok(())
+        }
+    }
+
     #[test]
     #[cfg(feature = "blocking-network-client")]
     fn fetch_with_alternates_adds_tips_from_alternates() -> crate::Result<()> {
@@ -446,6 +927,7 @@ mod blocking_and_async_io {
                 .await?
                 .prepare_fetch(gix::progress::Discard, Default::default())
                 .await?
+                .with_keep_pack(true)
                 .receive(gix::progress::Discard, &AtomicBool::default())
                 .await?;
 
@@ -465,6 +947,127 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_with_disabled_capabilities() -> crate::Result {
+        let daemon = spawn_git_daemon_if_async(repo_path("clone-as-base-with-changes"))?;
+        for disabled in [None, Some("shallow")] {
+            let (mut repo, _tmp) = repo_rw("two-origins");
+            if let Some(capability) = disabled {
+                repo.config_snapshot_mut().set_raw_value(
+                    "remote",
+                    Some("changes-on-top-of-origin".into()),
+                    "disabledCapabilities",
+                    capability,
+                )?;
+            }
+            let mut remote = into_daemon_remote_if_async(
+                repo.find_remote("changes-on-top-of-origin")?
+                    .with_fetch_tags(gix::remote::fetch::Tags::None),
+                daemon.as_ref(),
+                None,
+            );
+            remote.replace_refspecs(Some("HEAD"), Fetch)?;
+
+            let prepare = remote
+                .connect(Fetch)
+                .await?
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .await?;
+            let features = prepare.negotiated_features();
+            assert!(
+                features.iter().any(|(name, _)| *name == "agent"),
+                "the user agent is always sent"
+            );
+            assert_eq!(
+                features.iter().any(|(name, _)| *name == "shallow"),
+                disabled.is_none(),
+                "the server supports shallow clones, but we don't request it if it is disabled"
+            );
+
+            let res = prepare.receive(gix::progress::Discard, &AtomicBool::default()).await?;
+            assert!(
+                matches!(res.status, gix::remote::fetch::Status::Change { .. }),
+                "{disabled:?}: the fetch works either way"
+            );
+        }
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_small_pack_is_exploded_into_loose_objects() -> crate::Result {
+        let daemon = spawn_git_daemon_if_async(repo_path("clone-as-base-with-changes"))?;
+        for (unpack_limit, expect_loose_objects) in [(None, true), (Some("3"), false), (Some("0"), false)] {
+            let (mut repo, _tmp) = repo_rw("two-origins");
+            if let Some(limit) = unpack_limit {
+                repo.config_snapshot_mut()
+                    .set_raw_value("transfer", None, "unpackLimit", limit)?;
+            }
+            let packs_before = std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count();
+            let mut remote = into_daemon_remote_if_async(
+                repo.find_remote("changes-on-top-of-origin")?
+                    .with_fetch_tags(gix::remote::fetch::Tags::None),
+                daemon.as_ref(),
+                None,
+            );
+            remote.replace_refspecs(Some("HEAD"), Fetch)?;
+
+            let res: gix::remote::fetch::Outcome = remote
+                .connect(Fetch)
+                .await?
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .await?
+                .receive(gix::progress::Discard, &AtomicBool::default())
+                .await?;
+
+            match res.status {
+                gix::remote::fetch::Status::Change { write_pack_bundle, .. } => {
+                    assert_eq!(write_pack_bundle.index.num_objects, 3, "{unpack_limit:?}");
+                    assert_eq!(
+                        write_pack_bundle.data_path.is_none(),
+                        expect_loose_objects,
+                        "{unpack_limit:?}: exploded packs are removed"
+                    );
+                    assert_eq!(
+                        write_pack_bundle.keep_path.is_none() && write_pack_bundle.index_path.is_none(),
+                        expect_loose_objects,
+                        "{unpack_limit:?}"
+                    );
+                }
+                _ => unreachable!("there is a pack with changes"),
+            }
+            let packs_after = std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count();
+            assert_eq!(
+                packs_after == packs_before,
+                expect_loose_objects,
+                "{unpack_limit:?}: no pack is added if the objects are written loose"
+            );
+            let tip = res.ref_map.mappings[0]
+                .remote
+                .as_id()
+                .expect("the remote HEAD points to a commit")
+                .to_owned();
+            let hex = tip.to_hex().to_string();
+            assert_eq!(
+                repo.objects
+                    .store_ref()
+                    .path()
+                    .join(&hex[..2])
+                    .join(&hex[2..])
+                    .is_file(),
+                expect_loose_objects,
+                "{unpack_limit:?}: the fetched commit is written as loose object if the pack was exploded"
+            );
+            assert!(repo.has_object(tip), "the received objects are available either way");
+        }
+        Ok(())
+    }
+
     #[maybe_async::test(
         feature = "blocking-network-client",
         async(feature = "async-network-client-async-std", async_std::test)
@@ -528,8 +1131,10 @@ mod blocking_and_async_io {
                     .prepare_fetch(progress::Discard, Default::default())
                     .await?
                     .with_dry_run(dry_run)
+                    .with_keep_pack(true)
                     .receive(progress::Discard, &AtomicBool::default())
                     .await?;
+                let performance = outcome.performance;
                 let refs = match outcome.status {
                     fetch::Status::Change {
                         write_pack_bundle,
@@ -537,6 +1142,10 @@ mod blocking_and_async_io {
                         negotiate,
                     } => {
                         assert_eq!(negotiate.rounds.len(), 1);
+                        assert!(
+                            performance.pack_bytes_received > 0,
+                            "the size of the received pack is known"
+                        );
                         assert_eq!(write_pack_bundle.pack_version, gix::odb::pack::data::Version::V2);
                         assert_eq!(write_pack_bundle.object_hash, repo.object_hash());
                         assert_eq!(write_pack_bundle.index.num_objects, 4, "{dry_run}: this value is 4 when git does it with 'consecutive' negotiation style, but could be 33 if completely naive.");
@@ -1,5 +1,7 @@
 mod config_snapshot;
 mod identity;
+#[cfg(feature = "attributes")]
+mod programs;
 mod remote;
 
 #[cfg(feature = "blocking-network-client")]
@@ -31,6 +33,23 @@ mod ssh_options {
             opts.disallow_shell,
             "fallbacks won't allow shells, so must be a program or program name"
         );
+        assert_eq!(opts.askpass, None, "no askpass program is configured");
+        Ok(())
+    }
+
+    #[test]
+    fn with_askpass() -> crate::Result {
+        let repo = crate::util::repo_opts(
+            "make_basic_repo.sh",
+            crate::util::restricted().config_overrides(Some("core.askPass=/usr/bin/askpass")),
+        )?
+        .to_thread_local();
+        let opts = repo.ssh_connect_options()?;
+        assert_eq!(
+            opts.askpass.as_deref(),
+            Some(std::path::Path::new("/usr/bin/askpass")),
+            "the same program is used for credentials and ssh passphrases"
+        );
         Ok(())
     }
 }
@@ -38,6 +57,62 @@ mod ssh_options {
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 mod transport_options;
 
+mod compression {
+    use crate::util::{repo_opts, restricted};
+
+    #[allow(clippy::result_large_err)]
+    fn open(overrides: &[&str]) -> Result<gix::Repository, gix::open::Error> {
+        repo_opts(
+            "make_basic_repo.sh",
+            restricted()
+                .strict_config(true)
+                .config_overrides(overrides.iter().copied().map(ToOwned::to_owned)),
+        )
+        .map(|repo| repo.to_thread_local())
+    }
+
+    #[test]
+    fn defaults() -> crate::Result {
+        let repo = open(&[])?;
+        assert_eq!(repo.objects.store_ref().loose_compression_level(), 1, "like git");
+        assert_eq!(
+            repo.pack_compression_level()?,
+            3,
+            "faster than git at almost the same ratio"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn core_compression_is_the_fallback() -> crate::Result {
+        let repo = open(&["core.compression=-1"])?;
+        assert_eq!(repo.objects.store_ref().loose_compression_level(), 6);
+        assert_eq!(repo.pack_compression_level()?, 6);
+
+        let repo = open(&["core.compression=0", "core.looseCompression=9", "pack.compression=4"])?;
+        assert_eq!(repo.objects.store_ref().loose_compression_level(), 9);
+        assert_eq!(repo.pack_compression_level()?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_levels_are_rejected() {
+        let err = open(&["core.looseCompression=10"]).unwrap_err();
+        assert!(
+            matches!(err, gix::open::Error::Config(gix::config::Error::CompressionLevel(_))),
+            "loose levels are needed to open the object database"
+        );
+        assert_eq!(
+            open(&["pack.compression=-2"])
+                .expect("pack values are read on demand")
+                .pack_compression_level()
+                .unwrap_err()
+                .to_string(),
+            "The value of key \"pack.compression\" was invalid"
+        );
+    }
+}
+
 #[cfg(feature = "blocking-network-client")]
 #[cfg(feature = "blocking-network-client")]
 pub fn repo(name: &str) -> gix::Repository {
@@ -0,0 +1,77 @@
+use gix_sec::Permission;
+use gix_testtools::Env;
+use serial_test::serial;
+
+use crate::util::named_subrepo_opts;
+
+fn repo_with_overrides(overrides: &[&str]) -> crate::Result<gix::Repository> {
+    let mut opts = gix::open::Options::isolated().config_overrides(overrides.iter().copied());
+    opts.permissions.env.git_prefix = Permission::Allow;
+    Ok(named_subrepo_opts("make_config_repos.sh", "http-config", opts)?)
+}
+
+fn command(cmd: &gix::command::Prepare) -> &str {
+    cmd.command.to_str().expect("valid UTF-8 in tests")
+}
+
+#[test]
+#[serial]
+fn editor_precedence() -> crate::Result {
+    let _env = Env::new()
+        .unset("GIT_EDITOR")
+        .unset("GIT_SEQUENCE_EDITOR")
+        .set("TERM", "xterm")
+        .set("VISUAL", "visual-env")
+        .set("EDITOR", "editor-env");
+    let repo = repo_with_overrides(&["core.editor=editor-config"])?;
+    assert_eq!(command(&repo.editor()?), "editor-config");
+    assert_eq!(
+        command(&repo.sequence_editor()?),
+        "editor-config",
+        "the sequence editor falls back to the editor"
+    );
+
+    let repo = repo_with_overrides(&["sequence.editor=sequence-editor-config"])?;
+    assert_eq!(command(&repo.editor()?), "visual-env");
+    assert_eq!(command(&repo.sequence_editor()?), "sequence-editor-config");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn editor_on_dumb_terminal() -> crate::Result {
+    let _env = Env::new()
+        .unset("GIT_EDITOR")
+        .set("TERM", "dumb")
+        .set("VISUAL", "visual-env")
+        .unset("EDITOR");
+    let repo = repo_with_overrides(&[])?;
+    assert!(
+        matches!(repo.editor(), Err(gix::config::editor::Error::DumbTerminal)),
+        "VISUAL isn't used on dumb terminals, and there is no fallback then"
+    );
+
+    let _env = Env::new().set("TERM", "xterm").unset("VISUAL").unset("EDITOR");
+    assert_eq!(command(&repo.editor()?), "vi", "the default editor");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn pager() -> crate::Result {
+    let _env = Env::new().unset("GIT_PAGER").set("PAGER", "pager-env").unset("LESS");
+    let repo = repo_with_overrides(&["core.pager=less -S"])?;
+    let pager = repo.pager().expect("pager is configured");
+    assert_eq!(command(&pager), "less -S");
+    assert!(pager.use_shell, "pagers may be shell scripts");
+    assert!(pager.env.iter().any(|(key, value)| key == "LESS" && value == "FRX"));
+
+    let repo = repo_with_overrides(&[])?;
+    assert_eq!(command(&repo.pager().expect("set")), "pager-env");
+
+    for disabled in ["cat", ""] {
+        let repo = repo_with_overrides(&[&format!("core.pager={disabled}")])?;
+        assert!(repo.pager().is_none(), "{disabled:?} turns paging off");
+    }
+    Ok(())
+}
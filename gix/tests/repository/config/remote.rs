@@ -68,3 +68,41 @@ fn branch_remote() -> Result {
 
     Ok(())
 }
+
+#[test]
+fn branch_remote_tracking_ref_name() -> Result {
+    let mut repo = named_repo("make_remote_repo.sh")?;
+
+    assert_eq!(
+        repo.branch_remote_tracking_ref_name("main")
+            .expect("upstream is configured")?
+            .as_bstr(),
+        "refs/remotes/remote_repo/main",
+        "the upstream is mapped with the fetch refspec of the remote"
+    );
+    assert!(
+        repo.branch_remote_tracking_ref_name("broken").is_none(),
+        "without a remote there is no tracking branch"
+    );
+    assert!(repo.branch_remote_tracking_ref_name("missing").is_none());
+
+    let mut config = repo.config_snapshot_mut();
+    config.set_raw_value("branch", Some("local".into()), "remote", ".")?;
+    config.set_raw_value("branch", Some("local".into()), "merge", "refs/heads/main")?;
+    config.set_raw_value("branch", Some("broken".into()), "remote", "remote_repo")?;
+    config.commit()?;
+    assert_eq!(
+        repo.branch_remote_tracking_ref_name("local")
+            .expect("upstream is configured")?
+            .as_bstr(),
+        "refs/heads/main",
+        "local upstreams are used as is"
+    );
+    assert!(
+        repo.branch_remote_tracking_ref_name("broken")
+            .expect("upstream is configured")
+            .is_err(),
+        "the merge ref is invalid"
+    );
+    Ok(())
+}
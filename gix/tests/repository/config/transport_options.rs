@@ -46,6 +46,8 @@ mod http {
             follow_redirects,
             low_speed_limit_bytes_per_second,
             low_speed_time_seconds,
+            max_receive_bytes_per_second,
+            max_send_bytes_per_second,
             proxy,
             no_proxy,
             proxy_auth_method,
@@ -67,6 +69,11 @@ mod http {
         assert_eq!(follow_redirects, FollowRedirects::All);
         assert_eq!(low_speed_limit_bytes_per_second, 5120);
         assert_eq!(low_speed_time_seconds, 10);
+        assert_eq!(
+            max_receive_bytes_per_second, 0,
+            "transfer speeds aren't limited by default"
+        );
+        assert_eq!(max_send_bytes_per_second, 0);
         assert_eq!(proxy.as_deref(), Some("http://localhost:9090"),);
         assert!(
             proxy_authenticate.is_none(),
@@ -155,6 +162,16 @@ mod http {
         assert!(opts.ssl_version.is_none(), "empty strings reset what was there");
     }
 
+    #[test]
+    fn http_max_speeds() {
+        let repo = repo_opts("http-config", |opts| {
+            opts.config_overrides(["gitoxide.http.maxReceiveSpeed=1m", "gitoxide.http.maxSendSpeed=512"])
+        });
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert_eq!(opts.max_receive_bytes_per_second, 1024 * 1024);
+        assert_eq!(opts.max_send_bytes_per_second, 512);
+    }
+
     #[test]
     fn http_verbose() {
         let repo = repo("http-verbose");
@@ -0,0 +1,246 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::{
+    bstr::{BStr, BString},
+    history::rewrite::{Delegate, Options},
+};
+
+use crate::util::repo_rw;
+
+fn reference_names(repo: &gix::Repository) -> crate::Result<Vec<gix::refs::FullName>> {
+    repo.references()?.all()?.map(|r| Ok(r?.detach().name)).collect()
+}
+
+fn tree_paths(repo: &gix::Repository, rev: &str) -> crate::Result<Vec<BString>> {
+    let tree = repo.rev_parse_single(rev)?.object()?.peel_to_tree()?;
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    tree.traverse().breadthfirst(&mut recorder)?;
+    Ok(recorder
+        .records
+        .into_iter()
+        .filter(|entry| !entry.mode.is_tree())
+        .map(|entry| entry.filepath)
+        .collect())
+}
+
+struct RemoveSecretsAndRenameAuthor;
+
+impl Delegate for RemoveSecretsAndRenameAuthor {
+    fn keep_entry(&mut self, path: &BStr, _entry: &gix::objs::tree::EntryRef<'_>) -> bool {
+        path != "dir/secret"
+    }
+
+    fn rewrite_message(&mut self, commit: &gix::objs::Commit) -> Option<BString> {
+        (commit.message == "c3\n").then(|| "c3 (rewritten)\n".into())
+    }
+
+    fn rewrite_signature(&mut self, signature: &gix::actor::Signature) -> Option<gix::actor::Signature> {
+        let mut signature = signature.clone();
+        signature.email = "anonymous@example.com".into();
+        Some(signature)
+    }
+}
+
+#[test]
+fn rewrite_history_removes_paths_and_large_blobs_and_updates_references() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_history_to_rewrite.sh")?;
+    let original_main = repo.rev_parse_single("main")?.detach();
+    let original_c3 = repo.rev_parse_single("other")?.detach();
+
+    let outcome = repo.rewrite_history(
+        reference_names(&repo)?,
+        &mut RemoveSecretsAndRenameAuthor,
+        gix::progress::Discard,
+        &AtomicBool::default(),
+        Options {
+            max_blob_size: Some(1000),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.commits.len(), 4, "all commits were visited");
+    assert_eq!(outcome.ref_edits.len(), 3, "main, other and the annotated tag changed");
+
+    let main = repo.rev_parse_single("main")?.detach();
+    assert_eq!(
+        outcome.commits[&original_main],
+        Some(main),
+        "the last commit became empty and was removed"
+    );
+    assert_eq!(repo.rev_parse_single("other")?.detach(), main, "c3 is the new tip");
+    assert_eq!(outcome.commits[&original_c3], Some(main));
+    assert_eq!(
+        repo.rev_parse_single("v1^{commit}")?.detach(),
+        main,
+        "the annotated tag was rewritten to point to the new commit"
+    );
+    assert_eq!(tree_paths(&repo, "main")?, ["a"]);
+
+    let commits = repo
+        .rev_walk(Some(main))
+        .all()?
+        .map(|info| info.map(|info| info.id))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(commits.len(), 2, "the commit adding the big file became empty as well");
+    let tip = repo.find_object(main)?.into_commit();
+    assert_eq!(tip.message_raw()?, "c3 (rewritten)\n");
+    assert_eq!(tip.author()?.email, "anonymous@example.com");
+    assert_eq!(tip.committer()?.email, "anonymous@example.com");
+    assert!(repo.has_object(original_main), "the original history is never deleted");
+    Ok(())
+}
+
+#[test]
+fn rewrite_history_without_changes_keeps_everything() -> crate::Result {
+    struct Noop;
+    impl Delegate for Noop {}
+
+    let (repo, _tmp) = repo_rw("make_history_to_rewrite.sh")?;
+    let outcome = repo.rewrite_history(
+        reference_names(&repo)?,
+        &mut Noop,
+        gix::progress::Discard,
+        &AtomicBool::default(),
+        Options::default(),
+    )?;
+    assert!(outcome.ref_edits.is_empty(), "nothing changed");
+    assert!(
+        outcome
+            .commits
+            .iter()
+            .all(|(original, rewritten)| Some(*original) == *rewritten),
+        "all commits map to themselves"
+    );
+    Ok(())
+}
+
+#[test]
+fn rewrite_history_deletes_references_without_remaining_commits() -> crate::Result {
+    struct RemoveAll;
+    impl Delegate for RemoveAll {
+        fn keep_entry(&mut self, _path: &BStr, _entry: &gix::objs::tree::EntryRef<'_>) -> bool {
+            false
+        }
+    }
+
+    let (repo, _tmp) = repo_rw("make_history_to_rewrite.sh")?;
+    let outcome = repo.rewrite_history(
+        reference_names(&repo)?,
+        &mut RemoveAll,
+        gix::progress::Discard,
+        &AtomicBool::default(),
+        Options::default(),
+    )?;
+    assert!(
+        outcome.commits.values().all(Option::is_none),
+        "all commits were removed"
+    );
+    assert_eq!(outcome.ref_edits.len(), 3);
+    assert!(
+        outcome.ref_edits.iter().all(|edit| edit.change.new_value().is_none()),
+        "all references were deleted"
+    );
+    for name in ["refs/heads/main", "refs/heads/other", "refs/tags/v1"] {
+        assert!(repo.try_find_reference(name)?.is_none(), "{name} was deleted");
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "mailmap", feature = "blob-diff"))]
+mod author_stats {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::history::authors::{Author, Options};
+
+    use crate::util::named_repo;
+
+    fn time(seconds: gix::date::SecondsSinceUnixEpoch, offset: gix::date::OffsetInSeconds) -> gix::date::Time {
+        gix::date::Time::new(seconds, offset)
+    }
+
+    fn names_and_commits(authors: &[Author]) -> Vec<(String, usize)> {
+        authors
+            .iter()
+            .map(|author| (format!("{} <{}>", author.name, author.email), author.num_commits))
+            .collect()
+    }
+
+    #[test]
+    fn commits_and_active_days_per_author_with_mailmap_applied() -> crate::Result {
+        let repo = named_repo("make_author_history.sh")?;
+        let main = repo.rev_parse_single("main")?.detach();
+        let outcome = repo.author_stats(
+            Some(main),
+            None::<gix::ObjectId>,
+            gix::progress::Discard,
+            &AtomicBool::default(),
+            Options::default(),
+        )?;
+        assert_eq!(outcome.num_commits, 7);
+        assert_eq!(
+            names_and_commits(&outcome.authors),
+            [
+                ("Joe <joe@example.com>".into(), 4),
+                ("Jane Doe <jane@example.com>".into(), 3)
+            ],
+            "identities are unified by mailmap and by email ignoring case, with the most active author first"
+        );
+
+        let jane = &outcome.authors[1];
+        assert_eq!(jane.first_commit, time(946728000, 0));
+        assert_eq!(jane.last_commit, time(947062800, 0));
+        assert_eq!(
+            jane.active_days, 2,
+            "the second commit happened on the same day in the author's timezone"
+        );
+        assert_eq!(jane.lines, None, "line statistics are only computed on request");
+        assert_eq!(outcome.authors[0].active_days, 4);
+        assert_eq!(outcome.skipped_merge_commits, 0, "nothing is diffed");
+        Ok(())
+    }
+
+    #[test]
+    fn line_stats_skip_merge_commits_and_respect_hidden_commits() -> crate::Result {
+        let repo = named_repo("make_author_history.sh")?;
+        let main = repo.rev_parse_single("main")?.detach();
+        for thread_limit in [Some(1), None] {
+            let outcome = repo.author_stats(
+                Some(main),
+                None::<gix::ObjectId>,
+                gix::progress::Discard,
+                &AtomicBool::default(),
+                Options {
+                    line_stats: true,
+                    thread_limit,
+                },
+            )?;
+            assert_eq!(outcome.skipped_merge_commits, 1);
+            for author in &outcome.authors {
+                let lines = author.lines.expect("requested");
+                assert_eq!(
+                    (lines.files_changed, lines.insertions, lines.removals),
+                    (3, 3, 0),
+                    "each non-merge commit adds one line to a single file"
+                );
+            }
+        }
+
+        let base = repo.rev_parse_single("base")?.detach();
+        let outcome = repo.author_stats(
+            Some(main),
+            Some(base),
+            gix::progress::Discard,
+            &AtomicBool::default(),
+            Options::default(),
+        )?;
+        assert_eq!(outcome.num_commits, 4);
+        assert_eq!(
+            names_and_commits(&outcome.authors),
+            [
+                ("Joe <joe@example.com>".into(), 3),
+                ("Jane Doe <jane@example.com>".into(), 1)
+            ],
+            "commits reachable from hidden commits are ignored"
+        );
+        Ok(())
+    }
+}
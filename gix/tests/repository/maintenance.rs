@@ -0,0 +1,356 @@
+use std::{sync::atomic::AtomicBool, time::Duration};
+
+use gix::maintenance::{auto, lock, pack_loose_objects::Options, write_commit_graph};
+
+use crate::util::repo_rw;
+
+fn loose_objects(repo: &gix::Repository) -> usize {
+    gix::odb::loose::Store::at(repo.git_dir().join("objects"), repo.object_hash())
+        .iter()
+        .count()
+}
+
+fn pack_names(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+    let mut names: Vec<_> = std::fs::read_dir(repo.git_dir().join("objects").join("pack"))?
+        .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<_, _>>()?;
+    names.sort();
+    Ok(names)
+}
+
+#[test]
+fn pack_loose_objects_moves_all_loose_objects_into_a_new_pack() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let all_objects: Vec<_> = repo.objects.iter()?.collect::<Result<_, _>>()?;
+    assert_eq!(loose_objects(&repo), 3);
+
+    let outcome = repo.pack_loose_objects(gix::progress::Discard, &AtomicBool::default(), Options::default())?;
+    let pack = outcome.pack.expect("loose objects were packed");
+    assert_eq!(pack.index.num_objects, 3);
+    assert_eq!(outcome.deleted_loose_objects, 3);
+    assert_eq!(outcome.multi_pack_index_path, None, "there was no multi-pack index");
+    assert_eq!(loose_objects(&repo), 0, "all loose objects were deleted");
+    assert!(
+        !pack_names(&repo)?.iter().any(|name| name.ends_with(".keep")),
+        "the new pack isn't kept as it is already referenced"
+    );
+    assert_eq!(pack_names(&repo)?.len(), 4, "two packs with an index each");
+
+    for id in &all_objects {
+        assert!(repo.has_object(id), "{id} is still accessible through the new pack");
+    }
+
+    let outcome = repo.pack_loose_objects(gix::progress::Discard, &AtomicBool::default(), Options::default())?;
+    assert!(outcome.pack.is_none(), "without loose objects, there is nothing to do");
+    assert_eq!(outcome.deleted_loose_objects, 0);
+    Ok(())
+}
+
+#[test]
+fn pack_loose_objects_can_keep_loose_objects_and_write_a_multi_pack_index() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let outcome = repo.pack_loose_objects(
+        gix::progress::Discard,
+        &AtomicBool::default(),
+        Options {
+            thread_limit: Some(1),
+            compression_level: Some(0),
+            delete_loose_objects: false,
+            write_multi_pack_index: true,
+        },
+    )?;
+    assert_eq!(outcome.pack.expect("written").index.num_objects, 3);
+    assert_eq!(outcome.deleted_loose_objects, 0);
+    assert_eq!(loose_objects(&repo), 3, "loose objects were kept");
+
+    let midx_path = outcome.multi_pack_index_path.expect("multi-pack index was requested");
+    let midx = gix_pack::multi_index::File::at(&midx_path)?;
+    assert_eq!(midx.num_indices(), 2, "the existing and the new pack are included");
+    assert_eq!(
+        midx.num_objects(),
+        9,
+        "objects aren't duplicated in the multi-pack index"
+    );
+
+    std::fs::write(repo.git_dir().join("objects").join("pack").join("pack-extra.idx"), b"")?;
+    let err = repo
+        .pack_loose_objects(gix::progress::Discard, &AtomicBool::default(), Options::default())
+        .unwrap_err();
+    assert!(
+        matches!(err, gix::maintenance::pack_loose_objects::Error::WriteMultiPackIndex(_)),
+        "existing multi-pack indices are updated, and invalid indices make this fail: {err:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn auto_maintenance_packs_loose_objects_and_indexes_too_many_packs() -> crate::Result {
+    let (mut repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    assert!(
+        repo.auto_maintenance_reasons()?.is_empty(),
+        "the defaults are far from being reached"
+    );
+    assert!(matches!(
+        repo.auto_maintenance(gix::progress::Discard, &AtomicBool::default(), auto::Options::default())?,
+        auto::Outcome::NotNeeded
+    ));
+
+    // Only the fan-out directory `17` is used for the estimate, so make sure there are enough objects in it.
+    let mut in_fan_out_dir = 0;
+    let mut blob_index = 0;
+    while in_fan_out_dir < 2 {
+        let id = repo.write_blob(format!("{blob_index}"))?;
+        if id.as_bytes()[0] == 0x17 {
+            in_fan_out_dir += 1;
+        }
+        blob_index += 1;
+    }
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("gc", None, "auto", "1")?;
+        config.set_raw_value("gc", None, "autoPackLimit", "1")?;
+    }
+    assert_eq!(
+        repo.auto_maintenance_reasons()?,
+        auto::Reasons {
+            loose_objects: Some(2 * 256),
+            packs: None,
+        },
+        "the amount of loose objects is estimated, and a single pack isn't too many"
+    );
+
+    {
+        let _lock = repo.lock_for_maintenance(lock::Options::default())?;
+        let outcome =
+            repo.auto_maintenance(gix::progress::Discard, &AtomicBool::default(), auto::Options::default())?;
+        assert!(
+            matches!(outcome, auto::Outcome::AlreadyRunning { holder, .. } if holder.pid == std::process::id()),
+            "nothing is done while another maintenance run holds the lock"
+        );
+    }
+
+    let auto::Outcome::Performed { reasons, outcome } =
+        repo.auto_maintenance(gix::progress::Discard, &AtomicBool::default(), auto::Options::default())?
+    else {
+        unreachable!("maintenance isn't detached");
+    };
+    assert!(reasons.loose_objects.is_some());
+    assert_eq!(
+        outcome.pack.expect("loose objects were packed").index.num_objects as usize,
+        blob_index + 3
+    );
+    assert_eq!(outcome.multi_pack_index_path, None, "there weren't too many packs");
+    assert_eq!(loose_objects(&repo), 0);
+
+    assert_eq!(
+        repo.auto_maintenance_reasons()?,
+        auto::Reasons {
+            loose_objects: None,
+            packs: Some(2),
+        }
+    );
+    let auto::Outcome::Performed { outcome, .. } =
+        repo.auto_maintenance(gix::progress::Discard, &AtomicBool::default(), auto::Options::default())?
+    else {
+        unreachable!("maintenance isn't detached");
+    };
+    assert!(outcome.pack.is_none(), "there was nothing to pack");
+    assert!(
+        outcome.multi_pack_index_path.is_some(),
+        "too many packs are covered by a multi-pack index"
+    );
+    assert!(
+        repo.auto_maintenance_reasons()?.is_empty(),
+        "packs in the multi-pack index don't count"
+    );
+
+    repo.config_snapshot_mut().set_raw_value("gc", None, "auto", "0")?;
+    std::fs::write(repo.git_dir().join("objects").join("pack").join("pack-extra.pack"), b"")?;
+    assert!(
+        repo.auto_maintenance_reasons()?.is_empty(),
+        "setting `gc.auto` to 0 disables all checks"
+    );
+    Ok(())
+}
+
+#[test]
+fn lock_for_maintenance_fails_waits_or_steals_if_held() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let lock = repo.lock_for_maintenance(lock::Options::default())?;
+    assert_eq!(lock.path(), repo.common_dir().join("gc.pid"));
+    assert!(
+        !repo.common_dir().join("gc.pid.lock").exists(),
+        "like `git gc`, the lock file is only held while updating `gc.pid`, so `git gc` can read it and back off"
+    );
+    let holder = lock::Holder::from_bytes(&std::fs::read(lock.path())?).expect("valid");
+    assert_eq!(holder.pid, std::process::id(), "the file is compatible with `git gc`");
+
+    for policy in [lock::Policy::Fail, lock::Policy::Wait(Duration::from_millis(50))] {
+        let err = repo
+            .lock_for_maintenance(lock::Options {
+                policy,
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(
+            matches!(&err, lock::Error::Held { holder: h } if *h == holder),
+            "threads of the same process are coordinated as well: {err:?}"
+        );
+    }
+
+    let stolen = repo.lock_for_maintenance(lock::Options {
+        policy: lock::Policy::Steal,
+        ..Default::default()
+    })?;
+    drop(stolen);
+    assert!(!lock.path().exists(), "the lock is released on drop");
+    drop(lock);
+    Ok(())
+}
+
+#[test]
+fn lock_for_maintenance_is_taken_by_only_one_of_many_concurrent_lockers() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let repo = repo.into_sync();
+    let barrier = std::sync::Barrier::new(8);
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                scope.spawn(|| {
+                    let repo = repo.to_thread_local();
+                    barrier.wait();
+                    repo.lock_for_maintenance(lock::Options::default())
+                })
+            })
+            .collect();
+        threads.into_iter().map(|t| t.join().expect("no panic")).collect()
+    });
+    let (locks, errors): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+    assert_eq!(locks.len(), 1, "exactly one locker wins");
+    for err in errors.into_iter().map(Result::unwrap_err) {
+        assert!(
+            matches!(err, lock::Error::Held { .. }),
+            "the others see the holder instead of failing to obtain the lock file: {err:?}"
+        );
+    }
+
+    let path = repo.to_thread_local().common_dir().join("gc.pid");
+    assert!(path.is_file());
+    drop(locks);
+    assert!(!path.exists(), "the winner releases the lock");
+    Ok(())
+}
+
+#[test]
+fn lock_for_maintenance_is_not_released_by_the_holder_it_was_stolen_from() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let original = repo.lock_for_maintenance(lock::Options::default())?;
+    let stolen = repo.lock_for_maintenance(lock::Options {
+        policy: lock::Policy::Steal,
+        ..Default::default()
+    })?;
+    assert!(
+        !repo.common_dir().join("gc.pid.lock").exists(),
+        "stealing only replaces `gc.pid`"
+    );
+
+    drop(original);
+    assert!(
+        stolen.path().is_file(),
+        "the original holder doesn't remove the `gc.pid` file of the process that stole it"
+    );
+    assert!(
+        matches!(
+            repo.lock_for_maintenance(lock::Options::default()),
+            Err(lock::Error::Held { .. })
+        ),
+        "the lock is still held by the thief"
+    );
+
+    let path = stolen.path().to_owned();
+    drop(stolen);
+    assert!(!path.exists(), "the thief releases the lock");
+    let _lock = repo.lock_for_maintenance(lock::Options::default())?;
+    Ok(())
+}
+
+#[test]
+fn lock_for_maintenance_takes_over_stale_locks() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let path = repo.common_dir().join("gc.pid");
+    let own_hostname = {
+        let lock = repo.lock_for_maintenance(lock::Options::default())?;
+        lock::Holder::from_bytes(&std::fs::read(lock.path())?)
+            .expect("valid")
+            .hostname
+    };
+
+    if cfg!(target_os = "linux") {
+        std::fs::write(&path, format!("{} {own_hostname}", i32::MAX))?;
+        let lock = repo.lock_for_maintenance(lock::Options::default())?;
+        assert_eq!(
+            lock::Holder::from_bytes(&std::fs::read(lock.path())?).map(|h| h.pid),
+            Some(std::process::id()),
+            "the process holding the lock on this machine doesn't exist anymore"
+        );
+    }
+
+    std::fs::write(&path, "1 some-other-machine")?;
+    let err = repo.lock_for_maintenance(lock::Options::default()).unwrap_err();
+    assert!(
+        matches!(&err, lock::Error::Held { holder } if holder.hostname == "some-other-machine" && holder.pid == 1),
+        "processes on other machines are assumed to be alive: {err:?}"
+    );
+    let _lock = repo.lock_for_maintenance(lock::Options {
+        stale_after: Duration::ZERO,
+        ..Default::default()
+    })?;
+    assert_eq!(
+        lock::Holder::from_bytes(&std::fs::read(&path)?).map(|h| h.pid),
+        Some(std::process::id()),
+        "locks that are too old are taken over"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_commit_graph_replaces_the_existing_one() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_repo_with_fork_and_dates.sh")?;
+    let head = repo.head_id()?.detach();
+    assert!(
+        repo.commit_graph()?.commit_by_id(head).is_none(),
+        "the merge commit isn't part of the existing commit-graph"
+    );
+
+    let outcome = repo.write_commit_graph(
+        Some(head),
+        gix::progress::Discard,
+        &AtomicBool::default(),
+        write_commit_graph::Options { changed_paths: true },
+    )?;
+    assert_eq!(outcome.num_commits, 4, "all commits reachable from HEAD");
+    assert_eq!(
+        outcome.path,
+        repo.git_dir().join("objects").join("info").join("commit-graph")
+    );
+
+    let graph = repo.commit_graph()?;
+    graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    assert_eq!(graph.num_commits(), 4);
+    assert_eq!(graph.commit_by_id(head).expect("written").generation(), 3);
+
+    let tree = repo.head_commit()?.tree_id()?.detach();
+    let err = repo
+        .write_commit_graph(
+            Some(tree),
+            gix::progress::Discard,
+            &AtomicBool::default(),
+            write_commit_graph::Options::default(),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, write_commit_graph::Error::FindCommit(_)),
+        "tips must be commits: {err:?}"
+    );
+    Ok(())
+}
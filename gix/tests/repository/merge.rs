@@ -0,0 +1,575 @@
+use gix::{
+    bstr::ByteSlice,
+    merge::{commits::Strategy, plumbing::tree::ConflictKind},
+};
+
+use crate::util::{hex_to_id, repo_rw};
+
+fn labels() -> gix::merge::plumbing::blob::Labels<'static> {
+    gix::merge::plumbing::blob::Labels {
+        ancestor: None,
+        current: Some("main".into()),
+        other: Some("other".into()),
+    }
+}
+
+fn content_at(repo: &gix::Repository, tree: gix::ObjectId, path: &str) -> Option<String> {
+    let entry = repo
+        .find_object(tree)
+        .ok()?
+        .into_tree()
+        .peel_to_entry_by_path(path)
+        .ok()??;
+    Some(entry.object().ok()?.data.to_str().ok()?.to_owned())
+}
+
+#[test]
+fn merge_bases() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+    let id = |name: &str| repo.rev_parse_single(name).expect("valid name").detach();
+    assert_eq!(repo.merge_bases(id("main"), Some(id("conflicting")))?, [id("base")]);
+    assert_eq!(
+        repo.merge_bases(id("conflicting"), Some(id("clean")))?,
+        [id("base")],
+        "the order doesn't matter"
+    );
+    assert_eq!(repo.merge_bases(id("main"), Some(id("base")))?, [id("base")]);
+    assert!(repo.merge_bases(id("main"), Some(id("unrelated")))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn merge_commits_without_conflicts() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+    let id = |name: &str| repo.rev_parse_single(name).expect("valid name").detach();
+    let outcome = repo.merge_commits(id("main"), Some(id("clean")), labels(), Default::default())?;
+    assert!(!outcome.has_conflicts());
+    assert_eq!(outcome.merge_bases, [id("base")]);
+    assert_eq!(content_at(&repo, outcome.tree, "new").as_deref(), Some("new\n"));
+    assert_eq!(
+        content_at(&repo, outcome.tree, "numbers").as_deref(),
+        Some("one\n2\n3\n4\nfive by main\n6\n7\n8\n9\n")
+    );
+    Ok(())
+}
+
+#[test]
+fn merge_commits_with_conflicts() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+    let id = |name: &str| repo.rev_parse_single(name).expect("valid name").detach();
+    let outcome = repo.merge_commits(id("main"), Some(id("conflicting")), labels(), Default::default())?;
+    assert_eq!(outcome.conflicts.len(), 1);
+    let conflict = &outcome.conflicts[0];
+    assert_eq!(conflict.path, "numbers");
+    assert_eq!(conflict.kind, ConflictKind::Content);
+    assert!(conflict.ancestor.is_some() && conflict.ours.is_some() && conflict.theirs.is_some());
+    assert_eq!(
+        conflict.message(&labels()),
+        "CONFLICT (content): Merge conflict in numbers"
+    );
+    assert_eq!(
+        content_at(&repo, outcome.tree, "numbers").as_deref(),
+        Some("one\n2\n3\n4\n<<<<<<< main\nfive by main\n=======\nfive by conflicting\n>>>>>>> other\n6\n7\n8\n9\n")
+    );
+
+    let outcome = repo.merge_commits(id("main"), Some(id("unrelated")), labels(), Default::default())?;
+    assert!(outcome.merge_bases.is_empty());
+    assert!(
+        !outcome.has_conflicts(),
+        "unrelated histories merge as if the ancestor was empty"
+    );
+    assert_eq!(
+        content_at(&repo, outcome.tree, "unrelated").as_deref(),
+        Some("unrelated\n")
+    );
+    Ok(())
+}
+
+fn with_strategy(strategy: Strategy) -> gix::merge::commits::Options {
+    gix::merge::commits::Options {
+        strategy,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn merge_commits_with_octopus_strategy() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+    let id = |name: &str| repo.rev_parse_single(name).expect("valid name").detach();
+    let outcome = repo.merge_commits(
+        id("main"),
+        [id("clean"), id("clean-too"), id("base")],
+        labels(),
+        with_strategy(Strategy::Octopus),
+    )?;
+    assert!(!outcome.has_conflicts());
+    assert_eq!(
+        outcome.tree,
+        hex_to_id("955fee4e7562fe47e3de5bd01259c9805b1211f3"),
+        "the same tree as `git merge -s octopus clean clean-too` is produced, and merged commits are skipped"
+    );
+
+    let err = repo
+        .merge_commits(
+            id("main"),
+            [id("clean"), id("conflicting")],
+            labels(),
+            with_strategy(Strategy::Octopus),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, gix::merge::commits::Error::OctopusConflict { id: conflicting } if conflicting == id("conflicting")),
+        "octopus merges don't deal with conflicts"
+    );
+
+    let err = repo
+        .merge_commits(id("main"), [id("clean"), id("clean-too")], labels(), Default::default())
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        gix::merge::commits::Error::TooManyCommitsToMerge {
+            strategy: "ort",
+            count: 2
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn merge_commits_with_ours_strategy() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+    let id = |name: &str| repo.rev_parse_single(name).expect("valid name").detach();
+    let main = repo.find_object(id("main"))?.try_into_commit()?;
+    let outcome = repo.merge_commits(
+        id("main"),
+        [id("conflicting"), id("unrelated")],
+        labels(),
+        with_strategy(Strategy::Ours),
+    )?;
+    assert!(!outcome.has_conflicts());
+    assert_eq!(outcome.tree, main.tree_id()?, "changes of the other side are ignored");
+    Ok(())
+}
+
+#[test]
+fn merge_commits_with_subtree_strategy() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+    let id = |name: &str| repo.rev_parse_single(name).expect("valid name").detach();
+    for prefix in [None, Some("sub".into())] {
+        let outcome = repo.merge_commits(
+            id("with-subproject"),
+            Some(id("subproject")),
+            labels(),
+            with_strategy(Strategy::Subtree { prefix }),
+        )?;
+        assert!(!outcome.has_conflicts());
+        assert_eq!(
+            outcome.tree,
+            hex_to_id("63eec1cf34e6fc20a6c3715b76937e0328a283b0"),
+            "the same tree as `git merge -s subtree subproject` is produced"
+        );
+        assert_eq!(
+            content_at(&repo, outcome.tree, "sub/lib").as_deref(),
+            Some("one\n2\n3\n4\n5\n")
+        );
+    }
+    Ok(())
+}
+
+mod message {
+    use gix::merge::message::{Head, Options};
+
+    use crate::util::named_repo;
+
+    fn heads(repo: &gix::Repository, names: &[&str]) -> Vec<Head> {
+        names
+            .iter()
+            .map(|name| match repo.try_find_reference(*name).expect("valid name") {
+                Some(mut reference) => Head {
+                    id: reference.peel_to_id_in_place().expect("peelable").detach(),
+                    reference: Some(reference.name().to_owned()),
+                },
+                None => Head {
+                    id: repo.rev_parse_single(*name).expect("valid name").detach(),
+                    reference: None,
+                },
+            })
+            .collect()
+    }
+
+    fn message(repo: &gix::Repository, names: &[&str], options: Options) -> crate::Result<String> {
+        Ok(repo.merge_message(&heads(repo, names), options)?.to_string())
+    }
+
+    #[test]
+    fn title_groups_heads_by_kind() -> crate::Result {
+        let repo = named_repo("make_merge_commits_repo.sh")?;
+        assert_eq!(
+            message(&repo, &["clean"], Options::default())?,
+            "Merge branch 'clean'\n"
+        );
+        assert_eq!(
+            message(&repo, &["clean", "clean-too", "conflicting"], Options::default())?,
+            "Merge branches 'clean', 'clean-too' and 'conflicting'\n"
+        );
+        let subproject = repo.rev_parse_single("subproject")?;
+        assert_eq!(
+            message(
+                &repo,
+                &["clean", "clean-too", "base", &subproject.to_string()],
+                Options::default()
+            )?,
+            format!("Merge branches 'clean' and 'clean-too', tag 'base', commit '{subproject}'\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn destination_is_added_unless_suppressed() -> crate::Result {
+        let repo = named_repo("make_merge_commits_repo.sh")?;
+        let suppress = |patterns: &[&str]| Options {
+            suppress_destinations: Some(patterns.iter().map(|p| (*p).into()).collect()),
+            ..Default::default()
+        };
+        assert_eq!(
+            message(&repo, &["clean"], suppress(&[]))?,
+            "Merge branch 'clean' into main\n"
+        );
+        assert_eq!(
+            message(&repo, &["clean"], suppress(&["ma*"]))?,
+            "Merge branch 'clean'\n",
+            "patterns are globs"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn log_lists_the_summaries_of_new_commits() -> crate::Result {
+        let repo = named_repo("make_merge_commits_repo.sh")?;
+        let log = |limit: usize| Options {
+            log: Some(limit),
+            ..Default::default()
+        };
+        assert_eq!(
+            message(&repo, &["clean", "base"], log(20))?,
+            "Merge branch 'clean', tag 'base'\n\n* clean:\n  add a file\n",
+            "heads that are already merged are omitted"
+        );
+        assert_eq!(
+            message(&repo, &["subproject"], log(1))?,
+            "Merge branch 'subproject'\n\n* subproject: (2 commits)\n  change subproject\n  ...\n",
+            "unrelated histories are listed entirely, up to the limit"
+        );
+        assert_eq!(
+            message(&repo, &["with-subproject"], log(1))?,
+            "Merge branch 'with-subproject'\n\n* with-subproject: (2 commits)\n  subproject\n",
+            "merge commits are counted but not listed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn options_from_configuration() -> crate::Result {
+        let mut repo = named_repo("make_merge_commits_repo.sh")?;
+        assert_eq!(repo.merge_message_options()?, Options::default());
+
+        for (value, expected) in [("true", Some(20)), ("3", Some(3)), ("false", None), ("0", None)] {
+            repo.config_snapshot_mut()
+                .append_config([format!("merge.log={value}").as_str()], gix::config::Source::Api)?;
+            assert_eq!(repo.merge_message_options()?.log, expected, "{value}");
+        }
+        repo.config_snapshot_mut()
+            .append_config(["merge.log=sometimes"], gix::config::Source::Api)?;
+        assert!(repo.merge_message_options().is_err());
+
+        repo.config_snapshot_mut().append_config(
+            [
+                "merge.log=1",
+                "merge.suppressDest=main",
+                "merge.suppressDest=",
+                "merge.suppressDest=topic/*",
+            ],
+            gix::config::Source::Api,
+        )?;
+        assert_eq!(
+            repo.merge_message_options()?,
+            Options {
+                log: Some(1),
+                suppress_destinations: Some(vec!["topic/*".into()]),
+            },
+            "empty values clear the list of patterns"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "worktree-mutation")]
+mod worktree {
+    use gix::{
+        index::conflict::Resolution,
+        merge::worktree::{Error, Options, Outcome},
+    };
+
+    use super::labels;
+    use crate::util::repo_rw;
+
+    #[allow(clippy::result_large_err)]
+    fn merge(repo: &gix::Repository, theirs: &str) -> Result<Outcome, Error> {
+        let id = repo.rev_parse_single(theirs).expect("valid name").detach();
+        repo.merge_into_worktree(Some(id), labels(), "merge it", Options::default())
+    }
+
+    #[test]
+    fn without_conflicts_creates_a_merge_commit() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        let Outcome::Merged { commit } = merge(&repo, "clean")? else {
+            panic!("expected a merge commit")
+        };
+        let commit = repo.find_object(commit)?.into_commit();
+        assert_eq!(
+            commit.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(),
+            [head, repo.rev_parse_single("clean")?.detach()]
+        );
+        assert_eq!(commit.message_raw()?, "merge it");
+        assert_eq!(repo.head_id()?, commit.id);
+        let workdir = repo.work_dir().expect("non-bare");
+        assert_eq!(std::fs::read(workdir.join("new"))?, b"new\n");
+        assert!(repo.open_index()?.entry_by_path("new".into()).is_some());
+        assert!(repo.state().is_none());
+
+        assert!(matches!(merge(&repo, "clean")?, Outcome::UpToDate));
+        Ok(())
+    }
+
+    #[test]
+    fn fast_forward() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let main = repo.head_id()?.detach();
+        let mut reference = repo.find_reference("main")?;
+        reference.set_target_id(repo.rev_parse_single("base")?, "reset")?;
+        std::fs::write(
+            repo.work_dir().expect("non-bare").join("numbers"),
+            "1\n2\n3\n4\n5\n6\n7\n8\n9\n",
+        )?;
+        let mut index = repo.index_from_tree(&repo.rev_parse_single("base^{tree}")?)?;
+        index.write(Default::default())?;
+
+        assert!(matches!(merge(&repo, "main@{1}")?, Outcome::FastForward { commit } if commit == main));
+        assert_eq!(repo.head_id()?, main);
+        assert_eq!(
+            std::fs::read(repo.work_dir().expect("non-bare").join("numbers"))?,
+            b"one\n2\n3\n4\nfive by main\n6\n7\n8\n9\n"
+        );
+        assert_eq!(
+            repo.find_reference("ORIG_HEAD")?.id(),
+            repo.rev_parse_single("base")?,
+            "the previous HEAD is remembered"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn files_are_compared_in_their_git_form() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let main = repo.head_id()?.detach();
+        let mut reference = repo.find_reference("main")?;
+        reference.set_target_id(repo.rev_parse_single("base")?, "reset")?;
+        std::fs::create_dir_all(repo.git_dir().join("info"))?;
+        std::fs::write(
+            repo.git_dir().join("info").join("attributes"),
+            "numbers text eol=crlf\n",
+        )?;
+        std::fs::write(
+            repo.work_dir().expect("non-bare").join("numbers"),
+            "1\r\n2\r\n3\r\n4\r\n5\r\n6\r\n7\r\n8\r\n9\r\n",
+        )?;
+        let mut index = repo.index_from_tree(&repo.rev_parse_single("base^{tree}")?)?;
+        index.write(Default::default())?;
+
+        assert!(
+            matches!(merge(&repo, "main@{1}")?, Outcome::FastForward { commit } if commit == main),
+            "files with line endings that are converted by attributes aren't local changes"
+        );
+        assert_eq!(
+            std::fs::read(repo.work_dir().expect("non-bare").join("numbers"))?,
+            b"one\r\n2\r\n3\r\n4\r\nfive by main\r\n6\r\n7\r\n8\r\n9\r\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn with_conflicts_writes_the_merge_state_and_can_be_continued() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        let theirs = repo.rev_parse_single("conflicting")?.detach();
+        let Outcome::Conflicts { conflicts } = merge(&repo, "conflicting")? else {
+            panic!("expected conflicts")
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(repo.state(), Some(gix::state::InProgress::Merge));
+        assert_eq!(
+            std::fs::read(repo.git_dir().join("MERGE_HEAD"))?,
+            format!("{theirs}\n").as_bytes()
+        );
+        assert_eq!(
+            std::fs::read(repo.git_dir().join("MERGE_MSG"))?,
+            b"merge it\n\n# Conflicts:\n#\tnumbers\n"
+        );
+        let index = repo.open_index()?;
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .map(|entry| (entry.path(&index).to_string(), entry.stage()))
+                .collect::<Vec<_>>(),
+            [
+                ("file".into(), 0),
+                ("numbers".into(), 1),
+                ("numbers".into(), 2),
+                ("numbers".into(), 3)
+            ]
+        );
+        let workdir = repo.work_dir().expect("non-bare");
+        assert_eq!(
+            std::fs::read(workdir.join("numbers"))?,
+            b"one\n2\n3\n4\n<<<<<<< main\nfive by main\n=======\nfive by conflicting\n>>>>>>> other\n6\n7\n8\n9\n"
+        );
+        assert!(matches!(merge(&repo, "clean"), Err(Error::MergeInProgress)));
+        assert!(matches!(
+            repo.merge_continue(),
+            Err(Error::UnresolvedConflicts { paths }) if paths == ["numbers"]
+        ));
+
+        let resolved = b"one\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+        std::fs::write(workdir.join("numbers"), resolved)?;
+        let mut index = repo.open_index()?;
+        let blob = repo.write_blob(resolved)?.detach();
+        let mode = index
+            .conflict_by_path("numbers".into())
+            .and_then(|conflict| conflict.ours)
+            .expect("present")
+            .mode;
+        index.resolve_conflict("numbers".into(), Resolution::Merged { id: blob, mode })?;
+        index.write(Default::default())?;
+
+        let commit = repo.merge_continue()?;
+        assert_eq!(repo.head_id()?, commit);
+        let commit = commit.object()?.into_commit();
+        assert_eq!(
+            commit.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(),
+            [head, theirs]
+        );
+        assert_eq!(commit.message_raw()?, "merge it\n");
+        assert_eq!(
+            super::content_at(&repo, commit.tree_id()?.detach(), "numbers").as_deref(),
+            Some("one\n2\n3\n4\nfive\n6\n7\n8\n9\n")
+        );
+        assert!(repo.state().is_none());
+        assert!(!repo.git_dir().join("MERGE_HEAD").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn with_conflicts_can_be_aborted() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        assert!(matches!(merge(&repo, "conflicting")?, Outcome::Conflicts { .. }));
+        repo.merge_abort()?;
+        assert_eq!(repo.head_id()?, head);
+        assert!(repo.state().is_none());
+        assert_eq!(
+            std::fs::read(repo.work_dir().expect("non-bare").join("numbers"))?,
+            b"one\n2\n3\n4\nfive by main\n6\n7\n8\n9\n"
+        );
+        let index = repo.open_index()?;
+        assert!(index.entries().iter().all(|entry| entry.stage() == 0));
+        assert!(matches!(repo.merge_abort(), Err(Error::NoMergeInProgress)));
+        Ok(())
+    }
+
+    #[test]
+    fn files_and_directories_can_replace_each_other() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let workdir = repo.work_dir().expect("non-bare");
+        assert!(matches!(merge(&repo, "file-to-dir")?, Outcome::FastForward { .. }));
+        assert_eq!(std::fs::read(workdir.join("file").join("inner"))?, b"inner\n");
+
+        assert!(matches!(merge(&repo, "dir-to-file")?, Outcome::FastForward { .. }));
+        assert_eq!(std::fs::read(workdir.join("file"))?, b"file again\n");
+        let index = repo.open_index()?;
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .map(|entry| entry.path(&index).to_string())
+                .collect::<Vec<_>>(),
+            ["file", "numbers"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn untracked_files_prevent_replacing_their_directory_with_a_file() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let workdir = repo.work_dir().expect("non-bare");
+        assert!(matches!(merge(&repo, "file-to-dir")?, Outcome::FastForward { .. }));
+        let untracked = workdir.join("file").join("untracked");
+        std::fs::write(&untracked, "precious\n")?;
+
+        let head = repo.head_id()?;
+        assert!(matches!(
+            merge(&repo, "dir-to-file"),
+            Err(Error::LocalChanges { path }) if path == "file"
+        ));
+        assert_eq!(repo.head_id()?, head);
+        assert_eq!(
+            std::fs::read(&untracked)?,
+            b"precious\n",
+            "the untracked file is still present"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn local_changes_prevent_the_merge() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        std::fs::write(repo.work_dir().expect("non-bare").join("numbers"), "changed\n")?;
+        assert!(matches!(
+            merge(&repo, "conflicting"),
+            Err(Error::LocalChanges { path }) if path == "numbers"
+        ));
+        assert_eq!(repo.head_id()?, head);
+        assert!(repo.state().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn staged_changes_prevent_the_merge_so_aborting_cannot_lose_them() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_commits_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        std::fs::write(repo.work_dir().expect("non-bare").join("file"), "staged\n")?;
+        let staged = repo.write_blob("staged\n")?.detach();
+        let mut index = repo.open_index()?;
+        index.entry_mut_by_path_and_stage("file".into(), 0).expect("tracked").id = staged;
+        index.write(Default::default())?;
+
+        assert!(matches!(
+            merge(&repo, "conflicting"),
+            Err(Error::LocalChanges { path }) if path == "file"
+        ));
+        assert_eq!(repo.head_id()?, head);
+        assert!(repo.state().is_none());
+        assert!(matches!(repo.merge_abort(), Err(Error::NoMergeInProgress)));
+        assert_eq!(
+            repo.open_index()?
+                .entry_by_path_and_stage("file".into(), 0)
+                .expect("still tracked")
+                .id,
+            staged,
+            "the unrelated staged change is still present"
+        );
+        Ok(())
+    }
+}
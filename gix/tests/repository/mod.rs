@@ -3,6 +3,11 @@ use gix::Repository;
 mod config;
 #[cfg(feature = "attributes")]
 mod filter;
+mod history;
+#[cfg(feature = "maintenance")]
+mod maintenance;
+#[cfg(feature = "merge")]
+mod merge;
 mod object;
 mod open;
 #[cfg(feature = "attributes")]
@@ -10,6 +15,8 @@ mod pathspec;
 mod reference;
 mod remote;
 mod shallow;
+#[cfg(feature = "excludes")]
+mod sparse;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
@@ -54,6 +54,102 @@ mod write_blob {
     }
 }
 
+mod object_memory {
+    use crate::repository::object::empty_bare_repo;
+
+    fn num_loose_objects(repo: &gix::Repository) -> usize {
+        gix::odb::loose::Store::at(repo.git_dir().join("objects"), repo.object_hash())
+            .iter()
+            .count()
+    }
+
+    #[test]
+    fn written_objects_are_visible_but_not_persisted() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        let mut repo = repo.with_object_memory();
+        let blob = repo.write_blob(b"hello world")?.detach();
+        let tree = repo.write_object(gix::objs::Tree {
+            entries: vec![gix::objs::tree::Entry {
+                mode: gix::objs::tree::EntryKind::Blob.into(),
+                filename: "file".into(),
+                oid: blob,
+            }],
+        })?;
+        assert_eq!(
+            tree.object()?.into_tree().find_entry("file").expect("present").oid(),
+            blob
+        );
+        assert!(repo.clone().has_object(blob), "clones keep the objects in memory");
+        let pathspec = repo
+            .pathspec(
+                Some("file"),
+                false,
+                &gix::index::State::new(repo.object_hash()),
+                gix::worktree::stack::state::attributes::Source::IdMapping,
+            )?
+            .detach()?;
+        assert_eq!(
+            pathspec.odb.num_objects_in_memory(),
+            2,
+            "detached pathspecs can still see objects in memory"
+        );
+        assert_eq!(num_loose_objects(&repo), 0, "nothing was written to disk");
+
+        let memory = repo.take_object_memory().expect("enabled");
+        assert_eq!(memory.len(), 2);
+        assert!(!repo.has_object(blob), "objects in memory are gone");
+        repo.write_blob(b"hello world")?;
+        assert_eq!(num_loose_objects(&repo), 1, "now objects are written to disk");
+        Ok(())
+    }
+
+    #[test]
+    fn references_are_still_written_to_disk() -> crate::Result {
+        let (tmp, repo) = empty_bare_repo()?;
+        let mut repo = repo.with_object_memory();
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_raw_value("user", None, "name", "name")?;
+            config.set_raw_value("user", None, "email", "name@example.com")?;
+        }
+        let tree = repo.write_object(gix::objs::Tree::empty())?;
+        let commit = repo.commit("refs/heads/main", "simulated", tree, gix::commit::NO_PARENT_IDS)?;
+        assert_eq!(num_loose_objects(&repo), 0, "objects are kept in memory");
+
+        let on_disk = gix::open_opts(tmp.path(), gix::open::Options::isolated())?;
+        assert_eq!(
+            on_disk.find_reference("refs/heads/main")?.id(),
+            commit,
+            "the reference was written to disk"
+        );
+        assert!(
+            !on_disk.has_object(commit),
+            "but it points to an object that only existed in memory"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_enabled_when_opening() -> crate::Result {
+        let (tmp, _repo) = empty_bare_repo()?;
+        let repo = gix::open_opts(
+            tmp.path(),
+            gix::open::Options::isolated()
+                .object_memory(true)
+                .config_overrides(["user.name=overlay"]),
+        )?;
+        let blob = repo.write_blob(b"hello world")?;
+        assert!(repo.has_object(blob));
+        assert_eq!(num_loose_objects(&repo), 0);
+        assert_eq!(
+            repo.config_snapshot().string("user.name").expect("set").as_ref(),
+            "overlay",
+            "configuration can be changed in memory as well"
+        );
+        Ok(())
+    }
+}
+
 #[test]
 fn writes_avoid_io_using_duplicate_check() -> crate::Result {
     let repo = crate::named_repo("make_packed_and_loose.sh")?;
@@ -108,6 +204,33 @@ fn writes_avoid_io_using_duplicate_check() -> crate::Result {
     Ok(())
 }
 
+mod shorten_ids {
+    use gix::prelude::ObjectIdExt;
+
+    #[test]
+    fn yields_the_same_prefixes_as_shortening_each_id() -> crate::Result {
+        let repo = crate::named_repo("make_packed_and_loose.sh")?;
+        let ids: Vec<_> = repo.objects.iter()?.map(Result::unwrap).collect();
+        assert!(!ids.is_empty());
+
+        let expected = ids
+            .iter()
+            .map(|id| id.attach(&repo).shorten())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(repo.shorten_ids(ids.iter().copied())?, expected);
+
+        let null = gix::ObjectId::null(repo.object_hash());
+        assert!(
+            matches!(
+                repo.shorten_ids(Some(null)),
+                Err(gix::id::shorten::Error::NotFound { oid }) if oid == null
+            ),
+            "objects that don't exist can't be shortened"
+        );
+        Ok(())
+    }
+}
+
 mod find {
     use gix_pack::Find;
 
@@ -234,6 +234,100 @@ mod object_caches {
     }
 }
 
+mod object_replacements_and_mailmap {
+    fn open(opts: gix::open::Options) -> crate::Result<gix::Repository> {
+        let path = gix_testtools::scripted_fixture_read_only("make_replaced_history.sh")?;
+        Ok(gix::open_opts(path, opts)?)
+    }
+
+    fn num_commits(repo: &gix::Repository, use_object_replacements: Option<bool>) -> crate::Result<usize> {
+        Ok(repo
+            .head_id()?
+            .ancestors()
+            .use_object_replacements(use_object_replacements)
+            .all()?
+            .count())
+    }
+
+    #[test]
+    fn are_used_by_default_and_can_be_disabled_per_call() -> crate::Result {
+        let repo = open(crate::restricted())?;
+        assert_eq!(num_commits(&repo, None)?, 2, "the replaced commit has no parent");
+        assert_eq!(num_commits(&repo, Some(false))?, 3, "the raw history is visible");
+        assert_eq!(num_commits(&repo.clone(), None)?, 2, "clones retain the setting");
+        assert_eq!(repo.open_mailmap().entries().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_disabled_when_opening_and_enabled_per_call() -> crate::Result {
+        let repo = open(crate::restricted().object_replacements(false).mailmap(false))?;
+        assert_eq!(num_commits(&repo, None)?, 3, "the raw history is visible");
+        assert_eq!(num_commits(&repo, Some(true))?, 2, "the replaced commit has no parent");
+        assert_eq!(num_commits(&repo.clone(), None)?, 3, "clones retain the setting");
+
+        assert!(repo.open_mailmap().entries().is_empty(), "the mailmap is disabled");
+        let mut mailmap = gix::mailmap::Snapshot::default();
+        repo.open_mailmap_into(&mut mailmap)?;
+        assert_eq!(mailmap.entries().len(), 1, "it can still be loaded explicitly");
+        Ok(())
+    }
+}
+
+mod object_directories_and_index_file {
+    use gix_testtools::tempfile;
+
+    use crate::util::basic_repo;
+
+    fn open_with_override(
+        git_dir: &std::path::Path,
+        key: &str,
+        value: &std::path::Path,
+    ) -> crate::Result<gix::Repository> {
+        Ok(gix::open_opts(
+            git_dir,
+            crate::restricted().config_overrides([format!("{key}={}", value.display())]),
+        )?)
+    }
+
+    #[test]
+    fn can_be_overridden_by_configuration() -> crate::Result {
+        let basic = basic_repo()?;
+        let head = basic.head_id()?.detach();
+        let objects_dir = std::env::current_dir()?.join(basic.objects.store_ref().path());
+        let tmp = tempfile::tempdir()?;
+        let empty = gix::init_bare(tmp.path())?;
+        assert!(!empty.has_object(head));
+
+        let repo = open_with_override(empty.git_dir(), "gitoxide.objects.directory", &objects_dir)?;
+        assert_eq!(repo.objects.store_ref().path(), objects_dir);
+        assert!(repo.has_object(head), "objects are read from the configured directory");
+
+        let repo = open_with_override(empty.git_dir(), "gitoxide.objects.alternateDirectories", &objects_dir)?;
+        assert_eq!(repo.objects.store_ref().path(), empty.git_dir().join("objects"));
+        assert_eq!(repo.objects.store_ref().alternate_db_paths()?, [objects_dir]);
+        assert!(repo.has_object(head), "objects are read from the alternate");
+
+        let index_path = std::env::current_dir()?.join(basic.index_path());
+        let repo = open_with_override(empty.git_dir(), "gitoxide.core.indexFile", &index_path)?;
+        assert_eq!(repo.index_path(), index_path);
+        assert_eq!(
+            repo.open_index()?.entries().len(),
+            basic.open_index()?.entries().len(),
+            "the index is read from the configured location"
+        );
+        let index_path = tmp.path().join("index");
+        let repo = open_with_override(basic.git_dir(), "gitoxide.core.indexFile", &index_path)?;
+        let tree = repo.head_commit()?.tree_id()?;
+        assert_eq!(
+            repo.index_from_tree(&tree)?.path(),
+            index_path,
+            "indices created from trees are written to the configured location as well"
+        );
+        Ok(())
+    }
+}
+
 mod worktree {
     use gix::open;
 
@@ -37,3 +37,44 @@ fn defaults_are_taken_from_repo_config() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn set_index_entry_flags() -> crate::Result {
+    use gix::index::entry::Flags;
+    let repo = crate::util::named_subrepo_opts("make_sparse_checkout.sh", "disabled", crate::restricted())?;
+    let mut index = repo.open_index()?;
+    let mut pathspec = repo.pathspec(
+        ["included", ":!*/b"],
+        true,
+        &index,
+        Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+    )?;
+
+    let flags = Flags::ASSUME_VALID | Flags::SKIP_WORKTREE;
+    assert_eq!(pathspec.set_index_entry_flags(&mut index, flags, true), 1);
+    assert_eq!(
+        pathspec.set_index_entry_flags(&mut index, flags, true),
+        0,
+        "entries that already have the flags aren't counted"
+    );
+    let paths_with_flags = |index: &gix::index::State| {
+        index
+            .entries()
+            .iter()
+            .filter(|e| e.flags.contains(flags))
+            .map(|e| e.path(index).to_str_lossy().into_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(paths_with_flags(&index), ["included/a"]);
+
+    assert_eq!(
+        pathspec.set_index_entry_flags(&mut index, Flags::SKIP_WORKTREE, false),
+        1
+    );
+    assert_eq!(paths_with_flags(&index), Vec::<String>::new());
+    assert!(
+        index.entries().iter().any(|e| e.flags.contains(Flags::ASSUME_VALID)),
+        "only the given flags are removed"
+    );
+    Ok(())
+}
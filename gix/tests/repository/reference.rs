@@ -223,4 +223,254 @@ mod head {
         assert!(head.referent_name().is_none());
         Ok(())
     }
+
+    #[test]
+    fn rename_unborn() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo = gix::init(tmp.path())?;
+        assert_eq!(repo.rename_unborn_head("trunk".into())?.as_bstr(), "refs/heads/trunk");
+        assert_eq!(repo.head_name()?.expect("unborn").as_bstr(), "refs/heads/trunk");
+        assert_eq!(
+            repo.rename_unborn_head("refs/heads/main".into())?.as_bstr(),
+            "refs/heads/main",
+            "full names are supported as well"
+        );
+        assert!(
+            repo.rename_unborn_head("invalid..name".into()).is_err(),
+            "branch names are validated"
+        );
+
+        let repo = crate::basic_repo()?;
+        assert!(
+            matches!(
+                repo.rename_unborn_head("other".into()),
+                Err(gix::reference::rename_unborn_head::Error::NotUnborn)
+            ),
+            "existing branches can't be renamed"
+        );
+        Ok(())
+    }
+}
+
+mod delete_branch {
+    use gix::reference::delete_branch::Error;
+
+    fn repo_rw() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let tmp = gix_testtools::scripted_fixture_writable("make_branch_deletion.sh")?;
+        let repo = gix::open_opts(tmp.path().join("repo"), crate::restricted())?;
+        Ok((repo, tmp))
+    }
+
+    #[test]
+    fn merged_branches_are_deleted_along_with_their_configuration() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        assert!(repo.branch_names().contains("merged"));
+        let id = repo.delete_branch("merged".into(), false)?;
+        assert_eq!(id, repo.rev_parse_single("main~1")?.detach());
+        assert!(repo.try_find_reference("refs/heads/merged")?.is_none());
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        assert!(
+            !repo.branch_names().contains("merged"),
+            "the branch section was removed from the configuration file"
+        );
+
+        assert!(
+            matches!(repo.delete_branch("merged".into(), true), Err(Error::NotFound { .. })),
+            "deleting twice fails"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn branches_must_be_merged_into_their_upstream_or_head_unless_forced() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        assert!(matches!(
+            repo.delete_branch("unmerged".into(), false),
+            Err(Error::NotMerged { target, .. }) if target.as_bstr() == "HEAD"
+        ));
+        assert!(repo.branch_names().contains("tracking"), "the upstream is configured");
+        repo.delete_branch("refs/heads/tracking".into(), false)
+            .expect("merged into its upstream, even though it's not merged into HEAD");
+
+        repo.delete_branch("unmerged".into(), true)?;
+        assert!(repo.try_find_reference("unmerged")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn branches_in_use_by_any_worktree_are_never_deleted() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        for (name, head) in [("main", "main-worktree/HEAD"), ("in-worktree", "worktrees/wt/HEAD")] {
+            assert!(matches!(
+                repo.delete_branch(name.into(), true),
+                Err(Error::InUse { head: actual, .. }) if actual.as_bstr() == head
+            ));
+        }
+        assert!(repo.try_find_reference("in-worktree")?.is_some());
+        Ok(())
+    }
+}
+
+mod rename_branch {
+    use gix::reference::rename_branch::Error;
+
+    fn repo_rw() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let tmp = gix_testtools::scripted_fixture_writable("make_branch_deletion.sh")?;
+        let repo = gix::open_opts(tmp.path().join("repo"), crate::restricted())?;
+        Ok((repo, tmp))
+    }
+
+    fn reflog_messages(repo: &gix::Repository, name: &str) -> crate::Result<Vec<String>> {
+        let reference = repo.find_reference(name)?;
+        let mut log = reference.log_iter();
+        Ok(log
+            .all()?
+            .expect("present")
+            .map(|line| line.map(|line| line.message.to_string()))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn the_reflog_and_configuration_move_with_the_branch() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        let id = repo.find_reference("merged")?.id().detach();
+        let previous_log = reflog_messages(&repo, "merged")?;
+        assert!(!previous_log.is_empty(), "the fixture writes reflogs");
+
+        repo.rename_branch("merged".into(), "refs/heads/renamed".into(), false)?;
+        assert!(repo.try_find_reference("refs/heads/merged")?.is_none());
+        assert_eq!(repo.find_reference("renamed")?.id(), id);
+        let mut expected_log = previous_log;
+        expected_log.push("Branch: renamed refs/heads/merged to refs/heads/renamed".into());
+        assert_eq!(
+            reflog_messages(&repo, "renamed")?,
+            expected_log,
+            "the previous reflog is kept and the rename is logged"
+        );
+        assert!(!repo.git_dir().join("logs/refs/heads/merged").exists());
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        let config = repo.config_snapshot();
+        assert_eq!(
+            config.string("branch.renamed.description").as_deref(),
+            Some("merged into HEAD".into()),
+            "the branch section was renamed in the configuration file"
+        );
+        assert!(config.string("branch.merged.description").is_none());
+
+        assert!(
+            matches!(
+                repo.rename_branch("merged".into(), "other".into(), false),
+                Err(Error::NotFound { .. })
+            ),
+            "the old branch is gone"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn worktrees_that_have_the_branch_checked_out_are_updated() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        repo.rename_branch("in-worktree".into(), "moved".into(), false)?;
+        repo.rename_branch("main".into(), "trunk".into(), false)?;
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/trunk");
+        let worktree = repo
+            .worktrees()?
+            .pop()
+            .expect("one linked worktree")
+            .into_repo_with_possibly_inaccessible_worktree()?;
+        assert_eq!(
+            worktree.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/moved"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn existing_branches_are_only_overwritten_if_forced_and_not_in_use() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        assert!(matches!(
+            repo.rename_branch("merged".into(), "unmerged".into(), false),
+            Err(Error::AlreadyExists { name }) if name.as_bstr() == "refs/heads/unmerged"
+        ));
+        assert!(matches!(
+            repo.rename_branch("merged".into(), "in-worktree".into(), true),
+            Err(Error::InUse { head, .. }) if head.as_bstr() == "worktrees/wt/HEAD"
+        ));
+
+        let id = repo.find_reference("merged")?.id().detach();
+        repo.rename_branch("merged".into(), "unmerged".into(), true)?;
+        assert_eq!(repo.find_reference("unmerged")?.id(), id, "the branch was overwritten");
+        assert!(repo.try_find_reference("merged")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn branches_that_are_rebased_or_bisected_are_not_renamed() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        let rebase_dir = repo.git_dir().join("rebase-merge");
+        std::fs::create_dir(&rebase_dir)?;
+        std::fs::write(rebase_dir.join("head-name"), "refs/heads/merged\n")?;
+        assert!(matches!(
+            repo.rename_branch("merged".into(), "renamed".into(), false),
+            Err(Error::InUse { name, head }) if name.as_bstr() == "refs/heads/merged" && head.as_bstr() == "main-worktree/HEAD"
+        ));
+        std::fs::remove_dir_all(rebase_dir)?;
+
+        std::fs::write(repo.git_dir().join("BISECT_START"), "merged\n")?;
+        assert!(matches!(
+            repo.rename_branch("merged".into(), "renamed".into(), false),
+            Err(Error::InUse { .. })
+        ));
+        assert!(repo.try_find_reference("merged")?.is_some(), "nothing was renamed");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "revision")]
+mod list_references {
+    use gix::reference::list::{Format, Options, SortKey};
+
+    #[test]
+    fn filter_sort_and_format() -> crate::Result {
+        let repo = crate::named_repo("make_references_repo.sh")?;
+        let format = Format::parse("%(HEAD)%(refname:short) %(objecttype)".into())?;
+        let list = |options: &Options| -> crate::Result<Vec<String>> {
+            Ok(repo
+                .list_references(options)?
+                .iter()
+                .map(|entry| entry.format(&format, &repo).map(|line| line.to_string()))
+                .collect::<Result<_, _>>()?)
+        };
+
+        assert_eq!(
+            list(&Options {
+                patterns: vec!["refs/heads".into(), "refs/tags/d*".into()],
+                sort: vec![SortKey::parse("-refname".into())?],
+                ..Default::default()
+            })?,
+            [
+                " dt1 tag",
+                " multi-link-target1 commit",
+                "*main commit",
+                " dt1 commit",
+                " d1 commit"
+            ],
+            "symbolic references are followed, and sorting is descending"
+        );
+
+        let tag = repo.find_reference("refs/tags/dt1")?.id().detach();
+        assert_eq!(
+            list(&Options {
+                points_at: Some(tag),
+                ..Default::default()
+            })?,
+            [" dt1 tag"],
+            "only the tag object itself points at the tag"
+        );
+        Ok(())
+    }
 }
@@ -331,6 +331,31 @@ mod find_fetch_remote {
     }
 }
 
+mod promisor_remote_names {
+    use crate::remote;
+
+    #[test]
+    fn in_configuration_order_with_partial_clone_remote_last() -> crate::Result {
+        let mut repo = remote::repo("base");
+        assert!(repo.promisor_remote_names()?.is_empty(), "not a partial clone");
+
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("remote", Some("origin".into()), "promisor", "true")?;
+        config.set_raw_value("remote", Some("b".into()), "partialCloneFilter", "blob:none")?;
+        config.set_raw_value("remote", Some("c".into()), "promisor", "false")?;
+        config.set_raw_value("remote", Some("a".into()), "promisor", "true")?;
+        config.set_raw_value("extensions", None, "partialClone", "origin")?;
+        drop(config);
+
+        assert_eq!(
+            repo.promisor_remote_names()?,
+            ["b", "a", "origin"],
+            "remotes with a filter are promisors as well, and the one from the clone goes last"
+        );
+        Ok(())
+    }
+}
+
 mod find_default_remote {
 
     use crate::remote;
@@ -350,3 +375,124 @@ mod find_default_remote {
         Ok(())
     }
 }
+
+mod rename_remote {
+    use gix::remote::rename::Error;
+
+    fn repo_rw() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_edits.sh")?;
+        let repo = gix::open_opts(tmp.path().join("repo"), crate::restricted())?;
+        Ok((repo, tmp))
+    }
+
+    fn reference_names(repo: &gix::Repository, prefix: &str) -> crate::Result<Vec<String>> {
+        repo.references()?
+            .prefixed(prefix)?
+            .map(|r| r.map(|r| r.name().as_bstr().to_string()))
+            .collect::<Result<_, _>>()
+    }
+
+    #[test]
+    fn refspecs_branches_and_tracking_references_are_moved() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        let main_id = repo.find_reference("refs/remotes/origin/main")?.id().detach();
+        repo.rename_remote("origin", "renamed")?;
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        let remote = repo.find_remote("renamed")?;
+        assert_eq!(
+            remote
+                .refspecs(gix::remote::Direction::Fetch)
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring().to_string())
+                .collect::<Vec<_>>(),
+            ["+refs/heads/*:refs/remotes/renamed/*", "+refs/tags/*:refs/tags/*"],
+            "only destinations in the tracking branch namespace of the remote are changed"
+        );
+        assert!(repo.try_find_remote("origin").is_none());
+
+        let config = repo.config_snapshot();
+        for branch in ["main", "feature"] {
+            assert_eq!(
+                config.string(format!("branch.{branch}.remote").as_str()).as_deref(),
+                Some("renamed".into()),
+                "branches tracking the remote are adjusted"
+            );
+        }
+        assert_eq!(
+            config.string("branch.up.remote").as_deref(),
+            Some("upstream".into()),
+            "branches tracking other remotes are untouched"
+        );
+
+        assert_eq!(reference_names(&repo, "refs/remotes/origin/")?, Vec::<String>::new());
+        assert_eq!(
+            reference_names(&repo, "refs/remotes/renamed/")?,
+            [
+                "refs/remotes/renamed/HEAD",
+                "refs/remotes/renamed/main",
+                "refs/remotes/renamed/other"
+            ],
+            "loose and packed tracking references are moved"
+        );
+        let head = repo.find_reference("refs/remotes/renamed/HEAD")?;
+        assert_eq!(
+            head.target().try_name().expect("symbolic").as_bstr(),
+            "refs/remotes/renamed/main",
+            "symbolic references are adjusted to point into the new namespace"
+        );
+        assert_eq!(repo.find_reference("refs/remotes/renamed/main")?.id(), main_id);
+        assert_eq!(
+            reference_names(&repo, "refs/remotes/upstream/")?,
+            ["refs/remotes/upstream/main", "refs/remotes/upstream/other"],
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn names_are_validated() -> crate::Result {
+        let (repo, _keep) = repo_rw()?;
+        assert!(matches!(
+            repo.rename_remote("missing", "new"),
+            Err(Error::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.rename_remote("origin", "upstream"),
+            Err(Error::AlreadyExists { .. })
+        ));
+        assert!(matches!(
+            repo.rename_remote("origin", "in valid"),
+            Err(Error::InvalidName(_))
+        ));
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        assert!(repo.try_find_remote("origin").is_some(), "nothing was changed");
+        assert_eq!(reference_names(&repo, "refs/remotes/origin/")?.len(), 3);
+        Ok(())
+    }
+}
+
+mod remove_remote {
+    use gix::remote::remove::Error;
+
+    #[test]
+    fn configuration_upstreams_and_tracking_references_are_removed() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_edits.sh")?;
+        let repo = gix::open_opts(tmp.path().join("repo"), crate::restricted())?;
+        repo.remove_remote("origin")?;
+        assert!(matches!(repo.remove_remote("missing"), Err(Error::NotFound { .. })));
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        assert!(repo.try_find_remote("origin").is_none());
+        assert!(repo.try_find_remote("upstream").is_some());
+        let config = repo.config_snapshot();
+        for key in ["branch.main.remote", "branch.main.merge", "branch.feature.remote"] {
+            assert!(config.string(key).is_none(), "{key} is unset");
+        }
+        assert_eq!(config.string("branch.up.remote").as_deref(), Some("upstream".into()));
+
+        assert_eq!(repo.references()?.prefixed("refs/remotes/origin/")?.count(), 0);
+        assert_eq!(repo.references()?.prefixed("refs/remotes/upstream/")?.count(), 2);
+        Ok(())
+    }
+}
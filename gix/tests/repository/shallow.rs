@@ -60,14 +60,47 @@ mod traverse {
                     .ancestors()
                     .use_commit_graph(toggle)
                     .all()?
-                    .map(|c| c.map(|c| c.id))
+                    .map(|c| c.map(|c| (c.id, c.is_grafted)))
                     .collect::<Result<_, _>>()?;
-                assert_eq!(commits, [hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12")]);
+                assert_eq!(
+                    commits,
+                    [(hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12"), true)],
+                    "the commit at the shallow boundary is marked as grafted"
+                );
             }
         }
         Ok(())
     }
 
+    #[test]
+    #[parallel]
+    fn grafts_file_replaces_parents() -> crate::Result {
+        let (repo, _tmp) = crate::util::repo_rw("make_history_to_rewrite.sh")?;
+        let head = repo.head_id()?.detach();
+        let root = repo.rev_parse_single("HEAD~3")?.detach();
+        std::fs::create_dir_all(repo.git_dir().join("info"))?;
+        std::fs::write(
+            repo.git_dir().join("info").join("grafts"),
+            format!("# comments are ignored\n{head} {root}\n"),
+        )?;
+
+        let grafts = repo.commit_grafts()?;
+        assert_eq!(grafts.len(), 1);
+        assert_eq!(grafts[&head].as_slice(), [root]);
+
+        let commits: Vec<_> = repo
+            .rev_walk(Some(head))
+            .all()?
+            .map(|c| c.map(|c| (c.id, c.is_grafted)))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            commits,
+            [(head, true), (root, false)],
+            "HEAD is grafted onto the root commit"
+        );
+        Ok(())
+    }
+
     #[test]
     #[parallel]
     fn complex_graphs_can_be_iterated_despite_multiple_shallow_boundaries() -> crate::Result {
@@ -0,0 +1,112 @@
+use gix::bstr::ByteSlice;
+use gix::index::entry::Flags;
+
+use crate::util::named_subrepo_opts;
+
+fn skipped_paths(index: &gix::index::State) -> Vec<String> {
+    index
+        .entries()
+        .iter()
+        .filter(|e| e.flags.contains(Flags::SKIP_WORKTREE))
+        .map(|e| e.path(index).to_str_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn disabled() -> crate::Result {
+    let repo = named_subrepo_opts("make_sparse_checkout.sh", "disabled", crate::restricted())?;
+    assert!(
+        repo.sparse_checkout_patterns()?.is_none(),
+        "the patterns file exists, but the sparse-checkout isn't enabled"
+    );
+    Ok(())
+}
+
+#[test]
+fn cone() -> crate::Result {
+    let repo = named_subrepo_opts("make_sparse_checkout.sh", "cone", crate::restricted())?;
+    let patterns = repo.sparse_checkout_patterns()?.expect("enabled");
+    assert!(patterns.is_cone_mode());
+
+    for (path, is_dir, expected) in [
+        ("root-file", Some(false), true),
+        ("included", Some(true), true),
+        ("included/a", Some(false), true),
+        ("included/sub/b", Some(false), true),
+        ("excluded", Some(true), false),
+        ("excluded/c", Some(false), false),
+        ("excluded/sub/d", Some(false), false),
+    ] {
+        assert_eq!(patterns.is_included(path.into(), is_dir), expected, "{path}");
+    }
+
+    let mut index = repo.open_index()?;
+    assert_eq!(skipped_paths(&index), ["excluded/c", "excluded/sub/d"]);
+    let workdir = repo.work_dir().expect("non-bare");
+    let outcome = patterns.update_skip_worktree(&mut index, workdir);
+    assert_eq!(
+        outcome,
+        gix::sparse::Outcome {
+            skipped: 0,
+            unskipped: 1
+        },
+        "the file outside of the patterns is present on disk and is treated like any other"
+    );
+    assert!(outcome.index_changed());
+    assert_eq!(skipped_paths(&index), ["excluded/sub/d"]);
+
+    let outcome = patterns.update_skip_worktree(&mut index, workdir);
+    assert!(!outcome.index_changed(), "nothing changes on the second run");
+
+    for entry in index.entries_mut() {
+        entry.flags.remove(Flags::SKIP_WORKTREE);
+    }
+    let outcome = patterns.update_skip_worktree(&mut index, workdir);
+    assert_eq!(
+        outcome,
+        gix::sparse::Outcome {
+            skipped: 1,
+            unskipped: 0
+        },
+        "missing files outside of the patterns are skipped again"
+    );
+    assert_eq!(skipped_paths(&index), ["excluded/sub/d"]);
+    Ok(())
+}
+
+#[test]
+fn no_cone() -> crate::Result {
+    let repo = named_subrepo_opts("make_sparse_checkout.sh", "no-cone", crate::restricted())?;
+    let patterns = repo.sparse_checkout_patterns()?.expect("enabled");
+    assert!(!patterns.is_cone_mode());
+
+    for (path, is_dir, expected) in [
+        ("root-file", Some(false), true),
+        ("included/sub/b", Some(false), true),
+        ("excluded", Some(true), false),
+        ("excluded/c", Some(false), false),
+        ("excluded/sub/d", Some(false), true),
+    ] {
+        assert_eq!(patterns.is_included(path.into(), is_dir), expected, "{path}");
+    }
+
+    let mut index = repo.open_index()?;
+    assert_eq!(skipped_paths(&index), ["excluded/c"]);
+    for entry in index.entries_mut() {
+        entry.flags.remove(Flags::SKIP_WORKTREE);
+    }
+    let outcome = patterns.update_skip_worktree(&mut index, repo.work_dir().expect("non-bare"));
+    assert_eq!(outcome.skipped, 1);
+    assert_eq!(skipped_paths(&index), ["excluded/c"]);
+    Ok(())
+}
+
+#[test]
+fn cone_mode_falls_back_to_non_cone_patterns() {
+    let patterns = gix::sparse::Patterns::from_bytes(b"/*\n*.txt\n", true, gix::glob::pattern::Case::Sensitive);
+    assert!(
+        !patterns.is_cone_mode(),
+        "these patterns can't be represented in cone mode"
+    );
+    assert!(patterns.is_included("dir/file.txt".into(), Some(false)));
+}
@@ -336,3 +336,651 @@ fn run_assertions(main_repo: gix::Repository, should_be_bare: bool) {
         );
     }
 }
+
+#[cfg(feature = "worktree-mutation")]
+mod export {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::worktree::export::{Error, Options};
+    use gix_features::progress::Discard;
+
+    fn tree_id(repo: &gix::Repository) -> crate::Result<gix::ObjectId> {
+        Ok(repo.head_commit()?.tree_id()?.detach())
+    }
+
+    #[test]
+    fn all_files_with_filters() -> crate::Result {
+        let repo = crate::named_repo("make_export_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let destination = dir.path().join("out");
+
+        let outcome = repo.export_tree(
+            tree_id(&repo)?,
+            &destination,
+            None::<&str>,
+            &Discard,
+            &Discard,
+            &AtomicBool::default(),
+            Options::default(),
+        )?;
+        assert_eq!(
+            outcome.files_updated, 4,
+            "all files were written, including .gitattributes"
+        );
+        assert!(outcome.collisions.is_empty() && outcome.errors.is_empty());
+        assert_eq!(std::fs::read(destination.join("a"))?, b"a\n");
+        assert_eq!(std::fs::read(destination.join("dir/b"))?, b"b\n");
+        assert_eq!(
+            std::fs::read(destination.join("dir/sub/text.txt"))?,
+            b"text\r\n",
+            "attributes of the exported tree are used for conversions"
+        );
+        assert!(
+            !destination.join(".git").exists(),
+            "only the files are written, there is no repository or index"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn with_pathspec_and_without_filters() -> crate::Result {
+        let repo = crate::named_repo("make_export_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+
+        let outcome = repo.export_tree(
+            tree_id(&repo)?,
+            dir.path(),
+            Some("dir/sub"),
+            &Discard,
+            &Discard,
+            &AtomicBool::default(),
+            Options {
+                filters: false,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(outcome.files_updated, 1);
+        assert_eq!(
+            std::fs::read(dir.path().join("dir/sub/text.txt"))?,
+            b"text\n",
+            "blobs are written as stored"
+        );
+        assert!(
+            !dir.path().join("a").exists(),
+            "files outside of the pathspec aren't written"
+        );
+        assert!(!dir.path().join("dir/b").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn with_pathspec_attributes_outside_of_it_still_apply() -> crate::Result {
+        let repo = crate::named_repo("make_export_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+
+        let outcome = repo.export_tree(
+            tree_id(&repo)?,
+            dir.path(),
+            Some("dir/sub"),
+            &Discard,
+            &Discard,
+            &AtomicBool::default(),
+            Options::default(),
+        )?;
+        assert_eq!(outcome.files_updated, 1);
+        assert_eq!(
+            std::fs::read(dir.path().join("dir/sub/text.txt"))?,
+            b"text\r\n",
+            "the top-level .gitattributes file is used even though it's not exported"
+        );
+        assert!(!dir.path().join(".gitattributes").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn existing_files_collide_unless_overwritten() -> crate::Result {
+        let repo = crate::named_repo("make_export_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a"), b"untracked")?;
+
+        let export = |options: Options| -> crate::Result<gix::worktree::state::checkout::Outcome> {
+            Ok(repo.export_tree(
+                tree_id(&repo)?,
+                dir.path(),
+                Some("a"),
+                &Discard,
+                &Discard,
+                &AtomicBool::default(),
+                options,
+            )?)
+        };
+        let outcome = export(Options::default())?;
+        assert_eq!(outcome.collisions.len(), 1, "the existing file is left alone");
+        assert_eq!(std::fs::read(dir.path().join("a"))?, b"untracked");
+
+        let outcome = export(Options {
+            overwrite_existing: true,
+            ..Default::default()
+        })?;
+        assert!(outcome.collisions.is_empty());
+        assert_eq!(outcome.files_updated, 1);
+        assert_eq!(std::fs::read(dir.path().join("a"))?, b"a\n");
+        Ok(())
+    }
+
+    #[test]
+    fn only_trees_can_be_exported() -> crate::Result {
+        let repo = crate::named_repo("make_export_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let err = repo
+            .export_tree(
+                repo.head_id()?,
+                dir.path(),
+                None::<&str>,
+                &Discard,
+                &Discard,
+                &AtomicBool::default(),
+                Options::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::NotATree {
+                actual: gix::object::Kind::Commit,
+                ..
+            }
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "worktree-mutation")]
+mod manage {
+    use gix::worktree::{
+        add::{Error, Head},
+        prune, repair,
+    };
+
+    use crate::util::repo_rw;
+
+    fn realpath(path: impl AsRef<std::path::Path>) -> std::path::PathBuf {
+        gix::path::realpath(path).expect("valid path")
+    }
+
+    #[test]
+    fn add_checks_out_branches_only_once() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let head = repo.head_id()?.detach();
+
+        let proxy = repo.worktree_add(
+            dir.path().join("wt"),
+            Head::NewBranch {
+                name: "refs/heads/feature".try_into()?,
+                commit: head,
+            },
+            Default::default(),
+        )?;
+        assert_eq!(proxy.id(), "wt");
+        assert_eq!(proxy.base()?, realpath(dir.path().join("wt")));
+        assert!(!proxy.is_locked(), "the lock used during creation is removed");
+        let wt = proxy.into_repo()?;
+        assert_eq!(wt.head_name()?.expect("not detached").as_bstr(), "refs/heads/feature");
+        assert_eq!(wt.head_id()?, head);
+        assert_eq!(
+            std::fs::read(dir.path().join("wt").join("this"))?,
+            b"hello\n",
+            "files are checked out"
+        );
+        assert_eq!(wt.open_index()?.entries().len(), 1);
+        assert_eq!(repo.find_reference("feature")?.id(), head);
+
+        for name in ["refs/heads/feature", "refs/heads/main"] {
+            assert!(matches!(
+                repo.worktree_add(
+                    dir.path().join("other"),
+                    Head::Branch(name.try_into()?),
+                    Default::default()
+                ),
+                Err(Error::BranchInUse { .. })
+            ));
+        }
+        assert!(matches!(
+            repo.worktree_add(dir.path().join("wt"), Head::Detached(head), Default::default()),
+            Err(Error::DestinationExists { .. })
+        ));
+
+        let proxy = repo.worktree_add(
+            dir.path().join("nested").join("wt"),
+            Head::Detached(head),
+            gix::worktree::add::Options {
+                lock: Some("on a stick".into()),
+                checkout: false,
+            },
+        )?;
+        assert_eq!(proxy.id(), "wt1", "ids are unique");
+        assert_eq!(proxy.lock_reason().expect("locked"), "on a stick");
+        assert!(!dir.path().join("nested").join("wt").join("this").exists());
+        assert_eq!(repo.worktrees()?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn lock_move_prune_and_repair() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let head = repo.head_id()?.detach();
+        let proxy = repo.worktree_add(dir.path().join("wt"), Head::Detached(head), Default::default())?;
+
+        proxy.lock(Some("busy".into()))?;
+        assert!(matches!(
+            proxy.lock(None),
+            Err(gix::worktree::proxy::lock::Error::AlreadyLocked { reason }) if reason == "busy"
+        ));
+        assert!(matches!(
+            proxy.move_to(dir.path().join("moved")),
+            Err(gix::worktree::proxy::move_to::Error::Locked { .. })
+        ));
+        assert!(repo.worktrees_prune(false)?.is_empty());
+        proxy.unlock()?;
+        assert!(matches!(
+            proxy.unlock(),
+            Err(gix::worktree::proxy::lock::Error::NotLocked)
+        ));
+
+        let moved = proxy.move_to(dir.path().join("moved"))?;
+        assert_eq!(moved, realpath(dir.path().join("moved")));
+        assert_eq!(proxy.base()?, moved);
+        assert_eq!(proxy.clone().into_repo()?.head_id()?, head);
+
+        std::fs::rename(&moved, dir.path().join("manually-moved"))?;
+        assert_eq!(
+            repo.worktrees_repair([dir.path().join("manually-moved")])?,
+            [repair::Repaired {
+                path: proxy.git_dir().join("gitdir"),
+                kind: repair::Kind::GitDirFile
+            }]
+        );
+        assert_eq!(proxy.base()?, realpath(dir.path().join("manually-moved")));
+        std::fs::write(dir.path().join("manually-moved").join(".git"), "gitdir: /dev/null\n")?;
+        assert_eq!(
+            repo.worktrees_repair(None::<&str>)?.len(),
+            1,
+            "the .git file is repaired"
+        );
+        assert!(repo.worktrees_repair(None::<&str>)?.is_empty());
+        assert_eq!(proxy.into_repo()?.head_id()?, head);
+
+        std::fs::remove_dir_all(dir.path().join("manually-moved"))?;
+        let expected = [prune::Pruned {
+            id: "wt".into(),
+            reason: prune::Reason::MissingWorktree,
+        }];
+        assert_eq!(repo.worktrees_prune(true)?, expected);
+        assert_eq!(repo.worktrees()?.len(), 1, "nothing happens in a dry-run");
+        assert_eq!(repo.worktrees_prune(false)?, expected);
+        assert!(repo.worktrees()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn add_removes_everything_if_the_checkout_fails() -> crate::Result {
+        let (repo, tmp) = repo_rw("make_basic_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let head = repo.head_id()?.detach();
+        let blob = repo
+            .head_commit()?
+            .tree()?
+            .find_entry("this")
+            .expect("present")
+            .object_id();
+        let hex = blob.to_hex().to_string();
+        std::fs::remove_file(tmp.path().join(".git/objects").join(&hex[..2]).join(&hex[2..]))?;
+
+        assert!(matches!(
+            repo.worktree_add(dir.path().join("wt"), Head::Detached(head), Default::default()),
+            Err(Error::Checkout(_))
+        ));
+        assert!(!dir.path().join("wt").exists(), "the checkout is removed");
+        assert!(
+            repo.worktrees()?.is_empty(),
+            "the private git directory is removed as well"
+        );
+
+        std::fs::create_dir(dir.path().join("empty"))?;
+        assert!(repo
+            .worktree_add(dir.path().join("empty"), Head::Detached(head), Default::default())
+            .is_err());
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("empty"))?.count(),
+            0,
+            "existing directories are kept, but emptied"
+        );
+        assert!(repo.worktrees()?.is_empty());
+
+        assert!(matches!(
+            repo.worktree_add(
+                dir.path().join("wt"),
+                Head::NewBranch {
+                    name: "refs/heads/feature".try_into()?,
+                    commit: head,
+                },
+                Default::default()
+            ),
+            Err(Error::Checkout(_))
+        ));
+        assert!(
+            repo.try_find_reference("refs/heads/feature")?.is_none(),
+            "the branch created for the worktree is removed as well"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn add_refuses_branches_in_use_and_ignores_broken_worktrees() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let head = repo.head_id()?.detach();
+        repo.reference(
+            "refs/heads/rebased",
+            head,
+            gix::refs::transaction::PreviousValue::MustNotExist,
+            "",
+        )?;
+        std::fs::create_dir(repo.git_dir().join("rebase-merge"))?;
+        std::fs::write(
+            repo.git_dir().join("rebase-merge").join("head-name"),
+            "refs/heads/rebased\n",
+        )?;
+
+        let broken = repo.git_dir().join("worktrees").join("broken");
+        std::fs::create_dir_all(&broken)?;
+        std::fs::write(broken.join("gitdir"), "/does/not/exist/.git\n")?;
+        assert_eq!(repo.worktrees()?.len(), 1);
+
+        assert!(matches!(
+            repo.worktree_add(
+                dir.path().join("wt"),
+                Head::Branch("refs/heads/rebased".try_into()?),
+                Default::default()
+            ),
+            Err(Error::BranchInUse { head, .. }) if head.as_bstr() == "main-worktree/HEAD"
+        ));
+
+        std::fs::remove_dir_all(repo.git_dir().join("rebase-merge"))?;
+        let proxy = repo.worktree_add(
+            dir.path().join("wt"),
+            Head::Branch("refs/heads/rebased".try_into()?),
+            Default::default(),
+        )?;
+        assert_eq!(
+            proxy.into_repo()?.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/rebased",
+            "worktrees that can't be opened don't prevent adding new ones"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "status")]
+    fn remove_refuses_modified_worktrees_unless_forced() -> crate::Result {
+        use gix::worktree::proxy::remove::{Error, Options};
+
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let head = repo.head_id()?.detach();
+        let proxy = repo.worktree_add(dir.path().join("wt"), Head::Detached(head), Default::default())?;
+        std::fs::write(dir.path().join("wt").join("untracked"), "new")?;
+        assert!(matches!(
+            proxy.clone().remove(Options::default()),
+            Err(Error::Modified { .. })
+        ));
+        std::fs::remove_file(dir.path().join("wt").join("untracked"))?;
+        std::fs::write(dir.path().join("wt").join("this"), "changed")?;
+        assert!(matches!(
+            proxy.clone().remove(Options::default()),
+            Err(Error::Modified { .. })
+        ));
+        proxy.clone().remove(Options {
+            allow_modifications: true,
+            allow_locked: false,
+        })?;
+        assert!(!dir.path().join("wt").exists());
+        assert!(!proxy.git_dir().exists());
+        assert!(repo.worktrees()?.is_empty());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "worktree-mutation")]
+mod switch {
+    use gix::worktree::{add::Head, switch::Error};
+
+    use crate::util::repo_rw;
+
+    fn commit_of(repo: &gix::Repository, name: &str) -> crate::Result<gix::ObjectId> {
+        Ok(repo.find_reference(name)?.peel_to_id_in_place()?.detach())
+    }
+
+    fn conflicting_paths(err: Error) -> Vec<String> {
+        match err {
+            Error::Conflicts { conflicts } => conflicts.into_iter().map(|c| c.path.to_string()).collect(),
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn branches_keep_local_changes_unless_they_would_be_overwritten() -> crate::Result {
+        let (repo, tmp) = repo_rw("make_switch_repo.sh")?;
+        let workdir = tmp.path();
+        std::fs::write(workdir.join("keep"), "modified\n")?;
+
+        repo.switch(Head::Branch("refs/heads/other".try_into()?))?;
+        assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/other");
+        assert_eq!(std::fs::read(workdir.join("a"))?, b"changed\n");
+        assert_eq!(std::fs::read(workdir.join("new/d"))?, b"d\n");
+        assert!(
+            !workdir.join("dir").exists(),
+            "directories that become empty are removed"
+        );
+        assert_eq!(
+            std::fs::read(workdir.join("keep"))?,
+            b"modified\n",
+            "unrelated local changes are kept"
+        );
+        let index = repo.open_index()?;
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .map(|e| e.path(&index).to_string())
+                .collect::<Vec<_>>(),
+            ["a", "keep", "new/d"]
+        );
+        assert_ne!(
+            index.entry_by_path("a".into()).expect("present").stat.mtime.secs,
+            0,
+            "the stat information of written files is recorded"
+        );
+
+        std::fs::write(workdir.join("a"), "local\n")?;
+        std::fs::create_dir(workdir.join("dir"))?;
+        std::fs::write(workdir.join("dir/c"), "untracked\n")?;
+        assert_eq!(
+            conflicting_paths(
+                repo.switch(Head::Branch("refs/heads/main".try_into()?))
+                    .expect_err("local changes would be lost")
+            ),
+            ["a", "dir/c"]
+        );
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/other",
+            "nothing changes on conflict"
+        );
+        assert!(workdir.join("new/d").exists());
+
+        std::fs::remove_dir_all(workdir.join("dir"))?;
+        std::fs::write(workdir.join("a"), "changed\n")?;
+        let main = commit_of(&repo, "main")?;
+        repo.switch(Head::Detached(main))?;
+        assert_eq!(repo.head_name()?, None);
+        assert_eq!(repo.head_id()?, main);
+        assert_eq!(std::fs::read(workdir.join("a"))?, b"a\n");
+        assert_eq!(std::fs::read(workdir.join("dir/c"))?, b"c\n");
+        assert!(!workdir.join("new").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn new_branches_are_created_and_branches_in_use_are_refused() -> crate::Result {
+        let (repo, tmp) = repo_rw("make_switch_repo.sh")?;
+        let other = commit_of(&repo, "other")?;
+        repo.switch(Head::NewBranch {
+            name: "refs/heads/feature".try_into()?,
+            commit: other,
+        })?;
+        assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/feature");
+        assert_eq!(repo.head_id()?, other);
+
+        let main = commit_of(&repo, "main")?;
+        assert!(matches!(
+            repo.switch(Head::NewBranch {
+                name: "refs/heads/other".try_into()?,
+                commit: main,
+            }),
+            Err(Error::EditReference(_))
+        ));
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/feature",
+            "existing branches are detected before anything changes"
+        );
+        assert_eq!(
+            std::fs::read(tmp.path().join("a"))?,
+            b"changed\n",
+            "the worktree is unchanged"
+        );
+
+        std::fs::write(tmp.path().join("a"), "local\n")?;
+        assert_eq!(
+            conflicting_paths(
+                repo.switch(Head::NewBranch {
+                    name: "refs/heads/new".try_into()?,
+                    commit: main,
+                })
+                .expect_err("local changes would be lost")
+            ),
+            ["a"]
+        );
+        assert!(
+            repo.try_find_reference("refs/heads/new")?.is_none(),
+            "the new branch is removed if the checkout fails"
+        );
+        std::fs::write(tmp.path().join("a"), "changed\n")?;
+
+        let dir = gix_testtools::tempfile::tempdir()?;
+        repo.worktree_add(
+            dir.path().join("wt"),
+            Head::Branch("refs/heads/main".try_into()?),
+            Default::default(),
+        )?;
+        assert!(matches!(
+            repo.switch(Head::Branch("refs/heads/main".try_into()?)),
+            Err(Error::BranchInUse { name, .. }) if name.as_bstr() == "refs/heads/main"
+        ));
+        repo.switch(Head::Branch("refs/heads/feature".try_into()?))?;
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_tree_keeps_head() -> crate::Result {
+        let (repo, tmp) = repo_rw("make_switch_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        let tree = repo.find_object(commit_of(&repo, "other")?)?.peel_to_tree()?.id;
+        repo.checkout_tree(tree)?;
+        assert_eq!(repo.head_id()?, head);
+        assert_eq!(std::fs::read(tmp.path().join("a"))?, b"changed\n");
+        assert_eq!(repo.open_index()?.entries().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn reset_keep() -> crate::Result {
+        let (repo, tmp) = repo_rw("make_switch_repo.sh")?;
+        let workdir = tmp.path();
+        let main = repo.head_id()?.detach();
+        let other = commit_of(&repo, "other")?;
+        std::fs::write(workdir.join("keep"), "modified\n")?;
+
+        repo.reset_keep(other)?;
+        assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/main");
+        assert_eq!(commit_of(&repo, "main")?, other, "the branch is moved");
+        assert_eq!(commit_of(&repo, "ORIG_HEAD")?, main);
+        assert_eq!(std::fs::read(workdir.join("a"))?, b"changed\n");
+        assert_eq!(std::fs::read(workdir.join("keep"))?, b"modified\n");
+
+        std::fs::write(workdir.join("a"), "local\n")?;
+        assert_eq!(
+            conflicting_paths(repo.reset_keep(main).expect_err("'a' differs between the commits")),
+            ["a"]
+        );
+        assert_eq!(commit_of(&repo, "main")?, other);
+        Ok(())
+    }
+}
+
+#[test]
+fn checked_out_branches() -> crate::Result {
+    use gix::worktree::checked_out::State;
+    if gix_testtools::should_skip_as_git_version_is_smaller_than(2, 31, 0) {
+        return Ok(());
+    }
+    let dir = gix_testtools::scripted_fixture_writable("make_worktree_repo.sh")?;
+    let repo = gix::open(dir.path().join("repo"))?;
+    let branches = |repo: &gix::Repository| -> crate::Result<Vec<(String, String, State)>> {
+        Ok(repo
+            .checked_out_branches()?
+            .into_iter()
+            .map(|b| (b.name.as_bstr().to_string(), b.head.as_bstr().to_string(), b.state))
+            .collect())
+    };
+    let checked_out = |name: &str, head: &str| (format!("refs/heads/{name}"), head.to_owned(), State::Head);
+    let linked = gix::open(dir.path().join("wt-a"))?;
+    for repo in [&repo, &linked] {
+        assert_eq!(
+            branches(repo)?,
+            [
+                checked_out("main", "main-worktree/HEAD"),
+                checked_out("wt-a", "worktrees/wt-a/HEAD"),
+                checked_out("wt-c-locked", "worktrees/wt-c-locked/HEAD"),
+                checked_out("wt-deleted", "worktrees/wt-deleted/HEAD"),
+            ],
+            "worktrees with detached HEADs don't use any branch, but those that still have to be pruned do, no matter where we look from"
+        );
+    }
+
+    std::fs::create_dir(repo.git_dir().join("worktrees/wt-b/rebase-merge"))?;
+    std::fs::write(
+        repo.git_dir().join("worktrees/wt-b/rebase-merge/head-name"),
+        "refs/heads/feature\n",
+    )?;
+    std::fs::write(repo.git_dir().join("BISECT_START"), "main\n")?;
+    assert_eq!(
+        branches(&repo)?,
+        [
+            checked_out("main", "main-worktree/HEAD"),
+            ("refs/heads/main".into(), "main-worktree/HEAD".into(), State::Bisecting),
+            checked_out("wt-a", "worktrees/wt-a/HEAD"),
+            (
+                "refs/heads/feature".into(),
+                "worktrees/wt-b/HEAD".into(),
+                State::Rebasing
+            ),
+            checked_out("wt-c-locked", "worktrees/wt-c-locked/HEAD"),
+            checked_out("wt-deleted", "worktrees/wt-deleted/HEAD"),
+        ],
+        "branches that are rebased or bisected are in use as well"
+    );
+    Ok(())
+}
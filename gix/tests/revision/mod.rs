@@ -1 +1,3 @@
+mod name_rev;
+mod range;
 mod spec;
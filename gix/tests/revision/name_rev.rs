@@ -0,0 +1,125 @@
+use gix::revision::name_rev::Options;
+
+use crate::{named_repo, util::repo_rw};
+
+fn commit(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+fn name_of(names: &gix::revision::name_rev::Names, id: gix::ObjectId) -> Option<String> {
+    names.name(id).map(ToString::to_string)
+}
+
+#[test]
+fn older_tags_are_preferred() -> crate::Result {
+    let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+    for tags_only in [false, true] {
+        let names = repo.name_rev(Options {
+            tags_only,
+            ..Default::default()
+        })?;
+        assert_eq!(
+            name_of(&names, commit(&repo, "v1^{commit}")?).as_deref(),
+            Some("tags/v1^0"),
+            "annotated tags are marked as peeled"
+        );
+        assert_eq!(
+            name_of(&names, commit(&repo, "l0")?).as_deref(),
+            Some("tags/v1~1"),
+            "the annotated tag is older than the lightweight ones"
+        );
+        assert_eq!(
+            name_of(&names, commit(&repo, "HEAD")?).as_deref(),
+            Some("tags/v2^0"),
+            "the first of the tags with the same date"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn include_and_exclude_patterns() -> crate::Result {
+    let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+    let names = repo.name_rev(Options {
+        include: vec!["main".into()],
+        ..Default::default()
+    })?;
+    assert_eq!(name_of(&names, commit(&repo, "HEAD")?).as_deref(), Some("main"));
+    assert_eq!(name_of(&names, commit(&repo, "l0")?).as_deref(), Some("main~2"));
+
+    let names = repo.name_rev(Options {
+        exclude: vec!["v*".into()],
+        ..Default::default()
+    })?;
+    assert_eq!(
+        name_of(&names, commit(&repo, "HEAD~1")?).as_deref(),
+        Some("main~1"),
+        "no tag can reach it"
+    );
+    assert_eq!(name_of(&names, commit(&repo, "l0")?).as_deref(), Some("tags/l0"));
+
+    let names = repo.name_rev(Options {
+        include: vec!["refs/heads/*".into()],
+        exclude: vec!["main".into()],
+        ..Default::default()
+    })?;
+    assert!(names.is_empty(), "exclusions take precedence");
+    Ok(())
+}
+
+#[test]
+fn annotate() -> crate::Result {
+    let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+    let names = repo.name_rev(Options::default())?;
+    let id = commit(&repo, "v1^{commit}")?;
+    let tree_id = commit(&repo, "HEAD^{tree}")?;
+    let text = format!("{id}\nmerged {id}, see {tree_id} and {}.", id.to_hex_with_len(7));
+    assert_eq!(
+        names.annotate(text.as_str().into(), false),
+        format!(
+            "{id} (tags/v1^0)\nmerged {id} (tags/v1^0), see {tree_id} and {}.",
+            id.to_hex_with_len(7)
+        ),
+        "only full hashes of named commits are annotated"
+    );
+    assert_eq!(
+        names.annotate(text.as_str().into(), true),
+        format!(
+            "tags/v1^0\nmerged tags/v1^0, see {tree_id} and {}.",
+            id.to_hex_with_len(7)
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn annotated_tags_without_tagger_are_used() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_commit_describe_multiple_tags.sh")?;
+    let target = commit(&repo, "v1^{commit}")?;
+    let tag_id = repo.write_object(gix::objs::Tag {
+        target,
+        target_kind: gix::object::Kind::Commit,
+        name: "no-tagger".into(),
+        tagger: None,
+        message: "a tag without tagger".into(),
+        pgp_signature: None,
+    })?;
+    repo.reference(
+        "refs/tags/no-tagger",
+        tag_id,
+        gix::refs::transaction::PreviousValue::MustNotExist,
+        "",
+    )?;
+
+    let names = repo.name_rev(Options {
+        include: vec!["no-tagger".into()],
+        ..Default::default()
+    })?;
+    assert_eq!(name_of(&names, target).as_deref(), Some("tags/no-tagger^0"));
+    assert_eq!(
+        name_of(&names, commit(&repo, "l0")?).as_deref(),
+        Some("tags/no-tagger~1"),
+        "the time of the commit is used instead of the time of the missing tagger"
+    );
+    Ok(())
+}
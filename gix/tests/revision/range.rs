@@ -0,0 +1,96 @@
+use gix::revision::range::Side;
+
+use crate::named_repo;
+
+fn commit(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+fn summaries(
+    repo: &gix::Repository,
+    commits: &[gix::revision::range::Commit],
+    side: Side,
+) -> crate::Result<Vec<String>> {
+    let mut out = Vec::new();
+    for commit in commits.iter().filter(|c| c.side == side) {
+        out.push(
+            repo.find_object(commit.id)?
+                .into_commit()
+                .message()?
+                .summary()
+                .to_string(),
+        );
+    }
+    out.sort();
+    Ok(out)
+}
+
+#[test]
+fn symmetric_difference() -> crate::Result {
+    let repo = named_repo("make_cherry_picks.sh")?;
+    let commits = repo.symmetric_difference(commit(&repo, "left")?, commit(&repo, "right")?)?;
+    assert_eq!(
+        summaries(&repo, &commits, Side::Left)?,
+        ["add left", "indent", "spell out two"]
+    );
+    assert_eq!(
+        summaries(&repo, &commits, Side::Right)?,
+        ["add right", "indent differently", "spell out nine", "spell out two"]
+    );
+    assert!(commits.iter().all(|c| c.equivalent.is_none()), "nothing is marked yet");
+
+    let commits = repo.symmetric_difference(commit(&repo, "main")?, commit(&repo, "left")?)?;
+    assert_eq!(commits.len(), 3);
+    assert!(
+        commits.iter().all(|c| c.side == Side::Right),
+        "main is an ancestor of left"
+    );
+    assert!(repo
+        .symmetric_difference(commit(&repo, "left")?, commit(&repo, "left")?)?
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "blob-diff")]
+fn mark_equivalent_commits() -> crate::Result {
+    let repo = named_repo("make_cherry_picks.sh")?;
+    let mut commits = repo.symmetric_difference(commit(&repo, "left")?, commit(&repo, "right")?)?;
+    repo.mark_equivalent_commits(&mut commits)?;
+
+    let equivalent_of = |spec: &str| -> crate::Result<Option<gix::ObjectId>> {
+        let id = commit(&repo, spec)?;
+        Ok(commits
+            .iter()
+            .find(|c| c.id == id)
+            .expect("commit is part of the difference")
+            .equivalent)
+    };
+    assert_eq!(equivalent_of("left~2")?, Some(commit(&repo, "right~2")?));
+    assert_eq!(equivalent_of("right~2")?, Some(commit(&repo, "left~2")?));
+    assert_eq!(
+        equivalent_of("left")?,
+        Some(commit(&repo, "right")?),
+        "whitespace and context lines are ignored"
+    );
+    assert_eq!(equivalent_of("right")?, Some(commit(&repo, "left")?));
+    for unique in ["left~1", "right~1", "right~3"] {
+        assert_eq!(equivalent_of(unique)?, None, "{unique} has no counterpart");
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "blob-diff")]
+fn patch_id() -> crate::Result {
+    let repo = named_repo("make_cherry_picks.sh")?;
+    let picked = repo.patch_id(commit(&repo, "right~2")?)?;
+    assert!(picked.is_some());
+    assert_eq!(repo.patch_id(commit(&repo, "left~2")?)?, picked);
+    assert_ne!(repo.patch_id(commit(&repo, "right~3")?)?, picked);
+    assert!(
+        repo.patch_id(commit(&repo, "main")?)?.is_some(),
+        "root commits have a patch id"
+    );
+    Ok(())
+}
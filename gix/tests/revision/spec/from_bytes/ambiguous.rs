@@ -44,11 +44,38 @@ fn fully_failed_disambiguation_still_yields_an_ambiguity_error() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
     assert_eq!(
         parse_spec("0000000000^{tag}", &repo).unwrap_err().to_string(),
-        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\"\n\t0000000000c tree\n\t0000000000b blob",
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e commit 2005-04-07 - a2onsxbvj\n\t0000000000c tree\n\t0000000000b blob",
         "without special treatment, one would see a bunch of failed transformations with the impression that the first of them is the root cause, which isn't correct."
     );
 }
 
+#[test]
+fn ambiguous_candidates_are_accessible_even_if_wrapped_in_other_errors() {
+    let repo = repo("ambiguous_blob_tree_commit").unwrap();
+    let err = parse_spec_no_baseline("0000000000^{tag}", &repo).unwrap_err();
+    assert!(matches!(err, gix::revision::spec::parse::Error::Multi { .. }));
+    let (prefix, candidates) = err.ambiguous_candidates().expect("the ambiguity is the root cause");
+    assert_eq!(prefix.to_string(), "0000000000");
+    assert_eq!(
+        candidates
+            .iter()
+            .map(|(id, info)| format!("{id} {info}"))
+            .collect::<Vec<_>>(),
+        [
+            "0000000000e commit 2005-04-07 - a2onsxbvj",
+            "0000000000c tree",
+            "0000000000b blob"
+        ]
+    );
+    assert!(
+        parse_spec_no_baseline("0000000000cdc^{tag}", &repo)
+            .unwrap_err()
+            .ambiguous_candidates()
+            .is_none(),
+        "unambiguous ids don't have candidates"
+    );
+}
+
 #[test]
 fn ranges_are_auto_disambiguated_by_committish() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
@@ -74,7 +101,7 @@ fn blob_and_tree_can_be_disambiguated_by_type() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
     assert_eq!(
         parse_spec("0000000000", &repo).unwrap_err().to_string(),
-        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\"\n\t0000000000c tree\n\t0000000000b blob",
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e commit 2005-04-07 - a2onsxbvj\n\t0000000000c tree\n\t0000000000b blob",
         "in theory one could disambiguate with 0000000000^{{tree}} (which works in git) or 0000000000^{{blob}} which doesn't work for some reason."
     );
 
@@ -144,7 +171,7 @@ fn duplicates_are_deduplicated_across_all_odb_types() {
     let repo = repo("duplicate_ambiguous_objects").unwrap();
     assert_eq!(
         parse_spec_no_baseline("0000000000", &repo).unwrap_err().to_string(),
-        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000f8 tag \"v1.0.0\"\n\t000000000004 commit 2005-04-07 \"czy8f73t\"\n\t00000000006 commit 2005-04-07 \"ad2uee\"\n\t00000000008 commit 2005-04-07 \"ioiley5o\"\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\"\n\t000000000002 tree\n\t00000000005 tree\n\t00000000009 tree\n\t0000000000c tree\n\t0000000000fd tree\n\t00000000001 blob\n\t00000000003 blob\n\t0000000000a blob\n\t0000000000b blob\n\t0000000000f2 blob",
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000f8 tag 2005-04-07 - v1.0.0\n\t000000000004 commit 2005-04-07 - czy8f73t\n\t00000000006 commit 2005-04-07 - ad2uee\n\t00000000008 commit 2005-04-07 - ioiley5o\n\t0000000000e commit 2005-04-07 - a2onsxbvj\n\t000000000002 tree\n\t00000000005 tree\n\t00000000009 tree\n\t0000000000c tree\n\t0000000000fd tree\n\t00000000001 blob\n\t00000000003 blob\n\t0000000000a blob\n\t0000000000b blob\n\t0000000000f2 blob",
         "One day we want to see 16 objects here, and not 32 just because they exist in the loose and the packed odb"
     );
 }
@@ -231,13 +258,13 @@ fn repository_local_disambiguation_hints_disambiguate() {
     );
     assert_eq!(
         rev_parse("0000000000", &r).unwrap_err().to_string(),
-        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000f8 tag \"v1.0.0\"\n\t000000000004 commit 2005-04-07 \"czy8f73t\"\n\t00000000006 commit 2005-04-07 \"ad2uee\"\n\t00000000008 commit 2005-04-07 \"ioiley5o\"\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\""
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000f8 tag 2005-04-07 - v1.0.0\n\t000000000004 commit 2005-04-07 - czy8f73t\n\t00000000006 commit 2005-04-07 - ad2uee\n\t00000000008 commit 2005-04-07 - ioiley5o\n\t0000000000e commit 2005-04-07 - a2onsxbvj"
     );
 
     let r = repo("ambiguous_objects_disambiguation_config_treeish").unwrap();
     assert_eq!(
         rev_parse("0000000000f", &r).unwrap_err().to_string(),
-        "Short id 0000000000f is ambiguous. Candidates are:\n\t0000000000f8 tag \"v1.0.0\"\n\t0000000000fd tree",
+        "Short id 0000000000f is ambiguous. Candidates are:\n\t0000000000f8 tag 2005-04-07 - v1.0.0\n\t0000000000fd tree",
         "disambiguation might not always work either."
     );
 
@@ -261,11 +288,11 @@ fn repository_local_disambiguation_hints_disambiguate() {
     let r = repo("ambiguous_objects_disambiguation_config_commit").unwrap();
     assert_eq!(
         rev_parse("0000000000f", &r).unwrap_err().to_string(),
-        "Short id 0000000000f is ambiguous. Candidates are:\n\t0000000000f8 tag \"v1.0.0\"\n\t0000000000fd tree\n\t0000000000f2 blob",
+        "Short id 0000000000f is ambiguous. Candidates are:\n\t0000000000f8 tag 2005-04-07 - v1.0.0\n\t0000000000fd tree\n\t0000000000f2 blob",
     );
     assert_eq!(
         rev_parse("0000000000", &r).unwrap_err().to_string(),
-        "Short id 0000000000 is ambiguous. Candidates are:\n\t000000000004 commit 2005-04-07 \"czy8f73t\"\n\t00000000006 commit 2005-04-07 \"ad2uee\"\n\t00000000008 commit 2005-04-07 \"ioiley5o\"\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\"",
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t000000000004 commit 2005-04-07 - czy8f73t\n\t00000000006 commit 2005-04-07 - ad2uee\n\t00000000008 commit 2005-04-07 - ioiley5o\n\t0000000000e commit 2005-04-07 - a2onsxbvj",
     );
 
     let r = repo("ambiguous_objects_disambiguation_config_blob").unwrap();
@@ -1,7 +1,4 @@
-use gix::{
-    prelude::ObjectIdExt,
-    revision::{spec::parse::Error, Spec},
-};
+use gix::{prelude::ObjectIdExt, revision::Spec};
 
 use crate::{
     revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, repo},
@@ -78,10 +75,48 @@ fn by_index() {
 }
 
 #[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
+fn by_date() {
+    let repo = repo("complex_graph").unwrap();
+    for (spec, expected) in [
+        ("main@{1112912413}", "5b3f9e24965d0b28780b7ce5daf2b5b7f7e0459f"),
+        ("main@{1112912200}", "a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc"),
+        ("@{1112912200}", "a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc"),
+        ("main@{2000000000}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+    ] {
+        assert_eq!(
+            parse_spec_no_baseline(spec, &repo).unwrap(),
+            Spec::from_id(hex_to_id(expected).attach(&repo)),
+            "{spec}: the entry that was current at the given time, or the current value if the time is newer than the log"
+        );
+    }
+}
+
+#[test]
+fn by_date_before_the_log_starts_uses_the_oldest_entry() {
     let repo = repo("complex_graph").unwrap();
-    assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
-    ));
+    assert_eq!(
+        parse_spec_no_baseline("main@{1000000000}", &repo).unwrap(),
+        Spec::from_id(hex_to_id("9f9eac6bd1cd4b4cc6a494f044b28c985a22972b").attach(&repo)),
+        "git warns that the log only goes back so far, and uses the value of the oldest entry"
+    );
+}
+
+#[test]
+fn by_date_in_inconsistent_log_uses_current_value() {
+    let repo = repo("complex_graph").unwrap();
+    assert_eq!(
+        parse_spec_no_baseline("HEAD@{1112912300}", &repo).unwrap(),
+        Spec::from_id(hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e").attach(&repo)),
+        "the entry before an initial commit doesn't connect to it, so git warns and falls back to the current value"
+    );
+}
+
+#[test]
+fn by_relative_date() {
+    let repo = repo("complex_graph").unwrap();
+    assert_eq!(
+        parse_spec_no_baseline("main@{2.days.ago}", &repo).unwrap(),
+        Spec::from_id(hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e").attach(&repo)),
+        "the log is older than that, so the current value is used"
+    );
 }
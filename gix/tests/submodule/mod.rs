@@ -141,3 +141,190 @@ mod open {
         Ok(())
     }
 }
+
+#[cfg(feature = "status")]
+mod status {
+    use gix::submodule::config::Ignore;
+
+    use crate::submodule::repo;
+
+    #[test]
+    fn various() -> crate::Result {
+        for (name, expected_prefix, is_dirty) in [
+            ("with-submodules", ' ', Some(false)),
+            ("with-submodules-after-clone", '-', None),
+            ("submodule-head-changed", '+', Some(false)),
+            ("submodule-dirty", ' ', Some(true)),
+            ("submodule-untracked", ' ', Some(true)),
+        ] {
+            let repo = repo(name)?;
+            let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+            let status = sm.status(Ignore::None)?;
+            assert_eq!(status.prefix(), expected_prefix, "{name}");
+            assert_eq!(status.is_dirty, is_dirty, "{name}");
+            assert_eq!(status.index_id, sm.index_id()?, "{name}");
+            assert_eq!(
+                status.checked_out_head_id,
+                sm.open()?.map(|sm_repo| sm_repo.head_id().expect("born").detach()),
+                "{name}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn untracked_files_can_be_ignored() -> crate::Result {
+        let repo = repo("submodule-untracked")?;
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+        assert_eq!(sm.status(Ignore::None)?.is_dirty, Some(true));
+        assert_eq!(
+            sm.status(Ignore::Untracked)?.is_dirty,
+            Some(false),
+            "only tracked files are checked"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ignore_limits_the_amount_of_work() -> crate::Result {
+        let repo = repo("submodule-dirty")?;
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+
+        let status = sm.status(Ignore::Dirty)?;
+        assert!(status.checked_out_head_id.is_some());
+        assert_eq!(status.is_dirty, None, "the worktree isn't checked");
+
+        let status = sm.status(Ignore::All)?;
+        assert_eq!(status.checked_out_head_id, None, "the submodule isn't opened");
+        assert_eq!(status.is_head_changed(), None);
+        assert!(
+            status.index_id.is_some(),
+            "information of the superproject is always available"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+mod update_with_checkout {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::{submodule::UpdateOutcome, Submodule};
+
+    fn repo_rw(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let dir = gix_testtools::scripted_fixture_writable_with_args(
+            "make_submodules_to_update.sh",
+            None::<String>,
+            gix_testtools::Creation::ExecuteScript,
+        )?;
+        let repo = gix::open_opts(dir.path().join(name), crate::restricted())?;
+        Ok((repo, dir))
+    }
+
+    fn update(sm: &Submodule<'_>, init: bool) -> Result<UpdateOutcome, gix::submodule::update_with_checkout::Error> {
+        sm.update_with_checkout(init, gix::progress::Discard, &AtomicBool::default())
+    }
+
+    #[test]
+    fn uninitialized_submodules_are_cloned_and_checked_out_with_init() -> crate::Result {
+        let (repo, dir) = repo_rw("super-clone")?;
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+        assert!(
+            matches!(
+                update(&sm, false),
+                Err(gix::submodule::update_with_checkout::Error::NotInitialized { .. })
+            ),
+            "without init, nothing is done"
+        );
+        assert!(!sm.state()?.repository_exists);
+
+        let outcome = update(&sm, true)?;
+        assert_eq!(
+            outcome,
+            UpdateOutcome {
+                initialized: true,
+                cloned: true,
+                fetched: false,
+                checked_out: sm.index_id()?,
+            }
+        );
+        assert_eq!(
+            std::fs::read(dir.path().join("super-clone/m1/this"))?,
+            b"",
+            "the recorded commit is checked out, not the one of the default branch"
+        );
+
+        let repo = gix::open_opts(repo.path(), crate::restricted())?;
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+        assert_eq!(
+            sm.url()?.to_bstring(),
+            gix::path::into_bstr(dir.path().join("module1")).as_ref(),
+            "relative urls are resolved against the url of the default remote of the superproject"
+        );
+        assert_eq!(
+            sm.state()?,
+            gix::submodule::State {
+                repository_exists: true,
+                is_old_form: false,
+                worktree_checkout: true,
+                superproject_configuration: true,
+            }
+        );
+        let sm_repo = sm.open()?.expect("cloned");
+        assert!(sm_repo.head()?.is_detached());
+        assert_eq!(sm_repo.head_id()?, sm.index_id()?.expect("present"));
+        assert!(!sm_repo.is_bare(), "the clone is connected to its worktree");
+        assert!(!sm_repo.is_dirty(true)?);
+
+        assert_eq!(update(&sm, true)?, UpdateOutcome::default(), "nothing to do anymore");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_commits_are_fetched() -> crate::Result {
+        let (repo, _dir) = repo_rw("super-clone-index-ahead")?;
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+        let expected = sm.index_id()?.expect("present");
+        assert!(!sm.open()?.expect("initialized").has_object(expected));
+
+        let outcome = update(&sm, false)?;
+        assert_eq!(
+            outcome,
+            UpdateOutcome {
+                initialized: false,
+                cloned: false,
+                fetched: true,
+                checked_out: Some(expected),
+            }
+        );
+        let sm_repo = sm.open()?.expect("initialized");
+        assert_eq!(sm_repo.head_id()?, expected);
+        assert!(!sm_repo.is_dirty(true)?);
+        Ok(())
+    }
+
+    #[test]
+    fn only_the_checkout_strategy_is_supported() -> crate::Result {
+        let (repo, _dir) = repo_rw("super-clone-index-ahead")?;
+        for (strategy, expect_error) in [("rebase", true), ("merge", true), ("none", false)] {
+            let mut repo = gix::open_opts(repo.path(), crate::restricted())?;
+            let mut config = repo.config_snapshot_mut();
+            config.set_raw_value("submodule", Some("m1".into()), "update", strategy)?;
+            drop(config);
+            let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+            let res = update(&sm, false);
+            if expect_error {
+                assert!(
+                    matches!(
+                        res,
+                        Err(gix::submodule::update_with_checkout::Error::UnsupportedStrategy { .. })
+                    ),
+                    "{strategy}"
+                );
+            } else {
+                assert_eq!(res?, UpdateOutcome::default(), "submodules can opt out");
+            }
+        }
+        Ok(())
+    }
+}
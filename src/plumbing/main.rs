@@ -16,8 +16,9 @@ use gix::bstr::{io::BufReadExt, BString};
 
 use crate::plumbing::{
     options::{
-        attributes, commit, commitgraph, config, credential, exclude, free, fsck, index, mailmap, odb, revision, tree,
-        Args, Subcommands,
+        attributes, branch, commit, commitgraph, config, credential, diff, exclude, for_each_ref, free, fsck, index,
+        ls_tree, mailmap, merge, odb, repo_analytics, rev_parse, revision, shortlog, tag, tree, worktree, Args,
+        Subcommands,
     },
     show_progress,
 };
@@ -193,6 +194,25 @@ pub fn main() -> Result<()> {
                 None,
                 move |_progress, out, _err| core::repository::submodule::list(repository(Mode::Lenient)?, out, format),
             ),
+            #[cfg(feature = "gitoxide-core-blocking-client")]
+            crate::plumbing::options::submodule::Subcommands::Update { init, paths } => prepare_and_run(
+                "submodule-update",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |progress, out, _err| {
+                    core::repository::submodule::update(
+                        repository(Mode::LenientWithGitInstallConfig)?,
+                        paths,
+                        init,
+                        progress,
+                        out,
+                        format,
+                    )
+                },
+            ),
         },
         #[cfg(feature = "gitoxide-core-tools-archive")]
         Subcommands::Archive(crate::plumbing::options::archive::Platform {
@@ -201,6 +221,7 @@ pub fn main() -> Result<()> {
             compression_level,
             add_path,
             add_virtual_file,
+            deterministic,
             output_file,
             treeish,
         }) => prepare_and_run(
@@ -224,6 +245,7 @@ pub fn main() -> Result<()> {
                     progress,
                     core::repository::archive::Options {
                         add_paths: add_path,
+                        deterministic,
                         prefix,
                         files: add_virtual_file
                             .chunks(2)
@@ -311,22 +333,67 @@ pub fn main() -> Result<()> {
                 },
             )
             .map(|_| ()),
+            commitgraph::Subcommands::Write {
+                reachable,
+                stdin_commits,
+                changed_paths,
+            } => prepare_and_run(
+                "commitgraph-write",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                core::repository::commitgraph::write::PROGRESS_RANGE,
+                move |progress, out, _err| {
+                    core::repository::commitgraph::write(
+                        repository(Mode::Lenient)?,
+                        stdin_commits.then(stdin_or_bail).transpose()?,
+                        progress,
+                        out,
+                        &should_interrupt,
+                        core::repository::commitgraph::write::Options {
+                            reachable,
+                            changed_paths,
+                        },
+                    )
+                },
+            ),
         },
         #[cfg(feature = "gitoxide-core-blocking-client")]
         Subcommands::Clone(crate::plumbing::options::clone::Platform {
             handshake_info,
             bare,
+            mirror,
+            no_local,
+            no_hardlinks,
+            reference,
+            dissociate,
+            single_branch,
+            branch,
             no_tags,
             remote,
             shallow,
+            filter,
+            sparse,
+            recurse_submodules,
             directory,
         }) => {
             let opts = core::repository::clone::Options {
                 format,
                 bare,
+                mirror,
+                no_local,
+                no_hardlinks,
+                reference,
+                dissociate,
+                single_branch,
+                branch,
                 handshake_info,
                 no_tags,
                 shallow: shallow.into(),
+                filter,
+                sparse,
+                recurse_submodules,
             };
             prepare_and_run(
                 "clone",
@@ -344,7 +411,9 @@ pub fn main() -> Result<()> {
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
+            auto_maintenance,
             remote,
+            all,
             shallow,
             ref_spec,
         }) => {
@@ -352,11 +421,14 @@ pub fn main() -> Result<()> {
                 format,
                 dry_run,
                 remote,
+                all,
+                thread_limit,
                 handshake_info,
                 negotiation_info,
                 open_negotiation_graph,
                 shallow: shallow.into(),
                 ref_specs: ref_spec,
+                auto_maintenance,
             };
             prepare_and_run(
                 "fetch",
@@ -397,6 +469,7 @@ pub fn main() -> Result<()> {
                             ref_specs: ref_spec,
                             show_unmapped_remote_refs,
                         },
+                        _ => unreachable!("only reference listings are matched here"),
                     };
                     let context = core::repository::remote::refs::Options {
                         name_or_url: name,
@@ -442,9 +515,215 @@ pub fn main() -> Result<()> {
                         ))
                     }
                 }
+                remote::Subcommands::Add {
+                    tags,
+                    no_tags,
+                    name,
+                    url,
+                } => {
+                    let tags = match (tags, no_tags) {
+                        (true, _) => gix::remote::fetch::Tags::All,
+                        (false, true) => gix::remote::fetch::Tags::None,
+                        (false, false) => gix::remote::fetch::Tags::Included,
+                    };
+                    prepare_and_run(
+                        "remote-add",
+                        trace,
+                        verbose,
+                        progress,
+                        progress_keep_open,
+                        None,
+                        move |_progress, _out, _err| {
+                            core::repository::remote::add(repository(Mode::Lenient)?, &name, &url, tags)
+                        },
+                    )
+                }
+                remote::Subcommands::Remove { name } => prepare_and_run(
+                    "remote-remove",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, _out, _err| core::repository::remote::remove(repository(Mode::Lenient)?, &name),
+                ),
+                remote::Subcommands::Rename { old, new } => prepare_and_run(
+                    "remote-rename",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, _out, _err| {
+                        core::repository::remote::rename(repository(Mode::Lenient)?, &old, &new)
+                    },
+                ),
+                remote::Subcommands::SetUrl { push, add, name, url } => prepare_and_run(
+                    "remote-set-url",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, _out, _err| {
+                        core::repository::remote::set_url(repository(Mode::Lenient)?, &name, &url, push, add)
+                    },
+                ),
+                remote::Subcommands::Show {
+                    verbose: verbose_list,
+                    name,
+                } => prepare_and_run(
+                    "remote-show",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, out, _err| {
+                        let repo = repository(Mode::Lenient)?;
+                        match name {
+                            Some(name) => core::repository::remote::show(repo, &name, out),
+                            None => core::repository::remote::list(repo, verbose_list, out),
+                        }
+                    },
+                ),
+                remote::Subcommands::Prune { dry_run, name } => {
+                    #[cfg(feature = "gitoxide-core-blocking-client")]
+                    {
+                        prepare_and_run(
+                            "remote-prune",
+                            trace,
+                            auto_verbose,
+                            progress,
+                            progress_keep_open,
+                            core::repository::remote::prune::PROGRESS_RANGE,
+                            move |progress, out, _err| {
+                                core::repository::remote::prune(
+                                    repository(Mode::LenientWithGitInstallConfig)?,
+                                    name.as_deref(),
+                                    dry_run,
+                                    progress,
+                                    out,
+                                )
+                            },
+                        )
+                    }
+                    #[cfg(feature = "gitoxide-core-async-client")]
+                    {
+                        let (_handle, progress) = async_util::prepare(
+                            auto_verbose,
+                            trace,
+                            "remote-prune",
+                            Some(core::repository::remote::prune::PROGRESS_RANGE),
+                        );
+                        futures_lite::future::block_on(core::repository::remote::prune(
+                            repository(Mode::LenientWithGitInstallConfig)?,
+                            name.as_deref(),
+                            dry_run,
+                            progress,
+                            std::io::stdout(),
+                        ))
+                    }
+                }
             }
         }
-        Subcommands::Config(config::Platform { filter }) => prepare_and_run(
+        Subcommands::Config(config::Platform {
+            filter: _,
+            cmd: Some(cmd),
+        }) => {
+            fn to_scope(scope: config::Scope) -> Option<core::repository::config::Scope> {
+                use core::repository::config::Scope;
+                match scope {
+                    config::Scope { local: true, .. } => Some(Scope::Local),
+                    config::Scope { global: true, .. } => Some(Scope::Global),
+                    config::Scope { system: true, .. } => Some(Scope::System),
+                    config::Scope { worktree: true, .. } => Some(Scope::Worktree),
+                    _ => None,
+                }
+            }
+            match cmd {
+                config::Subcommands::Get {
+                    scope,
+                    value_type,
+                    all,
+                    show_origin,
+                    show_scope,
+                    key,
+                } => prepare_and_run(
+                    "config-get",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, out, _err| {
+                        use core::repository::config::get;
+                        core::repository::config::get(
+                            repository(Mode::LenientWithGitInstallConfig)?,
+                            key.as_ref(),
+                            get::Options {
+                                scope: to_scope(scope),
+                                value_type: value_type.map(|value_type| match value_type {
+                                    config::Type::Bool => get::Type::Bool,
+                                    config::Type::Int => get::Type::Int,
+                                    config::Type::BoolOrInt => get::Type::BoolOrInt,
+                                    config::Type::Path => get::Type::Path,
+                                }),
+                                all,
+                                show_origin,
+                                show_scope,
+                            },
+                            out,
+                        )
+                    },
+                ),
+                config::Subcommands::Set { scope, add, key, value } => prepare_and_run(
+                    "config-set",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, _out, _err| {
+                        core::repository::config::set(
+                            repository(Mode::Lenient)?,
+                            key.as_ref(),
+                            value.as_ref(),
+                            to_scope(scope),
+                            add,
+                        )
+                    },
+                ),
+                config::Subcommands::Unset { scope, all, key } => prepare_and_run(
+                    "config-unset",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, _out, _err| {
+                        core::repository::config::unset(repository(Mode::Lenient)?, key.as_ref(), to_scope(scope), all)
+                    },
+                ),
+                config::Subcommands::RenameSection { scope, old, new } => prepare_and_run(
+                    "config-rename-section",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    None,
+                    move |_progress, _out, _err| {
+                        core::repository::config::rename_section(
+                            repository(Mode::Lenient)?,
+                            old.as_ref(),
+                            new.as_ref(),
+                            to_scope(scope),
+                        )
+                    },
+                ),
+            }
+        }
+        Subcommands::Config(config::Platform { filter, cmd: None }) => prepare_and_run(
             "config-list",
             trace,
             verbose,
@@ -593,6 +872,7 @@ pub fn main() -> Result<()> {
                     repository,
                     expansion,
                     thin,
+                    deterministic,
                     statistics,
                     nondeterministic_count,
                     tips,
@@ -615,6 +895,7 @@ pub fn main() -> Result<()> {
                             let context = core::pack::create::Context {
                                 thread_limit,
                                 thin,
+                                deterministic,
                                 nondeterministic_thread_count: nondeterministic_count.then_some(counting_threads),
                                 pack_cache_size_in_bytes: pack_cache_size_mb.unwrap_or(0) * 1_000_000,
                                 object_cache_size_in_bytes: object_cache_size_mb.unwrap_or(0) * 1_000_000,
@@ -728,6 +1009,7 @@ pub fn main() -> Result<()> {
                             re_encode,
                             statistics,
                         },
+                    delta_chains,
                     path,
                 } => prepare_and_run(
                     "pack-verify",
@@ -744,6 +1026,7 @@ pub fn main() -> Result<()> {
                             progress,
                             verify::Context {
                                 output_statistics,
+                                output_delta_chains: delta_chains.then_some(format),
                                 out,
                                 err,
                                 thread_limit,
@@ -881,8 +1164,47 @@ pub fn main() -> Result<()> {
                 )
             },
         ),
+        Subcommands::RevParse(rev_parse::Platform {
+            verify,
+            short,
+            abbrev_ref,
+            show_toplevel,
+            git_dir,
+            is_inside_work_tree,
+            specs,
+        }) => prepare_and_run(
+            "rev-parse",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::rev_parse(
+                    repository(Mode::Lenient)?,
+                    specs,
+                    out,
+                    core::repository::rev_parse::Options {
+                        format,
+                        verify,
+                        short,
+                        abbrev_ref,
+                        show_toplevel,
+                        git_dir,
+                        is_inside_work_tree,
+                        current_dir: repository_path,
+                    },
+                )
+            },
+        ),
         Subcommands::Revision(cmd) => match cmd {
-            revision::Subcommands::List { spec, svg, limit } => prepare_and_run(
+            revision::Subcommands::List {
+                spec,
+                svg,
+                limit,
+                since,
+                until,
+            } => prepare_and_run(
                 "revision-list",
                 trace,
                 auto_verbose,
@@ -896,6 +1218,8 @@ pub fn main() -> Result<()> {
                         out,
                         core::repository::revision::list::Context {
                             limit,
+                            since,
+                            until,
                             spec,
                             format,
                             text: svg.map_or(core::repository::revision::list::Format::Text, |path| {
@@ -916,6 +1240,36 @@ pub fn main() -> Result<()> {
                     core::repository::revision::previous_branches(repository(Mode::Lenient)?, out, format)
                 },
             ),
+            revision::Subcommands::NameRev {
+                tags,
+                refs,
+                exclude,
+                name_only,
+                annotate_stdin,
+                specs,
+            } => prepare_and_run(
+                "revision-name-rev",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::revision::name_rev(
+                        repository(Mode::Lenient)?,
+                        specs,
+                        annotate_stdin.then(stdin_or_bail).transpose()?,
+                        out,
+                        core::repository::revision::name_rev::Options {
+                            format,
+                            tags_only: tags,
+                            refs,
+                            exclude,
+                            name_only,
+                        },
+                    )
+                },
+            ),
             revision::Subcommands::Explain { spec } => prepare_and_run(
                 "revision-explain",
                 trace,
@@ -972,6 +1326,312 @@ pub fn main() -> Result<()> {
                 },
             ),
         },
+        Subcommands::Diff(cmd) => match cmd {
+            diff::Subcommands::Tree {
+                shortstat,
+                numstat,
+                stat_width,
+                old_treeish,
+                new_treeish,
+            } => prepare_and_run(
+                "diff-tree",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::diff::tree(
+                        repository(Mode::Strict)?,
+                        &old_treeish,
+                        &new_treeish,
+                        if shortstat {
+                            gix::diff::stat::Format::ShortStat
+                        } else if numstat {
+                            gix::diff::stat::Format::NumStat
+                        } else {
+                            gix::diff::stat::Format::Stat { width: stat_width }
+                        },
+                        format,
+                        out,
+                    )
+                },
+            ),
+        },
+        Subcommands::Merge(cmd) => match cmd {
+            merge::Subcommands::Base { first, others } => prepare_and_run(
+                "merge-base",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::merge::base(repository(Mode::Lenient)?, &first, &others, format, out)
+                },
+            ),
+            merge::Subcommands::Commits {
+                strategy,
+                subtree_prefix,
+                ours,
+                theirs,
+            } => prepare_and_run(
+                "merge-commits",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    use gix::merge::commits::Strategy;
+                    let strategy = match strategy {
+                        merge::Strategy::Ort => Strategy::Ort,
+                        merge::Strategy::Octopus => Strategy::Octopus,
+                        merge::Strategy::Ours => Strategy::Ours,
+                        merge::Strategy::Subtree => Strategy::Subtree { prefix: subtree_prefix },
+                    };
+                    core::repository::merge::commits(repository(Mode::Strict)?, &ours, &theirs, strategy, format, out)
+                },
+            ),
+        },
+        Subcommands::Branch(cmd) => match cmd {
+            branch::Subcommands::List {
+                remotes,
+                all,
+                verbose: verbose_list,
+                contains,
+                merged,
+                no_merged,
+            } => prepare_and_run(
+                "branch-list",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::branch::list(
+                        repository(Mode::Lenient)?,
+                        format,
+                        out,
+                        core::repository::branch::list::Options {
+                            remotes,
+                            all,
+                            verbose: verbose_list,
+                            contains,
+                            merged,
+                            no_merged,
+                        },
+                    )
+                },
+            ),
+            branch::Subcommands::Create {
+                force,
+                name,
+                start_point,
+            } => prepare_and_run(
+                "branch-create",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| {
+                    core::repository::branch::create(repository(Mode::Strict)?, &name, start_point.as_deref(), force)
+                },
+            ),
+            branch::Subcommands::Rename { force, names } => prepare_and_run(
+                "branch-rename",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| {
+                    let (old, new) = match names.as_slice() {
+                        [new] => (None, new),
+                        [old, new] => (Some(old.as_str()), new),
+                        _ => unreachable!("clap ensures there are one or two names"),
+                    };
+                    core::repository::branch::rename(repository(Mode::Strict)?, old, new, force)
+                },
+            ),
+            branch::Subcommands::Delete { force, names } => prepare_and_run(
+                "branch-delete",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::branch::delete(repository(Mode::Strict)?, &names, force, out)
+                },
+            ),
+        },
+        Subcommands::Tag(cmd) => match cmd {
+            tag::Subcommands::List {
+                show_subject,
+                contains,
+                merged,
+                patterns,
+            } => prepare_and_run(
+                "tag-list",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::tag::list(
+                        repository(Mode::Lenient)?,
+                        format,
+                        out,
+                        core::repository::tag::list::Options {
+                            show_subject,
+                            contains,
+                            merged,
+                            patterns,
+                        },
+                    )
+                },
+            ),
+            tag::Subcommands::Create {
+                annotate,
+                message,
+                force,
+                name,
+                target,
+            } => prepare_and_run(
+                "tag-create",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| {
+                    core::repository::tag::create(
+                        repository(Mode::Strict)?,
+                        &name,
+                        core::repository::tag::create::Options {
+                            annotate,
+                            message,
+                            force,
+                            target,
+                        },
+                    )
+                },
+            ),
+            tag::Subcommands::Delete { names } => prepare_and_run(
+                "tag-delete",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| core::repository::tag::delete(repository(Mode::Strict)?, &names, out),
+            ),
+        },
+        Subcommands::Worktree(cmd) => match cmd {
+            worktree::Subcommands::List => prepare_and_run(
+                "worktree-list",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| core::repository::worktree::list(repository(Mode::Lenient)?, format, out),
+            ),
+            worktree::Subcommands::Add {
+                new_branch,
+                detach,
+                lock,
+                reason,
+                no_checkout,
+                path,
+                commitish,
+            } => prepare_and_run(
+                "worktree-add",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::worktree::add(
+                        repository(Mode::Strict)?,
+                        &path,
+                        commitish.as_deref(),
+                        new_branch.as_deref(),
+                        detach,
+                        lock.then(|| reason.unwrap_or_default()),
+                        !no_checkout,
+                        out,
+                    )
+                },
+            ),
+            worktree::Subcommands::Lock { reason, worktree } => prepare_and_run(
+                "worktree-lock",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| {
+                    core::repository::worktree::lock(repository(Mode::Lenient)?, &worktree, reason)
+                },
+            ),
+            worktree::Subcommands::Unlock { worktree } => prepare_and_run(
+                "worktree-unlock",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| core::repository::worktree::unlock(repository(Mode::Lenient)?, &worktree),
+            ),
+            worktree::Subcommands::Move { worktree, new_path } => prepare_and_run(
+                "worktree-move",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| {
+                    core::repository::worktree::move_to(repository(Mode::Lenient)?, &worktree, &new_path)
+                },
+            ),
+            worktree::Subcommands::Remove { force, worktree } => prepare_and_run(
+                "worktree-remove",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, _err| {
+                    core::repository::worktree::remove(repository(Mode::Lenient)?, &worktree, force)
+                },
+            ),
+            worktree::Subcommands::Prune { dry_run } => prepare_and_run(
+                "worktree-prune",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::worktree::prune(repository(Mode::Lenient)?, dry_run, verbose, out)
+                },
+            ),
+            worktree::Subcommands::Repair { paths } => prepare_and_run(
+                "worktree-repair",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| core::repository::worktree::repair(repository(Mode::Lenient)?, &paths, out),
+            ),
+        },
         Subcommands::Commit(cmd) => match cmd {
             commit::Subcommands::Verify { rev_spec } => prepare_and_run(
                 "commit-verify",
@@ -1060,6 +1720,38 @@ pub fn main() -> Result<()> {
                     )
                 },
             ),
+            tree::Subcommands::Export {
+                no_filters,
+                force,
+                keep_going,
+                treeish,
+                destination,
+                pathspec,
+            } => prepare_and_run(
+                "tree-export",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |progress, _out, err| {
+                    core::repository::tree::export(
+                        repository(Mode::Lenient)?,
+                        treeish.as_deref(),
+                        &destination,
+                        pathspec,
+                        progress,
+                        err,
+                        &should_interrupt,
+                        core::repository::tree::export::Options {
+                            filters: !no_filters,
+                            overwrite_existing: force,
+                            keep_going,
+                            thread_limit,
+                        },
+                    )
+                },
+            ),
         },
         Subcommands::Odb(cmd) => match cmd {
             odb::Subcommands::Stats => prepare_and_run(
@@ -1079,6 +1771,35 @@ pub fn main() -> Result<()> {
                     )
                 },
             ),
+            odb::Subcommands::PackLoose {
+                compression_level,
+                keep_loose,
+                multi_pack_index,
+            } => prepare_and_run(
+                "odb-pack-loose",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                core::repository::odb::pack_loose_objects::PROGRESS_RANGE,
+                move |progress, out, _err| {
+                    core::repository::odb::pack_loose_objects(
+                        repository(Mode::Strict)?,
+                        progress,
+                        out,
+                        &should_interrupt,
+                        core::repository::odb::pack_loose_objects::Options {
+                            thread_limit,
+                            compression_level: compression_level.map(|level| match level {
+                                -1 => gix::features::zlib::stream::deflate::LEVEL_DEFAULT,
+                                level => level as u32,
+                            }),
+                            keep_loose_objects: keep_loose,
+                            write_multi_pack_index: multi_pack_index,
+                        },
+                    )
+                },
+            ),
             odb::Subcommands::Entries => prepare_and_run(
                 "odb-entries",
                 trace,
@@ -1107,6 +1828,112 @@ pub fn main() -> Result<()> {
             None,
             move |_progress, out, _err| core::repository::fsck(repository(Mode::Strict)?, spec, out),
         ),
+        Subcommands::ForEachRef(for_each_ref::Platform {
+            format: ref_format,
+            sort,
+            points_at,
+            merged,
+            patterns,
+        }) => prepare_and_run(
+            "for-each-ref",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::for_each_ref(
+                    repository(Mode::Lenient)?,
+                    out,
+                    core::repository::for_each_ref::Options {
+                        format,
+                        ref_format,
+                        sort,
+                        points_at,
+                        merged,
+                        patterns,
+                    },
+                )
+            },
+        ),
+        Subcommands::RepoAnalytics(repo_analytics::Platform { limit, revisions }) => prepare_and_run(
+            "repo-analytics",
+            trace,
+            auto_verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |progress, out, _err| {
+                core::repository::analytics(
+                    repository(Mode::Lenient)?,
+                    progress,
+                    out,
+                    core::repository::analytics::Options {
+                        format,
+                        limit,
+                        revisions,
+                    },
+                )
+            },
+        ),
+        Subcommands::Shortlog(shortlog::Platform {
+            summary,
+            numbered,
+            email,
+            revisions,
+        }) => prepare_and_run(
+            "shortlog",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::shortlog(
+                    repository(Mode::Lenient)?,
+                    out,
+                    core::repository::shortlog::Options {
+                        format,
+                        summary,
+                        numbered,
+                        email,
+                        revisions,
+                    },
+                )
+            },
+        ),
+        Subcommands::LsTree(ls_tree::Platform {
+            recursive,
+            show_trees,
+            long,
+            name_only,
+            max_depth,
+            treeish,
+            pathspecs,
+        }) => prepare_and_run(
+            "ls-tree",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::ls_tree(
+                    repository(Mode::Lenient)?,
+                    &treeish,
+                    out,
+                    core::repository::ls_tree::Options {
+                        format,
+                        recursive: recursive || max_depth.is_some(),
+                        show_trees,
+                        long,
+                        name_only,
+                        max_depth,
+                        pathspecs,
+                    },
+                )
+            },
+        ),
         Subcommands::Mailmap(cmd) => match cmd {
             mailmap::Subcommands::Entries => prepare_and_run(
                 "mailmap-entries",
@@ -1272,6 +2099,32 @@ pub fn main() -> Result<()> {
                     )
                 },
             ),
+            index::Subcommands::UpdateFlags {
+                assume_unchanged,
+                no_assume_unchanged,
+                skip_worktree,
+                no_skip_worktree,
+                pathspec,
+            } => prepare_and_run(
+                "index-update-flags",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, _out, err| {
+                    let flag = |set: bool, clear: bool| (set || clear).then_some(set);
+                    core::repository::index::update_flags(
+                        repository(Mode::Strict)?,
+                        pathspec,
+                        err,
+                        core::repository::index::update_flags::Options {
+                            assume_unchanged: flag(assume_unchanged, no_assume_unchanged),
+                            skip_worktree: flag(skip_worktree, no_skip_worktree),
+                        },
+                    )
+                },
+            ),
         },
         Subcommands::Completions { shell, out_dir } => {
             let mut app = Args::command();
@@ -167,6 +167,13 @@ pub mod pack {
             /// Packs produced with this option enabled are only valid in transit, but not at rest.
             thin: bool,
 
+            #[clap(long)]
+            /// if set, the pack will be byte-identical for identical input, independently of the amount of threads used for counting
+            /// and of the configured compression level, which is fixed instead.
+            ///
+            /// This is useful for caching and reproducible builds.
+            deterministic: bool,
+
             /// The directory into which to write the pack file.
             #[clap(long, short = 'o')]
             output_directory: Option<PathBuf>,
@@ -250,6 +257,12 @@ pub mod pack {
             #[clap(flatten)]
             args: VerifyOptions,
 
+            /// Print each object along with its delta chain, followed by delta chain histograms, like `git verify-pack -v`.
+            ///
+            /// Only works with '.idx' files whose pack is present.
+            #[clap(long)]
+            delta_chains: bool,
+
             /// The '.pack', '.idx' or 'multi-pack-index' file to validate.
             path: PathBuf,
         },
@@ -89,13 +89,36 @@ pub enum Subcommands {
     Odb(odb::Subcommands),
     /// Check for missing objects.
     Fsck(fsck::Platform),
+    /// List references with custom formatting and sorting, like `git for-each-ref`.
+    ForEachRef(for_each_ref::Platform),
+    /// List the contents of a tree, like `git ls-tree`.
+    LsTree(ls_tree::Platform),
+    /// Find the largest objects, the paths that contributed most to the size of the history and objects stored more than once.
+    RepoAnalytics(repo_analytics::Platform),
+    /// Summarize commits by author, like `git shortlog`.
+    Shortlog(shortlog::Platform),
     /// Interact with tree objects.
     #[clap(subcommand)]
     Tree(tree::Subcommands),
     /// Interact with commit objects.
     #[clap(subcommand)]
     Commit(commit::Subcommands),
-    /// Verify the integrity of the entire repository
+    /// Show changes between trees.
+    #[clap(subcommand)]
+    Diff(diff::Subcommands),
+    /// Merge commits and compute merge-bases without touching the worktree.
+    #[clap(subcommand)]
+    Merge(merge::Subcommands),
+    /// Manage linked worktrees.
+    #[clap(subcommand)]
+    Worktree(worktree::Subcommands),
+    /// List, create, rename and delete branches.
+    #[clap(subcommand)]
+    Branch(branch::Subcommands),
+    /// List, create and delete tags.
+    #[clap(subcommand)]
+    Tag(tag::Subcommands),
+    /// Verify the integrity of the entire repository, including object databases, commit-graphs, reflogs and the index
     Verify {
         #[clap(flatten)]
         args: free::pack::VerifyOptions,
@@ -103,6 +126,8 @@ pub enum Subcommands {
     /// Query and obtain information about revisions.
     #[clap(subcommand)]
     Revision(revision::Subcommands),
+    /// Resolve revisions and query repository paths, like `git rev-parse`.
+    RevParse(rev_parse::Platform),
     /// A program just like `git credential`.
     #[clap(subcommand)]
     Credential(credential::Subcommands),
@@ -181,6 +206,9 @@ pub mod archive {
         /// Add the given path to the archive. Directories will always be empty.
         #[clap(long, short = 'p')]
         pub add_path: Vec<PathBuf>,
+        /// Produce the same archive for the same input, using a fixed timestamp instead of the current time if `treeish` isn't a commit.
+        #[clap(long)]
+        pub deterministic: bool,
         /// Add the new file from a slash-separated path, which must happen in pairs of two, first the path, then the content.
         #[clap(long, short = 'v')]
         pub add_virtual_file: Vec<String>,
@@ -277,7 +305,7 @@ pub mod config {
 
     /// Print all entries in a configuration file or access other sub-commands
     #[derive(Debug, clap::Parser)]
-    #[clap(subcommand_required(false))]
+    #[clap(subcommand_required(false), args_conflicts_with_subcommands(true))]
     pub struct Platform {
         /// The filter terms to limit the output to matching sections and subsections only.
         ///
@@ -285,6 +313,97 @@ pub mod config {
         /// and comparisons are case-insensitive.
         #[clap(value_parser = gitoxide::shared::AsBString)]
         pub filter: Vec<BString>,
+        #[clap(subcommand)]
+        pub cmd: Option<Subcommands>,
+    }
+
+    /// The configuration file to use, with all of them being read by default, and the local one being written to.
+    #[derive(Debug, clap::Args)]
+    pub struct Scope {
+        /// Use the configuration file of the repository.
+        #[clap(long, group = "scope")]
+        pub local: bool,
+        /// Use the configuration file of the current user.
+        #[clap(long, group = "scope")]
+        pub global: bool,
+        /// Use the configuration file of the system.
+        #[clap(long, group = "scope")]
+        pub system: bool,
+        /// Use the configuration file of the current worktree.
+        #[clap(long, group = "scope")]
+        pub worktree: bool,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+    pub enum Type {
+        /// Print `true` or `false`.
+        Bool,
+        /// Print a decimal number, with unit suffixes like `k` applied.
+        Int,
+        /// Print a decimal number if the value is one, or a boolean otherwise.
+        BoolOrInt,
+        /// Print a path, with `~/` expanded to the home directory.
+        Path,
+    }
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// Print the value of a key like `core.bare` or `remote.origin.url`.
+        Get {
+            #[clap(flatten)]
+            scope: Scope,
+            /// Interpret the value as the given type and print it in its canonical form.
+            #[clap(long = "type", short = 't', value_enum)]
+            value_type: Option<Type>,
+            /// Print all values of a multi-valued key instead of only the last one.
+            #[clap(long)]
+            all: bool,
+            /// Print the file each value is read from.
+            #[clap(long)]
+            show_origin: bool,
+            /// Print the scope of the file each value is read from, like `local` or `global`.
+            #[clap(long)]
+            show_scope: bool,
+            /// The key to print.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            key: BString,
+        },
+        /// Set the value of a key, replacing its last value.
+        Set {
+            #[clap(flatten)]
+            scope: Scope,
+            /// Add the value to the existing ones instead of replacing the last one.
+            #[clap(long)]
+            add: bool,
+            /// The key to set.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            key: BString,
+            /// The value to set.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            value: BString,
+        },
+        /// Remove a key.
+        Unset {
+            #[clap(flatten)]
+            scope: Scope,
+            /// Remove all values of a multi-valued key, which is refused otherwise.
+            #[clap(long)]
+            all: bool,
+            /// The key to remove.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            key: BString,
+        },
+        /// Rename all sections with the given name, like `branch.old` to `branch.new`.
+        RenameSection {
+            #[clap(flatten)]
+            scope: Scope,
+            /// The name of the sections to rename, as `section` or `section.subsection`.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            old: BString,
+            /// The new name of the sections.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            new: BString,
+        },
     }
 }
 
@@ -312,6 +431,12 @@ pub mod fetch {
         #[clap(long, value_name = "PATH", short = 'g')]
         pub open_negotiation_graph: Option<std::path::PathBuf>,
 
+        /// Pack loose objects and index packs if `gc.auto` or `gc.autoPackLimit` are exceeded after fetching.
+        ///
+        /// With `gc.autoDetach` set to `true`, `git gc --auto` is spawned to do the work in the background instead.
+        #[clap(long)]
+        pub auto_maintenance: bool,
+
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
@@ -321,6 +446,10 @@ pub mod fetch {
         #[clap(long, short = 'r')]
         pub remote: Option<String>,
 
+        /// Fetch from all configured remotes concurrently, with `--threads` limiting how many are fetched at once.
+        #[clap(long, conflicts_with_all = ["remote", "ref_spec", "open_negotiation_graph"])]
+        pub all: bool,
+
         /// Override the built-in and configured ref-specs with one or more of the given ones.
         #[clap(value_parser = gitoxide::shared::AsBString)]
         pub ref_spec: Vec<gix::bstr::BString>,
@@ -375,7 +504,7 @@ pub mod fetch {
 pub mod clone {
     use std::{ffi::OsString, num::NonZeroU32, path::PathBuf};
 
-    use gix::remote::fetch::Shallow;
+    use gix::{bstr::BString, remote::fetch::Shallow};
 
     #[derive(Debug, clap::Parser)]
     pub struct Platform {
@@ -384,9 +513,40 @@ pub mod clone {
         pub handshake_info: bool,
 
         /// The clone will be bare and a working tree checkout won't be available.
+        ///
+        /// Remote branches are stored as local branches, like `git clone --bare` does.
         #[clap(long)]
         pub bare: bool,
 
+        /// Mirror all references of the remote into a bare clone, and keep mirroring them on subsequent fetches.
+        #[clap(long, conflicts_with = "bare")]
+        pub mirror: bool,
+
+        /// Receive objects through the pack protocol even if the remote is a local path, instead of hardlinking them.
+        #[clap(long)]
+        pub no_local: bool,
+
+        /// Copy objects instead of hardlinking them if the remote is a local path.
+        #[clap(long, conflicts_with = "no_local")]
+        pub no_hardlinks: bool,
+
+        /// Borrow objects from the repository at the given path instead of receiving them. Can be given multiple times.
+        #[clap(long, value_name = "REPOSITORY")]
+        pub reference: Vec<PathBuf>,
+
+        /// Copy the objects borrowed from alternates once the clone is done, to obtain an independent repository.
+        #[clap(long)]
+        pub dissociate: bool,
+
+        /// Only fetch the branch that the remote HEAD points to, or the one given with `--branch`, now and in future.
+        #[clap(long)]
+        pub single_branch: bool,
+
+        /// The name of the branch to point HEAD to instead of the one the remote HEAD points to,
+        /// and the only one to fetch with `--single-branch`.
+        #[clap(long, short = 'b', value_name = "NAME", value_parser = gitoxide::shared::AsBString)]
+        pub branch: Option<BString>,
+
         /// Do not clone any tags. Useful to reduce the size of the clone if only branches are needed.
         #[clap(long)]
         pub no_tags: bool,
@@ -394,6 +554,20 @@ pub mod clone {
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
+        /// Create a partial clone by asking the remote to omit objects, like `blob:none` or `blob:limit=1m`.
+        ///
+        /// The remote is configured as promisor, and the filter is remembered for future fetches.
+        #[clap(long, value_name = "FILTER-SPEC")]
+        pub filter: Option<String>,
+
+        /// Initialize a sparse-checkout in cone mode so only files in the top-level directory are checked out.
+        #[clap(long, conflicts_with = "bare")]
+        pub sparse: bool,
+
+        /// Clone and check out all submodules recursively once the main worktree is checked out.
+        #[clap(long, conflicts_with = "bare")]
+        pub recurse_submodules: bool,
+
         /// The url of the remote to connect to, like `https://github.com/byron/gitoxide`.
         pub remote: OsString,
 
@@ -467,6 +641,61 @@ pub mod remote {
             #[clap(value_parser = gitoxide::shared::AsBString)]
             ref_spec: Vec<gix::bstr::BString>,
         },
+        /// Add a new remote fetching all branches from the given URL.
+        Add {
+            /// Fetch all tags from the remote, not only those pointing to fetched commits.
+            #[clap(long, conflicts_with = "no_tags")]
+            tags: bool,
+            /// Never fetch tags from the remote.
+            #[clap(long)]
+            no_tags: bool,
+            /// The name of the new remote.
+            name: String,
+            /// The URL to fetch from.
+            url: String,
+        },
+        /// Remove a remote, its remote tracking branches and the upstream configuration of branches tracking it.
+        #[clap(visible_alias = "rm")]
+        Remove {
+            /// The name of the remote to remove.
+            name: String,
+        },
+        /// Rename a remote along with its remote tracking branches.
+        Rename {
+            /// The current name of the remote.
+            old: String,
+            /// The new name of the remote.
+            new: String,
+        },
+        /// Change the URL of a remote.
+        SetUrl {
+            /// Change the push URL instead of the fetch URL.
+            #[clap(long)]
+            push: bool,
+            /// Add the URL instead of replacing the existing one.
+            #[clap(long)]
+            add: bool,
+            /// The name of the remote to change.
+            name: String,
+            /// The new URL.
+            url: String,
+        },
+        /// Show information about a remote, or list all remotes if no name is given.
+        Show {
+            /// Also print the URLs when listing all remotes.
+            #[clap(long, short = 'v')]
+            verbose: bool,
+            /// The name of the remote to show.
+            name: Option<String>,
+        },
+        /// Delete remote tracking branches whose branch doesn't exist on the remote anymore.
+        Prune {
+            /// Only print the remote tracking branches that would be deleted.
+            #[clap(long, short = 'n')]
+            dry_run: bool,
+            /// The name of the remote to prune, or the remote of the current branch if unset.
+            name: Option<String>,
+        },
     }
 }
 
@@ -485,9 +714,24 @@ pub mod odb {
         Entries,
         /// Provide general information about the object database.
         Info,
-        /// Count and obtain information on all, possibly duplicate, objects in the database.
+        /// Count and obtain information on all, possibly duplicate, objects in the database, along with statistics about
+        /// packs, loose objects, memory-mapped files and alternates.
         #[clap(visible_alias = "statistics")]
         Stats,
+        /// Write all loose objects into a new pack and delete them afterwards.
+        PackLoose {
+            /// The zlib compression level from -1 (zlib's default) to 9 (best), instead of the configured one.
+            #[clap(long, short = 'l', allow_hyphen_values = true, value_parser = clap::value_parser!(i8).range(-1..=9))]
+            compression_level: Option<i8>,
+            /// Keep the loose objects after they were written to the pack.
+            #[clap(long)]
+            keep_loose: bool,
+            /// Write a multi-pack index for all packs, even if there is none yet.
+            ///
+            /// An existing multi-pack index is always updated.
+            #[clap(long)]
+            multi_pack_index: bool,
+        },
     }
 }
 
@@ -499,7 +743,132 @@ pub mod fsck {
     }
 }
 
+pub mod for_each_ref {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The format of each line with placeholders like `%(refname:short)`, `%(objectname)`, `%(objecttype)`,
+        /// `%(upstream:track)` or `%(contents:subject)`.
+        #[clap(long, value_parser = gitoxide::shared::AsBString)]
+        pub format: Option<BString>,
+        /// A field to sort by like `refname`, `version:refname` or `-committerdate` for descending order.
+        ///
+        /// Can be given multiple times, with the last key being the primary one.
+        #[clap(long, value_parser = gitoxide::shared::AsBString)]
+        pub sort: Vec<BString>,
+        /// Only list references pointing at the given object, directly or after peeling tags.
+        #[clap(long, value_name = "OBJECT")]
+        pub points_at: Option<std::ffi::OsString>,
+        /// Only list references whose tips are reachable from the given commit.
+        #[clap(long, value_name = "COMMIT")]
+        pub merged: Option<std::ffi::OsString>,
+        /// Only list references matching one of the given prefixes like `refs/heads`, or glob patterns like `refs/tags/v1.*`.
+        #[clap(value_parser = gitoxide::shared::AsBString)]
+        pub patterns: Vec<BString>,
+    }
+}
+
+pub mod ls_tree {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Recurse into subtrees.
+        #[clap(short = 'r')]
+        pub recursive: bool,
+        /// Show tree entries even when recursing into them.
+        #[clap(short = 't')]
+        pub show_trees: bool,
+        /// Show the size of blob entries.
+        #[clap(short = 'l', long)]
+        pub long: bool,
+        /// List only the paths of entries, one per line.
+        #[clap(long)]
+        pub name_only: bool,
+        /// Don't recurse deeper than the given amount of levels, with 0 meaning only the top-level tree.
+        ///
+        /// Implies `-r`.
+        #[clap(long, value_name = "DEPTH")]
+        pub max_depth: Option<usize>,
+        /// The revision resolving to the tree to list, like `HEAD` or `main:src`.
+        pub treeish: String,
+        /// Only list entries matching these pathspecs.
+        #[clap(value_parser = gitoxide::shared::AsBString)]
+        pub pathspecs: Vec<BString>,
+    }
+}
+
+pub mod repo_analytics {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The amount of entries to show in each section of the report.
+        #[clap(long, short = 'n', default_value_t = 10)]
+        pub limit: usize,
+        /// The revisions whose history to attribute sizes to paths with, instead of using all references.
+        #[clap(value_parser = gitoxide::shared::AsBString)]
+        pub revisions: Vec<BString>,
+    }
+}
+
+pub mod shortlog {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Only print the amount of commits of each author.
+        #[clap(long, short = 's')]
+        pub summary: bool,
+        /// Sort authors by their amount of commits instead of alphabetically.
+        #[clap(long, short = 'n')]
+        pub numbered: bool,
+        /// Show the email address of each author.
+        #[clap(long, short = 'e')]
+        pub email: bool,
+        /// The revisions or ranges like `v1.0..main` to take commits from, or `HEAD` if unset.
+        #[clap(value_parser = gitoxide::shared::AsBString)]
+        pub revisions: Vec<BString>,
+    }
+}
+
+pub mod rev_parse {
+    use std::ffi::OsString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Fail unless exactly one revision is given which resolves to a single object.
+        #[clap(long)]
+        pub verify: bool,
+        /// Shorten object ids to a unique prefix of at least the given length, or of the length configured by `core.abbrev`.
+        ///
+        /// Implies `--verify`.
+        #[clap(long, value_name = "LENGTH", num_args = 0..=1, require_equals = true)]
+        pub short: Option<Option<usize>>,
+        /// Print the short name of the reference each revision refers to instead of its object id.
+        #[clap(long)]
+        pub abbrev_ref: bool,
+        /// Print the absolute path to the top-level directory of the work tree.
+        #[clap(long)]
+        pub show_toplevel: bool,
+        /// Print the path to the `.git` directory.
+        #[clap(long)]
+        pub git_dir: bool,
+        /// Print `true` if the repository directory is inside of the work tree, or `false` otherwise.
+        #[clap(long)]
+        pub is_inside_work_tree: bool,
+        /// The revisions to resolve, like `HEAD`, `@~2`, `main..feature` or `v1.0^{tree}`.
+        pub specs: Vec<OsString>,
+    }
+}
+
 pub mod tree {
+    use std::path::PathBuf;
+
+    use gitoxide::shared::CheckPathSpec;
+    use gix::bstr::BString;
+
     #[derive(Debug, clap::Subcommand)]
     pub enum Subcommands {
         /// Print entries in a given tree
@@ -523,6 +892,277 @@ pub mod tree {
             /// The revspec of the tree to traverse, or the tree at `HEAD` if unspecified.
             treeish: Option<String>,
         },
+        /// Write the files of a tree into a directory, without touching the index or `HEAD`.
+        Export {
+            /// Write blobs exactly as stored, without applying filters or end-of-line conversions.
+            #[clap(long)]
+            no_filters: bool,
+            /// Overwrite files that already exist in the destination directory.
+            #[clap(long, short = 'f')]
+            force: bool,
+            /// Keep writing files after errors, and report them at the end.
+            #[clap(long)]
+            keep_going: bool,
+            /// The revspec of the tree to export, or the tree at `HEAD` if unspecified.
+            #[clap(long, short = 't')]
+            treeish: Option<String>,
+            /// The directory to write the files into, which is created if needed.
+            destination: PathBuf,
+            /// Pathspecs to limit the exported files to, or all files if none are given.
+            #[clap(value_parser = CheckPathSpec)]
+            pathspec: Vec<BString>,
+        },
+    }
+}
+
+pub mod diff {
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// Print statistics of the changes needed to turn one tree into another, with renames tracked as configured.
+        Tree {
+            /// Print only the amount of changed files, inserted and removed lines, like `git diff --shortstat`.
+            #[clap(long, conflicts_with("numstat"))]
+            shortstat: bool,
+
+            /// Print the amount of inserted and removed lines of each file in a machine-readable format, like `git diff --numstat`.
+            #[clap(long, conflicts_with("shortstat"))]
+            numstat: bool,
+
+            /// The maximum width of each line if neither `--shortstat` nor `--numstat` are given.
+            #[clap(long, default_value = "80")]
+            stat_width: usize,
+
+            /// A revspec pointing to the tree to diff from.
+            old_treeish: String,
+
+            /// A revspec pointing to the tree to diff to.
+            new_treeish: String,
+        },
+    }
+}
+
+pub mod merge {
+    use gix::bstr::BString;
+
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+    pub enum Strategy {
+        /// A three-way merge of a single commit, with a virtual merge-base if there are multiple merge-bases.
+        #[default]
+        Ort,
+        /// Merge any amount of commits one after another, stopping at the first conflict.
+        Octopus,
+        /// Keep the tree of the commit to merge into.
+        Ours,
+        /// Like `ort`, but with the commit to merge moved into a subdirectory first.
+        Subtree,
+    }
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// Print all best common ancestors of the given commits, like `git merge-base --all`.
+        Base {
+            /// A revspec pointing to the first commit.
+            first: String,
+
+            /// Revspecs pointing to the commits to find common ancestors with.
+            #[clap(required = true)]
+            others: Vec<String>,
+        },
+        /// Merge commits into a tree which is written to the object database, and print its id along with all conflicts,
+        /// like `git merge-tree --write-tree`.
+        Commits {
+            /// The way to merge the commits, like `git merge --strategy`.
+            #[clap(long, short = 's', default_value = "ort", value_enum)]
+            strategy: Strategy,
+
+            /// The subdirectory to move the commit to merge into with the `subtree` strategy, or the one most similar to it if unset.
+            #[clap(long, value_parser = gitoxide::shared::AsBString)]
+            subtree_prefix: Option<BString>,
+
+            /// A revspec pointing to the commit to merge into, also known as _ours_.
+            ours: String,
+
+            /// Revspecs pointing to the commits to merge, also known as _theirs_.
+            #[clap(required = true)]
+            theirs: Vec<String>,
+        },
+    }
+}
+
+pub mod worktree {
+    use std::path::PathBuf;
+
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// List the main worktree and all linked worktrees along with their `HEAD`.
+        List,
+        /// Create a new linked worktree and check out a branch or commit in it.
+        Add {
+            /// Create a new branch with the given name at the commit to check out.
+            #[clap(short = 'b', long, conflicts_with = "detach")]
+            new_branch: Option<String>,
+            /// Detach `HEAD` at the commit to check out instead of checking out a branch.
+            #[clap(long)]
+            detach: bool,
+            /// Keep the new worktree locked after its creation.
+            #[clap(long)]
+            lock: bool,
+            /// The reason for locking the new worktree.
+            #[clap(long, requires = "lock", value_parser = gitoxide::shared::AsBString)]
+            reason: Option<BString>,
+            /// Don't check out any files into the new worktree.
+            #[clap(long)]
+            no_checkout: bool,
+            /// The directory to create the worktree in.
+            path: PathBuf,
+            /// The branch or commit to check out, or `HEAD` if unset.
+            commitish: Option<String>,
+        },
+        /// Prevent a linked worktree from being pruned, moved or removed.
+        Lock {
+            /// The reason for locking the worktree.
+            #[clap(long, value_parser = gitoxide::shared::AsBString)]
+            reason: Option<BString>,
+            /// The path or id of the worktree.
+            worktree: PathBuf,
+        },
+        /// Allow a locked worktree to be pruned, moved or removed again.
+        Unlock {
+            /// The path or id of the worktree.
+            worktree: PathBuf,
+        },
+        /// Move a linked worktree to a new location.
+        Move {
+            /// The path or id of the worktree.
+            worktree: PathBuf,
+            /// The new location, or the directory to move the worktree into if it exists.
+            new_path: PathBuf,
+        },
+        /// Remove a linked worktree along with its checkout.
+        Remove {
+            /// Remove the worktree even if it contains modifications, and if given twice, even if it is locked.
+            #[clap(long, short = 'f', action = clap::ArgAction::Count)]
+            force: u8,
+            /// The path or id of the worktree.
+            worktree: PathBuf,
+        },
+        /// Remove the administrative files of worktrees whose checkout doesn't exist anymore.
+        Prune {
+            /// Only print what would be pruned.
+            #[clap(long, short = 'n')]
+            dry_run: bool,
+        },
+        /// Repair the links between worktrees and their administrative files, after worktrees were moved manually.
+        Repair {
+            /// The new locations of worktrees that were moved manually.
+            paths: Vec<PathBuf>,
+        },
+    }
+}
+
+pub mod branch {
+    use std::ffi::OsString;
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// List local branches, marking the checked out one with `*`.
+        List {
+            /// List remote tracking branches instead.
+            #[clap(long, short = 'r', conflicts_with = "all")]
+            remotes: bool,
+            /// List local and remote tracking branches.
+            #[clap(long, short = 'a')]
+            all: bool,
+            /// Show the commit and subject of each branch, along with how far it is ahead or behind its upstream.
+            #[clap(long, short = 'v')]
+            verbose: bool,
+            /// Only list branches which contain the given commit.
+            #[clap(long, value_name = "COMMIT")]
+            contains: Option<OsString>,
+            /// Only list branches whose tips are reachable from the given commit.
+            #[clap(long, value_name = "COMMIT")]
+            merged: Option<OsString>,
+            /// Only list branches whose tips are not reachable from the given commit.
+            #[clap(long, value_name = "COMMIT")]
+            no_merged: Option<OsString>,
+        },
+        /// Create a new branch.
+        Create {
+            /// Reset the branch to the start point if it already exists.
+            #[clap(long, short = 'f')]
+            force: bool,
+            /// The name of the branch, without `refs/heads/` prefix.
+            name: String,
+            /// The commit the branch should point to, or `HEAD` if unset.
+            start_point: Option<OsString>,
+        },
+        /// Rename a branch along with its reflog and configuration.
+        Rename {
+            /// Overwrite the branch with the new name if it already exists.
+            #[clap(long, short = 'f')]
+            force: bool,
+            /// The branch to rename, or the checked out branch if only the new name is given, followed by its new name.
+            #[clap(required = true, num_args = 1..=2, value_name = "[OLD] NEW")]
+            names: Vec<String>,
+        },
+        /// Delete branches along with their configuration.
+        Delete {
+            /// Delete branches even if they are not merged into their upstream or `HEAD`.
+            #[clap(long, short = 'f')]
+            force: bool,
+            /// The names of the branches to delete.
+            #[clap(required = true)]
+            names: Vec<String>,
+        },
+    }
+}
+
+pub mod tag {
+    use std::ffi::OsString;
+
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// List tags in version order.
+        List {
+            /// Show the subject of each tag.
+            #[clap(short = 'n')]
+            show_subject: bool,
+            /// Only list tags which contain the given commit.
+            #[clap(long, value_name = "COMMIT")]
+            contains: Option<OsString>,
+            /// Only list tags whose commits are reachable from the given commit.
+            #[clap(long, value_name = "COMMIT")]
+            merged: Option<OsString>,
+            /// Only list tags matching one of the given glob patterns, like `v1.*`.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            patterns: Vec<BString>,
+        },
+        /// Create a lightweight or annotated tag.
+        Create {
+            /// Create an annotated tag, whose message is obtained from the editor unless `--message` is given.
+            #[clap(long, short = 'a')]
+            annotate: bool,
+            /// The message of an annotated tag, which implies `--annotate`.
+            #[clap(long, short = 'm')]
+            message: Option<String>,
+            /// Replace the tag if it already exists.
+            #[clap(long, short = 'f')]
+            force: bool,
+            /// The name of the tag, without `refs/tags/` prefix.
+            name: String,
+            /// The object to tag, or `HEAD` if unset.
+            target: Option<OsString>,
+        },
+        /// Delete tags.
+        Delete {
+            /// The names of the tags to delete.
+            #[clap(required = true)]
+            names: Vec<String>,
+        },
     }
 }
 
@@ -589,12 +1229,24 @@ pub mod credential {
 pub mod commitgraph {
     #[derive(Debug, clap::Subcommand)]
     pub enum Subcommands {
-        /// Verify the integrity of a commit graph
+        /// Verify the integrity of a commit graph and check it against the object database.
         Verify {
             /// output statistical information about the pack
             #[clap(long, short = 's')]
             statistics: bool,
         },
+        /// Write a commit-graph file for all commits in the object database, replacing the existing one.
+        Write {
+            /// Start the traversal from all references instead of using all commits in the object database.
+            #[clap(long, conflicts_with("stdin_commits"))]
+            reachable: bool,
+            /// Start the traversal from the commits read from stdin, one per line.
+            #[clap(long)]
+            stdin_commits: bool,
+            /// Also write bloom filters for the paths changed by each commit to speed up path-limited traversals.
+            #[clap(long)]
+            changed_paths: bool,
+        },
         /// List all entries in the commit-graph as reachable by starting from `HEAD`.
         List {
             /// The rev-spec to list reachable commits from.
@@ -640,6 +1292,12 @@ pub mod revision {
             /// Write the graph as SVG file to the given path.
             #[clap(long, short = 's')]
             svg: Option<std::path::PathBuf>,
+            /// Only list commits more recent than the given date, like "2 weeks ago".
+            #[clap(long, value_parser = gitoxide::shared::AsTime, value_name = "DATE")]
+            since: Option<gix::date::Time>,
+            /// Only list commits older than the given date.
+            #[clap(long, value_parser = gitoxide::shared::AsTime, value_name = "DATE")]
+            until: Option<gix::date::Time>,
             /// The rev-spec to list reachable commits from.
             #[clap(default_value = "@")]
             spec: std::ffi::OsString,
@@ -671,6 +1329,27 @@ pub mod revision {
         /// Return the names and hashes of all previously checked-out branches.
         #[clap(visible_alias = "prev")]
         PreviousBranches,
+        /// Name the given revisions relative to references, like `git name-rev`.
+        NameRev {
+            /// Only use tags to name revisions.
+            #[clap(long)]
+            tags: bool,
+            /// Only use references matching the given glob pattern, can be given multiple times.
+            #[clap(long = "refs", value_name = "PATTERN", value_parser = gitoxide::shared::AsBString)]
+            refs: Vec<gix::bstr::BString>,
+            /// Do not use references matching the given glob pattern, can be given multiple times.
+            #[clap(long, value_name = "PATTERN", value_parser = gitoxide::shared::AsBString)]
+            exclude: Vec<gix::bstr::BString>,
+            /// Print only the names, not the revisions or object ids they belong to.
+            #[clap(long)]
+            name_only: bool,
+            /// Read text from stdin and annotate all full object ids of commits with their name.
+            #[clap(long, conflicts_with = "specs")]
+            annotate_stdin: bool,
+            /// rev-specs like `@`, `@~1` or `HEAD^2` of commits to name.
+            #[clap(required_unless_present = "annotate_stdin")]
+            specs: Vec<std::ffi::OsString>,
+        },
     }
 }
 
@@ -791,6 +1470,27 @@ pub mod index {
             /// A revspec that points to the to generate the index from.
             spec: std::ffi::OsString,
         },
+        /// Set or clear flags of the index entries matching the given pathspecs, like `git update-index` does.
+        ///
+        /// Entries with any of these flags are not compared to the worktree when computing the status.
+        #[clap(visible_alias = "update-index")]
+        UpdateFlags {
+            /// Assume that matching entries are unchanged in the worktree.
+            #[clap(long, conflicts_with = "no_assume_unchanged")]
+            assume_unchanged: bool,
+            /// Check matching entries for changes in the worktree again.
+            #[clap(long)]
+            no_assume_unchanged: bool,
+            /// Don't expect matching entries in the worktree, and don't check them out.
+            #[clap(long, conflicts_with = "no_skip_worktree")]
+            skip_worktree: bool,
+            /// Expect matching entries in the worktree again.
+            #[clap(long)]
+            no_skip_worktree: bool,
+            /// The git path specifications to match the entries whose flags should change.
+            #[clap(value_parser = CheckPathSpec, required = true)]
+            pathspec: Vec<BString>,
+        },
     }
 }
 
@@ -806,6 +1506,18 @@ pub mod submodule {
     pub enum Subcommands {
         /// Print all direct submodules to standard output
         List,
+        /// Clone missing submodules and check out the commits recorded in the index of the superproject.
+        ///
+        /// Only the `checkout` strategy is supported, and submodules configured to use another one cause an error.
+        #[cfg(feature = "gitoxide-core-blocking-client")]
+        Update {
+            /// Initialize submodules that aren't initialized yet, instead of failing.
+            #[clap(long)]
+            init: bool,
+            /// The paths of the submodules to update, or all submodules if unset.
+            #[clap(value_parser = gitoxide::shared::AsBString)]
+            paths: Vec<gix::bstr::BString>,
+        },
     }
 }
 
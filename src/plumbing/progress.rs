@@ -140,14 +140,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "core.protectNTFS",
         usage: NotPlanned { reason: "lack of demand"},
     },
-    Record {
-        config: "core.sparseCheckout",
-        usage: Planned { note: Some("we want to support huge repos and be the fastest in doing so") },
-    },
-    Record {
-        config: "core.sparseCheckoutCone",
-        usage: Planned { note: Some("this is a nice improvement over spareCheckout alone and should one day be available too") },
-    },
     Record {
         config: "core.gitProxy",
         usage: NotPlanned { reason: "the transport mechanism works differently enough to not support it for now, but of course it's possible to add support if there is demand" },
@@ -290,10 +282,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "fetch.fsck.skipList",
         usage: Puzzled,
     },
-    Record {
-        config: "fetch.unpackLimit",
-        usage: Planned { note: None },
-    },
     Record {
         config: "fetch.prune",
         usage: Planned { note: None },
@@ -328,14 +316,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "advice.updateSparsePath",
         usage: NotApplicable { reason: "gitoxide does not yet have an 'advice' system" },
     },
-    Record {
-        config: "core.sparseCheckout",
-        usage: Planned { note: Some("together with 'index.sparse' and 'core.sparseCheckoutCone', configures if the index should be written sparse or not") },
-    },
-    Record {
-        config: "core.sparseCheckoutCone",
-        usage: Planned { note: Some("non-cone mode is deprecated but should still fail gracefully if encountered") },
-    },
     Record {
         config: "core.splitIndex",
         usage: NotPlanned { reason: "we don't want to be able to create split indices, but we will read them. It's (somewhat) superseded by sparse indices" },
@@ -456,10 +436,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "http.<url>.*",
         usage: Planned { note: Some("it's a vital part of git configuration. It's unclear how to get a baseline from git for this one.") }
     },
-    Record {
-        config: "init.templateDir",
-        usage: NotPlanned { reason: "git expects this dir to be a valid git dir - I'd find additive template dirs more interesting, or changes done afterwards procedurally. Maybe this needs a 'init_or_open' semantic to be really useful" }
-    },
     Record {
         config: "sparse.expectFilesOutsideOfPatterns",
         usage: NotPlanned { reason: "todo" },
@@ -34,6 +34,9 @@ pub enum Subcommands {
         ///
         /// Defaults to the current working directory.
         directory: Option<PathBuf>,
+        /// A directory whose content is copied into the new `.git` directory, like hooks or `info/exclude`.
+        #[clap(long)]
+        template: Option<PathBuf>,
     },
     #[cfg(feature = "gitoxide-core-tools")]
     /// A selection of useful tools
@@ -71,6 +71,36 @@ title "gix (with repository)"
         }
       )
       fi
+      (with "a commit-graph"
+        git commit-graph write --reachable &>/dev/null
+        it "verifies it" && {
+          WITH_SNAPSHOT="$snapshot/commit-graph-success" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose verify -s
+        }
+        (with "a corrupt commit-graph"
+          printf 'garbage' | dd of=.git/objects/info/commit-graph bs=1 seek=100 conv=notrunc &>/dev/null
+          it "reports the commit-graph as failed" && {
+            WITH_SNAPSHOT="$snapshot/commit-graph-corrupt-failure" \
+            expect_run_sh $WITH_FAILURE "$exe_plumbing --no-verbose verify -s 2>&1 | sed -E 's/[0-9a-f]{40}/<hash>/g'"
+          }
+        )
+        rm .git/objects/info/commit-graph
+      )
+      (with "a corrupt reflog"
+        echo "garbage" >> .git/logs/refs/heads/dev
+        it "reports the reflogs as failed" && {
+          WITH_SNAPSHOT="$snapshot/reflog-corrupt-failure" \
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose verify -s
+        }
+      )
+      (with "a reflog referring to a missing object"
+        sed -i.bak '$d' .git/logs/refs/heads/dev
+        echo "0000000000000000000000000000000000000000 1111111111111111111111111111111111111111 A U Thor <a@example.com> 1600000000 +0800	missing" >> .git/logs/refs/heads/dev
+        it "reports the reflogs as failed" && {
+          WITH_SNAPSHOT="$snapshot/reflog-missing-object-failure" \
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose verify -s
+        }
+      )
     )
   )
 
@@ -155,7 +185,104 @@ title "gix (with repository)"
         fi
       )
     )
+    title "gix remote add|set-url|show|rename|remove|prune"
+    (with "the subcommands editing remotes"
+      snapshot="$snapshot/edit"
+      (small-repo-in-sandbox
+        {
+          git init clone
+          cd clone
+          git remote add origin ..
+          git fetch origin
+          git branch --track main origin/main
+          git -C .. branch -D dev
+        } &>/dev/null
+
+        it "adds a remote" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote add --no-tags fork ../fork
+        }
+        it "fails to add an existing remote" && {
+          WITH_SNAPSHOT="$snapshot/add-existing-failure" \
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose remote add fork ../other
+        }
+        it "sets the push url" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote set-url --push fork ../fork-push
+        }
+        it "shows the new remote" && {
+          WITH_SNAPSHOT="$snapshot/show-fork" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote show fork
+        }
+        it "lists all remotes with their urls" && {
+          WITH_SNAPSHOT="$snapshot/list-verbose" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote show -v
+        }
+        if [[ "$kind" != "small" && "$kind" != "async" ]]; then
+        (with "prune"
+          it "shows which tracking branches would be pruned" && {
+            WITH_SNAPSHOT="$snapshot/prune-dry-run" \
+            expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote prune --dry-run origin
+          }
+          it "deletes tracking branches whose branch is gone" && {
+            WITH_SNAPSHOT="$snapshot/prune" \
+            expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote prune origin
+          }
+          it "leaves the remaining tracking branches in place" && {
+            WITH_SNAPSHOT="$snapshot/prune-remaining-refs" \
+            expect_run $SUCCESSFULLY git for-each-ref --format '%(refname)' refs/remotes/
+          }
+        )
+        fi
+        it "renames a remote" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote rename origin upstream
+        }
+        it "moves the tracking branches of the renamed remote" && {
+          expect_run $SUCCESSFULLY git rev-parse --verify -q refs/remotes/upstream/main
+          expect_run $WITH_FAILURE git rev-parse --verify -q refs/remotes/origin/main
+        }
+        it "removes a remote" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote remove fork
+        }
+        it "fails to remove a missing remote" && {
+          WITH_SNAPSHOT="$snapshot/remove-missing-failure" \
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose remote remove fork
+        }
+        it "shows the remaining remote under its new name" && {
+          WITH_SNAPSHOT="$snapshot/list-after-rename-and-remove" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose remote show
+        }
+        it "keeps the upstream of the branch tracking the renamed remote" && {
+          WITH_SNAPSHOT="$snapshot/branch-remote-after-rename" \
+          expect_run $SUCCESSFULLY git config branch.main.remote
+        }
+      )
+    )
+  )
+
+  if [[ "$kind" != "small" && "$kind" != "async" ]]; then
+  title "gix fetch"
+  (when "running 'fetch'"
+    snapshot="$snapshot/fetch"
+    (with "--all and a remote that can't be fetched from"
+      (small-repo-in-sandbox
+        {
+          git init clone
+          cd clone
+          git remote add origin ..
+          git remote add broken ../does-not-exist
+        } &>/dev/null
+
+        it "fetches from all other remotes and fails" && {
+          WITH_SNAPSHOT="$snapshot/all-with-failing-remote" \
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose fetch --all
+        }
+        it "updates the tracking branches of the remotes that could be fetched" && {
+          WITH_SNAPSHOT="$snapshot/all-with-failing-remote-refs" \
+          expect_run $SUCCESSFULLY git for-each-ref --format '%(refname)' refs/remotes/
+        }
+      )
+    )
   )
+  fi
 )
 
 title "gix attributes"
@@ -196,6 +323,317 @@ title "gix commit-graph"
   )
 )
 
+title "gix branch"
+(when "running 'branch'"
+  snapshot="$snapshot/branch"
+  (small-repo-in-sandbox
+    (with "the 'list' sub-command"
+      it "lists local branches and marks the checked out one" && {
+        WITH_SNAPSHOT="$snapshot/list" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch list
+      }
+      (with "--verbose"
+        it "shows the commit and subject of each branch" && {
+          WITH_SNAPSHOT="$snapshot/list-verbose" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch list --verbose
+        }
+      )
+      (with "--contains"
+        it "only shows branches which contain the commit" && {
+          WITH_SNAPSHOT="$snapshot/list-contains-head" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch list --contains HEAD
+        }
+      )
+    )
+    (with "the 'create' sub-command"
+      it "creates a branch at the start point" && {
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch create feature HEAD~2
+      }
+      it "points the branch to the start point" && {
+        expect_run $SUCCESSFULLY test "$(git rev-parse feature)" = "$(git rev-parse HEAD~2)"
+      }
+      it "refuses to overwrite an existing branch" && {
+        WITH_SNAPSHOT="$snapshot/create-existing-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose branch create feature
+      }
+      (with "--force"
+        it "resets the existing branch" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch create --force feature dev
+        }
+        it "points the branch to the new start point" && {
+          expect_run $SUCCESSFULLY test "$(git rev-parse feature)" = "$(git rev-parse dev)"
+        }
+      )
+    )
+    (with "the 'rename' sub-command"
+      git config branch.feature.description "a feature"
+      it "renames the branch" && {
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch rename feature renamed
+      }
+      it "keeps the reflog and moves the configuration" && {
+        WITH_SNAPSHOT="$snapshot/rename-reflog-and-config" \
+        expect_run_sh $SUCCESSFULLY "git reflog show --format=%gs renamed && git config --get-regexp '^branch\.'"
+      }
+      it "refuses to overwrite an existing branch" && {
+        WITH_SNAPSHOT="$snapshot/rename-existing-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose branch rename renamed dev
+      }
+      (with "only the new name"
+        it "renames the checked out branch" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch rename trunk
+        }
+        it "updates HEAD" && {
+          WITH_SNAPSHOT="$snapshot/rename-head" \
+          expect_run $SUCCESSFULLY git symbolic-ref HEAD
+        }
+      )
+    )
+    (with "the 'delete' sub-command"
+      git branch unmerged dev && git update-ref refs/heads/unmerged "$(git commit-tree -p dev -m unmerged 'dev^{tree}')"
+      it "refuses to delete branches which aren't merged" && {
+        WITH_SNAPSHOT="$snapshot/delete-unmerged-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose branch delete unmerged
+      }
+      it "refuses to delete the checked out branch" && {
+        WITH_SNAPSHOT="$snapshot/delete-checked-out-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose branch delete --force trunk
+      }
+      it "deletes merged branches" && {
+        WITH_SNAPSHOT="$snapshot/delete-merged" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch delete renamed
+      }
+      (with "--force"
+        it "deletes branches which aren't merged" && {
+          WITH_SNAPSHOT="$snapshot/delete-unmerged-force" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch delete --force unmerged
+        }
+      )
+      it "leaves the other branches" && {
+        WITH_SNAPSHOT="$snapshot/delete-remaining" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose branch list
+      }
+    )
+  )
+)
+
+title "gix tag"
+(when "running 'tag'"
+  snapshot="$snapshot/tag"
+  (small-repo-in-sandbox
+    (with "the 'list' sub-command"
+      it "lists tags along with their subject" && {
+        WITH_SNAPSHOT="$snapshot/list-with-subject" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose tag list -n
+      }
+    )
+    (with "the 'create' sub-command"
+      (with "--message"
+        it "creates an annotated tag" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose tag create --message "the message" v1.0 HEAD~1
+        }
+        it "writes a tag object pointing to the target" && {
+          WITH_SNAPSHOT="$snapshot/create-annotated-object" \
+          expect_run $SUCCESSFULLY git cat-file -p v1.0
+        }
+      )
+      it "creates a lightweight tag" && {
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose tag create v1.1
+      }
+      it "points the lightweight tag to HEAD" && {
+        expect_run $SUCCESSFULLY test "$(git rev-parse v1.1)" = "$(git rev-parse HEAD)"
+      }
+      it "refuses to overwrite an existing tag" && {
+        WITH_SNAPSHOT="$snapshot/create-existing-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose tag create v1.0
+      }
+      it "lists the new tags in version order" && {
+        WITH_SNAPSHOT="$snapshot/list-after-create" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose tag list -n
+      }
+    )
+    (with "the 'delete' sub-command"
+      it "deletes the tags" && {
+        WITH_SNAPSHOT="$snapshot/delete" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose tag delete v1.0 v1.1
+      }
+      it "leaves the other tags" && {
+        WITH_SNAPSHOT="$snapshot/list-after-delete" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose tag list
+      }
+    )
+  )
+)
+
+title "gix rev-parse"
+(when "running 'rev-parse'"
+  snapshot="$snapshot/rev-parse"
+  (small-repo-in-sandbox
+    {
+      mkdir dir
+      git checkout -b feature HEAD~1
+      git commit --allow-empty -m "on feature"
+      git checkout main
+      # These blobs share the prefix '6bb2f' to make it ambiguous.
+      echo 195 | git hash-object -w --stdin
+      echo 389 | git hash-object -w --stdin
+      git clone --bare . bare.git
+    } &>/dev/null
+
+    it "resolves revisions like git" && {
+      expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose rev-parse HEAD @~1 dev^{tree})" = "$(git rev-parse HEAD @~1 dev^{tree})"
+    }
+    it "expands ranges like git" && {
+      expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose rev-parse main..feature main...feature HEAD^@ HEAD^!)" = "$(git rev-parse main..feature main...feature HEAD^@ HEAD^!)"
+    }
+    it "fails on unknown revisions" && {
+      WITH_SNAPSHOT="$snapshot/unknown-revision-failure" \
+      expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose rev-parse does-not-exist
+    }
+    it "fails on ambiguous object names" && {
+      WITH_SNAPSHOT="$snapshot/ambiguous-prefix-failure" \
+      expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose rev-parse 6bb2f
+    }
+    (with "--verify"
+      it "resolves a single revision" && {
+        expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose rev-parse --verify main~1)" = "$(git rev-parse --verify main~1)"
+      }
+      it "fails if more than one revision is given" && {
+        WITH_SNAPSHOT="$snapshot/verify-multiple-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose rev-parse --verify main dev
+      }
+      it "fails if the revision is a range" && {
+        WITH_SNAPSHOT="$snapshot/verify-range-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose rev-parse --verify main..feature
+      }
+    )
+    (with "--short"
+      it "shortens to the configured length" && {
+        expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose rev-parse --short HEAD)" = "$(git rev-parse --short HEAD)"
+      }
+      it "shortens to the given length" && {
+        expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose rev-parse --short=4 HEAD)" = "$(git rev-parse --short=4 HEAD)"
+      }
+      it "extends the prefix until it is unambiguous" && {
+        WITH_SNAPSHOT="$snapshot/short-ambiguous-prefix" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose rev-parse --short=4 6bb2f98fb0227744dff2c9023c2a8d53cc721588
+      }
+      it "implies --verify" && {
+        WITH_SNAPSHOT="$snapshot/short-multiple-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose rev-parse --short main dev
+      }
+    )
+    (with "--abbrev-ref"
+      it "prints the short names of references" && {
+        WITH_SNAPSHOT="$snapshot/abbrev-ref" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose rev-parse --abbrev-ref HEAD refs/heads/dev main..feature
+      }
+      it "matches git" && {
+        expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose rev-parse --abbrev-ref HEAD refs/heads/dev main..feature)" = "$(git rev-parse --abbrev-ref HEAD refs/heads/dev main..feature)"
+      }
+    )
+    (with "path queries"
+      it "prints the top-level directory like git" && {
+        expect_run $SUCCESSFULLY test "$(cd dir && "$exe_plumbing" --no-verbose rev-parse --show-toplevel)" = "$(cd dir && git rev-parse --show-toplevel)"
+      }
+      it "knows it's inside of the work tree" && {
+        WITH_SNAPSHOT="$snapshot/is-inside-work-tree" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose rev-parse --is-inside-work-tree
+      }
+      it "knows a bare repository has no work tree" && {
+        WITH_SNAPSHOT="$snapshot/is-inside-work-tree-bare" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose -r bare.git rev-parse --is-inside-work-tree
+      }
+      it "fails to print the top-level directory of a bare repository" && {
+        WITH_SNAPSHOT="$snapshot/show-toplevel-bare-failure" \
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose -r bare.git rev-parse --show-toplevel
+      }
+      it "prints the git directory" && {
+        expect_run $SUCCESSFULLY test "$(realpath "$("$exe_plumbing" --no-verbose rev-parse --git-dir)")" = "$(realpath "$(git rev-parse --git-dir)")"
+      }
+    )
+  )
+)
+
+title "gix repo-analytics"
+(when "running 'repo-analytics'"
+  snapshot="$snapshot/repo-analytics"
+  (sandbox
+    {
+      git init
+      git checkout -b main
+      mkdir dir
+      seq 1000 > big
+      echo small > dir/small
+      git add .
+      git commit -m "first"
+      seq 2000 > big
+      git commit -am "second"
+      git rm dir/small
+      git commit -m "third"
+      git repack -adq
+      # Store the blob of the first version of 'big' once more in a pack of its own.
+      git rev-parse HEAD~2:big | git pack-objects -q .git/objects/pack/pack
+      echo "not reachable from any commit" | git hash-object -w --stdin
+    } &>/dev/null
+
+    it "shows the largest objects, the heaviest paths and duplicates" && {
+      WITH_SNAPSHOT="$snapshot/all-references" \
+      expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose repo-analytics -n 3
+    }
+    it "attributes paths only from the history of the given revisions" && {
+      WITH_SNAPSHOT="$snapshot/first-commit-only" \
+      expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose repo-analytics -n 3 HEAD~2
+    }
+  )
+)
+
+title "gix shortlog"
+(when "running 'shortlog'"
+  snapshot="$snapshot/shortlog"
+  (sandbox
+    {
+      git init
+      git checkout -b main
+      function commit-as() {
+        GIT_AUTHOR_NAME="$1" GIT_AUTHOR_EMAIL="$2" git commit --allow-empty -m "$3"
+      }
+      commit-as "Zed" zed@example.com "zed 1"
+      commit-as "Anna" anna@example.com "anna 1"
+      commit-as "Bert" bert@example.com "bert 1"
+      commit-as "Zed" zed@example.com "zed 2"
+      commit-as "anna" anna@old.example.com "anna 2 with old identity"
+      git branch old
+      commit-as "Zed" zed@example.com "zed 3"
+      commit-as "Bert" bert@example.com "bert 2"
+      echo "Anna <anna@example.com> anna <anna@old.example.com>" > .mailmap
+      git add .mailmap
+      commit-as "Anna" anna@example.com "add mailmap"
+    } &>/dev/null
+
+    it "groups the summaries of each author alphabetically, oldest first" && {
+      WITH_SNAPSHOT="$snapshot/default" \
+      expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose shortlog
+    }
+    it "merges identities through the mailmap like git" && {
+      expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose shortlog)" = "$(git shortlog HEAD)"
+    }
+    it "sorts by the amount of commits and keeps the alphabetical order for ties" && {
+      WITH_SNAPSHOT="$snapshot/summary-numbered" \
+      expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose shortlog -sn
+    }
+    it "sorts like git" && {
+      expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose shortlog -sne)" = "$(git shortlog -sne HEAD)"
+    }
+    it "only takes commits from the given range" && {
+      WITH_SNAPSHOT="$snapshot/range-with-email" \
+      expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose shortlog -e old..main
+    }
+    it "handles ranges like git" && {
+      expect_run $SUCCESSFULLY test "$("$exe_plumbing" --no-verbose shortlog -e old..main)" = "$(git shortlog -e old..main)"
+    }
+  )
+)
+
 (with "gix free"
   snapshot="$snapshot/no-repo"
   title "gix free pack"
@@ -748,6 +748,65 @@ impl<'a> Drop for Env<'a> {
     }
 }
 
+/// A span created while running the function passed to [`capture_spans()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedSpan {
+    /// The name of the span.
+    pub name: &'static str,
+    /// The values of all fields that were set when the span was created or recorded later, formatted with `Debug`.
+    pub fields: BTreeMap<&'static str, String>,
+}
+
+/// Run `f` with a `tracing` subscriber that captures all spans created on the current thread, and return its result
+/// along with these spans in the order they were created.
+///
+/// Note that spans are only created if the crates under test are compiled with the `tracing` feature of `gix-features`.
+pub fn capture_spans<T>(f: impl FnOnce() -> T) -> (T, Vec<CapturedSpan>) {
+    use std::sync::Arc;
+    use tracing_core::{field, span, Event, Metadata};
+
+    #[derive(Default)]
+    struct Capture(Arc<Mutex<Vec<CapturedSpan>>>);
+
+    struct Fields<'a>(&'a mut BTreeMap<&'static str, String>);
+    impl field::Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name(), format!("{value:?}"));
+        }
+    }
+
+    impl tracing_core::Subscriber for Capture {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut captured = CapturedSpan {
+                name: span.metadata().name(),
+                fields: Default::default(),
+            };
+            span.record(&mut Fields(&mut captured.fields));
+            let mut spans = self.0.lock();
+            spans.push(captured);
+            span::Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+            let mut spans = self.0.lock();
+            let captured = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut Fields(&mut captured.fields));
+        }
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+        fn event(&self, _event: &Event<'_>) {}
+        fn enter(&self, _span: &span::Id) {}
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    let capture = Capture::default();
+    let spans = capture.0.clone();
+    let res = tracing_core::dispatcher::with_default(&tracing_core::Dispatch::new(capture), f);
+    let spans = std::mem::take(&mut *spans.lock());
+    (res, spans)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;